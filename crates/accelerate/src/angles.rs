@@ -0,0 +1,224 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The canonical forms of rotation angles, shared by the synthesis and optimization passes so that
+//! they agree on when an angle is a multiple of `π/2` and on the phase that wrapping it costs.
+//!
+//! A rotation `exp(-iθP/2)` about a Pauli `P` has a period of `4π` rather than `2π`: shifting `θ` by
+//! `2π` negates the gate.  [canonicalize_rotation] therefore tracks the global phase of `π` that
+//! each turn removed from the angle induces.  Phase-like angles, such as those of `p`, `u1` or the
+//! outer angles of `u3`, have a period of `2π` and wrap for free.
+
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+/// An angle that is a multiple of `π/2`, for which a rotation is a Clifford gate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CliffordAngle {
+    Zero,
+    HalfPi,
+    Pi,
+    MinusHalfPi,
+}
+
+impl CliffordAngle {
+    /// The angle, in the interval `(-π, π]`.
+    pub fn value(&self) -> f64 {
+        match self {
+            CliffordAngle::Zero => 0.,
+            CliffordAngle::HalfPi => FRAC_PI_2,
+            CliffordAngle::Pi => PI,
+            CliffordAngle::MinusHalfPi => -FRAC_PI_2,
+        }
+    }
+
+    /// The number of quarter turns of the angle, in `0..4`.
+    pub fn quarter_turns(&self) -> u8 {
+        match self {
+            CliffordAngle::Zero => 0,
+            CliffordAngle::HalfPi => 1,
+            CliffordAngle::Pi => 2,
+            CliffordAngle::MinusHalfPi => 3,
+        }
+    }
+}
+
+/// A rotation angle in its canonical form.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanonicalAngle {
+    /// The angle, in the interval `(-π, π]`, and exactly the value of `clifford` if there is one.
+    pub angle: f64,
+    /// The multiple of `π/2` that the angle is within the tolerance of, if any.
+    pub clifford: Option<CliffordAngle>,
+    /// The global phase, `0` or `π`, that the rotation by the canonical angle must be multiplied
+    /// by to equal the rotation by the original angle.
+    pub global_phase: f64,
+}
+
+/// Wrap an angle into the interval `(-π, π]`.
+#[inline]
+pub fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(TAU);
+    if wrapped > PI {
+        wrapped - TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Wrap an angle into the interval `[-π, π)`, as `_mod_2pi` does in Python, clamping the angles
+/// within `atol` of `π` to `-π`.
+#[inline]
+pub fn mod_2pi(angle: f64, atol: f64) -> f64 {
+    // f64::rem_euclid() isn't exactly the same as Python's % operator, but because
+    // the RHS here is a constant and positive it is effectively equivalent for
+    // this case
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    if (wrapped - PI).abs() < atol {
+        -PI
+    } else {
+        wrapped
+    }
+}
+
+/// Wrap a global phase into the interval `[0, 2π)`.
+#[inline]
+pub fn normalize_phase(phase: f64) -> f64 {
+    phase.rem_euclid(TAU)
+}
+
+/// Whether an angle is within `atol` of a multiple of `2π`.
+#[inline]
+pub fn is_zero_mod_2pi(angle: f64, atol: f64) -> bool {
+    mod_2pi(angle, atol).abs() < atol
+}
+
+/// The multiple of `π/2` that an angle is within `atol` of, modulo `2π`, if any.
+pub fn clifford_angle(angle: f64, atol: f64) -> Option<CliffordAngle> {
+    let quarters = (angle / FRAC_PI_2).round();
+    if (angle - quarters * FRAC_PI_2).abs() >= atol {
+        return None;
+    }
+    match (quarters as i64).rem_euclid(4) {
+        0 => Some(CliffordAngle::Zero),
+        1 => Some(CliffordAngle::HalfPi),
+        2 => Some(CliffordAngle::Pi),
+        _ => Some(CliffordAngle::MinusHalfPi),
+    }
+}
+
+/// Round an angle that is within `atol` of a multiple of `π/2` to exactly that multiple, without
+/// wrapping it.
+#[inline]
+pub fn snap_clifford_angle(angle: f64, atol: f64) -> f64 {
+    let quarters = (angle / FRAC_PI_2).round();
+    if (angle - quarters * FRAC_PI_2).abs() < atol {
+        quarters * FRAC_PI_2
+    } else {
+        angle
+    }
+}
+
+/// The canonical form of the angle of a rotation `exp(-iθP/2)`, with angles within `atol` of a
+/// multiple of `π/2` rounded to it.
+pub fn canonicalize_rotation(angle: f64, atol: f64) -> CanonicalAngle {
+    let clifford = clifford_angle(angle, atol);
+    let canonical = match clifford {
+        Some(clifford) => clifford.value(),
+        None => wrap_angle(angle),
+    };
+    let turns = ((angle - canonical) / TAU).round() as i64;
+    CanonicalAngle {
+        angle: canonical,
+        clifford,
+        global_phase: if turns.rem_euclid(2) == 1 { PI } else { 0. },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap_angle(3. * PI), PI);
+        assert_eq!(wrap_angle(-PI), PI);
+        assert!((wrap_angle(TAU + 0.5) - 0.5).abs() < 1e-12);
+        assert_eq!(mod_2pi(PI, 1e-10), -PI);
+        assert_eq!(mod_2pi(PI - 1e-12, 1e-10), -PI);
+        assert!((mod_2pi(-0.5 - TAU, 1e-10) + 0.5).abs() < 1e-12);
+        assert!((normalize_phase(-0.5) - (TAU - 0.5)).abs() < 1e-12);
+        assert!(is_zero_mod_2pi(TAU + 1e-12, 1e-10));
+        assert!(is_zero_mod_2pi(-TAU, 1e-10));
+        assert!(!is_zero_mod_2pi(0.1, 1e-10));
+    }
+
+    #[test]
+    fn test_clifford_angle() {
+        assert_eq!(clifford_angle(0., 1e-10), Some(CliffordAngle::Zero));
+        assert_eq!(
+            clifford_angle(FRAC_PI_2 + 1e-12, 1e-10),
+            Some(CliffordAngle::HalfPi)
+        );
+        assert_eq!(
+            clifford_angle(3. * FRAC_PI_2, 1e-10),
+            Some(CliffordAngle::MinusHalfPi)
+        );
+        assert_eq!(clifford_angle(-PI, 1e-10), Some(CliffordAngle::Pi));
+        assert_eq!(clifford_angle(0.3, 1e-10), None);
+        for (turns, clifford) in [
+            CliffordAngle::Zero,
+            CliffordAngle::HalfPi,
+            CliffordAngle::Pi,
+            CliffordAngle::MinusHalfPi,
+        ]
+        .iter()
+        .enumerate()
+        {
+            assert_eq!(clifford.quarter_turns() as usize, turns);
+            assert_eq!(clifford_angle(clifford.value(), 1e-10), Some(*clifford));
+        }
+    }
+
+    #[test]
+    fn test_snap_clifford_angle() {
+        assert_eq!(snap_clifford_angle(FRAC_PI_2 + 1e-13, 1e-12), FRAC_PI_2);
+        // The angle isn't wrapped.
+        assert_eq!(
+            snap_clifford_angle(5. * FRAC_PI_2 - 1e-13, 1e-12),
+            5. * FRAC_PI_2
+        );
+        assert_eq!(snap_clifford_angle(0.3, 1e-12), 0.3);
+    }
+
+    #[test]
+    fn test_canonicalize_rotation() {
+        let canonical = canonicalize_rotation(3. * FRAC_PI_2, 1e-10);
+        assert_eq!(canonical.angle, -FRAC_PI_2);
+        assert_eq!(canonical.clifford, Some(CliffordAngle::MinusHalfPi));
+        assert_eq!(canonical.global_phase, PI);
+        let canonical = canonicalize_rotation(-PI, 1e-10);
+        assert_eq!((canonical.angle, canonical.global_phase), (PI, PI));
+        let canonical = canonicalize_rotation(0.5 + 2. * TAU, 1e-10);
+        assert_eq!(canonical.clifford, None);
+        assert_eq!(canonical.global_phase, 0.);
+        // The rotation `exp(-iθP/2)` by the original angle is the one by the canonical angle,
+        // times the global phase.
+        for k in -20..20 {
+            let angle = 0.37 * k as f64;
+            let canonical = canonicalize_rotation(angle, 1e-10);
+            assert!(canonical.angle > -PI);
+            assert!(canonical.angle <= PI);
+            let difference = (canonical.angle - angle) / 2. - canonical.global_phase;
+            assert!(is_zero_mod_2pi(difference, 1e-10));
+        }
+    }
+}
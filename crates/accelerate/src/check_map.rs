@@ -0,0 +1,136 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Validation that a routed circuit only has two-qubit instructions on coupled qubits, as checked
+//! by the `CheckMap` pass.  The direction of the couplings is ignored.
+
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use hashbrown::HashSet;
+
+#[cfg(feature = "python")]
+use crate::edge_collections::EdgeCollection;
+#[cfg(feature = "python")]
+use crate::instrumentation;
+use crate::nlayout::PhysicalQubit;
+
+/// The index of the first instruction that isn't on a coupled pair of qubits, if any.
+///
+/// `instructions` yields, for each instruction in order, the pair of qubits it acts on if it needs
+/// to be checked, or `None` for the instructions on any other number of qubits and the directives.
+pub fn check_map_inner<I>(edges: &[[PhysicalQubit; 2]], instructions: I) -> Option<usize>
+where
+    I: IntoIterator<Item = Option<[PhysicalQubit; 2]>>,
+{
+    let coupled = edges
+        .iter()
+        .flat_map(|[a, b]| [[*a, *b], [*b, *a]])
+        .collect::<HashSet<_>>();
+    instructions
+        .into_iter()
+        .position(|qubits| qubits.is_some_and(|qubits| !coupled.contains(&qubits)))
+}
+
+/// Find the first two-qubit instruction of a circuit that isn't on a coupled pair of qubits.
+///
+/// The circuit's qubits are taken to be the physical qubits of the coupling graph, and the
+/// direction of the edges is ignored.  Directives such as barriers are not checked, and neither
+/// are the bodies of control-flow operations.
+///
+/// Args:
+///     circuit (CircuitData): the routed circuit.
+///     edges (EdgeCollection): the edges of the coupling graph.
+///
+/// Returns:
+///     int | None: the index of the first instruction that violates the coupling graph, or
+///     ``None`` if the circuit is mapped.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "check_map", text_signature = "(circuit, edges, /)")]
+pub fn py_check_map(
+    py: Python,
+    circuit: &CircuitData,
+    edges: &EdgeCollection,
+) -> PyResult<Option<usize>> {
+    let _timer = instrumentation::Timer::start("check_map");
+    let edges = edges
+        .edges
+        .chunks_exact(2)
+        .map(|edge| [edge[0], edge[1]])
+        .collect::<Vec<_>>();
+    let mut instructions = Vec::with_capacity(circuit.iter_instructions().len());
+    for (op, qubits, _) in circuit.iter_instructions() {
+        let pair = match qubits {
+            [a, b] => {
+                let op = op.bind(py);
+                let directive = op.hasattr(intern!(py, "_directive"))?
+                    && op.getattr(intern!(py, "_directive"))?.is_truthy()?;
+                (!directive).then_some([PhysicalQubit::new(*a), PhysicalQubit::new(*b)])
+            }
+            _ => None,
+        };
+        instructions.push(pair);
+    }
+    Ok(check_map_inner(&edges, instructions))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn check_map(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_check_map))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(a: u32, b: u32) -> Option<[PhysicalQubit; 2]> {
+        Some([PhysicalQubit::new(a), PhysicalQubit::new(b)])
+    }
+
+    fn line(num_qubits: u32) -> Vec<[PhysicalQubit; 2]> {
+        (1..num_qubits)
+            .map(|i| [PhysicalQubit::new(i - 1), PhysicalQubit::new(i)])
+            .collect()
+    }
+
+    #[test]
+    fn test_mapped_circuit() {
+        let instructions = [pair(0, 1), None, pair(2, 1), pair(1, 2), None, pair(3, 2)];
+        assert_eq!(check_map_inner(&line(4), instructions), None);
+    }
+
+    #[test]
+    fn test_first_violation() {
+        let instructions = [pair(0, 1), None, pair(0, 2), pair(3, 0)];
+        assert_eq!(check_map_inner(&line(4), instructions), Some(2));
+    }
+
+    #[test]
+    fn test_unchecked_instructions() {
+        assert_eq!(check_map_inner(&line(4), [None, None]), None);
+        assert_eq!(check_map_inner(&line(4), []), None);
+    }
+
+    #[test]
+    fn test_no_edges() {
+        assert_eq!(check_map_inner(&[], [None, pair(0, 1)]), Some(1));
+    }
+}
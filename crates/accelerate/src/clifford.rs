@@ -0,0 +1,672 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The symplectic tableau of a Clifford operator.
+//!
+//! The layout is the same as `qiskit.quantum_info.Clifford.tableau`: a boolean matrix of shape
+//! `(2n, 2n + 1)` whose first `n` rows are the destabilizers and last `n` rows the stabilizers.
+//! Each row is the Pauli `(x | z)` that the corresponding single-qubit `X` or `Z` is mapped to,
+//! with the sign in the final column.  The gate-update rules and the phase bookkeeping of
+//! composition follow the Python implementation exactly, so the two can be used interchangeably.
+
+use std::str::FromStr;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use ndarray::prelude::*;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::threading::{self, ParallelKernel};
+
+/// The Clifford gates that can be appended to a tableau directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CliffordGate {
+    I,
+    X,
+    Y,
+    Z,
+    H,
+    S,
+    Sdg,
+    SX,
+    SXdg,
+    /// `Sdg` followed by `H`.
+    V,
+    /// Two `V` gates.
+    W,
+    CX,
+    CZ,
+    CY,
+    Swap,
+    ISwap,
+    DCX,
+    ECR,
+}
+
+impl CliffordGate {
+    pub fn num_qubits(&self) -> usize {
+        match self {
+            Self::I
+            | Self::X
+            | Self::Y
+            | Self::Z
+            | Self::H
+            | Self::S
+            | Self::Sdg
+            | Self::SX
+            | Self::SXdg
+            | Self::V
+            | Self::W => 1,
+            _ => 2,
+        }
+    }
+}
+
+impl FromStr for CliffordGate {
+    type Err = AccelerateError;
+
+    /// Parse a gate from its Qiskit instruction name, accepting the same aliases as
+    /// `Clifford.from_circuit`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let gate = match name {
+            "i" | "id" | "iden" => Self::I,
+            "x" => Self::X,
+            "y" => Self::Y,
+            "z" => Self::Z,
+            "h" => Self::H,
+            "s" => Self::S,
+            "sdg" | "sinv" => Self::Sdg,
+            "sx" => Self::SX,
+            "sxdg" => Self::SXdg,
+            "v" => Self::V,
+            "w" => Self::W,
+            "cx" => Self::CX,
+            "cz" => Self::CZ,
+            "cy" => Self::CY,
+            "swap" => Self::Swap,
+            "iswap" => Self::ISwap,
+            "dcx" => Self::DCX,
+            "ecr" => Self::ECR,
+            "t" | "tdg" | "ccx" | "ccz" => {
+                return Err(AccelerateError::Qiskit(format!(
+                    "Cannot update Clifford with non-Clifford gate {name}"
+                )))
+            }
+            _ => {
+                return Err(AccelerateError::Qiskit(format!(
+                    "Invalid Clifford gate name string {name}"
+                )))
+            }
+        };
+        Ok(gate)
+    }
+}
+
+/// The power of `i` picked up when multiplying the single-qubit Pauli `(x, z)` onto the running
+/// product `(x_acc, z_acc)`.  This is `Clifford._compose_lookup` in Python.
+#[inline]
+fn product_phase(x: bool, z: bool, x_acc: bool, z_acc: bool) -> i64 {
+    match (x, z, x_acc, z_acc) {
+        (false, true, true, false) | (true, false, true, true) | (true, true, false, true) => -1,
+        (false, true, true, true) | (true, false, false, true) | (true, true, true, false) => 1,
+        _ => 0,
+    }
+}
+
+/// The symplectic tableau of an `n`-qubit Clifford operator, up to global phase.
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.clifford"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CliffordTableau {
+    num_qubits: usize,
+    tableau: Array2<bool>,
+}
+
+impl CliffordTableau {
+    /// The tableau of the `num_qubits`-qubit identity.
+    pub fn identity(num_qubits: usize) -> Self {
+        CliffordTableau {
+            num_qubits,
+            tableau: Array2::from_shape_fn((2 * num_qubits, 2 * num_qubits + 1), |(i, j)| i == j),
+        }
+    }
+
+    /// Wrap an existing tableau of shape `(2n, 2n + 1)`.  The symplectic condition is not checked.
+    pub fn from_tableau(tableau: Array2<bool>) -> Result<Self, AccelerateError> {
+        let rows = tableau.nrows();
+        if rows % 2 != 0 || tableau.ncols() != rows + 1 {
+            return Err(AccelerateError::Qiskit(format!(
+                "a Clifford tableau must have shape (2n, 2n + 1), but got {:?}",
+                tableau.shape()
+            )));
+        }
+        Ok(CliffordTableau {
+            num_qubits: rows / 2,
+            tableau,
+        })
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn tableau(&self) -> ArrayView2<bool> {
+        self.tableau.view()
+    }
+
+    pub fn into_tableau(self) -> Array2<bool> {
+        self.tableau
+    }
+
+    /// The tableau, for the updates that aren't Clifford gates, such as the measurements of a
+    /// stabilizer state.
+    pub(crate) fn tableau_mut(&mut self) -> ArrayViewMut2<bool> {
+        self.tableau.view_mut()
+    }
+
+    /// The sign bits of the destabilizers followed by those of the stabilizers.
+    pub fn phase(&self) -> ArrayView1<bool> {
+        self.tableau.column(2 * self.num_qubits)
+    }
+
+    fn check_qubit(&self, qubit: usize) -> Result<(), AccelerateError> {
+        if qubit >= self.num_qubits {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {}-qubit Clifford",
+                self.num_qubits
+            )));
+        }
+        Ok(())
+    }
+
+    /// Update every row with `f(x, z, phase)` on the columns of `qubit`.
+    fn update_1q(&mut self, qubit: usize, f: impl Fn(bool, bool, bool) -> (bool, bool, bool)) {
+        let n = self.num_qubits;
+        for mut row in self.tableau.rows_mut() {
+            (row[qubit], row[n + qubit], row[2 * n]) = f(row[qubit], row[n + qubit], row[2 * n]);
+        }
+    }
+
+    /// Update every row with `f(x0, z0, x1, z1, phase)` on the columns of `qubit0` and `qubit1`.
+    #[allow(clippy::type_complexity)]
+    fn update_2q(
+        &mut self,
+        qubit0: usize,
+        qubit1: usize,
+        f: impl Fn(bool, bool, bool, bool, bool) -> (bool, bool, bool, bool, bool),
+    ) {
+        let n = self.num_qubits;
+        for mut row in self.tableau.rows_mut() {
+            (
+                row[qubit0],
+                row[n + qubit0],
+                row[qubit1],
+                row[n + qubit1],
+                row[2 * n],
+            ) = f(
+                row[qubit0],
+                row[n + qubit0],
+                row[qubit1],
+                row[n + qubit1],
+                row[2 * n],
+            );
+        }
+    }
+
+    fn append_1q(&mut self, gate: CliffordGate, q: usize) {
+        match gate {
+            CliffordGate::I => (),
+            CliffordGate::X => self.update_1q(q, |x, z, p| (x, z, p ^ z)),
+            CliffordGate::Y => self.update_1q(q, |x, z, p| (x, z, p ^ x ^ z)),
+            CliffordGate::Z => self.update_1q(q, |x, z, p| (x, z, p ^ x)),
+            CliffordGate::H => self.update_1q(q, |x, z, p| (z, x, p ^ (x & z))),
+            CliffordGate::S => self.update_1q(q, |x, z, p| (x, z ^ x, p ^ (x & z))),
+            CliffordGate::Sdg => self.update_1q(q, |x, z, p| (x, z ^ x, p ^ (x & !z))),
+            CliffordGate::SX => self.update_1q(q, |x, z, p| (x ^ z, z, p ^ (!x & z))),
+            CliffordGate::SXdg => self.update_1q(q, |x, z, p| (x ^ z, z, p ^ (x & z))),
+            CliffordGate::V => self.update_1q(q, |x, z, p| (x ^ z, x, p)),
+            CliffordGate::W => self.update_1q(q, |x, z, p| (z, x ^ z, p)),
+            _ => unreachable!("{gate:?} is not a single-qubit gate"),
+        }
+    }
+
+    fn append_cx(&mut self, control: usize, target: usize) {
+        self.update_2q(control, target, |x0, z0, x1, z1, p| {
+            (x0, z0 ^ z1, x1 ^ x0, z1, p ^ ((x1 ^ z0 ^ true) & z1 & x0))
+        });
+    }
+
+    /// Append `gate` acting on `qubits`, which is the update of the tableau for the circuit that
+    /// first applies the current Clifford and then `gate`.
+    pub fn append_gate(
+        &mut self,
+        gate: CliffordGate,
+        qubits: &[usize],
+    ) -> Result<(), AccelerateError> {
+        if qubits.len() != gate.num_qubits() {
+            return Err(AccelerateError::Qiskit(format!(
+                "Invalid qubits for {}-qubit gate.",
+                gate.num_qubits()
+            )));
+        }
+        for &qubit in qubits {
+            self.check_qubit(qubit)?;
+        }
+        if gate.num_qubits() == 1 {
+            self.append_1q(gate, qubits[0]);
+            return Ok(());
+        }
+        let (q0, q1) = (qubits[0], qubits[1]);
+        if q0 == q1 {
+            return Err(AccelerateError::Qiskit(format!(
+                "duplicate qubit {q0} in the arguments of a two-qubit gate"
+            )));
+        }
+        match gate {
+            CliffordGate::CX => self.append_cx(q0, q1),
+            CliffordGate::CZ => self.update_2q(q0, q1, |x0, z0, x1, z1, p| {
+                (x0, z0 ^ x1, x1, z1 ^ x0, p ^ (x0 & x1 & (z0 ^ z1)))
+            }),
+            CliffordGate::CY => {
+                self.append_1q(CliffordGate::Sdg, q1);
+                self.append_cx(q0, q1);
+                self.append_1q(CliffordGate::S, q1);
+            }
+            CliffordGate::Swap => {
+                let n = self.num_qubits;
+                for mut row in self.tableau.rows_mut() {
+                    row.swap(q0, q1);
+                    row.swap(n + q0, n + q1);
+                }
+            }
+            CliffordGate::ISwap => {
+                self.append_1q(CliffordGate::S, q0);
+                self.append_1q(CliffordGate::H, q0);
+                self.append_1q(CliffordGate::S, q1);
+                self.append_cx(q0, q1);
+                self.append_cx(q1, q0);
+                self.append_1q(CliffordGate::H, q1);
+            }
+            CliffordGate::DCX => {
+                self.append_cx(q0, q1);
+                self.append_cx(q1, q0);
+            }
+            CliffordGate::ECR => {
+                self.append_1q(CliffordGate::S, q0);
+                self.append_1q(CliffordGate::SX, q1);
+                self.append_cx(q0, q1);
+                self.append_1q(CliffordGate::X, q0);
+            }
+            _ => unreachable!("{gate:?} is not a two-qubit gate"),
+        }
+        Ok(())
+    }
+
+    /// The tableau of the Clifford that applies `second` and then `first`.  This is
+    /// `Clifford._compose_general`: each row of `second` selects the rows of `first` whose product
+    /// it is, and the sign is tracked through the powers of `i` of the Pauli products.
+    fn compose_general(first: &Self, second: &Self) -> Self {
+        let n = first.num_qubits;
+        let mut out = Array2::from_elem((2 * n, 2 * n + 1), false);
+        let fill_row = |(row2, mut out_row): (ArrayView1<bool>, ArrayViewMut1<bool>)| {
+            let selected = (0..2 * n).filter(|&j| row2[j]).collect::<Vec<_>>();
+            // Start with the factors of `-i` from `XZ = -iY` on the individual qubits.
+            let mut ifacts = (0..n).filter(|&q| row2[q] && row2[n + q]).count() as i64;
+            for q in 0..n {
+                let (mut x_acc, mut z_acc) = (false, false);
+                for (k, &j) in selected.iter().enumerate() {
+                    let (x, z) = (first.tableau[[j, q]], first.tableau[[j, n + q]]);
+                    if k > 0 {
+                        ifacts += product_phase(x, z, x_acc, z_acc);
+                    }
+                    x_acc ^= x;
+                    z_acc ^= z;
+                }
+                out_row[q] = x_acc;
+                out_row[n + q] = z_acc;
+            }
+            let phase = selected
+                .iter()
+                .fold(row2[2 * n], |acc, &j| acc ^ first.tableau[[j, 2 * n]]);
+            out_row[2 * n] = phase ^ (ifacts.rem_euclid(4) / 2 == 1);
+        };
+        if threading::run_in_parallel(ParallelKernel::Clifford, n) {
+            threading::install(|| {
+                second
+                    .tableau
+                    .axis_iter(Axis(0))
+                    .into_par_iter()
+                    .zip(out.axis_iter_mut(Axis(0)).into_par_iter())
+                    .for_each(fill_row)
+            });
+        } else {
+            second
+                .tableau
+                .rows()
+                .into_iter()
+                .zip(out.rows_mut())
+                .for_each(fill_row);
+        }
+        CliffordTableau {
+            num_qubits: n,
+            tableau: out,
+        }
+    }
+
+    /// Compose with another Clifford on the same qubits.  With `front = false` the result applies
+    /// `self` and then `other`; with `front = true` it applies `other` first.
+    pub fn compose(&self, other: &Self, front: bool) -> Result<Self, AccelerateError> {
+        if self.num_qubits != other.num_qubits {
+            return Err(AccelerateError::Qiskit(format!(
+                "cannot compose a {}-qubit Clifford with a {}-qubit Clifford",
+                self.num_qubits, other.num_qubits
+            )));
+        }
+        Ok(if front {
+            Self::compose_general(self, other)
+        } else {
+            Self::compose_general(other, self)
+        })
+    }
+
+    /// The inverse of the Clifford.  The symplectic part of the inverse is the symplectic
+    /// transpose of the tableau; its signs are whatever makes the composition with `self` the
+    /// identity.
+    pub fn adjoint(&self) -> Self {
+        let n = self.num_qubits;
+        let t = &self.tableau;
+        let mut tableau = Array2::from_shape_fn((2 * n, 2 * n + 1), |(i, j)| {
+            if j == 2 * n {
+                return t[[i, j]];
+            }
+            let (destab, qubit) = (i < n, i % n);
+            let (x, col) = (j < n, j % n);
+            match (destab, x) {
+                (true, true) => t[[n + col, n + qubit]],
+                (true, false) => t[[col, n + qubit]],
+                (false, true) => t[[n + col, qubit]],
+                (false, false) => t[[col, qubit]],
+            }
+        });
+        let correction = Self::compose_general(
+            self,
+            &CliffordTableau {
+                num_qubits: n,
+                tableau: tableau.clone(),
+            },
+        );
+        for (phase, fix) in tableau.column_mut(2 * n).iter_mut().zip(correction.phase()) {
+            *phase ^= fix;
+        }
+        CliffordTableau {
+            num_qubits: n,
+            tableau,
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl CliffordTableau {
+    #[new]
+    fn py_new(tableau: PyReadonlyArray2<bool>) -> PyResult<Self> {
+        Ok(Self::from_tableau(tableau.as_array().to_owned())?)
+    }
+
+    /// Create the tableau of the identity on ``num_qubits`` qubits.
+    #[staticmethod]
+    #[pyo3(name = "identity")]
+    fn py_identity(num_qubits: usize) -> Self {
+        Self::identity(num_qubits)
+    }
+
+    #[getter]
+    #[pyo3(name = "num_qubits")]
+    fn py_num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// A copy of the tableau as a boolean array of shape ``(2n, 2n + 1)``.
+    #[getter]
+    #[pyo3(name = "tableau")]
+    fn py_tableau(&self, py: Python) -> Py<PyArray2<bool>> {
+        self.tableau.clone().into_pyarray_bound(py).unbind()
+    }
+
+    /// Append a Clifford gate in place.
+    ///
+    /// Args:
+    ///     name (str): the name of the gate, as in ``Instruction.name``.
+    ///     qubits (list[int]): the qubits the gate acts on.
+    ///
+    /// Raises:
+    ///     QiskitError: if the gate is not a supported Clifford gate or the qubits are invalid.
+    #[pyo3(name = "append_gate", text_signature = "(self, name, qubits, /)")]
+    fn py_append_gate(&mut self, name: &str, qubits: Vec<usize>) -> PyResult<()> {
+        Ok(self.append_gate(name.parse()?, &qubits)?)
+    }
+
+    /// Compose with another tableau on the same number of qubits.
+    ///
+    /// Args:
+    ///     other (CliffordTableau): the tableau to compose with.
+    ///     front (bool): if ``True`` apply ``other`` before ``self``, otherwise after it.
+    ///
+    /// Returns:
+    ///     CliffordTableau: the composed tableau.
+    #[pyo3(name = "compose", signature = (other, front=false))]
+    fn py_compose(&self, other: &CliffordTableau, front: bool) -> PyResult<Self> {
+        Ok(self.compose(other, front)?)
+    }
+
+    /// Return the tableau of the inverse Clifford.
+    #[pyo3(name = "adjoint")]
+    fn py_adjoint(&self) -> Self {
+        self.adjoint()
+    }
+
+    fn __eq__(&self, other: &CliffordTableau) -> bool {
+        self == other
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn clifford(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<CliffordTableau>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_gates(num_qubits: usize, gates: &[(&str, &[usize])]) -> CliffordTableau {
+        let mut out = CliffordTableau::identity(num_qubits);
+        for (name, qubits) in gates {
+            out.append_gate(name.parse().unwrap(), qubits).unwrap();
+        }
+        out
+    }
+
+    /// A fixed sequence of gates that uses every supported gate.
+    const GATES: [(&str, &[usize]); 18] = [
+        ("h", &[0]),
+        ("s", &[1]),
+        ("cx", &[0, 2]),
+        ("sdg", &[2]),
+        ("y", &[0]),
+        ("cz", &[2, 1]),
+        ("sx", &[1]),
+        ("cy", &[1, 0]),
+        ("z", &[2]),
+        ("swap", &[0, 1]),
+        ("sxdg", &[0]),
+        ("iswap", &[2, 0]),
+        ("x", &[1]),
+        ("dcx", &[1, 2]),
+        ("v", &[0]),
+        ("ecr", &[0, 1]),
+        ("w", &[2]),
+        ("id", &[1]),
+    ];
+
+    #[test]
+    fn test_single_qubit_images() {
+        // The rows are the images of `X` and then `Z`, as `[x, z, sign]`.
+        let cases = [
+            ("x", [[true, false, false], [false, true, true]]),
+            ("y", [[true, false, true], [false, true, true]]),
+            ("z", [[true, false, true], [false, true, false]]),
+            ("h", [[false, true, false], [true, false, false]]),
+            ("s", [[true, true, false], [false, true, false]]),
+            ("sdg", [[true, true, true], [false, true, false]]),
+            ("sx", [[true, false, false], [true, true, true]]),
+            ("sxdg", [[true, false, false], [true, true, false]]),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                from_gates(1, &[(name, &[0])]).tableau(),
+                arr2(&expected),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cx_images() {
+        // `X0 -> X0 X1`, `X1 -> X1`, `Z0 -> Z0` and `Z1 -> Z0 Z1`.
+        let expected = arr2(&[
+            [true, true, false, false, false],
+            [false, true, false, false, false],
+            [false, false, true, false, false],
+            [false, false, true, true, false],
+        ]);
+        assert_eq!(from_gates(2, &[("cx", &[0, 1])]).tableau(), expected);
+    }
+
+    #[test]
+    fn test_gate_orders() {
+        let cases: [(&str, &[usize], usize); 14] = [
+            ("x", &[0], 2),
+            ("h", &[0], 2),
+            ("s", &[0], 4),
+            ("sdg", &[0], 4),
+            ("sx", &[0], 4),
+            ("v", &[0], 3),
+            ("w", &[0], 3),
+            ("cx", &[0, 1], 2),
+            ("cz", &[1, 0], 2),
+            ("cy", &[0, 1], 2),
+            ("swap", &[0, 1], 2),
+            ("iswap", &[0, 1], 4),
+            ("dcx", &[0, 1], 3),
+            ("ecr", &[1, 0], 2),
+        ];
+        let identity = CliffordTableau::identity(2);
+        for (name, qubits, order) in cases {
+            let gates = vec![(name, qubits); order];
+            assert_eq!(from_gates(2, &gates), identity, "{name}");
+            assert_ne!(from_gates(2, &gates[1..]), identity, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_composite_gates() {
+        let swap = from_gates(2, &[("cx", &[0, 1]), ("cx", &[1, 0]), ("cx", &[0, 1])]);
+        assert_eq!(from_gates(2, &[("swap", &[0, 1])]), swap);
+        let cy = from_gates(2, &[("sdg", &[1]), ("cx", &[0, 1]), ("s", &[1])]);
+        assert_eq!(from_gates(2, &[("cy", &[0, 1])]), cy);
+        assert_eq!(
+            from_gates(1, &[("v", &[0])]),
+            from_gates(1, &[("sdg", &[0]), ("h", &[0])])
+        );
+        assert_eq!(
+            from_gates(1, &[("s", &[0]), ("s", &[0])]),
+            from_gates(1, &[("z", &[0])])
+        );
+        assert_eq!(
+            from_gates(1, &[("sx", &[0]), ("sx", &[0])]),
+            from_gates(1, &[("x", &[0])])
+        );
+    }
+
+    #[test]
+    fn test_compose() {
+        let (first, second) = GATES.split_at(7);
+        let a = from_gates(3, first);
+        let b = from_gates(3, second);
+        let all = from_gates(3, &GATES);
+        assert_eq!(a.compose(&b, false).unwrap(), all);
+        assert_eq!(b.compose(&a, true).unwrap(), all);
+        let identity = CliffordTableau::identity(3);
+        assert_eq!(all.compose(&identity, false).unwrap(), all);
+        assert_eq!(identity.compose(&all, false).unwrap(), all);
+        assert!(matches!(
+            all.compose(&CliffordTableau::identity(2), false),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_adjoint() {
+        let identity = CliffordTableau::identity(3);
+        for len in 1..=GATES.len() {
+            let clifford = from_gates(3, &GATES[..len]);
+            let adjoint = clifford.adjoint();
+            assert_eq!(clifford.compose(&adjoint, false).unwrap(), identity);
+            assert_eq!(adjoint.compose(&clifford, false).unwrap(), identity);
+            assert_eq!(adjoint.adjoint(), clifford);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            "t".parse::<CliffordGate>(),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            "rz".parse::<CliffordGate>(),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut clifford = CliffordTableau::identity(2);
+        assert!(matches!(
+            clifford.append_gate(CliffordGate::H, &[2]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            clifford.append_gate(CliffordGate::CX, &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            clifford.append_gate(CliffordGate::CX, &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert_eq!(clifford, CliffordTableau::identity(2));
+        assert!(matches!(
+            CliffordTableau::from_tableau(Array2::from_elem((3, 4), false)),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            CliffordTableau::from_tableau(Array2::from_elem((4, 4), false)),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
@@ -0,0 +1,851 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Elements of the CNOT-dihedral group, generated by `CX`, `T` and `X`.
+//!
+//! An element acts on computational basis states as `|x> -> ω^{p(x)} |Ax + b>`, with `ω = e^{iπ/4}`,
+//! `A` an invertible matrix over `Z_2` (the linear part), `b` a bit vector (the shift) and `p` a
+//! polynomial of degree at most 3 in the bits of `x`, with coefficients in `Z_8`.  This is the
+//! representation of `qiskit.quantum_info.CNOTDihedral`, and the gate-update rules and composition
+//! follow the Python implementation exactly, so the two can be used interchangeably.
+
+use std::str::FromStr;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use itertools::Itertools;
+use ndarray::prelude::*;
+use smallvec::SmallVec;
+
+use crate::error::AccelerateError;
+
+/// The gates that can be appended to a CNOT-dihedral element directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CNOTDihedralGate {
+    I,
+    X,
+    Y,
+    Z,
+    T,
+    Tdg,
+    S,
+    Sdg,
+    CX,
+    CZ,
+    CS,
+    CSdg,
+    Swap,
+    CCZ,
+}
+
+impl CNOTDihedralGate {
+    pub fn num_qubits(&self) -> usize {
+        match self {
+            Self::I | Self::X | Self::Y | Self::Z | Self::T | Self::Tdg | Self::S | Self::Sdg => 1,
+            Self::CCZ => 3,
+            _ => 2,
+        }
+    }
+}
+
+impl FromStr for CNOTDihedralGate {
+    type Err = AccelerateError;
+
+    /// Parse a gate from its Qiskit instruction name.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let gate = match name {
+            "id" => Self::I,
+            "x" => Self::X,
+            "y" => Self::Y,
+            "z" => Self::Z,
+            "t" => Self::T,
+            "tdg" => Self::Tdg,
+            "s" => Self::S,
+            "sdg" => Self::Sdg,
+            "cx" => Self::CX,
+            "cz" => Self::CZ,
+            "cs" => Self::CS,
+            "csdg" => Self::CSdg,
+            "swap" => Self::Swap,
+            "ccz" => Self::CCZ,
+            _ => {
+                return Err(AccelerateError::Qiskit(format!(
+                    "Not a CNOT-Dihedral gate: {name}"
+                )))
+            }
+        };
+        Ok(gate)
+    }
+}
+
+/// `n` choose `k`, for the `k <= 3` that index the monomials.
+#[inline]
+fn binomial(n: usize, k: usize) -> usize {
+    match k {
+        0 => 1,
+        1 => n,
+        2 => n * n.saturating_sub(1) / 2,
+        3 => n * n.saturating_sub(1) * n.saturating_sub(2) / 6,
+        _ => unreachable!("monomials have degree at most 3"),
+    }
+}
+
+/// A polynomial of degree at most 3 in `n` variables over `Z_2`, with coefficients in `Z_8`.  This
+/// is `qiskit.quantum_info.operators.dihedral.polynomial.SpecialPolynomial`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecialPolynomial {
+    num_vars: usize,
+    /// The coefficients of the constant, the linear, the quadratic and the cubic monomials in turn,
+    /// each group in lexicographic order of the variables.  The Python weights are the four slices.
+    weights: Vec<u8>,
+}
+
+impl SpecialPolynomial {
+    /// The zero polynomial in `num_vars` variables.
+    pub fn zero(num_vars: usize) -> Self {
+        let len = (0..=3).map(|k| binomial(num_vars, k)).sum();
+        SpecialPolynomial {
+            num_vars,
+            weights: vec![0; len],
+        }
+    }
+
+    /// Wrap the concatenated weights of the constant, linear, quadratic and cubic terms, which are
+    /// reduced modulo 8.
+    pub fn from_weights(num_vars: usize, weights: Vec<u8>) -> Result<Self, AccelerateError> {
+        let mut out = Self::zero(num_vars);
+        if weights.len() != out.weights.len() {
+            return Err(AccelerateError::Qiskit(format!(
+                "a polynomial in {num_vars} variables has {} weights, but got {}",
+                out.weights.len(),
+                weights.len()
+            )));
+        }
+        for (out, weight) in out.weights.iter_mut().zip(weights) {
+            *out = weight % 8;
+        }
+        Ok(out)
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn weights(&self) -> &[u8] {
+        &self.weights
+    }
+
+    /// The weights of the monomials of degree `degree`.
+    pub fn weights_of_degree(&self, degree: usize) -> &[u8] {
+        let start = (0..degree)
+            .map(|k| binomial(self.num_vars, k))
+            .sum::<usize>();
+        &self.weights[start..start + binomial(self.num_vars, degree)]
+    }
+
+    /// The position in `weights` of the monomial of the strictly increasing `vars`, or `None` if
+    /// its degree is more than 3.
+    fn index(&self, vars: &[usize]) -> Option<usize> {
+        let n = self.num_vars;
+        match *vars {
+            [] => Some(0),
+            [i] => Some(1 + i),
+            [i, j] => Some(1 + n + i * n - i * (i + 1) / 2 + j - i - 1),
+            [i, j, k] => Some(
+                1 + n + binomial(n, 2) + binomial(n, 3)
+                    - binomial(n - i - 1, 3)
+                    - binomial(n - j - 1, 2)
+                    - (n - k),
+            ),
+            _ => None,
+        }
+    }
+
+    /// The monomials, as their strictly increasing variables, in the order of `weights`.
+    fn monomials(&self) -> impl Iterator<Item = SmallVec<[usize; 3]>> {
+        let n = self.num_vars;
+        (0..=3).flat_map(move |k| (0..n).combinations(k).map(SmallVec::from_vec))
+    }
+
+    /// The coefficient of the monomial of the strictly increasing `vars`.  Monomials of degree more
+    /// than 3 are always zero.
+    pub fn get_term(&self, vars: &[usize]) -> u8 {
+        self.index(vars).map_or(0, |i| self.weights[i])
+    }
+
+    /// Set the coefficient of the monomial of the strictly increasing `vars`, ignoring monomials of
+    /// degree more than 3.
+    pub fn set_term(&mut self, vars: &[usize], value: u8) {
+        if let Some(i) = self.index(vars) {
+            self.weights[i] = value % 8;
+        }
+    }
+
+    fn add_term(&mut self, vars: &[usize], value: u8) {
+        if let Some(i) = self.index(vars) {
+            self.weights[i] = (self.weights[i] + value % 8) % 8;
+        }
+    }
+
+    /// The polynomial `p_J(x) = sum_{a ⊆ J, a ≠ ∅} (-2)^{|a| - 1} x^a` of the variables in
+    /// `support`, which expresses the parity of those variables as an exponent of `ω`.
+    pub fn pj(num_vars: usize, support: &[usize]) -> Self {
+        let mut out = Self::zero(num_vars);
+        for (k, coeff) in [(1, 1), (2, 6), (3, 4)] {
+            for vars in support.iter().copied().combinations(k) {
+                out.set_term(&vars, coeff);
+            }
+        }
+        out
+    }
+
+    fn scale(&mut self, scalar: u8) {
+        for weight in self.weights.iter_mut() {
+            *weight = ((*weight as u16 * scalar as u16) % 8) as u8;
+        }
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        for (weight, other) in self.weights.iter_mut().zip(&other.weights) {
+            *weight = (*weight + other) % 8;
+        }
+    }
+
+    /// The product of two polynomials, using `x^2 = x` and dropping the monomials of degree more
+    /// than 3.
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut out = Self::zero(self.num_vars);
+        let nonzero = |poly: &Self| {
+            poly.monomials()
+                .zip(poly.weights.clone())
+                .filter(|(_, weight)| *weight != 0)
+                .collect::<Vec<_>>()
+        };
+        let right = nonzero(other);
+        for (left_vars, left_weight) in nonzero(self) {
+            for (right_vars, right_weight) in right.iter() {
+                let vars = left_vars
+                    .iter()
+                    .chain(right_vars.iter())
+                    .copied()
+                    .sorted()
+                    .dedup()
+                    .collect::<SmallVec<[usize; 6]>>();
+                out.add_term(
+                    &vars,
+                    ((left_weight as u16 * *right_weight as u16) % 8) as u8,
+                );
+            }
+        }
+        out
+    }
+
+    /// Substitute the polynomial `polys[i]` for each variable `x_i`.
+    pub fn evaluate(&self, polys: &[SpecialPolynomial]) -> Self {
+        let mut out = Self::zero(self.num_vars);
+        for (vars, weight) in self.monomials().zip(&self.weights) {
+            if *weight == 0 {
+                continue;
+            }
+            let mut term = Self::zero(self.num_vars);
+            term.weights[0] = 1;
+            for var in vars {
+                term = term.mul(&polys[var]);
+            }
+            term.scale(*weight);
+            out.add_assign(&term);
+        }
+        out
+    }
+}
+
+/// The product of two matrices over `Z_2`.
+fn z2_matmul(left: ArrayView2<bool>, right: ArrayView2<bool>) -> Array2<bool> {
+    Array2::from_shape_fn((left.nrows(), right.ncols()), |(i, j)| {
+        left.row(i)
+            .iter()
+            .zip(right.column(j))
+            .fold(false, |acc, (a, b)| acc ^ (a & b))
+    })
+}
+
+/// An element of the `n`-qubit CNOT-dihedral group.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.cnotdihedral")
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CNOTDihedralElement {
+    num_qubits: usize,
+    linear: Array2<bool>,
+    shift: Array1<bool>,
+    poly: SpecialPolynomial,
+}
+
+impl CNOTDihedralElement {
+    /// The `num_qubits`-qubit identity.
+    pub fn identity(num_qubits: usize) -> Self {
+        CNOTDihedralElement {
+            num_qubits,
+            linear: Array2::from_shape_fn((num_qubits, num_qubits), |(i, j)| i == j),
+            shift: Array1::from_elem(num_qubits, false),
+            poly: SpecialPolynomial::zero(num_qubits),
+        }
+    }
+
+    /// Build an element from its parts.  The invertibility of the linear part is not checked.
+    pub fn new(
+        linear: Array2<bool>,
+        shift: Array1<bool>,
+        poly: SpecialPolynomial,
+    ) -> Result<Self, AccelerateError> {
+        let num_qubits = shift.len();
+        if linear.shape() != [num_qubits, num_qubits] || poly.num_vars() != num_qubits {
+            return Err(AccelerateError::Qiskit(format!(
+                "inconsistent CNOT-dihedral parts: linear part of shape {:?}, shift of length \
+                 {num_qubits} and polynomial in {} variables",
+                linear.shape(),
+                poly.num_vars()
+            )));
+        }
+        Ok(CNOTDihedralElement {
+            num_qubits,
+            linear,
+            shift,
+            poly,
+        })
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    pub fn linear(&self) -> ArrayView2<bool> {
+        self.linear.view()
+    }
+
+    pub fn shift(&self) -> ArrayView1<bool> {
+        self.shift.view()
+    }
+
+    pub fn poly(&self) -> &SpecialPolynomial {
+        &self.poly
+    }
+
+    fn check_qubit(&self, qubit: usize) -> Result<(), AccelerateError> {
+        if qubit >= self.num_qubits {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {}-qubit CNOT-dihedral element",
+                self.num_qubits
+            )));
+        }
+        Ok(())
+    }
+
+    /// Left-multiply by `CX(control, target)`.
+    pub fn append_cx(&mut self, control: usize, target: usize) -> Result<(), AccelerateError> {
+        self.check_qubit(control)?;
+        self.check_qubit(target)?;
+        let control_row = self.linear.row(control).to_owned();
+        for (out, c) in self.linear.row_mut(target).iter_mut().zip(control_row) {
+            *out ^= c;
+        }
+        self.shift[target] ^= self.shift[control];
+        Ok(())
+    }
+
+    /// Left-multiply by `X(qubit)`.
+    pub fn append_x(&mut self, qubit: usize) -> Result<(), AccelerateError> {
+        self.check_qubit(qubit)?;
+        self.shift[qubit] ^= true;
+        Ok(())
+    }
+
+    /// Left-multiply by the `k`-th power of `T(qubit)`.
+    pub fn append_phase(&mut self, k: u8, qubit: usize) -> Result<(), AccelerateError> {
+        self.check_qubit(qubit)?;
+        // A flipped bit conjugates the phase.
+        let k = if self.shift[qubit] {
+            (8 - k % 8) % 8
+        } else {
+            k % 8
+        };
+        // Every subset `a` of the support of the row, of weight up to 3, picks up
+        // `k (-2)^(|a| - 1)`.
+        let support = (0..self.num_qubits)
+            .filter(|&j| self.linear[[qubit, j]])
+            .collect::<Vec<_>>();
+        for (size, coeff) in [(1, 1), (2, 6), (3, 4)] {
+            for vars in support.iter().copied().combinations(size) {
+                self.poly.add_term(&vars, coeff * k % 8);
+            }
+        }
+        Ok(())
+    }
+
+    /// Append `gate` acting on `qubits`, decomposed into `CX`, `X` and powers of `T` as in
+    /// `qiskit.quantum_info.CNOTDihedral`.
+    pub fn append_gate(
+        &mut self,
+        gate: CNOTDihedralGate,
+        qubits: &[usize],
+    ) -> Result<(), AccelerateError> {
+        if qubits.len() != gate.num_qubits() {
+            return Err(AccelerateError::Qiskit(format!(
+                "Invalid qubits for {}-qubit gate.",
+                gate.num_qubits()
+            )));
+        }
+        for &qubit in qubits {
+            self.check_qubit(qubit)?;
+        }
+        if qubits.iter().duplicates().next().is_some() {
+            return Err(AccelerateError::Qiskit(format!(
+                "duplicate qubits {qubits:?} in the arguments of a multi-qubit gate"
+            )));
+        }
+        match gate {
+            CNOTDihedralGate::I => (),
+            CNOTDihedralGate::X => self.append_x(qubits[0])?,
+            CNOTDihedralGate::Y => {
+                self.append_x(qubits[0])?;
+                self.append_phase(4, qubits[0])?;
+            }
+            CNOTDihedralGate::Z => self.append_phase(4, qubits[0])?,
+            CNOTDihedralGate::T => self.append_phase(1, qubits[0])?,
+            CNOTDihedralGate::Tdg => self.append_phase(7, qubits[0])?,
+            CNOTDihedralGate::S => self.append_phase(2, qubits[0])?,
+            CNOTDihedralGate::Sdg => self.append_phase(6, qubits[0])?,
+            CNOTDihedralGate::CX => self.append_cx(qubits[0], qubits[1])?,
+            CNOTDihedralGate::CZ => {
+                let (q0, q1) = (qubits[0], qubits[1]);
+                self.append_phase(7, q1)?;
+                self.append_phase(7, q0)?;
+                self.append_cx(q1, q0)?;
+                self.append_phase(2, q0)?;
+                self.append_cx(q1, q0)?;
+                self.append_phase(7, q1)?;
+                self.append_phase(7, q0)?;
+            }
+            CNOTDihedralGate::CS | CNOTDihedralGate::CSdg => {
+                let (q0, q1) = (qubits[0], qubits[1]);
+                let k = if gate == CNOTDihedralGate::CS { 1 } else { 7 };
+                self.append_phase(k, q1)?;
+                self.append_phase(k, q0)?;
+                self.append_cx(q1, q0)?;
+                self.append_phase(8 - k, q0)?;
+                self.append_cx(q1, q0)?;
+            }
+            CNOTDihedralGate::Swap => {
+                let (q0, q1) = (qubits[0], qubits[1]);
+                self.append_cx(q0, q1)?;
+                self.append_cx(q1, q0)?;
+                self.append_cx(q0, q1)?;
+            }
+            CNOTDihedralGate::CCZ => {
+                let (q0, q1, q2) = (qubits[0], qubits[1], qubits[2]);
+                self.append_cx(q1, q2)?;
+                self.append_phase(7, q2)?;
+                self.append_cx(q0, q2)?;
+                self.append_phase(1, q2)?;
+                self.append_cx(q1, q2)?;
+                self.append_phase(1, q1)?;
+                self.append_phase(7, q2)?;
+                self.append_cx(q0, q2)?;
+                self.append_cx(q0, q1)?;
+                self.append_phase(1, q2)?;
+                self.append_phase(1, q0)?;
+                self.append_phase(7, q1)?;
+                self.append_cx(q0, q1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The element that applies `first` and then `second`, including the global phase in the
+    /// constant term of the polynomial.  This is `CNOTDihedral._compose`.
+    fn compose_general(first: &Self, second: &Self) -> Self {
+        let n = first.num_qubits;
+        let linear = z2_matmul(second.linear.view(), first.linear.view());
+        let shift = Array1::from_shape_fn(n, |i| {
+            second
+                .linear
+                .row(i)
+                .iter()
+                .zip(&first.shift)
+                .fold(second.shift[i], |acc, (a, b)| acc ^ (a & b))
+        });
+        // The outputs `x' = A1 x + b1` of `first`, as polynomials in `x` through the `p_J`
+        // identity, are substituted into the polynomial of `second`.
+        let new_vars = (0..n)
+            .map(|i| {
+                let support = (0..n).filter(|&j| first.linear[[i, j]]).collect::<Vec<_>>();
+                let mut poly = SpecialPolynomial::pj(n, &support);
+                if first.shift[i] {
+                    poly.scale(7);
+                    poly.weights[0] = (poly.weights[0] + 1) % 8;
+                }
+                poly
+            })
+            .collect::<Vec<_>>();
+        let mut poly = second.poly.evaluate(&new_vars);
+        poly.add_assign(&first.poly);
+        CNOTDihedralElement {
+            num_qubits: n,
+            linear,
+            shift,
+            poly,
+        }
+    }
+
+    /// Compose with another element on the same qubits, up to global phase: the constant term of
+    /// the output polynomial is zero.  With `front = false` the result applies `self` and then
+    /// `other`; with `front = true` it applies `other` first.
+    pub fn compose(&self, other: &Self, front: bool) -> Result<Self, AccelerateError> {
+        if self.num_qubits != other.num_qubits {
+            return Err(AccelerateError::Qiskit(
+                "Incompatible dimension for composition".to_string(),
+            ));
+        }
+        let mut out = if front {
+            Self::compose_general(other, self)
+        } else {
+            Self::compose_general(self, other)
+        };
+        out.poly.weights[0] = 0;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+impl CNOTDihedralElement {
+    /// The image of the basis state `x` as `(k, y)`, for `|x> -> ω^k |y>`, with qubit `i` the
+    /// bit `i` of the states.
+    pub(crate) fn act(&self, x: usize) -> (u8, usize) {
+        let bit = |i: usize| (x >> i) & 1 == 1;
+        let phase = self
+            .poly
+            .monomials()
+            .zip(&self.poly.weights)
+            .filter(|(vars, _)| vars.iter().all(|&v| bit(v)))
+            .fold(0, |acc, (_, weight)| (acc + weight) % 8);
+        let y = (0..self.num_qubits)
+            .filter(|&i| {
+                let parity = (0..self.num_qubits)
+                    .filter(|&j| self.linear[[i, j]] && bit(j))
+                    .count();
+                (parity % 2 == 1) != self.shift[i]
+            })
+            .fold(0, |acc, i| acc | (1 << i));
+        (phase, y)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl CNOTDihedralElement {
+    #[new]
+    fn py_new(
+        linear: PyReadonlyArray2<bool>,
+        shift: PyReadonlyArray1<bool>,
+        weights: PyReadonlyArray1<u8>,
+    ) -> PyResult<Self> {
+        let shift = shift.as_array().to_owned();
+        let poly = SpecialPolynomial::from_weights(shift.len(), weights.as_array().to_vec())?;
+        Ok(Self::new(linear.as_array().to_owned(), shift, poly)?)
+    }
+
+    /// Create the identity on ``num_qubits`` qubits.
+    #[staticmethod]
+    #[pyo3(name = "identity")]
+    fn py_identity(num_qubits: usize) -> Self {
+        Self::identity(num_qubits)
+    }
+
+    #[getter]
+    #[pyo3(name = "num_qubits")]
+    fn py_num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// A copy of the linear part as a boolean array of shape ``(n, n)``.
+    #[getter]
+    #[pyo3(name = "linear")]
+    fn py_linear(&self, py: Python) -> Py<PyArray2<bool>> {
+        self.linear.clone().into_pyarray_bound(py).unbind()
+    }
+
+    /// A copy of the shift as a boolean array of length ``n``.
+    #[getter]
+    #[pyo3(name = "shift")]
+    fn py_shift(&self, py: Python) -> Py<PyArray1<bool>> {
+        self.shift.clone().into_pyarray_bound(py).unbind()
+    }
+
+    /// The weights of the constant, linear, quadratic and cubic terms of the phase polynomial, as
+    /// the ``weight_0`` to ``weight_3`` of ``SpecialPolynomial``.
+    #[getter]
+    #[pyo3(name = "poly")]
+    #[allow(clippy::type_complexity)]
+    fn py_poly(&self, py: Python) -> (u8, Py<PyArray1<u8>>, Py<PyArray1<u8>>, Py<PyArray1<u8>>) {
+        let weights =
+            |degree: usize| PyArray1::from_slice_bound(py, self.poly.weights_of_degree(degree));
+        (
+            self.poly.weights[0],
+            weights(1).unbind(),
+            weights(2).unbind(),
+            weights(3).unbind(),
+        )
+    }
+
+    /// Append a CNOT-dihedral gate in place.
+    ///
+    /// Args:
+    ///     name (str): the name of the gate, as in ``Instruction.name``.
+    ///     qubits (list[int]): the qubits the gate acts on.
+    ///
+    /// Raises:
+    ///     QiskitError: if the gate is not a supported CNOT-dihedral gate or the qubits are
+    ///         invalid.
+    #[pyo3(name = "append_gate", text_signature = "(self, name, qubits, /)")]
+    fn py_append_gate(&mut self, name: &str, qubits: Vec<usize>) -> PyResult<()> {
+        Ok(self.append_gate(name.parse()?, &qubits)?)
+    }
+
+    /// Append the ``k``-th power of :class:`.TGate` on ``qubit`` in place.
+    #[pyo3(name = "append_phase", text_signature = "(self, k, qubit, /)")]
+    fn py_append_phase(&mut self, k: u8, qubit: usize) -> PyResult<()> {
+        Ok(self.append_phase(k, qubit)?)
+    }
+
+    /// Compose with another element on the same number of qubits, up to global phase.
+    ///
+    /// Args:
+    ///     other (CNOTDihedralElement): the element to compose with.
+    ///     front (bool): if ``True`` apply ``other`` before ``self``, otherwise after it.
+    ///
+    /// Returns:
+    ///     CNOTDihedralElement: the composed element.
+    #[pyo3(name = "compose", signature = (other, front=false))]
+    fn py_compose(&self, other: &CNOTDihedralElement, front: bool) -> PyResult<Self> {
+        Ok(self.compose(other, front)?)
+    }
+
+    fn __eq__(&self, other: &CNOTDihedralElement) -> bool {
+        self == other
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn cnotdihedral(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<CNOTDihedralElement>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    const GATES: [CNOTDihedralGate; 14] = [
+        CNOTDihedralGate::I,
+        CNOTDihedralGate::X,
+        CNOTDihedralGate::Y,
+        CNOTDihedralGate::Z,
+        CNOTDihedralGate::T,
+        CNOTDihedralGate::Tdg,
+        CNOTDihedralGate::S,
+        CNOTDihedralGate::Sdg,
+        CNOTDihedralGate::CX,
+        CNOTDihedralGate::CZ,
+        CNOTDihedralGate::CS,
+        CNOTDihedralGate::CSdg,
+        CNOTDihedralGate::Swap,
+        CNOTDihedralGate::CCZ,
+    ];
+
+    /// The image `(k, y)` of the basis state `x` under `gate`, for `|x> -> ω^k |y>` up to a global
+    /// phase.
+    fn gate_action(gate: CNOTDihedralGate, qubits: &[usize], x: usize) -> (u8, usize) {
+        let bit = |i: usize| (x >> qubits[i]) & 1 == 1;
+        let all = |k: u8| {
+            if qubits.iter().all(|&q| (x >> q) & 1 == 1) {
+                k
+            } else {
+                0
+            }
+        };
+        match gate {
+            CNOTDihedralGate::I => (0, x),
+            CNOTDihedralGate::X => (0, x ^ (1 << qubits[0])),
+            // `Y = iXZ`, so `|x> -> i (-1)^x |x ^ 1>`, which is `(-1)^(x ^ 1)` up to the phase -i.
+            CNOTDihedralGate::Y => (if bit(0) { 0 } else { 4 }, x ^ (1 << qubits[0])),
+            CNOTDihedralGate::Z | CNOTDihedralGate::CZ | CNOTDihedralGate::CCZ => (all(4), x),
+            CNOTDihedralGate::T => (all(1), x),
+            CNOTDihedralGate::Tdg => (all(7), x),
+            CNOTDihedralGate::S | CNOTDihedralGate::CS => (all(2), x),
+            CNOTDihedralGate::Sdg | CNOTDihedralGate::CSdg => (all(6), x),
+            CNOTDihedralGate::CX => (0, if bit(0) { x ^ (1 << qubits[1]) } else { x }),
+            CNOTDihedralGate::Swap => {
+                let swapped = x & !(1 << qubits[0]) & !(1 << qubits[1]);
+                let (b0, b1) = (bit(0) as usize, bit(1) as usize);
+                (0, swapped | (b0 << qubits[1]) | (b1 << qubits[0]))
+            }
+        }
+    }
+
+    /// A random sequence of `len` gates on `num_qubits >= 3` qubits.
+    fn random_gates(
+        num_qubits: usize,
+        len: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<(CNOTDihedralGate, Vec<usize>)> {
+        (0..len)
+            .map(|_| {
+                let gate = GATES[rng.gen_range(0..GATES.len())];
+                let qubits = (0..num_qubits).choose_multiple(rng, gate.num_qubits());
+                (gate, qubits)
+            })
+            .collect()
+    }
+
+    fn element(num_qubits: usize, gates: &[(CNOTDihedralGate, Vec<usize>)]) -> CNOTDihedralElement {
+        let mut elem = CNOTDihedralElement::identity(num_qubits);
+        for (gate, qubits) in gates {
+            elem.append_gate(*gate, qubits).unwrap();
+        }
+        elem
+    }
+
+    /// Check `elem` maps each basis state as `action` does, up to a global phase.
+    fn assert_acts_as(
+        elem: &CNOTDihedralElement,
+        num_qubits: usize,
+        action: impl Fn(usize) -> (u8, usize),
+    ) {
+        let offset = (8 + elem.act(0).0 - action(0).0) % 8;
+        for x in 0..1 << num_qubits {
+            let (phase, y) = action(x);
+            assert_eq!(elem.act(x), ((phase + offset) % 8, y), "{x}");
+        }
+    }
+
+    #[test]
+    fn test_polynomial_indices() {
+        for num_vars in 0..6 {
+            let poly = SpecialPolynomial::zero(num_vars);
+            for (position, vars) in poly.monomials().enumerate() {
+                assert_eq!(poly.index(&vars), Some(position));
+            }
+            assert_eq!(poly.monomials().count(), poly.weights().len());
+        }
+        let mut poly = SpecialPolynomial::zero(4);
+        poly.set_term(&[0, 2, 3], 11);
+        assert_eq!(poly.get_term(&[0, 2, 3]), 3);
+        assert_eq!(poly.weights_of_degree(3), [0, 0, 3, 0]);
+        assert_eq!(poly.get_term(&[0, 1, 2, 3]), 0);
+    }
+
+    #[test]
+    fn test_gates() {
+        let num_qubits = 4;
+        for gate in GATES {
+            let qubits = [2, 0, 3][..gate.num_qubits()].to_vec();
+            let elem = element(num_qubits, &[(gate, qubits.clone())]);
+            assert_acts_as(&elem, num_qubits, |x| gate_action(gate, &qubits, x));
+        }
+    }
+
+    #[test]
+    fn test_random_sequences() {
+        let num_qubits = 4;
+        let mut rng = generator(2024);
+        for _ in 0..10 {
+            let gates = random_gates(num_qubits, 30, &mut rng);
+            let elem = element(num_qubits, &gates);
+            assert_acts_as(&elem, num_qubits, |x| {
+                gates.iter().fold((0, x), |(phase, x), (gate, qubits)| {
+                    let (k, y) = gate_action(*gate, qubits, x);
+                    ((phase + k) % 8, y)
+                })
+            });
+        }
+    }
+
+    #[test]
+    fn test_compose() {
+        let num_qubits = 3;
+        let mut rng = generator(7);
+        let first = random_gates(num_qubits, 15, &mut rng);
+        let second = random_gates(num_qubits, 15, &mut rng);
+        let (a, b) = (element(num_qubits, &first), element(num_qubits, &second));
+        let both = [first, second].concat();
+        let expected = element(num_qubits, &both);
+        for composed in [a.compose(&b, false).unwrap(), b.compose(&a, true).unwrap()] {
+            assert_eq!(composed.linear(), expected.linear());
+            assert_eq!(composed.shift(), expected.shift());
+            assert_eq!(composed.poly().weights()[0], 0);
+            assert_eq!(
+                composed.poly().weights()[1..],
+                expected.poly().weights()[1..]
+            );
+        }
+        assert!(matches!(
+            a.compose(&CNOTDihedralElement::identity(2), false),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            "h".parse::<CNOTDihedralGate>(),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut elem = CNOTDihedralElement::identity(2);
+        assert!(matches!(
+            elem.append_gate(CNOTDihedralGate::CX, &[0, 2]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            elem.append_gate(CNOTDihedralGate::CX, &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            elem.append_gate(CNOTDihedralGate::T, &[0, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            SpecialPolynomial::from_weights(2, vec![0; 3]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            CNOTDihedralElement::new(
+                Array2::from_elem((2, 2), false),
+                Array1::from_elem(3, false),
+                SpecialPolynomial::zero(3)
+            ),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
@@ -0,0 +1,466 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Collection of the maximal blocks of instructions that satisfy a predicate, as done by the
+//! `BlockCollector` for `CollectCliffords`, `CollectLinearFunctions` and the other
+//! `CollectAndCollapse` passes.
+//!
+//! Starting from the inputs of the circuit, the collector alternately takes the largest block of
+//! instructions that don't match and then the largest block that does, until every instruction is
+//! collected.  Taking the non-matching instructions first lets the matching blocks grow as large
+//! as possible.  The blocks can then be split into layers of non-overlapping instructions and into
+//! sub-blocks on disjoint sets of qubits.
+
+#[cfg(feature = "python")]
+use hashbrown::HashSet;
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use crate::error::AccelerateError;
+
+/// The wires of an instruction, which are the edges of the DAG of the circuit.
+#[derive(Clone, Debug, Default)]
+pub struct BlockInstruction {
+    pub qubits: SmallVec<[usize; 2]>,
+    /// The clbits of the instruction and of its classical condition, without repeats.
+    pub clbits: SmallVec<[usize; 2]>,
+}
+
+/// How the collected blocks are refined.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockCollectionOptions {
+    /// Split the blocks into sub-blocks on disjoint sets of qubits.
+    pub split_blocks: bool,
+    /// The fewest instructions in a block for it to be returned.
+    pub min_block_size: usize,
+    /// Split the blocks into layers of instructions on disjoint bits.
+    pub split_layers: bool,
+    /// Collect from the outputs of the circuit towards the inputs.
+    pub collect_from_back: bool,
+}
+
+impl Default for BlockCollectionOptions {
+    fn default() -> Self {
+        BlockCollectionOptions {
+            split_blocks: true,
+            min_block_size: 2,
+            split_layers: false,
+            collect_from_back: false,
+        }
+    }
+}
+
+/// The state of the greedy collection: the number of uncollected predecessors of each instruction
+/// and the instructions that have none left.
+struct Collector {
+    successors: Vec<SmallVec<[usize; 4]>>,
+    in_degree: Vec<usize>,
+    pending: Vec<usize>,
+}
+
+impl Collector {
+    fn new(num_wires: usize, wires: &[SmallVec<[usize; 4]>], from_back: bool) -> Self {
+        let mut successors = vec![SmallVec::new(); wires.len()];
+        let mut in_degree = vec![0; wires.len()];
+        let mut last = vec![None; num_wires];
+        let mut add_edges = |index: usize| {
+            for wire in wires[index].iter() {
+                if let Some(prev) = last[*wire].replace(index) {
+                    successors[prev].push(index);
+                    in_degree[index] += 1;
+                }
+            }
+        };
+        // Collecting from the back is collecting from the front of the reversed DAG.
+        if from_back {
+            (0..wires.len()).rev().for_each(&mut add_edges);
+        } else {
+            (0..wires.len()).for_each(&mut add_edges);
+        }
+        let pending = (0..wires.len())
+            .filter(|index| in_degree[*index] == 0)
+            .collect();
+        Collector {
+            successors,
+            in_degree,
+            pending,
+        }
+    }
+
+    /// Collect the largest block of pending instructions for which `filter` holds, adding the
+    /// successors of each collected instruction to the candidates as they become free.
+    fn collect_matching_block<F>(&mut self, filter: F) -> Vec<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut block = Vec::new();
+        let mut unprocessed = std::mem::take(&mut self.pending);
+        while !unprocessed.is_empty() {
+            let mut new_pending = Vec::new();
+            for index in unprocessed {
+                if filter(index) {
+                    block.push(index);
+                    for succ in self.successors[index].iter() {
+                        self.in_degree[*succ] -= 1;
+                        if self.in_degree[*succ] == 0 {
+                            new_pending.push(*succ);
+                        }
+                    }
+                } else {
+                    self.pending.push(index);
+                }
+            }
+            unprocessed = new_pending;
+        }
+        block
+    }
+}
+
+/// Split a block into layers of instructions that don't share any bit.
+fn split_block_into_layers(
+    num_qubits: usize,
+    instructions: &[BlockInstruction],
+    block: Vec<usize>,
+) -> Vec<Vec<usize>> {
+    let mut bit_depths = hashbrown::HashMap::<usize, usize>::new();
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    for index in block {
+        let inst = &instructions[index];
+        let bits = inst
+            .qubits
+            .iter()
+            .copied()
+            .chain(inst.clbits.iter().map(|clbit| num_qubits + clbit));
+        let depth = bits
+            .clone()
+            .map(|bit| bit_depths.get(&bit).copied().unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+        if layers.len() <= depth {
+            layers.resize_with(depth + 1, Vec::new);
+        }
+        for bit in bits {
+            bit_depths.insert(bit, depth + 1);
+        }
+        layers[depth].push(index);
+    }
+    layers
+}
+
+/// Split a block into sub-blocks on disjoint sets of qubits.  Instructions on no qubits are
+/// dropped.
+///
+/// The sets are found by union-find on the qubits, and the sub-blocks are in the order of the
+/// first qubit of each set to appear in the block.
+fn split_block_on_qubits(
+    num_qubits: usize,
+    instructions: &[BlockInstruction],
+    block: Vec<usize>,
+) -> Vec<Vec<usize>> {
+    let mut leader: Vec<Option<usize>> = vec![None; num_qubits];
+    let mut groups: Vec<Vec<usize>> = vec![Vec::new(); num_qubits];
+    let mut seen = Vec::new();
+
+    fn find(leader: &mut [Option<usize>], seen: &mut Vec<usize>, qubit: usize) -> usize {
+        match leader[qubit] {
+            None => {
+                leader[qubit] = Some(qubit);
+                seen.push(qubit);
+                qubit
+            }
+            Some(parent) if parent == qubit => qubit,
+            Some(parent) => {
+                let root = find(leader, seen, parent);
+                leader[qubit] = Some(root);
+                root
+            }
+        }
+    }
+
+    for index in block {
+        let Some((first, rest)) = instructions[index].qubits.split_first() else {
+            continue;
+        };
+        for qubit in rest {
+            let mut a = find(&mut leader, &mut seen, *first);
+            let mut b = find(&mut leader, &mut seen, *qubit);
+            if a == b {
+                continue;
+            }
+            if groups[a].len() < groups[b].len() {
+                std::mem::swap(&mut a, &mut b);
+            }
+            leader[b] = Some(a);
+            let moved = std::mem::take(&mut groups[b]);
+            groups[a].extend(moved);
+        }
+        let root = find(&mut leader, &mut seen, *first);
+        groups[root].push(index);
+    }
+    seen.into_iter()
+        .filter(|qubit| leader[*qubit] == Some(*qubit))
+        .map(|qubit| std::mem::take(&mut groups[qubit]))
+        .collect()
+}
+
+/// Collect the maximal blocks of the instructions of a circuit for which `matches` is true.
+///
+/// `instructions` are in a topological order of the circuit, and the blocks are lists of indices
+/// into it, each in a topological order.
+pub fn collect_matching_blocks_inner(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[BlockInstruction],
+    matches: &[bool],
+    options: &BlockCollectionOptions,
+) -> Vec<Vec<usize>> {
+    let wires = instructions
+        .iter()
+        .map(|inst| {
+            inst.qubits
+                .iter()
+                .copied()
+                .chain(inst.clbits.iter().map(|clbit| num_qubits + clbit))
+                .collect()
+        })
+        .collect::<Vec<SmallVec<[usize; 4]>>>();
+    let mut collector = Collector::new(num_qubits + num_clbits, &wires, options.collect_from_back);
+
+    let mut blocks = Vec::new();
+    while !collector.pending.is_empty() {
+        collector.collect_matching_block(|index| !matches[index]);
+        let block = collector.collect_matching_block(|index| matches[index]);
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+    if options.split_layers {
+        blocks = blocks
+            .into_iter()
+            .flat_map(|block| split_block_into_layers(num_qubits, instructions, block))
+            .collect();
+    }
+    if options.split_blocks {
+        blocks = blocks
+            .into_iter()
+            .flat_map(|block| split_block_on_qubits(num_qubits, instructions, block))
+            .collect();
+    }
+    if options.collect_from_back {
+        blocks.reverse();
+        for block in blocks.iter_mut() {
+            block.reverse();
+        }
+    }
+    blocks.retain(|block| block.len() >= options.min_block_size);
+    blocks
+}
+
+/// Read the wires of an instruction of `circuit`, adding the clbits of its classical condition,
+/// and whether it is conditioned.
+#[cfg(feature = "python")]
+pub(crate) fn block_instruction(
+    circuit: &CircuitData,
+    op: &Bound<PyAny>,
+    qubits: &[BitType],
+    clbits: &[BitType],
+) -> PyResult<(BlockInstruction, bool)> {
+    let py = op.py();
+    let mut inst = BlockInstruction {
+        qubits: qubits.iter().map(|q| *q as usize).collect(),
+        clbits: clbits.iter().map(|c| *c as usize).collect(),
+    };
+    let conditioned =
+        op.hasattr(intern!(py, "condition"))? && !op.getattr(intern!(py, "condition"))?.is_none();
+    if conditioned {
+        for bit in op.getattr(intern!(py, "condition_bits"))?.iter()? {
+            let bit = circuit.find_clbit(&bit?)?.ok_or_else(|| {
+                AccelerateError::Value(
+                    "a classical condition uses a clbit that is not in the circuit".to_string(),
+                )
+            })? as usize;
+            if !inst.clbits.contains(&bit) {
+                inst.clbits.push(bit);
+            }
+        }
+    }
+    Ok((inst, conditioned))
+}
+
+/// Collect the maximal blocks of the instructions of a circuit that are among a set of
+/// unconditioned gates, as the ``BlockCollector`` does with a filter function.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, in a topological order of its DAG.
+///     gate_names (set[str]): the names of the operations that may be in a block.  An instruction
+///         matches if its name is one of these and it has no classical condition.
+///     split_blocks (bool): whether to split the blocks into sub-blocks on disjoint qubits.
+///     min_block_size (int): the fewest instructions in a block for it to be returned.
+///     split_layers (bool): whether to split the blocks into layers of instructions on disjoint
+///         bits.
+///     collect_from_back (bool): whether to collect from the outputs of the circuit towards its
+///         inputs.
+///
+/// Returns:
+///     list[list[int]]: the indices of the instructions of each block, in a topological order.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, gate_names, split_blocks=true, min_block_size=2, split_layers=false, collect_from_back=false))]
+pub fn collect_matching_blocks(
+    py: Python,
+    circuit: &CircuitData,
+    gate_names: HashSet<String>,
+    split_blocks: bool,
+    min_block_size: usize,
+    split_layers: bool,
+    collect_from_back: bool,
+) -> PyResult<Vec<Vec<usize>>> {
+    let num_instructions = circuit.iter_instructions().len();
+    let mut instructions = Vec::with_capacity(num_instructions);
+    let mut matches = Vec::with_capacity(num_instructions);
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        let op = op.bind(py);
+        let (inst, conditioned) = block_instruction(circuit, op, qubits, clbits)?;
+        let name = op.getattr(intern!(py, "name"))?.extract::<String>()?;
+        matches.push(!conditioned && gate_names.contains(&name));
+        instructions.push(inst);
+    }
+    Ok(collect_matching_blocks_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions,
+        &matches,
+        &BlockCollectionOptions {
+            split_blocks,
+            min_block_size,
+            split_layers,
+            collect_from_back,
+        },
+    ))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn collect_blocks(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(collect_matching_blocks))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(qubits: &[usize]) -> BlockInstruction {
+        BlockInstruction {
+            qubits: qubits.iter().copied().collect(),
+            clbits: SmallVec::new(),
+        }
+    }
+
+    fn options(
+        split_blocks: bool,
+        split_layers: bool,
+        min_block_size: usize,
+    ) -> BlockCollectionOptions {
+        BlockCollectionOptions {
+            split_blocks,
+            min_block_size,
+            split_layers,
+            collect_from_back: false,
+        }
+    }
+
+    #[test]
+    fn test_maximal_blocks() {
+        // `h(0); cx(0, 1); t(1); cx(1, 2); s(2); x(0)`, where only `t` doesn't match.
+        let instructions = [
+            inst(&[0]),
+            inst(&[0, 1]),
+            inst(&[1]),
+            inst(&[1, 2]),
+            inst(&[2]),
+            inst(&[0]),
+        ];
+        let matches = [true, true, false, true, true, true];
+        let blocks = collect_matching_blocks_inner(
+            3,
+            0,
+            &instructions,
+            &matches,
+            &BlockCollectionOptions::default(),
+        );
+        assert_eq!(blocks, [vec![0, 1, 5], vec![3, 4]]);
+        let blocks =
+            collect_matching_blocks_inner(3, 0, &instructions, &matches, &options(true, false, 3));
+        assert_eq!(blocks, [vec![0, 1, 5]]);
+    }
+
+    #[test]
+    fn test_split_blocks_and_layers() {
+        // `cx(0, 1); cx(2, 3); h(0)`.
+        let instructions = [inst(&[0, 1]), inst(&[2, 3]), inst(&[0])];
+        let matches = [true; 3];
+        let collect =
+            |options| collect_matching_blocks_inner(4, 0, &instructions, &matches, &options);
+        assert_eq!(collect(options(false, false, 1)), [vec![0, 1, 2]]);
+        assert_eq!(collect(options(true, false, 1)), [vec![0, 2], vec![1]]);
+        assert_eq!(collect(options(false, true, 1)), [vec![0, 1], vec![2]]);
+        assert_eq!(collect(options(true, true, 1)), [vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_collect_from_back() {
+        // `cx(0, 1); z(0); h(1); cx(0, 1)`, where only `z` doesn't match.
+        let instructions = [inst(&[0, 1]), inst(&[0]), inst(&[1]), inst(&[0, 1])];
+        let matches = [true, false, true, true];
+        let mut options = options(true, false, 1);
+        let front = collect_matching_blocks_inner(2, 0, &instructions, &matches, &options);
+        assert_eq!(front, [vec![0, 2], vec![3]]);
+        options.collect_from_back = true;
+        let back = collect_matching_blocks_inner(2, 0, &instructions, &matches, &options);
+        assert_eq!(back, [vec![0], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_clbits_are_wires() {
+        // `x(0); measure(1, 0); x(0).c_if(0)`, where the measurement doesn't match.
+        let mut conditioned = inst(&[0]);
+        conditioned.clbits.push(0);
+        let mut measure = inst(&[1]);
+        measure.clbits.push(0);
+        let instructions = [inst(&[0]), measure, conditioned];
+        let matches = [true, false, true];
+        let blocks =
+            collect_matching_blocks_inner(2, 1, &instructions, &matches, &options(false, false, 1));
+        assert_eq!(blocks, [vec![0, 2]]);
+        // Splitting into layers respects the clbit too.
+        let blocks = collect_matching_blocks_inner(
+            2,
+            1,
+            &[inst(&[0]), instructions[1].clone(), instructions[2].clone()],
+            &[false, true, true],
+            &options(false, true, 1),
+        );
+        assert_eq!(blocks, [vec![1], vec![2]]);
+    }
+}
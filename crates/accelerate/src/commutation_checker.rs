@@ -0,0 +1,256 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Whether two standard gates commute, from their matrices.
+//!
+//! Both gates are embedded in the space of the qubits that either of them acts on, which is at most
+//! six qubits for the standard gates, and the two products are compared.  Gates on disjoint qubits
+//! always commute, without building any matrix.
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use smallvec::SmallVec;
+
+use qiskit_circuit::operations::StandardGate;
+
+use crate::error::AccelerateError;
+
+/// The tolerance on each entry of the commutator.
+const COMMUTATION_ATOL: f64 = 1e-10;
+
+/// A standard gate with its parameters and the qubits it acts on.
+#[derive(Clone, Copy, Debug)]
+pub struct GateRef<'a> {
+    pub gate: StandardGate,
+    pub params: &'a [f64],
+    pub qubits: &'a [usize],
+}
+
+/// The matrix `matrix` of an operation on `qubits`, embedded in the space of the qubits `space`,
+/// in Qiskit's little-endian convention, where the qubits of the operation are all in `space`.
+fn embed(qubits: &[usize], matrix: ArrayView2<Complex64>, space: &[usize]) -> Array2<Complex64> {
+    let positions = qubits
+        .iter()
+        .map(|qubit| space.iter().position(|q| q == qubit).unwrap())
+        .collect::<SmallVec<[usize; 3]>>();
+    let mask = positions.iter().fold(0usize, |mask, pos| mask | (1 << pos));
+    let local = |state: usize| {
+        positions
+            .iter()
+            .enumerate()
+            .fold(0, |out, (k, pos)| out | (((state >> pos) & 1) << k))
+    };
+    let dim = 1 << space.len();
+    Array2::from_shape_fn((dim, dim), |(row, col)| {
+        if row & !mask == col & !mask {
+            matrix[[local(row), local(col)]]
+        } else {
+            Complex64::new(0., 0.)
+        }
+    })
+}
+
+/// Whether two operations, given by their matrices and the qubits they act on, commute.
+pub fn matrices_commute(
+    first: ArrayView2<Complex64>,
+    first_qubits: &[usize],
+    second: ArrayView2<Complex64>,
+    second_qubits: &[usize],
+) -> bool {
+    if !first_qubits
+        .iter()
+        .any(|qubit| second_qubits.contains(qubit))
+    {
+        return true;
+    }
+    let mut space = first_qubits.to_vec();
+    space.extend(
+        second_qubits
+            .iter()
+            .filter(|qubit| !first_qubits.contains(qubit)),
+    );
+    let left = embed(first_qubits, first, &space);
+    let right = embed(second_qubits, second, &space);
+    let forward = left.dot(&right);
+    let backward = right.dot(&left);
+    forward
+        .iter()
+        .zip(backward.iter())
+        .all(|(a, b)| (a - b).norm() <= COMMUTATION_ATOL)
+}
+
+/// Whether two standard gates commute, or `None` if the parameters of either don't fit its gate.
+pub fn commute(first: GateRef, second: GateRef) -> Option<bool> {
+    if !first
+        .qubits
+        .iter()
+        .any(|qubit| second.qubits.contains(qubit))
+    {
+        return Some(true);
+    }
+    let left = first.gate.matrix(first.params)?;
+    let right = second.gate.matrix(second.params)?;
+    Some(matrices_commute(
+        left.view(),
+        first.qubits,
+        right.view(),
+        second.qubits,
+    ))
+}
+
+/// Look up a standard gate by name, checking the numbers of its parameters and qubits.
+pub fn standard_gate(
+    name: &str,
+    num_params: usize,
+    num_qubits: usize,
+) -> Result<StandardGate, AccelerateError> {
+    let gate = StandardGate::from_name(name)
+        .ok_or_else(|| AccelerateError::Value(format!("unknown standard gate '{name}'")))?;
+    if gate.num_params() as usize != num_params || gate.num_qubits() as usize != num_qubits {
+        return Err(AccelerateError::Value(format!(
+            "the gate '{name}' takes {} parameters on {} qubits, not {num_params} on {num_qubits}",
+            gate.num_params(),
+            gate.num_qubits()
+        )));
+    }
+    Ok(gate)
+}
+
+/// Return whether two standard gates commute.
+///
+/// Args:
+///     first (tuple[str, list[float], list[int]]): the name, the parameters and the qubits of the
+///         first gate.
+///     second (tuple[str, list[float], list[int]]): the same for the second gate.
+///
+/// Returns:
+///     bool: whether the gates commute.
+///
+/// Raises:
+///     ValueError: if a gate isn't a standard gate with the given numbers of parameters and qubits.
+#[pyfunction]
+#[pyo3(name = "commute", text_signature = "(first, second, /)")]
+pub fn py_commute(
+    first: (String, Vec<f64>, Vec<usize>),
+    second: (String, Vec<f64>, Vec<usize>),
+) -> PyResult<bool> {
+    let first_gate = standard_gate(&first.0, first.1.len(), first.2.len())?;
+    let second_gate = standard_gate(&second.0, second.1.len(), second.2.len())?;
+    Ok(commute(
+        GateRef {
+            gate: first_gate,
+            params: &first.1,
+            qubits: &first.2,
+        },
+        GateRef {
+            gate: second_gate,
+            params: &second.1,
+            qubits: &second.2,
+        },
+    )
+    .expect("the numbers of parameters are checked"))
+}
+
+#[pymodule]
+pub fn commutation_checker(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_commute))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate<'a>(gate: StandardGate, params: &'a [f64], qubits: &'a [usize]) -> GateRef<'a> {
+        GateRef {
+            gate,
+            params,
+            qubits,
+        }
+    }
+
+    #[test]
+    fn test_commute() {
+        let cx = StandardGate::CXGate;
+        let rzz = StandardGate::RZZGate;
+        // Shared controls commute, where a target that's the other gate's control doesn't.
+        assert_eq!(
+            commute(gate(cx, &[], &[0, 1]), gate(cx, &[], &[0, 2])),
+            Some(true)
+        );
+        assert_eq!(
+            commute(gate(cx, &[], &[0, 1]), gate(cx, &[], &[1, 2])),
+            Some(false)
+        );
+        assert_eq!(
+            commute(gate(cx, &[], &[0, 1]), gate(cx, &[], &[1, 0])),
+            Some(false)
+        );
+        assert_eq!(
+            commute(gate(rzz, &[0.3], &[0, 1]), gate(rzz, &[-1.2], &[2, 1])),
+            Some(true)
+        );
+        assert_eq!(
+            commute(
+                gate(StandardGate::RZGate, &[0.7], &[0]),
+                gate(cx, &[], &[0, 1])
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            commute(
+                gate(StandardGate::RXGate, &[0.7], &[0]),
+                gate(cx, &[], &[0, 1])
+            ),
+            Some(false)
+        );
+        assert_eq!(
+            commute(
+                gate(StandardGate::RXGate, &[0.7], &[1]),
+                gate(cx, &[], &[0, 1])
+            ),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_commute_disjoint_and_bad_params() {
+        let rx = StandardGate::RXGate;
+        assert_eq!(
+            commute(gate(rx, &[0.1], &[0]), gate(rx, &[], &[1])),
+            Some(true)
+        );
+        assert_eq!(commute(gate(rx, &[0.1], &[0]), gate(rx, &[], &[0])), None);
+    }
+
+    #[test]
+    fn test_standard_gate() {
+        assert!(matches!(
+            standard_gate("cx", 0, 2),
+            Ok(StandardGate::CXGate)
+        ));
+        assert!(matches!(
+            standard_gate("not_a_gate", 0, 1),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            standard_gate("rz", 0, 1),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            standard_gate("cx", 0, 1),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+}
@@ -0,0 +1,342 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The cancellation of pairs of gates that are inverses of each other, once the gates between them
+//! are commuted out of the way, as done by the `CommutativeInverseCancellation` pass.
+//!
+//! Each gate looks back through the live instructions on its qubits, latest first, for an inverse
+//! on the same qubits in the same order, and stops at the first instruction it doesn't commute
+//! with.  Only the instructions that share a qubit with the gate are visited, since the others
+//! always commute with it, so the scan is linear in the number of instructions when the circuit
+//! doesn't commute much.
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use pyo3::intern;
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use smallvec::SmallVec;
+
+use qiskit_circuit::circuit_data::CircuitData;
+
+use crate::commutation_checker::matrices_commute;
+use crate::instrumentation;
+use crate::operator::{ATOL_DEFAULT, RTOL_DEFAULT};
+use crate::statevector::{operation_matrix, OperationMatrix};
+
+/// How an instruction takes part in the cancellation.
+pub enum Candidate {
+    /// A gate that can be cancelled and commuted, with its matrix.
+    Gate(Array2<Complex64>),
+    /// An instruction that nothing commutes past on its qubits, such as a measurement, a barrier or
+    /// a gate without a matrix.
+    Blocker,
+    /// An instruction that nothing commutes past on any qubit, such as a classically conditioned
+    /// gate, as the Python-space commutation checker treats them.
+    Fence,
+}
+
+/// The phase `φ` with `second · first = e^{iφ} I`, if the two matrices are inverses up to a phase,
+/// and with `φ = 0` unless `ignore_phase`.
+pub fn inverse_phase(
+    first: ArrayView2<Complex64>,
+    second: ArrayView2<Complex64>,
+    ignore_phase: bool,
+) -> Option<f64> {
+    if first.dim() != second.dim() {
+        return None;
+    }
+    let product = second.dot(&first);
+    let phase = if ignore_phase {
+        (product.diag().sum() / product.nrows() as f64).arg()
+    } else {
+        0.
+    };
+    let diagonal = Complex64::from_polar(1., phase);
+    product
+        .indexed_iter()
+        .all(|((i, j), x)| {
+            let expected = if i == j {
+                diagonal
+            } else {
+                Complex64::new(0., 0.)
+            };
+            (expected - x).norm() <= ATOL_DEFAULT + RTOL_DEFAULT * expected.norm()
+        })
+        .then_some(phase)
+}
+
+/// The pairs of instructions of a circuit on `num_qubits` qubits that cancel, and the global phase
+/// their removal adds to the circuit.
+///
+/// Returns whether each instruction is removed, and the phase.
+pub fn cancel_inverses_inner(
+    num_qubits: usize,
+    instructions: &[(Candidate, SmallVec<[usize; 2]>)],
+    matrix_based: bool,
+) -> (Vec<bool>, f64) {
+    let mut removed = vec![false; instructions.len()];
+    let mut global_phase = 0.;
+    // The instructions on each qubit, in circuit order, including the removed ones.
+    let mut wires: Vec<Vec<usize>> = vec![Vec::new(); num_qubits];
+    let mut fence = 0;
+    for (index, (candidate, qubits)) in instructions.iter().enumerate() {
+        if let Candidate::Gate(matrix) = candidate {
+            // Walk back through the instructions on the gate's qubits, merging the lists of the
+            // qubits from their ends.
+            let mut cursors = qubits
+                .iter()
+                .map(|qubit| wires[*qubit].len())
+                .collect::<SmallVec<[usize; 2]>>();
+            loop {
+                let Some(other) = qubits
+                    .iter()
+                    .zip(cursors.iter())
+                    .filter(|(_, cursor)| **cursor > 0)
+                    .map(|(qubit, cursor)| wires[*qubit][cursor - 1])
+                    .max()
+                else {
+                    break;
+                };
+                for (qubit, cursor) in qubits.iter().zip(cursors.iter_mut()) {
+                    if *cursor > 0 && wires[*qubit][*cursor - 1] == other {
+                        *cursor -= 1;
+                    }
+                }
+                if other < fence {
+                    break;
+                }
+                if removed[other] {
+                    continue;
+                }
+                let (other_candidate, other_qubits) = &instructions[other];
+                let Candidate::Gate(other_matrix) = other_candidate else {
+                    break;
+                };
+                if other_qubits == qubits {
+                    if let Some(phase) =
+                        inverse_phase(other_matrix.view(), matrix.view(), matrix_based)
+                    {
+                        removed[other] = true;
+                        removed[index] = true;
+                        global_phase += phase;
+                        break;
+                    }
+                }
+                if !matrices_commute(other_matrix.view(), other_qubits, matrix.view(), qubits) {
+                    break;
+                }
+            }
+        } else if let Candidate::Fence = candidate {
+            fence = index + 1;
+        }
+        if !removed[index] {
+            for qubit in qubits.iter() {
+                wires[*qubit].push(index);
+            }
+        }
+    }
+    (removed, global_phase)
+}
+
+/// The candidates of the instructions of `circuit`.
+fn candidates(
+    py: Python,
+    circuit: &CircuitData,
+    max_qubits: usize,
+) -> PyResult<Vec<(Candidate, SmallVec<[usize; 2]>)>> {
+    circuit
+        .iter_instructions()
+        .map(|(op, qubits, clbits)| {
+            let op = op.bind(py);
+            let qubits: SmallVec<[usize; 2]> = qubits.iter().map(|q| *q as usize).collect();
+            if op.hasattr(intern!(py, "condition"))?
+                && !op.getattr(intern!(py, "condition"))?.is_none()
+            {
+                return Ok((Candidate::Fence, qubits));
+            }
+            if !clbits.is_empty()
+                || qubits.len() > max_qubits
+                || (op.hasattr(intern!(py, "_directive"))?
+                    && op.getattr(intern!(py, "_directive"))?.is_truthy()?)
+            {
+                return Ok((Candidate::Blocker, qubits));
+            }
+            let candidate = match operation_matrix(op)? {
+                OperationMatrix::Matrix(matrix) => Candidate::Gate(matrix),
+                OperationMatrix::Skip | OperationMatrix::Unsupported => Candidate::Blocker,
+            };
+            Ok((candidate, qubits))
+        })
+        .collect()
+}
+
+/// Cancel the pairs of gates of a circuit that are inverses of each other up to commuting the
+/// gates between them.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, which must not contain control flow.
+///     matrix_based (bool): whether the gates may be inverses up to a phase, which is then added
+///         to the global phase.  Otherwise, they must be exact inverses.
+///     max_qubits (int): the most qubits of a gate that can be cancelled or commuted.
+///
+/// Returns:
+///     (CircuitData, float): the circuit without the cancelled gates, and the global phase to add
+///     to it.
+#[pyfunction]
+#[pyo3(name = "commutative_inverse_cancellation", signature = (circuit, matrix_based=false, max_qubits=4))]
+pub fn py_commutative_inverse_cancellation(
+    py: Python,
+    circuit: &CircuitData,
+    matrix_based: bool,
+    max_qubits: usize,
+) -> PyResult<(CircuitData, f64)> {
+    let _timer = instrumentation::Timer::start("commutative_inverse_cancellation");
+    let instructions = candidates(py, circuit, max_qubits)?;
+    let (removed, global_phase) =
+        cancel_inverses_inner(circuit.num_qubits(), &instructions, matrix_based);
+    let mut out = circuit.copy_empty_like(py)?;
+    out.reserve(py, removed.iter().filter(|removed| !**removed).count());
+    for ((op, qubits, clbits), removed) in circuit.iter_instructions().zip(removed) {
+        if !removed {
+            out.push_instruction(op.clone_ref(py), qubits, clbits)?;
+        }
+    }
+    Ok((out, global_phase))
+}
+
+#[pymodule]
+pub fn commutative_inverse_cancellation(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_commutative_inverse_cancellation))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smallvec::smallvec;
+    use std::f64::consts::FRAC_PI_4;
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    fn x() -> Array2<Complex64> {
+        array![[c(0., 0.), c(1., 0.)], [c(1., 0.), c(0., 0.)]]
+    }
+
+    fn z() -> Array2<Complex64> {
+        array![[c(1., 0.), c(0., 0.)], [c(0., 0.), c(-1., 0.)]]
+    }
+
+    fn h() -> Array2<Complex64> {
+        let r = std::f64::consts::FRAC_1_SQRT_2;
+        array![[c(r, 0.), c(r, 0.)], [c(r, 0.), c(-r, 0.)]]
+    }
+
+    fn s(sign: f64) -> Array2<Complex64> {
+        array![[c(1., 0.), c(0., 0.)], [c(0., 0.), c(0., sign)]]
+    }
+
+    /// The matrix of a CX whose control is its first qubit, in Qiskit's little-endian convention.
+    fn cx() -> Array2<Complex64> {
+        let mut out = Array2::zeros((4, 4));
+        for (row, col) in [(0, 0), (1, 3), (2, 2), (3, 1)] {
+            out[[row, col]] = c(1., 0.);
+        }
+        out
+    }
+
+    fn gate(matrix: Array2<Complex64>, qubits: &[usize]) -> (Candidate, SmallVec<[usize; 2]>) {
+        (Candidate::Gate(matrix), qubits.iter().copied().collect())
+    }
+
+    #[test]
+    fn test_inverse_phase() {
+        assert_eq!(inverse_phase(s(1.).view(), s(-1.).view(), false), Some(0.));
+        assert_eq!(inverse_phase(s(1.).view(), s(1.).view(), false), None);
+        assert_eq!(inverse_phase(x().view(), cx().view(), true), None);
+        let phased = x().mapv(|x| x * Complex64::from_polar(1., FRAC_PI_4));
+        assert_eq!(inverse_phase(x().view(), phased.view(), false), None);
+        let phase = inverse_phase(x().view(), phased.view(), true).unwrap();
+        assert!((phase - FRAC_PI_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cancel_through_commuting_gates() {
+        // Z commutes with the control of a CX but not with its target.
+        let instructions = [gate(z(), &[0]), gate(cx(), &[0, 1]), gate(z(), &[0])];
+        let (removed, phase) = cancel_inverses_inner(2, &instructions, false);
+        assert_eq!(removed, [true, false, true]);
+        assert_eq!(phase, 0.);
+        let instructions = [gate(z(), &[1]), gate(cx(), &[0, 1]), gate(z(), &[1])];
+        let (removed, _) = cancel_inverses_inner(2, &instructions, false);
+        assert_eq!(removed, [false, false, false]);
+    }
+
+    #[test]
+    fn test_cancel_qubit_order() {
+        let instructions = [gate(cx(), &[0, 1]), gate(cx(), &[0, 1])];
+        assert_eq!(
+            cancel_inverses_inner(2, &instructions, false).0,
+            [true, true]
+        );
+        let instructions = [gate(cx(), &[0, 1]), gate(cx(), &[1, 0])];
+        assert_eq!(
+            cancel_inverses_inner(2, &instructions, false).0,
+            [false, false]
+        );
+    }
+
+    #[test]
+    fn test_cancel_nested_and_odd() {
+        // Removed gates don't stop the scan, and each gate cancels at most one other.
+        let instructions = [
+            gate(h(), &[0]),
+            gate(x(), &[0]),
+            gate(x(), &[0]),
+            gate(h(), &[0]),
+        ];
+        let (removed, _) = cancel_inverses_inner(1, &instructions, false);
+        assert_eq!(removed, [true, true, true, true]);
+        let instructions = [gate(x(), &[0]), gate(x(), &[0]), gate(x(), &[0])];
+        let (removed, _) = cancel_inverses_inner(1, &instructions, false);
+        assert_eq!(removed, [true, true, false]);
+    }
+
+    #[test]
+    fn test_cancel_blockers_and_fences() {
+        let blocked = |candidate: Candidate, qubit: usize| {
+            let instructions = [
+                gate(x(), &[0]),
+                (candidate, smallvec![qubit]),
+                gate(x(), &[0]),
+            ];
+            cancel_inverses_inner(2, &instructions, false).0
+        };
+        // A blocker only stops the gates on its qubits, where a fence stops all of them.
+        assert_eq!(blocked(Candidate::Blocker, 0), [false, false, false]);
+        assert_eq!(blocked(Candidate::Blocker, 1), [true, false, true]);
+        assert_eq!(blocked(Candidate::Fence, 1), [false, false, false]);
+    }
+
+    #[test]
+    fn test_cancel_phase() {
+        let phased = x().mapv(|x| x * Complex64::from_polar(1., FRAC_PI_4));
+        let instructions = [gate(x(), &[0]), gate(phased, &[0])];
+        let (removed, _) = cancel_inverses_inner(1, &instructions, false);
+        assert_eq!(removed, [false, false]);
+        let (removed, phase) = cancel_inverses_inner(1, &instructions, true);
+        assert_eq!(removed, [true, true]);
+        assert!((phase - FRAC_PI_4).abs() < 1e-12);
+    }
+}
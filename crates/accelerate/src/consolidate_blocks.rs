@@ -0,0 +1,446 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The collection of maximal runs of gates that act on at most two qubits, and their consolidation
+//! into single unitaries, as done by the `Collect2qBlocks` and `ConsolidateBlocks` passes.
+//!
+//! Blocks are collected in a single sweep over the instructions in circuit order.  Each qubit has
+//! at most one open block; a one-qubit gate joins the open block of its qubit, and a two-qubit gate
+//! joins the open block of its qubits if it is the same two-qubit block, and otherwise closes the
+//! two-qubit blocks on its qubits and opens a new block that absorbs their pending one-qubit runs.
+//! Any other instruction closes the blocks on its qubits.  Every instruction between the first
+//! and the last one of a block that touches its qubits is in the block, so the block can be
+//! replaced by a single operation at the position of its last instruction.
+
+#[cfg(feature = "python")]
+use hashbrown::HashSet;
+#[cfg(feature = "python")]
+use numpy::IntoPyArray;
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::IntoPyDict;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::imports;
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+
+#[cfg(feature = "python")]
+use ndarray::prelude::*;
+#[cfg(feature = "python")]
+use num_complex::Complex64;
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use crate::convert_2q_block_matrix::blocks_to_matrix_inner;
+#[cfg(feature = "python")]
+use crate::instrumentation;
+#[cfg(feature = "python")]
+use crate::operator::{ATOL_DEFAULT, RTOL_DEFAULT};
+#[cfg(feature = "python")]
+use crate::statevector::{operation_matrix, OperationMatrix};
+#[cfg(feature = "python")]
+use crate::two_qubit_decompose::TwoQubitBasisDecomposer;
+
+/// A block of instructions on one or two qubits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// The qubits of the block, in increasing order.
+    pub qubits: SmallVec<[usize; 2]>,
+    /// The indices of the instructions of the block, in circuit order.
+    pub instructions: Vec<usize>,
+}
+
+impl Block {
+    /// Whether the block contains a two-qubit instruction.
+    pub fn is_two_qubit(&self) -> bool {
+        self.qubits.len() == 2
+    }
+}
+
+/// Collect the maximal blocks of instructions on at most two qubits.
+///
+/// `instructions` yields, for each instruction in circuit order, the qubits it acts on and whether
+/// it can be part of a block at all.  The blocks are returned in the order they are closed, which is
+/// a topological order of the circuit; instructions that can't be collected are in no block.
+pub fn collect_2q_blocks_inner<'a, I>(num_qubits: usize, instructions: I) -> Vec<Block>
+where
+    I: IntoIterator<Item = (&'a [usize], bool)>,
+{
+    let mut blocks: Vec<Block> = Vec::new();
+    // The index in `open` of the open block of each qubit.
+    let mut open_on: Vec<Option<usize>> = vec![None; num_qubits];
+    let mut open: Vec<Option<Block>> = Vec::new();
+
+    let close = |index: usize,
+                 open: &mut Vec<Option<Block>>,
+                 open_on: &mut Vec<Option<usize>>,
+                 blocks: &mut Vec<Block>| {
+        if let Some(block) = open[index].take() {
+            for qubit in block.qubits.iter() {
+                open_on[*qubit] = None;
+            }
+            blocks.push(block);
+        }
+    };
+
+    for (index, (qubits, collectable)) in instructions.into_iter().enumerate() {
+        match (collectable, qubits) {
+            (true, [qubit]) => match open_on[*qubit] {
+                Some(block) => open[block].as_mut().unwrap().instructions.push(index),
+                None => {
+                    open_on[*qubit] = Some(open.len());
+                    open.push(Some(Block {
+                        qubits: [*qubit].into_iter().collect(),
+                        instructions: vec![index],
+                    }));
+                }
+            },
+            (true, [a, b]) if a != b => {
+                if let (Some(block_a), Some(block_b)) = (open_on[*a], open_on[*b]) {
+                    if block_a == block_b {
+                        open[block_a].as_mut().unwrap().instructions.push(index);
+                        continue;
+                    }
+                }
+                // The pending one-qubit runs on `a` and `b` start the new block, and any two-qubit
+                // block on either of them is finished.
+                let mut instructions = Vec::new();
+                for qubit in [*a, *b] {
+                    let Some(block) = open_on[qubit] else {
+                        continue;
+                    };
+                    if open[block].as_ref().unwrap().is_two_qubit() {
+                        close(block, &mut open, &mut open_on, &mut blocks);
+                    } else {
+                        instructions.extend(open[block].take().unwrap().instructions);
+                        open_on[qubit] = None;
+                    }
+                }
+                instructions.sort_unstable();
+                instructions.push(index);
+                open_on[*a] = Some(open.len());
+                open_on[*b] = Some(open.len());
+                open.push(Some(Block {
+                    qubits: [*a.min(b), *a.max(b)].into_iter().collect(),
+                    instructions,
+                }));
+            }
+            _ => {
+                for qubit in qubits {
+                    if let Some(block) = open_on[*qubit] {
+                        close(block, &mut open, &mut open_on, &mut blocks);
+                    }
+                }
+            }
+        }
+    }
+    // The blocks still open are closed in the order of their last instruction, to keep the output
+    // in topological order.
+    let mut remaining = open.into_iter().flatten().collect::<Vec<_>>();
+    remaining.sort_unstable_by_key(|block| *block.instructions.last().unwrap());
+    blocks.extend(remaining);
+    blocks
+}
+
+/// Whether `mat` is the identity, with the tolerances of :func:`numpy.allclose`.
+#[cfg(feature = "python")]
+fn is_identity(mat: ArrayView2<Complex64>) -> bool {
+    mat.indexed_iter().all(|((i, j), x)| {
+        let expected = if i == j {
+            Complex64::new(1., 0.)
+        } else {
+            Complex64::new(0., 0.)
+        };
+        (expected - x).norm() <= ATOL_DEFAULT + RTOL_DEFAULT * x.norm()
+    })
+}
+
+/// What to put in the output circuit in place of an instruction.
+#[cfg(feature = "python")]
+enum Replacement {
+    /// Keep the instruction as it is.
+    Keep,
+    /// Drop the instruction, which is part of a block that is either removed or emitted later.
+    Drop,
+    /// Replace the instruction by the unitary of its block, on the given qubits.
+    Unitary(Array2<Complex64>, SmallVec<[BitType; 2]>),
+}
+
+/// Consolidate the blocks of one- and two-qubit gates of a circuit into unitary gates.
+///
+/// This collects the blocks of ``Collect2qBlocks`` (and, with ``consolidate_1q_runs``, the runs of
+/// ``Collect1qRuns``) and applies the rules of ``ConsolidateBlocks`` to each of them: a block is
+/// replaced by a single :class:`.UnitaryGate` when it would need fewer basis gates than it
+/// currently contains, when it contains a gate outside ``basis_gates``, or when it is forced to,
+/// and it is removed if it is the identity.
+///
+/// Args:
+///     circuit (CircuitData): the circuit to consolidate.
+///     decomposer (TwoQubitBasisDecomposer): the decomposer that estimates the number of basis
+///         gates needed by a block.
+///     basis_gate_name (str): the name of the decomposer's two-qubit basis gate.
+///     basis_gates (set[str] | None): the basis gates of the target, if any.
+///     force_consolidate (bool): consolidate every two-qubit block.
+///     consolidate_1q_runs (bool): also consolidate the runs of one-qubit gates that are in no
+///         two-qubit block.
+///
+/// Returns:
+///     CircuitData: the consolidated circuit, on the same bits.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(
+    name = "consolidate_blocks",
+    signature = (circuit, decomposer, basis_gate_name, basis_gates=None, force_consolidate=false, consolidate_1q_runs=false)
+)]
+pub fn py_consolidate_blocks(
+    py: Python,
+    circuit: &CircuitData,
+    decomposer: &TwoQubitBasisDecomposer,
+    basis_gate_name: &str,
+    basis_gates: Option<HashSet<String>>,
+    force_consolidate: bool,
+    consolidate_1q_runs: bool,
+) -> PyResult<CircuitData> {
+    let _timer = instrumentation::Timer::start("consolidate_blocks");
+    const MAX_2Q_DEPTH: usize = 20;
+
+    let basis_gates = basis_gates.filter(|basis| !basis.is_empty());
+    let unitary_gate = imports::UNITARY_GATE.get_bound(py)?;
+
+    // The name, qubits and matrix of each instruction; the matrix is only computed for the
+    // instructions that can be collected.
+    let num_instructions = circuit.iter_instructions().len();
+    let mut names = Vec::with_capacity(num_instructions);
+    let mut qargs = Vec::with_capacity(num_instructions);
+    let mut matrices = Vec::with_capacity(num_instructions);
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        let op = op.bind(py);
+        names.push(op.getattr(intern!(py, "name"))?.extract::<String>()?);
+        qargs.push(
+            qubits
+                .iter()
+                .map(|q| *q as usize)
+                .collect::<SmallVec<[usize; 2]>>(),
+        );
+        let matrix = if clbits.is_empty() && (1..=2).contains(&qubits.len()) {
+            match operation_matrix(op)? {
+                OperationMatrix::Matrix(mat) => Some(mat),
+                OperationMatrix::Skip | OperationMatrix::Unsupported => None,
+            }
+        } else {
+            None
+        };
+        matrices.push(matrix);
+    }
+    let not_in_basis = |name: &str| {
+        basis_gates
+            .as_ref()
+            .is_some_and(|basis| !basis.contains(name))
+    };
+
+    let blocks = collect_2q_blocks_inner(
+        circuit.num_qubits(),
+        qargs
+            .iter()
+            .zip(&matrices)
+            .map(|(qubits, mat)| (qubits.as_slice(), mat.is_some())),
+    );
+    let mut replacements = (0..num_instructions)
+        .map(|_| Replacement::Keep)
+        .collect::<Vec<_>>();
+    for block in blocks {
+        let first = block.instructions[0];
+        if block.is_two_qubit() {
+            if block.instructions.len() == 1 && not_in_basis(&names[first]) {
+                replacements[first] = Replacement::Unitary(
+                    matrices[first].take().unwrap(),
+                    qargs[first].iter().map(|q| *q as BitType).collect(),
+                );
+                continue;
+            }
+            let basis_count = block
+                .instructions
+                .iter()
+                .filter(|index| names[**index] == basis_gate_name)
+                .count();
+            let outside_basis = block
+                .instructions
+                .iter()
+                .any(|index| not_in_basis(&names[*index]));
+            let local = block
+                .instructions
+                .iter()
+                .map(|index| {
+                    qargs[*index]
+                        .iter()
+                        .map(|q| (*q != block.qubits[0]) as u8)
+                        .collect::<SmallVec<[u8; 2]>>()
+                })
+                .collect::<Vec<_>>();
+            let matrix = blocks_to_matrix_inner(block.instructions.iter().zip(&local).map(
+                |(index, q_list)| (matrices[*index].as_ref().unwrap().view(), q_list.as_slice()),
+            ));
+            if force_consolidate
+                || decomposer.num_basis_gates_inner(matrix.view()) < basis_count
+                || block.instructions.len() > MAX_2Q_DEPTH
+                || outside_basis
+            {
+                for index in block.instructions.iter() {
+                    replacements[*index] = Replacement::Drop;
+                }
+                if !is_identity(matrix.view()) {
+                    let last = *block.instructions.last().unwrap();
+                    replacements[last] = Replacement::Unitary(
+                        matrix,
+                        block.qubits.iter().map(|q| *q as BitType).collect(),
+                    );
+                }
+            }
+        } else if consolidate_1q_runs {
+            let qubit = block.qubits[0] as BitType;
+            if block.instructions.len() == 1 && !not_in_basis(&names[first]) {
+                replacements[first] = Replacement::Unitary(
+                    matrices[first].take().unwrap(),
+                    [qubit].into_iter().collect(),
+                );
+                continue;
+            }
+            let mut matrix = Array2::<Complex64>::eye(2);
+            for index in block.instructions.iter() {
+                matrix = matrices[*index].as_ref().unwrap().dot(&matrix);
+                replacements[*index] = Replacement::Drop;
+            }
+            if !is_identity(matrix.view()) {
+                let last = *block.instructions.last().unwrap();
+                replacements[last] = Replacement::Unitary(matrix, [qubit].into_iter().collect());
+            }
+        }
+    }
+
+    let mut out = circuit.copy_empty_like(py)?;
+    out.reserve(py, num_instructions);
+    for ((op, qubits, clbits), replacement) in circuit.iter_instructions().zip(replacements) {
+        match replacement {
+            Replacement::Keep => out.push_instruction(op.clone_ref(py), qubits, clbits)?,
+            Replacement::Drop => (),
+            Replacement::Unitary(matrix, qubits) => {
+                let kwargs = [(intern!(py, "check_input"), false)].into_py_dict_bound(py);
+                let gate = unitary_gate.call((matrix.into_pyarray_bound(py),), Some(&kwargs))?;
+                out.push_instruction(gate.unbind(), &qubits, &[])?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn consolidate_blocks(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_consolidate_blocks))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The qubits and instructions of the blocks collected from `instructions`.
+    fn collect(
+        num_qubits: usize,
+        instructions: &[(&[usize], bool)],
+    ) -> Vec<(Vec<usize>, Vec<usize>)> {
+        collect_2q_blocks_inner(num_qubits, instructions.iter().copied())
+            .into_iter()
+            .map(|block| (block.qubits.to_vec(), block.instructions))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_block() {
+        let blocks = collect(
+            2,
+            &[(&[0], true), (&[1, 0], true), (&[1], true), (&[0, 1], true)],
+        );
+        assert_eq!(blocks, [(vec![0, 1], vec![0, 1, 2, 3])]);
+    }
+
+    #[test]
+    fn test_one_qubit_runs_join_next_block() {
+        let blocks = collect(
+            3,
+            &[
+                (&[2], true),
+                (&[1], true),
+                (&[1], true),
+                (&[1, 2], true),
+                (&[0], true),
+            ],
+        );
+        assert_eq!(blocks, [(vec![1, 2], vec![0, 1, 2, 3]), (vec![0], vec![4])]);
+    }
+
+    #[test]
+    fn test_overlapping_two_qubit_gate_closes_block() {
+        let blocks = collect(
+            3,
+            &[
+                (&[0, 1], true),
+                (&[0], true),
+                (&[1, 2], true),
+                (&[0], true),
+                (&[1, 2], true),
+            ],
+        );
+        assert_eq!(
+            blocks,
+            [
+                (vec![0, 1], vec![0, 1]),
+                (vec![0], vec![3]),
+                (vec![1, 2], vec![2, 4])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uncollectable_instructions_split_blocks() {
+        let blocks = collect(
+            3,
+            &[
+                (&[0, 1], true),
+                (&[0], false),
+                (&[0, 1], true),
+                (&[0, 1, 2], true),
+                (&[2], true),
+            ],
+        );
+        assert_eq!(
+            blocks,
+            [
+                (vec![0, 1], vec![0]),
+                (vec![0, 1], vec![2]),
+                (vec![2], vec![4])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_instructions() {
+        assert!(collect(2, &[]).is_empty());
+    }
+}
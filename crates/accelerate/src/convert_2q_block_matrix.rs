@@ -10,72 +10,610 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
+use ndarray::linalg::general_mat_mul;
+use ndarray::{s, Array2, ArrayView2, Zip};
 use num_complex::Complex64;
-use numpy::ndarray::linalg::kron;
-use numpy::ndarray::{aview2, Array2, ArrayView2};
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+#[cfg(feature = "python")]
 use smallvec::SmallVec;
 
-static ONE_QUBIT_IDENTITY: [[Complex64; 2]; 2] = [
-    [Complex64::new(1., 0.), Complex64::new(0., 0.)],
-    [Complex64::new(0., 0.), Complex64::new(1., 0.)],
-];
+use crate::error::AccelerateError;
+use crate::operator::embed_into;
+#[cfg(feature = "python")]
+use crate::statevector::{operation_matrix, OperationMatrix};
+use crate::utils::permutation_swaps;
+
+const ZERO: Complex64 = Complex64::new(0., 0.);
+const ONE: Complex64 = Complex64::new(1., 0.);
+
+/// The matrix of a block of `num_qubits` qubits.  Only three matrices are allocated, however long
+/// the block is: the accumulated matrix, the product of the next operation with it, and the
+/// embedding of the operations that don't act on all the block qubits in order.
+fn accumulate<'a, I>(num_qubits: usize, op_list: I) -> Array2<Complex64>
+where
+    I: IntoIterator<Item = (ArrayView2<'a, Complex64>, &'a [u8])>,
+{
+    let dim = 1 << num_qubits;
+    let mut matrix = Array2::eye(dim);
+    let mut product = Array2::zeros((dim, dim));
+    let mut embedded = Array2::zeros((dim, dim));
+    for (op_matrix, q_list) in op_list {
+        if q_list.is_empty() {
+            continue;
+        }
+        let in_order =
+            q_list.len() == num_qubits && q_list.iter().enumerate().all(|(i, q)| *q as usize == i);
+        let op_matrix = if in_order {
+            op_matrix.view()
+        } else {
+            embed_into(op_matrix, q_list, &mut embedded);
+            embedded.view()
+        };
+        general_mat_mul(ONE, &op_matrix, &matrix, ZERO, &mut product);
+        std::mem::swap(&mut matrix, &mut product);
+    }
+    matrix
+}
+
+/// The two-qubit matrix of a block of operations, each given by its matrix and the indices (`0` or
+/// `1`) of the block qubits it acts on, in the order they are applied.
+pub fn blocks_to_matrix_inner<'a, I>(op_list: I) -> Array2<Complex64>
+where
+    I: IntoIterator<Item = (ArrayView2<'a, Complex64>, &'a [u8])>,
+{
+    accumulate(2, op_list)
+}
+
+/// The three-qubit matrix of a block of operations, each given by its matrix and the indices (`0`,
+/// `1` or `2`) of the block qubits it acts on, in the order they are applied.  The `i`th qubit of
+/// an operation is the block qubit `q_list[i]`, so the operations can act on the block qubits in
+/// any order.
+pub fn blocks_to_matrix_3q_inner<'a, I>(op_list: I) -> Array2<Complex64>
+where
+    I: IntoIterator<Item = (ArrayView2<'a, Complex64>, &'a [u8])>,
+{
+    accumulate(3, op_list)
+}
+
+/// An operation of a block passed from Python, with the indices of the block qubits it acts on.
+#[cfg(feature = "python")]
+#[derive(FromPyObject)]
+pub enum BlockOperation<'py> {
+    /// A standard gate given by its instruction name and parameters, whose matrix is built in
+    /// Rust.
+    Standard(String, SmallVec<[f64; 3]>, SmallVec<[u8; 3]>),
+    /// Any operation, given by its matrix.
+    Matrix(PyReadonlyArray2<'py, Complex64>, SmallVec<[u8; 3]>),
+}
+
+#[cfg(feature = "python")]
+impl BlockOperation<'_> {
+    fn q_list(&self) -> &[u8] {
+        match self {
+            Self::Standard(_, _, q_list) => q_list,
+            Self::Matrix(_, q_list) => q_list,
+        }
+    }
+}
+
+/// The matrix of the standard gate `name` with `params`, applied to the block qubits `q_list` of a
+/// block of `num_qubits` qubits.
+#[cfg(feature = "python")]
+fn standard_gate_matrix(
+    name: &str,
+    params: &[f64],
+    q_list: &[u8],
+    num_qubits: usize,
+) -> PyResult<Array2<Complex64>> {
+    let gate = StandardGate::from_name(name)
+        .ok_or_else(|| AccelerateError::Value(format!("unknown standard gate '{name}'")))?;
+    if gate.num_qubits() as usize != q_list.len() || q_list.is_empty() || q_list.len() > num_qubits
+    {
+        return Err(AccelerateError::Value(format!(
+            "the gate '{name}' can't act on the block qubits {q_list:?}"
+        ))
+        .into());
+    }
+    gate.matrix(params).ok_or_else(|| {
+        AccelerateError::Value(format!(
+            "the gate '{name}' takes {} parameters, not {}",
+            gate.num_params(),
+            params.len()
+        ))
+        .into()
+    })
+}
+
+/// The matrices of the operations of `op_list`, with the matrices of its standard gates built in
+/// `standard_matrices`.
+#[cfg(feature = "python")]
+fn block_matrices<'a>(
+    op_list: &'a [BlockOperation],
+    standard_matrices: &'a [Option<Array2<Complex64>>],
+) -> Vec<(ArrayView2<'a, Complex64>, &'a [u8])> {
+    op_list
+        .iter()
+        .zip(standard_matrices)
+        .map(|(op, standard_matrix)| match op {
+            BlockOperation::Standard(_, _, q_list) => {
+                (standard_matrix.as_ref().unwrap().view(), q_list.as_slice())
+            }
+            BlockOperation::Matrix(op_matrix, q_list) => (op_matrix.as_array(), q_list.as_slice()),
+        })
+        .collect()
+}
+
+/// Build the matrices of the standard gates of `op_list`, in a block of `num_qubits` qubits.
+#[cfg(feature = "python")]
+fn standard_gate_matrices(
+    op_list: &[BlockOperation],
+    num_qubits: usize,
+) -> PyResult<Vec<Option<Array2<Complex64>>>> {
+    op_list
+        .iter()
+        .map(|op| match op {
+            BlockOperation::Standard(name, params, q_list) => {
+                standard_gate_matrix(name, params, q_list, num_qubits).map(Some)
+            }
+            BlockOperation::Matrix(..) => Ok(None),
+        })
+        .collect()
+}
+
+/// Check that each operation of `op_list` acts on distinct qubits of a block of `num_qubits`
+/// qubits, with a matrix of the right size.
+#[cfg(feature = "python")]
+fn check_block(op_list: &[BlockOperation], num_qubits: usize) -> PyResult<()> {
+    for op in op_list.iter() {
+        let q_list = op.q_list();
+        if q_list.len() > num_qubits
+            || q_list.iter().any(|q| *q as usize >= num_qubits)
+            || q_list
+                .iter()
+                .enumerate()
+                .any(|(i, q)| q_list[..i].contains(q))
+        {
+            return Err(AccelerateError::Value(format!(
+                "an operation can't act on the block qubits {q_list:?}"
+            ))
+            .into());
+        }
+        if let BlockOperation::Matrix(op_matrix, _) = op {
+            let dim = 1 << q_list.len();
+            if op_matrix.shape() != [dim, dim] {
+                return Err(AccelerateError::Value(format!(
+                    "a matrix of shape {:?} can't act on the block qubits {q_list:?}",
+                    op_matrix.shape()
+                ))
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
 
 /// Return the matrix Operator resulting from a block of Instructions.
+///
+/// Each operation is either a tuple ``(matrix, qubits)``, or a tuple ``(name, params, qubits)``
+/// for a one- or two-qubit standard gate, whose matrix is then built without going through
+/// Python.  The matrices are multiplied with the GIL released.
+///
+/// Raises:
+///     ValueError: if an operation doesn't act on distinct block qubits, or its matrix doesn't
+///         match its number of qubits.
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(text_signature = "(op_list, /")]
 pub fn blocks_to_matrix(
     py: Python,
-    op_list: Vec<(PyReadonlyArray2<Complex64>, SmallVec<[u8; 2]>)>,
+    op_list: Vec<BlockOperation>,
 ) -> PyResult<Py<PyArray2<Complex64>>> {
-    let identity = aview2(&ONE_QUBIT_IDENTITY);
-    let input_matrix = op_list[0].0.as_array();
-    let mut matrix: Array2<Complex64> = match op_list[0].1.as_slice() {
-        [0] => kron(&identity, &input_matrix),
-        [1] => kron(&input_matrix, &identity),
-        [0, 1] => input_matrix.to_owned(),
-        [1, 0] => change_basis(input_matrix),
-        [] => Array2::eye(4),
-        _ => unreachable!(),
-    };
-    for (op_matrix, q_list) in op_list.into_iter().skip(1) {
-        let op_matrix = op_matrix.as_array();
-
-        let result = match q_list.as_slice() {
-            [0] => Some(kron(&identity, &op_matrix)),
-            [1] => Some(kron(&op_matrix, &identity)),
-            [1, 0] => Some(change_basis(op_matrix)),
-            [] => Some(Array2::eye(4)),
-            _ => None,
-        };
-        matrix = match result {
-            Some(result) => result.dot(&matrix),
-            None => op_matrix.dot(&matrix),
+    check_block(&op_list, 2)?;
+    let standard_matrices = standard_gate_matrices(&op_list, 2)?;
+    let ops = block_matrices(&op_list, &standard_matrices);
+    let matrix = py.allow_threads(move || blocks_to_matrix_inner(ops));
+    Ok(matrix.into_pyarray_bound(py).unbind())
+}
+
+/// Return the matrix of a block of Instructions on three qubits.
+///
+/// The operations are given as for :func:`blocks_to_matrix`, and may act on any of the block
+/// qubits ``0``, ``1`` and ``2`` in any order.
+///
+/// Raises:
+///     ValueError: if an operation doesn't act on distinct block qubits, or its matrix doesn't
+///         match its number of qubits.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(op_list, /")]
+pub fn blocks_to_matrix_3q(
+    py: Python,
+    op_list: Vec<BlockOperation>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    check_block(&op_list, 3)?;
+    let standard_matrices = standard_gate_matrices(&op_list, 3)?;
+    let ops = block_matrices(&op_list, &standard_matrices);
+    let matrix = py.allow_threads(move || blocks_to_matrix_3q_inner(ops));
+    Ok(matrix.into_pyarray_bound(py).unbind())
+}
+
+/// Return the matrix of a block of the instructions of a circuit, without creating the
+/// Python-space instructions.
+///
+/// Args:
+///     circuit (CircuitData): the circuit.
+///     instructions (list[int]): the indices of the instructions of the block, in circuit order.
+///     qubits (list[int]): the two or three qubits of the block, in the order of the block's
+///         matrix.
+///
+/// Returns:
+///     ndarray | None: the matrix of the block, or ``None`` if an instruction of the block has no
+///     matrix or acts on clbits.
+///
+/// Raises:
+///     IndexError: if an instruction index is out of range.
+///     ValueError: if an instruction acts on a qubit outside the block.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, instructions, qubits))]
+pub fn circuit_block_to_matrix(
+    py: Python,
+    circuit: &CircuitData,
+    instructions: Vec<usize>,
+    qubits: SmallVec<[BitType; 3]>,
+) -> PyResult<Option<Py<PyArray2<Complex64>>>> {
+    if !(2..=3).contains(&qubits.len()) {
+        return Err(AccelerateError::Value(format!(
+            "a block has two or three qubits, not {}",
+            qubits.len()
+        ))
+        .into());
+    }
+    let mut matrices = Vec::with_capacity(instructions.len());
+    let mut q_lists = Vec::with_capacity(instructions.len());
+    for index in instructions {
+        let (op, op_qubits, clbits) = circuit.get_instruction(index).ok_or_else(|| {
+            AccelerateError::Index(format!("instruction index {index} out of range"))
+        })?;
+        if !clbits.is_empty() {
+            return Ok(None);
+        }
+        let Some(q_list) = op_qubits
+            .iter()
+            .map(|q| qubits.iter().position(|bit| bit == q).map(|pos| pos as u8))
+            .collect::<Option<SmallVec<[u8; 3]>>>()
+        else {
+            return Err(AccelerateError::Value(format!(
+                "the instruction {index} acts on qubits outside the block"
+            ))
+            .into());
         };
+        match operation_matrix(op.bind(py))? {
+            OperationMatrix::Matrix(matrix) => {
+                matrices.push(matrix);
+                q_lists.push(q_list);
+            }
+            OperationMatrix::Skip => (),
+            OperationMatrix::Unsupported => return Ok(None),
+        }
     }
-    Ok(matrix.into_pyarray_bound(py).unbind())
+    let ops = matrices
+        .iter()
+        .zip(&q_lists)
+        .map(|(matrix, q_list)| (matrix.view(), q_list.as_slice()))
+        .collect::<Vec<_>>();
+    let num_qubits = qubits.len();
+    let matrix = py.allow_threads(move || accumulate(num_qubits, ops));
+    Ok(Some(matrix.into_pyarray_bound(py).unbind()))
+}
+
+/// The permutation of the basis states of `perm.len()` qubits that reorders the qubits by `perm`:
+/// the state at each index is the one at the index of the result there.
+fn basis_permutation(perm: &[usize]) -> Vec<usize> {
+    (0..1usize << perm.len())
+        .map(|index| {
+            perm.iter()
+                .enumerate()
+                .fold(0, |old, (new, qubit)| old | (((index >> new) & 1) << qubit))
+        })
+        .collect()
+}
+
+fn check_qubit_permutation(
+    matrix: &ArrayView2<Complex64>,
+    perm: &[usize],
+) -> Result<(), AccelerateError> {
+    let dim = 1usize << perm.len();
+    if matrix.shape() != [dim, dim] {
+        return Err(AccelerateError::Value(format!(
+            "a permutation of {} qubits can't reorder a matrix of shape {:?}",
+            perm.len(),
+            matrix.shape()
+        )));
+    }
+    let mut seen = vec![false; perm.len()];
+    for qubit in perm {
+        match seen.get_mut(*qubit) {
+            Some(seen) if !*seen => *seen = true,
+            _ => {
+                return Err(AccelerateError::Value(format!(
+                    "{:?} isn't a permutation",
+                    perm
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reorder the qubits of an `n`-qubit operator, so that its qubit `i` is the qubit `perm[i]` of
+/// `matrix`.
+pub fn permute_qubits(
+    matrix: ArrayView2<Complex64>,
+    perm: &[usize],
+) -> Result<Array2<Complex64>, AccelerateError> {
+    check_qubit_permutation(&matrix, perm)?;
+    let basis = basis_permutation(perm);
+    Ok(Array2::from_shape_fn(matrix.dim(), |(row, col)| {
+        matrix[[basis[row], basis[col]]]
+    }))
+}
+
+/// Reorder the qubits of an `n`-qubit operator in place, as [permute_qubits] does, by swapping
+/// its rows and then its columns along the cycles of the permutation of the basis states.
+pub fn permute_qubits_in_place(
+    matrix: &mut Array2<Complex64>,
+    perm: &[usize],
+) -> Result<(), AccelerateError> {
+    check_qubit_permutation(&matrix.view(), perm)?;
+    let basis = basis_permutation(perm);
+    permutation_swaps(&basis, |a, b| {
+        let (mut row_a, mut row_b) = matrix.multi_slice_mut((s![a, ..], s![b, ..]));
+        Zip::from(&mut row_a)
+            .and(&mut row_b)
+            .for_each(std::mem::swap);
+    });
+    permutation_swaps(&basis, |a, b| {
+        let (mut col_a, mut col_b) = matrix.multi_slice_mut((s![.., a], s![.., b]));
+        Zip::from(&mut col_a)
+            .and(&mut col_b)
+            .for_each(std::mem::swap);
+    });
+    Ok(())
 }
 
 /// Switches the order of qubits in a two qubit operation.
 #[inline]
 pub fn change_basis(matrix: ArrayView2<Complex64>) -> Array2<Complex64> {
-    let mut trans_matrix: Array2<Complex64> = matrix.reversed_axes().to_owned();
-    for index in 0..trans_matrix.ncols() {
-        trans_matrix.swap([1, index], [2, index]);
-    }
-    trans_matrix = trans_matrix.reversed_axes();
-    for index in 0..trans_matrix.ncols() {
-        trans_matrix.swap([1, index], [2, index]);
-    }
-    trans_matrix
+    permute_qubits(matrix, &[1, 0]).expect("a two-qubit operator has two qubits to swap")
+}
+
+/// Reorder the qubits of an operator.
+///
+/// Args:
+///     matrix (ndarray): the matrix of an ``n``-qubit operator.
+///     perm (list[int]): a permutation of ``range(n)``; qubit ``i`` of the output is qubit
+///         ``perm[i]`` of ``matrix``.
+///
+/// Returns:
+///     ndarray: the matrix of the operator with its qubits reordered.
+///
+/// Raises:
+///     ValueError: if ``perm`` isn't a permutation of the qubits of ``matrix``.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "permute_qubits", text_signature = "(matrix, perm, /)")]
+pub fn py_permute_qubits(
+    py: Python,
+    matrix: PyReadonlyArray2<Complex64>,
+    perm: Vec<usize>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let matrix = matrix.as_array();
+    let out = py.allow_threads(|| permute_qubits(matrix, &perm))?;
+    Ok(out.into_pyarray_bound(py).unbind())
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn convert_2q_block_matrix(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(blocks_to_matrix))?;
+    m.add_wrapped(wrap_pyfunction!(blocks_to_matrix_3q))?;
+    m.add_wrapped(wrap_pyfunction!(circuit_block_to_matrix))?;
+    m.add_wrapped(wrap_pyfunction!(py_permute_qubits))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::array;
+    use ndarray::linalg::kron;
+
+    fn c64(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    /// A matrix with no symmetry, so that any mix-up of its rows, columns or qubits shows.
+    fn asymmetric(dim: usize, offset: f64) -> Array2<Complex64> {
+        Array2::from_shape_fn((dim, dim), |(row, col)| {
+            c64(
+                offset + (row * dim + col) as f64,
+                (row as f64) - 2. * (col as f64),
+            )
+        })
+    }
+
+    /// The permutation matrix that sends each basis state `b` to `map(b)`.
+    fn basis_map(dim: usize, map: impl Fn(usize) -> usize) -> Array2<Complex64> {
+        let mut out = Array2::zeros((dim, dim));
+        for b in 0..dim {
+            out[[map(b), b]] = ONE;
+        }
+        out
+    }
+
+    #[test]
+    fn test_3q_permutation_gates() {
+        let x = array![[ZERO, ONE], [ONE, ZERO]];
+        let cx = basis_map(4, |b| if b & 1 == 1 { b ^ 2 } else { b });
+        let ccx = basis_map(8, |b| if b & 3 == 3 { b ^ 4 } else { b });
+        for q in 0..3u8 {
+            let matrix = blocks_to_matrix_3q_inner([(x.view(), [q].as_slice())]);
+            assert_abs_diff_eq!(matrix, basis_map(8, |b| b ^ (1 << q)), epsilon = 1e-12);
+        }
+        for control in 0..3u8 {
+            for target in (0..3u8).filter(|target| *target != control) {
+                let q_list = [control, target];
+                let matrix = blocks_to_matrix_3q_inner([(cx.view(), q_list.as_slice())]);
+                let expected = basis_map(8, |b| {
+                    if (b >> control) & 1 == 1 {
+                        b ^ (1 << target)
+                    } else {
+                        b
+                    }
+                });
+                assert_abs_diff_eq!(matrix, expected, epsilon = 1e-12);
+            }
+        }
+        for q_list in [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ] {
+            let matrix = blocks_to_matrix_3q_inner([(ccx.view(), q_list.as_slice())]);
+            let controls = (1 << q_list[0]) | (1 << q_list[1]);
+            let expected = basis_map(8, |b| {
+                if b & controls == controls {
+                    b ^ (1 << q_list[2])
+                } else {
+                    b
+                }
+            });
+            assert_abs_diff_eq!(matrix, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_3q_matches_kron() {
+        let eye2 = Array2::<Complex64>::eye(2);
+        let eye4 = Array2::<Complex64>::eye(4);
+        let a = asymmetric(2, 0.5);
+        let b = asymmetric(2, -1.5);
+        let c = asymmetric(4, 3.);
+        // Qubit 0 is the least significant, so it's the rightmost factor.
+        let matrix = blocks_to_matrix_3q_inner([
+            (a.view(), [0].as_slice()),
+            (b.view(), [2].as_slice()),
+            (c.view(), [1, 2].as_slice()),
+        ]);
+        let expected = kron(&c, &eye2).dot(&kron(&b, &eye4)).dot(&kron(&eye4, &a));
+        assert_abs_diff_eq!(matrix, expected, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_3q_in_order() {
+        let d = asymmetric(8, 1.);
+        let e = asymmetric(8, -2.);
+        let matrix = blocks_to_matrix_3q_inner([
+            (d.view(), [0, 1, 2].as_slice()),
+            (e.view(), [0, 1, 2].as_slice()),
+        ]);
+        assert_abs_diff_eq!(matrix, e.dot(&d), epsilon = 1e-12);
+        let empty: [(ArrayView2<Complex64>, &[u8]); 0] = [];
+        assert_abs_diff_eq!(
+            blocks_to_matrix_3q_inner(empty),
+            Array2::<Complex64>::eye(8),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_scratch_reused() {
+        // Embedded and in-order operations alternate, so each of the scratch matrices is reused.
+        let eye2 = Array2::<Complex64>::eye(2);
+        let a = asymmetric(2, 0.5);
+        let c = asymmetric(4, 3.);
+        let matrix = blocks_to_matrix_inner([
+            (a.view(), [0].as_slice()),
+            (c.view(), [0, 1].as_slice()),
+            (a.view(), [1].as_slice()),
+            (c.view(), [0, 1].as_slice()),
+            (c.view(), [1, 0].as_slice()),
+        ]);
+        let expected = change_basis(c.view())
+            .dot(&c)
+            .dot(&kron(&a, &eye2))
+            .dot(&c)
+            .dot(&kron(&eye2, &a));
+        assert_abs_diff_eq!(matrix, expected, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_2q_order() {
+        let a = asymmetric(2, 0.5);
+        let c = asymmetric(4, 3.);
+        let matrix =
+            blocks_to_matrix_inner([(c.view(), [1, 0].as_slice()), (a.view(), [1].as_slice())]);
+        let expected = kron(&a, &Array2::eye(2)).dot(&change_basis(c.view()));
+        assert_abs_diff_eq!(matrix, expected, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_permute_qubits() {
+        let a = asymmetric(2, 0.5);
+        let b = asymmetric(2, -1.5);
+        let c = asymmetric(2, 3.);
+        // Qubit `i` of the output is qubit `perm[i]` of the input, and qubit 0 is the rightmost
+        // factor.
+        let matrix = kron(&a, &kron(&b, &c));
+        let out = permute_qubits(matrix.view(), &[1, 2, 0]).unwrap();
+        assert_abs_diff_eq!(out, kron(&c, &kron(&a, &b)), epsilon = 1e-12);
+        assert_eq!(permute_qubits(matrix.view(), &[0, 1, 2]).unwrap(), matrix);
+        assert_eq!(change_basis(kron(&a, &b).view()), kron(&b, &a));
+    }
+
+    #[test]
+    fn test_permute_qubits_in_place() {
+        let matrix = asymmetric(16, 0.25);
+        for perm in [[0, 1, 2, 3], [3, 0, 1, 2], [1, 0, 3, 2], [2, 3, 1, 0]] {
+            let mut in_place = matrix.clone();
+            permute_qubits_in_place(&mut in_place, &perm).unwrap();
+            assert_eq!(in_place, permute_qubits(matrix.view(), &perm).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_permute_qubits_errors() {
+        let matrix = asymmetric(4, 0.);
+        for perm in [vec![0], vec![0, 0], vec![0, 2], vec![0, 1, 2]] {
+            assert!(matches!(
+                permute_qubits(matrix.view(), &perm),
+                Err(AccelerateError::Value(_))
+            ));
+            let mut in_place = matrix.clone();
+            assert!(matches!(
+                permute_qubits_in_place(&mut in_place, &perm),
+                Err(AccelerateError::Value(_))
+            ));
+            assert_eq!(in_place, matrix);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A Rust-space coupling map, shared by the layout and routing passes so that each doesn't need
+//! its own adjacency and distance matrices passed in from Python.
+//!
+//! As in the Python :class:`.CouplingMap`, the edges are directed, but distances and shortest paths
+//! are undirected.  The distance matrix is computed on first use, one breadth-first search per
+//! qubit in parallel on large maps, and then cached.
+
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+
+use ndarray::prelude::*;
+use rayon::prelude::*;
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, ToPyArray};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::error::{AccelerateError, ErrorContext};
+use crate::nlayout::PhysicalQubit;
+use crate::sabre::NeighborTable;
+use crate::threading::{self, ParallelKernel};
+
+/// The qubits of a device and the directed edges between them.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.coupling_map")
+)]
+#[derive(Clone, Debug)]
+pub struct CouplingMap {
+    edges: Vec<[PhysicalQubit; 2]>,
+    /// The targets of the edges out of each qubit.
+    neighbors: NeighborTable,
+    /// The qubits joined to each qubit by an edge in either direction.
+    undirected: Vec<SmallVec<[PhysicalQubit; 4]>>,
+    distance: OnceLock<Array2<f64>>,
+}
+
+impl CouplingMap {
+    /// A coupling map on at least `num_qubits` qubits.  Qubits that appear in `edges` are added if
+    /// they're beyond `num_qubits`, like the Python coupling map does.
+    pub fn new(num_qubits: usize, edges: Vec<[PhysicalQubit; 2]>) -> Self {
+        let num_qubits = edges
+            .iter()
+            .flatten()
+            .map(|qubit| qubit.index() + 1)
+            .max()
+            .unwrap_or(0)
+            .max(num_qubits);
+        let mut neighbors = vec![SmallVec::<[PhysicalQubit; 4]>::new(); num_qubits];
+        let mut undirected = vec![SmallVec::<[PhysicalQubit; 4]>::new(); num_qubits];
+        for [a, b] in edges.iter() {
+            if !neighbors[a.index()].contains(b) {
+                neighbors[a.index()].push(*b);
+            }
+            if a == b {
+                continue;
+            }
+            if !undirected[a.index()].contains(b) {
+                undirected[a.index()].push(*b);
+                undirected[b.index()].push(*a);
+            }
+        }
+        CouplingMap {
+            edges,
+            neighbors: NeighborTable::from_neighbors(neighbors),
+            undirected,
+            distance: OnceLock::new(),
+        }
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.undirected.len()
+    }
+
+    /// The edges of the map, in the order they were given.
+    pub fn edges(&self) -> &[[PhysicalQubit; 2]] {
+        &self.edges
+    }
+
+    /// The table of the targets of the edges out of each qubit, as Sabre uses it.
+    pub fn neighbor_table(&self) -> &NeighborTable {
+        &self.neighbors
+    }
+
+    /// The targets of the edges out of `qubit`.
+    pub fn neighbors(&self, qubit: PhysicalQubit) -> &[PhysicalQubit] {
+        &self.neighbors[qubit]
+    }
+
+    /// The qubits joined to `qubit` by an edge in either direction.
+    pub fn undirected_neighbors(&self, qubit: PhysicalQubit) -> &[PhysicalQubit] {
+        &self.undirected[qubit.index()]
+    }
+
+    /// A dense adjacency matrix of the map, with a one for each edge.
+    pub fn adjacency_matrix(&self) -> Array2<f64> {
+        let mut out = Array2::zeros((self.num_qubits(), self.num_qubits()));
+        for [a, b] in self.edges.iter() {
+            out[[a.index(), b.index()]] = 1.;
+        }
+        out
+    }
+
+    /// The undirected distances from `source` to every qubit, with infinity for the qubits it
+    /// can't reach.
+    fn distances_from(&self, source: usize) -> Array1<f64> {
+        let mut out = Array1::from_elem(self.num_qubits(), f64::INFINITY);
+        let mut queue = VecDeque::from([source]);
+        out[source] = 0.;
+        while let Some(qubit) = queue.pop_front() {
+            let next = out[qubit] + 1.;
+            for neighbor in self.undirected[qubit].iter() {
+                if out[neighbor.index()] == f64::INFINITY {
+                    out[neighbor.index()] = next;
+                    queue.push_back(neighbor.index());
+                }
+            }
+        }
+        out
+    }
+
+    /// The matrix of the undirected distances between the qubits, with infinity between qubits
+    /// in different components.
+    pub fn distance_matrix(&self) -> ArrayView2<f64> {
+        self.distance
+            .get_or_init(|| {
+                let num_qubits = self.num_qubits();
+                let mut out = Array2::zeros((num_qubits, num_qubits));
+                if threading::run_in_parallel(ParallelKernel::CouplingMap, num_qubits) {
+                    threading::install(|| {
+                        out.axis_iter_mut(Axis(0))
+                            .into_par_iter()
+                            .enumerate()
+                            .for_each(|(source, mut row)| row.assign(&self.distances_from(source)))
+                    });
+                } else {
+                    for (source, mut row) in out.axis_iter_mut(Axis(0)).enumerate() {
+                        row.assign(&self.distances_from(source));
+                    }
+                }
+                out
+            })
+            .view()
+    }
+
+    /// The undirected distance between two qubits, or `None` if there's no path between them.
+    pub fn distance(&self, a: PhysicalQubit, b: PhysicalQubit) -> Option<usize> {
+        let distance = self.distance_matrix()[[a.index(), b.index()]];
+        distance.is_finite().then_some(distance as usize)
+    }
+
+    /// A shortest undirected path from `source` to `target`, including both ends, or `None` if
+    /// they're not connected.
+    pub fn shortest_undirected_path(
+        &self,
+        source: PhysicalQubit,
+        target: PhysicalQubit,
+    ) -> Option<Vec<PhysicalQubit>> {
+        let mut predecessor: Vec<Option<PhysicalQubit>> = vec![None; self.num_qubits()];
+        let mut queue = VecDeque::from([source]);
+        predecessor[source.index()] = Some(source);
+        while let Some(qubit) = queue.pop_front() {
+            if qubit == target {
+                let mut path = vec![target];
+                let mut current = target;
+                while current != source {
+                    current = predecessor[current.index()].unwrap();
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for neighbor in self.undirected[qubit.index()].iter() {
+                if predecessor[neighbor.index()].is_none() {
+                    predecessor[neighbor.index()] = Some(qubit);
+                    queue.push_back(*neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether every qubit can reach every other by undirected edges.  A map with no qubits isn't
+    /// connected.
+    pub fn is_connected(&self) -> bool {
+        self.num_qubits() > 0
+            && self
+                .distances_from(0)
+                .iter()
+                .all(|distance| distance.is_finite())
+    }
+
+    /// The qubits of each connected component of the map, each in increasing order, with the
+    /// components in order of their lowest qubit.
+    pub fn connected_components(&self) -> Vec<Vec<PhysicalQubit>> {
+        let mut seen = vec![false; self.num_qubits()];
+        let mut out = Vec::new();
+        for start in 0..self.num_qubits() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![PhysicalQubit::new(start as u32)];
+            let mut stack = vec![start];
+            while let Some(qubit) = stack.pop() {
+                for neighbor in self.undirected[qubit].iter() {
+                    if !seen[neighbor.index()] {
+                        seen[neighbor.index()] = true;
+                        component.push(*neighbor);
+                        stack.push(neighbor.index());
+                    }
+                }
+            }
+            component.sort_unstable();
+            out.push(component);
+        }
+        out
+    }
+
+    /// The coupling map on `qubits` and the edges between them, where qubit `i` of the new map is
+    /// `qubits[i]`.
+    pub fn subgraph(&self, qubits: &[PhysicalQubit]) -> Result<CouplingMap, AccelerateError> {
+        let mut new_index = vec![None; self.num_qubits()];
+        for (index, qubit) in qubits.iter().enumerate() {
+            let Some(slot) = new_index.get_mut(qubit.index()) else {
+                return Err(AccelerateError::Index(format!(
+                    "qubit {} is not in the coupling map",
+                    qubit.index()
+                )));
+            };
+            if slot.replace(PhysicalQubit::new(index as u32)).is_some() {
+                return Err(AccelerateError::Value(format!(
+                    "qubit {} appears more than once",
+                    qubit.index()
+                )));
+            }
+        }
+        let edges = self
+            .edges
+            .iter()
+            .filter_map(|[a, b]| Some([new_index[a.index()]?, new_index[b.index()]?]))
+            .collect();
+        Ok(CouplingMap::new(qubits.len(), edges))
+    }
+
+    fn check_qubit(&self, qubit: PhysicalQubit) -> Result<(), AccelerateError> {
+        if qubit.index() < self.num_qubits() {
+            Ok(())
+        } else {
+            Err(AccelerateError::Qiskit(format!(
+                "{} not in coupling graph",
+                qubit.index()
+            )))
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl CouplingMap {
+    #[new]
+    #[pyo3(signature = (num_qubits=0, edges=vec![]))]
+    fn py_new(num_qubits: usize, edges: Vec<[PhysicalQubit; 2]>) -> Self {
+        CouplingMap::new(num_qubits, edges)
+    }
+
+    fn __getnewargs__(&self) -> (usize, Vec<[PhysicalQubit; 2]>) {
+        (self.num_qubits(), self.edges.clone())
+    }
+
+    /// The number of qubits of the map.
+    fn size(&self) -> usize {
+        self.num_qubits()
+    }
+
+    #[pyo3(name = "get_edges")]
+    fn py_get_edges(&self) -> Vec<(PhysicalQubit, PhysicalQubit)> {
+        self.edges.iter().map(|[a, b]| (*a, *b)).collect()
+    }
+
+    #[pyo3(name = "neighbors")]
+    fn py_neighbors(&self, qubit: PhysicalQubit) -> PyResult<Vec<PhysicalQubit>> {
+        self.check_qubit(qubit)?;
+        Ok(self.neighbors(qubit).to_vec())
+    }
+
+    /// A copy of the Sabre neighbor table of the map.
+    #[pyo3(name = "neighbor_table")]
+    fn py_neighbor_table(&self) -> NeighborTable {
+        self.neighbors.clone()
+    }
+
+    #[pyo3(name = "adjacency_matrix")]
+    fn py_adjacency_matrix<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.adjacency_matrix().into_pyarray_bound(py)
+    }
+
+    /// A copy of the distance matrix, which is computed once and cached.
+    #[pyo3(name = "distance_matrix")]
+    fn py_distance_matrix<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f64>> {
+        self.distance_matrix().to_pyarray_bound(py)
+    }
+
+    #[pyo3(name = "distance")]
+    fn py_distance(&self, a: PhysicalQubit, b: PhysicalQubit) -> PyResult<usize> {
+        self.check_qubit(a)?;
+        self.check_qubit(b)?;
+        self.distance(a, b).ok_or_else(|| {
+            AccelerateError::Routing(
+                format!("No path from {} to {}", a.index(), b.index()),
+                ErrorContext::default().with_qubits([a.index(), b.index()]),
+            )
+            .into()
+        })
+    }
+
+    #[pyo3(name = "shortest_undirected_path")]
+    fn py_shortest_undirected_path(
+        &self,
+        a: PhysicalQubit,
+        b: PhysicalQubit,
+    ) -> PyResult<Vec<PhysicalQubit>> {
+        self.check_qubit(a)?;
+        self.check_qubit(b)?;
+        self.shortest_undirected_path(a, b).ok_or_else(|| {
+            AccelerateError::Routing(
+                format!("Nodes {} and {} are not connected", a.index(), b.index()),
+                ErrorContext::default().with_qubits([a.index(), b.index()]),
+            )
+            .into()
+        })
+    }
+
+    #[pyo3(name = "is_connected")]
+    fn py_is_connected(&self) -> bool {
+        self.is_connected()
+    }
+
+    #[pyo3(name = "connected_components")]
+    fn py_connected_components(&self) -> Vec<Vec<PhysicalQubit>> {
+        self.connected_components()
+    }
+
+    #[pyo3(name = "subgraph")]
+    fn py_subgraph(&self, qubits: Vec<PhysicalQubit>) -> PyResult<CouplingMap> {
+        Ok(self.subgraph(&qubits)?)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn coupling_map(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<CouplingMap>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qubits(indices: &[u32]) -> Vec<PhysicalQubit> {
+        indices.iter().copied().map(PhysicalQubit::new).collect()
+    }
+
+    fn coupling_map(num_qubits: usize, edges: &[[u32; 2]]) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits,
+            edges
+                .iter()
+                .map(|[a, b]| [PhysicalQubit::new(*a), PhysicalQubit::new(*b)])
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_qubits_from_edges() {
+        let cmap = coupling_map(2, &[[0, 1], [3, 1]]);
+        assert_eq!(cmap.num_qubits(), 4);
+        assert_eq!(cmap.neighbors(PhysicalQubit::new(3)), qubits(&[1]));
+        assert!(cmap.neighbors(PhysicalQubit::new(1)).is_empty());
+        assert_eq!(
+            cmap.undirected_neighbors(PhysicalQubit::new(1)),
+            qubits(&[0, 3])
+        );
+        assert_eq!(coupling_map(5, &[]).num_qubits(), 5);
+    }
+
+    #[test]
+    fn test_distance_matrix() {
+        // 0 -> 1 <- 2 -> 3, and 4 <-> 5 on their own.
+        let cmap = coupling_map(6, &[[0, 1], [2, 1], [2, 3], [4, 5], [5, 4]]);
+        let inf = f64::INFINITY;
+        let expected = array![
+            [0., 1., 2., 3., inf, inf],
+            [1., 0., 1., 2., inf, inf],
+            [2., 1., 0., 1., inf, inf],
+            [3., 2., 1., 0., inf, inf],
+            [inf, inf, inf, inf, 0., 1.],
+            [inf, inf, inf, inf, 1., 0.],
+        ];
+        assert_eq!(cmap.distance_matrix(), expected);
+        assert_eq!(
+            cmap.distance(PhysicalQubit::new(3), PhysicalQubit::new(0)),
+            Some(3)
+        );
+        assert_eq!(
+            cmap.distance(PhysicalQubit::new(0), PhysicalQubit::new(4)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_distance_matrix_is_cached() {
+        let cmap = coupling_map(3, &[[0, 1], [1, 2]]);
+        let first = cmap.distance_matrix().as_ptr();
+        assert_eq!(cmap.distance_matrix().as_ptr(), first);
+    }
+
+    #[test]
+    fn test_shortest_undirected_path() {
+        let cmap = coupling_map(6, &[[0, 1], [2, 1], [2, 3], [0, 4], [4, 3], [5, 5]]);
+        let path =
+            |a, b| cmap.shortest_undirected_path(PhysicalQubit::new(a), PhysicalQubit::new(b));
+        assert_eq!(path(0, 3), Some(qubits(&[0, 4, 3])));
+        assert_eq!(path(1, 1), Some(qubits(&[1])));
+        assert_eq!(path(2, 0), Some(qubits(&[2, 1, 0])));
+        assert_eq!(path(0, 5), None);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let cmap = coupling_map(7, &[[3, 1], [1, 0], [4, 6], [5, 5]]);
+        assert!(!cmap.is_connected());
+        assert_eq!(
+            cmap.connected_components(),
+            vec![
+                qubits(&[0, 1, 3]),
+                qubits(&[2]),
+                qubits(&[4, 6]),
+                qubits(&[5])
+            ]
+        );
+        assert!(coupling_map(3, &[[2, 0], [1, 2]]).is_connected());
+        assert!(!coupling_map(0, &[]).is_connected());
+    }
+
+    #[test]
+    fn test_subgraph() {
+        let cmap = coupling_map(5, &[[0, 1], [1, 2], [2, 3], [3, 4]]);
+        let subgraph = cmap.subgraph(&qubits(&[3, 1, 2])).unwrap();
+        assert_eq!(subgraph.num_qubits(), 3);
+        assert_eq!(
+            subgraph.edges(),
+            [
+                [PhysicalQubit::new(1), PhysicalQubit::new(2)],
+                [PhysicalQubit::new(2), PhysicalQubit::new(0)]
+            ]
+        );
+        assert!(matches!(
+            cmap.subgraph(&qubits(&[1, 1])),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            cmap.subgraph(&qubits(&[5])),
+            Err(AccelerateError::Index(_))
+        ));
+    }
+}
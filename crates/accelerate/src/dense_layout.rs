@@ -15,15 +15,22 @@ use ahash::RandomState;
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
 use ndarray::prelude::*;
-use numpy::IntoPyArray;
-use numpy::PyReadonlyArray2;
+use ndarray::Zip;
 use rayon::prelude::*;
 
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyReadonlyArray2};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
-use crate::getenv_use_multiple_threads;
+#[cfg(feature = "python")]
+use crate::coupling_map::CouplingMap;
+use crate::instrumentation;
+use crate::threading;
 
 struct SubsetResult {
     pub count: usize,
@@ -81,8 +88,7 @@ fn bfs_sort(adj_matrix: ArrayView2<f64>, start: usize, num_qubits: usize) -> Vec
 /// Args:
 ///
 ///     num_qubits (int): The number of circuit qubits
-///     coupling_adjacency (numpy.ndarray): An adjacency matrix for the
-///         coupling graph.
+///     coupling_map (CouplingMap): The coupling graph.
 ///     num_meas (int): The number of measurement operations in the circuit
 ///     num_cx (int): The number of CXGates that are in the circuit
 ///     use_error (bool): Set to True to use the error
@@ -92,33 +98,48 @@ fn bfs_sort(adj_matrix: ArrayView2<f64>, start: usize, num_qubits: usize) -> Vec
 ///         The diagonal (i.e. ``error_matrix[i][i]``) is the measurement error rate
 ///         for each qubit (``i``) and the positions where the indices differ are the
 ///         2q/cx error rate for the corresponding qubit pair.
+///     variance_matrix (numpy.ndarray | None): the variances of the error rates of
+///         ``error_matrix``, in the same layout.
+///     uncertainty_weight (float): the number of standard deviations of ``variance_matrix`` to
+///         raise each error rate by, so that noisily measured rates are avoided.
 ///
 /// Returns:
 ///     (rows, cols, best_map): A tuple of the rows, columns and the best
 ///     mapping found by the function. This can be used to efficiently create
 ///     a sparse matrix that maps the layout of virtual qubits
 ///     (0 to ``num_qubits``) to the physical qubits on the coupling graph.
+#[cfg(feature = "python")]
 #[pyfunction]
+#[pyo3(signature = (num_qubits, coupling_map, num_meas, num_cx, use_error, symmetric_coupling_map, error_matrix, variance_matrix=None, uncertainty_weight=0.))]
 pub fn best_subset(
     py: Python,
     num_qubits: usize,
-    coupling_adjacency: PyReadonlyArray2<f64>,
+    coupling_map: &CouplingMap,
     num_meas: usize,
     num_cx: usize,
     use_error: bool,
     symmetric_coupling_map: bool,
     error_matrix: PyReadonlyArray2<f64>,
+    variance_matrix: Option<PyReadonlyArray2<f64>>,
+    uncertainty_weight: f64,
 ) -> (PyObject, PyObject, PyObject) {
-    let coupling_adj_mat = coupling_adjacency.as_array();
-    let err = error_matrix.as_array();
+    let coupling_adj_mat = coupling_map.adjacency_matrix();
+    let err = match variance_matrix {
+        Some(variances) if uncertainty_weight != 0. => penalize_errors(
+            error_matrix.as_array(),
+            variances.as_array(),
+            uncertainty_weight,
+        ),
+        _ => error_matrix.as_array().to_owned(),
+    };
     let [rows, cols, best_map] = best_subset_inner(
         num_qubits,
-        coupling_adj_mat,
+        coupling_adj_mat.view(),
         num_meas,
         num_cx,
         use_error,
         symmetric_coupling_map,
-        err,
+        err.view(),
     );
     (
         rows.into_pyarray_bound(py).into(),
@@ -127,6 +148,18 @@ pub fn best_subset(
     )
 }
 
+/// The error rates `errors` raised by `uncertainty_weight` standard deviations of their
+/// `variances`, and capped at one.
+pub fn penalize_errors(
+    errors: ArrayView2<f64>,
+    variances: ArrayView2<f64>,
+    uncertainty_weight: f64,
+) -> Array2<f64> {
+    Zip::from(errors)
+        .and(variances)
+        .map_collect(|error, variance| (error + uncertainty_weight * variance.sqrt()).min(1.))
+}
+
 pub fn best_subset_inner(
     num_qubits: usize,
     coupling_adj_mat: ArrayView2<f64>,
@@ -136,6 +169,7 @@ pub fn best_subset_inner(
     symmetric_coupling_map: bool,
     err: ArrayView2<f64>,
 ) -> [Vec<usize>; 3] {
+    let _timer = instrumentation::Timer::start("dense_layout");
     let coupling_shape = coupling_adj_mat.shape();
     let avg_meas_err = err.diag().mean().unwrap();
 
@@ -216,11 +250,13 @@ pub fn best_subset_inner(
         }
     };
 
-    let best_result = if getenv_use_multiple_threads() {
-        (0..coupling_shape[0])
-            .into_par_iter()
-            .map(map_fn)
-            .reduce(reduce_identity_fn, reduce_fn)
+    let best_result = if threading::use_multiple_threads() {
+        threading::install(|| {
+            (0..coupling_shape[0])
+                .into_par_iter()
+                .map(map_fn)
+                .reduce(reduce_identity_fn, reduce_fn)
+        })
     } else {
         (0..coupling_shape[0])
             .map(map_fn)
@@ -244,6 +280,7 @@ pub fn best_subset_inner(
     [rows, cols, best_map]
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn dense_layout(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(best_subset))?;
@@ -0,0 +1,509 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Density-matrix evolution kernels.  A `k`-qubit operator `A` is applied as `A ρ B†` in two
+//! passes over the matrix: `conj(B)` acts on the column index of every row, which are contiguous,
+//! and `A` mixes whole rows, which we do over blocks of [BLOCK_SIZE] columns at a time so the rows
+//! being combined stay in cache for large dimensions.  Superoperators act on the column-stacked
+//! vectorisation of `ρ`, which is the statevector kernel applied to `ρ` as a `2n`-qubit state.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::statevector::{apply_matrix_inner, gate_offsets, insert_zero_bits};
+#[cfg(feature = "python")]
+use crate::statevector::{operation_matrix, OperationMatrix};
+use crate::threading::{self, ParallelKernel};
+
+/// The number of columns of each block when mixing rows.
+const BLOCK_SIZE: usize = 64;
+
+#[inline]
+fn run_in_parallel(dim: usize) -> bool {
+    threading::run_in_parallel(ParallelKernel::DensityMatrix, dim)
+}
+
+/// Check that `rho` is a qubit density matrix and that `qubits` are distinct qubits of it, returning
+/// the number of qubits of `rho`.
+fn check_qubits(rho: &Array2<Complex64>, qubits: &[usize]) -> Result<usize, AccelerateError> {
+    if rho.nrows() != rho.ncols() || !rho.nrows().is_power_of_two() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} is not a qubit density matrix",
+            rho.shape()
+        )));
+    }
+    let num_qubits = rho.nrows().trailing_zeros() as usize;
+    for (i, &qubit) in qubits.iter().enumerate() {
+        if qubit >= num_qubits {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {num_qubits}-qubit density matrix"
+            )));
+        }
+        if qubits[..i].contains(&qubit) {
+            return Err(AccelerateError::Qiskit(format!(
+                "duplicate qubit {qubit} in the arguments"
+            )));
+        }
+    }
+    Ok(num_qubits)
+}
+
+fn check_operator(
+    mat: &ArrayView2<Complex64>,
+    dim: usize,
+    num_qubits: usize,
+) -> Result<(), AccelerateError> {
+    if mat.shape() != [dim, dim] {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} cannot act on {num_qubits} qubits",
+            mat.shape(),
+        )));
+    }
+    Ok(())
+}
+
+/// Replace the rows of `block` by their combinations under `mat` acting on `qubits`.  `block` is a
+/// set of whole columns of the density matrix.
+fn mix_rows(
+    mut block: ArrayViewMut2<Complex64>,
+    mat: ArrayView2<Complex64>,
+    sorted: &[usize],
+    offsets: &[usize],
+) {
+    let mut scratch = Array2::<Complex64>::zeros((offsets.len(), block.ncols()));
+    for group in 0..(block.nrows() >> sorted.len()) {
+        let base = insert_zero_bits(group, sorted);
+        for (mut row, offset) in scratch.rows_mut().into_iter().zip(offsets) {
+            row.assign(&block.row(base + offset));
+        }
+        for (mat_row, offset) in mat.rows().into_iter().zip(offsets) {
+            let mut out = block.row_mut(base + offset);
+            out.fill(Complex64::new(0., 0.));
+            for (m, row) in mat_row.iter().zip(scratch.rows()) {
+                out.scaled_add(*m, &row);
+            }
+        }
+    }
+}
+
+/// Apply `left ρ right†`, where both operators act on `qubits` and are little-endian with respect
+/// to them.  The inputs must already have been validated.
+fn apply_sandwich(
+    rho: &mut Array2<Complex64>,
+    left: ArrayView2<Complex64>,
+    right: ArrayView2<Complex64>,
+    qubits: &[usize],
+) -> Result<(), AccelerateError> {
+    let parallel = run_in_parallel(rho.nrows());
+    let right_conj = right.mapv(|x| x.conj());
+    let apply_columns = |mut row: ArrayViewMut1<Complex64>| {
+        apply_matrix_inner(
+            row.as_slice_mut()
+                .expect("rows of a standard-layout matrix are contiguous"),
+            right_conj.view(),
+            qubits,
+        )
+    };
+    if parallel {
+        threading::install(|| {
+            rho.axis_iter_mut(Axis(0))
+                .into_par_iter()
+                .try_for_each(apply_columns)
+        })?;
+    } else {
+        rho.axis_iter_mut(Axis(0)).try_for_each(apply_columns)?;
+    }
+    let mut sorted = qubits.to_vec();
+    sorted.sort_unstable();
+    let offsets = gate_offsets(qubits);
+    if parallel {
+        threading::install(|| {
+            rho.axis_chunks_iter_mut(Axis(1), BLOCK_SIZE)
+                .into_par_iter()
+                .for_each(|block| mix_rows(block, left, &sorted, &offsets))
+        });
+    } else {
+        rho.axis_chunks_iter_mut(Axis(1), BLOCK_SIZE)
+            .for_each(|block| mix_rows(block, left, &sorted, &offsets));
+    }
+    Ok(())
+}
+
+/// Evolve the density matrix `rho` in place by the operator `mat` acting on `qubits`, so
+/// `ρ → A ρ A†`.
+///
+/// The matrix is in Qiskit's little-endian convention with respect to `qubits`, as in
+/// [apply_matrix_inner]; it need not be unitary.
+pub fn evolve_operator_inner(
+    rho: &mut Array2<Complex64>,
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+) -> Result<(), AccelerateError> {
+    check_qubits(rho, qubits)?;
+    check_operator(&mat, 1 << qubits.len(), qubits.len())?;
+    if !rho.is_standard_layout() {
+        *rho = rho.as_standard_layout().into_owned();
+    }
+    apply_sandwich(rho, mat, mat, qubits)
+}
+
+/// Evolve the density matrix `rho` by the channel with Kraus operators `kraus_left` acting on
+/// `qubits`, so `ρ → Σ_i K_i ρ K_i†`.
+///
+/// If `kraus_right` is given, this is instead the generalised (not completely positive) map
+/// `ρ → Σ_i K_i ρ L_i†`, where `L_i` are the right operators, as in `qiskit.quantum_info.Kraus`.
+pub fn evolve_kraus_inner<'a>(
+    rho: ArrayView2<Complex64>,
+    kraus_left: &[ArrayView2<'a, Complex64>],
+    kraus_right: Option<&[ArrayView2<'a, Complex64>]>,
+    qubits: &[usize],
+) -> Result<Array2<Complex64>, AccelerateError> {
+    let rho = rho.as_standard_layout().into_owned();
+    check_qubits(&rho, qubits)?;
+    let kraus_right = kraus_right.unwrap_or(kraus_left);
+    if kraus_left.len() != kraus_right.len() {
+        return Err(AccelerateError::Qiskit(format!(
+            "there are {} left Kraus operators but {} right ones",
+            kraus_left.len(),
+            kraus_right.len()
+        )));
+    }
+    for mat in kraus_left.iter().chain(kraus_right) {
+        check_operator(mat, 1 << qubits.len(), qubits.len())?;
+    }
+    let mut out = Array2::<Complex64>::zeros(rho.raw_dim());
+    for (left, right) in kraus_left.iter().zip(kraus_right) {
+        let mut term = rho.clone();
+        apply_sandwich(&mut term, left.view(), right.view(), qubits)?;
+        out += &term;
+    }
+    Ok(out)
+}
+
+/// Evolve the density matrix `rho` in place by the superoperator `superop` acting on `qubits`.
+///
+/// The superoperator is in Qiskit's `SuperOp` convention: it acts on the column-stacked
+/// vectorisation of the reduced density matrix, so for a unitary `U` it is `conj(U) ⊗ U`.
+pub fn evolve_superop_inner(
+    rho: &mut Array2<Complex64>,
+    superop: ArrayView2<Complex64>,
+    qubits: &[usize],
+) -> Result<(), AccelerateError> {
+    let num_qubits = check_qubits(rho, qubits)?;
+    check_operator(&superop, 1 << (2 * qubits.len()), 2 * qubits.len())?;
+    if !rho.is_standard_layout() {
+        *rho = rho.as_standard_layout().into_owned();
+    }
+    // In the C-ordered buffer the row index is the high `n` bits; the column-stacking puts the row
+    // index of the reduced matrix in the low half of the superoperator's index.
+    let vec_qubits = qubits
+        .iter()
+        .map(|q| q + num_qubits)
+        .chain(qubits.iter().copied())
+        .collect::<Vec<_>>();
+    apply_matrix_inner(
+        rho.as_slice_mut()
+            .expect("the density matrix was made contiguous above"),
+        superop,
+        &vec_qubits,
+    )
+}
+
+/// Evolve a density matrix by an operator acting on some of its qubits.
+///
+/// Args:
+///     rho (ndarray): the density matrix.
+///     mat (ndarray): the matrix of the operator, little-endian with respect to ``qubits``.
+///     qubits (list[int]): the qubits the operator acts on.
+///
+/// Returns:
+///     ndarray: the evolved density matrix ``mat @ rho @ mat.conj().T`` (with ``mat`` embedded).
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn evolve_operator(
+    py: Python,
+    rho: PyReadonlyArray2<Complex64>,
+    mat: PyReadonlyArray2<Complex64>,
+    qubits: Vec<usize>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let mut rho = rho.as_array().to_owned();
+    evolve_operator_inner(&mut rho, mat.as_array(), &qubits)?;
+    Ok(rho.into_pyarray_bound(py).unbind())
+}
+
+/// Evolve a density matrix by a channel given by its Kraus operators.
+///
+/// Args:
+///     rho (ndarray): the density matrix.
+///     kraus_left (list[ndarray]): the (left) Kraus operators, little-endian with respect to
+///         ``qubits``.
+///     qubits (list[int]): the qubits the channel acts on.
+///     kraus_right (list[ndarray] | None): the right Kraus operators of a non-CP map, if any.
+///
+/// Returns:
+///     ndarray: the evolved density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (rho, kraus_left, qubits, kraus_right=None))]
+pub fn evolve_kraus(
+    py: Python,
+    rho: PyReadonlyArray2<Complex64>,
+    kraus_left: Vec<PyReadonlyArray2<Complex64>>,
+    qubits: Vec<usize>,
+    kraus_right: Option<Vec<PyReadonlyArray2<Complex64>>>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let kraus_left = kraus_left.iter().map(|k| k.as_array()).collect::<Vec<_>>();
+    let kraus_right = kraus_right
+        .as_ref()
+        .map(|right| right.iter().map(|k| k.as_array()).collect::<Vec<_>>());
+    let out = evolve_kraus_inner(rho.as_array(), &kraus_left, kraus_right.as_deref(), &qubits)?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Evolve a density matrix by a superoperator acting on some of its qubits.
+///
+/// Args:
+///     rho (ndarray): the density matrix.
+///     superop (ndarray): the column-stacking superoperator matrix, as in ``SuperOp.data``.
+///     qubits (list[int]): the qubits the superoperator acts on.
+///
+/// Returns:
+///     ndarray: the evolved density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn evolve_superop(
+    py: Python,
+    rho: PyReadonlyArray2<Complex64>,
+    superop: PyReadonlyArray2<Complex64>,
+    qubits: Vec<usize>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let mut rho = rho.as_array().to_owned();
+    evolve_superop_inner(&mut rho, superop.as_array(), &qubits)?;
+    Ok(rho.into_pyarray_bound(py).unbind())
+}
+
+/// Simulate a circuit on the all-zeros density matrix.
+///
+/// Standard gates are applied from their Rust-space matrices; any other gate is applied through its
+/// ``to_matrix`` method, and barriers and delays are skipped.  The global phase has no effect on a
+/// density matrix.
+///
+/// Args:
+///     circuit (CircuitData): the instructions to simulate.
+///
+/// Returns:
+///     numpy.ndarray | None: the final density matrix, or ``None`` if the circuit contains an
+///     instruction that cannot be simulated by its matrix (for example a reset or a measurement),
+///     in which case the caller should fall back to the general Python-space simulation.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn from_circuit_data<'py>(
+    py: Python<'py>,
+    circuit: &CircuitData,
+) -> PyResult<Option<Bound<'py, PyArray2<Complex64>>>> {
+    let num_qubits = circuit.num_qubits();
+    if 2 * num_qubits >= usize::BITS as usize {
+        return Err(AccelerateError::Overflow(format!(
+            "a {num_qubits}-qubit density matrix is too large to represent"
+        ))
+        .into());
+    }
+    let dim = 1usize << num_qubits;
+    let mut rho = Array2::<Complex64>::zeros((dim, dim));
+    rho[[0, 0]] = Complex64::new(1., 0.);
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        if !clbits.is_empty() {
+            return Ok(None);
+        }
+        let mat = match operation_matrix(op.bind(py))? {
+            OperationMatrix::Skip => continue,
+            OperationMatrix::Matrix(mat) => mat,
+            OperationMatrix::Unsupported => return Ok(None),
+        };
+        let qubits = qubits.iter().map(|q| *q as usize).collect::<Vec<_>>();
+        evolve_operator_inner(&mut rho, mat.view(), &qubits)?;
+    }
+    Ok(Some(rho.into_pyarray_bound(py)))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn density_matrix(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(evolve_operator))?;
+    m.add_wrapped(wrap_pyfunction!(evolve_kraus))?;
+    m.add_wrapped(wrap_pyfunction!(evolve_superop))?;
+    m.add_wrapped(wrap_pyfunction!(from_circuit_data))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use ndarray::linalg::kron;
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    fn random_matrix(dim: usize, seed: u128) -> Array2<Complex64> {
+        let mut rng = generator(seed);
+        Array2::from_shape_simple_fn((dim, dim), || {
+            Complex64::new(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5)
+        })
+    }
+
+    fn views(mats: &[Array2<Complex64>]) -> Vec<ArrayView2<Complex64>> {
+        mats.iter().map(|mat| mat.view()).collect()
+    }
+
+    fn adjoint(mat: &Array2<Complex64>) -> Array2<Complex64> {
+        mat.t().mapv(|x| x.conj())
+    }
+
+    /// The matrix of `mat` acting on `qubits` of `num_qubits` qubits.
+    fn embedded(mat: &Array2<Complex64>, qubits: &[usize], num_qubits: usize) -> Array2<Complex64> {
+        let mask = qubits.iter().fold(0, |acc, q| acc | (1 << q));
+        let local = |index: usize| {
+            qubits
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (bit, q)| acc | (((index >> q) & 1) << bit))
+        };
+        Array2::from_shape_fn((1 << num_qubits, 1 << num_qubits), |(i, j)| {
+            if i & !mask == j & !mask {
+                mat[[local(i), local(j)]]
+            } else {
+                Complex64::new(0., 0.)
+            }
+        })
+    }
+
+    #[test]
+    fn test_evolve_operator() {
+        // Seven qubits, so the rows are mixed in more than one block of columns.
+        for qubits in [vec![1], vec![6, 0], vec![0, 5, 3]] {
+            let rho = random_matrix(128, 1);
+            let mat = random_matrix(1 << qubits.len(), 2);
+            let full = embedded(&mat, &qubits, 7);
+            let expected = full.dot(&rho).dot(&adjoint(&full));
+            let mut out = rho.clone();
+            evolve_operator_inner(&mut out, mat.view(), &qubits).unwrap();
+            assert_abs_diff_eq!(out, expected, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_evolve_operator_non_contiguous() {
+        let rho = random_matrix(8, 3);
+        let mat = random_matrix(4, 4);
+        let full = embedded(&mat, &[2, 1], 3);
+        let mut out = Array2::zeros((8, 8).f());
+        out.assign(&rho);
+        assert!(!out.is_standard_layout());
+        evolve_operator_inner(&mut out, mat.view(), &[2, 1]).unwrap();
+        assert_abs_diff_eq!(out, full.dot(&rho).dot(&adjoint(&full)), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_evolve_kraus() {
+        let rho = random_matrix(8, 5);
+        let left = [random_matrix(4, 6), random_matrix(4, 7)];
+        let right = [random_matrix(4, 8), random_matrix(4, 9)];
+        let qubits = [0, 2];
+        let expected = left.iter().fold(Array2::zeros((8, 8)), |acc, k| {
+            let full = embedded(k, &qubits, 3);
+            acc + full.dot(&rho).dot(&adjoint(&full))
+        });
+        let out = evolve_kraus_inner(rho.view(), &views(&left), None, &qubits).unwrap();
+        assert_abs_diff_eq!(out, expected, epsilon = 1e-12);
+
+        let expected = left
+            .iter()
+            .zip(&right)
+            .fold(Array2::zeros((8, 8)), |acc, (k, l)| {
+                let (k, l) = (embedded(k, &qubits, 3), embedded(l, &qubits, 3));
+                acc + k.dot(&rho).dot(&adjoint(&l))
+            });
+        let out = evolve_kraus_inner(rho.view(), &views(&left), Some(&views(&right)[..]), &qubits)
+            .unwrap();
+        assert_abs_diff_eq!(out, expected, epsilon = 1e-12);
+        assert!(matches!(
+            evolve_kraus_inner(
+                rho.view(),
+                &views(&left),
+                Some(&views(&right[..1])[..]),
+                &qubits
+            ),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_evolve_superop() {
+        let rho = random_matrix(8, 10);
+        let kraus = [random_matrix(4, 11), random_matrix(4, 12)];
+        // The column-stacking superoperator of the channel is `Σ_i conj(K_i) ⊗ K_i`.
+        let superop = kraus.iter().fold(Array2::zeros((16, 16)), |acc, k| {
+            acc + kron(&k.mapv(|x| x.conj()), k)
+        });
+        for qubits in [[0, 1], [2, 0], [1, 2]] {
+            let expected = evolve_kraus_inner(rho.view(), &views(&kraus), None, &qubits).unwrap();
+            let mut out = rho.clone();
+            evolve_superop_inner(&mut out, superop.view(), &qubits).unwrap();
+            assert_abs_diff_eq!(out, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mat = random_matrix(2, 13);
+        let mut not_square = Array2::zeros((4, 2));
+        assert!(matches!(
+            evolve_operator_inner(&mut not_square, mat.view(), &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut qutrit = Array2::zeros((3, 3));
+        assert!(matches!(
+            evolve_operator_inner(&mut qutrit, mat.view(), &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut rho = random_matrix(4, 14);
+        assert!(matches!(
+            evolve_operator_inner(&mut rho, mat.view(), &[2]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            evolve_operator_inner(&mut rho, random_matrix(4, 15).view(), &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            evolve_operator_inner(&mut rho, mat.view(), &[0, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            evolve_superop_inner(&mut rho, mat.view(), &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
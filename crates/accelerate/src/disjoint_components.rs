@@ -0,0 +1,314 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Splitting a layout problem over a device whose coupling map has several connected components.
+//!
+//! Qubits that interact have to be laid out on the same component, so the qubits of the circuit
+//! are grouped into the connected components of its interaction graph, and each group is assigned
+//! whole to a component of the device.  The groups are placed largest first, each on the component
+//! with the lowest average error that still has room for it, falling back to the component with
+//! the most free qubits when there are no error rates.  Each component that gets any qubits is then
+//! a separate layout problem.
+
+use std::cmp::Reverse;
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+#[cfg(feature = "python")]
+use crate::coupling_map::CouplingMap;
+use crate::error::{AccelerateError, ErrorContext};
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::instrumentation;
+use crate::nlayout::PhysicalQubit;
+
+/// The layout problem on one connected component of a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentProblem {
+    /// The virtual qubits to lay out on the component, in increasing order.
+    pub virtual_qubits: Vec<usize>,
+    /// The physical qubits of the component, in increasing order.
+    pub physical_qubits: Vec<PhysicalQubit>,
+}
+
+/// The connected components of the interaction graph of `num_qubits` virtual qubits with the given
+/// `edges`, each in increasing order, with the components in order of their lowest qubit.
+pub fn interaction_components(num_qubits: usize, edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); num_qubits];
+    for &[a, b] in edges {
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+    let mut seen = vec![false; num_qubits];
+    let mut out = Vec::new();
+    for start in 0..num_qubits {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(qubit) = stack.pop() {
+            for &neighbor in neighbors[qubit].iter() {
+                if !seen[neighbor] {
+                    seen[neighbor] = true;
+                    component.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+        component.sort_unstable();
+        out.push(component);
+    }
+    out
+}
+
+/// The mean of the error rates in `errors` that are entirely within each of `components`, or zero
+/// for a component with no known rates.
+fn component_errors(
+    components: &[Vec<PhysicalQubit>],
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+) -> Vec<f64> {
+    let mut component_of = HashMap::new();
+    for (index, component) in components.iter().enumerate() {
+        for qubit in component {
+            component_of.insert(*qubit, index);
+        }
+    }
+    let mut totals = vec![(0., 0usize); components.len()];
+    for ([a, b], error) in errors.iter() {
+        if error.is_nan() {
+            continue;
+        }
+        match (component_of.get(a), component_of.get(b)) {
+            (Some(left), Some(right)) if left == right => {
+                totals[*left].0 += error;
+                totals[*left].1 += 1;
+            }
+            _ => (),
+        }
+    }
+    totals
+        .into_iter()
+        .map(|(total, count)| if count == 0 { 0. } else { total / count as f64 })
+        .collect()
+}
+
+/// Split the layout of a circuit of `num_qubits` virtual qubits, of which the pairs in `edges`
+/// interact, over the connected `components` of a device.
+///
+/// Returns one problem for each component that any virtual qubits were assigned to, in the order
+/// of `components`.  Fails if a group of interacting qubits fits on none of the components.
+pub fn split_disjoint_components(
+    num_qubits: usize,
+    edges: &[[usize; 2]],
+    components: Vec<Vec<PhysicalQubit>>,
+    errors: Option<&HashMap<[PhysicalQubit; 2], f64>>,
+) -> Result<Vec<ComponentProblem>, AccelerateError> {
+    let _timer = instrumentation::Timer::start("disjoint_components");
+    let scores = match errors {
+        Some(errors) => component_errors(&components, errors),
+        None => vec![0.; components.len()],
+    };
+    let mut free = components.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut assigned = vec![Vec::new(); components.len()];
+    let mut groups = interaction_components(num_qubits, edges);
+    // The sort is stable, so groups of the same size stay in order of their lowest qubit.
+    groups.sort_by_key(|group| Reverse(group.len()));
+    for group in groups {
+        let target = (0..components.len())
+            .filter(|index| free[*index] >= group.len())
+            .min_by(|a, b| {
+                scores[*a]
+                    .total_cmp(&scores[*b])
+                    .then(free[*b].cmp(&free[*a]))
+                    .then(a.cmp(b))
+            })
+            .ok_or_else(|| {
+                AccelerateError::Layout(
+                    "A connected component of the DAGCircuit is too large for any of the \
+                     connected components in the coupling map."
+                        .to_string(),
+                    ErrorContext::default().with_qubits(group.iter().copied()),
+                )
+            })?;
+        free[target] -= group.len();
+        assigned[target].extend(group);
+    }
+    Ok(components
+        .into_iter()
+        .zip(assigned)
+        .filter(|(_, virtual_qubits)| !virtual_qubits.is_empty())
+        .map(|(physical_qubits, mut virtual_qubits)| {
+            virtual_qubits.sort_unstable();
+            ComponentProblem {
+                virtual_qubits,
+                physical_qubits,
+            }
+        })
+        .collect())
+}
+
+/// Split the layout of a circuit over the connected components of a coupling map.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     num_qubits (int): the number of virtual qubits of the circuit.
+///     edges (list[tuple[int, int]]): the pairs of virtual qubits that interact.
+///     error_map (ErrorMap | None): the average error rates of the device, used to prefer the
+///         more reliable components.
+///     physical_qubits (list[int] | None): the qubits of the coupling map that can be used, if not
+///         all of them.
+///
+/// Returns:
+///     list[tuple[list[int], list[int]]]: for each component of the device that has any qubits of
+///     the circuit, the virtual qubits assigned to it and its physical qubits.
+///
+/// Raises:
+///     TranspilerError: if a connected group of the virtual qubits fits on no component.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(
+    name = "split_disjoint_components",
+    signature = (coupling_map, num_qubits, edges, error_map=None, physical_qubits=None)
+)]
+pub fn py_split_disjoint_components(
+    coupling_map: &CouplingMap,
+    num_qubits: usize,
+    edges: Vec<[usize; 2]>,
+    error_map: Option<&ErrorMap>,
+    physical_qubits: Option<Vec<PhysicalQubit>>,
+) -> PyResult<Vec<(Vec<usize>, Vec<PhysicalQubit>)>> {
+    if let Some(qubit) = edges.iter().flatten().find(|qubit| **qubit >= num_qubits) {
+        return Err(AccelerateError::Index(format!(
+            "virtual qubit {qubit} is out of range for {num_qubits} qubits"
+        ))
+        .into());
+    }
+    let components = match physical_qubits {
+        Some(qubits) => coupling_map
+            .subgraph(&qubits)?
+            .connected_components()
+            .into_iter()
+            .map(|component| {
+                let mut component = component
+                    .into_iter()
+                    .map(|qubit| qubits[qubit.index()])
+                    .collect::<Vec<_>>();
+                component.sort_unstable();
+                component
+            })
+            .collect(),
+        None => coupling_map.connected_components(),
+    };
+    let problems = split_disjoint_components(
+        num_qubits,
+        &edges,
+        components,
+        error_map.map(|error_map| &error_map.error_map),
+    )?;
+    Ok(problems
+        .into_iter()
+        .map(|problem| (problem.virtual_qubits, problem.physical_qubits))
+        .collect())
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn disjoint_components(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_split_disjoint_components))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qubits(indices: &[u32]) -> Vec<PhysicalQubit> {
+        indices.iter().copied().map(PhysicalQubit::new).collect()
+    }
+
+    fn problem(virtual_qubits: &[usize], physical_qubits: &[u32]) -> ComponentProblem {
+        ComponentProblem {
+            virtual_qubits: virtual_qubits.to_vec(),
+            physical_qubits: qubits(physical_qubits),
+        }
+    }
+
+    #[test]
+    fn test_interaction_components() {
+        assert_eq!(
+            interaction_components(5, &[[0, 2], [3, 2]]),
+            [vec![0, 2, 3], vec![1], vec![4]]
+        );
+        assert!(interaction_components(0, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_largest_groups_first() {
+        let components = vec![qubits(&[0, 1, 2, 3]), qubits(&[4, 5, 6])];
+        let problems =
+            split_disjoint_components(6, &[[0, 1], [1, 2], [3, 4]], components, None).unwrap();
+        // The group of three goes on the component with the most room, which leaves the pair only
+        // one place to go, and the lone qubit goes on the first component with room.
+        assert_eq!(
+            problems,
+            [
+                problem(&[0, 1, 2, 5], &[0, 1, 2, 3]),
+                problem(&[3, 4], &[4, 5, 6])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lowest_error_component() {
+        let components = vec![qubits(&[0, 1]), qubits(&[2, 3]), qubits(&[4, 5, 6])];
+        let q = |index| PhysicalQubit::new(index);
+        let errors = [
+            ([q(0), q(1)], 0.2),
+            ([q(2), q(3)], 0.05),
+            ([q(3), q(3)], 0.07),
+            // Rates across components and `NaN` rates don't count.
+            ([q(1), q(2)], 0.),
+            ([q(2), q(2)], f64::NAN),
+            ([q(4), q(5)], 0.3),
+        ]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let problems = split_disjoint_components(2, &[[0, 1]], components, Some(&errors)).unwrap();
+        assert_eq!(problems, [problem(&[0, 1], &[2, 3])]);
+    }
+
+    #[test]
+    fn test_group_too_large() {
+        let components = vec![qubits(&[0, 1]), qubits(&[2, 3])];
+        let Err(AccelerateError::Layout(_, context)) =
+            split_disjoint_components(4, &[[0, 1], [2, 1]], components, None)
+        else {
+            panic!("expected a layout error");
+        };
+        assert_eq!(context.qubits, Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn test_no_qubits() {
+        let components = vec![qubits(&[0, 1])];
+        assert!(split_disjoint_components(0, &[], components, None)
+            .unwrap()
+            .is_empty());
+    }
+}
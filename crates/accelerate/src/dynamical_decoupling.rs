@@ -0,0 +1,435 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Insertion of dynamical-decoupling sequences in the idle windows of a scheduled circuit, as done
+//! by the `PadDynamicalDecoupling` pass.
+//!
+//! A sequence such as `XX` or `XY4` is spread over each window according to its spacing: the
+//! slack of the window, which is its length less the length of the gates, is split into the delays
+//! before, between and after the gates.  The delays are rounded down to the pulse alignment of the
+//! hardware, so that every gate starts on an aligned time, and the slack lost to rounding is added
+//! back where the slack distribution says.  Staggered variants, which shift the gates of
+//! neighbouring qubits against each other, are given by a different spacing for each qubit.
+
+#![allow(clippy::too_many_arguments)]
+
+use std::str::FromStr;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::imports;
+
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::instrumentation;
+#[cfg(feature = "python")]
+use crate::scheduling::{
+    idle_windows_inner, resolve_start_times, timed_instructions, TimedInstruction, TimingKind,
+};
+#[cfg(feature = "python")]
+use crate::target::Target;
+
+/// Where the slack that is lost to the pulse alignment is put back in a sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlackDistribution {
+    /// In the middle delay of the sequence; any remainder that isn't aligned goes at the end.
+    Middle,
+    /// Split as evenly as the alignment allows between the delays at the start and the end.
+    Edges,
+}
+
+impl FromStr for SlackDistribution {
+    type Err = AccelerateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "middle" => Ok(SlackDistribution::Middle),
+            "edges" => Ok(SlackDistribution::Edges),
+            _ => Err(AccelerateError::Transpiler(format!(
+                "Option extra_slack_distribution = {s} is invalid."
+            ))),
+        }
+    }
+}
+
+/// An element of a dynamical-decoupling sequence placed in a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequenceElement {
+    /// A delay of the given length.
+    Delay(i64),
+    /// The gate of the sequence with the given index.
+    Gate(usize),
+}
+
+/// Round `value` down to a multiple of `alignment`.
+fn constrained_length(value: f64, alignment: i64) -> i64 {
+    alignment * (value / alignment as f64).floor() as i64
+}
+
+/// The delays around the gates of a sequence that fills a window of length `time_interval`.
+///
+/// `lengths` are the lengths of the gates, and `spacing` the fractions of the slack to put before,
+/// between and after them, so it has one more element than `lengths`.  Returns `None` if the gates
+/// don't fit in the window.
+pub fn sequence_delays_inner(
+    time_interval: i64,
+    lengths: &[i64],
+    spacing: &[f64],
+    alignment: i64,
+    distribution: SlackDistribution,
+) -> Option<Vec<i64>> {
+    let slack = time_interval - lengths.iter().sum::<i64>();
+    if slack <= 0 {
+        return None;
+    }
+    let mut taus = spacing
+        .iter()
+        .map(|fraction| constrained_length(slack as f64 * fraction, alignment))
+        .collect::<Vec<_>>();
+    let extra_slack = slack - taus.iter().sum::<i64>();
+    let last = taus.len() - 1;
+    match distribution {
+        SlackDistribution::Middle => {
+            let to_middle = constrained_length(extra_slack as f64, alignment);
+            taus[last / 2] += to_middle;
+            taus[last] += extra_slack - to_middle;
+        }
+        SlackDistribution::Edges => {
+            let to_begin_edge = constrained_length(extra_slack as f64 / 2., alignment);
+            taus[0] += to_begin_edge;
+            taus[last] += extra_slack - to_begin_edge;
+        }
+    }
+    Some(taus)
+}
+
+/// The elements of a sequence that fills the window from `t_start` to `t_start + time_interval`,
+/// with their start times.
+///
+/// Returns `None` if the gates don't fit in the window, in which case it should be filled with a
+/// single delay.
+pub fn place_sequence_inner(
+    t_start: i64,
+    time_interval: i64,
+    lengths: &[i64],
+    spacing: &[f64],
+    alignment: i64,
+    distribution: SlackDistribution,
+) -> Option<Vec<(i64, SequenceElement)>> {
+    let taus = sequence_delays_inner(time_interval, lengths, spacing, alignment, distribution)?;
+    let mut out = Vec::with_capacity(taus.len() + lengths.len());
+    let mut idle_after = t_start;
+    for index in 0..taus.len().max(lengths.len()) {
+        if let Some(&tau) = taus.get(index) {
+            if tau > 0 {
+                out.push((idle_after, SequenceElement::Delay(tau)));
+                idle_after += tau;
+            }
+        }
+        if let Some(&length) = lengths.get(index) {
+            out.push((idle_after, SequenceElement::Gate(index)));
+            idle_after += length;
+        }
+    }
+    Some(out)
+}
+
+/// A description of the instruction at an end of an idle window, for error messages.
+#[cfg(feature = "python")]
+fn describe(instructions: &[TimedInstruction], index: Option<usize>, boundary: &str) -> String {
+    match index {
+        Some(index) => format!(
+            "{} on qargs {:?}",
+            instructions[index].name, instructions[index].qubits
+        ),
+        None => format!("the {boundary} of the circuit"),
+    }
+}
+
+/// Fill the idle windows of a scheduled circuit with a dynamical-decoupling sequence, as
+/// ``PadDynamicalDecoupling`` does for a sequence of two or more gates.
+///
+/// The delays of the input circuit are dropped and their time is padded again.  Windows on qubits
+/// without a sequence, windows that follow the start of the circuit or a reset when
+/// ``skip_reset_qubits`` is set, and windows too short for the sequence are filled with a delay.
+///
+/// Args:
+///     circuit (CircuitData): the scheduled circuit.
+///     start_times (list[int] | None): the start time of each instruction of ``circuit``.  By
+///         default, the start times recorded on ``circuit`` by a scheduler.
+///     unit (str): the time unit of the delays.
+///     dd_sequence (list[Gate]): the gates of the sequence.
+///     sequence_lengths (list[list[int] | None]): for each qubit, the lengths of the gates of the
+///         sequence on it, or ``None`` if the qubit isn't decoupled.
+///     spacings (list[list[float]]): for each qubit, the fractions of the slack to put before,
+///         between and after the gates.  Giving neighbouring qubits different spacings staggers
+///         their sequences.
+///     sequence_phase (float): the global phase of the sequence, which is added to the circuit for
+///         each sequence inserted.
+///     pulse_alignment (int | None): the alignment of the start times of the gates.  By default,
+///         the pulse alignment of ``target``, or 1 without one.
+///     extra_slack_distribution (str): ``"middle"`` or ``"edges"``.
+///     skip_reset_qubits (bool): whether to leave out the windows of qubits in the ground state.
+///     delay_qubits (list[bool] | None): whether each qubit supports delays; qubits that don't are
+///         not padded.  By default, all qubits are padded.
+///     target (Target | None): the Rust-space target to take the durations of the instructions
+///         from where it has them.
+///
+/// Returns:
+///     (CircuitData, list[int], int, float): the padded circuit, the start time of each of its
+///     instructions, the duration of the circuit, and the global phase to add to it.  The start
+///     times and the durations in ``dt`` of the instructions are also recorded on the padded
+///     circuit.
+///
+/// Raises:
+///     TranspilerError: if an idle window isn't a multiple of the pulse alignment, or if
+///         ``start_times`` isn't given and an instruction isn't scheduled.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, start_times, unit, dd_sequence, sequence_lengths, spacings, sequence_phase, pulse_alignment=None, extra_slack_distribution="middle", skip_reset_qubits=true, delay_qubits=None, target=None))]
+pub fn pad_dynamical_decoupling(
+    py: Python,
+    circuit: &CircuitData,
+    start_times: Option<Vec<i64>>,
+    unit: &str,
+    dd_sequence: Vec<PyObject>,
+    sequence_lengths: Vec<Option<Vec<i64>>>,
+    spacings: Vec<Vec<f64>>,
+    sequence_phase: f64,
+    pulse_alignment: Option<i64>,
+    extra_slack_distribution: &str,
+    skip_reset_qubits: bool,
+    delay_qubits: Option<Vec<bool>>,
+    target: Option<&Target>,
+) -> PyResult<(CircuitData, Vec<i64>, i64, f64)> {
+    let _timer = instrumentation::Timer::start("dynamical_decoupling");
+    let pulse_alignment = pulse_alignment.unwrap_or_else(|| {
+        target.map_or(1, |target| target.timing_constraints.pulse_alignment as i64)
+    });
+    let distribution = SlackDistribution::from_str(extra_slack_distribution)?;
+    let num_qubits = circuit.num_qubits();
+    if sequence_lengths.len() != num_qubits || spacings.len() != num_qubits {
+        return Err(AccelerateError::Value(format!(
+            "the sequence lengths and spacings must be given for each of the {num_qubits} qubits"
+        ))
+        .into());
+    }
+    for (lengths, spacing) in sequence_lengths.iter().zip(&spacings) {
+        if let Some(lengths) = lengths {
+            if lengths.len() != dd_sequence.len() || spacing.len() != dd_sequence.len() + 1 {
+                return Err(AccelerateError::Value(
+                    "the sequence lengths must have one entry per gate, and the spacings one more"
+                        .to_string(),
+                )
+                .into());
+            }
+        }
+    }
+
+    let start_times = resolve_start_times(circuit, start_times)?;
+    let instructions = timed_instructions(py, circuit, target)?;
+    let (windows, circuit_duration) =
+        idle_windows_inner(num_qubits, &instructions, &start_times, |qubit| {
+            delay_qubits.as_ref().map_or(true, |delay| delay[qubit])
+        })?;
+    let reset_class = imports::RESET.get_bound(py)?;
+    let delay_class = imports::DELAY.get_bound(py)?;
+    let is_reset = circuit
+        .iter_instructions()
+        .map(|(op, _, _)| op.bind(py).is_instance(&reset_class))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut out = circuit.copy_empty_like(py)?;
+    let mut out_start_times = Vec::with_capacity(instructions.len() + windows.len());
+    let mut out_durations = Vec::with_capacity(instructions.len() + windows.len());
+    let mut global_phase = 0.;
+    let mut windows = windows.into_iter().peekable();
+    let mut instructions_iter = circuit.iter_instructions().zip(&instructions).enumerate();
+    loop {
+        // The windows before the next instruction, or at the end of the circuit once the
+        // instructions run out.
+        let next = instructions_iter.next();
+        let index = next.as_ref().map(|(index, _)| *index);
+        while let Some(window) = windows.next_if(|window| index.is_none() || window.next == index) {
+            let time_interval = window.end - window.start;
+            if time_interval % pulse_alignment != 0 {
+                return Err(AccelerateError::Transpiler(format!(
+                    "Time interval {time_interval} is not divisible by alignment {pulse_alignment} \
+                     between DAGNode {} and {}.",
+                    describe(&instructions, window.prev, "start"),
+                    describe(&instructions, window.next, "end"),
+                ))
+                .into());
+            }
+            let ground_state = skip_reset_qubits && window.prev.map_or(true, |prev| is_reset[prev]);
+            let sequence = match &sequence_lengths[window.qubit] {
+                Some(lengths) if !ground_state => place_sequence_inner(
+                    window.start,
+                    time_interval,
+                    lengths,
+                    &spacings[window.qubit],
+                    pulse_alignment,
+                    distribution,
+                )
+                .map(|sequence| (sequence, lengths)),
+                _ => None,
+            };
+            let qubit = [window.qubit as u32];
+            match sequence {
+                Some((sequence, lengths)) => {
+                    for (t_start, element) in sequence {
+                        let (op, duration) = match element {
+                            SequenceElement::Delay(tau) => {
+                                (delay_class.call1((tau, unit))?.unbind(), tau)
+                            }
+                            SequenceElement::Gate(gate) => {
+                                (dd_sequence[gate].clone_ref(py), lengths[gate])
+                            }
+                        };
+                        out.push_instruction(op, &qubit, &[])?;
+                        out_start_times.push(t_start);
+                        out_durations.push(duration);
+                    }
+                    global_phase += sequence_phase;
+                }
+                None => {
+                    let delay = delay_class.call1((time_interval, unit))?;
+                    out.push_instruction(delay.unbind(), &qubit, &[])?;
+                    out_start_times.push(window.start);
+                    out_durations.push(time_interval);
+                }
+            }
+        }
+        let Some((index, ((op, qubits, clbits), inst))) = next else {
+            break;
+        };
+        if inst.kind != TimingKind::Delay {
+            out.push_instruction(op.clone_ref(py), qubits, clbits)?;
+            out_start_times.push(start_times[index]);
+            out_durations.push(inst.duration);
+        }
+    }
+    out.set_schedule(Some(&out_durations), &out_start_times)?;
+    Ok((out, out_start_times, circuit_duration, global_phase))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn dynamical_decoupling(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(pad_dynamical_decoupling))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XX_SPACING: [f64; 3] = [0.25, 0.5, 0.25];
+
+    #[test]
+    fn test_sequence_delays() {
+        let taus =
+            sequence_delays_inner(1000, &[100, 100], &XX_SPACING, 1, SlackDistribution::Middle);
+        assert_eq!(taus, Some(vec![200, 400, 200]));
+    }
+
+    #[test]
+    fn test_sequence_delays_aligned() {
+        // The slack is 680, whose quarters and half round down to 160 and 336, losing 24.
+        let middle = sequence_delays_inner(
+            1000,
+            &[160, 160],
+            &XX_SPACING,
+            16,
+            SlackDistribution::Middle,
+        );
+        assert_eq!(middle, Some(vec![160, 352, 168]));
+        let edges =
+            sequence_delays_inner(1000, &[160, 160], &XX_SPACING, 16, SlackDistribution::Edges);
+        assert_eq!(edges, Some(vec![160, 336, 184]));
+    }
+
+    #[test]
+    fn test_sequence_does_not_fit() {
+        for interval in [300, 320] {
+            assert_eq!(
+                sequence_delays_inner(
+                    interval,
+                    &[160, 160],
+                    &XX_SPACING,
+                    1,
+                    SlackDistribution::Middle
+                ),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_place_sequence() {
+        let placed = place_sequence_inner(
+            100,
+            1000,
+            &[100, 100],
+            &XX_SPACING,
+            1,
+            SlackDistribution::Middle,
+        )
+        .unwrap();
+        assert_eq!(
+            placed,
+            [
+                (100, SequenceElement::Delay(200)),
+                (300, SequenceElement::Gate(0)),
+                (400, SequenceElement::Delay(400)),
+                (800, SequenceElement::Gate(1)),
+                (900, SequenceElement::Delay(200)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_place_sequence_skips_empty_delays() {
+        let placed = place_sequence_inner(
+            0,
+            1000,
+            &[100, 100],
+            &[0., 1., 0.],
+            1,
+            SlackDistribution::Middle,
+        )
+        .unwrap();
+        assert_eq!(
+            placed,
+            [
+                (0, SequenceElement::Gate(0)),
+                (100, SequenceElement::Delay(800)),
+                (900, SequenceElement::Gate(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slack_distribution_from_str() {
+        assert_eq!("middle".parse(), Ok(SlackDistribution::Middle));
+        assert_eq!("edges".parse(), Ok(SlackDistribution::Edges));
+        assert!(matches!(
+            "random".parse::<SlackDistribution>(),
+            Err(AccelerateError::Transpiler(_))
+        ));
+    }
+}
@@ -0,0 +1,387 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Entanglement measures of quantum states: purity, von Neumann entropy, the entanglement entropy
+//! across a bipartition and the concurrence.
+//!
+//! For pure states the entanglement entropy and the concurrence only depend on the spectrum of the
+//! reduced state, which is the squared Schmidt coefficients.  We get those directly from the
+//! singular values of the bipartite amplitude matrix, so the reduced density matrix is never
+//! formed.
+
+#[cfg(feature = "python")]
+use numpy::{PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use faer_ext::IntoFaerComplex;
+use ndarray::prelude::*;
+use num_complex::Complex64;
+
+use crate::error::AccelerateError;
+use crate::fidelity::{hermitian_eigenvalues, sqrtm_psd};
+use crate::operator::matmul_inner;
+use crate::partial_trace::bipartite_amplitudes_inner;
+
+/// The Shannon entropy of a probability vector in the given logarithm `base`.  Entries outside
+/// `(0, 1)` contribute nothing, matching `qiskit.quantum_info.shannon_entropy`.
+pub fn shannon_entropy(probs: impl IntoIterator<Item = f64>, base: f64) -> f64 {
+    let log = |x: f64| {
+        if base == 2. {
+            x.log2()
+        } else if base == std::f64::consts::E {
+            x.ln()
+        } else {
+            x.ln() / base.ln()
+        }
+    };
+    probs
+        .into_iter()
+        .filter(|x| 0. < *x && *x < 1.)
+        .map(|x| -x * log(x))
+        .sum()
+}
+
+/// The purity `Tr[rho^2]` of a pure state, which is `<psi|psi>^2`.
+pub fn statevector_purity_inner(psi: ArrayView1<Complex64>) -> f64 {
+    psi.iter().map(|x| x.norm_sqr()).sum::<f64>().powi(2)
+}
+
+/// The purity `Tr[rho^2]` of a density matrix.
+pub fn density_matrix_purity_inner(rho: ArrayView2<Complex64>) -> Result<f64, AccelerateError> {
+    if rho.nrows() != rho.ncols() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a density matrix must be square, but got shape {:?}",
+            rho.shape()
+        )));
+    }
+    Ok(rho
+        .indexed_iter()
+        .map(|((i, j), x)| (x * rho[[j, i]]).re)
+        .sum())
+}
+
+/// The von Neumann entropy `-Tr[rho log(rho)]` of a density matrix.
+pub fn density_matrix_entropy_inner(
+    rho: ArrayView2<Complex64>,
+    base: f64,
+) -> Result<f64, AccelerateError> {
+    if rho.nrows() != rho.ncols() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a density matrix must be square, but got shape {:?}",
+            rho.shape()
+        )));
+    }
+    Ok(shannon_entropy(hermitian_eigenvalues(rho), base))
+}
+
+/// The spectrum of the reduced state of `psi` after tracing out the subsystems `qargs`.
+fn reduced_spectrum(
+    psi: ArrayView1<Complex64>,
+    dims: &[usize],
+    qargs: &[usize],
+) -> Result<Vec<f64>, AccelerateError> {
+    let amplitudes = bipartite_amplitudes_inner(psi, dims, qargs)?;
+    Ok(amplitudes
+        .view()
+        .into_faer_complex()
+        .singular_values()
+        .into_iter()
+        .map(|s| s * s)
+        .collect())
+}
+
+/// The entanglement entropy of the pure state `psi` across the bipartition into the subsystems
+/// `qargs` and the rest; this is the von Neumann entropy of the reduced state of either half.
+pub fn entanglement_entropy_inner(
+    psi: ArrayView1<Complex64>,
+    dims: &[usize],
+    qargs: &[usize],
+    base: f64,
+) -> Result<f64, AccelerateError> {
+    Ok(shannon_entropy(reduced_spectrum(psi, dims, qargs)?, base))
+}
+
+/// The concurrence `sqrt(2 (1 - Tr[rho_0^2]))` of a bipartite pure state, where `rho_0` is the
+/// reduced state of either subsystem.
+pub fn statevector_concurrence_inner(
+    psi: ArrayView1<Complex64>,
+    dims: &[usize],
+) -> Result<f64, AccelerateError> {
+    if dims.len() != 2 {
+        return Err(AccelerateError::Qiskit(
+            "Input is not a bipartite quantum state.".to_string(),
+        ));
+    }
+    // Trace out the larger subsystem, so the matrix we decompose is wide rather than tall.
+    let qargs = if dims[0] > dims[1] { [0] } else { [1] };
+    let purity: f64 = reduced_spectrum(psi, dims, &qargs)?
+        .into_iter()
+        .map(|p| p * p)
+        .sum();
+    Ok((2. * (1. - purity)).max(0.).sqrt())
+}
+
+/// The concurrence of a two-qubit density matrix, `max(0, l1 - l2 - l3 - l4)` where the `l` are
+/// the decreasing eigenvalues of `sqrt(sqrt(rho) (Y⊗Y) conj(rho) (Y⊗Y) sqrt(rho))`.
+pub fn density_matrix_concurrence_inner(
+    rho: ArrayView2<Complex64>,
+) -> Result<f64, AccelerateError> {
+    if rho.shape() != [4, 4] {
+        return Err(AccelerateError::Qiskit(
+            "Input density matrix must be a 2-qubit state.".to_string(),
+        ));
+    }
+    // Conjugation by `Y⊗Y` reverses the order of the basis and flips the sign of the elements
+    // whose row and column differ in parity.
+    let spin_flipped = Array2::from_shape_fn((4, 4), |(i, j)| {
+        let sign = if (i.count_ones() + j.count_ones()) % 2 == 0 {
+            1.
+        } else {
+            -1.
+        };
+        rho[[3 - i, 3 - j]].conj() * sign
+    });
+    let sqrt_rho = sqrtm_psd(rho);
+    let inner = matmul_inner(
+        matmul_inner(sqrt_rho.view(), spin_flipped.view())?.view(),
+        sqrt_rho.view(),
+    )?;
+    let mut lambdas = hermitian_eigenvalues(inner.view())
+        .into_iter()
+        .map(|x| x.max(0.).sqrt())
+        .collect::<Vec<_>>();
+    // `hermitian_eigenvalues` returns them in ascending order.
+    let largest = lambdas.pop().unwrap();
+    Ok((largest - lambdas.into_iter().sum::<f64>()).max(0.))
+}
+
+/// Compute the purity of a statevector.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(psi, /)")]
+pub fn statevector_purity(psi: PyReadonlyArray1<Complex64>) -> f64 {
+    statevector_purity_inner(psi.as_array())
+}
+
+/// Compute the purity of a density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(rho, /)")]
+pub fn density_matrix_purity(rho: PyReadonlyArray2<Complex64>) -> PyResult<f64> {
+    Ok(density_matrix_purity_inner(rho.as_array())?)
+}
+
+/// Compute the von Neumann entropy of a density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (rho, base=2.))]
+pub fn density_matrix_entropy(rho: PyReadonlyArray2<Complex64>, base: f64) -> PyResult<f64> {
+    Ok(density_matrix_entropy_inner(rho.as_array(), base)?)
+}
+
+/// Compute the entanglement entropy of a pure state across a bipartition.
+///
+/// Args:
+///     psi (ndarray): the statevector.
+///     dims (list[int]): the dimensions of the subsystems, in little-endian order.
+///     qargs (list[int]): the subsystems on one side of the bipartition.
+///     base (float): the base of the logarithm.
+///
+/// Returns:
+///     float: the von Neumann entropy of the reduced state.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (psi, dims, qargs, base=2.))]
+pub fn entanglement_entropy(
+    psi: PyReadonlyArray1<Complex64>,
+    dims: Vec<usize>,
+    qargs: Vec<usize>,
+    base: f64,
+) -> PyResult<f64> {
+    Ok(entanglement_entropy_inner(
+        psi.as_array(),
+        &dims,
+        &qargs,
+        base,
+    )?)
+}
+
+/// Compute the concurrence of a bipartite pure state.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(psi, dims, /)")]
+pub fn statevector_concurrence(
+    psi: PyReadonlyArray1<Complex64>,
+    dims: Vec<usize>,
+) -> PyResult<f64> {
+    Ok(statevector_concurrence_inner(psi.as_array(), &dims)?)
+}
+
+/// Compute the concurrence of a two-qubit density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(rho, /)")]
+pub fn density_matrix_concurrence(rho: PyReadonlyArray2<Complex64>) -> PyResult<f64> {
+    Ok(density_matrix_concurrence_inner(rho.as_array())?)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn entanglement(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(statevector_purity))?;
+    m.add_wrapped(wrap_pyfunction!(density_matrix_purity))?;
+    m.add_wrapped(wrap_pyfunction!(density_matrix_entropy))?;
+    m.add_wrapped(wrap_pyfunction!(entanglement_entropy))?;
+    m.add_wrapped(wrap_pyfunction!(statevector_concurrence))?;
+    m.add_wrapped(wrap_pyfunction!(density_matrix_concurrence))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    fn outer(psi: ArrayView1<Complex64>) -> Array2<Complex64> {
+        Array2::from_shape_fn((psi.len(), psi.len()), |(i, j)| psi[i] * psi[j].conj())
+    }
+
+    /// `cos(theta)|00> + sin(theta)|11>`.
+    fn entangled(theta: f64) -> Array1<Complex64> {
+        array![c(theta.cos(), 0.), c(0., 0.), c(0., 0.), c(theta.sin(), 0.)]
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_abs_diff_eq!(shannon_entropy([0.5, 0.5], 2.), 1., epsilon = 1e-12);
+        assert_abs_diff_eq!(shannon_entropy([0.25; 4], 2.), 2., epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            shannon_entropy([0.25; 4], std::f64::consts::E),
+            4f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(shannon_entropy([0.25; 4], 4.), 1., epsilon = 1e-12);
+        // Zeros, ones and rounding errors outside `[0, 1]` contribute nothing.
+        assert_eq!(shannon_entropy([1., 0., -1e-17], 2.), 0.);
+    }
+
+    #[test]
+    fn test_purity() {
+        let psi = entangled(0.3);
+        assert_abs_diff_eq!(statevector_purity_inner(psi.view()), 1., epsilon = 1e-12);
+        let rho = outer(psi.view());
+        assert_abs_diff_eq!(
+            density_matrix_purity_inner(rho.view()).unwrap(),
+            1.,
+            epsilon = 1e-12
+        );
+        let mixed = Array2::from_diag(&array![c(0.5, 0.), c(0.25, 0.), c(0.25, 0.)]);
+        assert_abs_diff_eq!(
+            density_matrix_purity_inner(mixed.view()).unwrap(),
+            0.375,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_density_matrix_entropy() {
+        let mixed = Array2::from_diag(&Array1::from_elem(4, c(0.25, 0.)));
+        assert_abs_diff_eq!(
+            density_matrix_entropy_inner(mixed.view(), 2.).unwrap(),
+            2.,
+            epsilon = 1e-12
+        );
+        let pure = outer(entangled(0.7).view());
+        assert_abs_diff_eq!(
+            density_matrix_entropy_inner(pure.view(), 2.).unwrap(),
+            0.,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_entanglement_entropy() {
+        for theta in [0., 0.3, std::f64::consts::FRAC_PI_4] {
+            let p = theta.cos().powi(2);
+            let expected = shannon_entropy([p, 1. - p], 2.);
+            let psi = entangled(theta);
+            for qargs in [[0], [1]] {
+                assert_abs_diff_eq!(
+                    entanglement_entropy_inner(psi.view(), &[2, 2], &qargs, 2.).unwrap(),
+                    expected,
+                    epsilon = 1e-10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_statevector_concurrence() {
+        for theta in [0., 0.3, std::f64::consts::FRAC_PI_4] {
+            let psi = entangled(theta);
+            assert_abs_diff_eq!(
+                statevector_concurrence_inner(psi.view(), &[2, 2]).unwrap(),
+                (2. * theta).sin().abs(),
+                epsilon = 1e-10
+            );
+        }
+        // A maximally entangled state of a qubit and a qutrit, with the qutrit as subsystem 0.
+        let amp = c(std::f64::consts::FRAC_1_SQRT_2, 0.);
+        let mut psi = Array1::zeros(6);
+        psi[0] = amp;
+        psi[4] = amp;
+        assert_abs_diff_eq!(
+            statevector_concurrence_inner(psi.view(), &[3, 2]).unwrap(),
+            1.,
+            epsilon = 1e-10
+        );
+        assert!(matches!(
+            statevector_concurrence_inner(entangled(0.).view(), &[2, 2, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_density_matrix_concurrence() {
+        for theta in [0., 0.3, std::f64::consts::FRAC_PI_4] {
+            let rho = outer(entangled(theta).view());
+            assert_abs_diff_eq!(
+                density_matrix_concurrence_inner(rho.view()).unwrap(),
+                (2. * theta).sin().abs(),
+                epsilon = 1e-7
+            );
+        }
+        // A Werner state `p |Bell><Bell| + (1 - p) I / 4` has concurrence `max(0, (3p - 1) / 2)`.
+        let bell = outer(entangled(std::f64::consts::FRAC_PI_4).view());
+        for p in [0.2, 0.5, 0.9] {
+            let werner = bell.mapv(|x| x * p)
+                + Array2::from_diag(&Array1::from_elem(4, c((1. - p) / 4., 0.)));
+            assert_abs_diff_eq!(
+                density_matrix_concurrence_inner(werner.view()).unwrap(),
+                ((3. * p - 1.) / 2.).max(0.),
+                epsilon = 1e-7
+            );
+        }
+        assert!(matches!(
+            density_matrix_concurrence_inner(Array2::zeros((2, 2)).view()),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
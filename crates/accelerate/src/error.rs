@@ -0,0 +1,155 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use std::fmt;
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyOverflowError, PyValueError};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3::PyTypeInfo;
+
+/// The structured details of a failure, which the Python exception carries as attributes
+/// alongside its message.  Each detail is optional.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorContext {
+    /// The transpiler pass or synthesis routine that failed.
+    pub pass_name: Option<String>,
+    /// The qubits involved in the failure.
+    pub qubits: Option<Vec<usize>>,
+    /// The numerical tolerance that couldn't be met.
+    pub tolerance: Option<f64>,
+}
+
+impl ErrorContext {
+    /// The context of a failure in `pass_name`.
+    pub fn pass(pass_name: &str) -> Self {
+        ErrorContext {
+            pass_name: Some(pass_name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Add the qubits involved in the failure.
+    pub fn with_qubits<I: IntoIterator<Item = usize>>(mut self, qubits: I) -> Self {
+        self.qubits = Some(qubits.into_iter().collect());
+        self
+    }
+
+    /// Add the tolerance that couldn't be met.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// An exception of type `T`, which must accept the context as keyword arguments, as
+    /// :class:`.QiskitRustError` does.
+    #[cfg(feature = "python")]
+    fn new_err<T: PyTypeInfo>(self, msg: String) -> PyErr {
+        Python::with_gil(|py| {
+            let kwargs = PyDict::new_bound(py);
+            let err = kwargs
+                .set_item("pass_name", self.pass_name)
+                .and_then(|_| kwargs.set_item("qubits", self.qubits))
+                .and_then(|_| kwargs.set_item("tolerance", self.tolerance))
+                .and_then(|_| T::type_object_bound(py).call((msg,), Some(&kwargs)));
+            match err {
+                Ok(err) => PyErr::from_value_bound(err),
+                Err(err) => err,
+            }
+        })
+    }
+}
+
+/// The error type of the Rust-space entry points to the numeric cores.
+///
+/// Each variant corresponds to the Python exception that the PyO3 bindings raise for it, so the
+/// bindings can propagate these errors with `?` and Python users see exactly the same exception
+/// types as before.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccelerateError {
+    /// A failure inside an algorithm; raised as :class:`.QiskitError`.
+    Qiskit(String),
+    /// An argument had an invalid value; raised as :class:`ValueError`.
+    Value(String),
+    /// An index was out of range; raised as :class:`IndexError`.
+    Index(String),
+    /// A key was missing from a mapping; raised as :class:`KeyError`.
+    Key(String),
+    /// A size was too large to be represented; raised as :class:`OverflowError`.
+    Overflow(String),
+    /// A circuit can't be compiled as requested; raised as :class:`.TranspilerError`.
+    Transpiler(String),
+    /// A synthesis routine failed to find a circuit; raised as :class:`.SynthesisError`.
+    Synthesis(String, ErrorContext),
+    /// A circuit can't be routed on a device; raised as :class:`.RoutingError`.
+    Routing(String, ErrorContext),
+    /// A circuit can't be laid out on a device; raised as :class:`.LayoutError`.
+    Layout(String, ErrorContext),
+}
+
+impl AccelerateError {
+    /// The human-readable message, without the error kind.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Qiskit(msg)
+            | Self::Value(msg)
+            | Self::Index(msg)
+            | Self::Key(msg)
+            | Self::Overflow(msg)
+            | Self::Transpiler(msg)
+            | Self::Synthesis(msg, _)
+            | Self::Routing(msg, _)
+            | Self::Layout(msg, _) => msg,
+        }
+    }
+
+    /// The structured details of the failure, if it has any.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Synthesis(_, context) | Self::Routing(_, context) | Self::Layout(_, context) => {
+                Some(context)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AccelerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AccelerateError {}
+
+#[cfg(feature = "python")]
+impl From<AccelerateError> for PyErr {
+    fn from(err: AccelerateError) -> PyErr {
+        match err {
+            AccelerateError::Qiskit(msg) => crate::QiskitError::new_err(msg),
+            AccelerateError::Value(msg) => PyValueError::new_err(msg),
+            AccelerateError::Index(msg) => PyIndexError::new_err(msg),
+            AccelerateError::Key(msg) => PyKeyError::new_err(msg),
+            AccelerateError::Overflow(msg) => PyOverflowError::new_err(msg),
+            AccelerateError::Transpiler(msg) => crate::TranspilerError::new_err(msg),
+            AccelerateError::Synthesis(msg, context) => {
+                context.new_err::<crate::SynthesisError>(msg)
+            }
+            AccelerateError::Routing(msg, context) => context.new_err::<crate::RoutingError>(msg),
+            AccelerateError::Layout(msg, context) => context.new_err::<crate::LayoutError>(msg),
+        }
+    }
+}
@@ -12,6 +12,9 @@
 
 use pyo3::exceptions::PyIndexError;
 use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::error::AccelerateError;
 
 use crate::nlayout::PhysicalQubit;
 
@@ -33,10 +36,88 @@ use hashbrown::HashMap;
 /// error rates, you should assign both elements of the key to the same
 /// qubit index. If an edge or qubit is ideal and has no error rate, you can
 /// either set it to ``0.0`` explicitly or as ``NaN``.
+///
+/// An entry can also carry the variance of its error rate, as an estimate of how much the rate is
+/// to be trusted.  Layout scoring can then penalize the uncertain entries with an
+/// ``uncertainty_weight``, so that a rate that looks good but was measured noisily isn't preferred
+/// to a slightly worse but well-known one.
 #[pyclass(mapping, module = "qiskit._accelerate.error_map")]
 #[derive(Clone, Debug)]
 pub struct ErrorMap {
     pub error_map: HashMap<[PhysicalQubit; 2], f64>,
+    /// The variance of the error rate of the entries that have one.
+    pub variances: HashMap<[PhysicalQubit; 2], f64>,
+}
+
+impl ErrorMap {
+    /// The error rate of `key`, raised by `uncertainty_weight` standard deviations of the rate if
+    /// it has a variance, and capped at one.  `NaN` rates, which mark ideal entries, are kept.
+    pub fn penalized_error(
+        &self,
+        key: &[PhysicalQubit; 2],
+        uncertainty_weight: f64,
+    ) -> Option<f64> {
+        let error = *self.error_map.get(key)?;
+        if uncertainty_weight == 0. || error.is_nan() {
+            return Some(error);
+        }
+        let deviation = self
+            .variances
+            .get(key)
+            .map_or(0., |variance| variance.sqrt());
+        Some((error + uncertainty_weight * deviation).min(1.))
+    }
+
+    /// The mean of the error rates given for each qubit and each pair of qubits, where the readout
+    /// errors and the errors of one-qubit gates on qubit `q` are averaged together at `[q, q]`.
+    /// Entries on qubits beyond `num_qubits`, as with the faulty qubits of some backends, and of
+    /// gates on more than two qubits are skipped.
+    pub fn from_average(
+        num_qubits: usize,
+        readout_errors: impl IntoIterator<Item = (usize, f64)>,
+        gate_errors: impl IntoIterator<Item = (Vec<PhysicalQubit>, f64)>,
+    ) -> Self {
+        let mut totals: HashMap<[PhysicalQubit; 2], (f64, usize)> = HashMap::new();
+        let mut add = |key: [PhysicalQubit; 2], error: f64| {
+            if key.iter().all(|qubit| qubit.index() < num_qubits) {
+                let total = totals.entry(key).or_insert((0., 0));
+                total.0 += error;
+                total.1 += 1;
+            }
+        };
+        for (qubit, error) in readout_errors {
+            if qubit < num_qubits {
+                let qubit = PhysicalQubit::new(qubit as u32);
+                add([qubit, qubit], error);
+            }
+        }
+        for (qubits, error) in gate_errors {
+            match qubits[..] {
+                [qubit] => add([qubit, qubit], error),
+                [a, b] => add([a, b], error),
+                _ => (),
+            }
+        }
+        ErrorMap {
+            error_map: totals
+                .into_iter()
+                .map(|(key, (total, count))| (key, total / count as f64))
+                .collect(),
+            variances: HashMap::new(),
+        }
+    }
+}
+
+/// The value of the entry called `name` in a list of ``{"name": ..., "value": ...}`` dictionaries,
+/// as in the qubit and gate properties of a backend properties payload.
+fn named_value(entries: &Bound<PyAny>, name: &str) -> PyResult<Option<f64>> {
+    for entry in entries.iter()? {
+        let entry = entry?;
+        if entry.get_item("name")?.extract::<String>()? == name {
+            return Ok(Some(entry.get_item("value")?.extract()?));
+        }
+    }
+    Ok(None)
 }
 
 #[pymethods]
@@ -47,9 +128,11 @@ impl ErrorMap {
         match size {
             Some(size) => ErrorMap {
                 error_map: HashMap::with_capacity(size),
+                variances: HashMap::new(),
             },
             None => ErrorMap {
                 error_map: HashMap::new(),
+                variances: HashMap::new(),
             },
         }
     }
@@ -63,26 +146,161 @@ impl ErrorMap {
     /// constructing an intermediate dict and using this constructor.
     #[staticmethod]
     fn from_dict(error_map: HashMap<[PhysicalQubit; 2], f64>) -> Self {
-        ErrorMap { error_map }
+        ErrorMap {
+            error_map,
+            variances: HashMap::new(),
+        }
+    }
+
+    /// Build the average error map of a backend from arrays of its error rates.
+    ///
+    /// The readout errors and the errors of one-qubit gates on each qubit are averaged together
+    /// at ``(q, q)``, and the errors of the gates on each pair of qubits at that pair.  Entries on
+    /// qubits beyond ``num_qubits`` and of gates on more than two qubits are skipped.
+    ///
+    /// Args:
+    ///     num_qubits (int): the number of qubits of the backend.
+    ///     readout_errors (list[float | None]): the readout error of each qubit, or ``None`` if it
+    ///         has none.
+    ///     gate_qubits (list[list[int]]): the qubits of each gate.
+    ///     gate_errors (list[float]): the error of each gate.
+    ///
+    /// Raises:
+    ///     ValueError: if there aren't as many gate errors as gates.
+    #[staticmethod]
+    #[pyo3(signature = (num_qubits, readout_errors, gate_qubits, gate_errors))]
+    fn from_arrays(
+        num_qubits: usize,
+        readout_errors: Vec<Option<f64>>,
+        gate_qubits: Vec<Vec<PhysicalQubit>>,
+        gate_errors: Vec<f64>,
+    ) -> PyResult<Self> {
+        if gate_qubits.len() != gate_errors.len() {
+            return Err(AccelerateError::Value(format!(
+                "there are {} gates but {} gate errors",
+                gate_qubits.len(),
+                gate_errors.len()
+            ))
+            .into());
+        }
+        Ok(ErrorMap::from_average(
+            num_qubits,
+            readout_errors
+                .into_iter()
+                .enumerate()
+                .filter_map(|(qubit, error)| error.map(|error| (qubit, error))),
+            gate_qubits.into_iter().zip(gate_errors),
+        ))
     }
 
-    fn add_error(&mut self, index: [PhysicalQubit; 2], error_rate: f64) {
+    /// Build the average error map of a backend from a backend properties payload, as returned
+    /// by :meth:`.BackendProperties.to_dict` or as its JSON encoding.
+    ///
+    /// The ``readout_error`` of each entry of ``"qubits"`` and the ``gate_error`` of each entry of
+    /// ``"gates"`` are averaged as in :meth:`from_arrays`; other properties are ignored.
+    ///
+    /// Args:
+    ///     num_qubits (int): the number of qubits of the backend.
+    ///     properties (dict | str): the payload.
+    ///
+    /// Raises:
+    ///     KeyError: if the payload has no ``"qubits"`` or ``"gates"``, or a property has no
+    ///         ``"name"`` or ``"value"``.
+    #[staticmethod]
+    #[pyo3(signature = (num_qubits, properties))]
+    fn from_properties(py: Python, num_qubits: usize, properties: &Bound<PyAny>) -> PyResult<Self> {
+        let properties = if properties.is_instance_of::<PyString>() {
+            py.import_bound("json")?
+                .call_method1("loads", (properties,))?
+        } else {
+            properties.clone()
+        };
+        let mut readout_errors = Vec::new();
+        for (qubit, entries) in properties.get_item("qubits")?.iter()?.enumerate() {
+            if let Some(error) = named_value(&entries?, "readout_error")? {
+                readout_errors.push((qubit, error));
+            }
+        }
+        let mut gate_errors = Vec::new();
+        for gate in properties.get_item("gates")?.iter()? {
+            let gate = gate?;
+            if let Some(error) = named_value(&gate.get_item("parameters")?, "gate_error")? {
+                gate_errors.push((gate.get_item("qubits")?.extract()?, error));
+            }
+        }
+        Ok(ErrorMap::from_average(
+            num_qubits,
+            readout_errors,
+            gate_errors,
+        ))
+    }
+
+    /// Add the error rate of an entry, and optionally the variance of the rate.
+    #[pyo3(signature = (index, error_rate, variance=None))]
+    fn add_error(&mut self, index: [PhysicalQubit; 2], error_rate: f64, variance: Option<f64>) {
         self.error_map.insert(index, error_rate);
+        match variance {
+            Some(variance) => self.variances.insert(index, variance),
+            None => self.variances.remove(&index),
+        };
     }
 
-    // The pickle protocol methods can't return `HashMap<[T; 2], f64>` to Python, because by PyO3's
-    // natural conversion as of 0.17.3 it will attempt to construct a `dict[list[T], float]`, where
-    // `list[T]` is unhashable in Python.
+    /// Set the variance of the error rate of an existing entry.
+    ///
+    /// Raises:
+    ///     IndexError: if there is no entry for ``index``.
+    fn set_variance(&mut self, index: [PhysicalQubit; 2], variance: f64) -> PyResult<()> {
+        if !self.error_map.contains_key(&index) {
+            return Err(PyIndexError::new_err("No node found for index"));
+        }
+        self.variances.insert(index, variance);
+        Ok(())
+    }
+
+    /// The variance of the error rate of an entry, or ``None`` if it has none.
+    fn variance(&self, index: [PhysicalQubit; 2]) -> Option<f64> {
+        self.variances.get(&index).copied()
+    }
 
-    fn __getstate__(&self) -> HashMap<(PhysicalQubit, PhysicalQubit), f64> {
-        self.error_map
+    /// The variances of the entries that have one.
+    ///
+    /// Returns:
+    ///     dict[tuple[int, int], float]: the variance of each entry.
+    fn variances(&self) -> HashMap<(PhysicalQubit, PhysicalQubit), f64> {
+        self.variances
             .iter()
             .map(|([a, b], value)| ((*a, *b), *value))
             .collect()
     }
 
-    fn __setstate__(&mut self, state: HashMap<[PhysicalQubit; 2], f64>) {
-        self.error_map = state;
+    // The pickle protocol methods can't return `HashMap<[T; 2], f64>` to Python, because by PyO3's
+    // natural conversion as of 0.17.3 it will attempt to construct a `dict[list[T], float]`, where
+    // `list[T]` is unhashable in Python.
+
+    #[allow(clippy::type_complexity)]
+    fn __getstate__(
+        &self,
+    ) -> (
+        HashMap<(PhysicalQubit, PhysicalQubit), f64>,
+        HashMap<(PhysicalQubit, PhysicalQubit), f64>,
+    ) {
+        (
+            self.error_map
+                .iter()
+                .map(|([a, b], value)| ((*a, *b), *value))
+                .collect(),
+            self.variances(),
+        )
+    }
+
+    fn __setstate__(
+        &mut self,
+        state: (
+            HashMap<[PhysicalQubit; 2], f64>,
+            HashMap<[PhysicalQubit; 2], f64>,
+        ),
+    ) {
+        (self.error_map, self.variances) = state;
     }
 
     fn __len__(&self) -> PyResult<usize> {
@@ -116,3 +334,71 @@ pub fn error_map(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<ErrorMap>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qubits(indices: &[u32]) -> Vec<PhysicalQubit> {
+        indices.iter().copied().map(PhysicalQubit::new).collect()
+    }
+
+    #[test]
+    fn test_from_average() {
+        let map = ErrorMap::from_average(
+            3,
+            [(0, 0.1), (1, 0.02)],
+            [
+                (qubits(&[0]), 0.3),
+                (qubits(&[0, 1]), 0.01),
+                (qubits(&[0, 1]), 0.03),
+                (qubits(&[1, 0]), 0.05),
+            ],
+        );
+        let [q0, q1, _] = [0, 1, 2].map(PhysicalQubit::new);
+        assert_eq!(map.error_map.len(), 4);
+        assert_eq!(map.error_map[&[q0, q0]], 0.2);
+        assert_eq!(map.error_map[&[q1, q1]], 0.02);
+        assert_eq!(map.error_map[&[q0, q1]], 0.02);
+        assert_eq!(map.error_map[&[q1, q0]], 0.05);
+        assert!(map.variances.is_empty());
+    }
+
+    #[test]
+    fn test_from_average_skips_entries() {
+        // Qubits beyond the map and gates on more than two qubits are left out.
+        let map = ErrorMap::from_average(
+            2,
+            [(2, 0.1)],
+            [
+                (qubits(&[1, 2]), 0.01),
+                (qubits(&[3]), 0.01),
+                (qubits(&[0, 1, 1]), 0.01),
+                (qubits(&[]), 0.01),
+            ],
+        );
+        assert!(map.error_map.is_empty());
+    }
+
+    #[test]
+    fn test_penalized_error() {
+        let [q0, q1] = [0, 1].map(PhysicalQubit::new);
+        let map = ErrorMap {
+            error_map: HashMap::from_iter([
+                ([q0, q0], 0.01),
+                ([q1, q1], 0.02),
+                ([q0, q1], f64::NAN),
+                ([q1, q0], 0.9),
+            ]),
+            variances: HashMap::from_iter([([q0, q0], 0.0004), ([q0, q1], 1.), ([q1, q0], 0.04)]),
+        };
+        assert_eq!(map.penalized_error(&[q0, q0], 0.), Some(0.01));
+        assert!((map.penalized_error(&[q0, q0], 2.).unwrap() - 0.05).abs() < 1e-15);
+        // Entries without a variance are unchanged, ideal entries stay ideal, and the penalized
+        // rates are capped at one.
+        assert_eq!(map.penalized_error(&[q1, q1], 2.), Some(0.02));
+        assert!(map.penalized_error(&[q0, q1], 2.).unwrap().is_nan());
+        assert_eq!(map.penalized_error(&[q1, q0], 1.), Some(1.));
+        assert_eq!(map.penalized_error(&[q1, PhysicalQubit::new(2)], 1.), None);
+    }
+}
@@ -0,0 +1,496 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The bookkeeping of the estimators that sample the observables of their pubs.
+//!
+//! The Pauli terms needed for each parameter set of a pub are grouped into sets of qubit-wise
+//! commuting terms, and each group is measured in one basis by one circuit.  From the counts of
+//! those circuits, each term gets its expectation value and variance, and the observables of the
+//! pub are then the sums of their terms.
+//!
+//! Pauli terms are labels, with qubit 0 last, as in the observables of the pubs.
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::error::AccelerateError;
+
+/// The Pauli of each qubit of `label`, in qubit order.
+fn parse_label(label: &str, num_qubits: usize) -> Result<Vec<u8>, AccelerateError> {
+    if label.len() != num_qubits {
+        return Err(AccelerateError::Value(format!(
+            "the Pauli '{}' isn't on {} qubits",
+            label, num_qubits
+        )));
+    }
+    label
+        .bytes()
+        .rev()
+        .map(|pauli| match pauli {
+            b'I' | b'X' | b'Y' | b'Z' => Ok(pauli),
+            _ => Err(AccelerateError::Value(format!(
+                "'{}' isn't a Pauli label",
+                label
+            ))),
+        })
+        .collect()
+}
+
+/// Whether `pauli` can be measured in `basis`, which it can if they agree on every qubit where
+/// neither is the identity.
+fn qubit_wise_commute(basis: &[u8], pauli: &[u8]) -> bool {
+    basis
+        .iter()
+        .zip(pauli)
+        .all(|(a, b)| *a == b'I' || *b == b'I' || a == b)
+}
+
+/// The outcome of `bits` classical bits in `outcome`, packed into words.  Only the first register
+/// of the outcome, which is the register of the measurements, is read.
+fn parse_outcome(outcome: &str, bits: usize) -> Result<Vec<u64>, AccelerateError> {
+    let register = outcome.split(' ').next().unwrap_or_default();
+    let mut out = vec![0u64; (bits + 63) / 64];
+    for (bit, value) in register.bytes().rev().enumerate() {
+        match value {
+            b'0' => (),
+            b'1' if bit < bits => out[bit / 64] |= 1 << (bit % 64),
+            b'1' => (),
+            _ => {
+                return Err(AccelerateError::Value(format!(
+                    "'{}' isn't a binary outcome",
+                    outcome
+                )))
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The Pauli terms of one parameter set that are measured together, in one basis.
+#[derive(Clone, Debug)]
+pub struct MeasurementGroup {
+    /// The parameter set of the terms.
+    pub param_index: usize,
+    /// The Pauli measured on each qubit, in qubit order, with `b'I'` on the unmeasured qubits.
+    pub basis: Vec<u8>,
+    /// The measured qubits, in the order of the classical bits they're measured into.  A basis
+    /// that is the identity everywhere measures qubit 0, so that the circuit has a measurement.
+    pub qubits: Vec<usize>,
+    /// The index of each term, with the classical bits of its support packed into words.
+    pub terms: Vec<(usize, Vec<u64>)>,
+}
+
+impl MeasurementGroup {
+    fn new(param_index: usize, basis: Vec<u8>, paulis: Vec<(usize, Vec<u8>)>) -> Self {
+        let mut qubits: Vec<usize> = (0..basis.len()).filter(|&q| basis[q] != b'I').collect();
+        if qubits.is_empty() {
+            qubits.push(0);
+        }
+        let words = (qubits.len() + 63) / 64;
+        let terms = paulis
+            .into_iter()
+            .map(|(term, pauli)| {
+                let mut mask = vec![0u64; words];
+                for (bit, qubit) in qubits.iter().enumerate() {
+                    if pauli.get(*qubit).map_or(false, |pauli| *pauli != b'I') {
+                        mask[bit / 64] |= 1 << (bit % 64);
+                    }
+                }
+                (term, mask)
+            })
+            .collect();
+        MeasurementGroup {
+            param_index,
+            basis,
+            qubits,
+            terms,
+        }
+    }
+
+    /// The label of the basis, with qubit 0 last.
+    pub fn basis_label(&self) -> String {
+        self.basis
+            .iter()
+            .rev()
+            .map(|pauli| *pauli as char)
+            .collect()
+    }
+
+    /// The expectation value and the variance of each term from the `counts` of the circuit of
+    /// the group, in the order of [MeasurementGroup::terms].
+    pub fn term_values(
+        &self,
+        counts: &HashMap<String, u64>,
+    ) -> Result<Vec<(f64, f64)>, AccelerateError> {
+        let mut sums = vec![0.; self.terms.len()];
+        let mut shots = 0;
+        for (outcome, freq) in counts {
+            let outcome = parse_outcome(outcome, self.qubits.len())?;
+            shots += freq;
+            for ((_, mask), sum) in self.terms.iter().zip(sums.iter_mut()) {
+                let parity: u32 = mask
+                    .iter()
+                    .zip(&outcome)
+                    .map(|(mask, outcome)| (mask & outcome).count_ones())
+                    .sum();
+                if parity % 2 == 0 {
+                    *sum += *freq as f64;
+                } else {
+                    *sum -= *freq as f64;
+                }
+            }
+        }
+        Ok(sums
+            .into_iter()
+            .map(|sum| {
+                let expval = sum / shots as f64;
+                (expval, 1. - expval * expval)
+            })
+            .collect())
+    }
+}
+
+/// The measurement groups of the Pauli terms of each parameter set of a pub.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.estimator_grouping", frozen)
+)]
+pub struct ObservableGrouping {
+    groups: Vec<MeasurementGroup>,
+    /// The index of each term, by parameter set and label.
+    terms: Vec<HashMap<String, usize>>,
+    num_terms: usize,
+}
+
+impl ObservableGrouping {
+    /// Group the Pauli `labels` of each parameter set, on `num_qubits` qubits.
+    ///
+    /// With `abelian_grouping`, each term joins the first group, in sorted order, that it
+    /// qubit-wise commutes with; without it, each term is a group of its own.  The groups of each
+    /// parameter set follow those of the previous one.
+    pub fn new(
+        num_qubits: usize,
+        labels: &[Vec<String>],
+        abelian_grouping: bool,
+    ) -> Result<Self, AccelerateError> {
+        let mut groups = Vec::new();
+        let mut terms = Vec::with_capacity(labels.len());
+        let mut num_terms = 0;
+        for (param_index, labels) in labels.iter().enumerate() {
+            let mut labels: Vec<&String> = labels.iter().collect();
+            labels.sort_unstable();
+            labels.dedup();
+            let mut param_terms = HashMap::with_capacity(labels.len());
+            let mut param_groups: Vec<(Vec<u8>, Vec<(usize, Vec<u8>)>)> = Vec::new();
+            for label in labels {
+                let pauli = parse_label(label, num_qubits)?;
+                param_terms.insert(label.clone(), num_terms);
+                let group = if abelian_grouping {
+                    param_groups
+                        .iter()
+                        .position(|(basis, _)| qubit_wise_commute(basis, &pauli))
+                } else {
+                    None
+                };
+                match group {
+                    Some(group) => {
+                        let (basis, members) = &mut param_groups[group];
+                        for (basis, pauli) in basis.iter_mut().zip(&pauli) {
+                            if *pauli != b'I' {
+                                *basis = *pauli;
+                            }
+                        }
+                        members.push((num_terms, pauli));
+                    }
+                    None => param_groups.push((pauli.clone(), vec![(num_terms, pauli)])),
+                }
+                num_terms += 1;
+            }
+            groups.extend(
+                param_groups
+                    .into_iter()
+                    .map(|(basis, members)| MeasurementGroup::new(param_index, basis, members)),
+            );
+            terms.push(param_terms);
+        }
+        Ok(ObservableGrouping {
+            groups,
+            terms,
+            num_terms,
+        })
+    }
+
+    /// The groups, in the order of their circuits.
+    pub fn groups(&self) -> &[MeasurementGroup] {
+        &self.groups
+    }
+
+    /// The expectation value and the variance of every term, from the `counts` of the circuit of
+    /// each group.
+    pub fn term_values(
+        &self,
+        counts: &[HashMap<String, u64>],
+    ) -> Result<Vec<(f64, f64)>, AccelerateError> {
+        if counts.len() != self.groups.len() {
+            return Err(AccelerateError::Value(format!(
+                "expected the counts of {} circuits, but got {}",
+                self.groups.len(),
+                counts.len()
+            )));
+        }
+        let mut out = vec![(0., 0.); self.num_terms];
+        for (group, counts) in self.groups.iter().zip(counts) {
+            for ((term, _), values) in group.terms.iter().zip(group.term_values(counts)?) {
+                out[*term] = values;
+            }
+        }
+        Ok(out)
+    }
+
+    /// The expectation value and the variance of each of `observables`, which are each the
+    /// index of a parameter set and the coefficients of the Pauli terms, from the `counts` of the
+    /// circuit of each group.
+    pub fn aggregate(
+        &self,
+        counts: &[HashMap<String, u64>],
+        observables: &[(usize, HashMap<String, f64>)],
+    ) -> Result<(Vec<f64>, Vec<f64>), AccelerateError> {
+        let values = self.term_values(counts)?;
+        let mut expvals = Vec::with_capacity(observables.len());
+        let mut variances = Vec::with_capacity(observables.len());
+        for (param_index, observable) in observables {
+            let terms = self.terms.get(*param_index).ok_or_else(|| {
+                AccelerateError::Index(format!("no parameter set {}", param_index))
+            })?;
+            let mut expval = 0.;
+            let mut variance = 0.;
+            for (label, coeff) in observable {
+                let term = terms.get(label).ok_or_else(|| {
+                    AccelerateError::Key(format!(
+                        "the Pauli '{}' wasn't measured for parameter set {}",
+                        label, param_index
+                    ))
+                })?;
+                expval += coeff * values[*term].0;
+                variance += coeff * coeff * values[*term].1;
+            }
+            expvals.push(expval);
+            variances.push(variance);
+        }
+        Ok((expvals, variances))
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ObservableGrouping {
+    #[new]
+    #[pyo3(signature = (num_qubits, labels, abelian_grouping=true))]
+    fn py_new(
+        num_qubits: usize,
+        labels: Vec<Vec<String>>,
+        abelian_grouping: bool,
+    ) -> PyResult<Self> {
+        Ok(Self::new(num_qubits, &labels, abelian_grouping)?)
+    }
+
+    fn __len__(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// The measurement of each group, in the order of their circuits.
+    ///
+    /// Returns:
+    ///     list[tuple[int, str, list[int]]]: the index of the parameter set of each group, the
+    ///     label of its basis, and the qubits it measures, in the order of the classical bits.
+    fn bases(&self) -> Vec<(usize, String, Vec<usize>)> {
+        self.groups
+            .iter()
+            .map(|group| (group.param_index, group.basis_label(), group.qubits.clone()))
+            .collect()
+    }
+
+    /// The expectation values and the variances of observables.
+    ///
+    /// Args:
+    ///     counts (list[dict[str, int]]): the counts of the circuit of each group.
+    ///     observables (list[tuple[int, dict[str, float]]]): the index of the parameter set of
+    ///         each observable, and the coefficients of its Pauli terms.
+    ///
+    /// Returns:
+    ///     (list[float], list[float]): the expectation value and the variance of each observable.
+    #[pyo3(name = "aggregate")]
+    fn py_aggregate(
+        &self,
+        py: Python,
+        counts: Vec<HashMap<String, u64>>,
+        observables: Vec<(usize, HashMap<String, f64>)>,
+    ) -> PyResult<(Vec<f64>, Vec<f64>)> {
+        Ok(py.allow_threads(|| self.aggregate(&counts, &observables))?)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn estimator_grouping(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<ObservableGrouping>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    fn counts(counts: &[(&str, u64)]) -> HashMap<String, u64> {
+        counts
+            .iter()
+            .map(|(outcome, freq)| (outcome.to_string(), *freq))
+            .collect()
+    }
+
+    fn observable(terms: &[(&str, f64)]) -> HashMap<String, f64> {
+        terms
+            .iter()
+            .map(|(label, coeff)| (label.to_string(), *coeff))
+            .collect()
+    }
+
+    fn bases(grouping: &ObservableGrouping) -> Vec<(usize, String, Vec<usize>)> {
+        grouping
+            .groups()
+            .iter()
+            .map(|group| (group.param_index, group.basis_label(), group.qubits.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(parse_label("XIZ", 3).unwrap(), b"ZIX".to_vec());
+        assert!(matches!(
+            parse_label("XZ", 3),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            parse_label("XAZ", 3),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(parse_outcome("110", 3).unwrap(), vec![0b110]);
+        // Only the first register is read, and bits beyond the measured ones are ignored.
+        assert_eq!(parse_outcome("101 11", 2).unwrap(), vec![0b01]);
+        let long = format!("1{}", "0".repeat(64));
+        assert_eq!(parse_outcome(&long, 65).unwrap(), vec![0, 1]);
+        assert!(matches!(
+            parse_outcome("102", 3),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_abelian_grouping() {
+        let grouping =
+            ObservableGrouping::new(2, &[labels(&["ZZ", "XX", "ZI", "IX", "XX"])], true).unwrap();
+        assert_eq!(
+            bases(&grouping),
+            vec![
+                (0, "XX".to_string(), vec![0, 1]),
+                (0, "ZZ".to_string(), vec![0, 1]),
+            ]
+        );
+        let terms = grouping.groups()[0]
+            .terms
+            .iter()
+            .map(|(term, _)| *term)
+            .collect::<Vec<_>>();
+        assert_eq!(terms, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_no_grouping() {
+        let grouping =
+            ObservableGrouping::new(2, &[labels(&["ZZ", "IX"]), labels(&["II"])], false).unwrap();
+        assert_eq!(
+            bases(&grouping),
+            vec![
+                (0, "IX".to_string(), vec![0]),
+                (0, "ZZ".to_string(), vec![0, 1]),
+                // The identity measures qubit 0, so that its circuit has a measurement.
+                (1, "II".to_string(), vec![0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_term_values() {
+        let grouping = ObservableGrouping::new(2, &[labels(&["IX", "XX", "II"])], true).unwrap();
+        assert_eq!(grouping.groups().len(), 1);
+        let values = grouping
+            .term_values(&[counts(&[("00", 3), ("01", 1), ("11", 2)])])
+            .unwrap();
+        // The terms are in sorted order: II, IX, XX.
+        let expected = [(1., 0.), (0., 1.), (4. / 6., 1. - 16. / 36.)];
+        for ((expval, variance), (expected_expval, expected_variance)) in
+            values.iter().zip(expected)
+        {
+            assert!((expval - expected_expval).abs() < 1e-12);
+            assert!((variance - expected_variance).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let grouping =
+            ObservableGrouping::new(1, &[labels(&["Z"]), labels(&["X", "Z"])], false).unwrap();
+        let circuit_counts = [
+            counts(&[("0", 3), ("1", 1)]),
+            counts(&[("0", 1), ("1", 1)]),
+            counts(&[("1", 4)]),
+        ];
+        let observables = [
+            (0, observable(&[("Z", 2.)])),
+            (1, observable(&[("X", 0.5), ("Z", -1.)])),
+        ];
+        let (expvals, variances) = grouping.aggregate(&circuit_counts, &observables).unwrap();
+        assert!((expvals[0] - 1.).abs() < 1e-12);
+        assert!((variances[0] - 4. * 0.75).abs() < 1e-12);
+        assert!((expvals[1] - 1.).abs() < 1e-12);
+        assert!((variances[1] - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_aggregate_errors() {
+        let grouping = ObservableGrouping::new(1, &[labels(&["Z"])], true).unwrap();
+        let circuit_counts = [counts(&[("0", 1)])];
+        assert!(matches!(
+            grouping.aggregate(&[], &[(0, observable(&[("Z", 1.)]))]),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            grouping.aggregate(&circuit_counts, &[(1, observable(&[("Z", 1.)]))]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            grouping.aggregate(&circuit_counts, &[(0, observable(&[("X", 1.)]))]),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+}
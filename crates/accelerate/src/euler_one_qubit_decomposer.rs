@@ -18,32 +18,49 @@ use num_complex::{Complex64, ComplexFloat};
 use smallvec::{smallvec, SmallVec};
 use std::cmp::Ordering;
 use std::f64::consts::PI;
+#[cfg(feature = "python")]
 use std::ops::Deref;
 use std::str::FromStr;
 
-use pyo3::exceptions::{PyIndexError, PyValueError};
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyIndexError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::PyString;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
 use ndarray::prelude::*;
+#[cfg(feature = "python")]
 use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
 use pyo3::pybacked::PyBackedStr;
 
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::{CircuitData, Param};
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+#[cfg(feature = "python")]
 use qiskit_circuit::SliceOrInt;
 
+use crate::angles::{is_zero_mod_2pi, mod_2pi};
+use crate::error::AccelerateError;
+
 pub const ANGLE_ZERO_EPSILON: f64 = 1e-12;
 
-#[pyclass(module = "qiskit._accelerate.euler_one_qubit_decomposer")]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.euler_one_qubit_decomposer")
+)]
 pub struct OneQubitGateErrorMap {
     error_map: Vec<HashMap<String, f64>>,
 }
 
-#[pymethods]
 impl OneQubitGateErrorMap {
-    #[new]
-    fn new(num_qubits: Option<usize>) -> Self {
+    pub fn new(num_qubits: Option<usize>) -> Self {
         OneQubitGateErrorMap {
             error_map: match num_qubits {
                 Some(n) => Vec::with_capacity(n),
@@ -52,9 +69,23 @@ impl OneQubitGateErrorMap {
         }
     }
 
-    fn add_qubit(&mut self, error_map: HashMap<String, f64>) {
+    pub fn add_qubit(&mut self, error_map: HashMap<String, f64>) {
         self.error_map.push(error_map);
     }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl OneQubitGateErrorMap {
+    #[new]
+    fn py_new(num_qubits: Option<usize>) -> Self {
+        OneQubitGateErrorMap::new(num_qubits)
+    }
+
+    #[pyo3(name = "add_qubit")]
+    fn py_add_qubit(&mut self, error_map: HashMap<String, f64>) {
+        self.add_qubit(error_map)
+    }
 
     fn __getstate__(&self) -> Vec<HashMap<String, f64>> {
         self.error_map.clone()
@@ -65,24 +96,64 @@ impl OneQubitGateErrorMap {
     }
 }
 
-#[pyclass(sequence)]
+#[cfg_attr(feature = "python", pyclass(sequence))]
 pub struct OneQubitGateSequence {
     pub gates: Vec<(String, SmallVec<[f64; 3]>)>,
-    #[pyo3(get)]
     pub global_phase: f64,
 }
 
+#[cfg(feature = "python")]
 type OneQubitGateSequenceState = (Vec<(String, SmallVec<[f64; 3]>)>, f64);
 
-#[pymethods]
 impl OneQubitGateSequence {
-    #[new]
-    fn new() -> Self {
+    pub fn new() -> Self {
         OneQubitGateSequence {
             gates: Vec::new(),
             global_phase: 0.,
         }
     }
+}
+
+#[cfg(feature = "python")]
+impl OneQubitGateSequence {
+    /// Build the one-qubit `CircuitData` of the sequence, with its global phase.
+    pub fn to_circuit_data(&self, py: Python) -> PyResult<CircuitData> {
+        let gates = self
+            .gates
+            .iter()
+            .map(|(name, params)| {
+                StandardGate::from_name(name)
+                    .map(|gate| (gate, params.to_vec(), vec![0]))
+                    .ok_or_else(|| {
+                        AccelerateError::Value(format!("'{name}' is not a standard gate"))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut data = CircuitData::from_standard_gates(py, 1, gates)?;
+        data.add_global_phase(py, &Param::Float(self.global_phase))?;
+        Ok(data)
+    }
+}
+
+impl Default for OneQubitGateSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl OneQubitGateSequence {
+    #[new]
+    fn py_new() -> Self {
+        OneQubitGateSequence::new()
+    }
+
+    #[getter]
+    fn global_phase(&self) -> f64 {
+        self.global_phase
+    }
+
     fn __getstate__(&self) -> OneQubitGateSequenceState {
         (self.gates.clone(), self.global_phase)
     }
@@ -182,7 +253,7 @@ fn circuit_kak(
         lam -= phi;
         phi = 0.;
     }
-    if mod_2pi(lam + PI, atol).abs() < atol || mod_2pi(phi + PI, atol).abs() < atol {
+    if is_zero_mod_2pi(lam + PI, atol) || is_zero_mod_2pi(phi + PI, atol) {
         lam += PI;
         theta = -theta;
         phi += PI;
@@ -341,7 +412,7 @@ where
         phi -= lam;
         lam = 0.;
     }
-    if mod_2pi(lam + PI, atol).abs() < atol || mod_2pi(phi, atol).abs() < atol {
+    if is_zero_mod_2pi(lam + PI, atol) || is_zero_mod_2pi(phi, atol) {
         lam += PI;
         theta = -theta;
         phi += PI;
@@ -356,7 +427,7 @@ where
     // emit circuit
     pfun(&mut circuit, lam);
     match xpifun {
-        Some(xpifun) if mod_2pi(theta, atol).abs() < atol => xpifun(&mut circuit),
+        Some(xpifun) if is_zero_mod_2pi(theta, atol) => xpifun(&mut circuit),
         _ => {
             xfun(&mut circuit);
             pfun(&mut circuit, theta);
@@ -384,7 +455,7 @@ fn circuit_rr(
         atol = -1.0;
     }
 
-    if mod_2pi((phi + lam) / 2., atol).abs() < atol {
+    if is_zero_mod_2pi((phi + lam) / 2., atol) {
         // This can be expressed as a single R gate
         if theta.abs() > atol {
             circuit.push((
@@ -412,6 +483,7 @@ fn circuit_rr(
     }
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn generate_circuit(
     target_basis: &EulerBasis,
@@ -422,7 +494,27 @@ pub fn generate_circuit(
     simplify: bool,
     atol: Option<f64>,
 ) -> PyResult<OneQubitGateSequence> {
-    let res = match target_basis {
+    Ok(generate_circuit_inner(
+        target_basis,
+        theta,
+        phi,
+        lam,
+        phase,
+        simplify,
+        atol,
+    ))
+}
+
+pub fn generate_circuit_inner(
+    target_basis: &EulerBasis,
+    theta: f64,
+    phi: f64,
+    lam: f64,
+    phase: f64,
+    simplify: bool,
+    atol: Option<f64>,
+) -> OneQubitGateSequence {
+    match target_basis {
         EulerBasis::ZYZ => circuit_kak(theta, phi, lam, phase, "rz", "ry", simplify, atol),
         EulerBasis::ZXZ => circuit_kak(theta, phi, lam, phase, "rz", "rx", simplify, atol),
         EulerBasis::XZX => circuit_kak(theta, phi, lam, phase, "rx", "rz", simplify, atol),
@@ -554,12 +646,14 @@ pub fn generate_circuit(
             )
         }
         EulerBasis::RR => circuit_rr(theta, phi, lam, phase, simplify, atol),
-    };
-    Ok(res)
+    }
 }
 
 #[derive(Clone, Debug, Copy)]
-#[pyclass(module = "qiskit._accelerate.euler_one_qubit_decomposer")]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.euler_one_qubit_decomposer")
+)]
 pub enum EulerBasis {
     U321,
     U3,
@@ -594,6 +688,7 @@ impl EulerBasis {
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl EulerBasis {
     fn __reduce__(&self, py: Python) -> Py<PyAny> {
@@ -606,13 +701,12 @@ impl EulerBasis {
 
     #[new]
     pub fn __new__(input: &str) -> PyResult<Self> {
-        Self::from_str(input)
-            .map_err(|_| PyValueError::new_err(format!("Invalid target basis '{input}'")))
+        Ok(Self::from_str(input)?)
     }
 }
 
 impl FromStr for EulerBasis {
-    type Err = ();
+    type Err = AccelerateError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -628,7 +722,9 @@ impl FromStr for EulerBasis {
             "ZXZ" => Ok(EulerBasis::ZXZ),
             "XYX" => Ok(EulerBasis::XYX),
             "XZX" => Ok(EulerBasis::XZX),
-            _ => Err(()),
+            _ => Err(AccelerateError::Value(format!(
+                "Invalid target basis '{s}'"
+            ))),
         }
     }
 }
@@ -671,7 +767,7 @@ fn compare_error_fn(
     }
 }
 
-fn compute_error(
+pub fn compute_error(
     gates: &[(String, SmallVec<[f64; 3]>)],
     error_map: Option<&OneQubitGateErrorMap>,
     qubit: usize,
@@ -689,6 +785,7 @@ fn compute_error(
     }
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn compute_error_one_qubit_sequence(
     circuit: &OneQubitGateSequence,
@@ -698,6 +795,7 @@ pub fn compute_error_one_qubit_sequence(
     compute_error(&circuit.gates, error_map, qubit)
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn compute_error_list(
     circuit: Vec<(String, SmallVec<[f64; 3]>)>,
@@ -707,6 +805,7 @@ pub fn compute_error_list(
     compute_error(&circuit, error_map, qubit)
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(signature = (unitary, target_basis_list, qubit, error_map=None, simplify=true, atol=None))]
 pub fn unitary_to_gate_sequence(
@@ -719,7 +818,7 @@ pub fn unitary_to_gate_sequence(
 ) -> PyResult<Option<OneQubitGateSequence>> {
     let mut target_basis_vec: Vec<EulerBasis> = Vec::with_capacity(target_basis_list.len());
     for basis in target_basis_list {
-        let basis_enum = EulerBasis::__new__(basis.deref())?;
+        let basis_enum = EulerBasis::from_str(basis.deref())?;
         target_basis_vec.push(basis_enum)
     }
     let unitary_mat = unitary.as_array();
@@ -733,6 +832,25 @@ pub fn unitary_to_gate_sequence(
     ))
 }
 
+/// Decompose a one-qubit unitary like `unitary_to_gate_sequence`, but return the circuit of
+/// standard gates directly, with the global phase set on it.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (unitary, target_basis_list, qubit, error_map=None, simplify=true, atol=None))]
+pub fn unitary_to_circuit(
+    py: Python,
+    unitary: PyReadonlyArray2<Complex64>,
+    target_basis_list: Vec<PyBackedStr>,
+    qubit: usize,
+    error_map: Option<&OneQubitGateErrorMap>,
+    simplify: bool,
+    atol: Option<f64>,
+) -> PyResult<Option<CircuitData>> {
+    unitary_to_gate_sequence(unitary, target_basis_list, qubit, error_map, simplify, atol)?
+        .map(|sequence| sequence.to_circuit_data(py))
+        .transpose()
+}
+
 #[inline]
 pub fn unitary_to_gate_sequence_inner(
     unitary_mat: ArrayView2<Complex64>,
@@ -746,7 +864,7 @@ pub fn unitary_to_gate_sequence_inner(
         .iter()
         .map(|target_basis| {
             let [theta, phi, lam, phase] = angles_from_unitary(unitary_mat, *target_basis);
-            generate_circuit(target_basis, theta, phi, lam, phase, simplify, atol).unwrap()
+            generate_circuit_inner(target_basis, theta, phi, lam, phase, simplify, atol)
         })
         .min_by(|a, b| {
             let error_a = compare_error_fn(a, &error_map, qubit);
@@ -760,20 +878,6 @@ pub fn det_one_qubit(mat: ArrayView2<Complex64>) -> Complex64 {
     mat[[0, 0]] * mat[[1, 1]] - mat[[0, 1]] * mat[[1, 0]]
 }
 
-/// Wrap angle into interval [-π,π). If within atol of the endpoint, clamp to -π
-#[inline]
-fn mod_2pi(angle: f64, atol: f64) -> f64 {
-    // f64::rem_euclid() isn't exactly the same as Python's % operator, but because
-    // the RHS here is a constant and positive it is effectively equivalent for
-    // this case
-    let wrapped = (angle + PI).rem_euclid(2. * PI) - PI;
-    if (wrapped - PI).abs() < atol {
-        -PI
-    } else {
-        wrapped
-    }
-}
-
 fn params_zyz_inner(mat: ArrayView2<Complex64>) -> [f64; 4] {
     let det_arg = det_one_qubit(mat).arg();
     let phase = 0.5 * det_arg;
@@ -790,6 +894,7 @@ fn params_zxz_inner(mat: ArrayView2<Complex64>) -> [f64; 4] {
     [theta, phi + PI / 2., lam - PI / 2., phase]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_zyz(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let mat = unitary.as_array();
@@ -805,6 +910,7 @@ fn params_u3_inner(mat: ArrayView2<Complex64>) -> [f64; 4] {
     [theta, phi, lam, phase - 0.5 * (phi + lam)]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_u3(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let mat = unitary.as_array();
@@ -819,6 +925,7 @@ fn params_u1x_inner(mat: ArrayView2<Complex64>) -> [f64; 4] {
     [theta, phi, lam, phase - 0.5 * (theta + phi + lam)]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_u1x(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let mat = unitary.as_array();
@@ -847,6 +954,7 @@ fn params_xyx_inner(mat: ArrayView2<Complex64>) -> [f64; 4] {
     ]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_xyx(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let mat = unitary.as_array();
@@ -871,18 +979,21 @@ fn params_xzx_inner(umat: ArrayView2<Complex64>) -> [f64; 4] {
     [theta, phi, lam, phase + phase_zxz]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_xzx(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let umat = unitary.as_array();
     params_xzx_inner(umat)
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn params_zxz(unitary: PyReadonlyArray2<Complex64>) -> [f64; 4] {
     let mat = unitary.as_array();
     params_zxz_inner(mat)
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn euler_one_qubit_decomposer(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(params_zyz))?;
@@ -893,6 +1004,7 @@ pub fn euler_one_qubit_decomposer(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(params_u1x))?;
     m.add_wrapped(wrap_pyfunction!(generate_circuit))?;
     m.add_wrapped(wrap_pyfunction!(unitary_to_gate_sequence))?;
+    m.add_wrapped(wrap_pyfunction!(unitary_to_circuit))?;
     m.add_wrapped(wrap_pyfunction!(compute_error_one_qubit_sequence))?;
     m.add_wrapped(wrap_pyfunction!(compute_error_list))?;
     m.add_class::<OneQubitGateSequence>()?;
@@ -0,0 +1,770 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! State and process fidelities.  These are the kernels behind `qiskit.quantum_info.state_fidelity`
+//! and `process_fidelity`; the mixed-state fidelity needs a matrix square root, which is taken
+//! through the singular values as in Python, or through the cheaper Hermitian eigendecomposition
+//! when the matrix is Hermitian.  For calibration analysis there are also the errors of noisy
+//! channels with respect to an ideal unitary gate, which only need traces of products, and the
+//! unitarity of a channel.
+
+#[cfg(feature = "python")]
+use numpy::{PyReadonlyArray1, PyReadonlyArray2, PyReadonlyArray3};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use faer::Side;
+use faer_ext::{IntoFaerComplex, IntoNdarrayComplex};
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::operator::matmul_inner;
+#[cfg(feature = "python")]
+use crate::statevector::{operation_matrix, OperationMatrix};
+use crate::threading::{self, ParallelKernel};
+
+fn check_same_square(
+    left: &ArrayView2<Complex64>,
+    right: &ArrayView2<Complex64>,
+) -> Result<(), AccelerateError> {
+    if left.nrows() != left.ncols() || left.shape() != right.shape() {
+        return Err(AccelerateError::Qiskit(format!(
+            "expected two square matrices of the same shape, but got shapes {:?} and {:?}",
+            left.shape(),
+            right.shape()
+        )));
+    }
+    Ok(())
+}
+
+/// The eigenvalues of the Hermitian matrix `mat`, in ascending order.  Only the lower triangle of
+/// `mat` is read.
+pub fn hermitian_eigenvalues(mat: ArrayView2<Complex64>) -> Vec<f64> {
+    let mut evals = mat.into_faer_complex().selfadjoint_eigenvalues(Side::Lower);
+    evals.sort_unstable_by(|a, b| a.total_cmp(b));
+    evals
+}
+
+/// The principal square root of the positive-semidefinite Hermitian matrix `mat`.  Numerically
+/// negative eigenvalues are clipped to zero.
+pub fn sqrtm_psd(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let eig = mat
+        .into_faer_complex()
+        .selfadjoint_eigendecomposition(Side::Lower);
+    let u = eig.u().into_ndarray_complex();
+    let sqrt_s = eig
+        .s()
+        .column_vector()
+        .iter()
+        .map(|s| s.re.max(0.).sqrt())
+        .collect::<Vec<_>>();
+    let mut u_sqrt_s = u.to_owned();
+    for (mut col, s) in u_sqrt_s.columns_mut().into_iter().zip(sqrt_s) {
+        col.mapv_inplace(|x| x * s);
+    }
+    u_sqrt_s.dot(&u.t().mapv(|x| x.conj()))
+}
+
+/// The largest difference between an element of a matrix and the conjugate of its transpose for
+/// the matrix to take the Hermitian path of `sqrtm_svd`.
+const HERMITIAN_ATOL: f64 = 1e-12;
+
+/// Whether `mat` is square and equal to its conjugate transpose, to within `HERMITIAN_ATOL`.
+pub fn is_hermitian(mat: ArrayView2<Complex64>) -> bool {
+    let dim = mat.nrows();
+    dim == mat.ncols()
+        && (0..dim)
+            .all(|i| (0..=i).all(|j| (mat[[i, j]] - mat[[j, i]].conj()).norm() <= HERMITIAN_ATOL))
+}
+
+/// The square root `U sqrt(S) V^†` of the square matrix `mat` with the singular-value
+/// decomposition `U S V^†`, which is the principal square root if `mat` is positive semidefinite.
+/// This is what `qiskit.quantum_info.states.utils._funm_svd` computes in Python.
+///
+/// A Hermitian matrix takes the cheaper path through its eigendecomposition, in `sqrtm_eigh`.
+pub fn sqrtm_svd(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    if is_hermitian(mat) {
+        return sqrtm_eigh(mat);
+    }
+    let svd = mat.into_faer_complex().svd();
+    let sqrt_s = svd
+        .s_diagonal()
+        .iter()
+        .map(|s| s.re.sqrt())
+        .collect::<Vec<_>>();
+    let v = svd.v().into_ndarray_complex();
+    scale_columns_dot(svd.u().into_ndarray_complex(), &sqrt_s, v)
+}
+
+/// The square root `U sqrt(S) V^†` of the Hermitian matrix `mat` with the eigendecomposition
+/// `W Λ W^†`, which is `W sign(Λ) sqrt(|Λ|) W^†`.  Numerically negative eigenvalues give the same
+/// result as in the SVD, rather than being clipped.  Only the lower triangle of `mat` is read.
+fn sqrtm_eigh(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let eig = mat
+        .into_faer_complex()
+        .selfadjoint_eigendecomposition(Side::Lower);
+    let signed_sqrt_s = eig
+        .s()
+        .column_vector()
+        .iter()
+        .map(|s| s.re.signum() * s.re.abs().sqrt())
+        .collect::<Vec<_>>();
+    let u = eig.u().into_ndarray_complex();
+    scale_columns_dot(u, &signed_sqrt_s, u)
+}
+
+/// The product `U diag(s) V^†`.
+fn scale_columns_dot(
+    u: ArrayView2<Complex64>,
+    s: &[f64],
+    v: ArrayView2<Complex64>,
+) -> Array2<Complex64> {
+    let mut u_s = u.to_owned();
+    for (mut col, s) in u_s.columns_mut().into_iter().zip(s.iter().copied()) {
+        col.mapv_inplace(|x| x * s);
+    }
+    u_s.dot(&v.t().mapv(|x| x.conj()))
+}
+
+/// The nuclear norm of `mat`, which is the sum of its singular values.
+pub fn nuclear_norm(mat: ArrayView2<Complex64>) -> f64 {
+    mat.into_faer_complex().singular_values().into_iter().sum()
+}
+
+/// The fidelity `|<b|a>|^2` of two pure states.
+pub fn statevector_fidelity_inner(
+    a: ArrayView1<Complex64>,
+    b: ArrayView1<Complex64>,
+) -> Result<f64, AccelerateError> {
+    if a.len() != b.len() {
+        return Err(AccelerateError::Qiskit(format!(
+            "statevectors of different dimensions ({} != {})",
+            a.len(),
+            b.len()
+        )));
+    }
+    Ok(b.iter()
+        .zip(a.iter())
+        .map(|(b, a)| b.conj() * a)
+        .sum::<Complex64>()
+        .norm_sqr())
+}
+
+/// The fidelity `<psi|rho|psi>` of a pure and a mixed state.
+pub fn statevector_density_matrix_fidelity_inner(
+    psi: ArrayView1<Complex64>,
+    rho: ArrayView2<Complex64>,
+) -> Result<f64, AccelerateError> {
+    if rho.shape() != [psi.len(), psi.len()] {
+        return Err(AccelerateError::Qiskit(format!(
+            "a statevector of dimension {} and a density matrix of shape {:?} are incompatible",
+            psi.len(),
+            rho.shape()
+        )));
+    }
+    Ok(psi.mapv(|x| x.conj()).dot(&rho.dot(&psi)).re)
+}
+
+/// The fidelity `Tr[sqrt(sqrt(rho1) rho2 sqrt(rho1))]^2` of two mixed states, computed as
+/// `||sqrt(rho1) sqrt(rho2)||_1^2`.  The matrices aren't required to be Hermitian, in which case
+/// the result is the same as that of the SVD-based computation in Python.
+pub fn density_matrix_fidelity_inner(
+    rho1: ArrayView2<Complex64>,
+    rho2: ArrayView2<Complex64>,
+) -> Result<f64, AccelerateError> {
+    check_same_square(&rho1, &rho2)?;
+    let product = matmul_inner(sqrtm_svd(rho1).view(), sqrtm_svd(rho2).view())?;
+    let trace_sqrt = nuclear_norm(product.view());
+    Ok(trace_sqrt * trace_sqrt)
+}
+
+/// The process fidelity of two channels given by their (unnormalized) Choi matrices, for channels
+/// with input dimension `input_dim`.  This is the state fidelity of the normalized Choi matrices.
+pub fn process_fidelity_inner(
+    choi1: ArrayView2<Complex64>,
+    choi2: ArrayView2<Complex64>,
+    input_dim: usize,
+) -> Result<f64, AccelerateError> {
+    let scale = Complex64::new(1. / input_dim as f64, 0.);
+    density_matrix_fidelity_inner(
+        choi1.mapv(|x| x * scale).view(),
+        choi2.mapv(|x| x * scale).view(),
+    )
+}
+
+/// Convert a process fidelity of a channel of dimension `dim` to the average gate fidelity.
+#[inline]
+pub fn average_gate_fidelity_inner(process_fidelity: f64, dim: usize) -> f64 {
+    let dim = dim as f64;
+    (dim * process_fidelity + 1.) / (dim + 1.)
+}
+
+/// The fidelities of pairs of mixed states.  `rho1` and `rho2` are stacks of density matrices with
+/// the pairs along the first axis.
+pub fn density_matrix_fidelities_inner(
+    rho1: ArrayView3<Complex64>,
+    rho2: ArrayView3<Complex64>,
+) -> Result<Vec<f64>, AccelerateError> {
+    if rho1.shape() != rho2.shape() {
+        return Err(AccelerateError::Qiskit(format!(
+            "batches of different shapes ({:?} != {:?})",
+            rho1.shape(),
+            rho2.shape()
+        )));
+    }
+    let pairs = rho1.outer_iter().zip(rho2.outer_iter()).collect::<Vec<_>>();
+    if threading::use_multiple_threads() {
+        threading::install(|| {
+            pairs
+                .into_par_iter()
+                .map(|(a, b)| density_matrix_fidelity_inner(a, b))
+                .collect()
+        })
+    } else {
+        pairs
+            .into_iter()
+            .map(|(a, b)| density_matrix_fidelity_inner(a, b))
+            .collect()
+    }
+}
+
+fn check_superop_dim(superop: &ArrayView2<Complex64>) -> Result<usize, AccelerateError> {
+    let dim = (superop.nrows() as f64).sqrt().round() as usize;
+    if superop.nrows() != superop.ncols() || dim * dim != superop.nrows() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} is not the superoperator of a channel",
+            superop.shape()
+        )));
+    }
+    Ok(dim)
+}
+
+fn check_ideal(ideal: &ArrayView2<Complex64>, dim: usize) -> Result<(), AccelerateError> {
+    if ideal.shape() != [dim, dim] {
+        return Err(AccelerateError::Qiskit(format!(
+            "an ideal gate of shape {:?} does not match a channel of dimension {dim}",
+            ideal.shape()
+        )));
+    }
+    Ok(())
+}
+
+/// The process fidelity of the channel with (column-stacking) superoperator `superop` to the
+/// unitary `ideal`.
+///
+/// For a unitary target this is `Tr[S_U† S] / d^2`, where `S_U = conj(U) ⊗ U`, so we never need
+/// the Choi matrix or a matrix square root.
+pub fn gate_process_fidelity_inner(
+    ideal: ArrayView2<Complex64>,
+    superop: ArrayView2<Complex64>,
+) -> Result<f64, AccelerateError> {
+    let dim = check_superop_dim(&superop)?;
+    check_ideal(&ideal, dim)?;
+    // `S_U[(a1, a0), (b1, b0)] = conj(U[a1, b1]) U[a0, b0]`, with the first index the slower one.
+    let overlap = |((row, col), s): ((usize, usize), &Complex64)| {
+        let (a1, a0) = (row / dim, row % dim);
+        let (b1, b0) = (col / dim, col % dim);
+        ideal[[a1, b1]] * ideal[[a0, b0]].conj() * s
+    };
+    let parallel = threading::run_in_parallel(ParallelKernel::Fidelity, superop.nrows());
+    let total: Complex64 = if parallel {
+        threading::install(|| {
+            superop
+                .indexed_iter()
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(overlap)
+                .sum()
+        })
+    } else {
+        superop.indexed_iter().map(overlap).sum()
+    };
+    Ok(total.re / (dim * dim) as f64)
+}
+
+/// The process fidelity of the channel with Kraus operators `kraus` to the unitary `ideal`, which
+/// is `Σ_i |Tr[U† K_i]|^2 / d^2`.
+pub fn gate_process_fidelity_kraus_inner(
+    ideal: ArrayView2<Complex64>,
+    kraus: &[ArrayView2<Complex64>],
+) -> Result<f64, AccelerateError> {
+    let dim = ideal.nrows();
+    let mut total = 0.;
+    for op in kraus {
+        check_same_square(&ideal, op)?;
+        total += ideal
+            .iter()
+            .zip(op.iter())
+            .map(|(u, k)| u.conj() * k)
+            .sum::<Complex64>()
+            .norm_sqr();
+    }
+    Ok(total / (dim * dim) as f64)
+}
+
+/// The gate error `1 - F_avg` of the channel with superoperator `superop` with respect to the
+/// unitary `ideal`.
+pub fn gate_error_inner(
+    ideal: ArrayView2<Complex64>,
+    superop: ArrayView2<Complex64>,
+) -> Result<f64, AccelerateError> {
+    let f_pro = gate_process_fidelity_inner(ideal, superop)?;
+    Ok(1. - average_gate_fidelity_inner(f_pro, ideal.nrows()))
+}
+
+/// The unitarity of the channel with (column-stacking) superoperator `superop`, as defined by
+/// Wallman et al. (2015): the average purity of the output of the channel on traceless inputs,
+/// with the identity component removed from the output.  This is `1` exactly for unitary channels
+/// and `(1 - p)^2` for a depolarizing channel with depolarizing probability `p`.
+///
+/// Writing `Π` for the projector onto `vec(I)`, this is `||(1 - Π) S (1 - Π)||_F^2 / (d^2 - 1)`.
+pub fn unitarity_inner(superop: ArrayView2<Complex64>) -> Result<f64, AccelerateError> {
+    let dim = check_superop_dim(&superop)?;
+    if dim < 2 {
+        return Err(AccelerateError::Value(
+            "unitarity is not defined for channels of dimension 1".to_string(),
+        ));
+    }
+    // The positions of the non-zero entries of `vec(I)`.
+    let diag = (0..dim).map(|i| i * (dim + 1)).collect::<Vec<_>>();
+    let scale = 1. / dim as f64;
+    // `vec(I)† S / d`, `S vec(I) / d` and `vec(I)† S vec(I) / d^2`.
+    let row = diag
+        .iter()
+        .fold(Array1::<Complex64>::zeros(superop.ncols()), |acc, &i| {
+            acc + superop.row(i)
+        })
+        * scale;
+    let col = diag
+        .iter()
+        .fold(Array1::<Complex64>::zeros(superop.nrows()), |acc, &j| {
+            acc + superop.column(j)
+        })
+        * scale;
+    let corner = diag.iter().map(|&j| row[j]).sum::<Complex64>() * scale;
+    let mut is_diag = vec![false; superop.nrows()];
+    diag.iter().for_each(|&i| is_diag[i] = true);
+    let norm_sqr: f64 = superop
+        .indexed_iter()
+        .map(|((i, j), s)| {
+            let mut value = *s;
+            if is_diag[i] {
+                value -= row[j];
+            }
+            if is_diag[j] {
+                value -= col[i];
+            }
+            if is_diag[i] && is_diag[j] {
+                value += corner;
+            }
+            value.norm_sqr()
+        })
+        .sum();
+    Ok(norm_sqr / (dim * dim - 1) as f64)
+}
+
+/// Compute the fidelity of two pure states.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(a, b, /)")]
+pub fn statevector_fidelity(
+    a: PyReadonlyArray1<Complex64>,
+    b: PyReadonlyArray1<Complex64>,
+) -> PyResult<f64> {
+    Ok(statevector_fidelity_inner(a.as_array(), b.as_array())?)
+}
+
+/// Compute the fidelity of a pure state and a density matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(psi, rho, /)")]
+pub fn statevector_density_matrix_fidelity(
+    psi: PyReadonlyArray1<Complex64>,
+    rho: PyReadonlyArray2<Complex64>,
+) -> PyResult<f64> {
+    Ok(statevector_density_matrix_fidelity_inner(
+        psi.as_array(),
+        rho.as_array(),
+    )?)
+}
+
+/// Compute the fidelity of two density matrices.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(rho1, rho2, /)")]
+pub fn density_matrix_fidelity(
+    rho1: PyReadonlyArray2<Complex64>,
+    rho2: PyReadonlyArray2<Complex64>,
+) -> PyResult<f64> {
+    Ok(density_matrix_fidelity_inner(
+        rho1.as_array(),
+        rho2.as_array(),
+    )?)
+}
+
+/// Compute the fidelities of pairs of density matrices.
+///
+/// Args:
+///     rho1 (ndarray): a stack of density matrices, with shape ``(n, d, d)``.
+///     rho2 (ndarray): a stack of density matrices of the same shape as ``rho1``.
+///
+/// Returns:
+///     list[float]: the fidelity of each pair ``(rho1[i], rho2[i])``.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(rho1, rho2, /)")]
+pub fn density_matrix_fidelities(
+    rho1: PyReadonlyArray3<Complex64>,
+    rho2: PyReadonlyArray3<Complex64>,
+) -> PyResult<Vec<f64>> {
+    Ok(density_matrix_fidelities_inner(
+        rho1.as_array(),
+        rho2.as_array(),
+    )?)
+}
+
+/// Compute the process fidelity of two channels from their Choi matrices.
+///
+/// Args:
+///     choi1 (ndarray): the Choi matrix of the first channel.
+///     choi2 (ndarray): the Choi matrix of the second channel.
+///     input_dim (int): the input dimension of the channels.
+///
+/// Returns:
+///     float: the process fidelity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(choi1, choi2, input_dim, /)")]
+pub fn process_fidelity(
+    choi1: PyReadonlyArray2<Complex64>,
+    choi2: PyReadonlyArray2<Complex64>,
+    input_dim: usize,
+) -> PyResult<f64> {
+    Ok(process_fidelity_inner(
+        choi1.as_array(),
+        choi2.as_array(),
+        input_dim,
+    )?)
+}
+
+/// Convert a process fidelity to an average gate fidelity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(process_fidelity, dim, /)")]
+pub fn average_gate_fidelity(process_fidelity: f64, dim: usize) -> f64 {
+    average_gate_fidelity_inner(process_fidelity, dim)
+}
+
+/// Extract the matrix of an ideal gate, which may be given either as a matrix or as a gate object.
+/// Standard gates are built from their Rust-space matrices.
+#[cfg(feature = "python")]
+fn ideal_matrix(ideal: &Bound<PyAny>) -> PyResult<Array2<Complex64>> {
+    if let Ok(mat) = ideal.extract::<PyReadonlyArray2<Complex64>>() {
+        return Ok(mat.as_array().to_owned());
+    }
+    match operation_matrix(ideal)? {
+        OperationMatrix::Matrix(mat) => Ok(mat),
+        _ => Err(AccelerateError::Value(
+            "the ideal gate must be a matrix or a gate with a matrix definition".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Compute the process fidelity of a noisy channel to an ideal unitary gate.
+///
+/// Args:
+///     ideal (Gate | ndarray): the ideal gate, either as a gate object (standard gates don't need
+///         to go through Python to build their matrices) or as its unitary matrix.
+///     superop (ndarray): the column-stacking superoperator of the noisy channel, as in
+///         ``SuperOp.data``.
+///
+/// Returns:
+///     float: the process fidelity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(ideal, superop, /)")]
+pub fn gate_process_fidelity(
+    ideal: &Bound<PyAny>,
+    superop: PyReadonlyArray2<Complex64>,
+) -> PyResult<f64> {
+    let ideal = ideal_matrix(ideal)?;
+    Ok(gate_process_fidelity_inner(
+        ideal.view(),
+        superop.as_array(),
+    )?)
+}
+
+/// Compute the process fidelity of a noisy channel given by Kraus operators to an ideal unitary
+/// gate.
+///
+/// Args:
+///     ideal (Gate | ndarray): the ideal gate, as a gate object or as its unitary matrix.
+///     kraus (list[ndarray]): the Kraus operators of the noisy channel.  A noisy unitary is a
+///         single Kraus operator.
+///
+/// Returns:
+///     float: the process fidelity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(ideal, kraus, /)")]
+pub fn gate_process_fidelity_kraus(
+    ideal: &Bound<PyAny>,
+    kraus: Vec<PyReadonlyArray2<Complex64>>,
+) -> PyResult<f64> {
+    let ideal = ideal_matrix(ideal)?;
+    let kraus = kraus.iter().map(|k| k.as_array()).collect::<Vec<_>>();
+    Ok(gate_process_fidelity_kraus_inner(ideal.view(), &kraus)?)
+}
+
+/// Compute the average gate error ``1 - F_avg`` of a noisy channel with respect to an ideal
+/// unitary gate.
+///
+/// Args:
+///     ideal (Gate | ndarray): the ideal gate, as a gate object or as its unitary matrix.
+///     superop (ndarray): the column-stacking superoperator of the noisy channel.
+///
+/// Returns:
+///     float: the gate error.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(ideal, superop, /)")]
+pub fn gate_error(ideal: &Bound<PyAny>, superop: PyReadonlyArray2<Complex64>) -> PyResult<f64> {
+    let ideal = ideal_matrix(ideal)?;
+    Ok(gate_error_inner(ideal.view(), superop.as_array())?)
+}
+
+/// Compute the unitarity of a channel.
+///
+/// Args:
+///     superop (ndarray): the column-stacking superoperator of the channel.
+///
+/// Returns:
+///     float: the unitarity, which is 1 for unitary channels and decreases with incoherent noise.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(superop, /)")]
+pub fn unitarity(superop: PyReadonlyArray2<Complex64>) -> PyResult<f64> {
+    Ok(unitarity_inner(superop.as_array())?)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn fidelity(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(statevector_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(statevector_density_matrix_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(density_matrix_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(density_matrix_fidelities))?;
+    m.add_wrapped(wrap_pyfunction!(process_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(average_gate_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(gate_process_fidelity))?;
+    m.add_wrapped(wrap_pyfunction!(gate_process_fidelity_kraus))?;
+    m.add_wrapped(wrap_pyfunction!(gate_error))?;
+    m.add_wrapped(wrap_pyfunction!(unitarity))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use ndarray::linalg::kron;
+
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+
+    // The SVD path is checked against Python's `_funm_svd` from Python space; these tests check
+    // that the Hermitian path agrees with the SVD.
+
+    fn svd_sqrtm(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+        let svd = mat.into_faer_complex().svd();
+        let sqrt_s = svd
+            .s_diagonal()
+            .iter()
+            .map(|s| s.re.sqrt())
+            .collect::<Vec<_>>();
+        let v = svd.v().into_ndarray_complex();
+        scale_columns_dot(svd.u().into_ndarray_complex(), &sqrt_s, v)
+    }
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    #[test]
+    fn test_is_hermitian() {
+        let herm = array![[c(1., 0.), c(0., 2.)], [c(0., -2.), c(-1., 0.)]];
+        assert!(is_hermitian(herm.view()));
+        let nonherm = array![[c(1., 0.), c(0., 2.)], [c(0., 2.), c(-1., 0.)]];
+        assert!(!is_hermitian(nonherm.view()));
+        let nearly = array![[c(1., 0.), c(0., 2.)], [c(1e-9, -2.), c(-1., 0.)]];
+        assert!(!is_hermitian(nearly.view()));
+        let rectangular = Array2::<Complex64>::zeros((2, 3));
+        assert!(!is_hermitian(rectangular.view()));
+    }
+
+    #[test]
+    fn test_eigh_matches_svd_psd() {
+        let b = array![
+            [c(0.5, 0.1), c(0.2, -0.3), c(0., 0.4)],
+            [c(-0.1, 0.), c(0.7, 0.2), c(0.3, 0.3)],
+            [c(0.2, 0.2), c(0., -0.1), c(0.6, 0.)]
+        ];
+        let rho = b.dot(&b.t().mapv(|x| x.conj()));
+        let sqrt_rho = sqrtm_eigh(rho.view());
+        assert_abs_diff_eq!(sqrt_rho, svd_sqrtm(rho.view()), epsilon = 1e-12);
+        assert_abs_diff_eq!(sqrt_rho.dot(&sqrt_rho), rho, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_eigh_matches_svd_indefinite() {
+        // The eigenvalues are 3 and -1; the SVD doesn't clip the negative one.
+        let mat = array![[c(1., 0.), c(0., 2.)], [c(0., -2.), c(1., 0.)]];
+        assert_abs_diff_eq!(
+            sqrtm_eigh(mat.view()),
+            svd_sqrtm(mat.view()),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_fidelity_pure_states() {
+        let plus = array![[c(0.5, 0.), c(0.5, 0.)], [c(0.5, 0.), c(0.5, 0.)]];
+        let zero = array![[c(1., 0.), c(0., 0.)], [c(0., 0.), c(0., 0.)]];
+        let fid = density_matrix_fidelity_inner(plus.view(), zero.view()).unwrap();
+        assert!((fid - 0.5).abs() < 1e-12);
+        let fid = density_matrix_fidelity_inner(plus.view(), plus.view()).unwrap();
+        assert!((fid - 1.).abs() < 1e-12);
+    }
+
+    /// The superoperator `Σ_i conj(K_i) ⊗ K_i` of the channel with Kraus operators `kraus`.
+    fn superop(kraus: &[&Array2<Complex64>]) -> Array2<Complex64> {
+        let dim = kraus[0].nrows();
+        kraus
+            .iter()
+            .fold(Array2::zeros((dim * dim, dim * dim)), |acc, k| {
+                acc + kron(&k.mapv(|x| x.conj()), k)
+            })
+    }
+
+    /// The superoperator of the unitary `unitary` followed by a depolarizing channel with
+    /// probability `p`.
+    fn depolarized(unitary: &Array2<Complex64>, p: f64) -> Array2<Complex64> {
+        let dim = unitary.nrows();
+        let vec_identity = Array1::from_shape_fn(dim * dim, |i| {
+            c(if i % (dim + 1) == 0 { 1. } else { 0. }, 0.)
+        });
+        let projector = Array2::from_shape_fn((dim * dim, dim * dim), |(i, j)| {
+            vec_identity[i] * vec_identity[j]
+        });
+        superop(&[unitary]) * c(1. - p, 0.) + projector * c(p / dim as f64, 0.)
+    }
+
+    #[test]
+    fn test_gate_process_fidelity_unitary() {
+        let unitary = random_unitary_inner(4, &mut generator(1));
+        let fid = gate_process_fidelity_inner(unitary.view(), superop(&[&unitary]).view());
+        assert!((fid.unwrap() - 1.).abs() < 1e-12);
+        // A different unitary has fidelity `|Tr[U† V]|^2 / d^2`.
+        let other = random_unitary_inner(4, &mut generator(2));
+        let overlap = unitary
+            .iter()
+            .zip(other.iter())
+            .map(|(u, v)| u.conj() * v)
+            .sum::<Complex64>()
+            .norm_sqr()
+            / 16.;
+        let fid = gate_process_fidelity_inner(unitary.view(), superop(&[&other]).view());
+        assert!((fid.unwrap() - overlap).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gate_process_fidelity_depolarizing() {
+        for (dim, p) in [(2, 0.1), (4, 0.25), (8, 0.02)] {
+            let unitary = random_unitary_inner(dim, &mut generator(dim as u128));
+            let noisy = depolarized(&unitary, p);
+            let expected = 1. - p + p / (dim * dim) as f64;
+            let fid = gate_process_fidelity_inner(unitary.view(), noisy.view()).unwrap();
+            assert!((fid - expected).abs() < 1e-12, "{fid} != {expected}");
+            let f_avg = (dim as f64 * expected + 1.) / (dim + 1) as f64;
+            let error = gate_error_inner(unitary.view(), noisy.view()).unwrap();
+            assert!((error - (1. - f_avg)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_gate_process_fidelity_kraus_matches_superop() {
+        let unitary = random_unitary_inner(4, &mut generator(3));
+        let noise = random_unitary_inner(4, &mut generator(4));
+        let first = unitary.mapv(|x| x * 0.9_f64.sqrt());
+        let second = noise.mapv(|x| x * 0.1_f64.sqrt());
+        let from_kraus =
+            gate_process_fidelity_kraus_inner(unitary.view(), &[first.view(), second.view()])
+                .unwrap();
+        let from_superop =
+            gate_process_fidelity_inner(unitary.view(), superop(&[&first, &second]).view())
+                .unwrap();
+        assert!((from_kraus - from_superop).abs() < 1e-12);
+        let single = gate_process_fidelity_kraus_inner(unitary.view(), &[unitary.view()]);
+        assert!((single.unwrap() - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unitarity() {
+        let unitary = random_unitary_inner(4, &mut generator(5));
+        let value = unitarity_inner(superop(&[&unitary]).view()).unwrap();
+        assert!((value - 1.).abs() < 1e-12);
+        for p in [0., 0.1, 0.5, 1.] {
+            let value = unitarity_inner(depolarized(&unitary, p).view()).unwrap();
+            assert!(
+                (value - (1. - p).powi(2)).abs() < 1e-12,
+                "{value} at p = {p}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let unitary = random_unitary_inner(2, &mut generator(6));
+        let not_square = Array2::<Complex64>::zeros((4, 3));
+        let bad_dim = Array2::<Complex64>::zeros((3, 3));
+        for bad in [&not_square, &bad_dim] {
+            assert!(matches!(
+                gate_process_fidelity_inner(unitary.view(), bad.view()),
+                Err(AccelerateError::Qiskit(_))
+            ));
+            assert!(matches!(
+                unitarity_inner(bad.view()),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+        let wide = random_unitary_inner(4, &mut generator(7));
+        assert!(matches!(
+            gate_process_fidelity_inner(unitary.view(), superop(&[&wide]).view()),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            gate_process_fidelity_kraus_inner(unitary.view(), &[wide.view()]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let scalar = array![[c(1., 0.)]];
+        assert!(matches!(
+            unitarity_inner(scalar.view()),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+}
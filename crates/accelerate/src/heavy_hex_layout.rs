@@ -0,0 +1,501 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A layout heuristic for circuits whose interactions form chains or a ring, on heavy-hex devices.
+//!
+//! A heavy-hex lattice is a hexagonal lattice with an extra qubit on every edge, so its qubits
+//! alternate between degree-3 vertices of the hexagons and degree-2 qubits between them, and every
+//! ring of qubits is twice as long as a ring of the hexagonal lattice: a multiple of four, and at
+//! least twelve for a single unit cell.  On such a sparse graph, a depth-first search that always
+//! steps to the free qubit with the fewest free neighbors (Warnsdorff's rule) snakes along the
+//! rows of unit cells without cutting off the qubits behind it, so a long path or ring is found
+//! with little backtracking.  This gives a perfect layout for chains and rings in far less time
+//! than a general subgraph-isomorphism search on a device of a hundred or more qubits.
+
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::coupling_map::CouplingMap;
+#[cfg(feature = "python")]
+use crate::error::AccelerateError;
+use crate::instrumentation;
+use crate::nlayout::PhysicalQubit;
+
+/// Whether a coupling map has the local structure of a heavy-hex lattice: some qubits have three
+/// neighbors and none has more, no two degree-3 qubits are neighbors, and the graph is bipartite.
+/// Lines and rings, which have no degree-3 qubits, don't count.
+pub fn is_heavy_hex(coupling: &CouplingMap) -> bool {
+    let degree = |qubit: PhysicalQubit| coupling.undirected_neighbors(qubit).len();
+    if !(0..coupling.num_qubits() as u32).any(|qubit| degree(PhysicalQubit::new(qubit)) == 3) {
+        return false;
+    }
+    let mut color: Vec<Option<bool>> = vec![None; coupling.num_qubits()];
+    for start in 0..coupling.num_qubits() {
+        if color[start].is_some() {
+            continue;
+        }
+        color[start] = Some(false);
+        let mut stack = vec![PhysicalQubit::new(start as u32)];
+        while let Some(qubit) = stack.pop() {
+            if degree(qubit) > 3 {
+                return false;
+            }
+            let side = color[qubit.index()].unwrap();
+            for neighbor in coupling.undirected_neighbors(qubit) {
+                if degree(qubit) == 3 && degree(*neighbor) == 3 {
+                    return false;
+                }
+                match color[neighbor.index()] {
+                    Some(other) if other == side => return false,
+                    Some(_) => (),
+                    None => {
+                        color[neighbor.index()] = Some(!side);
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+        }
+    }
+    true
+}
+
+/// The interactions of a circuit split into connected components, each in order along the chain
+/// or around the ring it forms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InteractionShape {
+    /// The chains, including the qubits with no interactions as chains of one.
+    pub chains: Vec<Vec<usize>>,
+    /// The ring, if one component is a ring.
+    pub ring: Option<Vec<usize>>,
+}
+
+/// Split the interaction graph of `num_qubits` virtual qubits with the given `edges` into chains
+/// and at most one ring.  Returns `None` if any qubit interacts with more than two others or if
+/// there is more than one ring.
+pub fn interaction_shape(num_qubits: usize, edges: &[[usize; 2]]) -> Option<InteractionShape> {
+    let mut neighbors = vec![SmallVec::<[usize; 2]>::new(); num_qubits];
+    for &[a, b] in edges {
+        if a == b || neighbors[a].contains(&b) {
+            continue;
+        }
+        if neighbors[a].len() == 2 || neighbors[b].len() == 2 {
+            return None;
+        }
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+    let mut visited = vec![false; num_qubits];
+    let walk = |start: usize, visited: &mut [bool]| {
+        let mut out = vec![start];
+        visited[start] = true;
+        let mut current = start;
+        while let Some(&next) = neighbors[current].iter().find(|next| !visited[**next]) {
+            visited[next] = true;
+            out.push(next);
+            current = next;
+        }
+        out
+    };
+    let mut shape = InteractionShape::default();
+    // Chains are walked from an end, so they're found before the rings, which have no ends.
+    for qubit in 0..num_qubits {
+        if !visited[qubit] && neighbors[qubit].len() < 2 {
+            shape.chains.push(walk(qubit, &mut visited));
+        }
+    }
+    for qubit in 0..num_qubits {
+        if !visited[qubit] {
+            if shape.ring.is_some() {
+                return None;
+            }
+            shape.ring = Some(walk(qubit, &mut visited));
+        }
+    }
+    Some(shape)
+}
+
+/// A depth-first search for a simple path or ring of physical qubits.
+struct Search<'a> {
+    coupling: &'a CouplingMap,
+    free: Vec<bool>,
+    path: Vec<PhysicalQubit>,
+    steps: usize,
+    max_steps: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(coupling: &'a CouplingMap, free: Vec<bool>, max_steps: usize) -> Self {
+        Search {
+            coupling,
+            free,
+            path: Vec::new(),
+            steps: 0,
+            max_steps,
+        }
+    }
+
+    fn free_degree(&self, qubit: PhysicalQubit) -> usize {
+        self.coupling
+            .undirected_neighbors(qubit)
+            .iter()
+            .filter(|neighbor| self.free[neighbor.index()])
+            .count()
+    }
+
+    /// The free neighbors of `qubit` in the order to try them: fewest free neighbors first.
+    fn candidates(&self, qubit: PhysicalQubit) -> SmallVec<[PhysicalQubit; 4]> {
+        let mut out = self
+            .coupling
+            .undirected_neighbors(qubit)
+            .iter()
+            .copied()
+            .filter(|neighbor| self.free[neighbor.index()])
+            .collect::<SmallVec<[PhysicalQubit; 4]>>();
+        out.sort_by_key(|neighbor| (self.free_degree(*neighbor), *neighbor));
+        out
+    }
+
+    fn push(&mut self, qubit: PhysicalQubit) {
+        self.free[qubit.index()] = false;
+        self.path.push(qubit);
+    }
+
+    fn pop(&mut self) {
+        let qubit = self.path.pop().unwrap();
+        self.free[qubit.index()] = true;
+    }
+
+    /// Extend the current path to `length` qubits, closing it into a ring if `ring` is set.
+    fn extend(&mut self, length: usize, ring: bool) -> bool {
+        if self.steps >= self.max_steps {
+            return false;
+        }
+        self.steps += 1;
+        let last = *self.path.last().unwrap();
+        let first = self.path[0];
+        if self.path.len() == length {
+            return !ring || self.coupling.undirected_neighbors(last).contains(&first);
+        }
+        if ring {
+            // The path has to get back next to the start with the qubits it has left.
+            let remaining = length - self.path.len();
+            match self.coupling.distance(last, first) {
+                Some(distance) if distance <= remaining + 1 => (),
+                _ => return false,
+            }
+        }
+        for next in self.candidates(last) {
+            // Rings are only searched from their lowest qubit, so each is found once.
+            if ring && next < first {
+                continue;
+            }
+            self.push(next);
+            if self.extend(length, ring) {
+                return true;
+            }
+            self.pop();
+        }
+        false
+    }
+
+    /// Find a path or ring of `length` free qubits, trying the starting qubits with the fewest free
+    /// neighbors first.
+    fn find(&mut self, length: usize, ring: bool) -> Option<Vec<PhysicalQubit>> {
+        if length == 0 {
+            return Some(Vec::new());
+        }
+        let mut starts = (0..self.coupling.num_qubits() as u32)
+            .map(PhysicalQubit::new)
+            .filter(|qubit| self.free[qubit.index()])
+            .collect::<Vec<_>>();
+        if !ring {
+            starts.sort_by_key(|qubit| (self.free_degree(*qubit), *qubit));
+        }
+        for start in starts {
+            if self.steps >= self.max_steps {
+                break;
+            }
+            self.push(start);
+            if self.extend(length, ring) {
+                return Some(std::mem::take(&mut self.path));
+            }
+            self.pop();
+        }
+        None
+    }
+}
+
+/// Find a layout of the virtual qubits of a circuit whose interactions form chains and at most one
+/// ring, in which every interaction is between neighboring physical qubits.
+///
+/// The ring, if any, is placed first, and then all the chains end to end along one path of the
+/// remaining qubits.  Returns the physical qubit of each virtual qubit, or `None` if the
+/// interactions aren't chains and a ring, if the coupling map isn't heavy-hex, or if no layout was
+/// found within `max_steps` steps of the search.
+pub fn heavy_hex_layout_inner(
+    coupling: &CouplingMap,
+    num_qubits: usize,
+    edges: &[[usize; 2]],
+    max_steps: usize,
+) -> Option<Vec<PhysicalQubit>> {
+    let _timer = instrumentation::Timer::start("heavy_hex_layout");
+    if num_qubits > coupling.num_qubits() || !is_heavy_hex(coupling) {
+        return None;
+    }
+    let shape = interaction_shape(num_qubits, edges)?;
+    let mut search = Search::new(coupling, vec![true; coupling.num_qubits()], max_steps);
+    let mut layout = vec![PhysicalQubit::new(0); num_qubits];
+    if let Some(ring) = shape.ring.as_ref() {
+        if ring.len() % 2 != 0 {
+            return None;
+        }
+        let physical = search.find(ring.len(), true)?;
+        // The qubits of the ring stay taken for the search of the chains.
+        for (virt, phys) in ring.iter().zip(physical) {
+            layout[*virt] = phys;
+        }
+    }
+    let chain_length = shape.chains.iter().map(|chain| chain.len()).sum();
+    let physical = search.find(chain_length, false)?;
+    for (virt, phys) in shape.chains.iter().flatten().zip(physical) {
+        layout[*virt] = phys;
+    }
+    Some(layout)
+}
+
+/// Find a layout for a circuit whose two-qubit interactions form chains and at most one ring on a
+/// heavy-hex coupling map, with every interaction on an edge of the map.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     num_qubits (int): the number of virtual qubits of the circuit.
+///     edges (list[tuple[int, int]]): the pairs of virtual qubits that interact.
+///     max_steps (int): the most steps of the search before giving up.
+///
+/// Returns:
+///     list[int] | None: the physical qubit of each virtual qubit, or ``None`` if the heuristic
+///     doesn't apply or found no layout.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "heavy_hex_layout", signature = (coupling_map, num_qubits, edges, max_steps=100_000))]
+pub fn py_heavy_hex_layout(
+    coupling_map: &CouplingMap,
+    num_qubits: usize,
+    edges: Vec<[usize; 2]>,
+    max_steps: usize,
+) -> PyResult<Option<Vec<PhysicalQubit>>> {
+    if let Some(qubit) = edges.iter().flatten().find(|qubit| **qubit >= num_qubits) {
+        return Err(AccelerateError::Index(format!(
+            "virtual qubit {qubit} is out of range for {num_qubits} qubits"
+        ))
+        .into());
+    }
+    Ok(heavy_hex_layout_inner(
+        coupling_map,
+        num_qubits,
+        &edges,
+        max_steps,
+    ))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn heavy_hex_layout(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_heavy_hex_layout))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coupling_map(num_qubits: usize, edges: &[[u32; 2]]) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits,
+            edges
+                .iter()
+                .map(|[a, b]| [PhysicalQubit::new(*a), PhysicalQubit::new(*b)])
+                .collect(),
+        )
+    }
+
+    /// Two hexagons sharing an edge, with an extra qubit on each of the eleven edges: 21 qubits,
+    /// two rings of 12 and an outer ring of 20.
+    fn two_cells() -> CouplingMap {
+        let hexagons = [
+            [0, 1],
+            [1, 2],
+            [2, 3],
+            [3, 4],
+            [4, 5],
+            [5, 0],
+            [1, 6],
+            [6, 7],
+            [7, 8],
+            [8, 9],
+            [9, 0],
+        ];
+        let edges = hexagons
+            .iter()
+            .enumerate()
+            .flat_map(|(index, [a, b])| {
+                let middle = 10 + index as u32;
+                [[*a, middle], [middle, *b]]
+            })
+            .collect::<Vec<_>>();
+        coupling_map(21, &edges)
+    }
+
+    fn line(num_qubits: u32) -> Vec<[usize; 2]> {
+        (1..num_qubits as usize).map(|q| [q - 1, q]).collect()
+    }
+
+    fn ring(num_qubits: u32) -> Vec<[usize; 2]> {
+        let mut edges = line(num_qubits);
+        edges.push([num_qubits as usize - 1, 0]);
+        edges
+    }
+
+    fn assert_valid_layout(coupling: &CouplingMap, layout: &[PhysicalQubit], edges: &[[usize; 2]]) {
+        let mut physical = layout.to_vec();
+        physical.sort();
+        physical.dedup();
+        assert_eq!(physical.len(), layout.len());
+        for [a, b] in edges {
+            assert!(coupling
+                .undirected_neighbors(layout[*a])
+                .contains(&layout[*b]));
+        }
+    }
+
+    #[test]
+    fn test_is_heavy_hex() {
+        assert!(is_heavy_hex(&two_cells()));
+        // A line has no degree-3 qubits.
+        assert!(!is_heavy_hex(&coupling_map(4, &[[0, 1], [1, 2], [2, 3]])));
+        // The hexagons without the extra qubits have neighboring degree-3 qubits.
+        assert!(!is_heavy_hex(&coupling_map(
+            10,
+            &[
+                [0, 1],
+                [1, 2],
+                [2, 3],
+                [3, 4],
+                [4, 5],
+                [5, 0],
+                [1, 6],
+                [6, 7],
+                [7, 8],
+                [8, 9],
+                [9, 0]
+            ]
+        )));
+        // A qubit with four neighbors.
+        assert!(!is_heavy_hex(&coupling_map(
+            7,
+            &[[0, 1], [0, 2], [0, 3], [0, 4], [4, 5], [4, 6]]
+        )));
+        // An odd ring with a tail.
+        assert!(!is_heavy_hex(&coupling_map(
+            6,
+            &[[0, 1], [1, 2], [2, 0], [0, 3], [3, 4], [4, 5]]
+        )));
+    }
+
+    #[test]
+    fn test_interaction_shape() {
+        assert_eq!(
+            interaction_shape(4, &[[0, 1], [2, 1], [1, 0]]),
+            Some(InteractionShape {
+                chains: vec![vec![0, 1, 2], vec![3]],
+                ring: None,
+            })
+        );
+        assert_eq!(
+            interaction_shape(5, &[[0, 1], [1, 2], [2, 0], [3, 4]]),
+            Some(InteractionShape {
+                chains: vec![vec![3, 4]],
+                ring: Some(vec![0, 1, 2]),
+            })
+        );
+        // A qubit interacting with three others.
+        assert_eq!(interaction_shape(4, &[[0, 1], [0, 2], [0, 3]]), None);
+        // Two rings.
+        assert_eq!(
+            interaction_shape(6, &[[0, 1], [1, 2], [2, 0], [3, 4], [4, 5], [5, 3]]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_chain_layout() {
+        let coupling = two_cells();
+        for num_qubits in [1, 2, 12, 21] {
+            let edges = line(num_qubits);
+            let layout =
+                heavy_hex_layout_inner(&coupling, num_qubits as usize, &edges, 100_000).unwrap();
+            assert_valid_layout(&coupling, &layout, &edges);
+        }
+    }
+
+    #[test]
+    fn test_ring_layout() {
+        let coupling = two_cells();
+        for num_qubits in [12, 20] {
+            let edges = ring(num_qubits);
+            let layout =
+                heavy_hex_layout_inner(&coupling, num_qubits as usize, &edges, 100_000).unwrap();
+            assert_valid_layout(&coupling, &layout, &edges);
+        }
+        // There are no rings of these lengths.
+        for num_qubits in [8, 13, 16] {
+            assert_eq!(
+                heavy_hex_layout_inner(&coupling, num_qubits as usize, &ring(num_qubits), 100_000),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_ring_and_chains_layout() {
+        let coupling = two_cells();
+        // A ring of twelve qubits, and two chains on the other five.
+        let mut edges = ring(12);
+        edges.extend([[12, 13], [13, 14], [15, 16]]);
+        let layout = heavy_hex_layout_inner(&coupling, 17, &edges, 100_000).unwrap();
+        assert_valid_layout(&coupling, &layout, &edges);
+    }
+
+    #[test]
+    fn test_no_layout() {
+        let coupling = two_cells();
+        // Too many qubits.
+        assert_eq!(
+            heavy_hex_layout_inner(&coupling, 22, &line(22), 100_000),
+            None
+        );
+        // Interactions that aren't chains.
+        assert_eq!(
+            heavy_hex_layout_inner(&coupling, 4, &[[0, 1], [0, 2], [0, 3]], 100_000),
+            None
+        );
+        // A coupling map that isn't heavy-hex.
+        let square = coupling_map(4, &[[0, 1], [1, 2], [2, 3], [3, 0]]);
+        assert_eq!(heavy_hex_layout_inner(&square, 4, &ring(4), 100_000), None);
+        // No steps to search with.
+        assert_eq!(heavy_hex_layout_inner(&coupling, 3, &line(3), 0), None);
+    }
+}
@@ -0,0 +1,215 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Opt-in instrumentation of the passes and kernels of the accelerate crate.
+//!
+//! Each instrumented function starts a [Timer] under its name, and the functions that run several
+//! trials report them with [add_trials].  While the instrumentation is disabled, which is the
+//! default, a timer is a single atomic load and records nothing.  While it's enabled, each name
+//! accumulates its number of calls, its total wall time, its trials, and the number and total size
+//! of the allocations made during its calls.
+//!
+//! The allocations are only counted when [CountingAllocator] is the global allocator, as it is in
+//! the Python extension.  They're counted across all threads, so they include the work of the
+//! thread pool, but also anything else running at the same time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static STATS: Mutex<BTreeMap<&'static str, Stats>> = Mutex::new(BTreeMap::new());
+
+/// The statistics accumulated under one name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub calls: u64,
+    pub wall_time: Duration,
+    pub trials: u64,
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+/// Whether the instrumentation is recording.
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Start or stop recording.  The statistics recorded so far are kept.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Clear the statistics recorded so far.
+pub fn reset() {
+    STATS.lock().unwrap().clear();
+}
+
+/// The statistics recorded so far, by name.  With `reset`, they're cleared too.
+pub fn stats(reset: bool) -> BTreeMap<&'static str, Stats> {
+    let mut stats = STATS.lock().unwrap();
+    if reset {
+        std::mem::take(&mut *stats)
+    } else {
+        stats.clone()
+    }
+}
+
+/// Count `trials` trials of the function `name`.
+pub fn add_trials(name: &'static str, trials: usize) {
+    if is_enabled() {
+        STATS.lock().unwrap().entry(name).or_default().trials += trials as u64;
+    }
+}
+
+/// Records a call of the function `name`, from its start until it's dropped.
+pub struct Timer {
+    name: &'static str,
+    start: Option<(Instant, u64, u64)>,
+}
+
+impl Timer {
+    pub fn start(name: &'static str) -> Self {
+        let start = is_enabled().then(|| {
+            (
+                Instant::now(),
+                ALLOCATIONS.load(Ordering::Relaxed),
+                ALLOCATED_BYTES.load(Ordering::Relaxed),
+            )
+        });
+        Timer { name, start }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let Some((start, allocations, allocated_bytes)) = self.start else {
+            return;
+        };
+        let wall_time = start.elapsed();
+        let mut stats = STATS.lock().unwrap();
+        let entry = stats.entry(self.name).or_default();
+        entry.calls += 1;
+        entry.wall_time += wall_time;
+        entry.allocations += ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+        entry.allocated_bytes += ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes;
+    }
+}
+
+/// The system allocator, counting the allocations while the instrumentation is enabled.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if is_enabled() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        }
+        System.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if is_enabled() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if is_enabled() && new_size > layout.size() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add((new_size - layout.size()) as u64, Ordering::Relaxed);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Start or stop recording the instrumentation of Qiskit's Rust routines.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "set_enabled")]
+pub fn py_set_enabled(enabled: bool) {
+    set_enabled(enabled)
+}
+
+/// Whether the instrumentation of Qiskit's Rust routines is recording.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "is_enabled")]
+pub fn py_is_enabled() -> bool {
+    is_enabled()
+}
+
+/// Clear the statistics recorded so far.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "reset")]
+pub fn py_reset() {
+    reset()
+}
+
+/// The statistics recorded so far.
+///
+/// Args:
+///     reset (bool): whether to also clear them.
+///
+/// Returns:
+///     dict[str, dict]: for each instrumented function that was called, its number of ``calls``,
+///     its total ``wall_time`` in seconds, its number of ``trials``, and the number of
+///     ``allocations`` and ``allocated_bytes`` made during its calls.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "get_stats", signature = (reset=false))]
+pub fn py_get_stats(py: Python, reset: bool) -> PyResult<PyObject> {
+    let out = PyDict::new_bound(py);
+    for (name, stats) in stats(reset) {
+        let entry = PyDict::new_bound(py);
+        entry.set_item("calls", stats.calls)?;
+        entry.set_item("wall_time", stats.wall_time.as_secs_f64())?;
+        entry.set_item("trials", stats.trials)?;
+        entry.set_item("allocations", stats.allocations)?;
+        entry.set_item("allocated_bytes", stats.allocated_bytes)?;
+        out.set_item(name, entry)?;
+    }
+    Ok(out.into_any().unbind())
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn instrumentation(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_set_enabled))?;
+    m.add_wrapped(wrap_pyfunction!(py_is_enabled))?;
+    m.add_wrapped(wrap_pyfunction!(py_reset))?;
+    m.add_wrapped(wrap_pyfunction!(py_get_stats))?;
+    Ok(())
+}
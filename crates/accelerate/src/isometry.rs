@@ -14,19 +14,22 @@ use std::ops::BitAnd;
 
 use approx::abs_diff_eq;
 use num_complex::{Complex64, ComplexFloat};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
-use hashbrown::HashSet;
-use itertools::Itertools;
 use ndarray::prelude::*;
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, PyReadonlyArray1, PyReadonlyArray2};
 
-use crate::two_qubit_decompose::ONE_QUBIT_IDENTITY;
+use qiskit_circuit::gate_matrix::ONE_QUBIT_IDENTITY;
 
 /// Find special unitary matrix that maps [c0,c1] to [r,0] or [0,r] if basis_state=0 or
 /// basis_state=1 respectively
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn reverse_qubit_state(
     py: Python,
@@ -46,7 +49,7 @@ fn l2_norm(vec: &[Complex64]) -> f64 {
         .sqrt()
 }
 
-fn reverse_qubit_state_inner(
+pub fn reverse_qubit_state_inner(
     state: &[Complex64; 2],
     basis_state: usize,
     epsilon: f64,
@@ -71,7 +74,7 @@ fn reverse_qubit_state_inner(
 /// This method finds the single-qubit gates for a UCGate to disentangle a qubit:
 /// we consider the n-qubit state v[:,0] starting with k zeros (in the computational basis).
 /// The qubit with label n-s-1 is disentangled into the basis state k_s(k,s).
-
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn find_squs_for_disentangling(
     py: Python,
@@ -81,7 +84,19 @@ pub fn find_squs_for_disentangling(
     epsilon: f64,
     n: usize,
 ) -> Vec<PyObject> {
-    let v = v.as_array();
+    find_squs_for_disentangling_inner(v.as_array(), k, s, epsilon, n)
+        .into_iter()
+        .map(|x| x.into_pyarray_bound(py).into())
+        .collect()
+}
+
+pub fn find_squs_for_disentangling_inner(
+    v: ArrayView2<Complex64>,
+    k: usize,
+    s: usize,
+    epsilon: f64,
+    n: usize,
+) -> Vec<Array2<Complex64>> {
     let k_prime = 0;
     let i_start = if b(k, s + 1) == 0 {
         a(k, s + 1)
@@ -103,11 +118,9 @@ pub fn find_squs_for_disentangling(
         .collect();
     output.append(&mut squs);
     output
-        .into_iter()
-        .map(|x| x.into_pyarray_bound(py).into())
-        .collect()
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn apply_ucg(
     py: Python,
@@ -116,6 +129,18 @@ pub fn apply_ucg(
     single_qubit_gates: Vec<PyReadonlyArray2<Complex64>>,
 ) -> PyObject {
     let mut m = m.as_array().to_owned();
+    let gates: Vec<_> = single_qubit_gates.iter().map(|x| x.as_array()).collect();
+    apply_ucg_inner(m.view_mut(), k, &gates);
+    m.into_pyarray_bound(py).into()
+}
+
+/// Apply the uniformly controlled gate with the given `single_qubit_gates` on the qubit with label
+/// `k`, controlled by the labels below it, to the columns of `m` in place.
+pub fn apply_ucg_inner(
+    mut m: ArrayViewMut2<Complex64>,
+    k: usize,
+    single_qubit_gates: &[ArrayView2<Complex64>],
+) {
     let shape = m.shape();
     let num_qubits = shape[0].ilog2();
     let num_col = shape[1];
@@ -123,23 +148,26 @@ pub fn apply_ucg(
     for j in 0..2_usize.pow(num_qubits - 1) {
         let i = (j / spacing) * spacing + j;
         let gate_index = i / (2_usize.pow(num_qubits - k as u32));
+        let gate = single_qubit_gates[gate_index];
         for col in 0..num_col {
-            let gate = single_qubit_gates[gate_index].as_array();
             let a = m[[i, col]];
             let b = m[[i + spacing, col]];
             m[[i, col]] = gate[[0, 0]] * a + gate[[0, 1]] * b;
             m[[i + spacing, col]] = gate[[1, 0]] * a + gate[[1, 1]] * b;
         }
     }
-    m.into_pyarray_bound(py).into()
 }
 
+/// The index into a diagonal gate acting on the qubits with `labels` (most significant first) of
+/// the basis state `state` of `num_qubits` qubits, where label 0 is the most significant qubit.
 #[inline(always)]
-fn bin_to_int(bin: &[u8]) -> usize {
-    bin.iter()
-        .fold(0_usize, |acc, digit| (acc << 1) + *digit as usize)
+fn diag_index(state: usize, labels: &[usize], num_qubits: usize) -> usize {
+    labels.iter().fold(0_usize, |acc, label| {
+        (acc << 1) + ((state >> (num_qubits - 1 - label)) & 1)
+    })
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn apply_diagonal_gate(
     py: Python,
@@ -149,24 +177,23 @@ pub fn apply_diagonal_gate(
 ) -> PyResult<PyObject> {
     let diag = diag.as_slice()?;
     let mut m = m.as_array().to_owned();
-    let shape = m.shape();
-    let num_qubits = shape[0].ilog2();
-    let num_col = shape[1];
-    for state in std::iter::repeat([0_u8, 1_u8])
-        .take(num_qubits as usize)
-        .multi_cartesian_product()
-    {
-        let diag_index = action_qubit_labels
-            .iter()
-            .fold(0_usize, |acc, i| (acc << 1) + state[*i] as usize);
-        let i = bin_to_int(&state);
-        for j in 0..num_col {
-            m[[i, j]] = diag[diag_index] * m[[i, j]]
-        }
-    }
+    apply_diagonal_gate_inner(m.view_mut(), &action_qubit_labels, diag);
     Ok(m.into_pyarray_bound(py).into())
 }
 
+pub fn apply_diagonal_gate_inner(
+    mut m: ArrayViewMut2<Complex64>,
+    action_qubit_labels: &[usize],
+    diag: &[Complex64],
+) {
+    let num_qubits = m.nrows().ilog2() as usize;
+    for (i, mut row) in m.rows_mut().into_iter().enumerate() {
+        let d = diag[diag_index(i, action_qubit_labels, num_qubits)];
+        row.mapv_inplace(|x| d * x);
+    }
+}
+
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn apply_diagonal_gate_to_diag(
     mut m_diagonal: Vec<Complex64>,
@@ -175,51 +202,22 @@ pub fn apply_diagonal_gate_to_diag(
     num_qubits: usize,
 ) -> PyResult<Vec<Complex64>> {
     let diag = diag.as_slice()?;
-    if m_diagonal.is_empty() {
-        return Ok(m_diagonal);
-    }
-    for state in std::iter::repeat([0_u8, 1_u8])
-        .take(num_qubits)
-        .multi_cartesian_product()
-        .take(m_diagonal.len())
-    {
-        let diag_index = action_qubit_labels
-            .iter()
-            .fold(0_usize, |acc, i| (acc << 1) + state[*i] as usize);
-        let i = bin_to_int(&state);
-        m_diagonal[i] *= diag[diag_index]
-    }
+    apply_diagonal_gate_to_diag_inner(&mut m_diagonal, &action_qubit_labels, diag, num_qubits);
     Ok(m_diagonal)
 }
 
-/// Helper method for _apply_multi_controlled_gate. This constructs the basis states the MG gate
-/// is acting on for a specific state state_free of the qubits we neither control nor act on
-fn construct_basis_states(
-    state_free: &[u8],
-    control_set: &HashSet<usize>,
-    target_label: usize,
-) -> [usize; 2] {
-    let size = state_free.len() + control_set.len() + 1;
-    let mut e1: usize = 0;
-    let mut e2: usize = 0;
-    let mut j = 0;
-    for i in 0..size {
-        e1 <<= 1;
-        e2 <<= 1;
-        if control_set.contains(&i) {
-            e1 += 1;
-            e2 += 1;
-        } else if i == target_label {
-            e2 += 1;
-        } else {
-            e1 += state_free[j] as usize;
-            e2 += state_free[j] as usize;
-            j += 1
-        }
+pub fn apply_diagonal_gate_to_diag_inner(
+    m_diagonal: &mut [Complex64],
+    action_qubit_labels: &[usize],
+    diag: &[Complex64],
+    num_qubits: usize,
+) {
+    for (i, entry) in m_diagonal.iter_mut().enumerate() {
+        *entry *= diag[diag_index(i, action_qubit_labels, num_qubits)];
     }
-    [e1, e2]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn apply_multi_controlled_gate(
     py: Python,
@@ -229,55 +227,56 @@ pub fn apply_multi_controlled_gate(
     gate: PyReadonlyArray2<Complex64>,
 ) -> PyObject {
     let mut m = m.as_array().to_owned();
-    let gate = gate.as_array();
-    let shape = m.shape();
-    let num_qubits = shape[0].ilog2();
-    let num_col = shape[1];
-    let free_qubits = num_qubits as usize - control_labels.len() - 1;
-    let control_set: HashSet<usize> = control_labels.into_iter().collect();
-    if free_qubits == 0 {
-        let [e1, e2] = construct_basis_states(&[], &control_set, target_label);
-        for i in 0..num_col {
-            let temp: Vec<_> = gate
-                .dot(&aview2(&[[m[[e1, i]]], [m[[e2, i]]]]))
-                .into_iter()
-                .take(2)
-                .collect();
-            m[[e1, i]] = temp[0];
-            m[[e2, i]] = temp[1];
+    apply_multi_controlled_gate_inner(m.view_mut(), &control_labels, target_label, gate.as_array());
+    m.into_pyarray_bound(py).into()
+}
+
+pub fn apply_multi_controlled_gate_inner(
+    mut m: ArrayViewMut2<Complex64>,
+    control_labels: &[usize],
+    target_label: usize,
+    gate: ArrayView2<Complex64>,
+) {
+    let num_qubits = m.nrows().ilog2() as usize;
+    let num_col = m.ncols();
+    let bit = |label: usize| 1_usize << (num_qubits - 1 - label);
+    let control_mask = control_labels
+        .iter()
+        .fold(0, |acc, label| acc | bit(*label));
+    let target = bit(target_label);
+    for e1 in 0..m.nrows() {
+        if e1 & control_mask != control_mask || e1 & target != 0 {
+            continue;
         }
-        return m.into_pyarray_bound(py).into();
-    }
-    for state_free in std::iter::repeat([0_u8, 1_u8])
-        .take(free_qubits)
-        .multi_cartesian_product()
-    {
-        let [e1, e2] = construct_basis_states(&state_free, &control_set, target_label);
+        let e2 = e1 | target;
         for i in 0..num_col {
-            let temp: Vec<_> = gate
-                .dot(&aview2(&[[m[[e1, i]]], [m[[e2, i]]]]))
-                .into_iter()
-                .take(2)
-                .collect();
-            m[[e1, i]] = temp[0];
-            m[[e2, i]] = temp[1];
+            let (x1, x2) = (m[[e1, i]], m[[e2, i]]);
+            m[[e1, i]] = gate[[0, 0]] * x1 + gate[[0, 1]] * x2;
+            m[[e2, i]] = gate[[1, 0]] * x1 + gate[[1, 1]] * x2;
         }
     }
-    m.into_pyarray_bound(py).into()
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn ucg_is_identity_up_to_global_phase(
     single_qubit_gates: Vec<PyReadonlyArray2<Complex64>>,
     epsilon: f64,
 ) -> bool {
-    let global_phase: Complex64 = if single_qubit_gates[0].as_array()[[0, 0]].abs() >= epsilon {
-        single_qubit_gates[0].as_array()[[0, 0]].finv()
+    let gates: Vec<_> = single_qubit_gates.iter().map(|x| x.as_array()).collect();
+    ucg_is_identity_up_to_global_phase_inner(&gates, epsilon)
+}
+
+pub fn ucg_is_identity_up_to_global_phase_inner(
+    single_qubit_gates: &[ArrayView2<Complex64>],
+    epsilon: f64,
+) -> bool {
+    let global_phase: Complex64 = if single_qubit_gates[0][[0, 0]].abs() >= epsilon {
+        single_qubit_gates[0][[0, 0]].finv()
     } else {
         return false;
     };
-    for raw_gate in single_qubit_gates {
-        let gate = raw_gate.as_array();
+    for gate in single_qubit_gates {
         if !abs_diff_eq!(
             gate.mapv(|x| x * global_phase),
             aview2(&ONE_QUBIT_IDENTITY),
@@ -289,8 +288,13 @@ pub fn ucg_is_identity_up_to_global_phase(
     true
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 fn diag_is_identity_up_to_global_phase(diag: Vec<Complex64>, epsilon: f64) -> bool {
+    diag_is_identity_up_to_global_phase_inner(&diag, epsilon)
+}
+
+pub fn diag_is_identity_up_to_global_phase_inner(diag: &[Complex64], epsilon: f64) -> bool {
     let global_phase: Complex64 = if diag[0].abs() >= epsilon {
         diag[0].finv()
     } else {
@@ -304,30 +308,40 @@ fn diag_is_identity_up_to_global_phase(diag: Vec<Complex64>, epsilon: f64) -> bo
     true
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn merge_ucgate_and_diag(
     py: Python,
     single_qubit_gates: Vec<PyReadonlyArray2<Complex64>>,
     diag: Vec<Complex64>,
 ) -> Vec<PyObject> {
+    let gates: Vec<_> = single_qubit_gates.iter().map(|x| x.as_array()).collect();
+    merge_ucgate_and_diag_inner(&gates, &diag)
+        .into_iter()
+        .map(|x| x.into_pyarray_bound(py).into())
+        .collect()
+}
+
+pub fn merge_ucgate_and_diag_inner(
+    single_qubit_gates: &[ArrayView2<Complex64>],
+    diag: &[Complex64],
+) -> Vec<Array2<Complex64>> {
     single_qubit_gates
         .iter()
         .enumerate()
-        .map(|(i, raw_gate)| {
-            let gate = raw_gate.as_array();
-            let res = aview2(&[
+        .map(|(i, gate)| {
+            aview2(&[
                 [diag[2 * i], Complex64::new(0., 0.)],
                 [Complex64::new(0., 0.), diag[2 * i + 1]],
             ])
-            .dot(&gate);
-            res.into_pyarray_bound(py).into()
+            .dot(gate)
         })
         .collect()
 }
 
 #[inline(always)]
-#[pyfunction]
-fn k_s(k: usize, s: usize) -> usize {
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn k_s(k: usize, s: usize) -> usize {
     if k == 0 {
         0
     } else {
@@ -337,17 +351,18 @@ fn k_s(k: usize, s: usize) -> usize {
 }
 
 #[inline(always)]
-#[pyfunction]
-fn a(k: usize, s: usize) -> usize {
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn a(k: usize, s: usize) -> usize {
     k / 2_usize.pow(s as u32)
 }
 
 #[inline(always)]
-#[pyfunction]
-fn b(k: usize, s: usize) -> usize {
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn b(k: usize, s: usize) -> usize {
     k - (a(k, s) * 2_usize.pow(s as u32))
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn isometry(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(diag_is_identity_up_to_global_phase))?;
@@ -0,0 +1,265 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The layers and the depths of circuits.
+//!
+//! The layer of an instruction is one past the latest layer of the instructions before it on any
+//! of its qubits and clbits, which is the partition of `DAGCircuit.layers` and of the depth of
+//! `QuantumCircuit.depth`.  Instructions that a depth doesn't count, such as the barriers or the
+//! single-qubit gates for a two-qubit depth, still order the instructions around them, but take up
+//! no layer of their own.
+
+#[cfg(feature = "python")]
+use indexmap::IndexMap;
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+#[cfg(feature = "python")]
+use crate::collect_blocks::block_instruction;
+use crate::collect_blocks::BlockInstruction;
+
+/// The level of each instruction, counting only the instructions for which `counted` holds, and
+/// the depth of the circuit.  The counted instructions have levels from one.
+fn levels<F>(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[BlockInstruction],
+    counted: F,
+) -> (Vec<usize>, usize)
+where
+    F: Fn(usize) -> bool,
+{
+    let mut level = vec![0; num_qubits + num_clbits];
+    let mut out = Vec::with_capacity(instructions.len());
+    for (index, inst) in instructions.iter().enumerate() {
+        let wires = || {
+            inst.qubits
+                .iter()
+                .copied()
+                .chain(inst.clbits.iter().map(|bit| num_qubits + bit))
+        };
+        let mut this = wires().map(|wire| level[wire]).max().unwrap_or(0);
+        if counted(index) {
+            this += 1;
+        }
+        for wire in wires() {
+            level[wire] = this;
+        }
+        out.push(this);
+    }
+    let depth = level.into_iter().max().unwrap_or(0);
+    (out, depth)
+}
+
+/// The depth of a circuit, counting only the instructions for which `counted` holds.
+pub fn depth_inner<F>(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[BlockInstruction],
+    counted: F,
+) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    levels(num_qubits, num_clbits, instructions, counted).1
+}
+
+/// The partition of the instructions of a circuit into layers of instructions on disjoint bits,
+/// each in the order of the circuit.
+pub fn layers_inner(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[BlockInstruction],
+) -> Vec<Vec<usize>> {
+    let (layer_of, depth) = levels(num_qubits, num_clbits, instructions, |_| true);
+    let mut out = vec![Vec::new(); depth];
+    for (index, level) in layer_of.into_iter().enumerate() {
+        out[level - 1].push(index);
+    }
+    out
+}
+
+/// The wires of the instructions of a circuit, with the name of each and whether it's a directive.
+#[cfg(feature = "python")]
+struct LayerInstructions {
+    wires: Vec<BlockInstruction>,
+    names: Vec<String>,
+    directives: Vec<bool>,
+}
+
+#[cfg(feature = "python")]
+fn layer_instructions(py: Python, circuit: &CircuitData) -> PyResult<LayerInstructions> {
+    let num_instructions = circuit.iter_instructions().len();
+    let mut out = LayerInstructions {
+        wires: Vec::with_capacity(num_instructions),
+        names: Vec::with_capacity(num_instructions),
+        directives: Vec::with_capacity(num_instructions),
+    };
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        let op = op.bind(py);
+        let (inst, _) = block_instruction(circuit, op, qubits, clbits)?;
+        out.wires.push(inst);
+        out.names
+            .push(op.getattr(intern!(py, "name"))?.extract::<String>()?);
+        out.directives.push(
+            op.hasattr(intern!(py, "_directive"))?
+                && op.getattr(intern!(py, "_directive"))?.is_truthy()?,
+        );
+    }
+    Ok(out)
+}
+
+/// Partition the instructions of a circuit into layers, as ``DAGCircuit.layers`` does.
+///
+/// Args:
+///     circuit (CircuitData): the circuit.
+///
+/// Returns:
+///     list[list[int]]: the indices of the instructions of each layer, in the order of the circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(circuit, /)")]
+pub fn as_layers(py: Python, circuit: &CircuitData) -> PyResult<Vec<Vec<usize>>> {
+    let instructions = layer_instructions(py, circuit)?;
+    Ok(layers_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions.wires,
+    ))
+}
+
+/// Count the operations in each layer of a circuit, by name.
+///
+/// Args:
+///     circuit (CircuitData): the circuit.
+///
+/// Returns:
+///     list[dict[str, int]]: the number of operations of each name in each layer, in the order of
+///     :func:`as_layers`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(circuit, /)")]
+pub fn layer_counts(py: Python, circuit: &CircuitData) -> PyResult<Vec<IndexMap<String, usize>>> {
+    let instructions = layer_instructions(py, circuit)?;
+    Ok(layers_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions.wires,
+    )
+    .into_iter()
+    .map(|layer| {
+        let mut counts = IndexMap::new();
+        for index in layer {
+            *counts.entry(instructions.names[index].clone()).or_insert(0) += 1;
+        }
+        counts
+    })
+    .collect())
+}
+
+/// Return the depth of a circuit, as ``QuantumCircuit.depth`` does with its default filter, or
+/// counting only the operations on two qubits.
+///
+/// Directives, such as barriers, are never counted.
+///
+/// Args:
+///     circuit (CircuitData): the circuit.
+///     two_qubit (bool): whether to count only the operations on two qubits.
+///
+/// Returns:
+///     int: the depth.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, two_qubit=false))]
+pub fn depth(py: Python, circuit: &CircuitData, two_qubit: bool) -> PyResult<usize> {
+    let instructions = layer_instructions(py, circuit)?;
+    Ok(depth_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions.wires,
+        |index| {
+            !instructions.directives[index]
+                && (!two_qubit || instructions.wires[index].qubits.len() == 2)
+        },
+    ))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn layers(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(as_layers))?;
+    m.add_wrapped(wrap_pyfunction!(layer_counts))?;
+    m.add_wrapped(wrap_pyfunction!(depth))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(qubits: &[usize], clbits: &[usize]) -> BlockInstruction {
+        BlockInstruction {
+            qubits: qubits.iter().copied().collect(),
+            clbits: clbits.iter().copied().collect(),
+        }
+    }
+
+    /// `h(0); cx(0, 1); x(2); measure(1, 0); x(2).c_if(0, 1)`.
+    fn circuit() -> Vec<BlockInstruction> {
+        vec![
+            inst(&[0], &[]),
+            inst(&[0, 1], &[]),
+            inst(&[2], &[]),
+            inst(&[1], &[0]),
+            inst(&[2], &[0]),
+        ]
+    }
+
+    #[test]
+    fn test_layers() {
+        assert_eq!(
+            layers_inner(3, 1, &circuit()),
+            [vec![0, 2], vec![1], vec![3], vec![4]]
+        );
+        assert!(layers_inner(2, 0, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_depth() {
+        let instructions = circuit();
+        assert_eq!(depth_inner(3, 1, &instructions, |_| true), 4);
+        assert_eq!(
+            depth_inner(3, 1, &instructions, |index| instructions[index]
+                .qubits
+                .len()
+                == 2),
+            1
+        );
+        assert_eq!(depth_inner(3, 1, &instructions, |_| false), 0);
+        assert_eq!(depth_inner(2, 0, &[], |_| true), 0);
+    }
+
+    #[test]
+    fn test_uncounted_instructions_order() {
+        // `h(0); barrier(0, 1); h(1)`, where the barrier isn't counted.
+        let instructions = [inst(&[0], &[]), inst(&[0, 1], &[]), inst(&[1], &[])];
+        assert_eq!(depth_inner(2, 0, &instructions, |index| index != 1), 2);
+        let instructions = [inst(&[0], &[]), inst(&[1], &[])];
+        assert_eq!(depth_inner(2, 0, &instructions, |_| true), 1);
+    }
+}
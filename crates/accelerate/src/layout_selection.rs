@@ -0,0 +1,604 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The selection of the best of several candidate layouts.
+//!
+//! Rather than trying the layout methods one after the other and keeping the first one whose
+//! condition holds, every candidate is computed (in parallel, where the threading configuration
+//! allows) and scored with the same function, and the best score wins.  The score of a layout is
+//! its estimated error, which is one minus the product of the reliabilities of the interactions
+//! and of the operations on each qubit, with non-neighboring interactions costed as the most
+//! reliable chain of swaps between them, as in the noise-adaptive layout.  Ties are broken by the
+//! estimated two-qubit duration, costed the same way, and then by the order of the candidates.
+//!
+//! The trivial, dense, noise-adaptive and line layouts are computed here.  Candidates whose search
+//! lives elsewhere, such as VF2 and Sabre, are computed by the caller and passed in to be scored
+//! alongside.
+
+use hashbrown::HashMap;
+use ndarray::prelude::*;
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use indexmap::IndexMap;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::coupling_map::CouplingMap;
+use crate::dense_layout::best_subset_inner;
+use crate::error::{AccelerateError, ErrorContext};
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::instrumentation;
+use crate::line_layout::line_layout_inner;
+use crate::nlayout::PhysicalQubit;
+use crate::noise_adaptive_layout::{
+    error_rate, gate_reliability, noise_adaptive_layout_inner, swap_path_costs,
+};
+use crate::threading;
+
+/// The estimated error and two-qubit duration of a layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutScore {
+    pub error: f64,
+    pub duration: f64,
+}
+
+/// The scoring of layouts on one device, shared by every candidate.
+pub struct LayoutScorer {
+    /// The logarithm of the reliability of an interaction between each pair of physical qubits.
+    log_reliability: Array2<f64>,
+    /// The duration of an interaction between each pair of physical qubits.
+    duration: Array2<f64>,
+    /// The logarithm of the reliability of an operation on each physical qubit.
+    qubit_log: Vec<f64>,
+}
+
+impl LayoutScorer {
+    /// Build the scorer of a device.
+    ///
+    /// `errors` are the average error rates of the edges, and of the qubits at `[q, q]`, with
+    /// missing rates taken as ideal.  `durations` are the durations of the two-qubit gates of the
+    /// edges, in the better direction; missing durations are taken as the mean of the others, and
+    /// without any every gate takes one unit of time.
+    pub fn new(
+        coupling: &CouplingMap,
+        errors: &HashMap<[PhysicalQubit; 2], f64>,
+        durations: &HashMap<[PhysicalQubit; 2], f64>,
+    ) -> Self {
+        let default_duration = if durations.is_empty() {
+            1.
+        } else {
+            durations.values().sum::<f64>() / durations.len() as f64
+        };
+        let gate_duration = |a: PhysicalQubit, b: PhysicalQubit| match (
+            durations.get(&[a, b]),
+            durations.get(&[b, a]),
+        ) {
+            (Some(forward), Some(backward)) => forward.min(*backward),
+            (Some(duration), None) | (None, Some(duration)) => *duration,
+            (None, None) => default_duration,
+        };
+        let log_reliability =
+            -swap_path_costs(coupling, |a, b| -gate_reliability(errors, a, b).ln());
+        let duration = swap_path_costs(coupling, gate_duration);
+        let qubit_log = (0..coupling.num_qubits() as u32)
+            .map(|qubit| {
+                let qubit = PhysicalQubit::new(qubit);
+                (1. - error_rate(errors, [qubit, qubit])).ln()
+            })
+            .collect();
+        LayoutScorer {
+            log_reliability,
+            duration,
+            qubit_log,
+        }
+    }
+
+    /// Score the layout of a circuit, given as the physical qubit of each virtual qubit.
+    ///
+    /// `interactions` are the pairs of virtual qubits that interact with the number of two-qubit
+    /// gates between each pair, and `qubit_counts` are the numbers of operations on each virtual
+    /// qubit that aren't two-qubit gates.
+    pub fn score(
+        &self,
+        interactions: &[([usize; 2], usize)],
+        qubit_counts: &[usize],
+        layout: &[PhysicalQubit],
+    ) -> LayoutScore {
+        let mut log_success = 0f64;
+        let mut duration = 0.;
+        for ([a, b], count) in interactions.iter().filter(|([a, b], _)| a != b) {
+            let pair = [layout[*a].index(), layout[*b].index()];
+            log_success += *count as f64 * self.log_reliability[pair];
+            duration += *count as f64 * self.duration[pair];
+        }
+        for (virt, count) in qubit_counts.iter().enumerate() {
+            log_success += *count as f64 * self.qubit_log[layout[virt].index()];
+        }
+        LayoutScore {
+            error: 1. - log_success.exp(),
+            duration,
+        }
+    }
+}
+
+/// The layout methods that run here.
+#[derive(Clone, Copy, Debug)]
+enum Method {
+    Trivial,
+    Dense,
+    NoiseAdaptive,
+    Line,
+}
+
+impl Method {
+    const ALL: [Method; 4] = [
+        Method::Trivial,
+        Method::Dense,
+        Method::NoiseAdaptive,
+        Method::Line,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Trivial => "trivial",
+            Method::Dense => "dense",
+            Method::NoiseAdaptive => "noise_adaptive",
+            Method::Line => "line",
+        }
+    }
+}
+
+/// The circuit and the device that the candidate layouts are computed for.
+pub struct SelectionProblem<'a> {
+    pub coupling: &'a CouplingMap,
+    pub errors: &'a HashMap<[PhysicalQubit; 2], f64>,
+    pub num_qubits: usize,
+    pub interactions: &'a [([usize; 2], usize)],
+    pub num_measurements: usize,
+    pub max_steps: usize,
+}
+
+impl<'a> SelectionProblem<'a> {
+    /// The layout of a method, or `None` if it doesn't apply to the circuit.
+    fn run(&self, method: Method) -> Result<Option<Vec<PhysicalQubit>>, AccelerateError> {
+        match method {
+            Method::Trivial => Ok(Some(
+                (0..self.num_qubits as u32)
+                    .map(PhysicalQubit::new)
+                    .collect(),
+            )),
+            Method::Dense => Ok(self.dense()),
+            Method::NoiseAdaptive => noise_adaptive_layout_inner(
+                self.num_qubits,
+                self.interactions,
+                self.coupling,
+                self.errors,
+            )
+            .map(Some),
+            Method::Line => {
+                let edges = self
+                    .interactions
+                    .iter()
+                    .filter(|([a, b], count)| a != b && *count > 0)
+                    .map(|(pair, _)| *pair)
+                    .collect::<Vec<_>>();
+                Ok(line_layout_inner(
+                    self.coupling,
+                    self.errors,
+                    self.num_qubits,
+                    &edges,
+                    self.max_steps,
+                ))
+            }
+        }
+    }
+
+    /// The densest subset of the device, in the order of its breadth-first search, without the
+    /// reordering of the `DenseLayout` pass.
+    fn dense(&self) -> Option<Vec<PhysicalQubit>> {
+        if self.num_qubits == 0 {
+            return Some(Vec::new());
+        }
+        let num_physical = self.coupling.num_qubits();
+        let mut err = Array2::zeros((num_physical, num_physical));
+        for ([a, b], error) in self.errors.iter() {
+            if !error.is_nan() {
+                err[[a.index(), b.index()]] = *error;
+            }
+        }
+        let num_cx = self
+            .interactions
+            .iter()
+            .filter(|([a, b], _)| a != b)
+            .map(|(_, count)| count)
+            .sum();
+        let [_, _, best_map] = best_subset_inner(
+            self.num_qubits,
+            self.coupling.adjacency_matrix().view(),
+            self.num_measurements,
+            num_cx,
+            !self.errors.is_empty(),
+            false,
+            err.view(),
+        );
+        // The search stays within one connected component, which may be too small.
+        (best_map.len() == self.num_qubits).then(|| {
+            best_map
+                .into_iter()
+                .map(|qubit| PhysicalQubit::new(qubit as u32))
+                .collect()
+        })
+    }
+}
+
+/// A scored candidate layout.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub name: String,
+    pub layout: Vec<PhysicalQubit>,
+    pub score: LayoutScore,
+}
+
+/// Compute the candidate layouts of a circuit, score them together with the `external` ones, and
+/// return the index of the best of them and all the scored candidates.
+///
+/// The candidates computed here come first, in the order trivial, dense, noise-adaptive and line,
+/// followed by the external ones; the methods that don't apply to the circuit are left out.  The
+/// best candidate has the lowest error, then the lowest duration, then comes first.
+pub fn select_layout_inner(
+    problem: &SelectionProblem,
+    scorer: &LayoutScorer,
+    qubit_counts: &[usize],
+    external: Vec<(String, Vec<PhysicalQubit>)>,
+) -> Result<(usize, Vec<Candidate>), AccelerateError> {
+    let _timer = instrumentation::Timer::start("select_layout");
+    if problem.num_qubits > problem.coupling.num_qubits() {
+        return Err(AccelerateError::Layout(
+            "Number of qubits greater than device.".to_string(),
+            ErrorContext::pass("SelectLayout"),
+        ));
+    }
+    let num_physical = problem.coupling.num_qubits();
+    for (name, layout) in external.iter() {
+        let mut used = vec![false; num_physical];
+        let valid = layout.len() == problem.num_qubits
+            && layout.iter().all(|qubit| {
+                qubit.index() < num_physical && !std::mem::replace(&mut used[qubit.index()], true)
+            });
+        if !valid {
+            return Err(AccelerateError::Value(format!(
+                "the layout of the candidate '{name}' isn't an injection of {} virtual qubits \
+                into {num_physical} physical qubits",
+                problem.num_qubits
+            )));
+        }
+    }
+    let run = |method: &Method| -> Result<Option<(String, Vec<PhysicalQubit>)>, AccelerateError> {
+        Ok(problem
+            .run(*method)?
+            .map(|layout| (method.name().to_string(), layout)))
+    };
+    let computed = if threading::use_multiple_threads() {
+        threading::install(|| {
+            Method::ALL
+                .par_iter()
+                .map(run)
+                .collect::<Result<Vec<_>, _>>()
+        })?
+    } else {
+        Method::ALL.iter().map(run).collect::<Result<Vec<_>, _>>()?
+    };
+    let candidates = computed
+        .into_iter()
+        .flatten()
+        .chain(external)
+        .map(|(name, layout)| {
+            let score = scorer.score(problem.interactions, qubit_counts, &layout);
+            Candidate {
+                name,
+                layout,
+                score,
+            }
+        })
+        .collect::<Vec<_>>();
+    let best = candidates
+        .iter()
+        .enumerate()
+        .min_by(|(i, a), (j, b)| {
+            a.score
+                .error
+                .total_cmp(&b.score.error)
+                .then(a.score.duration.total_cmp(&b.score.duration))
+                .then(i.cmp(j))
+        })
+        .map(|(index, _)| index)
+        .ok_or_else(|| {
+            AccelerateError::Layout(
+                "No candidate layout was found.".to_string(),
+                ErrorContext::pass("SelectLayout"),
+            )
+        })?;
+    Ok((best, candidates))
+}
+
+/// Compute the trivial, dense, noise-adaptive and line layouts of a circuit, score them together
+/// with the given candidates, and select the best.
+///
+/// The score of a layout is its estimated error, from the reliabilities of its interactions, with
+/// the interactions between qubits that aren't neighbors costed as the most reliable chain of
+/// swaps between them, and of the other operations on its qubits.  Ties are broken by the
+/// estimated duration of the two-qubit gates and swaps, and then by the order of the candidates.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     num_qubits (int): the number of virtual qubits of the circuit.
+///     interactions (list[tuple[tuple[int, int], int]]): each pair of virtual qubits that interacts
+///         with the number of two-qubit gates between them.
+///     error_map (ErrorMap | None): the average error rates of the edges, and of the qubits at
+///         ``(q, q)``.  Missing rates are taken as ideal.
+///     durations (dict[tuple[int, int], float] | None): the durations of the two-qubit gates of the
+///         edges.  Missing durations are taken as the mean of the others.
+///     qubit_counts (list[int] | None): the number of operations on each virtual qubit that aren't
+///         two-qubit gates, measurements included.
+///     num_measurements (int): the number of measurements of the circuit, for the dense layout.
+///     candidates (list[tuple[str, list[int]]] | None): other named layouts to score, such as those
+///         of VF2 or Sabre, each as the physical qubit of each virtual qubit.
+///     max_steps (int): the most steps of the search for the line layout.
+///
+/// Returns:
+///     tuple[str, list[int], dict[str, tuple[float, float]]]: the name and the layout of the best
+///     candidate, and the error and duration of every candidate by name.
+///
+/// Raises:
+///     LayoutError: if the circuit has more qubits than the device.
+///     ValueError: if a given candidate isn't a layout of the circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(
+    name = "select_layout",
+    signature = (
+        coupling_map,
+        num_qubits,
+        interactions,
+        error_map=None,
+        durations=None,
+        qubit_counts=None,
+        num_measurements=0,
+        candidates=None,
+        max_steps=100_000,
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn py_select_layout(
+    coupling_map: &CouplingMap,
+    num_qubits: usize,
+    interactions: Vec<([usize; 2], usize)>,
+    error_map: Option<&ErrorMap>,
+    durations: Option<HashMap<[PhysicalQubit; 2], f64>>,
+    qubit_counts: Option<Vec<usize>>,
+    num_measurements: usize,
+    candidates: Option<Vec<(String, Vec<PhysicalQubit>)>>,
+    max_steps: usize,
+) -> PyResult<(String, Vec<PhysicalQubit>, IndexMap<String, (f64, f64)>)> {
+    if let Some(qubit) = interactions
+        .iter()
+        .flat_map(|(pair, _)| pair)
+        .find(|qubit| **qubit >= num_qubits)
+    {
+        return Err(AccelerateError::Index(format!(
+            "virtual qubit {qubit} is out of range for {num_qubits} qubits"
+        ))
+        .into());
+    }
+    let qubit_counts = qubit_counts.unwrap_or_else(|| vec![0; num_qubits]);
+    if qubit_counts.len() != num_qubits {
+        return Err(AccelerateError::Value(format!(
+            "{} qubit counts were given for {num_qubits} qubits",
+            qubit_counts.len()
+        ))
+        .into());
+    }
+    let no_errors = HashMap::new();
+    let errors = error_map.map_or(&no_errors, |map| &map.error_map);
+    let problem = SelectionProblem {
+        coupling: coupling_map,
+        errors,
+        num_qubits,
+        interactions: &interactions,
+        num_measurements,
+        max_steps,
+    };
+    let scorer = LayoutScorer::new(coupling_map, errors, &durations.unwrap_or_default());
+    let (best, candidates) = select_layout_inner(
+        &problem,
+        &scorer,
+        &qubit_counts,
+        candidates.unwrap_or_default(),
+    )?;
+    let scores = candidates
+        .iter()
+        .map(|candidate| {
+            (
+                candidate.name.clone(),
+                (candidate.score.error, candidate.score.duration),
+            )
+        })
+        .collect();
+    let Candidate { name, layout, .. } = candidates.into_iter().nth(best).unwrap();
+    Ok((name, layout, scores))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn layout_selection(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_select_layout))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    fn qubit(index: u32) -> PhysicalQubit {
+        PhysicalQubit::new(index)
+    }
+
+    fn line(num_qubits: u32) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits as usize,
+            (1..num_qubits).map(|q| [qubit(q - 1), qubit(q)]).collect(),
+        )
+    }
+
+    /// The error map with `error` on each of the `edges` in both directions, and `qubit_errors`
+    /// on the qubits.
+    fn errors(edges: &[([u32; 2], f64)], qubit_errors: &[f64]) -> HashMap<[PhysicalQubit; 2], f64> {
+        let mut out = HashMap::new();
+        for ([a, b], error) in edges {
+            out.insert([qubit(*a), qubit(*b)], *error);
+            out.insert([qubit(*b), qubit(*a)], *error);
+        }
+        for (index, error) in qubit_errors.iter().enumerate() {
+            out.insert([qubit(index as u32), qubit(index as u32)], *error);
+        }
+        out
+    }
+
+    fn layout(qubits: &[u32]) -> Vec<PhysicalQubit> {
+        qubits.iter().map(|q| qubit(*q)).collect()
+    }
+
+    #[test]
+    fn test_score() {
+        let coupling = line(3);
+        let errors = errors(&[([0, 1], 0.1), ([1, 2], 0.2)], &[0.01, 0., 0.]);
+        let scorer = LayoutScorer::new(&coupling, &errors, &HashMap::new());
+        let interactions = [([0, 1], 2)];
+        let score = scorer.score(&interactions, &[1, 0], &layout(&[0, 1]));
+        assert_abs_diff_eq!(score.error, 1. - 0.81 * 0.99, epsilon = 1e-12);
+        assert_eq!(score.duration, 2.);
+        // Qubits that aren't neighbors interact through a chain of swaps of three gates each.
+        let score = scorer.score(&interactions, &[0, 0], &layout(&[2, 0]));
+        assert_abs_diff_eq!(score.error, 1. - (0.9f64 * 0.8).powi(6), epsilon = 1e-12);
+        assert_eq!(score.duration, 12.);
+    }
+
+    #[test]
+    fn test_score_durations() {
+        let coupling = line(3);
+        let durations = HashMap::from([([qubit(1), qubit(0)], 100.), ([qubit(0), qubit(1)], 300.)]);
+        let scorer = LayoutScorer::new(&coupling, &HashMap::new(), &durations);
+        // The better direction counts, and a missing duration is the mean of the others.
+        let score = scorer.score(&[([0, 1], 1)], &[0, 0], &layout(&[0, 1]));
+        assert_eq!(score.duration, 100.);
+        let score = scorer.score(&[([0, 1], 1)], &[0, 0], &layout(&[1, 2]));
+        assert_eq!(score.duration, 200.);
+        assert_eq!(score.error, 0.);
+    }
+
+    #[test]
+    fn test_select_avoids_noisy_edge() {
+        let coupling = line(4);
+        let errors = errors(&[([0, 1], 0.5), ([1, 2], 0.01), ([2, 3], 0.01)], &[]);
+        let interactions = [([0, 1], 10)];
+        let problem = SelectionProblem {
+            coupling: &coupling,
+            errors: &errors,
+            num_qubits: 2,
+            interactions: &interactions,
+            num_measurements: 0,
+            max_steps: 100_000,
+        };
+        let scorer = LayoutScorer::new(&coupling, &errors, &HashMap::new());
+        let (best, candidates) =
+            select_layout_inner(&problem, &scorer, &[0, 0], Vec::new()).unwrap();
+        let names = candidates
+            .iter()
+            .map(|candidate| candidate.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["trivial", "dense", "noise_adaptive", "line"]);
+        assert_ne!(best, 0);
+        assert_abs_diff_eq!(
+            candidates[best].score.error,
+            1. - 0.99f64.powi(10),
+            epsilon = 1e-12
+        );
+        assert!(candidates[best].layout.iter().all(|q| *q != qubit(0)));
+    }
+
+    #[test]
+    fn test_external_candidates() {
+        let coupling = line(4);
+        let errors = errors(&[([0, 1], 0.1), ([1, 2], 0.1), ([2, 3], 0.01)], &[]);
+        // A ring of three qubits isn't a chain, so there's no line layout.
+        let interactions = [([0, 1], 1), ([1, 2], 1), ([2, 0], 1)];
+        let problem = SelectionProblem {
+            coupling: &coupling,
+            errors: &errors,
+            num_qubits: 3,
+            interactions: &interactions,
+            num_measurements: 0,
+            max_steps: 100_000,
+        };
+        let scorer = LayoutScorer::new(&coupling, &errors, &HashMap::new());
+        let external = vec![("vf2".to_string(), layout(&[3, 2, 1]))];
+        let (best, candidates) = select_layout_inner(&problem, &scorer, &[0; 3], external).unwrap();
+        assert_eq!(candidates.len(), 4);
+        assert_eq!(candidates[3].name, "vf2");
+        let best_error = candidates[best].score.error;
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.score.error >= best_error));
+        // The earlier candidate wins a tie.
+        let first = candidates
+            .iter()
+            .position(|candidate| candidate.score == candidates[best].score);
+        assert_eq!(first, Some(best));
+    }
+
+    #[test]
+    fn test_bad_problems() {
+        let coupling = line(3);
+        let no_errors = HashMap::new();
+        let problem = SelectionProblem {
+            coupling: &coupling,
+            errors: &no_errors,
+            num_qubits: 2,
+            interactions: &[],
+            num_measurements: 0,
+            max_steps: 100_000,
+        };
+        let scorer = LayoutScorer::new(&coupling, &no_errors, &HashMap::new());
+        for bad in [layout(&[0]), layout(&[1, 1]), layout(&[0, 3])] {
+            let external = vec![("bad".to_string(), bad)];
+            assert!(matches!(
+                select_layout_inner(&problem, &scorer, &[0, 0], external),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+        let problem = SelectionProblem {
+            num_qubits: 4,
+            ..problem
+        };
+        assert!(matches!(
+            select_layout_inner(&problem, &scorer, &[0; 4], Vec::new()),
+            Err(AccelerateError::Layout(..))
+        ));
+    }
+}
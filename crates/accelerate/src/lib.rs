@@ -10,44 +10,103 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
-use std::env;
-
+#[cfg(feature = "python")]
 use pyo3::import_exception;
 
+// The modules in this first group make up the pure-Rust numeric cores, and compile without the
+// `python` feature.  Their PyO3 bindings are gated individually within each module.
+pub mod angles;
+pub mod check_map;
+pub mod clifford;
+pub mod collect_blocks;
+pub mod cnotdihedral;
+pub mod consolidate_blocks;
 pub mod convert_2q_block_matrix;
+pub mod coupling_map;
 pub mod dense_layout;
-pub mod edge_collections;
-pub mod error_map;
+pub mod density_matrix;
+pub mod disjoint_components;
+pub mod dynamical_decoupling;
+pub mod entanglement;
+pub mod error;
+pub mod estimator_grouping;
 pub mod euler_one_qubit_decomposer;
+pub mod fidelity;
+pub mod heavy_hex_layout;
+pub mod instrumentation;
 pub mod isometry;
+pub mod layers;
+pub mod layout_selection;
+pub mod line_layout;
+pub mod mirror_circuit;
 pub mod nlayout;
-pub mod optimize_1q_gates;
+pub mod noise_adaptive_layout;
+pub mod operator;
+pub mod parameter_shift;
+pub mod partial_trace;
+pub mod pass_loop;
+pub mod pauli;
 pub mod pauli_exp_val;
-pub mod results;
+pub mod pub_broadcast;
+pub mod random_quantum_info;
+pub mod readout_mitigation;
+pub mod rng;
 pub mod sabre;
+pub mod sampler_results;
+pub mod sampling;
 pub mod sampled_exp_val;
-pub mod sparse_pauli_op;
-pub mod stochastic_swap;
+pub mod scheduling;
+pub mod stabilizer_state;
+pub mod statevector;
+pub mod synthesis;
+pub mod target;
+pub mod threading;
+pub mod twirling;
 pub mod two_qubit_decompose;
 pub mod uc_gate;
+pub mod unitary_synthesis;
 pub mod utils;
+pub mod weyl;
+
+// These modules are only meaningful as Python extensions.
+#[cfg(feature = "python")]
+pub mod commutation_checker;
+#[cfg(feature = "python")]
+pub mod commutative_inverse_cancellation;
+#[cfg(feature = "python")]
+pub mod edge_collections;
+#[cfg(feature = "python")]
+pub mod error_map;
+#[cfg(feature = "python")]
+pub mod optimize_1q_gates;
+#[cfg(feature = "python")]
+pub mod random_circuit;
+#[cfg(feature = "python")]
+pub mod results;
+#[cfg(feature = "python")]
+pub mod sparse_pauli_op;
+#[cfg(feature = "python")]
+pub mod stochastic_swap;
+#[cfg(feature = "python")]
+pub mod swap_strategy;
+#[cfg(feature = "python")]
 pub mod vf2_layout;
 
+pub use error::{AccelerateError, ErrorContext};
+
+// Only the sparse Pauli operator kernels (and their tests) make use of these helpers.
+#[cfg(feature = "python")]
 mod rayon_ext;
-#[cfg(test)]
+#[cfg(all(test, feature = "python"))]
 mod test;
 
-#[inline]
-pub fn getenv_use_multiple_threads() -> bool {
-    let parallel_context = env::var("QISKIT_IN_PARALLEL")
-        .unwrap_or_else(|_| "FALSE".to_string())
-        .to_uppercase()
-        == "TRUE";
-    let force_threads = env::var("QISKIT_FORCE_THREADS")
-        .unwrap_or_else(|_| "FALSE".to_string())
-        .to_uppercase()
-        == "TRUE";
-    !parallel_context || force_threads
-}
-
+#[cfg(feature = "python")]
 import_exception!(qiskit.exceptions, QiskitError);
+#[cfg(feature = "python")]
+import_exception!(qiskit.transpiler.exceptions, TranspilerError);
+#[cfg(feature = "python")]
+import_exception!(qiskit.exceptions, SynthesisError);
+#[cfg(feature = "python")]
+import_exception!(qiskit.transpiler.exceptions, RoutingError);
+#[cfg(feature = "python")]
+import_exception!(qiskit.transpiler.exceptions, LayoutError);
@@ -0,0 +1,402 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A layout for circuits whose interactions form chains, such as Trotterized spin chains, on any
+//! coupling map.
+//!
+//! Such a circuit has a perfect layout on any simple path of physical qubits that is long enough,
+//! so rather than a general layout search, this looks for the most reliable path: the one with the
+//! largest product of the success rates of its edges and of its qubits.  The search is a
+//! depth-first branch and bound, which tries the most reliable edges first and drops any partial
+//! path that can't beat the best path found so far even if all its remaining edges and qubits
+//! were as reliable as the best of the device.  It stops after a set number of steps with the best
+//! path it has found.
+
+use hashbrown::HashMap;
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::coupling_map::CouplingMap;
+#[cfg(feature = "python")]
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::heavy_hex_layout::interaction_shape;
+use crate::instrumentation;
+use crate::nlayout::PhysicalQubit;
+use crate::noise_adaptive_layout::{error_rate, gate_reliability};
+
+/// A depth-first branch-and-bound search for the most reliable simple path of physical qubits.
+struct Search<'a> {
+    coupling: &'a CouplingMap,
+    errors: &'a HashMap<[PhysicalQubit; 2], f64>,
+    qubit_log: Vec<f64>,
+    /// The largest sum of the logarithms of the reliabilities of an edge and of a qubit, which
+    /// bounds what each further step of a path can add to its score.
+    best_step: f64,
+    free: Vec<bool>,
+    path: Vec<PhysicalQubit>,
+    score: f64,
+    best: Option<(Vec<PhysicalQubit>, f64)>,
+    steps: usize,
+    max_steps: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        coupling: &'a CouplingMap,
+        errors: &'a HashMap<[PhysicalQubit; 2], f64>,
+        max_steps: usize,
+    ) -> Self {
+        let qubit_log = (0..coupling.num_qubits() as u32)
+            .map(|qubit| {
+                let qubit = PhysicalQubit::new(qubit);
+                (1. - error_rate(errors, [qubit, qubit])).ln()
+            })
+            .collect::<Vec<_>>();
+        let best_step = coupling
+            .edges()
+            .iter()
+            .map(|[a, b]| {
+                gate_reliability(errors, *a, *b).ln()
+                    + qubit_log[a.index()].max(qubit_log[b.index()])
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+        Search {
+            coupling,
+            errors,
+            qubit_log,
+            best_step,
+            free: vec![true; coupling.num_qubits()],
+            path: Vec::new(),
+            score: 0.,
+            best: None,
+            steps: 0,
+            max_steps,
+        }
+    }
+
+    fn edge_log(&self, a: PhysicalQubit, b: PhysicalQubit) -> f64 {
+        gate_reliability(self.errors, a, b).ln()
+    }
+
+    fn push(&mut self, qubit: PhysicalQubit, gain: f64) {
+        self.free[qubit.index()] = false;
+        self.path.push(qubit);
+        self.score += gain;
+    }
+
+    fn pop(&mut self, gain: f64) {
+        let qubit = self.path.pop().unwrap();
+        self.free[qubit.index()] = true;
+        self.score -= gain;
+    }
+
+    /// Whether the current path can't beat the best one found, once it is `length` qubits long.
+    fn beaten(&self, length: usize) -> bool {
+        let Some((_, best)) = self.best.as_ref() else {
+            return false;
+        };
+        let remaining = length - self.path.len();
+        let bound = if remaining == 0 {
+            self.score
+        } else {
+            self.score + remaining as f64 * self.best_step
+        };
+        bound <= *best
+    }
+
+    fn extend(&mut self, length: usize) {
+        if self.steps >= self.max_steps {
+            return;
+        }
+        self.steps += 1;
+        if self.path.len() == length {
+            if !self.beaten(length) {
+                self.best = Some((self.path.clone(), self.score));
+            }
+            return;
+        }
+        if self.beaten(length) {
+            return;
+        }
+        let last = *self.path.last().unwrap();
+        let mut candidates = self
+            .coupling
+            .undirected_neighbors(last)
+            .iter()
+            .filter(|next| self.free[next.index()])
+            .map(|next| {
+                (
+                    *next,
+                    self.edge_log(last, *next) + self.qubit_log[next.index()],
+                )
+            })
+            .collect::<SmallVec<[(PhysicalQubit, f64); 4]>>();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        for (next, gain) in candidates {
+            self.push(next, gain);
+            self.extend(length);
+            self.pop(gain);
+        }
+    }
+
+    /// Find the most reliable path of `length` qubits that the search reaches, starting from the
+    /// most reliable qubits.
+    fn find(mut self, length: usize) -> Option<Vec<PhysicalQubit>> {
+        if length == 0 {
+            return Some(Vec::new());
+        }
+        let mut starts = (0..self.coupling.num_qubits() as u32)
+            .map(PhysicalQubit::new)
+            .collect::<Vec<_>>();
+        starts.sort_by(|a, b| {
+            self.qubit_log[b.index()]
+                .total_cmp(&self.qubit_log[a.index()])
+                .then(a.cmp(b))
+        });
+        for start in starts {
+            if self.steps >= self.max_steps {
+                break;
+            }
+            let gain = self.qubit_log[start.index()];
+            self.push(start, gain);
+            self.extend(length);
+            self.pop(gain);
+        }
+        self.best.map(|(path, _)| path)
+    }
+}
+
+/// The most reliable simple path of `length` physical qubits that is found within `max_steps`
+/// steps of the search, by the product of the success rates of its edges and its qubits, or
+/// `None` if none is found.
+///
+/// `errors` are the average error rates of the edges, and of the qubits at `[q, q]`; missing
+/// rates are taken as ideal.
+pub fn best_line(
+    coupling: &CouplingMap,
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+    length: usize,
+    max_steps: usize,
+) -> Option<Vec<PhysicalQubit>> {
+    if length > coupling.num_qubits() {
+        return None;
+    }
+    Search::new(coupling, errors, max_steps).find(length)
+}
+
+/// Find a layout of the virtual qubits of a circuit whose interactions form chains, in which
+/// every interaction is between neighboring physical qubits, on the most reliable line found.
+///
+/// The chains are placed end to end along the line.  Returns the physical qubit of each virtual
+/// qubit, or `None` if the interactions aren't all chains or if no line was found.
+pub fn line_layout_inner(
+    coupling: &CouplingMap,
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+    num_qubits: usize,
+    edges: &[[usize; 2]],
+    max_steps: usize,
+) -> Option<Vec<PhysicalQubit>> {
+    let _timer = instrumentation::Timer::start("line_layout");
+    let shape = interaction_shape(num_qubits, edges)?;
+    if shape.ring.is_some() {
+        return None;
+    }
+    let line = best_line(coupling, errors, num_qubits, max_steps)?;
+    let mut layout = vec![PhysicalQubit::new(0); num_qubits];
+    for (virt, phys) in shape.chains.iter().flatten().zip(line) {
+        layout[*virt] = phys;
+    }
+    Some(layout)
+}
+
+/// Find the most reliable line of physical qubits.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     length (int): the number of qubits of the line.
+///     error_map (ErrorMap | None): the average error rates of the edges, and of the qubits at
+///         ``(q, q)``.  Missing rates are taken as ideal.
+///     max_steps (int): the most steps of the search, after which the best line so far is
+///         returned.
+///
+/// Returns:
+///     list[int] | None: the physical qubits along the line, or ``None`` if none was found.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "best_line", signature = (coupling_map, length, error_map=None, max_steps=100_000))]
+pub fn py_best_line(
+    coupling_map: &CouplingMap,
+    length: usize,
+    error_map: Option<&ErrorMap>,
+    max_steps: usize,
+) -> Option<Vec<PhysicalQubit>> {
+    let no_errors = HashMap::new();
+    let errors = error_map.map_or(&no_errors, |map| &map.error_map);
+    best_line(coupling_map, errors, length, max_steps)
+}
+
+/// Find a layout for a circuit whose two-qubit interactions form chains, with every interaction on
+/// an edge of the coupling map, along the most reliable line found.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     num_qubits (int): the number of virtual qubits of the circuit.
+///     edges (list[tuple[int, int]]): the pairs of virtual qubits that interact.
+///     error_map (ErrorMap | None): the average error rates of the edges, and of the qubits at
+///         ``(q, q)``.  Missing rates are taken as ideal.
+///     max_steps (int): the most steps of the search, after which the best line so far is used.
+///
+/// Returns:
+///     list[int] | None: the physical qubit of each virtual qubit, or ``None`` if the interactions
+///     aren't chains or no line was found.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "line_layout", signature = (coupling_map, num_qubits, edges, error_map=None, max_steps=100_000))]
+pub fn py_line_layout(
+    coupling_map: &CouplingMap,
+    num_qubits: usize,
+    edges: Vec<[usize; 2]>,
+    error_map: Option<&ErrorMap>,
+    max_steps: usize,
+) -> PyResult<Option<Vec<PhysicalQubit>>> {
+    if let Some(qubit) = edges.iter().flatten().find(|qubit| **qubit >= num_qubits) {
+        return Err(AccelerateError::Index(format!(
+            "virtual qubit {qubit} is out of range for {num_qubits} qubits"
+        ))
+        .into());
+    }
+    let no_errors = HashMap::new();
+    let errors = error_map.map_or(&no_errors, |map| &map.error_map);
+    Ok(line_layout_inner(
+        coupling_map,
+        errors,
+        num_qubits,
+        &edges,
+        max_steps,
+    ))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn line_layout(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_best_line))?;
+    m.add_wrapped(wrap_pyfunction!(py_line_layout))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qubit(index: u32) -> PhysicalQubit {
+        PhysicalQubit::new(index)
+    }
+
+    fn coupling(num_qubits: usize, edges: &[[u32; 2]]) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits,
+            edges.iter().map(|[a, b]| [qubit(*a), qubit(*b)]).collect(),
+        )
+    }
+
+    fn line(num_qubits: u32) -> CouplingMap {
+        let edges = (1..num_qubits).map(|q| [q - 1, q]).collect::<Vec<_>>();
+        coupling(num_qubits as usize, &edges)
+    }
+
+    /// The error map with `error` on each of the `edges` in both directions, and `qubit_errors`
+    /// on the qubits.
+    fn errors(edges: &[([u32; 2], f64)], qubit_errors: &[f64]) -> HashMap<[PhysicalQubit; 2], f64> {
+        let mut out = HashMap::new();
+        for ([a, b], error) in edges {
+            out.insert([qubit(*a), qubit(*b)], *error);
+            out.insert([qubit(*b), qubit(*a)], *error);
+        }
+        for (index, error) in qubit_errors.iter().enumerate() {
+            out.insert([qubit(index as u32), qubit(index as u32)], *error);
+        }
+        out
+    }
+
+    fn indices(path: Vec<PhysicalQubit>) -> Vec<usize> {
+        path.into_iter().map(|qubit| qubit.index()).collect()
+    }
+
+    #[test]
+    fn test_best_line_avoids_bad_edges() {
+        let errors = errors(
+            &[([0, 1], 0.1), ([1, 2], 0.1), ([2, 3], 0.01), ([3, 4], 0.01)],
+            &[],
+        );
+        let path = best_line(&line(5), &errors, 3, 100_000).unwrap();
+        assert_eq!(indices(path), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_best_line_avoids_bad_qubits() {
+        let ring = coupling(4, &[[0, 1], [1, 2], [2, 3], [3, 0]]);
+        let errors = errors(&[], &[0.5, 0.01, 0., 0.]);
+        let mut path = indices(best_line(&ring, &errors, 3, 100_000).unwrap());
+        path.sort();
+        assert_eq!(path, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_no_line() {
+        let no_errors = HashMap::new();
+        assert_eq!(best_line(&line(3), &no_errors, 4, 100_000), None);
+        assert_eq!(best_line(&line(3), &no_errors, 0, 100_000), Some(vec![]));
+        let pairs = coupling(4, &[[0, 1], [2, 3]]);
+        assert_eq!(best_line(&pairs, &no_errors, 3, 100_000), None);
+        // The search stops before it reaches a path of two qubits.
+        assert_eq!(best_line(&line(3), &no_errors, 2, 1), None);
+    }
+
+    #[test]
+    fn test_line_layout() {
+        let coupling = line(6);
+        let errors = errors(&[([0, 1], 0.2)], &[]);
+        // A chain `0 - 2 - 1` and an idle qubit 3.
+        let layout = line_layout_inner(&coupling, &errors, 4, &[[0, 2], [2, 1]], 100_000).unwrap();
+        for [a, b] in [[0, 2], [2, 1]] {
+            assert!(coupling
+                .undirected_neighbors(layout[a])
+                .contains(&layout[b]));
+        }
+        let mut used = indices(layout);
+        used.sort();
+        used.dedup();
+        assert_eq!(used.len(), 4);
+        assert!(!used.contains(&0));
+    }
+
+    #[test]
+    fn test_line_layout_not_chains() {
+        let no_errors = HashMap::new();
+        let ring = [[0, 1], [1, 2], [2, 0]];
+        assert_eq!(
+            line_layout_inner(&line(5), &no_errors, 3, &ring, 100_000),
+            None
+        );
+        let star = [[0, 1], [0, 2], [0, 3]];
+        assert_eq!(
+            line_layout_inner(&line(5), &no_errors, 4, &star, 100_000),
+            None
+        );
+    }
+}
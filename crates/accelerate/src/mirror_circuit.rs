@@ -0,0 +1,411 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The construction of mirror circuits for benchmarking.
+//!
+//! A mirror circuit runs a circuit `C`, optionally a layer of random Paulis `P`, and then the
+//! inverse of `C`, whose layers are those of `C` in reverse with each instruction inverted.  It
+//! implements `C†PC`, which is the identity without the Paulis, so that each shot ideally measures
+//! all zeros.  With the Paulis, `C†PC` is a Pauli up to a sign whenever `C` is a Clifford circuit,
+//! and each shot ideally measures the bitstring of its `X` part, which the stabilizer simulation
+//! of [crate::stabilizer_state] finds without running the circuit.
+
+use std::f64::consts::FRAC_PI_2;
+
+use smallvec::{smallvec, SmallVec};
+
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::{CircuitData, Param};
+#[cfg(feature = "python")]
+use qiskit_circuit::imports;
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+use qiskit_circuit::operations::StandardGate;
+
+#[cfg(feature = "python")]
+use rand::prelude::*;
+
+#[cfg(feature = "python")]
+use crate::clifford::CliffordGate;
+#[cfg(feature = "python")]
+use crate::collect_blocks::block_instruction;
+#[cfg(feature = "python")]
+use crate::layers::layers_inner;
+#[cfg(feature = "python")]
+use crate::rng::{generator, PySeed};
+#[cfg(feature = "python")]
+use crate::stabilizer_state::{
+    stabilizer_operation, StabilizerInstruction, StabilizerOperation, StabilizerState,
+};
+
+/// The inverse of a standard gate with `params`, as a standard gate with its parameters, if the
+/// inverse is a standard gate.
+pub fn inverse_standard_gate(
+    gate: StandardGate,
+    params: &[f64],
+) -> Option<(StandardGate, SmallVec<[f64; 3]>)> {
+    if params.len() != gate.num_params() as usize {
+        return None;
+    }
+    let negated = || params.iter().map(|param| -param).collect();
+    let out = match gate {
+        StandardGate::IGate
+        | StandardGate::HGate
+        | StandardGate::XGate
+        | StandardGate::YGate
+        | StandardGate::ZGate
+        | StandardGate::CXGate
+        | StandardGate::CYGate
+        | StandardGate::CZGate
+        | StandardGate::CHGate
+        | StandardGate::SwapGate
+        | StandardGate::ECRGate
+        | StandardGate::CCXGate
+        | StandardGate::CSwapGate => (gate, SmallVec::new()),
+        StandardGate::SGate => (StandardGate::SdgGate, SmallVec::new()),
+        StandardGate::SdgGate => (StandardGate::SGate, SmallVec::new()),
+        StandardGate::TGate => (StandardGate::TdgGate, SmallVec::new()),
+        StandardGate::TdgGate => (StandardGate::TGate, SmallVec::new()),
+        StandardGate::SXGate => (StandardGate::SXdgGate, SmallVec::new()),
+        StandardGate::SXdgGate => (StandardGate::SXGate, SmallVec::new()),
+        StandardGate::GlobalPhaseGate
+        | StandardGate::RXGate
+        | StandardGate::RYGate
+        | StandardGate::RZGate
+        | StandardGate::PhaseGate
+        | StandardGate::U1Gate
+        | StandardGate::CPhaseGate
+        | StandardGate::CRXGate
+        | StandardGate::CRYGate
+        | StandardGate::CRZGate
+        | StandardGate::RXXGate
+        | StandardGate::RYYGate
+        | StandardGate::RZZGate
+        | StandardGate::RZXGate => (gate, negated()),
+        // Only the first angle of these is a rotation; the second is the axis.
+        StandardGate::RGate | StandardGate::XXMinusYYGate | StandardGate::XXPlusYYGate => {
+            (gate, smallvec![-params[0], params[1]])
+        }
+        StandardGate::U3Gate | StandardGate::UGate => {
+            (gate, smallvec![-params[0], -params[2], -params[1]])
+        }
+        // `u2(φ, λ)` is `u3(π/2, φ, λ)`.
+        StandardGate::U2Gate => (
+            StandardGate::U3Gate,
+            smallvec![-FRAC_PI_2, -params[1], -params[0]],
+        ),
+        StandardGate::ISwapGate => return None,
+    };
+    Some(out)
+}
+
+/// The instructions of the inverse of a circuit, in the order of its layers reversed.
+#[cfg(feature = "python")]
+struct MirrorHalves<'a> {
+    inverse: Vec<(PyObject, &'a [BitType], &'a [BitType])>,
+    /// The stabilizer operations of the circuit and of its inverse, if both are Clifford circuits.
+    clifford: Option<(Vec<StabilizerInstruction>, Vec<StabilizerInstruction>)>,
+}
+
+/// The stabilizer instruction of a Clifford gate or of an instruction that does nothing.
+#[cfg(feature = "python")]
+fn stabilizer_instruction(op: &Bound<PyAny>, qubits: &[BitType]) -> Option<StabilizerInstruction> {
+    match stabilizer_operation(op) {
+        Ok(operation @ (StabilizerOperation::Gate(_) | StabilizerOperation::Skip)) => {
+            Some(StabilizerInstruction {
+                operation,
+                qubits: qubits.iter().map(|q| *q as usize).collect(),
+                clbits: SmallVec::new(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Push a stabilizer instruction onto the instructions of a Clifford circuit, which stops being one
+/// once an instruction isn't.
+#[cfg(feature = "python")]
+fn push_clifford(
+    instructions: Option<Vec<StabilizerInstruction>>,
+    inst: Option<StabilizerInstruction>,
+) -> Option<Vec<StabilizerInstruction>> {
+    instructions.zip(inst).map(|(mut instructions, inst)| {
+        instructions.push(inst);
+        instructions
+    })
+}
+
+#[cfg(feature = "python")]
+fn mirror_halves<'a>(py: Python, circuit: &'a CircuitData) -> PyResult<MirrorHalves<'a>> {
+    let instructions = circuit.iter_instructions().collect::<Vec<_>>();
+    let wires = instructions
+        .iter()
+        .map(|(op, qubits, clbits)| Ok(block_instruction(circuit, op.bind(py), qubits, clbits)?.0))
+        .collect::<PyResult<Vec<_>>>()?;
+    let layers = layers_inner(circuit.num_qubits(), circuit.num_clbits(), &wires);
+    let mut forward = Some(Vec::with_capacity(instructions.len()));
+    for (op, qubits, _) in instructions.iter() {
+        forward = push_clifford(forward, stabilizer_instruction(op.bind(py), qubits));
+    }
+    let mut inverse = Vec::with_capacity(instructions.len());
+    let mut backward = forward
+        .as_ref()
+        .map(|_| Vec::with_capacity(instructions.len()));
+    for index in layers.into_iter().rev().flatten() {
+        let (op, qubits, clbits) = instructions[index];
+        let op = op.bind(py);
+        let inverse_op = match StandardGate::from_py_operation(op)?
+            .and_then(|(gate, params)| inverse_standard_gate(gate, &params))
+        {
+            Some((gate, params)) => gate.create_py_op(py, &params)?,
+            None => op.call_method0(intern!(py, "inverse"))?.unbind(),
+        };
+        if backward.is_some() {
+            backward = push_clifford(
+                backward,
+                stabilizer_instruction(inverse_op.bind(py), qubits),
+            );
+        }
+        inverse.push((inverse_op, qubits, clbits));
+    }
+    Ok(MirrorHalves {
+        inverse,
+        clifford: forward.zip(backward),
+    })
+}
+
+/// Build mirror circuits of a circuit, or the circuit with its inverse appended.
+///
+/// Each mirror circuit is the circuit, a layer of random Paulis on every qubit unless
+/// ``pauli_layer`` is false, and the inverse of the circuit, whose layers are those of the circuit
+/// in reverse with each instruction inverted.  The standard gates are inverted in Rust, and any
+/// other instruction by its ``inverse`` method.  The global phase of each mirror circuit is zero.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, without unbound parameters.
+///     num_circuits (int): the number of mirror circuits.
+///     pauli_layer (bool): whether to put a layer of random Paulis between the halves.
+///     barriers (bool): whether to put barriers around the middle of each mirror circuit.
+///     seed (int | SeedSequence | None): the seed of the random Paulis.
+///
+/// Returns:
+///     list[tuple[CircuitData, str | None]]: each mirror circuit with the bitstring that it
+///     ideally measures on its qubits, with the first qubit rightmost, or ``None`` if the circuit
+///     is not a Clifford circuit and there is a Pauli layer.
+///
+/// Raises:
+///     CircuitError: if an instruction of the circuit can't be inverted.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, num_circuits=1, pauli_layer=true, barriers=true, seed=None))]
+pub fn mirror_circuits(
+    py: Python,
+    circuit: &CircuitData,
+    num_circuits: usize,
+    pauli_layer: bool,
+    barriers: bool,
+    seed: Option<PySeed>,
+) -> PyResult<Vec<(CircuitData, Option<String>)>> {
+    let num_qubits = circuit.num_qubits();
+    let halves = mirror_halves(py, circuit)?;
+    let paulis = [
+        StandardGate::XGate,
+        StandardGate::YGate,
+        StandardGate::ZGate,
+    ]
+    .iter()
+    .map(|gate| gate.create_py_op(py, &[]))
+    .collect::<PyResult<Vec<_>>>()?;
+    let barrier = imports::BARRIER
+        .get_bound(py)?
+        .call1((num_qubits,))?
+        .unbind();
+    let all_qubits = (0..num_qubits as BitType).collect::<Vec<_>>();
+    // The state after the circuit, which each mirror circuit continues from.
+    let middle_state = match &halves.clifford {
+        Some((forward, _)) => {
+            let mut state = StabilizerState::zero(num_qubits);
+            state.run(forward, &mut [], &mut generator(0))?;
+            Some(state)
+        }
+        None => None,
+    };
+    let mut rng = PySeed::generator(seed);
+    let mut out = Vec::with_capacity(num_circuits);
+    for _ in 0..num_circuits {
+        // The index of the Pauli on each qubit in `I, X, Y, Z`.
+        let layer = if pauli_layer {
+            (0..num_qubits)
+                .map(|_| rng.gen_range(0..4))
+                .collect::<Vec<u8>>()
+        } else {
+            vec![0; num_qubits]
+        };
+        let mut mirror = circuit.copy_empty_like(py)?;
+        // The phases of the halves cancel.
+        mirror.set_global_phase(py, Param::Float(0.))?;
+        for (op, qubits, clbits) in circuit.iter_instructions() {
+            mirror.push_instruction(op.clone_ref(py), qubits, clbits)?;
+        }
+        if barriers {
+            mirror.push_instruction(barrier.clone_ref(py), &all_qubits, &[])?;
+        }
+        for (qubit, pauli) in layer.iter().enumerate() {
+            if *pauli != 0 {
+                let op = paulis[*pauli as usize - 1].clone_ref(py);
+                mirror.push_instruction(op, &[qubit as BitType], &[])?;
+            }
+        }
+        if barriers && pauli_layer {
+            mirror.push_instruction(barrier.clone_ref(py), &all_qubits, &[])?;
+        }
+        for (op, qubits, clbits) in halves.inverse.iter() {
+            mirror.push_instruction(op.clone_ref(py), qubits, clbits)?;
+        }
+        let expected = if !pauli_layer {
+            Some("0".repeat(num_qubits))
+        } else {
+            match (&middle_state, &halves.clifford) {
+                (Some(state), Some((_, backward))) => {
+                    Some(expected_bitstring(state, &layer, backward)?)
+                }
+                _ => None,
+            }
+        };
+        out.push((mirror, expected));
+    }
+    Ok(out)
+}
+
+/// The bitstring that a mirror circuit of a Clifford circuit measures, from the state after the
+/// circuit, the Pauli on each qubit of the middle layer and the inverse of the circuit.
+#[cfg(feature = "python")]
+fn expected_bitstring(
+    middle_state: &StabilizerState,
+    layer: &[u8],
+    backward: &[StabilizerInstruction],
+) -> PyResult<String> {
+    let mut state = middle_state.clone();
+    for (qubit, pauli) in layer.iter().enumerate() {
+        let gate = match pauli {
+            1 => CliffordGate::X,
+            2 => CliffordGate::Y,
+            3 => CliffordGate::Z,
+            _ => continue,
+        };
+        state.apply_gate(gate, &[qubit])?;
+    }
+    state.run(backward, &mut [], &mut generator(0))?;
+    (0..state.num_qubits())
+        .rev()
+        .map(|qubit| {
+            let outcome = state
+                .deterministic_outcome(qubit)?
+                .expect("a Pauli maps |0…0⟩ to a computational basis state");
+            Ok(if outcome { '1' } else { '0' })
+        })
+        .collect()
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn mirror_circuit(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(mirror_circuits))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ndarray::Array2;
+    use num_complex::Complex64;
+    use qiskit_circuit::operations::STANDARD_GATE_SIZE;
+
+    const GATE_NAMES: [&str; STANDARD_GATE_SIZE] = [
+        "global_phase",
+        "id",
+        "h",
+        "x",
+        "y",
+        "z",
+        "s",
+        "sdg",
+        "t",
+        "tdg",
+        "sx",
+        "sxdg",
+        "rx",
+        "ry",
+        "rz",
+        "r",
+        "p",
+        "u1",
+        "u2",
+        "u3",
+        "u",
+        "cx",
+        "cy",
+        "cz",
+        "ch",
+        "cp",
+        "crx",
+        "cry",
+        "crz",
+        "swap",
+        "iswap",
+        "ecr",
+        "rxx",
+        "ryy",
+        "rzz",
+        "rzx",
+        "xx_minus_yy",
+        "xx_plus_yy",
+        "ccx",
+        "cswap",
+    ];
+
+    #[test]
+    fn test_inverse_standard_gates() {
+        let params = [0.3, -1.1, 0.7];
+        for name in GATE_NAMES {
+            let gate = StandardGate::from_name(name).unwrap();
+            let params = &params[..gate.num_params() as usize];
+            let Some((inverse, inverse_params)) = inverse_standard_gate(gate, params) else {
+                assert_eq!(gate, StandardGate::ISwapGate);
+                continue;
+            };
+            let product = inverse
+                .matrix(&inverse_params)
+                .unwrap()
+                .dot(&gate.matrix(params).unwrap());
+            let identity = Array2::<Complex64>::eye(product.nrows());
+            let distance = (&product - &identity)
+                .iter()
+                .map(|value| value.norm())
+                .fold(0., f64::max);
+            assert!(distance < 1e-12, "the inverse of {name} is wrong");
+        }
+    }
+
+    #[test]
+    fn test_inverse_wrong_number_of_params() {
+        assert_eq!(inverse_standard_gate(StandardGate::RXGate, &[]), None);
+        assert_eq!(inverse_standard_gate(StandardGate::HGate, &[0.5]), None);
+    }
+}
@@ -10,11 +10,16 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::PyList;
 
 use hashbrown::HashMap;
 
+use crate::coupling_map::CouplingMap;
+use crate::error::AccelerateError;
+
 /// A newtype for the different categories of qubits used within layouts.  This is to enforce
 /// significantly more type safety when dealing with mixtures of physical and virtual qubits, as we
 /// typically are when dealing with layouts.  In Rust space, `NLayout` only works in terms of the
@@ -37,23 +42,27 @@ macro_rules! qubit_newtype {
             }
         }
 
+        #[cfg(feature = "python")]
         impl pyo3::IntoPy<PyObject> for $id {
             fn into_py(self, py: Python<'_>) -> PyObject {
                 self.0.into_py(py)
             }
         }
+        #[cfg(feature = "python")]
         impl pyo3::ToPyObject for $id {
             fn to_object(&self, py: Python<'_>) -> PyObject {
                 self.0.to_object(py)
             }
         }
 
+        #[cfg(feature = "python")]
         impl pyo3::FromPyObject<'_> for $id {
             fn extract(ob: &PyAny) -> PyResult<Self> {
                 Ok(Self(ob.extract()?))
             }
         }
 
+        #[cfg(feature = "python")]
         unsafe impl numpy::Element for $id {
             const IS_COPY: bool = true;
 
@@ -81,6 +90,52 @@ impl VirtualQubit {
     }
 }
 
+/// Summary statistics of how well a layout places the interactions of a circuit on a coupling map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.nlayout", frozen)
+)]
+pub struct LayoutStats {
+    /// The number of interactions, counting repeated pairs each time.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub num_interactions: usize,
+    /// The mean undirected distance between the physical qubits of the interacting pairs, which is
+    /// one for a layout that needs no routing, or zero if there are no interactions.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub mean_distance: f64,
+    /// The largest distance between the physical qubits of an interacting pair.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub max_distance: f64,
+    /// The number of interactions between physical qubits that aren't neighbors.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub non_adjacent: usize,
+    /// The number of virtual qubits that aren't on the physical qubit of the same index.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub num_displaced: usize,
+    /// The mean distance of each virtual qubit from the physical qubit of the same index, over all
+    /// the virtual qubits.
+    #[cfg_attr(feature = "python", pyo3(get))]
+    pub mean_displacement: f64,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl LayoutStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "LayoutStats(num_interactions={}, mean_distance={}, max_distance={}, non_adjacent={}, \
+             num_displaced={}, mean_displacement={})",
+            self.num_interactions,
+            self.mean_distance,
+            self.max_distance,
+            self.non_adjacent,
+            self.num_displaced,
+            self.mean_displacement
+        )
+    }
+}
+
 /// An unsigned integer Vector based layout class
 ///
 /// This class tracks the layout (or mapping between virtual qubits in the the
@@ -91,17 +146,15 @@ impl VirtualQubit {
 ///         physical qubit index on the coupling graph.
 ///     logical_qubits (int): The number of logical qubits in the layout
 ///     physical_qubits (int): The number of physical qubits in the layout
-#[pyclass(module = "qiskit._accelerate.nlayout")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.nlayout"))]
 #[derive(Clone, Debug)]
 pub struct NLayout {
     virt_to_phys: Vec<PhysicalQubit>,
     phys_to_virt: Vec<VirtualQubit>,
 }
 
-#[pymethods]
 impl NLayout {
-    #[new]
-    fn new(
+    pub fn new(
         qubit_indices: HashMap<VirtualQubit, PhysicalQubit>,
         virtual_qubits: usize,
         physical_qubits: usize,
@@ -117,45 +170,17 @@ impl NLayout {
         res
     }
 
-    fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
-        Ok((
-            py.get_type_bound::<Self>()
-                .getattr("from_virtual_to_physical")?,
-            (self.virt_to_phys.to_object(py),),
-        )
-            .into_py(py))
-    }
-
-    /// Return the layout mapping.
-    ///
-    /// .. note::
-    ///
-    ///     This copies the data from Rust to Python and has linear overhead based on the number of
-    ///     qubits.
-    ///
-    /// Returns:
-    ///     list: A list of 2 element lists in the form ``[(virtual_qubit, physical_qubit), ...]``,
-    ///     where the virtual qubit is the index in the qubit index in the circuit.
-    ///
-    #[pyo3(text_signature = "(self, /)")]
-    fn layout_mapping(&self, py: Python<'_>) -> Py<PyList> {
-        PyList::new_bound(py, self.iter_virtual()).into()
-    }
-
     /// Get physical bit from virtual bit
-    #[pyo3(text_signature = "(self, virtual, /)")]
     pub fn virtual_to_physical(&self, r#virtual: VirtualQubit) -> PhysicalQubit {
         self.virt_to_phys[r#virtual.index()]
     }
 
     /// Get virtual bit from physical bit
-    #[pyo3(text_signature = "(self, physical, /)")]
     pub fn physical_to_virtual(&self, physical: PhysicalQubit) -> VirtualQubit {
         self.phys_to_virt[physical.index()]
     }
 
     /// Swap the specified virtual qubits
-    #[pyo3(text_signature = "(self, bit_a, bit_b, /)")]
     pub fn swap_virtual(&mut self, bit_a: VirtualQubit, bit_b: VirtualQubit) {
         self.virt_to_phys.swap(bit_a.index(), bit_b.index());
         self.phys_to_virt[self.virt_to_phys[bit_a.index()].index()] = bit_a;
@@ -163,7 +188,6 @@ impl NLayout {
     }
 
     /// Swap the specified physical qubits
-    #[pyo3(text_signature = "(self, bit_a, bit_b, /)")]
     pub fn swap_physical(&mut self, bit_a: PhysicalQubit, bit_b: PhysicalQubit) {
         self.phys_to_virt.swap(bit_a.index(), bit_b.index());
         self.virt_to_phys[self.phys_to_virt[bit_a.index()].index()] = bit_a;
@@ -174,7 +198,6 @@ impl NLayout {
         self.clone()
     }
 
-    #[staticmethod]
     pub fn generate_trivial_layout(num_qubits: u32) -> Self {
         NLayout {
             virt_to_phys: (0..num_qubits).map(PhysicalQubit).collect(),
@@ -182,20 +205,22 @@ impl NLayout {
         }
     }
 
-    #[staticmethod]
-    pub fn from_virtual_to_physical(virt_to_phys: Vec<PhysicalQubit>) -> PyResult<Self> {
+    pub fn from_virtual_to_physical(
+        virt_to_phys: Vec<PhysicalQubit>,
+    ) -> Result<Self, AccelerateError> {
         let mut phys_to_virt = vec![VirtualQubit(std::u32::MAX); virt_to_phys.len()];
         for (virt, phys) in virt_to_phys.iter().enumerate() {
-            phys_to_virt[phys.index()] = VirtualQubit(virt.try_into()?);
+            phys_to_virt[phys.index()] =
+                VirtualQubit(virt.try_into().map_err(|err: std::num::TryFromIntError| {
+                    AccelerateError::Overflow(err.to_string())
+                })?);
         }
         Ok(NLayout {
             virt_to_phys,
             phys_to_virt,
         })
     }
-}
 
-impl NLayout {
     /// Iterator of `(VirtualQubit, PhysicalQubit)` pairs, in order of the `VirtualQubit` indices.
     pub fn iter_virtual(
         &'_ self,
@@ -214,10 +239,278 @@ impl NLayout {
             .enumerate()
             .map(|(p, v)| (PhysicalQubit::new(p as u32), *v))
     }
+
+    /// Summary statistics of the layout of a circuit whose virtual qubits interact in the pairs
+    /// `interactions` on `coupling`.  Distances are undirected, and infinite between qubits in
+    /// different components of the map.
+    pub fn stats(
+        &self,
+        coupling: &CouplingMap,
+        interactions: &[[VirtualQubit; 2]],
+    ) -> Result<LayoutStats, AccelerateError> {
+        if let Some(qubit) = interactions
+            .iter()
+            .flatten()
+            .find(|qubit| qubit.index() >= self.virt_to_phys.len())
+        {
+            return Err(AccelerateError::Index(format!(
+                "virtual qubit {} is out of range for {} virtual qubits",
+                qubit.index(),
+                self.virt_to_phys.len()
+            )));
+        }
+        if let Some((virt, phys)) = self
+            .iter_virtual()
+            .find(|(_, phys)| phys.index() >= coupling.num_qubits())
+        {
+            return Err(AccelerateError::Index(format!(
+                "virtual qubit {} is on physical qubit {}, which is out of range for {} qubits",
+                virt.index(),
+                phys.index(),
+                coupling.num_qubits()
+            )));
+        }
+        let distance = coupling.distance_matrix();
+        let mut total_distance = 0.;
+        let mut max_distance = 0f64;
+        let mut non_adjacent = 0;
+        for [a, b] in interactions {
+            let pair = distance[[a.to_phys(self).index(), b.to_phys(self).index()]];
+            total_distance += pair;
+            max_distance = max_distance.max(pair);
+            if pair > 1. {
+                non_adjacent += 1;
+            }
+        }
+        let mut num_displaced = 0;
+        let mut total_displacement = 0.;
+        for (virt, phys) in self.iter_virtual() {
+            if virt.index() == phys.index() {
+                continue;
+            }
+            num_displaced += 1;
+            total_displacement += if virt.index() < coupling.num_qubits() {
+                distance[[virt.index(), phys.index()]]
+            } else {
+                f64::INFINITY
+            };
+        }
+        let mean = |total: f64, count: usize| {
+            if count == 0 {
+                0.
+            } else {
+                total / count as f64
+            }
+        };
+        Ok(LayoutStats {
+            num_interactions: interactions.len(),
+            mean_distance: mean(total_distance, interactions.len()),
+            max_distance,
+            non_adjacent,
+            num_displaced,
+            mean_displacement: mean(total_displacement, self.virt_to_phys.len()),
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl NLayout {
+    #[new]
+    fn py_new(
+        qubit_indices: HashMap<VirtualQubit, PhysicalQubit>,
+        virtual_qubits: usize,
+        physical_qubits: usize,
+    ) -> Self {
+        NLayout::new(qubit_indices, virtual_qubits, physical_qubits)
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        Ok((
+            py.get_type_bound::<Self>()
+                .getattr("from_virtual_to_physical")?,
+            (self.virt_to_phys.to_object(py),),
+        )
+            .into_py(py))
+    }
+
+    /// Return the layout mapping.
+    ///
+    /// .. note::
+    ///
+    ///     This copies the data from Rust to Python and has linear overhead based on the number of
+    ///     qubits.
+    ///
+    /// Returns:
+    ///     list: A list of 2 element lists in the form ``[(virtual_qubit, physical_qubit), ...]``,
+    ///     where the virtual qubit is the index in the qubit index in the circuit.
+    ///
+    #[pyo3(text_signature = "(self, /)")]
+    fn layout_mapping(&self, py: Python<'_>) -> Py<PyList> {
+        PyList::new_bound(py, self.iter_virtual()).into()
+    }
+
+    /// Get physical bit from virtual bit
+    #[pyo3(name = "virtual_to_physical", text_signature = "(self, virtual, /)")]
+    fn py_virtual_to_physical(&self, r#virtual: VirtualQubit) -> PhysicalQubit {
+        self.virtual_to_physical(r#virtual)
+    }
+
+    /// Get virtual bit from physical bit
+    #[pyo3(name = "physical_to_virtual", text_signature = "(self, physical, /)")]
+    fn py_physical_to_virtual(&self, physical: PhysicalQubit) -> VirtualQubit {
+        self.physical_to_virtual(physical)
+    }
+
+    /// Swap the specified virtual qubits
+    #[pyo3(name = "swap_virtual", text_signature = "(self, bit_a, bit_b, /)")]
+    fn py_swap_virtual(&mut self, bit_a: VirtualQubit, bit_b: VirtualQubit) {
+        self.swap_virtual(bit_a, bit_b)
+    }
+
+    /// Swap the specified physical qubits
+    #[pyo3(name = "swap_physical", text_signature = "(self, bit_a, bit_b, /)")]
+    fn py_swap_physical(&mut self, bit_a: PhysicalQubit, bit_b: PhysicalQubit) {
+        self.swap_physical(bit_a, bit_b)
+    }
+
+    #[pyo3(name = "copy")]
+    fn py_copy(&self) -> NLayout {
+        self.clone()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "generate_trivial_layout")]
+    fn py_generate_trivial_layout(num_qubits: u32) -> Self {
+        NLayout::generate_trivial_layout(num_qubits)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_virtual_to_physical")]
+    fn py_from_virtual_to_physical(virt_to_phys: Vec<PhysicalQubit>) -> PyResult<Self> {
+        Ok(NLayout::from_virtual_to_physical(virt_to_phys)?)
+    }
+
+    /// Summary statistics of how well the layout places the interactions of a circuit.
+    ///
+    /// Args:
+    ///     coupling_map (CouplingMap): the coupling map of the device.
+    ///     interactions (list[tuple[int, int]]): the pairs of virtual qubits of each two-qubit
+    ///         gate of the circuit.  Repeated pairs count each time.
+    ///
+    /// Returns:
+    ///     LayoutStats: the distances between interacting qubits and from the trivial layout.
+    ///
+    /// Raises:
+    ///     IndexError: if a qubit is out of range of the layout or of the coupling map.
+    #[pyo3(
+        name = "stats",
+        text_signature = "(self, coupling_map, interactions, /)"
+    )]
+    fn py_stats(
+        &self,
+        coupling_map: &CouplingMap,
+        interactions: Vec<[VirtualQubit; 2]>,
+    ) -> PyResult<LayoutStats> {
+        Ok(self.stats(coupling_map, &interactions)?)
+    }
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn nlayout(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<NLayout>()?;
+    m.add_class::<LayoutStats>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(virt_to_phys: &[u32]) -> NLayout {
+        NLayout::from_virtual_to_physical(
+            virt_to_phys
+                .iter()
+                .copied()
+                .map(PhysicalQubit::new)
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    fn coupling_map(num_qubits: usize, edges: &[[u32; 2]]) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits,
+            edges
+                .iter()
+                .map(|[a, b]| [PhysicalQubit::new(*a), PhysicalQubit::new(*b)])
+                .collect(),
+        )
+    }
+
+    fn interactions(pairs: &[[u32; 2]]) -> Vec<[VirtualQubit; 2]> {
+        pairs
+            .iter()
+            .map(|[a, b]| [VirtualQubit::new(*a), VirtualQubit::new(*b)])
+            .collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let line = coupling_map(4, &[[0, 1], [2, 1], [2, 3]]);
+        let stats = layout(&[3, 0, 1, 2])
+            .stats(&line, &interactions(&[[0, 1], [1, 2], [1, 0]]))
+            .unwrap();
+        assert_eq!(
+            stats,
+            LayoutStats {
+                num_interactions: 3,
+                mean_distance: 7. / 3.,
+                max_distance: 3.,
+                non_adjacent: 2,
+                num_displaced: 4,
+                mean_displacement: 1.5,
+            }
+        );
+        let stats = layout(&[0, 1, 2, 3]).stats(&line, &[]).unwrap();
+        assert_eq!(
+            stats,
+            LayoutStats {
+                num_interactions: 0,
+                mean_distance: 0.,
+                max_distance: 0.,
+                non_adjacent: 0,
+                num_displaced: 0,
+                mean_displacement: 0.,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_disconnected() {
+        let two_edges = coupling_map(4, &[[0, 1], [2, 3]]);
+        let stats = layout(&[0, 1, 2, 3])
+            .stats(&two_edges, &interactions(&[[0, 1], [1, 2]]))
+            .unwrap();
+        assert_eq!(stats.mean_distance, f64::INFINITY);
+        assert_eq!(stats.max_distance, f64::INFINITY);
+        assert_eq!(stats.non_adjacent, 1);
+        let stats = layout(&[2, 1, 0, 3]).stats(&two_edges, &[]).unwrap();
+        assert_eq!(stats.num_displaced, 2);
+        assert_eq!(stats.mean_displacement, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_stats_out_of_range() {
+        let line = coupling_map(2, &[[0, 1]]);
+        assert!(matches!(
+            layout(&[0, 1]).stats(&line, &interactions(&[[0, 2]])),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            layout(&[2, 0, 1]).stats(&line, &interactions(&[[0, 1]])),
+            Err(AccelerateError::Index(_))
+        ));
+    }
+}
@@ -0,0 +1,449 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A greedy noise-adaptive layout, after Murali et al., "Noise-Adaptive Compiler Mappings for
+//! Noisy Intermediate-Scale Quantum Computers" (ASPLOS 2019).
+//!
+//! Each pair of physical qubits is given a reliability: the success rate of the two-qubit gate
+//! if they're neighbors, and otherwise the success rate of the most reliable chain of swaps
+//! between them, taking a swap as three two-qubit gates.  The virtual qubits are then placed one at
+//! a time to maximize the product of the reliabilities of their interactions with the qubits
+//! already placed and of their own single-qubit reliability.  The most used interaction is placed
+//! first, on the most reliable edge, and the placement grows from there along the interactions.
+//!
+//! Unlike VF2, this never needs to enumerate isomorphisms, so it gives a layout in polynomial time
+//! for any interaction graph, at the cost of the layout not being perfect even if one exists.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use hashbrown::HashMap;
+use ndarray::prelude::*;
+use rayon::prelude::*;
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::coupling_map::CouplingMap;
+use crate::error::{AccelerateError, ErrorContext};
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::instrumentation;
+use crate::nlayout::PhysicalQubit;
+use crate::threading::{self, ParallelKernel};
+
+/// The error rate of `qargs`, with missing and `NaN` rates taken as ideal.
+pub(crate) fn error_rate(
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+    qargs: [PhysicalQubit; 2],
+) -> f64 {
+    match errors.get(&qargs) {
+        Some(error) if !error.is_nan() => *error,
+        _ => 0.,
+    }
+}
+
+/// The success rate of a two-qubit gate between neighbors `a` and `b`, in the better direction.
+pub(crate) fn gate_reliability(
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+    a: PhysicalQubit,
+    b: PhysicalQubit,
+) -> f64 {
+    let forward = errors
+        .contains_key(&[a, b])
+        .then(|| error_rate(errors, [a, b]));
+    let backward = errors
+        .contains_key(&[b, a])
+        .then(|| error_rate(errors, [b, a]));
+    let error = match (forward, backward) {
+        (Some(forward), Some(backward)) => forward.min(backward),
+        (Some(error), None) | (None, Some(error)) => error,
+        (None, None) => 0.,
+    };
+    1. - error
+}
+
+/// The cost of an interaction between every pair of physical qubits: `gate_cost` between
+/// neighbors, and otherwise the cost of the cheapest chain of swaps between them, taking a swap as
+/// three gates.  The gate costs must not be negative.
+pub fn swap_path_costs<F>(coupling: &CouplingMap, gate_cost: F) -> Array2<f64>
+where
+    F: Fn(PhysicalQubit, PhysicalQubit) -> f64 + Sync,
+{
+    let num_qubits = coupling.num_qubits();
+    let row = |source: usize| -> Array1<f64> {
+        let mut dist = Array1::from_elem(num_qubits, f64::INFINITY);
+        dist[source] = 0.;
+        // The costs are all non-negative, and the bit patterns of non-negative floats order the
+        // same as their values, so they can be heap keys.
+        let mut heap = BinaryHeap::from([Reverse((0f64.to_bits(), source))]);
+        while let Some(Reverse((cost, qubit))) = heap.pop() {
+            let cost = f64::from_bits(cost);
+            if cost > dist[qubit] {
+                continue;
+            }
+            let qubit = PhysicalQubit::new(qubit as u32);
+            for neighbor in coupling.undirected_neighbors(qubit) {
+                let next = cost + 3. * gate_cost(qubit, *neighbor);
+                if next < dist[neighbor.index()] {
+                    dist[neighbor.index()] = next;
+                    heap.push(Reverse((next.to_bits(), neighbor.index())));
+                }
+            }
+        }
+        let source = PhysicalQubit::new(source as u32);
+        for neighbor in coupling.undirected_neighbors(source) {
+            dist[neighbor.index()] = gate_cost(source, *neighbor);
+        }
+        dist
+    };
+    let mut out = Array2::zeros((num_qubits, num_qubits));
+    if threading::run_in_parallel(ParallelKernel::NoiseAdaptiveLayout, num_qubits) {
+        threading::install(|| {
+            out.axis_iter_mut(Axis(0))
+                .into_par_iter()
+                .enumerate()
+                .for_each(|(source, mut out_row)| out_row.assign(&row(source)))
+        });
+    } else {
+        for (source, mut out_row) in out.axis_iter_mut(Axis(0)).enumerate() {
+            out_row.assign(&row(source));
+        }
+    }
+    out
+}
+
+/// The logarithms of the reliabilities of every pair of physical qubits, which are the negated
+/// lengths of the shortest paths with a cost of `-3 ln(r)` for a swap over an edge of gate
+/// reliability `r`, except between neighbors, where it's `ln(r)`.
+pub fn log_reliabilities(
+    coupling: &CouplingMap,
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+) -> Array2<f64> {
+    -swap_path_costs(coupling, |a, b| -gate_reliability(errors, a, b).ln())
+}
+
+/// The item with the largest score, with the first one winning ties.
+fn argmax<T, I>(values: I) -> Option<T>
+where
+    I: IntoIterator<Item = (T, f64)>,
+{
+    values
+        .into_iter()
+        .fold(None, |best: Option<(T, f64)>, (item, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((item, score)),
+        })
+        .map(|(item, _)| item)
+}
+
+/// Place `num_qubits` virtual qubits on a coupling map to maximize the reliability of their
+/// interactions.
+///
+/// `interactions` are the pairs of virtual qubits that interact and the number of two-qubit gates
+/// between each pair.  `errors` are the average error rates of the edges, and of the qubits at
+/// `[q, q]`.  Returns the physical qubit of each virtual qubit.
+pub fn noise_adaptive_layout_inner(
+    num_qubits: usize,
+    interactions: &[([usize; 2], usize)],
+    coupling: &CouplingMap,
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+) -> Result<Vec<PhysicalQubit>, AccelerateError> {
+    let _timer = instrumentation::Timer::start("noise_adaptive_layout");
+    let num_physical = coupling.num_qubits();
+    if num_qubits > num_physical {
+        return Err(AccelerateError::Layout(
+            "Number of qubits greater than device.".to_string(),
+            ErrorContext::pass("NoiseAdaptiveLayout"),
+        ));
+    }
+    let reliability = log_reliabilities(coupling, errors);
+    let qubit_reliability = (0..num_physical as u32)
+        .map(|qubit| {
+            let qubit = PhysicalQubit::new(qubit);
+            (1. - error_rate(errors, [qubit, qubit])).ln()
+        })
+        .collect::<Vec<_>>();
+
+    let mut interactions = interactions
+        .iter()
+        .filter(|([a, b], count)| a != b && *count > 0)
+        .map(|([a, b], count)| ([*a.min(b), *a.max(b)], *count))
+        .collect::<Vec<_>>();
+    interactions.sort_by_key(|(pair, count)| (Reverse(*count), *pair));
+    let mut partners = vec![SmallVec::<[(usize, usize); 4]>::new(); num_qubits];
+    for ([a, b], count) in interactions.iter() {
+        partners[*a].push((*b, *count));
+        partners[*b].push((*a, *count));
+    }
+
+    let mut layout: Vec<Option<PhysicalQubit>> = vec![None; num_qubits];
+    let mut used = vec![false; num_physical];
+    // The number of gates between each unplaced qubit and the placed ones.
+    let mut pull = vec![0usize; num_qubits];
+    let place = |virt: usize,
+                 phys: PhysicalQubit,
+                 layout: &mut [Option<PhysicalQubit>],
+                 used: &mut [bool],
+                 pull: &mut [usize]| {
+        layout[virt] = Some(phys);
+        used[phys.index()] = true;
+        for (partner, count) in partners[virt].iter() {
+            pull[*partner] += count;
+        }
+    };
+    let free = |used: &[bool]| {
+        (0..num_physical as u32)
+            .map(PhysicalQubit::new)
+            .filter(|qubit| !used[qubit.index()])
+            .collect::<Vec<_>>()
+    };
+
+    loop {
+        // Grow from the unplaced qubit with the most gates to the placed ones.
+        let next = argmax(
+            (0..num_qubits)
+                .filter(|virt| layout[*virt].is_none() && pull[*virt] > 0)
+                .map(|virt| (virt, pull[virt] as f64)),
+        );
+        if let Some(virt) = next {
+            let best = argmax(free(&used).into_iter().map(|phys| {
+                let score = qubit_reliability[phys.index()]
+                    + partners[virt]
+                        .iter()
+                        .filter_map(|(partner, count)| {
+                            let other = layout[*partner]?;
+                            Some(*count as f64 * reliability[[phys.index(), other.index()]])
+                        })
+                        .sum::<f64>();
+                (phys, score)
+            }))
+            .expect("there are more physical qubits than virtual ones");
+            place(virt, best, &mut layout, &mut used, &mut pull);
+            continue;
+        }
+        // Otherwise start again from the most used interaction between unplaced qubits, on the
+        // most reliable free edge.
+        let Some(([a, b], count)) = interactions
+            .iter()
+            .find(|([a, b], _)| layout[*a].is_none() && layout[*b].is_none())
+        else {
+            break;
+        };
+        let edge = argmax(free(&used).into_iter().flat_map(|p| {
+            let (used, reliability, qubit_reliability) = (&used, &reliability, &qubit_reliability);
+            coupling
+                .undirected_neighbors(p)
+                .iter()
+                .filter(move |q| !used[q.index()])
+                .map(move |q| {
+                    let score = *count as f64 * reliability[[p.index(), q.index()]]
+                        + qubit_reliability[p.index()]
+                        + qubit_reliability[q.index()];
+                    ((p, *q), score)
+                })
+        }));
+        match edge {
+            Some((p, q)) => {
+                place(*a, p, &mut layout, &mut used, &mut pull);
+                place(*b, q, &mut layout, &mut used, &mut pull);
+            }
+            None => {
+                // No free edges are left, so `b` is placed as near to `a` as possible next.
+                let best = argmax(
+                    free(&used)
+                        .into_iter()
+                        .map(|phys| (phys, qubit_reliability[phys.index()])),
+                )
+                .expect("there are more physical qubits than virtual ones");
+                place(*a, best, &mut layout, &mut used, &mut pull);
+            }
+        }
+    }
+    // The qubits without interactions go on the most reliable qubits left.
+    let mut remaining = free(&used);
+    remaining.sort_by(|a, b| {
+        qubit_reliability[b.index()]
+            .total_cmp(&qubit_reliability[a.index()])
+            .then(a.cmp(b))
+    });
+    let mut remaining = remaining.into_iter();
+    Ok(layout
+        .into_iter()
+        .map(|phys| phys.unwrap_or_else(|| remaining.next().unwrap()))
+        .collect())
+}
+
+/// Choose a layout that maximizes the reliability of the interactions of a circuit, as the
+/// ``NoiseAdaptiveLayout`` pass does.
+///
+/// Args:
+///     coupling_map (CouplingMap): the coupling map of the device.
+///     error_map (ErrorMap): the average error rates of the edges, and of the qubits at ``(q, q)``.
+///     num_qubits (int): the number of virtual qubits of the circuit.
+///     interactions (list[tuple[tuple[int, int], int]]): each pair of virtual qubits that interacts
+///         with the number of two-qubit gates between them.
+///
+/// Returns:
+///     list[int]: the physical qubit of each virtual qubit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "noise_adaptive_layout")]
+pub fn py_noise_adaptive_layout(
+    coupling_map: &CouplingMap,
+    error_map: &ErrorMap,
+    num_qubits: usize,
+    interactions: Vec<([usize; 2], usize)>,
+) -> PyResult<Vec<PhysicalQubit>> {
+    if let Some(qubit) = interactions
+        .iter()
+        .flat_map(|(pair, _)| pair)
+        .find(|qubit| **qubit >= num_qubits)
+    {
+        return Err(AccelerateError::Index(format!(
+            "virtual qubit {qubit} is out of range for {num_qubits} qubits"
+        ))
+        .into());
+    }
+    Ok(noise_adaptive_layout_inner(
+        num_qubits,
+        &interactions,
+        coupling_map,
+        &error_map.error_map,
+    )?)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn noise_adaptive_layout(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_noise_adaptive_layout))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    fn qubit(index: u32) -> PhysicalQubit {
+        PhysicalQubit::new(index)
+    }
+
+    fn line(num_qubits: u32) -> CouplingMap {
+        CouplingMap::new(
+            num_qubits as usize,
+            (1..num_qubits).map(|q| [qubit(q - 1), qubit(q)]).collect(),
+        )
+    }
+
+    /// The error map with `error` on each of the `edges` in both directions, and `qubit_errors`
+    /// on the qubits.
+    fn errors(edges: &[([u32; 2], f64)], qubit_errors: &[f64]) -> HashMap<[PhysicalQubit; 2], f64> {
+        let mut out = HashMap::new();
+        for ([a, b], error) in edges {
+            out.insert([qubit(*a), qubit(*b)], *error);
+            out.insert([qubit(*b), qubit(*a)], *error);
+        }
+        for (index, error) in qubit_errors.iter().enumerate() {
+            out.insert([qubit(index as u32), qubit(index as u32)], *error);
+        }
+        out
+    }
+
+    fn assert_adjacent(coupling: &CouplingMap, layout: &[PhysicalQubit], pairs: &[[usize; 2]]) {
+        for [a, b] in pairs {
+            assert!(coupling
+                .undirected_neighbors(layout[*a])
+                .contains(&layout[*b]));
+        }
+    }
+
+    #[test]
+    fn test_log_reliabilities() {
+        let coupling = line(4);
+        let mut errors = errors(&[([0, 1], 0.1), ([2, 3], 0.3)], &[]);
+        // The better direction of an edge counts, and a `NaN` error is ideal.
+        errors.insert([qubit(2), qubit(1)], 0.2);
+        errors.insert([qubit(1), qubit(2)], f64::NAN);
+        let reliability = log_reliabilities(&coupling, &errors);
+        let (r01, r12, r23) = (0.9f64.ln(), 0., 0.7f64.ln());
+        let expected = array![
+            [0., r01, 3. * (r01 + r12), 3. * (r01 + r12 + r23)],
+            [r01, 0., r12, 3. * (r12 + r23)],
+            [3. * (r01 + r12), r12, 0., r23],
+            [3. * (r01 + r12 + r23), 3. * (r12 + r23), r23, 0.],
+        ];
+        assert_abs_diff_eq!(reliability, expected, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_disconnected_reliabilities() {
+        let coupling = CouplingMap::new(3, vec![[qubit(0), qubit(1)]]);
+        let reliability = log_reliabilities(&coupling, &HashMap::new());
+        assert_eq!(reliability[[0, 1]], 0.);
+        assert_eq!(reliability[[0, 2]], f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_avoids_noisy_edges() {
+        let coupling = line(5);
+        let errors = errors(
+            &[([0, 1], 0.2), ([1, 2], 0.01), ([2, 3], 0.02), ([3, 4], 0.3)],
+            &[0.01; 5],
+        );
+        let interactions = [([0, 1], 2), ([2, 1], 1)];
+        let layout = noise_adaptive_layout_inner(3, &interactions, &coupling, &errors).unwrap();
+        assert_eq!(layout, [qubit(1), qubit(2), qubit(3)]);
+    }
+
+    #[test]
+    fn test_chain_placed_along_line() {
+        let coupling = line(6);
+        let errors = errors(&(0..5).map(|q| ([q, q + 1], 0.01)).collect::<Vec<_>>(), &[]);
+        let pairs = [[0, 1], [1, 2], [2, 3]];
+        let interactions = pairs.map(|pair| (pair, 1));
+        let layout = noise_adaptive_layout_inner(4, &interactions, &coupling, &errors).unwrap();
+        assert_adjacent(&coupling, &layout, &pairs);
+    }
+
+    #[test]
+    fn test_idle_qubits_on_most_reliable() {
+        let coupling = line(4);
+        let errors = errors(&[], &[0.3, 0.05, 0.2, 0.1]);
+        // Interactions with themselves or with no gates don't count.
+        let interactions = [([0, 0], 3), ([0, 1], 0)];
+        let layout = noise_adaptive_layout_inner(2, &interactions, &coupling, &errors).unwrap();
+        assert_eq!(layout, [qubit(1), qubit(3)]);
+    }
+
+    #[test]
+    fn test_no_free_edges() {
+        // Only one edge, so the second interaction can't be placed on one.
+        let coupling = CouplingMap::new(4, vec![[qubit(0), qubit(1)]]);
+        let interactions = [([0, 1], 2), ([2, 3], 1)];
+        let layout =
+            noise_adaptive_layout_inner(4, &interactions, &coupling, &HashMap::new()).unwrap();
+        assert_eq!(layout, [qubit(0), qubit(1), qubit(2), qubit(3)]);
+    }
+
+    #[test]
+    fn test_too_many_qubits() {
+        assert!(matches!(
+            noise_adaptive_layout_inner(4, &[], &line(3), &HashMap::new()),
+            Err(AccelerateError::Layout(..))
+        ));
+    }
+}
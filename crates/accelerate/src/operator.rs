@@ -0,0 +1,1648 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Dense operator algebra over complex matrices.  These are the kernels behind the hot methods of
+//! `qiskit.quantum_info.Operator`; every function here works on plain `ndarray` views, so they are
+//! equally usable from Rust.  [apply_left_inner] and `from_circuit_data` build the unitary of a
+//! circuit by applying each gate to the rows of the accumulated matrix in place.
+
+#[cfg(feature = "python")]
+use indexmap::IndexMap;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use pyo3::Python;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use faer::linalg::matmul::matmul;
+use faer::solvers::SolverCore;
+use faer::{Mat, Parallelism, Side};
+use faer_ext::{IntoFaerComplex, IntoNdarrayComplex};
+use ndarray::prelude::*;
+use ndarray::Zip;
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::statevector::apply_matrix_inner;
+#[cfg(feature = "python")]
+use crate::statevector::{operation_matrix, OperationMatrix};
+use crate::threading::{self, ParallelKernel};
+
+const C_ZERO: Complex64 = Complex64::new(0., 0.);
+const C_ONE: Complex64 = Complex64::new(1., 0.);
+const C_MINUS_ONE: Complex64 = Complex64::new(-1., 0.);
+const C_I: Complex64 = Complex64::new(0., 1.);
+const C_MINUS_I: Complex64 = Complex64::new(0., -1.);
+
+/// The tolerances `numpy.allclose` (and the Python-space `Operator`) default to.
+pub const RTOL_DEFAULT: f64 = 1e-5;
+pub const ATOL_DEFAULT: f64 = 1e-8;
+
+#[inline]
+fn run_in_parallel(dim: usize) -> bool {
+    threading::run_in_parallel(ParallelKernel::Operator, dim)
+}
+
+fn check_square(mat: &ArrayView2<Complex64>, what: &str) -> Result<(), AccelerateError> {
+    if mat.nrows() != mat.ncols() {
+        return Err(AccelerateError::Qiskit(format!(
+            "{what} requires a square matrix, but got shape {:?}",
+            mat.shape()
+        )));
+    }
+    Ok(())
+}
+
+/// Compute the matrix product `left @ right`.
+pub fn matmul_inner(
+    left: ArrayView2<Complex64>,
+    right: ArrayView2<Complex64>,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    if left.ncols() != right.nrows() {
+        return Err(AccelerateError::Qiskit(format!(
+            "cannot multiply matrices with shapes {:?} and {:?}",
+            left.shape(),
+            right.shape()
+        )));
+    }
+    let mut out = Mat::<faer::complex_native::c64>::zeros(left.nrows(), right.ncols());
+    let mut multiply = |parallelism| {
+        matmul(
+            out.as_mut(),
+            left.into_faer_complex(),
+            right.into_faer_complex(),
+            None,
+            faer::complex_native::c64::new(1., 0.),
+            parallelism,
+        )
+    };
+    if run_in_parallel(left.nrows().max(right.ncols())) {
+        // faer uses all the threads of the pool it's called from.
+        threading::install(|| multiply(Parallelism::Rayon(0)));
+    } else {
+        multiply(Parallelism::None);
+    }
+    Ok(out.as_ref().into_ndarray_complex().to_owned())
+}
+
+/// Compose two operators.  With `front == false` this is the operator that applies `first` and
+/// then `second`, _i.e._ the matrix product `second @ first`; with `front == true` the order of
+/// application is reversed.  This matches `Operator.compose`.
+pub fn compose_inner(
+    first: ArrayView2<Complex64>,
+    second: ArrayView2<Complex64>,
+    front: bool,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    if front {
+        matmul_inner(first, second)
+    } else {
+        matmul_inner(second, first)
+    }
+}
+
+/// The Kronecker product `left ⊗ right`.
+pub fn tensor_inner(
+    left: ArrayView2<Complex64>,
+    right: ArrayView2<Complex64>,
+) -> Array2<Complex64> {
+    let (rows_r, cols_r) = right.dim();
+    let mut out = Array2::<Complex64>::zeros((left.nrows() * rows_r, left.ncols() * cols_r));
+    let fill_row = |(row, mut out_row): (usize, ArrayViewMut1<Complex64>)| {
+        let left_row = left.row(row / rows_r);
+        let right_row = right.row(row % rows_r);
+        for (col_l, left_val) in left_row.iter().enumerate() {
+            out_row
+                .slice_mut(s![col_l * cols_r..(col_l + 1) * cols_r])
+                .zip_mut_with(&right_row, |out, right_val| *out = left_val * right_val);
+        }
+    };
+    if run_in_parallel(out.nrows()) {
+        threading::install(|| {
+            out.axis_iter_mut(Axis(0))
+                .into_par_iter()
+                .enumerate()
+                .for_each(fill_row)
+        });
+    } else {
+        out.axis_iter_mut(Axis(0)).enumerate().for_each(fill_row);
+    }
+    out
+}
+
+/// The transpose of a matrix, in standard (row-major) layout.
+pub fn transpose_inner(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let mut out = Array2::<Complex64>::zeros((mat.ncols(), mat.nrows()));
+    let zip = Zip::from(&mut out).and(mat.t());
+    if run_in_parallel(mat.nrows()) {
+        threading::install(|| zip.par_for_each(|out, val| *out = *val));
+    } else {
+        zip.for_each(|out, val| *out = *val);
+    }
+    out
+}
+
+/// The conjugate transpose of a matrix, in standard (row-major) layout.
+pub fn adjoint_inner(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let mut out = Array2::<Complex64>::zeros((mat.ncols(), mat.nrows()));
+    let zip = Zip::from(&mut out).and(mat.t());
+    if run_in_parallel(mat.nrows()) {
+        threading::install(|| zip.par_for_each(|out, val| *out = val.conj()));
+    } else {
+        zip.for_each(|out, val| *out = val.conj());
+    }
+    out
+}
+
+/// The inverse of a square matrix, computed by an LU decomposition with partial pivoting.
+pub fn inverse_inner(mat: ArrayView2<Complex64>) -> Result<Array2<Complex64>, AccelerateError> {
+    check_square(&mat, "inversion")?;
+    let inverse = mat.into_faer_complex().partial_piv_lu().inverse();
+    let out = inverse.as_ref().into_ndarray_complex().to_owned();
+    if out.iter().any(|x| !x.is_finite()) {
+        return Err(AccelerateError::Qiskit("Singular matrix".to_string()));
+    }
+    Ok(out)
+}
+
+/// Raise a square matrix to an integer power by repeated squaring.  Negative powers are powers of
+/// the inverse, and the zeroth power is the identity.
+pub fn power_inner(
+    mat: ArrayView2<Complex64>,
+    n: i64,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    check_square(&mat, "matrix power")?;
+    let mut base = if n < 0 {
+        inverse_inner(mat)?
+    } else {
+        mat.to_owned()
+    };
+    let mut exp = n.unsigned_abs();
+    let mut out: Option<Array2<Complex64>> = None;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = Some(match out {
+                Some(out) => matmul_inner(out.view(), base.view())?,
+                None => base.clone(),
+            });
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = matmul_inner(base.view(), base.view())?;
+        }
+    }
+    Ok(out.unwrap_or_else(|| Array2::eye(mat.nrows())))
+}
+
+/// The coefficients of the degree-13 Padé approximant of the exponential, from the constant term.
+const PADE_13: [f64; 14] = [
+    64764752532480000.,
+    32382376266240000.,
+    7771770303897600.,
+    1187353796428800.,
+    129060195264000.,
+    10559470521600.,
+    670442572800.,
+    33522128640.,
+    1323241920.,
+    40840800.,
+    960960.,
+    16380.,
+    182.,
+    1.,
+];
+
+/// The largest 1-norm of a matrix whose exponential the degree-13 Padé approximant gives to double
+/// precision.
+const PADE_13_THETA: f64 = 5.371920351148152;
+
+/// The largest off-diagonal element [unitary_eig] accepts when diagonalizing a unitary.
+const EIG_TOL: f64 = 1e-9;
+
+/// The dimension up to which [hamiltonian_evolution_inner] exponentiates through an
+/// eigendecomposition, rather than by scaling and squaring.
+const EVOLUTION_EIGH_MAX_DIM: usize = 64;
+
+/// The 1-norm of a matrix, _i.e._ its largest absolute column sum.
+fn norm_1(mat: &ArrayView2<Complex64>) -> f64 {
+    mat.columns()
+        .into_iter()
+        .map(|col| col.iter().map(|x| x.norm()).sum::<f64>())
+        .fold(0., f64::max)
+}
+
+/// The exponential of a square matrix, by scaling and squaring with a degree-13 Padé approximant,
+/// as in Higham, SIAM J. Matrix Anal. Appl. 26, 1179 (2005).
+pub fn expm_inner(mat: ArrayView2<Complex64>) -> Result<Array2<Complex64>, AccelerateError> {
+    check_square(&mat, "matrix exponential")?;
+    // `f64::max` skips `NaN`, so the norm alone doesn't catch every non-finite entry.
+    if mat.iter().any(|x| !x.is_finite()) {
+        return Err(AccelerateError::Value(
+            "cannot exponentiate a matrix with non-finite entries".to_string(),
+        ));
+    }
+    let norm = norm_1(&mat);
+    let squarings = if norm > PADE_13_THETA {
+        (norm / PADE_13_THETA).log2().ceil() as i32
+    } else {
+        0
+    };
+    let scale = 0.5f64.powi(squarings);
+    let a = mat.mapv(|x| x * scale);
+    let ident = Array2::<Complex64>::eye(a.nrows());
+    let a2 = matmul_inner(a.view(), a.view())?;
+    let a4 = matmul_inner(a2.view(), a2.view())?;
+    let a6 = matmul_inner(a4.view(), a2.view())?;
+    let b = PADE_13;
+    // The odd and even parts of the numerator, `u` and `v`, so the approximant is `(v - u)^-1
+    // (v + u)`.
+    let u_high = &a6 * b[13] + &a4 * b[11] + &a2 * b[9];
+    let u_low = &a6 * b[7] + &a4 * b[5] + &a2 * b[3] + &ident * b[1];
+    let u_inner = matmul_inner(a6.view(), u_high.view())? + u_low;
+    let u = matmul_inner(a.view(), u_inner.view())?;
+    let v_high = &a6 * b[12] + &a4 * b[10] + &a2 * b[8];
+    let v_low = &a6 * b[6] + &a4 * b[4] + &a2 * b[2] + &ident * b[0];
+    let v = matmul_inner(a6.view(), v_high.view())? + v_low;
+    let denominator = inverse_inner((&v - &u).view())?;
+    let mut out = matmul_inner(denominator.view(), (&v + &u).view())?;
+    for _ in 0..squarings {
+        out = matmul_inner(out.view(), out.view())?;
+    }
+    Ok(out)
+}
+
+/// The time-evolution operator `exp(-i t H)` of the Hermitian `hamiltonian` for a `time`.  Only
+/// the lower triangle of `hamiltonian` is read for small matrices, which are exponentiated through
+/// their eigendecomposition; larger ones go through [expm_inner].
+pub fn hamiltonian_evolution_inner(
+    hamiltonian: ArrayView2<Complex64>,
+    time: f64,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    check_square(&hamiltonian, "Hamiltonian evolution")?;
+    if hamiltonian.nrows() > EVOLUTION_EIGH_MAX_DIM {
+        let generator = hamiltonian.mapv(|x| x * Complex64::new(0., -time));
+        return expm_inner(generator.view());
+    }
+    let eig = hamiltonian
+        .into_faer_complex()
+        .selfadjoint_eigendecomposition(Side::Lower);
+    let u = eig.u().into_ndarray_complex();
+    let mut u_phase = u.to_owned();
+    for (mut col, s) in u_phase
+        .columns_mut()
+        .into_iter()
+        .zip(eig.s().column_vector().iter())
+    {
+        let phase = Complex64::from_polar(1., -time * s.re);
+        col.mapv_inplace(|x| x * phase);
+    }
+    matmul_inner(u_phase.view(), adjoint_inner(u).view())
+}
+
+/// The eigendecomposition `mat = V diag(λ) V†` of a unitary, with `V` unitary even when there are
+/// degenerate eigenvalues.
+///
+/// The Hermitian and anti-Hermitian parts of a unitary commute, so they have a common eigenbasis,
+/// which we find as the eigenbasis of a generic real combination of the two.  An unlucky
+/// combination can merge distinct eigenvalues, so we check the result and try another one.
+pub fn unitary_eig(mat: ArrayView2<Complex64>) -> (Array1<Complex64>, Array2<Complex64>) {
+    let adj = adjoint_inner(mat);
+    let herm = (&mat + &adj).mapv(|x| x * 0.5);
+    let anti = (&mat - &adj).mapv(|x| x * Complex64::new(0., -0.5));
+    let mut best: Option<(f64, Array1<Complex64>, Array2<Complex64>)> = None;
+    for weight in [0.577, 1.618, 2.414, 0.289] {
+        let combined = &herm + &anti.mapv(|x| x * weight);
+        let eig = combined
+            .view()
+            .into_faer_complex()
+            .selfadjoint_eigendecomposition(Side::Lower);
+        let vecs = eig.u().into_ndarray_complex().to_owned();
+        let diagonal = adjoint_inner(vecs.view()).dot(&mat).dot(&vecs);
+        let off_diagonal = diagonal
+            .indexed_iter()
+            .filter(|((i, j), _)| i != j)
+            .map(|(_, x)| x.norm())
+            .fold(0., f64::max);
+        let better = best
+            .as_ref()
+            .map_or(true, |(error, _, _)| off_diagonal < *error);
+        if better {
+            best = Some((off_diagonal, diagonal.diag().to_owned(), vecs));
+        }
+        if off_diagonal < EIG_TOL {
+            break;
+        }
+    }
+    let (_, vals, vecs) = best.expect("at least one combination was tried");
+    (vals, vecs)
+}
+
+/// The Hermitian generator `H` of a unitary, with `mat = exp(i H)` and the eigenvalues of `H` in
+/// `(-π, π]`, so that `i H` is the principal logarithm of `mat`.
+pub fn unitary_generator_inner(
+    mat: ArrayView2<Complex64>,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    check_square(&mat, "a unitary logarithm")?;
+    let (vals, vecs) = unitary_eig(mat);
+    let mut vecs_phase = vecs.clone();
+    for (mut col, val) in vecs_phase.columns_mut().into_iter().zip(vals.iter()) {
+        let phase = val.arg();
+        col.mapv_inplace(|x| x * phase);
+    }
+    Ok(vecs_phase.dot(&adjoint_inner(vecs.view())))
+}
+
+/// The principal logarithm of a unitary, which is anti-Hermitian.
+pub fn logm_unitary_inner(
+    mat: ArrayView2<Complex64>,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    Ok(unitary_generator_inner(mat)?.mapv(|x| x * Complex64::new(0., 1.)))
+}
+
+/// The decomposition `mat = exp(i phase) exp(-i Σ_P coeffs[P] P)` of a one- or two-qubit unitary
+/// into a global phase and a rotation about a sum of non-identity Paulis, from its principal
+/// logarithm.
+///
+/// The Paulis are indexed by two bits per qubit, with qubit `k` in bits `2k` and `2k + 1` and `0`,
+/// `1`, `2` and `3` standing for `I`, `X`, `Y` and `Z`; the identity, index `0`, is left out of
+/// `coeffs`, so the coefficient of the Pauli with index `p` is `coeffs[p - 1]`.
+#[derive(Clone, Debug)]
+pub struct PauliRotation {
+    pub num_qubits: usize,
+    pub phase: f64,
+    pub coeffs: Vec<f64>,
+}
+
+impl PauliRotation {
+    /// The Pauli label of the coefficient `coeffs[index]`, with qubit 0 rightmost.
+    pub fn label(&self, index: usize) -> String {
+        (0..self.num_qubits)
+            .rev()
+            .map(|qubit| ['I', 'X', 'Y', 'Z'][((index + 1) >> (2 * qubit)) & 3])
+            .collect()
+    }
+
+    /// The angle `θ` and the unit axis `n` of the rotation `exp(-i θ/2 n·σ)` of a one-qubit
+    /// unitary, with `θ` in `[0, 2π]`.  The axis of the identity rotation is taken as `Z`.
+    pub fn axis_angle(&self) -> Result<(f64, [f64; 3]), AccelerateError> {
+        if self.num_qubits != 1 {
+            return Err(AccelerateError::Qiskit(format!(
+                "a {}-qubit rotation has no axis",
+                self.num_qubits
+            )));
+        }
+        let norm = self.coeffs.iter().map(|c| c * c).sum::<f64>().sqrt();
+        if norm == 0. {
+            return Ok((0., [0., 0., 1.]));
+        }
+        Ok((
+            2. * norm,
+            [
+                self.coeffs[0] / norm,
+                self.coeffs[1] / norm,
+                self.coeffs[2] / norm,
+            ],
+        ))
+    }
+}
+
+/// The entry `[row, col]` of the Pauli with index `pauli`, in the indexing of [PauliRotation].
+fn pauli_entry(pauli: usize, row: usize, col: usize, num_qubits: usize) -> Complex64 {
+    const PAULIS: [[[Complex64; 2]; 2]; 4] = [
+        [[C_ONE, C_ZERO], [C_ZERO, C_ONE]],
+        [[C_ZERO, C_ONE], [C_ONE, C_ZERO]],
+        [[C_ZERO, C_MINUS_I], [C_I, C_ZERO]],
+        [[C_ONE, C_ZERO], [C_ZERO, C_MINUS_ONE]],
+    ];
+    (0..num_qubits).fold(C_ONE, |acc, qubit| {
+        acc * PAULIS[(pauli >> (2 * qubit)) & 3][(row >> qubit) & 1][(col >> qubit) & 1]
+    })
+}
+
+/// Decompose a one- or two-qubit unitary into a global phase and a Pauli rotation; see
+/// [PauliRotation].
+pub fn pauli_rotation_inner(mat: ArrayView2<Complex64>) -> Result<PauliRotation, AccelerateError> {
+    let num_qubits = match mat.dim() {
+        (2, 2) => 1,
+        (4, 4) => 2,
+        shape => {
+            return Err(AccelerateError::Qiskit(format!(
+                "expected a one- or two-qubit unitary, but got shape {:?}",
+                shape
+            )))
+        }
+    };
+    let generator = unitary_generator_inner(mat)?;
+    let dim = generator.nrows() as f64;
+    // `Tr[P H] / dim` is the coefficient of `P` in `H`, and the rotation is about `-H`.
+    let trace_with = |pauli: usize| {
+        generator
+            .indexed_iter()
+            .map(|((row, col), val)| pauli_entry(pauli, col, row, num_qubits) * val)
+            .sum::<Complex64>()
+            .re
+            / dim
+    };
+    Ok(PauliRotation {
+        num_qubits,
+        phase: trace_with(0),
+        coeffs: (1..1 << (2 * num_qubits)).map(|p| -trace_with(p)).collect(),
+    })
+}
+
+/// The unit-modulus phase that rotates the first entry of `mat` with magnitude above `atol` onto
+/// the positive real axis, or `1` if there's no such entry.
+fn phase_correction(mat: &ArrayView2<Complex64>, atol: f64) -> Complex64 {
+    mat.iter()
+        .find(|x| x.norm() > atol)
+        .map(|x| Complex64::from_polar(1., -x.arg()))
+        .unwrap_or(Complex64::new(1., 0.))
+}
+
+/// Check whether two matrices are equal to within the given tolerances, using the same
+/// elementwise criterion as `numpy.allclose`:
+///
+/// ```text
+/// |left - right| <= atol + rtol * |right|
+/// ```
+///
+/// If `ignore_phase` is set, both matrices are first rotated so that their first entries with
+/// magnitude above `atol` are real and positive, making the comparison insensitive to a global
+/// phase.  This is the same normalization as `qiskit.quantum_info.operators.predicates.matrix_equal`.
+pub fn matrix_equal_inner(
+    left: ArrayView2<Complex64>,
+    right: ArrayView2<Complex64>,
+    ignore_phase: bool,
+    rtol: f64,
+    atol: f64,
+) -> bool {
+    if left.shape() != right.shape() {
+        return false;
+    }
+    let (phase_l, phase_r) = if ignore_phase {
+        (
+            phase_correction(&left, atol),
+            phase_correction(&right, atol),
+        )
+    } else {
+        (Complex64::new(1., 0.), Complex64::new(1., 0.))
+    };
+    let close = |l: &Complex64, r: &Complex64| {
+        let r = r * phase_r;
+        (l * phase_l - r).norm() <= atol + rtol * r.norm()
+    };
+    if run_in_parallel(left.nrows()) {
+        threading::install(|| {
+            left.axis_iter(Axis(0))
+                .into_par_iter()
+                .zip(right.axis_iter(Axis(0)))
+                .all(|(l, r)| l.iter().zip(r.iter()).all(|(l, r)| close(l, r)))
+        })
+    } else {
+        Zip::from(&left).and(&right).all(close)
+    }
+}
+
+/// Whether the `(row, col)` entry of a matrix is close to that of the identity, with the same
+/// criterion as [matrix_equal_inner].
+#[inline]
+fn close_to_identity(val: Complex64, row: usize, col: usize, rtol: f64, atol: f64) -> bool {
+    if row == col {
+        (val - 1.).norm() <= atol + rtol
+    } else {
+        val.norm() <= atol
+    }
+}
+
+/// Whether every row of `mat`, by index, satisfies `pred`, with the rows split between threads for
+/// large matrices.
+fn all_rows<F>(mat: &ArrayView2<Complex64>, pred: F) -> bool
+where
+    F: Fn(usize, ArrayView1<Complex64>) -> bool + Sync,
+{
+    if run_in_parallel(mat.nrows()) {
+        threading::install(|| {
+            mat.axis_iter(Axis(0))
+                .into_par_iter()
+                .enumerate()
+                .all(|(row, vals)| pred(row, vals))
+        })
+    } else {
+        mat.axis_iter(Axis(0))
+            .enumerate()
+            .all(|(row, vals)| pred(row, vals))
+    }
+}
+
+/// Check whether a matrix is the identity to within the given tolerances, in the sense of
+/// [matrix_equal_inner].  A matrix that isn't square is never the identity.
+///
+/// If `ignore_phase` is set, the matrix is first rotated so that its `[0, 0]` entry is real and
+/// positive, as `qiskit.quantum_info.operators.predicates.is_identity_matrix` does.
+pub fn is_identity_inner(
+    mat: ArrayView2<Complex64>,
+    ignore_phase: bool,
+    rtol: f64,
+    atol: f64,
+) -> bool {
+    if mat.nrows() != mat.ncols() {
+        return false;
+    }
+    let phase = match mat.get((0, 0)) {
+        Some(first) if ignore_phase => Complex64::from_polar(1., -first.arg()),
+        _ => Complex64::new(1., 0.),
+    };
+    all_rows(&mat, |row, vals| {
+        vals.iter()
+            .enumerate()
+            .all(|(col, val)| close_to_identity(val * phase, row, col, rtol, atol))
+    })
+}
+
+/// Check whether a matrix is Hermitian to within the given tolerances, comparing it to its
+/// conjugate transpose in the sense of [matrix_equal_inner].  A matrix that isn't square is never
+/// Hermitian.
+pub fn is_hermitian_inner(mat: ArrayView2<Complex64>, rtol: f64, atol: f64) -> bool {
+    if mat.nrows() != mat.ncols() {
+        return false;
+    }
+    all_rows(&mat, |row, vals| {
+        vals.iter().enumerate().all(|(col, val)| {
+            let adjoint = mat[[col, row]].conj();
+            (val - adjoint).norm() <= atol + rtol * adjoint.norm()
+        })
+    })
+}
+
+/// Check whether a matrix is unitary to within the given tolerances, _i.e._ whether its product
+/// with its conjugate transpose is the identity in the sense of [is_identity_inner].  A matrix that
+/// isn't square is never unitary.
+///
+/// The product is never formed: each of its entries is the inner product of two rows of `mat`,
+/// computed as it's checked, so the check stops at the first entry that's out of tolerance.  For a
+/// square matrix, `U U^dagger` is the identity exactly when `U^dagger U` is, and working with rows
+/// keeps the memory access contiguous for C-ordered arrays.
+pub fn is_unitary_inner(mat: ArrayView2<Complex64>, rtol: f64, atol: f64) -> bool {
+    if mat.nrows() != mat.ncols() {
+        return false;
+    }
+    // The product is Hermitian, so only the entries on and above the diagonal are checked.
+    all_rows(&mat, |row, vals| {
+        (row..mat.nrows()).all(|col| {
+            let val = vals
+                .iter()
+                .zip(mat.row(col))
+                .fold(Complex64::new(0., 0.), |acc, (l, r)| acc + l * r.conj());
+            close_to_identity(val, row, col, rtol, atol)
+        })
+    })
+}
+
+/// Write into `out` the embedding of the `k`-qubit matrix `mat` into the operator on all the qubits
+/// of `out`, with `mat` acting on `qubits` and the identity on the other qubits.  The `i`th qubit of
+/// `mat` is qubit `qubits[i]` of `out`, so this handles every order of the qubits.
+///
+/// The `qubits` must be distinct and in range for `out`, and `mat` must be `2**k`-dimensional;
+/// [embed_inner] checks this.
+pub fn embed_into<Q>(mat: ArrayView2<Complex64>, qubits: &[Q], out: &mut Array2<Complex64>)
+where
+    Q: Copy + Into<usize> + Sync,
+{
+    let mask = qubits
+        .iter()
+        .fold(0usize, |mask, q| mask | (1 << (*q).into()));
+    // The index into `mat` of an index into `out`.
+    let local = |index: usize| {
+        qubits.iter().enumerate().fold(0usize, |acc, (i, q)| {
+            acc | (((index >> (*q).into()) & 1) << i)
+        })
+    };
+    let fill_row = |(row, mut out_row): (usize, ArrayViewMut1<Complex64>)| {
+        let local_row = local(row);
+        for (col, value) in out_row.iter_mut().enumerate() {
+            *value = if row & !mask == col & !mask {
+                mat[[local_row, local(col)]]
+            } else {
+                Complex64::new(0., 0.)
+            };
+        }
+    };
+    if run_in_parallel(out.nrows()) {
+        threading::install(|| {
+            out.axis_iter_mut(Axis(0))
+                .into_par_iter()
+                .enumerate()
+                .for_each(fill_row)
+        });
+    } else {
+        out.axis_iter_mut(Axis(0)).enumerate().for_each(fill_row);
+    }
+}
+
+/// The embedding of the `k`-qubit matrix `mat` acting on `qubits` into an operator on
+/// `num_qubits` qubits; see [embed_into].
+pub fn embed_inner(
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+    num_qubits: usize,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    if 2 * num_qubits >= usize::BITS as usize {
+        return Err(AccelerateError::Overflow(format!(
+            "a {num_qubits}-qubit operator is too large to represent"
+        )));
+    }
+    if let Some(&qubit) = qubits.iter().find(|&&q| q >= num_qubits) {
+        return Err(AccelerateError::Index(format!(
+            "qubit {qubit} is out of range for a {num_qubits}-qubit operator"
+        )));
+    }
+    if (1..qubits.len()).any(|i| qubits[..i].contains(&qubits[i])) {
+        return Err(AccelerateError::Value(format!(
+            "the qubits {qubits:?} are not distinct"
+        )));
+    }
+    let dim = 1usize << qubits.len();
+    if mat.shape() != [dim, dim] {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} can't act on {} qubits",
+            mat.shape(),
+            qubits.len()
+        )));
+    }
+    let mut out = Array2::zeros((1 << num_qubits, 1 << num_qubits));
+    embed_into(mat, qubits, &mut out);
+    Ok(out)
+}
+
+/// Check whether `left` is `exp(i φ) right` to within the given tolerances, in the sense of
+/// [matrix_equal_inner], and return the phase `φ` if so.
+///
+/// The phase is the one that best aligns the two matrices in the Frobenius norm, _i.e._ the phase
+/// of `Tr[right† left]`, so unlike the `ignore_phase` normalization of [matrix_equal_inner] it
+/// doesn't hang on any single entry.
+pub fn equal_up_to_phase_inner(
+    left: ArrayView2<Complex64>,
+    right: ArrayView2<Complex64>,
+    rtol: f64,
+    atol: f64,
+) -> Option<f64> {
+    if left.shape() != right.shape() {
+        return None;
+    }
+    let overlap = Zip::from(&left)
+        .and(&right)
+        .fold(Complex64::new(0., 0.), |acc, l, r| acc + r.conj() * l);
+    let phase = if overlap.norm() > 0. {
+        overlap.arg()
+    } else {
+        0.
+    };
+    let rotation = Complex64::from_polar(1., phase);
+    let close = |l: &Complex64, r: &Complex64| {
+        let r = r * rotation;
+        (l - r).norm() <= atol + rtol * r.norm()
+    };
+    let equal = if run_in_parallel(left.nrows()) {
+        threading::install(|| {
+            left.axis_iter(Axis(0))
+                .into_par_iter()
+                .zip(right.axis_iter(Axis(0)))
+                .all(|(l, r)| l.iter().zip(r.iter()).all(|(l, r)| close(l, r)))
+        })
+    } else {
+        Zip::from(&left).and(&right).all(close)
+    };
+    equal.then_some(phase)
+}
+
+/// Left-multiply the `2**n`-dimensional `unitary` in place by the embedding of the `k`-qubit
+/// matrix `mat` acting on `qubits`, _i.e._ apply `mat` to those qubits after `unitary`.  The matrix
+/// is little-endian with respect to `qubits`, as in `Operator.compose` with `qargs`.
+///
+/// This never forms the full `2**n`-dimensional embedding of `mat`: the row index of a C-ordered
+/// `unitary` is the high half of the bits of its flat index, so this is the statevector kernel
+/// applied to the `2n`-qubit "state" of the buffer.
+pub fn apply_left_inner(
+    unitary: &mut Array2<Complex64>,
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+) -> Result<(), AccelerateError> {
+    check_square(&unitary.view(), "applying a gate to an operator")?;
+    if !unitary.nrows().is_power_of_two() {
+        return Err(AccelerateError::Qiskit(format!(
+            "an operator of dimension {} is not a qubit operator",
+            unitary.nrows()
+        )));
+    }
+    let num_qubits = unitary.nrows().trailing_zeros() as usize;
+    if let Some(&qubit) = qubits.iter().find(|&&q| q >= num_qubits) {
+        return Err(AccelerateError::Index(format!(
+            "qubit {qubit} is out of range for a {num_qubits}-qubit operator"
+        )));
+    }
+    if !unitary.is_standard_layout() {
+        *unitary = unitary.as_standard_layout().into_owned();
+    }
+    let row_qubits = qubits.iter().map(|q| q + num_qubits).collect::<Vec<_>>();
+    apply_matrix_inner(
+        unitary
+            .as_slice_mut()
+            .expect("the operator was made contiguous above"),
+        mat,
+        &row_qubits,
+    )
+}
+
+/// Compose two operators given as matrices.
+///
+/// Args:
+///     first (ndarray): the matrix of the operator that is applied first.
+///     second (ndarray): the matrix of the operator that is applied second.
+///     front (bool): if ``True``, reverse the order of application, so the result is
+///         ``first @ second`` rather than ``second @ first``.
+///
+/// Returns:
+///     ndarray: the matrix of the composed operator.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (first, second, front=false))]
+pub fn compose(
+    py: Python,
+    first: PyReadonlyArray2<Complex64>,
+    second: PyReadonlyArray2<Complex64>,
+    front: bool,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let out = compose_inner(first.as_array(), second.as_array(), front)?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Return the Kronecker product ``left ⊗ right`` of two matrices.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(left, right, /)")]
+pub fn tensor(
+    py: Python,
+    left: PyReadonlyArray2<Complex64>,
+    right: PyReadonlyArray2<Complex64>,
+) -> Py<PyArray2<Complex64>> {
+    tensor_inner(left.as_array(), right.as_array())
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return the transpose of a matrix as a new array.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn transpose(py: Python, mat: PyReadonlyArray2<Complex64>) -> Py<PyArray2<Complex64>> {
+    transpose_inner(mat.as_array())
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return the conjugate transpose of a matrix as a new array.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn adjoint(py: Python, mat: PyReadonlyArray2<Complex64>) -> Py<PyArray2<Complex64>> {
+    adjoint_inner(mat.as_array())
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Raise a square matrix to an integer power.
+///
+/// Raises:
+///     QiskitError: if the matrix is not square, or if ``n`` is negative and the matrix is
+///         singular.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, n, /)")]
+pub fn power(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+    n: i64,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    Ok(power_inner(mat.as_array(), n)?
+        .into_pyarray_bound(py)
+        .unbind())
+}
+
+/// Test whether two matrices are equal to within a tolerance, optionally ignoring a global phase.
+///
+/// Args:
+///     left (ndarray): a complex matrix.
+///     right (ndarray): a complex matrix.
+///     ignore_phase (bool): whether to ignore a difference in global phase.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     bool: whether the two matrices are equal.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (left, right, ignore_phase=false, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn matrix_equal(
+    left: PyReadonlyArray2<Complex64>,
+    right: PyReadonlyArray2<Complex64>,
+    ignore_phase: bool,
+    rtol: f64,
+    atol: f64,
+) -> bool {
+    matrix_equal_inner(left.as_array(), right.as_array(), ignore_phase, rtol, atol)
+}
+
+/// Test whether a matrix is the identity to within a tolerance, optionally ignoring a global
+/// phase.  The phase is taken from the ``[0, 0]`` entry.
+///
+/// Args:
+///     mat (ndarray): a complex matrix.
+///     ignore_phase (bool): whether to ignore a global phase.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     bool: whether the matrix is the identity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, ignore_phase=false, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn is_identity(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+    ignore_phase: bool,
+    rtol: f64,
+    atol: f64,
+) -> bool {
+    let mat = mat.as_array();
+    py.allow_threads(|| is_identity_inner(mat, ignore_phase, rtol, atol))
+}
+
+/// Test whether a matrix is the identity up to a global phase, to within a tolerance.  The phase
+/// is taken from the ``[0, 0]`` entry.
+///
+/// Args:
+///     mat (ndarray): a complex matrix.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     bool: whether the matrix is a multiple of the identity by a phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn is_identity_up_to_phase(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+    rtol: f64,
+    atol: f64,
+) -> bool {
+    let mat = mat.as_array();
+    py.allow_threads(|| is_identity_inner(mat, true, rtol, atol))
+}
+
+/// Test whether a matrix is Hermitian to within a tolerance.
+///
+/// Args:
+///     mat (ndarray): a complex matrix.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     bool: whether the matrix is square and equal to its conjugate transpose.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn is_hermitian(py: Python, mat: PyReadonlyArray2<Complex64>, rtol: f64, atol: f64) -> bool {
+    let mat = mat.as_array();
+    py.allow_threads(|| is_hermitian_inner(mat, rtol, atol))
+}
+
+/// Test whether a matrix is unitary to within a tolerance, without forming its product with its
+/// conjugate transpose.
+///
+/// Args:
+///     mat (ndarray): a complex matrix.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     bool: whether the matrix is square and unitary.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn is_unitary(py: Python, mat: PyReadonlyArray2<Complex64>, rtol: f64, atol: f64) -> bool {
+    let mat = mat.as_array();
+    py.allow_threads(|| is_unitary_inner(mat, rtol, atol))
+}
+
+/// Return the exponential of a square matrix.
+///
+/// Raises:
+///     QiskitError: if the matrix is not square.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn expm(py: Python, mat: PyReadonlyArray2<Complex64>) -> PyResult<Py<PyArray2<Complex64>>> {
+    let mat = mat.as_array();
+    let out = py.allow_threads(|| expm_inner(mat))?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Return the time-evolution operator ``exp(-i t H)`` of a Hermitian matrix.
+///
+/// Args:
+///     hamiltonian (ndarray): the Hermitian matrix :math:`H`.  Its Hermiticity is not checked.
+///     time (float): the evolution time :math:`t`.
+///
+/// Returns:
+///     ndarray: the unitary matrix of the evolution.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(hamiltonian, time, /)")]
+pub fn hamiltonian_evolution(
+    py: Python,
+    hamiltonian: PyReadonlyArray2<Complex64>,
+    time: f64,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let hamiltonian = hamiltonian.as_array();
+    let out = py.allow_threads(|| hamiltonian_evolution_inner(hamiltonian, time))?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Return the principal logarithm of a unitary matrix.
+///
+/// This is computed from an eigendecomposition of the unitary, so unlike
+/// :func:`scipy.linalg.logm` the result is exactly anti-Hermitian.
+///
+/// Raises:
+///     QiskitError: if the matrix is not square.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn logm_unitary(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let mat = mat.as_array();
+    let out = py.allow_threads(|| logm_unitary_inner(mat))?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// Decompose a one- or two-qubit unitary into a global phase and a rotation about a sum of Paulis,
+/// ``mat = exp(i phase) exp(-i sum_P coeffs[P] P)``, from its principal logarithm.
+///
+/// Args:
+///     mat (ndarray): a one- or two-qubit unitary.
+///
+/// Returns:
+///     (float, dict[str, float]): the global phase, and the coefficient of each non-identity
+///     Pauli, keyed by its label.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn pauli_rotation(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+) -> PyResult<(f64, IndexMap<String, f64>)> {
+    let mat = mat.as_array();
+    let rotation = py.allow_threads(|| pauli_rotation_inner(mat))?;
+    let coeffs = rotation
+        .coeffs
+        .iter()
+        .enumerate()
+        .map(|(index, coeff)| (rotation.label(index), *coeff))
+        .collect();
+    Ok((rotation.phase, coeffs))
+}
+
+/// Decompose a single-qubit unitary into a global phase and a rotation about an axis,
+/// ``mat = exp(i phase) exp(-i angle/2 (n_x X + n_y Y + n_z Z))``.
+///
+/// Args:
+///     mat (ndarray): a single-qubit unitary.
+///
+/// Returns:
+///     (float, float, (float, float, float)): the global phase, the rotation angle in
+///     :math:`[0, 2\pi]`, and the unit axis of the rotation, which is :math:`Z` for the identity.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn axis_angle(mat: PyReadonlyArray2<Complex64>) -> PyResult<(f64, f64, [f64; 3])> {
+    let rotation = pauli_rotation_inner(mat.as_array())?;
+    let (angle, axis) = rotation.axis_angle()?;
+    Ok((rotation.phase, angle, axis))
+}
+
+/// Embed a matrix acting on some qubits into an operator on more qubits, as the identity on the
+/// other qubits.
+///
+/// Args:
+///     mat (ndarray): the matrix of a ``k``-qubit operator.
+///     qubits (list[int]): the qubits the operator acts on, with the ``i``th qubit of ``mat``
+///         being ``qubits[i]``.
+///     num_qubits (int): the number of qubits of the output.
+///
+/// Returns:
+///     ndarray: the ``2**num_qubits``-dimensional matrix of the embedded operator.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, qubits, num_qubits, /)")]
+pub fn embed(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+    qubits: Vec<usize>,
+    num_qubits: usize,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let mat = mat.as_array();
+    let out = py.allow_threads(|| embed_inner(mat, &qubits, num_qubits))?;
+    Ok(out.into_pyarray_bound(py).unbind())
+}
+
+/// The unitary of `circuit` with `global_phase`, or `None` if it contains an instruction without a
+/// matrix or with clbits.
+#[cfg(feature = "python")]
+fn circuit_unitary(
+    py: Python,
+    circuit: &CircuitData,
+    global_phase: f64,
+) -> PyResult<Option<Array2<Complex64>>> {
+    let num_qubits = circuit.num_qubits();
+    if 2 * num_qubits >= usize::BITS as usize {
+        return Err(AccelerateError::Overflow(format!(
+            "a {num_qubits}-qubit operator is too large to represent"
+        ))
+        .into());
+    }
+    let dim = 1usize << num_qubits;
+    let mut unitary = Array2::<Complex64>::eye(dim) * Complex64::new(0., global_phase).exp();
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        if !clbits.is_empty() {
+            return Ok(None);
+        }
+        let mat = match operation_matrix(op.bind(py))? {
+            OperationMatrix::Skip => continue,
+            OperationMatrix::Matrix(mat) => mat,
+            OperationMatrix::Unsupported => return Ok(None),
+        };
+        let qubits = qubits.iter().map(|q| *q as usize).collect::<Vec<_>>();
+        apply_left_inner(&mut unitary, mat.view(), &qubits)?;
+    }
+    Ok(Some(unitary))
+}
+
+/// Multiply out the unitary matrix of a circuit.
+///
+/// Standard gates are applied from their Rust-space matrices; any other gate is applied through its
+/// ``to_matrix`` method, and barriers and delays are skipped.
+///
+/// Args:
+///     circuit (CircuitData): the instructions of the circuit.
+///     global_phase (float): the global phase of the circuit.
+///
+/// Returns:
+///     numpy.ndarray | None: the unitary of the circuit, or ``None`` if the circuit contains an
+///     instruction without a matrix (for example one that must be expanded through its
+///     definition), in which case the caller should fall back to the Python-space construction.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, global_phase=0.))]
+pub fn from_circuit_data<'py>(
+    py: Python<'py>,
+    circuit: &CircuitData,
+    global_phase: f64,
+) -> PyResult<Option<Bound<'py, PyArray2<Complex64>>>> {
+    Ok(circuit_unitary(py, circuit, global_phase)?.map(|unitary| unitary.into_pyarray_bound(py)))
+}
+
+/// Test whether two matrices are equal up to a global phase, and find the phase.
+///
+/// Args:
+///     left (ndarray): a complex matrix.
+///     right (ndarray): a complex matrix.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     float | None: the phase :math:`\phi` with ``left`` equal to :math:`e^{i\phi}` ``right``,
+///     or ``None`` if there's no such phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (left, right, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn equal_up_to_phase(
+    py: Python,
+    left: PyReadonlyArray2<Complex64>,
+    right: PyReadonlyArray2<Complex64>,
+    rtol: f64,
+    atol: f64,
+) -> Option<f64> {
+    let (left, right) = (left.as_array(), right.as_array());
+    py.allow_threads(|| equal_up_to_phase_inner(left, right, rtol, atol))
+}
+
+/// Test whether the unitaries of two circuits are equal up to a global phase, and find the phase.
+///
+/// The global phases of the circuits aren't part of their ``CircuitData``; they only shift the
+/// returned phase by their difference.
+///
+/// Args:
+///     left (CircuitData): the instructions of a circuit.
+///     right (CircuitData): the instructions of a circuit.
+///     rtol (float): relative tolerance, as in :func:`numpy.allclose`.
+///     atol (float): absolute tolerance, as in :func:`numpy.allclose`.
+///
+/// Returns:
+///     float | None: the phase :math:`\phi` with the unitary of ``left`` equal to :math:`e^{i\phi}`
+///     times that of ``right``, or ``None`` if there's no such phase.
+///
+/// Raises:
+///     QiskitError: if either circuit contains an instruction without a matrix, or with clbits.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (left, right, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT))]
+pub fn circuits_equal_up_to_phase(
+    py: Python,
+    left: &CircuitData,
+    right: &CircuitData,
+    rtol: f64,
+    atol: f64,
+) -> PyResult<Option<f64>> {
+    if left.num_qubits() != right.num_qubits() {
+        return Ok(None);
+    }
+    let unitary = |circuit: &CircuitData| -> PyResult<Array2<Complex64>> {
+        circuit_unitary(py, circuit, 0.)?.ok_or_else(|| {
+            AccelerateError::Qiskit(
+                "a circuit contains an instruction without a matrix, or with clbits".to_string(),
+            )
+            .into()
+        })
+    };
+    let (left, right) = (unitary(left)?, unitary(right)?);
+    Ok(py.allow_threads(|| equal_up_to_phase_inner(left.view(), right.view(), rtol, atol)))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn operator(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(compose))?;
+    m.add_wrapped(wrap_pyfunction!(tensor))?;
+    m.add_wrapped(wrap_pyfunction!(embed))?;
+    m.add_wrapped(wrap_pyfunction!(transpose))?;
+    m.add_wrapped(wrap_pyfunction!(adjoint))?;
+    m.add_wrapped(wrap_pyfunction!(power))?;
+    m.add_wrapped(wrap_pyfunction!(expm))?;
+    m.add_wrapped(wrap_pyfunction!(hamiltonian_evolution))?;
+    m.add_wrapped(wrap_pyfunction!(logm_unitary))?;
+    m.add_wrapped(wrap_pyfunction!(pauli_rotation))?;
+    m.add_wrapped(wrap_pyfunction!(axis_angle))?;
+    m.add_wrapped(wrap_pyfunction!(matrix_equal))?;
+    m.add_wrapped(wrap_pyfunction!(equal_up_to_phase))?;
+    m.add_wrapped(wrap_pyfunction!(circuits_equal_up_to_phase))?;
+    m.add_wrapped(wrap_pyfunction!(is_identity))?;
+    m.add_wrapped(wrap_pyfunction!(is_identity_up_to_phase))?;
+    m.add_wrapped(wrap_pyfunction!(is_hermitian))?;
+    m.add_wrapped(wrap_pyfunction!(is_unitary))?;
+    m.add_wrapped(wrap_pyfunction!(from_circuit_data))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    fn hadamard() -> Array2<Complex64> {
+        array![[c(1., 0.), c(1., 0.)], [c(1., 0.), c(-1., 0.)]].mapv(|x| x * FRAC_1_SQRT_2)
+    }
+
+    #[test]
+    fn test_equal_up_to_phase() {
+        let right = hadamard();
+        for phase in [0., 0.3, -2., 3.] {
+            let left = right.mapv(|x| x * Complex64::from_polar(1., phase));
+            let found =
+                equal_up_to_phase_inner(left.view(), right.view(), RTOL_DEFAULT, ATOL_DEFAULT)
+                    .unwrap();
+            assert!(
+                (Complex64::from_polar(1., found) - Complex64::from_polar(1., phase)).norm()
+                    < 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_equal_up_to_phase_tolerance() {
+        let right = hadamard();
+        let mut left = right.mapv(|x| x * c(0., 1.));
+        left[[1, 1]] += c(1e-9, 0.);
+        let phase = equal_up_to_phase_inner(left.view(), right.view(), RTOL_DEFAULT, ATOL_DEFAULT);
+        assert!((phase.unwrap() - std::f64::consts::FRAC_PI_2).abs() < 1e-8);
+        left[[1, 1]] += c(1e-3, 0.);
+        assert_eq!(
+            equal_up_to_phase_inner(left.view(), right.view(), RTOL_DEFAULT, ATOL_DEFAULT),
+            None
+        );
+    }
+
+    #[test]
+    fn test_not_equal_up_to_phase() {
+        // Z and the identity agree up to phase on each entry, but not with a single phase.
+        let z = Array2::from_diag(&array![c(1., 0.), c(-1., 0.)]);
+        let identity = Array2::<Complex64>::eye(2);
+        assert_eq!(
+            equal_up_to_phase_inner(z.view(), identity.view(), RTOL_DEFAULT, ATOL_DEFAULT),
+            None
+        );
+        let identity = Array2::<Complex64>::eye(4);
+        assert_eq!(
+            equal_up_to_phase_inner(z.view(), identity.view(), RTOL_DEFAULT, ATOL_DEFAULT),
+            None
+        );
+    }
+
+    #[test]
+    fn test_zero_matrices_equal_with_no_phase() {
+        let zero = Array2::<Complex64>::zeros((2, 2));
+        assert_eq!(
+            equal_up_to_phase_inner(zero.view(), zero.view(), RTOL_DEFAULT, ATOL_DEFAULT),
+            Some(0.)
+        );
+    }
+
+    fn random_matrix(dim: usize, seed: u128) -> Array2<Complex64> {
+        use rand::prelude::*;
+        let mut rng = crate::rng::generator(seed);
+        Array2::from_shape_simple_fn((dim, dim), || {
+            c(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5)
+        })
+    }
+
+    /// The matrix of `mat` on `qubits` of `num_qubits` qubits, built entry by entry.
+    fn embedded(
+        mat: ArrayView2<Complex64>,
+        qubits: &[usize],
+        num_qubits: usize,
+    ) -> Array2<Complex64> {
+        let mask = qubits.iter().fold(0, |acc, q| acc | (1 << q));
+        let row = |index: usize| {
+            qubits
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (bit, q)| acc | (((index >> q) & 1) << bit))
+        };
+        let dim = 1 << num_qubits;
+        Array2::from_shape_fn((dim, dim), |(out, index)| {
+            if out & !mask == index & !mask {
+                mat[[row(out), row(index)]]
+            } else {
+                c(0., 0.)
+            }
+        })
+    }
+
+    #[test]
+    fn test_apply_left() {
+        let start = random_matrix(8, 1);
+        for (seed, qubits) in [vec![1], vec![2, 0], vec![0, 2, 1]].iter().enumerate() {
+            let mat = random_matrix(1 << qubits.len(), seed as u128 + 2);
+            let mut unitary = start.clone();
+            apply_left_inner(&mut unitary, mat.view(), qubits).unwrap();
+            let expected = embedded(mat.view(), qubits, 3).dot(&start);
+            assert_abs_diff_eq!(unitary, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_apply_left_non_contiguous() {
+        let start = random_matrix(4, 5);
+        let mat = random_matrix(2, 6);
+        let mut unitary = Array2::zeros((4, 4).f());
+        unitary.assign(&start);
+        assert!(!unitary.is_standard_layout());
+        apply_left_inner(&mut unitary, mat.view(), &[1]).unwrap();
+        let expected = embedded(mat.view(), &[1], 2).dot(&start);
+        assert_abs_diff_eq!(unitary, expected, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_apply_left_errors() {
+        let mat = random_matrix(2, 0);
+        assert!(matches!(
+            apply_left_inner(&mut random_matrix(3, 0), mat.view(), &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            apply_left_inner(&mut random_matrix(4, 0), mat.view(), &[2]),
+            Err(AccelerateError::Index(_))
+        ));
+    }
+
+    #[test]
+    fn test_matrix_equal() {
+        let equal = |left: &Array2<Complex64>, right: &Array2<Complex64>, ignore_phase| {
+            matrix_equal_inner(
+                left.view(),
+                right.view(),
+                ignore_phase,
+                RTOL_DEFAULT,
+                ATOL_DEFAULT,
+            )
+        };
+        let mat = random_matrix(4, 3);
+        let rotated = mat.mapv(|x| x * Complex64::from_polar(1., 0.7));
+        assert!(equal(&mat, &mat, false));
+        assert!(!equal(&rotated, &mat, false));
+        assert!(equal(&rotated, &mat, true));
+        assert!(!equal(&mat, &random_matrix(8, 3), true));
+        let mut perturbed = mat.clone();
+        perturbed[[2, 1]] += c(1e-4, 0.);
+        assert!(!equal(&perturbed, &mat, false));
+        assert!(matrix_equal_inner(
+            perturbed.view(),
+            mat.view(),
+            false,
+            RTOL_DEFAULT,
+            1e-3
+        ));
+    }
+
+    #[test]
+    fn test_is_identity() {
+        let is_identity = |mat: &Array2<Complex64>, ignore_phase| {
+            is_identity_inner(mat.view(), ignore_phase, RTOL_DEFAULT, ATOL_DEFAULT)
+        };
+        let identity = Array2::<Complex64>::eye(4);
+        assert!(is_identity(&identity, false));
+        let rotated = identity.mapv(|x| x * c(0., -1.));
+        assert!(!is_identity(&rotated, false));
+        assert!(is_identity(&rotated, true));
+        // The diagonal is compared with `rtol` relative to one, and the rest with `atol` alone.
+        let mut perturbed = identity.clone();
+        perturbed[[1, 1]] += c(5e-6, 0.);
+        assert!(is_identity(&perturbed, false));
+        perturbed[[0, 1]] += c(5e-6, 0.);
+        assert!(!is_identity(&perturbed, false));
+        assert!(!is_identity(&identity.slice_move(s![..2, ..]), false));
+    }
+
+    #[test]
+    fn test_is_hermitian() {
+        let is_hermitian =
+            |mat: &Array2<Complex64>| is_hermitian_inner(mat.view(), RTOL_DEFAULT, ATOL_DEFAULT);
+        let mat = random_matrix(8, 4);
+        let hermitian = &mat + &mat.t().mapv(|x| x.conj());
+        assert!(is_hermitian(&hermitian));
+        assert!(!is_hermitian(&mat));
+        assert!(!is_hermitian(&hermitian.mapv(|x| x * c(0., 1.))));
+        assert!(!is_hermitian(&hermitian.slice_move(s![..4, ..])));
+    }
+
+    #[test]
+    fn test_is_unitary() {
+        let is_unitary =
+            |mat: &Array2<Complex64>| is_unitary_inner(mat.view(), RTOL_DEFAULT, ATOL_DEFAULT);
+        let unitary = hadamard().mapv(|x| x * Complex64::from_polar(1., 0.4));
+        assert!(is_unitary(&unitary));
+        let scaled = unitary.mapv(|x| x * 1.001);
+        assert!(!is_unitary(&scaled));
+        assert!(is_unitary_inner(scaled.view(), 1e-2, ATOL_DEFAULT));
+        assert!(!is_unitary(&random_matrix(4, 8)));
+        assert!(!is_unitary(&Array2::eye(4).slice_move(s![..2, ..])));
+    }
+
+    #[test]
+    fn test_expm() {
+        // The exponential of a nilpotent matrix is a finite sum.
+        let nilpotent = array![[c(0., 0.), c(2., 1.)], [c(0., 0.), c(0., 0.)]];
+        let expected = array![[c(1., 0.), c(2., 1.)], [c(0., 0.), c(1., 0.)]];
+        assert_abs_diff_eq!(
+            expm_inner(nilpotent.view()).unwrap(),
+            expected,
+            epsilon = 1e-12
+        );
+        // `exp(-i t X) = cos(t) I - i sin(t) X`, where the larger time needs scaling and squaring.
+        for time in [0.3f64, 20.] {
+            let (cos, sin) = (c(time.cos(), 0.), c(0., -time.sin()));
+            let generator = array![[c(0., 0.), c(0., -time)], [c(0., -time), c(0., 0.)]];
+            let expected = array![[cos, sin], [sin, cos]];
+            assert_abs_diff_eq!(
+                expm_inner(generator.view()).unwrap(),
+                expected,
+                epsilon = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_expm_errors() {
+        assert!(matches!(
+            expm_inner(Array2::zeros((2, 3)).view()),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut mat = Array2::zeros((2, 2));
+        mat[[0, 1]] = c(f64::NAN, 0.);
+        assert!(matches!(
+            expm_inner(mat.view()),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    fn random_hermitian(dim: usize, seed: u128) -> Array2<Complex64> {
+        let mat = random_matrix(dim, seed);
+        &mat + &adjoint_inner(mat.view())
+    }
+
+    #[test]
+    fn test_hamiltonian_evolution() {
+        let hamiltonian = random_hermitian(4, 1);
+        let out = hamiltonian_evolution_inner(hamiltonian.view(), 0.7).unwrap();
+        let expected = expm_inner(hamiltonian.mapv(|x| x * c(0., -0.7)).view()).unwrap();
+        assert_abs_diff_eq!(out, expected, epsilon = 1e-10);
+        // Only the lower triangle of a small Hamiltonian is read.
+        let mut lower = hamiltonian.clone();
+        lower[[0, 3]] = c(100., 0.);
+        let out = hamiltonian_evolution_inner(lower.view(), 0.7).unwrap();
+        assert_abs_diff_eq!(out, expected, epsilon = 1e-10);
+        // Larger Hamiltonians are exponentiated by scaling and squaring.
+        let identity = Array2::<Complex64>::eye(EVOLUTION_EIGH_MAX_DIM);
+        let large = tensor_inner(hamiltonian.view(), identity.view());
+        let out = hamiltonian_evolution_inner(large.view(), 0.7).unwrap();
+        assert_abs_diff_eq!(
+            out,
+            tensor_inner(expected.view(), identity.view()),
+            epsilon = 1e-10
+        );
+        assert!(is_unitary_inner(out.view(), RTOL_DEFAULT, ATOL_DEFAULT));
+    }
+
+    /// The index of the Pauli with `label`, with qubit 0 rightmost, as [PauliRotation] has it.
+    fn pauli_index(label: &str) -> usize {
+        label
+            .chars()
+            .rev()
+            .enumerate()
+            .fold(0, |acc, (qubit, pauli)| {
+                acc | ("IXYZ".find(pauli).unwrap() << (2 * qubit))
+            })
+    }
+
+    /// The unitary `exp(i phase) exp(-i Σ coeff P)` of the Paulis `P` with their coefficients.
+    fn rotation(phase: f64, terms: &[(&str, f64)]) -> Array2<Complex64> {
+        let num_qubits = terms[0].0.len();
+        let dim = 1 << num_qubits;
+        let generator = Array2::from_shape_fn((dim, dim), |(row, col)| {
+            terms.iter().fold(c(0., 0.), |acc, (label, coeff)| {
+                acc + pauli_entry(pauli_index(label), row, col, num_qubits) * *coeff
+            })
+        });
+        hamiltonian_evolution_inner(generator.view(), 1.).unwrap()
+            * Complex64::from_polar(1., phase)
+    }
+
+    #[test]
+    fn test_unitary_eig_degenerate() {
+        let basis = hamiltonian_evolution_inner(random_hermitian(4, 3).view(), 1.).unwrap();
+        let diagonal = Array2::from_diag(&array![c(1., 0.), c(0., 1.), c(1., 0.), c(-1., 0.)]);
+        let mat = basis.dot(&diagonal).dot(&adjoint_inner(basis.view()));
+        let (vals, vecs) = unitary_eig(mat.view());
+        assert!(is_unitary_inner(vecs.view(), RTOL_DEFAULT, ATOL_DEFAULT));
+        let out = vecs
+            .dot(&Array2::from_diag(&vals))
+            .dot(&adjoint_inner(vecs.view()));
+        assert_abs_diff_eq!(out, mat, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_logm_unitary() {
+        let hamiltonian = random_hermitian(4, 5);
+        let unitary = hamiltonian_evolution_inner(hamiltonian.view(), 0.3).unwrap();
+        let log = logm_unitary_inner(unitary.view()).unwrap();
+        assert_abs_diff_eq!(log, hamiltonian.mapv(|x| x * c(0., -0.3)), epsilon = 1e-10);
+        assert_abs_diff_eq!(expm_inner(log.view()).unwrap(), unitary, epsilon = 1e-10);
+        // Phases close to `π` stay on their side of the branch cut.
+        let mat = Array2::from_diag(&array![
+            Complex64::from_polar(1., 3.1),
+            Complex64::from_polar(1., -3.1)
+        ]);
+        let log = logm_unitary_inner(mat.view()).unwrap();
+        assert_abs_diff_eq!(
+            log,
+            Array2::from_diag(&array![c(0., 3.1), c(0., -3.1)]),
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_pauli_rotation() {
+        let terms = [("XZ", 0.3), ("IZ", 0.1), ("YY", -0.4), ("ZI", 0.25)];
+        let out = pauli_rotation_inner(rotation(0.2, &terms).view()).unwrap();
+        assert_eq!(out.num_qubits, 2);
+        assert!((out.phase - 0.2).abs() < 1e-10);
+        assert_eq!(out.coeffs.len(), 15);
+        for (index, coeff) in out.coeffs.iter().enumerate() {
+            let label = out.label(index);
+            assert_eq!(pauli_index(&label), index + 1);
+            let expected = terms
+                .iter()
+                .find(|(term, _)| *term == label)
+                .map_or(0., |(_, coeff)| *coeff);
+            assert!((coeff - expected).abs() < 1e-10, "{label}: {coeff}");
+        }
+    }
+
+    #[test]
+    fn test_axis_angle() {
+        let out = pauli_rotation_inner(rotation(-0.5, &[("X", 0.3)]).view()).unwrap();
+        let (angle, axis) = out.axis_angle().unwrap();
+        assert!((out.phase + 0.5).abs() < 1e-10);
+        assert!((angle - 0.6).abs() < 1e-10);
+        assert_abs_diff_eq!(&axis[..], &[1., 0., 0.][..], epsilon = 1e-10);
+        let terms = [("X", 0.2), ("Y", -0.4), ("Z", 0.4)];
+        let (angle, axis) = pauli_rotation_inner(rotation(0., &terms).view())
+            .unwrap()
+            .axis_angle()
+            .unwrap();
+        assert!((angle - 1.2).abs() < 1e-10);
+        assert_abs_diff_eq!(
+            &axis[..],
+            &[1. / 3., -2. / 3., 2. / 3.][..],
+            epsilon = 1e-10
+        );
+        let identity = PauliRotation {
+            num_qubits: 1,
+            phase: 0.,
+            coeffs: vec![0.; 3],
+        };
+        assert_eq!(identity.axis_angle().unwrap(), (0., [0., 0., 1.]));
+        let out = pauli_rotation_inner(Array2::<Complex64>::eye(4).view()).unwrap();
+        assert!(matches!(out.axis_angle(), Err(AccelerateError::Qiskit(_))));
+    }
+
+    #[test]
+    fn test_pauli_rotation_errors() {
+        for dim in [3, 8] {
+            assert!(matches!(
+                pauli_rotation_inner(Array2::<Complex64>::eye(dim).view()),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_embed() {
+        for (seed, qubits) in [vec![], vec![2], vec![3, 0], vec![1, 3, 2]]
+            .iter()
+            .enumerate()
+        {
+            let mat = random_matrix(1 << qubits.len(), seed as u128);
+            let out = embed_inner(mat.view(), qubits, 4).unwrap();
+            assert_eq!(out, embedded(mat.view(), qubits, 4));
+        }
+        // Block consolidation passes the qubits as `u8`.
+        let mat = random_matrix(4, 9);
+        let mut out = Array2::zeros((32, 32));
+        embed_into(mat.view(), &[4u8, 1], &mut out);
+        assert_eq!(out, embedded(mat.view(), &[4, 1], 5));
+    }
+
+    #[test]
+    fn test_embed_errors() {
+        let mat = random_matrix(4, 0);
+        assert!(matches!(
+            embed_inner(mat.view(), &[0, 1], usize::BITS as usize / 2),
+            Err(AccelerateError::Overflow(_))
+        ));
+        assert!(matches!(
+            embed_inner(mat.view(), &[0, 3], 3),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            embed_inner(mat.view(), &[1, 1], 3),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            embed_inner(mat.view(), &[1], 3),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
@@ -13,6 +13,8 @@
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 
+use crate::angles::snap_clifford_angle;
+
 const PI: f64 = std::f64::consts::PI;
 
 ///     Return a triple theta, phi, lambda for the product.
@@ -83,8 +85,7 @@ pub fn compose_u3_rust(
     };
     euler
         .iter_mut()
-        .filter(|k| k.abs() < 1e-15)
-        .for_each(|k| *k = 0.0);
+        .for_each(|k| *k = snap_clifford_angle(*k, 1e-15));
 
     let out_angles: [f64; 3] = [euler[1], phi1 + euler[0], lambda2 + euler[2]];
     out_angles
@@ -0,0 +1,346 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The assembly of gradients from expectation values at shifted parameters.
+//!
+//! A parameter-shift rule evaluates the expectation values at a set of points, each of which
+//! shifts one parameter, and the derivative with respect to a parameter is a linear combination
+//! of the values at its points.  A [ShiftRule] is the list of those points, with the parameter
+//! each shifts, by how much, and its coefficient in the derivative, so that rules with any number
+//! of terms, or with the coefficients of the chain rule folded in, are assembled alike.
+
+#[cfg(feature = "python")]
+use std::f64::consts::FRAC_PI_2;
+
+use ndarray::{Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis};
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::threading::{self, ParallelKernel};
+
+/// A point of a parameter-shift rule.
+#[derive(Clone, Copy, Debug)]
+pub struct ShiftPoint {
+    /// The parameter that's shifted.
+    pub parameter: usize,
+    /// The shift of the parameter.
+    pub shift: f64,
+    /// The coefficient of the value at the point in the derivative with respect to the parameter.
+    pub coeff: f64,
+}
+
+/// The points of a parameter-shift rule, in the order of their expectation values.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.parameter_shift", frozen)
+)]
+pub struct ShiftRule {
+    num_parameters: usize,
+    points: Vec<ShiftPoint>,
+}
+
+impl ShiftRule {
+    pub fn new(num_parameters: usize, points: Vec<ShiftPoint>) -> Result<Self, AccelerateError> {
+        if let Some(point) = points
+            .iter()
+            .find(|point| point.parameter >= num_parameters)
+        {
+            return Err(AccelerateError::Index(format!(
+                "parameter {} is out of range for {} parameters",
+                point.parameter, num_parameters
+            )));
+        }
+        Ok(ShiftRule {
+            num_parameters,
+            points,
+        })
+    }
+
+    /// The two-term rule of gates generated by Paulis, which shifts each parameter by `shift`
+    /// either way, with the points of parameter `p` at `2p` and `2p + 1`.
+    pub fn two_term(num_parameters: usize, shift: f64) -> Result<Self, AccelerateError> {
+        let denominator = 2. * shift.sin();
+        if denominator.abs() < 1e-12 {
+            return Err(AccelerateError::Value(format!(
+                "the shift {} is a multiple of pi",
+                shift
+            )));
+        }
+        let coeff = 1. / denominator;
+        let points = (0..num_parameters)
+            .flat_map(|parameter| {
+                [
+                    ShiftPoint {
+                        parameter,
+                        shift,
+                        coeff,
+                    },
+                    ShiftPoint {
+                        parameter,
+                        shift: -shift,
+                        coeff: -coeff,
+                    },
+                ]
+            })
+            .collect();
+        Ok(ShiftRule {
+            num_parameters,
+            points,
+        })
+    }
+
+    pub fn num_parameters(&self) -> usize {
+        self.num_parameters
+    }
+
+    pub fn points(&self) -> &[ShiftPoint] {
+        &self.points
+    }
+
+    /// The parameter values of each point, one row per point, shifted from `base`.
+    pub fn parameter_values(&self, base: ArrayView1<f64>) -> Result<Array2<f64>, AccelerateError> {
+        if base.len() != self.num_parameters {
+            return Err(AccelerateError::Value(format!(
+                "expected {} parameter values, but got {}",
+                self.num_parameters,
+                base.len()
+            )));
+        }
+        let mut out = Array2::zeros((self.points.len(), self.num_parameters));
+        for (mut row, point) in out.axis_iter_mut(Axis(0)).zip(&self.points) {
+            row.assign(&base);
+            row[point.parameter] += point.shift;
+        }
+        Ok(out)
+    }
+
+    /// The Jacobian, with a row per observable and a column per parameter, from the `values` of
+    /// the observables at the points, with a row per point and a column per observable.
+    pub fn jacobian(&self, values: ArrayView2<f64>) -> Result<Array2<f64>, AccelerateError> {
+        if values.nrows() != self.points.len() {
+            return Err(AccelerateError::Value(format!(
+                "expected the values at {} points, but got {}",
+                self.points.len(),
+                values.nrows()
+            )));
+        }
+        let mut out = Array2::zeros((values.ncols(), self.num_parameters));
+        let assemble = |(mut gradient, column): (ArrayViewMut1<f64>, ArrayView1<f64>)| {
+            for (point, value) in self.points.iter().zip(column) {
+                gradient[point.parameter] += point.coeff * value;
+            }
+        };
+        let size = values.len();
+        if threading::run_in_parallel(ParallelKernel::ParameterShift, size) {
+            threading::install(|| {
+                out.axis_iter_mut(Axis(0))
+                    .into_par_iter()
+                    .zip(values.axis_iter(Axis(1)).into_par_iter())
+                    .for_each(assemble)
+            });
+        } else {
+            out.axis_iter_mut(Axis(0))
+                .zip(values.axis_iter(Axis(1)))
+                .for_each(assemble);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ShiftRule {
+    /// A rule from its points, each the parameter it shifts, the shift, and its coefficient in
+    /// the derivative with respect to the parameter.
+    #[new]
+    fn py_new(num_parameters: usize, points: Vec<(usize, f64, f64)>) -> PyResult<Self> {
+        let points = points
+            .into_iter()
+            .map(|(parameter, shift, coeff)| ShiftPoint {
+                parameter,
+                shift,
+                coeff,
+            })
+            .collect();
+        Ok(Self::new(num_parameters, points)?)
+    }
+
+    /// The two-term rule of gates generated by Paulis, with the points of parameter ``p`` at
+    /// ``2 * p`` and ``2 * p + 1``.
+    #[staticmethod]
+    #[pyo3(name = "two_term", signature = (num_parameters, shift=FRAC_PI_2))]
+    fn py_two_term(num_parameters: usize, shift: f64) -> PyResult<Self> {
+        Ok(Self::two_term(num_parameters, shift)?)
+    }
+
+    fn __len__(&self) -> usize {
+        self.points.len()
+    }
+
+    #[getter(num_parameters)]
+    fn py_num_parameters(&self) -> usize {
+        self.num_parameters
+    }
+
+    /// The points, each the parameter it shifts, the shift, and its coefficient.
+    #[getter(points)]
+    fn py_points(&self) -> Vec<(usize, f64, f64)> {
+        self.points
+            .iter()
+            .map(|point| (point.parameter, point.shift, point.coeff))
+            .collect()
+    }
+
+    /// The parameter values of each point.
+    ///
+    /// Args:
+    ///     base (numpy.ndarray): the parameter values the gradient is taken at.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: the array of the parameter values of each point, one row per point.
+    #[pyo3(name = "parameter_values")]
+    fn py_parameter_values(
+        &self,
+        py: Python,
+        base: PyReadonlyArray1<f64>,
+    ) -> PyResult<Py<PyArray2<f64>>> {
+        let out = self.parameter_values(base.as_array())?;
+        Ok(out.into_pyarray_bound(py).unbind())
+    }
+
+    /// The Jacobian of observables.
+    ///
+    /// Args:
+    ///     values (numpy.ndarray): the expectation values of the observables at the points, one
+    ///         row per point and one column per observable.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: the Jacobian, one row per observable and one column per parameter.
+    #[pyo3(name = "jacobian")]
+    fn py_jacobian(
+        &self,
+        py: Python,
+        values: PyReadonlyArray2<f64>,
+    ) -> PyResult<Py<PyArray2<f64>>> {
+        let values = values.as_array();
+        let out = py.allow_threads(|| self.jacobian(values))?;
+        Ok(out.into_pyarray_bound(py).unbind())
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn parameter_shift(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<ShiftRule>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1};
+
+    /// The values of cos(x) sin(y) and sin(x) at each point of `rule`, from `base`.
+    fn values(rule: &ShiftRule, base: &Array1<f64>) -> Array2<f64> {
+        let points = rule.parameter_values(base.view()).unwrap();
+        let mut out = Array2::zeros((points.nrows(), 2));
+        for (mut row, point) in out.axis_iter_mut(Axis(0)).zip(points.axis_iter(Axis(0))) {
+            row[0] = point[0].cos() * point[1].sin();
+            row[1] = point[0].sin();
+        }
+        out
+    }
+
+    fn assert_jacobian(rule: &ShiftRule) {
+        let base = array![0.3, -1.1];
+        let jacobian = rule.jacobian(values(rule, &base).view()).unwrap();
+        let (x, y) = (base[0], base[1]);
+        let expected = array![[-x.sin() * y.sin(), x.cos() * y.cos()], [x.cos(), 0.]];
+        for (a, b) in jacobian.iter().zip(&expected) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_two_term() {
+        let rule = ShiftRule::two_term(2, std::f64::consts::FRAC_PI_2).unwrap();
+        assert_eq!(rule.points().len(), 4);
+        let points = rule.parameter_values(array![0.1, 0.2].view()).unwrap();
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        assert_eq!(
+            points,
+            array![
+                [0.1 + half_pi, 0.2],
+                [0.1 - half_pi, 0.2],
+                [0.1, 0.2 + half_pi],
+                [0.1, 0.2 - half_pi]
+            ]
+        );
+        assert_jacobian(&rule);
+        // Any shift that isn't a multiple of pi gives the exact derivative.
+        assert_jacobian(&ShiftRule::two_term(2, 0.4).unwrap());
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        // The two-term rule with the points of each parameter split in halves, out of order.
+        let point = |parameter, shift: f64| ShiftPoint {
+            parameter,
+            shift,
+            coeff: 0.25 * shift.signum(),
+        };
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        let points = vec![
+            point(1, half_pi),
+            point(0, -half_pi),
+            point(0, half_pi),
+            point(1, -half_pi),
+            point(0, half_pi),
+            point(1, half_pi),
+            point(1, -half_pi),
+            point(0, -half_pi),
+        ];
+        assert_jacobian(&ShiftRule::new(2, points).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let point = ShiftPoint {
+            parameter: 2,
+            shift: 1.,
+            coeff: 1.,
+        };
+        assert!(matches!(
+            ShiftRule::new(2, vec![point]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            ShiftRule::two_term(1, std::f64::consts::PI),
+            Err(AccelerateError::Value(_))
+        ));
+        let rule = ShiftRule::two_term(2, 0.5).unwrap();
+        assert!(matches!(
+            rule.parameter_values(array![0.].view()),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            rule.jacobian(Array2::zeros((3, 1)).view()),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+}
@@ -0,0 +1,334 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Partial traces of statevectors and density matrices over arbitrary subsystems.
+//!
+//! States are described by the dimensions of their subsystems in Qiskit's little-endian order:
+//! subsystem 0 is the fastest-varying digit of the flat index.  Rather than permuting the input
+//! into a tensor, we precompute the flat-index offsets contributed by each configuration of the
+//! kept and the traced subsystems, so every amplitude is addressed as `kept[i] + traced[k]`.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::operator::{adjoint_inner, matmul_inner};
+use crate::threading::{self, ParallelKernel};
+
+/// The flat-index offsets of every configuration of the subsystems `systems` (given in increasing
+/// order), with all other subsystems set to zero.  The first of `systems` varies fastest.
+fn subsystem_offsets(dims: &[usize], systems: &[usize]) -> Vec<usize> {
+    let strides = dims
+        .iter()
+        .scan(1, |stride, dim| {
+            let out = *stride;
+            *stride *= dim;
+            Some(out)
+        })
+        .collect::<Vec<_>>();
+    let mut offsets = vec![0];
+    for &system in systems {
+        let stride = strides[system];
+        offsets = (0..dims[system])
+            .flat_map(|digit| offsets.iter().map(move |offset| offset + digit * stride))
+            .collect();
+    }
+    offsets
+}
+
+/// Validate the subsystem description and split the subsystems into the (sorted) kept and traced
+/// ones, returned as their offsets.
+fn split_subsystems(
+    dims: &[usize],
+    qargs: &[usize],
+    size: usize,
+) -> Result<(Vec<usize>, Vec<usize>), AccelerateError> {
+    if dims.iter().product::<usize>() != size {
+        return Err(AccelerateError::Qiskit(format!(
+            "subsystem dimensions {dims:?} do not match a state of dimension {size}"
+        )));
+    }
+    let mut traced = vec![false; dims.len()];
+    for &qarg in qargs {
+        if qarg >= dims.len() {
+            return Err(AccelerateError::Index(format!(
+                "subsystem {qarg} is out of range for a state with {} subsystems",
+                dims.len()
+            )));
+        }
+        if traced[qarg] {
+            return Err(AccelerateError::Qiskit(format!(
+                "subsystem {qarg} is traced out more than once"
+            )));
+        }
+        traced[qarg] = true;
+    }
+    let (traced_systems, kept_systems): (Vec<usize>, Vec<usize>) =
+        (0..dims.len()).partition(|system| traced[*system]);
+    Ok((
+        subsystem_offsets(dims, &kept_systems),
+        subsystem_offsets(dims, &traced_systems),
+    ))
+}
+
+/// The reduced density matrix of the pure state `state` after tracing out the subsystems `qargs`.
+/// The remaining subsystems keep their relative order.
+pub fn partial_trace_statevector_inner(
+    state: ArrayView1<Complex64>,
+    dims: &[usize],
+    qargs: &[usize],
+) -> Result<Array2<Complex64>, AccelerateError> {
+    // `rho = A @ A^dagger`, where the rows of `A` are the amplitudes with the kept subsystems
+    // fixed.
+    let amplitudes = bipartite_amplitudes_inner(state, dims, qargs)?;
+    matmul_inner(amplitudes.view(), adjoint_inner(amplitudes.view()).view())
+}
+
+/// Arrange the amplitudes of the pure state `state` into a matrix whose rows are indexed by the
+/// subsystems that are kept and whose columns are indexed by the subsystems `qargs` that are
+/// traced out.  The singular values of this matrix are the Schmidt coefficients of the state across
+/// that bipartition.
+pub fn bipartite_amplitudes_inner(
+    state: ArrayView1<Complex64>,
+    dims: &[usize],
+    qargs: &[usize],
+) -> Result<Array2<Complex64>, AccelerateError> {
+    let (kept, traced) = split_subsystems(dims, qargs, state.len())?;
+    Ok(Array2::from_shape_fn(
+        (kept.len(), traced.len()),
+        |(i, k)| state[kept[i] + traced[k]],
+    ))
+}
+
+/// The reduced density matrix of the density matrix `rho` after tracing out the subsystems
+/// `qargs`.  The remaining subsystems keep their relative order.
+pub fn partial_trace_density_matrix_inner(
+    rho: ArrayView2<Complex64>,
+    dims: &[usize],
+    qargs: &[usize],
+) -> Result<Array2<Complex64>, AccelerateError> {
+    if rho.nrows() != rho.ncols() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a density matrix must be square, but got shape {:?}",
+            rho.shape()
+        )));
+    }
+    let (kept, traced) = split_subsystems(dims, qargs, rho.nrows())?;
+    let mut out = Array2::<Complex64>::zeros((kept.len(), kept.len()));
+    let fill_row = |(i, mut out_row): (usize, ArrayViewMut1<Complex64>)| {
+        for (j, out) in out_row.iter_mut().enumerate() {
+            *out = traced.iter().map(|k| rho[[kept[i] + k, kept[j] + k]]).sum();
+        }
+    };
+    if threading::run_in_parallel(ParallelKernel::PartialTrace, kept.len()) {
+        threading::install(|| {
+            out.axis_iter_mut(Axis(0))
+                .into_par_iter()
+                .enumerate()
+                .for_each(fill_row)
+        });
+    } else {
+        out.axis_iter_mut(Axis(0)).enumerate().for_each(fill_row);
+    }
+    Ok(out)
+}
+
+/// Compute the reduced density matrix of a statevector.
+///
+/// Args:
+///     state (ndarray): the statevector.
+///     dims (list[int]): the dimensions of the subsystems, in little-endian order.
+///     qargs (list[int]): the subsystems to trace out.
+///
+/// Returns:
+///     ndarray: the reduced density matrix on the remaining subsystems, in their original order.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(state, dims, qargs, /)")]
+pub fn partial_trace_statevector(
+    py: Python,
+    state: PyReadonlyArray1<Complex64>,
+    dims: Vec<usize>,
+    qargs: Vec<usize>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    Ok(
+        partial_trace_statevector_inner(state.as_array(), &dims, &qargs)?
+            .into_pyarray_bound(py)
+            .unbind(),
+    )
+}
+
+/// Compute the reduced density matrix of a density matrix.
+///
+/// Args:
+///     rho (ndarray): the density matrix.
+///     dims (list[int]): the dimensions of the subsystems, in little-endian order.
+///     qargs (list[int]): the subsystems to trace out.
+///
+/// Returns:
+///     ndarray: the reduced density matrix on the remaining subsystems, in their original order.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(rho, dims, qargs, /)")]
+pub fn partial_trace_density_matrix(
+    py: Python,
+    rho: PyReadonlyArray2<Complex64>,
+    dims: Vec<usize>,
+    qargs: Vec<usize>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    Ok(
+        partial_trace_density_matrix_inner(rho.as_array(), &dims, &qargs)?
+            .into_pyarray_bound(py)
+            .unbind(),
+    )
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn partial_trace(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(partial_trace_statevector))?;
+    m.add_wrapped(wrap_pyfunction!(partial_trace_density_matrix))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    fn random_state(dim: usize, seed: u128) -> Array1<Complex64> {
+        let mut rng = generator(seed);
+        Array1::from_shape_simple_fn(dim, || {
+            Complex64::new(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5)
+        })
+    }
+
+    /// The index of the configuration of the subsystems `systems` within the flat index `index`,
+    /// with the first of `systems` varying fastest.
+    fn sub_index(mut index: usize, dims: &[usize], systems: &[usize]) -> usize {
+        let digits = dims
+            .iter()
+            .map(|dim| {
+                let digit = index % dim;
+                index /= dim;
+                digit
+            })
+            .collect::<Vec<_>>();
+        systems
+            .iter()
+            .rev()
+            .fold(0, |acc, &system| acc * dims[system] + digits[system])
+    }
+
+    /// The partial trace of `rho` over `qargs`, summed element by element.
+    fn reference(rho: ArrayView2<Complex64>, dims: &[usize], qargs: &[usize]) -> Array2<Complex64> {
+        let kept = (0..dims.len())
+            .filter(|system| !qargs.contains(system))
+            .collect::<Vec<_>>();
+        let mut traced = qargs.to_vec();
+        traced.sort();
+        let kept_dim = kept.iter().map(|system| dims[*system]).product::<usize>();
+        let mut out = Array2::zeros((kept_dim, kept_dim));
+        for ((a, b), value) in rho.indexed_iter() {
+            if sub_index(a, dims, &traced) == sub_index(b, dims, &traced) {
+                out[[sub_index(a, dims, &kept), sub_index(b, dims, &kept)]] += value;
+            }
+        }
+        out
+    }
+
+    fn outer(state: ArrayView1<Complex64>) -> Array2<Complex64> {
+        Array2::from_shape_fn((state.len(), state.len()), |(i, j)| {
+            state[i] * state[j].conj()
+        })
+    }
+
+    #[test]
+    fn test_bell_state() {
+        let amp = Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.);
+        let zero = Complex64::new(0., 0.);
+        let bell = array![amp, zero, zero, amp];
+        let expected = Array2::from_diag(&array![amp * amp, amp * amp]);
+        for qarg in [0, 1] {
+            let rho = partial_trace_statevector_inner(bell.view(), &[2, 2], &[qarg]).unwrap();
+            assert_abs_diff_eq!(rho, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_statevector_matches_reference() {
+        let dims = [2, 3, 2];
+        let state = random_state(12, 7);
+        let rho = outer(state.view());
+        for qargs in [vec![], vec![0], vec![1], vec![2, 0], vec![0, 1, 2]] {
+            let out = partial_trace_statevector_inner(state.view(), &dims, &qargs).unwrap();
+            assert_abs_diff_eq!(out, reference(rho.view(), &dims, &qargs), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_density_matrix_matches_reference() {
+        let dims = [3, 2, 2];
+        let first = outer(random_state(12, 1).view());
+        let second = outer(random_state(12, 2).view());
+        let rho = first * Complex64::new(0.25, 0.) + second * Complex64::new(0.75, 0.);
+        for qargs in [vec![], vec![0], vec![2], vec![1, 0], vec![2, 1, 0]] {
+            let out = partial_trace_density_matrix_inner(rho.view(), &dims, &qargs).unwrap();
+            assert_abs_diff_eq!(out, reference(rho.view(), &dims, &qargs), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bipartite_amplitudes() {
+        let state = Array1::from_shape_fn(6, |i| Complex64::new(i as f64, 0.));
+        // Subsystem 0 has dimension 2 and varies fastest, so tracing it out pairs the amplitudes
+        // `2 * row` and `2 * row + 1`.
+        let amplitudes = bipartite_amplitudes_inner(state.view(), &[2, 3], &[0]).unwrap();
+        assert_eq!(amplitudes.dim(), (3, 2));
+        for ((row, col), value) in amplitudes.indexed_iter() {
+            assert_eq!(value.re, (2 * row + col) as f64);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let state = random_state(8, 3);
+        assert!(matches!(
+            partial_trace_statevector_inner(state.view(), &[2, 3], &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            partial_trace_statevector_inner(state.view(), &[2, 2, 2], &[3]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            partial_trace_statevector_inner(state.view(), &[2, 2, 2], &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            partial_trace_density_matrix_inner(Array2::zeros((2, 4)).view(), &[2], &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
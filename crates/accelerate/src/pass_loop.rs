@@ -0,0 +1,490 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A controller that runs a sequence of Rust passes over a circuit until it stops changing, as a
+//! `DoWhileController` on a fixed point of the circuit does, without returning to Python between
+//! the iterations.
+//!
+//! The fixed point is found by hashing the content of the circuit after each iteration, so the
+//! passes don't need to report whether they changed anything.
+//!
+//! Between the tasks of a pass manager, consecutive Rust passes hand the circuit on as a
+//! [CircuitHandle], which shares the Rust-space data of the circuit, rather than as a
+//! `DAGCircuit`.  The circuit is only converted back to a `DAGCircuit` when a Python pass needs
+//! it.
+
+#[cfg(feature = "python")]
+use hashbrown::HashSet;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::pybacked::PyBackedStr;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::imports::{CIRCUIT_TO_DAG, DAG_TO_CIRCUIT};
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+#[cfg(feature = "python")]
+use std::str::FromStr;
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use crate::commutative_inverse_cancellation::py_commutative_inverse_cancellation;
+#[cfg(feature = "python")]
+use crate::consolidate_blocks::py_consolidate_blocks;
+#[cfg(feature = "python")]
+use crate::euler_one_qubit_decomposer::EulerBasis;
+#[cfg(feature = "python")]
+use crate::instrumentation;
+#[cfg(feature = "python")]
+use crate::two_qubit_decompose::TwoQubitBasisDecomposer;
+#[cfg(feature = "python")]
+use crate::unitary_synthesis::{synthesize_circuit, DefaultSynthesisBackends};
+
+/// Run `step` on a circuit until the `hash` of the circuit stops changing, or at most
+/// `max_iterations` times.
+///
+/// Returns the final circuit and the number of times `step` ran.  The last iteration is the one
+/// that left the circuit unchanged, unless the loop ran out of iterations.
+pub fn run_to_fixed_point_inner<C, E, S, H>(
+    circuit: C,
+    max_iterations: usize,
+    mut step: S,
+    hash: H,
+) -> Result<(C, usize), E>
+where
+    S: FnMut(C) -> Result<C, E>,
+    H: Fn(&C) -> Result<u64, E>,
+{
+    let mut circuit = circuit;
+    let mut last = hash(&circuit)?;
+    for iteration in 1..=max_iterations {
+        circuit = step(circuit)?;
+        let current = hash(&circuit)?;
+        if current == last {
+            return Ok((circuit, iteration));
+        }
+        last = current;
+    }
+    Ok((circuit, max_iterations))
+}
+
+#[cfg(feature = "python")]
+enum RustPassKind {
+    CommutativeInverseCancellation {
+        matrix_based: bool,
+        max_qubits: usize,
+    },
+    ConsolidateBlocks {
+        decomposer: Py<TwoQubitBasisDecomposer>,
+        basis_gate_name: String,
+        basis_gates: Option<HashSet<String>>,
+        force_consolidate: bool,
+        consolidate_1q_runs: bool,
+    },
+    UnitarySynthesis {
+        euler_bases: Vec<EulerBasis>,
+        decomposer_2q: Option<Py<TwoQubitBasisDecomposer>>,
+        basis_gate: Option<PyObject>,
+        approximate: bool,
+        synthesize_multi_qubit: bool,
+        min_qubits: usize,
+        coupling_edges: Option<HashSet<[BitType; 2]>>,
+    },
+}
+
+/// A Rust pass with its configuration, to be run by :func:`run_to_fixed_point`.
+///
+/// Passes are made with the static constructors, which take the same arguments as the Rust
+/// functions of the passes.
+#[cfg(feature = "python")]
+#[pyclass(module = "qiskit._accelerate.pass_loop", frozen)]
+pub struct RustPass {
+    kind: RustPassKind,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl RustPass {
+    /// The pass of :func:`.commutative_inverse_cancellation.commutative_inverse_cancellation`.
+    #[staticmethod]
+    #[pyo3(signature = (matrix_based=false, max_qubits=4))]
+    fn commutative_inverse_cancellation(matrix_based: bool, max_qubits: usize) -> Self {
+        RustPass {
+            kind: RustPassKind::CommutativeInverseCancellation {
+                matrix_based,
+                max_qubits,
+            },
+        }
+    }
+
+    /// The pass of :func:`.consolidate_blocks.consolidate_blocks`.
+    #[staticmethod]
+    #[pyo3(signature = (decomposer, basis_gate_name, basis_gates=None, force_consolidate=false, consolidate_1q_runs=false))]
+    fn consolidate_blocks(
+        decomposer: Py<TwoQubitBasisDecomposer>,
+        basis_gate_name: String,
+        basis_gates: Option<HashSet<String>>,
+        force_consolidate: bool,
+        consolidate_1q_runs: bool,
+    ) -> Self {
+        RustPass {
+            kind: RustPassKind::ConsolidateBlocks {
+                decomposer,
+                basis_gate_name,
+                basis_gates,
+                force_consolidate,
+                consolidate_1q_runs,
+            },
+        }
+    }
+
+    /// The pass of :func:`.unitary_synthesis.run_default_unitary_synthesis`.
+    #[staticmethod]
+    #[pyo3(signature = (euler_bases, decomposer_2q, basis_gate, approximate, synthesize_multi_qubit, min_qubits=0, coupling_edges=None))]
+    fn unitary_synthesis(
+        euler_bases: Vec<PyBackedStr>,
+        decomposer_2q: Option<Py<TwoQubitBasisDecomposer>>,
+        basis_gate: Option<PyObject>,
+        approximate: bool,
+        synthesize_multi_qubit: bool,
+        min_qubits: usize,
+        coupling_edges: Option<HashSet<[BitType; 2]>>,
+    ) -> PyResult<Self> {
+        let euler_bases = euler_bases
+            .iter()
+            .map(|basis| EulerBasis::from_str(basis))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(RustPass {
+            kind: RustPassKind::UnitarySynthesis {
+                euler_bases,
+                decomposer_2q,
+                basis_gate,
+                approximate,
+                synthesize_multi_qubit,
+                min_qubits,
+                coupling_edges,
+            },
+        })
+    }
+}
+
+#[cfg(feature = "python")]
+impl RustPass {
+    /// Run the pass, returning the new circuit and the global phase to add to it.
+    fn run(&self, py: Python, circuit: &CircuitData) -> PyResult<(CircuitData, f64)> {
+        match &self.kind {
+            RustPassKind::CommutativeInverseCancellation {
+                matrix_based,
+                max_qubits,
+            } => py_commutative_inverse_cancellation(py, circuit, *matrix_based, *max_qubits),
+            RustPassKind::ConsolidateBlocks {
+                decomposer,
+                basis_gate_name,
+                basis_gates,
+                force_consolidate,
+                consolidate_1q_runs,
+            } => Ok((
+                py_consolidate_blocks(
+                    py,
+                    circuit,
+                    &decomposer.borrow(py),
+                    basis_gate_name,
+                    basis_gates.clone(),
+                    *force_consolidate,
+                    *consolidate_1q_runs,
+                )?,
+                0.,
+            )),
+            RustPassKind::UnitarySynthesis {
+                euler_bases,
+                decomposer_2q,
+                basis_gate,
+                approximate,
+                synthesize_multi_qubit,
+                min_qubits,
+                coupling_edges,
+            } => {
+                let decomposer_2q = decomposer_2q.as_ref().map(|d| d.borrow(py));
+                let backends = DefaultSynthesisBackends {
+                    euler_bases,
+                    decomposer_2q: decomposer_2q.as_deref(),
+                    approximate: *approximate,
+                    synthesize_multi_qubit: *synthesize_multi_qubit,
+                };
+                synthesize_circuit(
+                    py,
+                    circuit,
+                    &backends,
+                    basis_gate.as_ref(),
+                    *min_qubits,
+                    coupling_edges.as_ref(),
+                )
+            }
+        }
+    }
+}
+
+/// A circuit handed between Rust passes without converting it to a ``DAGCircuit``.
+///
+/// The handle shares the Rust-space data of the circuit, so passing it on doesn't copy the
+/// instructions; a pass that changes the circuit makes a new handle.  Everything else about the
+/// circuit (its registers, its metadata and its calibrations) is kept by an empty copy of the
+/// circuit, the template, together with the global phase that the Rust passes added to it.
+///
+/// Handles are only made from circuits without control flow, and no Rust pass adds any.
+#[cfg(feature = "python")]
+#[pyclass(module = "qiskit._accelerate.pass_loop", frozen)]
+pub struct CircuitHandle {
+    data: Arc<CircuitData>,
+    template: PyObject,
+    global_phase: f64,
+}
+
+#[cfg(feature = "python")]
+impl CircuitHandle {
+    pub fn new(data: Arc<CircuitData>, template: PyObject, global_phase: f64) -> Self {
+        CircuitHandle {
+            data,
+            template,
+            global_phase,
+        }
+    }
+
+    /// The shared data of the circuit.
+    pub fn data(&self) -> &Arc<CircuitData> {
+        &self.data
+    }
+
+    /// The handle of the same circuit with its data replaced by `data`, and `global_phase` added
+    /// to its global phase.
+    pub fn with_data(&self, py: Python, data: Arc<CircuitData>, global_phase: f64) -> Self {
+        CircuitHandle {
+            data,
+            template: self.template.clone_ref(py),
+            global_phase: self.global_phase + global_phase,
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl CircuitHandle {
+    /// The handle of the circuit of ``dag``, which must not contain control flow.
+    #[staticmethod]
+    fn from_dag(py: Python, dag: &Bound<PyAny>) -> PyResult<Self> {
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("copy_operations", false)?;
+        let circuit = DAG_TO_CIRCUIT.get_bound(py)?.call((dag,), Some(&kwargs))?;
+        let data = circuit
+            .getattr("_data")?
+            .extract::<PyRef<CircuitData>>()?
+            .copy(py)?;
+        let template = circuit.call_method0("copy_empty_like")?;
+        Ok(Self::new(Arc::new(data), template.unbind(), 0.))
+    }
+
+    /// The global phase added to the circuit by the Rust passes.
+    #[getter]
+    fn global_phase(&self) -> f64 {
+        self.global_phase
+    }
+
+    /// The number of instructions of the circuit.
+    fn __len__(&self) -> usize {
+        self.data.__len__()
+    }
+
+    /// A copy of the data of the circuit.
+    fn circuit_data(&self, py: Python) -> PyResult<CircuitData> {
+        self.data.copy(py)
+    }
+
+    /// The circuit as a ``QuantumCircuit``.
+    fn to_circuit<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let out = self.template.bind(py).call_method0("copy_empty_like")?;
+        out.setattr("_data", Py::new(py, self.data.copy(py)?)?)?;
+        if self.global_phase != 0. {
+            let global_phase = out.getattr("global_phase")?.add(self.global_phase)?;
+            out.setattr("global_phase", global_phase)?;
+        }
+        Ok(out)
+    }
+
+    /// The circuit as a ``DAGCircuit``.
+    fn to_dag<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("copy_operations", false)?;
+        CIRCUIT_TO_DAG
+            .get_bound(py)?
+            .call((self.to_circuit(py)?,), Some(&kwargs))
+    }
+}
+
+/// Run a sequence of Rust passes, in order, over the circuit of a handle.
+///
+/// Args:
+///     handle (CircuitHandle): the circuit to transform.
+///     passes (list[RustPass]): the passes to run.
+///
+/// Returns:
+///     CircuitHandle: the transformed circuit, which shares the data of ``handle`` if no pass
+///     ran.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn run_passes(
+    py: Python,
+    handle: &CircuitHandle,
+    passes: Vec<PyRef<RustPass>>,
+) -> PyResult<CircuitHandle> {
+    let _timer = instrumentation::Timer::start("run_passes");
+    let mut data = handle.data().clone();
+    let mut global_phase = 0.;
+    for pass in passes.iter() {
+        let (out, phase) = pass.run(py, &data)?;
+        data = Arc::new(out);
+        global_phase += phase;
+    }
+    Ok(handle.with_data(py, data, global_phase))
+}
+
+/// Run a sequence of Rust passes over a circuit until it stops changing.
+///
+/// Each iteration runs all of ``passes`` in order.  The loop stops after the first iteration that
+/// leaves the content of the circuit unchanged, or after ``max_iterations`` iterations.
+///
+/// Args:
+///     circuit (CircuitData): the circuit to transform.
+///     passes (list[RustPass]): the passes of each iteration.
+///     max_iterations (int): the most iterations to run.
+///
+/// Returns:
+///     (CircuitData, float, int): the transformed circuit, the global phase to add to it, and the
+///     number of iterations that ran.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, passes, max_iterations=1000))]
+pub fn run_to_fixed_point(
+    py: Python,
+    circuit: &CircuitData,
+    passes: Vec<PyRef<RustPass>>,
+    max_iterations: usize,
+) -> PyResult<(CircuitData, f64, usize)> {
+    let _timer = instrumentation::Timer::start("run_to_fixed_point");
+    let mut global_phase = 0.;
+    let (circuit, iterations) = run_to_fixed_point_inner(
+        circuit.copy(py)?,
+        max_iterations,
+        |mut circuit| {
+            for pass in passes.iter() {
+                let (out, phase) = pass.run(py, &circuit)?;
+                circuit = out;
+                global_phase += phase;
+            }
+            Ok(circuit)
+        },
+        |circuit: &CircuitData| circuit.content_hash(py),
+    )?;
+    instrumentation::add_trials("run_to_fixed_point", iterations);
+    Ok((circuit, global_phase, iterations))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn pass_loop(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<RustPass>()?;
+    m.add_class::<CircuitHandle>()?;
+    m.add_wrapped(wrap_pyfunction!(run_to_fixed_point))?;
+    m.add_wrapped(wrap_pyfunction!(run_passes))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(value: &u64) -> Result<u64, String> {
+        Ok(*value)
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        // 40 -> 20 -> 10 -> 5 -> 2 -> 1 -> 0 -> 0: the seventh halving is the first to change
+        // nothing.
+        let result = run_to_fixed_point_inner(40, 100, |value| Ok(value / 2), hash);
+        assert_eq!(result, Ok((0, 7)));
+    }
+
+    #[test]
+    fn test_unchanged_by_first_iteration() {
+        let result = run_to_fixed_point_inner(3, 100, Ok, hash);
+        assert_eq!(result, Ok((3, 1)));
+    }
+
+    #[test]
+    fn test_max_iterations() {
+        let mut steps = 0;
+        let result = run_to_fixed_point_inner(
+            0,
+            5,
+            |value| {
+                steps += 1;
+                Ok(value + 1)
+            },
+            hash,
+        );
+        assert_eq!(result, Ok((5, 5)));
+        assert_eq!(steps, 5);
+    }
+
+    #[test]
+    fn test_no_iterations() {
+        let result = run_to_fixed_point_inner(8, 0, |value| Ok(value / 2), hash);
+        assert_eq!(result, Ok((8, 0)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let result = run_to_fixed_point_inner(
+            8,
+            100,
+            |value| {
+                if value == 2 {
+                    Err("step".to_string())
+                } else {
+                    Ok(value / 2)
+                }
+            },
+            hash,
+        );
+        assert_eq!(result, Err("step".to_string()));
+        let result = run_to_fixed_point_inner(
+            8,
+            100,
+            |value| Ok(value / 2),
+            |value: &u64| {
+                if *value == 1 {
+                    Err("hash".to_string())
+                } else {
+                    Ok(*value)
+                }
+            },
+        );
+        assert_eq!(result, Err("hash".to_string()));
+    }
+}
@@ -0,0 +1,589 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Single Paulis in the symplectic representation.
+//!
+//! A [Pauli] is `(-i)^phase P`, where `P` is the tensor product of `I`, `X`, `Y` and `Z` given by
+//! the `z` and `x` bits of each qubit (`Y` has both set) and `phase` is the *group* phase, as in
+//! `qiskit.quantum_info.Pauli.phase`.  Python's `BasePauli` stores the *ZX* phase instead, which
+//! is the exponent when `Y` is written as `iXZ`; the two differ by the number of `Y`s.  Products
+//! and conjugations are naturally expressed in the ZX convention, so that's what we compute in,
+//! only converting the `Y`s that a step actually touches.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::clifford::CliffordGate;
+use crate::error::AccelerateError;
+
+/// The coefficients `(-i)^phase` of the group phases, as they appear in labels.
+const PHASE_LABELS: [&str; 4] = ["", "-i", "-", "i"];
+
+/// A Pauli operator on `n` qubits, with a phase that is a power of `-i`.
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.pauli"))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Pauli {
+    z: Vec<bool>,
+    x: Vec<bool>,
+    phase: u8,
+}
+
+impl Pauli {
+    pub fn new(z: Vec<bool>, x: Vec<bool>, phase: u8) -> Result<Self, AccelerateError> {
+        if z.len() != x.len() {
+            return Err(AccelerateError::Qiskit(
+                "z and x vectors are different size.".to_string(),
+            ));
+        }
+        Ok(Pauli {
+            z,
+            x,
+            phase: phase % 4,
+        })
+    }
+
+    /// The identity on `num_qubits` qubits.
+    pub fn identity(num_qubits: usize) -> Self {
+        Pauli {
+            z: vec![false; num_qubits],
+            x: vec![false; num_qubits],
+            phase: 0,
+        }
+    }
+
+    /// Parse a label like `"-iXYZ"`, which is in big-endian order: the last character is qubit 0.
+    /// The coefficient is any of `+`, `-`, an optional `1`, and an optional `i` or `j`.
+    pub fn from_label(label: &str) -> Result<Self, AccelerateError> {
+        let invalid =
+            || AccelerateError::Qiskit(format!("Pauli string label \"{label}\" is not valid."));
+        let mut rest = label;
+        let mut phase = 0;
+        if let Some(stripped) = rest.strip_prefix('-') {
+            phase += 2;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix('+') {
+            rest = stripped;
+        }
+        rest = rest.strip_prefix('1').unwrap_or(rest);
+        if let Some(stripped) = rest.strip_prefix(['i', 'j']) {
+            // `i = (-i)^3`.
+            phase += 3;
+            rest = stripped;
+        }
+        let num_qubits = rest.len();
+        let mut out = Pauli::identity(num_qubits);
+        for (qubit, letter) in rest.bytes().rev().enumerate() {
+            let (z, x) = match letter {
+                b'I' => (false, false),
+                b'X' => (false, true),
+                b'Y' => (true, true),
+                b'Z' => (true, false),
+                _ => return Err(invalid()),
+            };
+            out.z[qubit] = z;
+            out.x[qubit] = x;
+        }
+        out.phase = phase % 4;
+        Ok(out)
+    }
+
+    /// The label of the Pauli, including its coefficient unless `full_group` is false.
+    pub fn to_label(&self, full_group: bool) -> String {
+        let mut label = String::with_capacity(self.num_qubits() + 2);
+        if full_group {
+            label.push_str(PHASE_LABELS[self.phase as usize]);
+        }
+        label.extend(self.z.iter().zip(&self.x).rev().map(|pair| match pair {
+            (false, false) => 'I',
+            (false, true) => 'X',
+            (true, true) => 'Y',
+            (true, false) => 'Z',
+        }));
+        label
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.z.len()
+    }
+
+    pub fn z(&self) -> &[bool] {
+        &self.z
+    }
+
+    pub fn x(&self) -> &[bool] {
+        &self.x
+    }
+
+    /// The group phase.
+    pub fn phase(&self) -> u8 {
+        self.phase
+    }
+
+    /// The ZX phase, which is the phase used by Python's `BasePauli`.
+    pub fn zx_phase(&self) -> u8 {
+        ((self.phase as usize + self.num_y()) % 4) as u8
+    }
+
+    fn num_y(&self) -> usize {
+        self.z
+            .iter()
+            .zip(&self.x)
+            .filter(|(z, x)| **z && **x)
+            .count()
+    }
+
+    fn check_same_size(&self, other: &Pauli) -> Result<(), AccelerateError> {
+        if self.num_qubits() != other.num_qubits() {
+            return Err(AccelerateError::Qiskit(format!(
+                "Number of qubits of other Pauli does not match the current Pauli ({} != {}).",
+                other.num_qubits(),
+                self.num_qubits()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The matrix product `self @ other`.
+    pub fn dot(&self, other: &Pauli) -> Result<Pauli, AccelerateError> {
+        self.check_same_size(other)?;
+        // Moving the `Z`s of `other` through the `X`s of `self` picks up a sign for each qubit.
+        let swaps = self
+            .x
+            .iter()
+            .zip(&other.z)
+            .filter(|(x, z)| **x && **z)
+            .count();
+        let zx_phase = self.zx_phase() as usize + other.zx_phase() as usize + 2 * swaps;
+        let mut out = Pauli {
+            z: self.z.iter().zip(&other.z).map(|(a, b)| a ^ b).collect(),
+            x: self.x.iter().zip(&other.x).map(|(a, b)| a ^ b).collect(),
+            phase: 0,
+        };
+        out.phase = ((zx_phase + 4 * self.num_qubits() - out.num_y()) % 4) as u8;
+        Ok(out)
+    }
+
+    /// The composition with `other` as operators.  With `front = false` this is `other @ self`
+    /// (apply `self` first), and with `front = true` it is `self @ other`.
+    pub fn compose(&self, other: &Pauli, front: bool) -> Result<Pauli, AccelerateError> {
+        if front {
+            self.dot(other)
+        } else {
+            other.dot(self)
+        }
+    }
+
+    /// Whether the two Paulis commute (rather than anticommute).
+    pub fn commutes(&self, other: &Pauli) -> Result<bool, AccelerateError> {
+        self.check_same_size(other)?;
+        let overlap = (0..self.num_qubits())
+            .filter(|&q| (self.x[q] & other.z[q]) ^ (self.z[q] & other.x[q]))
+            .count();
+        Ok(overlap % 2 == 0)
+    }
+
+    fn check_qubit(&self, qubit: usize) -> Result<(), AccelerateError> {
+        if qubit >= self.num_qubits() {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {}-qubit Pauli",
+                self.num_qubits()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply an update `f` of the bits on `qubits` that returns the change in the ZX phase, and
+    /// fix up the group phase for any `Y`s created or destroyed.
+    fn update(&mut self, qubits: &[usize], f: impl FnOnce(&mut Self) -> usize) {
+        let count_y = |pauli: &Self| qubits.iter().filter(|&&q| pauli.z[q] && pauli.x[q]).count();
+        let y_before = count_y(self);
+        let zx_delta = f(self);
+        let y_after = count_y(self);
+        self.phase = ((self.phase as usize + zx_delta + 4 * 2 + y_before - y_after) % 4) as u8;
+    }
+
+    fn conjugate_h(&mut self, q: usize) {
+        self.update(&[q], |p| {
+            let delta = 2 * (p.x[q] && p.z[q]) as usize;
+            std::mem::swap(&mut p.x[q], &mut p.z[q]);
+            delta
+        })
+    }
+
+    fn conjugate_s(&mut self, q: usize, dagger: bool) {
+        self.update(&[q], |p| {
+            p.z[q] ^= p.x[q];
+            match (p.x[q], dagger) {
+                (false, _) => 0,
+                (true, false) => 1,
+                (true, true) => 3,
+            }
+        })
+    }
+
+    fn conjugate_cx(&mut self, control: usize, target: usize) {
+        self.update(&[control, target], |p| {
+            p.x[target] ^= p.x[control];
+            p.z[control] ^= p.z[target];
+            0
+        })
+    }
+
+    /// Conjugate by a Clifford gate in place, `P -> G P G^dagger`.
+    pub fn conjugate_by_gate(
+        &mut self,
+        gate: CliffordGate,
+        qubits: &[usize],
+    ) -> Result<(), AccelerateError> {
+        if qubits.len() != gate.num_qubits() {
+            return Err(AccelerateError::Qiskit(format!(
+                "Invalid qubits for {}-qubit gate.",
+                gate.num_qubits()
+            )));
+        }
+        for &qubit in qubits {
+            self.check_qubit(qubit)?;
+        }
+        if gate.num_qubits() == 2 && qubits[0] == qubits[1] {
+            return Err(AccelerateError::Qiskit(format!(
+                "duplicate qubit {} in the arguments of a two-qubit gate",
+                qubits[0]
+            )));
+        }
+        let q0 = qubits[0];
+        let q1 = qubits.get(1).copied().unwrap_or(q0);
+        match gate {
+            CliffordGate::I => (),
+            CliffordGate::X => self.update(&[q0], |p| 2 * p.z[q0] as usize),
+            CliffordGate::Y => self.update(&[q0], |p| 2 * (p.x[q0] ^ p.z[q0]) as usize),
+            CliffordGate::Z => self.update(&[q0], |p| 2 * p.x[q0] as usize),
+            CliffordGate::H => self.conjugate_h(q0),
+            CliffordGate::S => self.conjugate_s(q0, false),
+            CliffordGate::Sdg => self.conjugate_s(q0, true),
+            // `SX = H S H` and `SXdg = H Sdg H`, up to global phase.
+            CliffordGate::SX | CliffordGate::SXdg => {
+                self.conjugate_h(q0);
+                self.conjugate_s(q0, gate == CliffordGate::SXdg);
+                self.conjugate_h(q0);
+            }
+            CliffordGate::V => {
+                self.conjugate_s(q0, true);
+                self.conjugate_h(q0);
+            }
+            CliffordGate::W => {
+                for _ in 0..2 {
+                    self.conjugate_s(q0, true);
+                    self.conjugate_h(q0);
+                }
+            }
+            CliffordGate::CX => self.conjugate_cx(q0, q1),
+            CliffordGate::CZ => self.update(&[q0, q1], |p| {
+                let (x0, x1) = (p.x[q0], p.x[q1]);
+                p.z[q0] ^= x1;
+                p.z[q1] ^= x0;
+                2 * (x0 && x1) as usize
+            }),
+            CliffordGate::CY => {
+                self.conjugate_s(q1, true);
+                self.conjugate_cx(q0, q1);
+                self.conjugate_s(q1, false);
+            }
+            CliffordGate::Swap => {
+                self.z.swap(q0, q1);
+                self.x.swap(q0, q1);
+            }
+            CliffordGate::ISwap => {
+                self.conjugate_s(q0, false);
+                self.conjugate_h(q0);
+                self.conjugate_s(q1, false);
+                self.conjugate_cx(q0, q1);
+                self.conjugate_cx(q1, q0);
+                self.conjugate_h(q1);
+            }
+            CliffordGate::DCX => {
+                self.conjugate_cx(q0, q1);
+                self.conjugate_cx(q1, q0);
+            }
+            CliffordGate::ECR => {
+                self.conjugate_s(q0, false);
+                self.conjugate_by_gate(CliffordGate::SX, &[q1])?;
+                self.conjugate_cx(q0, q1);
+                self.conjugate_by_gate(CliffordGate::X, &[q0])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Pauli {
+    #[new]
+    #[pyo3(signature = (z, x, phase=0))]
+    fn py_new(z: Vec<bool>, x: Vec<bool>, phase: u8) -> PyResult<Self> {
+        Ok(Self::new(z, x, phase)?)
+    }
+
+    /// Create a Pauli from a label such as ``"-iXYZ"``.
+    #[staticmethod]
+    #[pyo3(name = "from_label")]
+    fn py_from_label(label: &str) -> PyResult<Self> {
+        Ok(Self::from_label(label)?)
+    }
+
+    /// Return the label of the Pauli, with its coefficient unless ``full_group`` is false.
+    #[pyo3(name = "to_label", signature = (full_group=true))]
+    fn py_to_label(&self, full_group: bool) -> String {
+        self.to_label(full_group)
+    }
+
+    #[getter]
+    #[pyo3(name = "num_qubits")]
+    fn py_num_qubits(&self) -> usize {
+        self.num_qubits()
+    }
+
+    #[getter]
+    #[pyo3(name = "z")]
+    fn py_z(&self, py: Python) -> Py<PyArray1<bool>> {
+        self.z.clone().into_pyarray_bound(py).unbind()
+    }
+
+    #[getter]
+    #[pyo3(name = "x")]
+    fn py_x(&self, py: Python) -> Py<PyArray1<bool>> {
+        self.x.clone().into_pyarray_bound(py).unbind()
+    }
+
+    /// The group phase exponent, as in :attr:`.Pauli.phase`.
+    #[getter]
+    #[pyo3(name = "phase")]
+    fn py_phase(&self) -> u8 {
+        self.phase
+    }
+
+    /// The phase exponent in the ZX convention of the internal ``BasePauli._phase``.
+    #[getter]
+    #[pyo3(name = "zx_phase")]
+    fn py_zx_phase(&self) -> u8 {
+        self.zx_phase()
+    }
+
+    /// Return the matrix product ``self @ other``.
+    #[pyo3(name = "dot")]
+    fn py_dot(&self, other: &Pauli) -> PyResult<Self> {
+        Ok(self.dot(other)?)
+    }
+
+    /// Return the operator composition, as :meth:`.Pauli.compose` without ``qargs``.
+    #[pyo3(name = "compose", signature = (other, front=false))]
+    fn py_compose(&self, other: &Pauli, front: bool) -> PyResult<Self> {
+        Ok(self.compose(other, front)?)
+    }
+
+    /// Return whether the Pauli commutes with ``other``.
+    #[pyo3(name = "commutes")]
+    fn py_commutes(&self, other: &Pauli) -> PyResult<bool> {
+        Ok(self.commutes(other)?)
+    }
+
+    /// Conjugate the Pauli by a Clifford gate in place, ``P -> G P G^dagger``.
+    ///
+    /// Args:
+    ///     name (str): the name of the gate, as in ``Instruction.name``.
+    ///     qubits (list[int]): the qubits the gate acts on.
+    #[pyo3(name = "conjugate_by_gate", text_signature = "(self, name, qubits, /)")]
+    fn py_conjugate_by_gate(&mut self, name: &str, qubits: Vec<usize>) -> PyResult<()> {
+        Ok(self.conjugate_by_gate(name.parse()?, &qubits)?)
+    }
+
+    fn __eq__(&self, other: &Pauli) -> bool {
+        self == other
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Pauli('{}')", self.to_label(true))
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn pauli(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<Pauli>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(label: &str) -> Pauli {
+        Pauli::from_label(label).unwrap()
+    }
+
+    #[test]
+    fn test_from_label() {
+        let pauli = label("-iXYZI");
+        assert_eq!(pauli.z(), &[false, true, true, false]);
+        assert_eq!(pauli.x(), &[false, false, true, true]);
+        assert_eq!(pauli.phase(), 1);
+        assert_eq!(pauli.zx_phase(), 2);
+        for (spelling, phase) in [
+            ("X", 0),
+            ("+X", 0),
+            ("1X", 0),
+            ("+1X", 0),
+            ("-X", 2),
+            ("-1X", 2),
+            ("iX", 3),
+            ("jX", 3),
+            ("+1jX", 3),
+            ("-iX", 1),
+            ("-1jX", 1),
+        ] {
+            assert_eq!(
+                label(spelling),
+                Pauli::new(vec![false], vec![true], phase).unwrap()
+            );
+        }
+        for invalid in ["S", "XX-", "x", "iiX", "+-X", "i1X", "1+X"] {
+            assert!(matches!(
+                Pauli::from_label(invalid),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_to_label() {
+        for phase in ["", "-i", "-", "i"] {
+            for paulis in ["IXYZ", "YYY", "", "Z"] {
+                let full = format!("{phase}{paulis}");
+                assert_eq!(label(&full).to_label(true), full);
+                assert_eq!(label(&full).to_label(false), paulis);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dot() {
+        let cases = [
+            ("X", "Y", "iZ"),
+            ("Y", "X", "-iZ"),
+            ("Y", "Z", "iX"),
+            ("Z", "Y", "-iX"),
+            ("Z", "X", "iY"),
+            ("X", "Z", "-iY"),
+            ("Y", "Y", "I"),
+            ("-iX", "iX", "I"),
+            ("XZ", "YY", "ZX"),
+            ("iXY", "-YZ", "iZX"),
+        ];
+        for (first, second, expected) in cases {
+            assert_eq!(
+                label(first).dot(&label(second)).unwrap(),
+                label(expected),
+                "{first} @ {second}"
+            );
+            assert_eq!(
+                label(first).compose(&label(second), true).unwrap(),
+                label(expected)
+            );
+            assert_eq!(
+                label(second).compose(&label(first), false).unwrap(),
+                label(expected)
+            );
+        }
+        assert!(matches!(
+            label("X").dot(&label("XX")),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_commutes() {
+        assert!(label("X").commutes(&label("X")).unwrap());
+        assert!(!label("X").commutes(&label("Z")).unwrap());
+        assert!(label("XX").commutes(&label("ZZ")).unwrap());
+        assert!(!label("XY").commutes(&label("ZY")).unwrap());
+        assert!(label("-iXI").commutes(&label("IZ")).unwrap());
+    }
+
+    #[test]
+    fn test_conjugate_by_gate() {
+        let cases: [(CliffordGate, &[usize], &str, &str); 16] = [
+            (CliffordGate::X, &[0], "Z", "-Z"),
+            (CliffordGate::Y, &[0], "X", "-X"),
+            (CliffordGate::Z, &[0], "Y", "-Y"),
+            (CliffordGate::H, &[0], "X", "Z"),
+            (CliffordGate::H, &[0], "Y", "-Y"),
+            (CliffordGate::S, &[0], "X", "Y"),
+            (CliffordGate::S, &[0], "Y", "-X"),
+            (CliffordGate::Sdg, &[0], "X", "-Y"),
+            (CliffordGate::SX, &[0], "Z", "-Y"),
+            (CliffordGate::SXdg, &[0], "Z", "Y"),
+            (CliffordGate::CX, &[0, 1], "IX", "XX"),
+            (CliffordGate::CX, &[0, 1], "ZI", "ZZ"),
+            (CliffordGate::CX, &[0, 1], "YI", "YZ"),
+            (CliffordGate::CZ, &[0, 1], "IX", "ZX"),
+            (CliffordGate::Swap, &[0, 1], "-iXZ", "-iZX"),
+            (CliffordGate::CY, &[1, 0], "XI", "XY"),
+        ];
+        for (gate, qubits, before, after) in cases {
+            let mut pauli = label(before);
+            pauli.conjugate_by_gate(gate, qubits).unwrap();
+            assert_eq!(pauli, label(after), "{gate:?} {before}");
+        }
+    }
+
+    #[test]
+    fn test_conjugate_by_gate_and_inverse() {
+        let gates: [(CliffordGate, &[usize]); 6] = [
+            (CliffordGate::V, &[1]),
+            (CliffordGate::W, &[1]),
+            (CliffordGate::ISwap, &[0, 1]),
+            (CliffordGate::DCX, &[1, 0]),
+            (CliffordGate::DCX, &[0, 1]),
+            (CliffordGate::ECR, &[0, 1]),
+        ];
+        // `V W = I`, `iSwap^4 = I`, `DCX(1, 0) DCX(0, 1) = I` and `ECR^2 = I`.
+        let sequence = [0, 1, 2, 2, 2, 2, 3, 4, 5, 5];
+        for start in ["XY", "-iZX", "YI", "iIZ"] {
+            let mut pauli = label(start);
+            for index in sequence {
+                let (gate, qubits) = gates[index];
+                pauli.conjugate_by_gate(gate, qubits).unwrap();
+            }
+            assert_eq!(pauli, label(start));
+        }
+    }
+
+    #[test]
+    fn test_conjugate_by_gate_errors() {
+        let mut pauli = label("XY");
+        assert!(matches!(
+            pauli.conjugate_by_gate(CliffordGate::H, &[2]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            pauli.conjugate_by_gate(CliffordGate::CX, &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            pauli.conjugate_by_gate(CliffordGate::CX, &[1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert_eq!(pauli, label("XY"));
+    }
+}
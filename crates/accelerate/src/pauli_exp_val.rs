@@ -11,16 +11,13 @@
 // that they have been altered from the originals.
 
 use num_complex::Complex64;
-use numpy::PyReadonlyArray1;
 use pulp::Simd;
-use pyo3::exceptions::PyOverflowError;
-use pyo3::prelude::*;
-use pyo3::wrap_pyfunction;
 use rayon::prelude::*;
+#[cfg(feature = "python")]
+use {numpy::PyReadonlyArray1, pyo3::prelude::*, pyo3::wrap_pyfunction};
 
-use crate::getenv_use_multiple_threads;
-
-const PARALLEL_THRESHOLD: usize = 19;
+use crate::error::AccelerateError;
+use crate::threading::{self, ParallelKernel};
 
 #[pulp::with_simd(fast_sum = pulp::Arch::new())]
 #[inline(always)]
@@ -33,21 +30,17 @@ pub fn fast_sum_with_simd<S: Simd>(simd: S, values: &[f64]) -> f64 {
 }
 
 /// Compute the pauli expectatation value of a statevector without x
-#[pyfunction]
-#[pyo3(text_signature = "(data, num_qubits, z_mask, /)")]
-pub fn expval_pauli_no_x(
-    data: PyReadonlyArray1<Complex64>,
+pub fn expval_pauli_no_x_inner(
+    data_arr: &[Complex64],
     num_qubits: usize,
     z_mask: usize,
-) -> PyResult<f64> {
+) -> Result<f64, AccelerateError> {
     if num_qubits >= usize::BITS as usize {
-        return Err(PyOverflowError::new_err(format!(
+        return Err(AccelerateError::Overflow(format!(
             "The value for num_qubits, {num_qubits}, is too large and would overflow"
         )));
     }
-    let data_arr = data.as_slice()?;
     let size = 1_usize << num_qubits;
-    let run_in_parallel = getenv_use_multiple_threads();
     let map_fn = |i: usize| -> f64 {
         let mut val: f64 = data_arr[i].re * data_arr[i].re + data_arr[i].im * data_arr[i].im;
         if (i & z_mask).count_ones() & 1 != 0 {
@@ -56,34 +49,32 @@ pub fn expval_pauli_no_x(
         val
     };
 
-    if num_qubits < PARALLEL_THRESHOLD || !run_in_parallel {
+    if !threading::run_in_parallel(ParallelKernel::PauliExpVal, num_qubits) {
         Ok(fast_sum(&(0..size).map(map_fn).collect::<Vec<f64>>()))
     } else {
-        Ok((0..size).into_par_iter().map(map_fn).sum())
+        Ok(threading::install(|| {
+            (0..size).into_par_iter().map(map_fn).sum()
+        }))
     }
 }
 
 /// Compute the pauli expectatation value of a statevector with x
-#[pyfunction]
-#[pyo3(text_signature = "(data, num_qubits, z_mask, x_mask, phase, x_max, /)")]
-pub fn expval_pauli_with_x(
-    data: PyReadonlyArray1<Complex64>,
+pub fn expval_pauli_with_x_inner(
+    data_arr: &[Complex64],
     num_qubits: usize,
     z_mask: usize,
     x_mask: usize,
     phase: Complex64,
     x_max: u32,
-) -> PyResult<f64> {
+) -> Result<f64, AccelerateError> {
     if num_qubits > usize::BITS as usize {
-        return Err(PyOverflowError::new_err(format!(
+        return Err(AccelerateError::Overflow(format!(
             "The value for num_qubits, {num_qubits}, is too large and would overflow",
         )));
     }
-    let data_arr = data.as_slice()?;
     let mask_u = !(2_usize.pow(x_max + 1) - 1);
     let mask_l = 2_usize.pow(x_max) - 1;
     let size = 1_usize << (num_qubits - 1);
-    let run_in_parallel = getenv_use_multiple_threads();
     let map_fn = |i: usize| -> f64 {
         let index_0 = ((i << 1) & mask_u) | (i & mask_l);
         let index_1 = index_0 ^ x_mask;
@@ -114,30 +105,28 @@ pub fn expval_pauli_with_x(
         }
         val
     };
-    if num_qubits < PARALLEL_THRESHOLD || !run_in_parallel {
+    if !threading::run_in_parallel(ParallelKernel::PauliExpVal, num_qubits) {
         Ok(fast_sum(&(0..size).map(map_fn).collect::<Vec<f64>>()))
     } else {
-        Ok((0..size).into_par_iter().map(map_fn).sum())
+        Ok(threading::install(|| {
+            (0..size).into_par_iter().map(map_fn).sum()
+        }))
     }
 }
 
 /// Compute the pauli expectatation value of a density matrix without x
-#[pyfunction]
-#[pyo3(text_signature = "(data, num_qubits, z_mask, /)")]
-pub fn density_expval_pauli_no_x(
-    data: PyReadonlyArray1<Complex64>,
+pub fn density_expval_pauli_no_x_inner(
+    data_arr: &[Complex64],
     num_qubits: usize,
     z_mask: usize,
-) -> PyResult<f64> {
+) -> Result<f64, AccelerateError> {
     if num_qubits >= usize::BITS as usize {
-        return Err(PyOverflowError::new_err(format!(
+        return Err(AccelerateError::Overflow(format!(
             "The value for num_qubits, {num_qubits}, is too large and would overflow",
         )));
     }
-    let data_arr = data.as_slice()?;
     let num_rows = 1_usize << num_qubits;
     let stride = 1 + num_rows;
-    let run_in_parallel = getenv_use_multiple_threads();
     let map_fn = |i: usize| -> f64 {
         let index = i * stride;
         let mut val = data_arr[index].re;
@@ -146,34 +135,32 @@ pub fn density_expval_pauli_no_x(
         }
         val
     };
-    if num_qubits < PARALLEL_THRESHOLD || !run_in_parallel {
+    if !threading::run_in_parallel(ParallelKernel::PauliExpVal, num_qubits) {
         Ok(fast_sum(&(0..num_rows).map(map_fn).collect::<Vec<f64>>()))
     } else {
-        Ok((0..num_rows).into_par_iter().map(map_fn).sum())
+        Ok(threading::install(|| {
+            (0..num_rows).into_par_iter().map(map_fn).sum()
+        }))
     }
 }
 
 /// Compute the pauli expectatation value of a density matrix with x
-#[pyfunction]
-#[pyo3(text_signature = "(data, num_qubits, z_mask, x_mask, phase, x_max, /)")]
-pub fn density_expval_pauli_with_x(
-    data: PyReadonlyArray1<Complex64>,
+pub fn density_expval_pauli_with_x_inner(
+    data_arr: &[Complex64],
     num_qubits: usize,
     z_mask: usize,
     x_mask: usize,
     phase: Complex64,
     x_max: u32,
-) -> PyResult<f64> {
+) -> Result<f64, AccelerateError> {
     if num_qubits >= usize::BITS as usize {
-        return Err(PyOverflowError::new_err(format!(
+        return Err(AccelerateError::Overflow(format!(
             "The value for num_qubits, {num_qubits}, is too large and would overflow",
         )));
     }
-    let data_arr = data.as_slice()?;
     let mask_u = !(2_usize.pow(x_max + 1) - 1);
     let mask_l = 2_usize.pow(x_max) - 1;
     let num_rows = 1_usize << num_qubits;
-    let run_in_parallel = getenv_use_multiple_threads();
     let map_fn = |i: usize| -> f64 {
         let index_vec = ((i << 1) & mask_u) | (i & mask_l);
         let index_mat = (index_vec ^ x_mask) + num_rows * index_vec;
@@ -183,20 +170,272 @@ pub fn density_expval_pauli_with_x(
         }
         val
     };
-    if num_qubits < PARALLEL_THRESHOLD || !run_in_parallel {
+    if !threading::run_in_parallel(ParallelKernel::PauliExpVal, num_qubits) {
         Ok(fast_sum(
             &(0..num_rows >> 1).map(map_fn).collect::<Vec<f64>>(),
         ))
     } else {
-        Ok((0..num_rows >> 1).into_par_iter().map(map_fn).sum())
+        Ok(threading::install(|| {
+            (0..num_rows >> 1).into_par_iter().map(map_fn).sum()
+        }))
+    }
+}
+
+/// Compute the pauli expectatation values of a statevector for many Paulis in one pass
+///
+/// Each Pauli is given by the masks of the qubits it has a Z or an X on, and the phase of its Ys,
+/// and its expectation value is the sum over the basis states `i` of
+/// `(-1)^|i & z| Re(phase conj(data[i]) data[i ^ x])`.  Every amplitude is read once for all the
+/// Paulis, rather than once per Pauli.
+pub fn expval_paulis_inner(
+    data_arr: &[Complex64],
+    num_qubits: usize,
+    z_masks: &[usize],
+    x_masks: &[usize],
+    phases: &[Complex64],
+) -> Result<Vec<f64>, AccelerateError> {
+    if num_qubits >= usize::BITS as usize {
+        return Err(AccelerateError::Overflow(format!(
+            "The value for num_qubits, {num_qubits}, is too large and would overflow"
+        )));
+    }
+    let num_terms = z_masks.len();
+    if x_masks.len() != num_terms || phases.len() != num_terms {
+        return Err(AccelerateError::Value(format!(
+            "There are {} z masks, {} x masks and {} phases",
+            num_terms,
+            x_masks.len(),
+            phases.len()
+        )));
+    }
+    let size = 1_usize << num_qubits;
+    if let Some(mask) = z_masks.iter().chain(x_masks).find(|mask| **mask >= size) {
+        return Err(AccelerateError::Value(format!(
+            "The mask {mask} is out of range for {num_qubits} qubits"
+        )));
+    }
+    let add_terms = |mut acc: Vec<f64>, i: usize| -> Vec<f64> {
+        let amplitude = data_arr[i].conj();
+        for (k, out) in acc.iter_mut().enumerate() {
+            let val = (phases[k] * amplitude * data_arr[i ^ x_masks[k]]).re;
+            if (i & z_masks[k]).count_ones() & 1 != 0 {
+                *out -= val;
+            } else {
+                *out += val;
+            }
+        }
+        acc
+    };
+    if !threading::run_in_parallel(ParallelKernel::PauliExpVal, num_qubits) {
+        Ok((0..size).fold(vec![0.; num_terms], add_terms))
+    } else {
+        Ok(threading::install(|| {
+            (0..size)
+                .into_par_iter()
+                .fold(|| vec![0.; num_terms], add_terms)
+                .reduce(
+                    || vec![0.; num_terms],
+                    |mut left, right| {
+                        left.iter_mut().zip(right).for_each(|(a, b)| *a += b);
+                        left
+                    },
+                )
+        }))
+    }
+}
+
+/// Compute the pauli expectatation value of a statevector without x
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(data, num_qubits, z_mask, /)")]
+pub fn expval_pauli_no_x(
+    data: PyReadonlyArray1<Complex64>,
+    num_qubits: usize,
+    z_mask: usize,
+) -> PyResult<f64> {
+    Ok(expval_pauli_no_x_inner(
+        data.as_slice()?,
+        num_qubits,
+        z_mask,
+    )?)
+}
+
+/// Compute the pauli expectatation value of a statevector with x
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(data, num_qubits, z_mask, x_mask, phase, x_max, /)")]
+pub fn expval_pauli_with_x(
+    data: PyReadonlyArray1<Complex64>,
+    num_qubits: usize,
+    z_mask: usize,
+    x_mask: usize,
+    phase: Complex64,
+    x_max: u32,
+) -> PyResult<f64> {
+    Ok(expval_pauli_with_x_inner(
+        data.as_slice()?,
+        num_qubits,
+        z_mask,
+        x_mask,
+        phase,
+        x_max,
+    )?)
+}
+
+/// Compute the pauli expectatation value of a density matrix without x
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(data, num_qubits, z_mask, /)")]
+pub fn density_expval_pauli_no_x(
+    data: PyReadonlyArray1<Complex64>,
+    num_qubits: usize,
+    z_mask: usize,
+) -> PyResult<f64> {
+    Ok(density_expval_pauli_no_x_inner(
+        data.as_slice()?,
+        num_qubits,
+        z_mask,
+    )?)
+}
+
+/// Compute the pauli expectatation value of a density matrix with x
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(data, num_qubits, z_mask, x_mask, phase, x_max, /)")]
+pub fn density_expval_pauli_with_x(
+    data: PyReadonlyArray1<Complex64>,
+    num_qubits: usize,
+    z_mask: usize,
+    x_mask: usize,
+    phase: Complex64,
+    x_max: u32,
+) -> PyResult<f64> {
+    Ok(density_expval_pauli_with_x_inner(
+        data.as_slice()?,
+        num_qubits,
+        z_mask,
+        x_mask,
+        phase,
+        x_max,
+    )?)
+}
+
+/// Compute the pauli expectatation values of a statevector for many weighted Paulis in one pass
+///
+/// Args:
+///     data (numpy.ndarray): the statevector.
+///     num_qubits (int): the number of qubits of the statevector.
+///     z_masks (list[int]): the mask of the qubits each Pauli has a Z or a Y on.
+///     x_masks (list[int]): the mask of the qubits each Pauli has an X or a Y on.
+///     phases (list[complex]): the phase ``(-1j) ** num_y`` of each Pauli.
+///     coeffs (list[complex]): the coefficient of each Pauli.
+///
+/// Returns:
+///     list[complex]: the expectation value of each Pauli times its coefficient.
+///
+/// Raises:
+///     ValueError: if the lists don't all have the same length, or a mask is out of range.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(data, num_qubits, z_masks, x_masks, phases, coeffs, /)")]
+pub fn expval_paulis(
+    data: PyReadonlyArray1<Complex64>,
+    num_qubits: usize,
+    z_masks: Vec<usize>,
+    x_masks: Vec<usize>,
+    phases: Vec<Complex64>,
+    coeffs: Vec<Complex64>,
+) -> PyResult<Vec<Complex64>> {
+    if coeffs.len() != z_masks.len() {
+        return Err(AccelerateError::Value(format!(
+            "There are {} Paulis but {} coefficients",
+            z_masks.len(),
+            coeffs.len()
+        ))
+        .into());
     }
+    let values = expval_paulis_inner(data.as_slice()?, num_qubits, &z_masks, &x_masks, &phases)?;
+    Ok(values
+        .into_iter()
+        .zip(coeffs)
+        .map(|(value, coeff)| coeff * value)
+        .collect())
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn pauli_expval(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(expval_pauli_no_x))?;
     m.add_wrapped(wrap_pyfunction!(expval_pauli_with_x))?;
     m.add_wrapped(wrap_pyfunction!(density_expval_pauli_with_x))?;
     m.add_wrapped(wrap_pyfunction!(density_expval_pauli_no_x))?;
+    m.add_wrapped(wrap_pyfunction!(expval_paulis))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    /// A normalized three-qubit state with no special symmetry.
+    fn state() -> Vec<Complex64> {
+        let data = (0..8)
+            .map(|i| Complex64::new((i + 1) as f64, ((i * i) % 5) as f64 - 2.))
+            .collect::<Vec<_>>();
+        let norm = data.iter().map(|amp| amp.norm_sqr()).sum::<f64>().sqrt();
+        data.into_iter().map(|amp| amp / norm).collect()
+    }
+
+    #[test]
+    fn test_expval_paulis() {
+        let data = state();
+        let i = Complex64::new(0., 1.);
+        let one = Complex64::new(1., 0.);
+        // Z0, X1, Y2 and X0 Z1 Y2, given by their z masks, x masks and the phases of their Ys.
+        let z_masks = [1, 0, 4, 6];
+        let x_masks = [0, 2, 4, 5];
+        let phases = [one, one, -i, -i];
+        let values = expval_paulis_inner(&data, 3, &z_masks, &x_masks, &phases).unwrap();
+        assert_abs_diff_eq!(
+            values[0],
+            expval_pauli_no_x_inner(&data, 3, 1).unwrap(),
+            epsilon = 1e-12
+        );
+        for (k, x_max) in [(1, 1), (2, 2), (3, 2)] {
+            let expected =
+                expval_pauli_with_x_inner(&data, 3, z_masks[k], x_masks[k], phases[k], x_max)
+                    .unwrap();
+            assert_abs_diff_eq!(values[k], expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_expval_paulis_identity_and_empty() {
+        let data = state();
+        let values = expval_paulis_inner(&data, 3, &[0], &[0], &[Complex64::new(1., 0.)]).unwrap();
+        assert_abs_diff_eq!(values[0], 1., epsilon = 1e-12);
+        assert!(expval_paulis_inner(&data, 3, &[], &[], &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_expval_paulis_errors() {
+        let data = state();
+        let one = Complex64::new(1., 0.);
+        assert!(matches!(
+            expval_paulis_inner(&data, 3, &[1, 2], &[0], &[one, one]),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            expval_paulis_inner(&data, 3, &[1], &[8], &[one]),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            expval_paulis_inner(&data, 64, &[1], &[0], &[one]),
+            Err(AccelerateError::Overflow(_))
+        ));
+    }
+}
@@ -0,0 +1,370 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The broadcasting of the parameter values and the observables of the pubs of the primitives.
+//!
+//! The parameter values and the observables of a pub are arrays whose shapes broadcast together
+//! as NumPy's do, and the pub has the broadcast shape.  Rather than broadcasting arrays of Python
+//! objects, each element of the pub is paired with the flat indices, in C order, of its parameter
+//! set and its observable, and the results of the flat elements are reshaped to the pub at the
+//! end.
+
+use hashbrown::HashMap;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyTuple;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::error::AccelerateError;
+
+/// The shape that `shapes` broadcast to, by NumPy's rules: the shapes are right-aligned, and each
+/// dimension must either be one or agree with the others.
+pub fn broadcast_shapes(shapes: &[&[usize]]) -> Result<Vec<usize>, AccelerateError> {
+    let ndim = shapes.iter().map(|shape| shape.len()).max().unwrap_or(0);
+    let mut out = vec![1; ndim];
+    for shape in shapes {
+        for (dim, out) in shape.iter().rev().zip(out.iter_mut().rev()) {
+            if *out == 1 {
+                *out = *dim;
+            } else if *dim != 1 && dim != out {
+                return Err(AccelerateError::Value(format!(
+                    "shapes {:?} are not broadcastable",
+                    shapes
+                )));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The flat index into an array of `shape` of each element, in C order, of its broadcast to
+/// `target`, which `shape` must broadcast to.
+pub fn broadcast_index_map(shape: &[usize], target: &[usize]) -> Vec<usize> {
+    let offset = target.len() - shape.len();
+    // The stride of each dimension of `target` in the flat array of `shape`, which is zero for the
+    // dimensions that are broadcast.
+    let mut strides = vec![0; target.len()];
+    let mut stride = 1;
+    for (axis, dim) in shape.iter().enumerate().rev() {
+        if *dim != 1 {
+            strides[axis + offset] = stride;
+        }
+        stride *= dim;
+    }
+    let size: usize = target.iter().product();
+    let mut out = Vec::with_capacity(size);
+    let mut index = vec![0; target.len()];
+    let mut flat = 0;
+    for _ in 0..size {
+        out.push(flat);
+        // Step the multi-index like an odometer, with the last axis fastest.
+        for axis in (0..target.len()).rev() {
+            index[axis] += 1;
+            flat += strides[axis];
+            if index[axis] < target[axis] {
+                break;
+            }
+            flat -= strides[axis] * index[axis];
+            index[axis] = 0;
+        }
+    }
+    out
+}
+
+/// The multi-index in an array of `shape` of the flat index `index`, in C order.
+pub fn unravel_index(mut index: usize, shape: &[usize]) -> Vec<usize> {
+    let mut out = vec![0; shape.len()];
+    for (axis, dim) in shape.iter().enumerate().rev() {
+        out[axis] = index % dim;
+        index /= dim;
+    }
+    out
+}
+
+/// The pairing of the parameter sets and the observables of a pub.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.pub_broadcast", frozen)
+)]
+pub struct PubBroadcast {
+    shape: Vec<usize>,
+    parameter_shape: Vec<usize>,
+    /// The flat index of the parameter set of each element of the pub, in C order.
+    parameter_indices: Vec<usize>,
+    /// The flat index of the observable of each element of the pub, in C order.
+    observable_indices: Vec<usize>,
+}
+
+impl PubBroadcast {
+    /// Broadcast parameter values of `parameter_shape` with observables of `observable_shape`.
+    pub fn new(
+        parameter_shape: &[usize],
+        observable_shape: &[usize],
+    ) -> Result<Self, AccelerateError> {
+        let shape = broadcast_shapes(&[parameter_shape, observable_shape]).map_err(|_| {
+            AccelerateError::Value(format!(
+                "The observables shape {:?} and the parameter values shape {:?} are not \
+                 broadcastable.",
+                observable_shape, parameter_shape
+            ))
+        })?;
+        Ok(PubBroadcast {
+            parameter_indices: broadcast_index_map(parameter_shape, &shape),
+            observable_indices: broadcast_index_map(observable_shape, &shape),
+            parameter_shape: parameter_shape.to_vec(),
+            shape,
+        })
+    }
+
+    /// The shape of the pub.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The flat index of the parameter set of each element of the pub.
+    pub fn parameter_indices(&self) -> &[usize] {
+        &self.parameter_indices
+    }
+
+    /// The flat index of the observable of each element of the pub.
+    pub fn observable_indices(&self) -> &[usize] {
+        &self.observable_indices
+    }
+
+    /// The parameter sets that the pub uses, in the order that they first appear, each with the
+    /// distinct observables that it is paired with, and the position in that list of the parameter
+    /// set of each element of the pub.
+    pub fn binding_groups(&self) -> (Vec<(usize, Vec<usize>)>, Vec<usize>) {
+        let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+        let mut group_of = HashMap::new();
+        let mut positions = Vec::with_capacity(self.parameter_indices.len());
+        for (param, obs) in self.parameter_indices.iter().zip(&self.observable_indices) {
+            let position = *group_of.entry(*param).or_insert_with(|| {
+                groups.push((*param, Vec::new()));
+                groups.len() - 1
+            });
+            let observables = &mut groups[position].1;
+            if !observables.contains(obs) {
+                observables.push(*obs);
+            }
+            positions.push(position);
+        }
+        (groups, positions)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PubBroadcast {
+    #[new]
+    fn py_new(parameter_shape: Vec<usize>, observable_shape: Vec<usize>) -> PyResult<Self> {
+        Ok(Self::new(&parameter_shape, &observable_shape)?)
+    }
+
+    /// The shape of the pub.
+    #[getter(shape)]
+    fn py_shape<'py>(&self, py: Python<'py>) -> Bound<'py, PyTuple> {
+        PyTuple::new_bound(py, &self.shape)
+    }
+
+    /// The number of elements of the pub.
+    #[getter]
+    fn size(&self) -> usize {
+        self.parameter_indices.len()
+    }
+
+    /// The flat index of the parameter set of each element of the pub, in C order.
+    #[getter(parameter_indices)]
+    fn py_parameter_indices<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        self.parameter_indices.clone().into_pyarray_bound(py)
+    }
+
+    /// The flat index of the observable of each element of the pub, in C order.
+    #[getter(observable_indices)]
+    fn py_observable_indices<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<usize>> {
+        self.observable_indices.clone().into_pyarray_bound(py)
+    }
+
+    /// The parameter sets that the pub uses, with the observables paired with each.
+    ///
+    /// Returns:
+    ///     (list[tuple[tuple[int, ...], list[int]]], list[int]): the index in the bindings array of
+    ///     each parameter set that the pub uses, in the order that they first appear, with the flat
+    ///     indices of the distinct observables paired with it; and the position in that list of the
+    ///     parameter set of each element of the pub, in C order.
+    #[pyo3(name = "binding_groups")]
+    fn py_binding_groups<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> (Vec<(Bound<'py, PyTuple>, Vec<usize>)>, Vec<usize>) {
+        let (groups, positions) = self.binding_groups();
+        let groups = groups
+            .into_iter()
+            .map(|(param, observables)| {
+                let index = unravel_index(param, &self.parameter_shape);
+                (PyTuple::new_bound(py, index), observables)
+            })
+            .collect();
+        (groups, positions)
+    }
+}
+
+/// Return the shape that some shapes broadcast to, as ``numpy.broadcast_shapes`` does.
+///
+/// Args:
+///     shapes (list[tuple[int, ...]]): the shapes.
+///
+/// Returns:
+///     tuple[int, ...]: the broadcast shape.
+///
+/// Raises:
+///     ValueError: if the shapes don't broadcast together.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "broadcast_shapes")]
+pub fn py_broadcast_shapes(py: Python, shapes: Vec<Vec<usize>>) -> PyResult<Py<PyTuple>> {
+    let shapes = shapes
+        .iter()
+        .map(|shape| shape.as_slice())
+        .collect::<Vec<_>>();
+    Ok(PyTuple::new_bound(py, broadcast_shapes(&shapes)?).unbind())
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn pub_broadcast(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_broadcast_shapes))?;
+    m.add_class::<PubBroadcast>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The flat index in an array of `shape` of the multi-index `index`, in C order.
+    fn ravel_index(index: &[usize], shape: &[usize]) -> usize {
+        index
+            .iter()
+            .zip(shape)
+            .fold(0, |acc, (index, dim)| acc * dim + index)
+    }
+
+    /// The index map of `shape` broadcast to `target`, found element by element.
+    fn reference_index_map(shape: &[usize], target: &[usize]) -> Vec<usize> {
+        let offset = target.len() - shape.len();
+        (0..target.iter().product())
+            .map(|flat| {
+                let index = unravel_index(flat, target);
+                let index = index[offset..]
+                    .iter()
+                    .zip(shape)
+                    .map(|(index, dim)| if *dim == 1 { 0 } else { *index })
+                    .collect::<Vec<_>>();
+                ravel_index(&index, shape)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_broadcast_shapes() {
+        assert_eq!(broadcast_shapes(&[]).unwrap(), Vec::<usize>::new());
+        assert_eq!(broadcast_shapes(&[&[], &[3]]).unwrap(), vec![3]);
+        assert_eq!(broadcast_shapes(&[&[2, 1], &[4]]).unwrap(), vec![2, 4]);
+        assert_eq!(
+            broadcast_shapes(&[&[5, 1, 3], &[4, 1], &[1]]).unwrap(),
+            vec![5, 4, 3]
+        );
+        assert_eq!(broadcast_shapes(&[&[0], &[1]]).unwrap(), vec![0]);
+        assert!(matches!(
+            broadcast_shapes(&[&[2, 3], &[3, 3]]),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(broadcast_shapes(&[&[0], &[3]]).is_err());
+    }
+
+    #[test]
+    fn test_broadcast_index_map() {
+        let target = [2, 3, 4];
+        for shape in [
+            &[][..],
+            &[4],
+            &[3, 1],
+            &[1, 3, 4],
+            &[2, 1, 4],
+            &[2, 3, 1],
+            &[2, 3, 4],
+        ] {
+            assert_eq!(
+                broadcast_index_map(shape, &target),
+                reference_index_map(shape, &target),
+                "{shape:?}"
+            );
+        }
+        assert_eq!(
+            broadcast_index_map(&[2, 3], &[2, 3]),
+            (0..6).collect::<Vec<_>>()
+        );
+        assert!(broadcast_index_map(&[1], &[0]).is_empty());
+    }
+
+    #[test]
+    fn test_unravel_index() {
+        let shape = [3, 1, 4];
+        for flat in 0..12 {
+            assert_eq!(ravel_index(&unravel_index(flat, &shape), &shape), flat);
+        }
+        assert_eq!(unravel_index(7, &shape), vec![1, 0, 3]);
+        assert_eq!(unravel_index(0, &[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_pub_broadcast() {
+        let broadcast = PubBroadcast::new(&[2, 1], &[3]).unwrap();
+        assert_eq!(broadcast.shape(), &[2, 3]);
+        assert_eq!(broadcast.parameter_indices(), &[0, 0, 0, 1, 1, 1]);
+        assert_eq!(broadcast.observable_indices(), &[0, 1, 2, 0, 1, 2]);
+        let (groups, positions) = broadcast.binding_groups();
+        assert_eq!(groups, vec![(0, vec![0, 1, 2]), (1, vec![0, 1, 2])]);
+        assert_eq!(positions, vec![0, 0, 0, 1, 1, 1]);
+        assert!(matches!(
+            PubBroadcast::new(&[2], &[3]),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_binding_groups_distinct() {
+        // The observables broadcast over parameter sets that are used more than once, and each
+        // group lists each of its observables once, in the order that they first appear.
+        let broadcast = PubBroadcast::new(&[3], &[2, 1]).unwrap();
+        assert_eq!(broadcast.parameter_indices(), &[0, 1, 2, 0, 1, 2]);
+        assert_eq!(broadcast.observable_indices(), &[0, 0, 0, 1, 1, 1]);
+        let (groups, positions) = broadcast.binding_groups();
+        assert_eq!(
+            groups,
+            vec![(0, vec![0, 1]), (1, vec![0, 1]), (2, vec![0, 1])]
+        );
+        assert_eq!(positions, vec![0, 1, 2, 0, 1, 2]);
+
+        let broadcast = PubBroadcast::new(&[], &[2, 2]).unwrap();
+        let (groups, positions) = broadcast.binding_groups();
+        assert_eq!(groups, vec![(0, vec![0, 1, 2, 3])]);
+        assert_eq!(positions, vec![0; 4]);
+    }
+}
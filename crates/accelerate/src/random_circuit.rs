@@ -0,0 +1,313 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Seeded random circuits of standard gates, for building benchmarking suites.
+//!
+//! A circuit is made of layers in which every qubit is acted on by exactly one gate, like those of
+//! `qiskit.circuit.random.random_circuit`, but the whole circuit is drawn in Rust and emitted as
+//! `CircuitData` without creating any Python-space instructions one by one.
+
+use std::f64::consts::TAU;
+
+use pyo3::prelude::*;
+use pyo3::wrap_pyfunction;
+use rand::prelude::*;
+
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::imports;
+use qiskit_circuit::intern_context::BitType;
+use qiskit_circuit::operations::StandardGate;
+
+use crate::error::AccelerateError;
+use crate::rng::PySeed;
+
+/// The gates drawn from by default: every standard gate that acts on qubits.
+const DEFAULT_GATES: [StandardGate; 36] = [
+    StandardGate::IGate,
+    StandardGate::HGate,
+    StandardGate::XGate,
+    StandardGate::YGate,
+    StandardGate::ZGate,
+    StandardGate::SGate,
+    StandardGate::SdgGate,
+    StandardGate::TGate,
+    StandardGate::TdgGate,
+    StandardGate::SXGate,
+    StandardGate::SXdgGate,
+    StandardGate::RXGate,
+    StandardGate::RYGate,
+    StandardGate::RZGate,
+    StandardGate::RGate,
+    StandardGate::PhaseGate,
+    StandardGate::U1Gate,
+    StandardGate::U2Gate,
+    StandardGate::U3Gate,
+    StandardGate::UGate,
+    StandardGate::CXGate,
+    StandardGate::CYGate,
+    StandardGate::CZGate,
+    StandardGate::CHGate,
+    StandardGate::CPhaseGate,
+    StandardGate::CRXGate,
+    StandardGate::CRYGate,
+    StandardGate::CRZGate,
+    StandardGate::SwapGate,
+    StandardGate::ISwapGate,
+    StandardGate::ECRGate,
+    StandardGate::RXXGate,
+    StandardGate::RYYGate,
+    StandardGate::RZZGate,
+    StandardGate::CCXGate,
+    StandardGate::CSwapGate,
+];
+
+/// A gate of a random circuit, with its parameters and qubits.
+pub type RandomInstruction = (StandardGate, Vec<f64>, Vec<BitType>);
+
+/// Draw the instructions of `depth` layers of gates on `num_qubits` qubits.
+///
+/// Each layer takes the qubits in a random order and covers them with gates from `gates`: each
+/// gate is a multi-qubit one with probability `multi_qubit_density`, if one fits on the qubits
+/// left in the layer, and a single-qubit one otherwise.  The parameters are uniform in `[0, 2π)`.
+pub fn random_instructions<R: Rng + ?Sized>(
+    num_qubits: u32,
+    depth: usize,
+    gates: &[StandardGate],
+    multi_qubit_density: f64,
+    rng: &mut R,
+) -> Result<Vec<RandomInstruction>, AccelerateError> {
+    if !(0. ..=1.).contains(&multi_qubit_density) {
+        return Err(AccelerateError::Value(format!(
+            "the density {multi_qubit_density} isn't a probability"
+        )));
+    }
+    if let Some(gate) = gates.iter().find(|gate| gate.num_qubits() == 0) {
+        return Err(AccelerateError::Value(format!(
+            "the gate '{}' acts on no qubits",
+            gate.name()
+        )));
+    }
+    let (single, multi): (Vec<StandardGate>, Vec<StandardGate>) =
+        gates.iter().partition(|gate| gate.num_qubits() == 1);
+    if single.is_empty() && num_qubits > 0 && depth > 0 {
+        return Err(AccelerateError::Value(
+            "the gates must include a single-qubit gate to fill the layers".to_string(),
+        ));
+    }
+    let mut qubits: Vec<BitType> = (0..num_qubits).collect();
+    let mut out = Vec::with_capacity(depth * num_qubits as usize);
+    for _ in 0..depth {
+        qubits.shuffle(rng);
+        let mut start = 0;
+        while start < qubits.len() {
+            let remaining = qubits.len() - start;
+            let multi_gate = if rng.gen::<f64>() < multi_qubit_density {
+                multi
+                    .iter()
+                    .filter(|gate| gate.num_qubits() as usize <= remaining)
+                    .choose(rng)
+                    .copied()
+            } else {
+                None
+            };
+            let gate = match multi_gate {
+                Some(gate) => gate,
+                None => *single.choose(rng).expect("checked to be non-empty above"),
+            };
+            let size = gate.num_qubits() as usize;
+            let params = (0..gate.num_params())
+                .map(|_| rng.gen_range(0.0..TAU))
+                .collect();
+            out.push((gate, params, qubits[start..start + size].to_vec()));
+            start += size;
+        }
+    }
+    Ok(out)
+}
+
+/// Generate a random circuit of standard gates.
+///
+/// Every qubit is acted on by one gate in each layer, and the multi-qubit gates are drawn with
+/// the given density.  If ``measure`` is set, one clbit per qubit is added, and every qubit is
+/// measured into its clbit at the end.
+///
+/// Args:
+///     num_qubits (int): the number of qubits.
+///     depth (int): the number of layers.
+///     gates (list[str] | None): the names of the standard gates to draw from, or ``None`` for all
+///         of them.
+///     two_qubit_density (float): the probability that a gate is a multi-qubit gate, where one
+///         fits on the qubits left in its layer.
+///     max_operands (int): gates on more qubits than this are left out of ``gates``.
+///     measure (bool): whether to measure every qubit at the end.
+///     seed (int | SeedSequence | None): the seed of the circuit.
+///
+/// Returns:
+///     CircuitData: the instructions of the circuit.
+///
+/// Raises:
+///     ValueError: if a gate is unknown, if no single-qubit gate is left to draw from, or if the
+///         density isn't a probability.
+#[pyfunction]
+#[pyo3(name = "random_circuit", signature = (
+    num_qubits,
+    depth,
+    gates=None,
+    two_qubit_density=0.5,
+    max_operands=2,
+    measure=false,
+    seed=None,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_random_circuit(
+    py: Python,
+    num_qubits: u32,
+    depth: usize,
+    gates: Option<Vec<String>>,
+    two_qubit_density: f64,
+    max_operands: u32,
+    measure: bool,
+    seed: Option<PySeed>,
+) -> PyResult<CircuitData> {
+    let gates = match gates {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                StandardGate::from_name(name).ok_or_else(|| {
+                    AccelerateError::Value(format!("unknown standard gate '{name}'"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => DEFAULT_GATES.to_vec(),
+    };
+    let gates: Vec<StandardGate> = gates
+        .into_iter()
+        .filter(|gate| gate.num_qubits() <= max_operands)
+        .collect();
+    let mut rng = PySeed::generator(seed);
+    let instructions = py.allow_threads(|| {
+        random_instructions(num_qubits, depth, &gates, two_qubit_density, &mut rng)
+    })?;
+    let mut data = CircuitData::from_standard_gates(py, num_qubits, instructions)?;
+    if measure {
+        let clbit_class = imports::CLBIT.get_bound(py)?;
+        for _ in 0..num_qubits {
+            data.add_clbit(py, &clbit_class.call0()?, true)?;
+        }
+        let measure = imports::MEASURE.get_bound(py)?.call0()?;
+        for qubit in 0..num_qubits {
+            data.push_instruction(measure.clone().unbind(), &[qubit], &[qubit])?;
+        }
+    }
+    Ok(data)
+}
+
+#[pymodule]
+pub fn random_circuit(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_random_circuit))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rng::generator;
+
+    /// Split `instructions` into their layers, checking each instruction is well formed and each
+    /// layer acts on every qubit once.
+    fn layers(instructions: &[RandomInstruction], num_qubits: u32) -> Vec<&[RandomInstruction]> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        let mut covered = Vec::new();
+        for (index, (gate, params, qubits)) in instructions.iter().enumerate() {
+            assert_eq!(qubits.len(), gate.num_qubits() as usize);
+            assert_eq!(params.len(), gate.num_params() as usize);
+            assert!(params.iter().all(|param| (0. ..TAU).contains(param)));
+            covered.extend_from_slice(qubits);
+            if covered.len() == num_qubits as usize {
+                covered.sort();
+                assert_eq!(covered, (0..num_qubits).collect::<Vec<_>>());
+                covered.clear();
+                out.push(&instructions[start..=index]);
+                start = index + 1;
+            }
+        }
+        assert!(covered.is_empty());
+        out
+    }
+
+    #[test]
+    fn test_layers() {
+        let instructions =
+            random_instructions(5, 4, &DEFAULT_GATES, 0.5, &mut generator(1)).unwrap();
+        assert_eq!(layers(&instructions, 5).len(), 4);
+        assert!(
+            random_instructions(0, 4, &DEFAULT_GATES, 0.5, &mut generator(1))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_density() {
+        let gates = [
+            StandardGate::HGate,
+            StandardGate::CXGate,
+            StandardGate::CCXGate,
+        ];
+        let single = random_instructions(4, 3, &gates, 0., &mut generator(2)).unwrap();
+        assert_eq!(layers(&single, 4).len(), 3);
+        assert!(single
+            .iter()
+            .all(|(gate, _, _)| *gate == StandardGate::HGate));
+        // With a density of one, a single-qubit gate is only drawn where no other gate fits.
+        let multi = random_instructions(4, 10, &gates, 1., &mut generator(3)).unwrap();
+        for layer in layers(&multi, 4) {
+            let singles = layer
+                .iter()
+                .filter(|(gate, _, _)| gate.num_qubits() == 1)
+                .count();
+            assert!(singles <= 1);
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        let draw = |seed| random_instructions(6, 5, &DEFAULT_GATES, 0.3, &mut generator(seed));
+        assert_eq!(draw(7).unwrap(), draw(7).unwrap());
+        assert_ne!(draw(7).unwrap(), draw(8).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut rng = generator(0);
+        for density in [-0.1, 1.5, f64::NAN] {
+            assert!(matches!(
+                random_instructions(2, 2, &DEFAULT_GATES, density, &mut rng),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+        assert!(matches!(
+            random_instructions(2, 2, &[StandardGate::GlobalPhaseGate], 0.5, &mut rng),
+            Err(AccelerateError::Value(_))
+        ));
+        let multi = [StandardGate::CXGate];
+        assert!(matches!(
+            random_instructions(2, 2, &multi, 0.5, &mut rng),
+            Err(AccelerateError::Value(_))
+        ));
+        // There's nothing to fill without qubits or layers.
+        assert!(random_instructions(0, 2, &multi, 0.5, &mut rng).is_ok());
+        assert!(random_instructions(2, 0, &multi, 0.5, &mut rng).is_ok());
+    }
+}
@@ -0,0 +1,500 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Seeded generators of random quantum-information objects: Haar-random unitaries and
+//! statevectors, random density matrices and random Hermitian operators.
+//!
+//! Every generator takes the random-number generator explicitly.  The batch generators draw one
+//! seed per item from a [SeedSequence] before doing any work, so their output depends only on the
+//! seed and not on how the work is split between threads.
+
+use std::str::FromStr;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArray3};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use faer_ext::{IntoFaerComplex, IntoNdarrayComplex};
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use rand::prelude::*;
+use rand_distr::{Normal, StandardNormal};
+use rand_pcg::Pcg64Mcg;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+use crate::operator::{adjoint_inner, matmul_inner};
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+use crate::rng::SeedSequence;
+use crate::threading;
+
+/// The metric a random density matrix is sampled from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DensityMatrixMethod {
+    HilbertSchmidt,
+    Bures,
+}
+
+impl FromStr for DensityMatrixMethod {
+    type Err = AccelerateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Hilbert-Schmidt" => Ok(Self::HilbertSchmidt),
+            "Bures" => Ok(Self::Bures),
+            _ => Err(AccelerateError::Qiskit(format!(
+                "Error: unrecognized method {s}"
+            ))),
+        }
+    }
+}
+
+/// A complex matrix with independent standard-normal real and imaginary parts.
+fn ginibre_matrix<R: Rng + ?Sized>(nrows: usize, ncols: usize, rng: &mut R) -> Array2<Complex64> {
+    Array2::from_shape_simple_fn((nrows, ncols), || {
+        Complex64::new(rng.sample(StandardNormal), rng.sample(StandardNormal))
+    })
+}
+
+/// A unitary of dimension `dim` drawn from the Haar measure.
+///
+/// This is the QR decomposition of a Ginibre matrix, with the phases of the diagonal of `R`
+/// absorbed into `Q` so that the decomposition is unique and the distribution is exactly Haar.
+pub fn random_unitary_inner<R: Rng + ?Sized>(dim: usize, rng: &mut R) -> Array2<Complex64> {
+    let ginibre = ginibre_matrix(dim, dim, rng);
+    let qr = ginibre.view().into_faer_complex().qr();
+    let q = qr.compute_q();
+    let r = qr.compute_r();
+    let mut out = q.as_ref().into_ndarray_complex().to_owned();
+    let r = r.as_ref().into_ndarray_complex();
+    for (mut col, r_ii) in out.columns_mut().into_iter().zip(r.diag()) {
+        let norm = r_ii.norm();
+        if norm > 0. {
+            let phase = r_ii / norm;
+            col.mapv_inplace(|x| x * phase);
+        }
+    }
+    out
+}
+
+/// A special unitary of dimension `dim` drawn from the Haar measure, which is a Haar-random
+/// unitary with the `dim`-th root of its determinant divided out.
+pub fn random_special_unitary_inner<R: Rng + ?Sized>(dim: usize, rng: &mut R) -> Array2<Complex64> {
+    let mut out = random_unitary_inner(dim, rng);
+    if dim > 0 {
+        let det = out.view().into_faer_complex().determinant();
+        let phase = Complex64::from_polar(1., -det.im.atan2(det.re) / dim as f64);
+        out.mapv_inplace(|x| x * phase);
+    }
+    out
+}
+
+/// A pure state of dimension `dim` drawn from the Haar measure.
+pub fn random_statevector_inner<R: Rng + ?Sized>(dim: usize, rng: &mut R) -> Array1<Complex64> {
+    let mut out = Array1::from_shape_simple_fn(dim, || {
+        Complex64::new(rng.sample(StandardNormal), rng.sample(StandardNormal))
+    });
+    let norm = out.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+    out.mapv_inplace(|x| x / norm);
+    out
+}
+
+/// A density matrix of dimension `dim` and rank at most `rank`, drawn from the given metric.
+pub fn random_density_matrix_inner<R: Rng + ?Sized>(
+    dim: usize,
+    rank: usize,
+    method: DensityMatrixMethod,
+    rng: &mut R,
+) -> Result<Array2<Complex64>, AccelerateError> {
+    let mut mat = match method {
+        DensityMatrixMethod::HilbertSchmidt => ginibre_matrix(dim, rank, rng),
+        DensityMatrixMethod::Bures => {
+            let mut shift = random_unitary_inner(dim, rng);
+            shift.diag_mut().mapv_inplace(|x| x + 1.);
+            matmul_inner(shift.view(), ginibre_matrix(dim, rank, rng).view())?
+        }
+    };
+    mat = matmul_inner(mat.view(), adjoint_inner(mat.view()).view())?;
+    let trace = mat.diag().sum();
+    mat.mapv_inplace(|x| x / trace);
+    Ok(mat)
+}
+
+/// A Hermitian operator of dimension `dim` from the Gaussian unitary ensemble: the diagonal is
+/// drawn from `N(0, 1)` (or is zero, if `traceless`), and the real and imaginary parts of the
+/// off-diagonal elements from `N(0, 1/4)`.
+pub fn random_hermitian_inner<R: Rng + ?Sized>(
+    dim: usize,
+    traceless: bool,
+    rng: &mut R,
+) -> Array2<Complex64> {
+    let off_diagonal = Normal::new(0., 0.5).unwrap();
+    let mut out = Array2::<Complex64>::zeros((dim, dim));
+    for i in 0..dim {
+        if !traceless {
+            out[[i, i]] = Complex64::new(rng.sample(StandardNormal), 0.);
+        }
+        for j in 0..i {
+            let value = Complex64::new(rng.sample(off_diagonal), rng.sample(off_diagonal));
+            out[[i, j]] = value;
+            out[[j, i]] = value.conj();
+        }
+    }
+    out
+}
+
+/// Run `generate` once per item of a batch of `num` items, each with its own generator seeded by
+/// the next seed of `seeds`, and stack the results.
+fn batch<D, F>(num: usize, seeds: &mut SeedSequence, generate: F) -> Vec<Array<Complex64, D>>
+where
+    D: Dimension,
+    F: Fn(&mut Pcg64Mcg) -> Array<Complex64, D> + Sync + Send,
+{
+    let seeds = seeds.seeds(num);
+    let run = |seed: u64| generate(&mut Pcg64Mcg::seed_from_u64(seed));
+    if threading::use_multiple_threads() {
+        threading::install(|| seeds.into_par_iter().map(run).collect())
+    } else {
+        seeds.into_iter().map(run).collect()
+    }
+}
+
+/// `num` Haar-random unitaries of dimension `dim`, stacked along the first axis.
+pub fn random_unitaries_inner(
+    dim: usize,
+    num: usize,
+    seeds: &mut SeedSequence,
+) -> Array3<Complex64> {
+    let items = batch(num, seeds, |rng| random_unitary_inner(dim, rng));
+    let views = items.iter().map(|x| x.view()).collect::<Vec<_>>();
+    if views.is_empty() {
+        return Array3::zeros((0, dim, dim));
+    }
+    ndarray::stack(Axis(0), &views).unwrap()
+}
+
+/// `num` Haar-random two-qubit special unitaries, stacked along the first axis.
+pub fn random_two_qubit_unitaries_inner(num: usize, seeds: &mut SeedSequence) -> Array3<Complex64> {
+    let items = batch(num, seeds, |rng| random_special_unitary_inner(4, rng));
+    let views = items.iter().map(|x| x.view()).collect::<Vec<_>>();
+    if views.is_empty() {
+        return Array3::zeros((0, 4, 4));
+    }
+    ndarray::stack(Axis(0), &views).unwrap()
+}
+
+/// `num` Haar-random statevectors of dimension `dim`, stacked along the first axis.
+pub fn random_statevectors_inner(
+    dim: usize,
+    num: usize,
+    seeds: &mut SeedSequence,
+) -> Array2<Complex64> {
+    let items = batch(num, seeds, |rng| random_statevector_inner(dim, rng));
+    let views = items.iter().map(|x| x.view()).collect::<Vec<_>>();
+    if views.is_empty() {
+        return Array2::zeros((0, dim));
+    }
+    ndarray::stack(Axis(0), &views).unwrap()
+}
+
+/// Return a Haar-random unitary matrix.
+///
+/// Args:
+///     dim (int): the dimension of the unitary.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw it from.
+///
+/// Returns:
+///     ndarray: the unitary matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, seed=None))]
+pub fn random_unitary(py: Python, dim: usize, seed: Option<PySeed>) -> Py<PyArray2<Complex64>> {
+    random_unitary_inner(dim, &mut PySeed::generator(seed))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return a stack of Haar-random unitary matrices.
+///
+/// Args:
+///     dim (int): the dimension of the unitaries.
+///     num (int): the number of unitaries.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw the seed of each item from.
+///
+/// Returns:
+///     ndarray: an array of shape ``(num, dim, dim)``.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, num, seed=None))]
+pub fn random_unitaries(
+    py: Python,
+    dim: usize,
+    num: usize,
+    seed: Option<PySeed>,
+) -> Py<PyArray3<Complex64>> {
+    PySeed::with_sequence(seed, |seeds| random_unitaries_inner(dim, num, seeds))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return a stack of Haar-random two-qubit special unitaries, as for the benchmarking of
+/// two-qubit synthesis.
+///
+/// Args:
+///     num (int): the number of unitaries.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw the seed of each item from.
+///
+/// Returns:
+///     ndarray: an array of shape ``(num, 4, 4)`` of matrices with unit determinant.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num, seed=None))]
+pub fn random_two_qubit_unitaries(
+    py: Python,
+    num: usize,
+    seed: Option<PySeed>,
+) -> Py<PyArray3<Complex64>> {
+    PySeed::with_sequence(seed, |seeds| random_two_qubit_unitaries_inner(num, seeds))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return a Haar-random statevector.
+///
+/// Args:
+///     dim (int): the dimension of the state.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw it from.
+///
+/// Returns:
+///     ndarray: the statevector.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, seed=None))]
+pub fn random_statevector(py: Python, dim: usize, seed: Option<PySeed>) -> Py<PyArray1<Complex64>> {
+    random_statevector_inner(dim, &mut PySeed::generator(seed))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return a stack of Haar-random statevectors.
+///
+/// Args:
+///     dim (int): the dimension of the states.
+///     num (int): the number of states.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw the seed of each item from.
+///
+/// Returns:
+///     ndarray: an array of shape ``(num, dim)``.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, num, seed=None))]
+pub fn random_statevectors(
+    py: Python,
+    dim: usize,
+    num: usize,
+    seed: Option<PySeed>,
+) -> Py<PyArray2<Complex64>> {
+    PySeed::with_sequence(seed, |seeds| random_statevectors_inner(dim, num, seeds))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+/// Return a random density matrix.
+///
+/// Args:
+///     dim (int): the dimension of the state.
+///     rank (int | None): the maximum rank of the state.  Defaults to full rank.
+///     method (str): the metric to sample from, either ``"Hilbert-Schmidt"`` or ``"Bures"``.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw it from.
+///
+/// Returns:
+///     ndarray: the density matrix.
+///
+/// Raises:
+///     QiskitError: if the method is not valid.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, rank=None, method="Hilbert-Schmidt", seed=None))]
+pub fn random_density_matrix(
+    py: Python,
+    dim: usize,
+    rank: Option<usize>,
+    method: &str,
+    seed: Option<PySeed>,
+) -> PyResult<Py<PyArray2<Complex64>>> {
+    let method = method.parse::<DensityMatrixMethod>()?;
+    let rho = random_density_matrix_inner(
+        dim,
+        rank.unwrap_or(dim),
+        method,
+        &mut PySeed::generator(seed),
+    )?;
+    Ok(rho.into_pyarray_bound(py).unbind())
+}
+
+/// Return a random Hermitian matrix from the Gaussian unitary ensemble.
+///
+/// Args:
+///     dim (int): the dimension of the operator.
+///     traceless (bool): whether to make the diagonal zero.
+///     seed (int | SeedSequence | None): the seed of up to 128 bits for the random-number
+///         generator, or a sequence to draw it from.
+///
+/// Returns:
+///     ndarray: the Hermitian matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (dim, traceless=false, seed=None))]
+pub fn random_hermitian(
+    py: Python,
+    dim: usize,
+    traceless: bool,
+    seed: Option<PySeed>,
+) -> Py<PyArray2<Complex64>> {
+    random_hermitian_inner(dim, traceless, &mut PySeed::generator(seed))
+        .into_pyarray_bound(py)
+        .unbind()
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn random_quantum_info(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(random_unitary))?;
+    m.add_wrapped(wrap_pyfunction!(random_unitaries))?;
+    m.add_wrapped(wrap_pyfunction!(random_two_qubit_unitaries))?;
+    m.add_wrapped(wrap_pyfunction!(random_statevector))?;
+    m.add_wrapped(wrap_pyfunction!(random_statevectors))?;
+    m.add_wrapped(wrap_pyfunction!(random_density_matrix))?;
+    m.add_wrapped(wrap_pyfunction!(random_hermitian))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::generator;
+
+    fn assert_identity(mat: &Array2<Complex64>) {
+        for ((i, j), value) in mat.indexed_iter() {
+            let expected = if i == j { 1. } else { 0. };
+            assert!((*value - expected).norm() < 1e-10, "{}", mat);
+        }
+    }
+
+    fn gram(mat: &Array2<Complex64>) -> Array2<Complex64> {
+        matmul_inner(adjoint_inner(mat.view()).view(), mat.view()).unwrap()
+    }
+
+    #[test]
+    fn test_random_unitary_is_unitary() {
+        let mut rng = generator(1234);
+        for dim in [1, 2, 5, 8] {
+            assert_identity(&gram(&random_unitary_inner(dim, &mut rng)));
+        }
+    }
+
+    #[test]
+    fn test_random_special_unitary_has_unit_determinant() {
+        let mut rng = generator(99);
+        let unitary = random_special_unitary_inner(4, &mut rng);
+        assert_identity(&gram(&unitary));
+        let det = unitary.view().into_faer_complex().determinant();
+        assert!((det.re - 1.).abs() < 1e-10 && det.im.abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_random_statevector_is_normalized() {
+        let state = random_statevector_inner(16, &mut generator(7));
+        let norm = state.iter().map(|x| x.norm_sqr()).sum::<f64>();
+        assert!((norm - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_random_density_matrix_is_a_state() {
+        for method in [
+            DensityMatrixMethod::HilbertSchmidt,
+            DensityMatrixMethod::Bures,
+        ] {
+            let rho = random_density_matrix_inner(6, 2, method, &mut generator(11)).unwrap();
+            let trace = rho.diag().sum();
+            assert!((trace.re - 1.).abs() < 1e-12 && trace.im.abs() < 1e-12);
+            let adjoint = adjoint_inner(rho.view());
+            assert!(rho
+                .iter()
+                .zip(&adjoint)
+                .all(|(a, b)| (a - b).norm() < 1e-12));
+            // The rank is at most 2, so the 3x3 minors of the leading block vanish.
+            let minor = rho.slice(s![..3, ..3]).into_faer_complex().determinant();
+            assert!(minor.norm() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_random_hermitian() {
+        let mat = random_hermitian_inner(5, true, &mut generator(3));
+        let adjoint = adjoint_inner(mat.view());
+        assert!(mat
+            .iter()
+            .zip(&adjoint)
+            .all(|(a, b)| (a - b).norm() < 1e-15));
+        assert!(mat.diag().iter().all(|x| *x == Complex64::new(0., 0.)));
+    }
+
+    #[test]
+    fn test_batches_are_reproducible() {
+        let first = random_unitaries_inner(4, 3, &mut SeedSequence::new(Some(5)));
+        let second = random_unitaries_inner(4, 3, &mut SeedSequence::new(Some(5)));
+        assert_eq!(first, second);
+        assert_eq!(first.dim(), (3, 4, 4));
+        // Each item is the unitary of the next seed of the sequence.
+        let mut seeds = SeedSequence::new(Some(5));
+        seeds.next_seed();
+        let expected = random_unitary_inner(4, &mut Pcg64Mcg::seed_from_u64(seeds.next_seed()));
+        assert_eq!(first.index_axis(Axis(0), 1), expected);
+        assert_eq!(random_statevectors_inner(2, 0, &mut seeds).dim(), (0, 2));
+    }
+
+    #[test]
+    fn test_random_two_qubit_unitaries() {
+        let batch = random_two_qubit_unitaries_inner(3, &mut SeedSequence::new(Some(8)));
+        assert_eq!(batch.dim(), (3, 4, 4));
+        for unitary in batch.outer_iter() {
+            let unitary = unitary.to_owned();
+            assert_identity(&gram(&unitary));
+            let det = unitary.view().into_faer_complex().determinant();
+            assert!((det.re - 1.).abs() < 1e-10 && det.im.abs() < 1e-10);
+        }
+        assert_eq!(
+            batch,
+            random_two_qubit_unitaries_inner(3, &mut SeedSequence::new(Some(8)))
+        );
+        assert_eq!(
+            random_two_qubit_unitaries_inner(0, &mut SeedSequence::new(Some(8))).dim(),
+            (0, 4, 4)
+        );
+    }
+
+    #[test]
+    fn test_unknown_density_matrix_method() {
+        assert!("Frobenius".parse::<DensityMatrixMethod>().is_err());
+    }
+}
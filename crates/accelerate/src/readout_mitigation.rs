@@ -0,0 +1,759 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Readout-error mitigation for uncorrelated errors on each qubit.
+//!
+//! Each qubit has a 2x2 assignment matrix `A`, whose entry `A[m][p]` is the probability of
+//! measuring `m` when `p` was prepared, and mitigation undoes the tensor product of the matrices
+//! of the measured qubits.  Outcomes are packed into words, with the bit of the `j`th mitigated
+//! qubit at bit `j`.
+//!
+//! The mitigated distribution has an entry for every one of the `2^n` outcomes, so beyond a few
+//! tens of qubits only its entries on the observed outcomes are computed, each in `O(n)` per
+//! observed outcome.  Expectation values of product observables need no such restriction, since
+//! the observable can be pulled back through the inverse matrices one qubit at a time.
+
+use hashbrown::HashMap;
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use indexmap::IndexMap;
+#[cfg(feature = "python")]
+use ndarray::Array3;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray3, PyReadonlyArray3};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::error::AccelerateError;
+use crate::threading::{self, ParallelKernel};
+
+/// A 2x2 matrix, indexed by row and then column.
+pub type Matrix2 = [[f64; 2]; 2];
+
+/// The `j`th bit of the packed `outcome`.
+#[inline]
+fn bit(outcome: &[u64], j: usize) -> usize {
+    ((outcome[j / 64] >> (j % 64)) & 1) as usize
+}
+
+/// Parse the bits at `positions` of `outcome`, a binary string with bit 0 last, into words.
+pub fn parse_outcome(outcome: &str, positions: &[usize]) -> Result<Vec<u64>, AccelerateError> {
+    let bits: Vec<u8> = outcome
+        .bytes()
+        .filter(|bit| *bit != b' ' && *bit != b'_')
+        .collect();
+    let mut out = vec![0u64; (positions.len() + 63) / 64];
+    for (j, position) in positions.iter().enumerate() {
+        let index = bits.len().checked_sub(1 + position).ok_or_else(|| {
+            AccelerateError::Index(format!("the outcome '{}' has no bit {}", outcome, position))
+        })?;
+        match bits[index] {
+            b'0' => (),
+            b'1' => out[j / 64] |= 1 << (j % 64),
+            _ => {
+                return Err(AccelerateError::Value(format!(
+                    "'{}' isn't a binary outcome",
+                    outcome
+                )))
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The binary string of the `num_bits` bits of the packed `outcome`, with bit 0 last.
+#[cfg(feature = "python")]
+fn outcome_label(outcome: &[u64], num_bits: usize) -> String {
+    (0..num_bits)
+        .rev()
+        .map(|j| if bit(outcome, j) == 1 { '1' } else { '0' })
+        .collect()
+}
+
+/// The assignment matrix of each of `num_qubits` qubits, from the counts of calibration circuits
+/// that each prepare a basis state, given as a binary string with qubit 0 last.
+pub fn assignment_matrices(
+    num_qubits: usize,
+    calibrations: &[(String, HashMap<String, u64>)],
+) -> Result<Vec<Matrix2>, AccelerateError> {
+    let positions: Vec<usize> = (0..num_qubits).collect();
+    // The number of shots of each qubit, by prepared and then measured state.
+    let mut tallies = vec![[[0u64; 2]; 2]; num_qubits];
+    for (prepared, counts) in calibrations {
+        let prepared = parse_outcome(prepared, &positions)?;
+        for (outcome, freq) in counts {
+            let measured = parse_outcome(outcome, &positions)?;
+            for (qubit, tally) in tallies.iter_mut().enumerate() {
+                tally[bit(&prepared, qubit)][bit(&measured, qubit)] += freq;
+            }
+        }
+    }
+    tallies
+        .iter()
+        .enumerate()
+        .map(|(qubit, tally)| {
+            let mut out = [[0.; 2]; 2];
+            for (prepared, measured) in tally.iter().enumerate() {
+                let shots = measured[0] + measured[1];
+                if shots == 0 {
+                    return Err(AccelerateError::Value(format!(
+                        "qubit {} is never prepared in |{}>",
+                        qubit, prepared
+                    )));
+                }
+                out[0][prepared] = measured[0] as f64 / shots as f64;
+                out[1][prepared] = measured[1] as f64 / shots as f64;
+            }
+            Ok(out)
+        })
+        .collect()
+}
+
+/// The inverse of `matrix`, or its pseudo-inverse if it's singular.
+pub fn inverse(matrix: &Matrix2) -> Matrix2 {
+    let [[a, b], [c, d]] = *matrix;
+    let det = a * d - b * c;
+    if det != 0. {
+        return [[d / det, -b / det], [-c / det, a / det]];
+    }
+    // A singular 2x2 matrix has rank at most one, and then its pseudo-inverse is its transpose
+    // divided by its squared norm.
+    let norm = a * a + b * b + c * c + d * d;
+    if norm == 0. {
+        [[0.; 2]; 2]
+    } else {
+        [[a / norm, c / norm], [b / norm, d / norm]]
+    }
+}
+
+/// The weight of `from` going to `to` under the tensor product of `matrices`.
+#[inline]
+fn weight(matrices: &[Matrix2], to: &[u64], from: &[u64]) -> f64 {
+    matrices
+        .iter()
+        .enumerate()
+        .map(|(j, matrix)| matrix[bit(to, j)][bit(from, j)])
+        .product()
+}
+
+/// `op` of each index up to `len`, in parallel if `parallel`.
+fn map_indices<F>(len: usize, parallel: bool, op: F) -> Vec<f64>
+where
+    F: Fn(usize) -> f64 + Send + Sync,
+{
+    if parallel {
+        (0..len).into_par_iter().map(op).collect()
+    } else {
+        (0..len).map(op).collect()
+    }
+}
+
+/// The distinct outcomes of some counts, in the order they were first seen, with their
+/// probabilities.
+#[derive(Clone, Debug)]
+pub struct Observed {
+    /// The number of bits of each outcome.
+    pub num_bits: usize,
+    pub outcomes: Vec<Vec<u64>>,
+    pub probs: Vec<f64>,
+    pub shots: u64,
+}
+
+impl Observed {
+    /// The distribution of the bits at `positions` of the outcomes of `counts`.
+    pub fn from_counts<'a>(
+        counts: impl IntoIterator<Item = (&'a str, u64)>,
+        positions: &[usize],
+    ) -> Result<Self, AccelerateError> {
+        let mut index: HashMap<Vec<u64>, usize> = HashMap::new();
+        let mut outcomes = Vec::new();
+        let mut freqs: Vec<u64> = Vec::new();
+        let mut shots = 0;
+        for (outcome, freq) in counts {
+            let outcome = parse_outcome(outcome, positions)?;
+            shots += freq;
+            match index.get(&outcome) {
+                Some(&i) => freqs[i] += freq,
+                None => {
+                    index.insert(outcome.clone(), outcomes.len());
+                    outcomes.push(outcome);
+                    freqs.push(freq);
+                }
+            }
+        }
+        if shots == 0 {
+            return Err(AccelerateError::Value(
+                "the counts have no shots".to_string(),
+            ));
+        }
+        Ok(Observed {
+            num_bits: positions.len(),
+            outcomes,
+            probs: freqs
+                .into_iter()
+                .map(|freq| freq as f64 / shots as f64)
+                .collect(),
+            shots,
+        })
+    }
+
+    /// The probability of every outcome, indexed by the outcome.
+    pub fn to_dense(&self) -> Result<Vec<f64>, AccelerateError> {
+        if self.num_bits >= usize::BITS as usize - 1 {
+            return Err(AccelerateError::Overflow(format!(
+                "a dense distribution of {} bits is too large",
+                self.num_bits
+            )));
+        }
+        let mut out = vec![0.; 1 << self.num_bits];
+        for (outcome, prob) in self.outcomes.iter().zip(&self.probs) {
+            out[outcome.first().copied().unwrap_or(0) as usize] = *prob;
+        }
+        Ok(out)
+    }
+}
+
+/// The mitigator of uncorrelated readout errors, from the assignment matrix of each qubit.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.readout_mitigation", frozen)
+)]
+pub struct TensoredMitigator {
+    assignments: Vec<Matrix2>,
+    inverses: Vec<Matrix2>,
+}
+
+impl TensoredMitigator {
+    pub fn new(assignments: Vec<Matrix2>) -> Self {
+        let inverses = assignments.iter().map(inverse).collect();
+        TensoredMitigator {
+            assignments,
+            inverses,
+        }
+    }
+
+    /// The number of qubits with an assignment matrix.
+    pub fn num_qubits(&self) -> usize {
+        self.assignments.len()
+    }
+
+    /// The matrix of each of `qubits`, the inverse one if `inverse`.
+    fn matrices(&self, qubits: &[usize], inverse: bool) -> Result<Vec<Matrix2>, AccelerateError> {
+        let matrices = if inverse {
+            &self.inverses
+        } else {
+            &self.assignments
+        };
+        qubits
+            .iter()
+            .map(|qubit| {
+                matrices.get(*qubit).copied().ok_or_else(|| {
+                    AccelerateError::Index(format!("the mitigator has no qubit {}", qubit))
+                })
+            })
+            .collect()
+    }
+
+    /// Mitigate, in place, the dense distribution `probs` whose `j`th bit is measured on the
+    /// `j`th of `qubits`.
+    pub fn apply_dense(&self, qubits: &[usize], probs: &mut [f64]) -> Result<(), AccelerateError> {
+        if qubits.len() >= usize::BITS as usize || probs.len() != 1 << qubits.len() {
+            return Err(AccelerateError::Value(format!(
+                "a distribution on {} qubits can't have {} entries",
+                qubits.len(),
+                probs.len()
+            )));
+        }
+        let inverses = self.matrices(qubits, true)?;
+        let parallel = threading::run_in_parallel(ParallelKernel::ReadoutMitigation, probs.len());
+        for (j, inverse) in inverses.iter().enumerate() {
+            let update = |(zero, one): (&mut f64, &mut f64)| {
+                let (p0, p1) = (*zero, *one);
+                *zero = inverse[0][0] * p0 + inverse[0][1] * p1;
+                *one = inverse[1][0] * p0 + inverse[1][1] * p1;
+            };
+            // Each chunk is a block of outcomes with bit `j` clear, and then the same outcomes
+            // with it set.
+            if parallel {
+                threading::install(|| {
+                    probs.par_chunks_mut(2 << j).for_each(|chunk| {
+                        let (zeros, ones) = chunk.split_at_mut(1 << j);
+                        zeros.par_iter_mut().zip(ones).for_each(update);
+                    })
+                });
+            } else {
+                probs.chunks_mut(2 << j).for_each(|chunk| {
+                    let (zeros, ones) = chunk.split_at_mut(1 << j);
+                    zeros.iter_mut().zip(ones).for_each(update);
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The mitigated quasi-probability of each outcome of `observed`, whose `j`th bit is
+    /// measured on the `j`th of `qubits`.
+    pub fn quasi_probabilities(
+        &self,
+        qubits: &[usize],
+        observed: &Observed,
+    ) -> Result<Vec<f64>, AccelerateError> {
+        let inverses = self.matrices(qubits, true)?;
+        let len = observed.outcomes.len();
+        let quasi = |to: usize| -> f64 {
+            observed
+                .outcomes
+                .iter()
+                .zip(&observed.probs)
+                .map(|(from, prob)| weight(&inverses, &observed.outcomes[to], from) * prob)
+                .sum()
+        };
+        if threading::run_in_parallel(ParallelKernel::ReadoutMitigation, len.saturating_mul(len)) {
+            Ok(threading::install(|| map_indices(len, true, quasi)))
+        } else {
+            Ok(map_indices(len, false, quasi))
+        }
+    }
+
+    /// The probability of each outcome of `observed`, whose `j`th bit is measured on the `j`th
+    /// of `qubits`, by iterative Bayesian unfolding restricted to the observed outcomes.
+    ///
+    /// The iterations start from the observed distribution, and stop after `max_iterations` or
+    /// once no probability changes by more than `tolerance`.
+    pub fn unfold(
+        &self,
+        qubits: &[usize],
+        observed: &Observed,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> Result<Vec<f64>, AccelerateError> {
+        let assignments = self.matrices(qubits, false)?;
+        let outcomes = &observed.outcomes;
+        let len = outcomes.len();
+        let parallel =
+            threading::run_in_parallel(ParallelKernel::ReadoutMitigation, len.saturating_mul(len));
+        let run = || {
+            let mut estimate = observed.probs.clone();
+            for _ in 0..max_iterations {
+                // The probability of observing each outcome if the estimate were the truth.
+                let predicted = map_indices(len, parallel, |y| {
+                    (0..len)
+                        .map(|x| weight(&assignments, &outcomes[y], &outcomes[x]) * estimate[x])
+                        .sum()
+                });
+                let next = map_indices(len, parallel, |x| {
+                    let posterior: f64 = (0..len)
+                        .filter(|y| predicted[*y] > 0.)
+                        .map(|y| {
+                            observed.probs[y] * weight(&assignments, &outcomes[y], &outcomes[x])
+                                / predicted[y]
+                        })
+                        .sum();
+                    estimate[x] * posterior
+                });
+                let change = next
+                    .iter()
+                    .zip(&estimate)
+                    .map(|(a, b)| (a - b).abs())
+                    .fold(0., f64::max);
+                estimate = next;
+                if change <= tolerance {
+                    break;
+                }
+            }
+            estimate
+        };
+        if parallel {
+            Ok(threading::install(run))
+        } else {
+            Ok(run())
+        }
+    }
+
+    /// The mitigated expectation value, from `observed`, of the diagonal observable that's the
+    /// tensor product of `factors`, the diagonal of the `j`th bit on the `j`th of `qubits`.
+    pub fn expectation_value(
+        &self,
+        qubits: &[usize],
+        factors: &[[f64; 2]],
+        observed: &Observed,
+    ) -> Result<f64, AccelerateError> {
+        if factors.len() != qubits.len() {
+            return Err(AccelerateError::Value(format!(
+                "an observable on {} qubits can't be measured on {} qubits",
+                factors.len(),
+                qubits.len()
+            )));
+        }
+        // The observable pulled back through the transposed inverses, one qubit at a time.
+        let coeffs: Vec<[f64; 2]> = self
+            .matrices(qubits, true)?
+            .iter()
+            .zip(factors)
+            .map(|(inverse, factor)| {
+                [
+                    inverse[0][0] * factor[0] + inverse[1][0] * factor[1],
+                    inverse[0][1] * factor[0] + inverse[1][1] * factor[1],
+                ]
+            })
+            .collect();
+        Ok(observed
+            .outcomes
+            .iter()
+            .zip(&observed.probs)
+            .map(|(outcome, prob)| {
+                prob * coeffs
+                    .iter()
+                    .enumerate()
+                    .map(|(j, coeff)| coeff[bit(outcome, j)])
+                    .product::<f64>()
+            })
+            .sum())
+    }
+}
+
+#[cfg(feature = "python")]
+fn matrices_to_array(matrices: &[Matrix2]) -> Array3<f64> {
+    Array3::from_shape_fn((matrices.len(), 2, 2), |(qubit, row, col)| {
+        matrices[qubit][row][col]
+    })
+}
+
+#[cfg(feature = "python")]
+fn parse_counts(
+    counts: &HashMap<String, u64>,
+    positions: &[usize],
+) -> Result<Observed, AccelerateError> {
+    Observed::from_counts(
+        counts
+            .iter()
+            .map(|(outcome, freq)| (outcome.as_str(), *freq)),
+        positions,
+    )
+}
+
+#[cfg(feature = "python")]
+fn labelled(observed: &Observed, values: Vec<f64>) -> IndexMap<String, f64> {
+    observed
+        .outcomes
+        .iter()
+        .map(|outcome| outcome_label(outcome, observed.num_bits))
+        .zip(values)
+        .collect()
+}
+
+/// In the methods of the mitigator, ``counts`` are counts of binary outcomes, ``qubits`` is the
+/// index in the mitigator of the qubit of each mitigated bit, and ``positions`` is the position
+/// of each of those bits in the outcomes, with bit 0 last.  The methods return their result with
+/// the number of shots of the counts.
+#[cfg(feature = "python")]
+#[pymethods]
+impl TensoredMitigator {
+    #[new]
+    fn py_new(assignments: PyReadonlyArray3<f64>) -> PyResult<Self> {
+        let assignments = assignments.as_array();
+        if assignments.shape()[1..] != [2, 2] {
+            return Err(
+                AccelerateError::Value("the assignment matrices must be 2x2".to_string()).into(),
+            );
+        }
+        Ok(Self::new(
+            assignments
+                .outer_iter()
+                .map(|m| [[m[[0, 0]], m[[0, 1]]], [m[[1, 0]], m[[1, 1]]]])
+                .collect(),
+        ))
+    }
+
+    fn __len__(&self) -> usize {
+        self.num_qubits()
+    }
+
+    /// The inverse of the assignment matrix of each qubit, as an array of shape ``(n, 2, 2)``.
+    #[getter]
+    fn mitigation_matrices(&self, py: Python) -> Py<PyArray3<f64>> {
+        matrices_to_array(&self.inverses)
+            .into_pyarray_bound(py)
+            .unbind()
+    }
+
+    /// The mitigated quasi-probability of every outcome, as an array indexed by the outcome.
+    fn dense_quasi_probabilities(
+        &self,
+        py: Python,
+        counts: HashMap<String, u64>,
+        qubits: Vec<usize>,
+        positions: Vec<usize>,
+    ) -> PyResult<(Py<PyArray1<f64>>, u64)> {
+        let (probs, shots) = py.allow_threads(|| -> Result<_, AccelerateError> {
+            let observed = parse_counts(&counts, &positions)?;
+            let mut probs = observed.to_dense()?;
+            self.apply_dense(&qubits, &mut probs)?;
+            Ok((probs, observed.shots))
+        })?;
+        Ok((probs.into_pyarray_bound(py).unbind(), shots))
+    }
+
+    /// The mitigated quasi-probability of each observed outcome, keyed by its binary string.
+    #[pyo3(name = "quasi_probabilities")]
+    fn py_quasi_probabilities(
+        &self,
+        py: Python,
+        counts: HashMap<String, u64>,
+        qubits: Vec<usize>,
+        positions: Vec<usize>,
+    ) -> PyResult<(IndexMap<String, f64>, u64)> {
+        Ok(py.allow_threads(|| -> Result<_, AccelerateError> {
+            let observed = parse_counts(&counts, &positions)?;
+            let quasi = self.quasi_probabilities(&qubits, &observed)?;
+            Ok((labelled(&observed, quasi), observed.shots))
+        })?)
+    }
+
+    /// The probability of each observed outcome by iterative Bayesian unfolding, keyed by its
+    /// binary string.
+    #[pyo3(name = "unfold", signature = (counts, qubits, positions, max_iterations=100, tolerance=1e-8))]
+    fn py_unfold(
+        &self,
+        py: Python,
+        counts: HashMap<String, u64>,
+        qubits: Vec<usize>,
+        positions: Vec<usize>,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> PyResult<(IndexMap<String, f64>, u64)> {
+        Ok(py.allow_threads(|| -> Result<_, AccelerateError> {
+            let observed = parse_counts(&counts, &positions)?;
+            let probs = self.unfold(&qubits, &observed, max_iterations, tolerance)?;
+            Ok((labelled(&observed, probs), observed.shots))
+        })?)
+    }
+
+    /// The mitigated expectation value of the diagonal observable that's the tensor product of
+    /// ``factors``, the pair of diagonal entries of each bit.
+    #[pyo3(name = "expectation_value")]
+    fn py_expectation_value(
+        &self,
+        py: Python,
+        counts: HashMap<String, u64>,
+        qubits: Vec<usize>,
+        positions: Vec<usize>,
+        factors: Vec<[f64; 2]>,
+    ) -> PyResult<(f64, u64)> {
+        Ok(py.allow_threads(|| -> Result<_, AccelerateError> {
+            let observed = parse_counts(&counts, &positions)?;
+            let expval = self.expectation_value(&qubits, &factors, &observed)?;
+            Ok((expval, observed.shots))
+        })?)
+    }
+}
+
+/// The assignment matrix of each qubit from calibration counts.
+///
+/// Args:
+///     num_qubits (int): the number of qubits.
+///     calibrations (list[tuple[str, dict[str, int]]]): the basis state prepared by each
+///         calibration circuit, as a binary string with qubit 0 last, and the counts of the
+///         circuit.
+///
+/// Returns:
+///     numpy.ndarray: the array of shape ``(num_qubits, 2, 2)`` of the assignment matrices, whose
+///     entry ``[q, m, p]`` is the probability of measuring ``m`` on qubit ``q`` when ``p`` was
+///     prepared.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "assignment_matrices")]
+pub fn py_assignment_matrices(
+    py: Python,
+    num_qubits: usize,
+    calibrations: Vec<(String, HashMap<String, u64>)>,
+) -> PyResult<Py<PyArray3<f64>>> {
+    let matrices = py.allow_threads(|| assignment_matrices(num_qubits, &calibrations))?;
+    Ok(matrices_to_array(&matrices).into_pyarray_bound(py).unbind())
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn readout_mitigation(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_assignment_matrices))?;
+    m.add_class::<TensoredMitigator>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    const A0: Matrix2 = [[0.9, 0.2], [0.1, 0.8]];
+    const A1: Matrix2 = [[0.95, 0.1], [0.05, 0.9]];
+
+    /// The counts of 10000 shots of the two-qubit distribution `probs`, measured through `A0` on
+    /// bit 0 and `A1` on bit 1.
+    fn noisy_counts(probs: &[f64; 4]) -> Vec<(String, u64)> {
+        (0..4)
+            .map(|to: usize| {
+                let prob: f64 = (0..4)
+                    .map(|from: usize| A0[to & 1][from & 1] * A1[to >> 1][from >> 1] * probs[from])
+                    .sum();
+                (format!("{:02b}", to), (prob * 10000.).round() as u64)
+            })
+            .collect()
+    }
+
+    fn observed(counts: &[(String, u64)]) -> Observed {
+        Observed::from_counts(
+            counts
+                .iter()
+                .map(|(outcome, freq)| (outcome.as_str(), *freq)),
+            &[0, 1],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(parse_outcome("10 1", &[0, 2, 1]).unwrap(), vec![0b011]);
+        assert!(matches!(
+            parse_outcome("01", &[2]),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            parse_outcome("1x", &[0]),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_assignment_matrices() {
+        let calibrations = [
+            ("00".to_string(), [("00", 9), ("01", 1)]),
+            ("11".to_string(), [("11", 8), ("10", 2)]),
+        ]
+        .map(|(prepared, counts)| {
+            let counts = counts
+                .iter()
+                .map(|(outcome, freq)| (outcome.to_string(), *freq))
+                .collect();
+            (prepared, counts)
+        });
+        let matrices = assignment_matrices(2, &calibrations).unwrap();
+        assert_eq!(
+            matrices,
+            vec![[[0.9, 0.2], [0.1, 0.8]], [[1., 0.], [0., 1.]]]
+        );
+        assert!(matches!(
+            assignment_matrices(2, &calibrations[..1]),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let inv = inverse(&A0);
+        assert_abs_diff_eq!(
+            inv.concat()[..],
+            [8. / 7., -2. / 7., -1. / 7., 9. / 7.][..],
+            epsilon = 1e-10
+        );
+        // The pseudo-inverse of a singular matrix.
+        assert_eq!(inverse(&[[1., 1.], [0., 0.]]), [[0.5, 0.], [0.5, 0.]]);
+        assert_eq!(inverse(&[[0., 0.], [0., 0.]]), [[0., 0.], [0., 0.]]);
+    }
+
+    #[test]
+    fn test_observed() {
+        // Outcomes that agree on the read bits are merged.
+        let observed = Observed::from_counts([("10", 1), ("11", 2), ("00", 1)], &[1]).unwrap();
+        assert_eq!(observed.outcomes, vec![vec![1], vec![0]]);
+        assert_abs_diff_eq!(observed.probs[..], [0.75, 0.25][..], epsilon = 1e-10);
+        assert_eq!(observed.shots, 4);
+        assert_abs_diff_eq!(
+            observed.to_dense().unwrap()[..],
+            [0.25, 0.75][..],
+            epsilon = 1e-10
+        );
+        assert!(Observed::from_counts([("0", 0)], &[0]).is_err());
+    }
+
+    #[test]
+    fn test_mitigation() {
+        let probs = [0.1, 0.2, 0.3, 0.4];
+        let counts = noisy_counts(&probs);
+        let observed = observed(&counts);
+        let mitigator = TensoredMitigator::new(vec![A1, A0]);
+        let qubits = [1, 0];
+        let mut dense = observed.to_dense().unwrap();
+        mitigator.apply_dense(&qubits, &mut dense).unwrap();
+        assert_abs_diff_eq!(dense[..], probs[..], epsilon = 1e-10);
+        let quasi = mitigator.quasi_probabilities(&qubits, &observed).unwrap();
+        let expected: Vec<f64> = observed
+            .outcomes
+            .iter()
+            .map(|outcome| probs[outcome[0] as usize])
+            .collect();
+        assert_abs_diff_eq!(quasi[..], expected[..], epsilon = 1e-10);
+        let zz = mitigator
+            .expectation_value(&qubits, &[[1., -1.], [1., -1.]], &observed)
+            .unwrap();
+        assert_abs_diff_eq!(zz, 0.1 - 0.2 - 0.3 + 0.4, epsilon = 1e-10);
+        let z1 = mitigator
+            .expectation_value(&qubits, &[[1., 1.], [1., -1.]], &observed)
+            .unwrap();
+        assert_abs_diff_eq!(z1, 0.1 + 0.2 - 0.3 - 0.4, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_mitigation_errors() {
+        let observed = observed(&noisy_counts(&[0.25; 4]));
+        let mitigator = TensoredMitigator::new(vec![A0, A1]);
+        assert!(matches!(
+            mitigator.apply_dense(&[0, 1], &mut [0.; 3]),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            mitigator.quasi_probabilities(&[0, 2], &observed),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            mitigator.expectation_value(&[0, 1], &[[1., -1.]], &observed),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_unfold() {
+        let probs = [0.1, 0.2, 0.3, 0.4];
+        let observed = observed(&noisy_counts(&probs));
+        let mitigator = TensoredMitigator::new(vec![A0, A1]);
+        let unfolded = mitigator.unfold(&[0, 1], &observed, 10000, 1e-14).unwrap();
+        assert!(unfolded.iter().all(|prob| *prob >= 0.));
+        assert!((unfolded.iter().sum::<f64>() - 1.).abs() < 1e-12);
+        let expected: Vec<f64> = observed
+            .outcomes
+            .iter()
+            .map(|outcome| probs[outcome[0] as usize])
+            .collect();
+        for (prob, expected) in unfolded.iter().zip(&expected) {
+            assert!((prob - expected).abs() < 1e-4);
+        }
+        // Without readout errors, the observed distribution is already the fixed point.
+        let identity = TensoredMitigator::new(vec![[[1., 0.], [0., 1.]]; 2]);
+        let unfolded = identity.unfold(&[0, 1], &observed, 10, 0.).unwrap();
+        assert_abs_diff_eq!(unfolded[..], observed.probs[..], epsilon = 1e-10);
+    }
+}
@@ -0,0 +1,132 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use ndarray::Array1;
+use numpy::{IntoPyArray, PyReadonlyArray1};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::utils::arg_sort_by;
+
+/// The probability distribution nearest in the L2 norm to the quasi-probabilities `values`, by the
+/// method of Smolin et al., Phys. Rev. Lett. 108, 070502 (2012).
+///
+/// The outcomes whose probability isn't dropped are returned with their probability, in the order
+/// of increasing quasi-probability, along with the L2 distance between the distributions.
+pub fn nearest_probability(values: &[f64]) -> (Vec<(usize, f64)>, f64) {
+    let order = arg_sort_by(values, |a, b| a.total_cmp(b));
+    let mut num_elems = values.len() as f64;
+    let mut beta = 0.;
+    let mut diff = 0.;
+    let mut out = Vec::with_capacity(values.len());
+    for index in order {
+        let value = values[index];
+        if value + beta / num_elems < 0. {
+            beta += value;
+            num_elems -= 1.;
+            diff += value * value;
+        } else {
+            let shift = beta / num_elems;
+            diff += shift * shift;
+            out.push((index, value + shift));
+        }
+    }
+    (out, diff.sqrt())
+}
+
+/// Quasi-probabilities from Python: an array of them, or a dictionary keyed by outcome.
+#[derive(FromPyObject)]
+pub enum QuasiProbabilities<'py> {
+    Array(PyReadonlyArray1<'py, f64>),
+    Dict(Bound<'py, PyDict>),
+}
+
+/// The probability distribution nearest to quasi-probabilities in the L2 norm.
+///
+/// Args:
+///     quasi (dict | numpy.ndarray): the quasi-probability of each outcome.
+///
+/// Returns:
+///     (dict | numpy.ndarray, float): the probabilities, as a dictionary without the outcomes
+///     whose probability was dropped if ``quasi`` is a dictionary, and as an array with those
+///     probabilities zero if it's an array, and the L2 distance between the distributions.
+#[pyfunction]
+pub fn nearest_probability_distribution(
+    py: Python,
+    quasi: QuasiProbabilities,
+) -> PyResult<(PyObject, f64)> {
+    match quasi {
+        QuasiProbabilities::Array(array) => {
+            let values = array.as_array().to_vec();
+            let (probs, distance) = py.allow_threads(|| nearest_probability(&values));
+            let mut out = Array1::zeros(values.len());
+            for (index, prob) in probs {
+                out[index] = prob;
+            }
+            Ok((out.into_pyarray_bound(py).into_any().unbind(), distance))
+        }
+        QuasiProbabilities::Dict(dict) => {
+            let mut keys = Vec::with_capacity(dict.len());
+            let mut values = Vec::with_capacity(dict.len());
+            for (key, value) in dict.iter() {
+                keys.push(key);
+                values.push(value.extract::<f64>()?);
+            }
+            let (probs, distance) = py.allow_threads(|| nearest_probability(&values));
+            let out = PyDict::new_bound(py);
+            for (index, prob) in probs {
+                out.set_item(&keys[index], prob)?;
+            }
+            Ok((out.into_any().unbind(), distance))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_nearest(values: &[f64], expected: &[(usize, f64)], distance: f64) {
+        let (probs, out_distance) = nearest_probability(values);
+        assert_eq!(probs.len(), expected.len());
+        for ((index, prob), (expected_index, expected_prob)) in probs.iter().zip(expected) {
+            assert_eq!(index, expected_index);
+            assert!((prob - expected_prob).abs() < 1e-12);
+        }
+        assert!((out_distance - distance).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_probabilities_unchanged() {
+        assert_nearest(&[0.8, 0.2], &[(1, 0.2), (0, 0.8)], 0.);
+    }
+
+    #[test]
+    fn test_negative_dropped() {
+        assert_nearest(&[0.5, 0.6, -0.1], &[(0, 0.45), (1, 0.55)], 0.015_f64.sqrt());
+    }
+
+    #[test]
+    fn test_dropped_in_turn() {
+        // Dropping the first outcome makes the second negative once its share is taken.
+        assert_nearest(
+            &[1.0, -0.1, 0.3, -0.2],
+            &[(2, 0.15), (0, 0.85)],
+            0.095_f64.sqrt(),
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_nearest(&[], &[], 0.);
+    }
+}
@@ -11,7 +11,7 @@
 // that they have been altered from the originals.
 
 use super::converters::hex_to_bin;
-use crate::getenv_use_multiple_threads;
+use crate::threading;
 use hashbrown::HashMap;
 use ndarray::prelude::*;
 use num_bigint::BigUint;
@@ -131,7 +131,7 @@ pub fn marginal_memory(
     return_hex: bool,
     parallel_threshold: usize,
 ) -> PyResult<PyObject> {
-    let run_in_parallel = getenv_use_multiple_threads();
+    let run_in_parallel = threading::use_multiple_threads();
     let first_elem = memory.first();
     if first_elem.is_none() {
         let res: Vec<String> = Vec::new();
@@ -146,10 +146,12 @@ pub fn marginal_memory(
             .map(|x| map_memory(x, &indices, clbit_size, return_hex))
             .collect()
     } else {
-        memory
-            .par_iter()
-            .map(|x| map_memory(x, &indices, clbit_size, return_hex))
-            .collect()
+        threading::install(|| {
+            memory
+                .par_iter()
+                .map(|x| map_memory(x, &indices, clbit_size, return_hex))
+                .collect()
+        })
     };
     if return_int {
         if out_mem.len() < parallel_threshold || !run_in_parallel {
@@ -159,11 +161,13 @@ pub fn marginal_memory(
                 .collect::<Vec<BigUint>>()
                 .to_object(py))
         } else {
-            Ok(out_mem
-                .par_iter()
-                .map(|x| BigUint::parse_bytes(x.as_bytes(), 2).unwrap())
-                .collect::<Vec<BigUint>>()
-                .to_object(py))
+            Ok(threading::install(|| {
+                out_mem
+                    .par_iter()
+                    .map(|x| BigUint::parse_bytes(x.as_bytes(), 2).unwrap())
+                    .collect::<Vec<BigUint>>()
+            })
+            .to_object(py))
         }
     } else {
         Ok(out_mem.to_object(py))
@@ -11,7 +11,9 @@
 // that they have been altered from the originals.
 
 pub mod converters;
+pub mod distributions;
 pub mod marginalization;
+pub mod registers;
 
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
@@ -29,5 +31,10 @@ pub fn results(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(
         marginalization::marginal_measure_level_1_avg
     ))?;
+    m.add_wrapped(wrap_pyfunction!(
+        distributions::nearest_probability_distribution
+    ))?;
+    m.add_wrapped(wrap_pyfunction!(registers::py_split_register_counts))?;
+    m.add_wrapped(wrap_pyfunction!(registers::py_register_slice_counts))?;
     Ok(())
 }
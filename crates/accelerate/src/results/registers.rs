@@ -0,0 +1,200 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Splitting counts whose outcomes are grouped by classical register, like ``"01 110"``, into the
+//! counts of each register.  As in the outcomes, the last register is the leftmost group and the
+//! first bit of a register is its rightmost character.
+
+use hashbrown::HashMap;
+use pyo3::prelude::*;
+use smallvec::SmallVec;
+
+use crate::error::AccelerateError;
+
+/// The groups of an outcome, with the first register first.
+fn register_groups(outcome: &str) -> SmallVec<[&str; 4]> {
+    let mut groups = outcome.split(' ').collect::<SmallVec<[&str; 4]>>();
+    groups.reverse();
+    groups
+}
+
+/// The counts of each register, in order of the registers.
+pub fn split_registers<T: std::ops::AddAssign + Copy>(
+    counts: &HashMap<String, T>,
+) -> Result<Vec<HashMap<String, T>>, AccelerateError> {
+    let num_registers = counts
+        .keys()
+        .next()
+        .map_or(0, |outcome| outcome.split(' ').count());
+    let mut out = vec![HashMap::new(); num_registers];
+    for (outcome, value) in counts.iter() {
+        let groups = register_groups(outcome);
+        if groups.len() != num_registers {
+            return Err(AccelerateError::Value(format!(
+                "the outcome '{outcome}' has {} registers, not {num_registers}",
+                groups.len()
+            )));
+        }
+        for (register_counts, group) in out.iter_mut().zip(groups) {
+            register_counts
+                .entry(group.to_string())
+                .and_modify(|total| *total += *value)
+                .or_insert(*value);
+        }
+    }
+    Ok(out)
+}
+
+/// The counts of the bits `start..stop` of the register `register`, or of all its bits if `stop`
+/// is `None`.
+pub fn register_slice<T: std::ops::AddAssign + Copy>(
+    counts: &HashMap<String, T>,
+    register: usize,
+    start: usize,
+    stop: Option<usize>,
+) -> Result<HashMap<String, T>, AccelerateError> {
+    let mut out: HashMap<String, T> = HashMap::new();
+    for (outcome, value) in counts.iter() {
+        let groups = register_groups(outcome);
+        let group = groups.get(register).ok_or_else(|| {
+            AccelerateError::Index(format!(
+                "register {register} is out of range for the outcome '{outcome}'"
+            ))
+        })?;
+        let width = group.len();
+        let stop = stop.unwrap_or(width);
+        if start > stop || stop > width {
+            return Err(AccelerateError::Index(format!(
+                "the bits {start}..{stop} are out of range for a register of {width} bits"
+            )));
+        }
+        out.entry(group[width - stop..width - start].to_string())
+            .and_modify(|total| *total += *value)
+            .or_insert(*value);
+    }
+    Ok(out)
+}
+
+/// Split counts whose outcomes are grouped by register into the counts of each register.
+///
+/// Args:
+///     counts (dict[str, int]): the counts, with the bits of each register separated by a space
+///         and the last register leftmost.
+///
+/// Returns:
+///     list[dict[str, int]]: the counts of each register, with the first register first.
+///
+/// Raises:
+///     ValueError: if the outcomes don't all have the same number of registers.
+#[pyfunction]
+#[pyo3(name = "split_register_counts", text_signature = "(counts, /)")]
+pub fn py_split_register_counts(
+    counts: HashMap<String, u64>,
+) -> PyResult<Vec<HashMap<String, u64>>> {
+    Ok(split_registers(&counts)?)
+}
+
+/// Extract the counts of a slice of the bits of one register from counts whose outcomes are
+/// grouped by register.
+///
+/// Args:
+///     counts (dict[str, int]): the counts, with the bits of each register separated by a space
+///         and the last register leftmost.
+///     register (int): the index of the register.
+///     start (int): the first bit of the slice, where bit 0 is the rightmost of the register.
+///     stop (int | None): one past the last bit of the slice, or ``None`` for the whole register.
+///
+/// Returns:
+///     dict[str, int]: the counts of the slice.
+///
+/// Raises:
+///     IndexError: if the register or the bits are out of range of an outcome.
+#[pyfunction]
+#[pyo3(name = "register_slice_counts", signature = (counts, register, start=0, stop=None))]
+pub fn py_register_slice_counts(
+    counts: HashMap<String, u64>,
+    register: usize,
+    start: usize,
+    stop: Option<usize>,
+) -> PyResult<HashMap<String, u64>> {
+    Ok(register_slice(&counts, register, start, stop)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(entries: &[(&str, u64)]) -> HashMap<String, u64> {
+        entries
+            .iter()
+            .map(|(outcome, count)| (outcome.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_registers() {
+        let split = split_registers(&counts(&[("01 110", 3), ("11 110", 2), ("01 001", 1)]));
+        assert_eq!(
+            split.unwrap(),
+            vec![
+                counts(&[("110", 5), ("001", 1)]),
+                counts(&[("01", 4), ("11", 2)]),
+            ]
+        );
+        assert_eq!(split_registers::<u64>(&HashMap::new()).unwrap(), vec![]);
+        assert_eq!(
+            split_registers(&counts(&[("10", 2)])).unwrap(),
+            vec![counts(&[("10", 2)])]
+        );
+    }
+
+    #[test]
+    fn test_split_registers_mismatched() {
+        assert!(matches!(
+            split_registers(&counts(&[("01 110", 3), ("110", 2)])),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_slice() {
+        let counts_in = counts(&[("01 110", 3), ("11 110", 2), ("01 001", 1)]);
+        // Bit 0 of a register is its rightmost character.
+        assert_eq!(
+            register_slice(&counts_in, 0, 1, Some(3)).unwrap(),
+            counts(&[("11", 5), ("00", 1)])
+        );
+        assert_eq!(
+            register_slice(&counts_in, 0, 0, Some(1)).unwrap(),
+            counts(&[("0", 5), ("1", 1)])
+        );
+        assert_eq!(
+            register_slice(&counts_in, 1, 0, None).unwrap(),
+            counts(&[("01", 4), ("11", 2)])
+        );
+        assert_eq!(
+            register_slice(&counts_in, 1, 1, Some(1)).unwrap(),
+            counts(&[("", 6)])
+        );
+    }
+
+    #[test]
+    fn test_register_slice_out_of_range() {
+        let counts_in = counts(&[("01 110", 3)]);
+        for (register, start, stop) in [(2, 0, None), (0, 0, Some(4)), (0, 2, Some(1))] {
+            assert!(matches!(
+                register_slice(&counts_in, register, start, stop),
+                Err(AccelerateError::Index(_))
+            ));
+        }
+    }
+}
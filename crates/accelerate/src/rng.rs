@@ -0,0 +1,250 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The seeding of the random-number generators of the randomized algorithms.
+//!
+//! Seeds are 128 bits, the size of the state of [Pcg64Mcg].  Seeds that fit in 64 bits seed the
+//! generators exactly as they always have, so the results for those seeds are unchanged.
+//!
+//! The algorithms that run several trials draw one 64-bit seed per trial from a [SeedSequence].
+//! The state of a sequence is its seed and the number of seeds drawn from it, so a sequence can be
+//! exported after some runs and restored to carry on with the same seeds.
+
+use rand::prelude::*;
+use rand_pcg::Pcg64Mcg;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+#[cfg(feature = "python")]
+use crate::error::AccelerateError;
+
+/// The generator seeded by `seed`.
+pub fn generator(seed: u128) -> Pcg64Mcg {
+    match u64::try_from(seed) {
+        Ok(seed) => Pcg64Mcg::seed_from_u64(seed),
+        Err(_) => Pcg64Mcg::from_seed(seed.to_le_bytes()),
+    }
+}
+
+/// A fresh seed from the entropy of the operating system.
+pub fn entropy_seed() -> u128 {
+    Pcg64Mcg::from_entropy().gen()
+}
+
+/// A sequence of seeds for the trials of a randomized algorithm, drawn from a generator.
+///
+/// From Python, a sequence can be passed as the seed of the Rust routines to have them draw their
+/// seeds from it, and ``SeedSequence(seed, draws)`` restores the sequence of ``seed`` with
+/// ``draws`` seeds already drawn.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.rng"))]
+pub struct SeedSequence {
+    seed: u128,
+    draws: u64,
+    rng: Pcg64Mcg,
+}
+
+impl SeedSequence {
+    /// The sequence from `seed`, or from a seed from the operating system if there's none.
+    pub fn new(seed: Option<u128>) -> Self {
+        let seed = seed.unwrap_or_else(entropy_seed);
+        SeedSequence {
+            seed,
+            draws: 0,
+            rng: generator(seed),
+        }
+    }
+
+    /// The sequence from `seed` with `draws` seeds already drawn.
+    pub fn restore(seed: u128, draws: u64) -> Self {
+        let mut out = Self::new(Some(seed));
+        for _ in 0..draws {
+            out.next_seed();
+        }
+        out
+    }
+
+    /// The seed of the whole sequence, including one that was taken from the operating system.
+    pub fn seed(&self) -> u128 {
+        self.seed
+    }
+
+    /// The number of seeds drawn so far.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Draw the next seed.
+    pub fn next_seed(&mut self) -> u64 {
+        self.draws += 1;
+        self.rng.next_u64()
+    }
+
+    /// Draw the next `num` seeds.
+    pub fn seeds(&mut self, num: usize) -> Vec<u64> {
+        (0..num).map(|_| self.next_seed()).collect()
+    }
+}
+
+/// A seed passed from Python: either an integer, or a sequence that the seeds are drawn from,
+/// which is advanced in place.
+#[cfg(feature = "python")]
+#[derive(FromPyObject)]
+pub enum PySeed<'py> {
+    Sequence(PyRefMut<'py, SeedSequence>),
+    Seed(u128),
+}
+
+#[cfg(feature = "python")]
+impl PySeed<'_> {
+    /// Run `op` with the sequence of `seed`, which is a new one unless a sequence was passed.
+    pub fn with_sequence<T>(seed: Option<PySeed>, op: impl FnOnce(&mut SeedSequence) -> T) -> T {
+        match seed {
+            Some(PySeed::Sequence(mut sequence)) => op(&mut *sequence),
+            Some(PySeed::Seed(seed)) => op(&mut SeedSequence::new(Some(seed))),
+            None => op(&mut SeedSequence::new(None)),
+        }
+    }
+
+    /// The generator of `seed`, seeded by the next seed of the sequence if a sequence was passed.
+    pub fn generator(seed: Option<PySeed>) -> Pcg64Mcg {
+        match seed {
+            Some(PySeed::Sequence(mut sequence)) => generator(sequence.next_seed() as u128),
+            Some(PySeed::Seed(seed)) => generator(seed),
+            None => generator(entropy_seed()),
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SeedSequence {
+    #[new]
+    #[pyo3(signature = (seed=None, draws=0))]
+    fn py_new(seed: Option<u128>, draws: u64) -> PyResult<Self> {
+        match seed {
+            Some(seed) => Ok(Self::restore(seed, draws)),
+            None if draws == 0 => Ok(Self::new(None)),
+            None => Err(AccelerateError::Value(
+                "a sequence with seeds drawn can only be restored from its seed".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    fn __getnewargs__(&self) -> (u128, u64) {
+        (self.seed, self.draws)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SeedSequence(seed={}, draws={})", self.seed, self.draws)
+    }
+
+    /// The seed of the sequence, including one that was taken from the operating system.
+    #[getter(seed)]
+    fn py_seed(&self) -> u128 {
+        self.seed
+    }
+
+    /// The number of seeds drawn so far.
+    #[getter(draws)]
+    fn py_draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// The state of the sequence, from which ``SeedSequence(*state)`` carries on with the same
+    /// seeds.
+    fn state(&self) -> (u128, u64) {
+        (self.seed, self.draws)
+    }
+
+    /// Draw the next seed.
+    #[pyo3(name = "next_seed")]
+    fn py_next_seed(&mut self) -> u64 {
+        self.next_seed()
+    }
+}
+
+/// A fresh seed of 128 bits from the entropy of the operating system.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "entropy_seed")]
+pub fn py_entropy_seed() -> u128 {
+    entropy_seed()
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn rng(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_entropy_seed))?;
+    m.add_class::<SeedSequence>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_64_bit_seeds_unchanged() {
+        for seed in [0, 2024, u64::MAX] {
+            let mut expected = Pcg64Mcg::seed_from_u64(seed);
+            let mut rng = generator(seed as u128);
+            for _ in 0..4 {
+                assert_eq!(rng.next_u64(), expected.next_u64());
+            }
+        }
+    }
+
+    #[test]
+    fn test_128_bit_seeds() {
+        let low = 2024;
+        let high = (1 << 64) | low;
+        let mut expected = Pcg64Mcg::from_seed(high.to_le_bytes());
+        assert_eq!(generator(high).next_u64(), expected.next_u64());
+        assert_ne!(generator(high).next_u64(), generator(low).next_u64());
+    }
+
+    #[test]
+    fn test_sequence_draws_from_generator() {
+        // The trials used to take their seeds from a generator of the seed, and still do.
+        let expected = generator(42)
+            .sample_iter(rand::distributions::Standard)
+            .take(5)
+            .collect::<Vec<u64>>();
+        let mut sequence = SeedSequence::new(Some(42));
+        assert_eq!(sequence.seeds(5), expected);
+        assert_eq!(sequence.seed(), 42);
+        assert_eq!(sequence.draws(), 5);
+    }
+
+    #[test]
+    fn test_restore() {
+        let seed = u128::MAX - 7;
+        let mut sequence = SeedSequence::new(Some(seed));
+        sequence.seeds(3);
+        let mut restored = SeedSequence::restore(sequence.seed(), sequence.draws());
+        assert_eq!(restored.draws(), 3);
+        assert_eq!(restored.seeds(4), sequence.seeds(4));
+        assert_eq!(restored.draws(), 7);
+    }
+
+    #[test]
+    fn test_entropy_seed_recorded() {
+        let mut sequence = SeedSequence::new(None);
+        let seeds = sequence.seeds(2);
+        assert_eq!(SeedSequence::new(Some(sequence.seed())).seeds(2), seeds);
+    }
+}
@@ -0,0 +1,156 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The error model that decides between bridges and swaps.
+//!
+//! A CX between qubits `a` and `c` that share a neighbor `b` can be realized without moving any
+//! qubit by the bridge `CX(a, b) CX(b, c) CX(a, b) CX(b, c)`.  That's four two-qubit gates, the
+//! same as one swap and the gate, so which is cheaper depends on the error rates of the two edges
+//! and on whether the swap would help or hurt the gates that come after.
+
+use hashbrown::HashMap;
+
+use crate::nlayout::PhysicalQubit;
+use crate::noise_adaptive_layout::gate_reliability;
+
+use super::neighbor_table::NeighborTable;
+
+/// The cost of a two-qubit gate on each edge of a device, as the negated logarithm of its success
+/// rate.
+#[derive(Clone, Debug)]
+pub struct BridgeCosts {
+    costs: HashMap<[PhysicalQubit; 2], f64>,
+    /// The mean cost of a gate over the edges.
+    mean: f64,
+}
+
+impl BridgeCosts {
+    /// Build the costs from the average error rates of the edges, in either direction.  Missing
+    /// rates are taken as the mean of the others; if no edge has a non-zero rate, every gate costs
+    /// one, so the bridges and swaps are compared by their numbers of gates.
+    pub fn new(neighbors: &NeighborTable, errors: &HashMap<[PhysicalQubit; 2], f64>) -> Self {
+        let mut costs = HashMap::new();
+        let mut missing = Vec::new();
+        for a in (0..neighbors.num_qubits() as u32).map(PhysicalQubit::new) {
+            for b in neighbors[a].iter().copied().filter(|b| *b > a) {
+                if errors.contains_key(&[a, b]) || errors.contains_key(&[b, a]) {
+                    costs.insert([a, b], -gate_reliability(errors, a, b).ln());
+                } else {
+                    missing.push([a, b]);
+                }
+            }
+        }
+        let total = costs.values().sum::<f64>();
+        if total > 0. {
+            let mean = total / costs.len() as f64;
+            costs.extend(missing.into_iter().map(|edge| (edge, mean)));
+        } else {
+            costs = costs
+                .into_keys()
+                .chain(missing)
+                .map(|edge| (edge, 1.))
+                .collect();
+        }
+        let mean = if costs.is_empty() {
+            1.
+        } else {
+            costs.values().sum::<f64>() / costs.len() as f64
+        };
+        BridgeCosts { costs, mean }
+    }
+
+    /// The cost of a gate between the neighbors `a` and `b`.
+    #[inline]
+    pub fn gate(&self, a: PhysicalQubit, b: PhysicalQubit) -> f64 {
+        self.costs[&[a.min(b), a.max(b)]]
+    }
+
+    /// The mean cost of a gate over the edges.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// The expected cost of one unit of routing distance, which is one swap of three gates.
+    #[inline]
+    pub fn distance(&self) -> f64 {
+        3. * self.mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use smallvec::smallvec;
+
+    fn qubit(index: u32) -> PhysicalQubit {
+        PhysicalQubit::new(index)
+    }
+
+    /// The symmetric neighbor table of a line of three qubits.
+    fn line() -> NeighborTable {
+        NeighborTable::from_neighbors(vec![
+            smallvec![qubit(1)],
+            smallvec![qubit(0), qubit(2)],
+            smallvec![qubit(1)],
+        ])
+    }
+
+    #[test]
+    fn test_missing_errors_are_the_mean() {
+        let errors = HashMap::from([([qubit(1), qubit(0)], 0.1)]);
+        let costs = BridgeCosts::new(&line(), &errors);
+        let cost = -0.9f64.ln();
+        assert_abs_diff_eq!(costs.gate(qubit(0), qubit(1)), cost, epsilon = 1e-12);
+        assert_abs_diff_eq!(costs.gate(qubit(2), qubit(1)), cost, epsilon = 1e-12);
+        assert_abs_diff_eq!(costs.distance(), 3. * cost, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_better_direction() {
+        let errors = HashMap::from([
+            ([qubit(0), qubit(1)], 0.2),
+            ([qubit(1), qubit(0)], 0.1),
+            ([qubit(1), qubit(2)], 0.3),
+        ]);
+        let costs = BridgeCosts::new(&line(), &errors);
+        assert_abs_diff_eq!(
+            costs.gate(qubit(1), qubit(0)),
+            -0.9f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            costs.gate(qubit(1), qubit(2)),
+            -0.7f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            costs.distance(),
+            1.5 * -(0.9f64 * 0.7).ln(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_ideal_gates_count_one() {
+        for errors in [HashMap::new(), HashMap::from([([qubit(0), qubit(1)], 0.)])] {
+            let costs = BridgeCosts::new(&line(), &errors);
+            assert_eq!(costs.gate(qubit(0), qubit(1)), 1.);
+            assert_eq!(costs.gate(qubit(1), qubit(2)), 1.);
+            assert_eq!(costs.distance(), 3.);
+        }
+        let costs = BridgeCosts::new(&NeighborTable::from_neighbors(vec![]), &HashMap::new());
+        assert_eq!(costs.distance(), 3.);
+    }
+}
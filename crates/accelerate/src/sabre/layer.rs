@@ -13,6 +13,7 @@
 use indexmap::IndexMap;
 use ndarray::prelude::*;
 use rustworkx_core::petgraph::prelude::*;
+use smallvec::SmallVec;
 
 use crate::nlayout::PhysicalQubit;
 
@@ -29,35 +30,35 @@ use crate::nlayout::PhysicalQubit;
 pub struct FrontLayer {
     /// Map of the (index to the) node to the qubits it acts on.
     nodes: IndexMap<NodeIndex, [PhysicalQubit; 2], ::ahash::RandomState>,
-    /// Map of each qubit to the node that acts on it and the other qubit that node acts on, if this
-    /// qubit is active (otherwise `None`).
-    qubits: Vec<Option<(NodeIndex, PhysicalQubit)>>,
+    /// Map of each qubit to the nodes that act on it and the other qubit each of those nodes acts
+    /// on.  A qubit has more than one node only if the nodes commute, and the DAG was built to let
+    /// them be routed in any order.
+    qubits: Vec<SmallVec<[(NodeIndex, PhysicalQubit); 1]>>,
 }
 
 impl FrontLayer {
     pub fn new(num_qubits: u32) -> Self {
         FrontLayer {
-            // This is the maximum capacity of the front layer, since each qubit must be one of a
-            // pair, and can only have one gate in the layer.
+            // This is the maximum capacity of the front layer when no nodes commute, since each
+            // qubit must be one of a pair, and can then only have one gate in the layer.
             nodes: IndexMap::with_capacity_and_hasher(
                 num_qubits as usize / 2,
                 ::ahash::RandomState::default(),
             ),
-            qubits: vec![None; num_qubits as usize],
+            qubits: vec![SmallVec::new(); num_qubits as usize],
         }
     }
 
-    /// View onto the mapping between qubits and their `(node, other_qubit)` pair.  Index `i`
-    /// corresponds to physical qubit `i`.
-    pub fn qubits(&self) -> &[Option<(NodeIndex, PhysicalQubit)>] {
-        &self.qubits
+    /// View onto the `(node, other_qubit)` pairs of the nodes on a qubit.
+    pub fn on_qubit(&self, qubit: PhysicalQubit) -> &[(NodeIndex, PhysicalQubit)] {
+        &self.qubits[qubit.index()]
     }
 
     /// Add a node into the front layer, with the two qubits it operates on.
     pub fn insert(&mut self, index: NodeIndex, qubits: [PhysicalQubit; 2]) {
         let [a, b] = qubits;
-        self.qubits[a.index()] = Some((index, b));
-        self.qubits[b.index()] = Some((index, a));
+        self.qubits[a.index()].push((index, b));
+        self.qubits[b.index()].push((index, a));
         self.nodes.insert(index, qubits);
     }
 
@@ -66,14 +67,14 @@ impl FrontLayer {
         // The actual order in the indexmap doesn't matter as long as it's reproducible.
         // Swap-remove is more efficient than a full shift-remove.
         let [a, b] = self.nodes.swap_remove(index).unwrap();
-        self.qubits[a.index()] = None;
-        self.qubits[b.index()] = None;
+        self.qubits[a.index()].retain(|(node, _)| node != index);
+        self.qubits[b.index()].retain(|(node, _)| node != index);
     }
 
     /// Query whether a qubit has an active node.
     #[inline]
     pub fn is_active(&self, qubit: PhysicalQubit) -> bool {
-        self.qubits[qubit.index()].is_some()
+        !self.qubits[qubit.index()].is_empty()
     }
 
     /// Calculate the score _difference_ caused by this swap, compared to not making the swap.
@@ -82,18 +83,17 @@ impl FrontLayer {
         if self.is_empty() {
             return 0.0;
         }
-        // At most there can be two affected gates in the front layer (one on each qubit in the
-        // swap), since any gate whose closest path passes through the swapped qubit link has its
-        // "virtual-qubit path" order changed, but not the total weight.  In theory, we should
-        // never consider the same gate in both `if let` branches, because if we did, the gate would
-        // already be routable.  It doesn't matter, though, because the two distances would be
-        // equal anyway, so not affect the score.
+        // Only the gates on the qubits of the swap are affected, since any gate whose closest path
+        // passes through the swapped qubit link has its "virtual-qubit path" order changed, but not
+        // the total weight.  In theory, we should never see a gate on both qubits of the swap,
+        // because it would already be routable.  It doesn't matter, though, because the two
+        // distances would be equal anyway, so not affect the score.
         let [a, b] = swap;
         let mut total = 0.0;
-        if let Some((_, c)) = self.qubits[a.index()] {
+        for (_, c) in self.qubits[a.index()].iter() {
             total += dist[[b.index(), c.index()]] - dist[[a.index(), c.index()]]
         }
-        if let Some((_, c)) = self.qubits[b.index()] {
+        for (_, c) in self.qubits[b.index()].iter() {
             total += dist[[a.index(), c.index()]] - dist[[b.index(), c.index()]]
         }
         total / self.nodes.len() as f64
@@ -113,23 +113,31 @@ impl FrontLayer {
     /// Apply a physical swap to the current layout data structure.
     pub fn apply_swap(&mut self, swap: [PhysicalQubit; 2]) {
         let [a, b] = swap;
-        match (self.qubits[a.index()], self.qubits[b.index()]) {
-            (Some((index1, _)), Some((index2, _))) if index1 == index2 => {
-                let entry = self.nodes.get_mut(&index1).unwrap();
-                *entry = [entry[1], entry[0]];
-                return;
+        for (from, to) in [(a, b), (b, a)] {
+            for i in 0..self.qubits[from.index()].len() {
+                let (index, c) = self.qubits[from.index()][i];
+                let entry = self.nodes.get_mut(&index).unwrap();
+                if c == to {
+                    // The node is on both qubits, so it stays put with its qubits reversed.  Its
+                    // other qubit is set to the one it will be on the far side of after the
+                    // qubits' entries are swapped below.  This only happens once per node.
+                    if from == a {
+                        *entry = [entry[1], entry[0]];
+                    }
+                    self.qubits[from.index()][i].1 = from;
+                    continue;
+                }
+                for other in self.qubits[c.index()].iter_mut() {
+                    if other.0 == index {
+                        other.1 = to;
+                    }
+                }
+                *entry = if *entry == [from, c] {
+                    [to, c]
+                } else {
+                    [c, to]
+                };
             }
-            _ => {}
-        }
-        if let Some((index, c)) = self.qubits[a.index()] {
-            self.qubits[c.index()] = Some((index, b));
-            let entry = self.nodes.get_mut(&index).unwrap();
-            *entry = if *entry == [a, c] { [b, c] } else { [c, b] };
-        }
-        if let Some((index, c)) = self.qubits[b.index()] {
-            self.qubits[c.index()] = Some((index, a));
-            let entry = self.nodes.get_mut(&index).unwrap();
-            *entry = if *entry == [b, c] { [a, c] } else { [c, a] };
         }
         self.qubits.swap(a.index(), b.index());
     }
@@ -150,9 +158,14 @@ impl FrontLayer {
         self.nodes.keys()
     }
 
-    /// Iterator over the qubits that have active nodes on them.
+    /// Iterator over the qubits that have active nodes on them, each once, in the order of the
+    /// first node on each.
     pub fn iter_active(&self) -> impl Iterator<Item = &PhysicalQubit> {
-        self.nodes.values().flatten()
+        self.nodes.iter().flat_map(move |(index, qubits)| {
+            qubits
+                .iter()
+                .filter(move |qubit| self.qubits[qubit.index()][0].0 == *index)
+        })
     }
 }
 
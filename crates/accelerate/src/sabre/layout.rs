@@ -11,119 +11,292 @@
 // that they have been altered from the originals.
 #![allow(clippy::too_many_arguments)]
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
+#[cfg(feature = "python")]
+use hashbrown::HashMap;
 use hashbrown::HashSet;
 use ndarray::prelude::*;
-use numpy::{IntoPyArray, PyArray, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray};
 use rand::prelude::*;
 use rand_pcg::Pcg64Mcg;
 use rayon::prelude::*;
 
-use crate::getenv_use_multiple_threads;
+#[cfg(feature = "python")]
+use crate::coupling_map::CouplingMap;
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::instrumentation;
 use crate::nlayout::{NLayout, PhysicalQubit};
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+use crate::rng::SeedSequence;
 
+#[cfg(feature = "python")]
+use super::bridge::BridgeCosts;
 use super::neighbor_table::NeighborTable;
+#[cfg(feature = "python")]
+use super::objectives::ObjectiveWeights;
+use super::objectives::{pareto_front, TrialCriteria, TrialObjectives};
 use super::route::{swap_map, swap_map_trial, RoutingTargetView};
 use super::sabre_dag::SabreDAG;
-use super::swap_map::SwapMap;
-use super::{Heuristic, NodeBlockResults, SabreResult};
+use super::{Heuristic, SabreResult};
 
 use crate::dense_layout::best_subset_inner;
+use crate::threading;
 
+/// Run the full Sabre layout and routing search.
+///
+/// The trials are scored on the number of swaps they add, the depth of the routed circuit and
+/// the estimated error of the added gates, as the negated logarithm of their success rate under
+/// ``error_map``.  The best trial minimizes the sum of the objectives weighted by
+/// ``objective_weights``, a tuple ``(swaps, depth, error)`` that defaults to ``(1.0, 0.0, 0.0)``,
+/// both among the swap trials of each layout and among the layouts.
+///
+/// Returns:
+///     (NLayout, final_permutation, (SwapMap, gate_order, node_block_results, bridges)): the
+///     initial layout, the final permutation and the routing result of the best trial.  If
+///     ``pareto`` is set, a list of the layout trials that no other layout trial beats on every
+///     objective, each as the tuple of its objectives ``(swaps, depth, error)`` followed by the
+///     elements of the tuple above.
+#[cfg(feature = "python")]
 #[pyfunction]
-#[pyo3(signature = (dag, neighbor_table, distance_matrix, heuristic, max_iterations, num_swap_trials, num_random_trials, seed=None, partial_layouts=vec![]))]
+#[pyo3(signature = (
+    dag,
+    coupling_map,
+    heuristic,
+    max_iterations,
+    num_swap_trials,
+    num_random_trials,
+    seed=None,
+    partial_layouts=vec![],
+    error_map=None,
+    objective_weights=None,
+    pareto=false,
+))]
 pub fn sabre_layout_and_routing(
     py: Python,
     dag: &SabreDAG,
-    neighbor_table: &NeighborTable,
-    distance_matrix: PyReadonlyArray2<f64>,
+    coupling_map: &CouplingMap,
     heuristic: Heuristic,
     max_iterations: usize,
     num_swap_trials: usize,
     num_random_trials: usize,
-    seed: Option<u64>,
-    mut partial_layouts: Vec<Vec<Option<u32>>>,
-) -> (NLayout, PyObject, (SwapMap, PyObject, NodeBlockResults)) {
-    let run_in_parallel = getenv_use_multiple_threads();
-    let target = RoutingTargetView {
-        neighbors: neighbor_table,
-        coupling: &neighbor_table.coupling_graph(),
-        distance: distance_matrix.as_array(),
+    seed: Option<PySeed>,
+    partial_layouts: Vec<Vec<Option<u32>>>,
+    error_map: Option<&ErrorMap>,
+    objective_weights: Option<(f64, f64, f64)>,
+    pareto: bool,
+) -> PyObject {
+    let weights: ObjectiveWeights = objective_weights.map(Into::into).unwrap_or_default();
+    let costs = (weights.error != 0.).then(|| {
+        let no_errors = HashMap::new();
+        BridgeCosts::new(
+            coupling_map.neighbor_table(),
+            error_map.map_or(&no_errors, |map| &map.error_map),
+        )
+    });
+    let criteria = TrialCriteria {
+        weights,
+        costs: costs.as_ref(),
     };
+    let result_to_py = |(initial_layout, final_permutation, res): LayoutTrial| {
+        (
+            initial_layout,
+            PyArray::from_vec_bound(py, final_permutation).unbind(),
+            (
+                res.map,
+                res.node_order.into_pyarray_bound(py).unbind(),
+                res.node_block_results,
+                res.bridges,
+            ),
+        )
+    };
+    if pareto {
+        let trials = PySeed::with_sequence(seed, |seeds| {
+            sabre_layout_and_routing_pareto_inner(
+                dag,
+                coupling_map.neighbor_table(),
+                coupling_map.distance_matrix(),
+                heuristic,
+                max_iterations,
+                num_swap_trials,
+                num_random_trials,
+                seeds,
+                partial_layouts,
+                &criteria,
+            )
+        });
+        trials
+            .into_iter()
+            .map(|(objectives, trial)| {
+                let (layout, permutation, routing) = result_to_py(trial);
+                (objectives.as_tuple(), layout, permutation, routing)
+            })
+            .collect::<Vec<_>>()
+            .into_py(py)
+    } else {
+        let res = PySeed::with_sequence(seed, |seeds| {
+            sabre_layout_and_routing_inner(
+                dag,
+                coupling_map.neighbor_table(),
+                coupling_map.distance_matrix(),
+                heuristic,
+                max_iterations,
+                num_swap_trials,
+                num_random_trials,
+                seeds,
+                partial_layouts,
+                &criteria,
+            )
+        });
+        result_to_py(res).into_py(py)
+    }
+}
+
+/// The initial layout, the final permutation of the physical qubits and the routing result of a
+/// layout trial.
+type LayoutTrial = (NLayout, Vec<PhysicalQubit>, SabreResult);
+
+/// The starting layouts of the layout trials: the random ones, which are empty, the partial
+/// layouts and a dense layout.
+fn starting_layouts(
+    num_qubits: usize,
+    target: &RoutingTargetView,
+    num_random_trials: usize,
+    mut partial_layouts: Vec<Vec<Option<u32>>>,
+    run_in_parallel: bool,
+) -> Vec<Vec<Option<u32>>> {
     let mut starting_layouts: Vec<Vec<Option<u32>>> =
         (0..num_random_trials).map(|_| vec![]).collect();
     starting_layouts.append(&mut partial_layouts);
     // Run a dense layout trial
     starting_layouts.push(compute_dense_starting_layout(
+        num_qubits,
+        target,
+        run_in_parallel,
+    ));
+    starting_layouts
+}
+
+/// Run the full Sabre layout and routing search, returning the chosen initial layout, the final
+/// permutation of the physical qubits and the routing result of the best trial under the weights
+/// of `criteria`.
+pub fn sabre_layout_and_routing_inner(
+    dag: &SabreDAG,
+    neighbor_table: &NeighborTable,
+    distance_matrix: ArrayView2<f64>,
+    heuristic: Heuristic,
+    max_iterations: usize,
+    num_swap_trials: usize,
+    num_random_trials: usize,
+    seeds: &mut SeedSequence,
+    partial_layouts: Vec<Vec<Option<u32>>>,
+    criteria: &TrialCriteria,
+) -> LayoutTrial {
+    criteria.best(layout_trials(
+        dag,
+        neighbor_table,
+        distance_matrix,
+        heuristic,
+        max_iterations,
+        num_swap_trials,
+        num_random_trials,
+        seeds,
+        partial_layouts,
+        criteria,
+    ))
+}
+
+/// Run the full Sabre layout and routing search, returning the Pareto set of the layout trials
+/// under their objectives, in the order of the trials.
+pub fn sabre_layout_and_routing_pareto_inner(
+    dag: &SabreDAG,
+    neighbor_table: &NeighborTable,
+    distance_matrix: ArrayView2<f64>,
+    heuristic: Heuristic,
+    max_iterations: usize,
+    num_swap_trials: usize,
+    num_random_trials: usize,
+    seeds: &mut SeedSequence,
+    partial_layouts: Vec<Vec<Option<u32>>>,
+    criteria: &TrialCriteria,
+) -> Vec<(TrialObjectives, LayoutTrial)> {
+    pareto_front(layout_trials(
+        dag,
+        neighbor_table,
+        distance_matrix,
+        heuristic,
+        max_iterations,
+        num_swap_trials,
+        num_random_trials,
+        seeds,
+        partial_layouts,
+        criteria,
+    ))
+}
+
+/// Run (potentially in parallel) every layout trial, returning each with its objectives, in the
+/// order of the starting layouts.
+fn layout_trials(
+    dag: &SabreDAG,
+    neighbor_table: &NeighborTable,
+    distance_matrix: ArrayView2<f64>,
+    heuristic: Heuristic,
+    max_iterations: usize,
+    num_swap_trials: usize,
+    num_random_trials: usize,
+    seeds: &mut SeedSequence,
+    partial_layouts: Vec<Vec<Option<u32>>>,
+    criteria: &TrialCriteria,
+) -> Vec<(TrialObjectives, LayoutTrial)> {
+    let _timer = instrumentation::Timer::start("sabre_layout");
+    let run_in_parallel = threading::use_multiple_threads();
+    let coupling = neighbor_table.coupling_graph();
+    let target = RoutingTargetView {
+        neighbors: neighbor_table,
+        coupling: &coupling,
+        distance: distance_matrix.view(),
+        bridges: None,
+    };
+    let starting_layouts = starting_layouts(
         dag.num_qubits,
         &target,
+        num_random_trials,
+        partial_layouts,
         run_in_parallel,
-    ));
-    let outer_rng = match seed {
-        Some(seed) => Pcg64Mcg::seed_from_u64(seed),
-        None => Pcg64Mcg::from_entropy(),
+    );
+    instrumentation::add_trials("sabre_layout", starting_layouts.len());
+    let run_trial = |(index, seed_trial): (usize, u64)| {
+        let trial = layout_trial(
+            &target,
+            dag,
+            heuristic,
+            seed_trial,
+            max_iterations,
+            num_swap_trials,
+            run_in_parallel,
+            &starting_layouts[index],
+            criteria,
+        );
+        (criteria.objectives(dag, &trial.0, &trial.2), trial)
     };
-    let seed_vec: Vec<u64> = outer_rng
-        .sample_iter(&rand::distributions::Standard)
-        .take(starting_layouts.len())
-        .collect();
-    let res = if run_in_parallel && starting_layouts.len() > 1 {
-        seed_vec
-            .into_par_iter()
-            .enumerate()
-            .map(|(index, seed_trial)| {
-                (
-                    index,
-                    layout_trial(
-                        &target,
-                        dag,
-                        heuristic,
-                        seed_trial,
-                        max_iterations,
-                        num_swap_trials,
-                        run_in_parallel,
-                        &starting_layouts[index],
-                    ),
-                )
-            })
-            .min_by_key(|(index, (_, _, result))| {
-                (
-                    result.map.map.values().map(|x| x.len()).sum::<usize>(),
-                    *index,
-                )
-            })
-            .unwrap()
-            .1
+    let seed_vec = seeds.seeds(starting_layouts.len());
+    if run_in_parallel && starting_layouts.len() > 1 {
+        threading::install(|| {
+            seed_vec
+                .into_par_iter()
+                .enumerate()
+                .map(run_trial)
+                .collect()
+        })
     } else {
-        seed_vec
-            .into_iter()
-            .enumerate()
-            .map(|(index, seed_trial)| {
-                layout_trial(
-                    &target,
-                    dag,
-                    heuristic,
-                    seed_trial,
-                    max_iterations,
-                    num_swap_trials,
-                    run_in_parallel,
-                    &starting_layouts[index],
-                )
-            })
-            .min_by_key(|(_, _, result)| result.map.map.values().map(|x| x.len()).sum::<usize>())
-            .unwrap()
-    };
-    (
-        res.0,
-        PyArray::from_vec_bound(py, res.1).into(),
-        (
-            res.2.map,
-            res.2.node_order.into_pyarray_bound(py).into(),
-            res.2.node_block_results,
-        ),
-    )
+        seed_vec.into_iter().enumerate().map(run_trial).collect()
+    }
 }
 
 fn layout_trial(
@@ -135,7 +308,8 @@ fn layout_trial(
     num_swap_trials: usize,
     run_swap_in_parallel: bool,
     starting_layout: &[Option<u32>],
-) -> (NLayout, Vec<PhysicalQubit>, SabreResult) {
+    criteria: &TrialCriteria,
+) -> LayoutTrial {
     let num_physical_qubits: u32 = target.neighbors.num_qubits().try_into().unwrap();
     let mut rng = Pcg64Mcg::seed_from_u64(seed);
 
@@ -207,9 +381,10 @@ fn layout_trial(
         dag,
         heuristic,
         &initial_layout,
-        Some(seed),
+        &mut SeedSequence::new(Some(seed.into())),
         num_swap_trials,
         Some(run_swap_in_parallel),
+        criteria,
     );
     let final_permutation = initial_layout
         .iter_physical()
@@ -225,7 +400,7 @@ fn compute_dense_starting_layout(
 ) -> Vec<Option<u32>> {
     let mut adj_matrix = target.distance.to_owned();
     if run_in_parallel {
-        adj_matrix.par_mapv_inplace(|x| if x == 1. { 1. } else { 0. });
+        threading::install(|| adj_matrix.par_mapv_inplace(|x| if x == 1. { 1. } else { 0. }));
     } else {
         adj_matrix.mapv_inplace(|x| if x == 1. { 1. } else { 0. });
     }
@@ -10,26 +10,37 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+mod bridge;
 mod layer;
 mod layout;
 mod neighbor_table;
+mod objectives;
 mod route;
 mod sabre_dag;
 mod swap_map;
 
 use hashbrown::HashMap;
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, ToPyArray};
+#[cfg(feature = "python")]
 use pyo3::exceptions::PyIndexError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
 use crate::nlayout::PhysicalQubit;
-use neighbor_table::NeighborTable;
-use sabre_dag::SabreDAG;
-use swap_map::SwapMap;
+pub use bridge::BridgeCosts;
+pub use layout::sabre_layout_and_routing_inner;
+pub use neighbor_table::NeighborTable;
+pub use objectives::{pareto_front, ObjectiveWeights, TrialCriteria, TrialObjectives};
+pub use route::sabre_routing_inner;
+pub use sabre_dag::{DAGNode, SabreDAG};
+pub use swap_map::SwapMap;
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass)]
 #[derive(Clone, Copy)]
 pub enum Heuristic {
     Basic,
@@ -38,30 +49,59 @@ pub enum Heuristic {
 }
 
 /// A container for Sabre mapping results.
-#[pyclass(module = "qiskit._accelerate.sabre")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.sabre"))]
 #[derive(Clone, Debug)]
 pub struct SabreResult {
-    #[pyo3(get)]
     pub map: SwapMap,
     pub node_order: Vec<usize>,
-    #[pyo3(get)]
     pub node_block_results: NodeBlockResults,
+    /// Map of the node ids of the CX gates that are realized with a bridge to the physical qubit
+    /// that each bridge goes through.
+    pub bridges: HashMap<usize, PhysicalQubit>,
 }
 
+impl SabreResult {
+    /// The number of two-qubit gates added by the routing, in units of swaps: both a swap and a
+    /// bridge add three CX gates.
+    pub fn num_added(&self) -> usize {
+        self.map.map.values().map(|x| x.len()).sum::<usize>() + self.bridges.len()
+    }
+}
+
+#[cfg(feature = "python")]
 #[pymethods]
 impl SabreResult {
+    #[getter]
+    fn map(&self) -> SwapMap {
+        self.map.clone()
+    }
+
+    #[getter]
+    fn node_block_results(&self) -> NodeBlockResults {
+        self.node_block_results.clone()
+    }
+
     #[getter]
     fn node_order(&self, py: Python) -> PyObject {
         self.node_order.to_pyarray_bound(py).into()
     }
+
+    #[getter]
+    fn bridges(&self) -> HashMap<usize, PhysicalQubit> {
+        self.bridges.clone()
+    }
 }
 
-#[pyclass(mapping, module = "qiskit._accelerate.sabre")]
+#[cfg_attr(
+    feature = "python",
+    pyclass(mapping, module = "qiskit._accelerate.sabre")
+)]
 #[derive(Clone, Debug)]
 pub struct NodeBlockResults {
     pub results: HashMap<usize, Vec<BlockResult>>,
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl NodeBlockResults {
     // Mapping Protocol
@@ -92,16 +132,21 @@ impl NodeBlockResults {
     }
 }
 
-#[pyclass(module = "qiskit._accelerate.sabre")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.sabre"))]
 #[derive(Clone, Debug)]
 pub struct BlockResult {
-    #[pyo3(get)]
     pub result: SabreResult,
     pub swap_epilogue: Vec<[PhysicalQubit; 2]>,
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl BlockResult {
+    #[getter]
+    fn result(&self) -> SabreResult {
+        self.result.clone()
+    }
+
     #[getter]
     fn swap_epilogue(&self, py: Python) -> PyObject {
         self.swap_epilogue
@@ -113,6 +158,7 @@ impl BlockResult {
     }
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn sabre(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(route::sabre_routing))?;
@@ -10,16 +10,20 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
-use crate::getenv_use_multiple_threads;
 use ndarray::prelude::*;
+#[cfg(feature = "python")]
 use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::PyList;
 use rayon::prelude::*;
 use rustworkx_core::petgraph::prelude::*;
 use smallvec::SmallVec;
 
+use crate::error::AccelerateError;
 use crate::nlayout::PhysicalQubit;
+use crate::threading;
 
 /// A simple container that contains a vector of vectors representing
 /// neighbors of each node in the coupling map
@@ -31,7 +35,7 @@ use crate::nlayout::PhysicalQubit;
 ///
 /// and used solely to represent neighbors of each node in qiskit-terra's rust
 /// module.
-#[pyclass(module = "qiskit._accelerate.sabre")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.sabre"))]
 #[derive(Clone, Debug)]
 pub struct NeighborTable {
     // The choice of 4 `PhysicalQubit`s in the stack-allocated region is because a) this causes the
@@ -54,6 +58,48 @@ impl NeighborTable {
     pub fn num_qubits(&self) -> usize {
         self.neighbors.len()
     }
+
+    /// Build the table from the list of the neighbors of each qubit.
+    pub fn from_neighbors(neighbors: Vec<SmallVec<[PhysicalQubit; 4]>>) -> Self {
+        NeighborTable { neighbors }
+    }
+
+    /// Build the table from a dense adjacency matrix, where any non-zero entry `(i, j)` marks `j`
+    /// as a neighbor of `i`.
+    pub fn from_adjacency_matrix(adj_mat: ArrayView2<f64>) -> Result<Self, AccelerateError> {
+        let run_in_parallel = threading::use_multiple_threads();
+        let build_neighbors =
+            |row: ArrayView1<f64>| -> Result<SmallVec<[PhysicalQubit; 4]>, AccelerateError> {
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(row_index, value)| {
+                        if *value == 0. {
+                            None
+                        } else {
+                            Some(match row_index.try_into() {
+                                Ok(index) => Ok(PhysicalQubit::new(index)),
+                                Err(err) => Err(AccelerateError::Overflow(format!("{}", err))),
+                            })
+                        }
+                    })
+                    .collect()
+            };
+        let neighbors = if run_in_parallel {
+            threading::install(|| {
+                adj_mat
+                    .axis_iter(Axis(0))
+                    .into_par_iter()
+                    .map(build_neighbors)
+                    .collect::<Result<_, _>>()
+            })?
+        } else {
+            adj_mat
+                .axis_iter(Axis(0))
+                .map(build_neighbors)
+                .collect::<Result<_, _>>()?
+        };
+        Ok(NeighborTable { neighbors })
+    }
 }
 
 impl std::ops::Index<PhysicalQubit> for NeighborTable {
@@ -64,47 +110,20 @@ impl std::ops::Index<PhysicalQubit> for NeighborTable {
     }
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl NeighborTable {
     #[new]
     #[pyo3(text_signature = "(/, adjacency_matrix=None)")]
-    pub fn new(adjacency_matrix: Option<PyReadonlyArray2<f64>>) -> PyResult<Self> {
-        let run_in_parallel = getenv_use_multiple_threads();
-        let neighbors = match adjacency_matrix {
-            Some(adjacency_matrix) => {
-                let adj_mat = adjacency_matrix.as_array();
-                let build_neighbors =
-                    |row: ArrayView1<f64>| -> PyResult<SmallVec<[PhysicalQubit; 4]>> {
-                        row.iter()
-                            .enumerate()
-                            .filter_map(|(row_index, value)| {
-                                if *value == 0. {
-                                    None
-                                } else {
-                                    Some(match row_index.try_into() {
-                                        Ok(index) => Ok(PhysicalQubit::new(index)),
-                                        Err(err) => Err(err.into()),
-                                    })
-                                }
-                            })
-                            .collect()
-                    };
-                if run_in_parallel {
-                    adj_mat
-                        .axis_iter(Axis(0))
-                        .into_par_iter()
-                        .map(build_neighbors)
-                        .collect::<PyResult<_>>()?
-                } else {
-                    adj_mat
-                        .axis_iter(Axis(0))
-                        .map(build_neighbors)
-                        .collect::<PyResult<_>>()?
-                }
-            }
-            None => Vec::new(),
-        };
-        Ok(NeighborTable { neighbors })
+    fn py_new(adjacency_matrix: Option<PyReadonlyArray2<f64>>) -> PyResult<Self> {
+        match adjacency_matrix {
+            Some(adjacency_matrix) => Ok(NeighborTable::from_adjacency_matrix(
+                adjacency_matrix.as_array(),
+            )?),
+            None => Ok(NeighborTable {
+                neighbors: Vec::new(),
+            }),
+        }
     }
 
     fn __getstate__(&self, py: Python<'_>) -> Py<PyList> {
@@ -0,0 +1,329 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The objectives that the trials of Sabre are scored on, and the choice between the trials.
+//!
+//! Each trial is scored on the number of swaps and bridges it adds, the depth of the routed
+//! circuit and the estimated error of the gates it adds.  The winner is the trial with the least
+//! weighted sum of its objectives, where the default weights count only the swaps, and ties go to
+//! the earliest trial so that the choice doesn't depend on the threading.  Alternatively, the
+//! Pareto set of the trials, those that no other trial is at least as good as on every objective
+//! and better on one, leaves the trade-off to the caller.
+
+use std::cmp::Ordering;
+
+use hashbrown::HashMap;
+use smallvec::SmallVec;
+
+use crate::nlayout::{NLayout, PhysicalQubit, VirtualQubit};
+
+use super::bridge::BridgeCosts;
+use super::sabre_dag::SabreDAG;
+use super::SabreResult;
+
+/// The scores of a routing trial.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrialObjectives {
+    /// The number of swaps and bridges added, as [SabreResult::num_added] counts them.
+    pub swaps: usize,
+    /// The depth of the routed circuit, where each swap and bridge is one layer on its qubits and
+    /// the directives are ignored.
+    pub depth: usize,
+    /// The estimated error of the added gates, as the negated logarithm of their success rate.
+    pub error: f64,
+}
+
+impl TrialObjectives {
+    /// Whether these objectives are at least as good as `other` on each objective, and better on
+    /// one.
+    pub fn dominates(&self, other: &TrialObjectives) -> bool {
+        self.swaps <= other.swaps
+            && self.depth <= other.depth
+            && self.error <= other.error
+            && (self.swaps < other.swaps || self.depth < other.depth || self.error < other.error)
+    }
+
+    /// The objectives as a tuple of the swaps, the depth and the error.
+    pub fn as_tuple(&self) -> (usize, usize, f64) {
+        (self.swaps, self.depth, self.error)
+    }
+}
+
+/// The weights of the objectives in the score of a trial.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObjectiveWeights {
+    pub swaps: f64,
+    pub depth: f64,
+    pub error: f64,
+}
+
+impl Default for ObjectiveWeights {
+    /// Score the trials on the number of swaps alone.
+    fn default() -> Self {
+        ObjectiveWeights {
+            swaps: 1.,
+            depth: 0.,
+            error: 0.,
+        }
+    }
+}
+
+impl From<(f64, f64, f64)> for ObjectiveWeights {
+    fn from((swaps, depth, error): (f64, f64, f64)) -> Self {
+        ObjectiveWeights {
+            swaps,
+            depth,
+            error,
+        }
+    }
+}
+
+impl ObjectiveWeights {
+    /// The weighted sum of the objectives.
+    #[inline]
+    pub fn score(&self, objectives: &TrialObjectives) -> f64 {
+        self.swaps * objectives.swaps as f64
+            + self.depth * objectives.depth as f64
+            + self.error * objectives.error
+    }
+}
+
+/// How the trials of the routing are scored and compared.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrialCriteria<'a> {
+    pub weights: ObjectiveWeights,
+    /// The costs of the gates on the edges, for the error objective.  Without them, every gate
+    /// costs one, so the error is proportional to the number of swaps.
+    pub costs: Option<&'a BridgeCosts>,
+}
+
+impl<'a> TrialCriteria<'a> {
+    /// Score a trial of routing `dag` from `initial_layout`, by replaying its swaps in the order of
+    /// its nodes.
+    pub fn objectives(
+        &self,
+        dag: &SabreDAG,
+        initial_layout: &NLayout,
+        result: &SabreResult,
+    ) -> TrialObjectives {
+        let nodes: HashMap<usize, (&[VirtualQubit], bool)> = dag
+            .dag
+            .node_weights()
+            .map(|node| (node.py_node_id, (node.qubits.as_slice(), node.directive)))
+            .collect();
+        let gate = |a: PhysicalQubit, b: PhysicalQubit| self.costs.map_or(1., |c| c.gate(a, b));
+        let mean = self.costs.map_or(1., |costs| costs.mean());
+        let mut layout = initial_layout.clone();
+        // The depth of the routed circuit on each physical qubit so far.
+        let mut depths: Vec<usize> = Vec::new();
+        let mut push_layer = |qubits: &[PhysicalQubit]| {
+            if let Some(max) = qubits.iter().map(|q| q.index()).max() {
+                if max >= depths.len() {
+                    depths.resize(max + 1, 0);
+                }
+            }
+            let layer = 1 + qubits.iter().map(|q| depths[q.index()]).max().unwrap_or(0);
+            for qubit in qubits {
+                depths[qubit.index()] = layer;
+            }
+        };
+        let mut error = 0.;
+        for py_node_id in result.node_order.iter() {
+            if let Some(swaps) = result.map.map.get(py_node_id) {
+                for &[a, b] in swaps {
+                    push_layer(&[a, b]);
+                    error += 3. * gate(a, b);
+                    layout.swap_physical(a, b);
+                }
+            }
+            let Some((qubits, directive)) = nodes.get(py_node_id) else {
+                continue;
+            };
+            let mut physical = qubits
+                .iter()
+                .map(|q| q.to_phys(&layout))
+                .collect::<SmallVec<[PhysicalQubit; 3]>>();
+            if let Some(middle) = result.bridges.get(py_node_id) {
+                // The four gates of the bridge replace the CX, which a swap would have left to be
+                // paid for on top of its own three.
+                error += 2. * (gate(physical[0], *middle) + gate(*middle, physical[1])) - mean;
+                physical.push(*middle);
+            }
+            if !directive {
+                push_layer(&physical);
+            }
+        }
+        TrialObjectives {
+            swaps: result.num_added(),
+            depth: depths.into_iter().max().unwrap_or(0),
+            error,
+        }
+    }
+
+    /// Compare two indexed trials by their weighted scores, preferring the earlier on a tie.
+    #[inline]
+    pub fn compare(
+        &self,
+        left: (usize, &TrialObjectives),
+        right: (usize, &TrialObjectives),
+    ) -> Ordering {
+        self.weights
+            .score(left.1)
+            .total_cmp(&self.weights.score(right.1))
+            .then(left.0.cmp(&right.0))
+    }
+
+    /// The best of `trials` by their weighted scores, the earliest of those that tie.
+    ///
+    /// # Panics
+    ///
+    /// If there are no trials.
+    pub fn best<T>(&self, trials: Vec<(TrialObjectives, T)>) -> T {
+        let (_, (_, trial)) = trials
+            .into_iter()
+            .enumerate()
+            .min_by(|(i, (a, _)), (j, (b, _))| self.compare((*i, a), (*j, b)))
+            .expect("there should be at least one trial");
+        trial
+    }
+}
+
+/// The trials that no other trial dominates, in their original order, keeping only the first of
+/// the trials with equal objectives.
+pub fn pareto_front<T>(trials: Vec<(TrialObjectives, T)>) -> Vec<(TrialObjectives, T)> {
+    let keep = trials
+        .iter()
+        .enumerate()
+        .map(|(index, (objectives, _))| {
+            trials.iter().enumerate().all(|(other_index, (other, _))| {
+                !(other.dominates(objectives) || (other_index < index && other == objectives))
+            })
+        })
+        .collect::<Vec<_>>();
+    trials
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(trial, keep)| keep.then_some(trial))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sabre::{NodeBlockResults, SwapMap};
+    use hashbrown::HashSet;
+
+    fn objectives(swaps: usize, depth: usize, error: f64) -> TrialObjectives {
+        TrialObjectives {
+            swaps,
+            depth,
+            error,
+        }
+    }
+
+    #[test]
+    fn test_dominates() {
+        let base = objectives(2, 5, 1.0);
+        assert!(objectives(1, 5, 1.0).dominates(&base));
+        assert!(objectives(2, 4, 0.5).dominates(&base));
+        assert!(!base.dominates(&base));
+        assert!(!objectives(1, 6, 1.0).dominates(&base));
+        assert!(!base.dominates(&objectives(1, 6, 1.0)));
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let trials = vec![
+            (objectives(3, 4, 1.0), 0),
+            (objectives(2, 5, 1.0), 1),
+            (objectives(3, 5, 1.0), 2),
+            (objectives(2, 5, 1.0), 3),
+            (objectives(4, 3, 2.0), 4),
+        ];
+        let front = pareto_front(trials)
+            .into_iter()
+            .map(|(_, index)| index)
+            .collect::<Vec<_>>();
+        // Trial 2 is dominated by both 0 and 1, and trial 3 is equal to the earlier trial 1.
+        assert_eq!(front, vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn test_weighted_compare() {
+        let criteria = TrialCriteria {
+            weights: (1., 0.5, 0.).into(),
+            costs: None,
+        };
+        let a = objectives(2, 4, 0.);
+        let b = objectives(3, 2, 0.);
+        let c = objectives(1, 6, 0.);
+        assert_eq!(criteria.weights.score(&a), 4.);
+        // The scores are all equal, so the earlier trial wins.
+        assert_eq!(criteria.compare((0, &a), (1, &b)), Ordering::Less);
+        assert_eq!(criteria.compare((1, &b), (0, &a)), Ordering::Greater);
+        assert_eq!(criteria.compare((2, &c), (0, &a)), Ordering::Greater);
+        let default = TrialCriteria::default();
+        assert_eq!(default.compare((2, &c), (0, &a)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_best() {
+        let criteria = TrialCriteria {
+            weights: (1., 1., 0.).into(),
+            costs: None,
+        };
+        let trials = vec![
+            (objectives(3, 4, 0.), 0),
+            (objectives(2, 4, 0.), 1),
+            (objectives(1, 5, 0.), 2),
+            (objectives(4, 3, 0.), 3),
+        ];
+        // Trials 1, 2 and 3 all score 6, so the earliest of them wins.
+        assert_eq!(criteria.best(trials), 1);
+    }
+
+    #[test]
+    fn test_trial_objectives() {
+        let qubits = |qubits: &[u32]| -> Vec<VirtualQubit> {
+            qubits.iter().copied().map(VirtualQubit::new).collect()
+        };
+        let dag = SabreDAG::new(
+            3,
+            0,
+            vec![
+                (0, qubits(&[0, 1]), HashSet::new(), false),
+                (1, qubits(&[0, 2]), HashSet::new(), false),
+                (2, qubits(&[0, 1, 2]), HashSet::new(), true),
+            ],
+            HashMap::new(),
+        )
+        .unwrap();
+        let swap = [PhysicalQubit::new(1), PhysicalQubit::new(2)];
+        let result = SabreResult {
+            map: SwapMap {
+                map: [(1, vec![swap])].into_iter().collect(),
+            },
+            node_order: vec![0, 1, 2],
+            node_block_results: NodeBlockResults {
+                results: HashMap::new(),
+            },
+            bridges: HashMap::new(),
+        };
+        let layout = NLayout::generate_trivial_layout(3);
+        // The swap follows the first CX on qubit 1, and the second CX follows the swap on the
+        // physical qubits 0 and 1; the barrier adds no depth.
+        assert_eq!(
+            TrialCriteria::default().objectives(&dag, &layout, &result),
+            objectives(1, 3, 3.)
+        );
+    }
+}
@@ -12,13 +12,16 @@
 
 use std::cmp::Ordering;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::Python;
 
 use hashbrown::HashMap;
 use indexmap::IndexMap;
 use ndarray::prelude::*;
-use numpy::{IntoPyArray, PyArray, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray};
 use rand::prelude::*;
 use rand_pcg::Pcg64Mcg;
 use rayon::prelude::*;
@@ -28,11 +31,23 @@ use rustworkx_core::petgraph::visit::EdgeRef;
 use rustworkx_core::shortest_path::dijkstra;
 use rustworkx_core::token_swapper::token_swapper;
 
-use crate::getenv_use_multiple_threads;
+#[cfg(feature = "python")]
+use crate::coupling_map::CouplingMap;
+#[cfg(feature = "python")]
+use crate::error_map::ErrorMap;
+use crate::instrumentation;
 use crate::nlayout::{NLayout, PhysicalQubit};
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+use crate::rng::SeedSequence;
+use crate::threading;
 
+use super::bridge::BridgeCosts;
 use super::layer::{ExtendedSet, FrontLayer};
 use super::neighbor_table::NeighborTable;
+#[cfg(feature = "python")]
+use super::objectives::ObjectiveWeights;
+use super::objectives::{pareto_front, TrialCriteria, TrialObjectives};
 use super::sabre_dag::SabreDAG;
 use super::swap_map::SwapMap;
 use super::{BlockResult, Heuristic, NodeBlockResults, SabreResult};
@@ -58,6 +73,9 @@ pub struct RoutingTargetView<'a> {
     pub neighbors: &'a NeighborTable,
     pub coupling: &'a DiGraph<(), ()>,
     pub distance: ArrayView2<'a, f64>,
+    /// The costs of the gates on the edges, if the CX gates two qubits apart may be realized with
+    /// bridges rather than swaps.
+    pub bridges: Option<&'a BridgeCosts>,
 }
 
 /// Long-term internal state of the Sabre routing algorithm.  This includes all the scratch space
@@ -74,6 +92,8 @@ struct RoutingState<'a, 'b> {
     gate_order: Vec<usize>,
     /// Map from node index of a control-flow op to its inner result.
     node_block_results: HashMap<usize, Vec<BlockResult>>,
+    /// Map of the node ids of the bridged CX gates to the physical qubit each bridge goes through.
+    bridges: HashMap<usize, PhysicalQubit>,
     front_layer: FrontLayer,
     extended_set: ExtendedSet,
     /// How many predecessors still need to be satisfied for each node index before it is at the
@@ -100,15 +120,21 @@ impl<'a, 'b> RoutingState<'a, 'b> {
         self.layout.swap_physical(swap[0], swap[1]);
     }
 
-    /// Return the node, if any, that is on this qubit and is routable with the current layout.
+    /// Return the nodes, if any, that are on this qubit and are routable with the current layout.
     #[inline]
-    fn routable_node_on_qubit(&self, qubit: PhysicalQubit) -> Option<NodeIndex> {
-        self.front_layer.qubits()[qubit.index()].and_then(|(node, other)| {
-            self.target
-                .coupling
-                .contains_edge(NodeIndex::new(qubit.index()), NodeIndex::new(other.index()))
-                .then_some(node)
-        })
+    fn routable_nodes_on_qubit(
+        &self,
+        qubit: PhysicalQubit,
+    ) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.front_layer
+            .on_qubit(qubit)
+            .iter()
+            .filter_map(move |(node, other)| {
+                self.target
+                    .coupling
+                    .contains_edge(NodeIndex::new(qubit.index()), NodeIndex::new(other.index()))
+                    .then_some(*node)
+            })
     }
 
     /// Update the system state as the given `nodes` are added to the routing order, preceded by
@@ -160,10 +186,11 @@ impl<'a, 'b> RoutingState<'a, 'b> {
                         // A gate op whose connectivity must match the device to be placed in the
                         // gate order.
                         [a, b]
-                            if !self.target.coupling.contains_edge(
-                                NodeIndex::new(a.to_phys(&self.layout).index()),
-                                NodeIndex::new(b.to_phys(&self.layout).index()),
-                            ) =>
+                            if !self.bridges.contains_key(&node.py_node_id)
+                                && !self.target.coupling.contains_edge(
+                                    NodeIndex::new(a.to_phys(&self.layout).index()),
+                                    NodeIndex::new(b.to_phys(&self.layout).index()),
+                                ) =>
                         {
                             // 2Q op that cannot be placed. Add it to the front layer and move on.
                             self.front_layer.insert(
@@ -308,7 +335,7 @@ impl<'a, 'b> RoutingState<'a, 'b> {
                 Some(NodeIndex::new(qubits[1].index())),
                 |_| Ok(1.),
                 Some(&mut shortest_paths),
-            ) as PyResult<Vec<Option<f64>>>)
+            ) as Result<Vec<Option<f64>>, std::convert::Infallible>)
                 .unwrap();
             shortest_paths
                 .get(&NodeIndex::new(qubits[1].index()))
@@ -333,6 +360,41 @@ impl<'a, 'b> RoutingState<'a, 'b> {
         closest_node
     }
 
+    /// Return the first CX gate of the front layer, with the qubit to bridge it through, whose
+    /// bridge is cheaper than bringing its qubits together with a swap, if bridges are enabled.
+    ///
+    /// A swap costs its three gates and the gate itself, plus the change it makes to the distances
+    /// of the gates of the extended set, weighted as in the lookahead heuristic.  A bridge costs
+    /// its four gates and leaves the layout as it is.  Ties go to the swap.
+    fn choose_bridge(&self) -> Option<(NodeIndex, PhysicalQubit)> {
+        let costs = self.target.bridges?;
+        let dist = &self.target.distance;
+        let neighbors = self.target.neighbors;
+        self.front_layer.iter().find_map(|(node, &[a, c])| {
+            if !self.dag.dag[*node].bridgeable {
+                return None;
+            }
+            let mut bridge: Option<(PhysicalQubit, f64)> = None;
+            let mut swap = f64::INFINITY;
+            for &b in neighbors[a].iter().filter(|b| neighbors[c].contains(b)) {
+                let (ab, bc) = (costs.gate(a, b), costs.gate(b, c));
+                let cost = 2. * (ab + bc);
+                if bridge.map_or(true, |(_, best)| cost < best) {
+                    bridge = Some((b, cost));
+                }
+                for (moved, gate) in [([a, b], bc), ([c, b], ab)] {
+                    let lookahead = EXTENDED_SET_WEIGHT
+                        * self.extended_set.len() as f64
+                        * self.extended_set.score(moved, dist)
+                        * costs.distance();
+                    swap = swap.min(3. * costs.gate(moved[0], moved[1]) + gate + lookahead);
+                }
+            }
+            let (middle, cost) = bridge?;
+            (cost < swap).then_some((*node, middle))
+        })
+    }
+
     /// Return the swap of two virtual qubits that produces the best score of all possible swaps.
     fn choose_best_swap(&mut self) -> [PhysicalQubit; 2] {
         self.swap_scratch.clear();
@@ -392,105 +454,311 @@ fn obtain_swaps<'a>(
     })
 }
 
+/// The final permutation of a routing from `initial_layout` to `final_layout`, where
+/// `final_permutation[i]` is the final logical position of the qubit that began in position `i`.
+fn final_permutation(
+    neighbor_table: &NeighborTable,
+    initial_layout: &NLayout,
+    final_layout: &NLayout,
+) -> Vec<PhysicalQubit> {
+    (0u32..neighbor_table.num_qubits().try_into().unwrap())
+        .map(|phys| {
+            PhysicalQubit::new(phys)
+                .to_virt(initial_layout)
+                .to_phys(final_layout)
+        })
+        .collect()
+}
+
 /// Run sabre swap on a circuit
 ///
-/// Returns:
-///     (SwapMap, gate_order, node_block_results, final_permutation): A tuple where the first
-///     element is a mapping of DAGCircuit node ids to a list of virtual qubit swaps that should be
-///     added before that operation. The second element is a numpy array of node ids that
-///     represents the traversal order used by sabre.  The third is inner results for the blocks of
-///     control flow, and the fourth is a permutation, where `final_permution[i]` is the final
-///     logical position of the qubit that began in position `i`.
-#[pyfunction]
+/// Returns the routing result of the best trial under the weights of `criteria` and the final
+/// permutation, where `final_permutation[i]` is the final logical position of the qubit that began
+/// in position `i`.
 #[allow(clippy::too_many_arguments)]
-pub fn sabre_routing(
-    py: Python,
+pub fn sabre_routing_inner(
     dag: &SabreDAG,
     neighbor_table: &NeighborTable,
-    distance_matrix: PyReadonlyArray2<f64>,
+    distance_matrix: ArrayView2<f64>,
     heuristic: Heuristic,
     initial_layout: &NLayout,
     num_trials: usize,
-    seed: Option<u64>,
+    seeds: &mut SeedSequence,
     run_in_parallel: Option<bool>,
-) -> (SwapMap, PyObject, NodeBlockResults, PyObject) {
+    bridges: Option<&BridgeCosts>,
+    criteria: &TrialCriteria,
+) -> (SabreResult, Vec<PhysicalQubit>) {
+    criteria.best(routing_trials(
+        dag,
+        neighbor_table,
+        distance_matrix,
+        heuristic,
+        initial_layout,
+        num_trials,
+        seeds,
+        run_in_parallel,
+        bridges,
+        criteria,
+    ))
+}
+
+/// Run sabre swap on a circuit, returning the Pareto set of the trials under their objectives,
+/// each with its routing result and final permutation.
+#[allow(clippy::too_many_arguments)]
+pub fn sabre_routing_pareto_inner(
+    dag: &SabreDAG,
+    neighbor_table: &NeighborTable,
+    distance_matrix: ArrayView2<f64>,
+    heuristic: Heuristic,
+    initial_layout: &NLayout,
+    num_trials: usize,
+    seeds: &mut SeedSequence,
+    run_in_parallel: Option<bool>,
+    bridges: Option<&BridgeCosts>,
+    criteria: &TrialCriteria,
+) -> Vec<(TrialObjectives, SabreResult, Vec<PhysicalQubit>)> {
+    pareto_front(routing_trials(
+        dag,
+        neighbor_table,
+        distance_matrix,
+        heuristic,
+        initial_layout,
+        num_trials,
+        seeds,
+        run_in_parallel,
+        bridges,
+        criteria,
+    ))
+    .into_iter()
+    .map(|(objectives, (res, final_permutation))| (objectives, res, final_permutation))
+    .collect()
+}
+
+/// Run sabre swap on a circuit, returning every trial with its objectives, its routing result and
+/// its final permutation, in the order of the trials.
+#[allow(clippy::too_many_arguments)]
+fn routing_trials(
+    dag: &SabreDAG,
+    neighbor_table: &NeighborTable,
+    distance_matrix: ArrayView2<f64>,
+    heuristic: Heuristic,
+    initial_layout: &NLayout,
+    num_trials: usize,
+    seeds: &mut SeedSequence,
+    run_in_parallel: Option<bool>,
+    bridges: Option<&BridgeCosts>,
+    criteria: &TrialCriteria,
+) -> Vec<(TrialObjectives, (SabreResult, Vec<PhysicalQubit>))> {
+    let coupling = neighbor_table.coupling_graph();
     let target = RoutingTargetView {
         neighbors: neighbor_table,
-        coupling: &neighbor_table.coupling_graph(),
-        distance: distance_matrix.as_array(),
+        coupling: &coupling,
+        distance: distance_matrix.view(),
+        bridges,
     };
-    let (res, final_layout) = swap_map(
+    swap_map_trials(
         &target,
         dag,
         heuristic,
         initial_layout,
-        seed,
+        seeds,
         num_trials,
         run_in_parallel,
-    );
-    (
-        res.map,
-        res.node_order.into_pyarray_bound(py).into(),
-        res.node_block_results,
-        PyArray::from_iter_bound(
-            py,
-            (0u32..neighbor_table.num_qubits().try_into().unwrap()).map(|phys| {
-                PhysicalQubit::new(phys)
-                    .to_virt(initial_layout)
-                    .to_phys(&final_layout)
-            }),
-        )
-        .into(),
+        criteria,
     )
+    .into_iter()
+    .map(|(objectives, (res, final_layout))| {
+        let final_permutation = final_permutation(neighbor_table, initial_layout, &final_layout);
+        (objectives, (res, final_permutation))
+    })
+    .collect()
+}
+
+/// Run sabre swap on a circuit
+///
+/// The ``seed`` is an integer of up to 128 bits, or a ``SeedSequence`` to draw the seeds of the
+/// trials from, which is advanced by the number of trials.
+///
+/// If ``use_bridges`` is set, the CX gates marked as bridgeable in the DAG whose qubits are two
+/// apart may be realized with a bridge through the qubit between them, rather than with a swap,
+/// whichever is cheaper under the average error rates of ``error_map``.
+///
+/// The trials are scored on the number of swaps and bridges they add, the depth of the routed
+/// circuit and the estimated error of the added gates, as the negated logarithm of their success
+/// rate under ``error_map``.  The best trial minimizes the sum of the objectives weighted by
+/// ``objective_weights``, a tuple ``(swaps, depth, error)`` that defaults to ``(1.0, 0.0, 0.0)``.
+/// If ``pareto`` is set, the Pareto set of the trials is returned instead.
+///
+/// Returns:
+///     (SwapMap, gate_order, node_block_results, bridges, final_permutation): A tuple where the
+///     first element is a mapping of DAGCircuit node ids to a list of virtual qubit swaps that
+///     should be added before that operation. The second element is a numpy array of node ids that
+///     represents the traversal order used by sabre.  The third is inner results for the blocks of
+///     control flow, the fourth is a mapping of the node ids of the bridged CX gates to the
+///     physical qubit each bridge goes through, and the fifth is a permutation, where
+///     `final_permution[i]` is the final logical position of the qubit that began in position `i`.
+///
+///     If ``pareto`` is set, a list of the trials that no other trial beats on every objective,
+///     each as the tuple of its objectives ``(swaps, depth, error)`` followed by the elements of
+///     the tuple above.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (
+    dag,
+    coupling_map,
+    heuristic,
+    initial_layout,
+    num_trials,
+    seed=None,
+    run_in_parallel=None,
+    use_bridges=false,
+    error_map=None,
+    objective_weights=None,
+    pareto=false,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn sabre_routing(
+    py: Python,
+    dag: &SabreDAG,
+    coupling_map: &CouplingMap,
+    heuristic: Heuristic,
+    initial_layout: &NLayout,
+    num_trials: usize,
+    seed: Option<PySeed>,
+    run_in_parallel: Option<bool>,
+    use_bridges: bool,
+    error_map: Option<&ErrorMap>,
+    objective_weights: Option<(f64, f64, f64)>,
+    pareto: bool,
+) -> PyObject {
+    let weights: ObjectiveWeights = objective_weights.map(Into::into).unwrap_or_default();
+    let costs = (use_bridges || weights.error != 0.).then(|| {
+        let no_errors = HashMap::new();
+        BridgeCosts::new(
+            coupling_map.neighbor_table(),
+            error_map.map_or(&no_errors, |map| &map.error_map),
+        )
+    });
+    let criteria = TrialCriteria {
+        weights,
+        costs: costs.as_ref(),
+    };
+    let bridges = costs.as_ref().filter(|_| use_bridges);
+    let result_to_py = |res: SabreResult, final_permutation: Vec<PhysicalQubit>| {
+        (
+            res.map,
+            res.node_order.into_pyarray_bound(py).unbind(),
+            res.node_block_results,
+            res.bridges,
+            PyArray::from_vec_bound(py, final_permutation).unbind(),
+        )
+    };
+    if pareto {
+        let trials = PySeed::with_sequence(seed, |seeds| {
+            sabre_routing_pareto_inner(
+                dag,
+                coupling_map.neighbor_table(),
+                coupling_map.distance_matrix(),
+                heuristic,
+                initial_layout,
+                num_trials,
+                seeds,
+                run_in_parallel,
+                bridges,
+                &criteria,
+            )
+        });
+        trials
+            .into_iter()
+            .map(|(objectives, res, final_permutation)| {
+                let (map, order, blocks, bridges, permutation) =
+                    result_to_py(res, final_permutation);
+                (
+                    objectives.as_tuple(),
+                    map,
+                    order,
+                    blocks,
+                    bridges,
+                    permutation,
+                )
+            })
+            .collect::<Vec<_>>()
+            .into_py(py)
+    } else {
+        let (res, final_permutation) = PySeed::with_sequence(seed, |seeds| {
+            sabre_routing_inner(
+                dag,
+                coupling_map.neighbor_table(),
+                coupling_map.distance_matrix(),
+                heuristic,
+                initial_layout,
+                num_trials,
+                seeds,
+                run_in_parallel,
+                bridges,
+                &criteria,
+            )
+        });
+        result_to_py(res, final_permutation).into_py(py)
+    }
 }
 
 /// Run (potentially in parallel) several trials of the Sabre routing algorithm on the given
-/// problem and return the one with fewest swaps.
+/// problem and return the best under the weights of `criteria`, which by default is the one with
+/// fewest swaps and bridges.
+#[allow(clippy::too_many_arguments)]
 pub fn swap_map(
     target: &RoutingTargetView,
     dag: &SabreDAG,
     heuristic: Heuristic,
     initial_layout: &NLayout,
-    seed: Option<u64>,
+    seeds: &mut SeedSequence,
     num_trials: usize,
     run_in_parallel: Option<bool>,
+    criteria: &TrialCriteria,
 ) -> (SabreResult, NLayout) {
+    criteria.best(swap_map_trials(
+        target,
+        dag,
+        heuristic,
+        initial_layout,
+        seeds,
+        num_trials,
+        run_in_parallel,
+        criteria,
+    ))
+}
+
+/// Run (potentially in parallel) several trials of the Sabre routing algorithm on the given
+/// problem and return every trial with its objectives, in the order of the trials.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_map_trials(
+    target: &RoutingTargetView,
+    dag: &SabreDAG,
+    heuristic: Heuristic,
+    initial_layout: &NLayout,
+    seeds: &mut SeedSequence,
+    num_trials: usize,
+    run_in_parallel: Option<bool>,
+    criteria: &TrialCriteria,
+) -> Vec<(TrialObjectives, (SabreResult, NLayout))> {
+    let _timer = instrumentation::Timer::start("sabre_routing");
+    instrumentation::add_trials("sabre_routing", num_trials);
     let run_in_parallel = match run_in_parallel {
         Some(run_in_parallel) => run_in_parallel,
-        None => getenv_use_multiple_threads() && num_trials > 1,
+        None => threading::use_multiple_threads() && num_trials > 1,
     };
-    let outer_rng = match seed {
-        Some(seed) => Pcg64Mcg::seed_from_u64(seed),
-        None => Pcg64Mcg::from_entropy(),
+    let run_trial = |seed_trial| {
+        let (result, final_layout) =
+            swap_map_trial(target, dag, heuristic, initial_layout, seed_trial);
+        let objectives = criteria.objectives(dag, initial_layout, &result);
+        (objectives, (result, final_layout))
     };
-    let seed_vec: Vec<u64> = outer_rng
-        .sample_iter(&rand::distributions::Standard)
-        .take(num_trials)
-        .collect();
+    let seed_vec = seeds.seeds(num_trials);
     if run_in_parallel {
-        seed_vec
-            .into_par_iter()
-            .enumerate()
-            .map(|(index, seed_trial)| {
-                (
-                    index,
-                    swap_map_trial(target, dag, heuristic, initial_layout, seed_trial),
-                )
-            })
-            .min_by_key(|(index, (result, _))| {
-                [
-                    result.map.map.values().map(|x| x.len()).sum::<usize>(),
-                    *index,
-                ]
-            })
-            .unwrap()
-            .1
+        threading::install(|| seed_vec.into_par_iter().map(run_trial).collect())
     } else {
-        seed_vec
-            .into_iter()
-            .map(|seed_trial| swap_map_trial(target, dag, heuristic, initial_layout, seed_trial))
-            .min_by_key(|(result, _)| result.map.map.values().map(|x| x.len()).sum::<usize>())
-            .unwrap()
+        seed_vec.into_iter().map(run_trial).collect()
     }
 }
 
@@ -510,6 +778,7 @@ pub fn swap_map_trial(
         out_map: HashMap::new(),
         gate_order: Vec::with_capacity(dag.dag.node_count()),
         node_block_results: HashMap::with_capacity(dag.node_blocks.len()),
+        bridges: HashMap::new(),
         front_layer: FrontLayer::new(num_qubits),
         extended_set: ExtendedSet::new(num_qubits),
         required_predecessors: &mut vec![0; dag.dag.node_count()],
@@ -534,6 +803,11 @@ pub fn swap_map_trial(
     let mut routable_nodes = Vec::<NodeIndex>::with_capacity(2);
 
     while !state.front_layer.is_empty() {
+        if let Some((node, middle)) = state.choose_bridge() {
+            state.bridges.insert(dag.dag[node].py_node_id, middle);
+            state.update_route(&[node], Vec::new());
+            continue;
+        }
         let mut current_swaps: Vec<[PhysicalQubit; 2]> = Vec::new();
         // Swap-mapping loop.  This is the main part of the algorithm, which we repeat until we
         // either successfully route a node, or exceed the maximum number of attempts.
@@ -541,12 +815,8 @@ pub fn swap_map_trial(
             let best_swap = state.choose_best_swap();
             state.apply_swap(best_swap);
             current_swaps.push(best_swap);
-            if let Some(node) = state.routable_node_on_qubit(best_swap[1]) {
-                routable_nodes.push(node);
-            }
-            if let Some(node) = state.routable_node_on_qubit(best_swap[0]) {
-                routable_nodes.push(node);
-            }
+            routable_nodes.extend(state.routable_nodes_on_qubit(best_swap[1]));
+            routable_nodes.extend(state.routable_nodes_on_qubit(best_swap[0]));
             num_search_steps += 1;
             if num_search_steps >= DECAY_RESET_INTERVAL {
                 state.qubits_decay.fill(1.);
@@ -578,6 +848,7 @@ pub fn swap_map_trial(
             node_block_results: NodeBlockResults {
                 results: state.node_block_results,
             },
+            bridges: state.bridges,
         },
         state.layout,
     )
@@ -12,10 +12,13 @@
 
 use hashbrown::HashMap;
 use hashbrown::HashSet;
-use pyo3::exceptions::PyIndexError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 use rustworkx_core::petgraph::prelude::*;
 
+#[cfg(feature = "python")]
+use crate::commutation_checker::{commute, standard_gate, GateRef};
+use crate::error::AccelerateError;
 use crate::nlayout::VirtualQubit;
 
 /// Named access to the node elements in the [SabreDAG].
@@ -24,6 +27,8 @@ pub struct DAGNode {
     pub py_node_id: usize,
     pub qubits: Vec<VirtualQubit>,
     pub directive: bool,
+    /// Whether the node is a CX gate, which the router may realize with a bridge rather than swaps.
+    pub bridgeable: bool,
 }
 
 /// A DAG representation of the logical circuit to be routed.  This represents the same dataflow
@@ -35,7 +40,7 @@ pub struct DAGNode {
 /// Control-flow operations are represented by the presence of the Python [DAGCircuit]'s node id
 /// (the [DAGNode.py_node_id] field) as a key in [node_blocks], where the value is an array of the
 /// inner dataflow graphs.
-#[pyclass(module = "qiskit._accelerate.sabre")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.sabre"))]
 #[derive(Clone, Debug)]
 pub struct SabreDAG {
     pub num_qubits: usize,
@@ -46,17 +51,35 @@ pub struct SabreDAG {
     pub node_blocks: HashMap<usize, Vec<SabreDAG>>,
 }
 
-#[pymethods]
 impl SabreDAG {
-    #[new]
-    #[pyo3(text_signature = "(num_qubits, num_clbits, nodes, node_blocks, /)")]
     pub fn new(
         num_qubits: usize,
         num_clbits: usize,
         nodes: Vec<(usize, Vec<VirtualQubit>, HashSet<usize>, bool)>,
         node_blocks: HashMap<usize, Vec<SabreDAG>>,
-    ) -> PyResult<Self> {
-        let mut qubit_pos: Vec<Option<NodeIndex>> = vec![None; num_qubits];
+    ) -> Result<Self, AccelerateError> {
+        Self::with_commutation(num_qubits, num_clbits, nodes, node_blocks, |_, _| false)
+    }
+
+    /// Build the DAG with the ordering between the nodes that commute relaxed.
+    ///
+    /// `commute(i, j)` is whether the nodes at positions `i` and `j` of `nodes` commute.  The nodes
+    /// on each qubit are split into runs of mutually commuting nodes, and each node then only
+    /// follows all the nodes of the run before its own on each of its qubits, so the nodes of a run
+    /// can be routed in any order, and are in the front layer together.
+    pub fn with_commutation<F>(
+        num_qubits: usize,
+        num_clbits: usize,
+        nodes: Vec<(usize, Vec<VirtualQubit>, HashSet<usize>, bool)>,
+        node_blocks: HashMap<usize, Vec<SabreDAG>>,
+        commute: F,
+    ) -> Result<Self, AccelerateError>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        // The run of mutually commuting nodes that each qubit ends with, and the run before it.
+        let mut qubit_run: Vec<Vec<NodeIndex>> = vec![Vec::new(); num_qubits];
+        let mut qubit_before: Vec<Vec<NodeIndex>> = vec![Vec::new(); num_qubits];
         let mut clbit_pos: Vec<Option<NodeIndex>> = vec![None; num_clbits];
         let mut dag = DiGraph::with_capacity(nodes.len(), 2 * nodes.len());
         let mut first_layer = Vec::<NodeIndex>::new();
@@ -67,25 +90,34 @@ impl SabreDAG {
                 py_node_id: node.0,
                 qubits: qargs.clone(),
                 directive: node.3,
+                bridgeable: false,
             });
             let mut is_front = true;
             for x in qargs {
-                let pos = qubit_pos.get_mut(x.index()).ok_or_else(|| {
-                    PyIndexError::new_err(format!(
+                if x.index() >= num_qubits {
+                    return Err(AccelerateError::Index(format!(
                         "qubit index {} is out of range for {} qubits",
                         x.index(),
                         num_qubits
-                    ))
-                })?;
-                if let Some(predecessor) = *pos {
+                    )));
+                }
+                let run = &mut qubit_run[x.index()];
+                let joins = !run.is_empty()
+                    && run
+                        .iter()
+                        .all(|other| commute(other.index(), gate_index.index()));
+                if !joins {
+                    qubit_before[x.index()] = std::mem::take(run);
+                }
+                run.push(gate_index);
+                for predecessor in qubit_before[x.index()].iter() {
                     is_front = false;
-                    dag.add_edge(predecessor, gate_index, ());
+                    dag.add_edge(*predecessor, gate_index, ());
                 }
-                *pos = Some(gate_index);
             }
             for x in cargs {
                 let pos = clbit_pos.get_mut(*x).ok_or_else(|| {
-                    PyIndexError::new_err(format!(
+                    AccelerateError::Index(format!(
                         "clbit index {} is out of range for {} clbits",
                         *x, num_qubits
                     ))
@@ -109,6 +141,81 @@ impl SabreDAG {
             node_blocks,
         })
     }
+
+    /// Mark the nodes with the given ids as CX gates that may be realized with bridges.
+    pub fn with_bridgeable(mut self, bridgeable: &HashSet<usize>) -> Self {
+        for node in self.dag.node_weights_mut() {
+            node.bridgeable = bridgeable.contains(&node.py_node_id);
+        }
+        self
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SabreDAG {
+    /// ``commutation`` is the name and parameters of each node that is a standard gate, aligned
+    /// with ``nodes``, or ``None`` for the other nodes.  If it's given, the ordering between the
+    /// nodes that commute is relaxed, so they can be routed in any order.  Names that aren't of
+    /// standard gates known to Rust are taken as not commuting with anything.
+    #[new]
+    #[pyo3(signature = (num_qubits, num_clbits, nodes, node_blocks, bridgeable=None, commutation=None))]
+    fn py_new(
+        num_qubits: usize,
+        num_clbits: usize,
+        nodes: Vec<(usize, Vec<VirtualQubit>, HashSet<usize>, bool)>,
+        node_blocks: HashMap<usize, Vec<SabreDAG>>,
+        bridgeable: Option<HashSet<usize>>,
+        commutation: Option<Vec<Option<(String, Vec<f64>)>>>,
+    ) -> PyResult<Self> {
+        let dag = match commutation {
+            Some(commutation) => {
+                if commutation.len() != nodes.len() {
+                    return Err(AccelerateError::Value(format!(
+                        "{} gates were given for {} nodes",
+                        commutation.len(),
+                        nodes.len()
+                    ))
+                    .into());
+                }
+                let gates = commutation
+                    .iter()
+                    .zip(nodes.iter())
+                    .map(|(gate, node)| {
+                        let (name, params) = gate.as_ref()?;
+                        let qubits = node.1.iter().map(|q| q.index()).collect::<Vec<_>>();
+                        let gate = standard_gate(name, params.len(), qubits.len()).ok()?;
+                        Some((gate, params.as_slice(), qubits))
+                    })
+                    .collect::<Vec<_>>();
+                let commutes = |i: usize, j: usize| match (&gates[i], &gates[j]) {
+                    (
+                        Some((first, first_params, first_qubits)),
+                        Some((second, second_params, second_qubits)),
+                    ) => commute(
+                        GateRef {
+                            gate: *first,
+                            params: first_params,
+                            qubits: first_qubits,
+                        },
+                        GateRef {
+                            gate: *second,
+                            params: second_params,
+                            qubits: second_qubits,
+                        },
+                    )
+                    .unwrap_or(false),
+                    _ => false,
+                };
+                SabreDAG::with_commutation(num_qubits, num_clbits, nodes, node_blocks, commutes)?
+            }
+            None => SabreDAG::new(num_qubits, num_clbits, nodes, node_blocks)?,
+        };
+        Ok(match bridgeable {
+            Some(bridgeable) => dag.with_bridgeable(&bridgeable),
+            None => dag,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -116,6 +223,7 @@ mod test {
     use super::SabreDAG;
     use crate::nlayout::VirtualQubit;
     use hashbrown::{HashMap, HashSet};
+    use rustworkx_core::petgraph::prelude::*;
 
     #[test]
     fn no_panic_on_bad_qubits() {
@@ -140,4 +248,55 @@ mod test {
         )
         .is_err())
     }
+    fn nodes(qubits: &[[u32; 2]]) -> Vec<(usize, Vec<VirtualQubit>, HashSet<usize>, bool)> {
+        qubits
+            .iter()
+            .enumerate()
+            .map(|(id, pair)| {
+                let qargs = pair.iter().map(|q| VirtualQubit::new(*q)).collect();
+                (id, qargs, HashSet::new(), false)
+            })
+            .collect()
+    }
+
+    fn predecessors(dag: &SabreDAG, node: usize) -> HashSet<usize> {
+        dag.dag
+            .neighbors_directed(NodeIndex::new(node), Direction::Incoming)
+            .map(|predecessor| predecessor.index())
+            .collect()
+    }
+
+    #[test]
+    fn commuting_runs_in_front_layer() {
+        // The first three gates commute with each other, and the last with none of them.
+        let nodes = nodes(&[[0, 1], [1, 2], [0, 2], [0, 1]]);
+        let dag =
+            SabreDAG::with_commutation(3, 0, nodes.clone(), HashMap::new(), |i, j| i < 3 && j < 3)
+                .unwrap();
+        assert_eq!(
+            dag.first_layer,
+            vec![NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(2)]
+        );
+        assert_eq!(predecessors(&dag, 3), HashSet::from_iter([0, 1, 2]));
+
+        let dag = SabreDAG::new(3, 0, nodes, HashMap::new()).unwrap();
+        assert_eq!(dag.first_layer, vec![NodeIndex::new(0)]);
+        assert_eq!(predecessors(&dag, 1), HashSet::from_iter([0]));
+        assert_eq!(predecessors(&dag, 2), HashSet::from_iter([0, 1]));
+        assert_eq!(predecessors(&dag, 3), HashSet::from_iter([1, 2]));
+    }
+
+    #[test]
+    fn commuting_runs_split_by_other_gates() {
+        // A gate that doesn't commute ends the run on its qubit, and the gates after it follow it
+        // even if they commute with the run before it.  The last gate joins the run of the first
+        // on qubit 1, so it only follows the second gate.
+        let nodes = nodes(&[[0, 1], [0, 2], [0, 3], [0, 1]]);
+        let dag = SabreDAG::with_commutation(4, 0, nodes, HashMap::new(), |i, j| i != 1 && j != 1)
+            .unwrap();
+        assert_eq!(dag.first_layer, vec![NodeIndex::new(0)]);
+        assert_eq!(predecessors(&dag, 1), HashSet::from_iter([0]));
+        assert_eq!(predecessors(&dag, 2), HashSet::from_iter([1]));
+        assert_eq!(predecessors(&dag, 3), HashSet::from_iter([1]));
+    }
 }
@@ -11,18 +11,21 @@
 // that they have been altered from the originals.
 
 use hashbrown::HashMap;
+#[cfg(feature = "python")]
 use pyo3::exceptions::PyIndexError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 use crate::nlayout::PhysicalQubit;
 
 /// A container for required swaps before a gate qubit
-#[pyclass(module = "qiskit._accelerate.sabre")]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.sabre"))]
 #[derive(Clone, Debug)]
 pub struct SwapMap {
     pub map: HashMap<usize, Vec<[PhysicalQubit; 2]>>,
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl SwapMap {
     // Mapping Protocol
@@ -13,11 +13,14 @@
 use num_complex::Complex64;
 
 use hashbrown::HashMap;
-use numpy::PyReadonlyArray1;
-use pyo3::prelude::*;
-use pyo3::wrap_pyfunction;
+use rayon::prelude::*;
+#[cfg(feature = "python")]
+use {numpy::PyReadonlyArray1, pyo3::prelude::*, pyo3::wrap_pyfunction};
 
+#[cfg(feature = "python")]
+use crate::error::AccelerateError;
 use crate::pauli_exp_val::fast_sum;
+use crate::threading::{self, ParallelKernel};
 
 const OPER_TABLE_SIZE: usize = (b'Z' as usize) + 1;
 const fn generate_oper_table() -> [[f64; 2]; OPER_TABLE_SIZE] {
@@ -30,29 +33,130 @@ const fn generate_oper_table() -> [[f64; 2]; OPER_TABLE_SIZE] {
 
 static OPERS: [[f64; 2]; OPER_TABLE_SIZE] = generate_oper_table();
 
-fn bitstring_expval(dist: &HashMap<String, f64>, mut oper_str: String) -> f64 {
-    let inds: Vec<usize> = oper_str
-        .char_indices()
-        .filter_map(|(index, oper)| if oper != 'I' { Some(index) } else { None })
+/// A sampled distribution with its total weight, which is shared by the terms it is evaluated on.
+struct Outcomes<'a> {
+    outcomes: Vec<(&'a [u8], f64)>,
+    denom: f64,
+}
+
+impl<'a> Outcomes<'a> {
+    fn new(dist: &'a HashMap<String, f64>) -> Self {
+        let outcomes = dist
+            .iter()
+            .map(|(bits, val)| (bits.as_bytes(), *val))
+            .collect::<Vec<_>>();
+        let denom = fast_sum(&outcomes.iter().map(|(_, val)| *val).collect::<Vec<f64>>());
+        Outcomes { outcomes, denom }
+    }
+}
+
+/// The expectation value of the diagonal operator `oper_str` on the distribution, summed over
+/// the outcomes in parallel if `parallel`.
+fn bitstring_expval(dist: &Outcomes, oper_str: &str, parallel: bool) -> f64 {
+    let opers: Vec<(usize, [f64; 2])> = oper_str
+        .bytes()
+        .enumerate()
+        .filter(|(_, oper)| *oper != b'I')
+        .map(|(index, oper)| (index, OPERS[oper as usize]))
         .collect();
-    oper_str.retain(|c| !r#"I"#.contains(c));
-    let denom: f64 = fast_sum(&dist.values().copied().collect::<Vec<f64>>());
-    let exp_val: f64 = dist
-        .iter()
-        .map(|(bits, val)| {
-            let temp_product: f64 = oper_str.bytes().enumerate().fold(1.0, |acc, (idx, oper)| {
-                let diagonal: [f64; 2] = OPERS[oper as usize];
-                let index_char: char = bits.as_bytes()[inds[idx]] as char;
-                let index: usize = index_char.to_digit(10).unwrap() as usize;
-                acc * diagonal[index]
-            });
-            val * temp_product
+    let outcome_value = |(bits, val): &(&[u8], f64)| -> f64 {
+        let temp_product: f64 = opers.iter().fold(1.0, |acc, (index, diagonal)| {
+            acc * diagonal[(bits[*index] - b'0') as usize]
+        });
+        val * temp_product
+    };
+    let exp_val: f64 = if parallel {
+        dist.outcomes.par_iter().map(outcome_value).sum()
+    } else {
+        dist.outcomes.iter().map(outcome_value).sum()
+    };
+    exp_val / dist.denom
+}
+
+/// The expectation values of the diagonal operators `oper_strs` on the distribution `dist`.
+///
+/// This goes parallel over the terms when there are enough terms and outcomes together, and over
+/// the outcomes too when there are fewer terms than threads.
+fn term_expvals(oper_strs: &[&str], dist: &HashMap<String, f64>) -> Vec<f64> {
+    let outcomes = Outcomes::new(dist);
+    if threading::run_in_parallel(ParallelKernel::SampledExpVal, oper_strs.len() * dist.len()) {
+        threading::install(|| {
+            let inner_parallel = oper_strs.len() < threading::current_num_threads();
+            oper_strs
+                .par_iter()
+                .map(|string| bitstring_expval(&outcomes, string, inner_parallel))
+                .collect()
         })
+    } else {
+        oper_strs
+            .iter()
+            .map(|string| bitstring_expval(&outcomes, string, false))
+            .collect()
+    }
+}
+
+/// Compute the expectation value from a sampled distribution
+pub fn sampled_expval_float_inner(
+    oper_strs: Vec<String>,
+    coeff_arr: &[f64],
+    dist: &HashMap<String, f64>,
+) -> f64 {
+    let strs = oper_strs.iter().map(String::as_str).collect::<Vec<_>>();
+    term_expvals(&strs, dist)
+        .into_iter()
+        .zip(coeff_arr)
+        .map(|(exp_val, coeff)| coeff * exp_val)
+        .sum()
+}
+
+/// Compute the expectation value from a sampled distribution
+pub fn sampled_expval_complex_inner(
+    oper_strs: Vec<String>,
+    coeff_arr: &[Complex64],
+    dist: &HashMap<String, f64>,
+) -> f64 {
+    let strs = oper_strs.iter().map(String::as_str).collect::<Vec<_>>();
+    let out: Complex64 = term_expvals(&strs, dist)
+        .into_iter()
+        .zip(coeff_arr)
+        .map(|(exp_val, coeff)| *coeff * Complex64::new(exp_val, 0.))
         .sum();
-    exp_val / denom
+    out.re
+}
+
+/// Compute the expectation values of many observables from the same sampled distribution.
+///
+/// Each distinct operator string is evaluated once, however many observables it appears in.
+pub fn sampled_expval_batch_inner(
+    observables: &[(Vec<String>, Vec<Complex64>)],
+    dist: &HashMap<String, f64>,
+) -> Vec<f64> {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut strs = Vec::new();
+    for (oper_strs, _) in observables {
+        for string in oper_strs {
+            index.entry(string.as_str()).or_insert_with(|| {
+                strs.push(string.as_str());
+                strs.len() - 1
+            });
+        }
+    }
+    let exp_vals = term_expvals(&strs, dist);
+    observables
+        .iter()
+        .map(|(oper_strs, coeffs)| {
+            let out: Complex64 = oper_strs
+                .iter()
+                .zip(coeffs)
+                .map(|(string, coeff)| *coeff * exp_vals[index[string.as_str()]])
+                .sum();
+            out.re
+        })
+        .collect()
 }
 
 /// Compute the expectation value from a sampled distribution
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(text_signature = "(oper_strs, coeff, dist, /)")]
 pub fn sampled_expval_float(
@@ -60,16 +164,15 @@ pub fn sampled_expval_float(
     coeff: PyReadonlyArray1<f64>,
     dist: HashMap<String, f64>,
 ) -> PyResult<f64> {
-    let coeff_arr = coeff.as_slice()?;
-    let out = oper_strs
-        .into_iter()
-        .enumerate()
-        .map(|(idx, string)| coeff_arr[idx] * bitstring_expval(&dist, string))
-        .sum();
-    Ok(out)
+    Ok(sampled_expval_float_inner(
+        oper_strs,
+        coeff.as_slice()?,
+        &dist,
+    ))
 }
 
 /// Compute the expectation value from a sampled distribution
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(text_signature = "(oper_strs, coeff, dist, /)")]
 pub fn sampled_expval_complex(
@@ -77,18 +180,134 @@ pub fn sampled_expval_complex(
     coeff: PyReadonlyArray1<Complex64>,
     dist: HashMap<String, f64>,
 ) -> PyResult<f64> {
-    let coeff_arr = coeff.as_slice()?;
-    let out: Complex64 = oper_strs
-        .into_iter()
-        .enumerate()
-        .map(|(idx, string)| coeff_arr[idx] * Complex64::new(bitstring_expval(&dist, string), 0.))
-        .sum();
-    Ok(out.re)
+    Ok(sampled_expval_complex_inner(
+        oper_strs,
+        coeff.as_slice()?,
+        &dist,
+    ))
 }
 
+/// Compute the expectation values of many observables from the same sampled distribution
+///
+/// Args:
+///     observables (list[tuple[list[str], list[complex]]]): the operator strings and the
+///         coefficients of the terms of each observable.
+///     dist (dict[str, float]): the sampled distribution.
+///
+/// Returns:
+///     list[float]: the expectation value of each observable.
+///
+/// Raises:
+///     ValueError: if an observable doesn't have as many coefficients as terms.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(observables, dist, /)")]
+pub fn sampled_expval_batch(
+    observables: Vec<(Vec<String>, Vec<Complex64>)>,
+    dist: HashMap<String, f64>,
+) -> PyResult<Vec<f64>> {
+    if let Some((oper_strs, coeffs)) = observables
+        .iter()
+        .find(|(oper_strs, coeffs)| oper_strs.len() != coeffs.len())
+    {
+        return Err(AccelerateError::Value(format!(
+            "an observable has {} terms but {} coefficients",
+            oper_strs.len(),
+            coeffs.len()
+        ))
+        .into());
+    }
+    Ok(sampled_expval_batch_inner(&observables, &dist))
+}
+
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn sampled_exp_val(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(sampled_expval_float))?;
     m.add_wrapped(wrap_pyfunction!(sampled_expval_complex))?;
+    m.add_wrapped(wrap_pyfunction!(sampled_expval_batch))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    /// Unnormalized counts, so the total weight matters.
+    fn dist() -> HashMap<String, f64> {
+        [("00", 2.), ("01", 1.), ("11", 1.)]
+            .into_iter()
+            .map(|(bits, count)| (bits.to_string(), count))
+            .collect()
+    }
+
+    fn strings(opers: &[&str]) -> Vec<String> {
+        opers.iter().map(|oper| oper.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expvals() {
+        // The first character of an operator acts on the first character of each outcome.
+        let opers = strings(&["ZZ", "IZ", "ZI", "0I", "I1", "II"]);
+        let expected = [0.5, 0., 0.5, 0.75, 0.5, 1.];
+        for (oper, value) in opers.iter().zip(expected) {
+            assert_eq!(
+                sampled_expval_float_inner(vec![oper.clone()], &[1.], &dist()),
+                value
+            );
+        }
+        assert_eq!(
+            sampled_expval_float_inner(strings(&["ZZ", "0I"]), &[2., -1.], &dist()),
+            0.25
+        );
+        let coeffs = [Complex64::new(2., 5.), Complex64::new(-1., 3.)];
+        assert_eq!(
+            sampled_expval_complex_inner(strings(&["ZZ", "0I"]), &coeffs, &dist()),
+            0.25
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let observables = vec![
+            (
+                strings(&["ZZ", "IZ"]),
+                vec![Complex64::new(1., 0.), Complex64::new(0., 2.)],
+            ),
+            (strings(&["ZZ"]), vec![Complex64::new(3., 0.)]),
+            (vec![], vec![]),
+        ];
+        assert_eq!(
+            sampled_expval_batch_inner(&observables, &dist()),
+            vec![0.5, 1.5, 0.]
+        );
+        assert!(sampled_expval_batch_inner(&[], &dist()).is_empty());
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        let dist = (0..256)
+            .map(|outcome| (format!("{outcome:08b}"), (outcome % 7 + 1) as f64))
+            .collect::<HashMap<_, _>>();
+        let opers = (0..256)
+            .map(|term: usize| {
+                (0..8)
+                    .map(|bit| ['I', 'Z', '0', '1'][(term >> bit) % 4])
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let strs = opers.iter().map(String::as_str).collect::<Vec<_>>();
+        let outcomes = Outcomes::new(&dist);
+        for (oper, value) in strs.iter().zip(term_expvals(&strs, &dist)) {
+            let serial = bitstring_expval(&outcomes, oper, false);
+            assert_abs_diff_eq!(value, serial, epsilon = 1e-12);
+            assert_abs_diff_eq!(
+                bitstring_expval(&outcomes, oper, true),
+                serial,
+                epsilon = 1e-12
+            );
+        }
+    }
+}
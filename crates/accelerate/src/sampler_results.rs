@@ -0,0 +1,357 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The post-processing of the shots of the samplers.
+//!
+//! Backends report each shot as a hexadecimal string of all the classical bits of the circuit.  The
+//! shots are parsed into bytes, and each classical register is then sliced out of them into the
+//! packed layout of `BitArray`: one row of bytes per shot, with the most significant byte first
+//! and the bits above the register cleared.
+
+use indexmap::IndexMap;
+use ndarray::{Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis};
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::pybacked::PyBackedStr;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::error::AccelerateError;
+use crate::threading::{self, ParallelKernel};
+
+/// The number of bytes of a packed bit string of `num_bits` bits.
+pub fn num_bytes(num_bits: usize) -> usize {
+    (num_bits + 7) / 8
+}
+
+/// Parse the hexadecimal `shot`, such as `"0x1f"`, into `out`, least significant byte first.
+fn parse_shot(shot: &str, out: &mut [u8]) -> Result<(), AccelerateError> {
+    out.fill(0);
+    let digits = shot
+        .strip_prefix("0x")
+        .or_else(|| shot.strip_prefix("0X"))
+        .unwrap_or(shot);
+    for (index, digit) in digits.bytes().rev().enumerate() {
+        let value = (digit as char).to_digit(16).ok_or_else(|| {
+            AccelerateError::Value(format!("'{}' isn't a hexadecimal outcome", shot))
+        })? as u8;
+        if value == 0 {
+            continue;
+        }
+        match out.get_mut(index / 2) {
+            Some(byte) => *byte |= value << (4 * (index % 2)),
+            None => {
+                return Err(AccelerateError::Value(format!(
+                    "the outcome '{}' has more bits than the classical registers",
+                    shot
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy the `num_bits` bits from bit `start` of `shot`, which is least significant byte first,
+/// into `out`, which is most significant byte first.
+fn slice_register(shot: &[u8], start: usize, num_bits: usize, out: &mut [u8]) {
+    let byte = |index: usize| shot.get(index).copied().unwrap_or(0);
+    let len = out.len();
+    for (index, value) in out.iter_mut().rev().enumerate() {
+        let bit = start + 8 * index;
+        let shift = bit % 8;
+        *value = byte(bit / 8) >> shift;
+        if shift > 0 {
+            *value |= byte(bit / 8 + 1) << (8 - shift);
+        }
+    }
+    if num_bits % 8 > 0 && len > 0 {
+        out[0] &= (1 << (num_bits % 8)) - 1;
+    }
+}
+
+/// Parse each of `shots` into a row of `width` bytes, least significant byte first.
+fn parse_shots<S: AsRef<str> + Sync>(
+    shots: &[S],
+    width: usize,
+    parallel: bool,
+) -> Result<Array2<u8>, AccelerateError> {
+    let mut out = Array2::zeros((shots.len(), width));
+    let parse = |(mut row, shot): (ArrayViewMut1<u8>, &S)| {
+        parse_shot(shot.as_ref(), row.as_slice_mut().unwrap())
+    };
+    if parallel {
+        out.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(shots.par_iter())
+            .try_for_each(parse)?;
+    } else {
+        out.axis_iter_mut(Axis(0))
+            .zip(shots.iter())
+            .try_for_each(parse)?;
+    }
+    Ok(out)
+}
+
+/// The register of `num_bits` bits from bit `start` of each of the parsed `shots`.
+fn slice_shots(shots: &Array2<u8>, start: usize, num_bits: usize, parallel: bool) -> Array2<u8> {
+    let mut out = Array2::zeros((shots.nrows(), num_bytes(num_bits)));
+    let slice = |(mut row, shot): (ArrayViewMut1<u8>, ArrayView1<u8>)| {
+        slice_register(
+            shot.as_slice().unwrap(),
+            start,
+            num_bits,
+            row.as_slice_mut().unwrap(),
+        )
+    };
+    if parallel {
+        out.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(shots.axis_iter(Axis(0)).into_par_iter())
+            .for_each(slice);
+    } else {
+        out.axis_iter_mut(Axis(0))
+            .zip(shots.axis_iter(Axis(0)))
+            .for_each(slice);
+    }
+    out
+}
+
+/// Pack the hexadecimal `shots` into one array per register of `registers`, which are each the
+/// index of their first classical bit and their number of bits.
+///
+/// Each array has a row per shot, packed as in `BitArray`.
+pub fn pack_shots<S: AsRef<str> + Sync>(
+    shots: &[S],
+    registers: &[(usize, usize)],
+) -> Result<Vec<Array2<u8>>, AccelerateError> {
+    let width = registers
+        .iter()
+        .map(|(start, num_bits)| num_bytes(start + num_bits))
+        .max()
+        .unwrap_or(0);
+    let parallel = threading::run_in_parallel(ParallelKernel::SamplerResults, shots.len());
+    let pack = || -> Result<Vec<Array2<u8>>, AccelerateError> {
+        let parsed = parse_shots(shots, width, parallel)?;
+        Ok(registers
+            .iter()
+            .map(|(start, num_bits)| slice_shots(&parsed, *start, *num_bits, parallel))
+            .collect())
+    };
+    if parallel {
+        threading::install(pack)
+    } else {
+        pack()
+    }
+}
+
+/// The bit string of the `num_bits` low bits of the packed `row`, as `BitArray.get_counts` keys
+/// them.
+fn bitstring(row: &[u8], num_bits: usize) -> String {
+    if num_bits == 0 {
+        return "0".to_string();
+    }
+    (0..num_bits)
+        .rev()
+        .map(|bit| {
+            let byte = row
+                .len()
+                .checked_sub(1 + bit / 8)
+                .map_or(0, |index| row[index]);
+            if (byte >> (bit % 8)) & 1 == 1 {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect()
+}
+
+/// The number of times that each bit string of the `num_bits` low bits of the packed rows of
+/// `array` occurs, keyed and ordered as `BitArray.get_counts` does.
+pub fn bitstring_counts(array: ArrayView2<u8>, num_bits: usize) -> IndexMap<String, usize> {
+    let width = array.ncols();
+    let cleared = width.saturating_sub(num_bytes(num_bits));
+    let mut counts: IndexMap<Vec<u8>, usize> = IndexMap::new();
+    let mut row = Vec::with_capacity(width);
+    for shot in array.rows() {
+        row.clear();
+        row.extend(shot.iter().enumerate().map(
+            |(index, byte)| {
+                if index < cleared {
+                    0
+                } else {
+                    *byte
+                }
+            },
+        ));
+        if num_bits % 8 > 0 && num_bytes(num_bits) <= width {
+            row[cleared] &= (1 << (num_bits % 8)) - 1;
+        }
+        match counts.get_mut(&row) {
+            Some(count) => *count += 1,
+            None => {
+                counts.insert(row.clone(), 1);
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(row, count)| (bitstring(&row, num_bits), count))
+        .collect()
+}
+
+/// Pack the memory of circuits into the arrays of their classical registers.
+///
+/// Args:
+///     memory (list[list[str]]): the hexadecimal outcome of each shot of each circuit, with the
+///         same number of shots for every circuit.
+///     registers (list[tuple[int, int]]): the index of the first classical bit of each register,
+///         and its number of bits.
+///
+/// Returns:
+///     list[numpy.ndarray]: for each register, the ``uint8`` array of shape ``(num_circuits,
+///     num_shots, num_bytes)`` of its packed outcomes, as in a :class:`.BitArray`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "pack_memory")]
+pub fn py_pack_memory(
+    py: Python,
+    memory: Vec<Vec<PyBackedStr>>,
+    registers: Vec<(usize, usize)>,
+) -> PyResult<Vec<Py<PyArray3<u8>>>> {
+    let num_shots = memory.first().map_or(0, |shots| shots.len());
+    if memory.iter().any(|shots| shots.len() != num_shots) {
+        return Err(AccelerateError::Value(
+            "the circuits must all have the same number of shots".to_string(),
+        )
+        .into());
+    }
+    let shots: Vec<&str> = memory.iter().flatten().map(|shot| &**shot).collect();
+    let arrays = py.allow_threads(|| pack_shots(&shots, &registers))?;
+    arrays
+        .into_iter()
+        .map(|array| {
+            let width = array.ncols();
+            let array = array
+                .into_shape((memory.len(), num_shots, width))
+                .map_err(|err| AccelerateError::Value(err.to_string()))?;
+            Ok(array.into_pyarray_bound(py).unbind())
+        })
+        .collect()
+}
+
+/// The counts of the packed bit strings of a :class:`.BitArray`.
+///
+/// Args:
+///     array (numpy.ndarray): the ``uint8`` array of the packed shots, one per row.
+///     num_bits (int): the number of bits of each shot.
+///
+/// Returns:
+///     dict[str, int]: the number of times each bit string occurs, in the order of their first
+///     occurrence.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "bitstring_counts")]
+pub fn py_bitstring_counts(
+    py: Python,
+    array: PyReadonlyArray2<u8>,
+    num_bits: usize,
+) -> IndexMap<String, usize> {
+    let array = array.as_array();
+    py.allow_threads(|| bitstring_counts(array, num_bits))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn sampler_results(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_pack_memory))?;
+    m.add_wrapped(wrap_pyfunction!(py_bitstring_counts))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_parse_shot() {
+        let mut out = [0xffu8; 2];
+        parse_shot("0x1f3", &mut out).unwrap();
+        assert_eq!(out, [0xf3, 0x01]);
+        parse_shot("0X0001", &mut out).unwrap();
+        assert_eq!(out, [0x01, 0x00]);
+        assert!(matches!(
+            parse_shot("0x10000", &mut out),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            parse_shot("0x1g", &mut out),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_slice_register() {
+        let shot = [0xf3, 0x01];
+        let mut out = [0u8; 1];
+        slice_register(&shot, 2, 5, &mut out);
+        assert_eq!(out, [0x1c]);
+        let mut out = [0u8; 2];
+        slice_register(&shot, 4, 9, &mut out);
+        assert_eq!(out, [0x00, 0x1f]);
+        // Bits beyond the shot are zero.
+        slice_register(&shot, 8, 16, &mut out);
+        assert_eq!(out, [0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_pack_shots() {
+        let arrays = pack_shots(&["0x5", "0xe", "0x3ff"], &[(0, 2), (2, 2), (0, 10)]).unwrap();
+        assert_eq!(arrays[0], array![[0x01], [0x02], [0x03]]);
+        assert_eq!(arrays[1], array![[0x01], [0x03], [0x03]]);
+        assert_eq!(arrays[2], array![[0x00, 0x05], [0x00, 0x0e], [0x03, 0xff]]);
+        assert!(pack_shots(&["0x0"], &[]).unwrap().is_empty());
+        assert!(pack_shots(&["0x100"], &[(0, 4)]).is_err());
+    }
+
+    #[test]
+    fn test_bitstring() {
+        assert_eq!(bitstring(&[0x01, 0x05], 10), "0100000101");
+        assert_eq!(bitstring(&[0x05], 3), "101");
+        assert_eq!(bitstring(&[], 0), "0");
+    }
+
+    #[test]
+    fn test_bitstring_counts() {
+        let array = array![[0x05u8], [0x03], [0x05], [0xff]];
+        let counts = bitstring_counts(array.view(), 3);
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("101".to_string(), 2),
+                ("011".to_string(), 1),
+                ("111".to_string(), 1)
+            ]
+        );
+        // The bytes above the bits of the shots are ignored.
+        let array = array![[0xffu8, 0x05], [0x00, 0x05]];
+        let counts = bitstring_counts(array.view(), 3);
+        assert_eq!(counts.get("101"), Some(&2));
+        assert_eq!(counts.len(), 1);
+    }
+}
@@ -0,0 +1,289 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Sampling shots from a probability distribution with the alias method.
+//!
+//! The [AliasTable] of a distribution over `n` outcomes takes `O(n)` to build, and then draws each
+//! shot in constant time, from one uniform index and one uniform float, whatever the distribution.
+
+use num_complex::Complex64;
+use rand::prelude::*;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+
+/// The alias table of a discrete probability distribution.
+#[derive(Clone, Debug)]
+pub struct AliasTable {
+    /// The probability of keeping each outcome when its column is drawn.
+    keep: Vec<f64>,
+    /// The outcome drawn instead of each outcome when it isn't kept.
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// The table of the distribution proportional to `weights`, which need not be normalized.
+    pub fn new(weights: &[f64]) -> Result<Self, AccelerateError> {
+        if let Some(weight) = weights
+            .iter()
+            .find(|weight| !weight.is_finite() || **weight < 0.)
+        {
+            return Err(AccelerateError::Value(format!(
+                "the weight {} isn't a probability",
+                weight
+            )));
+        }
+        let total: f64 = weights.iter().sum();
+        if total == 0. || !total.is_finite() {
+            return Err(AccelerateError::Value(
+                "the probabilities must have a finite, nonzero total".to_string(),
+            ));
+        }
+        // Vose's method: each column is filled up to the mean with the excess of a heavy outcome.
+        let scale = weights.len() as f64 / total;
+        let mut keep: Vec<f64> = weights.iter().map(|weight| weight * scale).collect();
+        let mut alias: Vec<usize> = (0..weights.len()).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..weights.len()).partition(|&outcome| keep[outcome] < 1.);
+        while let (Some(&light), Some(&heavy)) = (small.last(), large.last()) {
+            small.pop();
+            alias[light] = heavy;
+            keep[heavy] -= 1. - keep[light];
+            if keep[heavy] < 1. {
+                large.pop();
+                small.push(heavy);
+            }
+        }
+        // What's left only differs from a full column by rounding.
+        for outcome in small.into_iter().chain(large) {
+            keep[outcome] = 1.;
+        }
+        Ok(AliasTable { keep, alias })
+    }
+
+    /// The table of the measurement distribution of the statevector `amplitudes`.
+    pub fn from_amplitudes(amplitudes: &[Complex64]) -> Result<Self, AccelerateError> {
+        let weights: Vec<f64> = amplitudes.iter().map(|amp| amp.norm_sqr()).collect();
+        Self::new(&weights)
+    }
+
+    /// The number of outcomes.
+    pub fn len(&self) -> usize {
+        self.keep.len()
+    }
+
+    /// Whether there are no outcomes, which a valid table never has.
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+    }
+
+    /// Draw an outcome.
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let outcome = rng.gen_range(0..self.keep.len());
+        if rng.gen::<f64>() < self.keep[outcome] {
+            outcome
+        } else {
+            self.alias[outcome]
+        }
+    }
+
+    /// Draw the outcome of each of `shots` shots, in order.
+    pub fn sample_memory<R: Rng + ?Sized>(&self, shots: usize, rng: &mut R) -> Vec<usize> {
+        (0..shots).map(|_| self.sample(rng)).collect()
+    }
+
+    /// Draw `shots` shots, and count them.  The counts are the outcomes that occurred, in
+    /// increasing order, with the number of times each occurred.
+    pub fn sample_counts<R: Rng + ?Sized>(&self, shots: usize, rng: &mut R) -> Vec<(usize, u64)> {
+        let mut counts = vec![0u64; self.len()];
+        for _ in 0..shots {
+            counts[self.sample(rng)] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+}
+
+/// The weights of a distribution from Python: probabilities, or statevector amplitudes.
+#[cfg(feature = "python")]
+#[derive(FromPyObject)]
+pub enum Weights<'py> {
+    Probabilities(PyReadonlyArray1<'py, f64>),
+    Amplitudes(PyReadonlyArray1<'py, Complex64>),
+}
+
+#[cfg(feature = "python")]
+impl Weights<'_> {
+    fn table(&self) -> Result<AliasTable, AccelerateError> {
+        match self {
+            Weights::Probabilities(probabilities) => {
+                AliasTable::new(&probabilities.as_array().to_vec())
+            }
+            Weights::Amplitudes(amplitudes) => {
+                AliasTable::from_amplitudes(&amplitudes.as_array().to_vec())
+            }
+        }
+    }
+}
+
+/// Sample the outcomes of shots.
+///
+/// Args:
+///     weights (numpy.ndarray): the probabilities of the outcomes, which need not be normalized,
+///         or the complex amplitudes of a statevector.
+///     shots (int): the number of shots.
+///     seed (int | SeedSequence | None): the seed of the sampling.
+///
+/// Returns:
+///     numpy.ndarray: the index of the outcome of each shot.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (weights, shots, seed=None))]
+pub fn sample_memory(
+    py: Python,
+    weights: Weights,
+    shots: usize,
+    seed: Option<PySeed>,
+) -> PyResult<Py<PyArray1<usize>>> {
+    let table = weights.table()?;
+    let mut rng = PySeed::generator(seed);
+    let memory = py.allow_threads(|| table.sample_memory(shots, &mut rng));
+    Ok(memory.into_pyarray_bound(py).unbind())
+}
+
+/// Sample shots, and count their outcomes.
+///
+/// Args:
+///     weights (numpy.ndarray): the probabilities of the outcomes, which need not be normalized,
+///         or the complex amplitudes of a statevector.
+///     shots (int): the number of shots.
+///     seed (int | SeedSequence | None): the seed of the sampling.
+///
+/// Returns:
+///     (numpy.ndarray, numpy.ndarray): the indices of the outcomes that occurred, in increasing
+///     order, and the number of times each occurred.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (weights, shots, seed=None))]
+pub fn sample_counts(
+    py: Python,
+    weights: Weights,
+    shots: usize,
+    seed: Option<PySeed>,
+) -> PyResult<(Py<PyArray1<usize>>, Py<PyArray1<u64>>)> {
+    let table = weights.table()?;
+    let mut rng = PySeed::generator(seed);
+    let (outcomes, counts): (Vec<usize>, Vec<u64>) = py
+        .allow_threads(|| table.sample_counts(shots, &mut rng))
+        .into_iter()
+        .unzip();
+    Ok((
+        outcomes.into_pyarray_bound(py).unbind(),
+        counts.into_pyarray_bound(py).unbind(),
+    ))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn sampling(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(sample_memory))?;
+    m.add_wrapped(wrap_pyfunction!(sample_counts))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_pcg::Pcg64Mcg;
+
+    #[test]
+    fn test_table() {
+        let table = AliasTable::new(&[1., 3.]).unwrap();
+        assert_eq!(table.keep, vec![0.5, 1.]);
+        assert_eq!(table.alias, vec![1, 1]);
+        let table = AliasTable::from_amplitudes(&[
+            Complex64::new(0., 0.5_f64.sqrt()),
+            Complex64::new(0.5_f64.sqrt(), 0.),
+        ])
+        .unwrap();
+        assert_eq!(table.len(), 2);
+        assert!(table.keep.iter().all(|keep| (keep - 1.).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_bad_weights() {
+        for weights in [vec![], vec![0., 0.], vec![1., -0.5], vec![f64::NAN, 1.]] {
+            assert!(matches!(
+                AliasTable::new(&weights),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let weights = [1., 0., 2., 3., 0., 4.];
+        let table = AliasTable::new(&weights).unwrap();
+        let shots = 100_000;
+        let counts = table.sample_counts(shots, &mut Pcg64Mcg::seed_from_u64(2024));
+        // Outcomes with no weight never occur.
+        assert_eq!(
+            counts
+                .iter()
+                .map(|(outcome, _)| *outcome)
+                .collect::<Vec<_>>(),
+            vec![0, 2, 3, 5]
+        );
+        assert_eq!(
+            counts.iter().map(|(_, count)| count).sum::<u64>(),
+            shots as u64
+        );
+        for (outcome, count) in counts {
+            assert!((count as f64 / shots as f64 - weights[outcome] / 10.).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_seeded() {
+        let table = AliasTable::new(&[0.1, 0.2, 0.3, 0.4]).unwrap();
+        let memory = table.sample_memory(100, &mut Pcg64Mcg::seed_from_u64(7));
+        assert_eq!(
+            memory,
+            table.sample_memory(100, &mut Pcg64Mcg::seed_from_u64(7))
+        );
+        let mut expected = vec![0u64; 4];
+        for outcome in memory {
+            expected[outcome] += 1;
+        }
+        let counts = table.sample_counts(100, &mut Pcg64Mcg::seed_from_u64(7));
+        assert_eq!(
+            counts,
+            expected
+                .into_iter()
+                .enumerate()
+                .filter(|(_, count)| *count > 0)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,921 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Instruction scheduling of physical circuits, as done by the `ASAPScheduleAnalysis` and
+//! `ALAPScheduleAnalysis` passes, and the idle windows of a scheduled circuit that the padding
+//! passes fill with delays or dynamical-decoupling sequences.
+//!
+//! All times are integers in units of the backend's `dt`.  The instructions are given in a
+//! topological order of the circuit, which is the order of a `CircuitData`.
+
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::imports;
+
+use smallvec::SmallVec;
+
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::instrumentation;
+#[cfg(feature = "python")]
+use crate::nlayout::PhysicalQubit;
+#[cfg(feature = "python")]
+use crate::target::{Qargs, Target};
+
+/// How an instruction is timed against the classical bits it uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimingKind {
+    /// A gate, which may be classically conditioned.
+    Gate,
+    /// A delay, which may be classically conditioned, and is dropped by padding.
+    Delay,
+    /// A measurement, which writes its clbits only at its end.
+    Measure,
+    /// Any other instruction, such as a barrier or a reset.
+    Other,
+}
+
+/// An instruction with the information needed to schedule it.
+#[derive(Clone, Debug)]
+pub struct TimedInstruction {
+    pub name: String,
+    pub qubits: SmallVec<[usize; 2]>,
+    pub clbits: SmallVec<[usize; 2]>,
+    /// The clbits of the classical condition of the instruction, if any.
+    pub condition_bits: SmallVec<[usize; 2]>,
+    pub duration: i64,
+    pub kind: TimingKind,
+}
+
+/// The latencies of the classical hardware, from the ``conditional_latency`` and
+/// ``clbit_write_latency`` fields of the property set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Latencies {
+    /// The time to read a clbit for a conditional operation.
+    pub conditional: i64,
+    /// The time before the end of a measurement at which its clbit is written.
+    pub clbit_write: i64,
+}
+
+fn latest<'a, I>(idle: &[i64], bits: I) -> i64
+where
+    I: IntoIterator<Item = &'a usize>,
+{
+    bits.into_iter().map(|bit| idle[*bit]).max().unwrap_or(0)
+}
+
+fn unsupported_condition(inst: &TimedInstruction, scheduler: &str) -> AccelerateError {
+    AccelerateError::Transpiler(format!(
+        "Conditional instruction {} is not supported in {scheduler} scheduler.",
+        inst.name
+    ))
+}
+
+/// Schedule each instruction as early as possible, returning their start times.
+pub fn asap_schedule_inner(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[TimedInstruction],
+    latencies: Latencies,
+) -> Result<Vec<i64>, AccelerateError> {
+    // The time after which each qubit, and then each clbit, is free.
+    let mut idle_after = vec![0; num_qubits + num_clbits];
+    let clbit = |bit: &usize| num_qubits + bit;
+    let mut start_times = Vec::with_capacity(instructions.len());
+    for inst in instructions {
+        let clbits = inst
+            .clbits
+            .iter()
+            .map(clbit)
+            .collect::<SmallVec<[usize; 2]>>();
+        let (t0, t1) = match inst.kind {
+            TimingKind::Gate | TimingKind::Delay => {
+                let t0q = latest(&idle_after, &inst.qubits);
+                let t0 = if inst.condition_bits.is_empty() {
+                    t0q
+                } else {
+                    let condition = inst
+                        .condition_bits
+                        .iter()
+                        .map(clbit)
+                        .collect::<SmallVec<[usize; 2]>>();
+                    // The read of the condition can happen before the qubits are free, as long as
+                    // it finishes by then.
+                    let mut t0c = latest(&idle_after, &condition);
+                    if t0q > t0c {
+                        t0c = t0c.max(t0q - latencies.conditional);
+                    }
+                    let t1c = t0c + latencies.conditional;
+                    for bit in condition {
+                        idle_after[bit] = t1c;
+                    }
+                    t0q.max(t1c)
+                };
+                (t0, t0 + inst.duration)
+            }
+            _ if !inst.condition_bits.is_empty() => {
+                return Err(unsupported_condition(inst, "ASAP"));
+            }
+            TimingKind::Measure => {
+                // The clbits are only written at the end of the measurement, so it may start
+                // before they are free.
+                let t0q = latest(&idle_after, &inst.qubits);
+                let t0c = latest(&idle_after, &clbits);
+                let t0 = t0q.max(t0c - latencies.clbit_write);
+                let t1 = t0 + inst.duration;
+                for bit in clbits {
+                    idle_after[bit] = t1;
+                }
+                (t0, t1)
+            }
+            TimingKind::Other => {
+                let t0 = latest(&idle_after, inst.qubits.iter().chain(&clbits));
+                (t0, t0 + inst.duration)
+            }
+        };
+        for qubit in inst.qubits.iter() {
+            idle_after[*qubit] = t1;
+        }
+        start_times.push(t0);
+    }
+    Ok(start_times)
+}
+
+/// Schedule each instruction as late as possible, returning their start times.
+///
+/// The instructions are packed from the end of the circuit, so the circuit is scheduled in reverse
+/// and the times are flipped at the end.
+pub fn alap_schedule_inner(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[TimedInstruction],
+    latencies: Latencies,
+) -> Result<Vec<i64>, AccelerateError> {
+    // The time, counted back from the end, before which each qubit and clbit is free.
+    let mut idle_before = vec![0; num_qubits + num_clbits];
+    let clbit = |bit: &usize| num_qubits + bit;
+    let mut end_times = Vec::with_capacity(instructions.len());
+    for inst in instructions.iter().rev() {
+        let clbits = inst
+            .clbits
+            .iter()
+            .map(clbit)
+            .collect::<SmallVec<[usize; 2]>>();
+        let t1 = match inst.kind {
+            TimingKind::Gate | TimingKind::Delay => {
+                let t0q = latest(&idle_before, &inst.qubits);
+                if inst.condition_bits.is_empty() {
+                    t0q + inst.duration
+                } else {
+                    let condition = inst
+                        .condition_bits
+                        .iter()
+                        .map(clbit)
+                        .collect::<SmallVec<[usize; 2]>>();
+                    let t0c = latest(&idle_before, &condition);
+                    let t0 = t0q.max(t0c - inst.duration);
+                    let t1 = t0 + inst.duration;
+                    for bit in condition {
+                        idle_before[bit] = t1 + latencies.conditional;
+                    }
+                    t1
+                }
+            }
+            _ if !inst.condition_bits.is_empty() => {
+                return Err(unsupported_condition(inst, "ALAP"));
+            }
+            TimingKind::Measure => {
+                let t0 = latest(&idle_before, inst.qubits.iter().chain(&clbits));
+                for bit in clbits {
+                    idle_before[bit] = t0 + (inst.duration - latencies.clbit_write);
+                }
+                t0 + inst.duration
+            }
+            TimingKind::Other => {
+                latest(&idle_before, inst.qubits.iter().chain(&clbits)) + inst.duration
+            }
+        };
+        for qubit in inst.qubits.iter() {
+            idle_before[*qubit] = t1;
+        }
+        end_times.push(t1);
+    }
+    let circuit_duration = idle_before.iter().copied().max().unwrap_or(0);
+    Ok(end_times
+        .into_iter()
+        .rev()
+        .map(|t1| circuit_duration - t1)
+        .collect())
+}
+
+/// An idle period of a qubit in a scheduled circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdleWindow {
+    pub qubit: usize,
+    pub start: i64,
+    pub end: i64,
+    /// The instruction before the window on the qubit, or `None` at the start of the circuit.
+    pub prev: Option<usize>,
+    /// The instruction after the window on the qubit, or `None` at the end of the circuit.
+    pub next: Option<usize>,
+}
+
+/// The idle windows of the qubits of a scheduled circuit, and the duration of the circuit.
+///
+/// Delays count as idle time.  The windows before each instruction are given in the order of the
+/// instructions and then of their qubits, followed by the windows at the end of the circuit in the
+/// order of the qubits, which is the order in which a padding pass inserts them.  Windows on the
+/// qubits for which `can_pad` is false are left out.
+pub fn idle_windows_inner<F>(
+    num_qubits: usize,
+    instructions: &[TimedInstruction],
+    start_times: &[i64],
+    can_pad: F,
+) -> Result<(Vec<IdleWindow>, i64), AccelerateError>
+where
+    F: Fn(usize) -> bool,
+{
+    if start_times.len() != instructions.len() {
+        return Err(AccelerateError::Transpiler(format!(
+            "{} instructions are scheduled, but the circuit has {}. Schedule the circuit again if \
+             you transformed it.",
+            start_times.len(),
+            instructions.len()
+        )));
+    }
+    let mut idle_after = vec![0; num_qubits];
+    let mut last = vec![None; num_qubits];
+    let mut windows = Vec::new();
+    let mut circuit_duration = 0;
+    for (index, (inst, t0)) in instructions.iter().zip(start_times).enumerate() {
+        let t1 = t0 + inst.duration;
+        circuit_duration = circuit_duration.max(t1);
+        if inst.kind == TimingKind::Delay {
+            continue;
+        }
+        for qubit in inst.qubits.iter() {
+            if *t0 > idle_after[*qubit] && can_pad(*qubit) {
+                windows.push(IdleWindow {
+                    qubit: *qubit,
+                    start: idle_after[*qubit],
+                    end: *t0,
+                    prev: last[*qubit],
+                    next: Some(index),
+                });
+            }
+            idle_after[*qubit] = t1;
+            last[*qubit] = Some(index);
+        }
+    }
+    for qubit in 0..num_qubits {
+        if circuit_duration > idle_after[qubit] && can_pad(qubit) {
+            windows.push(IdleWindow {
+                qubit,
+                start: idle_after[qubit],
+                end: circuit_duration,
+                prev: last[qubit],
+                next: None,
+            });
+        }
+    }
+    Ok((windows, circuit_duration))
+}
+
+/// The timing of the longest path through a circuit, weighted by the durations of the
+/// instructions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CriticalPath {
+    /// The length of the path, which is the shortest duration of any schedule of the circuit.
+    pub duration: i64,
+    /// The instructions on the path, in order.
+    pub path: Vec<usize>,
+    /// How much later than as soon as possible each instruction can start without making the
+    /// circuit longer, which is zero on the path.
+    pub slack: Vec<i64>,
+    /// The time each qubit isn't busy over the duration of the circuit, where delays count as idle.
+    pub idle_time: Vec<i64>,
+}
+
+/// The critical path of a circuit, and the slack of its instructions and the idle time of its
+/// qubits.
+///
+/// An instruction depends on the last instructions before it on each of its qubits and clbits,
+/// including those of its condition.  The latencies of the classical hardware are not taken into
+/// account, so the duration is that of the data dependencies alone.
+pub fn critical_path_inner(
+    num_qubits: usize,
+    num_clbits: usize,
+    instructions: &[TimedInstruction],
+) -> CriticalPath {
+    let wires = |inst: &TimedInstruction| -> SmallVec<[usize; 4]> {
+        inst.qubits
+            .iter()
+            .copied()
+            .chain(
+                inst.clbits
+                    .iter()
+                    .chain(&inst.condition_bits)
+                    .map(|bit| num_qubits + bit),
+            )
+            .collect()
+    };
+    // The earliest time at which each wire is free, and the instruction that last used it.
+    let mut ready = vec![0; num_qubits + num_clbits];
+    let mut last: Vec<Option<usize>> = vec![None; num_qubits + num_clbits];
+    let mut earliest = Vec::with_capacity(instructions.len());
+    let mut prev = Vec::with_capacity(instructions.len());
+    for (index, inst) in instructions.iter().enumerate() {
+        let wires = wires(inst);
+        let (t0, pred) = wires
+            .iter()
+            .filter(|wire| last[**wire].is_some())
+            .max_by_key(|wire| ready[**wire])
+            .map_or((0, None), |wire| (ready[*wire], last[*wire]));
+        for wire in wires {
+            ready[wire] = t0 + inst.duration;
+            last[wire] = Some(index);
+        }
+        earliest.push(t0);
+        prev.push(pred);
+    }
+    let duration = ready.iter().copied().max().unwrap_or(0);
+
+    let mut path = Vec::new();
+    let mut current = instructions
+        .iter()
+        .zip(&earliest)
+        .enumerate()
+        .max_by_key(|(_, (inst, t0))| *t0 + inst.duration)
+        .map(|(index, _)| index);
+    while let Some(index) = current {
+        path.push(index);
+        current = prev[index];
+    }
+    path.reverse();
+
+    // The latest time at which each wire must be free for the circuit to end by `duration`.
+    let mut deadline = vec![duration; num_qubits + num_clbits];
+    let mut slack = vec![0; instructions.len()];
+    for (index, inst) in instructions.iter().enumerate().rev() {
+        let wires = wires(inst);
+        let t1 = wires
+            .iter()
+            .map(|wire| deadline[*wire])
+            .min()
+            .unwrap_or(duration);
+        let t0 = t1 - inst.duration;
+        for wire in wires {
+            deadline[wire] = t0;
+        }
+        slack[index] = t0 - earliest[index];
+    }
+
+    let mut idle_time = vec![duration; num_qubits];
+    for inst in instructions {
+        if inst.kind != TimingKind::Delay {
+            for qubit in inst.qubits.iter() {
+                idle_time[*qubit] -= inst.duration;
+            }
+        }
+    }
+    CriticalPath {
+        duration,
+        path,
+        slack,
+        idle_time,
+    }
+}
+
+/// Read the scheduling information of the instructions of a circuit.
+///
+/// The durations are taken from `target` where it has them, in units of its `dt`, and otherwise
+/// are the ``duration`` attributes of the operations, which ``TimeUnitConversion`` has set in
+/// units of ``dt``.  Delays always take their own duration.
+#[cfg(feature = "python")]
+pub(crate) fn timed_instructions(
+    py: Python,
+    circuit: &CircuitData,
+    target: Option<&Target>,
+) -> PyResult<Vec<TimedInstruction>> {
+    let gate_class = imports::GATE.get_bound(py)?;
+    let delay_class = imports::DELAY.get_bound(py)?;
+    let measure_class = imports::MEASURE.get_bound(py)?;
+
+    let mut out = Vec::with_capacity(circuit.iter_instructions().len());
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        let op = op.bind(py);
+        let name = op.getattr(intern!(py, "name"))?.extract::<String>()?;
+        let kind = if op.is_instance(&delay_class)? {
+            TimingKind::Delay
+        } else if op.is_instance(&gate_class)? {
+            TimingKind::Gate
+        } else if op.is_instance(&measure_class)? {
+            TimingKind::Measure
+        } else {
+            TimingKind::Other
+        };
+        let target_duration = match target {
+            Some(target) if kind != TimingKind::Delay => {
+                let qargs = qubits
+                    .iter()
+                    .map(|q| PhysicalQubit::new(*q))
+                    .collect::<Qargs>();
+                target.duration_dt(&name, &qargs)?
+            }
+            _ => None,
+        };
+        let duration = match target_duration {
+            Some(duration) => duration as i64,
+            None => {
+                let duration = op.getattr(intern!(py, "duration"))?;
+                if duration.is_none() {
+                    return Err(AccelerateError::Transpiler(format!(
+                        "Duration of {name} on qubits {qubits:?} is not found."
+                    ))
+                    .into());
+                }
+                let Ok(duration) = duration.extract::<i64>() else {
+                    return Err(AccelerateError::Transpiler(format!(
+                        "Parameterized duration ({duration}) of {name} on qubits {qubits:?} is \
+                         not bounded."
+                    ))
+                    .into());
+                };
+                duration
+            }
+        };
+        let mut condition_bits = SmallVec::new();
+        if op.hasattr(intern!(py, "condition_bits"))? {
+            for bit in op.getattr(intern!(py, "condition_bits"))?.iter()? {
+                let bit = bit?;
+                let index = circuit.find_clbit(&bit)?.ok_or_else(|| {
+                    AccelerateError::Transpiler(format!(
+                        "The condition of {name} uses a clbit that is not in the circuit."
+                    ))
+                })?;
+                condition_bits.push(index as usize);
+            }
+        }
+        out.push(TimedInstruction {
+            name,
+            qubits: qubits.iter().map(|q| *q as usize).collect(),
+            clbits: clbits.iter().map(|c| *c as usize).collect(),
+            condition_bits,
+            duration,
+            kind,
+        });
+    }
+    Ok(out)
+}
+
+/// The start times of the instructions of `circuit`: `start_times` if given, and otherwise those
+/// that a scheduler recorded on the circuit.
+#[cfg(feature = "python")]
+pub(crate) fn resolve_start_times(
+    circuit: &CircuitData,
+    start_times: Option<Vec<i64>>,
+) -> PyResult<Vec<i64>> {
+    if let Some(start_times) = start_times {
+        return Ok(start_times);
+    }
+    (0..circuit.iter_instructions().len())
+        .map(|index| {
+            circuit
+                .timing(index)
+                .and_then(|timing| timing.start_time)
+                .ok_or_else(|| {
+                    AccelerateError::Transpiler(format!(
+                        "Instruction {index} of the circuit is not scheduled."
+                    ))
+                    .into()
+                })
+        })
+        .collect()
+}
+
+/// Schedule the instructions of a physical circuit as early as possible.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, whose operations all have durations in ``dt`` unless
+///         ``target`` has them.
+///     conditional_latency (int): the time to read a clbit for a conditional operation.
+///     clbit_write_latency (int): the time before the end of a measurement at which its clbit
+///         is written.
+///     target (Target | None): the Rust-space target to take the durations from where it has
+///         them, instead of from the operations.
+///
+/// Returns:
+///     list[int]: the start time of each instruction, which is also recorded on ``circuit`` with
+///     the duration of each instruction in ``dt``.
+///
+/// Raises:
+///     TranspilerError: if an instruction has no duration, or has a classical condition that the
+///         scheduler doesn't support.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, conditional_latency=0, clbit_write_latency=0, target=None))]
+pub fn asap_schedule(
+    py: Python,
+    circuit: &mut CircuitData,
+    conditional_latency: i64,
+    clbit_write_latency: i64,
+    target: Option<&Target>,
+) -> PyResult<Vec<i64>> {
+    let _timer = instrumentation::Timer::start("asap_schedule");
+    let instructions = timed_instructions(py, circuit, target)?;
+    let start_times = asap_schedule_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions,
+        Latencies {
+            conditional: conditional_latency,
+            clbit_write: clbit_write_latency,
+        },
+    )?;
+    let durations = instructions
+        .iter()
+        .map(|inst| inst.duration)
+        .collect::<Vec<_>>();
+    circuit.set_schedule(Some(&durations), &start_times)?;
+    Ok(start_times)
+}
+
+/// Schedule the instructions of a physical circuit as late as possible.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, whose operations all have durations in ``dt`` unless
+///         ``target`` has them.
+///     conditional_latency (int): the time to read a clbit for a conditional operation.
+///     clbit_write_latency (int): the time before the end of a measurement at which its clbit
+///         is written.
+///     target (Target | None): the Rust-space target to take the durations from where it has
+///         them, instead of from the operations.
+///
+/// Returns:
+///     list[int]: the start time of each instruction, which is also recorded on ``circuit`` with
+///     the duration of each instruction in ``dt``.
+///
+/// Raises:
+///     TranspilerError: if an instruction has no duration, or has a classical condition that the
+///         scheduler doesn't support.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, conditional_latency=0, clbit_write_latency=0, target=None))]
+pub fn alap_schedule(
+    py: Python,
+    circuit: &mut CircuitData,
+    conditional_latency: i64,
+    clbit_write_latency: i64,
+    target: Option<&Target>,
+) -> PyResult<Vec<i64>> {
+    let _timer = instrumentation::Timer::start("alap_schedule");
+    let instructions = timed_instructions(py, circuit, target)?;
+    let start_times = alap_schedule_inner(
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        &instructions,
+        Latencies {
+            conditional: conditional_latency,
+            clbit_write: clbit_write_latency,
+        },
+    )?;
+    let durations = instructions
+        .iter()
+        .map(|inst| inst.duration)
+        .collect::<Vec<_>>();
+    circuit.set_schedule(Some(&durations), &start_times)?;
+    Ok(start_times)
+}
+
+/// Find the longest path through a physical circuit, weighted by the durations of its
+/// instructions, as a measure of how well it can be scheduled.
+///
+/// The latencies of the classical hardware are not taken into account.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, whose operations all have durations in ``dt`` unless
+///         ``target`` has them.
+///     target (Target | None): the Rust-space target to take the durations from where it has
+///         them, instead of from the operations.
+///
+/// Returns:
+///     (int, list[int], list[int], list[int]): the duration of the path, the indices of the
+///     instructions on it in order, the slack of each instruction, which is how much later than
+///     as soon as possible it can start without making the circuit longer, and the idle time of
+///     each qubit, in which delays count as idle.
+///
+/// Raises:
+///     TranspilerError: if an instruction has no duration.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, target=None))]
+pub fn critical_path(
+    py: Python,
+    circuit: &CircuitData,
+    target: Option<&Target>,
+) -> PyResult<(i64, Vec<usize>, Vec<i64>, Vec<i64>)> {
+    let _timer = instrumentation::Timer::start("critical_path");
+    let instructions = timed_instructions(py, circuit, target)?;
+    let out = critical_path_inner(circuit.num_qubits(), circuit.num_clbits(), &instructions);
+    Ok((out.duration, out.path, out.slack, out.idle_time))
+}
+
+/// Fill the idle windows of a scheduled circuit with delays, as ``PadDelay`` does.
+///
+/// The delays of the input circuit are dropped and their time is padded again.
+///
+/// Args:
+///     circuit (CircuitData): the scheduled circuit.
+///     start_times (list[int] | None): the start time of each instruction of ``circuit``.  By
+///         default, the start times recorded on ``circuit`` by a scheduler.
+///     unit (str): the time unit of the delays.
+///     fill_very_end (bool): whether to pad the idle time at the end of the circuit.
+///     delay_qubits (list[bool] | None): whether each qubit supports delays; qubits that don't are
+///         not padded.  By default, all qubits are padded.
+///
+/// Returns:
+///     (CircuitData, list[int], int): the padded circuit, the start time of each of its
+///     instructions, and the duration of the circuit.  The start times and the durations in ``dt``
+///     of the instructions are also recorded on the padded circuit.
+///
+/// Raises:
+///     TranspilerError: if ``start_times`` isn't given and an instruction isn't scheduled.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, start_times, unit, fill_very_end=true, delay_qubits=None))]
+pub fn pad_delay(
+    py: Python,
+    circuit: &CircuitData,
+    start_times: Option<Vec<i64>>,
+    unit: &str,
+    fill_very_end: bool,
+    delay_qubits: Option<Vec<bool>>,
+) -> PyResult<(CircuitData, Vec<i64>, i64)> {
+    let start_times = resolve_start_times(circuit, start_times)?;
+    let instructions = timed_instructions(py, circuit, None)?;
+    let (windows, circuit_duration) =
+        idle_windows_inner(circuit.num_qubits(), &instructions, &start_times, |qubit| {
+            delay_qubits.as_ref().map_or(true, |delay| delay[qubit])
+        })?;
+    let delay_class = imports::DELAY.get_bound(py)?;
+
+    let mut out = circuit.copy_empty_like(py)?;
+    let mut out_start_times = Vec::with_capacity(instructions.len() + windows.len());
+    let mut out_durations = Vec::with_capacity(instructions.len() + windows.len());
+    let mut windows = windows.into_iter().peekable();
+    for (index, ((op, qubits, clbits), inst)) in
+        circuit.iter_instructions().zip(&instructions).enumerate()
+    {
+        while let Some(window) = windows.next_if(|window| window.next == Some(index)) {
+            let delay = delay_class.call1((window.end - window.start, unit))?;
+            out.push_instruction(delay.unbind(), &[window.qubit as u32], &[])?;
+            out_start_times.push(window.start);
+            out_durations.push(window.end - window.start);
+        }
+        if inst.kind != TimingKind::Delay {
+            out.push_instruction(op.clone_ref(py), qubits, clbits)?;
+            out_start_times.push(start_times[index]);
+            out_durations.push(inst.duration);
+        }
+    }
+    if fill_very_end {
+        for window in windows {
+            let delay = delay_class.call1((window.end - window.start, unit))?;
+            out.push_instruction(delay.unbind(), &[window.qubit as u32], &[])?;
+            out_start_times.push(window.start);
+            out_durations.push(window.end - window.start);
+        }
+    }
+    out.set_schedule(Some(&out_durations), &out_start_times)?;
+    Ok((out, out_start_times, circuit_duration))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn scheduling(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(asap_schedule))?;
+    m.add_wrapped(wrap_pyfunction!(alap_schedule))?;
+    m.add_wrapped(wrap_pyfunction!(critical_path))?;
+    m.add_wrapped(wrap_pyfunction!(pad_delay))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(
+        name: &str,
+        qubits: &[usize],
+        clbits: &[usize],
+        duration: i64,
+        kind: TimingKind,
+    ) -> TimedInstruction {
+        TimedInstruction {
+            name: name.to_string(),
+            qubits: qubits.iter().copied().collect(),
+            clbits: clbits.iter().copied().collect(),
+            condition_bits: SmallVec::new(),
+            duration,
+            kind,
+        }
+    }
+
+    fn conditioned(mut inst: TimedInstruction, bits: &[usize]) -> TimedInstruction {
+        inst.condition_bits = bits.iter().copied().collect();
+        inst
+    }
+
+    /// `sx(0); cx(0, 1); x(1); x(2)`.
+    fn gates() -> Vec<TimedInstruction> {
+        vec![
+            inst("sx", &[0], &[], 160, TimingKind::Gate),
+            inst("cx", &[0, 1], &[], 800, TimingKind::Gate),
+            inst("x", &[1], &[], 160, TimingKind::Gate),
+            inst("x", &[2], &[], 160, TimingKind::Gate),
+        ]
+    }
+
+    #[test]
+    fn test_asap() {
+        let times = asap_schedule_inner(3, 0, &gates(), Latencies::default()).unwrap();
+        assert_eq!(times, [0, 160, 960, 0]);
+    }
+
+    #[test]
+    fn test_alap() {
+        let times = alap_schedule_inner(3, 0, &gates(), Latencies::default()).unwrap();
+        assert_eq!(times, [0, 160, 960, 960]);
+    }
+
+    #[test]
+    fn test_measurements_on_one_clbit() {
+        let instructions = [
+            inst("measure", &[0], &[0], 1000, TimingKind::Measure),
+            inst("measure", &[1], &[0], 1000, TimingKind::Measure),
+        ];
+        let times = asap_schedule_inner(2, 1, &instructions, Latencies::default()).unwrap();
+        assert_eq!(times, [0, 1000]);
+        // The second measurement only needs the clbit by the time it writes it.
+        let latencies = Latencies {
+            conditional: 0,
+            clbit_write: 300,
+        };
+        let times = asap_schedule_inner(2, 1, &instructions, latencies).unwrap();
+        assert_eq!(times, [0, 700]);
+    }
+
+    #[test]
+    fn test_conditional_gate() {
+        let instructions = [
+            inst("measure", &[0], &[0], 1000, TimingKind::Measure),
+            conditioned(inst("x", &[1], &[], 200, TimingKind::Gate), &[0]),
+        ];
+        let times = asap_schedule_inner(2, 1, &instructions, Latencies::default()).unwrap();
+        assert_eq!(times, [0, 1000]);
+        let latencies = Latencies {
+            conditional: 200,
+            clbit_write: 0,
+        };
+        let times = asap_schedule_inner(2, 1, &instructions, latencies).unwrap();
+        assert_eq!(times, [0, 1200]);
+        let times = alap_schedule_inner(2, 1, &instructions, latencies).unwrap();
+        assert_eq!(times, [0, 1200]);
+    }
+
+    #[test]
+    fn test_conditional_measure_unsupported() {
+        let instructions = [conditioned(
+            inst("measure", &[0], &[1], 1000, TimingKind::Measure),
+            &[0],
+        )];
+        assert!(matches!(
+            asap_schedule_inner(1, 2, &instructions, Latencies::default()),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert!(matches!(
+            alap_schedule_inner(1, 2, &instructions, Latencies::default()),
+            Err(AccelerateError::Transpiler(_))
+        ));
+    }
+
+    #[test]
+    fn test_critical_path() {
+        let out = critical_path_inner(3, 0, &gates());
+        assert_eq!(
+            out,
+            CriticalPath {
+                duration: 1120,
+                path: vec![0, 1, 2],
+                slack: vec![0, 0, 0, 960],
+                idle_time: vec![160, 160, 960],
+            }
+        );
+    }
+
+    #[test]
+    fn test_critical_path_delays_are_idle() {
+        let instructions = [
+            inst("delay", &[0], &[], 500, TimingKind::Delay),
+            inst("x", &[0], &[], 160, TimingKind::Gate),
+            inst("x", &[1], &[], 100, TimingKind::Gate),
+        ];
+        let out = critical_path_inner(2, 0, &instructions);
+        assert_eq!(out.duration, 660);
+        assert_eq!(out.path, [0, 1]);
+        assert_eq!(out.slack, [0, 0, 560]);
+        assert_eq!(out.idle_time, [500, 560]);
+    }
+
+    #[test]
+    fn test_critical_path_through_condition() {
+        let instructions = [
+            inst("measure", &[0], &[0], 1000, TimingKind::Measure),
+            conditioned(inst("x", &[1], &[], 200, TimingKind::Gate), &[0]),
+        ];
+        let out = critical_path_inner(2, 1, &instructions);
+        assert_eq!(out.duration, 1200);
+        assert_eq!(out.path, [0, 1]);
+        assert_eq!(out.slack, [0, 0]);
+        assert_eq!(out.idle_time, [200, 1000]);
+    }
+
+    #[test]
+    fn test_critical_path_empty() {
+        let out = critical_path_inner(2, 0, &[]);
+        assert_eq!(out.duration, 0);
+        assert!(out.path.is_empty() && out.slack.is_empty());
+        assert_eq!(out.idle_time, [0, 0]);
+    }
+
+    #[test]
+    fn test_idle_windows() {
+        let instructions = gates();
+        let (windows, duration) =
+            idle_windows_inner(3, &instructions, &[0, 160, 960, 0], |_| true).unwrap();
+        assert_eq!(duration, 1120);
+        let window = |qubit, start, end, prev, next| IdleWindow {
+            qubit,
+            start,
+            end,
+            prev,
+            next,
+        };
+        assert_eq!(
+            windows,
+            [
+                window(1, 0, 160, None, Some(1)),
+                window(0, 960, 1120, Some(1), None),
+                window(2, 160, 1120, Some(3), None),
+            ]
+        );
+        let (windows, _) =
+            idle_windows_inner(3, &instructions, &[0, 160, 960, 0], |qubit| qubit != 2).unwrap();
+        assert_eq!(windows.len(), 2);
+    }
+
+    #[test]
+    fn test_idle_windows_include_delays() {
+        let instructions = [
+            inst("delay", &[0], &[], 500, TimingKind::Delay),
+            inst("x", &[0], &[], 160, TimingKind::Gate),
+        ];
+        let (windows, duration) =
+            idle_windows_inner(1, &instructions, &[0, 500], |_| true).unwrap();
+        assert_eq!(duration, 660);
+        assert_eq!(
+            windows,
+            [IdleWindow {
+                qubit: 0,
+                start: 0,
+                end: 500,
+                prev: None,
+                next: Some(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_idle_windows_stale_schedule() {
+        assert!(matches!(
+            idle_windows_inner(3, &gates(), &[0, 160], |_| true),
+            Err(AccelerateError::Transpiler(_))
+        ));
+    }
+}
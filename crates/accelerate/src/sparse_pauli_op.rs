@@ -20,12 +20,15 @@ use numpy::prelude::*;
 use numpy::{PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2, PyUntypedArrayMethods};
 
 use hashbrown::HashMap;
-use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis};
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut1, Axis};
 use num_complex::Complex64;
 use num_traits::Zero;
 use rayon::prelude::*;
 
+use crate::error::AccelerateError;
+use crate::nlayout::NLayout;
 use crate::rayon_ext::*;
+use crate::threading::{self, ParallelKernel};
 
 /// Find the unique elements of an array.
 ///
@@ -509,8 +512,11 @@ pub fn to_matrix_dense<'py>(
     let mut paulis = paulis_readonly.as_array().matrix_compress()?;
     paulis.combine();
     let side = 1usize << paulis.num_qubits();
-    let parallel = !force_serial && crate::getenv_use_multiple_threads();
-    let out = to_matrix_dense_inner(&paulis, parallel);
+    let out = if !force_serial && threading::use_multiple_threads() {
+        threading::install(|| to_matrix_dense_inner(&paulis, true))
+    } else {
+        to_matrix_dense_inner(&paulis, false)
+    };
     PyArray1::from_vec_bound(py, out).reshape([side, side])
 }
 
@@ -522,12 +528,19 @@ fn to_matrix_dense_inner(paulis: &MatrixCompressedPaulis, parallel: bool) -> Vec
     #[allow(clippy::uninit_vec)]
     let mut out = {
         let mut out = Vec::with_capacity(side * side);
-        // SAFETY: we iterate through the vec in chunks of `side`, and start each row by filling it
-        // with zeros before ever reading from it.  It's fine to overwrite the uninitialised memory
-        // because `Complex64: !Drop`.
+        // SAFETY: `write_matrix_dense` fills each row of `side` with zeros before ever reading
+        // from it.  It's fine to overwrite the uninitialised memory because `Complex64: !Drop`.
         unsafe { out.set_len(side * side) };
         out
     };
+    write_matrix_dense(paulis, &mut out, parallel);
+    out
+}
+
+/// Write the C-ordered dense matrix of the Paulis into `out`, which must have exactly one entry
+/// per matrix element.  Nothing in `out` is read, so it need not be initialised.
+fn write_matrix_dense(paulis: &MatrixCompressedPaulis, out: &mut [Complex64], parallel: bool) {
+    let side = 1usize << paulis.num_qubits();
     let write_row = |(i_row, row): (usize, &mut [Complex64])| {
         // Doing the initialisation here means that when we're in parallel contexts, we do the
         // zeroing across the whole threadpool.  This also seems to give a speed-up in serial
@@ -555,7 +568,43 @@ fn to_matrix_dense_inner(paulis: &MatrixCompressedPaulis, parallel: bool) -> Vec
     } else {
         out.chunks_mut(side).enumerate().for_each(write_row);
     }
-    out
+}
+
+/// Write the dense matrix of the given [ZXPaulis] object into an existing 2D Numpy matrix, so that
+/// the same output buffer can be reused for many operators of the same size.
+///
+/// Raises:
+///     ValueError: if ``out`` isn't a C-contiguous square matrix with one row per basis state.
+#[pyfunction]
+#[pyo3(signature = (/, paulis, out, force_serial=false))]
+pub fn to_matrix_dense_into(
+    py: Python,
+    paulis: &ZXPaulis,
+    out: &Bound<PyArray2<Complex64>>,
+    force_serial: bool,
+) -> PyResult<()> {
+    let paulis_readonly = paulis
+        .try_readonly(py)
+        .ok_or_else(|| PyRuntimeError::new_err("could not produce a safe view onto the data"))?;
+    let mut paulis = paulis_readonly.as_array().matrix_compress()?;
+    paulis.combine();
+    let side = 1usize << paulis.num_qubits();
+    if out.shape() != [side, side] {
+        return Err(PyValueError::new_err(format!(
+            "the output has shape {:?}, not ({side}, {side})",
+            out.shape()
+        )));
+    }
+    let mut out = out.try_readwrite()?;
+    let out = out
+        .as_slice_mut()
+        .map_err(|_| PyValueError::new_err("the output must be C-contiguous"))?;
+    if !force_serial && threading::use_multiple_threads() {
+        threading::install(|| write_matrix_dense(&paulis, out, true));
+    } else {
+        write_matrix_dense(&paulis, out, false);
+    }
+    Ok(())
 }
 
 type CSRData<T> = (Vec<Complex64>, Vec<T>, Vec<T>);
@@ -601,23 +650,121 @@ pub fn to_matrix_sparse(
     let max_entries_per_row = (paulis.num_ops() as u64).min(1u64 << (paulis.num_qubits() - 1));
     let use_32_bit =
         max_entries_per_row.saturating_mul(1u64 << paulis.num_qubits()) <= (i32::MAX as u64);
+    let parallel = threading::use_multiple_threads() && !force_serial;
     if use_32_bit {
-        let to_sparse: ToCSRData<i32> = if crate::getenv_use_multiple_threads() && !force_serial {
+        let to_sparse: ToCSRData<i32> = if parallel {
             to_matrix_sparse_parallel_32
         } else {
             to_matrix_sparse_serial_32
         };
-        Ok(to_py_tuple(py, to_sparse(&paulis)))
+        let data = if parallel {
+            threading::install(|| to_sparse(&paulis))
+        } else {
+            to_sparse(&paulis)
+        };
+        Ok(to_py_tuple(py, data))
     } else {
-        let to_sparse: ToCSRData<i64> = if crate::getenv_use_multiple_threads() && !force_serial {
+        let to_sparse: ToCSRData<i64> = if parallel {
             to_matrix_sparse_parallel_64
         } else {
             to_matrix_sparse_serial_64
         };
-        Ok(to_py_tuple(py, to_sparse(&paulis)))
+        let data = if parallel {
+            threading::install(|| to_sparse(&paulis))
+        } else {
+            to_sparse(&paulis)
+        };
+        Ok(to_py_tuple(py, data))
     }
 }
 
+/// Write CSR data into existing Numpy arrays with `write`, checking their shapes first.
+fn write_sparse_into<T>(
+    paulis: &MatrixCompressedPaulis,
+    data: &Bound<PyArray1<Complex64>>,
+    indices: &Bound<PyArray1<T>>,
+    indptr: &Bound<PyArray1<T>>,
+    write: fn(&MatrixCompressedPaulis, &mut [Complex64], &mut [T], &mut [T]) -> usize,
+) -> PyResult<usize>
+where
+    T: numpy::Element + TryFrom<usize>,
+{
+    let side = 1usize << paulis.num_qubits();
+    if indptr.len() != side + 1 {
+        return Err(PyValueError::new_err(format!(
+            "indptr has {} entries, not {}",
+            indptr.len(),
+            side + 1
+        )));
+    }
+    if T::try_from(side.max(data.len()).max(indices.len())).is_err() {
+        return Err(PyValueError::new_err(
+            "the index type is too small for the size of the matrix or of the buffers",
+        ));
+    }
+    let not_contiguous = |_| PyValueError::new_err("the buffers must be contiguous");
+    let mut data = data.try_readwrite()?;
+    let mut indices = indices.try_readwrite()?;
+    let mut indptr = indptr.try_readwrite()?;
+    let data = data.as_slice_mut().map_err(not_contiguous)?;
+    let indices = indices.as_slice_mut().map_err(not_contiguous)?;
+    let nnz = write(
+        paulis,
+        data,
+        indices,
+        indptr.as_slice_mut().map_err(not_contiguous)?,
+    );
+    let capacity = data.len().min(indices.len());
+    if nnz > capacity {
+        return Err(PyValueError::new_err(format!(
+            "the matrix has {nnz} nonzero entries, but the buffers only have room for {capacity}"
+        )));
+    }
+    Ok(nnz)
+}
+
+/// Write the three-array CSR form of the given [ZXPaulis] object into existing Numpy arrays, so
+/// that the same buffers can be reused for many operators of the same size.  This is serial.
+///
+/// `indices` and `indptr` must both be `int32` or both `int64`, and `indptr` must have one more
+/// entry than the matrix has rows.  Only the first `nnz` entries of `data` and `indices` are
+/// written, and `nnz` is returned.
+///
+/// Raises:
+///     ValueError: if the buffers have the wrong types or shapes, or if ``data`` and ``indices``
+///         are too short for the nonzero entries, in which case the message gives the length
+///         needed.
+#[pyfunction]
+#[pyo3(signature = (/, paulis, data, indices, indptr))]
+pub fn to_matrix_sparse_into(
+    py: Python,
+    paulis: &ZXPaulis,
+    data: &Bound<PyArray1<Complex64>>,
+    indices: &Bound<PyAny>,
+    indptr: &Bound<PyAny>,
+) -> PyResult<usize> {
+    let paulis_readonly = paulis
+        .try_readonly(py)
+        .ok_or_else(|| PyRuntimeError::new_err("could not produce a safe view onto the data"))?;
+    let mut paulis = paulis_readonly.as_array().matrix_compress()?;
+    paulis.combine();
+    if let (Ok(indices), Ok(indptr)) = (
+        indices.downcast::<PyArray1<i32>>(),
+        indptr.downcast::<PyArray1<i32>>(),
+    ) {
+        return write_sparse_into(&paulis, data, indices, indptr, to_matrix_sparse_into_32);
+    }
+    if let (Ok(indices), Ok(indptr)) = (
+        indices.downcast::<PyArray1<i64>>(),
+        indptr.downcast::<PyArray1<i64>>(),
+    ) {
+        return write_sparse_into(&paulis, data, indices, indptr, to_matrix_sparse_into_64);
+    }
+    Err(PyValueError::new_err(
+        "indices and indptr must be one-dimensional arrays, both of int32 or both of int64",
+    ))
+}
+
 /// Copy several slices into a single flat vec, in parallel.  Allocates a temporary `Vec<usize>` of
 /// the same length as the input slice to track the chunking.
 fn copy_flat_parallel<T, U>(slices: &[U]) -> Vec<T>
@@ -647,7 +794,51 @@ where
 }
 
 macro_rules! impl_to_matrix_sparse {
-    ($serial_fn:ident, $parallel_fn:ident, $int_ty:ty, $uint_ty:ty $(,)?) => {
+    (
+        $row_fn:ident,
+        $serial_fn:ident,
+        $parallel_fn:ident,
+        $into_fn:ident,
+        $int_ty:ty,
+        $uint_ty:ty $(,)?
+    ) => {
+        /// Emit the nonzero entries of row `i_row` of the matrix of the matrix-compressed set of
+        /// the Pauli operators, in order of their columns, as `(value, column)`.  `order` is
+        /// scratch space with one entry per operator.
+        #[inline]
+        fn $row_fn(
+            paulis: &MatrixCompressedPaulis,
+            i_row: usize,
+            order: &mut [usize],
+            mut emit: impl FnMut(Complex64, $int_ty),
+        ) {
+            order.sort_unstable_by(|&a, &b| {
+                ((i_row as $uint_ty) ^ (paulis.x_like[a] as $uint_ty))
+                    .cmp(&((i_row as $uint_ty) ^ (paulis.x_like[b] as $uint_ty)))
+            });
+            let mut running = Complex64::new(0.0, 0.0);
+            let mut prev_index = i_row ^ (paulis.x_like[order[0]] as usize);
+            for (x_like, z_like, coeff) in order
+                .iter()
+                .map(|&i| (paulis.x_like[i], paulis.z_like[i], paulis.coeffs[i]))
+            {
+                let coeff = if ((i_row as $uint_ty) & (z_like as $uint_ty)).count_ones() % 2 == 0 {
+                    coeff
+                } else {
+                    -coeff
+                };
+                let index = i_row ^ (x_like as usize);
+                if index == prev_index {
+                    running += coeff;
+                } else {
+                    emit(running, prev_index as $int_ty);
+                    running = coeff;
+                    prev_index = index;
+                }
+            }
+            emit(running, prev_index as $int_ty);
+        }
+
         /// Build CSR data arrays for the matrix-compressed set of the Pauli operators, using a
         /// completely serial strategy.
         fn $serial_fn(paulis: &MatrixCompressedPaulis) -> CSRData<$int_ty> {
@@ -663,41 +854,50 @@ macro_rules! impl_to_matrix_sparse {
             let mut indptr: Vec<$int_ty> = vec![0; side + 1];
             let mut nnz = 0;
             for i_row in 0..side {
-                order.sort_unstable_by(|&a, &b| {
-                    ((i_row as $uint_ty) ^ (paulis.x_like[a] as $uint_ty))
-                        .cmp(&((i_row as $uint_ty) ^ (paulis.x_like[b] as $uint_ty)))
+                $row_fn(paulis, i_row, &mut order, |value, index| {
+                    nnz += 1;
+                    values.push(value);
+                    indices.push(index);
                 });
-                let mut running = Complex64::new(0.0, 0.0);
-                let mut prev_index = i_row ^ (paulis.x_like[order[0]] as usize);
-                for (x_like, z_like, coeff) in order
-                    .iter()
-                    .map(|&i| (paulis.x_like[i], paulis.z_like[i], paulis.coeffs[i]))
-                {
-                    let coeff =
-                        if ((i_row as $uint_ty) & (z_like as $uint_ty)).count_ones() % 2 == 0 {
-                            coeff
-                        } else {
-                            -coeff
-                        };
-                    let index = i_row ^ (x_like as usize);
-                    if index == prev_index {
-                        running += coeff;
-                    } else {
-                        nnz += 1;
-                        values.push(running);
-                        indices.push(prev_index as $int_ty);
-                        running = coeff;
-                        prev_index = index;
-                    }
-                }
-                nnz += 1;
-                values.push(running);
-                indices.push(prev_index as $int_ty);
                 indptr[i_row + 1] = nnz;
             }
             (values, indices, indptr)
         }
 
+        /// Write the CSR data arrays for the matrix-compressed set of the Pauli operators into
+        /// existing buffers, serially.  `indptr` must have one more entry than the matrix has
+        /// rows.  Returns the number of nonzero entries, which are only all written if `values`
+        /// and `indices` have room for them.
+        fn $into_fn(
+            paulis: &MatrixCompressedPaulis,
+            values: &mut [Complex64],
+            indices: &mut [$int_ty],
+            indptr: &mut [$int_ty],
+        ) -> usize {
+            let side = 1 << paulis.num_qubits();
+            let num_ops = paulis.num_ops();
+            indptr[0] = 0;
+            if num_ops == 0 {
+                indptr.fill(0);
+                return 0;
+            }
+
+            let capacity = values.len().min(indices.len());
+            let mut order = (0..num_ops).collect::<Vec<_>>();
+            let mut nnz = 0;
+            for i_row in 0..side {
+                $row_fn(paulis, i_row, &mut order, |value, index| {
+                    if nnz < capacity {
+                        values[nnz] = value;
+                        indices[nnz] = index;
+                    }
+                    nnz += 1;
+                });
+                indptr[i_row + 1] = nnz as $int_ty;
+            }
+            nnz
+        }
+
         /// Build CSR data arrays for the matrix-compressed set of the Pauli operators, using a
         /// parallel strategy.  This involves more data copying than the serial form, so there is a
         /// nontrivial amount of parallel overhead.
@@ -744,36 +944,11 @@ macro_rules! impl_to_matrix_sparse {
                     let mut indices = Vec::<$int_ty>::with_capacity(chunk_size * (num_ops + 1) / 2);
                     let mut nnz = 0;
                     for i_row in start..end {
-                        order.sort_unstable_by(|&a, &b| {
-                            (i_row as $uint_ty ^ paulis.x_like[a] as $uint_ty)
-                                .cmp(&(i_row as $uint_ty ^ paulis.x_like[b] as $uint_ty))
+                        $row_fn(paulis, i_row, &mut order, |value, index| {
+                            nnz += 1;
+                            values.push(value);
+                            indices.push(index);
                         });
-                        let mut running = Complex64::new(0.0, 0.0);
-                        let mut prev_index = i_row ^ (paulis.x_like[order[0]] as usize);
-                        for (x_like, z_like, coeff) in order
-                            .iter()
-                            .map(|&i| (paulis.x_like[i], paulis.z_like[i], paulis.coeffs[i]))
-                        {
-                            let coeff =
-                                if (i_row as $uint_ty & z_like as $uint_ty).count_ones() % 2 == 0 {
-                                    coeff
-                                } else {
-                                    -coeff
-                                };
-                            let index = i_row ^ (x_like as usize);
-                            if index == prev_index {
-                                running += coeff;
-                            } else {
-                                nnz += 1;
-                                values.push(running);
-                                indices.push(prev_index as $int_ty);
-                                running = coeff;
-                                prev_index = index;
-                            }
-                        }
-                        nnz += 1;
-                        values.push(running);
-                        indices.push(prev_index as $int_ty);
                         // When we write it, this is a cumulative `nnz` _within the chunk_.  We
                         // turn that into a proper cumulative sum in serial afterwards.
                         indptr_chunk[i_row - start] = nnz;
@@ -806,24 +981,185 @@ macro_rules! impl_to_matrix_sparse {
 }
 
 impl_to_matrix_sparse!(
+    to_matrix_sparse_row_32,
     to_matrix_sparse_serial_32,
     to_matrix_sparse_parallel_32,
+    to_matrix_sparse_into_32,
     i32,
     u32
 );
 impl_to_matrix_sparse!(
+    to_matrix_sparse_row_64,
     to_matrix_sparse_serial_64,
     to_matrix_sparse_parallel_64,
+    to_matrix_sparse_into_64,
     i64,
     u64
 );
 
+/// A layout to apply to Pauli operators: either an ``NLayout``, whose virtual qubits are the qubits
+/// of the operators, or a list of the new index of each qubit.
+#[derive(FromPyObject)]
+pub enum PauliLayout {
+    NLayout(NLayout),
+    Indices(Vec<usize>),
+}
+
+impl PauliLayout {
+    /// The new index of each qubit, and the default number of qubits of the output.
+    fn into_indices(self) -> (Vec<usize>, usize) {
+        match self {
+            PauliLayout::NLayout(layout) => (
+                layout
+                    .iter_virtual()
+                    .map(|(_, phys)| phys.index())
+                    .collect(),
+                layout.iter_physical().len(),
+            ),
+            PauliLayout::Indices(indices) => {
+                let len = indices.len();
+                (indices, len)
+            }
+        }
+    }
+}
+
+/// The `z` and `x` bit-arrays of a list of Paulis.
+type SymplecticArrays = (Py<PyArray2<bool>>, Py<PyArray2<bool>>);
+
+/// Move column `i` of a symplectic bit-array to column `layout[i]` of a new array with `num_qubits`
+/// columns; every other column is zero (an identity).
+fn apply_layout_inner(
+    bits: ArrayView2<bool>,
+    layout: &[usize],
+    num_qubits: usize,
+    parallel: bool,
+) -> Array2<bool> {
+    let mut out = Array2::from_elem((bits.nrows(), num_qubits), false);
+    let permute = |(mut out_row, in_row): (ArrayViewMut1<bool>, ArrayView1<bool>)| {
+        for (&target, &bit) in layout.iter().zip(in_row.iter()) {
+            out_row[target] = bit;
+        }
+    };
+    if parallel {
+        out.axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(bits.axis_iter(Axis(0)).into_par_iter())
+            .for_each(permute);
+    } else {
+        out.axis_iter_mut(Axis(0))
+            .zip(bits.axis_iter(Axis(0)))
+            .for_each(permute);
+    }
+    out
+}
+
+/// Permute and embed the qubits of a list of Paulis in the symplectic representation.
+///
+/// This is the bulk of ``SparsePauliOp.apply_layout`` and ``Pauli.apply_layout``; it never forms
+/// the padded operator and composes it, it just moves the columns of the bit-arrays.  The phases
+/// are unaffected.
+///
+/// Args:
+///     z (numpy.ndarray): the ``z`` bits of the Paulis, with shape ``(num_paulis, num_qubits)``.
+///     x (numpy.ndarray): the ``x`` bits, of the same shape as ``z``.
+///     layout (NLayout | list[int] | None): where to move each qubit.  An :class:`.NLayout` maps
+///         each qubit, as a virtual qubit, to its physical qubit; a list gives the new index of each
+///         qubit.  ``None`` leaves the qubits in place and only pads the operators.
+///     num_qubits (int | None): the number of qubits of the output.  If not given, this is the
+///         number of physical qubits of an :class:`.NLayout`, or the number of qubits of the input.
+///
+/// Returns:
+///     (numpy.ndarray, numpy.ndarray): the ``z`` and ``x`` bits of the new Paulis.
+///
+/// Raises:
+///     QiskitError: if the layout doesn't match the Paulis or contains invalid indices.
+#[pyfunction]
+#[pyo3(signature = (z, x, layout, num_qubits=None))]
+pub fn apply_layout_to_paulis(
+    py: Python,
+    z: PyReadonlyArray2<bool>,
+    x: PyReadonlyArray2<bool>,
+    layout: Option<PauliLayout>,
+    num_qubits: Option<usize>,
+) -> PyResult<SymplecticArrays> {
+    let (z, x) = (z.as_array(), x.as_array());
+    if z.shape() != x.shape() {
+        return Err(AccelerateError::Qiskit(format!(
+            "z and x arrays have different shapes ({:?} != {:?})",
+            z.shape(),
+            x.shape()
+        ))
+        .into());
+    }
+    let in_qubits = z.ncols();
+    let (layout, default_qubits) = match layout {
+        Some(layout) => layout.into_indices(),
+        None => ((0..in_qubits).collect(), in_qubits),
+    };
+    if layout.len() != in_qubits {
+        return Err(AccelerateError::Qiskit(format!(
+            "A layout of {} qubits cannot be applied to a {in_qubits} qubit operator",
+            layout.len()
+        ))
+        .into());
+    }
+    let num_qubits = match num_qubits {
+        Some(num_qubits) if num_qubits < default_qubits => {
+            return Err(AccelerateError::Qiskit(format!(
+                "The input num_qubits is too small, a {num_qubits} qubit layout cannot be \
+                applied to a {default_qubits} qubit operator"
+            ))
+            .into())
+        }
+        Some(num_qubits) => num_qubits,
+        None => default_qubits,
+    };
+    let mut seen = vec![false; num_qubits];
+    for &index in layout.iter() {
+        if index >= num_qubits {
+            return Err(AccelerateError::Qiskit(
+                "Provided layout contains indices outside the number of qubits.".to_string(),
+            )
+            .into());
+        }
+        if seen[index] {
+            return Err(AccelerateError::Qiskit(
+                "Provided layout contains duplicate indices.".to_string(),
+            )
+            .into());
+        }
+        seen[index] = true;
+    }
+    let parallel = threading::run_in_parallel(ParallelKernel::SparsePauliOp, z.len());
+    let (z, x) = py.allow_threads(|| {
+        let apply = || {
+            (
+                apply_layout_inner(z, &layout, num_qubits, parallel),
+                apply_layout_inner(x, &layout, num_qubits, parallel),
+            )
+        };
+        if parallel {
+            threading::install(apply)
+        } else {
+            apply()
+        }
+    });
+    Ok((
+        z.into_pyarray_bound(py).unbind(),
+        x.into_pyarray_bound(py).unbind(),
+    ))
+}
+
 #[pymodule]
 pub fn sparse_pauli_op(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(unordered_unique))?;
     m.add_wrapped(wrap_pyfunction!(decompose_dense))?;
     m.add_wrapped(wrap_pyfunction!(to_matrix_dense))?;
     m.add_wrapped(wrap_pyfunction!(to_matrix_sparse))?;
+    m.add_wrapped(wrap_pyfunction!(to_matrix_dense_into))?;
+    m.add_wrapped(wrap_pyfunction!(to_matrix_sparse_into))?;
+    m.add_wrapped(wrap_pyfunction!(apply_layout_to_paulis))?;
     m.add_class::<ZXPaulis>()?;
     Ok(())
 }
@@ -876,4 +1212,97 @@ mod tests {
         let serial = to_matrix_sparse_serial_64(&paulis);
         assert_eq!(parallel, serial);
     }
+
+    #[test]
+    fn dense_into_overwrites_buffer() {
+        let paulis = example_paulis();
+        let mut out = vec![Complex64::new(1.0, -1.0); 1 << (2 * paulis.num_qubits())];
+        write_matrix_dense(&paulis, &mut out, false);
+        assert_eq!(out, to_matrix_dense_inner(&paulis, false));
+        in_scoped_thread_pool(|| write_matrix_dense(&paulis, &mut out, true)).unwrap();
+        assert_eq!(out, to_matrix_dense_inner(&paulis, false));
+    }
+
+    #[test]
+    fn sparse_into_and_serial_equal_32() {
+        let paulis = example_paulis();
+        let (values, indices, indptr) = to_matrix_sparse_serial_32(&paulis);
+        let mut out_values = vec![Complex64::new(1.0, -1.0); values.len() + 3];
+        let mut out_indices = vec![-1; indices.len() + 3];
+        let mut out_indptr = vec![-1; indptr.len()];
+        let nnz =
+            to_matrix_sparse_into_32(&paulis, &mut out_values, &mut out_indices, &mut out_indptr);
+        assert_eq!(nnz, values.len());
+        assert_eq!(out_values[..nnz], values);
+        assert_eq!(out_indices[..nnz], indices);
+        assert_eq!(out_indptr, indptr);
+    }
+
+    #[test]
+    fn sparse_into_and_serial_equal_64() {
+        let paulis = example_paulis();
+        let (values, indices, indptr) = to_matrix_sparse_serial_64(&paulis);
+        let mut out_values = vec![Complex64::new(1.0, -1.0); values.len()];
+        let mut out_indices = vec![-1; indices.len()];
+        let mut out_indptr = vec![-1; indptr.len()];
+        let nnz =
+            to_matrix_sparse_into_64(&paulis, &mut out_values, &mut out_indices, &mut out_indptr);
+        assert_eq!(nnz, values.len());
+        assert_eq!(out_values, values);
+        assert_eq!(out_indices, indices);
+        assert_eq!(out_indptr, indptr);
+    }
+
+    #[test]
+    fn sparse_into_short_buffers() {
+        // Buffers that are too short are filled as far as they go, but the full count of nonzero
+        // entries is still returned so the caller can tell how much room is needed.
+        let paulis = example_paulis();
+        let (values, indices, indptr) = to_matrix_sparse_serial_64(&paulis);
+        let mut out_values = vec![Complex64::new(0.0, 0.0); 2];
+        let mut out_indices = vec![0; 3];
+        let mut out_indptr = vec![0; indptr.len()];
+        let nnz =
+            to_matrix_sparse_into_64(&paulis, &mut out_values, &mut out_indices, &mut out_indptr);
+        assert_eq!(nnz, values.len());
+        assert_eq!(out_values, values[..2]);
+        assert_eq!(out_indices[..2], indices[..2]);
+        assert_eq!(out_indices[2], 0);
+        assert_eq!(out_indptr, indptr);
+    }
+
+    #[test]
+    fn sparse_into_no_operators() {
+        let paulis = MatrixCompressedPaulis {
+            num_qubits: 2,
+            x_like: vec![],
+            z_like: vec![],
+            coeffs: vec![],
+        };
+        let mut out_indptr = vec![-1; 5];
+        let nnz = to_matrix_sparse_into_32(&paulis, &mut [], &mut [], &mut out_indptr);
+        assert_eq!(nnz, 0);
+        assert_eq!(out_indptr, [0; 5]);
+    }
+
+    #[test]
+    fn layout_moves_columns() {
+        let bits = ndarray::array![[true, false, true], [false, true, false]];
+        let out = apply_layout_inner(bits.view(), &[3, 0, 1], 5, false);
+        let expected = ndarray::array![
+            [false, true, false, true, false],
+            [true, false, false, false, false]
+        ];
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn layout_threaded_and_serial_equal() {
+        let bits = Array2::from_shape_fn((64, 7), |(i, j)| (i * 7 + j) % 3 == 0);
+        let layout = [6, 2, 9, 0, 4, 1, 7];
+        let parallel =
+            in_scoped_thread_pool(|| apply_layout_inner(bits.view(), &layout, 10, true)).unwrap();
+        let serial = apply_layout_inner(bits.view(), &layout, 10, false);
+        assert_eq!(parallel, serial);
+    }
 }
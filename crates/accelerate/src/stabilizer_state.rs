@@ -0,0 +1,695 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The simulation of Clifford circuits with measurements and resets on stabilizer states.
+//!
+//! A state is the tableau of a Clifford that prepares it from `|0…0⟩`, in the layout of
+//! [CliffordTableau]: its stabilizers are the images of the single-qubit `Z`s, and its
+//! destabilizers those of the `X`s.  Gates update the tableau as they do a Clifford, and the
+//! measurements follow Aaronson and Gottesman, *Improved simulation of stabilizer circuits*, as
+//! `StabilizerState._measure_and_update` does in Python, so a seeded measurement collapses the
+//! state the same way in both.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use ndarray::prelude::*;
+use rand::prelude::*;
+use rayon::prelude::*;
+use smallvec::SmallVec;
+
+use crate::clifford::{CliffordGate, CliffordTableau};
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+use crate::rng::{generator, SeedSequence};
+use crate::threading::{self, ParallelKernel};
+
+/// What an instruction of a Clifford circuit does to a stabilizer state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StabilizerOperation {
+    Gate(CliffordGate),
+    /// A measurement of one qubit into one clbit.
+    Measure,
+    Reset,
+    /// An instruction that leaves the state as it is, such as a barrier or a delay.
+    Skip,
+}
+
+/// An instruction of a Clifford circuit, on the indices of its qubits and clbits.
+#[derive(Clone, Debug)]
+pub struct StabilizerInstruction {
+    pub operation: StabilizerOperation,
+    pub qubits: SmallVec<[usize; 2]>,
+    pub clbits: SmallVec<[usize; 2]>,
+}
+
+/// The power of `i` in `P(x1, z1) · P(x2, z2) = i^g P(x1 ^ x2, z1 ^ z2)` for single-qubit Paulis.
+#[inline]
+fn phase_exponent(x1: bool, z1: bool, x2: bool, z2: bool) -> i64 {
+    let (x1, z1, x2, z2) = (x1 as i64, z1 as i64, x2 as i64, z2 as i64);
+    (x2 * z1 * (1 + 2 * z2 + 2 * x1) - x1 * z2 * (1 + 2 * z1 + 2 * x2)).rem_euclid(4)
+}
+
+/// Multiply the row `row` of a tableau on `n` qubits onto the row `accum`, which must commute
+/// with it.  This is the `rowsum` of Aaronson and Gottesman.
+fn rowsum(n: usize, mut accum: ArrayViewMut1<bool>, row: ArrayView1<bool>) {
+    let mut exponent = 2 * (row[2 * n] as i64 + accum[2 * n] as i64);
+    for q in 0..n {
+        exponent += phase_exponent(row[q], row[n + q], accum[q], accum[n + q]);
+    }
+    debug_assert!(exponent % 2 == 0, "rowsum of anticommuting Paulis");
+    accum[2 * n] = exponent.rem_euclid(4) == 2;
+    for k in 0..2 * n {
+        accum[k] ^= row[k];
+    }
+}
+
+/// A stabilizer state, as the tableau of a Clifford that prepares it.
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.stabilizer_state")
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StabilizerState {
+    clifford: CliffordTableau,
+}
+
+impl StabilizerState {
+    /// The state `|0…0⟩` on `num_qubits` qubits.
+    pub fn zero(num_qubits: usize) -> Self {
+        StabilizerState {
+            clifford: CliffordTableau::identity(num_qubits),
+        }
+    }
+
+    /// The state that `clifford` prepares from `|0…0⟩`.
+    pub fn from_clifford(clifford: CliffordTableau) -> Self {
+        StabilizerState { clifford }
+    }
+
+    pub fn num_qubits(&self) -> usize {
+        self.clifford.num_qubits()
+    }
+
+    /// The Clifford that prepares the state.
+    pub fn clifford(&self) -> &CliffordTableau {
+        &self.clifford
+    }
+
+    /// The stabilizers of the state, as the rows `(x | z | sign)` of a boolean matrix of shape
+    /// `(n, 2n + 1)`.  They generate its stabilizer group.
+    pub fn stabilizers(&self) -> ArrayView2<bool> {
+        let n = self.num_qubits();
+        self.clifford.tableau().slice_move(s![n.., ..])
+    }
+
+    /// Apply a Clifford gate.
+    pub fn apply_gate(
+        &mut self,
+        gate: CliffordGate,
+        qubits: &[usize],
+    ) -> Result<(), AccelerateError> {
+        self.clifford.append_gate(gate, qubits)
+    }
+
+    fn check_qubit(&self, qubit: usize) -> Result<(), AccelerateError> {
+        if qubit >= self.num_qubits() {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {}-qubit state",
+                self.num_qubits()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The outcome of measuring `qubit` in the computational basis if it is certain, and
+    /// otherwise `None`.  The state is left as it is.
+    pub fn deterministic_outcome(&self, qubit: usize) -> Result<Option<bool>, AccelerateError> {
+        self.check_qubit(qubit)?;
+        let n = self.num_qubits();
+        let tableau = self.clifford.tableau();
+        if (n..2 * n).any(|row| tableau[[row, qubit]]) {
+            return Ok(None);
+        }
+        // `Z` on the qubit is the product of the stabilizers whose destabilizers anticommute with
+        // it, and the outcome is the sign of that product.
+        let mut product = Array1::from_elem(2 * n + 1, false);
+        for row in (0..n).filter(|row| tableau[[*row, qubit]]) {
+            rowsum(n, product.view_mut(), tableau.row(row + n));
+        }
+        Ok(Some(product[2 * n]))
+    }
+
+    /// Measure `qubit` in the computational basis, collapsing the state, and return the outcome.
+    /// A random outcome is `random_outcome`, which is only called if the outcome is random.
+    pub fn measure_with(
+        &mut self,
+        qubit: usize,
+        random_outcome: impl FnOnce() -> bool,
+    ) -> Result<bool, AccelerateError> {
+        if let Some(outcome) = self.deterministic_outcome(qubit)? {
+            return Ok(outcome);
+        }
+        let n = self.num_qubits();
+        let outcome = random_outcome();
+        let mut tableau = self.clifford.tableau_mut();
+        // The first stabilizer that anticommutes with `Z` on the qubit.
+        let pivot = (n..2 * n)
+            .find(|row| tableau[[*row, qubit]])
+            .expect("the outcome is random");
+        let pivot_row = tableau.row(pivot).to_owned();
+        for row in 0..2 * n {
+            if tableau[[row, qubit]] && row != pivot && row != pivot - n {
+                rowsum(n, tableau.row_mut(row), pivot_row.view());
+            }
+        }
+        tableau.row_mut(pivot - n).assign(&pivot_row);
+        let mut new_row = tableau.row_mut(pivot);
+        new_row.fill(false);
+        new_row[n + qubit] = true;
+        new_row[2 * n] = outcome;
+        Ok(outcome)
+    }
+
+    /// Measure `qubit` in the computational basis, drawing a random outcome from `rng`.
+    pub fn measure<R: Rng + ?Sized>(
+        &mut self,
+        qubit: usize,
+        rng: &mut R,
+    ) -> Result<bool, AccelerateError> {
+        self.measure_with(qubit, || rng.gen())
+    }
+
+    /// Reset `qubit` to `|0⟩`, by measuring it and flipping it back if the outcome is one.
+    pub fn reset<R: Rng + ?Sized>(
+        &mut self,
+        qubit: usize,
+        rng: &mut R,
+    ) -> Result<(), AccelerateError> {
+        if self.measure(qubit, rng)? {
+            self.apply_gate(CliffordGate::X, &[qubit])?;
+        }
+        Ok(())
+    }
+
+    /// Run `instructions` on the state, writing the outcomes of the measurements to `clbits`.
+    pub fn run<R: Rng + ?Sized>(
+        &mut self,
+        instructions: &[StabilizerInstruction],
+        clbits: &mut [bool],
+        rng: &mut R,
+    ) -> Result<(), AccelerateError> {
+        for inst in instructions {
+            match inst.operation {
+                StabilizerOperation::Gate(gate) => self.apply_gate(gate, &inst.qubits)?,
+                StabilizerOperation::Measure => {
+                    let (&[qubit], &[clbit]) = (inst.qubits.as_slice(), inst.clbits.as_slice())
+                    else {
+                        return Err(AccelerateError::Qiskit(
+                            "a measurement must be of one qubit into one clbit".to_string(),
+                        ));
+                    };
+                    let Some(slot) = clbits.get_mut(clbit) else {
+                        return Err(AccelerateError::Index(format!(
+                            "clbit {clbit} is out of range for {} clbits",
+                            clbits.len()
+                        )));
+                    };
+                    *slot = self.measure(qubit, rng)?;
+                }
+                StabilizerOperation::Reset => {
+                    for &qubit in inst.qubits.iter() {
+                        self.reset(qubit, rng)?;
+                    }
+                }
+                StabilizerOperation::Skip => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run `shots` shots of a Clifford circuit from `initial`, each with the generator seeded by the
+/// next seed of `seeds`, and return the clbits of each shot and the final state of the last.
+///
+/// The gates before the first measurement or reset are simulated once for all the shots.
+pub fn sample_circuit(
+    initial: &StabilizerState,
+    num_clbits: usize,
+    instructions: &[StabilizerInstruction],
+    shots: usize,
+    seeds: &mut SeedSequence,
+) -> Result<(Vec<Vec<bool>>, StabilizerState), AccelerateError> {
+    let split = instructions
+        .iter()
+        .position(|inst| {
+            matches!(
+                inst.operation,
+                StabilizerOperation::Measure | StabilizerOperation::Reset
+            )
+        })
+        .unwrap_or(instructions.len());
+    let (prefix, rest) = instructions.split_at(split);
+    let mut initial = initial.clone();
+    // The prefix has no measurements, so it neither writes clbits nor draws random numbers.
+    initial.run(prefix, &mut [], &mut generator(0))?;
+    let run_shot = |seed: u64| -> Result<(Vec<bool>, StabilizerState), AccelerateError> {
+        let mut state = initial.clone();
+        let mut clbits = vec![false; num_clbits];
+        state.run(rest, &mut clbits, &mut generator(seed as u128))?;
+        Ok((clbits, state))
+    };
+    let seed_vec = seeds.seeds(shots);
+    let parallel = threading::run_in_parallel(ParallelKernel::StabilizerState, shots);
+    let memory: Vec<Vec<bool>> = if parallel {
+        threading::install(|| {
+            seed_vec
+                .par_iter()
+                .map(|seed| run_shot(*seed).map(|(clbits, _)| clbits))
+                .collect::<Result<_, AccelerateError>>()
+        })?
+    } else {
+        seed_vec
+            .iter()
+            .map(|seed| run_shot(*seed).map(|(clbits, _)| clbits))
+            .collect::<Result<_, AccelerateError>>()?
+    };
+    // Rather than keeping the state of every shot, the last is run again, which its seed makes
+    // repeat exactly.
+    let state = match seed_vec.last() {
+        Some(seed) => run_shot(*seed)?.1,
+        None => initial,
+    };
+    Ok((memory, state))
+}
+
+/// What a Python-space operation does to a stabilizer state.
+#[cfg(feature = "python")]
+pub(crate) fn stabilizer_operation(op: &Bound<PyAny>) -> PyResult<StabilizerOperation> {
+    let py = op.py();
+    let name = op.getattr(intern!(py, "name"))?.extract::<String>()?;
+    if op.hasattr(intern!(py, "condition"))? && !op.getattr(intern!(py, "condition"))?.is_none() {
+        return Err(AccelerateError::Qiskit(format!(
+            "Cannot simulate the classically conditioned instruction {name}"
+        ))
+        .into());
+    }
+    Ok(match name.as_str() {
+        "measure" => StabilizerOperation::Measure,
+        "reset" => StabilizerOperation::Reset,
+        "barrier" | "delay" => StabilizerOperation::Skip,
+        _ => StabilizerOperation::Gate(name.parse()?),
+    })
+}
+
+/// The instructions of a Clifford circuit.
+#[cfg(feature = "python")]
+fn stabilizer_instructions(
+    py: Python,
+    circuit: &CircuitData,
+) -> PyResult<Vec<StabilizerInstruction>> {
+    circuit
+        .iter_instructions()
+        .map(|(op, qubits, clbits)| {
+            Ok(StabilizerInstruction {
+                operation: stabilizer_operation(op.bind(py))?,
+                qubits: qubits.iter().map(|q| *q as usize).collect(),
+                clbits: clbits.iter().map(|c| *c as usize).collect(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl StabilizerState {
+    /// Create the state that a Clifford tableau prepares from ``|0…0⟩``.
+    ///
+    /// Args:
+    ///     tableau (numpy.ndarray): the boolean tableau of shape ``(2n, 2n + 1)``, in the layout
+    ///         of ``Clifford.tableau``.
+    #[new]
+    fn py_new(tableau: PyReadonlyArray2<bool>) -> PyResult<Self> {
+        Ok(Self::from_clifford(CliffordTableau::from_tableau(
+            tableau.as_array().to_owned(),
+        )?))
+    }
+
+    /// Create the state ``|0…0⟩`` on ``num_qubits`` qubits.
+    #[staticmethod]
+    #[pyo3(name = "zero")]
+    fn py_zero(num_qubits: usize) -> Self {
+        Self::zero(num_qubits)
+    }
+
+    #[getter]
+    #[pyo3(name = "num_qubits")]
+    fn py_num_qubits(&self) -> usize {
+        self.num_qubits()
+    }
+
+    /// A copy of the tableau of the Clifford that prepares the state, as a boolean array of shape
+    /// ``(2n, 2n + 1)``.
+    #[getter]
+    #[pyo3(name = "tableau")]
+    fn py_tableau(&self, py: Python) -> Py<PyArray2<bool>> {
+        self.clifford
+            .tableau()
+            .to_owned()
+            .into_pyarray_bound(py)
+            .unbind()
+    }
+
+    /// The generators of the stabilizer group of the state, as a boolean array of shape
+    /// ``(n, 2n + 1)`` whose rows are the Paulis ``(x | z)`` with their signs in the final column.
+    #[getter]
+    #[pyo3(name = "stabilizers")]
+    fn py_stabilizers(&self, py: Python) -> Py<PyArray2<bool>> {
+        self.stabilizers()
+            .to_owned()
+            .into_pyarray_bound(py)
+            .unbind()
+    }
+
+    /// Apply a Clifford gate in place.
+    ///
+    /// Args:
+    ///     name (str): the name of the gate, as in ``Instruction.name``.
+    ///     qubits (list[int]): the qubits the gate acts on.
+    ///
+    /// Raises:
+    ///     QiskitError: if the gate is not a supported Clifford gate or the qubits are invalid.
+    #[pyo3(name = "apply_gate", text_signature = "(self, name, qubits, /)")]
+    fn py_apply_gate(&mut self, name: &str, qubits: Vec<usize>) -> PyResult<()> {
+        Ok(self.apply_gate(name.parse()?, &qubits)?)
+    }
+
+    /// Measure qubits in the computational basis in place.
+    ///
+    /// Args:
+    ///     qubits (list[int]): the qubits to measure, in order.
+    ///     seed (int | SeedSequence | None): the seed of the random outcomes.
+    ///
+    /// Returns:
+    ///     list[bool]: the outcome of each measurement.
+    #[pyo3(name = "measure", signature = (qubits, seed=None))]
+    fn py_measure(&mut self, qubits: Vec<usize>, seed: Option<PySeed>) -> PyResult<Vec<bool>> {
+        let mut rng = PySeed::generator(seed);
+        Ok(qubits
+            .into_iter()
+            .map(|qubit| self.measure(qubit, &mut rng))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Reset qubits to ``|0⟩`` in place.
+    ///
+    /// Args:
+    ///     qubits (list[int]): the qubits to reset.
+    ///     seed (int | SeedSequence | None): the seed of the random outcomes of the measurements
+    ///         that the resets are made of.
+    #[pyo3(name = "reset", signature = (qubits, seed=None))]
+    fn py_reset(&mut self, qubits: Vec<usize>, seed: Option<PySeed>) -> PyResult<()> {
+        let mut rng = PySeed::generator(seed);
+        for qubit in qubits {
+            self.reset(qubit, &mut rng)?;
+        }
+        Ok(())
+    }
+
+    /// The outcome of measuring a qubit if it is certain, without changing the state.
+    ///
+    /// Args:
+    ///     qubit (int): the qubit.
+    ///
+    /// Returns:
+    ///     bool | None: the outcome, or ``None`` if it is random.
+    #[pyo3(name = "deterministic_outcome")]
+    fn py_deterministic_outcome(&self, qubit: usize) -> PyResult<Option<bool>> {
+        Ok(self.deterministic_outcome(qubit)?)
+    }
+
+    /// Sample measurements of qubits in the computational basis, leaving the state as it is.
+    ///
+    /// Args:
+    ///     qubits (list[int]): the qubits to measure, in order.
+    ///     shots (int): the number of shots.
+    ///     seed (int | SeedSequence | None): the seed of the random outcomes.
+    ///
+    /// Returns:
+    ///     numpy.ndarray: the outcomes of each shot, as a boolean array of shape
+    ///     ``(shots, len(qubits))``.
+    #[pyo3(signature = (qubits, shots, seed=None))]
+    fn sample_measurements(
+        &self,
+        py: Python,
+        qubits: Vec<usize>,
+        shots: usize,
+        seed: Option<PySeed>,
+    ) -> PyResult<Py<PyArray2<bool>>> {
+        let instructions = qubits
+            .iter()
+            .enumerate()
+            .map(|(clbit, qubit)| StabilizerInstruction {
+                operation: StabilizerOperation::Measure,
+                qubits: [*qubit].into_iter().collect(),
+                clbits: [clbit].into_iter().collect(),
+            })
+            .collect::<Vec<_>>();
+        let (memory, _) = PySeed::with_sequence(seed, |seeds| {
+            py.allow_threads(|| sample_circuit(self, qubits.len(), &instructions, shots, seeds))
+        })?;
+        memory_array(py, memory, shots, qubits.len())
+    }
+
+    fn __eq__(&self, other: &StabilizerState) -> bool {
+        self == other
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// The memory of `shots` shots of `num_clbits` clbits each, as a boolean array.
+#[cfg(feature = "python")]
+fn memory_array(
+    py: Python,
+    memory: Vec<Vec<bool>>,
+    shots: usize,
+    num_clbits: usize,
+) -> PyResult<Py<PyArray2<bool>>> {
+    let memory = Array2::from_shape_vec((shots, num_clbits), memory.concat())
+        .expect("each shot has every clbit");
+    Ok(memory.into_pyarray_bound(py).unbind())
+}
+
+/// Simulate shots of a Clifford circuit with measurements and resets.
+///
+/// Args:
+///     circuit (CircuitData): the circuit, made of Clifford gates, measurements, resets, barriers
+///         and delays.
+///     shots (int): the number of shots.
+///     seed (int | SeedSequence | None): the seed of the random outcomes.
+///     initial_state (StabilizerState | None): the state to start each shot from, with ``|0…0⟩``
+///         if ``None``.
+///
+/// Returns:
+///     (numpy.ndarray, StabilizerState): the clbits of each shot, as a boolean array of shape
+///     ``(shots, num_clbits)``, and the final state of the last shot, or the state before any
+///     measurement if there are no shots.
+///
+/// Raises:
+///     QiskitError: if an instruction is not a Clifford gate, a measurement, a reset, a barrier
+///         or a delay, or is classically conditioned.
+///     ValueError: if the initial state is not on the qubits of the circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, shots, seed=None, initial_state=None))]
+pub fn sample_circuit_memory(
+    py: Python,
+    circuit: &CircuitData,
+    shots: usize,
+    seed: Option<PySeed>,
+    initial_state: Option<StabilizerState>,
+) -> PyResult<(Py<PyArray2<bool>>, StabilizerState)> {
+    let instructions = stabilizer_instructions(py, circuit)?;
+    let num_clbits = circuit.num_clbits();
+    let initial = initial_state.unwrap_or_else(|| StabilizerState::zero(circuit.num_qubits()));
+    if initial.num_qubits() != circuit.num_qubits() {
+        return Err(AccelerateError::Value(format!(
+            "the initial state has {} qubits but the circuit has {}",
+            initial.num_qubits(),
+            circuit.num_qubits()
+        ))
+        .into());
+    }
+    let (memory, state) = PySeed::with_sequence(seed, |seeds| {
+        py.allow_threads(|| sample_circuit(&initial, num_clbits, &instructions, shots, seeds))
+    })?;
+    Ok((memory_array(py, memory, shots, num_clbits)?, state))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn stabilizer_state(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(sample_circuit_memory))?;
+    m.add_class::<StabilizerState>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(
+        operation: StabilizerOperation,
+        qubits: &[usize],
+        clbits: &[usize],
+    ) -> StabilizerInstruction {
+        StabilizerInstruction {
+            operation,
+            qubits: SmallVec::from_slice(qubits),
+            clbits: SmallVec::from_slice(clbits),
+        }
+    }
+
+    fn bell() -> StabilizerState {
+        let mut state = StabilizerState::zero(2);
+        state.apply_gate(CliffordGate::H, &[0]).unwrap();
+        state.apply_gate(CliffordGate::CX, &[0, 1]).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_deterministic_outcomes() {
+        let mut state = StabilizerState::zero(3);
+        state.apply_gate(CliffordGate::X, &[1]).unwrap();
+        state.apply_gate(CliffordGate::H, &[2]).unwrap();
+        assert_eq!(state.deterministic_outcome(0).unwrap(), Some(false));
+        assert_eq!(state.deterministic_outcome(1).unwrap(), Some(true));
+        assert_eq!(state.deterministic_outcome(2).unwrap(), None);
+        assert!(matches!(
+            state.deterministic_outcome(3),
+            Err(AccelerateError::Index(_))
+        ));
+    }
+
+    #[test]
+    fn test_measure_collapses_bell_state() {
+        for outcome in [false, true] {
+            let mut state = bell();
+            assert_eq!(state.deterministic_outcome(1).unwrap(), None);
+            assert_eq!(state.measure_with(0, || outcome).unwrap(), outcome);
+            assert_eq!(state.deterministic_outcome(0).unwrap(), Some(outcome));
+            assert_eq!(state.deterministic_outcome(1).unwrap(), Some(outcome));
+            // A certain outcome doesn't draw a random one.
+            assert_eq!(
+                state
+                    .measure_with(1, || panic!("the outcome is certain"))
+                    .unwrap(),
+                outcome
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rng = generator(3);
+        for _ in 0..10 {
+            let mut state = bell();
+            state.reset(1, &mut rng).unwrap();
+            assert_eq!(state.deterministic_outcome(1).unwrap(), Some(false));
+        }
+    }
+
+    #[test]
+    fn test_run_measurement_errors() {
+        let mut state = StabilizerState::zero(2);
+        let mut rng = generator(0);
+        let out_of_range = [inst(StabilizerOperation::Measure, &[0], &[2])];
+        assert!(matches!(
+            state.run(&out_of_range, &mut [false; 2], &mut rng),
+            Err(AccelerateError::Index(_))
+        ));
+        let two_qubits = [inst(StabilizerOperation::Measure, &[0, 1], &[0, 1])];
+        assert!(matches!(
+            state.run(&two_qubits, &mut [false; 2], &mut rng),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_sample_circuit() {
+        let instructions = [
+            inst(StabilizerOperation::Gate(CliffordGate::H), &[0], &[]),
+            inst(StabilizerOperation::Gate(CliffordGate::CX), &[0, 1], &[]),
+            inst(StabilizerOperation::Skip, &[0, 1], &[]),
+            inst(StabilizerOperation::Measure, &[0], &[1]),
+            inst(StabilizerOperation::Measure, &[1], &[0]),
+            inst(StabilizerOperation::Gate(CliffordGate::X), &[1], &[]),
+        ];
+        let initial = StabilizerState::zero(2);
+        let (memory, state) = sample_circuit(
+            &initial,
+            2,
+            &instructions,
+            200,
+            &mut SeedSequence::new(Some(7)),
+        )
+        .unwrap();
+        assert_eq!(memory.len(), 200);
+        assert!(memory.iter().all(|clbits| clbits[0] == clbits[1]));
+        let ones = memory.iter().filter(|clbits| clbits[0]).count();
+        assert!(ones > 50 && ones < 150, "{ones} of 200 shots measured one");
+        let last = memory.last().unwrap()[0];
+        assert_eq!(state.deterministic_outcome(0).unwrap(), Some(last));
+        assert_eq!(state.deterministic_outcome(1).unwrap(), Some(!last));
+        // The shots are reproducible from the seed, and the initial state is left as it is.
+        let (again, _) = sample_circuit(
+            &initial,
+            2,
+            &instructions,
+            200,
+            &mut SeedSequence::new(Some(7)),
+        )
+        .unwrap();
+        assert_eq!(memory, again);
+        assert_eq!(initial, StabilizerState::zero(2));
+    }
+
+    #[test]
+    fn test_sample_circuit_no_shots() {
+        let instructions = [
+            inst(StabilizerOperation::Gate(CliffordGate::X), &[0], &[]),
+            inst(StabilizerOperation::Measure, &[0], &[0]),
+        ];
+        let (memory, state) = sample_circuit(
+            &StabilizerState::zero(1),
+            1,
+            &instructions,
+            0,
+            &mut SeedSequence::new(Some(1)),
+        )
+        .unwrap();
+        assert!(memory.is_empty());
+        assert_eq!(state.deterministic_outcome(0).unwrap(), Some(true));
+    }
+}
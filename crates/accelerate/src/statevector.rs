@@ -0,0 +1,867 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Statevector evolution kernels.  The core is [apply_matrix_inner], which applies a dense
+//! `k`-qubit matrix to a subset of the qubits of a state in place; the Python entry point
+//! simulates a whole `CircuitData` with it, so `Statevector.from_instruction` doesn't need to go
+//! through an `Operator` per instruction.
+//!
+//! The measurement probabilities of a state are also found here, marginalized onto some of its
+//! subsystems in the same pass that takes the squared norms of the amplitudes, and a session that
+//! pins a state for many such queries, and Pauli expectation values, from several threads.
+
+#[cfg(feature = "python")]
+use indexmap::IndexMap;
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use rayon::prelude::*;
+
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::pauli_exp_val::expval_paulis_inner;
+use crate::threading::{self, ParallelKernel};
+
+#[inline]
+fn run_in_parallel(dim: usize) -> bool {
+    threading::run_in_parallel(ParallelKernel::Statevector, dim)
+}
+
+/// Insert a zero into `index` at each of the bit positions `sorted_qubits` (in increasing order).
+/// Running `index` over `0..(len >> k)` enumerates the first amplitude of every group that a
+/// `k`-qubit gate mixes.
+#[inline]
+pub(crate) fn insert_zero_bits(index: usize, sorted_qubits: &[usize]) -> usize {
+    sorted_qubits.iter().fold(index, |acc, &q| {
+        let low = acc & ((1 << q) - 1);
+        ((acc ^ low) << 1) | low
+    })
+}
+
+/// The offsets from the first amplitude of a group to each of the amplitudes that a gate on
+/// `qubits` mixes, indexed by the little-endian row of the gate's matrix.
+pub(crate) fn gate_offsets(qubits: &[usize]) -> Vec<usize> {
+    (0..1usize << qubits.len())
+        .map(|row| {
+            qubits
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| row & (1 << bit) != 0)
+                .fold(0, |acc, (_, &q)| acc | (1 << q))
+        })
+        .collect()
+}
+
+/// Apply a single-qubit matrix to `qubit` of `state`.
+fn apply_1q(state: &mut [Complex64], mat: ArrayView2<Complex64>, qubit: usize) {
+    let (m00, m01, m10, m11) = (mat[[0, 0]], mat[[0, 1]], mat[[1, 0]], mat[[1, 1]]);
+    let stride = 1usize << qubit;
+    let kernel = |chunk: &mut [Complex64]| {
+        let (zeros, ones) = chunk.split_at_mut(stride);
+        for (a0, a1) in zeros.iter_mut().zip(ones.iter_mut()) {
+            let (v0, v1) = (*a0, *a1);
+            *a0 = m00 * v0 + m01 * v1;
+            *a1 = m10 * v0 + m11 * v1;
+        }
+    };
+    if run_in_parallel(state.len()) {
+        threading::install(|| state.par_chunks_mut(2 * stride).for_each(kernel));
+    } else {
+        state.chunks_mut(2 * stride).for_each(kernel);
+    }
+}
+
+/// Apply a `k`-qubit matrix by gathering the `2**k` amplitudes that mix with each other, multiplying
+/// and scattering them back.
+fn apply_kq_serial(
+    state: &mut [Complex64],
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+    offsets: &[usize],
+) {
+    let mut sorted = qubits.to_vec();
+    sorted.sort_unstable();
+    let mut scratch = vec![Complex64::new(0., 0.); offsets.len()];
+    for block in 0..(state.len() >> qubits.len()) {
+        let base = insert_zero_bits(block, &sorted);
+        for (value, offset) in scratch.iter_mut().zip(offsets) {
+            *value = state[base + offset];
+        }
+        for (row, offset) in offsets.iter().enumerate() {
+            state[base + offset] = mat
+                .row(row)
+                .iter()
+                .zip(scratch.iter())
+                .map(|(m, v)| m * v)
+                .sum();
+        }
+    }
+}
+
+/// Apply a `k`-qubit matrix out of place, with each output amplitude computed independently.
+fn apply_kq_parallel(
+    state: &mut [Complex64],
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+    offsets: &[usize],
+) {
+    let mask = offsets[offsets.len() - 1];
+    let input = state.to_vec();
+    state.par_iter_mut().enumerate().for_each(|(index, out)| {
+        let row = qubits
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (bit, &q)| acc | (((index >> q) & 1) << bit));
+        let base = index & !mask;
+        *out = mat
+            .row(row)
+            .iter()
+            .zip(offsets)
+            .map(|(m, offset)| m * input[base + offset])
+            .sum();
+    });
+}
+
+/// Apply the matrix `mat` to the qubits `qubits` of the statevector `state`, in place.
+///
+/// The matrix is in Qiskit's little-endian convention with respect to `qubits`: `qubits[0]` is the
+/// least significant bit of the row and column indices of `mat`.  A matrix acting on no qubits
+/// (a global phase) is allowed.
+pub fn apply_matrix_inner(
+    state: &mut [Complex64],
+    mat: ArrayView2<Complex64>,
+    qubits: &[usize],
+) -> Result<(), AccelerateError> {
+    if !state.len().is_power_of_two() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a statevector of length {} is not a qubit state",
+            state.len()
+        )));
+    }
+    let num_qubits = state.len().trailing_zeros() as usize;
+    let dim = 1usize << qubits.len();
+    if mat.shape() != [dim, dim] {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} cannot act on {} qubits",
+            mat.shape(),
+            qubits.len()
+        )));
+    }
+    for (i, &qubit) in qubits.iter().enumerate() {
+        if qubit >= num_qubits {
+            return Err(AccelerateError::Index(format!(
+                "qubit {qubit} is out of range for a {num_qubits}-qubit state"
+            )));
+        }
+        if qubits[..i].contains(&qubit) {
+            return Err(AccelerateError::Qiskit(format!(
+                "duplicate qubit {qubit} in the arguments"
+            )));
+        }
+    }
+    match qubits.len() {
+        0 => {
+            let phase = mat[[0, 0]];
+            if run_in_parallel(state.len()) {
+                threading::install(|| state.par_iter_mut().for_each(|x| *x *= phase));
+            } else {
+                state.iter_mut().for_each(|x| *x *= phase);
+            }
+        }
+        1 => apply_1q(state, mat, qubits[0]),
+        _ => {
+            let offsets = gate_offsets(qubits);
+            if run_in_parallel(state.len()) {
+                threading::install(|| apply_kq_parallel(state, mat, qubits, &offsets));
+            } else {
+                apply_kq_serial(state, mat, qubits, &offsets);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The marginalization of the subsystems of a state onto some of them.
+struct Marginal {
+    /// The stride in the state, the dimension and the stride in the marginal of each kept
+    /// subsystem.
+    kept: Vec<(usize, usize, usize)>,
+    /// The length of the marginal.
+    len: usize,
+}
+
+impl Marginal {
+    /// The marginal of a state of subsystems of dimensions `dims`, with subsystem 0 the least
+    /// significant, onto the subsystems `qargs`, with `qargs[0]` the least significant.
+    fn new(dims: &[usize], qargs: &[usize]) -> Result<Self, AccelerateError> {
+        let mut strides = Vec::with_capacity(dims.len());
+        let mut stride = 1usize;
+        for dim in dims {
+            strides.push(stride);
+            stride = stride.checked_mul(*dim).ok_or_else(|| {
+                AccelerateError::Overflow("the state has too many amplitudes".to_string())
+            })?;
+        }
+        let mut kept = Vec::with_capacity(qargs.len());
+        let mut len = 1;
+        for (i, &qarg) in qargs.iter().enumerate() {
+            if qarg >= dims.len() {
+                return Err(AccelerateError::Index(format!(
+                    "subsystem {qarg} is out of range for a state of {} subsystems",
+                    dims.len()
+                )));
+            }
+            if qargs[..i].contains(&qarg) {
+                return Err(AccelerateError::Qiskit(format!(
+                    "duplicate subsystem {qarg} in the arguments"
+                )));
+            }
+            kept.push((strides[qarg], dims[qarg], len));
+            len *= dims[qarg];
+        }
+        Ok(Marginal { kept, len })
+    }
+
+    /// The index in the marginal of the amplitude `index` of the state.
+    #[inline]
+    fn index(&self, index: usize) -> usize {
+        self.kept
+            .iter()
+            .map(|(stride, dim, out_stride)| (index / stride) % dim * out_stride)
+            .sum()
+    }
+
+    /// The index in the state of the amplitude whose kept subsystems are at `index` of the marginal
+    /// and whose other subsystems are all zero.
+    #[inline]
+    fn state_index(&self, index: usize) -> usize {
+        self.kept
+            .iter()
+            .map(|(stride, dim, out_stride)| (index / out_stride) % dim * stride)
+            .sum()
+    }
+}
+
+/// Round `value` to `decimals` decimal places, with ties to even, as NumPy does.
+fn round_decimals(value: f64, decimals: i32) -> f64 {
+    let scale = 10f64.powi(decimals);
+    let scaled = value * scale;
+    let rounded = scaled.round();
+    let rounded = if (scaled - scaled.trunc()).abs() == 0.5 {
+        2. * (scaled / 2.).round()
+    } else {
+        rounded
+    };
+    rounded / scale
+}
+
+/// The measurement probabilities of the subsystems `qargs` of `state`, whose subsystems have the
+/// dimensions `dims` with subsystem 0 the least significant, or of all of them in order if there's
+/// no `qargs`.  The probabilities are clipped to 1, and rounded to `decimals` if given.
+pub fn probabilities(
+    state: &[Complex64],
+    dims: &[usize],
+    qargs: Option<&[usize]>,
+    decimals: Option<i32>,
+) -> Result<Vec<f64>, AccelerateError> {
+    let all: Vec<usize>;
+    let qargs = match qargs {
+        Some(qargs) => qargs,
+        None => {
+            all = (0..dims.len()).collect();
+            &all
+        }
+    };
+    let marginal = Marginal::new(dims, qargs)?;
+    let num_amplitudes: usize = dims.iter().product();
+    if state.len() != num_amplitudes {
+        return Err(AccelerateError::Qiskit(format!(
+            "a state of {} amplitudes doesn't have subsystems of dimensions {:?}",
+            state.len(),
+            dims
+        )));
+    }
+    let parallel = run_in_parallel(state.len());
+    let mut out = if marginal.len == state.len() {
+        // Keeping every subsystem only permutes the probabilities, so each is found directly.
+        let probability = |index: usize| state[marginal.state_index(index)].norm_sqr();
+        if parallel {
+            threading::install(|| (0..marginal.len).into_par_iter().map(probability).collect())
+        } else {
+            (0..marginal.len).map(probability).collect()
+        }
+    } else if parallel {
+        threading::install(|| {
+            state
+                .par_iter()
+                .enumerate()
+                .fold(
+                    || vec![0.; marginal.len],
+                    |mut acc, (index, amplitude)| {
+                        acc[marginal.index(index)] += amplitude.norm_sqr();
+                        acc
+                    },
+                )
+                .reduce(
+                    || vec![0.; marginal.len],
+                    |mut acc, other| {
+                        acc.iter_mut().zip(other).for_each(|(a, b)| *a += b);
+                        acc
+                    },
+                )
+        })
+    } else {
+        let mut acc = vec![0.; marginal.len];
+        for (index, amplitude) in state.iter().enumerate() {
+            acc[marginal.index(index)] += amplitude.norm_sqr();
+        }
+        acc
+    };
+    for prob in out.iter_mut() {
+        *prob = prob.min(1.);
+        if let Some(decimals) = decimals {
+            *prob = round_decimals(*prob, decimals);
+        }
+    }
+    Ok(out)
+}
+
+/// The ket label of `index` over subsystems of dimensions `dims`, with the last subsystem first,
+/// and with commas between the subsystems if any has more than 10 levels.
+pub fn ket_label(mut index: usize, dims: &[usize]) -> String {
+    let digits: Vec<usize> = dims
+        .iter()
+        .map(|dim| {
+            let digit = index % dim;
+            index /= dim;
+            digit
+        })
+        .collect();
+    let separator = if dims.iter().any(|dim| *dim > 10) {
+        ","
+    } else {
+        ""
+    };
+    digits
+        .iter()
+        .rev()
+        .map(|digit| digit.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// How a Python-space operation acts when simulated by its matrix.
+#[cfg(feature = "python")]
+pub(crate) enum OperationMatrix {
+    /// The operation has no effect on the state (a barrier or a delay).
+    Skip,
+    /// The operation's unitary matrix, little-endian in the qubits it's applied to.
+    Matrix(Array2<Complex64>),
+    /// The operation can't be represented by a matrix (for example a reset, or a gate with a
+    /// classical condition).
+    Unsupported,
+}
+
+/// Find the matrix of a Python-space operation.  Standard gates are built in Rust; any other
+/// operation is asked for its ``to_matrix``.
+#[cfg(feature = "python")]
+pub(crate) fn operation_matrix(op: &Bound<PyAny>) -> PyResult<OperationMatrix> {
+    let py = op.py();
+    if let Some((gate, params)) = StandardGate::from_py_operation(op)? {
+        // `from_py_operation` has already checked the number of parameters.
+        return Ok(OperationMatrix::Matrix(gate.matrix(&params).unwrap()));
+    }
+    let op_type = op.get_type();
+    let class_name = op_type.name()?;
+    if class_name == "Barrier" || class_name == "Delay" {
+        return Ok(OperationMatrix::Skip);
+    }
+    if !op.hasattr(intern!(py, "to_matrix"))? || !op.getattr(intern!(py, "condition"))?.is_none() {
+        return Ok(OperationMatrix::Unsupported);
+    }
+    let Ok(mat) = op.call_method0(intern!(py, "to_matrix")) else {
+        return Ok(OperationMatrix::Unsupported);
+    };
+    let Ok(mat) = mat.extract::<PyReadonlyArray2<Complex64>>() else {
+        return Ok(OperationMatrix::Unsupported);
+    };
+    Ok(OperationMatrix::Matrix(mat.as_array().to_owned()))
+}
+
+/// Simulate a circuit on the all-zeros state.
+///
+/// Standard gates are applied from their Rust-space matrices; any other gate is applied through its
+/// ``to_matrix`` method, and barriers and delays are skipped.
+///
+/// Args:
+///     circuit (CircuitData): the instructions to simulate.
+///     global_phase (float): the global phase of the circuit.
+///
+/// Returns:
+///     numpy.ndarray | None: the final statevector, or ``None`` if the circuit contains an
+///     instruction that cannot be simulated by its matrix (for example a reset, a measurement or
+///     a classically conditioned gate), in which case the caller should fall back to the general
+///     Python-space simulation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, global_phase=0.))]
+pub fn from_circuit_data<'py>(
+    py: Python<'py>,
+    circuit: &CircuitData,
+    global_phase: f64,
+) -> PyResult<Option<Bound<'py, PyArray1<Complex64>>>> {
+    let mut state = vec![Complex64::new(0., 0.); 1usize << circuit.num_qubits()];
+    state[0] = Complex64::new(0., global_phase).exp();
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        if !clbits.is_empty() {
+            return Ok(None);
+        }
+        let mat = match operation_matrix(op.bind(py))? {
+            OperationMatrix::Skip => continue,
+            OperationMatrix::Matrix(mat) => mat,
+            OperationMatrix::Unsupported => return Ok(None),
+        };
+        let qubits = qubits.iter().map(|q| *q as usize).collect::<Vec<_>>();
+        apply_matrix_inner(&mut state, mat.view(), &qubits)?;
+    }
+    Ok(Some(state.into_pyarray_bound(py)))
+}
+
+/// The amplitudes of `state` as a slice, copied if they aren't contiguous.
+#[cfg(feature = "python")]
+fn amplitudes<'a>(state: &'a PyReadonlyArray1<Complex64>) -> std::borrow::Cow<'a, [Complex64]> {
+    match state.as_slice() {
+        Ok(slice) => std::borrow::Cow::Borrowed(slice),
+        Err(_) => std::borrow::Cow::Owned(state.as_array().to_vec()),
+    }
+}
+
+/// The measurement probabilities of subsystems of a statevector.
+///
+/// Args:
+///     state (numpy.ndarray): the amplitudes of the statevector.
+///     dims (list[int]): the dimension of each subsystem, with subsystem 0 the least significant.
+///     qargs (list[int] | None): the subsystems to return the probabilities of, with the first the
+///         least significant, or ``None`` for all of them.
+///     decimals (int | None): the number of decimal places to round the probabilities to.
+///
+/// Returns:
+///     numpy.ndarray: the probabilities, marginalized over the other subsystems.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "probabilities", signature = (state, dims, qargs=None, decimals=None))]
+pub fn py_probabilities(
+    py: Python,
+    state: PyReadonlyArray1<Complex64>,
+    dims: Vec<usize>,
+    qargs: Option<Vec<usize>>,
+    decimals: Option<i32>,
+) -> PyResult<Py<PyArray1<f64>>> {
+    let state = amplitudes(&state);
+    let probs = py.allow_threads(|| probabilities(&state, &dims, qargs.as_deref(), decimals))?;
+    Ok(probs.into_pyarray_bound(py).unbind())
+}
+
+/// The nonzero measurement probabilities of subsystems of a statevector, keyed by their kets.
+///
+/// The arguments are those of :func:`probabilities`.
+///
+/// Returns:
+///     dict[str, float]: the nonzero probabilities in increasing order of their outcomes, keyed by
+///     the digits of the subsystems with the last first, separated by commas if any subsystem has
+///     more than 10 levels.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "probabilities_dict", signature = (state, dims, qargs=None, decimals=None))]
+pub fn py_probabilities_dict(
+    py: Python,
+    state: PyReadonlyArray1<Complex64>,
+    dims: Vec<usize>,
+    qargs: Option<Vec<usize>>,
+    decimals: Option<i32>,
+) -> PyResult<IndexMap<String, f64>> {
+    let state = amplitudes(&state);
+    Ok(py.allow_threads(|| -> Result<_, AccelerateError> {
+        let probs = probabilities(&state, &dims, qargs.as_deref(), decimals)?;
+        let out_dims: Vec<usize> = match &qargs {
+            Some(qargs) => qargs.iter().map(|qarg| dims[*qarg]).collect(),
+            None => dims.clone(),
+        };
+        Ok(probs
+            .into_iter()
+            .enumerate()
+            .filter(|(_, prob)| *prob != 0.)
+            .map(|(index, prob)| (ket_label(index, &out_dims), prob))
+            .collect())
+    })?)
+}
+
+/// A statevector pinned for many expectation value and probability queries.
+///
+/// The amplitudes are validated and copied out of the array once, when the session is created, and
+/// every query reads that copy without the GIL, so queries from several Python threads run
+/// concurrently.
+///
+/// Args:
+///     state (numpy.ndarray): the amplitudes of the statevector.
+///     dims (list[int] | None): the dimension of each subsystem, with subsystem 0 the least
+///         significant, or ``None`` for qubits.
+///     lock (bool): whether to make ``state`` read-only, so that it can't drift from the session.
+///
+/// Raises:
+///     ValueError: if the number of amplitudes doesn't match ``dims``, or isn't a power of two when
+///         there are no ``dims``.
+#[cfg(feature = "python")]
+#[pyclass(module = "qiskit._accelerate.statevector", frozen)]
+pub struct StatevectorSession {
+    state: Vec<Complex64>,
+    dims: Vec<usize>,
+    /// The number of qubits, if every subsystem is a qubit.
+    num_qubits: Option<usize>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl StatevectorSession {
+    #[new]
+    #[pyo3(signature = (state, dims=None, lock=false))]
+    fn new(
+        state: PyReadonlyArray1<Complex64>,
+        dims: Option<Vec<usize>>,
+        lock: bool,
+    ) -> PyResult<Self> {
+        let amplitudes = state.as_array().to_vec();
+        let dims = match dims {
+            Some(dims) => dims,
+            None if amplitudes.len().is_power_of_two() => {
+                vec![2; amplitudes.len().trailing_zeros() as usize]
+            }
+            None => {
+                return Err(AccelerateError::Value(format!(
+                    "a qubit statevector can't have {} amplitudes",
+                    amplitudes.len()
+                ))
+                .into())
+            }
+        };
+        if dims.iter().product::<usize>() != amplitudes.len() {
+            return Err(AccelerateError::Value(format!(
+                "a state of {} amplitudes doesn't have subsystems of dimensions {:?}",
+                amplitudes.len(),
+                dims
+            ))
+            .into());
+        }
+        if lock {
+            state
+                .getattr(intern!(state.py(), "flags"))?
+                .setattr(intern!(state.py(), "writeable"), false)?;
+        }
+        let num_qubits = dims.iter().all(|dim| *dim == 2).then_some(dims.len());
+        Ok(StatevectorSession {
+            state: amplitudes,
+            dims,
+            num_qubits,
+        })
+    }
+
+    /// The number of amplitudes of the state.
+    fn __len__(&self) -> usize {
+        self.state.len()
+    }
+
+    /// The dimension of each subsystem.
+    #[getter]
+    fn dims(&self) -> Vec<usize> {
+        self.dims.clone()
+    }
+
+    /// Return the expectation values of many weighted Paulis, as ``pauli_expval.expval_paulis``
+    /// does for the pinned state.
+    ///
+    /// Args:
+    ///     z_masks (list[int]): the mask of the qubits each Pauli has a Z or a Y on.
+    ///     x_masks (list[int]): the mask of the qubits each Pauli has an X or a Y on.
+    ///     phases (list[complex]): the phase ``(-1j) ** num_y`` of each Pauli.
+    ///     coeffs (list[complex]): the coefficient of each Pauli.
+    ///
+    /// Returns:
+    ///     list[complex]: the expectation value of each Pauli times its coefficient.
+    ///
+    /// Raises:
+    ///     ValueError: if the state isn't of qubits, the lists don't all have the same length, or
+    ///         a mask is out of range.
+    fn expval_paulis(
+        &self,
+        py: Python,
+        z_masks: Vec<usize>,
+        x_masks: Vec<usize>,
+        phases: Vec<Complex64>,
+        coeffs: Vec<Complex64>,
+    ) -> PyResult<Vec<Complex64>> {
+        let Some(num_qubits) = self.num_qubits else {
+            return Err(AccelerateError::Value(
+                "Pauli expectation values need a state of qubits".to_string(),
+            )
+            .into());
+        };
+        if coeffs.len() != z_masks.len() {
+            return Err(AccelerateError::Value(format!(
+                "There are {} Paulis but {} coefficients",
+                z_masks.len(),
+                coeffs.len()
+            ))
+            .into());
+        }
+        let values = py.allow_threads(|| {
+            expval_paulis_inner(&self.state, num_qubits, &z_masks, &x_masks, &phases)
+        })?;
+        Ok(values
+            .into_iter()
+            .zip(coeffs)
+            .map(|(value, coeff)| coeff * value)
+            .collect())
+    }
+
+    /// Return the measurement probabilities of subsystems of the pinned state, as
+    /// :func:`probabilities` does.
+    #[pyo3(signature = (qargs=None, decimals=None))]
+    fn probabilities(
+        &self,
+        py: Python,
+        qargs: Option<Vec<usize>>,
+        decimals: Option<i32>,
+    ) -> PyResult<Py<PyArray1<f64>>> {
+        let probs = py
+            .allow_threads(|| probabilities(&self.state, &self.dims, qargs.as_deref(), decimals))?;
+        Ok(probs.into_pyarray_bound(py).unbind())
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn statevector(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(from_circuit_data))?;
+    m.add_wrapped(wrap_pyfunction!(py_probabilities))?;
+    m.add_wrapped(wrap_pyfunction!(py_probabilities_dict))?;
+    m.add_class::<StatevectorSession>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    fn random_vector(len: usize, seed: u128) -> Vec<Complex64> {
+        let mut rng = generator(seed);
+        (0..len)
+            .map(|_| Complex64::new(rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5))
+            .collect()
+    }
+
+    fn random_matrix(num_qubits: usize, seed: u128) -> Array2<Complex64> {
+        let dim = 1 << num_qubits;
+        Array2::from_shape_vec((dim, dim), random_vector(dim * dim, seed)).unwrap()
+    }
+
+    /// `mat` applied to `qubits` of `state` through the matrix of the whole space.
+    fn reference(
+        state: &[Complex64],
+        mat: ArrayView2<Complex64>,
+        qubits: &[usize],
+    ) -> Vec<Complex64> {
+        let mask = qubits.iter().fold(0, |acc, q| acc | (1 << q));
+        let row = |index: usize| {
+            qubits
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (bit, q)| acc | (((index >> q) & 1) << bit))
+        };
+        (0..state.len())
+            .map(|out| {
+                (0..state.len())
+                    .filter(|index| index & !mask == out & !mask)
+                    .map(|index| mat[[row(out), row(index)]] * state[index])
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_matrix() {
+        let state = random_vector(16, 1);
+        for (seed, qubits) in [
+            vec![0],
+            vec![3],
+            vec![0, 1],
+            vec![3, 1],
+            vec![2, 0, 3],
+            vec![1, 3, 0, 2],
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mat = random_matrix(qubits.len(), seed as u128 + 2);
+            let expected = reference(&state, mat.view(), qubits);
+            let mut out = state.clone();
+            apply_matrix_inner(&mut out, mat.view(), qubits).unwrap();
+            assert_abs_diff_eq!(out[..], expected[..], epsilon = 1e-12);
+            if qubits.len() > 1 {
+                let offsets = gate_offsets(qubits);
+                let mut serial = state.clone();
+                apply_kq_serial(&mut serial, mat.view(), qubits, &offsets);
+                assert_abs_diff_eq!(serial[..], expected[..], epsilon = 1e-12);
+                let mut parallel = state.clone();
+                apply_kq_parallel(&mut parallel, mat.view(), qubits, &offsets);
+                assert_abs_diff_eq!(parallel[..], expected[..], epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_global_phase() {
+        let state = random_vector(8, 3);
+        let phase = Complex64::from_polar(1., 0.7);
+        let mut out = state.clone();
+        apply_matrix_inner(&mut out, array![[phase]].view(), &[]).unwrap();
+        let expected = state.iter().map(|x| x * phase).collect::<Vec<_>>();
+        assert_abs_diff_eq!(out[..], expected[..], epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_apply_matrix_errors() {
+        let mat = random_matrix(1, 0);
+        assert!(matches!(
+            apply_matrix_inner(&mut random_vector(6, 0), mat.view(), &[0]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let mut state = random_vector(8, 0);
+        assert!(matches!(
+            apply_matrix_inner(&mut state, mat.view(), &[0, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            apply_matrix_inner(&mut state, mat.view(), &[3]),
+            Err(AccelerateError::Index(_))
+        ));
+        let mat = random_matrix(2, 0);
+        assert!(matches!(
+            apply_matrix_inner(&mut state, mat.view(), &[1, 1]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_insert_zero_bits() {
+        assert_eq!(insert_zero_bits(0b111, &[1]), 0b1101);
+        assert_eq!(insert_zero_bits(0b111, &[0, 2]), 0b11010);
+        assert_eq!(gate_offsets(&[2, 0]), vec![0b000, 0b100, 0b001, 0b101]);
+    }
+
+    fn state_of(probs: &[f64]) -> Vec<Complex64> {
+        probs
+            .iter()
+            .map(|prob| Complex64::new(0., prob.sqrt()))
+            .collect()
+    }
+
+    fn assert_probs(left: &[f64], right: &[f64]) {
+        assert_eq!(left.len(), right.len());
+        for (l, r) in left.iter().zip(right) {
+            assert!((l - r).abs() < 1e-12, "{l} != {r}");
+        }
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let state = state_of(&[0.1, 0.2, 0.3, 0.4]);
+        let probs = |qargs: Option<&[usize]>| probabilities(&state, &[2, 2], qargs, None).unwrap();
+        assert_probs(&probs(None), &[0.1, 0.2, 0.3, 0.4]);
+        assert_probs(&probs(Some(&[0, 1])), &[0.1, 0.2, 0.3, 0.4]);
+        assert_probs(&probs(Some(&[1, 0])), &[0.1, 0.3, 0.2, 0.4]);
+        assert_probs(&probs(Some(&[0])), &[0.4, 0.6]);
+        assert_probs(&probs(Some(&[1])), &[0.3, 0.7]);
+        assert_probs(&probs(Some(&[])), &[1.]);
+    }
+
+    #[test]
+    fn test_probabilities_qudits() {
+        // A qutrit as subsystem 0 and a qubit as subsystem 1.
+        let state = state_of(&[0.05, 0.1, 0.15, 0.2, 0.25, 0.25]);
+        let probs = |qargs: &[usize]| probabilities(&state, &[3, 2], Some(qargs), None).unwrap();
+        assert_probs(&probs(&[0]), &[0.25, 0.35, 0.4]);
+        assert_probs(&probs(&[1]), &[0.3, 0.7]);
+        assert_probs(&probs(&[1, 0]), &[0.05, 0.2, 0.1, 0.25, 0.15, 0.25]);
+    }
+
+    #[test]
+    fn test_probabilities_clipped_and_rounded() {
+        let state = [Complex64::new(1. + 1e-9, 0.), Complex64::new(0., 0.)];
+        assert_eq!(
+            probabilities(&state, &[2], None, None).unwrap(),
+            vec![1., 0.]
+        );
+        // Ties are rounded to even, as NumPy does.
+        let state = [Complex64::new(0.5, 0.); 4];
+        assert_eq!(
+            probabilities(&state, &[2, 2], None, Some(1)).unwrap(),
+            vec![0.2; 4]
+        );
+        assert_eq!(round_decimals(0.125, 2), 0.12);
+        assert_eq!(round_decimals(0.375, 2), 0.38);
+        assert_eq!(round_decimals(0.5, 0), 0.);
+        assert_eq!(round_decimals(1.5, 0), 2.);
+        assert_eq!(round_decimals(-0.25, 1), -0.2);
+    }
+
+    #[test]
+    fn test_probabilities_errors() {
+        let state = state_of(&[0.25; 4]);
+        assert!(matches!(
+            probabilities(&state, &[2, 2], Some(&[2]), None),
+            Err(AccelerateError::Index(_))
+        ));
+        assert!(matches!(
+            probabilities(&state, &[2, 2], Some(&[0, 0]), None),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            probabilities(&state, &[2, 3], None, None),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_ket_label() {
+        assert_eq!(ket_label(0b110, &[2, 2, 2]), "110");
+        assert_eq!(ket_label(5, &[3, 2]), "12");
+        assert_eq!(ket_label(11, &[11, 2]), "1,0");
+        assert_eq!(ket_label(0, &[]), "");
+    }
+}
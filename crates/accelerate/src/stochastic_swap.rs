@@ -20,7 +20,7 @@ use std::sync::RwLock;
 use hashbrown::HashSet;
 
 use ndarray::prelude::*;
-use numpy::{PyReadonlyArray1, PyReadonlyArray2};
+use numpy::PyReadonlyArray1;
 use rayon::prelude::*;
 
 use pyo3::prelude::*;
@@ -30,9 +30,12 @@ use rand::prelude::*;
 use rand_distr::{Distribution, Normal};
 use rand_pcg::Pcg64Mcg;
 
+use crate::coupling_map::CouplingMap;
 use crate::edge_collections::EdgeCollection;
-use crate::getenv_use_multiple_threads;
+use crate::instrumentation;
 use crate::nlayout::{NLayout, PhysicalQubit, VirtualQubit};
+use crate::rng::PySeed;
+use crate::threading;
 
 #[inline]
 fn compute_cost(
@@ -220,23 +223,18 @@ fn swap_trial(
 ///         The pairs are flattened on the array so that each pair in the list of 2q gates
 ///         are adjacent in the array. For example, if the 2q interaction list was
 ///         ``[(0, 1), (2, 1), (3, 2)]``, the input here would be ``[0, 1, 2, 1, 3, 2]``.
-///     cdist (ndarray): The distance matrix for the coupling graph of the target
-///         backend
-///     cdist2 (ndarray): The distance matrix squared for the coupling graph of the
-///         target backend
-///     edges (ndarray): A flattened 1d array of the edge list of the coupling graph.
-///         The pairs are flattened on the array so that each node pair in the edge are
-///         adjacent in the array. For example, if the edge list were ``[(0, 1), (1, 2), (2, 3)]``
-///         the input array here would be ``[0, 1, 1, 2, 2, 3]``.
-///     seed (int): An optional seed for the rng used to generate the random perturbation
-///         matrix used in each trial
+///     coupling_map (CouplingMap): The coupling graph of the target backend, whose edges are the
+///         candidate swaps and whose distance matrix scores the trials.
+///     seed (int | SeedSequence): An optional seed of up to 128 bits for the rng used to
+///         generate the random perturbation matrix used in each trial, or a ``SeedSequence`` to
+///         draw the seed of each trial from.
 /// Returns:
 ///     tuple: If a valid layout permutation is found a tuple of the form:
 ///         ``(edges, layout, depth)`` is returned. If a solution is not found the output
 ///         will be ``(None, None, max int)``.
 #[pyfunction]
 #[pyo3(
-    text_signature = "(num_trials, num_qubits, int_layout, int_qubit_subset, int_gates, cdist, cdist2, edges, /, seed=None)"
+    text_signature = "(num_trials, num_qubits, int_layout, int_qubit_subset, int_gates, coupling_map, /, seed=None)"
 )]
 pub fn swap_trials(
     num_trials: u64,
@@ -244,53 +242,55 @@ pub fn swap_trials(
     int_layout: &NLayout,
     int_qubit_subset: PyReadonlyArray1<VirtualQubit>,
     int_gates: PyReadonlyArray1<VirtualQubit>,
-    cdist: PyReadonlyArray2<f64>,
-    cdist2: PyReadonlyArray2<f64>,
-    edges: PyReadonlyArray1<PhysicalQubit>,
-    seed: Option<u64>,
+    coupling_map: &CouplingMap,
+    seed: Option<PySeed>,
 ) -> PyResult<(Option<EdgeCollection>, Option<NLayout>, usize)> {
+    let _timer = instrumentation::Timer::start("stochastic_swap");
+    instrumentation::add_trials("stochastic_swap", num_trials as usize);
     let int_qubit_subset_arr = int_qubit_subset.as_slice()?;
     let int_gates_arr = int_gates.as_slice()?;
-    let cdist_arr = cdist.as_array();
-    let cdist2_arr = cdist2.as_array();
-    let edges_arr = edges.as_slice()?;
+    let cdist_arr = coupling_map.distance_matrix();
+    let cdist2 = cdist_arr.mapv(|dist| dist * dist);
+    let cdist2_arr = cdist2.view();
+    let edges = coupling_map
+        .edges()
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+    let edges_arr = edges.as_slice();
     let num_gates: usize = int_gates.len()? / 2;
     let mut best_possible: Option<(u64, f64, EdgeCollection, NLayout)> = None;
     let locked_best_possible: RwLock<&mut Option<(u64, f64, EdgeCollection, NLayout)>> =
         RwLock::new(&mut best_possible);
-    let outer_rng: Pcg64Mcg = match seed {
-        Some(seed) => Pcg64Mcg::seed_from_u64(seed),
-        None => Pcg64Mcg::from_entropy(),
-    };
-    let seed_vec: Vec<u64> = outer_rng
-        .sample_iter(&rand::distributions::Standard)
-        .take(num_trials as usize)
-        .collect();
+    let seed_vec = PySeed::with_sequence(seed, |seeds| seeds.seeds(num_trials as usize));
     // Run in parallel only if we're not already in a multiprocessing context
     // unless force threads is set.
-    let run_in_parallel = getenv_use_multiple_threads();
+    let run_in_parallel = threading::use_multiple_threads();
 
     let mut best_depth = std::usize::MAX;
     let mut best_edges: Option<EdgeCollection> = None;
     let mut best_layout: Option<NLayout> = None;
     if run_in_parallel {
-        let result: Vec<Option<(f64, EdgeCollection, NLayout, usize)>> = (0..num_trials)
-            .into_par_iter()
-            .map(|trial_num| {
-                swap_trial(
-                    num_qubits,
-                    int_layout,
-                    int_qubit_subset_arr,
-                    int_gates_arr,
-                    cdist_arr,
-                    cdist2_arr,
-                    edges_arr,
-                    seed_vec[trial_num as usize],
-                    trial_num,
-                    Some(&locked_best_possible),
-                )
-            })
-            .collect();
+        let result: Vec<Option<(f64, EdgeCollection, NLayout, usize)>> = threading::install(|| {
+            (0..num_trials)
+                .into_par_iter()
+                .map(|trial_num| {
+                    swap_trial(
+                        num_qubits,
+                        int_layout,
+                        int_qubit_subset_arr,
+                        int_gates_arr,
+                        cdist_arr,
+                        cdist2_arr,
+                        edges_arr,
+                        seed_vec[trial_num as usize],
+                        trial_num,
+                        Some(&locked_best_possible),
+                    )
+                })
+                .collect()
+        });
         match best_possible {
             Some((_trial_num, _dist, edges, layout)) => {
                 best_edges = Some(edges);
@@ -0,0 +1,506 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Routing of blocks of commuting two-qubit gates with swap strategies, as done by the
+//! `Commuting2qGateRouter` pass.
+//!
+//! A swap strategy is a sequence of layers of parallel swaps on a coupling map, and two qubits
+//! are at distance `d` in it if they first become adjacent after `d` layers.  The gates of a
+//! commuting block can be applied in any order, so each is applied as soon as its qubits are
+//! adjacent: the gates at distance zero, then the first swap layer, then the gates at distance
+//! one, and so on up to the largest distance of the block.  See
+//! https://arxiv.org/abs/2202.03459 for more details.
+
+use hashbrown::HashMap;
+use ndarray::Array2;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+
+use crate::error::AccelerateError;
+
+/// The layers of parallel swaps of a swap strategy on a coupling map.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.swap_strategy", frozen)
+)]
+pub struct SwapStrategy {
+    num_vertices: usize,
+    swap_layers: Vec<Vec<[usize; 2]>>,
+    /// The number of swap layers after which each pair of vertices is first adjacent, or `-1` if
+    /// it never is.
+    distance: Array2<i64>,
+}
+
+impl SwapStrategy {
+    pub fn new(
+        num_vertices: usize,
+        edges: &[[usize; 2]],
+        swap_layers: Vec<Vec<[usize; 2]>>,
+    ) -> Result<Self, AccelerateError> {
+        if let Some(edge) = edges
+            .iter()
+            .find(|edge| edge.iter().any(|v| *v >= num_vertices))
+        {
+            return Err(AccelerateError::Index(format!(
+                "the edge {edge:?} is out of range for {num_vertices} vertices"
+            )));
+        }
+        for (i, layer) in swap_layers.iter().enumerate() {
+            if let Some(edge) = layer.iter().find(|edge| !edges.contains(edge)) {
+                return Err(AccelerateError::Qiskit(format!(
+                    "The {i}th swap layer contains the edge ({}, {}) which is not part of the \
+                     underlying coupling map.",
+                    edge[0], edge[1]
+                )));
+            }
+            let mut seen = vec![false; num_vertices];
+            for vertex in layer.iter().flatten() {
+                if std::mem::replace(&mut seen[*vertex], true) {
+                    return Err(AccelerateError::Qiskit(format!(
+                        "The {i}th swap layer contains a qubit with multiple swaps."
+                    )));
+                }
+            }
+        }
+        let mut distance = Array2::from_elem((num_vertices, num_vertices), -1);
+        distance.diag_mut().fill(0);
+        // The inverse of the permutation composed of the swap layers applied so far.
+        let mut permutation: Vec<usize> = (0..num_vertices).collect();
+        for i in 0..=swap_layers.len() {
+            for [j, k] in edges {
+                let (j, k) = (permutation[*j], permutation[*k]);
+                if distance[[j, k]] == -1 {
+                    distance[[j, k]] = i as i64;
+                    distance[[k, j]] = i as i64;
+                }
+            }
+            if let Some(layer) = swap_layers.get(i) {
+                for [j, k] in layer {
+                    permutation.swap(*j, *k);
+                }
+            }
+        }
+        Ok(SwapStrategy {
+            num_vertices,
+            swap_layers,
+            distance,
+        })
+    }
+
+    /// The strategy of alternating layers of swaps on the even and the odd edges of a line, which
+    /// reaches full connectivity in `line.len() - 2` layers.
+    pub fn from_line(
+        line: &[usize],
+        num_swap_layers: Option<usize>,
+    ) -> Result<Self, AccelerateError> {
+        if line.len() < 2 {
+            return Err(AccelerateError::Value(format!(
+                "The line cannot have less than two elements, but is {line:?}"
+            )));
+        }
+        let num_swap_layers = num_swap_layers.unwrap_or(line.len() - 2);
+        let layer = |start: usize| -> Vec<[usize; 2]> {
+            (start..line.len() - 1)
+                .step_by(2)
+                .map(|i| [line[i], line[i + 1]])
+                .collect()
+        };
+        let base_layers = [layer(0), layer(1)];
+        let swap_layers = (0..num_swap_layers)
+            .map(|i| base_layers[i % 2].clone())
+            .collect();
+        let edges = line
+            .windows(2)
+            .flat_map(|pair| [[pair[0], pair[1]], [pair[1], pair[0]]])
+            .collect::<Vec<_>>();
+        let num_vertices = line.iter().max().unwrap() + 1;
+        Self::new(num_vertices, &edges, swap_layers)
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    pub fn swap_layers(&self) -> &[Vec<[usize; 2]>] {
+        &self.swap_layers
+    }
+
+    pub fn distance_matrix(&self) -> &Array2<i64> {
+        &self.distance
+    }
+
+    /// Whether the strategy makes the vertices `j` and `k` adjacent at some point.
+    pub fn can_couple(&self, j: usize, k: usize) -> bool {
+        j != k && j < self.num_vertices && k < self.num_vertices && self.distance[[j, k]] >= 0
+    }
+}
+
+/// A gate of a routed block of commuting gates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoutedGate {
+    /// The gate of the block at `index`, applied to the positions `qubits`.
+    Term { index: usize, qubits: [usize; 2] },
+    /// A swap of the strategy.
+    Swap([usize; 2]),
+}
+
+/// Split the gates of a swap layer into sub-layers of gates on disjoint positions, greedily in the
+/// order of the gates, or by the color of their positions in `edge_coloring`.
+fn sub_layers(
+    layer: Vec<(usize, [usize; 2])>,
+    num_positions: usize,
+    edge_coloring: Option<&HashMap<[usize; 2], usize>>,
+) -> Result<Vec<Vec<(usize, [usize; 2])>>, AccelerateError> {
+    if let Some(coloring) = edge_coloring {
+        let num_colors = coloring.values().max().map_or(0, |color| color + 1);
+        let mut out = vec![Vec::new(); num_colors];
+        for (index, edge) in layer {
+            let color = coloring.get(&edge).ok_or_else(|| {
+                AccelerateError::Key(format!(
+                    "the edge ({}, {}) has no color in the edge coloring",
+                    edge[0], edge[1]
+                ))
+            })?;
+            out[*color].push((index, edge));
+        }
+        return Ok(out);
+    }
+    let mut out = Vec::new();
+    let mut remaining = layer;
+    while !remaining.is_empty() {
+        let mut blocked = vec![false; num_positions];
+        let (current, rest): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|(_, edge)| {
+            if edge.iter().any(|position| blocked[*position]) {
+                false
+            } else {
+                edge.iter().for_each(|position| blocked[*position] = true);
+                true
+            }
+        });
+        out.push(current);
+        remaining = rest;
+    }
+    Ok(out)
+}
+
+/// Route a block of commuting two-qubit gates, each given by the pair of virtual qubits it acts
+/// on, with a swap strategy.
+///
+/// `layout` is the position of each virtual qubit before the block, and is updated with the swaps
+/// of the routing, as the `current_layout` of `Commuting2qGateRouter` is.  The gates at each
+/// distance are split into sub-layers of gates on disjoint positions, by `edge_coloring` if it is
+/// given and greedily otherwise.
+pub fn route_commuting_gates(
+    strategy: &SwapStrategy,
+    gates: &[[usize; 2]],
+    layout: &mut [usize],
+    edge_coloring: Option<&HashMap<[usize; 2], usize>>,
+) -> Result<Vec<RoutedGate>, AccelerateError> {
+    let num_positions = layout.len();
+    if let Some(vertex) = strategy
+        .swap_layers
+        .iter()
+        .flatten()
+        .flatten()
+        .find(|vertex| **vertex >= num_positions)
+    {
+        return Err(AccelerateError::Index(format!(
+            "the swap strategy acts on vertex {vertex}, but there are only {num_positions} qubits"
+        )));
+    }
+    let mut inverse = vec![0; num_positions];
+    for (virt, phys) in layout.iter().enumerate() {
+        if *phys >= num_positions {
+            return Err(AccelerateError::Index(format!(
+                "position {phys} is out of range for {num_positions} qubits"
+            )));
+        }
+        inverse[*phys] = virt;
+    }
+
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    for (index, [j, k]) in gates.iter().enumerate() {
+        let distance = if *j < num_positions
+            && *k < num_positions
+            && strategy.can_couple(*j, *k)
+            && strategy.can_couple(layout[*j], layout[*k])
+        {
+            strategy.distance[[layout[*j], layout[*k]]] as usize
+        } else {
+            return Err(AccelerateError::Transpiler(format!(
+                "The swap strategy cannot implement the edge ({j}, {k})."
+            )));
+        };
+        if layers.len() <= distance {
+            layers.resize(distance + 1, Vec::new());
+        }
+        layers[distance].push(index);
+    }
+
+    let mut out = Vec::with_capacity(gates.len());
+    let max_distance = layers.len();
+    for (i, layer) in layers.into_iter().enumerate() {
+        let layer = layer
+            .into_iter()
+            .map(|index| {
+                let [j, k] = gates[index];
+                (index, [inverse[j], inverse[k]])
+            })
+            .collect();
+        for sub_layer in sub_layers(layer, num_positions, edge_coloring)? {
+            out.extend(
+                sub_layer
+                    .into_iter()
+                    .map(|(index, qubits)| RoutedGate::Term { index, qubits }),
+            );
+        }
+        if i + 1 < max_distance {
+            for [j, k] in strategy.swap_layers[i].iter() {
+                out.push(RoutedGate::Swap([*j, *k]));
+                layout.swap(*j, *k);
+                inverse[layout[*j]] = *j;
+                inverse[layout[*k]] = *k;
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl SwapStrategy {
+    #[new]
+    fn py_new(
+        num_vertices: usize,
+        edges: Vec<[usize; 2]>,
+        swap_layers: Vec<Vec<[usize; 2]>>,
+    ) -> PyResult<Self> {
+        Ok(Self::new(num_vertices, &edges, swap_layers)?)
+    }
+
+    /// The strategy of alternating layers of swaps on the even and the odd edges of a line.
+    #[staticmethod]
+    #[pyo3(name = "from_line", signature = (line, num_swap_layers=None))]
+    fn py_from_line(line: Vec<usize>, num_swap_layers: Option<usize>) -> PyResult<Self> {
+        Ok(Self::from_line(&line, num_swap_layers)?)
+    }
+
+    fn __len__(&self) -> usize {
+        self.swap_layers.len()
+    }
+
+    #[getter(num_vertices)]
+    fn py_num_vertices(&self) -> usize {
+        self.num_vertices
+    }
+
+    /// The number of swap layers after which each pair of vertices is first adjacent, or ``-1``
+    /// if it never is.
+    #[getter(distance_matrix)]
+    fn py_distance_matrix(&self, py: Python) -> Py<PyArray2<i64>> {
+        self.distance.clone().into_pyarray_bound(py).unbind()
+    }
+}
+
+/// Route a block of commuting two-qubit gates with a swap strategy.
+///
+/// Args:
+///     strategy (SwapStrategy): the swap strategy.
+///     num_qubits (int): the number of qubits of the routed circuit.
+///     gates (list[tuple[Gate, tuple[int, int]]]): the gates of the block, each with the pair of
+///         virtual qubits it acts on.
+///     layout (list[int]): the position of each virtual qubit before the block.
+///     edge_coloring (dict[tuple[int, int], int] | None): the color of each edge of the coupling
+///         map, by which the gates at each distance are put in sub-layers of gates on disjoint
+///         qubits.  The sub-layers are made greedily if it's not given.
+///
+/// Returns:
+///     (CircuitData, list[int]): the routed block, with the gates interleaved with the swaps of
+///     the strategy, and the position of each virtual qubit after it.
+///
+/// Raises:
+///     TranspilerError: if the strategy can't make the qubits of a gate adjacent.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (strategy, num_qubits, gates, layout, edge_coloring=None))]
+pub fn swap_decompose(
+    py: Python,
+    strategy: &SwapStrategy,
+    num_qubits: u32,
+    gates: Vec<(PyObject, [usize; 2])>,
+    mut layout: Vec<usize>,
+    edge_coloring: Option<HashMap<[usize; 2], usize>>,
+) -> PyResult<(CircuitData, Vec<usize>)> {
+    if layout.len() != num_qubits as usize {
+        return Err(AccelerateError::Value(format!(
+            "the layout is of {} qubits, but the circuit has {num_qubits}",
+            layout.len()
+        ))
+        .into());
+    }
+    let edges = gates.iter().map(|(_, edge)| *edge).collect::<Vec<_>>();
+    let routed = py.allow_threads(|| {
+        route_commuting_gates(strategy, &edges, &mut layout, edge_coloring.as_ref())
+    })?;
+    let mut out = CircuitData::from_standard_gates(py, num_qubits, std::iter::empty())?;
+    let swap = StandardGate::SwapGate.create_py_op(py, &[])?;
+    for gate in routed {
+        match gate {
+            RoutedGate::Term { index, qubits } => {
+                let qubits = qubits.map(|q| q as u32);
+                out.push_instruction(gates[index].0.clone_ref(py), &qubits, &[])?;
+            }
+            RoutedGate::Swap(qubits) => {
+                let qubits = qubits.map(|q| q as u32);
+                out.push_instruction(swap.clone_ref(py), &qubits, &[])?;
+            }
+        }
+    }
+    Ok((out, layout))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn swap_strategy(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<SwapStrategy>()?;
+    m.add_wrapped(wrap_pyfunction!(swap_decompose))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_line_distances() {
+        let strategy = SwapStrategy::from_line(&[0, 1, 2, 3], None).unwrap();
+        assert_eq!(strategy.swap_layers(), [vec![[0, 1], [2, 3]], vec![[1, 2]]]);
+        assert_eq!(
+            strategy.distance_matrix(),
+            array![[0, 0, 2, 1], [0, 0, 0, 2], [2, 0, 0, 0], [1, 2, 0, 0]]
+        );
+        assert!(strategy.can_couple(0, 2));
+        assert!(!strategy.can_couple(1, 1));
+        assert!(!strategy.can_couple(0, 4));
+        // Without swaps, only the neighbours of the line are ever adjacent.
+        let strategy = SwapStrategy::from_line(&[0, 1, 2], Some(0)).unwrap();
+        assert!(strategy.can_couple(1, 2));
+        assert!(!strategy.can_couple(0, 2));
+    }
+
+    #[test]
+    fn test_bad_strategies() {
+        assert!(matches!(
+            SwapStrategy::from_line(&[0], None),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            SwapStrategy::new(2, &[[0, 2]], vec![]),
+            Err(AccelerateError::Index(_))
+        ));
+        let edges = [[0, 1], [1, 0], [1, 2], [2, 1]];
+        assert!(matches!(
+            SwapStrategy::new(3, &edges, vec![vec![[0, 2]]]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            SwapStrategy::new(3, &edges, vec![vec![[0, 1], [1, 2]]]),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_route() {
+        let strategy = SwapStrategy::from_line(&[0, 1, 2], None).unwrap();
+        let mut layout = vec![0, 1, 2];
+        let routed = route_commuting_gates(&strategy, &[[0, 2], [0, 1]], &mut layout, None);
+        assert_eq!(
+            routed.unwrap(),
+            [
+                RoutedGate::Term {
+                    index: 1,
+                    qubits: [0, 1]
+                },
+                RoutedGate::Swap([0, 1]),
+                RoutedGate::Term {
+                    index: 0,
+                    qubits: [1, 2]
+                },
+            ]
+        );
+        assert_eq!(layout, [1, 0, 2]);
+        // No swaps are needed for gates that are already adjacent.
+        let routed = route_commuting_gates(&strategy, &[[0, 1]], &mut layout, None).unwrap();
+        assert_eq!(routed.len(), 1);
+        assert_eq!(layout, [1, 0, 2]);
+    }
+
+    #[test]
+    fn test_sub_layers() {
+        let strategy = SwapStrategy::from_line(&[0, 1, 2], None).unwrap();
+        let gates = [[0, 1], [1, 2]];
+        let indices = |routed: Vec<RoutedGate>| -> Vec<usize> {
+            routed
+                .into_iter()
+                .map(|gate| match gate {
+                    RoutedGate::Term { index, .. } => index,
+                    RoutedGate::Swap(_) => panic!("no swaps are needed"),
+                })
+                .collect()
+        };
+        let routed = route_commuting_gates(&strategy, &gates, &mut [0, 1, 2], None).unwrap();
+        assert_eq!(indices(routed), [0, 1]);
+        let coloring = HashMap::from([([0, 1], 1), ([1, 2], 0)]);
+        let routed =
+            route_commuting_gates(&strategy, &gates, &mut [0, 1, 2], Some(&coloring)).unwrap();
+        assert_eq!(indices(routed), [1, 0]);
+        let coloring = HashMap::from([([0, 1], 0)]);
+        assert!(matches!(
+            route_commuting_gates(&strategy, &gates, &mut [0, 1, 2], Some(&coloring)),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+
+    #[test]
+    fn test_route_errors() {
+        let strategy = SwapStrategy::from_line(&[0, 1, 2], Some(0)).unwrap();
+        assert!(matches!(
+            route_commuting_gates(&strategy, &[[0, 2]], &mut [0, 1, 2], None),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert!(matches!(
+            route_commuting_gates(&strategy, &[[1, 1]], &mut [0, 1, 2], None),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert!(matches!(
+            route_commuting_gates(&strategy, &[[0, 1]], &mut [0, 3, 2], None),
+            Err(AccelerateError::Index(_))
+        ));
+        // The second swap layer acts on a vertex beyond the qubits of the circuit.
+        let strategy = SwapStrategy::from_line(&[0, 1, 2], Some(2)).unwrap();
+        assert!(matches!(
+            route_commuting_gates(&strategy, &[[0, 1]], &mut [0, 1], None),
+            Err(AccelerateError::Index(_))
+        ));
+    }
+}
@@ -0,0 +1,588 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Clifford-to-circuit synthesis by symplectic Gaussian elimination.
+//!
+//! Both methods reduce a copy of the tableau to the identity by appending gates to it, and are
+//! gate-for-gate ports of `qiskit.synthesis.synth_clifford_ag` and `synth_clifford_greedy`.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+
+use smallvec::{smallvec, SmallVec};
+
+use crate::clifford::{CliffordGate, CliffordTableau};
+use crate::error::{AccelerateError, ErrorContext};
+
+/// A sequence of Clifford gates, each with the qubits it acts on.
+pub type CliffordGateSequence = Vec<(CliffordGate, SmallVec<[usize; 2]>)>;
+
+/// A tableau that is being reduced to the identity, along with the gates appended to it so far.
+struct Reduction {
+    tableau: CliffordTableau,
+    gates: CliffordGateSequence,
+}
+
+impl Reduction {
+    fn new(tableau: &CliffordTableau) -> Self {
+        Reduction {
+            tableau: tableau.clone(),
+            gates: Vec::new(),
+        }
+    }
+
+    /// Whether entry `(row, col)` of the tableau is set.
+    #[inline]
+    fn get(&self, row: usize, col: usize) -> bool {
+        self.tableau.tableau()[[row, col]]
+    }
+
+    fn apply(&mut self, gate: CliffordGate, qubits: &[usize]) -> Result<(), AccelerateError> {
+        self.tableau.append_gate(gate, qubits)?;
+        self.gates.push((gate, qubits.iter().copied().collect()));
+        Ok(())
+    }
+}
+
+/// Synthesize a Clifford with the method of Aaronson and Gottesman [1].
+///
+/// The tableau is reduced column by column; the circuit is the inverse of the reduction.
+///
+/// # References
+///
+/// 1. S. Aaronson, D. Gottesman, *Improved Simulation of Stabilizer Circuits*,
+///    Phys. Rev. A 70, 052328 (2004). [arXiv:quant-ph/0406196](https://arxiv.org/abs/quant-ph/0406196)
+pub fn synth_clifford_ag_inner(
+    clifford: &CliffordTableau,
+) -> Result<CliffordGateSequence, AccelerateError> {
+    let n = clifford.num_qubits();
+    let mut reduction = Reduction::new(clifford);
+    for qubit in 0..n {
+        ag_set_qubit_x_true(&mut reduction, qubit)?;
+        ag_set_row_x_zero(&mut reduction, qubit)?;
+        ag_set_row_z_zero(&mut reduction, qubit)?;
+    }
+    for qubit in 0..n {
+        if reduction.get(qubit, 2 * n) {
+            reduction.apply(CliffordGate::Z, &[qubit])?;
+        }
+        if reduction.get(n + qubit, 2 * n) {
+            reduction.apply(CliffordGate::X, &[qubit])?;
+        }
+    }
+    // Only `S` among the gates used in the reduction is not self-inverse.
+    Ok(reduction
+        .gates
+        .into_iter()
+        .rev()
+        .map(|(gate, qubits)| match gate {
+            CliffordGate::S => (CliffordGate::Sdg, qubits),
+            CliffordGate::Sdg => (CliffordGate::S, qubits),
+            _ => (gate, qubits),
+        })
+        .collect())
+}
+
+/// Set destabilizer `X[qubit, qubit]` by swapping in a later column, or applying a Hadamard if
+/// there is no `X` to swap in.
+fn ag_set_qubit_x_true(reduction: &mut Reduction, qubit: usize) -> Result<(), AccelerateError> {
+    let n = reduction.tableau.num_qubits();
+    if reduction.get(qubit, qubit) {
+        return Ok(());
+    }
+    if let Some(i) = (qubit + 1..n).find(|&i| reduction.get(qubit, i)) {
+        return reduction.apply(CliffordGate::Swap, &[i, qubit]);
+    }
+    if let Some(i) = (qubit..n).find(|&i| reduction.get(qubit, n + i)) {
+        reduction.apply(CliffordGate::H, &[i])?;
+        if i != qubit {
+            reduction.apply(CliffordGate::Swap, &[i, qubit])?;
+        }
+    }
+    Ok(())
+}
+
+/// Clear destabilizer `X[qubit, i]` for `i > qubit`, and then its `Z` part.
+fn ag_set_row_x_zero(reduction: &mut Reduction, qubit: usize) -> Result<(), AccelerateError> {
+    let n = reduction.tableau.num_qubits();
+    for i in qubit + 1..n {
+        if reduction.get(qubit, i) {
+            reduction.apply(CliffordGate::CX, &[qubit, i])?;
+        }
+    }
+    if (qubit..n).any(|i| reduction.get(qubit, n + i)) {
+        if !reduction.get(qubit, n + qubit) {
+            reduction.apply(CliffordGate::S, &[qubit])?;
+        }
+        for i in qubit + 1..n {
+            if reduction.get(qubit, n + i) {
+                reduction.apply(CliffordGate::CX, &[i, qubit])?;
+            }
+        }
+        reduction.apply(CliffordGate::S, &[qubit])?;
+    }
+    Ok(())
+}
+
+/// Clear stabilizer `Z[qubit, i]` for `i > qubit`, and then its `X` part.  This assumes the
+/// destabilizer row has already been reduced.
+fn ag_set_row_z_zero(reduction: &mut Reduction, qubit: usize) -> Result<(), AccelerateError> {
+    let n = reduction.tableau.num_qubits();
+    let row = n + qubit;
+    for i in qubit + 1..n {
+        if reduction.get(row, n + i) {
+            reduction.apply(CliffordGate::CX, &[i, qubit])?;
+        }
+    }
+    if (qubit..n).any(|i| reduction.get(row, i)) {
+        reduction.apply(CliffordGate::H, &[qubit])?;
+        for i in qubit + 1..n {
+            if reduction.get(row, i) {
+                reduction.apply(CliffordGate::CX, &[qubit, i])?;
+            }
+        }
+        if reduction.get(row, n + qubit) {
+            reduction.apply(CliffordGate::S, &[qubit])?;
+        }
+        reduction.apply(CliffordGate::H, &[qubit])?;
+    }
+    Ok(())
+}
+
+/// The five equivalence classes, under single-qubit Cliffords, of the pair of Paulis that a
+/// Clifford maps `X` and `Z` on one qubit to, restricted to another qubit.  The canonical
+/// representatives are `XZ`, `XX`, `XI`, `IZ` and `II`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PairClass {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+/// The restriction to one qubit of the pair of Paulis, as `(x.z, x.x, z.z, z.x)`.
+type PauliPair = (bool, bool, bool, bool);
+
+impl PairClass {
+    fn of(pair: PauliPair) -> Self {
+        let (xz, xx, zz, zx) = pair;
+        match (xz | xx, zz | zx) {
+            (true, true) if (xz, xx) == (zz, zx) => PairClass::B,
+            (true, true) => PairClass::A,
+            (true, false) => PairClass::C,
+            (false, true) => PairClass::D,
+            (false, false) => PairClass::E,
+        }
+    }
+}
+
+/// The restriction to `qubit` of the Schrödinger-frame images of `X` and `Z` on `target` under
+/// the symplectic transpose of `tableau`.
+fn pauli_pair(tableau: &CliffordTableau, target: usize, qubit: usize) -> PauliPair {
+    let n = tableau.num_qubits();
+    let t = tableau.tableau();
+    (
+        t[[qubit, n + target]],
+        t[[n + qubit, n + target]],
+        t[[qubit, target]],
+        t[[n + qubit, target]],
+    )
+}
+
+fn elimination_failed() -> AccelerateError {
+    AccelerateError::Synthesis(
+        "Symplectic Gaussian elimination fails.".to_string(),
+        ErrorContext::pass("synth_clifford_greedy"),
+    )
+}
+
+/// The CNOT cost of decoupling `target` from the other remaining `qubits`.
+fn greedy_cost(
+    tableau: &CliffordTableau,
+    target: usize,
+    qubits: &[usize],
+) -> Result<usize, AccelerateError> {
+    let mut counts = [0usize; 4];
+    for &qubit in qubits {
+        match PairClass::of(pauli_pair(tableau, target, qubit)) {
+            PairClass::A => counts[0] += 1,
+            PairClass::B => counts[1] += 1,
+            PairClass::C => counts[2] += 1,
+            PairClass::D => counts[3] += 1,
+            PairClass::E => (),
+        }
+    }
+    let [a, b, c, d] = counts;
+    if a % 2 == 0 {
+        return Err(elimination_failed());
+    }
+    let mut cost = 3 * (a - 1) / 2 + if b > 0 { b + 1 } else { 0 } + c + d;
+    // An extra swap, if the first remaining qubit isn't already in class A.
+    if PairClass::of(pauli_pair(tableau, target, qubits[0])) != PairClass::A {
+        cost += 3;
+    }
+    Ok(cost)
+}
+
+/// The gates of the operator that decouples `target` from the rest of `qubits`, reducing each of
+/// the pairs on the other qubits to `II` and the one on `target` to `XZ`.
+fn greedy_decoupling(
+    tableau: &CliffordTableau,
+    target: usize,
+    qubits: &[usize],
+) -> Result<CliffordGateSequence, AccelerateError> {
+    let mut gates: CliffordGateSequence = Vec::new();
+    let pairs = qubits
+        .iter()
+        .map(|&qubit| (qubit, pauli_pair(tableau, target, qubit)))
+        .collect::<Vec<_>>();
+
+    // Reduce each pair to the canonical representative of its class with single-qubit gates.
+    for &(qubit, pair) in &pairs {
+        let reduction: &[CliffordGate] = match pair {
+            // YI, YY, YZ
+            (true, true, false, false) | (true, true, true, true) | (true, true, true, false) => {
+                &[CliffordGate::S]
+            }
+            // ZI, ZZ, ZX, IX
+            (true, false, false, false)
+            | (true, false, true, false)
+            | (true, false, false, true)
+            | (false, false, false, true) => &[CliffordGate::H],
+            // IY, ZY
+            (false, false, true, true) | (true, false, true, true) => {
+                &[CliffordGate::S, CliffordGate::H]
+            }
+            // YX
+            (true, true, false, true) => &[CliffordGate::H, CliffordGate::S],
+            // XY
+            (false, true, true, true) => &[CliffordGate::S, CliffordGate::H, CliffordGate::S],
+            _ => &[],
+        };
+        gates.extend(reduction.iter().map(|gate| (*gate, smallvec![qubit])));
+    }
+
+    let mut classes: [Vec<usize>; 4] = Default::default();
+    for &(qubit, pair) in &pairs {
+        match PairClass::of(pair) {
+            PairClass::A => classes[0].push(qubit),
+            PairClass::B => classes[1].push(qubit),
+            PairClass::C => classes[2].push(qubit),
+            PairClass::D => classes[3].push(qubit),
+            PairClass::E => (),
+        }
+    }
+    let [mut a_qubits, mut b_qubits, mut c_qubits, mut d_qubits] = classes;
+    if a_qubits.len() % 2 != 1 {
+        return Err(elimination_failed());
+    }
+
+    // Make sure `target` is in class A, by swapping it with the first qubit that is.
+    if !a_qubits.contains(&target) {
+        let qubit_a = a_qubits[0];
+        gates.push((CliffordGate::Swap, smallvec![target, qubit_a]));
+        for others in [&mut b_qubits, &mut c_qubits, &mut d_qubits] {
+            if let Some(pos) = others.iter().position(|&q| q == target) {
+                others.remove(pos);
+                others.push(qubit_a);
+                break;
+            }
+        }
+        a_qubits.retain(|&q| q != qubit_a);
+        a_qubits.push(target);
+    }
+
+    for &qubit in &c_qubits {
+        gates.push((CliffordGate::CX, smallvec![target, qubit]));
+    }
+    for &qubit in &d_qubits {
+        gates.push((CliffordGate::CX, smallvec![qubit, target]));
+    }
+    if let Some((&qubit_b, rest)) = b_qubits.split_first() {
+        for &qubit in rest {
+            gates.push((CliffordGate::CX, smallvec![qubit_b, qubit]));
+        }
+        gates.push((CliffordGate::CX, smallvec![target, qubit_b]));
+        gates.push((CliffordGate::H, smallvec![qubit_b]));
+        gates.push((CliffordGate::CX, smallvec![qubit_b, target]));
+    }
+    let a_pairs = (a_qubits.len() - 1) / 2;
+    if a_pairs > 0 {
+        a_qubits.retain(|&q| q != target);
+    }
+    for k in 0..a_pairs {
+        let (first, second) = (a_qubits[2 * k], a_qubits[2 * k + 1]);
+        gates.push((CliffordGate::CX, smallvec![second, first]));
+        gates.push((CliffordGate::CX, smallvec![first, target]));
+        gates.push((CliffordGate::CX, smallvec![target, second]));
+    }
+    Ok(gates)
+}
+
+/// Synthesize a Clifford with the greedy compiler of Bravyi, Hu, Maslov and Shaydulin [1]
+/// (Appendix A).
+///
+/// At each step this picks the remaining qubit that is cheapest to decouple from the others,
+/// measured in CNOTs, and peels it off.  It typically needs fewer CNOTs than
+/// [synth_clifford_ag_inner].
+///
+/// # References
+///
+/// 1. S. Bravyi, S. Hu, D. Maslov, R. Shaydulin, *Clifford Circuit Optimization with Templates
+///    and Symbolic Pauli Gates*, [arXiv:2105.02291](https://arxiv.org/abs/2105.02291)
+pub fn synth_clifford_greedy_inner(
+    clifford: &CliffordTableau,
+) -> Result<CliffordGateSequence, AccelerateError> {
+    let n = clifford.num_qubits();
+    let mut remaining = clifford.clone();
+    let mut qubits = (0..n).collect::<Vec<_>>();
+    let mut gates: CliffordGateSequence = Vec::new();
+
+    while !qubits.is_empty() {
+        let mut best = None;
+        for &target in &qubits {
+            let cost = greedy_cost(&remaining, target, &qubits)?;
+            if best.map_or(true, |(best_cost, _)| cost < best_cost) {
+                best = Some((cost, target));
+            }
+        }
+        let (_, target) = best.unwrap();
+
+        let decoupling = greedy_decoupling(&remaining, target, &qubits)?;
+        let mut decoupler = CliffordTableau::identity(n);
+        for (gate, gate_qubits) in &decoupling {
+            decoupler.append_gate(*gate, gate_qubits)?;
+        }
+        // Now `remaining` acts trivially on `target`.
+        remaining = decoupler.adjoint().compose(&remaining, false)?;
+        gates.extend(decoupling);
+        qubits.retain(|&q| q != target);
+    }
+
+    let phase = remaining.phase();
+    for qubit in 0..n {
+        let gate = match (phase[qubit], phase[n + qubit]) {
+            (true, true) => CliffordGate::Y,
+            (false, true) => CliffordGate::X,
+            (true, false) => CliffordGate::Z,
+            (false, false) => continue,
+        };
+        gates.push((gate, smallvec![qubit]));
+    }
+    Ok(gates)
+}
+
+/// Build the `CircuitData` of a sequence of gates on `num_qubits` qubits.
+#[cfg(feature = "python")]
+fn gates_to_circuit(
+    py: Python,
+    num_qubits: usize,
+    gates: CliffordGateSequence,
+) -> PyResult<CircuitData> {
+    let standard_gate = |gate: CliffordGate| match gate {
+        CliffordGate::I => StandardGate::IGate,
+        CliffordGate::X => StandardGate::XGate,
+        CliffordGate::Y => StandardGate::YGate,
+        CliffordGate::Z => StandardGate::ZGate,
+        CliffordGate::H => StandardGate::HGate,
+        CliffordGate::S => StandardGate::SGate,
+        CliffordGate::Sdg => StandardGate::SdgGate,
+        CliffordGate::SX => StandardGate::SXGate,
+        CliffordGate::SXdg => StandardGate::SXdgGate,
+        CliffordGate::CX => StandardGate::CXGate,
+        CliffordGate::CZ => StandardGate::CZGate,
+        CliffordGate::CY => StandardGate::CYGate,
+        CliffordGate::Swap => StandardGate::SwapGate,
+        CliffordGate::ISwap => StandardGate::ISwapGate,
+        CliffordGate::ECR => StandardGate::ECRGate,
+        CliffordGate::V | CliffordGate::W | CliffordGate::DCX => {
+            unreachable!("the Clifford synthesis routines don't emit {gate:?}")
+        }
+    };
+    CircuitData::from_standard_gates(
+        py,
+        num_qubits as u32,
+        gates.into_iter().map(|(gate, qubits)| {
+            (
+                standard_gate(gate),
+                Vec::new(),
+                qubits.into_iter().map(|q| q as u32).collect(),
+            )
+        }),
+    )
+}
+
+/// Synthesize a Clifford with the Aaronson-Gottesman method.
+///
+/// Args:
+///     tableau (ndarray): the boolean tableau of the Clifford, of shape ``(2n, 2n + 1)``.
+///
+/// Returns:
+///     CircuitData: the circuit implementing the Clifford.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(tableau, /)")]
+pub fn synth_clifford_ag(py: Python, tableau: PyReadonlyArray2<bool>) -> PyResult<CircuitData> {
+    let clifford = CliffordTableau::from_tableau(tableau.as_array().to_owned())?;
+    let gates = synth_clifford_ag_inner(&clifford)?;
+    gates_to_circuit(py, clifford.num_qubits(), gates)
+}
+
+/// Synthesize a Clifford with the greedy compiler of Bravyi, Hu, Maslov and Shaydulin.
+///
+/// Args:
+///     tableau (ndarray): the boolean tableau of the Clifford, of shape ``(2n, 2n + 1)``.
+///
+/// Returns:
+///     CircuitData: the circuit implementing the Clifford.
+///
+/// Raises:
+///     QiskitError: if the symplectic Gaussian elimination fails.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(tableau, /)")]
+pub fn synth_clifford_greedy(py: Python, tableau: PyReadonlyArray2<bool>) -> PyResult<CircuitData> {
+    let clifford = CliffordTableau::from_tableau(tableau.as_array().to_owned())?;
+    let gates = synth_clifford_greedy_inner(&clifford)?;
+    gates_to_circuit(py, clifford.num_qubits(), gates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    /// The tableau of a random circuit of `num_gates` Clifford gates.
+    fn random_clifford(num_qubits: usize, num_gates: usize, seed: u128) -> CliffordTableau {
+        const GATES: [CliffordGate; 12] = [
+            CliffordGate::X,
+            CliffordGate::Y,
+            CliffordGate::Z,
+            CliffordGate::H,
+            CliffordGate::S,
+            CliffordGate::Sdg,
+            CliffordGate::SX,
+            CliffordGate::CX,
+            CliffordGate::CZ,
+            CliffordGate::CY,
+            CliffordGate::Swap,
+            CliffordGate::ISwap,
+        ];
+        let mut rng = generator(seed);
+        let mut out = CliffordTableau::identity(num_qubits);
+        for _ in 0..num_gates {
+            let gate = *GATES
+                .iter()
+                .filter(|gate| gate.num_qubits() <= num_qubits)
+                .choose(&mut rng)
+                .unwrap();
+            let qubits = (0..num_qubits).choose_multiple(&mut rng, gate.num_qubits());
+            out.append_gate(gate, &qubits).unwrap();
+        }
+        out
+    }
+
+    /// The tableau of the circuit `gates`.
+    fn replay(num_qubits: usize, gates: &CliffordGateSequence) -> CliffordTableau {
+        let mut out = CliffordTableau::identity(num_qubits);
+        for (gate, qubits) in gates {
+            out.append_gate(*gate, qubits).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn test_synth_clifford_ag() {
+        for num_qubits in 1..=5 {
+            for seed in 0..10 {
+                let clifford = random_clifford(num_qubits, 10 * num_qubits, seed);
+                let gates = synth_clifford_ag_inner(&clifford).unwrap();
+                assert_eq!(replay(num_qubits, &gates), clifford);
+                assert!(gates.iter().all(|(gate, _)| matches!(
+                    gate,
+                    CliffordGate::H
+                        | CliffordGate::S
+                        | CliffordGate::Sdg
+                        | CliffordGate::CX
+                        | CliffordGate::Swap
+                        | CliffordGate::X
+                        | CliffordGate::Z
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn test_synth_clifford_greedy() {
+        for num_qubits in 1..=5 {
+            for seed in 0..10 {
+                let clifford = random_clifford(num_qubits, 10 * num_qubits, seed);
+                let gates = synth_clifford_greedy_inner(&clifford).unwrap();
+                assert_eq!(replay(num_qubits, &gates), clifford);
+                assert!(gates.iter().all(|(gate, _)| matches!(
+                    gate,
+                    CliffordGate::H
+                        | CliffordGate::S
+                        | CliffordGate::CX
+                        | CliffordGate::Swap
+                        | CliffordGate::X
+                        | CliffordGate::Y
+                        | CliffordGate::Z
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn test_identity_needs_no_gates() {
+        let identity = CliffordTableau::identity(4);
+        assert!(synth_clifford_ag_inner(&identity).unwrap().is_empty());
+        assert!(synth_clifford_greedy_inner(&identity).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pauli_needs_only_paulis() {
+        let mut clifford = CliffordTableau::identity(3);
+        clifford.append_gate(CliffordGate::X, &[0]).unwrap();
+        clifford.append_gate(CliffordGate::Y, &[2]).unwrap();
+        for gates in [
+            synth_clifford_ag_inner(&clifford).unwrap(),
+            synth_clifford_greedy_inner(&clifford).unwrap(),
+        ] {
+            assert_eq!(replay(3, &gates), clifford);
+            assert!(gates.iter().all(|(gate, _)| matches!(
+                gate,
+                CliffordGate::X | CliffordGate::Y | CliffordGate::Z
+            )));
+        }
+    }
+
+    #[test]
+    fn test_synth_clifford_greedy_not_symplectic() {
+        let zeros =
+            CliffordTableau::from_tableau(ndarray::Array2::from_elem((4, 5), false)).unwrap();
+        assert!(matches!(
+            synth_clifford_greedy_inner(&zeros),
+            Err(AccelerateError::Synthesis(..))
+        ));
+    }
+}
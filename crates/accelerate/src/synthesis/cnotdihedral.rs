@@ -0,0 +1,249 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of CNOT-dihedral elements on any number of qubits (Cross, Magesan, Bishop, Smolin and
+//! Gambetta, npj Quantum Inf 2, 16012 (2016)).
+//!
+//! The affine part is reduced to the identity by `X` gates and Gaussian elimination with `CX`
+//! gates, and the phase polynomial is then built monomial by monomial, from the cubic terms down,
+//! with `CX` conjugations of phase gates.  This is a gate-for-gate port of
+//! `qiskit.synthesis.synth_cnotdihedral_general`; the number of `CX` gates is not optimal.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use std::f64::consts::FRAC_PI_4;
+
+use super::{GateSequence, SynthesisGate};
+use crate::cnotdihedral::CNOTDihedralElement;
+use crate::error::{AccelerateError, ErrorContext};
+
+/// Decompose a CNOT-dihedral element into `X`, `CX` and phase gates.
+pub fn synth_cnotdihedral_general_inner(
+    elem: &CNOTDihedralElement,
+) -> Result<GateSequence, AccelerateError> {
+    let num_qubits = elem.num_qubits();
+    let mut reduced = elem.clone();
+    // The `X` and `CX` gates that reduce the affine part, which are all self-inverse.
+    let mut reduction: Vec<(SynthesisGate, [usize; 2])> = Vec::new();
+
+    for i in 0..num_qubits {
+        if elem.shift()[i] {
+            reduced.append_x(i)?;
+            reduction.push((SynthesisGate::X, [i, i]));
+        }
+    }
+
+    for i in 0..num_qubits {
+        if !reduced.linear()[[i, i]] {
+            let pivot = (i + 1..num_qubits)
+                .find(|&j| reduced.linear()[[j, i]])
+                .ok_or_else(|| {
+                    AccelerateError::Synthesis(
+                        "Linear part is not invertible.".to_string(),
+                        ErrorContext::pass("synth_cnotdihedral_general").with_qubits([i]),
+                    )
+                })?;
+            // Swap the rows `i` and `pivot`.
+            for (control, target) in [(pivot, i), (i, pivot), (pivot, i)] {
+                reduced.append_cx(control, target)?;
+                reduction.push((SynthesisGate::CX, [control, target]));
+            }
+        }
+        for j in 0..num_qubits {
+            if j != i && reduced.linear()[[j, i]] {
+                reduced.append_cx(i, j)?;
+                reduction.push((SynthesisGate::CX, [i, j]));
+            }
+        }
+    }
+    if reduced.shift().iter().any(|x| *x)
+        || reduced
+            .linear()
+            .indexed_iter()
+            .any(|((i, j), x)| *x != (i == j))
+    {
+        return Err(AccelerateError::Synthesis(
+            "Cannot do Gauss elimination on linear part.".to_string(),
+            ErrorContext::pass("synth_cnotdihedral_general"),
+        ));
+    }
+
+    let target = reduced.poly();
+    let mut built = CNOTDihedralElement::identity(num_qubits);
+    let mut sequence = GateSequence::new();
+
+    // The monomials of weight 3.
+    for i in 0..num_qubits {
+        for j in i + 1..num_qubits {
+            for k in j + 1..num_qubits {
+                if target.get_term(&[i, j, k]) != 0 {
+                    built.append_cx(i, k)?;
+                    built.append_cx(j, k)?;
+                    built.append_phase(1, k)?;
+                    built.append_cx(i, k)?;
+                    built.append_cx(j, k)?;
+                    sequence.push(SynthesisGate::CX, &[], &[i, k]);
+                    sequence.push(SynthesisGate::CX, &[], &[j, k]);
+                    sequence.push(SynthesisGate::Phase, &[FRAC_PI_4], &[k]);
+                    sequence.push(SynthesisGate::CX, &[], &[i, k]);
+                    sequence.push(SynthesisGate::CX, &[], &[j, k]);
+                }
+            }
+        }
+    }
+
+    // The monomials of weight 2.
+    for i in 0..num_qubits {
+        for j in i + 1..num_qubits {
+            let tpow = ((built.poly().get_term(&[i, j]) + 8 - target.get_term(&[i, j])) % 8) / 2;
+            if tpow != 0 {
+                built.append_cx(i, j)?;
+                built.append_phase(tpow, j)?;
+                built.append_cx(i, j)?;
+                sequence.push(SynthesisGate::CX, &[], &[i, j]);
+                sequence.push(SynthesisGate::Phase, &[tpow as f64 * FRAC_PI_4], &[j]);
+                sequence.push(SynthesisGate::CX, &[], &[i, j]);
+            }
+        }
+    }
+
+    // The monomials of weight 1.
+    for i in 0..num_qubits {
+        let tpow = (target.get_term(&[i]) + 8 - built.poly().get_term(&[i])) % 8;
+        if tpow != 0 {
+            built.append_phase(tpow, i)?;
+            sequence.push(SynthesisGate::Phase, &[tpow as f64 * FRAC_PI_4], &[i]);
+        }
+    }
+
+    if elem.poly() != built.poly() {
+        return Err(AccelerateError::Synthesis(
+            "Could not recover phase polynomial.".to_string(),
+            ErrorContext::pass("synth_cnotdihedral_general"),
+        ));
+    }
+
+    for (gate, qubits) in reduction.into_iter().rev() {
+        let qubits = match gate {
+            SynthesisGate::X => &qubits[..1],
+            _ => &qubits[..],
+        };
+        sequence.push(gate, &[], qubits);
+    }
+    Ok(sequence)
+}
+
+/// Synthesize a CNOT-dihedral element on any number of qubits.
+///
+/// Args:
+///     elem (CNOTDihedralElement): the element to synthesize.
+///
+/// Returns:
+///     (CircuitData, float): the circuit of ``X``, ``CX`` and ``P`` gates, and its global phase.
+///
+/// Raises:
+///     QiskitError: if the element could not be decomposed into a circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(elem, /)")]
+pub fn synth_cnotdihedral_general(
+    py: Python,
+    elem: &CNOTDihedralElement,
+) -> PyResult<(CircuitData, f64)> {
+    synth_cnotdihedral_general_inner(elem)?.into_circuit(py, elem.num_qubits())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ndarray::prelude::*;
+    use num_complex::Complex64;
+    use rand::prelude::*;
+
+    use crate::cnotdihedral::CNOTDihedralGate;
+    use crate::rng::generator;
+    use crate::synthesis::test::assert_implements;
+
+    /// The unitary of `elem`, with the global phase that `sequence` gives it.
+    fn element_matrix(sequence: &GateSequence, elem: &CNOTDihedralElement) -> Array2<Complex64> {
+        let num_qubits = elem.num_qubits();
+        let omega = |k: u8| Complex64::from_polar(1., k as f64 * FRAC_PI_4);
+        let (phase, y) = elem.act(0);
+        let global = sequence.to_matrix(num_qubits)[[y, 0]] / omega(phase);
+        assert!((global.norm() - 1.).abs() < 1e-10);
+        let mut mat = Array2::<Complex64>::zeros((1 << num_qubits, 1 << num_qubits));
+        for x in 0..1 << num_qubits {
+            let (phase, y) = elem.act(x);
+            mat[[y, x]] = global * omega(phase);
+        }
+        mat
+    }
+
+    #[test]
+    fn test_synth_random_elements() {
+        let gates = [
+            CNOTDihedralGate::X,
+            CNOTDihedralGate::Y,
+            CNOTDihedralGate::T,
+            CNOTDihedralGate::S,
+            CNOTDihedralGate::CX,
+            CNOTDihedralGate::CS,
+            CNOTDihedralGate::Swap,
+            CNOTDihedralGate::CCZ,
+        ];
+        let mut rng = generator(31);
+        for num_qubits in 1..=4 {
+            for _ in 0..5 {
+                let mut elem = CNOTDihedralElement::identity(num_qubits);
+                for _ in 0..20 {
+                    let gate = gates[rng.gen_range(0..gates.len())];
+                    if gate.num_qubits() > num_qubits {
+                        continue;
+                    }
+                    let qubits = (0..num_qubits).choose_multiple(&mut rng, gate.num_qubits());
+                    elem.append_gate(gate, &qubits).unwrap();
+                }
+                let sequence = synth_cnotdihedral_general_inner(&elem).unwrap();
+                assert!(sequence.gates.iter().all(|(gate, _, _)| matches!(
+                    gate,
+                    SynthesisGate::X | SynthesisGate::CX | SynthesisGate::Phase
+                )));
+                let expected = element_matrix(&sequence, &elem);
+                assert_implements(&sequence, expected.view(), num_qubits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_synth_identity() {
+        let sequence = synth_cnotdihedral_general_inner(&CNOTDihedralElement::identity(3)).unwrap();
+        assert!(sequence.gates.is_empty());
+    }
+
+    #[test]
+    fn test_synth_not_invertible() {
+        let elem = CNOTDihedralElement::new(
+            Array2::from_elem((2, 2), false),
+            Array1::from_elem(2, false),
+            crate::cnotdihedral::SpecialPolynomial::zero(2),
+        )
+        .unwrap();
+        assert!(matches!(
+            synth_cnotdihedral_general_inner(&elem),
+            Err(AccelerateError::Synthesis(_, _))
+        ));
+    }
+}
@@ -0,0 +1,271 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The cosine-sine decomposition of unitaries, which splits the top qubit off an `n`-qubit unitary
+//! as a multiplexed `RY` rotation between two multiplexed `(n - 1)`-qubit unitaries.  This is the
+//! building block of the quantum Shannon decomposition, and it replaces `scipy.linalg.cossin` for
+//! the square, evenly split case that synthesis needs.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use faer_ext::{IntoFaerComplex, IntoNdarrayComplex};
+use ndarray::prelude::*;
+use num_complex::Complex64;
+
+use crate::error::AccelerateError;
+use crate::operator::adjoint_inner;
+
+/// The cosine-sine decomposition of a unitary of even dimension `2m`, split into `m x m` blocks:
+///
+/// ```text
+/// mat = [u1    ] [C  -S] [v1h     ]
+///       [    u2] [S   C] [     v2h]
+/// ```
+///
+/// with `C = diag(cos(theta))` and `S = diag(sin(theta))`.
+pub struct CosSin {
+    pub u1: Array2<Complex64>,
+    pub u2: Array2<Complex64>,
+    pub theta: Vec<f64>,
+    pub v1h: Array2<Complex64>,
+    pub v2h: Array2<Complex64>,
+}
+
+/// The closest unitary to `mat`, which is the unitary factor of its polar decomposition.
+fn closest_unitary(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let svd = mat.into_faer_complex().svd();
+    let u = svd.u().into_ndarray_complex();
+    let v = svd.v().into_ndarray_complex();
+    u.dot(&adjoint_inner(v))
+}
+
+/// Compute the cosine-sine decomposition of the unitary `mat`, whose dimension must be even.
+///
+/// The angles are in `[0, π/2]` and increase along `theta`.  The factors are computed from an SVD
+/// of the top-left block and a QR decomposition of the bottom-left one, choosing for each row of
+/// `v2h` whichever of the two right-hand blocks is better conditioned, so the decomposition stays
+/// accurate for degenerate and for near-zero angles.
+pub fn cossin_inner(mat: ArrayView2<Complex64>) -> Result<CosSin, AccelerateError> {
+    let dim = mat.nrows();
+    if dim != mat.ncols() || dim == 0 || dim % 2 != 0 {
+        return Err(AccelerateError::Qiskit(format!(
+            "the cosine-sine decomposition needs a square matrix of even dimension, but got shape \
+             {:?}",
+            mat.shape()
+        )));
+    }
+    let m = dim / 2;
+    let x11 = mat.slice(s![..m, ..m]).to_owned();
+    let x12 = mat.slice(s![..m, m..]);
+    let x21 = mat.slice(s![m.., ..m]);
+    let x22 = mat.slice(s![m.., m..]);
+
+    // The SVD of the top-left block gives the cosines.  We order them increasingly, so the sines
+    // decrease: the QR decomposition below then meets the columns of `x21 v1` that have
+    // ill-defined directions last, where they can't spoil the others.
+    let svd = x11.view().into_faer_complex().svd();
+    let singular = svd.s_diagonal();
+    let mut order = (0..m).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| singular.read(a).re.total_cmp(&singular.read(b).re));
+    let u = svd.u().into_ndarray_complex();
+    let v = svd.v().into_ndarray_complex();
+    let u1 = u.select(Axis(1), &order);
+    let v1 = v.select(Axis(1), &order);
+    let cos = order
+        .iter()
+        .map(|&i| singular.read(i).re.min(1.))
+        .collect::<Vec<_>>();
+
+    // The columns of `x21 v1` are orthogonal with norms equal to the sines.
+    let z = x21.dot(&v1);
+    let qr = z.view().into_faer_complex().qr();
+    let q = qr.compute_q();
+    let r = qr.compute_r();
+    let mut u2 = q.as_ref().into_ndarray_complex().to_owned();
+    let r = r.as_ref().into_ndarray_complex();
+    let mut sin = Vec::with_capacity(m);
+    for (mut col, r_ii) in u2.columns_mut().into_iter().zip(r.diag()) {
+        let norm = r_ii.norm();
+        if norm > 0. {
+            let phase = r_ii / norm;
+            col.mapv_inplace(|x| x * phase);
+        }
+        sin.push(norm.min(1.));
+    }
+
+    // Each row of `v2h` follows from whichever of `x12 = -u1 S v2h` and `x22 = u2 C v2h` divides
+    // by the larger of the sine and the cosine.
+    let from_cos = adjoint_inner(u2.view()).dot(&x22);
+    let from_sin = adjoint_inner(u1.view()).dot(&x12);
+    let mut v2h = Array2::<Complex64>::zeros((m, m));
+    for (i, mut row) in v2h.rows_mut().into_iter().enumerate() {
+        if cos[i] >= sin[i] {
+            row.assign(&from_cos.row(i).mapv(|x| x / cos[i]));
+        } else {
+            row.assign(&from_sin.row(i).mapv(|x| -x / sin[i]));
+        }
+    }
+    let v2h = closest_unitary(v2h.view());
+
+    Ok(CosSin {
+        u1,
+        u2,
+        theta: sin.iter().zip(&cos).map(|(s, c)| s.atan2(*c)).collect(),
+        v1h: adjoint_inner(v1.view()),
+        v2h,
+    })
+}
+
+#[cfg(feature = "python")]
+type PyCosSin = (
+    (Py<PyArray2<Complex64>>, Py<PyArray2<Complex64>>),
+    Py<PyArray1<f64>>,
+    (Py<PyArray2<Complex64>>, Py<PyArray2<Complex64>>),
+);
+
+/// Compute the cosine-sine decomposition of a unitary of even dimension, split into four equal
+/// blocks.
+///
+/// This matches ``scipy.linalg.cossin(mat, p=dim // 2, q=dim // 2, separate=True)``.
+///
+/// Args:
+///     mat (ndarray): the unitary to decompose.
+///
+/// Returns:
+///     tuple: ``((u1, u2), theta, (v1h, v2h))``, with the angles ``theta`` increasing.
+///
+/// Raises:
+///     QiskitError: if the matrix is not square with an even dimension.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(mat, /)")]
+pub fn cossin(py: Python, mat: PyReadonlyArray2<Complex64>) -> PyResult<PyCosSin> {
+    let CosSin {
+        u1,
+        u2,
+        theta,
+        v1h,
+        v2h,
+    } = cossin_inner(mat.as_array())?;
+    let array = |x: Array2<Complex64>| x.into_pyarray_bound(py).unbind();
+    Ok((
+        (array(u1), array(u2)),
+        theta.into_pyarray_bound(py).unbind(),
+        (array(v1h), array(v2h)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::FRAC_PI_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+
+    fn block_diag(a: &Array2<Complex64>, b: &Array2<Complex64>) -> Array2<Complex64> {
+        let m = a.nrows();
+        let mut out = Array2::zeros((2 * m, 2 * m));
+        out.slice_mut(s![..m, ..m]).assign(a);
+        out.slice_mut(s![m.., m..]).assign(b);
+        out
+    }
+
+    /// Check the decomposition of `mat` multiplies back to it and has unitary factors.
+    fn check(mat: &Array2<Complex64>) -> CosSin {
+        let decomposition = cossin_inner(mat.view()).unwrap();
+        let m = mat.nrows() / 2;
+        let mut middle = Array2::zeros((2 * m, 2 * m));
+        for (i, theta) in decomposition.theta.iter().enumerate() {
+            let (sin, cos) = theta.sin_cos();
+            middle[[i, i]] = Complex64::new(cos, 0.);
+            middle[[i + m, i + m]] = Complex64::new(cos, 0.);
+            middle[[i, i + m]] = Complex64::new(-sin, 0.);
+            middle[[i + m, i]] = Complex64::new(sin, 0.);
+        }
+        let left = block_diag(&decomposition.u1, &decomposition.u2);
+        let right = block_diag(&decomposition.v1h, &decomposition.v2h);
+        assert_abs_diff_eq!(left.dot(&middle).dot(&right), *mat, epsilon = 1e-10);
+        let eye = Array2::<Complex64>::eye(m);
+        for factor in [
+            &decomposition.u1,
+            &decomposition.u2,
+            &decomposition.v1h,
+            &decomposition.v2h,
+        ] {
+            assert_abs_diff_eq!(
+                adjoint_inner(factor.view()).dot(factor),
+                eye,
+                epsilon = 1e-10
+            );
+        }
+        assert!(decomposition
+            .theta
+            .windows(2)
+            .all(|pair| pair[0] <= pair[1] + 1e-12));
+        assert!(decomposition
+            .theta
+            .iter()
+            .all(|theta| (0. ..=FRAC_PI_2).contains(theta)));
+        decomposition
+    }
+
+    #[test]
+    fn test_random_unitaries() {
+        for num_qubits in 1..6 {
+            let mat = random_unitary_inner(1 << num_qubits, &mut generator(num_qubits as u128));
+            check(&mat);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_angles() {
+        // All angles zero, all angles `π/2`, and a mix of both.
+        let eye = Array2::<Complex64>::eye(8);
+        let decomposition = check(&eye);
+        assert!(decomposition.theta.iter().all(|theta| theta.abs() < 1e-12));
+
+        let mut swap = Array2::<Complex64>::zeros((8, 8));
+        swap.slice_mut(s![..4, 4..])
+            .assign(&(-&eye.slice(s![..4, ..4])));
+        swap.slice_mut(s![4.., ..4])
+            .assign(&eye.slice(s![..4, ..4]));
+        let decomposition = check(&swap);
+        assert!(decomposition
+            .theta
+            .iter()
+            .all(|theta| (theta - FRAC_PI_2).abs() < 1e-12));
+
+        let mut rng = generator(5);
+        let half = random_unitary_inner(4, &mut rng);
+        let other = random_unitary_inner(4, &mut rng);
+        let mixed = block_diag(&half, &other).dot(&eye.select(Axis(0), &[0, 5, 2, 7, 4, 1, 6, 3]));
+        check(&mixed);
+    }
+
+    #[test]
+    fn test_bad_shapes() {
+        for shape in [(3, 3), (4, 2), (0, 0)] {
+            let mat = Array2::<Complex64>::zeros(shape);
+            assert!(matches!(
+                cossin_inner(mat.view()),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+}
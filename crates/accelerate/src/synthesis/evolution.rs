@@ -0,0 +1,447 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of the time evolution `exp(-itH)` of a sum of Pauli strings `H` with product
+//! formulas (Lie-Trotter and Suzuki-Trotter).
+//!
+//! Each Pauli string is evolved exactly, as in `qiskit.synthesis.evolution.product_formula`: a
+//! single-qubit string is a rotation, a two-qubit string with equal Paulis is an `RXX`, `RYY` or
+//! `RZZ` gate, and any other string is rotated to a `Z` string, whose parity is collected on its
+//! lowest qubit with a ladder of `CX` gates for an `RZ` rotation.  Unlike the Python
+//! implementation, `ZX` and `XZ` strings use the `CX` ladder, since `RZX` is not a standard gate.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use super::{GateSequence, SynthesisGate};
+use crate::error::AccelerateError;
+
+/// A non-identity single-qubit Pauli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauliAxis {
+    X,
+    Y,
+    Z,
+}
+
+/// A Pauli string, as its non-identity Paulis along with their qubits, in increasing order of the
+/// qubits.
+pub type SparsePauli = Vec<(usize, PauliAxis)>;
+
+/// The arrangement of the `CX` gates that collect the parity of a Pauli string on one qubit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CXStructure {
+    /// Each qubit is the control of a `CX` onto the next lower qubit of the string.
+    Chain,
+    /// Every qubit is the control of a `CX` onto the lowest qubit of the string.
+    Fountain,
+}
+
+impl CXStructure {
+    /// The structure named `name`, which is `"chain"` or `"fountain"`.  As in Python, any other
+    /// name is a fountain.
+    pub fn from_name(name: &str) -> Self {
+        if name == "chain" {
+            CXStructure::Chain
+        } else {
+            CXStructure::Fountain
+        }
+    }
+}
+
+/// Parse a Pauli label without a phase, with qubit 0 the rightmost character.
+pub fn sparse_pauli_from_label(label: &str) -> Result<SparsePauli, AccelerateError> {
+    label
+        .bytes()
+        .rev()
+        .enumerate()
+        .filter(|(_, c)| *c != b'I')
+        .map(|(qubit, c)| match c {
+            b'X' => Ok((qubit, PauliAxis::X)),
+            b'Y' => Ok((qubit, PauliAxis::Y)),
+            b'Z' => Ok((qubit, PauliAxis::Z)),
+            _ => Err(AccelerateError::Value(format!(
+                "Invalid character '{}' in Pauli label '{}'.",
+                c as char, label
+            ))),
+        })
+        .collect()
+}
+
+/// Append the exact evolution `exp(-i time P)` of the Pauli string `pauli`.
+pub fn push_pauli_evolution(
+    circuit: &mut GateSequence,
+    pauli: &[(usize, PauliAxis)],
+    time: f64,
+    cx_structure: CXStructure,
+) {
+    match pauli {
+        [] => circuit.global_phase -= time,
+        [(qubit, axis)] => {
+            let gate = match axis {
+                PauliAxis::X => SynthesisGate::RX,
+                PauliAxis::Y => SynthesisGate::RY,
+                PauliAxis::Z => SynthesisGate::RZ,
+            };
+            circuit.push(gate, &[2. * time], &[*qubit]);
+        }
+        [(q0, a0), (q1, a1)] if a0 == a1 => {
+            let gate = match a0 {
+                PauliAxis::X => SynthesisGate::RXX,
+                PauliAxis::Y => SynthesisGate::RYY,
+                PauliAxis::Z => SynthesisGate::RZZ,
+            };
+            circuit.push(gate, &[2. * time], &[*q0, *q1]);
+        }
+        _ => push_multi_qubit_evolution(circuit, pauli, time, cx_structure),
+    }
+}
+
+/// Append the evolution of a Pauli string by rotating it to a `Z` string, and collecting its
+/// parity on its lowest qubit for an `RZ` rotation.
+fn push_multi_qubit_evolution(
+    circuit: &mut GateSequence,
+    pauli: &[(usize, PauliAxis)],
+    time: f64,
+    cx_structure: CXStructure,
+) {
+    let mut basis_change = GateSequence::new();
+    for &(qubit, axis) in pauli {
+        if axis == PauliAxis::Y {
+            basis_change.push(SynthesisGate::Sdg, &[], &[qubit]);
+        }
+        if axis != PauliAxis::Z {
+            basis_change.push(SynthesisGate::H, &[], &[qubit]);
+        }
+    }
+    let target = pauli[0].0;
+    let mut ladder = GateSequence::new();
+    match cx_structure {
+        CXStructure::Chain => {
+            for pair in pauli.windows(2).rev() {
+                ladder.push(SynthesisGate::CX, &[], &[pair[1].0, pair[0].0]);
+            }
+        }
+        CXStructure::Fountain => {
+            for &(qubit, _) in &pauli[1..] {
+                ladder.push(SynthesisGate::CX, &[], &[qubit, target]);
+            }
+        }
+    }
+    circuit.gates.extend(basis_change.gates.iter().cloned());
+    circuit.gates.extend(ladder.gates.iter().cloned());
+    circuit.push(SynthesisGate::RZ, &[2. * time], &[target]);
+    circuit.gates.extend(ladder.inverse().gates);
+    circuit.gates.extend(basis_change.inverse().gates);
+}
+
+/// The terms of one step of the product formula of the given `order` for the evolution of the
+/// sum of `coeffs.len()` terms for `time`, as the index of each term with its evolution time.
+///
+/// The first-order formula is the Lie-Trotter formula; the others are the Suzuki-Trotter formulas,
+/// built recursively as in `SuzukiTrotter._recurse`.
+fn product_formula_step(order: usize, time: f64, coeffs: &[f64]) -> Vec<(usize, f64)> {
+    match order {
+        1 => coeffs
+            .iter()
+            .enumerate()
+            .map(|(i, coeff)| (i, coeff * time))
+            .collect(),
+        2 => {
+            let Some((last, rest)) = coeffs.split_last() else {
+                return Vec::new();
+            };
+            let halves = rest
+                .iter()
+                .enumerate()
+                .map(|(i, coeff)| (i, coeff * time / 2.))
+                .collect::<Vec<_>>();
+            let mut step = halves.clone();
+            step.push((rest.len(), last * time));
+            step.extend(halves.into_iter().rev());
+            step
+        }
+        _ => {
+            let reduction = 1. / (4. - 4f64.powf(1. / (order - 1) as f64));
+            let outer = product_formula_step(order - 2, reduction * time, coeffs);
+            let inner = product_formula_step(order - 2, (1. - 4. * reduction) * time, coeffs);
+            let mut step = Vec::with_capacity(4 * outer.len() + inner.len());
+            step.extend(outer.iter().copied());
+            step.extend(outer.iter().copied());
+            step.extend(inner);
+            step.extend(outer.iter().copied());
+            step.extend(outer);
+            step
+        }
+    }
+}
+
+/// Synthesize the evolution `exp(-i time H)` of `H = Σ coeff P`, for the `(P, coeff)` in `terms`,
+/// with `reps` steps of the product formula of the given `order`.  The terms are evolved in the
+/// order given, so grouping commuting terms together is up to the caller.
+///
+/// Order 1 is the Lie-Trotter formula, and even orders are the Suzuki-Trotter formulas.
+///
+/// # References
+///
+/// 1. N. Hatano and M. Suzuki, *Finding Exponential Product Formulas of Higher Orders* (2005).
+///    [arXiv:math-ph/0506007](https://arxiv.org/abs/math-ph/0506007)
+pub fn synth_pauli_evolution_inner(
+    terms: &[(SparsePauli, f64)],
+    time: f64,
+    order: usize,
+    reps: usize,
+    cx_structure: CXStructure,
+) -> Result<GateSequence, AccelerateError> {
+    if order == 0 || (order > 1 && order % 2 == 1) {
+        return Err(AccelerateError::Value(
+            "Suzuki product formulae are symmetric and therefore only defined for even orders."
+                .to_string(),
+        ));
+    }
+    let coeffs = terms.iter().map(|(_, coeff)| *coeff).collect::<Vec<_>>();
+    let step = product_formula_step(order, time / reps.max(1) as f64, &coeffs);
+    let mut circuit = GateSequence::new();
+    for _ in 0..reps {
+        for &(index, term_time) in &step {
+            push_pauli_evolution(&mut circuit, &terms[index].0, term_time, cx_structure);
+        }
+    }
+    Ok(circuit)
+}
+
+/// Synthesize the evolution ``exp(-i time H)`` of a sum of Pauli strings with a product formula.
+///
+/// Args:
+///     num_qubits (int): the number of qubits.
+///     terms (list[tuple[str, float]]): the Pauli labels, without phase, and real coefficients of
+///         the terms of ``H``, in the order in which they are evolved.
+///     time (float): the evolution time.
+///     order (int): the order of the product formula: 1 for Lie-Trotter, or an even order for
+///         Suzuki-Trotter.
+///     reps (int): the number of time steps.
+///     cx_structure (str): the arrangement of the ``CX`` gates of each Pauli evolution,
+///         ``"chain"`` or ``"fountain"``.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+///
+/// Raises:
+///     ValueError: if a label is invalid or does not have ``num_qubits`` characters, or if the
+///         order is odd and larger than 1.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_qubits, terms, time, order=1, reps=1, cx_structure="chain"))]
+pub fn synth_pauli_evolution(
+    py: Python,
+    num_qubits: usize,
+    terms: Vec<(String, f64)>,
+    time: f64,
+    order: usize,
+    reps: usize,
+    cx_structure: &str,
+) -> PyResult<(CircuitData, f64)> {
+    let terms = terms
+        .iter()
+        .map(|(label, coeff)| {
+            if label.len() != num_qubits {
+                return Err(AccelerateError::Value(format!(
+                    "The label '{}' does not have {} qubits.",
+                    label, num_qubits
+                )));
+            }
+            Ok((sparse_pauli_from_label(label)?, *coeff))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let circuit = synth_pauli_evolution_inner(
+        &terms,
+        time,
+        order,
+        reps,
+        CXStructure::from_name(cx_structure),
+    )?;
+    circuit.into_circuit(py, num_qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ndarray::linalg::kron;
+    use ndarray::prelude::*;
+    use num_complex::Complex64;
+
+    const ALL_STRUCTURES: [CXStructure; 2] = [CXStructure::Chain, CXStructure::Fountain];
+
+    fn c(re: f64, im: f64) -> Complex64 {
+        Complex64::new(re, im)
+    }
+
+    /// The matrix of the Pauli string `pauli` on `num_qubits` qubits.
+    fn pauli_matrix(pauli: &[(usize, PauliAxis)], num_qubits: usize) -> Array2<Complex64> {
+        let mut out = array![[c(1., 0.)]];
+        for qubit in (0..num_qubits).rev() {
+            let single = match pauli
+                .iter()
+                .find(|(q, _)| *q == qubit)
+                .map(|(_, axis)| axis)
+            {
+                None => array![[c(1., 0.), c(0., 0.)], [c(0., 0.), c(1., 0.)]],
+                Some(PauliAxis::X) => array![[c(0., 0.), c(1., 0.)], [c(1., 0.), c(0., 0.)]],
+                Some(PauliAxis::Y) => array![[c(0., 0.), c(0., -1.)], [c(0., 1.), c(0., 0.)]],
+                Some(PauliAxis::Z) => array![[c(1., 0.), c(0., 0.)], [c(0., 0.), c(-1., 0.)]],
+            };
+            out = kron(&out, &single);
+        }
+        out
+    }
+
+    /// `exp(-i time P)` for the Pauli string `P`, which squares to the identity.
+    fn pauli_exp(pauli: &[(usize, PauliAxis)], num_qubits: usize, time: f64) -> Array2<Complex64> {
+        Array2::<Complex64>::eye(1 << num_qubits) * c(time.cos(), 0.)
+            + pauli_matrix(pauli, num_qubits) * c(0., -time.sin())
+    }
+
+    fn distance(a: &Array2<Complex64>, b: &Array2<Complex64>) -> f64 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y).norm())
+            .fold(0., f64::max)
+    }
+
+    /// Every Pauli string on `num_qubits` qubits.
+    fn all_paulis(num_qubits: usize) -> Vec<SparsePauli> {
+        let axes = [
+            None,
+            Some(PauliAxis::X),
+            Some(PauliAxis::Y),
+            Some(PauliAxis::Z),
+        ];
+        (0..1usize << (2 * num_qubits))
+            .map(|index| {
+                (0..num_qubits)
+                    .filter_map(|qubit| axes[(index >> (2 * qubit)) & 3].map(|axis| (qubit, axis)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sparse_pauli_from_label() {
+        assert_eq!(
+            sparse_pauli_from_label("XIZY").unwrap(),
+            vec![(0, PauliAxis::Y), (1, PauliAxis::Z), (3, PauliAxis::X)]
+        );
+        assert!(sparse_pauli_from_label("III").unwrap().is_empty());
+        assert!(matches!(
+            sparse_pauli_from_label("XA"),
+            Err(AccelerateError::Value(_))
+        ));
+        assert_eq!(CXStructure::from_name("chain"), CXStructure::Chain);
+        assert_eq!(CXStructure::from_name("fountain"), CXStructure::Fountain);
+    }
+
+    #[test]
+    fn test_pauli_evolution_exact() {
+        let num_qubits = 3;
+        for pauli in all_paulis(num_qubits) {
+            for cx_structure in ALL_STRUCTURES {
+                let mut circuit = GateSequence::new();
+                push_pauli_evolution(&mut circuit, &pauli, 0.37, cx_structure);
+                let expected = pauli_exp(&pauli, num_qubits, 0.37);
+                assert!(
+                    distance(&circuit.to_matrix(num_qubits), &expected) < 1e-12,
+                    "{pauli:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_commuting_terms() {
+        // Commuting terms are evolved exactly, whatever the formula.
+        let terms = [
+            (sparse_pauli_from_label("ZZI").unwrap(), 0.5),
+            (sparse_pauli_from_label("IZZ").unwrap(), -0.3),
+            (sparse_pauli_from_label("XXX").unwrap(), 0.8),
+            (sparse_pauli_from_label("III").unwrap(), 0.2),
+        ];
+        let time = 0.9;
+        let expected = terms
+            .iter()
+            .fold(Array2::<Complex64>::eye(8), |out, (pauli, coeff)| {
+                pauli_exp(pauli, 3, coeff * time).dot(&out)
+            });
+        for order in [1, 2, 4] {
+            for reps in [1, 3] {
+                let circuit =
+                    synth_pauli_evolution_inner(&terms, time, order, reps, CXStructure::Chain)
+                        .unwrap();
+                assert!(distance(&circuit.to_matrix(3), &expected) < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_product_formula_convergence() {
+        // `H = a X + b Z` has `exp(-i t H) = cos(t |h|) - i sin(t |h|) H / |h|`.
+        let (a, b, time) = (0.7, 0.4, 1.);
+        let norm = f64::hypot(a, b);
+        let hamiltonian = pauli_matrix(&[(0, PauliAxis::X)], 1) * c(a / norm, 0.)
+            + pauli_matrix(&[(0, PauliAxis::Z)], 1) * c(b / norm, 0.);
+        let expected = Array2::<Complex64>::eye(2) * c((time * norm).cos(), 0.)
+            + hamiltonian * c(0., -(time * norm).sin());
+        let terms = [(vec![(0, PauliAxis::X)], a), (vec![(0, PauliAxis::Z)], b)];
+        let errors = [1, 2, 4]
+            .into_iter()
+            .map(|order| {
+                let circuit =
+                    synth_pauli_evolution_inner(&terms, time, order, 20, CXStructure::Chain)
+                        .unwrap();
+                distance(&circuit.to_matrix(1), &expected)
+            })
+            .collect::<Vec<_>>();
+        // The Lie-Trotter error is about `t^2 |[aX, bZ]| / 2r = 0.014`.
+        assert!(errors[0] < 0.05, "{errors:?}");
+        assert!(errors[1] < errors[0] / 10., "{errors:?}");
+        assert!(errors[2] < errors[1], "{errors:?}");
+    }
+
+    #[test]
+    fn test_product_formula_times() {
+        // Every order evolves each term for its full time over a step.
+        let coeffs = [0.3, -1.2, 0.5];
+        for order in [1, 2, 4, 6] {
+            let step = product_formula_step(order, 2., &coeffs);
+            for (index, coeff) in coeffs.iter().enumerate() {
+                let total = step
+                    .iter()
+                    .filter(|(i, _)| *i == index)
+                    .map(|(_, time)| time)
+                    .sum::<f64>();
+                assert!((total - 2. * coeff).abs() < 1e-12, "order {order}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bad_order() {
+        for order in [0, 3, 5] {
+            assert!(matches!(
+                synth_pauli_evolution_inner(&[], 1., order, 1, CXStructure::Chain),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,1207 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Approximation of `RZ` rotations by Clifford+T circuits with the number-theoretic algorithm of
+//! Ross and Selinger ("gridsynth").
+//!
+//! Every Clifford+T single-qubit unitary of determinant one is of the form
+//!
+//! ```text
+//!     1   [ u  -t† ]
+//!   ----- [        ]
+//!   √2^k  [ t   u† ]
+//! ```
+//!
+//! with `u` and `t` in the ring `Z[ω]`, `ω = exp(iπ/4)`.  For increasing `k`, the algorithm
+//! enumerates the candidates `u` whose scaled value `u / √2^k` is within the operator distance
+//! `ε` of `RZ(θ)`, and whose image under the automorphism `√2 -> -√2` lies in the disk of radius
+//! `√2^k`.  For each candidate, it tries to solve the norm equation `t†t = 2^k - u†u`, and the
+//! first solution is turned into a circuit by exact synthesis.  The T count of the result is
+//! close to `3 log2(1/ε)`.
+//!
+//! The candidates are enumerated in double precision, and checked with exact integer arithmetic,
+//! so the precision is limited to about `1e-10`.  The norm equation is solved in arbitrary
+//! precision, with a bounded effort for the factorization of the norm of its right-hand side;
+//! candidates whose norm cannot be factored are skipped.
+
+use std::f64::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigInt;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use super::{GateSequence, SynthesisGate};
+use crate::error::{AccelerateError, ErrorContext};
+
+/// The smallest supported precision, below which the candidate enumeration is not reliable in
+/// double precision.
+pub const MIN_EPSILON: f64 = 1e-10;
+
+/// The largest denominator exponent `k` that is tried before giving up.
+const MAX_DENOMINATOR_EXPONENT: u32 = 120;
+
+/// The number of steps of each Pollard rho attempt.
+const RHO_STEPS: usize = 1 << 16;
+
+/// The silver ratio `1 + √2`, the fundamental unit of `Z[√2]`.
+const LAMBDA: f64 = 1. + SQRT_2;
+
+/// The integer types the rings are built on.
+trait Integer: Clone + PartialEq + Signed + ToPrimitive + From<i32> {}
+
+impl<T: Clone + PartialEq + Signed + ToPrimitive + From<i32>> Integer for T {}
+
+/// `n / d` rounded to the nearest integer, for `d != 0`.
+fn div_round<T: Integer>(n: &T, d: &T) -> T {
+    let (n, d) = if d.is_negative() {
+        (-n.clone(), -d.clone())
+    } else {
+        (n.clone(), d.clone())
+    };
+    let two = T::from(2);
+    let num = two.clone() * n + d.clone();
+    let den = two * d;
+    let quotient = num.clone() / den.clone();
+    if num.is_negative() && !(num % den).is_zero() {
+        quotient - T::one()
+    } else {
+        quotient
+    }
+}
+
+/// An element `a + b√2` of the ring `Z[√2]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ZRoot2<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Integer> ZRoot2<T> {
+    fn new(a: T, b: T) -> Self {
+        ZRoot2 { a, b }
+    }
+
+    fn from_int(a: T) -> Self {
+        ZRoot2::new(a, T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.a.is_zero() && self.b.is_zero()
+    }
+
+    /// The image `a - b√2` under the automorphism `√2 -> -√2`.
+    fn bullet(&self) -> Self {
+        ZRoot2::new(self.a.clone(), -self.b.clone())
+    }
+
+    /// The norm `a² - 2b²`, which is the product of the element with its `bullet`.
+    fn norm(&self) -> T {
+        self.a.clone() * self.a.clone() - T::from(2) * self.b.clone() * self.b.clone()
+    }
+
+    /// The sign of the real value, computed exactly.
+    fn signum(&self) -> T {
+        if !self.b.is_negative() && !self.a.is_negative() {
+            return if self.is_zero() { T::zero() } else { T::one() };
+        }
+        if !self.b.is_positive() && !self.a.is_positive() {
+            return -T::one();
+        }
+        // `a` and `b` have opposite signs, so the sign is that of the larger of `a` and `b√2`.
+        self.norm().signum() * self.a.signum()
+    }
+
+    /// The real value, accurate even when `a` and `b√2` nearly cancel.
+    fn value(&self) -> f64 {
+        let a = self.a.to_f64().unwrap();
+        let b = self.b.to_f64().unwrap();
+        if self.a.signum() == self.b.signum() || self.a.is_zero() || self.b.is_zero() {
+            a + b * SQRT_2
+        } else {
+            self.norm().to_f64().unwrap() / (a - b * SQRT_2)
+        }
+    }
+
+    /// Whether the element is divisible by `√2`.
+    fn is_divisible_by_sqrt2(&self) -> bool {
+        (self.a.clone() % T::from(2)).is_zero()
+    }
+
+    /// Divide by `√2`, assuming the element is divisible.
+    fn div_sqrt2(&self) -> Self {
+        ZRoot2::new(self.b.clone(), self.a.clone() / T::from(2))
+    }
+
+    /// The quotient by `d`, if `d` divides the element.
+    fn div_exact(&self, d: &Self) -> Option<Self> {
+        let n = d.norm();
+        let num = self.clone() * d.bullet();
+        if (num.a.clone() % n.clone()).is_zero() && (num.b.clone() % n.clone()).is_zero() {
+            Some(ZRoot2::new(num.a / n.clone(), num.b / n))
+        } else {
+            None
+        }
+    }
+
+    /// The quotient by `d`, rounded to a nearby element with a remainder of smaller norm.
+    fn div_nearest(&self, d: &Self) -> Self {
+        let n = d.norm();
+        let num = self.clone() * d.bullet();
+        ZRoot2::new(div_round(&num.a, &n), div_round(&num.b, &n))
+    }
+
+    fn pow(&self, exp: u32) -> Self {
+        (0..exp).fold(ZRoot2::from_int(T::one()), |acc, _| acc * self.clone())
+    }
+
+    /// The power `λ^n` of the silver ratio `λ = 1 + √2`, for any integer `n`.
+    fn lambda_pow(n: i32) -> Self {
+        let base = if n >= 0 {
+            ZRoot2::new(T::one(), T::one())
+        } else {
+            ZRoot2::new(-T::one(), T::one())
+        };
+        base.pow(n.unsigned_abs())
+    }
+}
+
+impl<T: Integer> Add for ZRoot2<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        ZRoot2::new(self.a + other.a, self.b + other.b)
+    }
+}
+
+impl<T: Integer> Sub for ZRoot2<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        ZRoot2::new(self.a - other.a, self.b - other.b)
+    }
+}
+
+impl<T: Integer> Mul for ZRoot2<T> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        ZRoot2::new(
+            self.a.clone() * other.a.clone() + T::from(2) * self.b.clone() * other.b.clone(),
+            self.a * other.b + self.b * other.a,
+        )
+    }
+}
+
+/// The greatest common divisor in the Euclidean domain `Z[√2]`, up to a unit.
+fn gcd_root2<T: Integer>(mut x: ZRoot2<T>, mut y: ZRoot2<T>) -> ZRoot2<T> {
+    while !y.is_zero() {
+        let r = x.clone() - x.div_nearest(&y) * y.clone();
+        x = y;
+        y = r;
+    }
+    x
+}
+
+/// An element `a + bω + cω² + dω³` of the ring `Z[ω]`, with `ω = exp(iπ/4)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ZOmega<T> {
+    c: [T; 4],
+}
+
+impl<T: Integer> ZOmega<T> {
+    fn new(a: T, b: T, c: T, d: T) -> Self {
+        ZOmega { c: [a, b, c, d] }
+    }
+
+    fn zero() -> Self {
+        ZOmega::new(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn one() -> Self {
+        ZOmega::from_root2(ZRoot2::from_int(T::one()))
+    }
+
+    /// The embedding of `x + y√2`, using `√2 = ω - ω³`.
+    fn from_root2(x: ZRoot2<T>) -> Self {
+        ZOmega::new(x.a, x.b.clone(), T::zero(), -x.b)
+    }
+
+    /// The product of `i` and `x + y√2`, using `i = ω²` and `i√2 = ω + ω³`.
+    fn i_times_root2(x: ZRoot2<T>) -> Self {
+        ZOmega::new(T::zero(), x.b.clone(), x.a, x.b)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.c.iter().all(|x| x.is_zero())
+    }
+
+    /// The complex conjugate.
+    fn adj(&self) -> Self {
+        let [a, b, c, d] = self.c.clone();
+        ZOmega::new(a, -d, -c, -b)
+    }
+
+    /// The product with `ω^n`.
+    fn mul_omega_pow(&self, n: i32) -> Self {
+        let mut out = self.clone();
+        for _ in 0..n.rem_euclid(8) {
+            let [a, b, c, d] = out.c;
+            out = ZOmega::new(-d, a, b, c);
+        }
+        out
+    }
+
+    /// The squared magnitude `u†u`, which is in `Z[√2]`.
+    fn norm_sq(&self) -> ZRoot2<T> {
+        let prod = self.adj() * self.clone();
+        ZRoot2::new(prod.c[0].clone(), prod.c[1].clone())
+    }
+
+    /// Whether the element is divisible by `√2`.
+    fn is_divisible_by_sqrt2(&self) -> bool {
+        let two = T::from(2);
+        ((self.c[0].clone() - self.c[2].clone()) % two.clone()).is_zero()
+            && ((self.c[1].clone() - self.c[3].clone()) % two).is_zero()
+    }
+
+    /// Divide by `√2`, assuming the element is divisible.
+    fn div_sqrt2(&self) -> Self {
+        let [a, b, c, d] = self.c.clone();
+        let two = T::from(2);
+        ZOmega::new(
+            (b.clone() - d.clone()) / two.clone(),
+            (a.clone() + c.clone()) / two.clone(),
+            (b + d) / two.clone(),
+            (c - a) / two,
+        )
+    }
+
+    /// The quotient by `d`, rounded to a nearby element with a remainder of smaller norm.
+    fn div_nearest(&self, d: &Self) -> Self {
+        let norm_sq = d.norm_sq();
+        let n = norm_sq.norm();
+        let num = self.clone() * d.adj() * ZOmega::from_root2(norm_sq.bullet());
+        let [a, b, c, d] = num.c;
+        ZOmega::new(
+            div_round(&a, &n),
+            div_round(&b, &n),
+            div_round(&c, &n),
+            div_round(&d, &n),
+        )
+    }
+
+    fn pow(&self, exp: u32) -> Self {
+        (0..exp).fold(ZOmega::one(), |acc, _| acc * self.clone())
+    }
+
+    /// The real and imaginary parts, each accurate even under cancellation.
+    fn complex_parts(&self) -> (f64, f64) {
+        let [a, b, c, d] = self.c.clone();
+        let re = ZRoot2::new(b.clone() - d.clone(), a).value() * FRAC_1_SQRT_2;
+        let im = ZRoot2::new(b + d, c).value() * FRAC_1_SQRT_2;
+        (re, im)
+    }
+}
+
+impl<T: Integer> Add for ZOmega<T> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let [a, b, c, d] = self.c;
+        let [e, f, g, h] = other.c;
+        ZOmega::new(a + e, b + f, c + g, d + h)
+    }
+}
+
+impl<T: Integer> Sub for ZOmega<T> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<T: Integer> Neg for ZOmega<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let [a, b, c, d] = self.c;
+        ZOmega::new(-a, -b, -c, -d)
+    }
+}
+
+impl<T: Integer> Mul for ZOmega<T> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let mut out = [T::zero(), T::zero(), T::zero(), T::zero()];
+        for (i, x) in self.c.iter().enumerate() {
+            for (j, y) in other.c.iter().enumerate() {
+                let term = x.clone() * y.clone();
+                // `ω^4 = -1`.
+                if i + j < 4 {
+                    out[i + j] = out[i + j].clone() + term;
+                } else {
+                    out[i + j - 4] = out[i + j - 4].clone() - term;
+                }
+            }
+        }
+        ZOmega { c: out }
+    }
+}
+
+/// The greatest common divisor in the Euclidean domain `Z[ω]`, up to a unit.
+fn gcd_omega<T: Integer>(mut x: ZOmega<T>, mut y: ZOmega<T>) -> ZOmega<T> {
+    while !y.is_zero() {
+        let r = x.clone() - x.div_nearest(&y) * y.clone();
+        x = y;
+        y = r;
+    }
+    x
+}
+
+fn from_big(x: &ZOmega<BigInt>) -> Option<ZOmega<i128>> {
+    let [a, b, c, d] = &x.c;
+    Some(ZOmega::new(
+        a.to_i128()?,
+        b.to_i128()?,
+        c.to_i128()?,
+        d.to_i128()?,
+    ))
+}
+
+/// Whether `n` is a probable prime, by the Miller-Rabin test with a fixed set of bases.
+fn is_probable_prime(n: &BigInt) -> bool {
+    let small = [2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    for p in small {
+        if *n == BigInt::from(p) {
+            return true;
+        }
+        if (n % p).is_zero() {
+            return false;
+        }
+    }
+    let n_minus_one: BigInt = n - 1;
+    let mut d = n_minus_one.clone();
+    let mut s = 0;
+    while (&d % 2u32).is_zero() {
+        d /= 2u32;
+        s += 1;
+    }
+    'bases: for p in small {
+        let mut x = BigInt::from(p).modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// A non-trivial factor of the odd composite `n` by Pollard's rho method, if one is found within
+/// the step budget.
+fn pollard_rho(n: &BigInt) -> Option<BigInt> {
+    for c in 1u32..4 {
+        let f = |x: &BigInt| (x * x + c) % n;
+        let mut x = BigInt::from(2);
+        let mut y = x.clone();
+        for _ in 0..RHO_STEPS {
+            x = f(&x);
+            y = f(&f(&y));
+            let d = gcd_int(&(&x - &y).abs(), n);
+            if d == *n {
+                break;
+            }
+            if !d.is_one() {
+                return Some(d);
+            }
+        }
+    }
+    None
+}
+
+fn gcd_int(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while !b.is_zero() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The distinct prime factors of `n > 0`, if the factorization succeeds within the effort bound.
+fn prime_factors(n: &BigInt) -> Option<Vec<BigInt>> {
+    let mut n = n.clone();
+    let mut primes = Vec::new();
+    for p in 2u32..1000 {
+        if (&n % p).is_zero() {
+            primes.push(BigInt::from(p));
+            while (&n % p).is_zero() {
+                n /= p;
+            }
+        }
+    }
+    let mut composites = vec![n];
+    while let Some(m) = composites.pop() {
+        if m.is_one() || primes.contains(&m) {
+            continue;
+        }
+        if is_probable_prime(&m) {
+            primes.push(m);
+        } else {
+            let d = pollard_rho(&m)?;
+            composites.push(&m / &d);
+            composites.push(d);
+        }
+    }
+    Some(primes)
+}
+
+/// A square root of `a` modulo the odd prime `p`, by the Tonelli-Shanks algorithm, if `a` is a
+/// quadratic residue.
+fn sqrt_mod(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = ((a % p) + p) % p;
+    let half = (p - 1) / 2;
+    let legendre = |x: &BigInt| x.modpow(&half, p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if !legendre(&a).is_one() {
+        return None;
+    }
+    let mut q: BigInt = p - 1;
+    let mut s = 0u32;
+    while (&q % 2u32).is_zero() {
+        q /= 2u32;
+        s += 1;
+    }
+    let mut z = BigInt::from(2);
+    while legendre(&z).is_one() {
+        z += 1;
+    }
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut r = a.modpow(&((&q + 1) / 2), p);
+    while !t.is_one() {
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while !t2.is_one() {
+            t2 = (&t2 * &t2) % p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigInt::one() << (m - i - 1)), p);
+        m = i;
+        c = (&b * &b) % p;
+        t = (&t * &c) % p;
+        r = (&r * &b) % p;
+    }
+    Some(r)
+}
+
+/// The multiplicity of `d` as a factor of `x`, dividing it out of `x`.
+fn divide_out(x: &mut ZRoot2<BigInt>, d: &ZRoot2<BigInt>) -> u32 {
+    let mut count = 0;
+    while let Some(q) = x.div_exact(d) {
+        *x = q;
+        count += 1;
+    }
+    count
+}
+
+/// Solve the norm equation `t†t = ξ` for a doubly positive `ξ` in `Z[√2]`, if it has a solution
+/// and the norm of `ξ` can be factored.
+fn solve_norm_equation(xi: &ZRoot2<BigInt>) -> Option<ZOmega<BigInt>> {
+    if xi.is_zero() {
+        return Some(ZOmega::zero());
+    }
+    let mut rest = xi.clone();
+    let mut t = ZOmega::one();
+    let i = ZOmega::new(
+        BigInt::zero(),
+        BigInt::zero(),
+        BigInt::one(),
+        BigInt::zero(),
+    );
+    let root_minus_2 = ZOmega::new(BigInt::zero(), BigInt::one(), BigInt::zero(), BigInt::one());
+    for p in prime_factors(&xi.norm().abs())? {
+        if p == BigInt::from(2) {
+            // `√2` is, up to a unit, the norm of `1 + ω`.
+            let one_plus_omega =
+                ZOmega::new(BigInt::one(), BigInt::one(), BigInt::zero(), BigInt::zero());
+            while rest.is_divisible_by_sqrt2() {
+                rest = rest.div_sqrt2();
+                t = t * one_plus_omega.clone();
+            }
+            continue;
+        }
+        let p_root2 = ZRoot2::from_int(p.clone());
+        match (&p % 8u32).to_u32().unwrap() {
+            residue @ (3 | 5) => {
+                // `p` is prime in `Z[√2]` and the product of two conjugate primes of `Z[ω]`.
+                let (square, root) = if residue == 3 {
+                    (-2, root_minus_2.clone())
+                } else {
+                    (-1, i.clone())
+                };
+                let h = sqrt_mod(&BigInt::from(square), &p)?;
+                let factor = gcd_omega(
+                    ZOmega::from_root2(p_root2.clone()),
+                    ZOmega::from_root2(ZRoot2::from_int(h)) + root,
+                );
+                let count = divide_out(&mut rest, &p_root2);
+                t = t * factor.pow(count);
+            }
+            residue => {
+                // `p` splits in `Z[√2]` as the product of `η` and `η•`.
+                let h = sqrt_mod(&BigInt::from(2), &p)?;
+                let eta = gcd_root2(p_root2, ZRoot2::new(h, BigInt::one()));
+                for eta in [eta.bullet(), eta] {
+                    let count = divide_out(&mut rest, &eta);
+                    if residue == 7 {
+                        // `η` stays prime in `Z[ω]`, so it must occur to an even power.
+                        if count % 2 == 1 {
+                            return None;
+                        }
+                        t = t * ZOmega::from_root2(eta).pow(count / 2);
+                    } else if count > 0 {
+                        let h = sqrt_mod(&BigInt::from(-1), &p)?;
+                        let factor = gcd_omega(
+                            ZOmega::from_root2(eta),
+                            ZOmega::from_root2(ZRoot2::from_int(h)) + i.clone(),
+                        );
+                        t = t * factor.pow(count);
+                    }
+                }
+            }
+        }
+    }
+    if rest.norm().abs() != BigInt::one() {
+        return None;
+    }
+    // `t†t` is now `ξ` times a doubly positive unit, which is an even power of `λ`.
+    let unit = t.norm_sq().div_exact(xi)?;
+    let power = (unit.value().ln() / (2. * LAMBDA.ln())).round() as i32;
+    let t = t * ZOmega::from_root2(ZRoot2::lambda_pow(-power));
+    (t.norm_sq() == *xi).then_some(t)
+}
+
+/// The power `√2^k`, correctly rounded.
+fn sqrt2_pow(k: u32) -> f64 {
+    let power = 2f64.powi((k / 2) as i32);
+    if k % 2 == 0 {
+        power
+    } else {
+        power * SQRT_2
+    }
+}
+
+/// All `x` in `Z[√2]` with `x` in `[x0, x1]` and `x•` in `[y0, y1]`, possibly along with a few
+/// elements just outside the intervals.
+fn solve_grid_problem_1d(x0: f64, x1: f64, y0: f64, y1: f64) -> Vec<ZRoot2<i128>> {
+    if x1 < x0 || y1 < y0 {
+        return Vec::new();
+    }
+    // Shift the intervals to be centered near zero by an element `z`.
+    let (cx, cy) = ((x0 + x1) / 2., (y0 + y1) / 2.);
+    let z = ZRoot2::new(
+        ((cx + cy) / 2.).round() as i128,
+        ((cx - cy) / (2. * SQRT_2)).round() as i128,
+    );
+    let (zx, zy) = (z.value(), z.bullet().value());
+    let (x0, x1, y0, y1) = (x0 - zx, x1 - zx, y0 - zy, y1 - zy);
+    // Scale by `λ^n` to balance the widths; this scales the conjugate by `(-λ)^-n`.
+    let width_ratio = (y1 - y0).max(1e-300) / (x1 - x0).max(1e-300);
+    let n = (width_ratio.ln() / (2. * LAMBDA.ln())).round() as i32;
+    let scale = LAMBDA.powi(n);
+    let (x0, x1) = (x0 * scale, x1 * scale);
+    let (y0, y1) = if n % 2 == 0 {
+        (y0 / scale, y1 / scale)
+    } else {
+        (-y1 / scale, -y0 / scale)
+    };
+    let tol = 1e-9 * (1. + x1.abs().max(y1.abs()).max(x0.abs()).max(y0.abs()));
+    let unscale = ZRoot2::<i128>::lambda_pow(-n);
+    let b_min = ((x0 - y1 - tol) / (2. * SQRT_2)).ceil() as i128;
+    let b_max = ((x1 - y0 + tol) / (2. * SQRT_2)).floor() as i128;
+    let mut out = Vec::new();
+    for b in b_min..=b_max {
+        let shift = b as f64 * SQRT_2;
+        let a_min = (x0 - shift).max(y0 + shift) - tol;
+        let a_max = (x1 - shift).min(y1 + shift) + tol;
+        for a in (a_min.ceil() as i128)..=(a_max.floor() as i128) {
+            out.push(ZRoot2::new(a, b) * unscale.clone() + z.clone());
+        }
+    }
+    out
+}
+
+/// Visit the candidates `u` at denominator exponent `k` for the target `z0 = exp(iφ0)`, with
+/// `cos(φ) = 1 - ε²/2`, until `visit` returns a result: those whose scaled value is close to the
+/// segment of the unit disk cut by `Re(w z0*) >= cos(φ)`, and whose conjugate is close to the disk
+/// of radius `√2^k`.
+fn find_candidate<R>(
+    k: u32,
+    phi0: f64,
+    phi: f64,
+    mut visit: impl FnMut(ZOmega<i128>) -> Option<R>,
+) -> Option<R> {
+    let radius = sqrt2_pow(k);
+    let pad = 4. * f64::EPSILON * radius;
+    let (sin0, cos0) = phi0.sin_cos();
+    let chord = radius * phi.cos();
+    // The horizontal extent of the segment is that of its arc.
+    let (lo, hi) = (phi0 - phi, phi0 + phi);
+    let contains = |angle: f64| (angle - lo).rem_euclid(2. * PI) <= hi - lo;
+    let x_min = if contains(PI) {
+        -radius
+    } else {
+        radius * lo.cos().min(hi.cos())
+    };
+    let x_max = if contains(0.) {
+        radius
+    } else {
+        radius * lo.cos().max(hi.cos())
+    };
+    // Every element of `Z[ω]` is `x + iy` or `x + iy + ω`, for `x` and `y` in `Z[√2]`.
+    for offset in [0., FRAC_1_SQRT_2] {
+        let xs = solve_grid_problem_1d(
+            x_min - pad - offset,
+            x_max + pad - offset,
+            -radius + offset,
+            radius + offset,
+        );
+        for x in xs {
+            let re = x.value() + offset;
+            let re_bullet = x.bullet().value() - offset;
+            let bullet_half_width = (radius * radius - re_bullet * re_bullet).sqrt();
+            if bullet_half_width.is_nan() {
+                continue;
+            }
+            let half_width = (radius * radius - re * re).max(0.).sqrt();
+            let (mut y_min, mut y_max) = (-half_width - pad, half_width + pad);
+            if sin0.abs() > f64::EPSILON {
+                let bound = (chord - re * cos0) / sin0;
+                let bound_pad = pad / sin0.abs();
+                if sin0 > 0. {
+                    y_min = y_min.max(bound - bound_pad);
+                } else {
+                    y_max = y_max.min(bound + bound_pad);
+                }
+            } else if re * cos0 < chord - pad {
+                continue;
+            }
+            let ys = solve_grid_problem_1d(
+                y_min - offset,
+                y_max - offset,
+                -bullet_half_width + offset,
+                bullet_half_width + offset,
+            );
+            let base = ZOmega::from_root2(x);
+            for y in ys {
+                let u = base.clone() + ZOmega::i_times_root2(y);
+                let u = if offset == 0. {
+                    u
+                } else {
+                    u + ZOmega::new(0, 1, 0, 0)
+                };
+                if let Some(result) = visit(u) {
+                    return Some(result);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The unitary `[[u, -t†], [t, u†]] / √2^k` within the operator-norm distance `epsilon` of
+/// `RZ(theta)`, if one is found among the candidates at denominator exponent `k`.
+fn approximate_at(k: u32, theta: f64, epsilon: f64) -> Option<ExactUnitary> {
+    // `RZ(θ) = diag(z0, z0*)` with `z0 = exp(-iθ/2)`.
+    let phi0 = -theta / 2.;
+    let (target_re, target_im) = (phi0.cos(), phi0.sin());
+    let phi = 2. * (epsilon / 2.).asin();
+    let scale = 2f64.powi(k as i32);
+    let scale_root = sqrt2_pow(k);
+    // The enumeration is the most efficient for targets away from the axes, so the problem is
+    // rotated by a power of `ω`, which preserves the constraint on the conjugate.
+    let rotation = ((PI / 4. - phi0) / (PI / 4.)).round() as i32;
+    find_candidate(k, phi0 + rotation as f64 * PI / 4., phi, |u| {
+        let u = u.mul_omega_pow(-rotation);
+        if k > 0 && u.is_divisible_by_sqrt2() {
+            // The candidate was already tried for a smaller exponent.
+            return None;
+        }
+        let xi = ZRoot2::from_int(1i128 << k) - u.norm_sq();
+        if xi.signum() < 0 || xi.bullet().signum() < 0 {
+            return None;
+        }
+        // The squared distance to `RZ(θ)` is `|u/√2^k - z0|² + ξ/2^k`.
+        let (re, im) = u.complex_parts();
+        let (d_re, d_im) = (re / scale_root - target_re, im / scale_root - target_im);
+        if d_re * d_re + d_im * d_im + xi.value() / scale > epsilon * epsilon {
+            return None;
+        }
+        let xi = ZRoot2::new(BigInt::from(xi.a), BigInt::from(xi.b));
+        let t = from_big(&solve_norm_equation(&xi)?)?;
+        Some(ExactUnitary {
+            rows: [[u.clone(), -t.adj()], [t, u.adj()]],
+            k,
+        })
+    })
+}
+
+/// A Clifford+T word as a product of the matrices `H` and `T^j`, read from the left.
+#[derive(Default)]
+struct Word {
+    /// The exponents `j` of the `T^j` between consecutive `H`: the word is
+    /// `T^j0 H T^j1 H ... H T^jn`.
+    t_powers: Vec<i32>,
+}
+
+impl Word {
+    fn push_t(&mut self, power: i32) {
+        match self.t_powers.last_mut() {
+            Some(last) => *last = (*last + power).rem_euclid(8),
+            None => self.t_powers.push(power.rem_euclid(8)),
+        }
+    }
+
+    fn push_h(&mut self) {
+        if self.t_powers.is_empty() {
+            self.t_powers.push(0);
+        }
+        // `H H` cancels.
+        if self.t_powers.len() >= 2 && self.t_powers.last() == Some(&0) {
+            self.t_powers.pop();
+        } else {
+            self.t_powers.push(0);
+        }
+    }
+
+    /// The circuit of the word, whose gates are applied in the reverse order of the matrices.
+    fn into_circuit(self, pre_x: bool, global_phase: f64) -> GateSequence {
+        let mut circuit = GateSequence::new();
+        circuit.global_phase = global_phase;
+        if pre_x {
+            circuit.push(SynthesisGate::X, &[], &[0]);
+        }
+        for (index, power) in self.t_powers.iter().rev().enumerate() {
+            if index > 0 {
+                circuit.push(SynthesisGate::H, &[], &[0]);
+            }
+            let gates: &[SynthesisGate] = match power {
+                1 => &[SynthesisGate::T],
+                2 => &[SynthesisGate::S],
+                3 => &[SynthesisGate::S, SynthesisGate::T],
+                4 => &[SynthesisGate::Z],
+                5 => &[SynthesisGate::Z, SynthesisGate::T],
+                6 => &[SynthesisGate::Sdg],
+                7 => &[SynthesisGate::Tdg],
+                _ => &[],
+            };
+            for gate in gates {
+                circuit.push(*gate, &[], &[0]);
+            }
+        }
+        circuit
+    }
+}
+
+/// The matrix `[[u, -t†], [t, u†]] / √2^k` in lowest terms.
+struct ExactUnitary {
+    rows: [[ZOmega<i128>; 2]; 2],
+    k: u32,
+}
+
+impl ExactUnitary {
+    fn reduce(&mut self) {
+        while self.k > 0
+            && self
+                .rows
+                .iter()
+                .flatten()
+                .all(|x| x.is_divisible_by_sqrt2())
+        {
+            for x in self.rows.iter_mut().flatten() {
+                *x = x.div_sqrt2();
+            }
+            self.k -= 1;
+        }
+    }
+
+    /// The unitary `H T^-j U`, in lowest terms.
+    fn reduce_step(&self, j: i32) -> Self {
+        let [r0, r1] = &self.rows;
+        let r1 = [r1[0].mul_omega_pow(-j), r1[1].mul_omega_pow(-j)];
+        let mut out = ExactUnitary {
+            rows: [
+                [r0[0].clone() + r1[0].clone(), r0[1].clone() + r1[1].clone()],
+                [r0[0].clone() - r1[0].clone(), r0[1].clone() - r1[1].clone()],
+            ],
+            k: self.k + 1,
+        };
+        out.reduce();
+        out
+    }
+
+    /// The power `n` with `x = ω^n`, if `x` is a power of `ω`.
+    fn omega_exponent(x: &ZOmega<i128>) -> Option<i32> {
+        (0..8).find(|&n| ZOmega::one().mul_omega_pow(n) == *x)
+    }
+
+    /// The smallest denominator exponent of `|u|² / 2^k` as an element of `Z[√2][1/√2]`, or
+    /// `-1` if `u` is zero.
+    fn sde_abs2(&self) -> i64 {
+        let mut x = self.rows[0][0].norm_sq();
+        if x.is_zero() {
+            return -1;
+        }
+        let mut exponent = 2 * self.k as i64;
+        while x.is_divisible_by_sqrt2() {
+            x = x.div_sqrt2();
+            exponent -= 1;
+        }
+        exponent
+    }
+
+    /// The exponents `j` of the shortest sequence of at most `depth` steps `H T^-j` that
+    /// reduces the unitary to a Clifford+T diagonal or anti-diagonal matrix.
+    fn search(&self, depth: u32) -> Option<Vec<i32>> {
+        if self.k == 0 {
+            return Some(Vec::new());
+        }
+        if depth == 0 {
+            return None;
+        }
+        (0..8).find_map(|j| {
+            let mut path = self.reduce_step(j).search(depth - 1)?;
+            path.insert(0, j);
+            Some(path)
+        })
+    }
+
+    /// Decompose the unitary into a Clifford+T circuit, and the global phase.
+    ///
+    /// Following Kliuchnikov, Maslov and Mosca, the steps `T^j H` are peeled off while one of
+    /// them lowers the denominator exponent of `|u|²`, which always holds while it is at least
+    /// 4.  The few remaining steps are found by search, before matching the remaining Clifford+T
+    /// diagonal or anti-diagonal matrix.
+    fn synthesize(mut self) -> Result<GateSequence, AccelerateError> {
+        let failure = || {
+            AccelerateError::Synthesis(
+                "Exact synthesis of the Clifford+T unitary failed.".into(),
+                ErrorContext::pass("gridsynth_rz"),
+            )
+        };
+        self.reduce();
+        let mut word = Word::default();
+        while self.k > 0 {
+            let sde = self.sde_abs2();
+            let (j, next) = (0..4)
+                .map(|j| (j, self.reduce_step(j)))
+                .min_by_key(|(_, next)| next.sde_abs2())
+                .unwrap();
+            let steps = if next.sde_abs2() < sde {
+                vec![j]
+            } else {
+                self.search(4).ok_or_else(failure)?
+            };
+            for j in steps {
+                word.push_t(j);
+                word.push_h();
+                self = self.reduce_step(j);
+            }
+        }
+        let [r0, r1] = &self.rows;
+        let (pre_x, top, bottom) = if r0[1].is_zero() {
+            (false, &r0[0], &r1[1])
+        } else {
+            (true, &r0[1], &r1[0])
+        };
+        let (Some(a), Some(b)) = (Self::omega_exponent(top), Self::omega_exponent(bottom)) else {
+            return Err(failure());
+        };
+        // `diag(ω^a, ω^b) = ω^a T^(b - a)`.
+        word.push_t(b - a);
+        Ok(word.into_circuit(pre_x, a as f64 * PI / 4.))
+    }
+}
+
+/// Approximate `RZ(theta)` by a Clifford+T circuit, whose matrix with the global phase is within
+/// the operator-norm distance `epsilon` of the rotation, with the Ross-Selinger algorithm.  The
+/// circuit consists of `H`, `T`, `Tdg`, `S`, `Sdg`, `Z` and `X` gates.
+///
+/// # References
+///
+/// 1. N. J. Ross and P. Selinger, *Optimal ancilla-free Clifford+T approximation of
+///    z-rotations*, Quantum Information and Computation 16 (2016).
+///    [arXiv:1403.2975](https://arxiv.org/abs/1403.2975)
+pub fn gridsynth_rz_inner(theta: f64, epsilon: f64) -> Result<GateSequence, AccelerateError> {
+    if !theta.is_finite() {
+        return Err(AccelerateError::Value(format!(
+            "The angle {} is not finite.",
+            theta
+        )));
+    }
+    if !(MIN_EPSILON..1.).contains(&epsilon) {
+        return Err(AccelerateError::Value(format!(
+            "The precision {} is not in the supported range [{}, 1).",
+            epsilon, MIN_EPSILON
+        )));
+    }
+    // Approximations of determinant one of `RZ(θ)` are hard to find when `θ` is close to an odd
+    // multiple of `π/4`, so `RZ(θ - π/4) T`, which is equal to `RZ(θ)` up to the phase
+    // `exp(iπ/8)`, is also tried.
+    for k in 0..=MAX_DENOMINATOR_EXPONENT {
+        if let Some(unitary) = approximate_at(k, theta, epsilon) {
+            return unitary.synthesize();
+        }
+        if let Some(mut unitary) = approximate_at(k, theta - PI / 4., epsilon) {
+            for row in unitary.rows.iter_mut() {
+                row[1] = row[1].mul_omega_pow(1);
+            }
+            let mut circuit = unitary.synthesize()?;
+            circuit.global_phase -= PI / 8.;
+            return Ok(circuit);
+        }
+    }
+    Err(AccelerateError::Synthesis(
+        format!(
+            "No Clifford+T approximation of RZ({}) within {} was found.",
+            theta, epsilon
+        ),
+        ErrorContext::pass("gridsynth_rz").with_tolerance(epsilon),
+    ))
+}
+
+/// Approximate an ``RZ`` rotation by a Clifford+T circuit with the Ross-Selinger algorithm.
+///
+/// Args:
+///     theta (float): the rotation angle.
+///     epsilon (float): the largest operator-norm distance of the circuit, with its global phase,
+///         to the rotation.  It must be at least ``1e-10``.
+///
+/// Returns:
+///     (CircuitData, float): the single-qubit circuit of ``H``, ``T``, ``Tdg``, ``S``, ``Sdg``,
+///     ``Z`` and ``X`` gates, and its global phase.
+///
+/// Raises:
+///     ValueError: if the angle is not finite or the precision is out of range.
+///     QiskitError: if no approximation is found.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (theta, epsilon=1e-10))]
+pub fn gridsynth_rz(py: Python, theta: f64, epsilon: f64) -> PyResult<(CircuitData, f64)> {
+    gridsynth_rz_inner(theta, epsilon)?.into_circuit(py, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use num_complex::Complex64;
+
+    fn omega_value(x: &ZOmega<i128>) -> Complex64 {
+        let omega = Complex64::from_polar(1., PI / 4.);
+        x.c.iter()
+            .enumerate()
+            .map(|(i, c)| omega.powi(i as i32) * *c as f64)
+            .sum()
+    }
+
+    fn big(x: i64) -> BigInt {
+        BigInt::from(x)
+    }
+
+    /// The operator-norm distance between the circuit and `RZ(theta)`.
+    fn rz_distance(circuit: &GateSequence, theta: f64) -> f64 {
+        let out = circuit.to_matrix(1);
+        let z0 = Complex64::from_polar(1., -theta / 2.);
+        let d = [
+            [out[[0, 0]] - z0, out[[0, 1]]],
+            [out[[1, 0]], out[[1, 1]] - z0.conj()],
+        ];
+        // The largest eigenvalue of `D†D` from its trace and determinant.
+        let trace = d.iter().flatten().map(|x| x.norm_sqr()).sum::<f64>();
+        let det = (d[0][0] * d[1][1] - d[0][1] * d[1][0]).norm_sqr();
+        ((trace + (trace * trace - 4. * det).max(0.).sqrt()) / 2.).sqrt()
+    }
+
+    fn t_count(circuit: &GateSequence) -> usize {
+        circuit
+            .gates
+            .iter()
+            .filter(|(gate, _, _)| matches!(gate, SynthesisGate::T | SynthesisGate::Tdg))
+            .count()
+    }
+
+    #[test]
+    fn test_root2_arithmetic() {
+        let x = ZRoot2::new(3i128, -2);
+        let y = ZRoot2::new(1i128, 1);
+        assert_eq!(x.norm(), 1);
+        assert!((x.value() - (3. - 2. * SQRT_2)).abs() < 1e-12);
+        assert_eq!(x.signum(), 1);
+        assert_eq!(ZRoot2::new(1i128, -1).signum(), -1);
+        assert_eq!((x.clone() * y.clone()).div_exact(&y), Some(x.clone()));
+        assert_eq!(ZRoot2::new(3i128, 0).div_exact(&ZRoot2::new(2, 0)), None);
+        assert_eq!(
+            ZRoot2::<i128>::lambda_pow(3) * ZRoot2::lambda_pow(-3),
+            ZRoot2::from_int(1)
+        );
+        assert_eq!(div_round(&7i128, &2), 4);
+        assert_eq!(div_round(&-7i128, &2), -3);
+        assert_eq!(div_round(&-5i128, &-3), 2);
+    }
+
+    #[test]
+    fn test_omega_arithmetic() {
+        let x = ZOmega::new(1i128, -2, 3, 5);
+        let y = ZOmega::new(-4i128, 0, 1, 2);
+        let (vx, vy) = (omega_value(&x), omega_value(&y));
+        assert!((omega_value(&(x.clone() * y.clone())) - vx * vy).norm() < 1e-10);
+        assert!((omega_value(&x.adj()) - vx.conj()).norm() < 1e-12);
+        assert!((x.norm_sq().value() - vx.norm_sqr()).abs() < 1e-10);
+        let (re, im) = x.complex_parts();
+        assert!((Complex64::new(re, im) - vx).norm() < 1e-12);
+        let omega = Complex64::from_polar(1., PI / 4.);
+        assert!((omega_value(&x.mul_omega_pow(-3)) - vx * omega.powi(-3)).norm() < 1e-10);
+        let root2 = ZOmega::from_root2(ZRoot2::new(0i128, 1));
+        let product = x.clone() * root2;
+        assert!(product.is_divisible_by_sqrt2());
+        assert_eq!(product.div_sqrt2(), x);
+        // The greatest common divisor is `y` up to a unit, whose absolute norm is one.
+        let gcd = gcd_omega(y.clone(), x * y.clone());
+        assert_eq!(gcd.norm_sq().norm().abs(), y.norm_sq().norm().abs());
+    }
+
+    #[test]
+    fn test_factorization() {
+        for p in [2, 3, 1009, 1_000_003, 2_147_483_647] {
+            assert!(is_probable_prime(&big(p)), "{p}");
+        }
+        for n in [1, 9, 561, 1_000_001, 2_147_483_649] {
+            assert!(!is_probable_prime(&big(n)), "{n}");
+        }
+        let mut factors = prime_factors(&big(2 * 2 * 3 * 1_000_003 * 1_000_033)).unwrap();
+        factors.sort();
+        assert_eq!(factors, [big(2), big(3), big(1_000_003), big(1_000_033)]);
+        for (a, p) in [(2, 7), (-1, 13), (-2, 11), (5, 1_000_003)] {
+            match sqrt_mod(&big(a), &big(p)) {
+                Some(root) => assert_eq!((&root * &root - big(a)) % big(p), big(0)),
+                None => assert!(!big(a).modpow(&big((p - 1) / 2), &big(p)).is_one()),
+            }
+        }
+        assert_eq!(sqrt_mod(&big(3), &big(7)), None);
+    }
+
+    #[test]
+    fn test_norm_equation() {
+        // `ξ` with a solution, and `7 = (3 + √2)(3 - √2)`, whose factors stay prime in `Z[ω]`.
+        for (a, b) in [
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (5, 0),
+            (2, 1),
+            (13, 4),
+            (1_000_003, 0),
+        ] {
+            let xi = ZRoot2::new(big(a), big(b));
+            let t = solve_norm_equation(&xi).unwrap();
+            assert_eq!(t.norm_sq(), xi);
+        }
+        assert!(solve_norm_equation(&ZRoot2::new(big(7), big(0))).is_none());
+    }
+
+    #[test]
+    fn test_grid_problem() {
+        let (x0, x1, y0, y1) = (-3.2, 4.5, 1.5, 2.7);
+        let found = solve_grid_problem_1d(x0, x1, y0, y1);
+        for a in -20i128..20 {
+            for b in -20i128..20 {
+                let z = ZRoot2::new(a, b);
+                let inside =
+                    (x0..=x1).contains(&z.value()) && (y0..=y1).contains(&z.bullet().value());
+                if inside {
+                    assert!(found.contains(&z), "{z:?}");
+                }
+            }
+        }
+        assert!(solve_grid_problem_1d(1., 0., 0., 1.).is_empty());
+    }
+
+    #[test]
+    fn test_gridsynth_rz() {
+        for theta in [0.1, -0.7, 1.3, 2.9, PI / 8., 5.5] {
+            for epsilon in [1e-2, 1e-4, 1e-6] {
+                let circuit = gridsynth_rz_inner(theta, epsilon).unwrap();
+                assert!(circuit.gates.iter().all(|(gate, _, qubits)| {
+                    qubits.as_slice() == [0]
+                        && matches!(
+                            gate,
+                            SynthesisGate::H
+                                | SynthesisGate::T
+                                | SynthesisGate::Tdg
+                                | SynthesisGate::S
+                                | SynthesisGate::Sdg
+                                | SynthesisGate::Z
+                                | SynthesisGate::X
+                        )
+                }));
+                assert!(rz_distance(&circuit, theta) <= epsilon, "{theta} {epsilon}");
+                // The T count is close to `3 log2(1/ε)`.
+                assert!(
+                    (t_count(&circuit) as f64) < 4. * epsilon.recip().log2() + 10.,
+                    "{theta} {epsilon}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gridsynth_clifford_angles() {
+        for k in -4..=4 {
+            let theta = k as f64 * PI / 2.;
+            let circuit = gridsynth_rz_inner(theta, 1e-8).unwrap();
+            assert_eq!(t_count(&circuit), 0, "{theta}");
+            assert!(rz_distance(&circuit, theta) < 1e-12);
+        }
+        let circuit = gridsynth_rz_inner(PI / 4., 1e-8).unwrap();
+        assert_eq!(t_count(&circuit), 1);
+        assert!(rz_distance(&circuit, PI / 4.) < 1e-12);
+    }
+
+    #[test]
+    fn test_bad_inputs() {
+        for (theta, epsilon) in [
+            (f64::NAN, 1e-3),
+            (f64::INFINITY, 1e-3),
+            (0.5, 1e-11),
+            (0.5, 1.),
+        ] {
+            assert!(matches!(
+                gridsynth_rz_inner(theta, epsilon),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,294 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Column-by-column decomposition of isometries from `m` to `n` qubits (Iten et al., Phys. Rev. A
+//! 93, 032318).  This is a port of the decomposition of `qiskit.circuit.library.Isometry`: each
+//! column is mapped to a basis state by disentangling one qubit at a time with a multi-controlled
+//! gate and a uniformly controlled gate, both implemented up to diagonals that are tracked in the
+//! remaining isometry.  The resulting circuit is then inverted.
+//!
+//! As in the Python code, qubits are labelled with decreasing significance: label `l` is the qubit
+//! `n - 1 - l` of the circuit.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use qiskit_circuit::gate_matrix::{rz_gate, H_GATE};
+use std::f64::consts::PI;
+
+use super::{GateSequence, SynthesisGate};
+use crate::error::AccelerateError;
+use crate::isometry::{
+    a, apply_diagonal_gate_inner, apply_diagonal_gate_to_diag_inner,
+    apply_multi_controlled_gate_inner, apply_ucg_inner, b,
+    diag_is_identity_up_to_global_phase_inner, find_squs_for_disentangling_inner, k_s,
+    merge_ucgate_and_diag_inner, reverse_qubit_state_inner,
+    ucg_is_identity_up_to_global_phase_inner,
+};
+use crate::uc_gate::dec_ucg_help_inner;
+
+/// The default error tolerance of `Isometry`.
+pub const ISOMETRY_EPS: f64 = 1e-10;
+
+/// Append the uniformly controlled gate with `single_qubit_gates` on `target`, multiplexed by
+/// `controls` (the first one being the least significant), implemented up to a diagonal.  Returns
+/// the diagonal, in the basis of the controls in decreasing significance followed by the target.
+fn append_ucg_up_to_diagonal(
+    circuit: &mut GateSequence,
+    single_qubit_gates: Vec<Array2<Complex64>>,
+    target: usize,
+    controls: &[usize],
+) -> Array1<Complex64> {
+    if controls.is_empty() {
+        circuit.push_one_qubit_unitary(single_qubit_gates[0].view(), target);
+        return Array1::ones(2);
+    }
+    let (single_qubit_gates, diag) =
+        dec_ucg_help_inner(single_qubit_gates, controls.len() as u32 + 1);
+    let hadamard = aview2(&H_GATE);
+    // The matrix of `UCGate._rz(pi / 2)`, which is `RZ(-pi / 2)`.
+    let rz_pi2 = rz_gate(-PI / 2.);
+    let rz = aview2(&rz_pi2);
+    let last = single_qubit_gates.len() - 1;
+    for (i, gate) in single_qubit_gates.iter().enumerate() {
+        // Absorb the Hadamards and the `RZ(pi / 2)` rotations into the single-qubit gates.
+        let squ = if i == 0 {
+            hadamard.dot(gate)
+        } else if i == last {
+            gate.dot(&rz).dot(&hadamard)
+        } else {
+            hadamard.dot(&gate.dot(&rz)).dot(&hadamard)
+        };
+        circuit.push_one_qubit_unitary(squ.view(), target);
+        if i != last {
+            let control = controls[(i + 1).trailing_zeros() as usize];
+            circuit.push(SynthesisGate::CX, &[], &[control, target]);
+            circuit.global_phase -= 0.25 * PI;
+        }
+    }
+    diag
+}
+
+struct IsometryDecomposer {
+    num_qubits: usize,
+    epsilon: f64,
+    circuit: GateSequence,
+    diag: Vec<Complex64>,
+}
+
+impl IsometryDecomposer {
+    #[inline]
+    fn qubit(&self, label: usize) -> usize {
+        self.num_qubits - 1 - label
+    }
+
+    /// Disentangle the `s`-th significant qubit of the first column of `v`, which is the column
+    /// `k` of the isometry, into the zero or the one state (depending on `k`).
+    fn disentangle(&mut self, mut v: ArrayViewMut2<Complex64>, k: usize, s: usize) {
+        let n = self.num_qubits;
+        let target_label = n - s - 1;
+        let target = self.qubit(target_label);
+
+        // A multi-controlled gate sets one entry to zero, to prepare for the disentangling.
+        let index1 = 2 * a(k, s + 1) * 2_usize.pow(s as u32) + b(k, s + 1);
+        let index2 = (2 * a(k, s + 1) + 1) * 2_usize.pow(s as u32) + b(k, s + 1);
+        if k_s(k, s) == 0 && b(k, s + 1) != 0 && v[[index2, 0]].norm() > self.epsilon {
+            let gate =
+                reverse_qubit_state_inner(&[v[[index1, 0]], v[[index2, 0]]], 0, self.epsilon);
+            let control_labels = (0..n)
+                .filter(|label| *label != target_label && (k >> (n - 1 - label)) & 1 == 1)
+                .collect::<Vec<_>>();
+            let controls = control_labels
+                .iter()
+                .rev()
+                .map(|label| self.qubit(*label))
+                .collect::<Vec<_>>();
+            let mut gates = vec![Array2::eye(2); 1 << controls.len()];
+            gates[(1 << controls.len()) - 1] = gate.clone();
+            let diagonal = append_ucg_up_to_diagonal(&mut self.circuit, gates, target, &controls);
+            apply_multi_controlled_gate_inner(
+                v.view_mut(),
+                &control_labels,
+                target_label,
+                gate.view(),
+            );
+            // Correct for the implementation up to a diagonal.
+            let diagonal_inverse = diagonal.iter().map(|x| x.conj()).collect::<Vec<_>>();
+            let mut action_labels = control_labels;
+            action_labels.push(target_label);
+            apply_diagonal_gate_inner(v.view_mut(), &action_labels, &diagonal_inverse);
+            apply_diagonal_gate_to_diag_inner(&mut self.diag, &action_labels, &diagonal_inverse, n);
+        }
+
+        // A uniformly controlled gate disentangles the qubit.
+        let single_qubit_gates = find_squs_for_disentangling_inner(v.view(), k, s, self.epsilon, n);
+        let views = single_qubit_gates
+            .iter()
+            .map(|x| x.view())
+            .collect::<Vec<_>>();
+        if ucg_is_identity_up_to_global_phase_inner(&views, self.epsilon) {
+            return;
+        }
+        let controls = (target + 1..n).collect::<Vec<_>>();
+        let diagonal = append_ucg_up_to_diagonal(
+            &mut self.circuit,
+            single_qubit_gates.clone(),
+            target,
+            &controls,
+        );
+        // Merge the inverse of the diagonal into the gate, and apply both to the isometry.
+        let diagonal_inverse = diagonal.iter().map(|x| x.conj()).collect::<Vec<_>>();
+        let merged = merge_ucgate_and_diag_inner(&views, &diagonal_inverse);
+        let merged = merged.iter().map(|x| x.view()).collect::<Vec<_>>();
+        apply_ucg_inner(v.view_mut(), target_label, &merged);
+        let action_labels = (0..=target_label).collect::<Vec<_>>();
+        apply_diagonal_gate_to_diag_inner(&mut self.diag, &action_labels, &diagonal_inverse, n);
+    }
+}
+
+/// Synthesize the isometry `iso`, a `2^n x 2^m` matrix with orthonormal columns, as a circuit on
+/// `n` qubits whose first `m` qubits are the input.
+///
+/// The output uses `U`, `RZ` and `CX` gates.  `epsilon` is the error tolerance of the numerical
+/// steps; see [ISOMETRY_EPS].
+pub fn isometry_inner(
+    iso: ArrayView2<Complex64>,
+    epsilon: f64,
+) -> Result<GateSequence, AccelerateError> {
+    let (rows, cols) = iso.dim();
+    if !rows.is_power_of_two() {
+        return Err(AccelerateError::Qiskit(
+            "The number of rows of the isometry is not a non negative power of 2.".to_string(),
+        ));
+    }
+    if !cols.is_power_of_two() {
+        return Err(AccelerateError::Qiskit(
+            "The number of columns of the isometry is not a non negative power of 2.".to_string(),
+        ));
+    }
+    if cols > rows {
+        return Err(AccelerateError::Qiskit(
+            "The input matrix has more columns than rows and hence it can't be an isometry."
+                .to_string(),
+        ));
+    }
+    let num_qubits = rows.trailing_zeros() as usize;
+    let num_input = cols.trailing_zeros() as usize;
+    let mut decomposer = IsometryDecomposer {
+        num_qubits,
+        epsilon,
+        circuit: GateSequence::new(),
+        diag: Vec::with_capacity(cols),
+    };
+    // Map the isometry to the first columns of the identity, up to a diagonal.  The columns that
+    // have been handled are only remembered through their phases in `diag`.
+    let mut remaining = iso.to_owned();
+    for column in 0..cols {
+        for s in 0..num_qubits {
+            decomposer.disentangle(remaining.slice_mut(s![.., column..]), column, s);
+        }
+        let phase = remaining[[column, column]];
+        decomposer.diag.push(phase);
+    }
+    let IsometryDecomposer {
+        mut circuit, diag, ..
+    } = decomposer;
+    if diag.len() > 1 && !diag_is_identity_up_to_global_phase_inner(&diag, epsilon) {
+        let diag = diag.iter().map(|x| x.conj()).collect::<Vec<_>>();
+        circuit.push_diagonal(&diag, &(0..num_input).collect::<Vec<_>>());
+    }
+    Ok(circuit.inverse())
+}
+
+/// Synthesize an isometry as in the definition of :class:`.Isometry`.
+///
+/// Args:
+///     isometry (ndarray): a ``2**n x 2**m`` complex matrix with orthonormal columns.
+///     num_ancillas (int): the number of additional qubits in the circuit, which the
+///         decomposition does not act on.
+///     epsilon (float): the error tolerance of the calculations.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (isometry, num_ancillas=0, epsilon=ISOMETRY_EPS))]
+pub fn synth_isometry(
+    py: Python,
+    isometry: PyReadonlyArray2<Complex64>,
+    num_ancillas: usize,
+    epsilon: f64,
+) -> PyResult<(CircuitData, f64)> {
+    let isometry = isometry.as_array();
+    let num_qubits = isometry.nrows().trailing_zeros() as usize + num_ancillas;
+    let sequence = isometry_inner(isometry, epsilon)?;
+    sequence.into_circuit(py, num_qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+    use crate::synthesis::test::assert_implements;
+
+    #[test]
+    fn test_random_isometries() {
+        for (num_qubits, num_input) in [(1, 0), (1, 1), (2, 0), (2, 1), (3, 1), (3, 2), (4, 2)] {
+            let unitary = random_unitary_inner(1 << num_qubits, &mut generator(num_qubits as u128));
+            let iso = unitary.slice(s![.., ..1 << num_input]);
+            let sequence = isometry_inner(iso, ISOMETRY_EPS).unwrap();
+            assert_implements(&sequence, iso, num_qubits);
+            assert!(sequence.gates.iter().all(|(gate, _, _)| matches!(
+                gate,
+                SynthesisGate::U | SynthesisGate::RZ | SynthesisGate::CX
+            )));
+        }
+    }
+
+    #[test]
+    fn test_unitaries() {
+        for num_qubits in 1..4 {
+            let unitary = random_unitary_inner(1 << num_qubits, &mut generator(7));
+            let sequence = isometry_inner(unitary.view(), ISOMETRY_EPS).unwrap();
+            assert_implements(&sequence, unitary.view(), num_qubits);
+        }
+    }
+
+    #[test]
+    fn test_permutation() {
+        let perm = [5, 2, 7, 0, 3, 6, 1, 4];
+        let iso = Array2::from_shape_fn((8, 8), |(i, j)| {
+            Complex64::new(if perm[j] == i { 1. } else { 0. }, 0.)
+        });
+        let sequence = isometry_inner(iso.view(), ISOMETRY_EPS).unwrap();
+        assert_implements(&sequence, iso.view(), 3);
+    }
+
+    #[test]
+    fn test_bad_shapes() {
+        for shape in [(3, 1), (4, 3), (2, 4)] {
+            let iso = Array2::<Complex64>::zeros(shape);
+            assert!(matches!(
+                isometry_inner(iso.view(), ISOMETRY_EPS),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,724 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of linear reversible (`CX`-only) circuits from invertible binary matrices, and the
+//! binary matrix utilities they rely on.
+//!
+//! A circuit of `CX` gates acts on the computational basis as an invertible matrix over GF(2).
+//! A `CX` with control `c` and target `t` adds row `c` of the matrix to row `t`.  Both synthesis
+//! methods are gate-for-gate ports of `qiskit.synthesis.synth_cnot_count_full_pmh` and
+//! `synth_cnot_depth_line_kms`.
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2, PyReadwriteArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+
+use hashbrown::HashMap;
+use ndarray::prelude::*;
+
+use crate::error::AccelerateError;
+
+/// A sequence of `CX` gates, as `(control, target)` pairs.
+pub type CXSequence = Vec<(usize, usize)>;
+
+/// Add row `ctrl` of `mat` to row `trgt`.
+#[inline]
+pub(crate) fn row_op(mat: &mut ArrayViewMut2<bool>, ctrl: usize, trgt: usize) {
+    let (ctrl_row, mut trgt_row) = mat.multi_slice_mut((s![ctrl, ..], s![trgt, ..]));
+    trgt_row.zip_mut_with(&ctrl_row, |x, y| *x ^= *y);
+}
+
+/// Add column `trgt` of `mat` to column `ctrl`.  The argument order matches `_col_op` in Python.
+#[inline]
+fn col_op(mat: &mut ArrayViewMut2<bool>, ctrl: usize, trgt: usize) {
+    let (mut ctrl_col, trgt_col) = mat.multi_slice_mut((s![.., ctrl], s![.., trgt]));
+    ctrl_col.zip_mut_with(&trgt_col, |x, y| *x ^= *y);
+}
+
+/// Gaussian elimination of the binary matrix `mat` in place, restricted to its first `ncols`
+/// columns if given.  With `full_elim`, the entries above the pivots are also eliminated.
+///
+/// Returns the permutation of the rows that was done along the way; its first `rank` entries are
+/// the indices of linearly independent rows of the original matrix.
+pub fn gauss_elimination_with_perm(
+    mut mat: ArrayViewMut2<bool>,
+    ncols: Option<usize>,
+    full_elim: bool,
+) -> Vec<usize> {
+    let (m, mut n) = mat.dim();
+    if let Some(ncols) = ncols {
+        n = n.min(ncols);
+    }
+    let mut perm = (0..m).collect::<Vec<_>>();
+    let mut r = 0; // current rank
+    let mut k = 0; // current pivot column
+    while r < m && k < n {
+        let Some((new_k, new_r)) =
+            (k..n).find_map(|j| (r..m).find(|i| mat[[*i, j]]).map(|i| (j, i)))
+        else {
+            // The matrix is in its canonical form.
+            return perm;
+        };
+        k = new_k;
+        if new_r != r {
+            for j in 0..mat.ncols() {
+                mat.swap([r, j], [new_r, j]);
+            }
+            perm.swap(r, new_r);
+        }
+        let rows = if full_elim { 0..m } else { r + 1..m };
+        for i in rows {
+            if i != r && mat[[i, k]] {
+                row_op(&mut mat, r, i);
+            }
+        }
+        r += 1;
+    }
+    perm
+}
+
+/// The rank of a binary matrix that is already in its Gaussian-eliminated form, which is its
+/// number of non-zero rows.
+pub fn compute_rank_after_gauss_elim(mat: ArrayView2<bool>) -> usize {
+    mat.rows()
+        .into_iter()
+        .filter(|row| row.iter().any(|x| *x))
+        .count()
+}
+
+/// The rank of the binary matrix `mat` over GF(2).
+pub fn compute_rank(mat: ArrayView2<bool>) -> usize {
+    let mut mat = mat.to_owned();
+    gauss_elimination_with_perm(mat.view_mut(), None, false);
+    compute_rank_after_gauss_elim(mat.view())
+}
+
+/// Whether `mat` is a square binary matrix that is invertible over GF(2).
+pub fn check_invertible_binary_matrix_inner(mat: ArrayView2<bool>) -> bool {
+    mat.nrows() == mat.ncols() && compute_rank(mat) == mat.nrows()
+}
+
+/// The product of two binary matrices over GF(2).
+pub fn binary_matmul(left: ArrayView2<bool>, right: ArrayView2<bool>) -> Array2<bool> {
+    Array2::from_shape_fn((left.nrows(), right.ncols()), |(i, j)| {
+        left.row(i)
+            .iter()
+            .zip(right.column(j))
+            .fold(false, |acc, (x, y)| acc ^ (*x & *y))
+    })
+}
+
+/// The inverse of the square binary matrix `mat` over GF(2).  With `verify`, the product of the
+/// matrix and its inverse is checked to be the identity.
+pub fn calc_inverse_matrix_inner(
+    mat: ArrayView2<bool>,
+    verify: bool,
+) -> Result<Array2<bool>, AccelerateError> {
+    let n = mat.nrows();
+    if n != mat.ncols() {
+        return Err(AccelerateError::Qiskit(
+            "Matrix to invert is a non-square matrix.".to_string(),
+        ));
+    }
+    // Eliminate the matrix augmented by the identity.
+    let mut augmented = Array2::from_elem((n, 2 * n), false);
+    augmented.slice_mut(s![.., ..n]).assign(&mat);
+    for i in 0..n {
+        augmented[[i, n + i]] = true;
+    }
+    gauss_elimination_with_perm(augmented.view_mut(), None, true);
+    if compute_rank_after_gauss_elim(augmented.slice(s![.., ..n])) < n {
+        return Err(AccelerateError::Qiskit(
+            "The matrix is not invertible.".to_string(),
+        ));
+    }
+    let inverse = augmented.slice(s![.., n..]).to_owned();
+    if verify
+        && binary_matmul(mat, inverse.view()) != Array2::from_shape_fn((n, n), |(i, j)| i == j)
+    {
+        return Err(AccelerateError::Qiskit(
+            "The computed inverse is not the inverse of the matrix.".to_string(),
+        ));
+    }
+    Ok(inverse)
+}
+
+/// Reduce `state` to an upper-triangular matrix with row operations, in sections of
+/// `section_size` columns, as in `_lwr_cnot_synth`.  Returns the row operations done.  The
+/// section size must be positive.
+///
+/// Within a section, repeated sub-rows are first cleared against their first occurrence, which
+/// leaves fewer entries to clear with the Gaussian elimination that follows.  Rows below the pivot
+/// that overlap with it in more than one entry are also added back to the pivot row, to keep its
+/// weight low.
+fn lwr_cnot_synth(mut state: ArrayViewMut2<bool>, section_size: usize) -> CXSequence {
+    const CUTOFF: usize = 1;
+    let num_qubits = state.nrows();
+    let mut circuit = CXSequence::new();
+    // The last section may be narrower than the others.
+    for start in (0..num_qubits).step_by(section_size) {
+        let columns = start..(start + section_size).min(num_qubits);
+        // Remove the duplicate sub-rows in the section.
+        let mut patterns: HashMap<Vec<bool>, usize> = HashMap::new();
+        for row in start..num_qubits {
+            let sub_row = state.slice(s![row, columns.clone()]).to_vec();
+            if !sub_row.iter().any(|x| *x) {
+                continue;
+            }
+            match patterns.get(&sub_row) {
+                Some(&first) => {
+                    row_op(&mut state, first, row);
+                    circuit.push((first, row));
+                }
+                None => {
+                    patterns.insert(sub_row, row);
+                }
+            }
+        }
+        // Use Gaussian elimination for the remaining entries in the section.
+        for col in columns {
+            let mut diag_one = state[[col, col]];
+            for row in col + 1..num_qubits {
+                if state[[row, col]] {
+                    if !diag_one {
+                        row_op(&mut state, row, col);
+                        circuit.push((row, col));
+                        diag_one = true;
+                    }
+                    row_op(&mut state, col, row);
+                    circuit.push((col, row));
+                }
+                // Back reduce the pivot row with the current row.
+                let overlap = state
+                    .row(col)
+                    .iter()
+                    .zip(state.row(row))
+                    .filter(|(x, y)| **x && **y)
+                    .count();
+                if overlap > CUTOFF {
+                    row_op(&mut state, row, col);
+                    circuit.push((row, col));
+                }
+            }
+        }
+    }
+    circuit
+}
+
+/// Synthesize the invertible binary matrix `mat` as a `CX` circuit for all-to-all connectivity
+/// with the method of Patel, Markov and Hayes [1], with columns handled in sections of
+/// `section_size`.
+///
+/// When the number of qubits is not a multiple of the section size, the remaining columns form a
+/// narrower last section.
+///
+/// # References
+///
+/// 1. Patel, Ketan N., Igor L. Markov, and John P. Hayes, *Optimal synthesis of linear reversible
+///    circuits*, Quantum Information & Computation 8.3 (2008): 282-294.
+///    [arXiv:quant-ph/0302002](https://arxiv.org/abs/quant-ph/0302002)
+pub fn synth_cnot_count_full_pmh_inner(
+    mat: ArrayView2<bool>,
+    section_size: usize,
+) -> Result<CXSequence, AccelerateError> {
+    if section_size == 0 {
+        return Err(AccelerateError::Value(
+            "The section size must be positive.".to_string(),
+        ));
+    }
+    let mut state = mat.to_owned();
+    // Synthesize the lower-triangular part, then the upper-triangular part of the transpose.
+    let circuit_l = lwr_cnot_synth(state.view_mut(), section_size);
+    let mut state = state.reversed_axes();
+    let circuit_u = lwr_cnot_synth(state.view_mut(), section_size);
+    Ok(circuit_u
+        .into_iter()
+        .map(|(ctrl, trgt)| (trgt, ctrl))
+        .chain(circuit_l.into_iter().rev())
+        .collect())
+}
+
+/// Add row `a` of `mat` to row `b`, and record the `CX` gate.
+#[inline]
+fn row_op_update_instructions(
+    instructions: &mut CXSequence,
+    mat: &mut ArrayViewMut2<bool>,
+    a: usize,
+    b: usize,
+) {
+    instructions.push((a, b));
+    row_op(mat, a, b);
+}
+
+/// The rows operations of a lower-triangular basis change of `mat`, applied to `mat_inv` (as
+/// column operations) and to a copy of `mat`, as in the proof of Proposition 7.3 of [KMS].
+fn get_lower_triangular(
+    mat: ArrayView2<bool>,
+    mat_inv: ArrayView2<bool>,
+) -> (Array2<bool>, Array2<bool>) {
+    let n = mat.nrows();
+    let mut mat = mat.to_owned();
+    let mut mat_t = mat.clone();
+    let mut mat_inv_t = mat_inv.to_owned();
+    let mut instructions = CXSequence::new();
+    // Use only gates `cx(a, b)` with `a > b` to transform the matrix into a permuted
+    // lower-triangular one.
+    for i in (0..n).rev() {
+        // Zero out all the entries of row `i` but the last one with column operations.
+        let mut first_j = None;
+        for j in (0..n).rev() {
+            if mat[[i, j]] {
+                match first_j {
+                    None => first_j = Some(j),
+                    Some(first_j) => col_op(&mut mat.view_mut(), j, first_j),
+                }
+            }
+        }
+        // Zero out the entries above the remaining one with row operations going upwards.
+        let first_j = first_j.expect("the matrix is invertible");
+        for k in (0..i).rev() {
+            if mat[[k, first_j]] {
+                row_op_update_instructions(&mut instructions, &mut mat.view_mut(), i, k);
+            }
+        }
+    }
+    for (a, b) in instructions {
+        row_op(&mut mat_t.view_mut(), a, b);
+        col_op(&mut mat_inv_t.view_mut(), a, b);
+    }
+    (mat_t, mat_inv_t)
+}
+
+/// Whether `row` is a linear combination of the rows of the matrix whose inverse is `mat_inv_t`,
+/// without the row labelled by `k`.
+fn in_linear_combination(
+    label_arr_t: &[usize],
+    mat_inv_t: ArrayView2<bool>,
+    row: ArrayView1<bool>,
+    k: usize,
+) -> bool {
+    let mut w_needed = Array1::from_elem(row.len(), false);
+    for (l, _) in row.iter().enumerate().filter(|(_, x)| **x) {
+        w_needed.zip_mut_with(&mat_inv_t.row(l), |x, y| *x ^= *y);
+    }
+    !w_needed[label_arr_t[k]]
+}
+
+/// Transform the invertible `mat` into a north-west triangular matrix in place, by Proposition 7.3
+/// of [KMS].  Returns the row operations done.
+fn matrix_to_north_west(mut mat: ArrayViewMut2<bool>, mat_inv: ArrayView2<bool>) -> CXSequence {
+    let n = mat.nrows();
+    // The rows of `mat_t` are the vectors `w_j` of [KMS], and `mat_inv_t` is its inverse.
+    let (mat_t, mat_inv_t) = get_lower_triangular(mat.view(), mat_inv);
+    // The labels `pi(i)`: for each row of `mat_t`, the distance of its last one from the end.
+    let mut label_arr = mat_t
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().rev().position(|x| *x).unwrap())
+        .collect::<Vec<_>>();
+    let mut label_arr_t = vec![0; n];
+    for (i, label) in label_arr.iter().enumerate() {
+        label_arr_t[*label] = i;
+    }
+
+    let mut first_qubit = 0;
+    let mut empty_layers = 0;
+    let mut instructions = CXSequence::new();
+    // Each layer swaps the out-of-order labels of alternately even and odd pairs of neighbours,
+    // until two layers in a row do nothing.
+    while empty_layers < 2 {
+        let mut at_least_one_needed = false;
+        for i in (first_qubit..n.saturating_sub(1)).step_by(2) {
+            if label_arr[i] > label_arr[i + 1] {
+                at_least_one_needed = true;
+                // Perform a box on `i` and `i + 1` that writes a vector in the span of all the
+                // `w_l` with `l != k` to wire `i + 1`.
+                let k = label_arr[i + 1];
+                let in_span = |row: ArrayView1<bool>| {
+                    in_linear_combination(&label_arr_t, mat_inv_t.view(), row, k)
+                };
+                if in_span(mat.row(i + 1)) {
+                    // Nothing to do.
+                } else if in_span((&mat.row(i + 1) ^ &mat.row(i)).view()) {
+                    row_op_update_instructions(&mut instructions, &mut mat, i, i + 1);
+                } else if in_span(mat.row(i)) {
+                    row_op_update_instructions(&mut instructions, &mut mat, i + 1, i);
+                    row_op_update_instructions(&mut instructions, &mut mat, i, i + 1);
+                }
+                label_arr.swap(i, i + 1);
+            }
+        }
+        if at_least_one_needed {
+            empty_layers = 0;
+        } else {
+            empty_layers += 1;
+        }
+        first_qubit = 1 - first_qubit;
+    }
+    instructions
+}
+
+/// Transform the north-west triangular `mat` into the identity in place, in depth `3n`, by
+/// Proposition 7.4 of [KMS].  Returns the row operations done.
+fn north_west_to_identity(mut mat: ArrayViewMut2<bool>) -> CXSequence {
+    let n = mat.nrows();
+    // The labels start in reverse order.
+    let mut label_arr = (0..n).rev().collect::<Vec<_>>();
+    let mut first_qubit = 0;
+    let mut empty_layers = 0;
+    let mut instructions = CXSequence::new();
+    while empty_layers < 2 {
+        let mut at_least_one_needed = false;
+        for i in (first_qubit..n.saturating_sub(1)).step_by(2) {
+            if label_arr[i] > label_arr[i + 1] {
+                at_least_one_needed = true;
+                // If row `i` has a one in the column of the label `i + 1`, swap the rows and
+                // remove the one in depth 2; otherwise only swap them, in depth 3.
+                if !mat[[i, label_arr[i + 1]]] {
+                    row_op_update_instructions(&mut instructions, &mut mat, i + 1, i);
+                }
+                row_op_update_instructions(&mut instructions, &mut mat, i, i + 1);
+                row_op_update_instructions(&mut instructions, &mut mat, i + 1, i);
+                label_arr.swap(i, i + 1);
+            }
+        }
+        if at_least_one_needed {
+            empty_layers = 0;
+        } else {
+            empty_layers += 1;
+        }
+        first_qubit = 1 - first_qubit;
+    }
+    instructions
+}
+
+/// The row operations that reduce the inverse of the invertible `mat` to a north-west triangular
+/// matrix, and then that matrix to the identity, for linear nearest-neighbour connectivity [KMS].
+/// Applied in order to the identity, the two sequences together give `mat`.
+pub fn optimize_cx_circ_depth_5n_line(
+    mat: ArrayView2<bool>,
+) -> Result<(CXSequence, CXSequence), AccelerateError> {
+    let mut mat_cpy = calc_inverse_matrix_inner(mat, false)?;
+    let to_north_west = matrix_to_north_west(mat_cpy.view_mut(), mat);
+    let to_identity = north_west_to_identity(mat_cpy.view_mut());
+    Ok((to_north_west, to_identity))
+}
+
+/// Synthesize the invertible binary matrix `mat` as a `CX` circuit for linear nearest-neighbour
+/// connectivity, in depth at most `5n`, with the method of Kutin, Moulton and Smithline [KMS],
+/// section 7.
+///
+/// The inverse of the matrix is first transformed into a north-west triangular matrix (one with
+/// `A[i][j] = 0` for all `i + j >= n`), which is then transformed into the identity.
+///
+/// # References
+///
+/// [KMS]: Kutin, S., Moulton, D. P., Smithline, L., *Computation at a distance*, Chicago J. Theor.
+/// Comput. Sci., vol. 2007, (2007). [arXiv:quant-ph/0701194](https://arxiv.org/abs/quant-ph/0701194)
+pub fn synth_cnot_depth_line_kms_inner(
+    mat: ArrayView2<bool>,
+) -> Result<CXSequence, AccelerateError> {
+    if !check_invertible_binary_matrix_inner(mat) {
+        return Err(AccelerateError::Qiskit(
+            "The input matrix is not invertible.".to_string(),
+        ));
+    }
+    let (mut instructions, nw_to_identity) = optimize_cx_circ_depth_5n_line(mat)?;
+    instructions.extend(nw_to_identity);
+    Ok(instructions)
+}
+
+#[cfg(feature = "python")]
+fn cx_sequence_to_circuit(
+    py: Python,
+    num_qubits: usize,
+    gates: CXSequence,
+) -> PyResult<CircuitData> {
+    CircuitData::from_standard_gates(
+        py,
+        num_qubits as u32,
+        gates.into_iter().map(|(ctrl, trgt)| {
+            (
+                StandardGate::CXGate,
+                vec![],
+                [ctrl as u32, trgt as u32].into_iter().collect(),
+            )
+        }),
+    )
+}
+
+/// Gaussian elimination of a binary matrix, in place.
+///
+/// Args:
+///     mat (ndarray): the boolean matrix, which is modified in place.
+///     ncols (int | None): the number of leading columns to eliminate, or all if ``None``.
+///     full_elim (bool): whether to also eliminate the entries above the pivots.
+///
+/// Returns:
+///     list[int]: the permutation of the rows that was done during the elimination.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, ncols=None, full_elim=false))]
+pub fn gauss_elimination_with_perm_inplace(
+    mut mat: PyReadwriteArray2<bool>,
+    ncols: Option<usize>,
+    full_elim: bool,
+) -> Vec<usize> {
+    gauss_elimination_with_perm(mat.as_array_mut(), ncols, full_elim)
+}
+
+/// Compute the rank of a binary matrix over GF(2).
+///
+/// Args:
+///     mat (ndarray): the boolean matrix.
+///
+/// Returns:
+///     int: the rank of the matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "compute_rank", signature = (mat))]
+pub fn py_compute_rank(mat: PyReadonlyArray2<bool>) -> usize {
+    compute_rank(mat.as_array())
+}
+
+/// Compute the inverse of a square binary matrix over GF(2).
+///
+/// Args:
+///     mat (ndarray): the boolean matrix.
+///     verify (bool): whether to check that the product of the matrix and its inverse is the
+///         identity.
+///
+/// Returns:
+///     ndarray: the boolean inverse matrix.
+///
+/// Raises:
+///     QiskitError: if the matrix is not square or not invertible.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, verify=false))]
+pub fn calc_inverse_matrix(
+    py: Python,
+    mat: PyReadonlyArray2<bool>,
+    verify: bool,
+) -> PyResult<Py<PyArray2<bool>>> {
+    let inverse = calc_inverse_matrix_inner(mat.as_array(), verify)?;
+    Ok(inverse.into_pyarray_bound(py).unbind())
+}
+
+/// Check that a binary matrix is square and invertible over GF(2).
+///
+/// Args:
+///     mat (ndarray): the boolean matrix.
+///
+/// Returns:
+///     bool: whether the matrix is invertible.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat))]
+pub fn check_invertible_binary_matrix(mat: PyReadonlyArray2<bool>) -> bool {
+    check_invertible_binary_matrix_inner(mat.as_array())
+}
+
+/// Synthesize a linear reversible circuit for all-to-all connectivity with the Patel, Markov and
+/// Hayes method.
+///
+/// Args:
+///     mat (ndarray): the boolean invertible matrix of the circuit.
+///     section_size (int): the number of columns in each section of the algorithm.
+///
+/// Returns:
+///     CircuitData: the ``CX`` circuit implementing the matrix.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, section_size=2))]
+pub fn synth_cnot_count_full_pmh(
+    py: Python,
+    mat: PyReadonlyArray2<bool>,
+    section_size: usize,
+) -> PyResult<CircuitData> {
+    let mat = mat.as_array();
+    let gates = synth_cnot_count_full_pmh_inner(mat, section_size)?;
+    cx_sequence_to_circuit(py, mat.nrows(), gates)
+}
+
+/// The ``CX`` gates of the Kutin, Moulton and Smithline synthesis of a linear reversible circuit,
+/// in its two stages: the reduction of the inverse matrix to a north-west triangular matrix, and
+/// of that matrix to the identity.
+///
+/// Args:
+///     mat (ndarray): the boolean invertible matrix of the circuit.
+///
+/// Returns:
+///     (list[tuple[int, int]], list[tuple[int, int]]): the ``(control, target)`` pairs of the
+///     ``CX`` gates of the two stages.
+///
+/// Raises:
+///     QiskitError: if the matrix is not invertible.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "optimize_cx_circ_depth_5n_line", signature = (mat))]
+pub fn py_optimize_cx_circ_depth_5n_line(
+    mat: PyReadonlyArray2<bool>,
+) -> PyResult<(CXSequence, CXSequence)> {
+    Ok(optimize_cx_circ_depth_5n_line(mat.as_array())?)
+}
+
+/// Synthesize a linear reversible circuit for linear nearest-neighbour connectivity in depth at
+/// most ``5n``, with the Kutin, Moulton and Smithline method.
+///
+/// Args:
+///     mat (ndarray): the boolean invertible matrix of the circuit.
+///
+/// Returns:
+///     CircuitData: the ``CX`` circuit implementing the matrix.
+///
+/// Raises:
+///     QiskitError: if the matrix is not invertible.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat))]
+pub fn synth_cnot_depth_line_kms(py: Python, mat: PyReadonlyArray2<bool>) -> PyResult<CircuitData> {
+    let mat = mat.as_array();
+    let gates = synth_cnot_depth_line_kms_inner(mat)?;
+    cx_sequence_to_circuit(py, mat.nrows(), gates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+
+    fn identity(n: usize) -> Array2<bool> {
+        Array2::from_shape_fn((n, n), |(i, j)| i == j)
+    }
+
+    /// A random invertible matrix, as a product of random row operations.
+    fn random_invertible(n: usize, seed: u128) -> Array2<bool> {
+        let mut rng = generator(seed);
+        let mut mat = identity(n);
+        for _ in 0..4 * n * n {
+            let ctrl = rng.gen_range(0..n);
+            let trgt = rng.gen_range(0..n);
+            if ctrl != trgt {
+                row_op(&mut mat.view_mut(), ctrl, trgt);
+            }
+        }
+        mat
+    }
+
+    /// The matrix of the `CX` circuit `gates` on `n` qubits.
+    fn circuit_matrix(gates: &CXSequence, n: usize) -> Array2<bool> {
+        let mut mat = identity(n);
+        for (ctrl, trgt) in gates {
+            row_op(&mut mat.view_mut(), *ctrl, *trgt);
+        }
+        mat
+    }
+
+    #[test]
+    fn test_rank_and_inverse() {
+        for n in 1..9 {
+            let mat = random_invertible(n, n as u128);
+            assert_eq!(compute_rank(mat.view()), n);
+            assert!(check_invertible_binary_matrix_inner(mat.view()));
+            let inverse = calc_inverse_matrix_inner(mat.view(), true).unwrap();
+            assert_eq!(binary_matmul(mat.view(), inverse.view()), identity(n));
+            assert_eq!(binary_matmul(inverse.view(), mat.view()), identity(n));
+        }
+    }
+
+    #[test]
+    fn test_singular() {
+        let mut mat = random_invertible(5, 3);
+        // Make the last row the sum of the first two.
+        let sum = &mat.row(0) ^ &mat.row(1);
+        mat.row_mut(4).assign(&sum);
+        assert_eq!(compute_rank(mat.view()), 4);
+        assert!(!check_invertible_binary_matrix_inner(mat.view()));
+        assert!(matches!(
+            calc_inverse_matrix_inner(mat.view(), false),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        assert!(matches!(
+            synth_cnot_depth_line_kms_inner(mat.view()),
+            Err(AccelerateError::Qiskit(_))
+        ));
+        let wide = Array2::from_elem((2, 3), true);
+        assert!(!check_invertible_binary_matrix_inner(wide.view()));
+        assert!(matches!(
+            calc_inverse_matrix_inner(wide.view(), false),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+
+    #[test]
+    fn test_gauss_elimination_permutation() {
+        let mat = array![
+            [false, true, true],
+            [false, false, false],
+            [true, false, true],
+            [true, true, false],
+        ];
+        let mut reduced = mat.clone();
+        let perm = gauss_elimination_with_perm(reduced.view_mut(), None, true);
+        assert_eq!(compute_rank_after_gauss_elim(reduced.view()), 2);
+        // The first `rank` rows of the permutation are independent rows of the input.
+        let independent = Array2::from_shape_fn((2, 3), |(i, j)| mat[[perm[i], j]]);
+        assert_eq!(compute_rank(independent.view()), 2);
+    }
+
+    #[test]
+    fn test_pmh() {
+        for n in 1..10 {
+            let mat = random_invertible(n, 10 + n as u128);
+            // The section sizes include ones that don't divide the number of qubits.
+            for section_size in 1..=n {
+                let gates = synth_cnot_count_full_pmh_inner(mat.view(), section_size).unwrap();
+                assert_eq!(
+                    circuit_matrix(&gates, n),
+                    mat,
+                    "section size {section_size}"
+                );
+            }
+        }
+        assert!(matches!(
+            synth_cnot_count_full_pmh_inner(identity(3).view(), 0),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_kms() {
+        for n in 1..10 {
+            let mat = random_invertible(n, 20 + n as u128);
+            let gates = synth_cnot_depth_line_kms_inner(mat.view()).unwrap();
+            assert_eq!(circuit_matrix(&gates, n), mat);
+            assert!(gates.iter().all(|(ctrl, trgt)| ctrl.abs_diff(*trgt) == 1));
+            // Greedily layer the gates to bound the depth.
+            let mut depths = vec![0; n];
+            for (ctrl, trgt) in &gates {
+                let depth = depths[*ctrl].max(depths[*trgt]) + 1;
+                depths[*ctrl] = depth;
+                depths[*trgt] = depth;
+            }
+            assert!(depths.into_iter().max().unwrap_or(0) <= 5 * n);
+        }
+    }
+}
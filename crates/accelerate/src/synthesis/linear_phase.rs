@@ -0,0 +1,555 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of circuits made of `CX` and diagonal phase gates: `CZ` networks for linear
+//! nearest-neighbour connectivity, and phase polynomials over `CX` and phase gates (GraySynth).
+//! Both are gate-for-gate ports of `qiskit.synthesis.synth_cz_depth_line_mr` and
+//! `synth_cnot_phase_aam`.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use std::f64::consts::PI;
+use std::iter::once;
+
+use hashbrown::HashMap;
+use ndarray::prelude::*;
+
+use super::linear::{row_op, synth_cnot_count_full_pmh_inner};
+use super::permutation::{push_cx_stage1, push_cx_stage2};
+use super::{GateSequence, SynthesisGate};
+use crate::error::AccelerateError;
+
+/// A phase gate of a phase polynomial.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PhaseGate {
+    T,
+    Tdg,
+    S,
+    Sdg,
+    Z,
+    /// A `Phase` gate, whose angle is reduced modulo `π` as in `synth_cnot_phase_aam`.
+    P(f64),
+}
+
+impl PhaseGate {
+    fn push(self, circuit: &mut GateSequence, qubit: usize) {
+        match self {
+            PhaseGate::T => circuit.push(SynthesisGate::T, &[], &[qubit]),
+            PhaseGate::Tdg => circuit.push(SynthesisGate::Tdg, &[], &[qubit]),
+            PhaseGate::S => circuit.push(SynthesisGate::S, &[], &[qubit]),
+            PhaseGate::Sdg => circuit.push(SynthesisGate::Sdg, &[], &[qubit]),
+            PhaseGate::Z => circuit.push(SynthesisGate::Z, &[], &[qubit]),
+            PhaseGate::P(angle) => {
+                circuit.push(SynthesisGate::Phase, &[angle.rem_euclid(PI)], &[qubit])
+            }
+        }
+    }
+}
+
+/// The patterns `Pj` and `Pk` of Maslov and Roetteler, for at least two qubits.
+fn lnn_patterns(num_qubits: usize) -> (Vec<usize>, Vec<usize>) {
+    let n = num_qubits;
+    let mut pat1 = Vec::with_capacity(n);
+    let mut pat2 = Vec::with_capacity(n);
+    if n % 2 == 0 {
+        // [n-1, n-3, n-3, ..., 1, 1, 0, 0, 2, 2, ..., n-4, n-4, n-2]
+        pat1.push(n - 1);
+        for i in 0..(n - 2) / 2 {
+            pat1.extend([n - 2 * i - 3; 2]);
+        }
+        for i in 0..(n - 2) / 2 {
+            pat1.extend([2 * i; 2]);
+        }
+        pat1.push(n - 2);
+        // [2, 2, 4, 4, ..., n-2, n-2, n-1, n-1, ..., 3, 3, 1, 1]
+        for i in 0..(n - 2) / 2 {
+            pat2.extend([2 * (i + 1); 2]);
+        }
+        for i in 0..n / 2 {
+            pat2.extend([n - 2 * i - 1; 2]);
+        }
+    } else {
+        // [n-2, n-4, n-4, ..., 3, 3, 1, 1, 0, 0, 2, 2, ..., n-3, n-3]
+        pat1.push(n - 2);
+        for i in 0..(n - 3) / 2 {
+            pat1.extend([n - 2 * i - 4; 2]);
+        }
+        for i in 0..(n - 1) / 2 {
+            pat1.extend([2 * i; 2]);
+        }
+        // [2, 2, 4, 4, ..., n-1, n-1, n-2, n-2, n-4, n-4, ..., 5, 5, 3, 3, 1]
+        for i in 0..(n - 1) / 2 {
+            pat2.extend([2 * i + 2; 2]);
+        }
+        for i in 0..(n - 3) / 2 {
+            pat2.extend([n - 2 * i - 2; 2]);
+        }
+        pat2.push(1);
+    }
+    (pat1, pat2)
+}
+
+/// The pairs of qubits whose `CZ` phases are collected in each layer of the circuit, as in
+/// `cz_depth_lnn._create_patterns`.  Entry `[layer][i]` is the pattern of qubit `i`.
+fn create_patterns(num_qubits: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut pats = vec![(0..num_qubits).map(|i| (i, i)).collect::<Vec<_>>()];
+    if num_qubits < 2 {
+        return pats;
+    }
+    let (pat1, pat2) = lnn_patterns(num_qubits);
+    let mut ind1 = if num_qubits % 2 == 0 {
+        num_qubits - 2
+    } else {
+        num_qubits - 3
+    };
+    let mut ind2 = 0;
+    for layer in 0..num_qubits / 2 {
+        pats.push(
+            (0..num_qubits)
+                .map(|i| (pat1[ind1 + i], pat2[ind2 + i]))
+                .collect(),
+        );
+        // The last layer is the one where the index would run off the start of the pattern.
+        if layer + 1 < num_qubits / 2 {
+            ind1 -= 2;
+        }
+        ind2 += 2;
+    }
+    pats
+}
+
+/// Append the phase gates that implement `s_gates[i]` `Sdg` gates on each qubit `i`, and reset
+/// the counts.
+fn push_phase_layer(circuit: &mut GateSequence, s_gates: &mut [usize]) {
+    for (qubit, count) in s_gates.iter_mut().enumerate() {
+        match *count % 4 {
+            1 => circuit.push(SynthesisGate::Sdg, &[], &[qubit]),
+            2 => circuit.push(SynthesisGate::Z, &[], &[qubit]),
+            3 => circuit.push(SynthesisGate::S, &[], &[qubit]),
+            _ => (),
+        }
+        *count = 0;
+    }
+}
+
+/// Synthesize the `CZ` circuit given by the upper-triangular part of `mat`, where `mat[[i, j]]`
+/// for `i < j` is a `CZ` gate between qubits `i` and `j`, for linear nearest-neighbour
+/// connectivity with two-qubit depth at most `2n + 2`.
+///
+/// The circuit is made of `CX`, `S`, `Sdg` and `Z` gates, and reverses the order of the qubits.
+///
+/// # References
+///
+/// 1. Dmitri Maslov, Martin Roetteler, *Shorter stabilizer circuits via Bruhat decomposition and
+///    quantum circuit transformations*,
+///    [arXiv:1705.09176](https://arxiv.org/abs/1705.09176)
+pub fn synth_cz_depth_line_mr_inner(mat: ArrayView2<bool>) -> GateSequence {
+    let num_qubits = mat.nrows();
+    let pats = create_patterns(num_qubits);
+    let mut pattern_counts: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut s_gates = vec![0; num_qubits];
+    for i in 0..num_qubits {
+        for j in i + 1..num_qubits {
+            if mat[[i, j]] {
+                // A CZ gate is a Z gate on each qubit, followed by the phases collected by its
+                // patterns.
+                s_gates[i] += 2;
+                s_gates[j] += 2;
+                for pattern in [(i, j - 1), (i, j), (i + 1, j - 1), (i + 1, j)] {
+                    *pattern_counts.entry(pattern).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut circuit = GateSequence::new();
+    for layer in pats.iter().take((num_qubits + 1) / 2) {
+        for (count, pattern) in s_gates.iter_mut().zip(layer) {
+            *count += pattern_counts.get(pattern).unwrap_or(&0);
+        }
+        push_phase_layer(&mut circuit, &mut s_gates);
+        push_cx_stage1(&mut circuit, num_qubits);
+        push_cx_stage2(&mut circuit, num_qubits);
+    }
+    if num_qubits % 2 == 0 {
+        for (count, pattern) in s_gates.iter_mut().zip(&pats[num_qubits / 2]) {
+            if pattern.0 != pattern.1 {
+                *count += pattern_counts.get(pattern).unwrap_or(&0);
+            }
+        }
+        push_phase_layer(&mut circuit, &mut s_gates);
+        push_cx_stage1(&mut circuit, num_qubits);
+    }
+    circuit
+}
+
+/// A set of parities still to be synthesized by GraySynth, as the columns of `cnots`, along with
+/// the rows that may still be used to split it and the target qubit of its parities.
+#[derive(Clone, Debug, PartialEq)]
+struct GraySynthEntry {
+    cnots: Array2<bool>,
+    ilist: Vec<usize>,
+    qubit: usize,
+}
+
+/// Append the phase gates of all remaining parities equal to `parity` on `qubit`, and remove
+/// them from the parities to synthesize.
+fn push_matching_phases(
+    circuit: &mut GateSequence,
+    parities: &mut Vec<(Array1<bool>, PhaseGate)>,
+    parity: ArrayView1<bool>,
+    qubit: usize,
+) {
+    parities.retain(|(other, gate)| {
+        if *other == parity {
+            gate.push(circuit, qubit);
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Synthesize the phase polynomial whose parities are the columns of `cnots` (over the qubits
+/// indexing its rows), with `angles[i]` the phase gate of parity `i`, with the GraySynth
+/// algorithm of Amy, Azimzadeh and Mosca.  The linear reversible circuit left at the end is
+/// undone with the Patel-Markov-Hayes algorithm, with the given `section_size`.
+///
+/// # References
+///
+/// 1. Matthew Amy, Parsiad Azimzadeh, and Michele Mosca, *On the controlled-NOT complexity of
+///    controlled-NOT–phase circuits*, Quantum Science and Technology 4.1 (2018): 015002.
+///    [arXiv:1712.01859](https://arxiv.org/abs/1712.01859)
+pub fn synth_cnot_phase_aam_inner(
+    cnots: ArrayView2<bool>,
+    angles: &[PhaseGate],
+    section_size: usize,
+) -> Result<GateSequence, AccelerateError> {
+    if cnots.ncols() != angles.len() {
+        return Err(AccelerateError::Qiskit(
+            "Size of \"cnots\" and \"angles\" do not match.".to_string(),
+        ));
+    }
+    let num_qubits = cnots.nrows();
+    let epsilon = num_qubits;
+    let mut circuit = GateSequence::new();
+    let mut parities: Vec<(Array1<bool>, PhaseGate)> = cnots
+        .columns()
+        .into_iter()
+        .map(|column| column.to_owned())
+        .zip(angles.iter().copied())
+        .collect();
+    // The parities currently held by each qubit.
+    let mut state = Array2::from_shape_fn((num_qubits, num_qubits), |(i, j)| i == j);
+
+    // Apply the phase gates that need no CX gates first.
+    for qubit in 0..num_qubits {
+        push_matching_phases(&mut circuit, &mut parities, state.row(qubit), qubit);
+    }
+
+    let mut stack = vec![GraySynthEntry {
+        cnots: cnots.to_owned(),
+        ilist: (0..num_qubits).collect(),
+        qubit: epsilon,
+    }];
+    while let Some(mut entry) = stack.pop() {
+        if entry.cnots.ncols() == 0 {
+            continue;
+        }
+        let qubit = entry.qubit;
+        if qubit < num_qubits {
+            let mut condition = true;
+            while condition {
+                condition = false;
+                for j in 0..num_qubits {
+                    if j == qubit || !entry.cnots.row(j).iter().all(|x| *x) {
+                        continue;
+                    }
+                    condition = true;
+                    circuit.push(SynthesisGate::CX, &[], &[j, qubit]);
+                    row_op(&mut state.view_mut(), j, qubit);
+                    push_matching_phases(&mut circuit, &mut parities, state.row(qubit), qubit);
+                    // Express the remaining parities over the new state.  Sets equal to an earlier
+                    // one are left alone, as in the reference implementation.
+                    let unique = {
+                        let entries = stack.iter().chain(once(&entry)).collect::<Vec<_>>();
+                        (0..entries.len())
+                            .map(|k| !entries[..k].contains(&entries[k]))
+                            .collect::<Vec<_>>()
+                    };
+                    for (other, is_unique) in stack.iter_mut().chain(once(&mut entry)).zip(unique) {
+                        if is_unique && other.cnots.ncols() > 0 {
+                            row_op(&mut other.cnots.view_mut(), qubit, j);
+                        }
+                    }
+                }
+            }
+        }
+        if entry.ilist.is_empty() {
+            continue;
+        }
+        // Split on the row that maximizes the size of the largest of the two subsets, since the
+        // larger a subset, the closer it gets to the ideal of one CX gate per parity.
+        let ncols = entry.cnots.ncols();
+        let mut j = entry.ilist[0];
+        let mut best = 0;
+        for &k in &entry.ilist {
+            let ones = entry.cnots.row(k).iter().filter(|x| **x).count();
+            let size = ones.max(ncols - ones);
+            if size > best {
+                best = size;
+                j = k;
+            }
+        }
+        let (cols1, cols0): (Vec<usize>, Vec<usize>) =
+            (0..ncols).partition(|col| entry.cnots[[j, *col]]);
+        let ilist = entry
+            .ilist
+            .iter()
+            .copied()
+            .filter(|k| *k != j)
+            .collect::<Vec<_>>();
+        stack.push(GraySynthEntry {
+            cnots: entry.cnots.select(Axis(1), &cols1),
+            ilist: ilist.clone(),
+            qubit: if qubit == epsilon { j } else { qubit },
+        });
+        stack.push(GraySynthEntry {
+            cnots: entry.cnots.select(Axis(1), &cols0),
+            ilist,
+            qubit,
+        });
+    }
+
+    for (ctrl, trgt) in synth_cnot_count_full_pmh_inner(state.view(), section_size)?
+        .into_iter()
+        .rev()
+    {
+        circuit.push(SynthesisGate::CX, &[], &[ctrl, trgt]);
+    }
+    Ok(circuit)
+}
+
+/// Synthesize a ``CZ`` circuit for linear nearest-neighbour connectivity, with the method of
+/// Maslov and Roetteler.  The circuit reverses the order of the qubits.
+///
+/// Args:
+///     mat (ndarray): the boolean upper-triangular matrix of the ``CZ`` gates, where
+///         ``mat[i][j]`` for ``i < j`` is a ``CZ`` gate between qubits ``i`` and ``j``.
+///
+/// Returns:
+///     CircuitData: the circuit of ``CX``, ``S``, ``Sdg`` and ``Z`` gates, of two-qubit depth at
+///     most ``2n + 2``.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat))]
+pub fn synth_cz_depth_line_mr(py: Python, mat: PyReadonlyArray2<bool>) -> PyResult<CircuitData> {
+    let mat = mat.as_array();
+    let (data, _) = synth_cz_depth_line_mr_inner(mat).into_circuit(py, mat.nrows())?;
+    Ok(data)
+}
+
+/// Synthesize a circuit of ``CX`` and phase gates with the GraySynth algorithm of Amy, Azimzadeh
+/// and Mosca.
+///
+/// Args:
+///     cnots (ndarray): the boolean matrix whose columns are the parities to synthesize.
+///     angles (list[str | float]): the phase gate of each parity: ``"t"``, ``"tdg"``, ``"s"``,
+///         ``"sdg"``, ``"z"``, or the angle of a ``PhaseGate``.
+///     section_size (int): the size of the sections of the Patel-Markov-Hayes synthesis of the
+///         final linear reversible circuit.
+///
+/// Returns:
+///     CircuitData: the synthesized circuit.
+///
+/// Raises:
+///     QiskitError: if the number of parities and angles differ.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (cnots, angles, section_size=2))]
+pub fn synth_cnot_phase_aam(
+    py: Python,
+    cnots: PyReadonlyArray2<bool>,
+    angles: Vec<Bound<PyAny>>,
+    section_size: usize,
+) -> PyResult<CircuitData> {
+    let angles = angles
+        .iter()
+        .map(|angle| {
+            if let Ok(name) = angle.extract::<String>() {
+                match name.as_str() {
+                    "t" => Ok(PhaseGate::T),
+                    "tdg" => Ok(PhaseGate::Tdg),
+                    "s" => Ok(PhaseGate::S),
+                    "sdg" => Ok(PhaseGate::Sdg),
+                    "z" => Ok(PhaseGate::Z),
+                    _ => Err(
+                        AccelerateError::Value(format!("Unknown phase gate '{}'.", name)).into(),
+                    ),
+                }
+            } else {
+                Ok(PhaseGate::P(angle.extract::<f64>()?))
+            }
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let cnots = cnots.as_array();
+    let circuit = synth_cnot_phase_aam_inner(cnots, &angles, section_size)?;
+    let (data, _) = circuit.into_circuit(py, cnots.nrows())?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use num_complex::Complex64;
+    use rand::prelude::*;
+
+    use crate::rng::generator;
+    use crate::synthesis::test::assert_implements;
+
+    const GATES: [PhaseGate; 6] = [
+        PhaseGate::T,
+        PhaseGate::Tdg,
+        PhaseGate::S,
+        PhaseGate::Sdg,
+        PhaseGate::Z,
+        PhaseGate::P(0.7),
+    ];
+
+    fn angle(gate: PhaseGate) -> f64 {
+        match gate {
+            PhaseGate::T => PI / 4.,
+            PhaseGate::Tdg => -PI / 4.,
+            PhaseGate::S => PI / 2.,
+            PhaseGate::Sdg => -PI / 2.,
+            PhaseGate::Z => PI,
+            PhaseGate::P(angle) => angle,
+        }
+    }
+
+    /// The unitary on `num_qubits` qubits that maps each basis state `x` to `phase(x)` times the
+    /// basis state `permute(x)`.
+    fn phase_permutation(
+        num_qubits: usize,
+        phase: impl Fn(usize) -> f64,
+        permute: impl Fn(usize) -> usize,
+    ) -> Array2<Complex64> {
+        Array2::from_shape_fn((1 << num_qubits, 1 << num_qubits), |(row, col)| {
+            if row == permute(col) {
+                Complex64::from_polar(1., phase(col))
+            } else {
+                Complex64::new(0., 0.)
+            }
+        })
+    }
+
+    fn reverse_bits(x: usize, num_qubits: usize) -> usize {
+        (0..num_qubits).fold(0, |out, i| out | (((x >> i) & 1) << (num_qubits - 1 - i)))
+    }
+
+    #[test]
+    fn test_cz_depth_line_mr() {
+        for num_qubits in 3..8 {
+            let mut rng = generator(num_qubits as u128);
+            let mat = Array2::from_shape_fn((num_qubits, num_qubits), |(i, j)| {
+                i < j && rng.gen::<bool>()
+            });
+            let sequence = synth_cz_depth_line_mr_inner(mat.view());
+            let phase = |x: usize| {
+                let mut phase = 0.;
+                for ((i, j), cz) in mat.indexed_iter() {
+                    if *cz && (x >> i) & 1 == 1 && (x >> j) & 1 == 1 {
+                        phase += PI;
+                    }
+                }
+                phase
+            };
+            let expected = phase_permutation(num_qubits, phase, |x| reverse_bits(x, num_qubits));
+            assert_implements(&sequence, expected.view(), num_qubits);
+
+            // The two-qubit gates are all between neighbours, in at most `2n + 2` layers.
+            let mut depths = vec![0; num_qubits];
+            for (gate, _, qubits) in &sequence.gates {
+                if qubits.len() == 2 {
+                    assert_eq!(*gate, SynthesisGate::CX);
+                    assert_eq!(qubits[0].abs_diff(qubits[1]), 1);
+                    let depth = depths[qubits[0]].max(depths[qubits[1]]) + 1;
+                    depths[qubits[0]] = depth;
+                    depths[qubits[1]] = depth;
+                }
+            }
+            assert!(depths.into_iter().max().unwrap() <= 2 * num_qubits + 2);
+        }
+    }
+
+    #[test]
+    fn test_cnot_phase_aam_ccz() {
+        // The doubly-controlled `Z` of example 4.3 of the paper.
+        let cnots = array![
+            [true, false, false, true, true, false, true],
+            [false, true, false, true, false, true, true],
+            [false, false, true, false, true, true, true],
+        ];
+        let t = PhaseGate::T;
+        let tdg = PhaseGate::Tdg;
+        let angles = [t, t, t, tdg, tdg, tdg, t];
+        let sequence = synth_cnot_phase_aam_inner(cnots.view(), &angles, 2).unwrap();
+        let expected = phase_permutation(3, |x| if x == 7 { PI } else { 0. }, |x| x);
+        assert_implements(&sequence, expected.view(), 3);
+    }
+
+    #[test]
+    fn test_cnot_phase_aam_random() {
+        for num_qubits in 1..7 {
+            let mut rng = generator(100 + num_qubits as u128);
+            let num_parities = 2 * num_qubits;
+            let parities = (0..num_parities)
+                .map(|_| rng.gen_range(1..1usize << num_qubits))
+                .collect::<Vec<_>>();
+            let cnots = Array2::from_shape_fn((num_qubits, num_parities), |(i, j)| {
+                (parities[j] >> i) & 1 == 1
+            });
+            let angles = (0..num_parities)
+                .map(|_| GATES[rng.gen_range(0..GATES.len())])
+                .collect::<Vec<_>>();
+            let phase = |x: usize| {
+                parities
+                    .iter()
+                    .zip(&angles)
+                    .filter(|(parity, _)| (*parity & x).count_ones() % 2 == 1)
+                    .map(|(_, gate)| angle(*gate))
+                    .sum::<f64>()
+            };
+            for section_size in 1..=num_qubits.min(3) {
+                let sequence =
+                    synth_cnot_phase_aam_inner(cnots.view(), &angles, section_size).unwrap();
+                let expected = phase_permutation(num_qubits, phase, |x| x);
+                assert_implements(&sequence, expected.view(), num_qubits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cnot_phase_aam_size_mismatch() {
+        let cnots = Array2::from_elem((2, 3), true);
+        assert!(matches!(
+            synth_cnot_phase_aam_inner(cnots.view(), &[PhaseGate::T; 2], 2),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
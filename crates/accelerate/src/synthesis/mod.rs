@@ -0,0 +1,355 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Circuit synthesis routines.  The algorithms themselves produce plain gate sequences; the Python
+//! bindings turn those into `CircuitData` so that `qiskit.synthesis` can wrap them in a
+//! `QuantumCircuit` without building the circuit gate by gate in Python.
+
+pub mod clifford;
+pub mod cnotdihedral;
+pub mod cossin;
+pub mod evolution;
+pub mod gridsynth;
+pub mod isometry;
+pub mod linear;
+pub mod linear_phase;
+pub mod multi_controlled;
+pub mod permutation;
+pub mod qsd;
+pub mod state_preparation;
+pub mod three_qubit;
+
+#[cfg(test)]
+mod test;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+use qiskit_circuit::operations::StandardGate;
+
+use ndarray::ArrayView2;
+use num_complex::Complex64;
+use smallvec::SmallVec;
+
+use crate::euler_one_qubit_decomposer::{unitary_to_gate_sequence_inner, EulerBasis};
+
+/// Rotations of multiplexors with angles smaller than this are dropped, as in `UCPauliRotGate`.
+const ANGLE_EPS: f64 = 1e-10;
+
+/// The gates emitted by the numerical synthesis routines.  Parameterized gates carry their angles
+/// in the gate sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SynthesisGate {
+    /// The generic single-qubit `U(θ, φ, λ)` gate.
+    U,
+    X,
+    H,
+    Z,
+    S,
+    Sdg,
+    T,
+    Tdg,
+    Phase,
+    RX,
+    RY,
+    RZ,
+    CX,
+    CZ,
+    CPhase,
+    RXX,
+    RYY,
+    RZZ,
+    CCX,
+}
+
+impl SynthesisGate {
+    /// The standard gate that this gate is built as.
+    pub fn standard_gate(&self) -> StandardGate {
+        match self {
+            Self::U => StandardGate::UGate,
+            Self::X => StandardGate::XGate,
+            Self::H => StandardGate::HGate,
+            Self::Z => StandardGate::ZGate,
+            Self::S => StandardGate::SGate,
+            Self::Sdg => StandardGate::SdgGate,
+            Self::T => StandardGate::TGate,
+            Self::Tdg => StandardGate::TdgGate,
+            Self::Phase => StandardGate::PhaseGate,
+            Self::RX => StandardGate::RXGate,
+            Self::RY => StandardGate::RYGate,
+            Self::RZ => StandardGate::RZGate,
+            Self::CX => StandardGate::CXGate,
+            Self::CZ => StandardGate::CZGate,
+            Self::CPhase => StandardGate::CPhaseGate,
+            Self::RXX => StandardGate::RXXGate,
+            Self::RYY => StandardGate::RYYGate,
+            Self::RZZ => StandardGate::RZZGate,
+            Self::CCX => StandardGate::CCXGate,
+        }
+    }
+}
+
+/// A gate with its parameters and the qubits it acts on.
+pub type SynthesisInstruction = (SynthesisGate, SmallVec<[f64; 3]>, SmallVec<[usize; 2]>);
+
+/// A sequence of gates and the global phase of the circuit they make up.
+#[derive(Clone, Debug, Default)]
+pub struct GateSequence {
+    pub gates: Vec<SynthesisInstruction>,
+    pub global_phase: f64,
+}
+
+impl GateSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, gate: SynthesisGate, params: &[f64], qubits: &[usize]) {
+        self.gates.push((
+            gate,
+            params.iter().copied().collect(),
+            qubits.iter().copied().collect(),
+        ));
+    }
+
+    /// Append the gates of `other` to this sequence, with each qubit `q` of `other` mapped to
+    /// `qubits[q]`.
+    pub fn extend_mapped(&mut self, other: GateSequence, qubits: &[usize]) {
+        self.gates
+            .extend(other.gates.into_iter().map(|(gate, params, gate_qubits)| {
+                (
+                    gate,
+                    params,
+                    gate_qubits.iter().map(|q| qubits[*q]).collect(),
+                )
+            }));
+        self.global_phase += other.global_phase;
+    }
+
+    /// The inverse of the sequence.
+    pub fn inverse(self) -> Self {
+        let gates = self
+            .gates
+            .into_iter()
+            .rev()
+            .map(|(gate, params, qubits)| {
+                let params = match gate {
+                    SynthesisGate::U => [-params[0], -params[2], -params[1]].into_iter().collect(),
+                    _ => params.iter().map(|x| -x).collect(),
+                };
+                let gate = match gate {
+                    SynthesisGate::S => SynthesisGate::Sdg,
+                    SynthesisGate::Sdg => SynthesisGate::S,
+                    SynthesisGate::T => SynthesisGate::Tdg,
+                    SynthesisGate::Tdg => SynthesisGate::T,
+                    gate => gate,
+                };
+                (gate, params, qubits)
+            })
+            .collect();
+        GateSequence {
+            gates,
+            global_phase: -self.global_phase,
+        }
+    }
+
+    /// Append a single-qubit unitary on `qubit` as a `U` gate, or nothing if it is the identity up
+    /// to phase.
+    pub fn push_one_qubit_unitary(&mut self, mat: ArrayView2<Complex64>, qubit: usize) {
+        if let Some(sequence) =
+            unitary_to_gate_sequence_inner(mat, &[EulerBasis::U], 0, None, true, None)
+        {
+            self.global_phase += sequence.global_phase;
+            for (_, params) in sequence.gates {
+                self.push(SynthesisGate::U, &params, &[qubit]);
+            }
+        }
+    }
+
+    /// Append the rotation `gate` (`RY` or `RZ`) of `qubits[0]`, multiplexed by the other
+    /// `qubits`, with `angles[i]` applied when the controls are in the state `i` (little-endian).
+    ///
+    /// The multiplexor is decomposed as in `UCPauliRotGate`, with an `entangler` (`CX` or `CZ`)
+    /// after each rotation, or before each rotation if `reversed`.  Without `outer_entangler`, the
+    /// entangler at the end (or the start, if `reversed`) is left off, for the caller to cancel or
+    /// absorb into a neighbouring operation.
+    pub fn push_uc_rotation(
+        &mut self,
+        gate: SynthesisGate,
+        angles: &[f64],
+        qubits: &[usize],
+        entangler: SynthesisGate,
+        reversed: bool,
+        outer_entangler: bool,
+    ) {
+        let (target, controls) = (qubits[0], &qubits[1..]);
+        if controls.is_empty() {
+            if angles[0].abs() > ANGLE_EPS {
+                self.push(gate, &angles[..1], &[target]);
+            }
+            return;
+        }
+        let mut angles = angles.to_vec();
+        dec_uc_rotations(&mut angles, false);
+        // The decomposition is symmetric under transposition, so the reversed circuit uses the
+        // same angles.
+        let last = angles.len() - 1;
+        let mut steps = Vec::with_capacity(2 * angles.len());
+        for (i, angle) in angles.into_iter().enumerate() {
+            steps.push((angle, None));
+            if i != last {
+                steps.push((0., Some(controls[(i + 1).trailing_zeros() as usize])));
+            } else if outer_entangler {
+                steps.push((0., Some(controls[controls.len() - 1])));
+            }
+        }
+        if reversed {
+            steps.reverse();
+        }
+        for (angle, control) in steps {
+            match control {
+                Some(control) => self.push(entangler, &[], &[control, target]),
+                None if angle.abs() > ANGLE_EPS => self.push(gate, &[angle], &[target]),
+                None => (),
+            }
+        }
+    }
+
+    /// Append the diagonal gate `diag` on `qubits`, with `qubits[0]` the least significant, as a
+    /// sequence of multiplexed `RZ` gates.  The entries of `diag` must have unit modulus.
+    pub fn push_diagonal(&mut self, diag: &[Complex64], qubits: &[usize]) {
+        let mut phases = diag.iter().map(|x| x.arg()).collect::<Vec<_>>();
+        let mut len = phases.len();
+        while len >= 2 {
+            let mut angles = Vec::with_capacity(len / 2);
+            for i in (0..len).step_by(2) {
+                angles.push(phases[i + 1] - phases[i]);
+                phases[i / 2] = (phases[i] + phases[i + 1]) / 2.;
+            }
+            let first = qubits.len() - len.trailing_zeros() as usize;
+            self.push_uc_rotation(
+                SynthesisGate::RZ,
+                &angles,
+                &qubits[first..],
+                SynthesisGate::CX,
+                false,
+                true,
+            );
+            len /= 2;
+        }
+        self.global_phase += phases[0];
+    }
+
+    /// Build the `CircuitData` of the sequence on `num_qubits` qubits, returning it along with the
+    /// global phase.
+    #[cfg(feature = "python")]
+    pub fn into_circuit(self, py: Python, num_qubits: usize) -> PyResult<(CircuitData, f64)> {
+        let data = CircuitData::from_standard_gates(
+            py,
+            num_qubits as u32,
+            self.gates.into_iter().map(|(gate, params, qubits)| {
+                (
+                    gate.standard_gate(),
+                    params.into_vec(),
+                    qubits.into_iter().map(|q| q as u32).collect(),
+                )
+            }),
+        )?;
+        Ok((data, self.global_phase))
+    }
+}
+
+#[cfg(test)]
+impl GateSequence {
+    /// The unitary of the sequence on `num_qubits` qubits, including its global phase.
+    pub fn to_matrix(&self, num_qubits: usize) -> ndarray::Array2<Complex64> {
+        let phase = Complex64::from_polar(1., self.global_phase);
+        let mut out = ndarray::Array2::<Complex64>::eye(1 << num_qubits) * phase;
+        for (gate, params, qubits) in &self.gates {
+            let mat = gate
+                .standard_gate()
+                .matrix(params)
+                .expect("synthesis gates have the parameters of their standard gates");
+            crate::operator::apply_left_inner(&mut out, mat.view(), qubits).unwrap();
+        }
+        out
+    }
+}
+
+/// Compute the angles of the rotations of a multiplexed Pauli rotation decomposed with a `CX` (or
+/// `CZ`) after each rotation, as in `UCPauliRotGate._dec_uc_rotations`.  With `reversed`, the
+/// entangling gate is at the start instead.
+fn dec_uc_rotations(angles: &mut [f64], reversed: bool) {
+    let half = angles.len() / 2;
+    for i in 0..half {
+        let (a, b) = (angles[i], angles[i + half]);
+        let (sum, diff) = ((a + b) / 2., (a - b) / 2.);
+        if reversed {
+            angles[i + half] = sum;
+            angles[i] = diff;
+        } else {
+            angles[i] = sum;
+            angles[i + half] = diff;
+        }
+    }
+    if half > 1 {
+        let (low, high) = angles.split_at_mut(half);
+        dec_uc_rotations(low, false);
+        dec_uc_rotations(high, true);
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn synthesis(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(clifford::synth_clifford_ag))?;
+    m.add_wrapped(wrap_pyfunction!(clifford::synth_clifford_greedy))?;
+    m.add_wrapped(wrap_pyfunction!(cnotdihedral::synth_cnotdihedral_general))?;
+    m.add_wrapped(wrap_pyfunction!(cossin::cossin))?;
+    m.add_wrapped(wrap_pyfunction!(evolution::synth_pauli_evolution))?;
+    m.add_wrapped(wrap_pyfunction!(gridsynth::gridsynth_rz))?;
+    m.add_wrapped(wrap_pyfunction!(isometry::synth_isometry))?;
+    m.add_wrapped(wrap_pyfunction!(
+        linear::gauss_elimination_with_perm_inplace
+    ))?;
+    m.add_wrapped(wrap_pyfunction!(linear::py_compute_rank))?;
+    m.add_wrapped(wrap_pyfunction!(linear::calc_inverse_matrix))?;
+    m.add_wrapped(wrap_pyfunction!(linear::check_invertible_binary_matrix))?;
+    m.add_wrapped(wrap_pyfunction!(linear::py_optimize_cx_circ_depth_5n_line))?;
+    m.add_wrapped(wrap_pyfunction!(linear::synth_cnot_count_full_pmh))?;
+    m.add_wrapped(wrap_pyfunction!(linear::synth_cnot_depth_line_kms))?;
+    m.add_wrapped(wrap_pyfunction!(linear_phase::synth_cnot_phase_aam))?;
+    m.add_wrapped(wrap_pyfunction!(linear_phase::synth_cz_depth_line_mr))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcx_gray_code))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcx_noancilla))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcx_recursive))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcx_v_chain))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcphase))?;
+    m.add_wrapped(wrap_pyfunction!(multi_controlled::synth_mcrz))?;
+    m.add_wrapped(wrap_pyfunction!(permutation::py_get_ordered_swap))?;
+    m.add_wrapped(wrap_pyfunction!(permutation::py_inverse_pattern))?;
+    m.add_wrapped(wrap_pyfunction!(permutation::synth_permutation_basic))?;
+    m.add_wrapped(wrap_pyfunction!(permutation::synth_permutation_acg))?;
+    m.add_wrapped(wrap_pyfunction!(
+        permutation::synth_permutation_depth_lnn_kms
+    ))?;
+    m.add_wrapped(wrap_pyfunction!(
+        permutation::synth_permutation_reverse_lnn_kms
+    ))?;
+    m.add_wrapped(wrap_pyfunction!(qsd::qs_decomposition))?;
+    m.add_wrapped(wrap_pyfunction!(state_preparation::synth_state_preparation))?;
+    m.add_wrapped(wrap_pyfunction!(three_qubit::three_qubit_decomposition))?;
+    Ok(())
+}
@@ -0,0 +1,647 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of multi-controlled `X`, `RZ` and phase gates.  These are ports of the definitions of
+//! `MCXGate`, `MCXGrayCode`, `MCXRecursive`, `MCXVChain`, `MCPhaseGate` and `QuantumCircuit.mcrz`,
+//! which are regenerated for every new number of controls.
+//!
+//! The circuits act on the controls first, then the target, then any ancillas.  Gates without a
+//! standard gate in Rust (`RCCX`, `C3X`, ...) are expanded with their own definitions.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_8, PI};
+
+use super::{GateSequence, SynthesisGate};
+
+/// The relative-phase Toffoli gate `RCCXGate` on `[control, control, target]`.
+fn rccx() -> GateSequence {
+    let mut circuit = GateSequence::new();
+    circuit.push(SynthesisGate::H, &[], &[2]);
+    circuit.push(SynthesisGate::Phase, &[FRAC_PI_4], &[2]);
+    circuit.push(SynthesisGate::CX, &[], &[1, 2]);
+    circuit.push(SynthesisGate::Phase, &[-FRAC_PI_4], &[2]);
+    circuit.push(SynthesisGate::CX, &[], &[0, 2]);
+    circuit.push(SynthesisGate::Phase, &[FRAC_PI_4], &[2]);
+    circuit.push(SynthesisGate::CX, &[], &[1, 2]);
+    circuit.push(SynthesisGate::Phase, &[-FRAC_PI_4], &[2]);
+    circuit.push(SynthesisGate::H, &[], &[2]);
+    circuit
+}
+
+/// The relative-phase 3-controlled `X` gate `RC3XGate`.
+fn rc3x() -> GateSequence {
+    let mut circuit = GateSequence::new();
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    circuit.push(SynthesisGate::Phase, &[FRAC_PI_4], &[3]);
+    circuit.push(SynthesisGate::CX, &[], &[2, 3]);
+    circuit.push(SynthesisGate::Phase, &[-FRAC_PI_4], &[3]);
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    for _ in 0..2 {
+        circuit.push(SynthesisGate::CX, &[], &[0, 3]);
+        circuit.push(SynthesisGate::Phase, &[FRAC_PI_4], &[3]);
+        circuit.push(SynthesisGate::CX, &[], &[1, 3]);
+        circuit.push(SynthesisGate::Phase, &[-FRAC_PI_4], &[3]);
+    }
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    circuit.push(SynthesisGate::Phase, &[FRAC_PI_4], &[3]);
+    circuit.push(SynthesisGate::CX, &[], &[2, 3]);
+    circuit.push(SynthesisGate::Phase, &[-FRAC_PI_4], &[3]);
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    circuit
+}
+
+/// The 3-controlled `X` gate `C3XGate`.
+fn c3x() -> GateSequence {
+    let mut circuit = GateSequence::new();
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    for qubit in 0..4 {
+        circuit.push(SynthesisGate::Phase, &[FRAC_PI_8], &[qubit]);
+    }
+    circuit.push(SynthesisGate::CX, &[], &[0, 1]);
+    circuit.push(SynthesisGate::Phase, &[-FRAC_PI_8], &[1]);
+    circuit.push(SynthesisGate::CX, &[], &[0, 1]);
+    // The remaining gates are `CX` gates, each followed by a phase of the given sign on its
+    // target, if any.
+    let steps: [(usize, usize, Option<f64>); 12] = [
+        (1, 2, Some(-1.)),
+        (0, 2, Some(1.)),
+        (1, 2, Some(-1.)),
+        (0, 2, None),
+        (2, 3, Some(-1.)),
+        (1, 3, Some(1.)),
+        (2, 3, Some(-1.)),
+        (0, 3, Some(1.)),
+        (2, 3, Some(-1.)),
+        (1, 3, Some(1.)),
+        (2, 3, Some(-1.)),
+        (0, 3, None),
+    ];
+    for (control, target, sign) in steps {
+        circuit.push(SynthesisGate::CX, &[], &[control, target]);
+        if let Some(sign) = sign {
+            circuit.push(SynthesisGate::Phase, &[sign * FRAC_PI_8], &[target]);
+        }
+    }
+    circuit.push(SynthesisGate::H, &[], &[3]);
+    circuit
+}
+
+/// The 3-controlled `SX` gate `C3SXGate`.
+fn c3sx() -> GateSequence {
+    let mut circuit = GateSequence::new();
+    // Each controlled phase on the target is conjugated by Hadamard gates.  After the first one,
+    // they are controlled on the target of a preceding `CX` between the controls.
+    let controlled_phase = |circuit: &mut GateSequence, sign: f64, control: usize| {
+        circuit.push(SynthesisGate::H, &[], &[3]);
+        circuit.push(SynthesisGate::CPhase, &[sign * FRAC_PI_8], &[control, 3]);
+        circuit.push(SynthesisGate::H, &[], &[3]);
+    };
+    controlled_phase(&mut circuit, 1., 0);
+    let steps: [(usize, usize, f64); 6] = [
+        (0, 1, -1.),
+        (0, 1, 1.),
+        (1, 2, -1.),
+        (0, 2, 1.),
+        (1, 2, -1.),
+        (0, 2, 1.),
+    ];
+    for (control, target, sign) in steps {
+        circuit.push(SynthesisGate::CX, &[], &[control, target]);
+        controlled_phase(&mut circuit, sign, target);
+    }
+    circuit
+}
+
+/// The 4-controlled `X` gate `C4XGate`.
+fn c4x() -> GateSequence {
+    let mut circuit = GateSequence::new();
+    circuit.push(SynthesisGate::H, &[], &[4]);
+    circuit.push(SynthesisGate::CPhase, &[FRAC_PI_2], &[3, 4]);
+    circuit.push(SynthesisGate::H, &[], &[4]);
+    circuit.extend_mapped(rc3x(), &[0, 1, 2, 3]);
+    circuit.push(SynthesisGate::H, &[], &[4]);
+    circuit.push(SynthesisGate::CPhase, &[-FRAC_PI_2], &[3, 4]);
+    circuit.push(SynthesisGate::H, &[], &[4]);
+    circuit.extend_mapped(rc3x().inverse(), &[0, 1, 2, 3]);
+    circuit.extend_mapped(c3sx(), &[0, 1, 2, 4]);
+    circuit
+}
+
+/// Append the `X` gate on `qubits[n]` controlled on the `n` qubits before it, for up to four
+/// controls, with the dedicated gates.
+fn push_small_mcx(circuit: &mut GateSequence, qubits: &[usize]) {
+    match qubits.len() {
+        2 => circuit.push(SynthesisGate::CX, &[], qubits),
+        3 => circuit.push(SynthesisGate::CCX, &[], qubits),
+        4 => circuit.extend_mapped(c3x(), qubits),
+        5 => circuit.extend_mapped(c4x(), qubits),
+        _ => unreachable!("only defined for one to four controls"),
+    }
+}
+
+/// Append the gray-code chain of `_gray_code_chain`: the controlled phase `lam` on `target`,
+/// multiplexed over all the non-zero states of `controls`, with parities alternating the sign.
+/// With `lam = λ / 2^(n - 1)`, this is the phase `λ` controlled on all the `n` controls.
+fn push_gray_code_chain(circuit: &mut GateSequence, lam: f64, controls: &[usize], target: usize) {
+    let n = controls.len();
+    // Position `i` of a pattern is its `i`-th most significant bit, as in the Python strings.
+    let bit = |pattern: usize, i: usize| (pattern >> (n - 1 - i)) & 1 == 1;
+    let mut last_pattern = None;
+    for i in 1..(1_usize << n) {
+        let pattern = i ^ (i >> 1);
+        let last = *last_pattern.get_or_insert(pattern);
+        let lm_pos = (0..n).find(|i| bit(pattern, *i)).unwrap();
+        if let Some(pos) = (0..n).find(|i| bit(pattern, *i) != bit(last, *i)) {
+            if pos != lm_pos {
+                circuit.push(SynthesisGate::CX, &[], &[controls[pos], controls[lm_pos]]);
+            } else {
+                for idx in (lm_pos + 1..n).filter(|i| bit(pattern, *i)) {
+                    circuit.push(SynthesisGate::CX, &[], &[controls[idx], controls[lm_pos]]);
+                }
+            }
+        }
+        let angle = if pattern.count_ones() % 2 == 0 {
+            -lam
+        } else {
+            lam
+        };
+        circuit.push(SynthesisGate::CPhase, &[angle], &[controls[lm_pos], target]);
+        last_pattern = Some(pattern);
+    }
+}
+
+/// The phase `lam` on the last of `num_ctrl_qubits + 1` qubits controlled on all the others,
+/// decomposed with a gray-code chain of `CPhase` gates, as for `MCU1Gate`.
+pub fn mcphase_gray_code(lam: f64, num_ctrl_qubits: usize) -> GateSequence {
+    let mut circuit = GateSequence::new();
+    match num_ctrl_qubits {
+        0 => circuit.push(SynthesisGate::Phase, &[lam], &[0]),
+        1 => circuit.push(SynthesisGate::CPhase, &[lam], &[0, 1]),
+        _ => {
+            let controls = (0..num_ctrl_qubits).collect::<Vec<_>>();
+            let scaled_lam = lam / 2_f64.powi(num_ctrl_qubits as i32 - 1);
+            push_gray_code_chain(&mut circuit, scaled_lam, &controls, num_ctrl_qubits);
+        }
+    }
+    circuit
+}
+
+/// The multi-controlled `X` gate of `MCXGrayCode`, which is a multi-controlled phase of `π`
+/// between Hadamard gates.
+pub fn mcx_gray_code(num_ctrl_qubits: usize) -> GateSequence {
+    let mut circuit = GateSequence::new();
+    circuit.push(SynthesisGate::H, &[], &[num_ctrl_qubits]);
+    circuit.extend_mapped(
+        mcphase_gray_code(PI, num_ctrl_qubits),
+        &(0..=num_ctrl_qubits).collect::<Vec<_>>(),
+    );
+    circuit.push(SynthesisGate::H, &[], &[num_ctrl_qubits]);
+    circuit
+}
+
+/// The multi-controlled `X` gate without ancillas of `MCXGate`.  Up to four controls, the
+/// dedicated gates are used; beyond that the gate is a multi-controlled phase of `π` between
+/// Hadamard gates.
+pub fn mcx_noancilla(num_ctrl_qubits: usize) -> GateSequence {
+    let qubits = (0..=num_ctrl_qubits).collect::<Vec<_>>();
+    let mut circuit = GateSequence::new();
+    if num_ctrl_qubits <= 4 {
+        push_small_mcx(&mut circuit, &qubits);
+    } else {
+        circuit.push(SynthesisGate::H, &[], &[num_ctrl_qubits]);
+        circuit.extend_mapped(mcphase(PI, num_ctrl_qubits), &qubits);
+        circuit.push(SynthesisGate::H, &[], &[num_ctrl_qubits]);
+    }
+    circuit
+}
+
+/// The multi-controlled `X` gate of `MCXRecursive`.  Beyond four controls, the last qubit is a
+/// dirty ancilla, and the gate is split recursively into four gates on halves of the controls.
+pub fn mcx_recursive(num_ctrl_qubits: usize) -> GateSequence {
+    fn recurse(circuit: &mut GateSequence, qubits: &[usize], ancilla: usize) {
+        if qubits.len() <= 5 {
+            push_small_mcx(circuit, qubits);
+            return;
+        }
+        let num_ctrl_qubits = qubits.len() - 1;
+        let middle = (num_ctrl_qubits + 1) / 2;
+        let mut first_half = qubits[..middle].to_vec();
+        first_half.push(ancilla);
+        let mut second_half = qubits[middle..num_ctrl_qubits].to_vec();
+        second_half.push(ancilla);
+        second_half.push(qubits[num_ctrl_qubits]);
+        for _ in 0..2 {
+            recurse(circuit, &first_half, qubits[middle]);
+            recurse(circuit, &second_half, qubits[middle - 1]);
+        }
+    }
+
+    let mut circuit = GateSequence::new();
+    let qubits = (0..=num_ctrl_qubits).collect::<Vec<_>>();
+    recurse(&mut circuit, &qubits, num_ctrl_qubits + 1);
+    circuit
+}
+
+/// The multi-controlled `X` gate of `MCXVChain`, with `num_ctrl_qubits - 2` ancillas (for three
+/// or more controls) to hold a chain of relative-phase Toffoli gates.  With `dirty_ancillas`, the
+/// ancillas may start in any state, and are restored at the end.
+pub fn mcx_v_chain(num_ctrl_qubits: usize, dirty_ancillas: bool) -> GateSequence {
+    let mut circuit = GateSequence::new();
+    if num_ctrl_qubits <= 2 {
+        push_small_mcx(&mut circuit, &(0..=num_ctrl_qubits).collect::<Vec<_>>());
+        return circuit;
+    }
+    let target = num_ctrl_qubits;
+    let control = |j: usize| j;
+    let ancilla = |i: usize| num_ctrl_qubits + 1 + i;
+    let last_control = control(num_ctrl_qubits - 1);
+    // Bring the target into the chain, through the last ancilla.
+    let ancilla_rule = |circuit: &mut GateSequence, i: usize, pre: bool| {
+        let steps = [
+            (target, -1.),
+            (last_control, 1.),
+            (target, -1.),
+            (last_control, 1.),
+        ];
+        if pre {
+            circuit.push(SynthesisGate::H, &[], &[target]);
+            for (qubit, sign) in steps {
+                circuit.push(SynthesisGate::CX, &[], &[qubit, ancilla(i)]);
+                circuit.push(SynthesisGate::Phase, &[sign * FRAC_PI_4], &[ancilla(i)]);
+            }
+        } else {
+            for (qubit, sign) in steps.into_iter().rev() {
+                circuit.push(SynthesisGate::Phase, &[-sign * FRAC_PI_4], &[ancilla(i)]);
+                circuit.push(SynthesisGate::CX, &[], &[qubit, ancilla(i)]);
+            }
+            circuit.push(SynthesisGate::H, &[], &[target]);
+        }
+    };
+
+    if dirty_ancillas {
+        let mut i = num_ctrl_qubits - 3;
+        ancilla_rule(&mut circuit, i, true);
+        for j in (2..num_ctrl_qubits - 1).rev() {
+            circuit.extend_mapped(rccx(), &[control(j), ancilla(i - 1), ancilla(i)]);
+            i -= 1;
+        }
+    }
+    circuit.extend_mapped(rccx(), &[control(0), control(1), ancilla(0)]);
+    let mut i = 0;
+    for j in 2..num_ctrl_qubits - 1 {
+        circuit.extend_mapped(rccx(), &[control(j), ancilla(i), ancilla(i + 1)]);
+        i += 1;
+    }
+    if dirty_ancillas {
+        ancilla_rule(&mut circuit, i, false);
+    } else {
+        circuit.push(SynthesisGate::CCX, &[], &[last_control, ancilla(i), target]);
+    }
+    for j in (2..num_ctrl_qubits - 1).rev() {
+        circuit.extend_mapped(rccx(), &[control(j), ancilla(i - 1), ancilla(i)]);
+        i -= 1;
+    }
+    circuit.extend_mapped(rccx(), &[control(0), control(1), ancilla(i)]);
+    if dirty_ancillas {
+        for (i, j) in (2..num_ctrl_qubits - 1).enumerate() {
+            circuit.extend_mapped(rccx(), &[control(j), ancilla(i), ancilla(i + 1)]);
+        }
+    }
+    circuit
+}
+
+/// Append the special unitary `unitary`, which must have a real main or secondary diagonal, on
+/// `target` controlled on `controls`, as in `_mcsu2_real_diagonal` (Vale et al.,
+/// arXiv:2302.06377).  The multi-controlled `X` gates use the other half of the controls as dirty
+/// ancillas.
+fn push_mcsu2_real_diagonal(
+    circuit: &mut GateSequence,
+    unitary: ArrayView2<Complex64>,
+    controls: &[usize],
+    target: usize,
+) {
+    const EPS: f64 = 1e-8;
+    let is_secondary_diag_real = unitary[[0, 1]].im.abs() < EPS && unitary[[1, 0]].im.abs() < EPS;
+    let (x, z) = if is_secondary_diag_real {
+        (unitary[[0, 1]].re, unitary[[1, 1]])
+    } else {
+        (
+            -unitary[[0, 1]].re,
+            unitary[[1, 1]] - Complex64::new(0., unitary[[0, 1]].im),
+        )
+    };
+    let s_op = if (z + 1.).norm() < EPS {
+        array![
+            [Complex64::new(1., 0.), Complex64::new(0., 0.)],
+            [Complex64::new(0., 0.), Complex64::new(0., 1.)]
+        ]
+    } else {
+        let root = ((z.re + 1.) / 2.).sqrt() + 1.;
+        let denominator = 2. * ((z.re + 1.) * root).sqrt();
+        let alpha = Complex64::new((root / 2.).sqrt(), z.im / denominator);
+        let beta = Complex64::new(x / denominator, 0.);
+        array![[alpha, -beta.conj()], [beta, alpha.conj()]]
+    };
+    let s_op_inverse = s_op.t().mapv(|x| x.conj());
+
+    let num_controls = controls.len();
+    let k_1 = (num_controls + 1) / 2;
+    let k_2 = num_controls / 2;
+    let mcx_qubits = |range: std::ops::Range<usize>, ancillas: std::ops::Range<usize>| {
+        let mut qubits = controls[range].to_vec();
+        qubits.push(target);
+        qubits.extend_from_slice(&controls[ancillas]);
+        qubits
+    };
+    let mcx_1 = mcx_qubits(0..k_1, k_1..(2 * k_1).saturating_sub(2).max(k_1));
+    let mcx_2 = mcx_qubits(k_1..num_controls, (k_1 + 2 - k_2).min(k_1)..k_1);
+    let mcx_1_gate = mcx_v_chain(k_1, true);
+    let mcx_2_gate = mcx_v_chain(k_2, true);
+
+    if !is_secondary_diag_real {
+        circuit.push(SynthesisGate::H, &[], &[target]);
+    }
+    for _ in 0..2 {
+        circuit.extend_mapped(mcx_1_gate.clone(), &mcx_1);
+        circuit.push_one_qubit_unitary(s_op.view(), target);
+        circuit.extend_mapped(mcx_2_gate.clone(), &mcx_2);
+        circuit.push_one_qubit_unitary(s_op_inverse.view(), target);
+    }
+    if !is_secondary_diag_real {
+        circuit.push(SynthesisGate::H, &[], &[target]);
+    }
+}
+
+/// The `RZ(lam)` rotation of the last of `num_ctrl_qubits + 1` qubits controlled on all the
+/// others, as in `QuantumCircuit.mcrz` with `use_basis_gates=True`.
+pub fn mcrz(lam: f64, num_ctrl_qubits: usize) -> GateSequence {
+    let mut circuit = GateSequence::new();
+    let target = num_ctrl_qubits;
+    match num_ctrl_qubits {
+        0 => circuit.push(SynthesisGate::RZ, &[lam], &[target]),
+        1 => {
+            circuit.push(SynthesisGate::Phase, &[lam / 2.], &[target]);
+            circuit.push(SynthesisGate::CX, &[], &[0, target]);
+            circuit.push(SynthesisGate::Phase, &[-lam / 2.], &[target]);
+            circuit.push(SynthesisGate::CX, &[], &[0, target]);
+        }
+        _ => {
+            let rz = array![
+                [Complex64::from_polar(1., -lam / 2.), Complex64::new(0., 0.)],
+                [Complex64::new(0., 0.), Complex64::from_polar(1., lam / 2.)]
+            ];
+            let controls = (0..num_ctrl_qubits).collect::<Vec<_>>();
+            push_mcsu2_real_diagonal(&mut circuit, rz.view(), &controls, target);
+        }
+    }
+    circuit
+}
+
+/// The phase `lam` on the last of `num_ctrl_qubits + 1` qubits controlled on all the others, as
+/// in the definition of `MCPhaseGate` with a numeric angle.  The phase is decomposed into
+/// multi-controlled `RZ` rotations on successively fewer qubits.
+pub fn mcphase(lam: f64, num_ctrl_qubits: usize) -> GateSequence {
+    if num_ctrl_qubits <= 1 {
+        return mcphase_gray_code(lam, num_ctrl_qubits);
+    }
+    let mut circuit = GateSequence::new();
+    let mut qubits = (0..=num_ctrl_qubits).collect::<Vec<_>>();
+    for k in 0..num_ctrl_qubits {
+        circuit.extend_mapped(mcrz(lam / 2_f64.powi(k as i32), qubits.len() - 1), &qubits);
+        qubits.pop();
+    }
+    circuit.push(
+        SynthesisGate::Phase,
+        &[lam / 2_f64.powi(num_ctrl_qubits as i32)],
+        &[0],
+    );
+    circuit
+}
+
+/// Synthesize the multi-controlled ``X`` gate of :class:`.MCXGrayCode`.
+///
+/// Args:
+///     num_ctrl_qubits (int): the number of controls.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_ctrl_qubits))]
+pub fn synth_mcx_gray_code(py: Python, num_ctrl_qubits: usize) -> PyResult<(CircuitData, f64)> {
+    mcx_gray_code(num_ctrl_qubits).into_circuit(py, num_ctrl_qubits + 1)
+}
+
+/// Synthesize the multi-controlled ``X`` gate of :class:`.MCXGate`, without ancillas.
+///
+/// Args:
+///     num_ctrl_qubits (int): the number of controls.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_ctrl_qubits))]
+pub fn synth_mcx_noancilla(py: Python, num_ctrl_qubits: usize) -> PyResult<(CircuitData, f64)> {
+    mcx_noancilla(num_ctrl_qubits).into_circuit(py, num_ctrl_qubits + 1)
+}
+
+/// Synthesize the multi-controlled ``X`` gate of :class:`.MCXRecursive`, which uses one dirty
+/// ancilla after the target for more than four controls.
+///
+/// Args:
+///     num_ctrl_qubits (int): the number of controls.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_ctrl_qubits))]
+pub fn synth_mcx_recursive(py: Python, num_ctrl_qubits: usize) -> PyResult<(CircuitData, f64)> {
+    let num_ancillas = usize::from(num_ctrl_qubits > 4);
+    mcx_recursive(num_ctrl_qubits).into_circuit(py, num_ctrl_qubits + 1 + num_ancillas)
+}
+
+/// Synthesize the multi-controlled ``X`` gate of :class:`.MCXVChain`, which uses
+/// ``num_ctrl_qubits - 2`` ancillas after the target.
+///
+/// Args:
+///     num_ctrl_qubits (int): the number of controls.
+///     dirty_ancillas (bool): whether the ancillas may start in any state.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_ctrl_qubits, dirty_ancillas=false))]
+pub fn synth_mcx_v_chain(
+    py: Python,
+    num_ctrl_qubits: usize,
+    dirty_ancillas: bool,
+) -> PyResult<(CircuitData, f64)> {
+    let num_ancillas = num_ctrl_qubits.saturating_sub(2);
+    mcx_v_chain(num_ctrl_qubits, dirty_ancillas)
+        .into_circuit(py, num_ctrl_qubits + 1 + num_ancillas)
+}
+
+/// Synthesize the multi-controlled ``RZ`` rotation of :meth:`.QuantumCircuit.mcrz` with
+/// ``use_basis_gates=True``.
+///
+/// Args:
+///     lam (float): the rotation angle.
+///     num_ctrl_qubits (int): the number of controls.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (lam, num_ctrl_qubits))]
+pub fn synth_mcrz(py: Python, lam: f64, num_ctrl_qubits: usize) -> PyResult<(CircuitData, f64)> {
+    mcrz(lam, num_ctrl_qubits).into_circuit(py, num_ctrl_qubits + 1)
+}
+
+/// Synthesize the multi-controlled phase gate of :class:`.MCPhaseGate` with a numeric angle.
+///
+/// Args:
+///     lam (float): the phase.
+///     num_ctrl_qubits (int): the number of controls.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (lam, num_ctrl_qubits))]
+pub fn synth_mcphase(py: Python, lam: f64, num_ctrl_qubits: usize) -> PyResult<(CircuitData, f64)> {
+    mcphase(lam, num_ctrl_qubits).into_circuit(py, num_ctrl_qubits + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::operator::embed_inner;
+
+    /// The matrix of the gate `mat` on qubit `num_ctrl_qubits` controlled on all the qubits before
+    /// it, with the identity on the qubits after it up to `num_qubits`.
+    fn controlled(
+        mat: ArrayView2<Complex64>,
+        num_ctrl_qubits: usize,
+        num_qubits: usize,
+    ) -> Array2<Complex64> {
+        let dim = 2 << num_ctrl_qubits;
+        let mut small = Array2::<Complex64>::eye(dim);
+        let on = (1 << num_ctrl_qubits) - 1;
+        let indices = [on, on | (1 << num_ctrl_qubits)];
+        for (i, row) in indices.iter().enumerate() {
+            for (j, col) in indices.iter().enumerate() {
+                small[[*row, *col]] = mat[[i, j]];
+            }
+        }
+        let qubits = (0..=num_ctrl_qubits).collect::<Vec<_>>();
+        embed_inner(small.view(), &qubits, num_qubits).unwrap()
+    }
+
+    fn x() -> Array2<Complex64> {
+        array![
+            [Complex64::new(0., 0.), Complex64::new(1., 0.)],
+            [Complex64::new(1., 0.), Complex64::new(0., 0.)]
+        ]
+    }
+
+    fn diag(first: Complex64, second: Complex64) -> Array2<Complex64> {
+        array![
+            [first, Complex64::new(0., 0.)],
+            [Complex64::new(0., 0.), second]
+        ]
+    }
+
+    /// Check that the columns `columns` of the unitary of `sequence` are those of `expected`.
+    fn assert_columns(
+        sequence: &GateSequence,
+        expected: &Array2<Complex64>,
+        columns: impl Iterator<Item = usize>,
+    ) {
+        let num_qubits = expected.nrows().trailing_zeros() as usize;
+        let out = sequence.to_matrix(num_qubits);
+        for col in columns {
+            for (l, r) in out.column(col).iter().zip(expected.column(col)) {
+                assert!((l - r).norm() < 1e-8, "column {col}: {l} != {r}");
+            }
+        }
+    }
+
+    fn assert_equal(sequence: &GateSequence, expected: &Array2<Complex64>) {
+        assert_columns(sequence, expected, 0..expected.ncols());
+    }
+
+    #[test]
+    fn test_mcx_noancilla() {
+        for num_ctrl_qubits in 1..7 {
+            let expected = controlled(x().view(), num_ctrl_qubits, num_ctrl_qubits + 1);
+            assert_equal(&mcx_noancilla(num_ctrl_qubits), &expected);
+            assert_equal(&mcx_gray_code(num_ctrl_qubits), &expected);
+        }
+    }
+
+    #[test]
+    fn test_mcx_recursive() {
+        // Beyond four controls, there is a dirty ancilla, which may start in any state.
+        for num_ctrl_qubits in 1..8 {
+            let num_qubits = num_ctrl_qubits + 1 + usize::from(num_ctrl_qubits > 4);
+            let expected = controlled(x().view(), num_ctrl_qubits, num_qubits);
+            assert_equal(&mcx_recursive(num_ctrl_qubits), &expected);
+        }
+    }
+
+    #[test]
+    fn test_mcx_v_chain() {
+        for num_ctrl_qubits in 1..6 {
+            let num_qubits = num_ctrl_qubits + 1 + num_ctrl_qubits.saturating_sub(2);
+            let expected = controlled(x().view(), num_ctrl_qubits, num_qubits);
+            // Dirty ancillas are restored from any state.
+            assert_equal(&mcx_v_chain(num_ctrl_qubits, true), &expected);
+            // Clean ancillas are only correct if they start in the zero state.
+            let clean = 0..1 << (num_ctrl_qubits + 1);
+            assert_columns(&mcx_v_chain(num_ctrl_qubits, false), &expected, clean);
+        }
+    }
+
+    #[test]
+    fn test_mcphase() {
+        for num_ctrl_qubits in 0..6 {
+            let lam = 0.3 + num_ctrl_qubits as f64;
+            let phase = diag(Complex64::new(1., 0.), Complex64::from_polar(1., lam));
+            let expected = controlled(phase.view(), num_ctrl_qubits, num_ctrl_qubits + 1);
+            assert_equal(&mcphase(lam, num_ctrl_qubits), &expected);
+            assert_equal(&mcphase_gray_code(lam, num_ctrl_qubits), &expected);
+        }
+    }
+
+    #[test]
+    fn test_mcrz() {
+        for num_ctrl_qubits in 0..6 {
+            let lam = -0.7 + num_ctrl_qubits as f64;
+            let rz = diag(
+                Complex64::from_polar(1., -lam / 2.),
+                Complex64::from_polar(1., lam / 2.),
+            );
+            let expected = controlled(rz.view(), num_ctrl_qubits, num_ctrl_qubits + 1);
+            assert_equal(&mcrz(lam, num_ctrl_qubits), &expected);
+        }
+    }
+}
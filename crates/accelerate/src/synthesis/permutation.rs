@@ -0,0 +1,450 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Synthesis of qubit permutations as networks of `SWAP` gates, and of the reversal of the qubits
+//! as a network of `CX` gates for linear nearest-neighbour connectivity.
+//!
+//! A permutation pattern `pattern` follows the Qiskit convention: `pattern[k] = m` when qubit `m`
+//! is moved to position `k`.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+#[cfg(feature = "python")]
+use qiskit_circuit::operations::StandardGate;
+
+use super::{GateSequence, SynthesisGate};
+use crate::error::AccelerateError;
+
+/// A sequence of `SWAP` gates, as pairs of qubits.
+pub type SwapSequence = Vec<(usize, usize)>;
+
+/// Check that `pattern` is a permutation of `0..pattern.len()`, and convert it to indices.
+pub fn validate_permutation(pattern: &[i64]) -> Result<Vec<usize>, AccelerateError> {
+    let n = pattern.len();
+    let mut seen = vec![false; n];
+    pattern
+        .iter()
+        .map(|&x| {
+            if x < 0 || x as usize >= n {
+                return Err(AccelerateError::Value(format!(
+                    "Index {} is out of range for a permutation of {} elements.",
+                    x, n
+                )));
+            }
+            let x = x as usize;
+            if seen[x] {
+                return Err(AccelerateError::Value(format!(
+                    "Index {} appears more than once in the permutation.",
+                    x
+                )));
+            }
+            seen[x] = true;
+            Ok(x)
+        })
+        .collect()
+}
+
+/// The inverse of the permutation `pattern`.
+pub fn invert(pattern: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; pattern.len()];
+    for (i, &x) in pattern.iter().enumerate() {
+        inverse[x] = i;
+    }
+    inverse
+}
+
+/// Sort `pattern` by putting each element in its place with a `SWAP`, if it is not already
+/// there.  A cycle of length `m` takes `m - 1` swaps, so the permutation takes at most `n - 1`.
+///
+/// Applying the returned swaps in order to `0..n` gives `pattern`.
+pub fn get_ordered_swap(pattern: &[usize]) -> SwapSequence {
+    let mut permutation = pattern.to_vec();
+    let mut index_map = invert(pattern);
+    let mut swaps = Vec::with_capacity(pattern.len());
+    for i in 0..permutation.len() {
+        let val = permutation[i];
+        if val != i {
+            let j = index_map[i];
+            swaps.push((i, j));
+            permutation.swap(i, j);
+            index_map[val] = j;
+            index_map[i] = i;
+        }
+    }
+    swaps.reverse();
+    swaps
+}
+
+/// The disjoint cycles of length at least two of `pattern`, in the order in which they are
+/// reached by following the values of `pattern`.
+fn pattern_to_cycles(pattern: &[usize]) -> Vec<Vec<usize>> {
+    let mut explored = vec![false; pattern.len()];
+    let mut cycles = Vec::new();
+    for &start in pattern {
+        let mut cycle = Vec::new();
+        let mut i = start;
+        while !explored[i] {
+            cycle.push(i);
+            explored[i] = true;
+            i = pattern[i];
+        }
+        if cycle.len() >= 2 {
+            cycles.push(cycle);
+        }
+    }
+    cycles
+}
+
+/// Decompose each of the disjoint `cycles` into two layers of `SWAP` gates.
+fn decompose_cycles(cycles: &[Vec<usize>]) -> SwapSequence {
+    let mut swaps = Vec::new();
+    for cycle in cycles {
+        let m = cycle.len();
+        for i in 0..(m - 1) / 2 {
+            swaps.push((cycle[(i + m - 1) % m], cycle[m - 3 - i]));
+        }
+        for i in 0..m / 2 {
+            swaps.push((cycle[(i + m - 1) % m], cycle[m - 2 - i]));
+        }
+    }
+    swaps
+}
+
+/// Synthesize the permutation `pattern` for full connectivity by sorting it, with a `SWAP` gate
+/// per element that is out of place.
+pub fn synth_permutation_basic_inner(pattern: &[usize]) -> SwapSequence {
+    get_ordered_swap(pattern)
+}
+
+/// Synthesize the permutation `pattern` for full connectivity in two layers of `SWAP` gates, with
+/// the method of Alon, Chung and Graham.
+///
+/// # References
+///
+/// 1. N. Alon, F. R. K. Chung, and R. L. Graham, *Routing Permutations on Graphs Via Matchings*,
+///    Proceedings of the Twenty-Fifth Annual ACM Symposium on Theory of Computing (1993),
+///    pages 583–591. [doi:10.1145/167088.167239](https://doi.org/10.1145/167088.167239)
+pub fn synth_permutation_acg_inner(pattern: &[usize]) -> SwapSequence {
+    // The cycles are those of the map from each qubit to its position.
+    decompose_cycles(&pattern_to_cycles(&invert(pattern)))
+}
+
+/// Synthesize the permutation `pattern` for linear nearest-neighbour connectivity with the
+/// odd-even sorting network of Kutin, Moulton and Smithline, of depth at most `n` and with at most
+/// `n(n - 1) / 2` `SWAP` gates.
+///
+/// # References
+///
+/// 1. Samuel A. Kutin, David Petrie Moulton and Lawren M. Smithline, *Computation at a distance*,
+///    [arXiv:quant-ph/0701194](https://arxiv.org/abs/quant-ph/0701194)
+pub fn synth_permutation_depth_lnn_kms_inner(pattern: &[usize]) -> SwapSequence {
+    let num_qubits = pattern.len();
+    let mut positions = invert(pattern);
+    let mut swaps = Vec::new();
+    for layer in 0..num_qubits {
+        for j in (layer % 2..num_qubits.saturating_sub(1)).step_by(2) {
+            if positions[j] > positions[j + 1] {
+                swaps.push((j, j + 1));
+                positions.swap(j, j + 1);
+            }
+        }
+    }
+    swaps
+}
+
+/// Append the first stage of `CX` gates of the reversal of the qubits for linear nearest-neighbour
+/// connectivity.
+pub(crate) fn push_cx_stage1(circuit: &mut GateSequence, num_qubits: usize) {
+    for i in 0..num_qubits / 2 {
+        circuit.push(SynthesisGate::CX, &[], &[2 * i, 2 * i + 1]);
+    }
+    for i in 0..((num_qubits + 1) / 2).saturating_sub(1) {
+        circuit.push(SynthesisGate::CX, &[], &[2 * i + 2, 2 * i + 1]);
+    }
+}
+
+/// Append the second stage of `CX` gates of the reversal of the qubits for linear
+/// nearest-neighbour connectivity.
+pub(crate) fn push_cx_stage2(circuit: &mut GateSequence, num_qubits: usize) {
+    for i in 0..num_qubits / 2 {
+        circuit.push(SynthesisGate::CX, &[], &[2 * i + 1, 2 * i]);
+    }
+    for i in 0..((num_qubits + 1) / 2).saturating_sub(1) {
+        circuit.push(SynthesisGate::CX, &[], &[2 * i + 1, 2 * i + 2]);
+    }
+}
+
+/// Synthesize the reversal of `num_qubits` qubits for linear nearest-neighbour connectivity with
+/// `CX` gates in depth `2n + 2`, with the method of Kutin, Moulton and Smithline.
+///
+/// # References
+///
+/// 1. Samuel A. Kutin, David Petrie Moulton and Lawren M. Smithline, *Computation at a distance*,
+///    Chicago J. Theor. Comput. Sci., vol. 2007 (2007),
+///    [arXiv:quant-ph/0701194](https://arxiv.org/abs/quant-ph/0701194)
+pub fn synth_permutation_reverse_lnn_kms_inner(num_qubits: usize) -> GateSequence {
+    let mut circuit = GateSequence::new();
+    for _ in 0..(num_qubits + 1) / 2 {
+        push_cx_stage1(&mut circuit, num_qubits);
+        push_cx_stage2(&mut circuit, num_qubits);
+    }
+    if num_qubits % 2 == 0 {
+        push_cx_stage1(&mut circuit, num_qubits);
+    }
+    circuit
+}
+
+#[cfg(feature = "python")]
+fn swap_sequence_to_circuit(
+    py: Python,
+    num_qubits: usize,
+    swaps: SwapSequence,
+) -> PyResult<CircuitData> {
+    CircuitData::from_standard_gates(
+        py,
+        num_qubits as u32,
+        swaps.into_iter().map(|(a, b)| {
+            (
+                StandardGate::SwapGate,
+                vec![],
+                [a as u32, b as u32].into_iter().collect(),
+            )
+        }),
+    )
+}
+
+#[cfg(feature = "python")]
+fn extract_pattern(pattern: PyReadonlyArray1<i64>) -> PyResult<Vec<usize>> {
+    Ok(validate_permutation(&pattern.as_array().to_vec())?)
+}
+
+/// Sort a permutation pattern with ``SWAP`` gates, putting each element in its place in turn.
+///
+/// Args:
+///     pattern (ndarray): the permutation pattern.
+///
+/// Returns:
+///     list[tuple[int, int]]: the swaps which, applied in order to ``range(n)``, give ``pattern``.
+///
+/// Raises:
+///     ValueError: if ``pattern`` is not a permutation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "_get_ordered_swap", signature = (pattern))]
+pub fn py_get_ordered_swap(pattern: PyReadonlyArray1<i64>) -> PyResult<SwapSequence> {
+    let pattern = extract_pattern(pattern)?;
+    Ok(get_ordered_swap(&pattern))
+}
+
+/// The inverse of a permutation pattern.
+///
+/// Args:
+///     pattern (ndarray): the permutation pattern.
+///
+/// Returns:
+///     list[int]: the inverse pattern.
+///
+/// Raises:
+///     ValueError: if ``pattern`` is not a permutation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "_inverse_pattern", signature = (pattern))]
+pub fn py_inverse_pattern(pattern: PyReadonlyArray1<i64>) -> PyResult<Vec<usize>> {
+    let pattern = extract_pattern(pattern)?;
+    Ok(invert(&pattern))
+}
+
+/// Synthesize a permutation for full connectivity by sorting, with at most ``n - 1`` ``SWAP``
+/// gates.
+///
+/// Args:
+///     pattern (ndarray): the permutation pattern.
+///
+/// Returns:
+///     CircuitData: the ``SWAP`` circuit.
+///
+/// Raises:
+///     ValueError: if ``pattern`` is not a permutation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (pattern))]
+pub fn synth_permutation_basic(
+    py: Python,
+    pattern: PyReadonlyArray1<i64>,
+) -> PyResult<CircuitData> {
+    let pattern = extract_pattern(pattern)?;
+    swap_sequence_to_circuit(py, pattern.len(), synth_permutation_basic_inner(&pattern))
+}
+
+/// Synthesize a permutation for full connectivity in ``SWAP`` depth two, with the method of Alon,
+/// Chung and Graham.
+///
+/// Args:
+///     pattern (ndarray): the permutation pattern.
+///
+/// Returns:
+///     CircuitData: the ``SWAP`` circuit.
+///
+/// Raises:
+///     ValueError: if ``pattern`` is not a permutation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (pattern))]
+pub fn synth_permutation_acg(py: Python, pattern: PyReadonlyArray1<i64>) -> PyResult<CircuitData> {
+    let pattern = extract_pattern(pattern)?;
+    swap_sequence_to_circuit(py, pattern.len(), synth_permutation_acg_inner(&pattern))
+}
+
+/// Synthesize a permutation for linear nearest-neighbour connectivity in ``SWAP`` depth at most
+/// ``n``, with the method of Kutin, Moulton and Smithline.
+///
+/// Args:
+///     pattern (ndarray): the permutation pattern.
+///
+/// Returns:
+///     CircuitData: the ``SWAP`` circuit.
+///
+/// Raises:
+///     ValueError: if ``pattern`` is not a permutation.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (pattern))]
+pub fn synth_permutation_depth_lnn_kms(
+    py: Python,
+    pattern: PyReadonlyArray1<i64>,
+) -> PyResult<CircuitData> {
+    let pattern = extract_pattern(pattern)?;
+    swap_sequence_to_circuit(
+        py,
+        pattern.len(),
+        synth_permutation_depth_lnn_kms_inner(&pattern),
+    )
+}
+
+/// Synthesize the reversal of the qubits for linear nearest-neighbour connectivity with ``CX``
+/// gates in depth ``2n + 2``, with the method of Kutin, Moulton and Smithline.
+///
+/// Args:
+///     num_qubits (int): the number of qubits.
+///
+/// Returns:
+///     CircuitData: the ``CX`` circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (num_qubits))]
+pub fn synth_permutation_reverse_lnn_kms(py: Python, num_qubits: usize) -> PyResult<CircuitData> {
+    let (data, _) =
+        synth_permutation_reverse_lnn_kms_inner(num_qubits).into_circuit(py, num_qubits)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ndarray::Array2;
+    use rand::prelude::*;
+
+    use super::super::linear::row_op;
+    use crate::rng::generator;
+
+    fn random_pattern(num_qubits: usize, seed: u128) -> Vec<usize> {
+        let mut pattern = (0..num_qubits).collect::<Vec<_>>();
+        pattern.shuffle(&mut generator(seed));
+        pattern
+    }
+
+    /// The result of applying `swaps` in order to `0..num_qubits`.
+    fn apply_swaps(swaps: &SwapSequence, num_qubits: usize) -> Vec<usize> {
+        let mut out = (0..num_qubits).collect::<Vec<_>>();
+        for &(a, b) in swaps {
+            out.swap(a, b);
+        }
+        out
+    }
+
+    /// The depth of the two-qubit gates on `pairs`.
+    fn depth(pairs: impl Iterator<Item = (usize, usize)>, num_qubits: usize) -> usize {
+        let mut depths = vec![0; num_qubits];
+        for (a, b) in pairs {
+            let depth = depths[a].max(depths[b]) + 1;
+            depths[a] = depth;
+            depths[b] = depth;
+        }
+        depths.into_iter().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_validate_permutation() {
+        assert_eq!(validate_permutation(&[2, 0, 1]).unwrap(), vec![2, 0, 1]);
+        assert!(validate_permutation(&[]).unwrap().is_empty());
+        for bad in [&[0, 3, 1][..], &[0, -1, 1], &[0, 1, 1]] {
+            assert!(matches!(
+                validate_permutation(bad),
+                Err(AccelerateError::Value(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invert() {
+        let pattern = random_pattern(12, 1);
+        let inverse = invert(&pattern);
+        for (i, &x) in pattern.iter().enumerate() {
+            assert_eq!(inverse[x], i);
+        }
+    }
+
+    #[test]
+    fn test_swap_networks() {
+        for num_qubits in 0..12 {
+            let pattern = random_pattern(num_qubits, 7 + num_qubits as u128);
+
+            let basic = synth_permutation_basic_inner(&pattern);
+            assert_eq!(apply_swaps(&basic, num_qubits), pattern);
+            assert!(basic.len() < num_qubits.max(1));
+
+            let acg = synth_permutation_acg_inner(&pattern);
+            assert_eq!(apply_swaps(&acg, num_qubits), pattern);
+            assert!(depth(acg.iter().copied(), num_qubits) <= 2);
+
+            let kms = synth_permutation_depth_lnn_kms_inner(&pattern);
+            assert_eq!(apply_swaps(&kms, num_qubits), pattern);
+            assert!(kms.iter().all(|&(a, b)| b == a + 1));
+            assert!(depth(kms.iter().copied(), num_qubits) <= num_qubits);
+            assert!(kms.len() <= num_qubits * num_qubits.saturating_sub(1) / 2);
+        }
+    }
+
+    #[test]
+    fn test_reverse_lnn_kms() {
+        for num_qubits in 1..12 {
+            let circuit = synth_permutation_reverse_lnn_kms_inner(num_qubits);
+            let mut mat = Array2::from_shape_fn((num_qubits, num_qubits), |(i, j)| i == j);
+            let mut pairs = Vec::with_capacity(circuit.gates.len());
+            for (gate, _, qubits) in &circuit.gates {
+                assert_eq!(*gate, SynthesisGate::CX);
+                assert_eq!(qubits[0].abs_diff(qubits[1]), 1);
+                row_op(&mut mat.view_mut(), qubits[0], qubits[1]);
+                pairs.push((qubits[0], qubits[1]));
+            }
+            let reversal =
+                Array2::from_shape_fn((num_qubits, num_qubits), |(i, j)| i + j == num_qubits - 1);
+            assert_eq!(mat, reversal);
+            assert!(depth(pairs.into_iter(), num_qubits) <= 2 * num_qubits + 2);
+        }
+    }
+}
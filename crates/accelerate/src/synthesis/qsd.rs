@@ -0,0 +1,437 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Quantum Shannon decomposition of `n`-qubit unitaries (Shende, Bullock and Markov,
+//! arXiv:quant-ph/0406176).
+//!
+//! An `n`-qubit unitary is split by a cosine-sine decomposition into a multiplexed `RY` between two
+//! multiplexed `(n - 1)`-qubit unitaries, and each multiplexed unitary is demultiplexed into a
+//! multiplexed `RZ` between two plain `(n - 1)`-qubit unitaries, recursively down to two qubits.
+//! The two-qubit blocks use the Weyl-chamber based [TwoQubitBasisDecomposer].  This is a port of
+//! `qiskit.synthesis.qs_decomposition` with the default decomposers, including its optimizations
+//! A.1 (merging a `CZ` of each multiplexed `RY` into the neighbouring unitary) and A.2 (decomposing
+//! the two-qubit blocks up to a diagonal, which is absorbed by the next block).
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use faer_ext::IntoFaerComplex;
+use ndarray::prelude::*;
+use num_complex::Complex64;
+use smallvec::SmallVec;
+
+use super::cossin::{cossin_inner, CosSin};
+use super::{GateSequence, SynthesisGate};
+use crate::error::AccelerateError;
+use crate::euler_one_qubit_decomposer::EulerBasis;
+use crate::operator::unitary_eig;
+use crate::two_qubit_decompose::{TwoQubitBasisDecomposer, TwoQubitGateSequence};
+
+/// The tolerances of `numpy.allclose`, used to detect identity blocks.
+const IDENTITY_RTOL: f64 = 1e-5;
+const IDENTITY_ATOL: f64 = 1e-8;
+
+const C0: Complex64 = Complex64::new(0., 0.);
+const C1: Complex64 = Complex64::new(1., 0.);
+
+/// The matrix of `CX` with qubit 0 as the control, little-endian.
+const CX_MATRIX: [[Complex64; 4]; 4] = [
+    [C1, C0, C0, C0],
+    [C0, C0, C0, C1],
+    [C0, C0, C1, C0],
+    [C0, C1, C0, C0],
+];
+
+#[inline]
+fn adjoint(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    mat.t().mapv(|x| x.conj())
+}
+
+fn is_identity(mat: ArrayView2<Complex64>) -> bool {
+    mat.indexed_iter().all(|((i, j), x)| {
+        let expected = if i == j { C1 } else { C0 };
+        (x - expected).norm() <= IDENTITY_ATOL + IDENTITY_RTOL * expected.norm()
+    })
+}
+
+/// The decomposition of a two-qubit unitary `mat` as `diag(d) · C`, where `C` needs only two `CX`
+/// gates.  Returns `d` and `C`.  This is `TwoQubitDecomposeUpToDiagonal`.
+fn decompose_up_to_diagonal(
+    decomposer: &TwoQubitBasisDecomposer,
+    mat: ArrayView2<Complex64>,
+) -> Result<([Complex64; 4], GateSequence), AccelerateError> {
+    let det = mat.into_faer_complex().determinant();
+    let phase_factor = Complex64::new(det.re, det.im).powf(-0.25).conj();
+    let su4 = mat.mapv(|x| x / phase_factor);
+    let m = |i: usize, j: usize| su4[[i, j]];
+    let a1 = -m(1, 3) * m(2, 0) + m(1, 2) * m(2, 1) + m(1, 1) * m(2, 2) - m(1, 0) * m(2, 3);
+    let a2 = m(0, 3) * m(3, 0) - m(0, 2) * m(3, 1) - m(0, 1) * m(3, 2) + m(0, 0) * m(3, 3);
+    let psi = (a1.im + a2.im).atan2(a1.re - a2.re);
+    let real_map = [
+        C1,
+        C1,
+        Complex64::from_polar(1., -psi),
+        Complex64::from_polar(1., psi),
+    ];
+    let mut mapped = su4;
+    for (mut row, scale) in mapped.rows_mut().into_iter().zip(real_map) {
+        row.mapv_inplace(|x| x * scale);
+    }
+    let mut circuit = decompose_two_qubit(decomposer, mapped.view())?;
+    circuit.global_phase += phase_factor.arg();
+    Ok((real_map.map(|x| x.conj()), circuit))
+}
+
+fn decompose_two_qubit(
+    decomposer: &TwoQubitBasisDecomposer,
+    mat: ArrayView2<Complex64>,
+) -> Result<GateSequence, AccelerateError> {
+    let TwoQubitGateSequence {
+        gates,
+        global_phase,
+    } = decomposer.call_inner(mat, None, true, None)?;
+    let mut out = GateSequence::new();
+    out.global_phase = global_phase;
+    for (name, params, qubits) in gates {
+        let gate = match name.as_str() {
+            "u" => SynthesisGate::U,
+            "cx" => SynthesisGate::CX,
+            _ => unreachable!("the CX decomposer in the U basis doesn't emit {name}"),
+        };
+        let qubits = qubits.iter().map(|q| *q as usize).collect::<Vec<_>>();
+        out.push(gate, &params, &qubits);
+    }
+    Ok(out)
+}
+
+/// A step of the decomposition: either a gate or a two-qubit unitary on qubits 0 and 1 that is
+/// synthesized at the end, so optimization A.2 can pass diagonals between neighbouring blocks.
+enum Block {
+    Gate(SynthesisGate, SmallVec<[f64; 3]>, SmallVec<[usize; 2]>),
+    TwoQubit(Array2<Complex64>),
+}
+
+struct ShannonDecomposer {
+    opt_a1: bool,
+    opt_a2: bool,
+    decomposer_2q: TwoQubitBasisDecomposer,
+    blocks: Vec<Block>,
+    global_phase: f64,
+}
+
+impl ShannonDecomposer {
+    fn sequence(&mut self, sequence: GateSequence) {
+        self.global_phase += sequence.global_phase;
+        self.blocks.extend(
+            sequence
+                .gates
+                .into_iter()
+                .map(|(gate, params, qubits)| Block::Gate(gate, params, qubits)),
+        );
+    }
+
+    /// Decompose `mat`, which acts on the lowest qubits.
+    fn decompose(
+        &mut self,
+        mat: ArrayView2<Complex64>,
+        depth: usize,
+    ) -> Result<(), AccelerateError> {
+        if is_identity(mat) {
+            return Ok(());
+        }
+        let dim = mat.nrows();
+        match dim {
+            2 => {
+                let mut sequence = GateSequence::new();
+                sequence.push_one_qubit_unitary(mat, 0);
+                self.sequence(sequence);
+            }
+            4 if self.opt_a2 && depth > 0 => self.blocks.push(Block::TwoQubit(mat.to_owned())),
+            4 => {
+                let sequence = decompose_two_qubit(&self.decomposer_2q, mat)?;
+                self.sequence(sequence);
+            }
+            _ => {
+                let num_qubits = dim.trailing_zeros() as usize;
+                let CosSin {
+                    u1,
+                    mut u2,
+                    theta,
+                    v1h,
+                    v2h,
+                } = cossin_inner(mat)?;
+                self.demultiplex(v1h.view(), v2h.view(), depth)?;
+                let angles = theta.iter().map(|t| 2. * t).collect::<Vec<_>>();
+                if self.opt_a1 {
+                    // The last `CZ` of the multiplexed `RY` is absorbed into `u2`.
+                    self.uc_rotation(SynthesisGate::RY, &angles, num_qubits, SynthesisGate::CZ);
+                    let half = theta.len() / 2;
+                    u2.slice_mut(s![.., half..]).mapv_inplace(|x| -x);
+                } else {
+                    self.uc_rotation(SynthesisGate::RY, &angles, num_qubits, SynthesisGate::CX);
+                }
+                self.demultiplex(u1.view(), u2.view(), depth)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decompose the multiplexor `diag(um0, um1)`, controlled by the highest qubit, into
+    /// `(I ⊗ V) · multiplexed RZ · (I ⊗ W)`.
+    fn demultiplex(
+        &mut self,
+        um0: ArrayView2<Complex64>,
+        um1: ArrayView2<Complex64>,
+        depth: usize,
+    ) -> Result<(), AccelerateError> {
+        let num_qubits = (2 * um0.nrows()).trailing_zeros() as usize;
+        let (eigvals, vmat) = unitary_eig(um0.dot(&adjoint(um1)).view());
+        let dvals = eigvals.mapv(|x| x.sqrt());
+        let mut wmat = adjoint(vmat.view()).dot(&um1);
+        for (mut row, d) in wmat.rows_mut().into_iter().zip(dvals.iter()) {
+            row.mapv_inplace(|x| x * d);
+        }
+        self.decompose(wmat.view(), depth + 1)?;
+        let angles = dvals
+            .iter()
+            .map(|d| 2. * d.conj().arg())
+            .collect::<Vec<_>>();
+        self.uc_rotation(SynthesisGate::RZ, &angles, num_qubits, SynthesisGate::CX);
+        self.decompose(vmat.view(), depth + 1)
+    }
+
+    /// A rotation `gate` of the highest of `num_qubits` qubits, multiplexed by the others.
+    fn uc_rotation(
+        &mut self,
+        gate: SynthesisGate,
+        angles: &[f64],
+        num_qubits: usize,
+        entangler: SynthesisGate,
+    ) {
+        let qubits = std::iter::once(num_qubits - 1)
+            .chain(0..num_qubits - 1)
+            .collect::<Vec<_>>();
+        let mut sequence = GateSequence::new();
+        // With `CZ`, the last entangler is absorbed by the caller.
+        let outer_entangler = entangler == SynthesisGate::CX;
+        sequence.push_uc_rotation(gate, angles, &qubits, entangler, false, outer_entangler);
+        self.sequence(sequence);
+    }
+
+    /// Synthesize the deferred two-qubit blocks and return the full gate sequence.  With A.2, each
+    /// block but the last is decomposed up to a diagonal, which commutes through the multiplexor
+    /// controls in between and is absorbed into the next block.
+    fn finish(mut self) -> Result<GateSequence, AccelerateError> {
+        let deferred = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| matches!(block, Block::TwoQubit(_)))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let mut synthesized = vec![GateSequence::new(); self.blocks.len()];
+        for (position, &index) in deferred.iter().enumerate() {
+            let Block::TwoQubit(mat) = &self.blocks[index] else {
+                unreachable!("only two-qubit blocks are deferred")
+            };
+            let sequence = match deferred.get(position + 1) {
+                Some(&next) => {
+                    let (diagonal, sequence) =
+                        decompose_up_to_diagonal(&self.decomposer_2q, mat.view())?;
+                    let Block::TwoQubit(next_mat) = &mut self.blocks[next] else {
+                        unreachable!("only two-qubit blocks are deferred")
+                    };
+                    for (mut col, d) in next_mat.columns_mut().into_iter().zip(diagonal) {
+                        col.mapv_inplace(|x| x * d);
+                    }
+                    sequence
+                }
+                None => decompose_two_qubit(&self.decomposer_2q, mat.view())?,
+            };
+            synthesized[index] = sequence;
+        }
+        let mut out = GateSequence::new();
+        out.global_phase = self.global_phase;
+        for (block, sequence) in self.blocks.into_iter().zip(synthesized) {
+            match block {
+                Block::Gate(gate, params, qubits) => out.gates.push((gate, params, qubits)),
+                Block::TwoQubit(_) => out.extend_mapped(sequence, &[0, 1]),
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Decompose the unitary `mat` on `n` qubits with the quantum Shannon decomposition.
+///
+/// `opt_a1` and `opt_a2` enable the optimizations A.1 and A.2 of Shende et al.; with A.1 the
+/// output contains `CZ` gates as well as `CX`.  The output uses `U`, `RY`, `RZ`, `CX` and `CZ`.
+pub fn qs_decomposition_inner(
+    mat: ArrayView2<Complex64>,
+    opt_a1: bool,
+    opt_a2: bool,
+) -> Result<GateSequence, AccelerateError> {
+    let dim = mat.nrows();
+    if dim != mat.ncols() || dim < 2 || !dim.is_power_of_two() {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} is not an n-qubit unitary",
+            mat.shape()
+        )));
+    }
+    let decomposer_2q = TwoQubitBasisDecomposer::new_inner(
+        "cx".to_string(),
+        aview2(&CX_MATRIX),
+        1.0,
+        EulerBasis::U,
+        None,
+    )?;
+    let mut decomposer = ShannonDecomposer {
+        opt_a1,
+        opt_a2,
+        decomposer_2q,
+        blocks: Vec::new(),
+        global_phase: 0.,
+    };
+    decomposer.decompose(mat, 0)?;
+    decomposer.finish()
+}
+
+/// Decompose a unitary with the quantum Shannon decomposition.
+///
+/// Args:
+///     mat (ndarray): the unitary to decompose.
+///     opt_a1 (bool): whether to use optimization A.1 of Shende et al., which leaves :class:`.CZGate`
+///         instances in the output.
+///     opt_a2 (bool): whether to use optimization A.2 of Shende et al., which decomposes the
+///         two-qubit blocks up to diagonals.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (mat, opt_a1=true, opt_a2=true))]
+pub fn qs_decomposition(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+    opt_a1: bool,
+    opt_a2: bool,
+) -> PyResult<(CircuitData, f64)> {
+    let mat = mat.as_array();
+    let num_qubits = mat.nrows().trailing_zeros() as usize;
+    let sequence = qs_decomposition_inner(mat, opt_a1, opt_a2)?;
+    sequence.into_circuit(py, num_qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+    use crate::synthesis::test::assert_implements;
+
+    const OPTIONS: [(bool, bool); 4] = [(false, false), (true, false), (false, true), (true, true)];
+
+    fn num_entanglers(sequence: &GateSequence) -> usize {
+        sequence
+            .gates
+            .iter()
+            .filter(|(gate, _, _)| matches!(gate, SynthesisGate::CX | SynthesisGate::CZ))
+            .count()
+    }
+
+    #[test]
+    fn test_random_unitaries() {
+        for num_qubits in 1..5 {
+            let mat = random_unitary_inner(1 << num_qubits, &mut generator(num_qubits as u128));
+            for (opt_a1, opt_a2) in OPTIONS {
+                let sequence = qs_decomposition_inner(mat.view(), opt_a1, opt_a2).unwrap();
+                assert_implements(&sequence, mat.view(), num_qubits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_entangler_counts() {
+        for num_qubits in 3..5 {
+            let mat = random_unitary_inner(1 << num_qubits, &mut generator(17));
+            let (small, large) = (1 << num_qubits, 1 << (2 * num_qubits));
+            // `9/16 4^n - 3/2 2^n` without the optimizations, and `23/48 4^n - 3/2 2^n + 4/3` with
+            // both of them.
+            let plain = qs_decomposition_inner(mat.view(), false, false).unwrap();
+            assert!(num_entanglers(&plain) <= 9 * large / 16 - 3 * small / 2);
+            let optimized = qs_decomposition_inner(mat.view(), true, true).unwrap();
+            assert_eq!(
+                num_entanglers(&optimized),
+                (23 * large - 72 * small + 64) / 48
+            );
+        }
+    }
+
+    #[test]
+    fn test_gate_set() {
+        let mat = random_unitary_inner(8, &mut generator(5));
+        let plain = qs_decomposition_inner(mat.view(), false, true).unwrap();
+        assert!(plain.gates.iter().all(|(gate, _, _)| matches!(
+            gate,
+            SynthesisGate::U | SynthesisGate::RY | SynthesisGate::RZ | SynthesisGate::CX
+        )));
+        let a1 = qs_decomposition_inner(mat.view(), true, true).unwrap();
+        assert!(a1
+            .gates
+            .iter()
+            .any(|(gate, _, _)| *gate == SynthesisGate::CZ));
+    }
+
+    #[test]
+    fn test_identity() {
+        let mat = Array2::<Complex64>::eye(8);
+        let sequence = qs_decomposition_inner(mat.view(), true, true).unwrap();
+        assert!(sequence.gates.is_empty());
+        assert_eq!(sequence.global_phase, 0.);
+    }
+
+    #[test]
+    fn test_diagonal_and_real() {
+        let diag = Array2::from_diag(&Array1::from_shape_fn(8, |i| {
+            Complex64::from_polar(1., 0.3 * i as f64 + 0.1)
+        }));
+        // A real orthogonal matrix: the unitary of a sequence of `RY` and `CX` gates.
+        let mut real = GateSequence::new();
+        for (i, angle) in [0.4, 1.3, -0.7].into_iter().enumerate() {
+            real.push(SynthesisGate::RY, &[angle], &[i]);
+            real.push(SynthesisGate::CX, &[], &[i, (i + 1) % 3]);
+        }
+        let real = real.to_matrix(3);
+        for mat in [diag, real] {
+            for (opt_a1, opt_a2) in OPTIONS {
+                let sequence = qs_decomposition_inner(mat.view(), opt_a1, opt_a2).unwrap();
+                assert_implements(&sequence, mat.view(), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_a_unitary_of_qubits() {
+        for shape in [(3, 3), (2, 4), (1, 1)] {
+            let mat = Array2::<Complex64>::zeros(shape);
+            assert!(matches!(
+                qs_decomposition_inner(mat.view(), true, true),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! State preparation with multiplexed rotations (Shende, Bullock and Markov,
+//! arXiv:quant-ph/0406176, section 4).
+//!
+//! The state is disentangled one qubit at a time, starting from the least significant: a
+//! multiplexed `RZ` equalizes the phases of each pair of amplitudes that differ only in the
+//! current qubit, and a multiplexed `RY` then moves the weight of the pair onto its first member.
+//! The preparation circuit is the inverse of this disentangler.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray1;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use num_complex::Complex64;
+
+use super::{GateSequence, SynthesisGate, ANGLE_EPS};
+use crate::error::AccelerateError;
+
+/// Amplitude pairs with a smaller norm than this are treated as zero.
+const NORM_EPS: f64 = 1e-10;
+
+/// The rotations that take the pair of amplitudes `(a, b)` to `(r e^{it/2}, 0)`.  Returns the
+/// remaining amplitude, and the angles of the `RY` and `RZ` rotations.
+fn bloch_angles(a: Complex64, b: Complex64) -> (Complex64, f64, f64) {
+    let r = a.norm().hypot(b.norm());
+    if r < NORM_EPS {
+        return (Complex64::new(0., 0.), 0., 0.);
+    }
+    let theta = 2. * (a.norm() / r).min(1.).acos();
+    let (a_arg, b_arg) = (a.arg(), b.arg());
+    let t = a_arg + b_arg;
+    let phi = b_arg - a_arg;
+    (Complex64::from_polar(r, t / 2.), -theta, -phi)
+}
+
+/// Synthesize a circuit that prepares the normalized state `amplitudes` from the all-zero state.
+///
+/// The output uses `RY`, `RZ` and `CX` gates.
+pub fn state_preparation_inner(amplitudes: &[Complex64]) -> Result<GateSequence, AccelerateError> {
+    let dim = amplitudes.len();
+    if dim < 2 || !dim.is_power_of_two() {
+        return Err(AccelerateError::Qiskit(
+            "Desired statevector length not a positive power of 2.".to_string(),
+        ));
+    }
+    let num_qubits = dim.trailing_zeros() as usize;
+    let mut disentangler = GateSequence::new();
+    let mut remaining = amplitudes.to_vec();
+    for qubit in 0..num_qubits {
+        let len = remaining.len() / 2;
+        let mut thetas = Vec::with_capacity(len);
+        let mut phis = Vec::with_capacity(len);
+        for i in 0..len {
+            let (amplitude, theta, phi) = bloch_angles(remaining[2 * i], remaining[2 * i + 1]);
+            remaining[i] = amplitude;
+            thetas.push(theta);
+            phis.push(phi);
+        }
+        remaining.truncate(len);
+        let qubits = (qubit..num_qubits).collect::<Vec<_>>();
+        let has_phis = phis.iter().any(|x| x.abs() > ANGLE_EPS);
+        let has_thetas = thetas.iter().any(|x| x.abs() > ANGLE_EPS);
+        // The `RY` multiplexor is laid out in reverse, so that the entanglers between the two
+        // multiplexors are equal and cancel.
+        let outer_entangler = !(has_phis && has_thetas);
+        if has_phis {
+            disentangler.push_uc_rotation(
+                SynthesisGate::RZ,
+                &phis,
+                &qubits,
+                SynthesisGate::CX,
+                false,
+                outer_entangler,
+            );
+        }
+        if has_thetas {
+            disentangler.push_uc_rotation(
+                SynthesisGate::RY,
+                &thetas,
+                &qubits,
+                SynthesisGate::CX,
+                true,
+                outer_entangler,
+            );
+        }
+    }
+    disentangler.global_phase -= remaining[0].arg();
+    Ok(disentangler.inverse())
+}
+
+/// Synthesize the preparation of a state from the all-zero state with multiplexed rotations.
+///
+/// Args:
+///     amplitudes (ndarray): the normalized complex amplitudes of the state.
+///
+/// Returns:
+///     (CircuitData, float): the circuit and its global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (amplitudes))]
+pub fn synth_state_preparation(
+    py: Python,
+    amplitudes: PyReadonlyArray1<Complex64>,
+) -> PyResult<(CircuitData, f64)> {
+    let amplitudes = amplitudes.as_array().to_vec();
+    let num_qubits = amplitudes.len().trailing_zeros() as usize;
+    let sequence = state_preparation_inner(&amplitudes)?;
+    sequence.into_circuit(py, num_qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::random_quantum_info::random_statevector_inner;
+    use crate::rng::generator;
+
+    /// Check that `amplitudes` is prepared from the all-zero state, including its global phase.
+    fn assert_prepares(amplitudes: &[Complex64]) {
+        let num_qubits = amplitudes.len().trailing_zeros() as usize;
+        let sequence = state_preparation_inner(amplitudes).unwrap();
+        assert!(sequence.gates.iter().all(|(gate, _, _)| matches!(
+            gate,
+            SynthesisGate::RY | SynthesisGate::RZ | SynthesisGate::CX
+        )));
+        let out = sequence.to_matrix(num_qubits);
+        for (l, r) in out.column(0).iter().zip(amplitudes) {
+            assert!(
+                (l - r).norm() < 1e-10,
+                "{} != {:?}",
+                out.column(0),
+                amplitudes
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_states() {
+        for num_qubits in 1..6 {
+            let state =
+                random_statevector_inner(1 << num_qubits, &mut generator(num_qubits as u128));
+            assert_prepares(state.as_slice().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sparse_states() {
+        let zero = Complex64::new(0., 0.);
+        for index in 0..8 {
+            let mut state = vec![zero; 8];
+            state[index] = Complex64::from_polar(1., 0.3 * index as f64);
+            assert_prepares(&state);
+        }
+        // A GHZ state with a relative phase, and a real state with negative amplitudes.
+        let mut ghz = vec![zero; 16];
+        ghz[0] = Complex64::new(0.5_f64.sqrt(), 0.);
+        ghz[15] = Complex64::new(0., -(0.5_f64.sqrt()));
+        assert_prepares(&ghz);
+        let real = [0.5, -0.5, 0., 0.5, 0., 0., -0.5, 0.].map(|x| Complex64::new(x, 0.));
+        assert_prepares(&real);
+    }
+
+    #[test]
+    fn test_bad_length() {
+        for len in [0, 1, 3, 6] {
+            let state = vec![Complex64::new(1., 0.); len];
+            assert!(matches!(
+                state_preparation_inner(&state),
+                Err(AccelerateError::Qiskit(_))
+            ));
+        }
+    }
+}
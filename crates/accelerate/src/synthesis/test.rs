@@ -0,0 +1,35 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Helpers shared by the tests of the synthesis routines.
+
+use approx::assert_abs_diff_eq;
+use ndarray::{s, ArrayView2};
+use num_complex::Complex64;
+
+use super::GateSequence;
+
+/// Assert that `sequence` on `num_qubits` qubits implements `expected`, including its global
+/// phase.  If `expected` has fewer columns than the unitary of the sequence, as an isometry does,
+/// it's compared with the leading columns only.
+pub fn assert_implements(
+    sequence: &GateSequence,
+    expected: ArrayView2<Complex64>,
+    num_qubits: usize,
+) {
+    let out = sequence.to_matrix(num_qubits);
+    assert_abs_diff_eq!(
+        out.slice(s![.., ..expected.ncols()]),
+        expected,
+        epsilon = 1e-8
+    );
+}
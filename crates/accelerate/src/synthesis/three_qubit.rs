@@ -0,0 +1,208 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Decomposition of three-qubit unitaries, as produced by `ConsolidateBlocks`.
+//!
+//! The Khaneja-Glaser decomposition of `SU(8)` splits a unitary on one of its qubits into
+//! `K1 A K2`, where the `K` are multiplexed two-qubit unitaries controlled by that qubit and `A` is
+//! a multiplexed `RY` rotation on it.  A second Cartan decomposition splits each multiplexor into
+//! two plain two-qubit unitaries around a multiplexed `RZ` rotation, and the four two-qubit
+//! unitaries are decomposed in the Weyl chamber with at most three `CX` each.  This is the
+//! structure of the quantum Shannon decomposition, which already implements it with the
+//! optimizations of Shende et al., for at most 20 `CX` gates.
+//!
+//! On top of it, this decomposition detects unitaries that are a tensor product of a two-qubit and
+//! a single-qubit unitary, which need at most three `CX`, and otherwise tries the split on each of
+//! the three qubits and keeps the one with the fewest two-qubit gates.  Blocks with a simple
+//! structure on a well-chosen qubit, such as a two-qubit unitary followed by a controlled gate,
+//! then need much fewer than 20 gates.
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::CircuitData;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+
+use super::qsd::qs_decomposition_inner;
+use super::GateSequence;
+use crate::error::AccelerateError;
+
+/// The largest deviation, in any element, of the reconstructed tensor product from the input for
+/// it to be considered a product.
+const PRODUCT_TOL: f64 = 1e-12;
+
+/// The unitary `mat` on three qubits, with its qubits reordered so that qubit `i` of the result is
+/// qubit `order[i]` of `mat`.
+fn permute_qubits(mat: ArrayView2<Complex64>, order: [usize; 3]) -> Array2<Complex64> {
+    let original = |index: usize| {
+        (0..3)
+            .map(|i| ((index >> i) & 1) << order[i])
+            .sum::<usize>()
+    };
+    Array2::from_shape_fn((8, 8), |(i, j)| mat[[original(i), original(j)]])
+}
+
+/// The factors `(V, W)` of `mat = W ⊗ V`, with `W` on the highest qubit, if `mat` is a tensor
+/// product.
+fn split_highest_qubit(
+    mat: ArrayView2<Complex64>,
+) -> Option<(Array2<Complex64>, Array2<Complex64>)> {
+    let block = |a: usize, b: usize| mat.slice(s![4 * a..4 * a + 4, 4 * b..4 * b + 4]);
+    // The largest block is `W[a, b] V`, with `|W[a, b]|² >= 1/2` since `W` is unitary.
+    let (a, b) = [(0, 0), (0, 1), (1, 0), (1, 1)]
+        .into_iter()
+        .max_by(|x, y| {
+            let norm = |(a, b)| block(a, b).iter().map(|z| z.norm_sqr()).sum::<f64>();
+            norm(*x).total_cmp(&norm(*y))
+        })
+        .unwrap();
+    let scale = (block(a, b).iter().map(|z| z.norm_sqr()).sum::<f64>() / 4.).sqrt();
+    let v = block(a, b).mapv(|z| z / scale);
+    let w = Array2::from_shape_fn((2, 2), |(i, j)| {
+        v.iter()
+            .zip(block(i, j).iter())
+            .map(|(x, y)| x.conj() * y)
+            .sum::<Complex64>()
+            / 4.
+    });
+    let is_product = (0..2).all(|i| {
+        (0..2).all(|j| {
+            block(i, j)
+                .iter()
+                .zip(v.iter())
+                .all(|(x, y)| (x - w[[i, j]] * y).norm() <= PRODUCT_TOL.max(1e-9 * x.norm()))
+        })
+    });
+    is_product.then_some((v, w))
+}
+
+/// The number of two-qubit gates of a sequence.
+fn two_qubit_count(sequence: &GateSequence) -> usize {
+    sequence
+        .gates
+        .iter()
+        .filter(|(_, _, qubits)| qubits.len() == 2)
+        .count()
+}
+
+/// Decompose the three-qubit unitary `mat` into a circuit of `U`, `RY`, `RZ`, `CX` and `CZ`
+/// gates.
+pub fn three_qubit_decomposition_inner(
+    mat: ArrayView2<Complex64>,
+) -> Result<GateSequence, AccelerateError> {
+    if mat.shape() != [8, 8] {
+        return Err(AccelerateError::Qiskit(format!(
+            "a matrix of shape {:?} is not a three-qubit unitary",
+            mat.shape()
+        )));
+    }
+    // The qubit orders with each qubit highest in turn.
+    let orders = [[0, 1, 2], [2, 0, 1], [1, 2, 0]];
+    for order in orders {
+        let permuted = permute_qubits(mat, order);
+        if let Some((v, w)) = split_highest_qubit(permuted.view()) {
+            let mut sequence = GateSequence::new();
+            sequence.extend_mapped(qs_decomposition_inner(v.view(), true, true)?, &order[..2]);
+            sequence.push_one_qubit_unitary(w.view(), order[2]);
+            return Ok(sequence);
+        }
+    }
+    let mut best: Option<GateSequence> = None;
+    for order in orders {
+        let permuted = permute_qubits(mat, order);
+        let mut sequence = GateSequence::new();
+        sequence.extend_mapped(qs_decomposition_inner(permuted.view(), true, true)?, &order);
+        if best.as_ref().map_or(true, |best| {
+            two_qubit_count(&sequence) < two_qubit_count(best)
+        }) {
+            best = Some(sequence);
+        }
+    }
+    Ok(best.unwrap())
+}
+
+/// Decompose a three-qubit unitary, splitting it on the qubit that gives the fewest two-qubit
+/// gates.
+///
+/// Args:
+///     mat (ndarray): the :math:`8 \times 8` unitary to decompose.
+///
+/// Returns:
+///     (CircuitData, float): the circuit of ``U``, ``RY``, ``RZ``, ``CX`` and ``CZ`` gates, and its
+///     global phase.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn three_qubit_decomposition(
+    py: Python,
+    mat: PyReadonlyArray2<Complex64>,
+) -> PyResult<(CircuitData, f64)> {
+    three_qubit_decomposition_inner(mat.as_array())?.into_circuit(py, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::operator::embed_inner;
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+    use crate::synthesis::test::assert_implements;
+
+    #[test]
+    fn test_permute_qubits() {
+        let mut rng = generator(3);
+        let single = random_unitary_inner(2, &mut rng);
+        let mat = embed_inner(single.view(), &[2], 3).unwrap();
+        assert_eq!(permute_qubits(mat.view(), [0, 1, 2]), mat);
+        // Qubit 0 of the result is qubit 2 of `mat`.
+        let expected = embed_inner(single.view(), &[0], 3).unwrap();
+        assert_eq!(permute_qubits(mat.view(), [2, 0, 1]), expected);
+    }
+
+    #[test]
+    fn test_random_unitaries() {
+        for seed in 0..4 {
+            let mat = random_unitary_inner(8, &mut generator(seed));
+            let sequence = three_qubit_decomposition_inner(mat.view()).unwrap();
+            assert_implements(&sequence, mat.view(), 3);
+            assert!(two_qubit_count(&sequence) <= 20);
+        }
+    }
+
+    #[test]
+    fn test_tensor_products() {
+        let mut rng = generator(11);
+        for (pair, single) in [([0, 1], 2), ([0, 2], 1), ([2, 1], 0)] {
+            let two = random_unitary_inner(4, &mut rng);
+            let one = random_unitary_inner(2, &mut rng);
+            let mat = embed_inner(two.view(), &pair, 3)
+                .unwrap()
+                .dot(&embed_inner(one.view(), &[single], 3).unwrap());
+            let sequence = three_qubit_decomposition_inner(mat.view()).unwrap();
+            assert_implements(&sequence, mat.view(), 3);
+            assert!(two_qubit_count(&sequence) <= 3, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn test_bad_shape() {
+        let mat = Array2::<Complex64>::eye(4);
+        assert!(matches!(
+            three_qubit_decomposition_inner(mat.view()),
+            Err(AccelerateError::Qiskit(_))
+        ));
+    }
+}
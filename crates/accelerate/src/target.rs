@@ -0,0 +1,885 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! A Rust-space model of the transpiler's :class:`.Target`: the instructions a backend supports,
+//! the qubits each runs on with their error and duration, and the global properties of the
+//! backend.
+//!
+//! The instructions are identified by name, as in the Python target, and the queries mirror its
+//! methods of the same names.  Instructions added with no qubit count are the variadic operations
+//! that the Python target stores by class, such as control flow; they are supported on any
+//! distinct qubits.  Instructions with a `None` qargs entry are ideal operations supported on
+//! any qubits.
+
+use hashbrown::HashMap;
+use indexmap::{IndexMap, IndexSet};
+use smallvec::SmallVec;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyTuple;
+
+use crate::error::AccelerateError;
+use crate::nlayout::PhysicalQubit;
+
+/// The qubits an instruction acts on.
+pub type Qargs = SmallVec<[PhysicalQubit; 2]>;
+
+/// The properties of an instruction on some qubits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InstructionProperties {
+    /// The duration of the instruction, in seconds.
+    pub duration: Option<f64>,
+    /// The average error rate of the instruction.
+    pub error: Option<f64>,
+}
+
+/// The properties of a qubit of the target.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QubitProperties {
+    pub t1: Option<f64>,
+    pub t2: Option<f64>,
+    pub frequency: Option<f64>,
+}
+
+/// The constraints of the control electronics on the lengths and start times of pulses, in units
+/// of `dt`, as in :class:`.TimingConstraints`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimingConstraints {
+    /// Pulse lengths must be a multiple of this.
+    pub granularity: u32,
+    /// The shortest pulse.
+    pub min_length: u32,
+    /// Gates must start at a multiple of this.
+    pub pulse_alignment: u32,
+    /// Measurements must start at a multiple of this.
+    pub acquire_alignment: u32,
+}
+
+impl Default for TimingConstraints {
+    fn default() -> Self {
+        TimingConstraints {
+            granularity: 1,
+            min_length: 1,
+            pulse_alignment: 1,
+            acquire_alignment: 1,
+        }
+    }
+}
+
+impl TimingConstraints {
+    /// Whether a pulse of `duration` can be played.
+    pub fn is_valid_pulse_length(&self, duration: u64) -> bool {
+        duration >= self.min_length as u64 && duration % self.granularity as u64 == 0
+    }
+
+    /// Whether an instruction of `duration` keeps the instructions after it aligned, so a circuit
+    /// made of them needs no rescheduling.
+    pub fn is_aligned_duration(&self, duration: u64) -> bool {
+        duration % self.pulse_alignment as u64 == 0 && duration % self.acquire_alignment as u64 == 0
+    }
+
+    /// The earliest time from `time` at which a gate, or a measurement if `acquire` is set, can
+    /// start.
+    pub fn aligned_start(&self, time: u64, acquire: bool) -> u64 {
+        let alignment = if acquire {
+            self.acquire_alignment
+        } else {
+            self.pulse_alignment
+        } as u64;
+        time + (alignment - time % alignment) % alignment
+    }
+}
+
+/// Round `value` to the nearest integer, with ties to even as Python's `round` does, which
+/// :class:`.InstructionDurations` uses.  [f64::round] takes ties away from zero.
+fn round_ties_even(value: f64) -> f64 {
+    let rounded = value.round();
+    if (value - value.trunc()).abs() == 0.5 && rounded % 2. != 0. {
+        rounded - value.signum()
+    } else {
+        rounded
+    }
+}
+
+/// The qubits an instruction is supported on, keyed by qargs with `None` for all qubits, and its
+/// properties on them.
+pub type PropertyMap = IndexMap<Option<Qargs>, Option<InstructionProperties>>;
+
+#[derive(Clone, Debug)]
+struct TargetInstruction {
+    /// The number of qubits of the operation, or `None` for a variadic operation.
+    num_qubits: Option<u32>,
+    properties: PropertyMap,
+}
+
+/// The instructions supported by a backend and their properties.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "python", pyclass(module = "qiskit._accelerate.target"))]
+pub struct Target {
+    pub description: Option<String>,
+    /// The number of qubits, which grows to fit the qargs of the instructions as they are added.
+    pub num_qubits: Option<usize>,
+    /// The time resolution of the input signals, in seconds.
+    pub dt: Option<f64>,
+    pub timing_constraints: TimingConstraints,
+    pub qubit_properties: Option<Vec<Option<QubitProperties>>>,
+    gate_map: IndexMap<String, TargetInstruction>,
+    /// The names of the ideal operations of each number of qubits.
+    global_operations: HashMap<u32, IndexSet<String>>,
+    /// The names of the operations supported on each qargs, with `None` for the ideal operations.
+    qarg_gate_map: IndexMap<Option<Qargs>, IndexSet<String>>,
+}
+
+impl Target {
+    /// An empty target with the default timing constraints.
+    pub fn new(num_qubits: Option<usize>, dt: Option<f64>) -> Self {
+        Target {
+            num_qubits,
+            dt,
+            ..Default::default()
+        }
+    }
+
+    /// Add the instruction `name`, with `properties` on the qargs it is supported on.
+    ///
+    /// A variadic operation has no `num_qubits` and can't have properties.  With no properties,
+    /// the instruction is ideal and supported on any qubits.
+    pub fn add_instruction(
+        &mut self,
+        name: &str,
+        num_qubits: Option<u32>,
+        properties: Option<PropertyMap>,
+    ) -> Result<(), AccelerateError> {
+        if self.gate_map.contains_key(name) {
+            return Err(AccelerateError::Value(format!(
+                "Instruction {name} is already in the target"
+            )));
+        }
+        let properties = match (num_qubits, properties) {
+            (None, Some(_)) => {
+                return Err(AccelerateError::Transpiler(
+                    "An instruction added globally by class can't have properties set.".to_string(),
+                ))
+            }
+            (None, None) => [(None, None)].into_iter().collect(),
+            (Some(num_qubits), properties) => {
+                let properties = properties.unwrap_or_else(|| [(None, None)].into_iter().collect());
+                if properties.contains_key(&None) {
+                    self.global_operations
+                        .entry(num_qubits)
+                        .or_default()
+                        .insert(name.to_string());
+                }
+                for qargs in properties.keys() {
+                    if let Some(qargs) = qargs {
+                        if qargs.len() != num_qubits as usize {
+                            return Err(AccelerateError::Transpiler(format!(
+                                "The number of qubits for {name} does not match the number of \
+                                 qubits in the properties dictionary: {:?}",
+                                qargs.iter().map(|q| q.index()).collect::<Vec<_>>()
+                            )));
+                        }
+                        let needed = qargs.iter().map(|q| q.index() + 1).max().unwrap_or(0);
+                        self.num_qubits = Some(self.num_qubits.unwrap_or(0).max(needed));
+                    }
+                    self.qarg_gate_map
+                        .entry(qargs.clone())
+                        .or_default()
+                        .insert(name.to_string());
+                }
+                properties
+            }
+        };
+        self.gate_map.insert(
+            name.to_string(),
+            TargetInstruction {
+                num_qubits,
+                properties,
+            },
+        );
+        Ok(())
+    }
+
+    /// Replace the properties of the instruction `name` on `qargs`.
+    pub fn update_instruction_properties(
+        &mut self,
+        name: &str,
+        qargs: Option<&[PhysicalQubit]>,
+        properties: Option<InstructionProperties>,
+    ) -> Result<(), AccelerateError> {
+        let Some(instruction) = self.gate_map.get_mut(name) else {
+            return Err(AccelerateError::Key(format!(
+                "Provided instruction: '{name}' not in this Target"
+            )));
+        };
+        let key = qargs.map(Qargs::from_slice);
+        match instruction.properties.get_mut(&key) {
+            Some(entry) => {
+                *entry = properties;
+                Ok(())
+            }
+            None => Err(AccelerateError::Key(format!(
+                "Provided qarg: '{qargs:?}' not in this Target for {name}"
+            ))),
+        }
+    }
+
+    /// The names of the instructions, in the order they were added.
+    pub fn operation_names(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.gate_map.keys().map(|name| name.as_str())
+    }
+
+    /// Whether the target has an instruction called `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.gate_map.contains_key(name)
+    }
+
+    /// The number of qubits of the operation `name`, or `None` if it's variadic or not in the
+    /// target.
+    pub fn operation_num_qubits(&self, name: &str) -> Option<u32> {
+        self.gate_map.get(name)?.num_qubits
+    }
+
+    /// Whether all of `qargs` are qubits of the target.
+    fn qargs_in_range(&self, qargs: &[PhysicalQubit]) -> bool {
+        let num_qubits = self.num_qubits.unwrap_or(0);
+        qargs.iter().all(|q| q.index() < num_qubits)
+    }
+
+    /// Whether the instruction `name` is supported on `qargs`, or on any qubits if `qargs` is
+    /// `None`.
+    pub fn instruction_supported(&self, name: &str, qargs: Option<&[PhysicalQubit]>) -> bool {
+        let Some(instruction) = self.gate_map.get(name) else {
+            return false;
+        };
+        // With no qubit count, only the global support of the operations can be checked.
+        let qargs = match (self.num_qubits, qargs) {
+            (Some(_), Some(qargs)) => qargs,
+            _ => return true,
+        };
+        if instruction
+            .properties
+            .contains_key(&Some(Qargs::from_slice(qargs)))
+        {
+            return true;
+        }
+        if !instruction.properties.contains_key(&None) {
+            return false;
+        }
+        match instruction.num_qubits {
+            // A variadic operation needs distinct qubits of the target.
+            None => {
+                let distinct = qargs.iter().collect::<hashbrown::HashSet<_>>().len() == qargs.len();
+                distinct
+                    && qargs
+                        .iter()
+                        .all(|q| q.index() <= self.num_qubits.unwrap_or(0))
+            }
+            Some(num_qubits) => num_qubits as usize == qargs.len() && self.qargs_in_range(qargs),
+        }
+    }
+
+    /// The names of the operations supported on `qargs`, or the ideal operations if `qargs` is
+    /// `None`.  Variadic operations are supported on any qargs.
+    pub fn operation_names_for_qargs(
+        &self,
+        qargs: Option<&[PhysicalQubit]>,
+    ) -> Result<IndexSet<&str>, AccelerateError> {
+        // A target with no qubits only has global operations.
+        let qargs = match self.num_qubits {
+            None | Some(0) => None,
+            Some(_) => qargs,
+        };
+        if let Some(qargs) = qargs {
+            if !self.qargs_in_range(qargs) {
+                return Err(AccelerateError::Key(format!(
+                    "{:?} not in target.",
+                    qargs.iter().map(|q| q.index()).collect::<Vec<_>>()
+                )));
+            }
+        }
+        let key = qargs.map(Qargs::from_slice);
+        let mut out = self
+            .qarg_gate_map
+            .get(&key)
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<IndexSet<_>>()
+            })
+            .unwrap_or_default();
+        if let Some(qargs) = qargs {
+            if let Some(global) = self.global_operations.get(&(qargs.len() as u32)) {
+                out.extend(global.iter().map(|name| name.as_str()));
+            }
+        }
+        out.extend(
+            self.gate_map
+                .iter()
+                .filter(|(_, instruction)| instruction.num_qubits.is_none())
+                .map(|(name, _)| name.as_str()),
+        );
+        if out.is_empty() {
+            return Err(AccelerateError::Key(format!("{qargs:?} not in target.")));
+        }
+        Ok(out)
+    }
+
+    /// The qargs the operation `name` is supported on, or `None` if it's supported on any qubits.
+    pub fn qargs_for_operation_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<impl Iterator<Item = &Qargs> + '_>, AccelerateError> {
+        let Some(instruction) = self.gate_map.get(name) else {
+            return Err(AccelerateError::Key(format!(
+                "Operation '{name}' not in this Target"
+            )));
+        };
+        if instruction.properties.contains_key(&None) {
+            return Ok(None);
+        }
+        Ok(Some(instruction.properties.keys().flatten()))
+    }
+
+    /// The properties of the instruction `name` on `qargs`, if it has any there.
+    pub fn instruction_properties(
+        &self,
+        name: &str,
+        qargs: Option<&[PhysicalQubit]>,
+    ) -> Option<&InstructionProperties> {
+        let key = qargs.map(Qargs::from_slice);
+        self.gate_map.get(name)?.properties.get(&key)?.as_ref()
+    }
+
+    /// The error of the instruction `name` on `qargs`, if the target has it.
+    pub fn error(&self, name: &str, qargs: &[PhysicalQubit]) -> Option<f64> {
+        self.instruction_properties(name, Some(qargs))?.error
+    }
+
+    /// The duration of the instruction `name` on `qargs` in seconds, if the target has it.
+    pub fn duration(&self, name: &str, qargs: &[PhysicalQubit]) -> Option<f64> {
+        self.instruction_properties(name, Some(qargs))?.duration
+    }
+
+    /// The duration of the instruction `name` on `qargs` in units of `dt`, rounded to the nearest
+    /// whole `dt`, if the target has it.  Fails if the target has the duration but no `dt`.
+    pub fn duration_dt(
+        &self,
+        name: &str,
+        qargs: &[PhysicalQubit],
+    ) -> Result<Option<u64>, AccelerateError> {
+        let Some(duration) = self.duration(name, qargs) else {
+            return Ok(None);
+        };
+        self.seconds_to_dt(duration).map(Some)
+    }
+
+    /// Convert a time in seconds to units of `dt`, rounded to the nearest whole `dt`.
+    pub fn seconds_to_dt(&self, seconds: f64) -> Result<u64, AccelerateError> {
+        let Some(dt) = self.dt else {
+            return Err(AccelerateError::Transpiler(
+                "dt is necessary to convert durations from 's' to 'dt'".to_string(),
+            ));
+        };
+        Ok(round_ties_even(seconds / dt) as u64)
+    }
+
+    /// The qargs of all the instructions of the target, or `None` if every instruction is ideal.
+    pub fn qargs(&self) -> Option<impl Iterator<Item = &Qargs> + '_> {
+        if self.qarg_gate_map.len() == 1 && self.qarg_gate_map.contains_key(&None) {
+            return None;
+        }
+        Some(self.qarg_gate_map.keys().flatten())
+    }
+}
+
+/// The Python form of instruction properties: a ``(duration, error)`` tuple.
+#[cfg(feature = "python")]
+type PyInstructionProperties = Option<(Option<f64>, Option<f64>)>;
+
+#[cfg(feature = "python")]
+fn from_py_properties(properties: PyInstructionProperties) -> Option<InstructionProperties> {
+    properties.map(|(duration, error)| InstructionProperties { duration, error })
+}
+
+#[cfg(feature = "python")]
+fn qargs_to_py<'py>(py: Python<'py>, qargs: &Qargs) -> Bound<'py, PyTuple> {
+    PyTuple::new_bound(py, qargs.iter())
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Target {
+    /// A Rust-space copy of a :class:`.Target`, for the Rust passes to query.
+    ///
+    /// Build it with :meth:`.Target._build_rust_target`, which adds each instruction of the
+    /// target with :meth:`add_instruction`.  Instruction properties are given as
+    /// ``(duration, error)`` tuples and qubit properties as ``(t1, t2, frequency)`` tuples.
+    #[new]
+    #[pyo3(signature = (num_qubits=None, dt=None, granularity=1, min_length=1, pulse_alignment=1, acquire_alignment=1, qubit_properties=None, description=None))]
+    fn py_new(
+        num_qubits: Option<usize>,
+        dt: Option<f64>,
+        granularity: u32,
+        min_length: u32,
+        pulse_alignment: u32,
+        acquire_alignment: u32,
+        qubit_properties: Option<Vec<Option<(Option<f64>, Option<f64>, Option<f64>)>>>,
+        description: Option<String>,
+    ) -> Self {
+        let qubit_properties = qubit_properties.map(|properties| {
+            properties
+                .into_iter()
+                .map(|qubit| qubit.map(|(t1, t2, frequency)| QubitProperties { t1, t2, frequency }))
+                .collect()
+        });
+        Target {
+            description,
+            num_qubits,
+            dt,
+            timing_constraints: TimingConstraints {
+                granularity,
+                min_length,
+                pulse_alignment,
+                acquire_alignment,
+            },
+            qubit_properties,
+            ..Default::default()
+        }
+    }
+
+    #[getter]
+    fn get_num_qubits(&self) -> Option<usize> {
+        self.num_qubits
+    }
+
+    #[getter]
+    fn get_dt(&self) -> Option<f64> {
+        self.dt
+    }
+
+    #[getter]
+    fn get_granularity(&self) -> u32 {
+        self.timing_constraints.granularity
+    }
+
+    #[getter]
+    fn get_min_length(&self) -> u32 {
+        self.timing_constraints.min_length
+    }
+
+    #[getter]
+    fn get_pulse_alignment(&self) -> u32 {
+        self.timing_constraints.pulse_alignment
+    }
+
+    #[getter]
+    fn get_acquire_alignment(&self) -> u32 {
+        self.timing_constraints.acquire_alignment
+    }
+
+    /// Add an instruction, with a dictionary of its qargs (or ``None`` for all qubits) to its
+    /// properties.  ``num_qubits`` is ``None`` for a variadic operation.
+    #[pyo3(name = "add_instruction", signature = (name, num_qubits, properties=None))]
+    fn py_add_instruction(
+        &mut self,
+        name: &str,
+        num_qubits: Option<u32>,
+        properties: Option<IndexMap<Option<Qargs>, PyInstructionProperties>>,
+    ) -> PyResult<()> {
+        let properties = properties.map(|properties| {
+            properties
+                .into_iter()
+                .map(|(qargs, props)| (qargs, from_py_properties(props)))
+                .collect()
+        });
+        Ok(self.add_instruction(name, num_qubits, properties)?)
+    }
+
+    #[pyo3(name = "update_instruction_properties")]
+    fn py_update_instruction_properties(
+        &mut self,
+        name: &str,
+        qargs: Option<Qargs>,
+        properties: PyInstructionProperties,
+    ) -> PyResult<()> {
+        Ok(self.update_instruction_properties(
+            name,
+            qargs.as_deref(),
+            from_py_properties(properties),
+        )?)
+    }
+
+    #[pyo3(name = "operation_names")]
+    fn py_operation_names(&self) -> Vec<&str> {
+        self.operation_names().collect()
+    }
+
+    #[pyo3(name = "instruction_supported", signature = (operation_name, qargs=None))]
+    fn py_instruction_supported(&self, operation_name: &str, qargs: Option<Qargs>) -> bool {
+        self.instruction_supported(operation_name, qargs.as_deref())
+    }
+
+    #[pyo3(name = "operation_names_for_qargs")]
+    fn py_operation_names_for_qargs(&self, qargs: Option<Qargs>) -> PyResult<Vec<&str>> {
+        Ok(self
+            .operation_names_for_qargs(qargs.as_deref())?
+            .into_iter()
+            .collect())
+    }
+
+    #[pyo3(name = "qargs_for_operation_name")]
+    fn py_qargs_for_operation_name(
+        &self,
+        py: Python,
+        operation: &str,
+    ) -> PyResult<Option<Vec<Py<PyTuple>>>> {
+        Ok(self
+            .qargs_for_operation_name(operation)?
+            .map(|qargs| qargs.map(|qargs| qargs_to_py(py, qargs).unbind()).collect()))
+    }
+
+    /// The ``(duration, error)`` of an instruction on some qargs, or ``None`` if it has no
+    /// properties there.
+    #[pyo3(name = "instruction_properties", signature = (operation_name, qargs=None))]
+    fn py_instruction_properties(
+        &self,
+        operation_name: &str,
+        qargs: Option<Qargs>,
+    ) -> PyInstructionProperties {
+        self.instruction_properties(operation_name, qargs.as_deref())
+            .map(|props| (props.duration, props.error))
+    }
+
+    /// The duration of an instruction on some qargs in seconds, or ``None`` if the target doesn't
+    /// have it.
+    #[pyo3(name = "duration")]
+    fn py_duration(&self, operation_name: &str, qargs: Qargs) -> Option<f64> {
+        self.duration(operation_name, &qargs)
+    }
+
+    /// The duration of an instruction on some qargs in units of ``dt``, or ``None`` if the target
+    /// doesn't have it.
+    #[pyo3(name = "duration_dt")]
+    fn py_duration_dt(&self, operation_name: &str, qargs: Qargs) -> PyResult<Option<u64>> {
+        Ok(self.duration_dt(operation_name, &qargs)?)
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.contains(name)
+    }
+
+    fn __len__(&self) -> usize {
+        self.gate_map.len()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn target(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<Target>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qargs(qubits: &[u32]) -> Qargs {
+        qubits.iter().map(|q| PhysicalQubit::new(*q)).collect()
+    }
+
+    fn properties(entries: &[(&[u32], f64)]) -> PropertyMap {
+        entries
+            .iter()
+            .map(|(qubits, error)| {
+                (
+                    Some(qargs(qubits)),
+                    Some(InstructionProperties {
+                        duration: Some(1e-7),
+                        error: Some(*error),
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    /// A three-qubit line with `x` on every qubit, `cx` on (0, 1) and (1, 2), an ideal `h`, and
+    /// the variadic `if_else`.
+    fn line_target() -> Target {
+        let mut target = Target::new(None, Some(2.2e-10));
+        target
+            .add_instruction(
+                "x",
+                Some(1),
+                Some(properties(&[(&[0], 0.1), (&[1], 0.2), (&[2], 0.3)])),
+            )
+            .unwrap();
+        target
+            .add_instruction(
+                "cx",
+                Some(2),
+                Some(properties(&[(&[0, 1], 0.01), (&[1, 2], 0.02)])),
+            )
+            .unwrap();
+        target.add_instruction("h", Some(1), None).unwrap();
+        target.add_instruction("if_else", None, None).unwrap();
+        target
+    }
+
+    fn names<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        let mut names = names.into_iter().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_num_qubits_grows() {
+        let target = line_target();
+        assert_eq!(target.num_qubits, Some(3));
+        assert_eq!(
+            target.operation_names().collect::<Vec<_>>(),
+            ["x", "cx", "h", "if_else"]
+        );
+        assert_eq!(target.operation_num_qubits("cx"), Some(2));
+        assert_eq!(target.operation_num_qubits("if_else"), None);
+        assert!(target.contains("h"));
+        assert!(!target.contains("cz"));
+    }
+
+    #[test]
+    fn test_instruction_supported() {
+        let target = line_target();
+        assert!(target.instruction_supported("cx", Some(&qargs(&[0, 1]))));
+        assert!(!target.instruction_supported("cx", Some(&qargs(&[1, 0]))));
+        assert!(target.instruction_supported("cx", None));
+        assert!(target.instruction_supported("h", Some(&qargs(&[2]))));
+        assert!(!target.instruction_supported("h", Some(&qargs(&[3]))));
+        assert!(!target.instruction_supported("h", Some(&qargs(&[0, 1]))));
+        assert!(target.instruction_supported("if_else", Some(&qargs(&[2, 0, 1]))));
+        assert!(!target.instruction_supported("if_else", Some(&qargs(&[0, 0]))));
+        assert!(!target.instruction_supported("cz", None));
+    }
+
+    #[test]
+    fn test_operation_names_for_qargs() {
+        let target = line_target();
+        assert_eq!(
+            names(
+                target
+                    .operation_names_for_qargs(Some(&qargs(&[1])))
+                    .unwrap()
+            ),
+            ["h", "if_else", "x"]
+        );
+        assert_eq!(
+            names(
+                target
+                    .operation_names_for_qargs(Some(&qargs(&[1, 2])))
+                    .unwrap()
+            ),
+            ["cx", "if_else"]
+        );
+        // Only the variadic operation is supported on qargs no instruction has.
+        assert_eq!(
+            names(
+                target
+                    .operation_names_for_qargs(Some(&qargs(&[2, 0])))
+                    .unwrap()
+            ),
+            ["if_else"]
+        );
+        assert_eq!(
+            names(target.operation_names_for_qargs(None).unwrap()),
+            ["h", "if_else"]
+        );
+        assert!(matches!(
+            target.operation_names_for_qargs(Some(&qargs(&[3]))),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+
+    #[test]
+    fn test_operation_names_for_qargs_without_qubits() {
+        let mut target = Target::new(None, None);
+        target.add_instruction("h", Some(1), None).unwrap();
+        // A target with no qubit count only has its global operations.
+        assert_eq!(
+            names(
+                target
+                    .operation_names_for_qargs(Some(&qargs(&[7])))
+                    .unwrap()
+            ),
+            ["h"]
+        );
+        assert!(matches!(
+            Target::new(None, None).operation_names_for_qargs(None),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+
+    #[test]
+    fn test_qargs_for_operation_name() {
+        let target = line_target();
+        assert_eq!(
+            target
+                .qargs_for_operation_name("cx")
+                .unwrap()
+                .unwrap()
+                .cloned()
+                .collect::<Vec<_>>(),
+            [qargs(&[0, 1]), qargs(&[1, 2])]
+        );
+        assert!(target.qargs_for_operation_name("h").unwrap().is_none());
+        assert!(matches!(
+            target.qargs_for_operation_name("cz"),
+            Err(AccelerateError::Key(_))
+        ));
+        assert_eq!(target.qargs().unwrap().count(), 5);
+    }
+
+    #[test]
+    fn test_instruction_properties() {
+        let mut target = line_target();
+        assert_eq!(target.error("cx", &qargs(&[1, 2])), Some(0.02));
+        assert_eq!(target.duration("x", &qargs(&[0])), Some(1e-7));
+        assert_eq!(target.error("cx", &qargs(&[2, 1])), None);
+        assert_eq!(target.instruction_properties("h", None), None);
+        let new = InstructionProperties {
+            duration: None,
+            error: Some(0.5),
+        };
+        target
+            .update_instruction_properties("x", Some(&qargs(&[1])), Some(new))
+            .unwrap();
+        assert_eq!(
+            target.instruction_properties("x", Some(&qargs(&[1]))),
+            Some(&new)
+        );
+        assert_eq!(target.duration("x", &qargs(&[1])), None);
+        assert!(matches!(
+            target.update_instruction_properties("x", Some(&qargs(&[0, 1])), None),
+            Err(AccelerateError::Key(_))
+        ));
+        assert!(matches!(
+            target.update_instruction_properties("cz", None, None),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_instruction_errors() {
+        let mut target = line_target();
+        assert!(matches!(
+            target.add_instruction("x", Some(1), None),
+            Err(AccelerateError::Value(_))
+        ));
+        assert!(matches!(
+            target.add_instruction("ccx", Some(3), Some(properties(&[(&[0, 1], 0.1)]))),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert!(matches!(
+            target.add_instruction("for_loop", None, Some(properties(&[(&[0], 0.1)]))),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert!(!target.contains("ccx"));
+    }
+
+    #[test]
+    fn test_ideal_target_has_no_qargs() {
+        let mut target = Target::new(Some(2), None);
+        target.add_instruction("h", Some(1), None).unwrap();
+        target.add_instruction("cx", Some(2), None).unwrap();
+        assert!(target.qargs().is_none());
+        assert!(target.instruction_supported("cx", Some(&qargs(&[1, 0]))));
+        assert!(!target.instruction_supported("cx", Some(&qargs(&[1, 2]))));
+    }
+
+    #[test]
+    fn test_timing_constraints() {
+        let constraints = TimingConstraints {
+            granularity: 16,
+            min_length: 64,
+            pulse_alignment: 8,
+            acquire_alignment: 16,
+        };
+        assert!(constraints.is_valid_pulse_length(64));
+        assert!(constraints.is_valid_pulse_length(80));
+        assert!(!constraints.is_valid_pulse_length(48));
+        assert!(!constraints.is_valid_pulse_length(72));
+        assert!(constraints.is_aligned_duration(32));
+        assert!(!constraints.is_aligned_duration(24));
+        assert_eq!(constraints.aligned_start(0, false), 0);
+        assert_eq!(constraints.aligned_start(1, false), 8);
+        assert_eq!(constraints.aligned_start(8, false), 8);
+        assert_eq!(constraints.aligned_start(9, true), 16);
+        let default = TimingConstraints::default();
+        assert!(default.is_valid_pulse_length(1));
+        assert!(!default.is_valid_pulse_length(0));
+        assert_eq!(default.aligned_start(7, true), 7);
+    }
+
+    #[test]
+    fn test_round_ties_even() {
+        for (value, expected) in [
+            (0.5, 0.),
+            (1.5, 2.),
+            (2.5, 2.),
+            (-2.5, -2.),
+            (2.4, 2.),
+            (2.6, 3.),
+            (3., 3.),
+        ] {
+            assert_eq!(round_ties_even(value), expected);
+        }
+    }
+
+    #[test]
+    fn test_duration_dt() {
+        let dt = 2f64.powi(-30);
+        let mut target = Target::new(None, Some(dt));
+        let x_properties = [(0, 2.5), (1, 3.5), (2, 160.2)]
+            .into_iter()
+            .map(|(qubit, length)| {
+                (
+                    Some(qargs(&[qubit])),
+                    Some(InstructionProperties {
+                        duration: Some(length * dt),
+                        error: None,
+                    }),
+                )
+            })
+            .collect();
+        target
+            .add_instruction("x", Some(1), Some(x_properties))
+            .unwrap();
+        target
+            .add_instruction("cx", Some(2), Some(properties(&[(&[0, 1], 0.01)])))
+            .unwrap();
+        // The durations are rounded to the nearest `dt`, with ties to even.
+        assert_eq!(target.duration_dt("x", &qargs(&[0])), Ok(Some(2)));
+        assert_eq!(target.duration_dt("x", &qargs(&[1])), Ok(Some(4)));
+        assert_eq!(target.duration_dt("x", &qargs(&[2])), Ok(Some(160)));
+        assert_eq!(target.duration_dt("cx", &qargs(&[0, 1])), Ok(Some(107)));
+        assert_eq!(target.duration_dt("cx", &qargs(&[1, 0])), Ok(None));
+        assert_eq!(target.duration_dt("h", &qargs(&[0])), Ok(None));
+        target.dt = None;
+        assert!(matches!(
+            target.duration_dt("x", &qargs(&[0])),
+            Err(AccelerateError::Transpiler(_))
+        ));
+        assert_eq!(target.duration_dt("cx", &qargs(&[1, 0])), Ok(None));
+    }
+}
@@ -0,0 +1,380 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The global configuration of the threads used by the accelerate crate.
+//!
+//! Every parallel kernel checks `run_in_parallel` (or `use_multiple_threads` if it has no size
+//! threshold) before it goes parallel, and then runs its parallel work with `install` on one
+//! shared thread pool, so the configuration here bounds the CPU usage of all of them.  The
+//! configuration can be changed from Python at any time; the pool is rebuilt on the next parallel
+//! call after its size changes.
+//!
+//! By default, the kernels run serially inside a Python process pool (where `parallel_map` sets
+//! `QISKIT_IN_PARALLEL=TRUE`), unless `QISKIT_FORCE_THREADS=TRUE` is set, so that each process
+//! doesn't start a thread per CPU.
+
+use std::env;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+#[cfg(feature = "python")]
+use hashbrown::HashMap;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::error::AccelerateError;
+
+/// The kernels that only go parallel on large enough inputs.  Each has its own threshold, which
+/// Python code sets by the kernel's name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParallelKernel {
+    Clifford,
+    CouplingMap,
+    DensityMatrix,
+    Fidelity,
+    GateTwirling,
+    NoiseAdaptiveLayout,
+    Operator,
+    ParameterShift,
+    PartialTrace,
+    PauliExpVal,
+    ReadoutMitigation,
+    SampledExpVal,
+    SamplerResults,
+    SparsePauliOp,
+    StabilizerState,
+    Statevector,
+    Twirling,
+    Vf2Layout,
+}
+
+const NUM_KERNELS: usize = 18;
+
+const _: () = assert!(ParallelKernel::Vf2Layout as usize + 1 == NUM_KERNELS);
+
+impl ParallelKernel {
+    /// All the kernels, in the order of their discriminants.
+    const ALL: [ParallelKernel; NUM_KERNELS] = [
+        Self::Clifford,
+        Self::CouplingMap,
+        Self::DensityMatrix,
+        Self::Fidelity,
+        Self::GateTwirling,
+        Self::NoiseAdaptiveLayout,
+        Self::Operator,
+        Self::ParameterShift,
+        Self::PartialTrace,
+        Self::PauliExpVal,
+        Self::ReadoutMitigation,
+        Self::SampledExpVal,
+        Self::SamplerResults,
+        Self::SparsePauliOp,
+        Self::StabilizerState,
+        Self::Statevector,
+        Self::Twirling,
+        Self::Vf2Layout,
+    ];
+
+    /// The name of the kernel in the Python-space configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Clifford => "clifford",
+            Self::CouplingMap => "coupling_map",
+            Self::DensityMatrix => "density_matrix",
+            Self::Fidelity => "fidelity",
+            Self::GateTwirling => "gate_twirling",
+            Self::NoiseAdaptiveLayout => "noise_adaptive_layout",
+            Self::Operator => "operator",
+            Self::ParameterShift => "parameter_shift",
+            Self::PartialTrace => "partial_trace",
+            Self::PauliExpVal => "pauli_exp_val",
+            Self::ReadoutMitigation => "readout_mitigation",
+            Self::SampledExpVal => "sampled_exp_val",
+            Self::SamplerResults => "sampler_results",
+            Self::SparsePauliOp => "sparse_pauli_op",
+            Self::StabilizerState => "stabilizer_state",
+            Self::Statevector => "statevector",
+            Self::Twirling => "twirling",
+            Self::Vf2Layout => "vf2_layout",
+        }
+    }
+
+    /// The default threshold of the kernel.  The size it applies to is described alongside.
+    const fn default_threshold(self) -> usize {
+        match self {
+            // The number of qubits of a composition of Cliffords.
+            Self::Clifford => 64,
+            // The number of qubits of a coupling map whose distance matrix is computed.
+            Self::CouplingMap => 300,
+            // The number of rows of a density matrix that's updated.
+            Self::DensityMatrix => 128,
+            // The number of rows of a superoperator that's reduced, which corresponds to 5-qubit
+            // channels.
+            Self::Fidelity => 1024,
+            // The number of twirled copies of a circuit times the number of gates twirled in each.
+            Self::GateTwirling => 1 << 14,
+            // The number of qubits of a device whose reliabilities are computed.
+            Self::NoiseAdaptiveLayout => 300,
+            // The number of rows of a matrix that's composed, tensored or compared, which
+            // corresponds to 7-qubit operators.
+            Self::Operator => 128,
+            // The number of expectation values that are assembled into a Jacobian.
+            Self::ParameterShift => 1 << 16,
+            // The number of rows of a reduced state.
+            Self::PartialTrace => 128,
+            // The number of qubits of a state whose Pauli expectation value is taken.
+            Self::PauliExpVal => 19,
+            // The number of entries of a readout-mitigated distribution times the number of
+            // outcomes that each of them sums over.
+            Self::ReadoutMitigation => 1 << 14,
+            // The number of terms of the observables whose expectation values are taken from a
+            // sampled distribution times the number of its outcomes.
+            Self::SampledExpVal => 1 << 14,
+            // The number of shots whose outcomes are packed into the registers of a sampler
+            // result.
+            Self::SamplerResults => 1 << 16,
+            // The number of Pauli terms that a layout is applied to.
+            Self::SparsePauliOp => 1 << 16,
+            // The number of shots of a Clifford circuit that are simulated on stabilizer states.
+            Self::StabilizerState => 256,
+            // The number of amplitudes of a state that's updated, which corresponds to 14 qubits;
+            // below it the thread overhead dominates.
+            Self::Statevector => 1 << 14,
+            // The number of expectation values of twirled terms that are combined.
+            Self::Twirling => 1 << 16,
+            // The number of edges or qubits of a layout that's scored.
+            Self::Vf2Layout => 50,
+        }
+    }
+}
+
+impl FromStr for ParallelKernel {
+    type Err = AccelerateError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kernel| kernel.name() == name)
+            .ok_or_else(|| AccelerateError::Key(format!("no parallel threshold for '{name}'")))
+    }
+}
+
+const DEFAULT_THRESHOLDS: [usize; NUM_KERNELS] = {
+    let mut thresholds = [0; NUM_KERNELS];
+    let mut i = 0;
+    while i < NUM_KERNELS {
+        thresholds[i] = ParallelKernel::ALL[i].default_threshold();
+        i += 1;
+    }
+    thresholds
+};
+
+#[derive(Clone, Debug)]
+struct ThreadingConfig {
+    /// The number of threads of the pool, with zero for one per CPU.
+    max_threads: usize,
+    /// Whether to stay serial inside a Python process pool.
+    serial_in_parallel: bool,
+    /// The parallel threshold of each kernel, indexed by the kernel.
+    thresholds: [usize; NUM_KERNELS],
+}
+
+static CONFIG: RwLock<ThreadingConfig> = RwLock::new(ThreadingConfig {
+    max_threads: 0,
+    serial_in_parallel: true,
+    thresholds: DEFAULT_THRESHOLDS,
+});
+
+/// The shared pool, built on first use and dropped when its size is changed.
+static POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+
+fn env_is_true(name: &str) -> bool {
+    env::var(name)
+        .map(|value| value.to_uppercase() == "TRUE")
+        .unwrap_or(false)
+}
+
+/// Whether the kernels may use more than one thread.
+pub fn use_multiple_threads() -> bool {
+    let config = CONFIG.read().unwrap();
+    if config.max_threads == 1 {
+        return false;
+    }
+    !config.serial_in_parallel
+        || !env_is_true("QISKIT_IN_PARALLEL")
+        || env_is_true("QISKIT_FORCE_THREADS")
+}
+
+/// The smallest input for which `kernel` goes parallel.
+pub fn parallel_threshold(kernel: ParallelKernel) -> usize {
+    CONFIG.read().unwrap().thresholds[kernel as usize]
+}
+
+/// Whether `kernel` should go parallel on an input of `size`.
+pub fn run_in_parallel(kernel: ParallelKernel, size: usize) -> bool {
+    size >= parallel_threshold(kernel) && use_multiple_threads()
+}
+
+/// Set the parallel threshold of `kernel`, or restore its default with `None`.
+pub fn set_parallel_threshold(kernel: ParallelKernel, threshold: Option<usize>) {
+    CONFIG.write().unwrap().thresholds[kernel as usize] =
+        threshold.unwrap_or(kernel.default_threshold());
+}
+
+/// Set the number of threads of the shared pool, with zero for one per CPU (or as many as
+/// `RAYON_NUM_THREADS` says).  One thread makes every kernel serial.
+pub fn set_max_threads(max_threads: usize) {
+    let mut config = CONFIG.write().unwrap();
+    if config.max_threads != max_threads {
+        config.max_threads = max_threads;
+        // Work already running keeps its own handle on the old pool.
+        *POOL.write().unwrap() = None;
+    }
+}
+
+/// Set whether the kernels stay serial inside a Python process pool.
+pub fn set_serial_in_parallel(serial_in_parallel: bool) {
+    CONFIG.write().unwrap().serial_in_parallel = serial_in_parallel;
+}
+
+fn pool() -> Arc<ThreadPool> {
+    if let Some(pool) = POOL.read().unwrap().as_ref() {
+        return pool.clone();
+    }
+    let max_threads = CONFIG.read().unwrap().max_threads;
+    POOL.write()
+        .unwrap()
+        .get_or_insert_with(|| {
+            Arc::new(
+                ThreadPoolBuilder::new()
+                    .num_threads(max_threads)
+                    .thread_name(|index| format!("qiskit-accelerate-{index}"))
+                    .build()
+                    .expect("failed to build the accelerate thread pool"),
+            )
+        })
+        .clone()
+}
+
+/// The number of threads of the shared pool.
+pub fn current_num_threads() -> usize {
+    pool().current_num_threads()
+}
+
+/// Run `op` on the shared pool, so that the parallel iterators within it use the pool's threads.
+/// Calls from within the pool run `op` directly.
+pub fn install<OP, R>(op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    pool().install(op)
+}
+
+/// Configure the threads that Qiskit's Rust code uses.
+///
+/// Args:
+///     max_threads (int | None): the number of threads of the shared pool, with zero for one per
+///         CPU.  One thread makes everything serial.
+///     serial_in_parallel (bool | None): whether to stay serial inside a process pool of
+///         :func:`.parallel_map`.
+///     parallel_thresholds (dict[str, int | None] | None): the smallest inputs for which each
+///         kernel goes parallel, by kernel name, with ``None`` to restore a default.
+///
+/// Any argument that is ``None`` is left as it is.
+///
+/// Raises:
+///     KeyError: if a kernel has no parallel threshold.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "configure", signature = (max_threads=None, serial_in_parallel=None, parallel_thresholds=None))]
+pub fn py_configure(
+    max_threads: Option<usize>,
+    serial_in_parallel: Option<bool>,
+    parallel_thresholds: Option<HashMap<String, Option<usize>>>,
+) -> PyResult<()> {
+    if let Some(thresholds) = parallel_thresholds {
+        // Check all the names before changing anything.
+        let thresholds = thresholds
+            .into_iter()
+            .map(|(name, threshold)| Ok((name.parse::<ParallelKernel>()?, threshold)))
+            .collect::<Result<Vec<_>, AccelerateError>>()?;
+        for (kernel, threshold) in thresholds {
+            set_parallel_threshold(kernel, threshold);
+        }
+    }
+    if let Some(max_threads) = max_threads {
+        set_max_threads(max_threads);
+    }
+    if let Some(serial_in_parallel) = serial_in_parallel {
+        set_serial_in_parallel(serial_in_parallel);
+    }
+    Ok(())
+}
+
+/// The current threading configuration.
+///
+/// Returns:
+///     dict: the ``max_threads`` and ``serial_in_parallel`` settings, the ``num_threads`` of the
+///     shared pool, and the ``parallel_thresholds`` of the kernels.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn get_config(py: Python) -> PyResult<PyObject> {
+    let config = CONFIG.read().unwrap().clone();
+    let out = pyo3::types::PyDict::new_bound(py);
+    out.set_item("max_threads", config.max_threads)?;
+    out.set_item("serial_in_parallel", config.serial_in_parallel)?;
+    out.set_item("num_threads", current_num_threads())?;
+    out.set_item(
+        "parallel_thresholds",
+        ParallelKernel::ALL
+            .into_iter()
+            .map(|kernel| (kernel.name(), config.thresholds[kernel as usize]))
+            .collect::<HashMap<_, _>>(),
+    )?;
+    Ok(out.into_any().unbind())
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn threading(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_configure))?;
+    m.add_wrapped(wrap_pyfunction!(get_config))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernels_in_discriminant_order() {
+        for (index, kernel) in ParallelKernel::ALL.into_iter().enumerate() {
+            assert_eq!(kernel as usize, index);
+        }
+    }
+
+    #[test]
+    fn kernel_names_round_trip() {
+        for kernel in ParallelKernel::ALL {
+            assert_eq!(kernel.name().parse::<ParallelKernel>(), Ok(kernel));
+        }
+        assert!(matches!(
+            "no_such_kernel".parse::<ParallelKernel>(),
+            Err(AccelerateError::Key(_))
+        ));
+    }
+}
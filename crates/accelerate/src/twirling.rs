@@ -0,0 +1,540 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The generation of Pauli-twirled circuits and the combination of the expectation values
+//! measured under them.
+//!
+//! A gate twirl dresses each instance of a Clifford two-qubit gate `G` with a random Pauli `P`
+//! before it and the Pauli `Q = ±GPG†` after it, so that `QGP = ±G` and each twirled copy of the
+//! circuit implements the original one, up to a global phase that is compensated.  Averaged over
+//! the copies, the noise of the gates is tailored into a Pauli channel.
+//!
+//! A measurement twirl flips some of the measured qubits with an `X` before the measurement, and
+//! flips their classical bits back afterwards.  A Pauli term that is measured from the flipped bits
+//! is negated once for each flipped qubit in its support, so each randomization's value is signed
+//! back before the randomizations are averaged, weighted by their shots.  The variance of a term is
+//! that of the pooled shots of all the randomizations, by the law of total variance.
+
+use std::f64::consts::PI;
+
+use ndarray::linalg::kron;
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use num_complex::Complex64;
+use rand::prelude::*;
+use rayon::prelude::*;
+
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1, PyReadonlyArray2};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::{CircuitData, Param};
+use qiskit_circuit::operations::StandardGate;
+
+use crate::error::AccelerateError;
+#[cfg(feature = "python")]
+use crate::rng::PySeed;
+use crate::rng::{generator, SeedSequence};
+use crate::threading::{self, ParallelKernel};
+
+/// The two-qubit gates that a gate twirl can dress with Paulis.
+pub const TWIRLED_GATES: [StandardGate; 4] = [
+    StandardGate::CXGate,
+    StandardGate::CZGate,
+    StandardGate::ECRGate,
+    StandardGate::ISwapGate,
+];
+
+/// The single-qubit Paulis, in the order of their indices in a [GateTwirl].
+const PAULI_GATES: [StandardGate; 4] = [
+    StandardGate::IGate,
+    StandardGate::XGate,
+    StandardGate::YGate,
+    StandardGate::ZGate,
+];
+
+/// A twirl of a two-qubit gate: the Paulis on its qubits before and after it, each as its index in
+/// `I, X, Y, Z`, whose product with the gate is the gate, negated if `negated`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GateTwirl {
+    pub before: [u8; 2],
+    pub after: [u8; 2],
+    pub negated: bool,
+}
+
+/// The matrix of a two-qubit Pauli, from the index of the Pauli on each qubit.
+fn two_qubit_pauli(paulis: [u8; 2]) -> Array2<Complex64> {
+    let matrix = |pauli: u8| {
+        PAULI_GATES[pauli as usize]
+            .matrix(&[])
+            .expect("the Paulis have no parameters")
+    };
+    // The first qubit is the least significant, so it's the right factor of the product.
+    kron(&matrix(paulis[1]), &matrix(paulis[0]))
+}
+
+/// The sixteen twirls of a Clifford two-qubit gate, indexed by the Pauli before the gate, whose
+/// index on the first qubit is the least significant in base four.
+pub fn gate_twirls(gate: StandardGate) -> Result<Vec<GateTwirl>, AccelerateError> {
+    let Some(matrix) = gate.matrix(&[]).filter(|matrix| matrix.nrows() == 4) else {
+        return Err(AccelerateError::Value(format!(
+            "{} is not a two-qubit gate without parameters",
+            gate.name()
+        )));
+    };
+    let adjoint = matrix.t().mapv(|value| value.conj());
+    let paulis = (0..16u8)
+        .map(|index| {
+            let qubits = [index % 4, index / 4];
+            (qubits, two_qubit_pauli(qubits))
+        })
+        .collect::<Vec<_>>();
+    paulis
+        .iter()
+        .map(|(before, pauli)| {
+            let conjugated = matrix.dot(pauli).dot(&adjoint);
+            // The Paulis are orthogonal, so the overlap `tr(QM) / 4` is `±1` exactly when the
+            // conjugated Pauli `M` is `±Q`.
+            paulis
+                .iter()
+                .find_map(|(after, other)| {
+                    let overlap = other.dot(&conjugated).diag().sum() / 4.;
+                    let negated = if (overlap - 1.).norm() < 1e-9 {
+                        false
+                    } else if (overlap + 1.).norm() < 1e-9 {
+                        true
+                    } else {
+                        return None;
+                    };
+                    Some(GateTwirl {
+                        before: *before,
+                        after: *after,
+                        negated,
+                    })
+                })
+                .ok_or_else(|| {
+                    AccelerateError::Value(format!("{} is not a Clifford gate", gate.name()))
+                })
+        })
+        .collect()
+}
+
+/// Draw the twirls of `num_gates` gates in each of `num_twirls` twirled copies of a circuit, as
+/// the index in `0..16` of the Pauli before each gate, with a row per copy.  Each copy draws from a
+/// generator seeded by the next seed of `seeds`, so the copies don't depend on the threading.
+pub fn sample_gate_twirls(
+    num_twirls: usize,
+    num_gates: usize,
+    seeds: &mut SeedSequence,
+) -> Array2<u8> {
+    let draw = |seed: &u64| {
+        let mut rng = generator(*seed as u128);
+        (0..num_gates)
+            .map(|_| rng.gen_range(0..16))
+            .collect::<Vec<u8>>()
+    };
+    let seeds = seeds.seeds(num_twirls);
+    let parallel = threading::run_in_parallel(ParallelKernel::GateTwirling, num_twirls * num_gates);
+    let rows: Vec<Vec<u8>> = if parallel {
+        threading::install(|| seeds.par_iter().map(draw).collect())
+    } else {
+        seeds.iter().map(draw).collect()
+    };
+    Array2::from_shape_vec((num_twirls, num_gates), rows.concat())
+        .expect("each copy has a twirl per gate")
+}
+
+/// The sign of each term under each twirl, with a row per twirl, from the qubits that each twirl
+/// `flips` and the qubits of the `supports` of the terms, each with a row per twirl or term and a
+/// column per measured qubit.
+pub fn twirl_signs(
+    flips: ArrayView2<bool>,
+    supports: ArrayView2<bool>,
+) -> Result<Array2<f64>, AccelerateError> {
+    if flips.ncols() != supports.ncols() {
+        return Err(AccelerateError::Value(format!(
+            "the twirls flip {} qubits, but the terms are on {}",
+            flips.ncols(),
+            supports.ncols()
+        )));
+    }
+    Ok(Array2::from_shape_fn(
+        (flips.nrows(), supports.nrows()),
+        |(twirl, term)| {
+            let flipped = flips
+                .row(twirl)
+                .iter()
+                .zip(supports.row(term))
+                .filter(|(flip, support)| **flip && **support)
+                .count();
+            if flipped % 2 == 0 {
+                1.
+            } else {
+                -1.
+            }
+        },
+    ))
+}
+
+/// The expectation values, the variances and the standard errors of some terms combined from
+/// their twirls.
+#[derive(Clone, Debug)]
+pub struct TwirledValues {
+    pub expvals: Array1<f64>,
+    pub variances: Array1<f64>,
+    pub stds: Array1<f64>,
+}
+
+/// Combine the `expvals` and the `variances` of terms measured under twirls, each with a row per
+/// twirl and a column per term, into those of the untwirled terms.
+///
+/// The twirls are weighted by their `shots`, and the value of each term under each twirl is
+/// multiplied by its sign of [twirl_signs] first.
+pub fn combine_twirls(
+    expvals: ArrayView2<f64>,
+    variances: ArrayView2<f64>,
+    shots: ArrayView1<u64>,
+    signs: ArrayView2<f64>,
+) -> Result<TwirledValues, AccelerateError> {
+    let shape = expvals.dim();
+    if variances.dim() != shape || signs.dim() != shape || shots.len() != shape.0 {
+        return Err(AccelerateError::Value(format!(
+            "the values of {} twirls of {} terms don't match the variances of shape {:?}, the \
+             signs of shape {:?} and the shots of {} twirls",
+            shape.0,
+            shape.1,
+            variances.dim(),
+            signs.dim(),
+            shots.len()
+        )));
+    }
+    let total: u64 = shots.sum();
+    if total == 0 {
+        return Err(AccelerateError::Value(
+            "the twirls have no shots".to_string(),
+        ));
+    }
+    let total = total as f64;
+    let weights: Vec<f64> = shots.iter().map(|shots| *shots as f64 / total).collect();
+    let combine = |term: usize| {
+        let mut mean = 0.;
+        let mut second = 0.;
+        for (twirl, weight) in weights.iter().enumerate() {
+            let expval = expvals[[twirl, term]];
+            mean += weight * signs[[twirl, term]] * expval;
+            second += weight * (variances[[twirl, term]] + expval * expval);
+        }
+        let variance = (second - mean * mean).max(0.);
+        (mean, variance, (variance / total).sqrt())
+    };
+    let parallel = threading::run_in_parallel(ParallelKernel::Twirling, expvals.len());
+    let values: Vec<(f64, f64, f64)> = if parallel {
+        threading::install(|| (0..shape.1).into_par_iter().map(combine).collect())
+    } else {
+        (0..shape.1).map(combine).collect()
+    };
+    Ok(TwirledValues {
+        expvals: values.iter().map(|value| value.0).collect(),
+        variances: values.iter().map(|value| value.1).collect(),
+        stds: values.iter().map(|value| value.2).collect(),
+    })
+}
+
+/// The sign of each term under each twirl.
+///
+/// Args:
+///     flips (numpy.ndarray): whether each twirl flips each measured qubit, as a boolean array with
+///         a row per twirl.
+///     supports (numpy.ndarray): whether each term acts on each measured qubit, as a boolean array
+///         with a row per term.
+///
+/// Returns:
+///     numpy.ndarray: the sign of each term under each twirl, with a row per twirl.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "twirl_signs")]
+pub fn py_twirl_signs(
+    py: Python,
+    flips: PyReadonlyArray2<bool>,
+    supports: PyReadonlyArray2<bool>,
+) -> PyResult<Py<PyArray2<f64>>> {
+    let signs = twirl_signs(flips.as_array(), supports.as_array())?;
+    Ok(signs.into_pyarray_bound(py).unbind())
+}
+
+/// Combine the expectation values of terms measured under twirls.
+///
+/// Args:
+///     expvals (numpy.ndarray): the expectation value of each term under each twirl, as measured,
+///         with a row per twirl.
+///     variances (numpy.ndarray): the variance of each term under each twirl.
+///     shots (numpy.ndarray): the ``uint64`` number of shots of each twirl.
+///     flips (numpy.ndarray): whether each twirl flips each measured qubit, as a boolean array with
+///         a row per twirl.
+///     supports (numpy.ndarray): whether each term acts on each measured qubit, as a boolean array
+///         with a row per term.
+///
+/// Returns:
+///     (numpy.ndarray, numpy.ndarray, numpy.ndarray): the expectation value, the variance and the
+///     standard error of each term.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "combine_twirls")]
+pub fn py_combine_twirls(
+    py: Python,
+    expvals: PyReadonlyArray2<f64>,
+    variances: PyReadonlyArray2<f64>,
+    shots: PyReadonlyArray1<u64>,
+    flips: PyReadonlyArray2<bool>,
+    supports: PyReadonlyArray2<bool>,
+) -> PyResult<(Py<PyArray1<f64>>, Py<PyArray1<f64>>, Py<PyArray1<f64>>)> {
+    let (expvals, variances, shots) = (expvals.as_array(), variances.as_array(), shots.as_array());
+    let (flips, supports) = (flips.as_array(), supports.as_array());
+    let values = py.allow_threads(|| -> Result<_, AccelerateError> {
+        let signs = twirl_signs(flips, supports)?;
+        combine_twirls(expvals, variances, shots, signs.view())
+    })?;
+    Ok((
+        values.expvals.into_pyarray_bound(py).unbind(),
+        values.variances.into_pyarray_bound(py).unbind(),
+        values.stds.into_pyarray_bound(py).unbind(),
+    ))
+}
+
+/// Generate Pauli-twirled copies of a circuit.
+///
+/// Each instance of a twirled gate is dressed with a random two-qubit Pauli before it and the
+/// Pauli that undoes it after it, and the global phase of each copy is corrected so that it
+/// implements the same unitary as the circuit.  Identities are left out, and the conditioned
+/// gates aren't twirled.
+///
+/// Args:
+///     circuit (CircuitData): the circuit.
+///     num_twirls (int): the number of twirled copies.
+///     twirled_gates (list[str] | None): the names of the gates to twirl, out of ``cx``, ``cz``,
+///         ``ecr`` and ``iswap``, with all of them if ``None``.
+///     seed (int | SeedSequence | None): the seed of the random Paulis.
+///
+/// Returns:
+///     list[CircuitData]: the twirled copies, on the bits of the circuit.
+///
+/// Raises:
+///     ValueError: if a gate can't be twirled.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, num_twirls, twirled_gates=None, seed=None))]
+pub fn twirl_circuit(
+    py: Python,
+    circuit: &CircuitData,
+    num_twirls: usize,
+    twirled_gates: Option<Vec<String>>,
+    seed: Option<PySeed>,
+) -> PyResult<Vec<CircuitData>> {
+    let gates = match twirled_gates {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                StandardGate::from_name(name)
+                    .filter(|gate| TWIRLED_GATES.contains(gate))
+                    .ok_or_else(|| {
+                        AccelerateError::Value(format!("Cannot twirl the gate {name}")).into()
+                    })
+            })
+            .collect::<PyResult<Vec<_>>>()?,
+        None => TWIRLED_GATES.to_vec(),
+    };
+    let twirls = gates
+        .iter()
+        .map(|gate| gate_twirls(*gate))
+        .collect::<Result<Vec<_>, _>>()?;
+    // The position in `gates` of the gate of each instruction that's twirled.
+    let sites = circuit
+        .iter_instructions()
+        .map(|(op, _, _)| {
+            Ok(StandardGate::from_py_operation(op.bind(py))?
+                .and_then(|(gate, _)| gates.iter().position(|other| *other == gate)))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let num_gates = sites.iter().flatten().count();
+    let choices = PySeed::with_sequence(seed, |seeds| {
+        py.allow_threads(|| sample_gate_twirls(num_twirls, num_gates, seeds))
+    });
+    let paulis = PAULI_GATES
+        .iter()
+        .map(|gate| gate.create_py_op(py, &[]))
+        .collect::<PyResult<Vec<_>>>()?;
+    let mut out = Vec::with_capacity(num_twirls);
+    for row in choices.rows() {
+        let mut twirled = circuit.copy_empty_like(py)?;
+        let mut draws = row.iter();
+        let mut negated = false;
+        for ((op, qubits, clbits), site) in circuit.iter_instructions().zip(&sites) {
+            let Some(gate) = site else {
+                twirled.push_instruction(op.clone_ref(py), qubits, clbits)?;
+                continue;
+            };
+            let twirl = twirls[*gate][*draws.next().expect("a twirl per gate") as usize];
+            for (pauli, qubit) in twirl.before.iter().zip(qubits) {
+                if *pauli != 0 {
+                    twirled.push_instruction(
+                        paulis[*pauli as usize].clone_ref(py),
+                        &[*qubit],
+                        &[],
+                    )?;
+                }
+            }
+            twirled.push_instruction(op.clone_ref(py), qubits, clbits)?;
+            for (pauli, qubit) in twirl.after.iter().zip(qubits) {
+                if *pauli != 0 {
+                    twirled.push_instruction(
+                        paulis[*pauli as usize].clone_ref(py),
+                        &[*qubit],
+                        &[],
+                    )?;
+                }
+            }
+            negated ^= twirl.negated;
+        }
+        if negated {
+            twirled.add_global_phase(py, &Param::Float(PI))?;
+        }
+        out.push(twirled);
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn twirling(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_twirl_signs))?;
+    m.add_wrapped(wrap_pyfunction!(py_combine_twirls))?;
+    m.add_wrapped(wrap_pyfunction!(twirl_circuit))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ndarray::array;
+
+    #[test]
+    fn test_gate_twirls_preserve_gate() {
+        for gate in TWIRLED_GATES {
+            let matrix = gate.matrix(&[]).unwrap();
+            let twirls = gate_twirls(gate).unwrap();
+            assert_eq!(twirls.len(), 16);
+            for (index, twirl) in twirls.iter().enumerate() {
+                assert_eq!(twirl.before, [index as u8 % 4, index as u8 / 4]);
+                let twirled = two_qubit_pauli(twirl.after)
+                    .dot(&matrix)
+                    .dot(&two_qubit_pauli(twirl.before));
+                let sign = if twirl.negated { -1. } else { 1. };
+                let distance = (&twirled - &matrix.mapv(|value| value * sign))
+                    .iter()
+                    .map(|value| value.norm())
+                    .fold(0., f64::max);
+                assert!(
+                    distance < 1e-12,
+                    "{}: twirl {:?} doesn't preserve the gate",
+                    gate.name(),
+                    twirl
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gate_twirls_identity_twirl() {
+        for gate in TWIRLED_GATES {
+            let twirl = gate_twirls(gate).unwrap()[0];
+            assert_eq!(
+                twirl,
+                GateTwirl {
+                    before: [0, 0],
+                    after: [0, 0],
+                    negated: false
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_gate_twirls_invalid_gates() {
+        for gate in [
+            StandardGate::HGate,
+            StandardGate::CHGate,
+            StandardGate::RZZGate,
+        ] {
+            assert!(matches!(gate_twirls(gate), Err(AccelerateError::Value(_))));
+        }
+    }
+
+    #[test]
+    fn test_sample_gate_twirls_is_seeded() {
+        let twirls = sample_gate_twirls(5, 7, &mut SeedSequence::new(Some(11)));
+        assert_eq!(twirls.dim(), (5, 7));
+        assert!(twirls.iter().all(|twirl| *twirl < 16));
+        assert_eq!(
+            twirls,
+            sample_gate_twirls(5, 7, &mut SeedSequence::new(Some(11)))
+        );
+        // Each copy only depends on its own seed.
+        let mut seeds = SeedSequence::new(Some(11));
+        seeds.next_seed();
+        let rest = sample_gate_twirls(4, 7, &mut seeds);
+        assert_eq!(twirls.slice(ndarray::s![1.., ..]), rest);
+    }
+
+    #[test]
+    fn test_twirl_signs() {
+        let flips = array![[false, false], [true, false], [true, true]];
+        let supports = array![[true, false], [true, true], [false, false]];
+        let signs = twirl_signs(flips.view(), supports.view()).unwrap();
+        assert_eq!(signs, array![[1., 1., 1.], [-1., -1., 1.], [-1., 1., 1.]]);
+        assert!(twirl_signs(flips.view(), array![[true]].view()).is_err());
+    }
+
+    #[test]
+    fn test_combine_twirls() {
+        // One term, measured under a twirl that doesn't flip it and one that does.
+        let expvals = array![[0.5], [-0.3]];
+        let variances = array![[0.75], [0.91]];
+        let shots = array![100, 300];
+        let signs = array![[1.], [-1.]];
+        let values =
+            combine_twirls(expvals.view(), variances.view(), shots.view(), signs.view()).unwrap();
+        let mean = 0.25 * 0.5 + 0.75 * 0.3;
+        let second = 0.25 * (0.75 + 0.25) + 0.75 * (0.91 + 0.09);
+        let variance: f64 = second - mean * mean;
+        assert!((values.expvals[0] - mean).abs() < 1e-12);
+        assert!((values.variances[0] - variance).abs() < 1e-12);
+        assert!((values.stds[0] - (variance / 400.).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_combine_twirls_errors() {
+        let values = array![[0.5]];
+        let no_shots = array![0];
+        assert!(matches!(
+            combine_twirls(values.view(), values.view(), no_shots.view(), values.view()),
+            Err(AccelerateError::Value(_))
+        ));
+        let shots = array![1, 2];
+        assert!(matches!(
+            combine_twirls(values.view(), values.view(), shots.view(), values.view()),
+            Err(AccelerateError::Value(_))
+        ));
+    }
+}
@@ -19,38 +19,58 @@
 // In order to avoid copying we want to use `MatRef<c64>` or `MatMut<c64>`.
 
 use approx::{abs_diff_eq, relative_eq};
-use num_complex::{Complex, Complex64, ComplexFloat};
+use num_complex::{Complex64, ComplexFloat};
 use num_traits::Zero;
+#[cfg(feature = "python")]
 use pyo3::exceptions::{PyIndexError, PyValueError};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 use smallvec::{smallvec, SmallVec};
 use std::f64::consts::{FRAC_1_SQRT_2, PI};
+#[cfg(feature = "python")]
 use std::ops::Deref;
+#[cfg(feature = "python")]
+use std::str::FromStr;
 
 use faer::Side::Lower;
-use faer::{prelude::*, scale, ComplexField, Mat, MatRef};
+use faer::{prelude::*, ComplexField, Mat, MatRef};
 use faer_ext::{IntoFaer, IntoFaerComplex, IntoNdarray, IntoNdarrayComplex};
 use ndarray::linalg::kron;
 use ndarray::prelude::*;
 use ndarray::Zip;
+#[cfg(feature = "python")]
 use numpy::PyReadonlyArray2;
-use numpy::{IntoPyArray, ToPyArray};
+#[cfg(feature = "python")]
+use numpy::{IntoPyArray, PyArray3, ToPyArray};
+#[cfg(feature = "python")]
 use pyo3::pybacked::PyBackedStr;
 
+use crate::angles::normalize_phase;
 use crate::convert_2q_block_matrix::change_basis;
+use crate::error::{AccelerateError, ErrorContext};
 use crate::euler_one_qubit_decomposer::{
     angles_from_unitary, det_one_qubit, unitary_to_gate_sequence_inner, EulerBasis,
     OneQubitGateSequence, ANGLE_ZERO_EPSILON,
 };
 use crate::utils;
-use crate::QiskitError;
+use crate::weyl::{
+    canonical_trace, canonicalize, chamber_distance, closest_partial_swap, weyl_coordinates_faer,
+};
 
 use rand::prelude::*;
 use rand_distr::StandardNormal;
 use rand_pcg::Pcg64Mcg;
 
+use qiskit_circuit::gate_matrix::{
+    rx_gate, ry_gate, rz_gate, CX_GATE, H_GATE, ONE_QUBIT_IDENTITY, SX_GATE, X_GATE,
+};
+#[cfg(feature = "python")]
 use qiskit_circuit::SliceOrInt;
 
 const PI2: f64 = PI / 2.0;
@@ -58,13 +78,6 @@ const PI4: f64 = PI / 4.0;
 const PI32: f64 = 3.0 * PI2;
 const TWO_PI: f64 = 2.0 * PI;
 
-const C1: c64 = c64 { re: 1.0, im: 0.0 };
-
-pub static ONE_QUBIT_IDENTITY: [[Complex64; 2]; 2] = [
-    [Complex64::new(1., 0.), Complex64::new(0., 0.)],
-    [Complex64::new(0., 0.), Complex64::new(1., 0.)],
-];
-
 static B_NON_NORMALIZED: [[Complex64; 4]; 4] = [
     [
         Complex64::new(1.0, 0.),
@@ -136,7 +149,7 @@ fn magic_basis_transform(
     }
 }
 
-fn transform_from_magic_basis(u: Mat<c64>) -> Mat<c64> {
+pub fn transform_from_magic_basis(u: Mat<c64>) -> Mat<c64> {
     let unitary: ArrayView2<Complex64> = u.as_ref().into_ndarray_complex();
     magic_basis_transform(unitary, MagicBasisTransform::OutOf)
         .view()
@@ -194,7 +207,7 @@ impl TraceToFidelity for c64 {
 
 fn decompose_two_qubit_product_gate(
     special_unitary: ArrayView2<Complex64>,
-) -> PyResult<(Array2<Complex64>, Array2<Complex64>, f64)> {
+) -> Result<(Array2<Complex64>, Array2<Complex64>, f64), AccelerateError> {
     let mut r: Array2<Complex64> = special_unitary.slice(s![..2, ..2]).to_owned();
     let mut det_r = det_one_qubit(r.view());
     if det_r.abs() < 0.1 {
@@ -202,8 +215,9 @@ fn decompose_two_qubit_product_gate(
         det_r = det_one_qubit(r.view());
     }
     if det_r.abs() < 0.1 {
-        return Err(QiskitError::new_err(
-            "decompose_two_qubit_product_gate: unable to decompose: detR < 0.1",
+        return Err(AccelerateError::Synthesis(
+            "decompose_two_qubit_product_gate: unable to decompose: detR < 0.1".to_string(),
+            ErrorContext::pass("decompose_two_qubit_product_gate").with_tolerance(0.1),
         ));
     }
     r.mapv_inplace(|x| x / det_r.sqrt());
@@ -214,8 +228,9 @@ fn decompose_two_qubit_product_gate(
     let mut l = temp.slice(s![..;2, ..;2]).to_owned();
     let det_l = det_one_qubit(l.view());
     if det_l.abs() < 0.9 {
-        return Err(QiskitError::new_err(
-            "decompose_two_qubit_product_gate: unable to decompose: detL < 0.9",
+        return Err(AccelerateError::Synthesis(
+            "decompose_two_qubit_product_gate: unable to decompose: detL < 0.9".to_string(),
+            ErrorContext::pass("decompose_two_qubit_product_gate").with_tolerance(0.9),
         ));
     }
     l.mapv_inplace(|x| x / det_l.sqrt());
@@ -223,79 +238,25 @@ fn decompose_two_qubit_product_gate(
     Ok((l, r, phase))
 }
 
-fn __weyl_coordinates(unitary: MatRef<c64>) -> [f64; 3] {
-    let uscaled = scale(C1 / unitary.determinant().powf(0.25)) * unitary;
-    let uup = transform_from_magic_basis(uscaled);
-    let mut darg: Vec<_> = (uup.transpose() * &uup)
-        .complex_eigenvalues()
-        .into_iter()
-        .map(|x: c64| -x.arg() / 2.0)
-        .collect();
-    darg[3] = -darg[0] - darg[1] - darg[2];
-    let mut cs: Vec<_> = (0..3)
-        .map(|i| ((darg[i] + darg[3]) / 2.0).rem_euclid(2.0 * PI))
-        .collect();
-    let cstemp: Vec<f64> = cs
-        .iter()
-        .map(|x| x.rem_euclid(PI2))
-        .map(|x| x.min(PI2 - x))
-        .collect();
-    let mut order = utils::arg_sort(&cstemp);
-    (order[0], order[1], order[2]) = (order[1], order[2], order[0]);
-    (cs[0], cs[1], cs[2]) = (cs[order[0]], cs[order[1]], cs[order[2]]);
-
-    // Flip into Weyl chamber
-    if cs[0] > PI2 {
-        cs[0] -= PI32;
-    }
-    if cs[1] > PI2 {
-        cs[1] -= PI32;
-    }
-    let mut conjs = 0;
-    if cs[0] > PI4 {
-        cs[0] = PI2 - cs[0];
-        conjs += 1;
-    }
-    if cs[1] > PI4 {
-        cs[1] = PI2 - cs[1];
-        conjs += 1;
-    }
-    if cs[2] > PI2 {
-        cs[2] -= PI32;
-    }
-    if conjs == 1 {
-        cs[2] = PI2 - cs[2];
-    }
-    if cs[2] > PI4 {
-        cs[2] -= PI2;
-    }
-    [cs[1], cs[0], cs[2]]
-}
-
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(text_signature = "(basis_b, basis_fidelity, unitary, /")]
 pub fn _num_basis_gates(
     basis_b: f64,
     basis_fidelity: f64,
-    unitary: PyReadonlyArray2<Complex<f64>>,
+    unitary: PyReadonlyArray2<Complex64>,
 ) -> usize {
     let u = unitary.as_array().into_faer_complex();
     __num_basis_gates(basis_b, basis_fidelity, u)
 }
 
 fn __num_basis_gates(basis_b: f64, basis_fidelity: f64, unitary: MatRef<c64>) -> usize {
-    let [a, b, c] = __weyl_coordinates(unitary);
+    let [a, b, c] = weyl_coordinates_faer(unitary);
     let traces = [
-        c64::new(
-            4.0 * (a.cos() * b.cos() * c.cos()),
-            4.0 * (a.sin() * b.sin() * c.sin()),
-        ),
-        c64::new(
-            4.0 * (PI4 - a).cos() * (basis_b - b).cos() * c.cos(),
-            4.0 * (PI4 - a).sin() * (basis_b - b).sin() * c.sin(),
-        ),
-        c64::new(4.0 * c.cos(), 0.0),
-        c64::new(4.0, 0.0),
+        canonical_trace([a, b, c]),
+        canonical_trace([PI4 - a, basis_b - b, c]),
+        Complex64::new(4.0 * c.cos(), 0.0),
+        Complex64::new(4.0, 0.0),
     ];
     // The originial Python had `np.argmax`, which returns the lowest index in case two or more
     // values have a common maximum value.
@@ -311,85 +272,106 @@ fn __num_basis_gates(basis_b: f64, basis_fidelity: f64, unitary: MatRef<c64>) ->
         .0
 }
 
-/// A good approximation to the best value x to get the minimum
-/// trace distance for :math:`U_d(x, x, x)` from :math:`U_d(a, b, c)`.
-fn closest_partial_swap(a: f64, b: f64, c: f64) -> f64 {
-    let m = (a + b + c) / 3.;
-    let [am, bm, cm] = [a - m, b - m, c - m];
-    let [ab, bc, ca] = [a - b, b - c, c - a];
-    m + am * bm * cm * (6. + ab * ab + bc * bc + ca * ca) / 18.
+/// The most applications of a basis gate that `num_basis_gates_weyl_inner` considers.
+const MAX_BASIS_APPLICATIONS: usize = 64;
+
+/// The tolerance within which a Weyl coordinate is taken to be zero or `π/4`.  The coordinates
+/// computed from a unitary are only this accurate on the edges of the chamber.
+const COORDINATE_ATOL: f64 = 1e-7;
+
+/// The largest fraction `λ ≤ 1` such that `λ` times the coordinates of `target` are estimated to be
+/// reachable with `count ≥ 2` applications of the basis gate of coordinates `basis`, both in the
+/// Weyl chamber.
+///
+/// The estimate takes the interaction strength `a + b + |c|` to be at most additive over the
+/// applications.  For bases of the XX family, `(θ, 0, 0)`, it also uses that `count` applications
+/// only reach `b + |c| ≤ (count - 1) θ` and `|c| ≤ (count - 2) θ`, so two of them can't leave the
+/// `c = 0` plane.  Sums of target coordinates within `COORDINATE_ATOL` of zero aren't bounded.
+fn reachable_fraction(target: [f64; 3], basis: [f64; 3], count: usize) -> f64 {
+    let [a, b, c] = target;
+    let strength = basis[0] + basis[1] + basis[2].abs();
+    let mut bounds = vec![(a + b + c.abs(), count as f64 * strength)];
+    if basis[1] < COORDINATE_ATOL && basis[2].abs() < COORDINATE_ATOL {
+        bounds.push((b + c.abs(), (count - 1) as f64 * basis[0]));
+        bounds.push((c.abs(), (count - 2) as f64 * basis[0]));
+    }
+    bounds
+        .into_iter()
+        .filter(|(value, _)| *value > COORDINATE_ATOL)
+        .map(|(value, bound)| bound / value)
+        .fold(1., f64::min)
+}
+
+/// The number of applications of a two-qubit basis gate of Weyl coordinates `basis_coordinates`
+/// that best approximates `unitary`, when each application has fidelity `basis_fidelity`.
+///
+/// Supercontrolled bases, `(π/4, b, 0)`, use the same count as `TwoQubitBasisDecomposer`.  For any
+/// other basis, zero applications reach the identity and one application reaches the class of
+/// the basis itself; the reach of more applications is estimated by `reachable_fraction`, and the
+/// fidelity of each count is that of the nearest reachable point on the way from the identity to
+/// the target.  Ties go to the fewer applications.
+pub fn num_basis_gates_weyl_inner(
+    basis_coordinates: [f64; 3],
+    basis_fidelity: f64,
+    unitary: MatRef<c64>,
+) -> usize {
+    let basis = canonicalize(basis_coordinates);
+    if (PI4 - basis[0]).abs() < COORDINATE_ATOL && basis[2].abs() < COORDINATE_ATOL {
+        return __num_basis_gates(basis[1], basis_fidelity, unitary);
+    }
+    let target = weyl_coordinates_faer(unitary);
+    let mut fidelities = vec![
+        1. - chamber_distance(target, [0., 0., 0.]),
+        1. - chamber_distance(target, basis),
+    ];
+    if basis.iter().any(|x| x.abs() >= COORDINATE_ATOL) {
+        for count in 2..=MAX_BASIS_APPLICATIONS {
+            let fraction = reachable_fraction(target, basis, count);
+            fidelities.push(1. - chamber_distance(target, target.map(|x| fraction * x)));
+            if fraction >= 1. {
+                break;
+            }
+        }
+    }
+    fidelities
+        .into_iter()
+        .enumerate()
+        .map(|(count, fidelity)| (count, fidelity * basis_fidelity.powi(count as i32)))
+        .min_by(|(_, left), (_, right)| right.partial_cmp(left).unwrap())
+        .unwrap()
+        .0
+}
+
+/// Return the number of applications of a two-qubit basis gate, given by its Weyl coordinates,
+/// that best approximates ``unitary``.  Unlike ``_num_basis_gates``, the basis need not be
+/// supercontrolled, but for other bases the count is an estimate.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(text_signature = "(basis_coordinates, basis_fidelity, unitary, /)")]
+pub fn num_basis_gates_weyl(
+    basis_coordinates: [f64; 3],
+    basis_fidelity: f64,
+    unitary: PyReadonlyArray2<Complex64>,
+) -> usize {
+    num_basis_gates_weyl_inner(
+        basis_coordinates,
+        basis_fidelity,
+        unitary.as_array().into_faer_complex(),
+    )
 }
 
 fn rx_matrix(theta: f64) -> Array2<Complex64> {
-    let half_theta = theta / 2.;
-    let cos = Complex64::new(half_theta.cos(), 0.);
-    let isin = Complex64::new(0., -half_theta.sin());
-    array![[cos, isin], [isin, cos]]
+    aview2(&rx_gate(theta)).to_owned()
 }
 
 fn ry_matrix(theta: f64) -> Array2<Complex64> {
-    let half_theta = theta / 2.;
-    let cos = Complex64::new(half_theta.cos(), 0.);
-    let sin = Complex64::new(half_theta.sin(), 0.);
-    array![[cos, -sin], [sin, cos]]
+    aview2(&ry_gate(theta)).to_owned()
 }
 
 fn rz_matrix(theta: f64) -> Array2<Complex64> {
-    let ilam2 = Complex64::new(0., 0.5 * theta);
-    array![
-        [(-ilam2).exp(), Complex64::new(0., 0.)],
-        [Complex64::new(0., 0.), ilam2.exp()]
-    ]
+    aview2(&rz_gate(theta)).to_owned()
 }
 
-static HGATE: [[Complex64; 2]; 2] = [
-    [
-        Complex64::new(FRAC_1_SQRT_2, 0.),
-        Complex64::new(FRAC_1_SQRT_2, 0.),
-    ],
-    [
-        Complex64::new(FRAC_1_SQRT_2, 0.),
-        Complex64::new(-FRAC_1_SQRT_2, 0.),
-    ],
-];
-
-static CXGATE: [[Complex64; 4]; 4] = [
-    [
-        Complex64::new(1., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-    ],
-    [
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(1., 0.),
-    ],
-    [
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(1., 0.),
-        Complex64::new(0., 0.),
-    ],
-    [
-        Complex64::new(0., 0.),
-        Complex64::new(1., 0.),
-        Complex64::new(0., 0.),
-        Complex64::new(0., 0.),
-    ],
-];
-
-static SXGATE: [[Complex64; 2]; 2] = [
-    [Complex64::new(0.5, 0.5), Complex64::new(0.5, -0.5)],
-    [Complex64::new(0.5, -0.5), Complex64::new(0.5, 0.5)],
-];
-
-static XGATE: [[Complex64; 2]; 2] = [
-    [Complex64::new(0., 0.), Complex64::new(1., 0.)],
-    [Complex64::new(1., 0.), Complex64::new(0., 0.)],
-];
-
 fn compute_unitary(sequence: &TwoQubitSequenceVec, global_phase: f64) -> Array2<Complex64> {
     let identity = aview2(&ONE_QUBIT_IDENTITY);
     let phase = Complex64::new(0., global_phase).exp();
@@ -402,10 +384,10 @@ fn compute_unitary(sequence: &TwoQubitSequenceVec, global_phase: f64) -> Array2<
             // sequence. If we get a different gate this is getting called
             // by something else and is invalid.
             let gate_matrix = match inst.0.as_ref() {
-                "sx" => aview2(&SXGATE).to_owned(),
+                "sx" => aview2(&SX_GATE).to_owned(),
                 "rz" => rz_matrix(inst.1[0]),
-                "cx" => aview2(&CXGATE).to_owned(),
-                "x" => aview2(&XGATE).to_owned(),
+                "cx" => aview2(&CX_GATE).to_owned(),
+                "x" => aview2(&X_GATE).to_owned(),
                 _ => unreachable!("Undefined gate"),
             };
             (gate_matrix, &inst.2)
@@ -430,8 +412,11 @@ const DEFAULT_FIDELITY: f64 = 1.0 - 1.0e-9;
 const C1_IM: Complex64 = Complex64::new(0.0, 1.0);
 
 #[derive(Clone, Debug, Copy)]
-#[pyclass(module = "qiskit._accelerate.two_qubit_decompose")]
-enum Specialization {
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.two_qubit_decompose")
+)]
+pub enum Specialization {
     General,
     IdEquiv,
     SWAPEquiv,
@@ -449,8 +434,53 @@ enum Specialization {
     fSimabmbEquiv,
 }
 
+impl Specialization {
+    /// Every specialization, in the order of their discriminants.
+    pub const ALL: [Specialization; 10] = [
+        Self::General,
+        Self::IdEquiv,
+        Self::SWAPEquiv,
+        Self::PartialSWAPEquiv,
+        Self::PartialSWAPFlipEquiv,
+        Self::ControlledEquiv,
+        Self::MirrorControlledEquiv,
+        Self::fSimaabEquiv,
+        Self::fSimabbEquiv,
+        Self::fSimabmbEquiv,
+    ];
+
+    /// The name of the variant, which is also the suffix of its Python subclass of
+    /// `TwoQubitWeylDecomposition`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::IdEquiv => "IdEquiv",
+            Self::SWAPEquiv => "SWAPEquiv",
+            Self::PartialSWAPEquiv => "PartialSWAPEquiv",
+            Self::PartialSWAPFlipEquiv => "PartialSWAPFlipEquiv",
+            Self::ControlledEquiv => "ControlledEquiv",
+            Self::MirrorControlledEquiv => "MirrorControlledEquiv",
+            Self::fSimaabEquiv => "fSimaabEquiv",
+            Self::fSimabbEquiv => "fSimabbEquiv",
+            Self::fSimabmbEquiv => "fSimabmbEquiv",
+        }
+    }
+}
+
+#[cfg(feature = "python")]
 #[pymethods]
 impl Specialization {
+    #[getter(name)]
+    fn py_name(&self) -> &'static str {
+        self.name()
+    }
+
+    /// Every specialization, in the order of their discriminants.
+    #[staticmethod]
+    fn _variants() -> Vec<Self> {
+        Self::ALL.to_vec()
+    }
+
     fn __reduce__(&self, py: Python) -> PyResult<Py<PyAny>> {
         // Ideally we'd use the string-only form of `__reduce__` for simplicity, but PyO3 enums
         // don't produce Python singletons, and pickle doesn't like that.
@@ -491,31 +521,87 @@ impl Specialization {
 
 #[derive(Clone, Debug)]
 #[allow(non_snake_case)]
-#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)
+)]
 pub struct TwoQubitWeylDecomposition {
-    #[pyo3(get)]
-    a: f64,
-    #[pyo3(get)]
-    b: f64,
-    #[pyo3(get)]
-    c: f64,
-    #[pyo3(get)]
-    global_phase: f64,
-    K1l: Array2<Complex64>,
-    K2l: Array2<Complex64>,
-    K1r: Array2<Complex64>,
-    K2r: Array2<Complex64>,
-    #[pyo3(get)]
-    specialization: Specialization,
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub global_phase: f64,
+    pub K1l: Array2<Complex64>,
+    pub K2l: Array2<Complex64>,
+    pub K1r: Array2<Complex64>,
+    pub K2r: Array2<Complex64>,
+    pub specialization: Specialization,
     default_euler_basis: EulerBasis,
-    #[pyo3(get)]
-    requested_fidelity: Option<f64>,
-    #[pyo3(get)]
-    calculated_fidelity: f64,
-    unitary_matrix: Array2<Complex64>,
+    pub requested_fidelity: Option<f64>,
+    pub calculated_fidelity: f64,
+    pub unitary_matrix: Array2<Complex64>,
+}
+
+/// Which of the Weyl coordinates `π/4 ≥ a ≥ b ≥ |c|` of a decomposition are within a tolerance of
+/// the values on the boundaries and high-symmetry lines of the Weyl chamber.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeylCloseness {
+    pub a_is_zero: bool,
+    pub a_is_pi4: bool,
+    pub b_is_zero: bool,
+    pub b_is_pi4: bool,
+    pub c_is_zero: bool,
+    /// Whether `|c|` is `π/4`.
+    pub c_is_pi4: bool,
+    pub a_equals_b: bool,
+    /// Whether `b` is `|c|`.
+    pub b_equals_c: bool,
+}
+
+impl WeylCloseness {
+    /// The names and values of the flags, in the order of the fields.
+    pub fn items(&self) -> [(&'static str, bool); 8] {
+        [
+            ("a_is_zero", self.a_is_zero),
+            ("a_is_pi4", self.a_is_pi4),
+            ("b_is_zero", self.b_is_zero),
+            ("b_is_pi4", self.b_is_pi4),
+            ("c_is_zero", self.c_is_zero),
+            ("c_is_pi4", self.c_is_pi4),
+            ("a_equals_b", self.a_equals_b),
+            ("b_equals_c", self.b_equals_c),
+        ]
+    }
 }
 
 impl TwoQubitWeylDecomposition {
+    /// The local matrices `K1l`, `K1r`, `K2l` and `K2r`, stacked in that order into one
+    /// `(4, 2, 2)` array.
+    pub fn stacked_k_matrices(&self) -> Array3<Complex64> {
+        let mut out = Array3::zeros((4, 2, 2));
+        for (mut slot, k) in out
+            .outer_iter_mut()
+            .zip([&self.K1l, &self.K1r, &self.K2l, &self.K2r])
+        {
+            slot.assign(k);
+        }
+        out
+    }
+
+    /// Which of the Weyl coordinates are within `atol` of special values.
+    pub fn closeness(&self, atol: f64) -> WeylCloseness {
+        let close = |x: f64, y: f64| (x - y).abs() <= atol;
+        WeylCloseness {
+            a_is_zero: close(self.a, 0.),
+            a_is_pi4: close(self.a, PI4),
+            b_is_zero: close(self.b, 0.),
+            b_is_pi4: close(self.b, PI4),
+            c_is_zero: close(self.c, 0.),
+            c_is_pi4: close(self.c.abs(), PI4),
+            a_equals_b: close(self.a, self.b),
+            b_equals_c: close(self.b, self.c.abs()),
+        }
+    }
+
     fn weyl_gate(
         &self,
         simplify: bool,
@@ -565,6 +651,7 @@ static IPX: [[Complex64; 2]; 2] = [
     [C1_IM, Complex64::new(0., 0.)],
 ];
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl TwoQubitWeylDecomposition {
     #[staticmethod]
@@ -622,12 +709,129 @@ impl TwoQubitWeylDecomposition {
         fidelity: Option<f64>,
         _specialization: Option<Specialization>,
     ) -> PyResult<Self> {
+        Ok(Self::new_inner(
+            unitary_matrix.as_array(),
+            fidelity,
+            _specialization,
+        )?)
+    }
+
+    #[getter]
+    fn a(&self) -> f64 {
+        self.a
+    }
+
+    #[getter]
+    fn b(&self) -> f64 {
+        self.b
+    }
+
+    #[getter]
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    #[getter]
+    fn global_phase(&self) -> f64 {
+        self.global_phase
+    }
+
+    #[getter]
+    fn specialization(&self) -> Specialization {
+        self.specialization
+    }
+
+    #[getter]
+    fn requested_fidelity(&self) -> Option<f64> {
+        self.requested_fidelity
+    }
+
+    #[getter]
+    fn calculated_fidelity(&self) -> f64 {
+        self.calculated_fidelity
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K1l(&self, py: Python) -> PyObject {
+        self.K1l.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K1r(&self, py: Python) -> PyObject {
+        self.K1r.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K2l(&self, py: Python) -> PyObject {
+        self.K2l.to_pyarray_bound(py).into()
+    }
+
+    #[allow(non_snake_case)]
+    #[getter]
+    fn K2r(&self, py: Python) -> PyObject {
+        self.K2r.to_pyarray_bound(py).into()
+    }
+
+    #[getter]
+    fn unitary_matrix(&self, py: Python) -> PyObject {
+        self.unitary_matrix.to_pyarray_bound(py).into()
+    }
+
+    /// Return the local matrices stacked into one array, and which of the Weyl coordinates are
+    /// close to special values.
+    ///
+    /// Args:
+    ///     atol (float): the tolerance on the Weyl coordinates.
+    ///
+    /// Returns:
+    ///     (np.ndarray, dict[str, bool]): the ``(4, 2, 2)`` array of ``K1l``, ``K1r``, ``K2l`` and
+    ///     ``K2r``, and the flags ``a_is_zero``, ``a_is_pi4``, ``b_is_zero``, ``b_is_pi4``,
+    ///     ``c_is_zero``, ``c_is_pi4`` (of ``|c|``), ``a_equals_b`` and ``b_equals_c`` (of
+    ///     ``|c|``).
+    #[pyo3(signature = (atol=ANGLE_ZERO_EPSILON))]
+    fn k_matrices_and_closeness<'py>(
+        &self,
+        py: Python<'py>,
+        atol: f64,
+    ) -> PyResult<(Bound<'py, PyArray3<Complex64>>, Bound<'py, PyDict>)> {
+        let flags = PyDict::new_bound(py);
+        for (name, value) in self.closeness(atol).items() {
+            flags.set_item(name, value)?;
+        }
+        Ok((self.stacked_k_matrices().into_pyarray_bound(py), flags))
+    }
+
+    #[pyo3(signature = (euler_basis=None, simplify=false, atol=None))]
+    fn circuit(
+        &self,
+        euler_basis: Option<PyBackedStr>,
+        simplify: bool,
+        atol: Option<f64>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        let euler_basis: Option<EulerBasis> = match euler_basis {
+            Some(basis) => Some(EulerBasis::from_str(basis.deref())?),
+            None => None,
+        };
+        Ok(self.circuit_inner(euler_basis, simplify, atol))
+    }
+}
+
+impl TwoQubitWeylDecomposition {
+    /// Compute the Weyl decomposition of a 4x4 unitary.
+    pub fn new_inner(
+        unitary_matrix: ArrayView2<Complex64>,
+        fidelity: Option<f64>,
+        _specialization: Option<Specialization>,
+    ) -> Result<Self, AccelerateError> {
         let ipz: ArrayView2<Complex64> = aview2(&IPZ);
         let ipy: ArrayView2<Complex64> = aview2(&IPY);
         let ipx: ArrayView2<Complex64> = aview2(&IPX);
 
-        let mut u = unitary_matrix.as_array().to_owned();
-        let unitary_matrix = unitary_matrix.as_array().to_owned();
+        let mut u = unitary_matrix.to_owned();
+        let unitary_matrix = unitary_matrix.to_owned();
         let det_u = u.view().into_faer_complex().determinant().to_num_complex();
         let det_pow = det_u.powf(-0.25);
         u.mapv_inplace(|x| x * det_pow);
@@ -692,9 +896,12 @@ impl TwoQubitWeylDecomposition {
             }
         }
         if !found {
-            return Err(QiskitError::new_err(format!(
-                "TwoQubitWeylDecomposition: failed to diagonalize M2. Please report this at https://github.com/Qiskit/qiskit-terra/issues/4159. Input: {:?}", unitary_matrix
-            )));
+            return Err(AccelerateError::Synthesis(
+                format!(
+                    "TwoQubitWeylDecomposition: failed to diagonalize M2. Please report this at https://github.com/Qiskit/qiskit-terra/issues/4159. Input: {:?}", unitary_matrix
+                ),
+                ErrorContext::pass("TwoQubitWeylDecomposition").with_tolerance(1.0e-13),
+            ));
         }
         let mut d = -d.map(|x| x.arg() / 2.);
         d[3] = -d[0] - d[1] - d[2];
@@ -787,12 +994,7 @@ impl TwoQubitWeylDecomposition {
         }
         let [a, b, c] = [cs[1], cs[0], cs[2]];
         let is_close = |ap: f64, bp: f64, cp: f64| -> bool {
-            let [da, db, dc] = [a - ap, b - bp, c - cp];
-            let tr = 4.
-                * Complex64::new(
-                    da.cos() * db.cos() * dc.cos(),
-                    da.sin() * db.sin() * dc.sin(),
-                );
+            let tr = canonical_trace([a - ap, b - bp, c - cp]);
             match fidelity {
                 Some(fid) => tr.trace_to_fid() >= fid,
                 // Set to false here to default to general specialization in the absence of a
@@ -1063,77 +1265,42 @@ impl TwoQubitWeylDecomposition {
         };
 
         let tr = if flipped_from_original {
-            let [da, db, dc] = [
+            canonical_trace([
                 PI2 - a - specialized.a,
                 b - specialized.b,
                 -c - specialized.c,
-            ];
-            4. * Complex64::new(
-                da.cos() * db.cos() * dc.cos(),
-                da.sin() * db.sin() * dc.sin(),
-            )
+            ])
         } else {
-            let [da, db, dc] = [a - specialized.a, b - specialized.b, c - specialized.c];
-            4. * Complex64::new(
-                da.cos() * db.cos() * dc.cos(),
-                da.sin() * db.sin() * dc.sin(),
-            )
+            canonical_trace([a - specialized.a, b - specialized.b, c - specialized.c])
         };
         specialized.calculated_fidelity = tr.trace_to_fid();
         if let Some(fid) = specialized.requested_fidelity {
             if specialized.calculated_fidelity + 1.0e-13 < fid {
-                return Err(QiskitError::new_err(format!(
-                    "Specialization: {:?} calculated fidelity: {} is worse than requested fidelity: {}",
-                    specialized.specialization,
-                    specialized.calculated_fidelity,
-                    fid
-                )));
+                // The requested fidelity is reported as a tolerance on the infidelity.
+                return Err(AccelerateError::Synthesis(
+                    format!(
+                        "Specialization: {:?} calculated fidelity: {} is worse than requested fidelity: {}",
+                        specialized.specialization,
+                        specialized.calculated_fidelity,
+                        fid
+                    ),
+                    ErrorContext::pass("TwoQubitWeylDecomposition").with_tolerance(1. - fid),
+                ));
             }
         }
         specialized.global_phase += tr.arg();
         Ok(specialized)
     }
 
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K1l(&self, py: Python) -> PyObject {
-        self.K1l.to_pyarray_bound(py).into()
-    }
-
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K1r(&self, py: Python) -> PyObject {
-        self.K1r.to_pyarray_bound(py).into()
-    }
-
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K2l(&self, py: Python) -> PyObject {
-        self.K2l.to_pyarray_bound(py).into()
-    }
-
-    #[allow(non_snake_case)]
-    #[getter]
-    fn K2r(&self, py: Python) -> PyObject {
-        self.K2r.to_pyarray_bound(py).into()
-    }
-
-    #[getter]
-    fn unitary_matrix(&self, py: Python) -> PyObject {
-        self.unitary_matrix.to_pyarray_bound(py).into()
-    }
-
-    #[pyo3(signature = (euler_basis=None, simplify=false, atol=None))]
-    fn circuit(
+    /// Synthesize the decomposition as a two-qubit gate sequence, using ``euler_basis`` (or the
+    /// default basis of the decomposition if ``None``) for the single-qubit components.
+    pub fn circuit_inner(
         &self,
-        euler_basis: Option<PyBackedStr>,
+        euler_basis: Option<EulerBasis>,
         simplify: bool,
         atol: Option<f64>,
-    ) -> PyResult<TwoQubitGateSequence> {
-        let euler_basis: EulerBasis = match euler_basis {
-            Some(basis) => EulerBasis::__new__(basis.deref())?,
-            None => self.default_euler_basis,
-        };
+    ) -> TwoQubitGateSequence {
+        let euler_basis: EulerBasis = euler_basis.unwrap_or(self.default_euler_basis);
         let target_1q_basis_list: Vec<EulerBasis> = vec![euler_basis];
 
         let mut gate_sequence = Vec::new();
@@ -1196,31 +1363,48 @@ impl TwoQubitWeylDecomposition {
         for gate in c1l.gates {
             gate_sequence.push((gate.0, gate.1, smallvec![1]))
         }
-        Ok(TwoQubitGateSequence {
+        TwoQubitGateSequence {
             gates: gate_sequence,
             global_phase,
-        })
+        }
     }
 }
 
-type TwoQubitSequenceVec = Vec<(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>)>;
+pub type TwoQubitSequenceVec = Vec<(String, SmallVec<[f64; 3]>, SmallVec<[u8; 2]>)>;
 
-#[pyclass(sequence)]
+#[cfg_attr(feature = "python", pyclass(sequence))]
 pub struct TwoQubitGateSequence {
-    gates: TwoQubitSequenceVec,
-    #[pyo3(get)]
-    global_phase: f64,
+    pub gates: TwoQubitSequenceVec,
+    pub global_phase: f64,
 }
 
-#[pymethods]
 impl TwoQubitGateSequence {
-    #[new]
-    fn new() -> Self {
+    pub fn new() -> Self {
         TwoQubitGateSequence {
             gates: Vec::new(),
             global_phase: 0.,
         }
     }
+}
+
+impl Default for TwoQubitGateSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl TwoQubitGateSequence {
+    #[new]
+    fn py_new() -> Self {
+        TwoQubitGateSequence::new()
+    }
+
+    #[getter]
+    fn global_phase(&self) -> f64 {
+        self.global_phase
+    }
 
     fn __getstate__(&self) -> (TwoQubitSequenceVec, f64) {
         (self.gates.clone(), self.global_phase)
@@ -1279,15 +1463,17 @@ impl TwoQubitGateSequence {
     }
 }
 #[allow(non_snake_case)]
-#[pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "qiskit._accelerate.two_qubit_decompose", subclass)
+)]
 pub struct TwoQubitBasisDecomposer {
     gate: String,
     basis_fidelity: f64,
     euler_basis: EulerBasis,
     pulse_optimize: Option<bool>,
     basis_decomposer: TwoQubitWeylDecomposition,
-    #[pyo3(get)]
-    super_controlled: bool,
+    pub super_controlled: bool,
     u0l: Array2<Complex64>,
     u0r: Array2<Complex64>,
     u1l: Array2<Complex64>,
@@ -1439,7 +1625,7 @@ impl TwoQubitBasisDecomposer {
         let mut gates = Vec::new();
         let mut global_phase = target_decomposed.global_phase;
         global_phase -= 3. * self.basis_decomposer.global_phase;
-        global_phase = global_phase.rem_euclid(TWO_PI);
+        global_phase = normalize_phase(global_phase);
         let atol = 1e-10; // absolute tolerance for floats
                           // Decompose source unitaries to zxz
         let euler_q0: Vec<[f64; 3]> = decomposition
@@ -1481,7 +1667,7 @@ impl TwoQubitBasisDecomposer {
         } else {
             euler_matrix_q0 = rz_matrix(euler_q0[0][2] + euler_q0[1][0]).dot(&euler_matrix_q0);
         }
-        euler_matrix_q0 = aview2(&HGATE).dot(&euler_matrix_q0);
+        euler_matrix_q0 = aview2(&H_GATE).dot(&euler_matrix_q0);
         self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q0.view(), 0);
 
         let rx_0 = rx_matrix(euler_q1[0][0]);
@@ -1489,7 +1675,7 @@ impl TwoQubitBasisDecomposer {
         let rx_1 = rx_matrix(euler_q1[0][2] + euler_q1[1][0]);
         let mut euler_matrix_q1 = rz.dot(&rx_0);
         euler_matrix_q1 = rx_1.dot(&euler_matrix_q1);
-        euler_matrix_q1 = aview2(&HGATE).dot(&euler_matrix_q1);
+        euler_matrix_q1 = aview2(&H_GATE).dot(&euler_matrix_q1);
         self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix_q1.view(), 1);
 
         gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
@@ -1550,12 +1736,12 @@ impl TwoQubitBasisDecomposer {
             return None;
         }
         gates.push(("cx".to_string(), smallvec![], smallvec![1, 0]));
-        let mut euler_matrix = rz_matrix(euler_q0[2][2] + euler_q0[3][0]).dot(&aview2(&HGATE));
+        let mut euler_matrix = rz_matrix(euler_q0[2][2] + euler_q0[3][0]).dot(&aview2(&H_GATE));
         euler_matrix = rx_matrix(euler_q0[3][1]).dot(&euler_matrix);
         euler_matrix = rz_matrix(euler_q0[3][2]).dot(&euler_matrix);
         self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 0);
 
-        let mut euler_matrix = rx_matrix(euler_q1[2][2] + euler_q1[3][0]).dot(&aview2(&HGATE));
+        let mut euler_matrix = rx_matrix(euler_q1[2][2] + euler_q1[3][0]).dot(&aview2(&H_GATE));
         euler_matrix = rz_matrix(euler_q1[3][1]).dot(&euler_matrix);
         euler_matrix = rx_matrix(euler_q1[3][2]).dot(&euler_matrix);
         self.append_1q_sequence(&mut gates, &mut global_phase, euler_matrix.view(), 1);
@@ -1604,7 +1790,7 @@ impl TwoQubitBasisDecomposer {
         best_nbasis: u8,
         decomposition: &SmallVec<[Array2<Complex64>; 8]>,
         target_decomposed: &TwoQubitWeylDecomposition,
-    ) -> PyResult<Option<TwoQubitGateSequence>> {
+    ) -> Result<Option<TwoQubitGateSequence>, AccelerateError> {
         if self.pulse_optimize.is_some()
             && (best_nbasis == 0 || best_nbasis == 1 || best_nbasis > 3)
         {
@@ -1615,7 +1801,7 @@ impl TwoQubitBasisDecomposer {
             EulerBasis::ZSXX => (),
             _ => {
                 if self.pulse_optimize.is_some() {
-                    return Err(QiskitError::new_err(format!(
+                    return Err(AccelerateError::Qiskit(format!(
                         "'pulse_optimize' currently only works with ZSX basis ({} used)",
                         self.euler_basis.as_str()
                     )));
@@ -1626,8 +1812,8 @@ impl TwoQubitBasisDecomposer {
         }
         if self.gate != "cx" {
             if self.pulse_optimize.is_some() {
-                return Err(QiskitError::new_err(
-                    "pulse_optimizer currently only works with CNOT entangling gate",
+                return Err(AccelerateError::Qiskit(
+                    "pulse_optimizer currently only works with CNOT entangling gate".to_string(),
                 ));
             } else {
                 return Ok(None);
@@ -1641,8 +1827,9 @@ impl TwoQubitBasisDecomposer {
             None
         };
         if self.pulse_optimize.is_some() && res.is_none() {
-            return Err(QiskitError::new_err(
-                "Failed to compute requested pulse optimal decomposition",
+            return Err(AccelerateError::Synthesis(
+                "Failed to compute requested pulse optimal decomposition".to_string(),
+                ErrorContext::pass("TwoQubitBasisDecomposer"),
             ));
         }
         Ok(res)
@@ -1669,6 +1856,7 @@ fn decomp0_inner(target: &TwoQubitWeylDecomposition) -> SmallVec<[Array2<Complex
     smallvec![target.K1r.dot(&target.K2r), target.K1l.dot(&target.K2l),]
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl TwoQubitBasisDecomposer {
     fn __getnewargs__(&self, py: Python) -> (String, PyObject, f64, &str, Option<bool>) {
@@ -1693,9 +1881,130 @@ impl TwoQubitBasisDecomposer {
         euler_basis: &str,
         pulse_optimize: Option<bool>,
     ) -> PyResult<Self> {
+        Ok(Self::new_inner(
+            gate,
+            gate_matrix.as_array(),
+            basis_fidelity,
+            EulerBasis::from_str(euler_basis)?,
+            pulse_optimize,
+        )?)
+    }
+
+    #[getter]
+    fn super_controlled(&self) -> bool {
+        self.super_controlled
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`0` uses of the basis gate.
+    /// Result :math:`U_r` has trace:
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r\cdot U_\text{target}^{\dag})\Big\vert =
+    ///     4\Big\vert (\cos(x)\cos(y)\cos(z)+ j \sin(x)\sin(y)\sin(z)\Big\vert
+    ///
+    /// which is optimal for all targets and bases
+    #[staticmethod]
+    fn decomp0(py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 2]> {
+        decomp0_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`1` use of the basis gate
+    /// math:`\sim U_d(a, b, c)`.
+    /// Result :math:`U_r` has trace:
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^{\dag})\Big\vert =
+    ///     4\Big\vert \cos(x-a)\cos(y-b)\cos(z-c) + j \sin(x-a)\sin(y-b)\sin(z-c)\Big\vert
+    ///
+    /// which is optimal for all targets and bases with ``z==0`` or ``c==0``.
+    fn decomp1(&self, py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 4]> {
+        self.decomp1_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`2` uses of the basis gate.
+    ///
+    /// For supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b, result :math:`U_r` has trace
+    ///
+    /// .. math::
+    ///
+    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^\dag) \Big\vert = 4\cos(z)
+    ///
+    /// which is the optimal approximation for basis of CNOT-class :math:`\sim U_d(\pi/4, 0, 0)`
+    /// or DCNOT-class :math:`\sim U_d(\pi/4, \pi/4, 0)` and any target. It may
+    /// be sub-optimal for :math:`b \neq 0` (i.e. there exists an exact decomposition for any target
+    /// using :math:`B \sim U_d(\pi/4, \pi/8, 0)`, but it may not be this decomposition).
+    /// This is an exact decomposition for supercontrolled basis and target :math:`\sim U_d(x, y, 0)`.
+    /// No guarantees for non-supercontrolled basis.
+    fn decomp2_supercontrolled(
+        &self,
+        py: Python,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[PyObject; 6]> {
+        self.decomp2_supercontrolled_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose target with :math:`3` uses of the basis.
+    ///
+    /// This is an exact decomposition for supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b,
+    /// and any target. No guarantees for non-supercontrolled basis.
+    fn decomp3_supercontrolled(
+        &self,
+        py: Python,
+        target: &TwoQubitWeylDecomposition,
+    ) -> SmallVec<[PyObject; 8]> {
+        self.decomp3_supercontrolled_inner(target)
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect()
+    }
+
+    /// Decompose a two-qubit ``unitary`` over fixed basis and :math:`SU(2)` using the best
+    /// approximation given that each basis application has a finite ``basis_fidelity``.
+    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, _num_basis_uses=None))]
+    fn __call__(
+        &self,
+        unitary: PyReadonlyArray2<Complex64>,
+        basis_fidelity: Option<f64>,
+        approximate: bool,
+        _num_basis_uses: Option<u8>,
+    ) -> PyResult<TwoQubitGateSequence> {
+        Ok(self.call_inner(
+            unitary.as_array(),
+            basis_fidelity,
+            approximate,
+            _num_basis_uses,
+        )?)
+    }
+
+    fn num_basis_gates(&self, unitary: PyReadonlyArray2<Complex64>) -> usize {
+        _num_basis_gates(self.basis_decomposer.b, self.basis_fidelity, unitary)
+    }
+}
+
+impl TwoQubitBasisDecomposer {
+    /// Build a decomposer targeting the two-qubit basis gate named ``gate`` with the 4x4 unitary
+    /// ``gate_matrix``.
+    pub fn new_inner(
+        gate: String,
+        gate_matrix: ArrayView2<Complex64>,
+        basis_fidelity: f64,
+        euler_basis: EulerBasis,
+        pulse_optimize: Option<bool>,
+    ) -> Result<Self, AccelerateError> {
         let ipz: ArrayView2<Complex64> = aview2(&IPZ);
         let basis_decomposer =
-            TwoQubitWeylDecomposition::new(gate_matrix, Some(DEFAULT_FIDELITY), None)?;
+            TwoQubitWeylDecomposition::new_inner(gate_matrix, Some(DEFAULT_FIDELITY), None)?;
         let super_controlled = relative_eq!(basis_decomposer.a, PI4, max_relative = 1e-09)
             && relative_eq!(basis_decomposer.c, 0.0, max_relative = 1e-09);
 
@@ -1817,7 +2126,7 @@ impl TwoQubitBasisDecomposer {
         Ok(TwoQubitBasisDecomposer {
             gate,
             basis_fidelity,
-            euler_basis: EulerBasis::__new__(euler_basis)?,
+            euler_basis,
             pulse_optimize,
             basis_decomposer,
             super_controlled,
@@ -1845,114 +2154,29 @@ impl TwoQubitBasisDecomposer {
 
     fn traces(&self, target: &TwoQubitWeylDecomposition) -> [Complex64; 4] {
         [
-            4. * Complex64::new(
-                target.a.cos() * target.b.cos() * target.c.cos(),
-                target.a.sin() * target.b.sin() * target.c.sin(),
-            ),
-            4. * Complex64::new(
-                (PI4 - target.a).cos()
-                    * (self.basis_decomposer.b - target.b).cos()
-                    * target.c.cos(),
-                (PI4 - target.a).sin()
-                    * (self.basis_decomposer.b - target.b).sin()
-                    * target.c.sin(),
-            ),
+            canonical_trace([target.a, target.b, target.c]),
+            canonical_trace([PI4 - target.a, self.basis_decomposer.b - target.b, target.c]),
             Complex64::new(4. * target.c.cos(), 0.),
             Complex64::new(4., 0.),
         ]
     }
 
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`0` uses of the basis gate.
-    /// Result :math:`U_r` has trace:
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r\cdot U_\text{target}^{\dag})\Big\vert =
-    ///     4\Big\vert (\cos(x)\cos(y)\cos(z)+ j \sin(x)\sin(y)\sin(z)\Big\vert
-    ///
-    /// which is optimal for all targets and bases
-    #[staticmethod]
-    fn decomp0(py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 2]> {
-        decomp0_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
-    }
-
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`1` use of the basis gate
-    /// math:`\sim U_d(a, b, c)`.
-    /// Result :math:`U_r` has trace:
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^{\dag})\Big\vert =
-    ///     4\Big\vert \cos(x-a)\cos(y-b)\cos(z-c) + j \sin(x-a)\sin(y-b)\sin(z-c)\Big\vert
-    ///
-    /// which is optimal for all targets and bases with ``z==0`` or ``c==0``.
-    fn decomp1(&self, py: Python, target: &TwoQubitWeylDecomposition) -> SmallVec<[PyObject; 4]> {
-        self.decomp1_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
-    }
-
-    /// Decompose target :math:`\sim U_d(x, y, z)` with :math:`2` uses of the basis gate.
-    ///
-    /// For supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b, result :math:`U_r` has trace
-    ///
-    /// .. math::
-    ///
-    ///     \Big\vert\text{Tr}(U_r \cdot U_\text{target}^\dag) \Big\vert = 4\cos(z)
-    ///
-    /// which is the optimal approximation for basis of CNOT-class :math:`\sim U_d(\pi/4, 0, 0)`
-    /// or DCNOT-class :math:`\sim U_d(\pi/4, \pi/4, 0)` and any target. It may
-    /// be sub-optimal for :math:`b \neq 0` (i.e. there exists an exact decomposition for any target
-    /// using :math:`B \sim U_d(\pi/4, \pi/8, 0)`, but it may not be this decomposition).
-    /// This is an exact decomposition for supercontrolled basis and target :math:`\sim U_d(x, y, 0)`.
-    /// No guarantees for non-supercontrolled basis.
-    fn decomp2_supercontrolled(
-        &self,
-        py: Python,
-        target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[PyObject; 6]> {
-        self.decomp2_supercontrolled_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
-    }
-
-    /// Decompose target with :math:`3` uses of the basis.
-    ///
-    /// This is an exact decomposition for supercontrolled basis :math:`\sim U_d(\pi/4, b, 0)`, all b,
-    /// and any target. No guarantees for non-supercontrolled basis.
-    fn decomp3_supercontrolled(
-        &self,
-        py: Python,
-        target: &TwoQubitWeylDecomposition,
-    ) -> SmallVec<[PyObject; 8]> {
-        self.decomp3_supercontrolled_inner(target)
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect()
-    }
-
     /// Decompose a two-qubit ``unitary`` over fixed basis and :math:`SU(2)` using the best
     /// approximation given that each basis application has a finite ``basis_fidelity``.
-    #[pyo3(signature = (unitary, basis_fidelity=None, approximate=true, _num_basis_uses=None))]
-    fn __call__(
+    pub fn call_inner(
         &self,
-        unitary: PyReadonlyArray2<Complex64>,
+        unitary: ArrayView2<Complex64>,
         basis_fidelity: Option<f64>,
         approximate: bool,
         _num_basis_uses: Option<u8>,
-    ) -> PyResult<TwoQubitGateSequence> {
+    ) -> Result<TwoQubitGateSequence, AccelerateError> {
         let basis_fidelity = if !approximate {
             1.0
         } else {
             basis_fidelity.unwrap_or(self.basis_fidelity)
         };
         let target_decomposed =
-            TwoQubitWeylDecomposition::new(unitary, Some(DEFAULT_FIDELITY), None)?;
+            TwoQubitWeylDecomposition::new_inner(unitary, Some(DEFAULT_FIDELITY), None)?;
         let traces = self.traces(&target_decomposed);
         let best_nbasis = traces
             .into_iter()
@@ -2037,17 +2261,95 @@ impl TwoQubitBasisDecomposer {
         })
     }
 
-    fn num_basis_gates(&self, unitary: PyReadonlyArray2<Complex64>) -> usize {
-        _num_basis_gates(self.basis_decomposer.b, self.basis_fidelity, unitary)
+    /// The number of basis gates needed to best approximate ``unitary``.
+    pub fn num_basis_gates_inner(&self, unitary: ArrayView2<Complex64>) -> usize {
+        __num_basis_gates(
+            self.basis_decomposer.b,
+            self.basis_fidelity,
+            unitary.into_faer_complex(),
+        )
     }
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn two_qubit_decompose(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(_num_basis_gates))?;
+    m.add_wrapped(wrap_pyfunction!(num_basis_gates_weyl))?;
     m.add_class::<TwoQubitGateSequence>()?;
     m.add_class::<TwoQubitWeylDecomposition>()?;
     m.add_class::<Specialization>()?;
     m.add_class::<TwoQubitBasisDecomposer>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specialization_variants_and_names() {
+        for (index, specialization) in Specialization::ALL.iter().enumerate() {
+            assert_eq!(*specialization as usize, index);
+            assert_eq!(specialization.name(), format!("{specialization:?}"));
+        }
+    }
+
+    fn decomposition(a: f64, b: f64, c: f64) -> TwoQubitWeylDecomposition {
+        let k = |x: f64| Array2::from_diag(&array![Complex64::new(x, 0.), Complex64::new(0., x)]);
+        TwoQubitWeylDecomposition {
+            a,
+            b,
+            c,
+            global_phase: 0.,
+            K1l: k(1.),
+            K2l: k(3.),
+            K1r: k(2.),
+            K2r: k(4.),
+            specialization: Specialization::General,
+            default_euler_basis: EulerBasis::ZYZ,
+            requested_fidelity: None,
+            calculated_fidelity: 1.,
+            unitary_matrix: Array2::eye(4),
+        }
+    }
+
+    #[test]
+    fn stacked_k_matrices_order() {
+        let decomposition = decomposition(0.3, 0.2, 0.1);
+        let stacked = decomposition.stacked_k_matrices();
+        assert_eq!(stacked.dim(), (4, 2, 2));
+        for (slot, k) in stacked.outer_iter().zip([
+            &decomposition.K1l,
+            &decomposition.K1r,
+            &decomposition.K2l,
+            &decomposition.K2r,
+        ]) {
+            assert_eq!(slot, k);
+        }
+    }
+
+    #[test]
+    fn closeness_flags() {
+        let flags = decomposition(PI4, PI4 - 1e-13, -PI4).closeness(1e-12);
+        assert_eq!(
+            flags,
+            WeylCloseness {
+                a_is_zero: false,
+                a_is_pi4: true,
+                b_is_zero: false,
+                b_is_pi4: true,
+                c_is_zero: false,
+                c_is_pi4: true,
+                a_equals_b: true,
+                b_equals_c: true,
+            }
+        );
+        let flags = decomposition(0.3, 1e-13, 0.).closeness(1e-12);
+        assert!(flags.b_is_zero && flags.c_is_zero && flags.b_equals_c);
+        assert!(!(flags.a_is_zero || flags.a_is_pi4 || flags.a_equals_b));
+        let names = flags.items().map(|(name, _)| name);
+        assert_eq!(names[0], "a_is_zero");
+        assert_eq!(names[7], "b_equals_c");
+    }
+}
@@ -11,13 +11,17 @@
 // that they have been altered from the originals.
 
 use num_complex::{Complex64, ComplexFloat};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
 use pyo3::Python;
 use std::f64::consts::{FRAC_1_SQRT_2, PI};
 
 use faer_ext::{IntoFaerComplex, IntoNdarrayComplex};
 use ndarray::prelude::*;
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, PyReadonlyArray2};
 
 use crate::euler_one_qubit_decomposer::det_one_qubit;
@@ -80,16 +84,33 @@ fn demultiplex_single_uc(
     [v, u, r]
 }
 
+#[cfg(feature = "python")]
 #[pyfunction]
 pub fn dec_ucg_help(
     py: Python,
     sq_gates: Vec<PyReadonlyArray2<Complex64>>,
     num_qubits: u32,
 ) -> (Vec<PyObject>, PyObject) {
-    let mut single_qubit_gates: Vec<Array2<Complex64>> = sq_gates
+    let single_qubit_gates: Vec<Array2<Complex64>> = sq_gates
         .into_iter()
         .map(|x| x.as_array().to_owned())
         .collect();
+    let (single_qubit_gates, diag) = dec_ucg_help_inner(single_qubit_gates, num_qubits);
+    (
+        single_qubit_gates
+            .into_iter()
+            .map(|x| x.into_pyarray_bound(py).into())
+            .collect(),
+        diag.into_pyarray_bound(py).into(),
+    )
+}
+
+/// Find the single-qubit gates of the decomposition of the uniformly controlled gate with
+/// `single_qubit_gates` on `num_qubits` qubits, and the diagonal up to which it is implemented.
+pub fn dec_ucg_help_inner(
+    mut single_qubit_gates: Vec<Array2<Complex64>>,
+    num_qubits: u32,
+) -> (Vec<Array2<Complex64>>, Array1<Complex64>) {
     let mut diag: Array1<Complex64> = Array1::ones(2_usize.pow(num_qubits));
     let num_controls = num_qubits - 1;
     for dec_step in 0..num_controls {
@@ -147,15 +168,10 @@ pub fn dec_ucg_help(
             }
         }
     }
-    (
-        single_qubit_gates
-            .into_iter()
-            .map(|x| x.into_pyarray_bound(py).into())
-            .collect(),
-        diag.into_pyarray_bound(py).into(),
-    )
+    (single_qubit_gates, diag)
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn uc_gate(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(dec_ucg_help))?;
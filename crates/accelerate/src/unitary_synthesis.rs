@@ -0,0 +1,648 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The flow of the default `UnitarySynthesis` plugin: each unitary of a circuit is synthesized by
+//! the decomposer for its size (the Euler decomposers for one qubit, the two-qubit basis decomposer
+//! for two, and the Shannon decomposition above that), and the result is spliced back into the
+//! circuit in its place.
+
+#![allow(clippy::too_many_arguments)]
+
+#[cfg(feature = "python")]
+use hashbrown::HashSet;
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::pybacked::PyBackedStr;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+#[cfg(feature = "python")]
+use qiskit_circuit::circuit_data::{CircuitData, Param};
+#[cfg(feature = "python")]
+use qiskit_circuit::intern_context::BitType;
+#[cfg(feature = "python")]
+use std::str::FromStr;
+
+use ndarray::prelude::*;
+use num_complex::Complex64;
+
+use qiskit_circuit::operations::StandardGate;
+
+use crate::error::AccelerateError;
+use crate::euler_one_qubit_decomposer::{
+    unitary_to_gate_sequence_inner, EulerBasis, OneQubitGateSequence,
+};
+#[cfg(feature = "python")]
+use crate::instrumentation;
+use crate::synthesis::qsd::qs_decomposition_inner;
+use crate::synthesis::three_qubit::three_qubit_decomposition_inner;
+use crate::synthesis::GateSequence;
+use crate::two_qubit_decompose::{TwoQubitBasisDecomposer, TwoQubitGateSequence};
+
+/// The decomposers that the default plugin chose for the basis gates of the target.
+pub struct DefaultSynthesisBackends<'a> {
+    /// The Euler bases that one-qubit unitaries can be synthesized in; the one with the fewest
+    /// gates is used.  With no bases, one-qubit unitaries are left as they are.
+    pub euler_bases: &'a [EulerBasis],
+    /// The decomposer for two-qubit unitaries, if the basis has a suitable two-qubit gate.
+    pub decomposer_2q: Option<&'a TwoQubitBasisDecomposer>,
+    /// Whether the two-qubit decomposer may approximate to its basis fidelity.
+    pub approximate: bool,
+    /// Whether unitaries on three or more qubits are synthesized; they need a basis to lower to.
+    pub synthesize_multi_qubit: bool,
+}
+
+/// The gates of each Euler basis, in the order of `ONE_QUBIT_EULER_BASIS_GATES` in Python.
+static EULER_BASIS_GATES: [(EulerBasis, &[&str]); 12] = [
+    (EulerBasis::U3, &["u3"]),
+    (EulerBasis::U321, &["u3", "u2", "u1"]),
+    (EulerBasis::U, &["u"]),
+    (EulerBasis::PSX, &["p", "sx"]),
+    (EulerBasis::U1X, &["u1", "rx"]),
+    (EulerBasis::RR, &["r"]),
+    (EulerBasis::ZYZ, &["rz", "ry"]),
+    (EulerBasis::ZXZ, &["rz", "rx"]),
+    (EulerBasis::XZX, &["rz", "rx"]),
+    (EulerBasis::XYX, &["rx", "ry"]),
+    (EulerBasis::ZSXX, &["rz", "sx", "x"]),
+    (EulerBasis::ZSX, &["rz", "sx"]),
+];
+
+/// The two-qubit basis gates that `TwoQubitBasisDecomposer` can target, in order of preference.
+/// `rxx` and `rzx` need the XX decomposer, which is only in Python.
+static KAK_GATES: [StandardGate; 4] = [
+    StandardGate::CXGate,
+    StandardGate::CZGate,
+    StandardGate::ECRGate,
+    StandardGate::ISwapGate,
+];
+
+/// The Euler bases whose gates are all in `basis_gates`, without the bases that another one of
+/// them supersedes, as `Optimize1qGatesDecomposition` chooses them.
+pub fn euler_bases_for(basis_gates: &[&str]) -> Vec<EulerBasis> {
+    let mut bases = EULER_BASIS_GATES
+        .iter()
+        .filter(|(_, gates)| gates.iter().all(|gate| basis_gates.contains(gate)))
+        .map(|(basis, _)| *basis)
+        .collect::<Vec<_>>();
+    let has_u321 = bases.iter().any(|basis| matches!(basis, EulerBasis::U321));
+    let has_zsxx = bases.iter().any(|basis| matches!(basis, EulerBasis::ZSXX));
+    bases.retain(|basis| match basis {
+        EulerBasis::U3 => !has_u321,
+        EulerBasis::ZSX => !has_zsxx,
+        _ => true,
+    });
+    bases
+}
+
+/// The decomposer for two-qubit unitaries into the first gate of `basis_gates` that it supports,
+/// with that gate, or `None` if there is no such gate.
+pub fn decomposer_2q_for(
+    basis_gates: &[&str],
+    euler_basis: EulerBasis,
+    basis_fidelity: f64,
+) -> Result<Option<(StandardGate, TwoQubitBasisDecomposer)>, AccelerateError> {
+    let Some(gate) = KAK_GATES
+        .iter()
+        .find(|gate| basis_gates.contains(&gate.name()))
+    else {
+        return Ok(None);
+    };
+    let matrix = gate.matrix(&[]).expect("the KAK gates have no parameters");
+    let decomposer = TwoQubitBasisDecomposer::new_inner(
+        gate.name().to_string(),
+        matrix.view(),
+        basis_fidelity,
+        euler_basis,
+        None,
+    )?;
+    Ok(Some((*gate, decomposer)))
+}
+
+/// The synthesized circuit of a unitary, in the form of its decomposer.
+pub enum SynthesizedUnitary {
+    OneQubit(OneQubitGateSequence),
+    /// The `USER_GATE` entries of the sequence are the decomposer's basis gate.
+    TwoQubit(TwoQubitGateSequence),
+    MultiQubit(GateSequence),
+}
+
+/// Swap the two qubits of a two-qubit unitary.
+fn swap_qubits(mat: ArrayView2<Complex64>) -> Array2<Complex64> {
+    let order = [0, 2, 1, 3];
+    Array2::from_shape_fn((4, 4), |(i, j)| mat[[order[i], order[j]]])
+}
+
+/// Synthesize the unitary `mat` with the decomposer for its size, or return `None` if there is no
+/// decomposer for it.
+///
+/// `preferred_direction` is the direction `[control, target]` that the two-qubit basis gates
+/// should have, if the hardware has a native direction.  If the decomposer puts its last basis gate
+/// the other way round, the unitary is synthesized again with its qubits swapped, and the qubits of
+/// that circuit are swapped back.
+pub fn synthesize_unitary_inner(
+    mat: ArrayView2<Complex64>,
+    backends: &DefaultSynthesisBackends,
+    preferred_direction: Option<[u8; 2]>,
+) -> Result<Option<SynthesizedUnitary>, AccelerateError> {
+    match mat.shape() {
+        [2, 2] => {
+            Ok(
+                unitary_to_gate_sequence_inner(mat, backends.euler_bases, 0, None, true, None)
+                    .map(SynthesizedUnitary::OneQubit),
+            )
+        }
+        [4, 4] => {
+            let Some(decomposer) = backends.decomposer_2q else {
+                return Ok(None);
+            };
+            let sequence = decomposer.call_inner(mat, None, backends.approximate, None)?;
+            let direction = sequence
+                .gates
+                .iter()
+                .rev()
+                .find(|(_, _, qubits)| qubits.len() == 2)
+                .map(|(_, _, qubits)| [qubits[0], qubits[1]]);
+            match (preferred_direction, direction) {
+                (Some(preferred), Some(direction)) if preferred != direction => {
+                    let mut sequence = decomposer.call_inner(
+                        swap_qubits(mat).view(),
+                        None,
+                        backends.approximate,
+                        None,
+                    )?;
+                    for (_, _, qubits) in sequence.gates.iter_mut() {
+                        for qubit in qubits.iter_mut() {
+                            *qubit = 1 - *qubit;
+                        }
+                    }
+                    Ok(Some(SynthesizedUnitary::TwoQubit(sequence)))
+                }
+                _ => Ok(Some(SynthesizedUnitary::TwoQubit(sequence))),
+            }
+        }
+        [8, 8] if backends.synthesize_multi_qubit => Ok(Some(SynthesizedUnitary::MultiQubit(
+            three_qubit_decomposition_inner(mat)?,
+        ))),
+        [_, _] if backends.synthesize_multi_qubit => Ok(Some(SynthesizedUnitary::MultiQubit(
+            qs_decomposition_inner(mat, true, true)?,
+        ))),
+        _ => Ok(None),
+    }
+}
+
+/// The standard gate with the instruction name `name`.
+#[cfg(feature = "python")]
+fn gate_from_name(name: &str) -> PyResult<StandardGate> {
+    StandardGate::from_name(name).ok_or_else(|| {
+        AccelerateError::Qiskit(format!("synthesis produced the unknown gate '{name}'")).into()
+    })
+}
+
+/// Synthesize the unitaries of a circuit as the default ``UnitarySynthesis`` plugin does when it
+/// targets a list of basis gates.
+///
+/// Each ``unitary`` instruction on at least ``min_qubits`` qubits is replaced by its synthesized
+/// circuit: one-qubit unitaries are decomposed in the best of ``euler_bases``, two-qubit unitaries
+/// by ``decomposer_2q`` (in the native direction of ``coupling_edges``, if it has one), and larger
+/// unitaries by the three-qubit and quantum Shannon decompositions.  Unitaries with no suitable
+/// decomposer are left as they are.
+///
+/// Args:
+///     circuit (CircuitData): the circuit to synthesize.
+///     euler_bases (list[str]): the Euler bases for one-qubit unitaries.
+///     decomposer_2q (TwoQubitBasisDecomposer | None): the decomposer for two-qubit unitaries.
+///     basis_gate (Gate | None): the basis gate of ``decomposer_2q``, placed for the ``USER_GATE``
+///         entries of its output.
+///     approximate (bool): whether ``decomposer_2q`` may approximate.
+///     synthesize_multi_qubit (bool): whether to synthesize the unitaries on three or more qubits.
+///     min_qubits (int): the smallest unitaries to synthesize.
+///     coupling_edges (set[tuple[int, int]] | None): the directed edges of the coupling map, to
+///         find the native direction of two-qubit gates.
+///
+/// Returns:
+///     (CircuitData, float): the synthesized circuit, on the same bits, and the global phase to add
+///     to the circuit.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (circuit, euler_bases, decomposer_2q, basis_gate, approximate, synthesize_multi_qubit, min_qubits=0, coupling_edges=None))]
+pub fn run_default_unitary_synthesis(
+    py: Python,
+    circuit: &CircuitData,
+    euler_bases: Vec<PyBackedStr>,
+    decomposer_2q: Option<PyRef<TwoQubitBasisDecomposer>>,
+    basis_gate: Option<PyObject>,
+    approximate: bool,
+    synthesize_multi_qubit: bool,
+    min_qubits: usize,
+    coupling_edges: Option<HashSet<[BitType; 2]>>,
+) -> PyResult<(CircuitData, f64)> {
+    let _timer = instrumentation::Timer::start("unitary_synthesis");
+    let euler_bases = euler_bases
+        .iter()
+        .map(|basis| EulerBasis::from_str(basis))
+        .collect::<Result<Vec<_>, _>>()?;
+    let backends = DefaultSynthesisBackends {
+        euler_bases: &euler_bases,
+        decomposer_2q: decomposer_2q.as_deref(),
+        approximate,
+        synthesize_multi_qubit,
+    };
+    synthesize_circuit(
+        py,
+        circuit,
+        &backends,
+        basis_gate.as_ref(),
+        min_qubits,
+        coupling_edges.as_ref(),
+    )
+}
+
+/// Synthesize the unitaries of `circuit` with `backends`, as `run_default_unitary_synthesis` does.
+#[cfg(feature = "python")]
+pub(crate) fn synthesize_circuit(
+    py: Python,
+    circuit: &CircuitData,
+    backends: &DefaultSynthesisBackends,
+    basis_gate: Option<&PyObject>,
+    min_qubits: usize,
+    coupling_edges: Option<&HashSet<[BitType; 2]>>,
+) -> PyResult<(CircuitData, f64)> {
+    let preferred_direction = |qubits: &[BitType]| -> Option<[u8; 2]> {
+        let edges = coupling_edges?;
+        let forward = edges.contains(&[qubits[0], qubits[1]]);
+        let backward = edges.contains(&[qubits[1], qubits[0]]);
+        match (forward, backward) {
+            (true, false) => Some([0, 1]),
+            (false, true) => Some([1, 0]),
+            _ => None,
+        }
+    };
+
+    let mut out = circuit.copy_empty_like(py)?;
+    out.reserve(py, circuit.iter_instructions().len());
+    let mut global_phase = 0.;
+    for (op, qubits, clbits) in circuit.iter_instructions() {
+        let op = op.bind(py);
+        let is_unitary = qubits.len() >= min_qubits
+            && op
+                .getattr(intern!(py, "name"))?
+                .eq(intern!(py, "unitary"))?;
+        let synthesized = if is_unitary {
+            let mat = op.call_method0(intern!(py, "to_matrix"))?;
+            let mat = mat.extract::<PyReadonlyArray2<Complex64>>()?;
+            let direction = if qubits.len() == 2 {
+                preferred_direction(qubits)
+            } else {
+                None
+            };
+            synthesize_unitary_inner(mat.as_array(), backends, direction)?
+        } else {
+            None
+        };
+        match synthesized {
+            None => out.push_instruction(op.clone().unbind(), qubits, clbits)?,
+            Some(SynthesizedUnitary::OneQubit(sequence)) => {
+                global_phase += sequence.global_phase;
+                for (name, params) in sequence.gates {
+                    let gate = gate_from_name(&name)?.create_py_op(py, &params)?;
+                    out.push_instruction(gate, qubits, &[])?;
+                }
+            }
+            Some(SynthesizedUnitary::TwoQubit(sequence)) => {
+                global_phase += sequence.global_phase;
+                for (name, params, gate_qubits) in sequence.gates {
+                    let gate = match (name.as_str(), basis_gate) {
+                        ("USER_GATE", Some(basis_gate)) => basis_gate.clone_ref(py),
+                        _ => gate_from_name(&name)?.create_py_op(py, &params)?,
+                    };
+                    let gate_qubits = gate_qubits
+                        .iter()
+                        .map(|q| qubits[*q as usize])
+                        .collect::<Vec<_>>();
+                    out.push_instruction(gate, &gate_qubits, &[])?;
+                }
+            }
+            Some(SynthesizedUnitary::MultiQubit(sequence)) => {
+                global_phase += sequence.global_phase;
+                for (gate, params, gate_qubits) in sequence.gates {
+                    let gate = gate.standard_gate().create_py_op(py, &params)?;
+                    let gate_qubits = gate_qubits.iter().map(|q| qubits[*q]).collect::<Vec<_>>();
+                    out.push_instruction(gate, &gate_qubits, &[])?;
+                }
+            }
+        }
+    }
+    Ok((out, global_phase))
+}
+
+/// Synthesize a unitary matrix into a circuit in ``basis_gates``.
+///
+/// The decomposer is chosen by the size of the unitary, as the default ``UnitarySynthesis`` plugin
+/// does: one qubit is decomposed in the best Euler basis of ``basis_gates``, two qubits by the
+/// two-qubit basis decomposer for the first of ``cx``, ``cz``, ``ecr`` and ``iswap`` in
+/// ``basis_gates``, three qubits by the three-qubit decomposition and more by the quantum Shannon
+/// decomposition.  The last two produce ``cx`` and one-qubit gates, which may need translating to
+/// ``basis_gates``.  Bases of ``rxx`` or ``rzx`` gates need the XX decomposer, which isn't
+/// available here.
+///
+/// Args:
+///     unitary (np.ndarray): the unitary matrix, of a size that is a power of two.
+///     basis_gates (list[str]): the names of the basis gates.
+///     tolerance (float): the infidelity that the two-qubit decomposer may approximate to.  With
+///         zero, the synthesis is exact.
+///
+/// Returns:
+///     CircuitData | None: the synthesized circuit, including its global phase, or ``None`` if
+///     ``basis_gates`` have no decomposer for the size of the unitary.
+///
+/// Raises:
+///     ValueError: if ``unitary`` isn't square with a power-of-two size.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (unitary, basis_gates, tolerance=0.))]
+pub fn synthesize_unitary(
+    py: Python,
+    unitary: PyReadonlyArray2<Complex64>,
+    basis_gates: Vec<PyBackedStr>,
+    tolerance: f64,
+) -> PyResult<Option<CircuitData>> {
+    let _timer = instrumentation::Timer::start("synthesize_unitary");
+    let mat = unitary.as_array();
+    let dim = mat.nrows();
+    if dim != mat.ncols() || !dim.is_power_of_two() || dim < 2 {
+        return Err(AccelerateError::Value(format!(
+            "a unitary must be square with a power-of-two size, not {:?}",
+            mat.shape()
+        ))
+        .into());
+    }
+    let num_qubits = dim.trailing_zeros();
+    let basis_gates = basis_gates.iter().map(|gate| &**gate).collect::<Vec<_>>();
+    let euler_bases = euler_bases_for(&basis_gates);
+    let euler_basis = euler_bases.first().copied().unwrap_or(EulerBasis::U);
+    let kak = if dim == 4 {
+        decomposer_2q_for(&basis_gates, euler_basis, 1. - tolerance)?
+    } else {
+        None
+    };
+    let backends = DefaultSynthesisBackends {
+        euler_bases: &euler_bases,
+        decomposer_2q: kak.as_ref().map(|(_, decomposer)| decomposer),
+        approximate: tolerance > 0.,
+        synthesize_multi_qubit: !basis_gates.is_empty(),
+    };
+    let Some(synthesized) = synthesize_unitary_inner(mat, &backends, None)? else {
+        return Ok(None);
+    };
+    let (instructions, global_phase) = match synthesized {
+        SynthesizedUnitary::OneQubit(sequence) => (
+            sequence
+                .gates
+                .into_iter()
+                .map(|(name, params)| Ok((gate_from_name(&name)?, params.to_vec(), vec![0])))
+                .collect::<PyResult<Vec<_>>>()?,
+            sequence.global_phase,
+        ),
+        SynthesizedUnitary::TwoQubit(sequence) => (
+            sequence
+                .gates
+                .into_iter()
+                .map(|(name, params, qubits)| {
+                    let gate = match (name.as_str(), &kak) {
+                        ("USER_GATE", Some((gate, _))) => *gate,
+                        _ => gate_from_name(&name)?,
+                    };
+                    Ok((
+                        gate,
+                        params.to_vec(),
+                        qubits.iter().map(|q| *q as BitType).collect(),
+                    ))
+                })
+                .collect::<PyResult<Vec<_>>>()?,
+            sequence.global_phase,
+        ),
+        SynthesizedUnitary::MultiQubit(sequence) => (
+            sequence
+                .gates
+                .into_iter()
+                .map(|(gate, params, qubits)| {
+                    (
+                        gate.standard_gate(),
+                        params.to_vec(),
+                        qubits.iter().map(|q| *q as BitType).collect(),
+                    )
+                })
+                .collect(),
+            sequence.global_phase,
+        ),
+    };
+    let mut circuit = CircuitData::from_standard_gates(py, num_qubits, instructions)?;
+    circuit.add_global_phase(py, &Param::Float(global_phase))?;
+    Ok(Some(circuit))
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn unitary_synthesis(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(run_default_unitary_synthesis))?;
+    m.add_wrapped(wrap_pyfunction!(synthesize_unitary))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::operator::apply_left_inner;
+    use crate::random_quantum_info::random_unitary_inner;
+    use crate::rng::generator;
+
+    /// The matrix of the gates `(name, params, qubits)` on `num_qubits` qubits, times a phase.
+    fn sequence_matrix<'a>(
+        num_qubits: usize,
+        gates: impl IntoIterator<Item = (&'a str, &'a [f64], Vec<usize>)>,
+        global_phase: f64,
+    ) -> Array2<Complex64> {
+        let mut out =
+            Array2::<Complex64>::eye(1 << num_qubits) * Complex64::from_polar(1., global_phase);
+        for (name, params, qubits) in gates {
+            let mat = StandardGate::from_name(name)
+                .unwrap()
+                .matrix(params)
+                .unwrap();
+            apply_left_inner(&mut out, mat.view(), &qubits).unwrap();
+        }
+        out
+    }
+
+    fn assert_matrix_eq(actual: ArrayView2<Complex64>, expected: ArrayView2<Complex64>) {
+        let diff = (&actual - &expected).mapv(|x| x.norm()).sum();
+        assert!(diff < 1e-8, "{actual:?} != {expected:?}");
+    }
+
+    fn cx_decomposer() -> TwoQubitBasisDecomposer {
+        let cx = StandardGate::CXGate.matrix(&[]).unwrap();
+        TwoQubitBasisDecomposer::new_inner("cx".to_string(), cx.view(), 1., EulerBasis::ZSX, None)
+            .unwrap()
+    }
+
+    fn synthesize_two_qubit(
+        mat: ArrayView2<Complex64>,
+        backends: &DefaultSynthesisBackends,
+        preferred_direction: Option<[u8; 2]>,
+    ) -> TwoQubitGateSequence {
+        match synthesize_unitary_inner(mat, backends, preferred_direction).unwrap() {
+            Some(SynthesizedUnitary::TwoQubit(sequence)) => sequence,
+            _ => panic!("a two-qubit unitary should be synthesized by the two-qubit decomposer"),
+        }
+    }
+
+    fn two_qubit_matrix(sequence: &TwoQubitGateSequence) -> Array2<Complex64> {
+        sequence_matrix(
+            2,
+            sequence.gates.iter().map(|(name, params, qubits)| {
+                (
+                    name.as_str(),
+                    params.as_slice(),
+                    qubits.iter().map(|q| *q as usize).collect(),
+                )
+            }),
+            sequence.global_phase,
+        )
+    }
+
+    #[test]
+    fn test_swap_qubits() {
+        let cx = StandardGate::CXGate.matrix(&[]).unwrap();
+        let expected = sequence_matrix(2, [("cx", &[][..], vec![1, 0])], 0.);
+        assert_matrix_eq(swap_qubits(cx.view()).view(), expected.view());
+    }
+
+    #[test]
+    fn test_one_qubit() {
+        let backends = DefaultSynthesisBackends {
+            euler_bases: &[EulerBasis::ZSX],
+            decomposer_2q: None,
+            approximate: false,
+            synthesize_multi_qubit: false,
+        };
+        let mut rng = generator(11);
+        for _ in 0..10 {
+            let mat = random_unitary_inner(2, &mut rng);
+            let Some(SynthesizedUnitary::OneQubit(sequence)) =
+                synthesize_unitary_inner(mat.view(), &backends, None).unwrap()
+            else {
+                panic!("a one-qubit unitary should be synthesized by the Euler decomposer");
+            };
+            assert!(sequence
+                .gates
+                .iter()
+                .all(|(name, _)| ["rz", "sx", "x"].contains(&name.as_str())));
+            let out = sequence_matrix(
+                1,
+                sequence
+                    .gates
+                    .iter()
+                    .map(|(name, params)| (name.as_str(), params.as_slice(), vec![0])),
+                sequence.global_phase,
+            );
+            assert_matrix_eq(out.view(), mat.view());
+        }
+    }
+
+    #[test]
+    fn test_two_qubit() {
+        let decomposer = cx_decomposer();
+        let backends = DefaultSynthesisBackends {
+            euler_bases: &[EulerBasis::ZSX],
+            decomposer_2q: Some(&decomposer),
+            approximate: false,
+            synthesize_multi_qubit: false,
+        };
+        let mut rng = generator(12);
+        for _ in 0..10 {
+            let mat = random_unitary_inner(4, &mut rng);
+            let sequence = synthesize_two_qubit(mat.view(), &backends, None);
+            let num_cx = sequence
+                .gates
+                .iter()
+                .filter(|(name, _, _)| name == "cx")
+                .count();
+            assert!(num_cx <= 3);
+            assert_matrix_eq(two_qubit_matrix(&sequence).view(), mat.view());
+        }
+    }
+
+    #[test]
+    fn test_two_qubit_preferred_direction() {
+        let decomposer = cx_decomposer();
+        let backends = DefaultSynthesisBackends {
+            euler_bases: &[EulerBasis::ZSX],
+            decomposer_2q: Some(&decomposer),
+            approximate: false,
+            synthesize_multi_qubit: false,
+        };
+        let mut rng = generator(13);
+        for direction in [[0, 1], [1, 0]] {
+            let mat = random_unitary_inner(4, &mut rng);
+            let sequence = synthesize_two_qubit(mat.view(), &backends, Some(direction));
+            assert!(sequence
+                .gates
+                .iter()
+                .filter(|(_, _, qubits)| qubits.len() == 2)
+                .all(|(_, _, qubits)| qubits.as_slice() == direction));
+            assert_matrix_eq(two_qubit_matrix(&sequence).view(), mat.view());
+        }
+    }
+
+    #[test]
+    fn test_multi_qubit() {
+        let backends = DefaultSynthesisBackends {
+            euler_bases: &[EulerBasis::U],
+            decomposer_2q: None,
+            approximate: false,
+            synthesize_multi_qubit: true,
+        };
+        let mut rng = generator(14);
+        for num_qubits in [3, 4] {
+            let mat = random_unitary_inner(1 << num_qubits, &mut rng);
+            let Some(SynthesizedUnitary::MultiQubit(sequence)) =
+                synthesize_unitary_inner(mat.view(), &backends, None).unwrap()
+            else {
+                panic!("a multi-qubit unitary should be synthesized by the Shannon decomposition");
+            };
+            assert_matrix_eq(sequence.to_matrix(num_qubits).view(), mat.view());
+        }
+    }
+
+    #[test]
+    fn test_no_decomposer() {
+        let backends = DefaultSynthesisBackends {
+            euler_bases: &[],
+            decomposer_2q: None,
+            approximate: false,
+            synthesize_multi_qubit: false,
+        };
+        let mut rng = generator(15);
+        for dim in [2, 4, 8] {
+            let mat = random_unitary_inner(dim, &mut rng);
+            assert!(synthesize_unitary_inner(mat.view(), &backends, None)
+                .unwrap()
+                .is_none());
+        }
+    }
+}
@@ -10,23 +10,132 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::cmp::Ordering;
+
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
+#[cfg(feature = "python")]
 use faer_ext::IntoFaerComplex;
+#[cfg(feature = "python")]
 use num_complex::Complex;
+#[cfg(feature = "python")]
 use numpy::{IntoPyArray, PyReadonlyArray2};
 
+use crate::error::AccelerateError;
+
 /// Return indices that sort partially ordered data.
 /// If `data` contains two elements that are incomparable,
 /// an error will be thrown.
 pub fn arg_sort<T: PartialOrd>(data: &[T]) -> Vec<usize> {
+    arg_sort_by(data, |a, b| a.partial_cmp(b).unwrap())
+}
+
+/// Return indices that sort `data` by `compare`.  The sort is stable, so equal elements keep the
+/// order of their indices.
+pub fn arg_sort_by<T, F>(data: &[T], mut compare: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     let mut indices = (0..data.len()).collect::<Vec<_>>();
-    indices.sort_by(|&a, &b| data[a].partial_cmp(&data[b]).unwrap());
+    indices.sort_by(|&a, &b| compare(&data[a], &data[b]));
     indices
 }
 
+/// Return indices that sort `data` by the keys of its elements, stably.
+pub fn arg_sort_by_key<T, K, F>(data: &[T], mut key: F) -> Vec<usize>
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    let mut indices = (0..data.len()).collect::<Vec<_>>();
+    indices.sort_by_key(|&a| key(&data[a]));
+    indices
+}
+
+/// Return the indices of the `k` first elements of `data` by `compare`, in order, which are the
+/// first `k` indices of [arg_sort_by] without sorting the rest of `data`.
+pub fn arg_top_k_by<T, F>(data: &[T], k: usize, mut compare: F) -> Vec<usize>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut indices = (0..data.len()).collect::<Vec<_>>();
+    // Ties are broken by index, so the selection agrees with the stable sort.
+    let mut by_index = |a: &usize, b: &usize| compare(&data[*a], &data[*b]).then(a.cmp(b));
+    if k < indices.len() {
+        if k > 0 {
+            indices.select_nth_unstable_by(k - 1, &mut by_index);
+        }
+        indices.truncate(k);
+    }
+    indices.sort_unstable_by(by_index);
+    indices
+}
+
+/// Return the inverse of `permutation`, the indices that sort it.
+pub fn invert_permutation(permutation: &[usize]) -> Vec<usize> {
+    let mut out = vec![0; permutation.len()];
+    for (index, target) in permutation.iter().enumerate() {
+        out[*target] = index;
+    }
+    out
+}
+
+/// Reorder `data` in place so that its element at each index is the one that was at the index of
+/// `permutation` there, as the output of [arg_sort] sorts the data it was taken from.
+///
+/// An error is returned if `permutation` isn't a permutation of the indices of `data`, which is
+/// then left unchanged.
+pub fn apply_permutation<T>(data: &mut [T], permutation: &[usize]) -> Result<(), AccelerateError> {
+    if permutation.len() != data.len() {
+        return Err(AccelerateError::Value(format!(
+            "a permutation of {} elements can't reorder {}",
+            permutation.len(),
+            data.len()
+        )));
+    }
+    let mut seen = vec![false; data.len()];
+    for index in permutation {
+        match seen.get_mut(*index) {
+            Some(seen) if !*seen => *seen = true,
+            _ => {
+                return Err(AccelerateError::Value(format!(
+                    "{:?} isn't a permutation",
+                    permutation
+                )))
+            }
+        }
+    }
+    permutation_swaps(permutation, |a, b| data.swap(a, b));
+    Ok(())
+}
+
+/// Call `swap` with the pairs of indices whose swaps, in order, reorder data as
+/// [apply_permutation] does, for data that isn't a plain slice, such as the rows of a matrix.
+/// `permutation` must be a permutation.
+pub fn permutation_swaps<F>(permutation: &[usize], mut swap: F)
+where
+    F: FnMut(usize, usize),
+{
+    // Follow each cycle, carrying the element of its start along it.
+    let mut done = vec![false; permutation.len()];
+    for start in 0..permutation.len() {
+        let mut index = start;
+        while !done[index] {
+            done[index] = true;
+            let next = permutation[index];
+            if next == start {
+                break;
+            }
+            swap(index, next);
+            index = next;
+        }
+    }
+}
+
 /// Return the eigenvalues of `unitary` as a one-dimensional `numpy.ndarray`
 /// with `dtype(complex128)`.
+#[cfg(feature = "python")]
 #[pyfunction]
 #[pyo3(text_signature = "(unitary, /")]
 pub fn eigenvalues(py: Python, unitary: PyReadonlyArray2<Complex<f64>>) -> PyObject {
@@ -41,8 +150,71 @@ pub fn eigenvalues(py: Python, unitary: PyReadonlyArray2<Complex<f64>>) -> PyObj
         .into()
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn utils(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(eigenvalues))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arg_sort() {
+        assert_eq!(arg_sort(&[3., 1., 2.]), vec![1, 2, 0]);
+        // Equal elements keep the order of their indices.
+        let data = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        assert_eq!(arg_sort_by(&data, |a, b| a.0.cmp(&b.0)), vec![1, 3, 0, 2]);
+        assert_eq!(
+            arg_sort_by_key(&[-3i32, 1, -1, 2], |x| x.abs()),
+            vec![1, 2, 3, 0]
+        );
+        assert!(arg_sort::<f64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_arg_top_k_by() {
+        let data = [5, 1, 4, 1, 3, 4];
+        let sorted = arg_sort_by(&data, |a, b| a.cmp(b));
+        for k in 0..=data.len() + 1 {
+            assert_eq!(
+                arg_top_k_by(&data, k, |a, b| a.cmp(b)),
+                sorted[..k.min(data.len())]
+            );
+        }
+        assert_eq!(arg_top_k_by(&data, 2, |a, b| b.cmp(a)), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_permutations() {
+        let permutation = [2, 0, 3, 1];
+        assert_eq!(invert_permutation(&permutation), vec![1, 3, 0, 2]);
+        let mut data = ['a', 'b', 'c', 'd'];
+        apply_permutation(&mut data, &permutation).unwrap();
+        assert_eq!(data, ['c', 'a', 'd', 'b']);
+        apply_permutation(&mut data, &invert_permutation(&permutation)).unwrap();
+        assert_eq!(data, ['a', 'b', 'c', 'd']);
+        // The output of an argsort sorts the data it was taken from.
+        let mut data = [0.3, -1., 2., 0.];
+        let order = arg_sort(&data);
+        apply_permutation(&mut data, &order).unwrap();
+        assert_eq!(data, [-1., 0., 0.3, 2.]);
+        let mut swaps = Vec::new();
+        permutation_swaps(&permutation, |a, b| swaps.push((a, b)));
+        assert_eq!(swaps, vec![(0, 2), (2, 3), (3, 1)]);
+    }
+
+    #[test]
+    fn test_bad_permutations() {
+        let mut data = [1, 2, 3];
+        for permutation in [vec![0, 1], vec![0, 0, 1], vec![0, 1, 3]] {
+            assert!(matches!(
+                apply_permutation(&mut data, &permutation),
+                Err(AccelerateError::Value(_))
+            ));
+            assert_eq!(data, [1, 2, 3]);
+        }
+    }
+}
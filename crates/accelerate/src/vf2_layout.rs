@@ -10,15 +10,19 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+use std::collections::VecDeque;
+
+use hashbrown::{HashMap, HashSet};
 use numpy::PyReadonlyArray1;
 use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 use rayon::prelude::*;
 
+use crate::error::AccelerateError;
 use crate::error_map::ErrorMap;
-use crate::nlayout::{NLayout, VirtualQubit};
-
-const PARALLEL_THRESHOLD: usize = 50;
+use crate::instrumentation;
+use crate::nlayout::{NLayout, PhysicalQubit, VirtualQubit};
+use crate::threading::{self, ParallelKernel};
 
 #[pyclass]
 pub struct EdgeList {
@@ -34,9 +38,13 @@ impl EdgeList {
 }
 
 /// Score a given circuit with a layout applied
+///
+/// With a nonzero ``uncertainty_weight``, the error rate of each entry of ``error_map`` that has a
+/// variance is raised by that many standard deviations before the scoring.
 #[pyfunction]
 #[pyo3(
-    text_signature = "(bit_list, edge_list, error_matrix, layout, strict_direction, run_in_parallel, /)"
+    signature = (bit_list, edge_list, error_map, layout, strict_direction, run_in_parallel, uncertainty_weight=0.),
+    text_signature = "(bit_list, edge_list, error_matrix, layout, strict_direction, run_in_parallel, uncertainty_weight=0.0, /)"
 )]
 pub fn score_layout(
     bit_list: PyReadonlyArray1<i32>,
@@ -45,16 +53,20 @@ pub fn score_layout(
     layout: &NLayout,
     strict_direction: bool,
     run_in_parallel: bool,
+    uncertainty_weight: f64,
 ) -> PyResult<f64> {
+    let _timer = instrumentation::Timer::start("vf2_layout_score");
     let bit_counts = bit_list.as_slice()?;
     let edge_filter_map = |(index_arr, gate_count): &([VirtualQubit; 2], i32)| -> Option<f64> {
-        let mut error = error_map
-            .error_map
-            .get(&[index_arr[0].to_phys(layout), index_arr[1].to_phys(layout)]);
+        let mut error = error_map.penalized_error(
+            &[index_arr[0].to_phys(layout), index_arr[1].to_phys(layout)],
+            uncertainty_weight,
+        );
         if !strict_direction && error.is_none() {
-            error = error_map
-                .error_map
-                .get(&[index_arr[1].to_phys(layout), index_arr[0].to_phys(layout)]);
+            error = error_map.penalized_error(
+                &[index_arr[1].to_phys(layout), index_arr[0].to_phys(layout)],
+                uncertainty_weight,
+            );
         }
         error.map(|error| {
             if !error.is_nan() {
@@ -66,7 +78,7 @@ pub fn score_layout(
     };
     let bit_filter_map = |(v_bit_index, gate_counts): (usize, &i32)| -> Option<f64> {
         let p_bit = VirtualQubit::new(v_bit_index.try_into().unwrap()).to_phys(layout);
-        let error = error_map.error_map.get(&[p_bit, p_bit]);
+        let error = error_map.penalized_error(&[p_bit, p_bit], uncertainty_weight);
 
         error.map(|error| {
             if !error.is_nan() {
@@ -77,38 +89,471 @@ pub fn score_layout(
         })
     };
 
-    let mut fidelity: f64 = if edge_list.edge_list.len() < PARALLEL_THRESHOLD || !run_in_parallel {
+    let edges_in_parallel = run_in_parallel
+        && threading::run_in_parallel(ParallelKernel::Vf2Layout, edge_list.edge_list.len());
+    let bits_in_parallel =
+        run_in_parallel && threading::run_in_parallel(ParallelKernel::Vf2Layout, bit_list.len()?);
+    let mut fidelity: f64 = if !edges_in_parallel {
         edge_list
             .edge_list
             .iter()
             .filter_map(edge_filter_map)
             .product()
     } else {
-        edge_list
-            .edge_list
-            .par_iter()
-            .filter_map(edge_filter_map)
-            .product()
+        threading::install(|| {
+            edge_list
+                .edge_list
+                .par_iter()
+                .filter_map(edge_filter_map)
+                .product()
+        })
     };
-    fidelity *= if bit_list.len()? < PARALLEL_THRESHOLD || !run_in_parallel {
+    fidelity *= if !bits_in_parallel {
         bit_counts
             .iter()
             .enumerate()
             .filter_map(bit_filter_map)
             .product::<f64>()
     } else {
-        bit_counts
-            .par_iter()
-            .enumerate()
-            .filter_map(bit_filter_map)
-            .product()
+        threading::install(|| {
+            bit_counts
+                .par_iter()
+                .enumerate()
+                .filter_map(bit_filter_map)
+                .product()
+        })
     };
     Ok(1. - fidelity)
 }
 
+/// The error rate of an entry of an error map, as bits so that it can be compared and hashed, with
+/// every `NaN` the same.
+fn error_key(errors: &HashMap<[PhysicalQubit; 2], f64>, a: usize, b: usize) -> Option<u64> {
+    errors
+        .get(&[PhysicalQubit::new(a as u32), PhysicalQubit::new(b as u32)])
+        .map(|error| {
+            if error.is_nan() {
+                f64::NAN.to_bits()
+            } else {
+                error.to_bits()
+            }
+        })
+}
+
+/// A backtracking search for the permutations of the physical qubits that map the error map onto
+/// itself, which are the automorphisms of the coupling graph that keep every error rate.
+///
+/// The qubits are mapped in breadth-first order, so every qubit but the first of each connected
+/// component can only go to a neighbor of the image of its parent, and each qubit is only tried on
+/// qubits with the same error rate and the same rates on its incoming and outgoing edges.
+struct AutomorphismSearch<'a> {
+    errors: &'a HashMap<[PhysicalQubit; 2], f64>,
+    neighbors: Vec<Vec<usize>>,
+    color: Vec<usize>,
+    order: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    image: Vec<Option<usize>>,
+    used: Vec<bool>,
+    found: Vec<Vec<PhysicalQubit>>,
+    max_size: usize,
+}
+
+impl<'a> AutomorphismSearch<'a> {
+    fn new(
+        num_qubits: usize,
+        errors: &'a HashMap<[PhysicalQubit; 2], f64>,
+        max_size: usize,
+    ) -> Self {
+        let mut neighbors = vec![Vec::new(); num_qubits];
+        let mut outgoing = vec![Vec::new(); num_qubits];
+        let mut incoming = vec![Vec::new(); num_qubits];
+        for [a, b] in errors.keys() {
+            let (a, b) = (a.index(), b.index());
+            if a == b {
+                continue;
+            }
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+            let key = error_key(errors, a, b).unwrap();
+            outgoing[a].push(key);
+            incoming[b].push(key);
+        }
+        let mut colors = HashMap::new();
+        let color = (0..num_qubits)
+            .map(|qubit| {
+                neighbors[qubit].sort_unstable();
+                neighbors[qubit].dedup();
+                outgoing[qubit].sort_unstable();
+                incoming[qubit].sort_unstable();
+                let signature = (
+                    error_key(errors, qubit, qubit),
+                    std::mem::take(&mut outgoing[qubit]),
+                    std::mem::take(&mut incoming[qubit]),
+                );
+                let next = colors.len();
+                *colors.entry(signature).or_insert(next)
+            })
+            .collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(num_qubits);
+        let mut parent = vec![None; num_qubits];
+        let mut seen = vec![false; num_qubits];
+        for root in 0..num_qubits {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut queue = VecDeque::from([root]);
+            while let Some(qubit) = queue.pop_front() {
+                order.push(qubit);
+                for &next in neighbors[qubit].iter() {
+                    if !seen[next] {
+                        seen[next] = true;
+                        parent[next] = Some(qubit);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        AutomorphismSearch {
+            errors,
+            neighbors,
+            color,
+            order,
+            parent,
+            image: vec![None; num_qubits],
+            used: vec![false; num_qubits],
+            found: Vec::new(),
+            max_size,
+        }
+    }
+
+    /// Whether mapping `qubit` to `target` keeps every error rate between `qubit` and the qubits
+    /// that are already mapped, and adds no edge that isn't there.
+    fn consistent(&self, qubit: usize, target: usize) -> bool {
+        let mut mapped = 0;
+        for &other in self.neighbors[qubit].iter() {
+            let Some(other_image) = self.image[other] else {
+                continue;
+            };
+            if error_key(self.errors, other, qubit) != error_key(self.errors, other_image, target)
+                || error_key(self.errors, qubit, other)
+                    != error_key(self.errors, target, other_image)
+            {
+                return false;
+            }
+            mapped += 1;
+        }
+        mapped
+            == self.neighbors[target]
+                .iter()
+                .filter(|other| self.used[**other])
+                .count()
+    }
+
+    fn extend(&mut self, depth: usize) {
+        if self.found.len() >= self.max_size {
+            return;
+        }
+        if depth == self.order.len() {
+            self.found.push(
+                self.image
+                    .iter()
+                    .map(|target| PhysicalQubit::new(target.unwrap() as u32))
+                    .collect(),
+            );
+            return;
+        }
+        let qubit = self.order[depth];
+        let candidates = match self.parent[qubit] {
+            Some(parent) => self.neighbors[self.image[parent].unwrap()].clone(),
+            None => (0..self.order.len()).collect(),
+        };
+        for target in candidates {
+            if self.used[target]
+                || self.color[target] != self.color[qubit]
+                || !self.consistent(qubit, target)
+            {
+                continue;
+            }
+            self.image[qubit] = Some(target);
+            self.used[target] = true;
+            self.extend(depth + 1);
+            self.image[qubit] = None;
+            self.used[target] = false;
+        }
+    }
+}
+
+/// The permutations of `num_qubits` physical qubits that map the error map `errors` onto itself,
+/// starting with the identity, up to `max_size` of them.
+///
+/// Every permutation keeps the score of any layout it is applied to, since it keeps every error
+/// rate that the score reads.  If there are more than `max_size`, the returned ones aren't a group,
+/// but they still only relate layouts of the same score.
+pub fn error_map_automorphisms(
+    num_qubits: usize,
+    errors: &HashMap<[PhysicalQubit; 2], f64>,
+    max_size: usize,
+) -> Vec<Vec<PhysicalQubit>> {
+    let _timer = instrumentation::Timer::start("vf2_layout_automorphisms");
+    let mut search = AutomorphismSearch::new(num_qubits, errors, max_size.max(1));
+    search.extend(0);
+    search.found
+}
+
+/// The group generated by the permutations `generators` of `num_qubits` physical qubits, starting
+/// with the identity, up to `max_size` of its elements.
+pub fn generated_group(
+    num_qubits: usize,
+    generators: &[Vec<PhysicalQubit>],
+    max_size: usize,
+) -> Vec<Vec<PhysicalQubit>> {
+    let identity = (0..num_qubits as u32)
+        .map(PhysicalQubit::new)
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::from([identity.clone()]);
+    let mut group = vec![identity];
+    let mut next = 0;
+    while next < group.len() && group.len() < max_size {
+        let element = group[next].clone();
+        next += 1;
+        for generator in generators {
+            let product = element
+                .iter()
+                .map(|qubit| generator[qubit.index()])
+                .collect::<Vec<_>>();
+            if seen.insert(product.clone()) {
+                group.push(product);
+                if group.len() >= max_size {
+                    break;
+                }
+            }
+        }
+    }
+    group
+}
+
+/// The symmetries of a device that keep the score of every layout, used to score only one layout
+/// of each class of equivalent layouts that VF2 finds.
+///
+/// Two layouts are equivalent if one is the other followed by a symmetry.  The canonical
+/// representative of a layout is the smallest of its images under the symmetries, in
+/// lexicographic order of the physical qubits of the virtual qubits.
+#[pyclass(module = "qiskit._accelerate.vf2_layout", frozen)]
+pub struct LayoutSymmetry {
+    num_qubits: usize,
+    group: Vec<Vec<PhysicalQubit>>,
+}
+
+#[pymethods]
+impl LayoutSymmetry {
+    /// Find the permutations of the physical qubits that keep every error rate of an error map,
+    /// and so the connectivity of its edges.
+    ///
+    /// Args:
+    ///     num_qubits (int): the number of physical qubits.
+    ///     error_map (ErrorMap): the average error rates of the edges, and of the qubits at
+    ///         ``(q, q)``.
+    ///     max_size (int): the most symmetries to find.  Past this the pruning is only partial.
+    #[staticmethod]
+    #[pyo3(signature = (num_qubits, error_map, max_size=1024))]
+    fn from_error_map(num_qubits: usize, error_map: &ErrorMap, max_size: usize) -> PyResult<Self> {
+        if let Some(qubit) = error_map
+            .error_map
+            .keys()
+            .flatten()
+            .find(|qubit| qubit.index() >= num_qubits)
+        {
+            return Err(AccelerateError::Index(format!(
+                "physical qubit {} of the error map is out of range for {num_qubits} qubits",
+                qubit.index()
+            ))
+            .into());
+        }
+        Ok(LayoutSymmetry {
+            num_qubits,
+            group: error_map_automorphisms(num_qubits, &error_map.error_map, max_size),
+        })
+    }
+
+    /// Use the group generated by known symmetries of the device.  The caller is responsible for
+    /// each generator keeping the error rates that layouts are scored with.
+    ///
+    /// Args:
+    ///     num_qubits (int): the number of physical qubits.
+    ///     generators (list[list[int]]): permutations of the physical qubits, as the image of each
+    ///         qubit.
+    ///     max_size (int): the most elements of the group to generate.
+    ///
+    /// Raises:
+    ///     ValueError: if a generator isn't a permutation of ``num_qubits`` qubits.
+    #[staticmethod]
+    #[pyo3(signature = (num_qubits, generators, max_size=1024))]
+    fn from_generators(
+        num_qubits: usize,
+        generators: Vec<Vec<PhysicalQubit>>,
+        max_size: usize,
+    ) -> PyResult<Self> {
+        for generator in generators.iter() {
+            let mut hit = vec![false; num_qubits];
+            let valid = generator.len() == num_qubits
+                && generator.iter().all(|qubit| {
+                    qubit.index() < num_qubits && !std::mem::replace(&mut hit[qubit.index()], true)
+                });
+            if !valid {
+                return Err(AccelerateError::Value(format!(
+                    "{:?} is not a permutation of {num_qubits} qubits",
+                    generator
+                        .iter()
+                        .map(|qubit| qubit.index())
+                        .collect::<Vec<_>>()
+                ))
+                .into());
+            }
+        }
+        Ok(LayoutSymmetry {
+            num_qubits,
+            group: generated_group(num_qubits, &generators, max_size.max(1)),
+        })
+    }
+
+    /// The canonical representative of a layout.
+    ///
+    /// Args:
+    ///     layout (list[int]): the physical qubit of each virtual qubit.
+    ///
+    /// Returns:
+    ///     list[int]: the representative, which is the same for every equivalent layout.
+    fn canonical(&self, layout: Vec<PhysicalQubit>) -> PyResult<Vec<PhysicalQubit>> {
+        if let Some(qubit) = layout.iter().find(|qubit| qubit.index() >= self.num_qubits) {
+            return Err(AccelerateError::Index(format!(
+                "physical qubit {} is out of range for {} qubits",
+                qubit.index(),
+                self.num_qubits
+            ))
+            .into());
+        }
+        let mut best = layout.clone();
+        let mut image = Vec::with_capacity(layout.len());
+        for element in self.group.iter() {
+            image.clear();
+            image.extend(layout.iter().map(|qubit| element[qubit.index()]));
+            if image < best {
+                best.clone_from(&image);
+            }
+        }
+        Ok(best)
+    }
+
+    /// The number of symmetries, including the identity.
+    fn __len__(&self) -> usize {
+        self.group.len()
+    }
+}
+
 #[pymodule]
 pub fn vf2_layout(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(score_layout))?;
     m.add_class::<EdgeList>()?;
+    m.add_class::<LayoutSymmetry>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qubits(indices: &[u32]) -> Vec<PhysicalQubit> {
+        indices.iter().copied().map(PhysicalQubit::new).collect()
+    }
+
+    fn errors(entries: &[([u32; 2], f64)]) -> HashMap<[PhysicalQubit; 2], f64> {
+        entries
+            .iter()
+            .map(|([a, b], error)| ([PhysicalQubit::new(*a), PhysicalQubit::new(*b)], *error))
+            .collect()
+    }
+
+    #[test]
+    fn test_line_reflection() {
+        let uniform = errors(&[
+            ([0, 1], 0.01),
+            ([1, 0], 0.01),
+            ([1, 2], 0.01),
+            ([2, 1], 0.01),
+        ]);
+        assert_eq!(
+            error_map_automorphisms(3, &uniform, 1024),
+            vec![qubits(&[0, 1, 2]), qubits(&[2, 1, 0])]
+        );
+        let uneven = errors(&[
+            ([0, 1], 0.01),
+            ([1, 0], 0.01),
+            ([1, 2], 0.02),
+            ([2, 1], 0.02),
+        ]);
+        assert_eq!(
+            error_map_automorphisms(3, &uneven, 1024),
+            vec![qubits(&[0, 1, 2])]
+        );
+    }
+
+    #[test]
+    fn test_qubit_errors_and_directions() {
+        let mut with_qubits = errors(&[
+            ([0, 1], 0.01),
+            ([1, 0], 0.01),
+            ([1, 2], 0.01),
+            ([2, 1], 0.01),
+        ]);
+        with_qubits.extend(errors(&[([0, 0], 0.001), ([2, 2], 0.002)]));
+        assert_eq!(error_map_automorphisms(3, &with_qubits, 1024).len(), 1);
+        // Both edges pointing into the middle qubit are reflected onto each other, where a directed
+        // path isn't.
+        let inward = errors(&[([0, 1], 0.01), ([2, 1], 0.01)]);
+        assert_eq!(error_map_automorphisms(3, &inward, 1024).len(), 2);
+        let path = errors(&[([0, 1], 0.01), ([1, 2], 0.01)]);
+        assert_eq!(error_map_automorphisms(3, &path, 1024).len(), 1);
+    }
+
+    #[test]
+    fn test_disconnected_and_max_size() {
+        // The edge can be flipped and the two qubits without errors swapped.
+        let edge = errors(&[([0, 1], 0.01), ([1, 0], 0.01)]);
+        let found = error_map_automorphisms(4, &edge, 1024);
+        assert_eq!(found.len(), 4);
+        assert_eq!(found[0], qubits(&[0, 1, 2, 3]));
+        assert!(found.contains(&qubits(&[1, 0, 3, 2])));
+        let ring = errors(&[
+            ([0, 1], 0.01),
+            ([1, 2], 0.01),
+            ([2, 3], 0.01),
+            ([3, 0], 0.01),
+        ]);
+        let ring = ring
+            .iter()
+            .flat_map(|([a, b], error)| [([*a, *b], *error), ([*b, *a], *error)])
+            .collect();
+        assert_eq!(error_map_automorphisms(4, &ring, 1024).len(), 8);
+        assert_eq!(error_map_automorphisms(4, &ring, 3).len(), 3);
+        assert_eq!(error_map_automorphisms(4, &ring, 0).len(), 1);
+    }
+
+    #[test]
+    fn test_generated_group() {
+        let rotation = qubits(&[1, 2, 3, 0]);
+        let reflection = qubits(&[3, 2, 1, 0]);
+        let group = generated_group(4, &[rotation.clone()], 1024);
+        assert_eq!(group.len(), 4);
+        assert_eq!(group[0], qubits(&[0, 1, 2, 3]));
+        let group = generated_group(4, &[rotation.clone(), reflection], 1024);
+        assert_eq!(group.len(), 8);
+        assert_eq!(
+            group.iter().collect::<HashSet<_>>().len(),
+            8,
+            "the elements are distinct"
+        );
+        assert_eq!(generated_group(4, &[rotation], 2).len(), 2);
+        assert_eq!(generated_group(4, &[], 1024), vec![qubits(&[0, 1, 2, 3])]);
+    }
+}
@@ -0,0 +1,282 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The geometry of the Weyl chamber of two-qubit gates.
+//!
+//! Every two-qubit unitary is locally equivalent to a canonical gate
+//! `Ud(a, b, c) = exp(i (a XX + b YY + c ZZ))`, and the coordinates `(a, b, c)` are unique once they
+//! are brought into the Weyl chamber `π/4 ≥ a ≥ b ≥ |c|`.  The fidelity between two canonical
+//! gates only depends on the differences of their coordinates, which is what the specializations
+//! of the Weyl decomposition and the basis-gate counting of the decomposers are built on.
+
+use std::f64::consts::PI;
+
+use faer::complex_native::c64;
+use faer::MatRef;
+use faer_ext::IntoFaerComplex;
+use ndarray::ArrayView2;
+use num_complex::Complex64;
+
+#[cfg(feature = "python")]
+use numpy::PyReadonlyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
+
+use crate::two_qubit_decompose::{transform_from_magic_basis, Arg, PowF, TraceToFidelity};
+use crate::utils;
+
+const PI2: f64 = PI / 2.0;
+const PI4: f64 = PI / 4.0;
+const PI32: f64 = 3.0 * PI2;
+
+/// The tolerance within which a coordinate is taken to be on the `a = π/4` face of the chamber.
+const CHAMBER_ATOL: f64 = 1e-12;
+
+/// The Weyl-chamber coordinates `[a, b, c]` of a two-qubit unitary.
+pub fn weyl_coordinates_faer(unitary: MatRef<c64>) -> [f64; 3] {
+    let uscaled = faer::scale(c64::new(1., 0.) / unitary.determinant().powf(0.25)) * unitary;
+    let uup = transform_from_magic_basis(uscaled);
+    let mut darg: Vec<_> = (uup.transpose() * &uup)
+        .complex_eigenvalues()
+        .into_iter()
+        .map(|x: c64| -x.arg() / 2.0)
+        .collect();
+    darg[3] = -darg[0] - darg[1] - darg[2];
+    let mut cs: Vec<_> = (0..3)
+        .map(|i| ((darg[i] + darg[3]) / 2.0).rem_euclid(2.0 * PI))
+        .collect();
+    let cstemp: Vec<f64> = cs
+        .iter()
+        .map(|x| x.rem_euclid(PI2))
+        .map(|x| x.min(PI2 - x))
+        .collect();
+    let mut order = utils::arg_sort(&cstemp);
+    (order[0], order[1], order[2]) = (order[1], order[2], order[0]);
+    (cs[0], cs[1], cs[2]) = (cs[order[0]], cs[order[1]], cs[order[2]]);
+
+    // Flip into Weyl chamber
+    if cs[0] > PI2 {
+        cs[0] -= PI32;
+    }
+    if cs[1] > PI2 {
+        cs[1] -= PI32;
+    }
+    let mut conjs = 0;
+    if cs[0] > PI4 {
+        cs[0] = PI2 - cs[0];
+        conjs += 1;
+    }
+    if cs[1] > PI4 {
+        cs[1] = PI2 - cs[1];
+        conjs += 1;
+    }
+    if cs[2] > PI2 {
+        cs[2] -= PI32;
+    }
+    if conjs == 1 {
+        cs[2] = PI2 - cs[2];
+    }
+    if cs[2] > PI4 {
+        cs[2] -= PI2;
+    }
+    [cs[1], cs[0], cs[2]]
+}
+
+/// The Weyl-chamber coordinates `[a, b, c]` of a two-qubit unitary.
+pub fn weyl_coordinates(unitary: ArrayView2<Complex64>) -> [f64; 3] {
+    weyl_coordinates_faer(unitary.into_faer_complex())
+}
+
+/// Bring any coordinates `[a, b, c]` of a canonical gate into the Weyl chamber, `π/4 ≥ a ≥ b ≥
+/// |c|`, through the shifts by `π/2`, the permutations and the paired sign flips of the
+/// coordinates that preserve the local-equivalence class of the gate.
+pub fn canonicalize(coordinates: [f64; 3]) -> [f64; 3] {
+    // Shift each coordinate into `(-π/4, π/4]`.
+    let mut cs = coordinates.map(|x| {
+        let shifted = (x + PI4).rem_euclid(PI2) - PI4;
+        if shifted <= -PI4 {
+            shifted + PI2
+        } else {
+            shifted
+        }
+    });
+    cs.sort_by(|x, y| y.abs().total_cmp(&x.abs()));
+    // Only pairs of signs can be flipped, so the sign of `c` absorbs the others.
+    if cs[0] < 0. {
+        cs[0] = -cs[0];
+        cs[2] = -cs[2];
+    }
+    if cs[1] < 0. {
+        cs[1] = -cs[1];
+        cs[2] = -cs[2];
+    }
+    // On the `a = π/4` face, flipping `a` and `c` and shifting `a` back maps `c` to `-c`.
+    if cs[2] < 0. && PI4 - cs[0] < CHAMBER_ATOL {
+        cs[2] = -cs[2];
+    }
+    cs
+}
+
+/// The trace `Tr[Ud(a, b, c)† Ud(a', b', c')]` of two canonical gates, from the differences
+/// `[a' - a, b' - b, c' - c]` of their coordinates.
+#[inline]
+pub fn canonical_trace(delta: [f64; 3]) -> Complex64 {
+    let [da, db, dc] = delta;
+    4. * Complex64::new(
+        da.cos() * db.cos() * dc.cos(),
+        da.sin() * db.sin() * dc.sin(),
+    )
+}
+
+/// The average gate infidelity between the canonical gates of two points of the Weyl chamber.
+/// The point `(π/2 - a, b, -c)` is the same class as `(a, b, c)`, which is its mirror image across
+/// the `a = π/4` face, so the nearer of the two images of `right` is used.
+pub fn chamber_distance(left: [f64; 3], right: [f64; 3]) -> f64 {
+    let [a, b, c] = left;
+    let direct = canonical_trace([right[0] - a, right[1] - b, right[2] - c]).trace_to_fid();
+    let mirrored =
+        canonical_trace([PI2 - right[0] - a, right[1] - b, -right[2] - c]).trace_to_fid();
+    1. - direct.max(mirrored)
+}
+
+/// A good approximation to the best value x to get the minimum
+/// trace distance for :math:`U_d(x, x, x)` from :math:`U_d(a, b, c)`.
+pub fn closest_partial_swap(a: f64, b: f64, c: f64) -> f64 {
+    let m = (a + b + c) / 3.;
+    let [am, bm, cm] = [a - m, b - m, c - m];
+    let [ab, bc, ca] = [a - b, b - c, c - a];
+    m + am * bm * cm * (6. + ab * ab + bc * bc + ca * ca) / 18.
+}
+
+/// Return the Weyl-chamber coordinates ``[a, b, c]`` of a two-qubit unitary.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "weyl_coordinates", text_signature = "(unitary, /)")]
+pub fn py_weyl_coordinates(unitary: PyReadonlyArray2<Complex64>) -> [f64; 3] {
+    weyl_coordinates(unitary.as_array())
+}
+
+/// Bring the coordinates ``[a, b, c]`` of a canonical gate into the Weyl chamber
+/// :math:`\pi/4 \ge a \ge b \ge |c|`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "canonicalize", text_signature = "(coordinates, /)")]
+pub fn py_canonicalize(coordinates: [f64; 3]) -> [f64; 3] {
+    canonicalize(coordinates)
+}
+
+/// Return the trace of the product of two canonical gates, from the differences of their
+/// coordinates.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "canonical_trace", text_signature = "(delta, /)")]
+pub fn py_canonical_trace(delta: [f64; 3]) -> Complex64 {
+    canonical_trace(delta)
+}
+
+/// Return the average gate infidelity between the canonical gates of two points of the Weyl
+/// chamber.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "chamber_distance", text_signature = "(left, right, /)")]
+pub fn py_chamber_distance(left: [f64; 3], right: [f64; 3]) -> f64 {
+    chamber_distance(left, right)
+}
+
+/// Return the ``x`` for which :math:`U_d(x, x, x)` is approximately nearest to
+/// :math:`U_d(a, b, c)`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(name = "closest_partial_swap", text_signature = "(a, b, c, /)")]
+pub fn py_closest_partial_swap(a: f64, b: f64, c: f64) -> f64 {
+    closest_partial_swap(a, b, c)
+}
+
+#[cfg(feature = "python")]
+#[pymodule]
+pub fn weyl(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_wrapped(wrap_pyfunction!(py_weyl_coordinates))?;
+    m.add_wrapped(wrap_pyfunction!(py_canonicalize))?;
+    m.add_wrapped(wrap_pyfunction!(py_canonical_trace))?;
+    m.add_wrapped(wrap_pyfunction!(py_chamber_distance))?;
+    m.add_wrapped(wrap_pyfunction!(py_closest_partial_swap))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn test_canonicalize() {
+        // Points in the chamber are left as they are.
+        for point in [
+            [0., 0., 0.],
+            [PI4, 0., 0.],
+            [PI4, PI4, PI4],
+            [0.3, 0.2, -0.1],
+        ] {
+            assert_abs_diff_eq!(canonicalize(point)[..], point[..], epsilon = 1e-12);
+        }
+        // Permutations, paired sign flips and shifts by `π/2` are undone.
+        assert_abs_diff_eq!(
+            canonicalize([0.1, -0.3, 0.2])[..],
+            [0.3, 0.2, -0.1][..],
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            canonicalize([-0.2, -0.3, 0.1])[..],
+            [0.3, 0.2, 0.1][..],
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            canonicalize([0.3 + PI2, 0.2 - PI, -0.1])[..],
+            [0.3, 0.2, -0.1][..],
+            epsilon = 1e-12
+        );
+        // On the `a = π/4` face, `c` and `-c` are the same class.
+        assert_abs_diff_eq!(
+            canonicalize([PI4, 0.1, -0.05])[..],
+            [PI4, 0.1, 0.05][..],
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_chamber_distance() {
+        assert_eq!(canonical_trace([0., 0., 0.]), Complex64::new(4., 0.));
+        for point in [[0., 0., 0.], [PI4, 0., 0.], [0.6, 0.2, 0.1]] {
+            assert!(chamber_distance(point, point).abs() < 1e-12);
+        }
+        // The mirror image across the `a = π/4` face is the same class.
+        assert!(chamber_distance([0.6, 0.2, 0.1], [PI2 - 0.6, 0.2, -0.1]).abs() < 1e-12);
+        // The identity and `CX` have a trace of `2√2`, so an average gate fidelity of `3/5`.
+        assert!((chamber_distance([0., 0., 0.], [PI4, 0., 0.]) - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_closest_partial_swap() {
+        assert_eq!(closest_partial_swap(0.3, 0.3, 0.3), 0.3);
+        for [a, b, c] in [[0.7, 0.3, 0.1], [0.5, 0.2, 0.1], [0.4, 0.4, 0.]] {
+            let x = closest_partial_swap(a, b, c);
+            let best = (0..=1000)
+                .map(|i| -PI4 + PI2 * i as f64 / 1000.)
+                .map(|y| chamber_distance([a, b, c], [y, y, y]))
+                .fold(f64::INFINITY, f64::min);
+            assert!(chamber_distance([a, b, c], [x, x, x]) - best < 1e-3);
+        }
+    }
+}
@@ -11,16 +11,149 @@
 // that they have been altered from the originals.
 
 use crate::circuit_instruction::CircuitInstruction;
+use crate::imports;
 use crate::intern_context::{BitType, IndexType, InternContext};
+use crate::operations::StandardGate;
 use crate::SliceOrInt;
 
 use hashbrown::HashMap;
-use pyo3::exceptions::{PyIndexError, PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::types::{PyList, PySet, PySlice, PyTuple, PyType};
 use pyo3::{PyObject, PyResult, PyTraverseError, PyVisit};
+use std::f64::consts::TAU;
 use std::hash::{Hash, Hasher};
 
+/// A real-valued parameter of a circuit, such as its global phase: either a float, or a
+/// Python-space :class:`.ParameterExpression`.
+///
+/// Arithmetic on two floats stays in Rust; only an expression needs a call into Python.
+#[derive(Clone, Debug)]
+pub enum Param {
+    Float(f64),
+    ParameterExpression(PyObject),
+}
+
+impl Param {
+    /// The sum `self + other`.
+    pub fn add(&self, py: Python, other: &Param) -> PyResult<Param> {
+        match (self, other) {
+            (Param::Float(left), Param::Float(right)) => Ok(Param::Float(left + right)),
+            (Param::Float(left), Param::ParameterExpression(right)) => right
+                .bind(py)
+                .call_method1(intern!(py, "__radd__"), (*left,))?
+                .extract(),
+            (Param::ParameterExpression(left), right) => left
+                .bind(py)
+                .call_method1(intern!(py, "__add__"), (right.to_object(py),))?
+                .extract(),
+        }
+    }
+
+    /// The negation `-self`.
+    pub fn neg(&self, py: Python) -> PyResult<Param> {
+        match self {
+            Param::Float(value) => Ok(Param::Float(-value)),
+            Param::ParameterExpression(expr) => expr
+                .bind(py)
+                .call_method0(intern!(py, "__neg__"))?
+                .extract(),
+        }
+    }
+
+    /// The normal form of an angle, as :class:`.QuantumCircuit` stores its global phase: a float
+    /// in `[0, 2π)`, unless this is an expression with unbound parameters.
+    pub fn normalize_angle(self, py: Python) -> PyResult<Param> {
+        let value = match self {
+            Param::Float(value) => value,
+            Param::ParameterExpression(expr) => {
+                let bound = expr.bind(py);
+                if bound.getattr(intern!(py, "parameters"))?.len()? > 0 {
+                    return Ok(Param::ParameterExpression(expr));
+                }
+                bound.call_method0(intern!(py, "__float__"))?.extract()?
+            }
+        };
+        Ok(Param::Float(value.rem_euclid(TAU)))
+    }
+}
+
+impl<'py> FromPyObject<'py> for Param {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if ob.is_instance(&imports::PARAMETER_EXPRESSION.get_bound(ob.py())?)? {
+            return Ok(Param::ParameterExpression(ob.clone().unbind()));
+        }
+        match ob.extract::<f64>() {
+            Ok(value) => Ok(Param::Float(value)),
+            Err(_) => Err(PyTypeError::new_err(format!(
+                "expected a float or a ParameterExpression, but got '{}'",
+                ob.get_type().name()?
+            ))),
+        }
+    }
+}
+
+impl ToPyObject for Param {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            Param::Float(value) => value.to_object(py),
+            Param::ParameterExpression(expr) => expr.clone_ref(py),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for Param {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            Param::Float(value) => value.into_py(py),
+            Param::ParameterExpression(expr) => expr,
+        }
+    }
+}
+
+/// The timing of an instruction in a scheduled circuit, as set by the scheduling passes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstructionTiming {
+    /// The duration of the instruction, in `unit`.
+    pub duration: Option<f64>,
+    /// The time unit of the duration, such as `"dt"` or `"s"`.
+    pub unit: String,
+    /// The start time of the instruction, in units of `dt`.
+    pub start_time: Option<i64>,
+}
+
+impl Default for InstructionTiming {
+    fn default() -> Self {
+        InstructionTiming {
+            duration: None,
+            unit: "dt".to_string(),
+            start_time: None,
+        }
+    }
+}
+
+impl InstructionTiming {
+    /// The timing as the tuple ``(duration, unit, start_time)`` used in Python space.
+    fn to_tuple(&self) -> (Option<f64>, String, Option<i64>) {
+        (self.duration, self.unit.clone(), self.start_time)
+    }
+
+    /// The timing of the tuple ``(duration, unit, start_time)``, or `None` if it has neither a
+    /// duration nor a start time.
+    fn from_tuple(
+        (duration, unit, start_time): (Option<f64>, String, Option<i64>),
+    ) -> Option<Box<Self>> {
+        (duration.is_some() || start_time.is_some()).then(|| {
+            Box::new(InstructionTiming {
+                duration,
+                unit,
+                start_time,
+            })
+        })
+    }
+}
+
 /// Private type used to store instructions with interned arg lists.
 #[derive(Clone, Debug)]
 struct PackedInstruction {
@@ -30,6 +163,9 @@ struct PackedInstruction {
     qubits_id: IndexType,
     /// The index under which the interner has stored `clbits`.
     clbits_id: IndexType,
+    /// The timing of the instruction, if the circuit has been scheduled.  This is boxed because
+    /// most instructions have none.
+    timing: Option<Box<InstructionTiming>>,
 }
 
 /// Private wrapper for Python-side Bit instances that implements
@@ -127,6 +263,8 @@ impl Eq for BitAsKey {}
 ///         before copying instructions into the container when ``data``
 ///         is provided, so the initialized container's unused capacity will
 ///         be ``max(0, reserve - len(data))``.
+///     global_phase (float | :class:`.ParameterExpression`): The global phase of the
+///         circuit, in radians.
 ///
 /// Raises:
 ///     KeyError: if ``data`` contains a reference to a bit that is not present
@@ -152,18 +290,21 @@ pub struct CircuitData {
     qubits: Py<PyList>,
     /// The clbits registered, cached as a ``list[Clbit]``.
     clbits: Py<PyList>,
+    /// The global phase of the circuit, in normal form.
+    global_phase: Param,
 }
 
 #[pymethods]
 impl CircuitData {
     #[new]
-    #[pyo3(signature = (qubits=None, clbits=None, data=None, reserve=0))]
+    #[pyo3(signature = (qubits=None, clbits=None, data=None, reserve=0, global_phase=Param::Float(0.)))]
     pub fn new(
         py: Python<'_>,
         qubits: Option<&Bound<PyAny>>,
         clbits: Option<&Bound<PyAny>>,
         data: Option<&Bound<PyAny>>,
         reserve: usize,
+        global_phase: Param,
     ) -> PyResult<Self> {
         let mut self_ = CircuitData {
             data: Vec::new(),
@@ -174,6 +315,7 @@ impl CircuitData {
             clbit_indices_native: HashMap::new(),
             qubits: PyList::empty_bound(py).unbind(),
             clbits: PyList::empty_bound(py).unbind(),
+            global_phase: global_phase.normalize_angle(py)?,
         };
         if let Some(qubits) = qubits {
             for bit in qubits.iter()? {
@@ -194,16 +336,36 @@ impl CircuitData {
 
     pub fn __reduce__(self_: &Bound<CircuitData>, py: Python<'_>) -> PyResult<PyObject> {
         let ty: Bound<PyType> = self_.get_type();
-        let args = {
+        let (qubits, clbits, len, global_phase, timings) = {
             let self_ = self_.borrow();
             (
                 self_.qubits.clone_ref(py),
                 self_.clbits.clone_ref(py),
-                None::<()>,
                 self_.data.len(),
+                self_.global_phase.clone(),
+                self_.is_timed().then(|| self_.get_timings()),
             )
         };
-        Ok((ty, args, None::<()>, self_.iter()?).into_py(py))
+        match timings {
+            // `copy` sets the state before it appends the list items, but `__setstate__` needs the
+            // instructions in place, so a timed circuit passes them to the constructor instead.
+            Some(timings) => {
+                let data = PyList::new_bound(py, self_.iter()?.collect::<PyResult<Vec<_>>>()?);
+                let args = (qubits, clbits, data, len, global_phase);
+                Ok((ty, args, timings).into_py(py))
+            }
+            None => {
+                let args = (qubits, clbits, None::<()>, len, global_phase);
+                Ok((ty, args, None::<()>, self_.iter()?).into_py(py))
+            }
+        }
+    }
+
+    pub fn __setstate__(
+        &mut self,
+        state: Vec<Option<(Option<f64>, String, Option<i64>)>>,
+    ) -> PyResult<()> {
+        self.set_timings(state)
     }
 
     /// Returns the current sequence of registered :class:`.Qubit` instances as a list.
@@ -235,6 +397,25 @@ impl CircuitData {
         self.clbits.clone_ref(py)
     }
 
+    /// The global phase of the circuit, in radians.
+    ///
+    /// Rust passes accumulate their phase changes here, and
+    /// :meth:`.QuantumCircuit._from_circuit_data` takes it as the phase of the new circuit.  Float
+    /// phases are normalized to :math:`[0, 2\pi)`.
+    ///
+    /// Returns:
+    ///     float | ParameterExpression: The global phase.
+    #[getter]
+    pub fn get_global_phase(&self, py: Python<'_>) -> PyObject {
+        self.global_phase.to_object(py)
+    }
+
+    #[setter]
+    pub fn set_global_phase(&mut self, py: Python<'_>, angle: Param) -> PyResult<()> {
+        self.global_phase = angle.normalize_angle(py)?;
+        Ok(())
+    }
+
     /// Registers a :class:`.Qubit` instance.
     ///
     /// Args:
@@ -322,6 +503,7 @@ impl CircuitData {
             Some(self.clbits.bind(py)),
             None,
             0,
+            self.global_phase.clone(),
         )?;
         res.intern_context = self.intern_context.clone();
         res.data.clone_from(&self.data);
@@ -458,7 +640,7 @@ impl CircuitData {
         qubits: Option<&Bound<PyAny>>,
         clbits: Option<&Bound<PyAny>>,
     ) -> PyResult<()> {
-        let mut temp = CircuitData::new(py, qubits, clbits, None, 0)?;
+        let mut temp = CircuitData::new(py, qubits, clbits, None, 0, Param::Float(0.))?;
         if qubits.is_some() {
             if temp.qubits_native.len() < self.qubits_native.len() {
                 return Err(PyValueError::new_err(format!(
@@ -669,6 +851,7 @@ impl CircuitData {
                     op: inst.op.clone_ref(py),
                     qubits_id: self.intern_context.intern(qubits)?,
                     clbits_id: self.intern_context.intern(clbits)?,
+                    timing: inst.timing.clone(),
                 });
             }
             return Ok(());
@@ -685,6 +868,126 @@ impl CircuitData {
         Ok(())
     }
 
+    /// The timing of the instruction at ``index``, if the circuit has been scheduled.
+    ///
+    /// Args:
+    ///     index (int): the index of the instruction.
+    ///
+    /// Returns:
+    ///     tuple[float | None, str, int | None] | None: the duration of the instruction, its time
+    ///     unit and its start time in ``dt``, or ``None`` if the instruction has no timing.
+    pub fn get_timing(&self, index: isize) -> PyResult<Option<(Option<f64>, String, Option<i64>)>> {
+        let index = self.convert_py_index(index)?;
+        Ok(self.data[index]
+            .timing
+            .as_ref()
+            .map(|timing| timing.to_tuple()))
+    }
+
+    /// Set the timing of the instruction at ``index``.  Setting neither a duration nor a start
+    /// time removes its timing.
+    ///
+    /// Args:
+    ///     index (int): the index of the instruction.
+    ///     duration (float | None): the duration of the instruction, in ``unit``.
+    ///     unit (str): the time unit of the duration.
+    ///     start_time (int | None): the start time of the instruction, in ``dt``.
+    #[pyo3(signature = (index, duration=None, unit="dt", start_time=None))]
+    pub fn set_timing(
+        &mut self,
+        index: isize,
+        duration: Option<f64>,
+        unit: &str,
+        start_time: Option<i64>,
+    ) -> PyResult<()> {
+        let index = self.convert_py_index(index)?;
+        self.data[index].timing =
+            InstructionTiming::from_tuple((duration, unit.to_string(), start_time));
+        Ok(())
+    }
+
+    /// The timing of each instruction, as :meth:`get_timing` returns it.
+    ///
+    /// Returns:
+    ///     list[tuple[float | None, str, int | None] | None]: the timings.
+    pub fn get_timings(&self) -> Vec<Option<(Option<f64>, String, Option<i64>)>> {
+        self.data
+            .iter()
+            .map(|inst| inst.timing.as_ref().map(|timing| timing.to_tuple()))
+            .collect()
+    }
+
+    /// Set the timing of each instruction, as :meth:`get_timings` returns them.
+    ///
+    /// Args:
+    ///     timings (list[tuple[float | None, str, int | None] | None]): the timings.
+    ///
+    /// Raises:
+    ///     ValueError: if the number of timings isn't the number of instructions.
+    pub fn set_timings(
+        &mut self,
+        timings: Vec<Option<(Option<f64>, String, Option<i64>)>>,
+    ) -> PyResult<()> {
+        if timings.len() != self.data.len() {
+            return Err(PyValueError::new_err(format!(
+                "got {} timings for {} instructions",
+                timings.len(),
+                self.data.len()
+            )));
+        }
+        for (inst, timing) in self.data.iter_mut().zip(timings) {
+            inst.timing = timing.and_then(InstructionTiming::from_tuple);
+        }
+        Ok(())
+    }
+
+    /// The start time of each instruction in ``dt``, or ``None`` for the instructions that
+    /// haven't been scheduled.  Setting this keeps the durations of the instructions; setting it
+    /// to ``None`` removes the start times.
+    #[getter]
+    pub fn get_start_times(&self) -> Vec<Option<i64>> {
+        self.data
+            .iter()
+            .map(|inst| inst.timing.as_ref().and_then(|timing| timing.start_time))
+            .collect()
+    }
+
+    #[setter]
+    pub fn set_start_times(&mut self, start_times: Option<Vec<i64>>) -> PyResult<()> {
+        match start_times {
+            Some(start_times) => self.set_schedule(None, &start_times),
+            None => {
+                for inst in self.data.iter_mut() {
+                    if let Some(mut timing) = inst.timing.take() {
+                        timing.start_time = None;
+                        inst.timing = timing.duration.is_some().then_some(timing);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The duration of each instruction with its time unit, or ``None`` for the instructions
+    /// without a duration.
+    #[getter]
+    pub fn get_durations(&self) -> Vec<Option<(f64, String)>> {
+        self.data
+            .iter()
+            .map(|inst| {
+                let timing = inst.timing.as_ref()?;
+                Some((timing.duration?, timing.unit.clone()))
+            })
+            .collect()
+    }
+
+    /// Remove the timing of every instruction.
+    pub fn clear_timings(&mut self) {
+        for inst in self.data.iter_mut() {
+            inst.timing = None;
+        }
+    }
+
     // Marks this pyclass as NOT hashable.
     #[classattr]
     const __hash__: Option<Py<PyAny>> = None;
@@ -722,6 +1025,9 @@ impl CircuitData {
         for packed in self.data.iter() {
             visit.call(&packed.op)?;
         }
+        if let Param::ParameterExpression(expr) = &self.global_phase {
+            visit.call(expr)?;
+        }
         for bit in self.qubits_native.iter().chain(self.clbits_native.iter()) {
             visit.call(bit)?;
         }
@@ -738,6 +1044,7 @@ impl CircuitData {
     fn __clear__(&mut self) {
         // Clear anything that could have a reference cycle.
         self.data.clear();
+        self.global_phase = Param::Float(0.);
         self.qubits_native.clear();
         self.clbits_native.clear();
         self.qubit_indices_native.clear();
@@ -746,6 +1053,207 @@ impl CircuitData {
 }
 
 impl CircuitData {
+    /// The number of qubits registered in the circuit.
+    pub fn num_qubits(&self) -> usize {
+        self.qubits_native.len()
+    }
+
+    /// The number of clbits registered in the circuit.
+    pub fn num_clbits(&self) -> usize {
+        self.clbits_native.len()
+    }
+
+    /// Build a circuit on `num_qubits` new qubits (and no clbits) from a sequence of standard
+    /// gates, each given with its parameters and the indices of the qubits it acts on.
+    pub fn from_standard_gates<I>(py: Python, num_qubits: u32, instructions: I) -> PyResult<Self>
+    where
+        I: IntoIterator<Item = (StandardGate, Vec<f64>, Vec<BitType>)>,
+    {
+        let qubit_class = imports::QUBIT.get_bound(py)?;
+        let qubits = PyList::empty_bound(py);
+        for _ in 0..num_qubits {
+            qubits.append(qubit_class.call0()?)?;
+        }
+        let mut res = CircuitData::new(py, Some(qubits.as_any()), None, None, 0, Param::Float(0.))?;
+        let instructions = instructions.into_iter();
+        res.reserve(py, instructions.size_hint().0);
+        let no_clbits = res.intern_context.intern(Vec::new())?;
+        for (gate, params, qargs) in instructions {
+            if let Some(qubit) = qargs.iter().find(|qubit| **qubit >= num_qubits) {
+                return Err(PyIndexError::new_err(format!(
+                    "qubit {qubit} is out of range for a circuit with {num_qubits} qubits"
+                )));
+            }
+            res.data.push(PackedInstruction {
+                op: gate.create_py_op(py, &params)?,
+                qubits_id: res.intern_context.intern(qargs)?,
+                clbits_id: no_clbits,
+                timing: None,
+            });
+        }
+        Ok(res)
+    }
+
+    /// The index of the Python-space :class:`.Clbit` `bit` in the circuit, if it has been added.
+    pub fn find_clbit(&self, bit: &Bound<PyAny>) -> PyResult<Option<BitType>> {
+        Ok(self.clbit_indices_native.get(&BitAsKey::new(bit)?).copied())
+    }
+
+    /// A circuit with the same qubits, clbits and global phase as this one, but no instructions.
+    pub fn copy_empty_like(&self, py: Python) -> PyResult<Self> {
+        CircuitData::new(
+            py,
+            Some(self.qubits.bind(py)),
+            Some(self.clbits.bind(py)),
+            None,
+            0,
+            self.global_phase.clone(),
+        )
+    }
+
+    /// The global phase of the circuit.
+    pub fn global_phase(&self) -> &Param {
+        &self.global_phase
+    }
+
+    /// Add `angle` to the global phase of the circuit in place, without going through Python
+    /// unless either phase is a :class:`.ParameterExpression`.
+    pub fn add_global_phase(&mut self, py: Python, angle: &Param) -> PyResult<()> {
+        self.global_phase = self.global_phase.add(py, angle)?.normalize_angle(py)?;
+        Ok(())
+    }
+
+    /// Append the Python-space operation `op`, acting on the qubits and clbits with the given
+    /// indices, without creating a :class:`.CircuitInstruction`.
+    pub fn push_instruction(
+        &mut self,
+        op: PyObject,
+        qubits: &[BitType],
+        clbits: &[BitType],
+    ) -> PyResult<()> {
+        if let Some(qubit) = qubits
+            .iter()
+            .find(|qubit| **qubit as usize >= self.num_qubits())
+        {
+            return Err(PyIndexError::new_err(format!(
+                "qubit {qubit} is out of range for a circuit with {} qubits",
+                self.num_qubits()
+            )));
+        }
+        if let Some(clbit) = clbits
+            .iter()
+            .find(|clbit| **clbit as usize >= self.num_clbits())
+        {
+            return Err(PyIndexError::new_err(format!(
+                "clbit {clbit} is out of range for a circuit with {} clbits",
+                self.num_clbits()
+            )));
+        }
+        let qubits_id = self.intern_context.intern(qubits.to_vec())?;
+        let clbits_id = self.intern_context.intern(clbits.to_vec())?;
+        self.data.push(PackedInstruction {
+            op,
+            qubits_id,
+            clbits_id,
+            timing: None,
+        });
+        Ok(())
+    }
+
+    /// A hash of the instructions of the circuit: the name and parameters of each operation and
+    /// the bits it acts on.
+    ///
+    /// Two circuits with the same instructions have the same hash, even if their operations are
+    /// different Python objects, so this detects whether a transformation changed the circuit.
+    /// Float parameters are hashed by value, array parameters (such as the matrix of a
+    /// :class:`.UnitaryGate`) by their bytes, and any other parameter by its string form.
+    pub fn content_hash(&self, py: Python) -> PyResult<u64> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.data.len().hash(&mut hasher);
+        for (op, qubits, clbits) in self.iter_instructions() {
+            let op = op.bind(py);
+            op.getattr(intern!(py, "name"))?
+                .extract::<String>()?
+                .hash(&mut hasher);
+            for param in op.getattr(intern!(py, "params"))?.iter()? {
+                let param = param?;
+                if let Ok(value) = param.extract::<f64>() {
+                    value.to_bits().hash(&mut hasher);
+                } else if param.hasattr(intern!(py, "tobytes"))? {
+                    param
+                        .call_method0(intern!(py, "tobytes"))?
+                        .extract::<Vec<u8>>()?
+                        .hash(&mut hasher);
+                } else {
+                    param.str()?.to_cow()?.hash(&mut hasher);
+                }
+            }
+            qubits.hash(&mut hasher);
+            clbits.hash(&mut hasher);
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Whether any instruction of the circuit has a timing.
+    pub fn is_timed(&self) -> bool {
+        self.data.iter().any(|inst| inst.timing.is_some())
+    }
+
+    /// The timing of the instruction at `index`, if it is in range and has one.
+    pub fn timing(&self, index: usize) -> Option<&InstructionTiming> {
+        self.data.get(index)?.timing.as_deref()
+    }
+
+    /// Record a schedule of the circuit: the start time of each instruction, in units of `dt`,
+    /// and, if given, its duration in `dt`, which otherwise keeps the duration it had.
+    pub fn set_schedule(&mut self, durations: Option<&[i64]>, start_times: &[i64]) -> PyResult<()> {
+        let num_durations = durations.map_or(self.data.len(), |durations| durations.len());
+        if start_times.len() != self.data.len() || num_durations != self.data.len() {
+            return Err(PyValueError::new_err(format!(
+                "got {} start times and {} durations for {} instructions",
+                start_times.len(),
+                num_durations,
+                self.data.len()
+            )));
+        }
+        for (index, (inst, start_time)) in self.data.iter_mut().zip(start_times).enumerate() {
+            let timing = inst.timing.get_or_insert_with(Default::default);
+            timing.start_time = Some(*start_time);
+            if let Some(durations) = durations {
+                timing.duration = Some(durations[index] as f64);
+                timing.unit = "dt".to_string();
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterate over the instructions in order, yielding each Python-space operation alongside the
+    /// indices of the qubits and clbits it acts on, without creating any
+    /// :class:`.CircuitInstruction` instances.
+    pub fn iter_instructions(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (&PyObject, &[BitType], &[BitType])> + '_ {
+        self.data.iter().map(|inst| {
+            (
+                &inst.op,
+                self.intern_context.lookup(inst.qubits_id),
+                self.intern_context.lookup(inst.clbits_id),
+            )
+        })
+    }
+
+    /// The Python-space operation of the instruction at `index`, alongside the indices of the
+    /// qubits and clbits it acts on, or `None` if `index` is out of range.
+    pub fn get_instruction(&self, index: usize) -> Option<(&PyObject, &[BitType], &[BitType])> {
+        self.data.get(index).map(|inst| {
+            (
+                &inst.op,
+                self.intern_context.lookup(inst.qubits_id),
+                self.intern_context.lookup(inst.clbits_id),
+            )
+        })
+    }
+
     /// Converts a Python slice to a `Vec` of indices into
     /// the instruction listing, [CircuitData.data].
     fn convert_py_slice(&self, slice: &Bound<PySlice>) -> PyResult<Vec<isize>> {
@@ -817,12 +1325,13 @@ impl CircuitData {
                         })
                     })
                     .collect::<PyResult<Vec<BitType>>>()?;
-                self.intern_context.intern(args)
+                Ok(self.intern_context.intern(args)?)
             };
         Ok(PackedInstruction {
             op: inst.operation.clone_ref(py),
             qubits_id: interned_bits(&self.qubit_indices_native, inst.qubits.bind(py))?,
             clbits_id: interned_bits(&self.clbit_indices_native, inst.clbits.bind(py))?,
+            timing: None,
         })
     }
 
@@ -0,0 +1,276 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The circuit container of the crate built without the `python` feature.
+//!
+//! Without Python, an operation can't be an arbitrary Python object, so this `CircuitData` holds
+//! standard gates with their parameters, on qubits and clbits that are plain indices.  The bit
+//! sequences are interned as in the Python-backed container, and the methods that the two share
+//! have the same names and meanings.
+
+use std::fmt;
+
+use crate::intern_context::{BitType, CapacityExceeded, IndexType, InternContext};
+use crate::operations::StandardGate;
+
+/// The error of an instruction that doesn't fit its gate or its circuit.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CircuitDataError {
+    /// A qubit or clbit index is beyond the bits of the circuit.
+    BitOutOfRange { bit: BitType, num_bits: usize },
+    /// The gate was given the wrong number of qubits or parameters.
+    WrongArity {
+        gate: StandardGate,
+        num_qubits: usize,
+        num_params: usize,
+    },
+    /// The interner is full.
+    CapacityExceeded,
+}
+
+impl fmt::Display for CircuitDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BitOutOfRange { bit, num_bits } => write!(
+                f,
+                "bit {bit} is out of range for a circuit with {num_bits} bits"
+            ),
+            Self::WrongArity {
+                gate,
+                num_qubits,
+                num_params,
+            } => write!(
+                f,
+                "{} acts on {} qubits with {} parameters, not {num_qubits} qubits with \
+                 {num_params} parameters",
+                gate.name(),
+                gate.num_qubits(),
+                gate.num_params()
+            ),
+            Self::CapacityExceeded => write!(f, "{CapacityExceeded}"),
+        }
+    }
+}
+
+impl std::error::Error for CircuitDataError {}
+
+impl From<CapacityExceeded> for CircuitDataError {
+    fn from(_: CapacityExceeded) -> Self {
+        CircuitDataError::CapacityExceeded
+    }
+}
+
+/// Private type used to store instructions with interned arg lists.
+#[derive(Clone, Debug)]
+struct PackedInstruction {
+    op: StandardGate,
+    params: Vec<f64>,
+    /// The index under which the interner has stored `qubits`.
+    qubits_id: IndexType,
+    /// The index under which the interner has stored `clbits`.
+    clbits_id: IndexType,
+}
+
+/// A circuit of standard gates, with its qubits and clbits given by index.
+#[derive(Clone, Debug)]
+pub struct CircuitData {
+    data: Vec<PackedInstruction>,
+    intern_context: InternContext,
+    num_qubits: usize,
+    num_clbits: usize,
+    global_phase: f64,
+}
+
+impl CircuitData {
+    /// An empty circuit on `num_qubits` qubits and `num_clbits` clbits.
+    pub fn new(num_qubits: usize, num_clbits: usize, global_phase: f64) -> Self {
+        CircuitData {
+            data: Vec::new(),
+            intern_context: InternContext::new(),
+            num_qubits,
+            num_clbits,
+            global_phase,
+        }
+    }
+
+    /// Build a circuit on `num_qubits` qubits (and no clbits) from a sequence of standard gates,
+    /// each given with its parameters and the indices of the qubits it acts on.
+    pub fn from_standard_gates<I>(
+        num_qubits: u32,
+        instructions: I,
+    ) -> Result<Self, CircuitDataError>
+    where
+        I: IntoIterator<Item = (StandardGate, Vec<f64>, Vec<BitType>)>,
+    {
+        let mut res = CircuitData::new(num_qubits as usize, 0, 0.);
+        let instructions = instructions.into_iter();
+        res.data.reserve(instructions.size_hint().0);
+        for (gate, params, qargs) in instructions {
+            res.push_standard_gate(gate, params, qargs, Vec::new())?;
+        }
+        Ok(res)
+    }
+
+    /// Append `gate` with `params` on the qubits `qargs` and the clbits `cargs`.
+    pub fn push_standard_gate(
+        &mut self,
+        gate: StandardGate,
+        params: Vec<f64>,
+        qargs: Vec<BitType>,
+        cargs: Vec<BitType>,
+    ) -> Result<(), CircuitDataError> {
+        if qargs.len() != gate.num_qubits() as usize || params.len() != gate.num_params() as usize {
+            return Err(CircuitDataError::WrongArity {
+                gate,
+                num_qubits: qargs.len(),
+                num_params: params.len(),
+            });
+        }
+        for (bits, num_bits) in [(&qargs, self.num_qubits), (&cargs, self.num_clbits)] {
+            if let Some(bit) = bits.iter().find(|bit| **bit as usize >= num_bits) {
+                return Err(CircuitDataError::BitOutOfRange {
+                    bit: *bit,
+                    num_bits,
+                });
+            }
+        }
+        self.data.push(PackedInstruction {
+            op: gate,
+            params,
+            qubits_id: self.intern_context.intern(qargs)?,
+            clbits_id: self.intern_context.intern(cargs)?,
+        });
+        Ok(())
+    }
+
+    /// The number of qubits of the circuit.
+    pub fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// The number of clbits of the circuit.
+    pub fn num_clbits(&self) -> usize {
+        self.num_clbits
+    }
+
+    /// The global phase of the circuit.
+    pub fn global_phase(&self) -> f64 {
+        self.global_phase
+    }
+
+    /// Set the global phase of the circuit.
+    pub fn set_global_phase(&mut self, angle: f64) {
+        self.global_phase = angle;
+    }
+
+    /// The number of instructions of the circuit.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the circuit has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Remove all the instructions, keeping the bits and the global phase.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Iterate over the instructions in order, yielding each gate with its parameters and the
+    /// indices of the qubits and clbits it acts on.
+    pub fn iter_instructions(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (StandardGate, &[f64], &[BitType], &[BitType])> {
+        self.data.iter().map(|inst| {
+            (
+                inst.op,
+                inst.params.as_slice(),
+                self.intern_context.lookup(inst.qubits_id),
+                self.intern_context.lookup(inst.clbits_id),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_standard_gates() {
+        let circuit = CircuitData::from_standard_gates(
+            2,
+            [
+                (StandardGate::HGate, vec![], vec![0]),
+                (StandardGate::CXGate, vec![], vec![0, 1]),
+                (StandardGate::RZGate, vec![0.5], vec![1]),
+                (StandardGate::CXGate, vec![], vec![0, 1]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(circuit.len(), 4);
+        assert_eq!((circuit.num_qubits(), circuit.num_clbits()), (2, 0));
+        let instructions = circuit.iter_instructions().collect::<Vec<_>>();
+        assert_eq!(
+            instructions[2],
+            (StandardGate::RZGate, &[0.5][..], &[1][..], &[][..])
+        );
+        assert_eq!(instructions[1].2, instructions[3].2);
+        // Equal bit sequences are interned once.
+        assert_eq!(circuit.data[1].qubits_id, circuit.data[3].qubits_id);
+    }
+
+    #[test]
+    fn test_push_errors() {
+        let mut circuit = CircuitData::new(2, 1, 0.25);
+        assert_eq!(
+            circuit.push_standard_gate(StandardGate::CXGate, vec![], vec![0], vec![]),
+            Err(CircuitDataError::WrongArity {
+                gate: StandardGate::CXGate,
+                num_qubits: 1,
+                num_params: 0
+            })
+        );
+        assert_eq!(
+            circuit.push_standard_gate(StandardGate::RXGate, vec![], vec![0], vec![]),
+            Err(CircuitDataError::WrongArity {
+                gate: StandardGate::RXGate,
+                num_qubits: 1,
+                num_params: 0
+            })
+        );
+        assert_eq!(
+            circuit.push_standard_gate(StandardGate::XGate, vec![], vec![2], vec![]),
+            Err(CircuitDataError::BitOutOfRange {
+                bit: 2,
+                num_bits: 2
+            })
+        );
+        assert_eq!(
+            circuit.push_standard_gate(StandardGate::XGate, vec![], vec![1], vec![1]),
+            Err(CircuitDataError::BitOutOfRange {
+                bit: 1,
+                num_bits: 1
+            })
+        );
+        assert!(circuit.is_empty());
+        circuit
+            .push_standard_gate(StandardGate::XGate, vec![], vec![1], vec![0])
+            .unwrap();
+        assert_eq!(circuit.len(), 1);
+        assert_eq!(circuit.global_phase(), 0.25);
+        circuit.clear();
+        assert!(circuit.is_empty());
+    }
+}
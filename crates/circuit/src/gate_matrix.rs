@@ -0,0 +1,437 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! The matrices of the standard gates.
+//!
+//! All the multi-qubit matrices use Qiskit's little-endian convention: the first qubit an
+//! operation acts on is the least significant bit of the matrix index, so for the controlled
+//! gates the control is qubit 0.
+//!
+//! The matrices are available in double and single precision.  Each static matrix `NAME` has a
+//! single-precision counterpart `NAME_F32`, and the constructors of the parametric gates are
+//! generic over the [GateScalar] of their output; the parameters are always `f64`.  The
+//! single-precision matrices are computed in double precision and rounded once at the end.
+
+use num_complex::{Complex32, Complex64};
+use std::f64::consts::FRAC_1_SQRT_2;
+
+const fn c64(re: f64, im: f64) -> Complex64 {
+    Complex64::new(re, im)
+}
+
+pub type GateArray0Q<T = Complex64> = [[T; 1]; 1];
+pub type GateArray1Q<T = Complex64> = [[T; 2]; 2];
+pub type GateArray2Q<T = Complex64> = [[T; 4]; 4];
+pub type GateArray3Q<T = Complex64> = [[T; 8]; 8];
+
+/// The complex scalars that the gate matrices are available in.
+pub trait GateScalar: Copy {
+    /// Round a double-precision value to this precision.
+    fn from_c64(value: Complex64) -> Self;
+}
+
+impl GateScalar for Complex64 {
+    #[inline]
+    fn from_c64(value: Complex64) -> Self {
+        value
+    }
+}
+
+impl GateScalar for Complex32 {
+    #[inline]
+    fn from_c64(value: Complex64) -> Self {
+        Complex32::new(value.re as f32, value.im as f32)
+    }
+}
+
+#[inline]
+fn cast<T: GateScalar, const N: usize>(matrix: [[Complex64; N]; N]) -> [[T; N]; N] {
+    matrix.map(|row| row.map(T::from_c64))
+}
+
+const ZERO: Complex64 = c64(0., 0.);
+const ONE: Complex64 = c64(1., 0.);
+const M_ONE: Complex64 = c64(-1., 0.);
+const IM: Complex64 = c64(0., 1.);
+const M_IM: Complex64 = c64(0., -1.);
+
+/// Define each static matrix `$name` and its single-precision counterpart `$name_f32`.  The
+/// rounding loop runs in the `static` initializer, so it's done at compile time.
+macro_rules! static_gates {
+    ($($name:ident, $name_f32:ident: $array:ident = $matrix:expr;)+) => {
+        $(
+            pub static $name: $array = $matrix;
+
+            pub static $name_f32: $array<Complex32> = {
+                const DIM: usize = static_gates!(@dim $array);
+                let matrix: $array = $matrix;
+                let mut out = [[Complex32::new(0., 0.); DIM]; DIM];
+                let mut row = 0;
+                while row < DIM {
+                    let mut col = 0;
+                    while col < DIM {
+                        let value = matrix[row][col];
+                        out[row][col] = Complex32::new(value.re as f32, value.im as f32);
+                        col += 1;
+                    }
+                    row += 1;
+                }
+                out
+            };
+        )+
+    };
+    (@dim GateArray1Q) => { 2 };
+    (@dim GateArray2Q) => { 4 };
+    (@dim GateArray3Q) => { 8 };
+}
+
+static_gates! {
+    ONE_QUBIT_IDENTITY, ONE_QUBIT_IDENTITY_F32: GateArray1Q = [[ONE, ZERO], [ZERO, ONE]];
+
+    H_GATE, H_GATE_F32: GateArray1Q = [
+        [c64(FRAC_1_SQRT_2, 0.), c64(FRAC_1_SQRT_2, 0.)],
+        [c64(FRAC_1_SQRT_2, 0.), c64(-FRAC_1_SQRT_2, 0.)],
+    ];
+
+    X_GATE, X_GATE_F32: GateArray1Q = [[ZERO, ONE], [ONE, ZERO]];
+
+    Y_GATE, Y_GATE_F32: GateArray1Q = [[ZERO, M_IM], [IM, ZERO]];
+
+    Z_GATE, Z_GATE_F32: GateArray1Q = [[ONE, ZERO], [ZERO, M_ONE]];
+
+    S_GATE, S_GATE_F32: GateArray1Q = [[ONE, ZERO], [ZERO, IM]];
+
+    SDG_GATE, SDG_GATE_F32: GateArray1Q = [[ONE, ZERO], [ZERO, M_IM]];
+
+    T_GATE, T_GATE_F32: GateArray1Q = [
+        [ONE, ZERO],
+        [ZERO, c64(FRAC_1_SQRT_2, FRAC_1_SQRT_2)],
+    ];
+
+    TDG_GATE, TDG_GATE_F32: GateArray1Q = [
+        [ONE, ZERO],
+        [ZERO, c64(FRAC_1_SQRT_2, -FRAC_1_SQRT_2)],
+    ];
+
+    SX_GATE, SX_GATE_F32: GateArray1Q = [
+        [c64(0.5, 0.5), c64(0.5, -0.5)],
+        [c64(0.5, -0.5), c64(0.5, 0.5)],
+    ];
+
+    SXDG_GATE, SXDG_GATE_F32: GateArray1Q = [
+        [c64(0.5, -0.5), c64(0.5, 0.5)],
+        [c64(0.5, 0.5), c64(0.5, -0.5)],
+    ];
+
+    CX_GATE, CX_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ONE],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, ONE, ZERO, ZERO],
+    ];
+
+    CY_GATE, CY_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, M_IM],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, IM, ZERO, ZERO],
+    ];
+
+    CZ_GATE, CZ_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, ZERO, ZERO, M_ONE],
+    ];
+
+    CH_GATE, CH_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, c64(FRAC_1_SQRT_2, 0.), ZERO, c64(FRAC_1_SQRT_2, 0.)],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, c64(FRAC_1_SQRT_2, 0.), ZERO, c64(-FRAC_1_SQRT_2, 0.)],
+    ];
+
+    SWAP_GATE, SWAP_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ONE],
+    ];
+
+    ISWAP_GATE, ISWAP_GATE_F32: GateArray2Q = [
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, IM, ZERO],
+        [ZERO, IM, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ONE],
+    ];
+
+    ECR_GATE, ECR_GATE_F32: GateArray2Q = [
+        [ZERO, c64(FRAC_1_SQRT_2, 0.), ZERO, c64(0., FRAC_1_SQRT_2)],
+        [c64(FRAC_1_SQRT_2, 0.), ZERO, c64(0., -FRAC_1_SQRT_2), ZERO],
+        [ZERO, c64(0., FRAC_1_SQRT_2), ZERO, c64(FRAC_1_SQRT_2, 0.)],
+        [c64(0., -FRAC_1_SQRT_2), ZERO, c64(FRAC_1_SQRT_2, 0.), ZERO],
+    ];
+
+    CCX_GATE, CCX_GATE_F32: GateArray3Q = [
+        [ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ONE],
+        [ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO],
+        [ZERO, ZERO, ZERO, ONE, ZERO, ZERO, ZERO, ZERO],
+    ];
+
+    CSWAP_GATE, CSWAP_GATE_F32: GateArray3Q = [
+        [ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ONE, ZERO, ZERO, ZERO, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO],
+        [ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ONE],
+    ];
+}
+
+#[inline]
+pub fn global_phase_gate<T: GateScalar>(theta: f64) -> GateArray0Q<T> {
+    cast([[c64(0., theta).exp()]])
+}
+
+#[inline]
+pub fn rx_gate<T: GateScalar>(theta: f64) -> GateArray1Q<T> {
+    let half_theta = theta / 2.;
+    let cos = c64(half_theta.cos(), 0.);
+    let isin = c64(0., -half_theta.sin());
+    cast([[cos, isin], [isin, cos]])
+}
+
+#[inline]
+pub fn ry_gate<T: GateScalar>(theta: f64) -> GateArray1Q<T> {
+    let half_theta = theta / 2.;
+    let cos = c64(half_theta.cos(), 0.);
+    let sin = c64(half_theta.sin(), 0.);
+    cast([[cos, -sin], [sin, cos]])
+}
+
+#[inline]
+pub fn rz_gate<T: GateScalar>(theta: f64) -> GateArray1Q<T> {
+    let ilam2 = c64(0., 0.5 * theta);
+    cast([[(-ilam2).exp(), ZERO], [ZERO, ilam2.exp()]])
+}
+
+#[inline]
+pub fn r_gate<T: GateScalar>(theta: f64, phi: f64) -> GateArray1Q<T> {
+    let half_theta = theta / 2.;
+    let cos = c64(half_theta.cos(), 0.);
+    let sin = half_theta.sin();
+    let exp_m = c64(0., -phi).exp();
+    let exp_p = c64(0., phi).exp();
+    cast([[cos, M_IM * exp_m * sin], [M_IM * exp_p * sin, cos]])
+}
+
+#[inline]
+pub fn phase_gate<T: GateScalar>(lam: f64) -> GateArray1Q<T> {
+    cast([[ONE, ZERO], [ZERO, c64(0., lam).exp()]])
+}
+
+#[inline]
+pub fn u_gate<T: GateScalar>(theta: f64, phi: f64, lam: f64) -> GateArray1Q<T> {
+    let cos = (theta / 2.).cos();
+    let sin = (theta / 2.).sin();
+    cast([
+        [c64(cos, 0.), -c64(0., lam).exp() * sin],
+        [c64(0., phi).exp() * sin, c64(0., phi + lam).exp() * cos],
+    ])
+}
+
+#[inline]
+pub fn u2_gate<T: GateScalar>(phi: f64, lam: f64) -> GateArray1Q<T> {
+    u_gate(std::f64::consts::FRAC_PI_2, phi, lam)
+}
+
+#[inline]
+pub fn cp_gate<T: GateScalar>(lam: f64) -> GateArray2Q<T> {
+    cast([
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, ZERO, ZERO, c64(0., lam).exp()],
+    ])
+}
+
+#[inline]
+pub fn crx_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let half_theta = theta / 2.;
+    let cos = c64(half_theta.cos(), 0.);
+    let isin = c64(0., -half_theta.sin());
+    cast([
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, cos, ZERO, isin],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, isin, ZERO, cos],
+    ])
+}
+
+#[inline]
+pub fn cry_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let half_theta = theta / 2.;
+    let cos = c64(half_theta.cos(), 0.);
+    let sin = c64(half_theta.sin(), 0.);
+    cast([
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, cos, ZERO, -sin],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, sin, ZERO, cos],
+    ])
+}
+
+#[inline]
+pub fn crz_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let i_theta = c64(0., theta / 2.);
+    cast([
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, (-i_theta).exp(), ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO],
+        [ZERO, ZERO, ZERO, i_theta.exp()],
+    ])
+}
+
+#[inline]
+pub fn rxx_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let ccos = c64(cost, 0.);
+    let csinm = c64(0., -sint);
+    cast([
+        [ccos, ZERO, ZERO, csinm],
+        [ZERO, ccos, csinm, ZERO],
+        [ZERO, csinm, ccos, ZERO],
+        [csinm, ZERO, ZERO, ccos],
+    ])
+}
+
+#[inline]
+pub fn ryy_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let ccos = c64(cost, 0.);
+    let csin = c64(0., sint);
+    cast([
+        [ccos, ZERO, ZERO, csin],
+        [ZERO, ccos, -csin, ZERO],
+        [ZERO, -csin, ccos, ZERO],
+        [csin, ZERO, ZERO, ccos],
+    ])
+}
+
+#[inline]
+pub fn rzz_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let exp_it2 = c64(cost, sint);
+    let exp_mit2 = c64(cost, -sint);
+    cast([
+        [exp_mit2, ZERO, ZERO, ZERO],
+        [ZERO, exp_it2, ZERO, ZERO],
+        [ZERO, ZERO, exp_it2, ZERO],
+        [ZERO, ZERO, ZERO, exp_mit2],
+    ])
+}
+
+#[inline]
+pub fn rzx_gate<T: GateScalar>(theta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let ccos = c64(cost, 0.);
+    let csin = c64(0., sint);
+    cast([
+        [ccos, ZERO, -csin, ZERO],
+        [ZERO, ccos, ZERO, csin],
+        [-csin, ZERO, ccos, ZERO],
+        [ZERO, csin, ZERO, ccos],
+    ])
+}
+
+#[inline]
+pub fn xx_minus_yy_gate<T: GateScalar>(theta: f64, beta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let ccos = c64(cost, 0.);
+    cast([
+        [ccos, ZERO, ZERO, c64(0., -sint) * c64(0., -beta).exp()],
+        [ZERO, ONE, ZERO, ZERO],
+        [ZERO, ZERO, ONE, ZERO],
+        [c64(0., -sint) * c64(0., beta).exp(), ZERO, ZERO, ccos],
+    ])
+}
+
+#[inline]
+pub fn xx_plus_yy_gate<T: GateScalar>(theta: f64, beta: f64) -> GateArray2Q<T> {
+    let (sint, cost) = (theta / 2.0).sin_cos();
+    let ccos = c64(cost, 0.);
+    cast([
+        [ONE, ZERO, ZERO, ZERO],
+        [ZERO, ccos, c64(0., -sint) * c64(0., -beta).exp(), ZERO],
+        [ZERO, c64(0., -sint) * c64(0., beta).exp(), ccos, ZERO],
+        [ZERO, ZERO, ZERO, ONE],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_rounded<const N: usize>(single: [[Complex32; N]; N], double: [[Complex64; N]; N]) {
+        for (single_row, double_row) in single.iter().zip(double.iter()) {
+            for (single, double) in single_row.iter().zip(double_row.iter()) {
+                assert_eq!(*single, Complex32::from_c64(*double));
+            }
+        }
+    }
+
+    #[test]
+    fn test_static_f32() {
+        assert_rounded(H_GATE_F32, H_GATE);
+        assert_rounded(T_GATE_F32, T_GATE);
+        assert_rounded(SX_GATE_F32, SX_GATE);
+        assert_rounded(CX_GATE_F32, CX_GATE);
+        assert_rounded(CCX_GATE_F32, CCX_GATE);
+    }
+
+    #[test]
+    fn test_parametric_f32() {
+        let (theta, phi, lam) = (0.3, -1.2, 2.5);
+        assert_rounded(
+            u_gate::<Complex32>(theta, phi, lam),
+            u_gate(theta, phi, lam),
+        );
+        assert_rounded(rzz_gate::<Complex32>(theta), rzz_gate(theta));
+        assert_rounded(
+            xx_plus_yy_gate::<Complex32>(theta, phi),
+            xx_plus_yy_gate(theta, phi),
+        );
+        assert_rounded(global_phase_gate::<Complex32>(lam), global_phase_gate(lam));
+    }
+
+    #[test]
+    fn test_parametric_values() {
+        let rz = rz_gate::<Complex64>(std::f64::consts::PI);
+        assert!((rz[0][0] - c64(0., -1.)).norm() < 1e-15);
+        assert!((rz[1][1] - c64(0., 1.)).norm() < 1e-15);
+        assert_eq!(rz[0][1], ZERO);
+        // The phase gate at pi / 4 is the T gate.
+        let p = phase_gate::<Complex64>(std::f64::consts::FRAC_PI_4);
+        for (row, t_row) in p.iter().zip(T_GATE.iter()) {
+            for (value, t_value) in row.iter().zip(t_row.iter()) {
+                assert!((value - t_value).norm() < 1e-15);
+            }
+        }
+    }
+}
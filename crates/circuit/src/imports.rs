@@ -0,0 +1,202 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+//! Cached imports of the Python objects that Rust needs.
+//!
+//! Rust code declares each object it needs as a static [ImportOnceCell] with the module and the
+//! name to import, and gets a bound handle from it with [ImportOnceCell::get_bound]; the import
+//! only happens on first use.  Objects that aren't known statically, or that Python code provides
+//! itself through [py_register_import], go through the registry of [register] and [get_path]
+//! instead.
+//!
+//! The caches only hold their objects while the GIL is held, so they need no `unsafe` and are
+//! shared safely between threads.  Objects from a finalized interpreter must not be used in a new
+//! one, so the caches are invalidated by [clear_caches] whenever the `circuit` module is
+//! initialized.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::sync::GILProtected;
+
+use crate::operations::{StandardGate, STANDARD_GATE_SIZE};
+
+/// The number of times the caches have been invalidated.  A cached object is only valid if it was
+/// cached in the current generation.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Import `object` from `module`.
+fn import_attr<'py>(py: Python<'py>, module: &str, object: &str) -> PyResult<Bound<'py, PyAny>> {
+    py.import_bound(module)?.getattr(object)
+}
+
+/// A Python object that's imported on first use, and then cached.
+pub struct ImportOnceCell {
+    module: &'static str,
+    object: &'static str,
+    cell: GILProtected<RefCell<Option<(u64, PyObject)>>>,
+}
+
+impl ImportOnceCell {
+    pub const fn new(module: &'static str, object: &'static str) -> Self {
+        ImportOnceCell {
+            module,
+            object,
+            cell: GILProtected::new(RefCell::new(None)),
+        }
+    }
+
+    /// The object, imported if it isn't cached yet.
+    pub fn get_bound<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        let cell = self.cell.get(py);
+        if let Some((cached, object)) = cell.borrow().as_ref() {
+            if *cached == generation {
+                return Ok(object.bind(py).clone());
+            }
+        }
+        // The import runs Python code, which may need the object itself, so the cell isn't
+        // borrowed during it.
+        let object = import_attr(py, self.module, self.object)?;
+        let stale = cell.replace(Some((generation, object.clone().unbind())));
+        // An object of an earlier generation may belong to a finalized interpreter, so it's
+        // leaked rather than released.
+        if let Some((cached, object)) = stale {
+            if cached != generation {
+                std::mem::forget(object);
+            }
+        }
+        Ok(object)
+    }
+}
+
+pub static QUBIT: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Qubit");
+pub static CLBIT: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Clbit");
+pub static PARAMETER_EXPRESSION: ImportOnceCell =
+    ImportOnceCell::new("qiskit.circuit.parameterexpression", "ParameterExpression");
+pub static BARRIER: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Barrier");
+pub static GATE: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Gate");
+pub static DELAY: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Delay");
+pub static MEASURE: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Measure");
+pub static RESET: ImportOnceCell = ImportOnceCell::new("qiskit.circuit", "Reset");
+pub static UNITARY_GATE: ImportOnceCell = ImportOnceCell::new(
+    "qiskit.circuit.library.generalized_gates.unitary",
+    "UnitaryGate",
+);
+pub static CIRCUIT_TO_DAG: ImportOnceCell =
+    ImportOnceCell::new("qiskit.converters", "circuit_to_dag");
+pub static DAG_TO_CIRCUIT: ImportOnceCell =
+    ImportOnceCell::new("qiskit.converters", "dag_to_circuit");
+
+const NO_CLASS: Option<(u64, PyObject)> = None;
+
+/// The Python class of each standard gate, indexed by the gate, with the generation it was imported
+/// in.
+static STDGATE_PYTHON_GATES: GILProtected<RefCell<[Option<(u64, PyObject)>; STANDARD_GATE_SIZE]>> =
+    GILProtected::new(RefCell::new([NO_CLASS; STANDARD_GATE_SIZE]));
+
+/// The Python class of the standard gate `gate`, imported on first use.
+pub fn get_std_gate_class(py: Python, gate: StandardGate) -> PyResult<Bound<PyAny>> {
+    let generation = GENERATION.load(Ordering::Relaxed);
+    let cache = STDGATE_PYTHON_GATES.get(py);
+    if let Some((cached, class)) = cache.borrow()[gate as usize].as_ref() {
+        if *cached == generation {
+            return Ok(class.bind(py).clone());
+        }
+    }
+    // The import runs Python code, which may need a class itself, so the cache isn't borrowed
+    // during it.
+    let class = import_attr(
+        py,
+        "qiskit.circuit.library.standard_gates",
+        gate.class_name(),
+    )?;
+    let stale = cache.borrow_mut()[gate as usize].replace((generation, class.clone().unbind()));
+    // As in [ImportOnceCell::get_bound], a class of an earlier generation is leaked.
+    if let Some((cached, class)) = stale {
+        if cached != generation {
+            std::mem::forget(class);
+        }
+    }
+    Ok(class)
+}
+
+/// The objects registered or imported by their path.
+static REGISTRY: GILProtected<RefCell<BTreeMap<String, PyObject>>> =
+    GILProtected::new(RefCell::new(BTreeMap::new()));
+
+/// Register `object` under `path`, so that [get_path] returns it instead of importing `path`.  This
+/// replaces any object that was registered or imported under `path` before.
+pub fn register(py: Python, path: &str, object: Bound<PyAny>) {
+    REGISTRY
+        .get(py)
+        .borrow_mut()
+        .insert(path.to_owned(), object.unbind());
+}
+
+/// The object registered under `path`, or else the object of the dotted `path`, such as
+/// `"qiskit.circuit.Delay"`, imported on first use.
+pub fn get_path<'py>(py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyAny>> {
+    if let Some(object) = REGISTRY.get(py).borrow().get(path) {
+        return Ok(object.bind(py).clone());
+    }
+    // The import runs Python code, which may register objects itself, so the registry isn't
+    // borrowed during it.
+    let object = match path.rsplit_once('.') {
+        Some((module, object)) => import_attr(py, module, object)?,
+        None => py.import_bound(path)?.into_any(),
+    };
+    REGISTRY
+        .get(py)
+        .borrow_mut()
+        .insert(path.to_owned(), object.clone().unbind());
+    Ok(object)
+}
+
+/// Register an object for the Rust code that looks it up by its dotted path, in place of the
+/// object that the path would import.
+///
+/// Args:
+///     path (str): the dotted path, such as ``"qiskit.circuit.Delay"``.
+///     object: the object to return for ``path``.
+#[pyfunction]
+#[pyo3(name = "register_import")]
+pub fn py_register_import(path: &str, object: &Bound<PyAny>) {
+    register(object.py(), path, object.clone());
+}
+
+/// Return the object registered under a dotted path, or else the object that the path imports.
+///
+/// Args:
+///     path (str): the dotted path, such as ``"qiskit.circuit.Delay"``.
+///
+/// Raises:
+///     ImportError: if nothing is registered under ``path`` and it can't be imported.
+#[pyfunction]
+#[pyo3(name = "get_import")]
+pub fn py_get_import<'py>(py: Python<'py>, path: &str) -> PyResult<Bound<'py, PyAny>> {
+    get_path(py, path)
+}
+
+/// Invalidate all the cached and registered objects, so that they're imported again on next use.
+///
+/// This never releases a cached or registered object, since it may belong to a finalized
+/// interpreter: the cached objects are only marked stale, and are leaked when they're replaced,
+/// and the registered objects are leaked as they're removed.
+pub fn clear_caches(py: Python) {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    for (_, object) in std::mem::take(&mut *REGISTRY.get(py).borrow_mut()) {
+        std::mem::forget(object);
+    }
+}
@@ -11,13 +11,35 @@
 // that they have been altered from the originals.
 
 use hashbrown::HashMap;
+#[cfg(feature = "python")]
 use pyo3::exceptions::PyRuntimeError;
-use pyo3::PyResult;
+#[cfg(feature = "python")]
+use pyo3::PyErr;
+use std::fmt;
 use std::sync::Arc;
 
 pub type IndexType = u32;
 pub type BitType = u32;
 
+/// The error of an [InternContext] that already holds as many sequences as [IndexType] can index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityExceeded;
+
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InternContext capacity exceeded!")
+    }
+}
+
+impl std::error::Error for CapacityExceeded {}
+
+#[cfg(feature = "python")]
+impl From<CapacityExceeded> for PyErr {
+    fn from(err: CapacityExceeded) -> Self {
+        PyRuntimeError::new_err(err.to_string())
+    }
+}
+
 /// A Rust-only data structure (not a pyclass!) for interning
 /// `Vec<BitType>`.
 ///
@@ -41,17 +63,13 @@ impl InternContext {
     /// Takes `args` by reference and returns an index that can be used
     /// to obtain a reference to an equivalent sequence of `BitType` by
     /// calling [CircuitData.lookup].
-    pub fn intern(&mut self, args: Vec<BitType>) -> PyResult<IndexType> {
+    pub fn intern(&mut self, args: Vec<BitType>) -> Result<IndexType, CapacityExceeded> {
         if let Some(slot_idx) = self.slot_lookup.get(&args) {
             return Ok(*slot_idx);
         }
 
         let args = Arc::new(args);
-        let slot_idx: IndexType = self
-            .slots
-            .len()
-            .try_into()
-            .map_err(|_| PyRuntimeError::new_err("InternContext capacity exceeded!"))?;
+        let slot_idx: IndexType = self.slots.len().try_into().map_err(|_| CapacityExceeded)?;
         self.slots.push(args.clone());
         self.slot_lookup.insert_unique_unchecked(args, slot_idx);
         Ok(slot_idx)
@@ -10,16 +10,39 @@
 // copyright notice, and modified files need to carry a notice indicating
 // that they have been altered from the originals.
 
+//! The data model of Qiskit's circuits.
+//!
+//! The gate matrices, the standard gates and the interning of bit sequences are plain Rust.  With
+//! the `python` feature, which is a default, the circuit containers, `CircuitData` and
+//! `CircuitInstruction`, store their operations and bits as Python objects, and the DAG nodes and
+//! the Python bindings are built too.  Without it, the crate doesn't depend on PyO3 and builds for
+//! `wasm32-unknown-unknown`, and its `CircuitData` holds standard gates on indexed bits instead.
+
+#[cfg(feature = "python")]
+pub mod circuit_data;
+#[cfg(not(feature = "python"))]
+#[path = "circuit_data_native.rs"]
 pub mod circuit_data;
+#[cfg(feature = "python")]
 pub mod circuit_instruction;
+#[cfg(feature = "python")]
 pub mod dag_node;
+pub mod gate_matrix;
+#[cfg(feature = "python")]
+pub mod imports;
 pub mod intern_context;
+pub mod operations;
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::PySlice;
+#[cfg(feature = "python")]
+use pyo3::wrap_pyfunction;
 
 /// A private enumeration type used to extract arguments to pymethod
 /// that may be either an index or a slice
+#[cfg(feature = "python")]
 #[derive(FromPyObject)]
 pub enum SliceOrInt<'a> {
     // The order here defines the order the variants are tried in the FromPyObject` derivation.
@@ -28,13 +51,18 @@ pub enum SliceOrInt<'a> {
     Slice(Bound<'a, PySlice>),
 }
 
+#[cfg(feature = "python")]
 #[pymodule]
 pub fn circuit(m: Bound<PyModule>) -> PyResult<()> {
+    // Any objects cached by an earlier interpreter are no longer valid.
+    imports::clear_caches(m.py());
     m.add_class::<circuit_data::CircuitData>()?;
     m.add_class::<dag_node::DAGNode>()?;
     m.add_class::<dag_node::DAGInNode>()?;
     m.add_class::<dag_node::DAGOutNode>()?;
     m.add_class::<dag_node::DAGOpNode>()?;
     m.add_class::<circuit_instruction::CircuitInstruction>()?;
+    m.add_wrapped(wrap_pyfunction!(imports::py_register_import))?;
+    m.add_wrapped(wrap_pyfunction!(imports::py_get_import))?;
     Ok(())
 }
@@ -0,0 +1,428 @@
+// This code is part of Qiskit.
+//
+// (C) Copyright IBM 2024
+//
+// This code is licensed under the Apache License, Version 2.0. You may
+// obtain a copy of this license in the LICENSE.txt file in the root directory
+// of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+//
+// Any modifications or derivative works of this code must retain this
+// copyright notice, and modified files need to carry a notice indicating
+// that they have been altered from the originals.
+
+use crate::gate_matrix;
+#[cfg(feature = "python")]
+use crate::imports;
+
+use ndarray::{aview2, Array2};
+use num_complex::Complex64;
+#[cfg(feature = "python")]
+use pyo3::intern;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyTuple;
+
+/// The number of [StandardGate]s, which index arrays by `gate as usize`.
+pub const STANDARD_GATE_SIZE: usize = 40;
+
+const _: () = assert!(StandardGate::CSwapGate as usize + 1 == STANDARD_GATE_SIZE);
+
+/// The standard gates of `qiskit.circuit.library.standard_gates` that Rust knows the matrix of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StandardGate {
+    GlobalPhaseGate,
+    IGate,
+    HGate,
+    XGate,
+    YGate,
+    ZGate,
+    SGate,
+    SdgGate,
+    TGate,
+    TdgGate,
+    SXGate,
+    SXdgGate,
+    RXGate,
+    RYGate,
+    RZGate,
+    RGate,
+    PhaseGate,
+    U1Gate,
+    U2Gate,
+    U3Gate,
+    UGate,
+    CXGate,
+    CYGate,
+    CZGate,
+    CHGate,
+    CPhaseGate,
+    CRXGate,
+    CRYGate,
+    CRZGate,
+    SwapGate,
+    ISwapGate,
+    ECRGate,
+    RXXGate,
+    RYYGate,
+    RZZGate,
+    RZXGate,
+    XXMinusYYGate,
+    XXPlusYYGate,
+    CCXGate,
+    CSwapGate,
+}
+
+impl StandardGate {
+    /// Look up a gate by the name of its Python class.
+    pub fn from_class_name(name: &str) -> Option<Self> {
+        let gate = match name {
+            "GlobalPhaseGate" => Self::GlobalPhaseGate,
+            "IGate" => Self::IGate,
+            "HGate" => Self::HGate,
+            "XGate" => Self::XGate,
+            "YGate" => Self::YGate,
+            "ZGate" => Self::ZGate,
+            "SGate" => Self::SGate,
+            "SdgGate" => Self::SdgGate,
+            "TGate" => Self::TGate,
+            "TdgGate" => Self::TdgGate,
+            "SXGate" => Self::SXGate,
+            "SXdgGate" => Self::SXdgGate,
+            "RXGate" => Self::RXGate,
+            "RYGate" => Self::RYGate,
+            "RZGate" => Self::RZGate,
+            "RGate" => Self::RGate,
+            "PhaseGate" => Self::PhaseGate,
+            "U1Gate" => Self::U1Gate,
+            "U2Gate" => Self::U2Gate,
+            "U3Gate" => Self::U3Gate,
+            "UGate" => Self::UGate,
+            "CXGate" => Self::CXGate,
+            "CYGate" => Self::CYGate,
+            "CZGate" => Self::CZGate,
+            "CHGate" => Self::CHGate,
+            "CPhaseGate" => Self::CPhaseGate,
+            "CRXGate" => Self::CRXGate,
+            "CRYGate" => Self::CRYGate,
+            "CRZGate" => Self::CRZGate,
+            "SwapGate" => Self::SwapGate,
+            "iSwapGate" => Self::ISwapGate,
+            "ECRGate" => Self::ECRGate,
+            "RXXGate" => Self::RXXGate,
+            "RYYGate" => Self::RYYGate,
+            "RZZGate" => Self::RZZGate,
+            "RZXGate" => Self::RZXGate,
+            "XXMinusYYGate" => Self::XXMinusYYGate,
+            "XXPlusYYGate" => Self::XXPlusYYGate,
+            "CCXGate" => Self::CCXGate,
+            "CSwapGate" => Self::CSwapGate,
+            _ => return None,
+        };
+        Some(gate)
+    }
+
+    /// The name of the gate's Python class, the inverse of [StandardGate::from_class_name].
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            Self::GlobalPhaseGate => "GlobalPhaseGate",
+            Self::IGate => "IGate",
+            Self::HGate => "HGate",
+            Self::XGate => "XGate",
+            Self::YGate => "YGate",
+            Self::ZGate => "ZGate",
+            Self::SGate => "SGate",
+            Self::SdgGate => "SdgGate",
+            Self::TGate => "TGate",
+            Self::TdgGate => "TdgGate",
+            Self::SXGate => "SXGate",
+            Self::SXdgGate => "SXdgGate",
+            Self::RXGate => "RXGate",
+            Self::RYGate => "RYGate",
+            Self::RZGate => "RZGate",
+            Self::RGate => "RGate",
+            Self::PhaseGate => "PhaseGate",
+            Self::U1Gate => "U1Gate",
+            Self::U2Gate => "U2Gate",
+            Self::U3Gate => "U3Gate",
+            Self::UGate => "UGate",
+            Self::CXGate => "CXGate",
+            Self::CYGate => "CYGate",
+            Self::CZGate => "CZGate",
+            Self::CHGate => "CHGate",
+            Self::CPhaseGate => "CPhaseGate",
+            Self::CRXGate => "CRXGate",
+            Self::CRYGate => "CRYGate",
+            Self::CRZGate => "CRZGate",
+            Self::SwapGate => "SwapGate",
+            Self::ISwapGate => "iSwapGate",
+            Self::ECRGate => "ECRGate",
+            Self::RXXGate => "RXXGate",
+            Self::RYYGate => "RYYGate",
+            Self::RZZGate => "RZZGate",
+            Self::RZXGate => "RZXGate",
+            Self::XXMinusYYGate => "XXMinusYYGate",
+            Self::XXPlusYYGate => "XXPlusYYGate",
+            Self::CCXGate => "CCXGate",
+            Self::CSwapGate => "CSwapGate",
+        }
+    }
+
+    /// Look up a gate by its instruction name, such as `"cx"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let gate = match name {
+            "global_phase" => Self::GlobalPhaseGate,
+            "id" => Self::IGate,
+            "h" => Self::HGate,
+            "x" => Self::XGate,
+            "y" => Self::YGate,
+            "z" => Self::ZGate,
+            "s" => Self::SGate,
+            "sdg" => Self::SdgGate,
+            "t" => Self::TGate,
+            "tdg" => Self::TdgGate,
+            "sx" => Self::SXGate,
+            "sxdg" => Self::SXdgGate,
+            "rx" => Self::RXGate,
+            "ry" => Self::RYGate,
+            "rz" => Self::RZGate,
+            "r" => Self::RGate,
+            "p" => Self::PhaseGate,
+            "u1" => Self::U1Gate,
+            "u2" => Self::U2Gate,
+            "u3" => Self::U3Gate,
+            "u" => Self::UGate,
+            "cx" => Self::CXGate,
+            "cy" => Self::CYGate,
+            "cz" => Self::CZGate,
+            "ch" => Self::CHGate,
+            "cp" => Self::CPhaseGate,
+            "crx" => Self::CRXGate,
+            "cry" => Self::CRYGate,
+            "crz" => Self::CRZGate,
+            "swap" => Self::SwapGate,
+            "iswap" => Self::ISwapGate,
+            "ecr" => Self::ECRGate,
+            "rxx" => Self::RXXGate,
+            "ryy" => Self::RYYGate,
+            "rzz" => Self::RZZGate,
+            "rzx" => Self::RZXGate,
+            "xx_minus_yy" => Self::XXMinusYYGate,
+            "xx_plus_yy" => Self::XXPlusYYGate,
+            "ccx" => Self::CCXGate,
+            "cswap" => Self::CSwapGate,
+            _ => return None,
+        };
+        Some(gate)
+    }
+
+    /// The name of the gate's instructions, the inverse of [StandardGate::from_name].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GlobalPhaseGate => "global_phase",
+            Self::IGate => "id",
+            Self::HGate => "h",
+            Self::XGate => "x",
+            Self::YGate => "y",
+            Self::ZGate => "z",
+            Self::SGate => "s",
+            Self::SdgGate => "sdg",
+            Self::TGate => "t",
+            Self::TdgGate => "tdg",
+            Self::SXGate => "sx",
+            Self::SXdgGate => "sxdg",
+            Self::RXGate => "rx",
+            Self::RYGate => "ry",
+            Self::RZGate => "rz",
+            Self::RGate => "r",
+            Self::PhaseGate => "p",
+            Self::U1Gate => "u1",
+            Self::U2Gate => "u2",
+            Self::U3Gate => "u3",
+            Self::UGate => "u",
+            Self::CXGate => "cx",
+            Self::CYGate => "cy",
+            Self::CZGate => "cz",
+            Self::CHGate => "ch",
+            Self::CPhaseGate => "cp",
+            Self::CRXGate => "crx",
+            Self::CRYGate => "cry",
+            Self::CRZGate => "crz",
+            Self::SwapGate => "swap",
+            Self::ISwapGate => "iswap",
+            Self::ECRGate => "ecr",
+            Self::RXXGate => "rxx",
+            Self::RYYGate => "ryy",
+            Self::RZZGate => "rzz",
+            Self::RZXGate => "rzx",
+            Self::XXMinusYYGate => "xx_minus_yy",
+            Self::XXPlusYYGate => "xx_plus_yy",
+            Self::CCXGate => "ccx",
+            Self::CSwapGate => "cswap",
+        }
+    }
+
+    pub fn num_qubits(&self) -> u32 {
+        match self {
+            Self::GlobalPhaseGate => 0,
+            Self::IGate
+            | Self::HGate
+            | Self::XGate
+            | Self::YGate
+            | Self::ZGate
+            | Self::SGate
+            | Self::SdgGate
+            | Self::TGate
+            | Self::TdgGate
+            | Self::SXGate
+            | Self::SXdgGate
+            | Self::RXGate
+            | Self::RYGate
+            | Self::RZGate
+            | Self::RGate
+            | Self::PhaseGate
+            | Self::U1Gate
+            | Self::U2Gate
+            | Self::U3Gate
+            | Self::UGate => 1,
+            Self::CCXGate | Self::CSwapGate => 3,
+            _ => 2,
+        }
+    }
+
+    pub fn num_params(&self) -> u32 {
+        match self {
+            Self::GlobalPhaseGate
+            | Self::RXGate
+            | Self::RYGate
+            | Self::RZGate
+            | Self::PhaseGate
+            | Self::U1Gate
+            | Self::CPhaseGate
+            | Self::CRXGate
+            | Self::CRYGate
+            | Self::CRZGate
+            | Self::RXXGate
+            | Self::RYYGate
+            | Self::RZZGate
+            | Self::RZXGate => 1,
+            Self::RGate | Self::U2Gate | Self::XXMinusYYGate | Self::XXPlusYYGate => 2,
+            Self::U3Gate | Self::UGate => 3,
+            _ => 0,
+        }
+    }
+
+    /// The unitary matrix of the gate, in Qiskit's little-endian convention.  Returns `None` if
+    /// `params` doesn't have the right length for the gate.
+    pub fn matrix(&self, params: &[f64]) -> Option<Array2<Complex64>> {
+        if params.len() != self.num_params() as usize {
+            return None;
+        }
+        let out = match self {
+            Self::GlobalPhaseGate => aview2(&gate_matrix::global_phase_gate(params[0])).to_owned(),
+            Self::IGate => aview2(&gate_matrix::ONE_QUBIT_IDENTITY).to_owned(),
+            Self::HGate => aview2(&gate_matrix::H_GATE).to_owned(),
+            Self::XGate => aview2(&gate_matrix::X_GATE).to_owned(),
+            Self::YGate => aview2(&gate_matrix::Y_GATE).to_owned(),
+            Self::ZGate => aview2(&gate_matrix::Z_GATE).to_owned(),
+            Self::SGate => aview2(&gate_matrix::S_GATE).to_owned(),
+            Self::SdgGate => aview2(&gate_matrix::SDG_GATE).to_owned(),
+            Self::TGate => aview2(&gate_matrix::T_GATE).to_owned(),
+            Self::TdgGate => aview2(&gate_matrix::TDG_GATE).to_owned(),
+            Self::SXGate => aview2(&gate_matrix::SX_GATE).to_owned(),
+            Self::SXdgGate => aview2(&gate_matrix::SXDG_GATE).to_owned(),
+            Self::RXGate => aview2(&gate_matrix::rx_gate(params[0])).to_owned(),
+            Self::RYGate => aview2(&gate_matrix::ry_gate(params[0])).to_owned(),
+            Self::RZGate => aview2(&gate_matrix::rz_gate(params[0])).to_owned(),
+            Self::RGate => aview2(&gate_matrix::r_gate(params[0], params[1])).to_owned(),
+            Self::PhaseGate | Self::U1Gate => {
+                aview2(&gate_matrix::phase_gate(params[0])).to_owned()
+            }
+            Self::U2Gate => aview2(&gate_matrix::u2_gate(params[0], params[1])).to_owned(),
+            Self::U3Gate | Self::UGate => {
+                aview2(&gate_matrix::u_gate(params[0], params[1], params[2])).to_owned()
+            }
+            Self::CXGate => aview2(&gate_matrix::CX_GATE).to_owned(),
+            Self::CYGate => aview2(&gate_matrix::CY_GATE).to_owned(),
+            Self::CZGate => aview2(&gate_matrix::CZ_GATE).to_owned(),
+            Self::CHGate => aview2(&gate_matrix::CH_GATE).to_owned(),
+            Self::CPhaseGate => aview2(&gate_matrix::cp_gate(params[0])).to_owned(),
+            Self::CRXGate => aview2(&gate_matrix::crx_gate(params[0])).to_owned(),
+            Self::CRYGate => aview2(&gate_matrix::cry_gate(params[0])).to_owned(),
+            Self::CRZGate => aview2(&gate_matrix::crz_gate(params[0])).to_owned(),
+            Self::SwapGate => aview2(&gate_matrix::SWAP_GATE).to_owned(),
+            Self::ISwapGate => aview2(&gate_matrix::ISWAP_GATE).to_owned(),
+            Self::ECRGate => aview2(&gate_matrix::ECR_GATE).to_owned(),
+            Self::RXXGate => aview2(&gate_matrix::rxx_gate(params[0])).to_owned(),
+            Self::RYYGate => aview2(&gate_matrix::ryy_gate(params[0])).to_owned(),
+            Self::RZZGate => aview2(&gate_matrix::rzz_gate(params[0])).to_owned(),
+            Self::RZXGate => aview2(&gate_matrix::rzx_gate(params[0])).to_owned(),
+            Self::XXMinusYYGate => {
+                aview2(&gate_matrix::xx_minus_yy_gate(params[0], params[1])).to_owned()
+            }
+            Self::XXPlusYYGate => {
+                aview2(&gate_matrix::xx_plus_yy_gate(params[0], params[1])).to_owned()
+            }
+            Self::CCXGate => aview2(&gate_matrix::CCX_GATE).to_owned(),
+            Self::CSwapGate => aview2(&gate_matrix::CSWAP_GATE).to_owned(),
+        };
+        Some(out)
+    }
+}
+
+#[cfg(feature = "python")]
+impl StandardGate {
+    /// Create the Python-space instance of the gate with the given parameters.
+    pub fn create_py_op(&self, py: Python, params: &[f64]) -> PyResult<PyObject> {
+        let class = imports::get_std_gate_class(py, *self)?;
+        Ok(class.call1(PyTuple::new_bound(py, params))?.unbind())
+    }
+
+    /// Recognise a Python-space operation as a standard gate with real-valued parameters.
+    ///
+    /// This returns `None` (rather than an error) whenever the operation is not something whose
+    /// action Rust can reproduce exactly from [StandardGate::matrix]: a class outside the standard
+    /// library (including user subclasses), a classically conditioned gate, a controlled gate with
+    /// an open control, or a gate with unbound or complex parameters.
+    pub fn from_py_operation(op: &Bound<PyAny>) -> PyResult<Option<(Self, Vec<f64>)>> {
+        let py = op.py();
+        let op_type = op.get_type();
+        let module: String = op_type.getattr(intern!(py, "__module__"))?.extract()?;
+        if !module.starts_with("qiskit.circuit.library.standard_gates") {
+            return Ok(None);
+        }
+        let Some(gate) = StandardGate::from_class_name(&op_type.name()?) else {
+            return Ok(None);
+        };
+        if !op.getattr(intern!(py, "condition"))?.is_none() {
+            return Ok(None);
+        }
+        if let Ok(ctrl_state) = op.getattr(intern!(py, "ctrl_state")) {
+            let ctrl_state: u32 = ctrl_state.extract()?;
+            let num_ctrl_qubits: u32 = op.getattr(intern!(py, "num_ctrl_qubits"))?.extract()?;
+            if ctrl_state != (1 << num_ctrl_qubits) - 1 {
+                return Ok(None);
+            }
+        }
+        let mut params = Vec::with_capacity(gate.num_params() as usize);
+        for param in op.getattr(intern!(py, "params"))?.iter()? {
+            let param = param?;
+            let value = match param.extract::<f64>() {
+                Ok(value) => value,
+                // Bound `ParameterExpression`s with real values support `float()`.
+                Err(_) => match param
+                    .call_method0(intern!(py, "__float__"))
+                    .and_then(|value| value.extract::<f64>())
+                {
+                    Ok(value) => value,
+                    Err(_) => return Ok(None),
+                },
+            };
+            params.push(value);
+        }
+        if params.len() != gate.num_params() as usize {
+            return Ok(None);
+        }
+        Ok(Some((gate, params)))
+    }
+}
@@ -13,37 +13,101 @@
 use pyo3::prelude::*;
 use pyo3::wrap_pymodule;
 
+use qiskit_accelerate::instrumentation::CountingAllocator;
 use qiskit_accelerate::{
-    convert_2q_block_matrix::convert_2q_block_matrix, dense_layout::dense_layout,
-    error_map::error_map, euler_one_qubit_decomposer::euler_one_qubit_decomposer,
-    isometry::isometry, nlayout::nlayout, optimize_1q_gates::optimize_1q_gates,
-    pauli_exp_val::pauli_expval, results::results, sabre::sabre, sampled_exp_val::sampled_exp_val,
-    sparse_pauli_op::sparse_pauli_op, stochastic_swap::stochastic_swap,
-    two_qubit_decompose::two_qubit_decompose, uc_gate::uc_gate, utils::utils,
-    vf2_layout::vf2_layout,
+    check_map::check_map, clifford::clifford, cnotdihedral::cnotdihedral,
+    collect_blocks::collect_blocks, commutation_checker::commutation_checker,
+    commutative_inverse_cancellation::commutative_inverse_cancellation,
+    consolidate_blocks::consolidate_blocks, convert_2q_block_matrix::convert_2q_block_matrix,
+    coupling_map::coupling_map, dense_layout::dense_layout, density_matrix::density_matrix,
+    disjoint_components::disjoint_components, dynamical_decoupling::dynamical_decoupling,
+    entanglement::entanglement, error_map::error_map, estimator_grouping::estimator_grouping,
+    euler_one_qubit_decomposer::euler_one_qubit_decomposer, fidelity::fidelity,
+    heavy_hex_layout::heavy_hex_layout, instrumentation::instrumentation, isometry::isometry,
+    layers::layers, layout_selection::layout_selection, line_layout::line_layout,
+    mirror_circuit::mirror_circuit, nlayout::nlayout, noise_adaptive_layout::noise_adaptive_layout,
+    operator::operator, optimize_1q_gates::optimize_1q_gates, parameter_shift::parameter_shift,
+    partial_trace::partial_trace, pass_loop::pass_loop, pauli::pauli, pauli_exp_val::pauli_expval,
+    pub_broadcast::pub_broadcast, random_circuit::random_circuit,
+    random_quantum_info::random_quantum_info, readout_mitigation::readout_mitigation,
+    results::results, rng::rng, sabre::sabre, sampled_exp_val::sampled_exp_val,
+    sampler_results::sampler_results, sampling::sampling, scheduling::scheduling,
+    sparse_pauli_op::sparse_pauli_op, stabilizer_state::stabilizer_state, statevector::statevector,
+    stochastic_swap::stochastic_swap, swap_strategy::swap_strategy, synthesis::synthesis,
+    target::target, threading::threading, twirling::twirling,
+    two_qubit_decompose::two_qubit_decompose, uc_gate::uc_gate,
+    unitary_synthesis::unitary_synthesis, utils::utils, vf2_layout::vf2_layout, weyl::weyl,
 };
 
+// Counts the allocations made while the instrumentation is enabled, and otherwise just forwards to
+// the system allocator.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 #[pymodule]
 fn _accelerate(m: &Bound<PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pymodule!(qiskit_circuit::circuit))?;
     m.add_wrapped(wrap_pymodule!(qiskit_qasm2::qasm2))?;
     m.add_wrapped(wrap_pymodule!(qiskit_qasm3::qasm3))?;
+    m.add_wrapped(wrap_pymodule!(check_map))?;
+    m.add_wrapped(wrap_pymodule!(clifford))?;
+    m.add_wrapped(wrap_pymodule!(cnotdihedral))?;
+    m.add_wrapped(wrap_pymodule!(commutation_checker))?;
+    m.add_wrapped(wrap_pymodule!(commutative_inverse_cancellation))?;
+    m.add_wrapped(wrap_pymodule!(consolidate_blocks))?;
+    m.add_wrapped(wrap_pymodule!(collect_blocks))?;
     m.add_wrapped(wrap_pymodule!(convert_2q_block_matrix))?;
+    m.add_wrapped(wrap_pymodule!(coupling_map))?;
     m.add_wrapped(wrap_pymodule!(dense_layout))?;
+    m.add_wrapped(wrap_pymodule!(density_matrix))?;
+    m.add_wrapped(wrap_pymodule!(disjoint_components))?;
+    m.add_wrapped(wrap_pymodule!(dynamical_decoupling))?;
+    m.add_wrapped(wrap_pymodule!(entanglement))?;
     m.add_wrapped(wrap_pymodule!(error_map))?;
+    m.add_wrapped(wrap_pymodule!(estimator_grouping))?;
     m.add_wrapped(wrap_pymodule!(euler_one_qubit_decomposer))?;
+    m.add_wrapped(wrap_pymodule!(fidelity))?;
+    m.add_wrapped(wrap_pymodule!(heavy_hex_layout))?;
+    m.add_wrapped(wrap_pymodule!(instrumentation))?;
     m.add_wrapped(wrap_pymodule!(isometry))?;
+    m.add_wrapped(wrap_pymodule!(layers))?;
+    m.add_wrapped(wrap_pymodule!(layout_selection))?;
+    m.add_wrapped(wrap_pymodule!(line_layout))?;
+    m.add_wrapped(wrap_pymodule!(mirror_circuit))?;
     m.add_wrapped(wrap_pymodule!(nlayout))?;
+    m.add_wrapped(wrap_pymodule!(noise_adaptive_layout))?;
+    m.add_wrapped(wrap_pymodule!(operator))?;
     m.add_wrapped(wrap_pymodule!(optimize_1q_gates))?;
+    m.add_wrapped(wrap_pymodule!(parameter_shift))?;
+    m.add_wrapped(wrap_pymodule!(partial_trace))?;
+    m.add_wrapped(wrap_pymodule!(pass_loop))?;
+    m.add_wrapped(wrap_pymodule!(pauli))?;
     m.add_wrapped(wrap_pymodule!(pauli_expval))?;
+    m.add_wrapped(wrap_pymodule!(pub_broadcast))?;
+    m.add_wrapped(wrap_pymodule!(random_circuit))?;
+    m.add_wrapped(wrap_pymodule!(random_quantum_info))?;
+    m.add_wrapped(wrap_pymodule!(readout_mitigation))?;
     m.add_wrapped(wrap_pymodule!(results))?;
+    m.add_wrapped(wrap_pymodule!(rng))?;
     m.add_wrapped(wrap_pymodule!(sabre))?;
     m.add_wrapped(wrap_pymodule!(sampled_exp_val))?;
+    m.add_wrapped(wrap_pymodule!(sampler_results))?;
+    m.add_wrapped(wrap_pymodule!(sampling))?;
+    m.add_wrapped(wrap_pymodule!(scheduling))?;
     m.add_wrapped(wrap_pymodule!(sparse_pauli_op))?;
+    m.add_wrapped(wrap_pymodule!(stabilizer_state))?;
+    m.add_wrapped(wrap_pymodule!(statevector))?;
     m.add_wrapped(wrap_pymodule!(stochastic_swap))?;
+    m.add_wrapped(wrap_pymodule!(swap_strategy))?;
+    m.add_wrapped(wrap_pymodule!(synthesis))?;
+    m.add_wrapped(wrap_pymodule!(target))?;
+    m.add_wrapped(wrap_pymodule!(threading))?;
+    m.add_wrapped(wrap_pymodule!(twirling))?;
     m.add_wrapped(wrap_pymodule!(two_qubit_decompose))?;
     m.add_wrapped(wrap_pymodule!(uc_gate))?;
+    m.add_wrapped(wrap_pymodule!(unitary_synthesis))?;
     m.add_wrapped(wrap_pymodule!(utils))?;
     m.add_wrapped(wrap_pymodule!(vf2_layout))?;
+    m.add_wrapped(wrap_pymodule!(weyl))?;
     Ok(())
 }
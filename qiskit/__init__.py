@@ -60,28 +60,73 @@ import qiskit._numpy_compat
 # We manually define them on import so people can directly import qiskit._accelerate.* submodules
 # and not have to rely on attribute access.  No action needed for top-level extension packages.
 sys.modules["qiskit._accelerate.circuit"] = qiskit._accelerate.circuit
+sys.modules["qiskit._accelerate.check_map"] = qiskit._accelerate.check_map
+sys.modules["qiskit._accelerate.clifford"] = qiskit._accelerate.clifford
+sys.modules["qiskit._accelerate.cnotdihedral"] = qiskit._accelerate.cnotdihedral
+sys.modules["qiskit._accelerate.commutation_checker"] = qiskit._accelerate.commutation_checker
+sys.modules["qiskit._accelerate.commutative_inverse_cancellation"] = (
+    qiskit._accelerate.commutative_inverse_cancellation
+)
+sys.modules["qiskit._accelerate.collect_blocks"] = qiskit._accelerate.collect_blocks
+sys.modules["qiskit._accelerate.consolidate_blocks"] = qiskit._accelerate.consolidate_blocks
 sys.modules["qiskit._accelerate.convert_2q_block_matrix"] = (
     qiskit._accelerate.convert_2q_block_matrix
 )
+sys.modules["qiskit._accelerate.coupling_map"] = qiskit._accelerate.coupling_map
 sys.modules["qiskit._accelerate.dense_layout"] = qiskit._accelerate.dense_layout
+sys.modules["qiskit._accelerate.disjoint_components"] = qiskit._accelerate.disjoint_components
+sys.modules["qiskit._accelerate.dynamical_decoupling"] = qiskit._accelerate.dynamical_decoupling
+sys.modules["qiskit._accelerate.density_matrix"] = qiskit._accelerate.density_matrix
+sys.modules["qiskit._accelerate.entanglement"] = qiskit._accelerate.entanglement
 sys.modules["qiskit._accelerate.error_map"] = qiskit._accelerate.error_map
+sys.modules["qiskit._accelerate.estimator_grouping"] = qiskit._accelerate.estimator_grouping
+sys.modules["qiskit._accelerate.fidelity"] = qiskit._accelerate.fidelity
+sys.modules["qiskit._accelerate.heavy_hex_layout"] = qiskit._accelerate.heavy_hex_layout
+sys.modules["qiskit._accelerate.instrumentation"] = qiskit._accelerate.instrumentation
 sys.modules["qiskit._accelerate.isometry"] = qiskit._accelerate.isometry
+sys.modules["qiskit._accelerate.operator"] = qiskit._accelerate.operator
+sys.modules["qiskit._accelerate.parameter_shift"] = qiskit._accelerate.parameter_shift
+sys.modules["qiskit._accelerate.partial_trace"] = qiskit._accelerate.partial_trace
+sys.modules["qiskit._accelerate.pass_loop"] = qiskit._accelerate.pass_loop
+sys.modules["qiskit._accelerate.pauli"] = qiskit._accelerate.pauli
+sys.modules["qiskit._accelerate.pub_broadcast"] = qiskit._accelerate.pub_broadcast
+sys.modules["qiskit._accelerate.random_circuit"] = qiskit._accelerate.random_circuit
+sys.modules["qiskit._accelerate.random_quantum_info"] = qiskit._accelerate.random_quantum_info
+sys.modules["qiskit._accelerate.readout_mitigation"] = qiskit._accelerate.readout_mitigation
+sys.modules["qiskit._accelerate.statevector"] = qiskit._accelerate.statevector
+sys.modules["qiskit._accelerate.synthesis"] = qiskit._accelerate.synthesis
+sys.modules["qiskit._accelerate.target"] = qiskit._accelerate.target
+sys.modules["qiskit._accelerate.threading"] = qiskit._accelerate.threading
+sys.modules["qiskit._accelerate.twirling"] = qiskit._accelerate.twirling
 sys.modules["qiskit._accelerate.uc_gate"] = qiskit._accelerate.uc_gate
+sys.modules["qiskit._accelerate.unitary_synthesis"] = qiskit._accelerate.unitary_synthesis
 sys.modules["qiskit._accelerate.euler_one_qubit_decomposer"] = (
     qiskit._accelerate.euler_one_qubit_decomposer
 )
+sys.modules["qiskit._accelerate.layers"] = qiskit._accelerate.layers
+sys.modules["qiskit._accelerate.layout_selection"] = qiskit._accelerate.layout_selection
+sys.modules["qiskit._accelerate.line_layout"] = qiskit._accelerate.line_layout
+sys.modules["qiskit._accelerate.mirror_circuit"] = qiskit._accelerate.mirror_circuit
 sys.modules["qiskit._accelerate.nlayout"] = qiskit._accelerate.nlayout
+sys.modules["qiskit._accelerate.noise_adaptive_layout"] = qiskit._accelerate.noise_adaptive_layout
 sys.modules["qiskit._accelerate.optimize_1q_gates"] = qiskit._accelerate.optimize_1q_gates
 sys.modules["qiskit._accelerate.pauli_expval"] = qiskit._accelerate.pauli_expval
 sys.modules["qiskit._accelerate.qasm2"] = qiskit._accelerate.qasm2
 sys.modules["qiskit._accelerate.qasm3"] = qiskit._accelerate.qasm3
 sys.modules["qiskit._accelerate.results"] = qiskit._accelerate.results
+sys.modules["qiskit._accelerate.rng"] = qiskit._accelerate.rng
 sys.modules["qiskit._accelerate.sabre"] = qiskit._accelerate.sabre
 sys.modules["qiskit._accelerate.sampled_exp_val"] = qiskit._accelerate.sampled_exp_val
+sys.modules["qiskit._accelerate.sampler_results"] = qiskit._accelerate.sampler_results
+sys.modules["qiskit._accelerate.sampling"] = qiskit._accelerate.sampling
+sys.modules["qiskit._accelerate.scheduling"] = qiskit._accelerate.scheduling
 sys.modules["qiskit._accelerate.sparse_pauli_op"] = qiskit._accelerate.sparse_pauli_op
+sys.modules["qiskit._accelerate.stabilizer_state"] = qiskit._accelerate.stabilizer_state
 sys.modules["qiskit._accelerate.stochastic_swap"] = qiskit._accelerate.stochastic_swap
+sys.modules["qiskit._accelerate.swap_strategy"] = qiskit._accelerate.swap_strategy
 sys.modules["qiskit._accelerate.two_qubit_decompose"] = qiskit._accelerate.two_qubit_decompose
 sys.modules["qiskit._accelerate.vf2_layout"] = qiskit._accelerate.vf2_layout
+sys.modules["qiskit._accelerate.weyl"] = qiskit._accelerate.weyl
 
 from qiskit.exceptions import QiskitError, MissingOptionalLibraryError
 
@@ -23,8 +23,8 @@ from qiskit.circuit.gate import Gate
 from qiskit.circuit.library.standard_gates.x import XGate
 from qiskit.circuit.library.standard_gates.h import HGate
 from qiskit.circuit.library.standard_gates.s import SGate, SdgGate
-from qiskit.circuit.library.generalized_gates import Isometry
 from qiskit.circuit.exceptions import CircuitError
+from qiskit._accelerate.synthesis import synth_state_preparation
 from qiskit.quantum_info.states.statevector import Statevector  # pylint: disable=cyclic-import
 
 _EPS = 1e-10  # global variable used to chop very small numbers to zero
@@ -69,13 +69,12 @@ class StatePreparation(Gate):
         Raises:
             QiskitError: ``num_qubits`` parameter used when ``params`` is not an integer
 
-        When a Statevector argument is passed the state is prepared based on the
-        :class:`~.library.Isometry` synthesis described in [1].
+        When a Statevector argument is passed the state is prepared with the multiplexed
+        rotations described in [1].
 
         References:
-            1. Iten et al., Quantum circuits for isometries (2016).
-               `Phys. Rev. A 93, 032318
-               <https://journals.aps.org/pra/abstract/10.1103/PhysRevA.93.032318>`__.
+            1. Shende, Bullock, Markov, *Synthesis of Quantum Logic Circuits*,
+               `arXiv:0406176 [quant-ph] <https://arxiv.org/abs/quant-ph/0406176>`_
 
         """
         self._params_arg = params
@@ -117,7 +116,7 @@ class StatePreparation(Gate):
         elif self._from_int:
             self.definition = self._define_from_int()
         else:
-            self.definition = self._define_synthesis_isom()
+            self.definition = self._define_synthesis()
 
     def _define_from_label(self):
         q = QuantumRegister(self.num_qubits, "q")
@@ -166,13 +165,10 @@ class StatePreparation(Gate):
         # we don't need to invert anything
         return initialize_circuit
 
-    def _define_synthesis_isom(self):
-        """Calculate a subcircuit that implements this initialization via isometry"""
-        q = QuantumRegister(self.num_qubits, "q")
-        initialize_circuit = QuantumCircuit(q, name="init_def")
-
-        isom = Isometry(self._params_arg, 0, 0)
-        initialize_circuit.append(isom, q[:])
+    def _define_synthesis(self):
+        """Calculate a subcircuit that implements this state preparation with multiplexed
+        rotations"""
+        initialize_circuit = self._preparation_circuit()
 
         # invert the circuit to create the desired vector from zero (assuming
         # the qubits are in the zero state)
@@ -181,6 +177,15 @@ class StatePreparation(Gate):
 
         return initialize_circuit
 
+    def _preparation_circuit(self):
+        """Synthesize the circuit taking the zero state to ``self.params``."""
+        data, global_phase = synth_state_preparation(np.asarray(self.params, dtype=complex))
+        return QuantumCircuit._from_circuit_data(data, name="init_def", global_phase=global_phase)
+
+    def _gates_to_uncompute(self):
+        """Call to create a circuit with gates that take the desired vector to zero."""
+        return self._preparation_circuit().inverse()
+
     def _get_num_qubits(self, num_qubits, params):
         """Get number of qubits needed for state preparation"""
         if isinstance(params, str):
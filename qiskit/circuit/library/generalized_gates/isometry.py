@@ -30,6 +30,7 @@ from qiskit.circuit.quantumregister import QuantumRegister
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators.predicates import is_isometry
 from qiskit._accelerate import isometry as isometry_rs
+from qiskit._accelerate.synthesis import synth_isometry
 
 from .diagonal import Diagonal
 from .uc import UCGate
@@ -118,15 +119,14 @@ class Isometry(Instruction):
         super().__init__("isometry", num_qubits, 0, [isometry])
 
     def _define(self):
-        # TODO The inverse().inverse() is because there is code to uncompute (_gates_to_uncompute)
-        #  an isometry, but not for generating its decomposition. It would be cheaper to do the
-        #  later here instead.
-        gate = self.inv_gate()
-        gate = gate.inverse()
-        q = QuantumRegister(self.num_qubits, "q")
-        iso_circuit = QuantumCircuit(q, name="isometry")
-        iso_circuit.append(gate, q[:])
-        self.definition = iso_circuit
+        data, global_phase = synth_isometry(
+            self.iso_data,
+            num_ancillas=self.num_ancillas_zero + self.num_ancillas_dirty,
+            epsilon=self._epsilon,
+        )
+        self.definition = QuantumCircuit._from_circuit_data(
+            data, name="isometry", global_phase=global_phase
+        )
 
     def inverse(self, annotated: bool = False):
         self.params = []
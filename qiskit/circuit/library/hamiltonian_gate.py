@@ -22,6 +22,7 @@ from numbers import Number
 import numpy as np
 
 from qiskit import _numpy_compat
+from qiskit._accelerate import operator as operator_rs
 from qiskit.circuit.gate import Gate
 from qiskit.circuit.quantumcircuit import QuantumCircuit
 from qiskit.circuit.quantumregister import QuantumRegister
@@ -95,8 +96,6 @@ class HamiltonianGate(Gate):
 
     def __array__(self, dtype=None, copy=None):
         """Return matrix for the unitary."""
-        import scipy.linalg
-
         if copy is False:
             raise ValueError("unable to avoid copy while creating an array as requested")
         try:
@@ -106,7 +105,7 @@ class HamiltonianGate(Gate):
                 "Unable to generate Unitary matrix for "
                 "unbound t parameter {}".format(self.params[1])
             ) from ex
-        arr = scipy.linalg.expm(-1j * self.params[0] * time)
+        arr = operator_rs.hamiltonian_evolution(np.asarray(self.params[0], dtype=complex), time)
         dtype = complex if dtype is None else dtype
         return np.array(arr, dtype=dtype, copy=_numpy_compat.COPY_ONLY_IF_NEEDED)
 
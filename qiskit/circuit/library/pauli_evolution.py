@@ -17,6 +17,8 @@ from __future__ import annotations
 from typing import Union, Optional, TYPE_CHECKING
 import numpy as np
 
+from qiskit import _numpy_compat
+from qiskit._accelerate import operator as operator_rs
 from qiskit.circuit.gate import Gate
 from qiskit.circuit.parameterexpression import ParameterExpression
 from qiskit.quantum_info import Pauli, SparsePauliOp
@@ -139,6 +141,21 @@ class PauliEvolutionGate(Gate):
         """
         self.params = [time]
 
+    def __array__(self, dtype=None, copy=None):
+        """Return the matrix of the exact evolution under the sum of the operators."""
+        if copy is False:
+            raise ValueError("unable to avoid copy while creating an array as requested")
+        try:
+            time = float(self.time)
+        except TypeError as ex:
+            raise TypeError(
+                f"Unable to generate Unitary matrix for unbound t parameter {self.time}"
+            ) from ex
+        operator = sum(self.operator) if isinstance(self.operator, list) else self.operator
+        arr = operator_rs.hamiltonian_evolution(operator.to_matrix(), time)
+        dtype = complex if dtype is None else dtype
+        return np.array(arr, dtype=dtype, copy=_numpy_compat.COPY_ONLY_IF_NEEDED)
+
     def _define(self):
         """Unroll, where the default synthesis is matrix based."""
         self.definition = self.synthesis.synthesize(self)
@@ -23,6 +23,7 @@ from qiskit.circuit.library.standard_gates.x import MCXGate
 from qiskit.circuit.library.standard_gates.u3 import _generate_gray_code
 from qiskit.circuit.parameterexpression import ParameterValueType
 from qiskit.exceptions import QiskitError
+from qiskit._accelerate.synthesis import synth_mcrz
 
 
 def _apply_cu(circuit, theta, phi, lam, control, target, use_basis_gates=True):
@@ -374,7 +375,11 @@ def mcrz(
     self._check_dups(all_qubits)
 
     n_c = len(control_qubits)
-    if n_c == 1:
+    if use_basis_gates and type(lam) in [float, int]:
+        data, global_phase = synth_mcrz(float(lam), n_c)
+        cgate = QuantumCircuit._from_circuit_data(data, global_phase=global_phase)
+        self.compose(cgate, control_qubits + [target_qubit], inplace=True)
+    elif n_c == 1:
         if use_basis_gates:
             self.u(0, 0, lam / 2, target_qubit)
             self.cx(control_qubits[0], target_qubit)
@@ -19,6 +19,7 @@ from qiskit.circuit.controlledgate import ControlledGate
 from qiskit.circuit.gate import Gate
 from qiskit.circuit.quantumregister import QuantumRegister
 from qiskit.circuit.parameterexpression import ParameterValueType
+from qiskit._accelerate.synthesis import synth_mcphase
 
 
 class PhaseGate(Gate):
@@ -368,13 +369,10 @@ class MCPhaseGate(ControlledGate):
         else:
             lam = self.params[0]
             if type(lam) in [float, int]:
-                q_controls = list(range(self.num_ctrl_qubits))
-                q_target = self.num_ctrl_qubits
-                new_target = q_target
-                for k in range(self.num_ctrl_qubits):
-                    qc.mcrz(lam / (2**k), q_controls, new_target, use_basis_gates=True)
-                    new_target = q_controls.pop()
-                qc.p(lam / (2**self.num_ctrl_qubits), new_target)
+                data, global_phase = synth_mcphase(float(lam), self.num_ctrl_qubits)
+                qc = QuantumCircuit._from_circuit_data(
+                    data, name=self.name, global_phase=global_phase
+                )
             else:  # in this case type(lam) is ParameterValueType
                 from .u3 import _gray_code_chain
 
@@ -1131,7 +1131,6 @@ class QuantumCircuit:
         self._parameters = None
 
         self._layout = None
-        self._global_phase: ParameterValueType = 0
         self.global_phase = global_phase
 
         # Add classical variables.  Resolve inputs and captures first because they can't depend on
@@ -1159,6 +1158,27 @@ class QuantumCircuit:
         Qiskit will not examine the content of this mapping, but it will pass it through the
         transpiler and reattach it to the output, so you can track your own metadata."""
 
+    @staticmethod
+    def _from_circuit_data(
+        data: CircuitData, name: str | None = None, global_phase: ParameterValueType | None = None
+    ) -> "QuantumCircuit":
+        """A private constructor from Rust-space circuit data.
+
+        The circuit takes ownership of ``data``.  Its instructions must not contain unbound
+        parameters, since they are not added to the parameter table.  The global phase is that of
+        ``data``, unless ``global_phase`` is given.
+        """
+        if global_phase is None:
+            global_phase = data.global_phase
+        # The phase is reset so the public setter below adds any parameters in it to the table.
+        data.global_phase = 0
+        out = QuantumCircuit(name=name)
+        out.add_bits(data.qubits)
+        out.add_bits(data.clbits)
+        out._data = data
+        out.global_phase = global_phase
+        return out
+
     @staticmethod
     def from_instructions(
         instructions: Iterable[
@@ -3655,7 +3675,9 @@ class QuantumCircuit:
             cpy._parameter_table[parameter] = ParameterReferences(
                 [(ParameterTable.GLOBAL_PHASE, None)]
             )
-        cpy._data = CircuitData(self._data.qubits, self._data.clbits)
+        cpy._data = CircuitData(
+            self._data.qubits, self._data.clbits, global_phase=self._data.global_phase
+        )
 
         cpy._calibrations = _copy.deepcopy(self._calibrations)
         cpy._metadata = _copy.deepcopy(self._metadata)
@@ -3959,7 +3981,11 @@ class QuantumCircuit:
         circ._clbit_indices = {}
 
         # Clear instruction info
-        circ._data = CircuitData(qubits=circ._data.qubits, reserve=len(circ._data))
+        circ._data = CircuitData(
+            qubits=circ._data.qubits,
+            reserve=len(circ._data),
+            global_phase=circ._data.global_phase,
+        )
         circ._parameter_table.clear()
         # Repopulate the parameter table with any global-phase entries.
         circ.global_phase = circ.global_phase
@@ -4035,7 +4061,7 @@ class QuantumCircuit:
         """The global phase of the current circuit scope in radians."""
         if self._control_flow_scopes:
             return self._control_flow_scopes[-1].global_phase
-        return self._global_phase
+        return self._data.global_phase
 
     @global_phase.setter
     def global_phase(self, angle: ParameterValueType):
@@ -4044,10 +4070,10 @@ class QuantumCircuit:
         Args:
             angle (float, ParameterExpression): radians
         """
-        # If we're currently parametric, we need to throw away the references.  This setter is
-        # called by some subclasses before the inner `_global_phase` is initialised.
+        # If we're currently parametric, we need to throw away the references.  The phase itself is
+        # stored in the Rust-space `CircuitData`, so there's only one copy to keep up to date.
         global_phase_reference = (ParameterTable.GLOBAL_PHASE, None)
-        if isinstance(previous := getattr(self, "_global_phase", None), ParameterExpression):
+        if isinstance(previous := self._data.global_phase, ParameterExpression):
             self._parameters = None
             self._parameter_table.discard_references(previous, global_phase_reference)
 
@@ -4062,7 +4088,7 @@ class QuantumCircuit:
         if self._control_flow_scopes:
             self._control_flow_scopes[-1].global_phase = angle
         else:
-            self._global_phase = angle
+            self._data.global_phase = angle
 
     @property
     def parameters(self) -> ParameterView:
@@ -4333,7 +4359,7 @@ class QuantumCircuit:
                 if operation is ParameterTable.GLOBAL_PHASE:
                     # We've already handled parameter table updates in bulk, so we need to skip the
                     # public setter trying to do it again.
-                    target._global_phase = new_parameter
+                    target._data.global_phase = new_parameter
                 else:
                     operation.params[index] = new_parameter
 
@@ -14,6 +14,7 @@
 
 import numpy as np
 
+from qiskit._accelerate import mirror_circuit as mirror_circuit_rs
 from qiskit.circuit import ClassicalRegister, QuantumCircuit, CircuitInstruction
 from qiskit.circuit import Reset
 from qiskit.circuit.library import standard_gates
@@ -276,3 +277,48 @@ def random_clifford_circuit(num_qubits, num_gates, gates="all", seed=None):
         circ.append(gate, qargs, copy=False)
 
     return circ
+
+
+def mirror_circuits(circuit, num_circuits=1, pauli_layer=True, barriers=True, seed=None):
+    """Generate mirror circuits of a circuit for benchmarking.
+
+    Each mirror circuit runs ``circuit``, a layer of random Pauli gates on every qubit, and the
+    inverse of ``circuit``, whose layers are those of ``circuit`` in reverse with each instruction
+    inverted.  Without the Pauli layer, this is ``circuit`` with its inverse appended, which ideally
+    measures all zeros.  The circuits are built in Rust.
+
+    .. code-block:: python
+
+        from qiskit.circuit.random import mirror_circuits, random_clifford_circuit
+
+        circuit = random_clifford_circuit(num_qubits=3, num_gates=10, seed=1)
+        for mirror, expected in mirror_circuits(circuit, num_circuits=4, seed=2):
+            mirror.measure_all()
+
+    Args:
+        circuit (QuantumCircuit): the circuit, without unbound parameters.
+        num_circuits (int): the number of mirror circuits.
+        pauli_layer (bool): whether to put a layer of random Pauli gates between the halves.
+        barriers (bool): whether to put barriers around the middle of each mirror circuit.
+        seed (int | np.random.Generator): sets random seed/generator (optional).
+
+    Returns:
+        list[tuple[QuantumCircuit, str | None]]: each mirror circuit with the bitstring that it
+        ideally measures, with the first qubit rightmost, or ``None`` if ``circuit`` is not a
+        Clifford circuit and there is a Pauli layer.
+
+    Raises:
+        CircuitError: if ``circuit`` has unbound parameters or an instruction that can't be
+            inverted.
+    """
+    if circuit.parameters:
+        raise CircuitError("Cannot build mirror circuits of a circuit with unbound parameters.")
+    if isinstance(seed, np.random.Generator):
+        seed = int(seed.integers(1 << 64, dtype=np.uint64))
+    mirrors = mirror_circuit_rs.mirror_circuits(
+        circuit._data, num_circuits, pauli_layer=pauli_layer, barriers=barriers, seed=seed
+    )
+    return [
+        (QuantumCircuit._from_circuit_data(data, name=f"{circuit.name}_mirror"), expected)
+        for data, expected in mirrors
+    ]
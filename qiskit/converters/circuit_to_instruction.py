@@ -158,9 +158,7 @@ def circuit_to_instruction(circuit, parameter_map=None, equivalence_library=None
             for param in target._parameter_table
         }
     )
-
-    if circuit.global_phase:
-        qc.global_phase = circuit.global_phase
+    # The global phase of ``target`` was carried over along with ``data``.
 
     out_instruction.definition = qc
 
@@ -46,6 +46,14 @@ filename that cannot be used:
 .. autoexception:: QiskitUserConfigError
 .. autoexception:: InvalidFileError
 
+Failures inside Qiskit's compiled routines raise errors that carry structured details of the
+failure as attributes, such as the qubits involved, alongside their messages.  These derive from
+:exc:`QiskitRustError`, and the more specific errors :exc:`.RoutingError` and :exc:`.LayoutError`
+of :mod:`qiskit.transpiler` are also types of :exc:`.TranspilerError`.
+
+.. autoexception:: QiskitRustError
+.. autoexception:: SynthesisError
+
 
 Warnings
 ========
@@ -139,6 +147,28 @@ class InvalidFileError(QiskitError):
     """Raised when the file provided is not valid for the specific task."""
 
 
+class QiskitRustError(QiskitError):
+    """Base class for errors that carry structured details of a failure, as raised by Qiskit's
+    compiled routines.
+
+    Attributes:
+        pass_name (str | None): the transpiler pass or synthesis routine that failed.
+        qubits (tuple[int, ...] | None): the indices of the qubits involved in the failure.
+        tolerance (float | None): the numerical tolerance that couldn't be met.
+    """
+
+    def __init__(self, *message, pass_name=None, qubits=None, tolerance=None):
+        """Set the error message and the details of the failure."""
+        super().__init__(*message)
+        self.pass_name = pass_name
+        self.qubits = None if qubits is None else tuple(qubits)
+        self.tolerance = tolerance
+
+
+class SynthesisError(QiskitRustError):
+    """Raised when a synthesis routine fails to find a circuit."""
+
+
 class QiskitWarning(UserWarning):
     """Common subclass of warnings for Qiskit-specific warnings being raised."""
 
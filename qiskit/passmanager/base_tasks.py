@@ -95,7 +95,7 @@ class GenericPass(Task, ABC):
         start_time = time.time()
         try:
             if self not in state.workflow_status.completed_passes:
-                ret = self.run(passmanager_ir)
+                ret = self._run_in_workflow(passmanager_ir)
                 run_state = RunState.SUCCESS
             else:
                 run_state = RunState.SKIP
@@ -137,6 +137,17 @@ class GenericPass(Task, ABC):
             state.workflow_status.completed_passes.add(self)
         return state
 
+    def _run_in_workflow(
+        self,
+        passmanager_ir: PassManagerIR,
+    ) -> PassManagerIR:
+        """Run the pass as a task of a pass manager workflow.
+
+        This is :meth:`run`, unless a subclass hands another form of the IR between the tasks
+        that support it.
+        """
+        return self.run(passmanager_ir)
+
     @abstractmethod
     def run(
         self,
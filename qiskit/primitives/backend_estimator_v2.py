@@ -21,19 +21,20 @@ from dataclasses import dataclass
 
 import numpy as np
 
+from qiskit._accelerate.estimator_grouping import ObservableGrouping
+from qiskit._accelerate.pub_broadcast import PubBroadcast
 from qiskit.circuit import ClassicalRegister, QuantumCircuit, QuantumRegister
 from qiskit.exceptions import QiskitError
 from qiskit.providers import BackendV1, BackendV2
-from qiskit.quantum_info import Pauli, PauliList
-from qiskit.result import Counts
 from qiskit.transpiler import PassManager, PassManagerConfig
 from qiskit.transpiler.passes import Optimize1qGatesDecomposition
 
-from .backend_estimator import _pauli_expval_with_variance, _prepare_counts, _run_circuits
+from .backend_estimator import _prepare_counts, _run_circuits
 from .base import BaseEstimatorV2
 from .containers import DataBin, EstimatorPubLike, PrimitiveResult, PubResult
 from .containers.bindings_array import BindingsArray
 from .containers.estimator_pub import EstimatorPub
+from .containers.observables_array import ObservablesArray
 from .primitive_job import PrimitiveJob
 
 
@@ -64,11 +65,18 @@ class _PreprocessedData:
     circuits: list[QuantumCircuit]
     """The quantum circuits generated by binding parameters of the pub's circuit."""
 
-    parameter_indices: np.ndarray
-    """The indices of the pub's bindings array broadcast to the shape of the pub."""
+    broadcast: PubBroadcast
+    """The pairing of the parameter value sets and the observables of each element of the pub."""
 
-    observables: np.ndarray
-    """The pub's observable array broadcast to the shape of the pub."""
+    observables: ObservablesArray
+    """The pub's observable array, flattened."""
+
+    grouping: ObservableGrouping
+    """The measurement groups of the Pauli terms of each parameter value set, in the order of
+    the circuits."""
+
+    parameter_positions: list[int]
+    """The position in ``grouping`` of the parameter value set of each element of the pub."""
 
 
 class BackendEstimatorV2(BaseEstimatorV2):
@@ -183,7 +191,7 @@ class BackendEstimatorV2(BaseEstimatorV2):
             preprocessed_data.append(data)
             flat_circuits.extend(data.circuits)
 
-        run_result, metadata = _run_circuits(
+        run_result, _ = _run_circuits(
             flat_circuits, self._backend, shots=shots, seed_simulator=self._options.seed_simulator
         )
         counts = _prepare_counts(run_result)
@@ -192,9 +200,8 @@ class BackendEstimatorV2(BaseEstimatorV2):
         start = 0
         for pub, data in zip(pubs, preprocessed_data):
             end = start + len(data.circuits)
-            expval_map = self._calc_expval_map(counts[start:end], metadata[start:end])
+            results.append(self._postprocess_pub(pub, counts[start:end], data, shots))
             start = end
-            results.append(self._postprocess_pub(pub, expval_map, data, shots))
         return results
 
     def _preprocess_pub(self, pub: EstimatorPub) -> _PreprocessedData:
@@ -207,30 +214,35 @@ class BackendEstimatorV2(BaseEstimatorV2):
             pub: The pub to preprocess.
 
         Returns:
-            The values ``(circuits, bc_param_ind, bc_obs)`` where ``circuits`` are the circuits to
-            execute on the backend, ``bc_param_ind`` are indices of the pub's bindings array and
-            ``bc_obs`` is the observables array, both broadcast to the shape of the pub.
+            The circuits to execute on the backend, the pairing of the parameter value sets and
+            the observables of the pub, and the measurement groups that the circuits measure.
         """
         circuit = pub.circuit
         observables = pub.observables
         parameter_values = pub.parameter_values
 
         # calculate broadcasting of parameters and observables
-        param_shape = parameter_values.shape
-        param_indices = np.fromiter(np.ndindex(param_shape), dtype=object).reshape(param_shape)
-        bc_param_ind, bc_obs = np.broadcast_arrays(param_indices, observables)
+        broadcast = PubBroadcast(parameter_values.shape, observables.shape)
+        flat_observables = observables.ravel()
 
         # calculate expectation values for each pair of parameter value set and pauli
-        param_obs_map = defaultdict(set)
-        for index in np.ndindex(*bc_param_ind.shape):
-            param_index = bc_param_ind[index]
-            param_obs_map[param_index].update(bc_obs[index])
-
-        bound_circuits = self._bind_and_add_measurements(circuit, parameter_values, param_obs_map)
-        return _PreprocessedData(bound_circuits, bc_param_ind, bc_obs)
+        binding_groups, positions = broadcast.binding_groups()
+        binding_indices = [param_index for param_index, _ in binding_groups]
+        grouping = ObservableGrouping(
+            circuit.num_qubits,
+            [
+                list({label for obs in obs_indices for label in flat_observables[obs]})
+                for _, obs_indices in binding_groups
+            ],
+            self._options.abelian_grouping,
+        )
+        bound_circuits = self._bind_and_add_measurements(
+            circuit, parameter_values, binding_indices, grouping
+        )
+        return _PreprocessedData(bound_circuits, broadcast, flat_observables, grouping, positions)
 
     def _postprocess_pub(
-        self, pub: EstimatorPub, expval_map: dict, data: _PreprocessedData, shots: int
+        self, pub: EstimatorPub, counts: list[dict[str, int]], data: _PreprocessedData, shots: int
     ) -> PubResult:
         """Computes expectation values (evs) and standard errors (stds).
 
@@ -238,23 +250,22 @@ class BackendEstimatorV2(BaseEstimatorV2):
 
         Args:
             pub: The pub to postprocess.
-            expval_map: The map
+            counts: The counts of the circuits of the pub.
             data: The result data of the preprocessing.
             shots: The number of shots.
 
         Returns:
             The pub result.
         """
-        bc_param_ind = data.parameter_indices
-        bc_obs = data.observables
-        evs = np.zeros_like(bc_param_ind, dtype=float)
-        variances = np.zeros_like(bc_param_ind, dtype=float)
-        for index in np.ndindex(*bc_param_ind.shape):
-            param_index = bc_param_ind[index]
-            for pauli, coeff in bc_obs[index].items():
-                expval, variance = expval_map[param_index, pauli]
-                evs[index] += expval * coeff
-                variances[index] += variance * coeff**2
+        observables = [
+            (position, data.observables[obs])
+            for position, obs in zip(
+                data.parameter_positions, data.broadcast.observable_indices.tolist()
+            )
+        ]
+        evs, variances = data.grouping.aggregate(counts, observables)
+        evs = np.array(evs, dtype=float).reshape(data.broadcast.shape)
+        variances = np.array(variances, dtype=float).reshape(data.broadcast.shape)
         stds = np.sqrt(variances / shots)
         data_bin = DataBin(evs=evs, stds=stds, shape=evs.shape)
         return PubResult(data_bin, metadata={"target_precision": pub.precision})
@@ -263,7 +274,8 @@ class BackendEstimatorV2(BaseEstimatorV2):
         self,
         circuit: QuantumCircuit,
         parameter_values: BindingsArray,
-        param_obs_map: dict[tuple[int, ...], set[str]],
+        param_indices: list[tuple[int, ...]],
+        grouping: ObservableGrouping,
     ) -> list[QuantumCircuit]:
         """Bind the given circuit against each parameter value set, and add necessary measurements
         to each.
@@ -271,100 +283,51 @@ class BackendEstimatorV2(BaseEstimatorV2):
         Args:
             circuit: The (possibly parametric) circuit of interest.
             parameter_values: An array of parameter value sets that can be applied to the circuit.
-            param_obs_map: A mapping from locations in ``parameter_values`` to a sets of
-                Pauli terms whose expectation values are required in those locations.
+            param_indices: The locations in ``parameter_values`` of the parameter value sets of
+                ``grouping``.
+            grouping: The measurement groups of the Pauli terms whose expectation values are
+                required at each of ``param_indices``.
 
         Returns:
-            A flat list of circuits sufficient to measure all Pauli terms in the ``param_obs_map``
-            values at the corresponding ``parameter_values`` location, where requisite
-            book-keeping is stored as circuit metadata.
+            A flat list of circuits, one for each group of ``grouping`` in order, that measure
+            all the Pauli terms at the corresponding ``parameter_values`` location.
         """
+        bases = defaultdict(list)
+        for position, basis, qubits in grouping.bases():
+            bases[position].append((basis, qubits))
         circuits = []
-        for param_index, pauli_strings in param_obs_map.items():
+        for position, param_index in enumerate(param_indices):
             bound_circuit = parameter_values.bind(circuit, param_index)
-            # sort pauli_strings so that the order is deterministic
-            meas_paulis = PauliList(sorted(pauli_strings))
             new_circuits = self._create_measurement_circuits(
-                bound_circuit, meas_paulis, param_index
+                bound_circuit, bases[position], param_index
             )
             circuits.extend(new_circuits)
         return circuits
 
-    def _calc_expval_map(
-        self,
-        counts: list[Counts],
-        metadata: dict,
-    ) -> dict[tuple[tuple[int, ...], str], tuple[float, float]]:
-        """Computes the map of expectation values.
-
-        Args:
-            counts: The counts data.
-            metadata: The metadata.
-
-        Returns:
-            The map of expectation values takes a pair of an index of the bindings array and
-            a pauli string as a key and returns the expectation value of the pauli string
-            with the the pub's circuit bound against the parameter value set in the index of
-            the bindings array.
-        """
-        expval_map: dict[tuple[tuple[int, ...], str], tuple[float, float]] = {}
-        for count, meta in zip(counts, metadata):
-            orig_paulis = meta["orig_paulis"]
-            meas_paulis = meta["meas_paulis"]
-            param_index = meta["param_index"]
-            expvals, variances = _pauli_expval_with_variance(count, meas_paulis)
-            for pauli, expval, variance in zip(orig_paulis, expvals, variances):
-                expval_map[param_index, pauli.to_label()] = (expval, variance)
-        return expval_map
-
     def _create_measurement_circuits(
-        self, circuit: QuantumCircuit, observable: PauliList, param_index: tuple[int, ...]
+        self,
+        circuit: QuantumCircuit,
+        bases: list[tuple[str, list[int]]],
+        param_index: tuple[int, ...],
     ) -> list[QuantumCircuit]:
-        """Generate a list of circuits sufficient to estimate each of the given Paulis.
+        """Generate a circuit to measure each of the given bases.
 
-        Paulis are divided into qubitwise-commuting subsets to reduce the total circuit count.
         Metadata is attached to circuits in order to remember what each one measures, and
         where it belongs in the output.
 
         Args:
             circuit: The circuit of interest.
-            observable: Which Pauli terms we would like to observe.
+            bases: The label of each basis to measure, and the qubits it measures.
             param_index: Where to put the data we estimate (only passed to metadata).
 
         Returns:
-            A list of circuits sufficient to estimate each of the given Paulis.
+            A list of circuits that measure each of the given bases.
         """
         meas_circuits: list[QuantumCircuit] = []
-        if self._options.abelian_grouping:
-            for obs in observable.group_commuting(qubit_wise=True):
-                basis = Pauli((np.logical_or.reduce(obs.z), np.logical_or.reduce(obs.x)))
-                meas_circuit, indices = _measurement_circuit(circuit.num_qubits, basis)
-                paulis = PauliList.from_symplectic(
-                    obs.z[:, indices],
-                    obs.x[:, indices],
-                    obs.phase,
-                )
-                meas_circuit.metadata = {
-                    "orig_paulis": obs,
-                    "meas_paulis": paulis,
-                    "param_index": param_index,
-                }
-                meas_circuits.append(meas_circuit)
-        else:
-            for basis in observable:
-                meas_circuit, indices = _measurement_circuit(circuit.num_qubits, basis)
-                obs = PauliList(basis)
-                paulis = PauliList.from_symplectic(
-                    obs.z[:, indices],
-                    obs.x[:, indices],
-                    obs.phase,
-                )
-                meas_circuit.metadata = {
-                    "orig_paulis": obs,
-                    "meas_paulis": paulis,
-                    "param_index": param_index,
-                }
-                meas_circuits.append(meas_circuit)
+        for basis, qubits in bases:
+            meas_circuit = _measurement_circuit(circuit.num_qubits, basis, qubits)
+            meas_circuit.metadata = {"basis": basis, "param_index": param_index}
+            meas_circuits.append(meas_circuit)
 
         # unroll basis gates
         meas_circuits = self._passmanager.run(meas_circuits)
@@ -390,21 +353,19 @@ class BackendEstimatorV2(BaseEstimatorV2):
         return preprocessed_circuits
 
 
-def _measurement_circuit(num_qubits: int, pauli: Pauli):
-    # Note: if pauli is I for all qubits, this function generates a circuit to measure only
-    # the first qubit.
+def _measurement_circuit(num_qubits: int, basis: str, qubits: list[int]):
+    # Note: if the basis is I for all qubits, ``qubits`` is only the first qubit, so that the
+    # circuit measures something.
     # Although such an operator can be optimized out by interpreting it as a constant (1),
     # this optimization requires changes in various methods. So it is left as future work.
-    qubit_indices = np.arange(pauli.num_qubits)[pauli.z | pauli.x]
-    if not np.any(qubit_indices):
-        qubit_indices = [0]
     meas_circuit = QuantumCircuit(
-        QuantumRegister(num_qubits, "q"), ClassicalRegister(len(qubit_indices), f"__c_{pauli}")
+        QuantumRegister(num_qubits, "q"), ClassicalRegister(len(qubits), f"__c_{basis}")
     )
-    for clbit, i in enumerate(qubit_indices):
-        if pauli.x[i]:
-            if pauli.z[i]:
-                meas_circuit.sdg(i)
-            meas_circuit.h(i)
-        meas_circuit.measure(i, clbit)
-    return meas_circuit, qubit_indices
+    for clbit, qubit in enumerate(qubits):
+        pauli = basis[num_qubits - 1 - qubit]
+        if pauli in "XY":
+            if pauli == "Y":
+                meas_circuit.sdg(qubit)
+            meas_circuit.h(qubit)
+        meas_circuit.measure(qubit, clbit)
+    return meas_circuit
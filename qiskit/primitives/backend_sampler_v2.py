@@ -20,8 +20,8 @@ from dataclasses import dataclass
 from typing import Iterable
 
 import numpy as np
-from numpy.typing import NDArray
 
+from qiskit._accelerate.sampler_results import pack_memory
 from qiskit.circuit import QuantumCircuit
 from qiskit.primitives.backend_estimator import _run_circuits
 from qiskit.primitives.base import BaseSamplerV2
@@ -179,12 +179,10 @@ class BackendSamplerV2(BaseSamplerV2):
         results = []
         start = 0
         for pub, bound in zip(pubs, bound_circuits):
-            meas_info, max_num_bytes = _analyze_circuit(pub.circuit)
+            meas_info, _ = _analyze_circuit(pub.circuit)
             end = start + bound.size
             results.append(
-                self._postprocess_pub(
-                    result_memory[start:end], shots, bound.shape, meas_info, max_num_bytes
-                )
+                self._postprocess_pub(result_memory[start:end], shots, bound.shape, meas_info)
             )
             start = end
 
@@ -196,22 +194,12 @@ class BackendSamplerV2(BaseSamplerV2):
         shots: int,
         shape: tuple[int, ...],
         meas_info: list[_MeasureInfo],
-        max_num_bytes: int,
     ) -> SamplerPubResult:
         """Converts the memory data into an array of bit arrays with the shape of the pub."""
-        arrays = {
-            item.creg_name: np.zeros(shape + (shots, item.num_bytes), dtype=np.uint8)
-            for item in meas_info
-        }
-        memory_array = _memory_array(result_memory, max_num_bytes)
-
-        for samples, index in zip(memory_array, np.ndindex(*shape)):
-            for item in meas_info:
-                ary = _samples_to_packed_array(samples, item.num_bits, item.start)
-                arrays[item.creg_name][index] = ary
-
+        arrays = pack_memory(result_memory, [(item.start, item.num_bits) for item in meas_info])
         meas = {
-            item.creg_name: BitArray(arrays[item.creg_name], item.num_bits) for item in meas_info
+            item.creg_name: BitArray(array.reshape(shape + (shots, item.num_bytes)), item.num_bits)
+            for item, array in zip(meas_info, arrays)
         }
         return SamplerPubResult(DataBin(**meas, shape=shape), metadata={})
 
@@ -250,38 +238,3 @@ def _prepare_memory(results: list[Result]) -> list[list[str]]:
                 # no measure in a circuit
                 lst.append(["0x0"] * exp.shots)
     return lst
-
-
-def _memory_array(results: list[list[str]], num_bytes: int) -> NDArray[np.uint8]:
-    """Converts the memory data into an array in an unpacked way."""
-    lst = []
-    for memory in results:
-        if num_bytes > 0:
-            data = b"".join(int(i, 16).to_bytes(num_bytes, "big") for i in memory)
-            data = np.frombuffer(data, dtype=np.uint8).reshape(-1, num_bytes)
-        else:
-            # no measure in a circuit
-            data = np.zeros((len(memory), num_bytes), dtype=np.uint8)
-        lst.append(data)
-    ary = np.asarray(lst)
-    return np.unpackbits(ary, axis=-1, bitorder="big")
-
-
-def _samples_to_packed_array(
-    samples: NDArray[np.uint8], num_bits: int, start: int
-) -> NDArray[np.uint8]:
-    """Converts an unpacked array of the memory data into a packed array."""
-    # samples of `Backend.run(memory=True)` will be the order of
-    # clbit_last, ..., clbit_1, clbit_0
-    # place samples in the order of clbit_start+num_bits-1, ..., clbit_start+1, clbit_start
-    if start == 0:
-        ary = samples[:, -start - num_bits :]
-    else:
-        ary = samples[:, -start - num_bits : -start]
-    # pad 0 in the left to align the number to be mod 8
-    # since np.packbits(bitorder='big') pads 0 to the right.
-    pad_size = -num_bits % 8
-    ary = np.pad(ary, ((0, 0), (pad_size, 0)), constant_values=0)
-    # pack bits in big endian order
-    ary = np.packbits(ary, axis=-1, bitorder="big")
-    return ary
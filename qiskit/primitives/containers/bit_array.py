@@ -24,6 +24,7 @@ from typing import Callable, Iterable, Literal, Mapping, Sequence
 import numpy as np
 from numpy.typing import NDArray
 
+from qiskit._accelerate.sampler_results import bitstring_counts
 from qiskit.exceptions import QiskitError
 from qiskit.result import Counts, sampled_expectation_value
 
@@ -313,6 +314,9 @@ class BitArray(ShapedMixin):
         Returns:
             A dictionary mapping bitstrings to the number of occurrences of that bitstring.
         """
+        arr = self._array.reshape(-1, self._array.shape[-1]) if loc is None else self._array[loc]
+        if arr.ndim == 2:
+            return bitstring_counts(arr, self.num_bits)
         mask = 2**self.num_bits - 1
         converter = partial(self._bytes_to_bitstring, num_bits=self.num_bits, mask=mask)
         return self._get_counts(loc=loc, converter=converter)
@@ -24,6 +24,7 @@ from typing import Tuple, Union
 import numpy as np
 
 from qiskit import QuantumCircuit
+from qiskit._accelerate.pub_broadcast import broadcast_shapes
 
 from .bindings_array import BindingsArray, BindingsArrayLike
 from .observables_array import ObservablesArray, ObservablesArrayLike
@@ -74,7 +75,7 @@ class EstimatorPub(ShapedMixin):
         # for ShapedMixin
         try:
             # _shape has to be defined to properly be Shaped, so we can't put it in validation
-            self._shape = np.broadcast_shapes(self.observables.shape, self.parameter_values.shape)
+            self._shape = broadcast_shapes([self.observables.shape, self.parameter_values.shape])
         except ValueError as ex:
             raise ValueError(
                 f"The observables shape {self.observables.shape} and the "
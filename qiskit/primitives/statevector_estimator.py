@@ -19,6 +19,7 @@ from collections.abc import Iterable
 
 import numpy as np
 
+from qiskit._accelerate.pub_broadcast import PubBroadcast
 from qiskit.quantum_info import SparsePauliOp, Statevector
 
 from .base import BaseEstimatorV2
@@ -144,13 +145,16 @@ class StatevectorEstimator(BaseEstimatorV2):
         observables = pub.observables
         parameter_values = pub.parameter_values
         precision = pub.precision
-        bound_circuits = parameter_values.bind_all(circuit)
-        bc_circuits, bc_obs = np.broadcast_arrays(bound_circuits, observables)
-        evs = np.zeros_like(bc_circuits, dtype=np.float64)
-        stds = np.zeros_like(bc_circuits, dtype=np.float64)
-        for index in np.ndindex(*bc_circuits.shape):
-            bound_circuit = bc_circuits[index]
-            observable = bc_obs[index]
+        broadcast = PubBroadcast(parameter_values.shape, observables.shape)
+        bound_circuits = parameter_values.bind_all(circuit).ravel()
+        flat_observables = observables.ravel()
+        evs = np.zeros(broadcast.size, dtype=np.float64)
+        stds = np.zeros(broadcast.size, dtype=np.float64)
+        for index, (param_index, obs_index) in enumerate(
+            zip(broadcast.parameter_indices.tolist(), broadcast.observable_indices.tolist())
+        ):
+            bound_circuit = bound_circuits[param_index]
+            observable = flat_observables[obs_index]
             final_state = Statevector(bound_circuit_to_instruction(bound_circuit))
             paulis, coeffs = zip(*observable.items())
             obs = SparsePauliOp(paulis, coeffs)  # TODO: support non Pauli operators
@@ -161,5 +165,7 @@ class StatevectorEstimator(BaseEstimatorV2):
                 expectation_value = rng.normal(expectation_value, precision)
             evs[index] = expectation_value
 
+        evs = evs.reshape(broadcast.shape)
+        stds = stds.reshape(broadcast.shape)
         data = DataBin(evs=evs, stds=stds, shape=evs.shape)
         return PubResult(data, metadata={"precision": precision})
@@ -21,6 +21,7 @@ from numbers import Number
 import numpy as np
 
 from qiskit import circuit
+from qiskit._accelerate import density_matrix as density_matrix_rs
 from qiskit.circuit.quantumcircuit import QuantumCircuit
 from qiskit.circuit.instruction import Instruction
 from qiskit.exceptions import QiskitError
@@ -188,6 +189,28 @@ class Kraus(QuantumChannel):
         return is_identity_matrix(accum, rtol=rtol, atol=atol)
 
     def _evolve(self, state, qargs=None):
+        # Prevent cyclic imports by importing DensityMatrix here
+        # pylint: disable=cyclic-import
+        from qiskit.quantum_info.states.densitymatrix import DensityMatrix
+
+        if not isinstance(state, DensityMatrix):
+            state = DensityMatrix(state)
+        rust_qargs = range(state.num_qubits or 0) if qargs is None else qargs
+        if (
+            state.num_qubits is not None
+            and self.num_qubits is not None
+            and len(rust_qargs) == self.num_qubits
+        ):
+            # Qubit channels are applied by the Rust kernels one Kraus operator at a time, without
+            # converting to a superoperator.
+            kraus_l, kraus_r = self._data
+            data = density_matrix_rs.evolve_kraus(
+                state.data,
+                [np.asarray(k, dtype=complex) for k in kraus_l],
+                list(rust_qargs),
+                None if kraus_r is None else [np.asarray(k, dtype=complex) for k in kraus_r],
+            )
+            return DensityMatrix(data, dims=state.dims())
         return SuperOp(self)._evolve(state, qargs)
 
     # ---------------------------------------------------------------------
@@ -22,6 +22,7 @@ from typing import TYPE_CHECKING
 import numpy as np
 
 from qiskit import _numpy_compat
+from qiskit._accelerate import density_matrix as density_matrix_rs
 from qiskit.circuit.instruction import Instruction
 from qiskit.circuit.quantumcircuit import QuantumCircuit
 from qiskit.exceptions import QiskitError
@@ -260,6 +261,18 @@ class SuperOp(QuantumChannel):
         if not isinstance(state, DensityMatrix):
             state = DensityMatrix(state)
 
+        rust_qargs = range(state.num_qubits or 0) if qargs is None else qargs
+        if (
+            state.num_qubits is not None
+            and self.num_qubits is not None
+            and len(rust_qargs) == self.num_qubits
+        ):
+            # Qubit channels are applied by the Rust kernels without forming a tensor.
+            data = density_matrix_rs.evolve_superop(
+                state.data, np.asarray(self.data, dtype=complex), list(rust_qargs)
+            )
+            return DensityMatrix(data, dims=state.dims())
+
         if qargs is None:
             # Evolution on full matrix
             if state._op_shape.shape[0] != self._op_shape.shape[1]:
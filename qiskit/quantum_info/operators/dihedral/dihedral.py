@@ -17,6 +17,7 @@ from __future__ import annotations
 import itertools
 import numpy as np
 
+from qiskit._accelerate.cnotdihedral import CNOTDihedralElement
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators.base_operator import BaseOperator
 from qiskit.quantum_info.operators.operator import Operator
@@ -184,60 +185,27 @@ class CNOTDihedral(BaseOperator, AdjointMixin):
         """Number of classical bits."""
         return 0
 
-    def _z2matmul(self, left, right):
-        """Compute product of two n x n z2 matrices."""
-        prod = np.mod(np.dot(left, right), 2)
-        return prod
-
-    def _z2matvecmul(self, mat, vec):
-        """Compute mat*vec of n x n z2 matrix and vector."""
-        prod = np.mod(np.dot(mat, vec), 2)
-        return prod
-
-    def _dot(self, other):
-        """Left multiplication self * other."""
-        if self.num_qubits != other.num_qubits:
-            raise QiskitError("Multiplication on different number of qubits.")
-        result = CNOTDihedral(num_qubits=self.num_qubits)
-        result.shift = [
-            (x[0] + x[1]) % 2 for x in zip(self._z2matvecmul(self.linear, other.shift), self.shift)
-        ]
-        result.linear = self._z2matmul(self.linear, other.linear)
-        # Compute x' = B1*x + c1 using the p_j identity
-        new_vars = []
-        for i in range(self.num_qubits):
-            support = np.arange(self.num_qubits)[np.nonzero(other.linear[i])]
-            poly = SpecialPolynomial(self.num_qubits)
-            poly.set_pj(support)
-            if other.shift[i] == 1:
-                poly = -1 * poly
-                poly.weight_0 = (poly.weight_0 + 1) % 8
-            new_vars.append(poly)
-        # p' = p1 + p2(x')
-        result.poly = other.poly + self.poly.evaluate(new_vars)
-        return result
+    def _to_rust(self):
+        """Return the Rust-space representation of this element."""
+        poly = self.poly
+        weights = np.concatenate(
+            ([poly.weight_0], poly.weight_1, poly.weight_2, poly.weight_3)
+        ).astype(np.uint8)
+        return CNOTDihedralElement(
+            np.asarray(self.linear, dtype=bool), np.asarray(self.shift, dtype=bool), weights % 8
+        )
 
-    def _compose(self, other):
-        """Right multiplication other * self."""
-        if self.num_qubits != other.num_qubits:
-            raise QiskitError("Multiplication on different number of qubits.")
-        result = CNOTDihedral(num_qubits=self.num_qubits)
-        result.shift = [
-            (x[0] + x[1]) % 2 for x in zip(self._z2matvecmul(other.linear, self.shift), other.shift)
-        ]
-        result.linear = self._z2matmul(other.linear, self.linear)
-        # Compute x' = B1*x + c1 using the p_j identity
-        new_vars = []
-        for i in range(self.num_qubits):
-            support = np.arange(other.num_qubits)[np.nonzero(self.linear[i])]
-            poly = SpecialPolynomial(self.num_qubits)
-            poly.set_pj(support)
-            if self.shift[i] == 1:
-                poly = -1 * poly
-                poly.weight_0 = (poly.weight_0 + 1) % 8
-            new_vars.append(poly)
-        # p' = p1 + p2(x')
-        result.poly = self.poly + other.poly.evaluate(new_vars)
+    @classmethod
+    def _from_rust(cls, elem):
+        """Build an element from its Rust-space representation."""
+        result = cls(num_qubits=elem.num_qubits, validate=False)
+        result.linear = elem.linear.astype(np.int8)
+        result.shift = elem.shift.astype(np.int8)
+        weight_0, weight_1, weight_2, weight_3 = elem.poly
+        result.poly.weight_0 = weight_0
+        result.poly.weight_1 = weight_1.astype(np.int8)
+        result.poly.weight_2 = weight_2.astype(np.int8)
+        result.poly.weight_3 = weight_3.astype(np.int8)
         return result
 
     def __eq__(self, other):
@@ -378,12 +346,9 @@ class CNOTDihedral(BaseOperator, AdjointMixin):
             raise NotImplementedError("compose method does not support qargs.")
         if self.num_qubits != other.num_qubits:
             raise QiskitError("Incompatible dimension for composition")
-        if front:
-            other = self._dot(other)
-        else:
-            other = self._compose(other)
-        other.poly.weight_0 = 0  # set global phase
-        return other
+        # The substitution of the affine part into the phase polynomial is done in Rust, and the
+        # result is up to global phase.
+        return self._from_rust(self._to_rust().compose(other._to_rust(), front=front))
 
     def _tensor(self, other, reverse=False):
         """Returns the tensor product operator."""
@@ -18,14 +18,13 @@ from __future__ import annotations
 import logging
 import numpy as np
 
+from qiskit._accelerate import fidelity as fidelity_rs
 from qiskit.exceptions import QiskitError, MissingOptionalLibraryError
 from qiskit.circuit.gate import Gate
 from qiskit.quantum_info.operators.base_operator import BaseOperator
 from qiskit.quantum_info.operators.operator import Operator
 from qiskit.quantum_info.operators.channel.quantum_channel import QuantumChannel
 from qiskit.quantum_info.operators.channel import Choi, SuperOp
-from qiskit.quantum_info.states.densitymatrix import DensityMatrix
-from qiskit.quantum_info.states.measures import state_fidelity
 from qiskit.utils import optionals as _optionals
 
 logger = logging.getLogger(__name__)
@@ -137,9 +136,11 @@ def process_fidelity(
     # For comparing two non-unitary channels we compute the state fidelity of
     # the normalized Choi-matrices. This is equivalent to the previous definition
     # when the target is a unitary channel.
-    state1 = DensityMatrix(Choi(channel).data / input_dim)
-    state2 = DensityMatrix(Choi(target).data / input_dim)
-    return state_fidelity(state1, state2, validate=False)
+    return fidelity_rs.process_fidelity(
+        np.asarray(Choi(channel).data, dtype=complex),
+        np.asarray(Choi(target).data, dtype=complex),
+        input_dim,
+    )
 
 
 def average_gate_fidelity(
@@ -201,7 +202,7 @@ def average_gate_fidelity(
             ) from ex
     dim, _ = channel.dim
     f_pro = process_fidelity(channel, target=target, require_cp=require_cp, require_tp=require_tp)
-    return (dim * f_pro + 1) / (dim + 1)
+    return fidelity_rs.average_gate_fidelity(f_pro, dim)
 
 
 def gate_error(
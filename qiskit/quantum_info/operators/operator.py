@@ -24,6 +24,7 @@ from typing import TYPE_CHECKING
 import numpy as np
 
 from qiskit import _numpy_compat
+from qiskit._accelerate import operator as operator_rs
 from qiskit.circuit.instruction import Instruction
 from qiskit.circuit.library.standard_gates import HGate, IGate, SGate, TGate, XGate, YGate, ZGate
 from qiskit.circuit.operation import Operation
@@ -32,7 +33,7 @@ from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators.base_operator import BaseOperator
 from qiskit.quantum_info.operators.linear_op import LinearOp
 from qiskit.quantum_info.operators.mixins import generate_apidocs
-from qiskit.quantum_info.operators.predicates import is_unitary_matrix, matrix_equal
+from qiskit.quantum_info.operators.predicates import is_unitary_matrix
 
 if TYPE_CHECKING:
     from qiskit.transpiler.layout import Layout
@@ -485,7 +486,14 @@ class Operator(LinearOp):
 
         # Full composition of operators
         if qargs is None:
-            if front:
+            if _is_complex128(self._data, other.data):
+                # Composition self * other if front else other * self
+                data = operator_rs.compose(
+                    np.asarray(self._data, dtype=complex),
+                    np.asarray(other.data, dtype=complex),
+                    front,
+                )
+            elif front:
                 # Composition self * other
                 data = np.dot(self._data, other.data)
             else:
@@ -538,7 +546,10 @@ class Operator(LinearOp):
             raise QiskitError("Can only power with input_dims = output_dims.")
         ret = _copy.copy(self)
         if isinstance(n, int):
-            ret._data = np.linalg.matrix_power(self.data, n)
+            if _is_complex128(self.data):
+                ret._data = operator_rs.power(np.asarray(self.data, dtype=complex), n)
+            else:
+                ret._data = np.linalg.matrix_power(self.data, n)
         else:
             import scipy.linalg
 
@@ -565,7 +576,12 @@ class Operator(LinearOp):
     def _tensor(cls, a, b):
         ret = _copy.copy(a)
         ret._op_shape = a._op_shape.tensor(b._op_shape)
-        ret._data = np.kron(a.data, b.data)
+        if _is_complex128(a.data, b.data):
+            ret._data = operator_rs.tensor(
+                np.asarray(a.data, dtype=complex), np.asarray(b.data, dtype=complex)
+            )
+        else:
+            ret._data = np.kron(a.data, b.data)
         return ret
 
     def _add(self, other, qargs=None):
@@ -642,7 +658,13 @@ class Operator(LinearOp):
             atol = self.atol
         if rtol is None:
             rtol = self.rtol
-        return matrix_equal(self.data, other.data, ignore_phase=True, rtol=rtol, atol=atol)
+        return operator_rs.matrix_equal(
+            np.asarray(self.data, dtype=complex),
+            np.asarray(other.data, dtype=complex),
+            ignore_phase=True,
+            rtol=rtol,
+            atol=atol,
+        )
 
     def reverse_qargs(self) -> Operator:
         r"""Return an Operator with reversed subsystem ordering.
@@ -712,6 +734,16 @@ class Operator(LinearOp):
         if hasattr(instruction, "__array__"):
             return Operator(np.array(instruction, dtype=complex))
 
+        if isinstance(instruction, QuantumCircuit):
+            # Circuits made of matrix-defined gates are multiplied out entirely in Rust.
+            try:
+                global_phase = float(instruction.global_phase)
+            except TypeError:
+                global_phase = None
+            if global_phase is not None:
+                data = operator_rs.from_circuit_data(instruction._data, global_phase)
+                if data is not None:
+                    return Operator(data)
         dimension = 2**instruction.num_qubits
         op = Operator(np.eye(dimension))
         # Convert circuit to an instruction
@@ -798,5 +830,11 @@ class Operator(LinearOp):
                 self._append_instruction(instruction.operation, qargs=new_qargs)
 
 
+def _is_complex128(*arrays):
+    """Whether the result of an operation on ``arrays`` is of ``complex128`` type, which the Rust
+    kernels work in.  The results of other types are computed with Numpy, so their type is kept."""
+    return np.result_type(*arrays) == np.complex128
+
+
 # Update docstrings for API docs
 generate_apidocs(Operator)
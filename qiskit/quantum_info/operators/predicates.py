@@ -17,6 +17,8 @@ Predicates for operators.
 from __future__ import annotations
 import numpy as np
 
+from qiskit._accelerate import operator as operator_rs
+
 ATOL_DEFAULT = 1e-8
 RTOL_DEFAULT = 1e-5
 
@@ -123,10 +125,10 @@ def is_hermitian_matrix(mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT):
         atol = ATOL_DEFAULT
     if rtol is None:
         rtol = RTOL_DEFAULT
-    mat = np.array(mat)
+    mat = np.asarray(mat)
     if mat.ndim != 2:
         return False
-    return np.allclose(mat, np.conj(mat.T), rtol=rtol, atol=atol)
+    return operator_rs.is_hermitian(np.asarray(mat, dtype=complex), rtol=rtol, atol=atol)
 
 
 def is_positive_semidefinite_matrix(mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT):
@@ -151,23 +153,25 @@ def is_identity_matrix(mat, ignore_phase=False, rtol=RTOL_DEFAULT, atol=ATOL_DEF
         atol = ATOL_DEFAULT
     if rtol is None:
         rtol = RTOL_DEFAULT
-    mat = np.array(mat)
+    mat = np.asarray(mat)
     if mat.ndim != 2:
         return False
-    if ignore_phase:
-        # If the matrix is equal to an identity up to a phase, we can
-        # remove the phase by multiplying each entry by the complex
-        # conjugate of the phase of the [0, 0] entry.
-        theta = np.angle(mat[0, 0])
-        mat = np.exp(-1j * theta) * mat
-    # Check if square identity
-    iden = np.eye(len(mat))
-    return np.allclose(mat, iden, rtol=rtol, atol=atol)
+    # If the matrix is equal to an identity up to a phase, the phase is removed by multiplying each
+    # entry by the complex conjugate of the phase of the [0, 0] entry.
+    return operator_rs.is_identity(
+        np.asarray(mat, dtype=complex), ignore_phase=ignore_phase, rtol=rtol, atol=atol
+    )
 
 
 def is_unitary_matrix(mat, rtol=RTOL_DEFAULT, atol=ATOL_DEFAULT):
     """Test if an array is a unitary matrix."""
-    mat = np.array(mat)
+    if atol is None:
+        atol = ATOL_DEFAULT
+    if rtol is None:
+        rtol = RTOL_DEFAULT
+    mat = np.asarray(mat)
+    if mat.ndim == 2 and mat.shape[0] == mat.shape[1]:
+        return operator_rs.is_unitary(np.asarray(mat, dtype=complex), rtol=rtol, atol=atol)
     # Compute A^dagger.A and see if it is identity matrix
     mat = np.conj(mat.T).dot(mat)
     return is_identity_matrix(mat, ignore_phase=False, rtol=rtol, atol=atol)
@@ -22,6 +22,7 @@ from typing import Literal
 
 import numpy as np
 
+from qiskit._accelerate.clifford import CliffordTableau
 from qiskit.circuit import Instruction, QuantumCircuit
 from qiskit.circuit.library.standard_gates import HGate, IGate, SGate, XGate, YGate, ZGate
 from qiskit.circuit.operation import Operation
@@ -135,7 +136,6 @@ class Clifford(BaseOperator, AdjointMixin, Operation):
            `arXiv:quant-ph/0406196 <https://arxiv.org/abs/quant-ph/0406196>`_
     """
 
-    _COMPOSE_PHASE_LOOKUP = None
     _COMPOSE_1Q_LOOKUP = None
 
     def __array__(self, dtype=None, copy=None):
@@ -451,31 +451,12 @@ class Clifford(BaseOperator, AdjointMixin, Operation):
 
     @classmethod
     def _compose_general(cls, first, second):
-        # Correcting for phase due to Pauli multiplication. Start with factors of -i from XZ = -iY
-        # on individual qubits, and then handle multiplication between each qubitwise pair.
-        ifacts = np.sum(second.x & second.z, axis=1, dtype=int)
-
-        x1, z1 = first.x.astype(np.uint8), first.z.astype(np.uint8)
-        lookup = cls._compose_lookup()
-
-        # The loop is over 2*n_qubits entries, and the entire loop is cubic in the number of qubits.
-        for k, row2 in enumerate(second.symplectic_matrix):
-            x1_select = x1[row2]
-            z1_select = z1[row2]
-            x1_accum = np.logical_xor.accumulate(x1_select, axis=0).astype(np.uint8)
-            z1_accum = np.logical_xor.accumulate(z1_select, axis=0).astype(np.uint8)
-            indexer = (x1_select[1:], z1_select[1:], x1_accum[:-1], z1_accum[:-1])
-            ifacts[k] += np.sum(lookup[indexer])
-        p = np.mod(ifacts, 4) // 2
-
-        phase = (
-            (np.matmul(second.symplectic_matrix, first.phase, dtype=int) + second.phase + p) % 2
-        ).astype(bool)
-        data = cls._stack_table_phase(
-            (np.matmul(second.symplectic_matrix, first.symplectic_matrix, dtype=int) % 2).astype(
-                bool
-            ),
-            phase,
+        # The phase correction for the Pauli products makes this cubic in the number of qubits, so
+        # the tableau product is done in Rust.
+        data = (
+            CliffordTableau(first.tableau)
+            .compose(CliffordTableau(second.tableau), front=True)
+            .tableau
         )
         return Clifford(data, validate=False, copy=False)
 
@@ -507,21 +488,6 @@ class Clifford(BaseOperator, AdjointMixin, Operation):
             }
         return cls._COMPOSE_1Q_LOOKUP[cls._hash(first), cls._hash(second)].copy()
 
-    @classmethod
-    def _compose_lookup(
-        cls,
-    ):
-        if cls._COMPOSE_PHASE_LOOKUP is None:
-            # A lookup table for calculating phases.  The indices are
-            #     current_x, current_z, running_x_count, running_z_count
-            # where all counts taken modulo 2.
-            lookup = np.zeros((2, 2, 2, 2), dtype=int)
-            lookup[0, 1, 1, 0] = lookup[1, 0, 1, 1] = lookup[1, 1, 0, 1] = -1
-            lookup[0, 1, 1, 1] = lookup[1, 0, 0, 1] = lookup[1, 1, 1, 0] = 1
-            lookup.setflags(write=False)
-            cls._COMPOSE_PHASE_LOOKUP = lookup
-        return cls._COMPOSE_PHASE_LOOKUP
-
     # ---------------------------------------------------------------------
     # Representation conversions
     # ---------------------------------------------------------------------
@@ -874,14 +840,7 @@ class Clifford(BaseOperator, AdjointMixin, Operation):
         ret = clifford.copy()
         if method in ["A", "T"]:
             # Apply inverse
-            # Update table
-            tmp = ret.destab_x.copy()
-            ret.destab_x = ret.stab_z.T
-            ret.destab_z = ret.destab_z.T
-            ret.stab_x = ret.stab_x.T
-            ret.stab_z = tmp.T
-            # Update phase
-            ret.phase ^= clifford.dot(ret).phase
+            ret.tableau = CliffordTableau(ret.tableau).adjoint().tableau
         if method in ["C", "T"]:
             # Apply conjugate
             ret.phase ^= np.mod(_count_y(ret.x, ret.z), 2).astype(bool)
@@ -17,6 +17,7 @@ from __future__ import annotations
 
 import numpy as np
 
+from qiskit._accelerate.clifford import CliffordTableau
 from qiskit.circuit import Barrier, Delay, Gate
 from qiskit.circuit.exceptions import CircuitError
 from qiskit.exceptions import QiskitError
@@ -39,6 +40,9 @@ def _append_circuit(clifford, circuit, qargs=None):
     if qargs is None:
         qargs = list(range(clifford.num_qubits))
 
+    # Runs of basis gates are applied to a Rust-space tableau, which is only converted back when
+    # we reach an operation that needs the Python-space Clifford.
+    tableau = None
     for instruction in circuit:
         if instruction.clbits:
             raise QiskitError(
@@ -46,10 +50,32 @@ def _append_circuit(clifford, circuit, qargs=None):
             )
         # Get the integer position of the flat register
         new_qubits = [qargs[circuit.find_bit(bit).index] for bit in instruction.qubits]
-        clifford = _append_operation(clifford, instruction.operation, new_qubits)
+        operation = instruction.operation
+        if _is_basis_gate(operation, new_qubits):
+            if tableau is None:
+                tableau = CliffordTableau(clifford.tableau)
+            tableau.append_gate(operation.name, new_qubits)
+            continue
+        if tableau is not None:
+            clifford.tableau = tableau.tableau
+            tableau = None
+        clifford = _append_operation(clifford, operation, new_qubits)
+    if tableau is not None:
+        clifford.tableau = tableau.tableau
     return clifford
 
 
+def _is_basis_gate(operation, qargs):
+    """Whether ``operation`` is a basis Clifford gate that can be appended on ``qargs`` directly."""
+    if isinstance(operation, (Barrier, Delay)) or getattr(operation, "condition", None) is not None:
+        return False
+    if operation.name in _BASIS_1Q:
+        return len(qargs) == 1
+    if operation.name in _BASIS_2Q:
+        return len(qargs) == 2
+    return False
+
+
 def _append_operation(clifford, operation, qargs=None):
     """Update Clifford inplace by applying a Clifford operation.
 
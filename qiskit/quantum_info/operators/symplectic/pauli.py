@@ -15,7 +15,6 @@ N-qubit Pauli Operator Class
 
 from __future__ import annotations
 
-import re
 from typing import Literal, TYPE_CHECKING
 
 import numpy as np
@@ -25,6 +24,8 @@ from qiskit.circuit.barrier import Barrier
 from qiskit.circuit.delay import Delay
 from qiskit.circuit.library.generalized_gates import PauliGate
 from qiskit.circuit.library.standard_gates import IGate, XGate, YGate, ZGate
+from qiskit._accelerate.pauli import Pauli as Pauli_rs
+from qiskit._accelerate.sparse_pauli_op import apply_layout_to_paulis
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators.mixins import generate_apidocs
 from qiskit.quantum_info.operators.scalar_op import ScalarOp
@@ -156,9 +157,6 @@ class Pauli(BasePauli):
     # Set the max Pauli string size before truncation
     __truncate__ = 50
 
-    _VALID_LABEL_PATTERN = re.compile(r"(?P<coeff>[+-]?1?[ij]?)(?P<pauli>[IXYZ]*)")
-    _CANONICAL_PHASE_LABEL = {"": 0, "-i": 1, "-": 2, "i": 3}
-
     def __init__(self, data: str | tuple | Pauli | ScalarOp | QuantumCircuit | None = None):
         """Initialize the Pauli.
 
@@ -617,19 +615,10 @@ class Pauli(BasePauli):
         Raises:
             QiskitError: if Pauli string is not valid.
         """
-        match_ = Pauli._VALID_LABEL_PATTERN.fullmatch(label)
-        if match_ is None:
-            raise QiskitError(f'Pauli string label "{label}" is not valid.')
-        phase = Pauli._CANONICAL_PHASE_LABEL[
-            (match_["coeff"] or "").replace("1", "").replace("+", "").replace("j", "i")
-        ]
-
-        # Convert to Symplectic representation
-        pauli_bytes = np.frombuffer(match_["pauli"].encode("ascii"), dtype=np.uint8)[::-1]
-        ys = pauli_bytes == ord("Y")
-        base_x = np.logical_or(pauli_bytes == ord("X"), ys).reshape(1, -1)
-        base_z = np.logical_or(pauli_bytes == ord("Z"), ys).reshape(1, -1)
-        base_phase = np.array([(phase + np.count_nonzero(ys)) % 4], dtype=int)
+        pauli = Pauli_rs.from_label(label)
+        base_z = pauli.z.reshape(1, -1)
+        base_x = pauli.x.reshape(1, -1)
+        base_phase = np.array([pauli.zx_phase], dtype=int)
         return base_z, base_x, base_phase
 
     @classmethod
@@ -741,8 +730,8 @@ class Pauli(BasePauli):
                 raise QiskitError("Provided layout contains indices outside the number of qubits.")
             if len(set(layout)) != len(layout):
                 raise QiskitError("Provided layout contains duplicate indices.")
-        new_op = type(self)("I" * n_qubits)
-        return new_op.compose(self, qargs=layout)
+        z, x = apply_layout_to_paulis(self._z, self._x, layout, n_qubits)
+        return type(self)((z[0], x[0], self.phase))
 
 
 # Update docstrings for API docs
@@ -25,9 +25,12 @@ import rustworkx as rx
 
 from qiskit._accelerate.sparse_pauli_op import (
     ZXPaulis,
+    apply_layout_to_paulis,
     decompose_dense,
     to_matrix_dense,
+    to_matrix_dense_into,
     to_matrix_sparse,
+    to_matrix_sparse_into,
     unordered_unique,
 )
 from qiskit.circuit.parameter import Parameter
@@ -931,7 +934,7 @@ class SparsePauliOp(LinearOp):
             return labels
         return labels.tolist()
 
-    def to_matrix(self, sparse: bool = False, force_serial: bool = False) -> np.ndarray:
+    def to_matrix(self, sparse: bool = False, force_serial: bool = False, out=None) -> np.ndarray:
         """Convert to a dense or sparse matrix.
 
         Args:
@@ -941,14 +944,33 @@ class SparsePauliOp(LinearOp):
                 the `Qiskit threading-control environment variables
                 <https://docs.quantum.ibm.com/start/configure-qiskit-local#environment-variables>`__.
                 By default, this will use threaded parallelism over the available CPUs.
+            out: buffers to write the matrix into instead of allocating new ones, so that they can
+                be reused when converting many operators of the same size.  If ``sparse=False``,
+                this is a C-contiguous ``complex128`` array of the shape of the matrix.  If
+                ``sparse=True``, it is a tuple ``(data, indices, indptr)`` of one-dimensional
+                arrays, where ``data`` is ``complex128``, ``indices`` and ``indptr`` are both
+                ``int32`` or both ``int64``, ``indptr`` has one more entry than the matrix has rows,
+                and ``data`` and ``indices`` have room for all the nonzero entries.  Writing into
+                sparse buffers is always serial.
 
         Returns:
-            array: A dense matrix if `sparse=False`.
-            csr_matrix: A sparse matrix in CSR format if `sparse=True`.
+            array: A dense matrix if `sparse=False`, which is ``out`` if it is given.
+            csr_matrix: A sparse matrix in CSR format if `sparse=True`, which shares the memory of
+            ``out`` if it is given.
+
+        Raises:
+            QiskitError: if ``out`` is given for a sparse matrix with object coefficients.
         """
         if self.coeffs.dtype == object:
             # Fallback to slow Python-space method.
-            return sum(self.matrix_iter(sparse=sparse))
+            if out is None:
+                return sum(self.matrix_iter(sparse=sparse))
+            if sparse:
+                raise QiskitError(
+                    "cannot write a sparse matrix with object coefficients into 'out'"
+                )
+            out[...] = sum(self.matrix_iter())
+            return out
 
         pauli_list = self.paulis
         zx = ZXPaulis(
@@ -960,9 +982,18 @@ class SparsePauliOp(LinearOp):
         if sparse:
             from scipy.sparse import csr_matrix
 
-            data, indices, indptr = to_matrix_sparse(zx, force_serial=force_serial)
             side = 1 << self.num_qubits
+            if out is not None:
+                data, indices, indptr = out
+                nnz = to_matrix_sparse_into(zx, data, indices, indptr)
+                return csr_matrix(
+                    (data[:nnz], indices[:nnz], indptr), shape=(side, side), copy=False
+                )
+            data, indices, indptr = to_matrix_sparse(zx, force_serial=force_serial)
             return csr_matrix((data, indices, indptr), shape=(side, side))
+        if out is not None:
+            to_matrix_dense_into(zx, out, force_serial=force_serial)
+            return out
         return to_matrix_dense(zx, force_serial=force_serial)
 
     def to_operator(self) -> Operator:
@@ -1165,10 +1196,8 @@ class SparsePauliOp(LinearOp):
                 raise QiskitError("Provided layout contains indices outside the number of qubits.")
             if len(set(layout)) != len(layout):
                 raise QiskitError("Provided layout contains duplicate indices.")
-        if self.num_qubits == 0:
-            return type(self)(["I" * n_qubits] * self.size, self.coeffs)
-        new_op = type(self)("I" * n_qubits)
-        return new_op.compose(self, qargs=layout)
+        z, x = apply_layout_to_paulis(self.paulis.z, self.paulis.x, layout, n_qubits)
+        return type(self)(PauliList.from_symplectic(z, x, self.paulis.phase), self.coeffs)
 
 
 # Update docstrings for API docs
@@ -34,6 +34,7 @@ from qiskit.quantum_info.operators.predicates import is_positive_semidefinite_ma
 from qiskit.quantum_info.operators.channel.quantum_channel import QuantumChannel
 from qiskit.quantum_info.operators.channel.superop import SuperOp
 
+from qiskit._accelerate import density_matrix as density_matrix_rs
 from qiskit._accelerate.pauli_expval import density_expval_pauli_no_x, density_expval_pauli_with_x
 from qiskit.quantum_info.states.statevector import Statevector
 
@@ -617,8 +618,12 @@ class DensityMatrix(QuantumState, TolerancesMixin):
             QiskitError: if the instruction contains invalid instructions for
                          density matrix simulation.
         """
-        # Convert circuit to an instruction
         if isinstance(instruction, QuantumCircuit):
+            # Circuits made of matrix-defined gates are simulated entirely in Rust.
+            data = density_matrix_rs.from_circuit_data(instruction._data)
+            if data is not None:
+                return DensityMatrix(data, dims=instruction.num_qubits * (2,))
+            # Convert circuit to an instruction
             instruction = instruction.to_instruction()
         # Initialize an the statevector in the all |0> state
         num_qubits = instruction.num_qubits
@@ -716,6 +721,15 @@ class DensityMatrix(QuantumState, TolerancesMixin):
         new_shape._num_qargs_r = new_shape._num_qargs_l
 
         ret = _copy.copy(self)
+        if self.num_qubits is not None and other.num_qubits is not None:
+            # Qubit operators are applied by the Rust kernels without forming a tensor.
+            if qargs is None:
+                qargs = range(self.num_qubits)
+            ret._data = density_matrix_rs.evolve_operator(
+                self.data, np.asarray(other.data, dtype=complex), list(qargs)
+            )
+            ret._op_shape = new_shape
+            return ret
         if qargs is None:
             # Evolution on full matrix
             op_mat = other.data
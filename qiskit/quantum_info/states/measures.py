@@ -15,6 +15,8 @@ Quantum information measures, metrics, and related functions for states.
 
 from __future__ import annotations
 import numpy as np
+from qiskit._accelerate import entanglement as entanglement_rs
+from qiskit._accelerate import fidelity as fidelity_rs
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.states.statevector import Statevector
 from qiskit.quantum_info.states.densitymatrix import DensityMatrix
@@ -22,7 +24,6 @@ from qiskit.quantum_info.states.utils import (
     partial_trace,
     shannon_entropy,
     _format_state,
-    _funm_svd,
 )
 
 
@@ -58,25 +59,19 @@ def state_fidelity(
     state2 = _format_state(state2, validate=validate)
 
     # Get underlying numpy arrays
-    arr1 = state1.data
-    arr2 = state2.data
+    arr1 = np.asarray(state1.data, dtype=complex)
+    arr2 = np.asarray(state2.data, dtype=complex)
     if isinstance(state1, Statevector):
         if isinstance(state2, Statevector):
             # Fidelity of two Statevectors
-            fid = np.abs(arr2.conj().dot(arr1)) ** 2
-        else:
-            # Fidelity of Statevector(1) and DensityMatrix(2)
-            fid = arr1.conj().dot(arr2).dot(arr1)
-    elif isinstance(state2, Statevector):
+            return fidelity_rs.statevector_fidelity(arr1, arr2)
+        # Fidelity of Statevector(1) and DensityMatrix(2)
+        return fidelity_rs.statevector_density_matrix_fidelity(arr1, arr2)
+    if isinstance(state2, Statevector):
         # Fidelity of Statevector(2) and DensityMatrix(1)
-        fid = arr2.conj().dot(arr1).dot(arr2)
-    else:
-        # Fidelity of two DensityMatrices
-        s1sq = _funm_svd(arr1, np.sqrt)
-        s2sq = _funm_svd(arr2, np.sqrt)
-        fid = np.linalg.norm(s1sq.dot(s2sq), ord="nuc") ** 2
-    # Convert to py float rather than return np.float
-    return float(np.real(fid))
+        return fidelity_rs.statevector_density_matrix_fidelity(arr2, arr1)
+    # Fidelity of two DensityMatrices
+    return fidelity_rs.density_matrix_fidelity(arr1, arr2)
 
 
 def purity(state: Statevector | DensityMatrix, validate: bool = True) -> float:
@@ -99,7 +94,10 @@ def purity(state: Statevector | DensityMatrix, validate: bool = True) -> float:
         QiskitError: if the input isn't a valid quantum state.
     """
     state = _format_state(state, validate=validate)
-    return state.purity()
+    data = np.asarray(state.data, dtype=complex)
+    if isinstance(state, Statevector):
+        return entanglement_rs.statevector_purity(data)
+    return entanglement_rs.density_matrix_purity(data)
 
 
 def entropy(state: Statevector | DensityMatrix, base: int = 2) -> float:
@@ -121,14 +119,11 @@ def entropy(state: Statevector | DensityMatrix, base: int = 2) -> float:
     Raises:
         QiskitError: if the input state is not a valid QuantumState.
     """
-    import scipy.linalg as la
-
     state = _format_state(state, validate=True)
     if isinstance(state, Statevector):
         return 0
     # Density matrix case
-    evals = np.maximum(np.real(la.eigvals(state.data)), 0.0)
-    return shannon_entropy(evals, base=base)
+    return entanglement_rs.density_matrix_entropy(np.asarray(state.data, dtype=complex), base)
 
 
 def mutual_information(state: Statevector | DensityMatrix, base: int = 2) -> float:
@@ -199,8 +194,6 @@ def concurrence(state: Statevector | DensityMatrix) -> float:
         QiskitError: if input is not a bipartite QuantumState.
         QiskitError: if density matrix input is not a 2-qubit state.
     """
-    import scipy.linalg as la
-
     # Concurrence computation requires the state to be valid
     state = _format_state(state, validate=True)
     if isinstance(state, Statevector):
@@ -208,18 +201,13 @@ def concurrence(state: Statevector | DensityMatrix) -> float:
         dims = state.dims()
         if len(dims) != 2:
             raise QiskitError("Input is not a bipartite quantum state.")
-        qargs = [0] if dims[0] > dims[1] else [1]
-        rho = partial_trace(state, qargs)
-        return float(np.sqrt(2 * (1 - np.real(purity(rho)))))
+        return entanglement_rs.statevector_concurrence(
+            np.asarray(state.data, dtype=complex), list(dims)
+        )
     # If input is a density matrix it must be a 2-qubit state
     if state.dim != 4:
         raise QiskitError("Input density matrix must be a 2-qubit state.")
-    rho = DensityMatrix(state).data
-    yy_mat = np.fliplr(np.diag([-1, 1, 1, -1]))
-    sigma = rho.dot(yy_mat).dot(rho.conj()).dot(yy_mat)
-    w = np.sort(np.real(la.eigvals(sigma)))
-    w = np.sqrt(np.maximum(w, 0.0))
-    return max(0.0, w[-1] - np.sum(w[0:-1]))
+    return entanglement_rs.density_matrix_concurrence(np.asarray(state.data, dtype=complex))
 
 
 def entanglement_of_formation(state: Statevector | DensityMatrix) -> float:
@@ -246,8 +234,9 @@ def entanglement_of_formation(state: Statevector | DensityMatrix) -> float:
         dims = state.dims()
         if len(dims) != 2:
             raise QiskitError("Input is not a bipartite quantum state.")
-        qargs = [0] if dims[0] > dims[1] else [1]
-        return entropy(partial_trace(state, qargs), base=2)
+        return entanglement_rs.entanglement_entropy(
+            np.asarray(state.data, dtype=complex), list(dims), [0], 2
+        )
 
     # If input is a density matrix it must be a 2-qubit state
     if state.dim != 4:
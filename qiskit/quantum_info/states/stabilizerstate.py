@@ -20,6 +20,7 @@ from collections.abc import Collection
 
 import numpy as np
 
+from qiskit._accelerate import stabilizer_state as stabilizer_state_rs
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators.op_shape import OpShape
 from qiskit.quantum_info.operators.operator import Operator
@@ -527,17 +528,47 @@ class StabilizerState(QuantumState):
 
         Additional Information:
 
-            This function implements the measurement :meth:`measure` method.
+            The shots are measured in Rust, each on a copy of the state, as
+            :meth:`measure` does.
 
             The seed for random number generator used for sampling can be
             set to a fixed value by using the stats :meth:`seed` method.
         """
-        memory = []
-        for _ in range(shots):
-            # copy the StabilizerState since measure updates it
-            stab = self.copy()
-            memory.append(stab.measure(qargs)[0])
-        return memory
+        if qargs is None:
+            qargs = range(self.clifford.num_qubits)
+        state = stabilizer_state_rs.StabilizerState(self.clifford.tableau)
+        outcomes = state.sample_measurements(list(qargs), shots, seed=self._sampling_seed())
+        return _memory_labels(outcomes)
+
+    @classmethod
+    def sample_circuit(
+        cls, circuit: QuantumCircuit, shots: int, seed: int | None = None
+    ) -> tuple[list[str], StabilizerState]:
+        """Sample shots of a Clifford circuit with measurements and resets.
+
+        Each shot is simulated on a stabilizer state in Rust, starting from the all-zero state.
+
+        Args:
+            circuit (QuantumCircuit): the circuit, made of Clifford gates, measurements, resets,
+                barriers and delays.
+            shots (int): the number of shots.
+            seed (int or None): the seed of the random outcomes of the measurements.
+
+        Returns:
+            tuple: the pair ``(memory, state)`` where ``memory`` is the list of the classical
+            bits of each shot as a bitstring, with the first clbit rightmost, and ``state`` is
+            the final state of the last shot.
+
+        Raises:
+            QiskitError: if the circuit contains an instruction that is not a Clifford gate,
+                a measurement, a reset, a barrier or a delay, or is classically conditioned.
+        """
+        outcomes, state = stabilizer_state_rs.sample_circuit_memory(circuit._data, shots, seed=seed)
+        return _memory_labels(outcomes), cls(Clifford(state.tableau, validate=False))
+
+    def _sampling_seed(self) -> int:
+        """A seed for the Rust sampler, drawn from the generator of :meth:`seed`."""
+        return int(self._rng.integers(1 << 64, dtype=np.uint64))
 
     # -----------------------------------------------------------------------
     # Helper functions for calculating the measurement
@@ -771,3 +802,9 @@ class StabilizerState(QuantumState):
                 probs[key] = round(value, decimals)
 
         return probs
+
+
+def _memory_labels(outcomes: np.ndarray) -> list[str]:
+    """The bitstrings of a boolean array of outcomes of shape ``(shots, num_bits)``, with the
+    first bit rightmost."""
+    return ["".join("1" if bit else "0" for bit in reversed(shot)) for shot in outcomes]
@@ -31,10 +31,14 @@ from qiskit.quantum_info.operators.operator import Operator, BaseOperator
 from qiskit.quantum_info.operators.symplectic import Pauli, SparsePauliOp
 from qiskit.quantum_info.operators.op_shape import OpShape
 from qiskit.quantum_info.operators.predicates import matrix_equal
+from qiskit.result.counts import Counts
 
+from qiskit._accelerate import statevector as statevector_rs
+from qiskit._accelerate import sampling as sampling_rs
 from qiskit._accelerate.pauli_expval import (
     expval_pauli_no_x,
     expval_pauli_with_x,
+    expval_paulis,
 )
 
 
@@ -491,6 +495,34 @@ class Statevector(QuantumState, TolerancesMixin):
             self.data, self.num_qubits, z_mask, x_mask, y_phase, x_max
         )
 
+    def _expectation_value_sparse_pauli_op(self, oper, qargs=None):
+        """Compute the expectation value of a SparsePauliOp, in one pass over the statevector for
+        all its terms.
+
+        Args:
+            oper (SparsePauliOp): the operator to evaluate expval of.
+            qargs (None or list): subsystems to apply operator on.
+
+        Returns:
+            complex: the expectation value.
+        """
+        if qargs is None:
+            qubits = np.arange(oper.num_qubits)
+        else:
+            qubits = np.array(qargs)
+        weights = 1 << qubits
+        x_masks = np.dot(oper.paulis.x, weights).tolist()
+        z_masks = np.dot(oper.paulis.z, weights).tolist()
+        phases = ((-1j) ** oper.paulis._count_y()).tolist()
+        coeffs = oper.coeffs.tolist()
+        values = np.array(
+            expval_paulis(self.data, self.num_qubits, z_masks, x_masks, phases, coeffs)
+        )
+        # As for a single Pauli, the value of an identity term is the norm of the statevector.
+        identity = ~(oper.paulis.x.any(axis=1) | oper.paulis.z.any(axis=1))
+        values[identity] = oper.coeffs[identity] * np.linalg.norm(self.data)
+        return values.sum()
+
     def expectation_value(
         self, oper: BaseOperator | QuantumCircuit | Instruction, qargs: None | list[int] = None
     ) -> complex:
@@ -507,10 +539,7 @@ class Statevector(QuantumState, TolerancesMixin):
             return self._expectation_value_pauli(oper, qargs)
 
         if isinstance(oper, SparsePauliOp):
-            return sum(
-                coeff * self._expectation_value_pauli(Pauli((z, x)), qargs)
-                for z, x, coeff in zip(oper.paulis.z, oper.paulis.x, oper.coeffs)
-            )
+            return self._expectation_value_sparse_pauli_op(oper, qargs)
 
         val = self.evolve(oper, qargs=qargs)
         conj = self.conjugate()
@@ -586,17 +615,56 @@ class Statevector(QuantumState, TolerancesMixin):
                 Swapped probs: [0.5 0.5 0.  0. ]
 
         """
-        probs = self._subsystem_probabilities(
-            np.abs(self.data) ** 2, self._op_shape.dims_l(), qargs=qargs
+        # The probabilities are clipped to 1 to account for roundoff errors.
+        return statevector_rs.probabilities(
+            self._data,
+            list(self._op_shape.dims_l()),
+            qargs=None if qargs is None else list(qargs),
+            decimals=decimals,
+        )
+
+    def probabilities_dict(
+        self, qargs: None | list[int] = None, decimals: None | int = None
+    ) -> dict:
+        """Return the subsystem measurement probability dictionary.
+
+        The nonzero probabilities are found and labelled in Rust. See
+        :meth:`.QuantumState.probabilities_dict` for the arguments.
+        """
+        return statevector_rs.probabilities_dict(
+            self._data,
+            list(self._op_shape.dims_l()),
+            qargs=None if qargs is None else list(qargs),
+            decimals=decimals,
         )
 
-        # to account for roundoff errors, we clip
-        probs = np.clip(probs, a_min=0, a_max=1)
+    def sample_memory(self, shots: int, qargs: None | list = None) -> np.ndarray:
+        """Sample a list of qubit measurement outcomes in the computational basis.
 
-        if decimals is not None:
-            probs = probs.round(decimals=decimals)
+        The shots are drawn in Rust by the alias method, seeded from the generator of
+        :meth:`seed`.  See :meth:`.QuantumState.sample_memory` for the arguments.
+        """
+        weights = self._data if qargs is None else self.probabilities(qargs)
+        indices = sampling_rs.sample_memory(weights, shots, seed=self._sampling_seed())
+        labels = self._index_to_ket_array(
+            np.arange(len(weights)), self.dims(qargs), string_labels=True
+        )
+        return labels[indices]
+
+    def sample_counts(self, shots: int, qargs: None | list = None) -> Counts:
+        """Sample a dict of qubit measurement outcomes in the computational basis.
 
-        return probs
+        The shots are drawn and counted in Rust by the alias method, seeded from the generator of
+        :meth:`seed`.  See :meth:`.QuantumState.sample_counts` for the arguments.
+        """
+        weights = self._data if qargs is None else self.probabilities(qargs)
+        outcomes, counts = sampling_rs.sample_counts(weights, shots, seed=self._sampling_seed())
+        labels = self._index_to_ket_array(outcomes, self.dims(qargs), string_labels=True)
+        return Counts(zip(labels, counts))
+
+    def _sampling_seed(self) -> int:
+        """A seed for the Rust sampler, drawn from the generator of :meth:`seed`."""
+        return int(self._rng.integers(1 << 64, dtype=np.uint64))
 
     def reset(self, qargs: list[int] | None = None) -> Statevector:
         """Reset state or subsystems to the 0-state.
@@ -759,8 +827,17 @@ class Statevector(QuantumState, TolerancesMixin):
             QiskitError: if the instruction contains invalid instructions for
                          the statevector simulation.
         """
-        # Convert circuit to an instruction
         if isinstance(instruction, QuantumCircuit):
+            # Circuits made of matrix-defined gates are simulated entirely in Rust.
+            try:
+                global_phase = float(instruction.global_phase)
+            except TypeError:
+                global_phase = None
+            if global_phase is not None:
+                data = statevector_rs.from_circuit_data(instruction._data, global_phase)
+                if data is not None:
+                    return Statevector(data, dims=instruction.num_qubits * (2,))
+            # Convert circuit to an instruction
             instruction = instruction.to_instruction()
         # Initialize an the statevector in the all |0> state
         init = np.zeros(2**instruction.num_qubits, dtype=complex)
@@ -19,10 +19,10 @@ import math
 
 import numpy as np
 
+from qiskit._accelerate import partial_trace as partial_trace_rs
 from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.states.statevector import Statevector
 from qiskit.quantum_info.states.densitymatrix import DensityMatrix
-from qiskit.quantum_info.operators.channel import SuperOp
 from qiskit.quantum_info.operators.predicates import ATOL_DEFAULT
 
 
@@ -57,27 +57,19 @@ def partial_trace(state: Statevector | DensityMatrix, qargs: list) -> DensityMat
 
     # Statevector case
     if isinstance(state, Statevector):
-        trace_systems = len(state._op_shape.dims_l()) - 1 - np.array(qargs)
-        arr = state._data.reshape(state._op_shape.tensor_shape)
-        rho = np.tensordot(arr, arr.conj(), axes=(trace_systems, trace_systems))
-        rho = np.reshape(rho, traced_shape.shape)
+        rho = partial_trace_rs.partial_trace_statevector(
+            np.asarray(state._data, dtype=complex), list(state.dims()), list(qargs)
+        )
         return DensityMatrix(rho, dims=traced_shape._dims_l)
 
     # Density matrix case
     # Empty partial trace case.
     if not qargs:
         return state.copy()
-    # Trace first subsystem to avoid coping whole density matrix
-    dims = state.dims(qargs)
-    tr_op = SuperOp(np.eye(dims[0]).reshape(1, dims[0] ** 2), input_dims=[dims[0]], output_dims=[1])
-    ret = state.evolve(tr_op, [qargs[0]])
-    # Trace over remaining subsystems
-    for qarg, dim in zip(qargs[1:], dims[1:]):
-        tr_op = SuperOp(np.eye(dim).reshape(1, dim**2), input_dims=[dim], output_dims=[1])
-        ret = ret.evolve(tr_op, [qarg])
-    # Remove traced over subsystems which are listed as dimension 1
-    ret._op_shape = traced_shape
-    return ret
+    rho = partial_trace_rs.partial_trace_density_matrix(
+        np.asarray(state._data, dtype=complex), list(state.dims()), list(qargs)
+    )
+    return DensityMatrix(rho, dims=traced_shape._dims_l)
 
 
 def shannon_entropy(pvec: list | np.ndarray, base: int = 2) -> float:
@@ -47,6 +47,7 @@ Expectation values
 ==================
 
 .. autofunction:: sampled_expectation_value
+.. autofunction:: sampled_expectation_values
 
 Mitigation
 ==========
@@ -67,7 +68,7 @@ from .utils import marginal_memory
 from .counts import Counts
 
 from .distributions import QuasiDistribution, ProbDistribution
-from .sampled_expval import sampled_expectation_value
+from .sampled_expval import sampled_expectation_value, sampled_expectation_values
 from .mitigation.base_readout_mitigator import BaseReadoutMitigator
 from .mitigation.correlated_readout_mitigator import CorrelatedReadoutMitigator
 from .mitigation.local_readout_mitigator import LocalReadoutMitigator
@@ -11,9 +11,10 @@
 # that they have been altered from the originals.
 """Quasidistribution class"""
 
-from math import sqrt
 import re
 
+from qiskit._accelerate.results import nearest_probability_distribution
+
 from .probability import ProbDistribution
 
 
@@ -100,22 +101,9 @@ class QuasiDistribution(dict):
         Notes:
             Method from Smolin et al., Phys. Rev. Lett. 108, 070502 (2012).
         """
-        sorted_probs = dict(sorted(self.items(), key=lambda item: item[1]))
-        num_elems = len(sorted_probs)
-        new_probs = {}
-        beta = 0
-        diff = 0
-        for key, val in sorted_probs.items():
-            temp = val + beta / num_elems
-            if temp < 0:
-                beta += val
-                num_elems -= 1
-                diff += val * val
-            else:
-                diff += (beta / num_elems) * (beta / num_elems)
-                new_probs[key] = sorted_probs[key] + beta / num_elems
+        new_probs, distance = nearest_probability_distribution(self)
         if return_distance:
-            return ProbDistribution(new_probs, self.shots), sqrt(diff)
+            return ProbDistribution(new_probs, self.shots), distance
         return ProbDistribution(new_probs, self.shots)
 
     def binary_probabilities(self, num_bits=None):
@@ -19,10 +19,12 @@ from typing import Optional, List, Tuple, Iterable, Callable, Union, Dict
 import numpy as np
 
 from qiskit.exceptions import QiskitError
+from qiskit._accelerate import readout_mitigation as readout_mitigation_rs
+from ..distributions.probability import ProbDistribution
 from ..distributions.quasi import QuasiDistribution
 from ..counts import Counts
 from .base_readout_mitigator import BaseReadoutMitigator
-from .utils import counts_probability_vector, z_diagonal, str2diag
+from .utils import counts_bit_positions, counts_probability_vector, str2diag
 
 
 class LocalReadoutMitigator(BaseReadoutMitigator):
@@ -35,8 +37,15 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
     are assumed to be uncorrelated. For *N* qubits there are *N* mitigation matrices,
     each of size :math:`2 x 2` and the mitigation complexity is :math:`O(2^N)`,
     so it is more efficient than the :class:`CorrelatedReadoutMitigator` class.
+
+    Beyond 20 qubits, :meth:`quasi_probabilities` only returns the
+    quasi-probabilities of the observed outcomes, which takes :math:`O(N)` per pair of
+    observed outcomes rather than :math:`O(2^N)`, and the expectation values of
+    product diagonals never build the :math:`2^N` distribution.
     """
 
+    _DENSE_QUBITS = 20
+
     def __init__(
         self,
         assignment_matrices: Optional[List[np.ndarray]] = None,
@@ -76,7 +85,10 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
 
         self._qubit_index = dict(zip(self._qubits, range(self._num_qubits)))
         self._assignment_mats = assignment_matrices
-        self._mitigation_mats = np.zeros([self._num_qubits, 2, 2], dtype=float)
+        self._mitigator = readout_mitigation_rs.TensoredMitigator(
+            np.asarray(assignment_matrices, dtype=float).reshape(-1, 2, 2)
+        )
+        self._mitigation_mats = self._mitigator.mitigation_matrices
         self._gammas = np.zeros(self._num_qubits, dtype=float)
 
         for i in range(self._num_qubits):
@@ -85,12 +97,33 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
             error0 = mat[1, 0]
             error1 = mat[0, 1]
             self._gammas[i] = (1 + abs(error0 - error1)) / (1 - error0 - error1)
-            # Compute inverse mitigation matrix
-            try:
-                ainv = np.linalg.inv(mat)
-            except np.linalg.LinAlgError:
-                ainv = np.linalg.pinv(mat)
-            self._mitigation_mats[i] = ainv
+
+    @classmethod
+    def from_calibration_counts(
+        cls,
+        calibrations: Iterable[Tuple[str, Counts]],
+        qubits: Optional[Iterable[int]] = None,
+    ) -> "LocalReadoutMitigator":
+        r"""Build a mitigator from the counts of calibration circuits.
+
+        Args:
+            calibrations: the basis state prepared by each calibration circuit, as a
+                bitstring with qubit 0 last, and the counts of the circuit. Each qubit
+                must be prepared in both :math:`|0\rangle` and :math:`|1\rangle`.
+            qubits: Optional, the measured physical qubits for mitigation.
+
+        Returns:
+            LocalReadoutMitigator: the mitigator of the measured assignment matrices.
+
+        Raises:
+            QiskitError: if there are no calibrations.
+        """
+        calibrations = [(prepared, dict(counts)) for prepared, counts in calibrations]
+        if not calibrations:
+            raise QiskitError("The mitigator needs at least one calibration circuit.")
+        num_qubits = len(calibrations[0][0].replace(" ", ""))
+        amats = readout_mitigation_rs.assignment_matrices(num_qubits, calibrations)
+        return cls(list(amats), qubits=qubits)
 
     @property
     def settings(self) -> Dict:
@@ -138,6 +171,23 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
         if qubits is None:
             qubits = self._qubits
         num_qubits = len(qubits)
+
+        # The expectation values of product diagonals are taken without the probability vector.
+        if diagonal is None:
+            factors = num_qubits * [[1.0, -1.0]]
+        elif isinstance(diagonal, str) and len(diagonal) == num_qubits:
+            factors = [str2diag(char).tolist() for char in reversed(diagonal)]
+        else:
+            factors = None
+        if factors is not None:
+            expval, shots = self._mitigator.expectation_value(
+                data,
+                [self._qubit_index[qubit] for qubit in qubits],
+                counts_bit_positions(self._qubit_index, qubits, clbits),
+                factors,
+            )
+            return (expval, self.stddev_upper_bound(shots, qubits))
+
         probs_vec, shots = counts_probability_vector(
             data, qubit_index=self._qubit_index, clbits=clbits, qubits=qubits
         )
@@ -147,9 +197,7 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
         ainvs = self._mitigation_mats[qubit_indices]
 
         # Get operator coeffs
-        if diagonal is None:
-            diagonal = z_diagonal(2**num_qubits)
-        elif isinstance(diagonal, str):
+        if isinstance(diagonal, str):
             diagonal = str2diag(diagonal)
 
         # Apply transpose of mitigation matrix
@@ -193,35 +241,63 @@ class LocalReadoutMitigator(BaseReadoutMitigator):
         if qubits is None:
             qubits = self._qubits
 
-        num_qubits = len(qubits)
-
-        probs_vec, calculated_shots = counts_probability_vector(
-            data, qubit_index=self._qubit_index, clbits=clbits, qubits=qubits
-        )
+        qubit_indices = [self._qubit_index[qubit] for qubit in qubits]
+        positions = counts_bit_positions(self._qubit_index, qubits, clbits)
+        if len(qubits) <= self._DENSE_QUBITS:
+            probs_vec, calculated_shots = self._mitigator.dense_quasi_probabilities(
+                data, qubit_indices, positions
+            )
+            probs_dict = dict(enumerate(probs_vec))
+        else:
+            probs_dict, calculated_shots = self._mitigator.quasi_probabilities(
+                data, qubit_indices, positions
+            )
         if shots is None:
             shots = calculated_shots
 
-        # Get qubit mitigation matrix and mitigate probs
-        qubit_indices = [self._qubit_index[qubit] for qubit in qubits]
-        ainvs = self._mitigation_mats[qubit_indices]
-
-        # Apply transpose of mitigation matrix
-        prob_tens = np.reshape(probs_vec, num_qubits * [2])
-        einsum_args = [prob_tens, list(range(num_qubits))]
-        for i, ainv in enumerate(reversed(ainvs)):
-            einsum_args += [ainv, [num_qubits + i, i]]
-        einsum_args += [list(range(num_qubits, 2 * num_qubits))]
-        probs_vec = np.einsum(*einsum_args).ravel()
-
-        probs_dict = {}
-        for index, _ in enumerate(probs_vec):
-            probs_dict[index] = probs_vec[index]
-
         quasi_dist = QuasiDistribution(
             probs_dict, shots=shots, stddev_upper_bound=self.stddev_upper_bound(shots, qubits)
         )
         return quasi_dist
 
+    def unfolded_probabilities(
+        self,
+        data: Counts,
+        qubits: Optional[List[int]] = None,
+        clbits: Optional[List[int]] = None,
+        max_iterations: int = 100,
+        tolerance: float = 1e-8,
+    ) -> ProbDistribution:
+        """Compute mitigated probabilities by iterative Bayesian unfolding.
+
+        Unlike the quasi-probabilities of :meth:`quasi_probabilities`, the unfolded
+        probabilities are never negative. They are restricted to the observed outcomes,
+        and the iterations start from the observed distribution.
+
+        Args:
+            data: counts object
+            qubits: qubits the count bitstrings correspond to.
+            clbits: Optional, marginalize counts to just these bits.
+            max_iterations: the maximum number of iterations.
+            tolerance: the iterations stop once no probability changes by more than this.
+
+        Returns:
+            ProbDistribution: the unfolded probability of each observed outcome.
+
+        Raises:
+            QiskitError: if qubit and clbit kwargs are not valid.
+        """
+        if qubits is None:
+            qubits = self._qubits
+        probs, shots = self._mitigator.unfold(
+            data,
+            [self._qubit_index[qubit] for qubit in qubits],
+            counts_bit_positions(self._qubit_index, qubits, clbits),
+            max_iterations=max_iterations,
+            tolerance=tolerance,
+        )
+        return ProbDistribution(probs, shots=shots)
+
     def mitigation_matrix(self, qubits: Optional[Union[List[int], int]] = None) -> np.ndarray:
         r"""Return the measurement mitigation matrix for the specified qubits.
 
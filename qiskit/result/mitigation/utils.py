@@ -160,3 +160,34 @@ def counts_probability_vector(
     vec, shots = counts_to_vector(counts, num_qubits)
     vec = remap_qubits(vec, num_qubits, qubits)
     return vec, shots
+
+
+def counts_bit_positions(
+    qubit_index: Dict[int, int],
+    qubits: List[int],
+    clbits: Optional[List[int]] = None,
+) -> List[int]:
+    """Compute the position in the count bitstrings of the bit of each qubit.
+
+    The positions are those that :func:`counts_probability_vector` reads the bits of ``qubits``
+    from, so that the counts can be mitigated without building their probability vector.
+
+    Args:
+        qubit_index: For each qubit, its index in the mitigator qubits list
+        qubits: qubits the count bitstrings correspond to.
+        clbits: Optional, the bits of the count bitstrings to read.
+
+    Raises:
+        QiskitError: if qubits and clbits kwargs are not valid.
+
+    Returns:
+        list[int]: the position of the bit of each qubit, with bit 0 last in the bitstrings.
+    """
+    if clbits is None:
+        clbits = [qubit_index[qubit] for qubit in qubits]
+    elif len(clbits) != len(qubits):
+        raise QiskitError(
+            f"Num qubits ({len(qubits)}) does not match number of clbits ({len(clbits)})."
+        )
+    sorted_clbits = sorted(clbits)
+    return [sorted_clbits[index] for index in np.argsort(qubits)]
@@ -14,7 +14,11 @@
 """Routines for computing expectation values from sampled distributions"""
 import numpy as np
 
-from qiskit._accelerate.sampled_exp_val import sampled_expval_float, sampled_expval_complex
+from qiskit._accelerate.sampled_exp_val import (
+    sampled_expval_float,
+    sampled_expval_complex,
+    sampled_expval_batch,
+)
 from qiskit.exceptions import QiskitError
 from .distributions import QuasiDistribution, ProbDistribution
 
@@ -38,8 +42,41 @@ def sampled_expectation_value(dist, oper):
     Raises:
         QiskitError: if the input distribution or operator is an invalid type
     """
+    dist = _bitstring_dist(dist)
+    oper_strs, coeffs = _diagonal_terms(oper, len(next(iter(dist))))
+    # Dispatch to Rust routines
+    if coeffs.dtype == np.dtype(complex).type:
+        return sampled_expval_complex(oper_strs, coeffs, dist)
+    else:
+        return sampled_expval_float(oper_strs, coeffs, dist)
+
+
+def sampled_expectation_values(dist, opers):
+    """Computes the expectation values of many operators from the same sampled distribution
+
+    This is faster than calling :func:`.sampled_expectation_value` on each operator, as the
+    distribution is converted once and each distinct Pauli term is evaluated once.
+
+    Parameters:
+        dist (Counts or QuasiDistribution or ProbDistribution or dict): Input sampled distribution
+        opers (list[str or Pauli or SparsePauliOp]): The operators for the observables
+
+    Returns:
+        list[float]: The expectation value of each operator
+    Raises:
+        QiskitError: if the input distribution or an operator is an invalid type
+    """
+    dist = _bitstring_dist(dist)
+    bitstring_len = len(next(iter(dist)))
+    observables = []
+    for oper in opers:
+        oper_strs, coeffs = _diagonal_terms(oper, bitstring_len)
+        observables.append((oper_strs, coeffs.astype(complex).tolist()))
+    return sampled_expval_batch(observables, dist)
+
+
+def _bitstring_dist(dist):
     from .counts import Counts
-    from qiskit.quantum_info import Pauli, SparsePauliOp
 
     # This should be removed when these return bit-string keys
     if isinstance(dist, (QuasiDistribution, ProbDistribution)):
@@ -47,6 +84,12 @@ def sampled_expectation_value(dist, oper):
 
     if not isinstance(dist, (Counts, dict)):
         raise QiskitError("Invalid input distribution type")
+    return dist
+
+
+def _diagonal_terms(oper, bitstring_len):
+    from qiskit.quantum_info import Pauli, SparsePauliOp
+
     if isinstance(oper, str):
         oper_strs = [oper.upper()]
         coeffs = np.asarray([1.0])
@@ -60,7 +103,6 @@ def sampled_expectation_value(dist, oper):
         raise QiskitError("Invalid operator type")
 
     # Do some validation here
-    bitstring_len = len(next(iter(dist)))
     if any(len(op) != bitstring_len for op in oper_strs):
         raise QiskitError(
             f"One or more operators not same length ({bitstring_len}) as input bitstrings"
@@ -68,8 +110,4 @@ def sampled_expectation_value(dist, oper):
     for op in oper_strs:
         if set(op).difference(OPERS):
             raise QiskitError(f"Input operator {op} is not diagonal")
-    # Dispatch to Rust routines
-    if coeffs.dtype == np.dtype(complex).type:
-        return sampled_expval_complex(oper_strs, coeffs, dist)
-    else:
-        return sampled_expval_float(oper_strs, coeffs, dist)
+    return oper_strs, coeffs
@@ -87,6 +87,8 @@ Discrete Basis Synthesis
 
 .. autofunction:: generate_basic_approximations
 
+.. autofunction:: gridsynth_rz
+
 Basis Change Synthesis
 ======================
 
@@ -161,7 +163,11 @@ from .stabilizer import (
     synth_stabilizer_depth_lnn,
     synth_circuit_from_stabilizers,
 )
-from .discrete_basis import SolovayKitaevDecomposition, generate_basic_approximations
+from .discrete_basis import (
+    SolovayKitaevDecomposition,
+    generate_basic_approximations,
+    gridsynth_rz,
+)
 from .qft import synth_qft_line
 from .unitary.qsd import qs_decomposition
 from .unitary import aqc
@@ -0,0 +1,46 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Clifford+T approximation of RZ rotations with the Ross-Selinger algorithm."""
+
+from __future__ import annotations
+
+from qiskit.circuit import QuantumCircuit
+from qiskit._accelerate.synthesis import gridsynth_rz as gridsynth_rz_rs
+
+
+def gridsynth_rz(theta: float, epsilon: float = 1e-10) -> QuantumCircuit:
+    r"""Approximate an :class:`.RZGate` by a Clifford+T circuit.
+
+    The circuit is found with the number-theoretic algorithm of Ross and Selinger [1], and is
+    within the operator-norm distance ``epsilon`` of :math:`R_Z(\theta)`, including the global
+    phase of the circuit.  Its T count is close to :math:`3 \log_2(1/\epsilon)`, which is optimal
+    for most angles among the circuits of determinant one.
+
+    Args:
+        theta: The rotation angle.
+        epsilon: The precision of the approximation.  It must be at least ``1e-10``, since the
+            candidates are enumerated in double precision.
+
+    Returns:
+        A single-qubit circuit of ``h``, ``t``, ``tdg``, ``s``, ``sdg``, ``z`` and ``x`` gates.
+
+    Raises:
+        ValueError: if the angle is not finite or the precision is out of range.
+
+    References:
+        1. Neil J. Ross and Peter Selinger, *Optimal ancilla-free Clifford+T approximation of
+           z-rotations*, Quantum Information and Computation 16 (2016).
+           `arXiv:1403.2975 <https://arxiv.org/abs/1403.2975>`_
+    """
+    data, global_phase = gridsynth_rz_rs(theta, epsilon)
+    return QuantumCircuit._from_circuit_data(data, global_phase=global_phase)
@@ -18,6 +18,7 @@ import numpy as np
 from qiskit.circuit.parameterexpression import ParameterExpression
 from qiskit.circuit.quantumcircuit import QuantumCircuit
 from qiskit.quantum_info import SparsePauliOp, Pauli
+from qiskit._accelerate.synthesis import synth_pauli_evolution
 
 from .evolution_synthesis import EvolutionSynthesis
 
@@ -115,6 +116,16 @@ def evolve_pauli(
     Returns:
         A quantum circuit implementing the time evolution of the Pauli.
     """
+    if not isinstance(time, ParameterExpression):
+        # The phase of the Pauli is dropped, since the coefficients carry all phases.
+        label = pauli.to_label().lstrip("-i")
+        circuit_data, global_phase = synth_pauli_evolution(
+            pauli.num_qubits, [(label, 1.0)], float(time), cx_structure=cx_structure
+        )
+        return QuantumCircuit._from_circuit_data(
+            circuit_data, name=f"exp(it {pauli.to_label()})", global_phase=global_phase
+        )
+
     num_non_identity = len([label for label in pauli.to_label() if label != "I"])
 
     # first check, if the Pauli is only the identity, in which case the evolution only
@@ -321,6 +332,16 @@ def _default_atomic_evolution(operator, time, cx_structure):
         # sum of Pauli operators: exponentiate each term (this assumes they commute)
         pauli_list = [(Pauli(op), np.real(coeff)) for op, coeff in operator.to_list()]
         name = f"exp(it {[pauli.to_label() for pauli, _ in pauli_list]})"
+        if not isinstance(time, ParameterExpression):
+            circuit_data, global_phase = synth_pauli_evolution(
+                operator.num_qubits,
+                [(pauli.to_label(), float(coeff)) for pauli, coeff in pauli_list],
+                float(time),
+                cx_structure=cx_structure,
+            )
+            return QuantumCircuit._from_circuit_data(
+                circuit_data, name=name, global_phase=global_phase
+            )
         evolution_circuit = QuantumCircuit(operator.num_qubits, name=name)
         for pauli, coeff in pauli_list:
             evolution_circuit.compose(evolve_pauli(pauli, coeff * time, cx_structure), inplace=True)
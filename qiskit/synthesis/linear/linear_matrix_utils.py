@@ -14,7 +14,12 @@
 
 from typing import Optional, Union
 import numpy as np
-from qiskit.exceptions import QiskitError
+from qiskit._accelerate.synthesis import (
+    calc_inverse_matrix as calc_inverse_matrix_rs,
+    check_invertible_binary_matrix as check_invertible_binary_matrix_rs,
+    compute_rank as compute_rank_rs,
+    gauss_elimination_with_perm_inplace,
+)
 
 
 def check_invertible_binary_matrix(mat: np.ndarray):
@@ -29,8 +34,7 @@ def check_invertible_binary_matrix(mat: np.ndarray):
     if len(mat.shape) != 2 or mat.shape[0] != mat.shape[1]:
         return False
 
-    rank = _compute_rank(mat)
-    return rank == mat.shape[0]
+    return check_invertible_binary_matrix_rs(np.asarray(mat, dtype=bool))
 
 
 def random_invertible_binary_matrix(
@@ -72,48 +76,9 @@ def _gauss_elimination_with_perm(mat, ncols=None, full_elim=False):
     Returns the matrix mat, and the permutation perm that was done on the rows during the process.
     perm[0 : rank] represents the indices of linearly independent rows in the original matrix."""
 
-    # Treat the matrix A as containing integer values
-    mat = np.array(mat, dtype=int, copy=True)
-
-    m = mat.shape[0]  # no. of rows
-    n = mat.shape[1]  # no. of columns
-    if ncols is not None:
-        n = min(n, ncols)  # no. of active columns
-
-    perm = np.array(range(m))  # permutation on the rows
-
-    r = 0  # current rank
-    k = 0  # current pivot column
-    while (r < m) and (k < n):
-        is_non_zero = False
-        new_r = r
-        for j in range(k, n):
-            for i in range(r, m):
-                if mat[i][j]:
-                    is_non_zero = True
-                    k = j
-                    new_r = i
-                    break
-            if is_non_zero:
-                break
-        if not is_non_zero:
-            return mat, perm  # A is in the canonical form
-
-        if new_r != r:
-            mat[[r, new_r]] = mat[[new_r, r]]
-            perm[r], perm[new_r] = perm[new_r], perm[r]
-
-        if full_elim:
-            for i in range(0, r):
-                if mat[i][k]:
-                    mat[i] = mat[i] ^ mat[r]
-
-        for i in range(r + 1, m):
-            if mat[i][k]:
-                mat[i] = mat[i] ^ mat[r]
-        r += 1
-
-    return mat, perm
+    mat = np.array(mat, dtype=bool, copy=True)
+    perm = gauss_elimination_with_perm_inplace(mat, ncols=ncols, full_elim=full_elim)
+    return mat.astype(int), np.array(perm)
 
 
 def calc_inverse_matrix(mat: np.ndarray, verify: bool = False):
@@ -130,46 +95,9 @@ def calc_inverse_matrix(mat: np.ndarray, verify: bool = False):
          QiskitError: if the matrix is not square.
          QiskitError: if the matrix is not invertible.
     """
-
-    if mat.shape[0] != mat.shape[1]:
-        raise QiskitError("Matrix to invert is a non-square matrix.")
-
-    n = mat.shape[0]
-    # concatenate the matrix and identity
-    mat1 = np.concatenate((mat, np.eye(n, dtype=int)), axis=1)
-    mat1 = _gauss_elimination(mat1, None, full_elim=True)
-
-    r = _compute_rank_after_gauss_elim(mat1[:, 0:n])
-
-    if r < n:
-        raise QiskitError("The matrix is not invertible.")
-
-    matinv = mat1[:, n : 2 * n]
-
-    if verify:
-        mat2 = np.dot(mat, matinv) % 2
-        assert np.array_equal(mat2, np.eye(n))
-
-    return matinv
-
-
-def _compute_rank_after_gauss_elim(mat):
-    """Given a matrix A after Gaussian elimination, computes its rank
-    (i.e. simply the number of nonzero rows)"""
-    return np.sum(mat.any(axis=1))
+    return calc_inverse_matrix_rs(np.asarray(mat, dtype=bool), verify=verify).astype(int)
 
 
 def _compute_rank(mat):
     """Given a matrix A computes its rank"""
-    mat = _gauss_elimination(mat)
-    return np.sum(mat.any(axis=1))
-
-
-def _row_op(mat, ctrl, trgt):
-    # Perform ROW operation on a matrix mat
-    mat[trgt] = mat[trgt] ^ mat[ctrl]
-
-
-def _col_op(mat, ctrl, trgt):
-    # Perform COL operation on a matrix mat
-    mat[:, ctrl] = mat[:, trgt] ^ mat[:, ctrl]
+    return compute_rank_rs(np.asarray(mat, dtype=bool))
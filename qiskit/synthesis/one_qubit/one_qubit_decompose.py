@@ -221,6 +221,11 @@ class OneQubitEulerDecomposer:
         return self._decompose(unitary, simplify=simplify, atol=atol)
 
     def _decompose(self, unitary, simplify=True, atol=DEFAULT_ATOL):
+        if not self.use_dag:
+            data = euler_one_qubit_decomposer.unitary_to_circuit(
+                unitary, [self.basis], 0, None, simplify, atol
+            )
+            return QuantumCircuit._from_circuit_data(data)
         circuit_sequence = euler_one_qubit_decomposer.unitary_to_gate_sequence(
             unitary, [self.basis], 0, None, simplify, atol
         )
@@ -16,11 +16,9 @@ from __future__ import annotations
 
 import numpy as np
 from qiskit.circuit.quantumcircuit import QuantumCircuit
-from .permutation_utils import (
-    _get_ordered_swap,
-    _inverse_pattern,
-    _pattern_to_cycles,
-    _decompose_cycles,
+from qiskit._accelerate.synthesis import (
+    synth_permutation_basic as synth_permutation_basic_rs,
+    synth_permutation_acg as synth_permutation_acg_rs,
 )
 
 
@@ -45,16 +43,8 @@ def synth_permutation_basic(pattern: list[int] | np.ndarray[int]) -> QuantumCirc
         The synthesized quantum circuit.
     """
     # This is the very original Qiskit algorithm for synthesizing permutations.
-
-    num_qubits = len(pattern)
-    qc = QuantumCircuit(num_qubits)
-
-    swaps = _get_ordered_swap(pattern)
-
-    for swap in swaps:
-        qc.swap(swap[0], swap[1])
-
-    return qc
+    circuit_data = synth_permutation_basic_rs(np.asarray(pattern, dtype=np.int64))
+    return QuantumCircuit._from_circuit_data(circuit_data)
 
 
 def synth_permutation_acg(pattern: list[int] | np.ndarray[int]) -> QuantumCircuit:
@@ -88,15 +78,5 @@ def synth_permutation_acg(pattern: list[int] | np.ndarray[int]) -> QuantumCircui
            `(Full paper) <https://www.cs.tau.ac.il/~nogaa/PDFS/r.pdf>`_
     """
 
-    num_qubits = len(pattern)
-    qc = QuantumCircuit(num_qubits)
-
-    # invert pattern (Qiskit notation is opposite)
-    cur_pattern = _inverse_pattern(pattern)
-    cycles = _pattern_to_cycles(cur_pattern)
-    swaps = _decompose_cycles(cycles)
-
-    for swap in swaps:
-        qc.swap(swap[0], swap[1])
-
-    return qc
+    circuit_data = synth_permutation_acg_rs(np.asarray(pattern, dtype=np.int64))
+    return QuantumCircuit._from_circuit_data(circuit_data)
@@ -14,24 +14,9 @@ Synthesis of a reverse permutation for LNN connectivity.
 """
 
 from qiskit.circuit import QuantumCircuit
-
-
-def _append_cx_stage1(qc, n):
-    """A single layer of CX gates."""
-    for i in range(n // 2):
-        qc.cx(2 * i, 2 * i + 1)
-    for i in range((n + 1) // 2 - 1):
-        qc.cx(2 * i + 2, 2 * i + 1)
-    return qc
-
-
-def _append_cx_stage2(qc, n):
-    """A single layer of CX gates."""
-    for i in range(n // 2):
-        qc.cx(2 * i + 1, 2 * i)
-    for i in range((n + 1) // 2 - 1):
-        qc.cx(2 * i + 1, 2 * i + 2)
-    return qc
+from qiskit._accelerate.synthesis import (
+    synth_permutation_reverse_lnn_kms as synth_permutation_reverse_lnn_kms_rs,
+)
 
 
 def _append_reverse_permutation_lnn_kms(qc: QuantumCircuit, num_qubits: int) -> None:
@@ -56,11 +41,7 @@ def _append_reverse_permutation_lnn_kms(qc: QuantumCircuit, num_qubits: int) ->
            `arXiv:quant-ph/0701194 <https://arxiv.org/abs/quant-ph/0701194>`_
     """
 
-    for _ in range((num_qubits + 1) // 2):
-        _append_cx_stage1(qc, num_qubits)
-        _append_cx_stage2(qc, num_qubits)
-    if (num_qubits % 2) == 0:
-        _append_cx_stage1(qc, num_qubits)
+    qc.compose(synth_permutation_reverse_lnn_kms(num_qubits), inplace=True)
 
 
 def synth_permutation_reverse_lnn_kms(num_qubits: int) -> QuantumCircuit:
@@ -84,7 +65,5 @@ def synth_permutation_reverse_lnn_kms(num_qubits: int) -> QuantumCircuit:
            `arXiv:quant-ph/0701194 <https://arxiv.org/abs/quant-ph/0701194>`_
     """
 
-    qc = QuantumCircuit(num_qubits)
-    _append_reverse_permutation_lnn_kms(qc, num_qubits)
-
-    return qc
+    circuit_data = synth_permutation_reverse_lnn_kms_rs(num_qubits)
+    return QuantumCircuit._from_circuit_data(circuit_data)
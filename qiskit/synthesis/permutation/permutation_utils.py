@@ -12,6 +12,13 @@
 
 """Utility functions for handling permutations."""
 
+import numpy as np
+
+from qiskit._accelerate.synthesis import (
+    _get_ordered_swap as _get_ordered_swap_rs,
+    _inverse_pattern as _inverse_pattern_rs,
+)
+
 
 def _get_ordered_swap(permutation_in):
     """Sorts the input permutation by iterating through the permutation list
@@ -24,50 +31,9 @@ def _get_ordered_swap(permutation_in):
     if the input  permutation consists of several disjoint cycles, then each cycle
     is essentially treated independently.
     """
-    permutation = list(permutation_in[:])
-    swap_list = []
-    index_map = _inverse_pattern(permutation_in)
-    for i, val in enumerate(permutation):
-        if val != i:
-            j = index_map[i]
-            swap_list.append((i, j))
-            permutation[i], permutation[j] = permutation[j], permutation[i]
-            index_map[val] = j
-            index_map[i] = i
-    swap_list.reverse()
-    return swap_list
+    return _get_ordered_swap_rs(np.asarray(permutation_in, dtype=np.int64))
 
 
 def _inverse_pattern(pattern):
     """Finds inverse of a permutation pattern."""
-    b_map = {pos: idx for idx, pos in enumerate(pattern)}
-    return [b_map[pos] for pos in range(len(pattern))]
-
-
-def _pattern_to_cycles(pattern):
-    """Given a permutation pattern, creates its disjoint cycle decomposition."""
-    nq = len(pattern)
-    explored = [False] * nq
-    cycles = []
-    for i in pattern:
-        cycle = []
-        while not explored[i]:
-            cycle.append(i)
-            explored[i] = True
-            i = pattern[i]
-        if len(cycle) >= 2:
-            cycles.append(cycle)
-    return cycles
-
-
-def _decompose_cycles(cycles):
-    """Given a disjoint cycle decomposition, decomposes every cycle into a SWAP
-    circuit of depth 2."""
-    swap_list = []
-    for cycle in cycles:
-        m = len(cycle)
-        for i in range((m - 1) // 2):
-            swap_list.append((cycle[i - 1], cycle[m - 3 - i]))
-        for i in range(m // 2):
-            swap_list.append((cycle[i - 1], cycle[m - 2 - i]))
-    return swap_list
+    return _inverse_pattern_rs(np.asarray(pattern, dtype=np.int64))
@@ -154,6 +154,10 @@ class TwoQubitWeylDecomposition:
     chamber. If there is a high-symmetry gate "nearby" (in terms of the requested average gate fidelity),
     then it return a canonicalized decomposition of that high-symmetry gate.
 
+    The decomposition is an instance of the subclass for its specialization, such as
+    ``TwoQubitWeylControlledEquiv``, and constructing one of those subclasses directly forces its
+    specialization.
+
     References:
         1. Cross, A. W., Bishop, L. S., Sheldon, S., Nation, P. D. & Gambetta, J. M.,
            *Validating quantum computers using randomized model circuits*,
@@ -180,6 +184,32 @@ class TwoQubitWeylDecomposition:
     calculated_fidelity: float  # Fidelity after specialization
 
     _specializations = two_qubit_decompose.Specialization
+    # The specialization that the subclass of a specialization forces, or ``None`` for the base.
+    _class_specialization: two_qubit_decompose.Specialization | None = None
+
+    def __new__(
+        cls,
+        unitary_matrix: np.ndarray | None = None,
+        fidelity: float | None = 1.0 - 1.0e-9,
+        *,
+        _specialization: two_qubit_decompose.Specialization | None = None,
+    ):
+        if unitary_matrix is None:
+            # Unpickling and copying create the instance without arguments, then set its state.
+            return super().__new__(cls)
+        if _specialization is None:
+            _specialization = cls._class_specialization
+        inner = two_qubit_decompose.TwoQubitWeylDecomposition(
+            np.asarray(unitary_matrix, dtype=complex),
+            fidelity=fidelity,
+            _specialization=_specialization,
+        )
+        instance = super().__new__(_SPECIALIZED_CLASSES[inner.specialization.name])
+        instance._inner_decomposition = inner
+        if not isinstance(instance, cls):
+            # Python only calls `__init__` on instances of `cls`.
+            instance.__init__(unitary_matrix, fidelity)
+        return instance
 
     def __init__(
         self,
@@ -188,10 +218,8 @@ class TwoQubitWeylDecomposition:
         *,
         _specialization: two_qubit_decompose.Specialization | None = None,
     ):
+        del _specialization  # Used by `__new__`.
         unitary_matrix = np.asarray(unitary_matrix, dtype=complex)
-        self._inner_decomposition = two_qubit_decompose.TwoQubitWeylDecomposition(
-            unitary_matrix, fidelity=fidelity, _specialization=_specialization
-        )
         self.a = self._inner_decomposition.a
         self.b = self._inner_decomposition.b
         self.c = self._inner_decomposition.c
@@ -238,6 +266,12 @@ class TwoQubitWeylDecomposition:
             getattr(circ, name)(*params, *qubits)
         return circ
 
+    def k_matrices_and_closeness(self, atol: float = 1e-12) -> tuple[np.ndarray, dict[str, bool]]:
+        """Return ``K1l``, ``K1r``, ``K2l`` and ``K2r`` stacked into one ``(4, 2, 2)`` array, and
+        flags of which Weyl coordinates are within ``atol`` of special values, such as
+        ``c_is_pi4`` or ``a_equals_b``."""
+        return self._inner_decomposition.k_matrices_and_closeness(atol)
+
     def actual_fidelity(self, **kwargs) -> float:
         """Calculates the actual fidelity of the decomposed circuit to the input unitary."""
         circ = self.circuit(**kwargs)
@@ -294,6 +328,30 @@ class TwoQubitWeylDecomposition:
         return f"{pre}{circ_indent}\n)"
 
 
+def _specialized_subclass(
+    specialization: two_qubit_decompose.Specialization,
+) -> Type[TwoQubitWeylDecomposition]:
+    """The subclass of :class:`.TwoQubitWeylDecomposition` for the decompositions with
+    ``specialization``, which forces it when constructed directly."""
+    return type(
+        f"TwoQubitWeyl{specialization.name}",
+        (TwoQubitWeylDecomposition,),
+        {
+            "__module__": __name__,
+            "__doc__": f"A :class:`.TwoQubitWeylDecomposition` with the specialization "
+            f"``{specialization.name}``.",
+            "_class_specialization": specialization,
+        },
+    )
+
+
+_SPECIALIZED_CLASSES = {
+    specialization.name: _specialized_subclass(specialization)
+    for specialization in two_qubit_decompose.Specialization._variants()
+}
+globals().update({cls.__name__: cls for cls in _SPECIALIZED_CLASSES.values()})
+
+
 class TwoQubitControlledUDecomposer:
     r"""Decompose two-qubit unitary in terms of a desired
     :math:`U \sim U_d(\alpha, 0, 0) \sim \text{Ctrl-U}`
@@ -17,6 +17,8 @@
 from __future__ import annotations
 import numpy as np
 
+from qiskit._accelerate import weyl as weyl_rs
+
 # "Magic" basis used for the Weyl decomposition. The basis and its adjoint are stored individually
 # unnormalized, but such that their matrix multiplication is still the identity.  This is because
 # they are only used in unitary transformations (so it's safe to do so), and `sqrt(0.5)` is not
@@ -55,43 +57,4 @@ def weyl_coordinates(U: np.ndarray) -> np.ndarray:
     Returns:
         np.ndarray: Array of the 3 Weyl coordinates.
     """
-    import scipy.linalg as la
-
-    pi2 = np.pi / 2
-    pi4 = np.pi / 4
-
-    U = U / la.det(U) ** (0.25)
-    Up = transform_to_magic_basis(U, reverse=True)
-    # We only need the eigenvalues of `M2 = Up.T @ Up` here, not the full diagonalization.
-    D = la.eigvals(Up.T @ Up)
-    d = -np.angle(D) / 2
-    d[3] = -d[0] - d[1] - d[2]
-    cs = np.mod((d[:3] + d[3]) / 2, 2 * np.pi)
-
-    # Reorder the eigenvalues to get in the Weyl chamber
-    cstemp = np.mod(cs, pi2)
-    np.minimum(cstemp, pi2 - cstemp, cstemp)
-    order = np.argsort(cstemp)[[1, 2, 0]]
-    cs = cs[order]
-    d[:3] = d[order]
-
-    # Flip into Weyl chamber
-    if cs[0] > pi2:
-        cs[0] -= 3 * pi2
-    if cs[1] > pi2:
-        cs[1] -= 3 * pi2
-    conjs = 0
-    if cs[0] > pi4:
-        cs[0] = pi2 - cs[0]
-        conjs += 1
-    if cs[1] > pi4:
-        cs[1] = pi2 - cs[1]
-        conjs += 1
-    if cs[2] > pi2:
-        cs[2] -= 3 * pi2
-    if conjs == 1:
-        cs[2] = pi2 - cs[2]
-    if cs[2] > pi4:
-        cs[2] -= pi2
-
-    return cs[[1, 0, 2]]
+    return np.array(weyl_rs.weyl_coordinates(np.asarray(U, dtype=complex)))
@@ -30,6 +30,7 @@ from qiskit.circuit.library.standard_gates import CXGate
 from qiskit.circuit.library.generalized_gates.uc_pauli_rot import UCPauliRotGate, _EPS
 from qiskit.circuit.library.generalized_gates.ucry import UCRYGate
 from qiskit.circuit.library.generalized_gates.ucrz import UCRZGate
+from qiskit._accelerate.synthesis import cossin, qs_decomposition as qs_decomposition_rs
 
 
 def qs_decomposition(
@@ -97,6 +98,11 @@ def qs_decomposition(
     nqubits = int(math.log2(dim))
     if np.allclose(np.identity(dim), mat):
         return QuantumCircuit(nqubits)
+    if decomposer_1q is None and decomposer_2q is None and _depth == 0:
+        data, global_phase = qs_decomposition_rs(
+            np.asarray(mat, dtype=complex), opt_a1=opt_a1, opt_a2=opt_a2
+        )
+        return QuantumCircuit._from_circuit_data(data, global_phase=global_phase)
     if dim == 2:
         if decomposer_1q is None:
             decomposer_1q = one_qubit_decompose.OneQubitEulerDecomposer()
@@ -121,9 +127,8 @@ def qs_decomposition(
     else:
         qr = QuantumRegister(nqubits)
         circ = QuantumCircuit(qr)
-        dim_o2 = dim // 2
         # perform cosine-sine decomposition
-        (u1, u2), vtheta, (v1h, v2h) = scipy.linalg.cossin(mat, separate=True, p=dim_o2, q=dim_o2)
+        (u1, u2), vtheta, (v1h, v2h) = cossin(np.asarray(mat, dtype=complex))
         # left circ
         left_circ = _demultiplex(v1h, v2h, opt_a1=opt_a1, opt_a2=opt_a2, _depth=_depth)
         circ.append(left_circ.to_instruction(), qr)
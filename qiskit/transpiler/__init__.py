@@ -1251,6 +1251,7 @@ Exceptions
 .. autoexception:: TranspilerAccessError
 .. autoexception:: CouplingError
 .. autoexception:: LayoutError
+.. autoexception:: RoutingError
 .. autoexception:: CircuitTooWideForTarget
 .. autoexception:: InvalidLayoutError
 
@@ -1270,6 +1271,7 @@ from .exceptions import (
     TranspilerAccessError,
     CouplingError,
     LayoutError,
+    RoutingError,
     CircuitTooWideForTarget,
     InvalidLayoutError,
 )
@@ -18,6 +18,7 @@ from abc import abstractmethod
 from collections.abc import Callable, Hashable, Iterable
 from inspect import signature
 
+from qiskit._accelerate.pass_loop import CircuitHandle
 from qiskit.circuit import QuantumCircuit
 from qiskit.converters import circuit_to_dag, dag_to_circuit
 from qiskit.dagcircuit import DAGCircuit
@@ -98,6 +99,43 @@ class BasePass(GenericPass, metaclass=MetaPass):
         """
         raise NotImplementedError
 
+    def execute(
+        self,
+        passmanager_ir: PassManagerIR,
+        state: PassManagerState,
+        callback: Callable = None,
+    ) -> tuple[PassManagerIR, PassManagerState]:
+        self.property_set = state.property_set
+        if isinstance(passmanager_ir, CircuitHandle) and not self._can_run_on_circuit_handle(None):
+            # The DAG is handed on to the next tasks, so it's only converted once.
+            passmanager_ir = passmanager_ir.to_dag()
+        return super().execute(passmanager_ir=passmanager_ir, state=state, callback=callback)
+
+    def _run_in_workflow(self, passmanager_ir: PassManagerIR) -> PassManagerIR:
+        if isinstance(passmanager_ir, CircuitHandle):
+            if self._can_run_on_circuit_handle(None):
+                return self._run_on_circuit_handle(passmanager_ir)
+            passmanager_ir = passmanager_ir.to_dag()
+        elif self._can_run_on_circuit_handle(passmanager_ir):
+            return self._run_on_circuit_handle(CircuitHandle.from_dag(passmanager_ir))
+        return self.run(passmanager_ir)
+
+    def _can_run_on_circuit_handle(self, dag: DAGCircuit | None) -> bool:
+        """Whether the pass can run in Rust on a :class:`.CircuitHandle` of the circuit.
+
+        Within a pass manager, the passes that can run on a handle hand the circuit on as one, so
+        it's only converted to a :class:`.DAGCircuit` for the passes that need one.
+
+        Args:
+            dag: the circuit, or ``None`` if it's already a handle.  Handles never contain
+                control flow.
+        """
+        return False
+
+    def _run_on_circuit_handle(self, handle: CircuitHandle) -> CircuitHandle:
+        """Run the pass on a handle of the circuit, if :meth:`_can_run_on_circuit_handle`."""
+        raise NotImplementedError
+
     @property
     def is_transformation_pass(self):
         """Check if the pass is a transformation pass.
@@ -200,9 +238,11 @@ class TransformationPass(BasePass):  # pylint: disable=abstract-method
 
         if state.workflow_status.previous_run == RunState.SUCCESS:
             if isinstance(new_dag, DAGCircuit):
-                # Copy calibration data from the original program
-                new_dag.calibrations = passmanager_ir.calibrations
-            else:
+                # Copy calibration data from the original program.  A handle keeps the
+                # calibrations of its circuit, and restores them when it's converted.
+                if isinstance(passmanager_ir, DAGCircuit):
+                    new_dag.calibrations = passmanager_ir.calibrations
+            elif not isinstance(new_dag, CircuitHandle):
                 raise TranspilerError(
                     "Transformation passes should return a transformed dag."
                     f"The pass {self.__class__.__name__} is returning a {type(new_dag)}"
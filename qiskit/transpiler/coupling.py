@@ -26,6 +26,7 @@ import rustworkx as rx
 from rustworkx.visualization import graphviz_draw
 
 from qiskit.transpiler.exceptions import CouplingError
+from qiskit._accelerate.coupling_map import CouplingMap as RustCouplingMap
 
 
 class CouplingMap:
@@ -41,6 +42,7 @@ class CouplingMap:
         "description",
         "graph",
         "_dist_matrix",
+        "_rust_coupling_map",
         "_qubit_list",
         "_size",
         "_is_symmetric",
@@ -62,6 +64,8 @@ class CouplingMap:
         self.graph = rx.PyDiGraph()
         # a dict of dicts from node pairs to distances
         self._dist_matrix = None
+        # the Rust-space copy of the graph that the Rust passes use
+        self._rust_coupling_map = None
         # a sorted list of physical qubits (integers) in this coupling map
         self._qubit_list = None
         # number of qubits in the graph
@@ -105,6 +109,7 @@ class CouplingMap:
             )
         self.graph.add_node(physical_qubit)
         self._dist_matrix = None  # invalidate
+        self._rust_coupling_map = None  # invalidate
         self._qubit_list = None  # invalidate
         self._size = None  # invalidate
 
@@ -121,6 +126,7 @@ class CouplingMap:
             self.add_physical_qubit(dst)
         self.graph.add_edge(src, dst, None)
         self._dist_matrix = None  # invalidate
+        self._rust_coupling_map = None  # invalidate
         self._is_symmetric = None  # invalidate
 
     @property
@@ -162,17 +168,26 @@ class CouplingMap:
     def compute_distance_matrix(self):
         """Compute the full distance matrix on pairs of nodes.
 
-        The distance map self._dist_matrix is computed from the graph using
-        all_pairs_shortest_path_length. This is normally handled internally
+        The distance map self._dist_matrix is computed from the graph by a
+        breadth-first search from each qubit in Rust. This is normally handled internally
         by the :attr:`~qiskit.transpiler.CouplingMap.distance_matrix`
         attribute or the :meth:`~qiskit.transpiler.CouplingMap.distance` method
         but can be called if you're accessing the distance matrix outside of
         those or want to pre-generate it.
         """
         if self._dist_matrix is None:
-            self._dist_matrix = rx.digraph_distance_matrix(
-                self.graph, as_undirected=True, null_value=math.inf
-            )
+            self._dist_matrix = self._get_rust_coupling_map().distance_matrix()
+
+    def _get_rust_coupling_map(self):
+        """Get the Rust-space copy of this coupling map, which the Rust layout and routing
+        passes take in place of its adjacency and distance matrices.
+
+        The copy is cached with its distance matrix, and rebuilt after the graph is modified
+        through the methods of this class.
+        """
+        if self._rust_coupling_map is None:
+            self._rust_coupling_map = RustCouplingMap(len(self.graph), self.graph.edge_list())
+        return self._rust_coupling_map
 
     def distance(self, physical_qubit1, physical_qubit2):
         """Returns the undirected distance between physical_qubit1 and physical_qubit2.
@@ -240,6 +255,7 @@ class CouplingMap:
             if (dest, src) not in edge_set:
                 self.graph.add_edge(dest, src, None)
         self._dist_matrix = None  # invalidate
+        self._rust_coupling_map = None  # invalidate
         self._is_symmetric = None  # invalidate
 
     def _check_symmetry(self):
@@ -13,7 +13,7 @@
 """
 Exception for errors raised by the transpiler.
 """
-from qiskit.exceptions import QiskitError
+from qiskit.exceptions import QiskitError, QiskitRustError
 from qiskit.passmanager.exceptions import PassManagerError
 
 
@@ -38,12 +38,12 @@ class CouplingError(QiskitError):
         return repr(self.msg)
 
 
-class LayoutError(QiskitError):
-    """Errors raised by the layout object."""
+class LayoutError(TranspilerError, QiskitRustError):
+    """Errors raised by the layout object, or when a circuit can't be laid out on a device."""
 
-    def __init__(self, *msg):
+    def __init__(self, *msg, pass_name=None, qubits=None, tolerance=None):
         """Set the error message."""
-        super().__init__(*msg)
+        super().__init__(*msg, pass_name=pass_name, qubits=qubits, tolerance=tolerance)
         self.msg = " ".join(msg)
 
     def __str__(self):
@@ -51,6 +51,10 @@ class LayoutError(QiskitError):
         return repr(self.msg)
 
 
+class RoutingError(TranspilerError, QiskitRustError):
+    """Error raised when a circuit can't be routed on a device."""
+
+
 class CircuitTooWideForTarget(TranspilerError):
     """Error raised if the circuit is too wide for the target."""
 
@@ -26,6 +26,7 @@ Layout Selection (Placement)
    SetLayout
    TrivialLayout
    DenseLayout
+   NoiseAdaptiveLayout
    SabreLayout
    CSPLayout
    VF2Layout
@@ -191,6 +192,7 @@ Additional Passes
 from .layout import SetLayout
 from .layout import TrivialLayout
 from .layout import DenseLayout
+from .layout import NoiseAdaptiveLayout
 from .layout import SabreLayout
 from .layout import CSPLayout
 from .layout import VF2Layout
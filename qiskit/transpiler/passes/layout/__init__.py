@@ -15,6 +15,7 @@
 from .set_layout import SetLayout
 from .trivial_layout import TrivialLayout
 from .dense_layout import DenseLayout
+from .noise_adaptive_layout import NoiseAdaptiveLayout
 from .sabre_layout import SabreLayout
 from .csp_layout import CSPLayout
 from .vf2_layout import VF2Layout
@@ -14,7 +14,6 @@
 
 
 import numpy as np
-import rustworkx
 
 from qiskit.transpiler.layout import Layout
 from qiskit.transpiler.basepasses import AnalysisPass
@@ -36,18 +35,32 @@ class DenseLayout(AnalysisPass):
         by being set in ``property_set``.
     """
 
-    def __init__(self, coupling_map=None, backend_prop=None, target=None):
+    def __init__(
+        self,
+        coupling_map=None,
+        backend_prop=None,
+        target=None,
+        variances=None,
+        uncertainty_weight=0.0,
+    ):
         """DenseLayout initializer.
 
         Args:
             coupling_map (Coupling): directed graph representing a coupling map.
             backend_prop (BackendProperties): backend properties object
             target (Target): A target representing the target backend.
+            variances (ErrorMap): An error map whose entries carry the variances of the error
+                rates of the backend, as set with :meth:`.ErrorMap.add_error`.
+            uncertainty_weight (float): The number of standard deviations of ``variances`` to
+                raise each error rate by when scoring subsets, so that qubits whose error rates
+                were measured noisily are avoided.
         """
         super().__init__()
         self.coupling_map = coupling_map
         self.backend_prop = backend_prop
         self.target = target
+        self.variances = variances
+        self.uncertainty_weight = uncertainty_weight
         self.adjacency_matrix = None
         if target is not None:
             self.coupling_map = target.build_coupling_map()
@@ -121,7 +134,6 @@ class DenseLayout(AnalysisPass):
         if num_qubits == 0:
             return []
 
-        adjacency_matrix = rustworkx.adjacency_matrix(coupling_map.graph)
         reverse_index_map = {v: k for k, v in enumerate(coupling_map.graph.nodes())}
 
         error_mat, use_error = _build_error_matrix(
@@ -131,15 +143,23 @@ class DenseLayout(AnalysisPass):
             coupling_map=self.coupling_map,
             target=self.target,
         )
+        variance_mat = None
+        if self.variances is not None and self.uncertainty_weight:
+            variance_mat = np.zeros((coupling_map.size(), coupling_map.size()))
+            for (qubit_a, qubit_b), variance in self.variances.variances().items():
+                if qubit_a in reverse_index_map and qubit_b in reverse_index_map:
+                    variance_mat[reverse_index_map[qubit_a]][reverse_index_map[qubit_b]] = variance
 
         rows, cols, best_map = best_subset(
             num_qubits,
-            adjacency_matrix,
+            coupling_map._get_rust_coupling_map(),
             num_meas,
             num_cx,
             use_error,
             coupling_map.is_symmetric,
             error_mat,
+            variance_mat,
+            self.uncertainty_weight,
         )
         data = [1] * len(rows)
         sp_sub_graph = coo_matrix((data, (rows, cols)), shape=(num_qubits, num_qubits)).tocsr()
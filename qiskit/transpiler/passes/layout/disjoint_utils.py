@@ -13,7 +13,7 @@
 """This module contains common utils for disjoint coupling maps."""
 from __future__ import annotations
 from collections import defaultdict
-from typing import List, Callable, TypeVar, Dict, Union
+from typing import List, Callable, TypeVar, Dict, Tuple, Union
 import uuid
 
 import rustworkx as rx
@@ -26,6 +26,8 @@ from qiskit.transpiler.coupling import CouplingMap
 from qiskit.transpiler.target import Target
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.passes.layout import vf2_utils
+from qiskit._accelerate.disjoint_components import split_disjoint_components
+from qiskit._accelerate.error_map import ErrorMap
 
 T = TypeVar("T")
 
@@ -36,8 +38,10 @@ def run_pass_over_connected_components(
     run_func: Callable[[DAGCircuit, CouplingMap], T],
 ) -> List[T]:
     """Run a transpiler pass inner function over mapped components."""
+    error_map = None
     if isinstance(components_source, Target):
         coupling_map = components_source.build_coupling_map(filter_idle_qubits=True)
+        error_map = vf2_utils.build_average_error_map(components_source, None, coupling_map)
     else:
         coupling_map = components_source
     cmap_components = coupling_map.connected_components()
@@ -49,30 +53,63 @@ def run_pass_over_connected_components(
                 "components in the coupling map."
             )
         return [run_func(dag, cmap_components[0])]
-    dag_components = separate_dag(dag)
-    mapped_components = map_components(dag_components, cmap_components)
-    out_component_pairs = []
-    for cmap_index, dags in mapped_components.items():
-        # Take the first dag from the mapped dag components and then
-        # compose it with any other dag components that are operating on the
-        # same coupling map connected component. This results in a subcircuit
-        # of possibly disjoint circuit components which we will run the layout
-        # pass on.
-        out_dag = dag_components[dags.pop()]
-        for dag_index in dags:
-            dag = dag_components[dag_index]
-            out_dag.add_qubits(dag.qubits)
-            out_dag.add_clbits(dag.clbits)
-            for qreg in dag.qregs:
-                out_dag.add_qreg(qreg)
-            for creg in dag.cregs:
-                out_dag.add_creg(creg)
-            out_dag.compose(dag, qubits=dag.qubits, clbits=dag.clbits)
-        out_component_pairs.append((out_dag, cmap_components[cmap_index]))
+    out_component_pairs = split_dag_over_components(dag, coupling_map, error_map)
     res = [run_func(out_dag, cmap) for out_dag, cmap in out_component_pairs]
     return res
 
 
+def split_dag_over_components(
+    dag: DAGCircuit, coupling_map: CouplingMap, error_map: ErrorMap | None = None
+) -> List[Tuple[DAGCircuit, CouplingMap]]:
+    """Split a dag into the subcircuits to lay out on each connected component of a coupling map.
+
+    The qubits of each connected component of the dag's interaction graph are placed together on
+    one component of the coupling map, the largest groups first, each on the component with the
+    lowest average error in ``error_map`` that has room for it.  Qubits with no operations are
+    left out of the subcircuits.
+
+    Returns:
+        A list of pairs of the subcircuit of the qubits placed on a component of the coupling map
+        and the coupling map of that component.  The data payload of each node in the graph of the
+        component is the index of the qubit in ``coupling_map``, as for
+        :meth:`.CouplingMap.connected_components`.
+
+    Raises:
+        TranspilerError: if a connected component of the dag fits on no component of the coupling
+            map.
+    """
+    # Split barriers into single qubit barriers before splitting connected components
+    split_barriers(dag)
+    qubit_indices = {bit: index for index, bit in enumerate(dag.qubits)}
+    # The qubits with operations, in the order they're passed to Rust.
+    active = {}
+    edges = set()
+    for node in dag.op_nodes(include_directives=False):
+        qargs = [active.setdefault(qubit_indices[bit], len(active)) for bit in node.qargs]
+        edges.update(zip(qargs, qargs[1:]))
+    active_qubits = list(active)
+    problems = split_disjoint_components(
+        coupling_map._get_rust_coupling_map(),
+        len(active_qubits),
+        list(edges),
+        error_map,
+        physical_qubits=list(coupling_map.graph.node_indices()),
+    )
+    # Set payload to index
+    for node in coupling_map.graph.node_indices():
+        coupling_map.graph[node] = node
+    out = []
+    for virtual_qubits, physical_qubits in problems:
+        component_cmap = CouplingMap()
+        component_cmap.graph = coupling_map.graph.subgraph(physical_qubits)
+        dag_qubits = {dag.qubits[active_qubits[qubit]] for qubit in virtual_qubits}
+        out.append((_restrict_dag(dag, dag_qubits), component_cmap))
+    # Reverse split barriers on input dag to avoid leaking out internal transformations as
+    # part of splitting
+    combine_barriers(dag, retain_uuid=False)
+    return out
+
+
 def map_components(
     dag_components: List[DAGCircuit], cmap_components: List[CouplingMap]
 ) -> Dict[int, List[int]]:
@@ -193,25 +230,26 @@ def separate_dag(dag: DAGCircuit) -> List[DAGCircuit]:
     for component in connected_components:
         component_qubits.append({qubit_map[x] for x in component})
 
-    qubits = set(dag.qubits)
-
-    decomposed_dags = []
-    for dag_qubits in component_qubits:
-        new_dag = dag.copy_empty_like()
-        new_dag.remove_qubits(*qubits - dag_qubits)
-        new_dag.global_phase = 0
-        for node in dag.topological_op_nodes():
-            if dag_qubits.issuperset(node.qargs):
-                new_dag.apply_operation_back(node.op, node.qargs, node.cargs, check=False)
-        idle_clbits = []
-        for bit, node in new_dag.input_map.items():
-            succ_node = next(new_dag.successors(node))
-            if isinstance(succ_node, DAGOutNode) and isinstance(succ_node.wire, Clbit):
-                idle_clbits.append(bit)
-        new_dag.remove_clbits(*idle_clbits)
-        combine_barriers(new_dag)
-        decomposed_dags.append(new_dag)
+    decomposed_dags = [_restrict_dag(dag, dag_qubits) for dag_qubits in component_qubits]
     # Reverse split barriers on input dag to avoid leaking out internal transformations as
     # part of splitting
     combine_barriers(dag, retain_uuid=False)
     return decomposed_dags
+
+
+def _restrict_dag(dag: DAGCircuit, dag_qubits: set[Qubit]) -> DAGCircuit:
+    """The part of a dag with split barriers that acts on ``dag_qubits``."""
+    new_dag = dag.copy_empty_like()
+    new_dag.remove_qubits(*set(dag.qubits) - dag_qubits)
+    new_dag.global_phase = 0
+    for node in dag.topological_op_nodes():
+        if dag_qubits.issuperset(node.qargs):
+            new_dag.apply_operation_back(node.op, node.qargs, node.cargs, check=False)
+    idle_clbits = []
+    for bit, node in new_dag.input_map.items():
+        succ_node = next(new_dag.successors(node))
+        if isinstance(succ_node, DAGOutNode) and isinstance(succ_node.wire, Clbit):
+            idle_clbits.append(bit)
+    new_dag.remove_clbits(*idle_clbits)
+    combine_barriers(new_dag)
+    return new_dag
@@ -0,0 +1,91 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Choose a noise-adaptive Layout based on the error rates of the backend."""
+
+from collections import defaultdict
+import itertools
+
+from qiskit.transpiler.layout import Layout
+from qiskit.transpiler.basepasses import AnalysisPass
+from qiskit.transpiler.exceptions import TranspilerError
+from qiskit.transpiler.passes.layout import vf2_utils
+from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.noise_adaptive_layout import noise_adaptive_layout
+
+
+class NoiseAdaptiveLayout(AnalysisPass):
+    """Choose a noise-adaptive Layout based on the error rates of the backend.
+
+    This pass associates a physical qubit (int) to each virtual qubit of the circuit (Qubit),
+    greedily maximizing the product of the reliabilities of the two-qubit interactions of the
+    circuit and of the qubits it uses. The reliability of an interaction between qubits that
+    are not coupled is that of the most reliable chain of swaps between them. The method is
+    described in Murali et al., "Noise-Adaptive Compiler Mappings for Noisy Intermediate-Scale
+    Quantum Computers" (ASPLOS 2019).
+
+    Unlike :class:`.VF2Layout`, this pass always finds a layout in polynomial time, whatever the
+    shape of the interaction graph, but the layout may need routing even if a perfect one exists.
+
+    Interactions of gates on more than two qubits are counted between each pair of their qubits.
+    If the backend has no error rates, the errors are estimated from the degrees of the qubits
+    as for :class:`.VF2Layout`.
+    """
+
+    def __init__(self, coupling_map=None, properties=None, target=None):
+        """NoiseAdaptiveLayout initializer.
+
+        Args:
+            coupling_map (CouplingMap): directed graph representing a coupling map.
+            properties (BackendProperties): the error rates of the backend.
+            target (Target): a target representing the backend. If specified, it supersedes
+                ``coupling_map`` and ``properties``.
+        """
+        super().__init__()
+        self.target = target
+        if target is not None:
+            self.coupling_map = target.build_coupling_map()
+        else:
+            self.coupling_map = coupling_map
+        self.properties = properties
+
+    def run(self, dag):
+        """Run the NoiseAdaptiveLayout pass on `dag`.
+
+        Args:
+            dag (DAGCircuit): DAG to find layout for.
+
+        Raises:
+            TranspilerError: if there is no coupling map or the dag is wider than the device.
+        """
+        if self.coupling_map is None:
+            raise TranspilerError("coupling_map or target must be specified.")
+        error_map = vf2_utils.build_average_error_map(
+            self.target, self.properties, self.coupling_map
+        )
+        if error_map is None:
+            error_map = ErrorMap(0)
+        qubit_indices = {bit: index for index, bit in enumerate(dag.qubits)}
+        interactions = defaultdict(int)
+        for node in dag.op_nodes(include_directives=False):
+            qubits = sorted(qubit_indices[bit] for bit in node.qargs)
+            for pair in itertools.combinations(qubits, 2):
+                interactions[pair] += 1
+        physical_qubits = noise_adaptive_layout(
+            self.coupling_map._get_rust_coupling_map(),
+            error_map,
+            len(dag.qubits),
+            list(interactions.items()),
+        )
+        self.property_set["layout"] = Layout(dict(zip(dag.qubits, physical_qubits)))
+        for qreg in dag.qregs.values():
+            self.property_set["layout"].add_register(qreg)
@@ -20,7 +20,6 @@ import functools
 import time
 
 import numpy as np
-import rustworkx as rx
 
 from qiskit.converters import dag_to_circuit
 from qiskit.circuit import QuantumRegister
@@ -29,18 +28,24 @@ from qiskit.transpiler.passes.layout.set_layout import SetLayout
 from qiskit.transpiler.passes.layout.full_ancilla_allocation import FullAncillaAllocation
 from qiskit.transpiler.passes.layout.enlarge_with_ancilla import EnlargeWithAncilla
 from qiskit.transpiler.passes.layout.apply_layout import ApplyLayout
-from qiskit.transpiler.passes.layout import disjoint_utils
+from qiskit.transpiler.passes.layout import disjoint_utils, vf2_utils
 from qiskit.transpiler.passmanager import PassManager
 from qiskit.transpiler.layout import Layout
 from qiskit.transpiler.basepasses import TransformationPass
 from qiskit.transpiler.exceptions import TranspilerError
+from qiskit._accelerate.error_map import ErrorMap
 from qiskit._accelerate.nlayout import NLayout
+from qiskit._accelerate.rng import entropy_seed
 from qiskit._accelerate.sabre import (
     sabre_layout_and_routing,
     Heuristic,
-    NeighborTable,
 )
-from qiskit.transpiler.passes.routing.sabre_swap import _build_sabre_dag, _apply_sabre_result
+from qiskit.transpiler.passes.routing.sabre_swap import (
+    _build_sabre_dag,
+    _apply_sabre_result,
+    _choose_from_pareto_front,
+    _objective_weights,
+)
 from qiskit.transpiler.target import Target
 from qiskit.transpiler.coupling import CouplingMap
 from qiskit.utils.parallel import CPU_COUNT
@@ -119,6 +124,8 @@ class SabreLayout(TransformationPass):
         swap_trials=None,
         layout_trials=None,
         skip_routing=False,
+        objective_weights=None,
+        pareto_front=False,
     ):
         """SabreLayout initializer.
 
@@ -131,7 +138,9 @@ class SabreLayout(TransformationPass):
                 exclusive with the ``swap_trials`` and the ``layout_trials``
                 arguments and if this is specified at the same time as either
                 argument an error will be raised.
-            seed (int): seed for setting a random first trial layout.
+            seed (int): seed for setting a random first trial layout.  Seeds of up to 128 bits
+                are accepted.  The seed that was used, including one drawn when this is
+                ``None``, is stored in the ``sabre_layout_seed`` field of the property set.
             max_iterations (int): number of forward-backward iterations.
             swap_trials (int): The number of trials to run of
                 :class:`~.SabreSwap` for each iteration. This is equivalent to
@@ -155,10 +164,23 @@ class SabreLayout(TransformationPass):
                 will be set in the property set. This is a tradeoff to run custom
                 routing with multiple layout trials, as using this option will cause
                 SabreLayout to run the routing stage internally but not use that result.
+            objective_weights (dict[str, float]): the weights of the objectives that the swap
+                trials and the layout trials are scored on, as the ``objective_weights`` argument
+                on :class:`~.SabreSwap`.  If not specified, the trial with the fewest swaps wins.
+                This has no effect with ``routing_pass``.
+            pareto_front (bool): if true, the objectives of the layout trials that no other layout
+                trial is at least as good as on every objective and better on one are stored in
+                the ``sabre_layout_pareto_front`` field of the property set, as a list with one
+                Pareto set for each connected component of the coupling map that the circuit is
+                laid out on.  Each Pareto set is a list of dictionaries keyed like
+                ``objective_weights``, in the order of the trials, and the layout chosen is that of
+                the first of these trials with the least weighted sum.  This has no effect with
+                ``routing_pass``.
 
         Raises:
             TranspilerError: If both ``routing_pass`` and ``swap_trials`` or
-            both ``routing_pass`` and ``layout_trials`` are specified
+            both ``routing_pass`` and ``layout_trials`` are specified, or if
+            ``objective_weights`` has an unknown objective.
         """
         super().__init__()
         if isinstance(coupling_map, Target):
@@ -167,7 +189,6 @@ class SabreLayout(TransformationPass):
         else:
             self.target = None
             self.coupling_map = coupling_map
-        self._neighbor_table = None
         if routing_pass is not None and (swap_trials is not None or layout_trials is not None):
             raise TranspilerError("Both routing_pass and swap_trials can't be set at the same time")
         self.routing_pass = routing_pass
@@ -183,6 +204,8 @@ class SabreLayout(TransformationPass):
         else:
             self.layout_trials = layout_trials
         self.skip_routing = skip_routing
+        self.objective_weights = _objective_weights(objective_weights)
+        self.pareto_front = pareto_front
         if self.coupling_map is not None:
             if not self.coupling_map.is_symmetric:
                 # deepcopy is needed here if we don't own the coupling map (i.e. we were passed it
@@ -191,7 +214,6 @@ class SabreLayout(TransformationPass):
                 if isinstance(coupling_map, CouplingMap):
                     self.coupling_map = copy.deepcopy(self.coupling_map)
                 self.coupling_map.make_symmetric()
-            self._neighbor_table = NeighborTable(rx.adjacency_matrix(self.coupling_map.graph))
 
     def run(self, dag):
         """Run the SabreLayout pass on `dag`.
@@ -261,12 +283,18 @@ class SabreLayout(TransformationPass):
             target.make_symmetric()
         else:
             target = self.coupling_map
-        inner_run = self._inner_run
+        seed = entropy_seed() if self.seed is None else self.seed
+        self.property_set["sabre_layout_seed"] = seed
+        inner_run = functools.partial(self._inner_run, seed=seed)
         if "sabre_starting_layouts" in self.property_set:
             inner_run = functools.partial(
-                self._inner_run, starting_layouts=self.property_set["sabre_starting_layouts"]
+                inner_run, starting_layouts=self.property_set["sabre_starting_layouts"]
             )
         components = disjoint_utils.run_pass_over_connected_components(dag, target, inner_run)
+        if self.pareto_front:
+            self.property_set["sabre_layout_pareto_front"] = [
+                component.pareto_front for component in components
+            ]
         self.property_set["layout"] = Layout(
             {
                 component.dag.qubits[logic]: component.coupling_map.graph[phys]
@@ -363,15 +391,13 @@ class SabreLayout(TransformationPass):
         disjoint_utils.combine_barriers(mapped_dag, retain_uuid=False)
         return mapped_dag
 
-    def _inner_run(self, dag, coupling_map, starting_layouts=None):
+    def _inner_run(self, dag, coupling_map, seed, starting_layouts=None):
         if not coupling_map.is_symmetric:
             # deepcopy is needed here to avoid modifications updating
             # shared references in passes which require directional
             # constraints
             coupling_map = copy.deepcopy(coupling_map)
             coupling_map.make_symmetric()
-        neighbor_table = NeighborTable(rx.adjacency_matrix(coupling_map.graph))
-        dist_matrix = coupling_map.distance_matrix
         original_qubit_indices = {bit: index for index, bit in enumerate(dag.qubits)}
         partial_layouts = []
         if starting_layouts is not None:
@@ -394,17 +420,23 @@ class SabreLayout(TransformationPass):
             original_qubit_indices,
         )
         sabre_start = time.perf_counter()
-        (initial_layout, final_permutation, sabre_result) = sabre_layout_and_routing(
+        result = sabre_layout_and_routing(
             sabre_dag,
-            neighbor_table,
-            dist_matrix,
+            coupling_map._get_rust_coupling_map(),
             Heuristic.Decay,
             self.max_iterations,
             self.swap_trials,
             self.layout_trials,
-            self.seed,
+            seed,
             partial_layouts,
+            error_map=self._component_error_map(coupling_map),
+            objective_weights=self.objective_weights,
+            pareto=self.pareto_front,
         )
+        pareto_front = None
+        if self.pareto_front:
+            pareto_front, result = _choose_from_pareto_front(result, self.objective_weights)
+        (initial_layout, final_permutation, sabre_result) = result
         sabre_stop = time.perf_counter()
         logger.debug(
             "Sabre layout algorithm execution for a connected component complete in: %s sec.",
@@ -417,8 +449,24 @@ class SabreLayout(TransformationPass):
             final_permutation,
             sabre_result,
             circuit_to_dag_dict,
+            pareto_front,
         )
 
+    def _component_error_map(self, coupling_map):
+        """The average error rates of the ``Target`` on the edges of a connected component of the
+        coupling map, in the indices of the component, if the routing objectives need them."""
+        if self.target is None or self.objective_weights is None or not self.objective_weights[2]:
+            return None
+        full_map = vf2_utils.build_average_error_map(self.target, None, None)
+        if full_map is None:
+            return None
+        error_map = ErrorMap(coupling_map.graph.num_edges())
+        for edge in coupling_map.graph.edge_list():
+            full_edge = (coupling_map.graph[edge[0]], coupling_map.graph[edge[1]])
+            if full_edge in full_map:
+                error_map.add_error(edge, full_map[full_edge])
+        return error_map
+
     def _ancilla_allocation_no_pass_manager(self, dag):
         """Run the ancilla-allocation and -enlargment passes on the DAG chained onto our
         ``property_set``, skipping the DAG-to-circuit conversion cost of using a ``PassManager``."""
@@ -470,11 +518,13 @@ class _DisjointComponent:
         "final_permutation",
         "sabre_result",
         "circuit_to_dag_dict",
+        "pareto_front",
     )
 
     dag: DAGCircuit
     coupling_map: CouplingMap
     initial_layout: NLayout
     final_permutation: "list[int]"
-    sabre_result: "tuple[SwapMap, Sequence[int], NodeBlockResults]"
+    sabre_result: "tuple[SwapMap, Sequence[int], NodeBlockResults, dict[int, int]]"
     circuit_to_dag_dict: "dict[int, DAGCircuit]"
+    pareto_front: "list[dict[str, float]] | None"
@@ -14,9 +14,10 @@
 
 import itertools
 
-from qiskit.transpiler import CouplingMap, Target, AnalysisPass, TranspilerError
+from qiskit.transpiler import CouplingMap, Layout, Target, AnalysisPass, TranspilerError
 from qiskit.transpiler.passes.layout.vf2_layout import VF2Layout
 from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.heavy_hex_layout import heavy_hex_layout
 
 
 class SabrePreLayout(AnalysisPass):
@@ -38,6 +39,7 @@ class SabrePreLayout(AnalysisPass):
         max_trials_vf2=100,
         call_limit_vf2=None,
         improve_layout=True,
+        max_steps_heavy_hex=100_000,
     ):
         """SabrePreLayout initializer.
 
@@ -52,6 +54,10 @@ class SabrePreLayout(AnalysisPass):
         is found. This involves removing extra edges and running VF2 to see if an
         isomorphism still exists.
 
+        If the coupling map is a heavy-hex lattice and the two-qubit interactions of the
+        circuit form chains and at most one ring, a search that follows the structure of the
+        lattice is tried first, which usually finds a perfect layout much faster than VF2.
+
         Args:
             coupling_map (Union[CouplingMap, Target]): directed graph representing the
                 original coupling map or a target modelling the backend (including its
@@ -66,6 +72,8 @@ class SabrePreLayout(AnalysisPass):
             improve_layout (bool): whether to improve the layout by minimizing the number of
                 extra edges involved. This might be time-consuming as this requires additional
                 VF2 calls.
+            max_steps_heavy_hex (int): the maximum number of steps of the heavy-hex layout
+                search. Set to ``0`` to skip the search.
 
         Raises:
             TranspilerError: At runtime, if neither ``coupling_map`` or ``target`` are provided.
@@ -76,6 +84,7 @@ class SabrePreLayout(AnalysisPass):
         self.max_trials_vf2 = max_trials_vf2
         self.call_limit_vf2 = call_limit_vf2
         self.improve_layout = improve_layout
+        self.max_steps_heavy_hex = max_steps_heavy_hex
 
         if isinstance(coupling_map, Target):
             self.target = coupling_map
@@ -102,7 +111,11 @@ class SabrePreLayout(AnalysisPass):
                 "CouplingMap or a Target."
             )
 
-        starting_layout = None
+        starting_layout = self._heavy_hex_layout(dag)
+        if starting_layout is not None:
+            self._add_starting_layout(starting_layout)
+            return
+
         cur_distance = 1
         while cur_distance <= self.max_distance:
             augmented_map, augmented_error_map = self._add_extra_edges(cur_distance)
@@ -125,11 +138,36 @@ class SabrePreLayout(AnalysisPass):
             # optionally improve starting layout
             if self.improve_layout:
                 starting_layout = self._minimize_extra_edges(dag, starting_layout)
-            # write discovered layout into the property set
-            if "sabre_starting_layouts" not in self.property_set:
-                self.property_set["sabre_starting_layouts"] = [starting_layout]
-            else:
-                self.property_set["sabre_starting_layouts"].append(starting_layout)
+            self._add_starting_layout(starting_layout)
+
+    def _add_starting_layout(self, starting_layout):
+        """Writes a discovered layout into the property set."""
+        if "sabre_starting_layouts" not in self.property_set:
+            self.property_set["sabre_starting_layouts"] = [starting_layout]
+        else:
+            self.property_set["sabre_starting_layouts"].append(starting_layout)
+
+    def _heavy_hex_layout(self, dag):
+        """Finds a perfect layout with the heavy-hex layout search, if the search applies to the
+        circuit and the coupling map."""
+        if not self.max_steps_heavy_hex:
+            return None
+        qubit_indices = {bit: index for index, bit in enumerate(dag.qubits)}
+        edges = set()
+        for node in dag.op_nodes(include_directives=False):
+            if len(node.qargs) > 2:
+                return None
+            if len(node.qargs) == 2:
+                edges.add(tuple(sorted(qubit_indices[bit] for bit in node.qargs)))
+        physical_qubits = heavy_hex_layout(
+            self.coupling_map._get_rust_coupling_map(),
+            len(dag.qubits),
+            list(edges),
+            self.max_steps_heavy_hex,
+        )
+        if physical_qubits is None:
+            return None
+        return Layout(dict(zip(dag.qubits, physical_qubits)))
 
     def _add_extra_edges(self, distance):
         """Augments the coupling map with extra edges that connect nodes ``distance``
@@ -24,6 +24,8 @@ from qiskit.transpiler.basepasses import AnalysisPass
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.passes.layout import vf2_utils
 
+from qiskit._accelerate.vf2_layout import LayoutSymmetry
+
 
 logger = logging.getLogger(__name__)
 
@@ -71,6 +73,11 @@ class VF2Layout(AnalysisPass):
     error rate for gates that operate on (0, 1) is 0.01 and (1, 0) is not
     supported by the target. This will be used for scoring if it's set as the
     ``vf2_avg_error_map`` key in the property set when :class:`~.VF2Layout` is run.
+
+    Layouts that are the same up to a symmetry of the device which keeps every error rate, such as
+    a reflection of a grid with uniform errors, have the same score, so by default only one
+    layout of each such class is scored and counted as a trial.  The symmetries are found from the
+    error map, or can be given as generating permutations of the physical qubits.
     """
 
     def __init__(
@@ -83,6 +90,8 @@ class VF2Layout(AnalysisPass):
         properties=None,
         max_trials=None,
         target=None,
+        automorphisms=True,
+        uncertainty_weight=0.0,
     ):
         """Initialize a ``VF2Layout`` pass instance
 
@@ -105,6 +114,16 @@ class VF2Layout(AnalysisPass):
             target (Target): A target representing the backend device to run ``VF2Layout`` on.
                 If specified it will supersede a set value for ``properties`` and
                 ``coupling_map``.
+            automorphisms (bool | list[list[int]]): If ``True``, find the symmetries of the
+                device that keep every error rate and skip scoring layouts equivalent to one
+                already scored.  A list of permutations of the physical qubits, as the image of
+                each qubit, is used as generators of the symmetries instead; each must keep the
+                error rates the layouts are scored with.  If ``False``, every layout is scored.
+            uncertainty_weight (float): The number of standard deviations to raise each error rate
+                of the ``vf2_avg_error_map`` by, for the entries that carry a variance, so that
+                layouts avoid qubits whose error rates were measured noisily.  The symmetries of
+                ``automorphisms=True`` don't account for the variances, so they aren't used with a
+                nonzero weight.
 
         Raises:
             TypeError: At runtime, if neither ``coupling_map`` or ``target`` are provided.
@@ -121,6 +140,8 @@ class VF2Layout(AnalysisPass):
         self.call_limit = call_limit
         self.time_limit = time_limit
         self.max_trials = max_trials
+        self.automorphisms = automorphisms
+        self.uncertainty_weight = uncertainty_weight
         self.avg_error_map = None
 
     def run(self, dag):
@@ -173,15 +194,28 @@ class VF2Layout(AnalysisPass):
         chosen_layout_score = None
         start_time = time.time()
         trials = 0
+        symmetry = self._layout_symmetry(len(cm_graph) == len(im_graph))
+        seen_layouts = set()
+        pruned = 0
 
         def mapping_to_layout(layout_mapping):
             return Layout({reverse_im_graph_node_map[k]: v for k, v in layout_mapping.items()})
 
         for mapping in mappings:
+            layout_mapping = {im_i: cm_nodes[cm_i] for cm_i, im_i in mapping.items()}
+            if symmetry is not None:
+                canonical = tuple(
+                    symmetry.canonical([layout_mapping[im_i] for im_i in sorted(layout_mapping)])
+                )
+                if canonical in seen_layouts:
+                    pruned += 1
+                    if self.time_limit is not None and time.time() - start_time >= self.time_limit:
+                        break
+                    continue
+                seen_layouts.add(canonical)
             trials += 1
             logger.debug("Running trial: %s", trials)
             stop_reason = VF2LayoutStopReason.SOLUTION_FOUND
-            layout_mapping = {im_i: cm_nodes[cm_i] for cm_i, im_i in mapping.items()}
 
             # If the graphs have the same number of nodes we don't need to score or do multiple
             # trials as the score heuristic currently doesn't weigh nodes based on gates on a
@@ -203,6 +237,7 @@ class VF2Layout(AnalysisPass):
                 self.strict_direction,
                 edge_list=scoring_edge_list,
                 bit_list=scoring_bit_list,
+                uncertainty_weight=self.uncertainty_weight,
             )
             # If the layout score is 0 we can't do any better and we'll just
             # waste time finding additional mappings that will at best match
@@ -236,6 +271,8 @@ class VF2Layout(AnalysisPass):
                     self.time_limit,
                 )
                 break
+        if pruned:
+            logger.debug("Skipped scoring %s layouts equivalent to scored ones", pruned)
         if chosen_layout is None:
             stop_reason = VF2LayoutStopReason.NO_SOLUTION_FOUND
         else:
@@ -255,3 +292,20 @@ class VF2Layout(AnalysisPass):
                 self.property_set["layout"].add_register(reg)
 
         self.property_set["VF2Layout_stop_reason"] = stop_reason
+
+    def _layout_symmetry(self, same_size):
+        """The symmetries that relate layouts of the same score, or ``None`` if there are none or
+        no layouts will be compared."""
+        if self.automorphisms is False or self.avg_error_map is None or same_size:
+            return None
+        if self.automorphisms is True and self.uncertainty_weight:
+            return None
+        num_qubits = self.coupling_map.size()
+        if self.automorphisms is True:
+            symmetry = LayoutSymmetry.from_error_map(num_qubits, self.avg_error_map)
+        else:
+            symmetry = LayoutSymmetry.from_generators(num_qubits, self.automorphisms)
+        logger.debug("Found %s symmetries of the device", len(symmetry))
+        if len(symmetry) <= 1:
+            return None
+        return symmetry
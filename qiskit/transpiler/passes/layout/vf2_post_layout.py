@@ -109,6 +109,7 @@ class VF2PostLayout(AnalysisPass):
         time_limit=None,
         strict_direction=True,
         max_trials=0,
+        uncertainty_weight=0.0,
     ):
         """Initialize a ``VF2PostLayout`` pass instance
 
@@ -136,6 +137,10 @@ class VF2PostLayout(AnalysisPass):
                 the target set of instructions.
             max_trials (int): The maximum number of trials to run VF2 to find
                 a layout. A value of ``0`` (the default) means 'unlimited'.
+            uncertainty_weight (float): The number of standard deviations to raise each error rate
+                of the ``vf2_avg_error_map`` by, for the entries that carry a variance, when
+                ``strict_direction=False``, so that layouts avoid qubits whose error rates were
+                measured noisily.
 
         Raises:
             TypeError: At runtime, if neither ``coupling_map`` or ``target`` are provided.
@@ -149,6 +154,7 @@ class VF2PostLayout(AnalysisPass):
         self.max_trials = max_trials
         self.seed = seed
         self.strict_direction = strict_direction
+        self.uncertainty_weight = uncertainty_weight
         self.avg_error_map = None
 
     def run(self, dag):
@@ -280,6 +286,7 @@ class VF2PostLayout(AnalysisPass):
                     self.strict_direction,
                     edge_list=scoring_edge_list,
                     bit_list=scoring_bit_list,
+                    uncertainty_weight=self.uncertainty_weight,
                 )
             chosen_layout = initial_layout
             stop_reason = VF2PostLayoutStopReason.NO_BETTER_SOLUTION_FOUND
@@ -315,6 +322,7 @@ class VF2PostLayout(AnalysisPass):
                     self.strict_direction,
                     edge_list=scoring_edge_list,
                     bit_list=scoring_bit_list,
+                    uncertainty_weight=self.uncertainty_weight,
                 )
             logger.debug("Trial %s has score %s", trials, layout_score)
             if layout_score < chosen_layout_score:
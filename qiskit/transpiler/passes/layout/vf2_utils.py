@@ -13,7 +13,6 @@
 """This module contains common utils for vf2 layout passes."""
 
 from collections import defaultdict
-import statistics
 import random
 
 import numpy as np
@@ -126,8 +125,12 @@ def score_layout(
     run_in_parallel=False,
     edge_list=None,
     bit_list=None,
+    uncertainty_weight=0.0,
 ):
-    """Score a layout given an average error map."""
+    """Score a layout given an average error map.
+
+    With a nonzero ``uncertainty_weight``, the error rates of ``avg_error_map`` that carry a
+    variance are raised by that many standard deviations."""
     if layout_mapping:
         size = max(max(layout_mapping), max(layout_mapping.values()))
     else:
@@ -138,7 +141,13 @@ def score_layout(
     if edge_list is None:
         edge_list = build_edge_list(im_graph)
     return vf2_layout.score_layout(
-        bit_list, edge_list, avg_error_map, nlayout, strict_direction, run_in_parallel
+        bit_list,
+        edge_list,
+        avg_error_map,
+        nlayout,
+        strict_direction,
+        run_in_parallel,
+        uncertainty_weight,
     )
 
 
@@ -174,28 +183,21 @@ def build_average_error_map(target, properties, coupling_map):
                 avg_map.add_error(qargs, qarg_error / count)
                 built = True
     elif properties is not None:
-        errors = defaultdict(list)
-        for qubit in range(len(properties.qubits)):
-            errors[(qubit,)].append(properties.readout_error(qubit))
+        readout_errors = [
+            properties.readout_error(qubit) for qubit in range(len(properties.qubits))
+        ]
+        gate_qubits = []
+        gate_errors = []
         for gate in properties.gates:
-            qubits = tuple(gate.qubits)
             for param in gate.parameters:
                 if param.name == "gate_error":
-                    errors[qubits].append(param.value)
-        for k, v in errors.items():
-            if len(k) == 1:
-                qargs = (k[0], k[0])
-            else:
-                qargs = k
-            # If the properties payload contains an index outside the number of qubits
-            # the properties are invalid for the given input. This normally happens either
-            # with a malconstructed properties payload or if the faulty qubits feature of
-            # BackendV1/BackendPropeties is being used. In such cases we map noise characteristics
-            # so we should just treat the mapping as an ideal case.
-            if qargs[0] >= num_qubits or qargs[1] >= num_qubits:
-                continue
-            avg_map.add_error(qargs, statistics.mean(v))
-            built = True
+                    gate_qubits.append(gate.qubits)
+                    gate_errors.append(param.value)
+        # Entries on qubits outside the number of qubits are skipped.  This normally happens either
+        # with a malconstructed properties payload or if the faulty qubits feature of
+        # BackendV1/BackendPropeties is being used, so we just treat those qubits as ideal.
+        avg_map = ErrorMap.from_arrays(num_qubits, readout_errors, gate_qubits, gate_errors)
+        built = len(avg_map) > 0
     # if there are no error rates in the target we should fallback to using the degree heuristic
     # used for a coupling map. To do this we can build the coupling map from the target before
     # running the fallback heuristic
@@ -14,10 +14,14 @@
 """Provides a general transpiler pass for collecting and consolidating blocks of nodes
 in a circuit."""
 
+from qiskit.circuit import CircuitInstruction
+from qiskit.dagcircuit import DAGCircuit
 from qiskit.transpiler.basepasses import TransformationPass
 from qiskit.converters import dag_to_dagdependency, dagdependency_to_dag
 from qiskit.dagcircuit.collect_blocks import BlockCollector, BlockCollapser
 from qiskit.transpiler.passes.utils import control_flow
+from qiskit._accelerate.circuit import CircuitData
+from qiskit._accelerate.collect_blocks import collect_matching_blocks
 
 
 class CollectAndCollapse(TransformationPass):
@@ -95,10 +99,32 @@ def collect_using_filter_function(
     min_block_size,
     split_layers=False,
     collect_from_back=False,
+    gate_names=None,
 ):
     """Corresponds to an important block collection strategy that greedily collects
     maximal blocks of nodes matching a given ``filter_function``.
+
+    If ``filter_function`` only matches the unconditioned operations whose names are in
+    ``gate_names``, passing these names lets the blocks of a :class:`.DAGCircuit` be collected
+    in Rust.
     """
+    if gate_names is not None and isinstance(dag, DAGCircuit):
+        nodes = list(dag.topological_op_nodes())
+        data = CircuitData(
+            dag.qubits,
+            dag.clbits,
+            (CircuitInstruction(node.op, node.qargs, node.cargs) for node in nodes),
+            len(nodes),
+        )
+        blocks = collect_matching_blocks(
+            data,
+            set(gate_names),
+            split_blocks=split_blocks,
+            min_block_size=min_block_size,
+            split_layers=split_layers,
+            collect_from_back=collect_from_back,
+        )
+        return [[nodes[index] for index in block] for block in blocks]
     return BlockCollector(dag).collect_all_matching_blocks(
         filter_fn=filter_function,
         split_blocks=split_blocks,
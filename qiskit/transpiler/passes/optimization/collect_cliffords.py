@@ -59,6 +59,7 @@ class CollectCliffords(CollectAndCollapse):
             min_block_size=min_block_size,
             split_layers=split_layers,
             collect_from_back=collect_from_back,
+            gate_names=clifford_gate_names,
         )
         collapse_function = partial(collapse_to_operation, collapse_function=_collapse_to_clifford)
 
@@ -57,6 +57,7 @@ class CollectLinearFunctions(CollectAndCollapse):
             min_block_size=min_block_size,
             split_layers=split_layers,
             collect_from_back=collect_from_back,
+            gate_names=_linear_gate_names,
         )
         collapse_function = partial(
             collapse_to_operation, collapse_function=_collapse_to_linear_function
@@ -69,9 +70,12 @@ class CollectLinearFunctions(CollectAndCollapse):
         )
 
 
+_linear_gate_names = ("cx", "swap")
+
+
 def _is_linear_gate(node):
     """Specifies whether a node holds a linear gate."""
-    return node.op.name in ("cx", "swap") and getattr(node.op, "condition", None) is None
+    return node.op.name in _linear_gate_names and getattr(node.op, "condition", None) is None
 
 
 def _collapse_to_linear_function(circuit):
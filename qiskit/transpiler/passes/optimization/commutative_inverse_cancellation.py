@@ -12,10 +12,12 @@
 
 """Cancel pairs of inverse gates exploiting commutation relations."""
 from qiskit.circuit.commutation_library import SessionCommutationChecker as scc
+from qiskit.circuit.controlflow import ControlFlowOp
 from qiskit.dagcircuit import DAGCircuit, DAGOpNode
 from qiskit.quantum_info import Operator
 from qiskit.quantum_info.operators.predicates import matrix_equal
 from qiskit.transpiler.basepasses import TransformationPass
+from qiskit._accelerate.pass_loop import CircuitHandle, RustPass, run_passes
 
 
 class CommutativeInverseCancellation(TransformationPass):
@@ -86,6 +88,9 @@ class CommutativeInverseCancellation(TransformationPass):
         Returns:
             DAGCircuit: Transformed DAG.
         """
+        if self._can_run_on_circuit_handle(dag):
+            return self._run_on_circuit_handle(CircuitHandle.from_dag(dag)).to_dag()
+
         topo_sorted_nodes = list(dag.topological_op_nodes())
 
         circ_size = len(topo_sorted_nodes)
@@ -141,3 +146,15 @@ class CommutativeInverseCancellation(TransformationPass):
             dag.global_phase += phase_update
 
         return dag
+
+    def _can_run_on_circuit_handle(self, dag):
+        """The Rust pass scans the instructions in circuit order, which is a topological order, and
+        only needs the matrices of the gates, so it runs on any circuit without control flow.
+        ``dag`` is ``None`` if the circuit is already a :class:`.CircuitHandle`."""
+        return dag is None or not any(isinstance(node.op, ControlFlowOp) for node in dag.op_nodes())
+
+    def _run_on_circuit_handle(self, handle):
+        rust_pass = RustPass.commutative_inverse_cancellation(
+            matrix_based=self._matrix_based, max_qubits=self._max_qubits
+        )
+        return run_passes(handle, [rust_pass])
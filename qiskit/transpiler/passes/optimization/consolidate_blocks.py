@@ -28,6 +28,7 @@ from qiskit.circuit.controlflow import ControlFlowOp
 from qiskit.transpiler.passmanager import PassManager
 from qiskit.transpiler.passes.synthesis import unitary_synthesis
 from qiskit.transpiler.passes.utils import _block_to_matrix
+from qiskit._accelerate.pass_loop import CircuitHandle, RustPass, run_passes
 from .collect_1q_runs import Collect1qRuns
 from .collect_2q_blocks import Collect2qBlocks
 
@@ -91,6 +92,9 @@ class ConsolidateBlocks(TransformationPass):
         if self.decomposer is None:
             return dag
 
+        if self._can_run_in_rust(dag):
+            return self._run_on_circuit_handle(CircuitHandle.from_dag(dag)).to_dag()
+
         blocks = self.property_set["block_list"] or []
         basis_gate_name = self.decomposer.gate.name
         all_block_gates = set()
@@ -114,7 +118,10 @@ class ConsolidateBlocks(TransformationPass):
                         basis_count += 1
                     if self._check_not_in_basis(dag, nd.op.name, nd.qargs):
                         outside_basis = True
-                if len(block_qargs) > 2:
+                if len(block_qargs) == 3 and not block_cargs:
+                    matrix = _block_to_matrix(block, block_index_map)
+                    unitary = UnitaryGate(matrix, check_input=False)
+                elif len(block_qargs) > 2:
                     q = QuantumRegister(len(block_qargs))
                     qc = QuantumCircuit(q)
                     if block_cargs:
@@ -170,15 +177,48 @@ class ConsolidateBlocks(TransformationPass):
                     dag.replace_block_with_op(run, unitary, {qubit: 0}, cycle_check=False)
 
         dag = self._handle_control_flow_ops(dag)
+        self._clear_collected_blocks()
+        return dag
+
+    def _can_run_in_rust(self, dag):
+        """Whether the pass can run over the circuit's Rust-space data in one go.
 
+        The Rust pass collects the two-qubit blocks (and the one-qubit runs, if ``Collect1qRuns``
+        was run) itself, as part of a single sweep over the circuit, so it only replaces the blocks
+        of ``Collect2qBlocks``.  ``dag`` is ``None`` if the circuit is already a
+        :class:`.CircuitHandle`."""
+        return (
+            "block_list" in self.property_set
+            and self.target is None
+            and isinstance(self.decomposer, TwoQubitBasisDecomposer)
+            and (
+                dag is None
+                or not any(isinstance(node.op, ControlFlowOp) for node in dag.op_nodes())
+            )
+        )
+
+    def _can_run_on_circuit_handle(self, dag):
+        return self.decomposer is not None and self._can_run_in_rust(dag)
+
+    def _run_on_circuit_handle(self, handle):
+        rust_pass = RustPass.consolidate_blocks(
+            self.decomposer._inner_decomposer,
+            self.decomposer.gate.name,
+            basis_gates=self.basis_gates,
+            force_consolidate=self.force_consolidate,
+            consolidate_1q_runs="run_list" in self.property_set,
+        )
+        handle = run_passes(handle, [rust_pass])
+        self._clear_collected_blocks()
+        return handle
+
+    def _clear_collected_blocks(self):
         # Clear collected blocks and runs as they are no longer valid after consolidation
         if "run_list" in self.property_set:
             del self.property_set["run_list"]
         if "block_list" in self.property_set:
             del self.property_set["block_list"]
 
-        return dag
-
     def _handle_control_flow_ops(self, dag):
         """
         This is similar to transpiler/passes/utils/control_flow.py except that the
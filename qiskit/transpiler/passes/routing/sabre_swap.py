@@ -16,10 +16,12 @@ import logging
 from copy import deepcopy
 import time
 
-import rustworkx
-
 from qiskit.circuit import SwitchCaseOp, ControlFlowOp, Clbit, ClassicalRegister
-from qiskit.circuit.library.standard_gates import SwapGate
+from qiskit.circuit.library.standard_gates import (
+    CXGate,
+    SwapGate,
+    get_standard_gate_name_mapping,
+)
 from qiskit.circuit.controlflow import condition_resources, node_resources
 from qiskit.converters import dag_to_circuit
 from qiskit.transpiler.basepasses import TransformationPass
@@ -27,17 +29,17 @@ from qiskit.transpiler.coupling import CouplingMap
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.layout import Layout
 from qiskit.transpiler.target import Target
-from qiskit.transpiler.passes.layout import disjoint_utils
+from qiskit.transpiler.passes.layout import disjoint_utils, vf2_utils
 from qiskit.dagcircuit import DAGCircuit
 from qiskit.utils.parallel import CPU_COUNT
 
 from qiskit._accelerate.sabre import (
     sabre_routing,
     Heuristic,
-    NeighborTable,
     SabreDAG,
 )
 from qiskit._accelerate.nlayout import NLayout
+from qiskit._accelerate.rng import entropy_seed
 
 logger = logging.getLogger(__name__)
 
@@ -79,14 +81,27 @@ class SabreSwap(TransformationPass):
     `arXiv:1809.02573 <https://arxiv.org/pdf/1809.02573.pdf>`_
     """
 
-    def __init__(self, coupling_map, heuristic="basic", seed=None, fake_run=False, trials=None):
+    def __init__(
+        self,
+        coupling_map,
+        heuristic="basic",
+        seed=None,
+        fake_run=False,
+        trials=None,
+        bridges=False,
+        commuting_sets=False,
+        objective_weights=None,
+        pareto_front=False,
+    ):
         r"""SabreSwap initializer.
 
         Args:
             coupling_map (Union[CouplingMap, Target]): CouplingMap of the target backend.
             heuristic (str): The type of heuristic to use when deciding best
                 swap strategy ('basic' or 'lookahead' or 'decay').
-            seed (int): random seed used to tie-break among candidate swaps.
+            seed (int): random seed used to tie-break among candidate swaps.  Seeds of up to
+                128 bits are accepted.  The seed that was used, including one drawn when this
+                is ``None``, is stored in the ``sabre_swap_seed`` field of the property set.
             fake_run (bool): if true, it only pretend to do routing, i.e., no
                 swap is effectively added.
             trials (int): The number of seed trials to run sabre with. These will
@@ -95,9 +110,33 @@ class SabreSwap(TransformationPass):
                 CPUs on the local system. For reproducible results it is recommended
                 that you set this explicitly, as the output will be deterministic for
                 a fixed number of trials.
+            bridges (bool): if true, a CX gate between qubits that share a neighbor may be
+                realized with a bridge of four CX gates through that neighbor, which leaves the
+                layout as it is, instead of being brought together with a swap.  The choice is made
+                per gate, by whichever is cheaper under the average error rates of the ``Target``
+                (or by the number of gates without one), with the effect of the swap on the
+                upcoming gates counted against it.  The node ids of the bridged gates are in the
+                routing result.
+            commuting_sets (bool): if true, the ordering between standard gates that commute is
+                relaxed, so that a run of mutually commuting two-qubit gates on a qubit, such as
+                the ``RZZGate`` layers of a QAOA circuit, is in the front layer at once and can be
+                routed in whichever order the swaps reach it.  This can reduce the number of swaps
+                for such circuits, at the cost of the commutation checks.
+            objective_weights (dict[str, float]): the weights of the objectives that the trials
+                are scored on, keyed by ``"swaps"`` (the number of swaps and bridges added),
+                ``"depth"`` (the depth of the routed circuit) and ``"error"`` (the estimated error
+                of the added gates under the average error rates of the ``Target``, or their number
+                without one), with the missing objectives weighted zero.  The trial with the least
+                weighted sum wins.  If not specified, the trial with the fewest swaps wins.
+            pareto_front (bool): if true, the objectives of the trials that no other trial is at
+                least as good as on every objective and better on one are stored in the
+                ``sabre_swap_pareto_front`` field of the property set, as a list of dictionaries
+                keyed like ``objective_weights``, in the order of the trials.  The routing applied
+                is that of the first of these trials with the least weighted sum.
 
         Raises:
-            TranspilerError: If the specified heuristic is not valid.
+            TranspilerError: If the specified heuristic is not valid, or if ``objective_weights``
+                has an unknown objective.
 
         Additional Information:
 
@@ -159,11 +198,6 @@ class SabreSwap(TransformationPass):
             if isinstance(coupling_map, CouplingMap):
                 self.coupling_map = deepcopy(self.coupling_map)
             self.coupling_map.make_symmetric()
-        self._neighbor_table = None
-        if self.coupling_map is not None:
-            self._neighbor_table = NeighborTable(
-                rustworkx.adjacency_matrix(self.coupling_map.graph)
-            )
 
         self.heuristic = heuristic
         self.seed = seed
@@ -173,6 +207,10 @@ class SabreSwap(TransformationPass):
             self.trials = trials
 
         self.fake_run = fake_run
+        self.bridges = bridges
+        self.commuting_sets = commuting_sets
+        self.objective_weights = _objective_weights(objective_weights)
+        self.pareto_front = pareto_front
         self._qubit_indices = None
         self._clbit_indices = None
         self.dist_matrix = None
@@ -237,17 +275,33 @@ class SabreSwap(TransformationPass):
             dag,
             self.coupling_map.size(),
             self._qubit_indices,
+            bridges=self.bridges,
+            commuting_sets=self.commuting_sets,
         )
+        error_map = None
+        uses_errors = self.objective_weights is not None and self.objective_weights[2] != 0.0
+        if (self.bridges or uses_errors) and self.target is not None:
+            error_map = vf2_utils.build_average_error_map(self.target, None, self.coupling_map)
+        seed = entropy_seed() if self.seed is None else self.seed
+        self.property_set["sabre_swap_seed"] = seed
         sabre_start = time.perf_counter()
-        *sabre_result, final_permutation = sabre_routing(
+        routing = sabre_routing(
             sabre_dag,
-            self._neighbor_table,
-            self.dist_matrix,
+            self.coupling_map._get_rust_coupling_map(),
             heuristic,
             initial_layout,
             self.trials,
-            self.seed,
+            seed,
+            use_bridges=self.bridges,
+            error_map=error_map,
+            objective_weights=self.objective_weights,
+            pareto=self.pareto_front,
         )
+        if self.pareto_front:
+            self.property_set["sabre_swap_pareto_front"], routing = _choose_from_pareto_front(
+                routing, self.objective_weights
+            )
+        *sabre_result, final_permutation = routing
         sabre_stop = time.perf_counter()
         logging.debug("Sabre swap algorithm execution complete in: %s", sabre_stop - sabre_start)
         final_layout = Layout(dict(zip(dag.qubits, final_permutation)))
@@ -269,9 +323,35 @@ class SabreSwap(TransformationPass):
         )
 
 
-def _build_sabre_dag(dag, num_physical_qubits, qubit_indices):
+_OBJECTIVES = ("swaps", "depth", "error")
+
+
+def _objective_weights(weights):
+    """The weights of the routing objectives as the tuple ``(swaps, depth, error)`` that the Rust
+    routing takes, or ``None`` to score the trials on the number of swaps alone."""
+    if weights is None:
+        return None
+    unknown = set(weights).difference(_OBJECTIVES)
+    if unknown:
+        raise TranspilerError(f"Unknown routing objectives: {sorted(unknown)}")
+    return tuple(float(weights.get(objective, 0.0)) for objective in _OBJECTIVES)
+
+
+def _choose_from_pareto_front(front, weights):
+    """Split the Pareto set of trials returned by the Rust routing into the objectives of the
+    trials, as dictionaries, and the result of the first trial with the least weighted sum of its
+    objectives under ``weights`` (as returned by :func:`_objective_weights`)."""
+    weights = (1.0, 0.0, 0.0) if weights is None else weights
+    scores = [sum(w * x for w, x in zip(weights, trial[0])) for trial in front]
+    best = min(range(len(front)), key=scores.__getitem__)
+    return [dict(zip(_OBJECTIVES, trial[0])) for trial in front], front[best][1:]
+
+
+def _build_sabre_dag(dag, num_physical_qubits, qubit_indices, bridges=False, commuting_sets=False):
     from qiskit.converters import circuit_to_dag
 
+    standard_gates = get_standard_gate_name_mapping() if commuting_sets else None
+
     # Maps id(block): circuit_to_dag(block) for all descendant blocks
     circuit_to_dag_dict = {}
 
@@ -287,6 +367,8 @@ def _build_sabre_dag(dag, num_physical_qubits, qubit_indices):
     def process_dag(block_dag, wire_map):
         dag_list = []
         node_blocks = {}
+        bridgeable = set()
+        commutation = None if standard_gates is None else []
         for node in block_dag.topological_op_nodes():
             cargs_bits = set(node.cargs)
             if node.op.condition is not None:
@@ -308,6 +390,10 @@ def _build_sabre_dag(dag, num_physical_qubits, qubit_indices):
                     )
                     for block in node.op.blocks
                 ]
+            elif bridges and node.op.name == "cx" and node.op.condition is None:
+                bridgeable.add(node._node_id)
+            if commutation is not None:
+                commutation.append(_standard_gate_params(node, standard_gates))
             dag_list.append(
                 (
                     node._node_id,
@@ -316,11 +402,31 @@ def _build_sabre_dag(dag, num_physical_qubits, qubit_indices):
                     getattr(node.op, "_directive", False),
                 )
             )
-        return SabreDAG(num_physical_qubits, block_dag.num_clbits(), dag_list, node_blocks)
+        return SabreDAG(
+            num_physical_qubits,
+            block_dag.num_clbits(),
+            dag_list,
+            node_blocks,
+            bridgeable,
+            commutation,
+        )
 
     return process_dag(dag, qubit_indices), circuit_to_dag_dict
 
 
+def _standard_gate_params(node, standard_gates):
+    """Return the name and the parameters of ``node`` if it's an unconditioned standard gate with
+    bound parameters, whose commutation the Sabre DAG can check, or ``None`` otherwise."""
+    op = node.op
+    standard = standard_gates.get(op.name)
+    if type(op) is not type(standard) or node.cargs or op.condition is not None:
+        return None
+    try:
+        return op.name, [float(param) for param in op.params]
+    except TypeError:
+        return None
+
+
 def _apply_sabre_result(
     out_dag,
     in_dag,
@@ -340,8 +446,8 @@ def _apply_sabre_result(
     Args:
         out_dag (DAGCircuit): the physical DAG that the output should be written to.
         in_dag (DAGCircuit): the source of the nodes that are being routed.
-        sabre_result (tuple[SwapMap, Sequence[int], NodeBlockResults]): the result object from the
-            Rust run of the Sabre routing algorithm.
+        sabre_result (tuple[SwapMap, Sequence[int], NodeBlockResults, dict[int, int]]): the result
+            object from the Rust run of the Sabre routing algorithm.
         initial_layout (NLayout): a Rust-space mapping of virtual indices (i.e. those of the qubits
             in ``in_dag``) to physical ones.
         physical_qubits (list[Qubit]): an indexable sequence of :class:`.circuit.Qubit` objects
@@ -355,6 +461,7 @@ def _apply_sabre_result(
     # The swap gate is a singleton instance, so we don't need to waste time reconstructing it each
     # time we need to use it.
     swap_singleton = SwapGate()
+    cx_singleton = CXGate()
 
     def empty_dag(block):
         empty = DAGCircuit()
@@ -378,11 +485,20 @@ def _apply_sabre_result(
 
         ``root_virtual_map`` is a mapping of the (virtual) qubit in ``source_dag`` to the index of
         the virtual qubit in the root source DAG that it is bound to."""
-        swap_map, node_order, node_block_results = result
+        swap_map, node_order, node_block_results, bridges = result
         for node_id in node_order:
             node = source_dag._multi_graph[node_id]
             if node_id in swap_map:
                 apply_swaps(dest_dag, swap_map[node_id], layout)
+            if node_id in bridges:
+                control, target = (
+                    physical_qubits[layout.virtual_to_physical(root_logical_map[q])]
+                    for q in node.qargs
+                )
+                middle = physical_qubits[bridges[node_id]]
+                for qubits in [(control, middle), (middle, target)] * 2:
+                    dest_dag.apply_operation_back(cx_singleton, qubits, (), check=False)
+                continue
             if not isinstance(node.op, ControlFlowOp):
                 dest_dag.apply_operation_back(
                     node.op,
@@ -410,6 +526,7 @@ def _apply_sabre_result(
                         block_result.result.map,
                         block_result.result.node_order,
                         block_result.result.node_block_results,
+                        block_result.result.bridges,
                     ),
                     block_root_logical_map,
                     layout.copy(),
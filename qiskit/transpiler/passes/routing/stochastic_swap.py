@@ -37,6 +37,7 @@ from qiskit.circuit import (
 )
 from qiskit._accelerate import stochastic_swap as stochastic_swap_rs
 from qiskit._accelerate import nlayout
+from qiskit._accelerate.rng import entropy_seed
 from qiskit.transpiler.passes.layout import disjoint_utils
 
 from .utils import get_swap_map_dag
@@ -70,7 +71,9 @@ class StochasticSwap(TransformationPass):
             coupling_map (Union[CouplingMap, Target]): Directed graph representing a coupling
                 map.
             trials (int): maximum number of iterations to attempt
-            seed (int): seed for random number generator
+            seed (int): seed for random number generator, of up to 128 bits.  The seed that was
+                used, including one drawn when this is ``None``, is stored in the
+                ``stochastic_swap_seed`` field of the property set.
             fake_run (bool): if true, it will only pretend to do routing, i.e., no
                 swap is effectively added.
             initial_layout (Layout): starting layout at beginning of pass.
@@ -85,6 +88,7 @@ class StochasticSwap(TransformationPass):
         self.trials = trials
         self.seed = seed
         self.rng = None
+        self._run_seed = None
         self.fake_run = fake_run
         self.qregs = None
         self.initial_layout = initial_layout
@@ -116,7 +120,9 @@ class StochasticSwap(TransformationPass):
             dag, self.coupling_map if self.target is None else self.target
         )
 
-        self.rng = np.random.default_rng(self.seed)
+        self._run_seed = entropy_seed() if self.seed is None else self.seed
+        self.property_set["stochastic_swap_seed"] = self._run_seed
+        self.rng = np.random.default_rng(self._run_seed)
 
         canonical_register = dag.qregs["q"]
         if self.initial_layout is None:
@@ -126,7 +132,7 @@ class StochasticSwap(TransformationPass):
         self._int_to_qubit = tuple(dag.qubits)
 
         self.qregs = dag.qregs
-        logger.debug("StochasticSwap rng seeded with seed=%s", self.seed)
+        logger.debug("StochasticSwap rng seeded with seed=%s", self._run_seed)
         self.coupling_map.compute_distance_matrix()
         new_dag = self._mapper(dag, self.coupling_map, trials=self.trials)
         return new_dag
@@ -198,7 +204,6 @@ class StochasticSwap(TransformationPass):
         best_circuit = None  # initialize best swap circuit
         best_layout = None  # initialize best final layout
 
-        cdist2 = coupling._dist_matrix**2
         int_qubit_subset = np.fromiter(
             (dag.find_bit(bit).index for bit in qubit_subset),
             dtype=np.uint32,
@@ -217,18 +222,14 @@ class StochasticSwap(TransformationPass):
         trial_circuit = DAGCircuit()  # SWAP circuit for slice of swaps in this trial
         trial_circuit.add_qubits(layout.get_virtual_bits())
 
-        edges = np.asarray(coupling.get_edges(), dtype=np.uint32).ravel()
-        cdist = coupling._dist_matrix
         best_edges, best_layout, best_depth = stochastic_swap_rs.swap_trials(
             trials,
             num_qubits,
             int_layout,
             int_qubit_subset,
             int_gates,
-            cdist,
-            cdist2,
-            edges,
-            seed=self.seed,
+            coupling._get_rust_coupling_map(),
+            seed=self._run_seed,
         )
         # If we have no best circuit for this layer, all of the trials have failed
         if best_layout is None:
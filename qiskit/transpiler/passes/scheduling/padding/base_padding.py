@@ -16,12 +16,13 @@ from __future__ import annotations
 from collections.abc import Iterable
 import logging
 
-from qiskit.circuit import Qubit, Clbit, Instruction
+from qiskit.circuit import Qubit, Clbit, Instruction, CircuitInstruction
 from qiskit.circuit.delay import Delay
 from qiskit.dagcircuit import DAGCircuit, DAGNode
 from qiskit.transpiler.basepasses import TransformationPass
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.circuit import CircuitData
 
 logger = logging.getLogger(__name__)
 
@@ -84,23 +85,7 @@ class BasePadding(TransformationPass):
         self._pre_runhook(dag)
 
         node_start_time = self.property_set["node_start_time"].copy()
-
-        new_dag = DAGCircuit()
-        for qreg in dag.qregs.values():
-            new_dag.add_qreg(qreg)
-        for creg in dag.cregs.values():
-            new_dag.add_creg(creg)
-
-        # Update start time dictionary for the new_dag.
-        # This information may be used for further scheduling tasks,
-        # but this is immediately invalidated because node id is updated in the new_dag.
-        self.property_set["node_start_time"].clear()
-
-        new_dag.name = dag.name
-        new_dag.metadata = dag.metadata
-        new_dag.unit = self.property_set["time_unit"]
-        new_dag.calibrations = dag.calibrations
-        new_dag.global_phase = dag.global_phase
+        new_dag = self._empty_padded_dag(dag)
 
         idle_after = {bit: 0 for bit in dag.qubits}
 
@@ -165,6 +150,71 @@ class BasePadding(TransformationPass):
 
         return new_dag
 
+    def _empty_padded_dag(self, dag: DAGCircuit) -> DAGCircuit:
+        """An empty copy of ``dag`` to build the padded circuit in.
+
+        This also clears the start times of the nodes of ``dag``, since the padded circuit will have
+        new nodes.
+        """
+        new_dag = DAGCircuit()
+        for qreg in dag.qregs.values():
+            new_dag.add_qreg(qreg)
+        for creg in dag.cregs.values():
+            new_dag.add_creg(creg)
+
+        # Update start time dictionary for the new_dag.
+        # This information may be used for further scheduling tasks,
+        # but this is immediately invalidated because node id is updated in the new_dag.
+        self.property_set["node_start_time"].clear()
+
+        new_dag.name = dag.name
+        new_dag.metadata = dag.metadata
+        new_dag.unit = self.property_set["time_unit"]
+        new_dag.calibrations = dag.calibrations
+        new_dag.global_phase = dag.global_phase
+        return new_dag
+
+    def _scheduled_circuit_data(self, dag: DAGCircuit) -> CircuitData | None:
+        """The instructions of ``dag`` in topological order with their start times recorded on
+        them, for the Rust padding routines.
+
+        Returns ``None`` if the durations aren't all in ``dt`` or some node isn't scheduled, in
+        which case the pass must run in Python to treat it.
+        """
+        if self.property_set.get("time_unit") != "dt" or dag.calibrations:
+            return None
+        node_start_time = self.property_set["node_start_time"]
+        nodes = list(dag.topological_op_nodes())
+        if node_start_time is None or any(node not in node_start_time for node in nodes):
+            return None
+        data = CircuitData(
+            dag.qubits,
+            dag.clbits,
+            (CircuitInstruction(node.op, node.qargs, node.cargs) for node in nodes),
+            len(nodes),
+        )
+        data.start_times = [node_start_time[node] for node in nodes]
+        return data
+
+    def _delay_qubits(self, dag: DAGCircuit) -> list[bool] | None:
+        """Whether each qubit of ``dag`` supports delays, or ``None`` if they all do."""
+        if self.target is None:
+            return None
+        return [self.__delay_supported(qarg) for qarg in range(dag.num_qubits())]
+
+    def _padded_dag_from_circuit_data(
+        self, dag: DAGCircuit, data: CircuitData, duration: int
+    ) -> DAGCircuit:
+        """Build the padded DAG from the output of a Rust padding routine, which records the start
+        times of the instructions on them."""
+        new_dag = self._empty_padded_dag(dag)
+        for instruction, t_start in zip(data, data.start_times):
+            self._apply_scheduled_op(
+                new_dag, t_start, instruction.operation, instruction.qubits, instruction.clbits
+            )
+        new_dag.duration = duration
+        return new_dag
+
     def __delay_supported(self, qarg: int) -> bool:
         """Delay operation is supported on the qubit (qarg) or not."""
         if self.target is None or self.target.instruction_supported("delay", qargs=(qarg,)):
@@ -27,6 +27,7 @@ from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.instruction_durations import InstructionDurations
 from qiskit.transpiler.passes.optimization import Optimize1qGates
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.dynamical_decoupling import pad_dynamical_decoupling
 
 from .base_padding import BasePadding
 
@@ -179,6 +180,31 @@ class PadDynamicalDecoupling(BasePadding):
                         f"{gate.name} in dd_sequence is not supported in the target"
                     )
 
+    def run(self, dag: DAGCircuit):
+        data = self._scheduled_circuit_data(dag)
+        if data is None or len(self._dd_sequence) == 1:
+            # A single-gate sequence is absorbed into the neighbouring gates, which is done in
+            # Python.
+            return super().run(dag)
+        self._pre_runhook(dag)
+        data, _, duration, phase = pad_dynamical_decoupling(
+            data,
+            None,
+            self.property_set["time_unit"],
+            self._dd_sequence,
+            [self._dd_sequence_lengths.get(qubit) for qubit in dag.qubits],
+            [self._spacing] * dag.num_qubits(),
+            self._sequence_phase,
+            self._alignment,
+            self._extra_slack_distribution,
+            self._skip_reset_qubits,
+            self._delay_qubits(dag),
+            None if self.target is None else self.target._build_rust_target(),
+        )
+        new_dag = self._padded_dag_from_circuit_data(dag, data, duration)
+        new_dag.global_phase = new_dag.global_phase + phase
+        return new_dag
+
     def _update_inst_durations(self, dag):
         """Update instruction durations with circuit information. If the dag contains gate
         calibrations and no instruction durations were provided through the target or as a
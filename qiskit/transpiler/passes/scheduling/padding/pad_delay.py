@@ -16,6 +16,7 @@ from qiskit.circuit import Qubit
 from qiskit.circuit.delay import Delay
 from qiskit.dagcircuit import DAGCircuit, DAGNode, DAGOutNode
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.scheduling import pad_delay
 
 from .base_padding import BasePadding
 
@@ -63,6 +64,20 @@ class PadDelay(BasePadding):
         super().__init__(target=target)
         self.fill_very_end = fill_very_end
 
+    def run(self, dag: DAGCircuit):
+        data = self._scheduled_circuit_data(dag)
+        if data is None:
+            return super().run(dag)
+        self._pre_runhook(dag)
+        data, _, duration = pad_delay(
+            data,
+            None,
+            self.property_set["time_unit"],
+            self.fill_very_end,
+            self._delay_qubits(dag),
+        )
+        return self._padded_dag_from_circuit_data(dag, data, duration)
+
     def _pad(
         self,
         dag: DAGCircuit,
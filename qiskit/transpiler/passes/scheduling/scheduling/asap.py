@@ -41,6 +41,10 @@ class ASAPScheduleAnalysis(BaseScheduler):
         if len(dag.qregs) != 1 or dag.qregs.get("q", None) is None:
             raise TranspilerError("ASAP schedule runs on physical circuits only")
 
+        if self._can_run_in_rust(dag):
+            self.property_set["node_start_time"] = self._schedule_in_rust(dag, alap=False)
+            return
+
         conditional_latency = self.property_set.get("conditional_latency", 0)
         clbit_write_latency = self.property_set.get("clbit_write_latency", 0)
 
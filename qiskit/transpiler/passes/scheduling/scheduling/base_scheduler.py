@@ -11,6 +11,7 @@
 # that they have been altered from the originals.
 
 """Base circuit scheduling pass."""
+from __future__ import annotations
 
 import warnings
 
@@ -18,10 +19,12 @@ from qiskit.transpiler import InstructionDurations
 from qiskit.transpiler.basepasses import AnalysisPass
 from qiskit.transpiler.passes.scheduling.time_unit_conversion import TimeUnitConversion
 from qiskit.dagcircuit import DAGOpNode, DAGCircuit
-from qiskit.circuit import Delay, Gate
+from qiskit.circuit import CircuitInstruction, Delay, Gate
 from qiskit.circuit.parameterexpression import ParameterExpression
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.circuit import CircuitData
+from qiskit._accelerate.scheduling import alap_schedule, asap_schedule
 
 
 class BaseScheduler(AnalysisPass):
@@ -41,6 +44,7 @@ class BaseScheduler(AnalysisPass):
         """
         super().__init__()
         self.durations = durations
+        self.target = target
         if target is not None:
             self.durations = target.durations()
 
@@ -85,5 +89,32 @@ class BaseScheduler(AnalysisPass):
 
         return duration
 
+    def _can_run_in_rust(self, dag: DAGCircuit) -> bool:
+        """Whether the Rust scheduler can schedule ``dag``.
+
+        It reads the durations of the operations, so they must all be in ``dt`` and there must be
+        no calibrations that override them.
+        """
+        return self.property_set.get("time_unit") == "dt" and not dag.calibrations
+
+    def _schedule_in_rust(self, dag: DAGCircuit, alap: bool) -> dict[DAGOpNode, int]:
+        """Schedule the nodes of ``dag`` with the Rust scheduler, returning their start times."""
+        nodes = list(dag.topological_op_nodes())
+        data = CircuitData(
+            dag.qubits,
+            dag.clbits,
+            (CircuitInstruction(node.op, node.qargs, node.cargs) for node in nodes),
+            len(nodes),
+        )
+        schedule = alap_schedule if alap else asap_schedule
+        start_times = schedule(
+            data,
+            self.property_set.get("conditional_latency", 0),
+            self.property_set.get("clbit_write_latency", 0),
+            # With a target, the durations are looked up in Rust rather than read from each node.
+            None if self.target is None else self.target._build_rust_target(),
+        )
+        return dict(zip(nodes, start_times))
+
     def run(self, dag: DAGCircuit):
         raise NotImplementedError
@@ -42,7 +42,7 @@ from qiskit.synthesis.two_qubit.two_qubit_decompose import (
     GATE_NAME_MAP,
 )
 from qiskit.quantum_info import Operator
-from qiskit.circuit import ControlFlowOp, Gate, Parameter
+from qiskit.circuit import ControlFlowOp, Gate, Parameter, QuantumCircuit
 from qiskit.circuit.library.standard_gates import (
     iSwapGate,
     CXGate,
@@ -58,6 +58,8 @@ from qiskit.transpiler.passes.optimization.optimize_1q_decomposition import (
 from qiskit.providers.models import BackendProperties
 from qiskit.circuit.library.standard_gates import get_standard_gate_name_mapping
 from qiskit.exceptions import QiskitError
+from qiskit._accelerate.synthesis import three_qubit_decomposition
+from qiskit._accelerate.pass_loop import CircuitHandle, RustPass, run_passes
 
 
 KAK_GATE_NAMES = {
@@ -403,6 +405,9 @@ class UnitarySynthesis(TransformationPass):
         if not set(self._synth_gates).intersection(dag.count_ops()):
             return dag
 
+        if self._can_run_in_rust(dag):
+            return self._run_default_in_rust(dag)
+
         if self.plugins:
             plugin_method = self.plugins.ext_plugins[self.method].obj
         else:
@@ -475,6 +480,51 @@ class UnitarySynthesis(TransformationPass):
             dag, qubit_indices, plugin_method, plugin_kwargs, default_method, default_kwargs
         )
 
+    def _can_run_in_rust(self, dag):
+        """Whether the default plugin flow can run over the circuit's Rust-space data.
+
+        This needs the default plugin targeting plain basis gates (not a :class:`.Target` or
+        backend properties), a two-qubit decomposer that is a :class:`.TwoQubitBasisDecomposer`
+        (or none at all), and no control flow.  ``dag`` is ``None`` if the circuit is already a
+        :class:`.CircuitHandle`."""
+        if self.method != "default" or self._target is not None or self._backend_props is not None:
+            return False
+        if self._natural_direction is True:
+            return False
+        decomposer2q = _decomposer_2q_from_basis_gates(
+            self._basis_gates, self._pulse_optimize, self._approximation_degree
+        )
+        if decomposer2q is not None and not isinstance(decomposer2q, TwoQubitBasisDecomposer):
+            return False
+        return dag is None or not any(isinstance(node.op, ControlFlowOp) for node in dag.op_nodes())
+
+    def _run_default_in_rust(self, dag):
+        return self._run_on_circuit_handle(CircuitHandle.from_dag(dag)).to_dag()
+
+    def _can_run_on_circuit_handle(self, dag):
+        if dag is not None and not set(self._synth_gates).intersection(dag.count_ops()):
+            # There's nothing to synthesize, and `run` returns the DAG as it is.
+            return False
+        return self._can_run_in_rust(dag)
+
+    def _run_on_circuit_handle(self, handle):
+        decomposer2q = _decomposer_2q_from_basis_gates(
+            self._basis_gates, self._pulse_optimize, self._approximation_degree
+        )
+        coupling_edges = None
+        if self._natural_direction is None and self._coupling_map is not None:
+            coupling_edges = set(self._coupling_map.get_edges())
+        rust_pass = RustPass.unitary_synthesis(
+            _possible_decomposers(self._basis_gates or None),
+            None if decomposer2q is None else decomposer2q._inner_decomposer,
+            None if decomposer2q is None else decomposer2q.gate,
+            self._approximation_degree != 1.0,
+            bool(self._basis_gates),
+            min_qubits=self._min_qubits,
+            coupling_edges=coupling_edges,
+        )
+        return run_passes(handle, [rust_pass])
+
     def _run_main_loop(
         self, dag, qubit_indices, plugin_method, plugin_kwargs, default_method, default_kwargs
     ):
@@ -955,7 +1005,13 @@ class DefaultUnitarySynthesis(plugin.UnitarySynthesisPlugin):
             )
 
             # only decompose if needed. TODO: handle basis better
-            synth_circuit = qs_decomposition(unitary) if (basis_gates or target) else None
+            if not (basis_gates or target):
+                synth_circuit = None
+            elif unitary.shape == (8, 8):
+                data, global_phase = three_qubit_decomposition(np.asarray(unitary, dtype=complex))
+                synth_circuit = QuantumCircuit._from_circuit_data(data, global_phase=global_phase)
+            else:
+                synth_circuit = qs_decomposition(unitary)
         if synth_circuit is None:
             return None
         if isinstance(synth_circuit, DAGCircuit):
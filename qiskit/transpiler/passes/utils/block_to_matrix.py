@@ -10,20 +10,46 @@
 # copyright notice, and modified files need to carry a notice indicating
 # that they have been altered from the originals.
 
-"""Converts any block of 2 qubit gates into a matrix."""
+"""Converts any block of 2 or 3 qubit gates into a matrix."""
 
 from qiskit.quantum_info import Operator
 from qiskit.exceptions import QiskitError
-from qiskit._accelerate.convert_2q_block_matrix import blocks_to_matrix
+from qiskit._accelerate.convert_2q_block_matrix import blocks_to_matrix, blocks_to_matrix_3q
+
+# The standard gates whose matrices ``blocks_to_matrix`` builds itself from
+# their names and parameters.
+_RUST_GATE_NAMES = frozenset(
+    (
+        "id h x y z s sdg t tdg sx sxdg rx ry rz r p u1 u2 u3 u "
+        "cx cy cz ch cp crx cry crz swap iswap ecr rxx ryy rzz ccx cswap"
+    ).split()
+)
+
+
+def _rust_gate_params(op):
+    """Return the real parameters of ``op`` if ``blocks_to_matrix`` can build its matrix from its
+    name, or ``None`` if it has to be given the matrix."""
+    if op.name not in _RUST_GATE_NAMES or not type(op).__module__.startswith(
+        "qiskit.circuit.library.standard_gates"
+    ):
+        return None
+    if getattr(op, "condition", None) is not None:
+        return None
+    if getattr(op, "ctrl_state", None) not in (None, 2 ** getattr(op, "num_ctrl_qubits", 0) - 1):
+        return None
+    try:
+        return [float(param) for param in op.params]
+    except TypeError:
+        return None
 
 
 def _block_to_matrix(block, block_index_map):
     """
-    The function converts any sequence of operations between two qubits into a matrix
+    The function converts any sequence of operations between two or three qubits into a matrix
     that can be utilized to create a gate or a unitary.
 
     Args:
-        block (List(DAGOpNode)): A block of operations on two qubits.
+        block (List(DAGOpNode)): A block of operations on two or three qubits.
         block_index_map (dict(Qubit, int)): The mapping of the qubit indices in the main circuit.
 
     Returns:
@@ -31,17 +57,23 @@ def _block_to_matrix(block, block_index_map):
     """
     op_list = []
     block_index_length = len(block_index_map)
-    if block_index_length != 2:
+    if block_index_length not in (2, 3):
         raise QiskitError(
-            "This function can only operate with blocks of 2 qubits."
+            "This function can only operate with blocks of 2 or 3 qubits."
             + f"This block had {block_index_length}"
         )
     for node in block:
+        q_list = [block_index_map[qubit] for qubit in node.qargs]
+        params = _rust_gate_params(node.op)
+        if params is not None:
+            op_list.append((node.op.name, params, q_list))
+            continue
         try:
             current = node.op.to_matrix()
         except QiskitError:
             current = Operator(node.op).data
-        q_list = [block_index_map[qubit] for qubit in node.qargs]
         op_list.append((current, q_list))
+    if block_index_length == 3:
+        return blocks_to_matrix_3q(op_list)
     matrix = blocks_to_matrix(op_list)
     return matrix
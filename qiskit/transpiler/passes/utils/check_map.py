@@ -15,7 +15,9 @@
 from qiskit.transpiler.basepasses import AnalysisPass
 from qiskit.transpiler.target import Target
 from qiskit.circuit.controlflow import ControlFlowOp
-from qiskit.converters import circuit_to_dag
+from qiskit.converters import circuit_to_dag, dag_to_circuit
+from qiskit._accelerate.check_map import check_map
+from qiskit._accelerate.stochastic_swap import EdgeCollection
 
 
 class CheckMap(AnalysisPass):
@@ -50,11 +52,14 @@ class CheckMap(AnalysisPass):
             cmap = coupling_map
         if cmap is None:
             self.qargs = None
+            self._edges = None
         else:
             self.qargs = set()
+            self._edges = EdgeCollection()
             for edge in cmap.get_edges():
                 self.qargs.add(edge)
                 self.qargs.add((edge[1], edge[0]))
+                self._edges.add(*edge)
 
     def run(self, dag):
         """Run the CheckMap pass on `dag`.
@@ -68,6 +73,19 @@ class CheckMap(AnalysisPass):
         if not self.qargs:
             self.property_set[self.property_set_field] = True
             return
+        if not dag.calibrations and not any(
+            isinstance(node.op, ControlFlowOp) for node in dag.op_nodes()
+        ):
+            circuit = dag_to_circuit(dag, copy_operations=False)
+            index = check_map(circuit._data, self._edges)
+            if index is not None:
+                instruction = circuit.data[index]
+                self.property_set["check_map_msg"] = "{}({}, {}) failed".format(
+                    instruction.operation.name,
+                    *(circuit.find_bit(qubit).index for qubit in instruction.qubits),
+                )
+            self.property_set[self.property_set_field] = index is None
+            return
         wire_map = {bit: index for index, bit in enumerate(dag.qubits)}
         self.property_set[self.property_set_field] = self._recurse(dag, wire_map)
 
@@ -20,6 +20,7 @@ from collections.abc import Iterator, Iterable, Callable
 from functools import wraps
 from typing import Union, List, Any
 
+from qiskit._accelerate.pass_loop import CircuitHandle
 from qiskit.circuit import QuantumCircuit
 from qiskit.converters import circuit_to_dag, dag_to_circuit
 from qiskit.dagcircuit import DAGCircuit
@@ -63,10 +64,12 @@ class PassManager(BasePassManager):
 
     def _passmanager_backend(
         self,
-        passmanager_ir: DAGCircuit,
+        passmanager_ir: DAGCircuit | CircuitHandle,
         in_program: QuantumCircuit,
         **kwargs,
     ) -> QuantumCircuit:
+        if isinstance(passmanager_ir, CircuitHandle):
+            passmanager_ir = passmanager_ir.to_dag()
         out_program = dag_to_circuit(passmanager_ir, copy_operations=False)
 
         self._finalize_layouts(passmanager_ir)
@@ -478,6 +481,8 @@ for _name, _method in inspect.getmembers(PassManager, predicate=inspect.isfuncti
 
 def _legacy_style_callback(callback: Callable):
     def _wrapped_callable(task, passmanager_ir, property_set, running_time, count):
+        if isinstance(passmanager_ir, CircuitHandle):
+            passmanager_ir = passmanager_ir.to_dag()
         callback(
             pass_=task,
             dag=passmanager_ir,
@@ -1090,6 +1090,44 @@ class Target(Mapping):
             graph.remove_nodes_from(list(to_remove))
         return graph
 
+    def _build_rust_target(self):
+        """Build a copy of this target in Rust space, for the Rust passes to query.
+
+        The copy only has the names, qargs, durations and errors of the instructions and the
+        global properties of the target, and it isn't updated when this target changes.
+        """
+        from qiskit._accelerate.target import Target as RustTarget
+
+        qubit_properties = None
+        if self.qubit_properties is not None:
+            qubit_properties = [
+                None if props is None else (props.t1, props.t2, props.frequency)
+                for props in self.qubit_properties
+            ]
+        out = RustTarget(
+            num_qubits=self.num_qubits,
+            dt=self.dt,
+            granularity=self.granularity,
+            min_length=self.min_length,
+            pulse_alignment=self.pulse_alignment,
+            acquire_alignment=self.acquire_alignment,
+            qubit_properties=qubit_properties,
+            description=self.description,
+        )
+        for name, qarg_map in self._gate_map.items():
+            operation = self._gate_name_map[name]
+            if inspect.isclass(operation):
+                out.add_instruction(name, None)
+                continue
+            properties = None
+            if qarg_map is not None:
+                properties = {
+                    qargs: None if props is None else (props.duration, props.error)
+                    for qargs, props in qarg_map.items()
+                }
+            out.add_instruction(name, operation.num_qubits, properties)
+        return out
+
     @property
     def physical_qubits(self):
         """Returns a sorted list of physical_qubits"""
@@ -49,6 +49,21 @@ A helper function for calling a custom function with Python
 
 .. autofunction:: parallel_map
 
+The routines of Qiskit written in Rust use a shared pool of threads, which can be configured with:
+
+.. autofunction:: configure_rust_threads
+.. autofunction:: rust_thread_config
+
+Profiling
+=========
+
+The Rust routines that passes of the transpiler call can record how long they take, without
+rebuilding Qiskit:
+
+.. autofunction:: rust_instrumentation
+.. autofunction:: enable_rust_instrumentation
+.. autofunction:: rust_instrumentation_stats
+
 Optional Dependency Checkers
 ============================
 
@@ -70,7 +85,17 @@ from .lazy_tester import LazyDependencyManager, LazyImportTester, LazySubprocess
 
 from . import optionals
 
-from .parallel import parallel_map, should_run_in_parallel
+from .instrumentation import (
+    enable_rust_instrumentation,
+    rust_instrumentation,
+    rust_instrumentation_stats,
+)
+from .parallel import (
+    configure_rust_threads,
+    parallel_map,
+    rust_thread_config,
+    should_run_in_parallel,
+)
 
 __all__ = [
     "LazyDependencyManager",
@@ -86,4 +111,9 @@ __all__ = [
     "apply_prefix",
     "parallel_map",
     "should_run_in_parallel",
+    "configure_rust_threads",
+    "rust_thread_config",
+    "enable_rust_instrumentation",
+    "rust_instrumentation",
+    "rust_instrumentation_stats",
 ]
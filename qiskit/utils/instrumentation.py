@@ -0,0 +1,76 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Profiling of Qiskit's compiled Rust routines."""
+
+from __future__ import annotations
+
+import contextlib
+
+from qiskit._accelerate import instrumentation as _instrumentation_rs
+
+
+def enable_rust_instrumentation(enabled: bool = True):
+    """Start or stop recording statistics of Qiskit's compiled Rust routines.
+
+    While recording, each instrumented routine, such as the Sabre layout and routing or the default
+    unitary synthesis, accumulates its number of calls, its total wall time, the number of trials
+    it ran, and the number and total size of the allocations made during its calls.  Recording
+    costs a little time per call, so it's off by default.
+
+    Args:
+        enabled: whether to record.  The statistics recorded so far are kept either way.
+    """
+    _instrumentation_rs.set_enabled(enabled)
+
+
+def rust_instrumentation_stats(reset: bool = False) -> dict[str, dict]:
+    """The statistics recorded by the Rust routines since they were last reset.
+
+    Args:
+        reset: whether to also clear the statistics.
+
+    Returns:
+        For each instrumented routine that was called, a dictionary of its number of ``calls``, its
+        total ``wall_time`` in seconds, its number of ``trials``, and the number of
+        ``allocations`` and ``allocated_bytes`` made during its calls.  The allocations are counted
+        across all threads, so they include anything else running at the same time.
+    """
+    return _instrumentation_rs.get_stats(reset)
+
+
+@contextlib.contextmanager
+def rust_instrumentation():
+    """A context manager that records the statistics of the Rust routines called within it.
+
+    For example, to profile the Rust parts of a transpilation::
+
+        from qiskit import transpile
+        from qiskit.utils import rust_instrumentation
+
+        with rust_instrumentation() as stats:
+            transpile(circuit, backend)
+        print(stats["sabre_layout"]["wall_time"])
+
+    Yields:
+        dict[str, dict]: the statistics, as from :func:`rust_instrumentation_stats`, which are
+        filled in when the context exits.
+    """
+    was_enabled = _instrumentation_rs.is_enabled()
+    _instrumentation_rs.reset()
+    _instrumentation_rs.set_enabled(True)
+    stats = {}
+    try:
+        yield stats
+    finally:
+        _instrumentation_rs.set_enabled(was_enabled)
+        stats.update(_instrumentation_rs.get_stats(True))
@@ -54,6 +54,7 @@ import os
 from concurrent.futures import ProcessPoolExecutor
 import sys
 
+from qiskit._accelerate import threading as rust_threading
 from qiskit.exceptions import QiskitError
 from qiskit.utils.multiprocessing import local_hardware_info
 from qiskit import user_config
@@ -118,6 +119,43 @@ def should_run_in_parallel(num_processes: int | None = None) -> bool:
     )
 
 
+def configure_rust_threads(
+    max_threads: int | None = None,
+    serial_in_parallel: bool | None = None,
+    parallel_thresholds: dict[str, int | None] | None = None,
+):
+    """Configure the threads that Qiskit's compiled Rust routines use.
+
+    All the multithreaded routines share one thread pool, so this bounds the CPU usage of all of
+    them, without needing the ``RAYON_NUM_THREADS`` or ``QISKIT_FORCE_THREADS`` environment
+    variables.  Any argument that is ``None`` is left as it is.
+
+    Args:
+        max_threads: the number of threads of the shared pool, with ``0`` for one per CPU.  With
+            ``1``, every routine runs serially.
+        serial_in_parallel: whether the routines run serially within the worker processes of
+            :func:`parallel_map`, which is the default.
+        parallel_thresholds: the smallest input size for which each routine goes parallel, by the
+            name of its module (for example ``"statevector"``), with ``None`` to restore a default.
+            The current values are in :func:`rust_thread_config`.
+
+    Raises:
+        KeyError: if a routine has no parallel threshold.
+    """
+    rust_threading.configure(max_threads, serial_in_parallel, parallel_thresholds)
+
+
+def rust_thread_config() -> dict:
+    """The current configuration of the threads of Qiskit's compiled Rust routines.
+
+    Returns:
+        A dictionary of the ``max_threads`` and ``serial_in_parallel`` settings of
+        :func:`configure_rust_threads`, the ``num_threads`` of the shared pool, and the
+        ``parallel_thresholds`` of the routines.
+    """
+    return rust_threading.get_config()
+
+
 def parallel_map(  # pylint: disable=dangerous-default-value
     task, values, task_args=(), task_kwargs={}, num_processes=CPU_COUNT
 ):
@@ -20,8 +20,10 @@ from ddt import ddt, data, unpack
 from qiskit.circuit import QuantumCircuit, Parameter
 from qiskit.circuit.library import PauliEvolutionGate
 from qiskit.synthesis import LieTrotter, SuzukiTrotter, MatrixExponential, QDrift
+from qiskit.synthesis.evolution.product_formula import evolve_pauli
 from qiskit.converters import circuit_to_dag
 from qiskit.quantum_info import Operator, SparsePauliOp, Pauli, Statevector
+from qiskit._accelerate.synthesis import synth_pauli_evolution
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 X = SparsePauliOp("X")
@@ -203,6 +205,24 @@ class TestEvolutionGate(QiskitTestCase):
         ]
         self.assertListEqual(pauli_strings, expected)
 
+    def test_matrix(self):
+        """Test the matrix of the gate is the exact evolution under the sum of its operators,
+        whatever the synthesis."""
+        grouped_ops = [(X ^ Y) + (Y ^ X), (Z ^ I) + (Z ^ Z) + (I ^ Z), (X ^ X)]
+        exact = scipy.linalg.expm(-0.12j * sum(grouped_ops).to_matrix())
+        evo_gate = PauliEvolutionGate(grouped_ops, time=0.12, synthesis=LieTrotter())
+        np.testing.assert_allclose(evo_gate.to_matrix(), exact, atol=1e-12)
+        self.assertEqual(Operator(evo_gate), Operator(exact))
+        evo_gate = PauliEvolutionGate(Pauli("XZ"), time=0.5)
+        exact = scipy.linalg.expm(-0.5j * Pauli("XZ").to_matrix())
+        np.testing.assert_allclose(evo_gate.to_matrix(), exact, atol=1e-12)
+
+    def test_matrix_unbound_time_raises(self):
+        """Test the matrix of a gate with an unbound time can't be taken."""
+        evo_gate = PauliEvolutionGate(X, time=Parameter("t"))
+        with self.assertRaises(TypeError):
+            evo_gate.to_matrix()
+
     def test_dag_conversion(self):
         """Test constructing a circuit with evolutions yields a DAG with evolution blocks."""
         time = Parameter("t")
@@ -327,6 +347,33 @@ class TestEvolutionGate(QiskitTestCase):
                 self.assertEqual(evo.name, "PauliEvolution")
                 self.assertEqual(evo.label, f"exp(-it {label})")
 
+    @data("chain", "fountain")
+    def test_evolve_pauli_exact(self, cx_structure):
+        """Test the evolution of each Pauli string is exact, including its global phase."""
+        time = 0.37
+        for label in ["III", "IXI", "ZZI", "YIY", "XZI", "IZX", "XYZ", "YYY", "ZXZ"]:
+            with self.subTest(label=label):
+                circuit = evolve_pauli(Pauli(label), time, cx_structure)
+                exact = scipy.linalg.expm(-1j * time * Pauli(label).to_matrix())
+                self.assertEqual(Operator(circuit), Operator(exact))
+
+    def test_zx_uses_cx(self):
+        """Test a ZX string is evolved with CX gates, rather than an RZX gate."""
+        circuit = evolve_pauli(Pauli("ZX"), 0.5)
+        self.assertNotIn("rzx", circuit.count_ops())
+        self.assertEqual(circuit.count_ops()["cx"], 2)
+        exact = scipy.linalg.expm(-0.5j * Pauli("ZX").to_matrix())
+        self.assertEqual(Operator(circuit), Operator(exact))
+
+    def test_synth_pauli_evolution_bad_label(self):
+        """Test the Rust synthesis raises on labels of the wrong size or with invalid characters."""
+        with self.assertRaises(ValueError):
+            synth_pauli_evolution(2, [("XYZ", 1.0)], 1.0)
+        with self.assertRaises(ValueError):
+            synth_pauli_evolution(2, [("XA", 1.0)], 1.0)
+        with self.assertRaises(ValueError):
+            synth_pauli_evolution(2, [("XY", 1.0)], 1.0, order=3)
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -20,9 +20,10 @@ import numpy as np
 from ddt import ddt, data
 
 from qiskit import QuantumCircuit, QuantumRegister
-from qiskit.quantum_info import Statevector, Operator
+from qiskit.quantum_info import Statevector, Operator, random_statevector
 from qiskit.exceptions import QiskitError
 from qiskit.circuit.library import StatePreparation
+from test import combine  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -113,6 +114,53 @@ class TestStatePreparation(QiskitTestCase):
         qc.append(StatePreparation("01").repeat(2), [0, 1])
         self.assertEqual(qc.decompose().count_ops()["state_preparation"], 2)
 
+    @combine(num_qubits=[1, 2, 3, 4, 6], seed=[0, 1, 2])
+    def test_synthesis_prepares_random_state(self, num_qubits, seed):
+        """Test that the synthesized definition prepares a random state, including its phase."""
+        state = random_statevector(2**num_qubits, seed=seed)
+        definition = StatePreparation(state.data).definition
+        self.assertLessEqual(set(definition.count_ops()), {"ry", "rz", "cx"})
+        self.assertEqual(Statevector(definition), state)
+
+    @data(
+        [0, 0, 0, 1],
+        [0, 1j, 0, 0, 0, 0, 0, 0],
+        [1 / math.sqrt(2), 0, 0, 0, 0, 0, 0, -1 / math.sqrt(2)],
+        [0.5, 0.5, -0.5, 0.5],
+        [0, 0.6, 0, 0, 0, 0, 0.8j, 0],
+    )
+    def test_synthesis_prepares_sparse_state(self, state):
+        """Test that states with zero amplitudes, or without relative phases, are prepared."""
+        definition = StatePreparation(state).definition
+        self.assertEqual(Statevector(definition), Statevector(state))
+
+    def test_synthesis_prepares_normalized_state(self):
+        """Test that the state is prepared after normalizing it."""
+        rng = np.random.default_rng(42)
+        state = rng.normal(size=8) + 1j * rng.normal(size=8)
+        definition = StatePreparation(state, normalize=True).definition
+        self.assertEqual(Statevector(definition), Statevector(state / np.linalg.norm(state)))
+
+    def test_synthesis_inverse(self):
+        """Test that the inverse of the definition uncomputes the state."""
+        state = random_statevector(8, seed=12)
+        qc = QuantumCircuit(3)
+        qc.append(StatePreparation(state.data), [0, 1, 2])
+        qc.append(StatePreparation(state.data, inverse=True), [0, 1, 2])
+        self.assertEqual(Statevector(qc), Statevector.from_int(0, 8))
+
+    @data(5, 0, 7)
+    def test_definition_from_int(self, state):
+        """Test that the definition of an integer state prepares that basis state."""
+        definition = StatePreparation(state, num_qubits=3).definition
+        self.assertEqual(Statevector(definition), Statevector.from_int(state, 8))
+
+    @data("01+-", "rl10", "0000")
+    def test_definition_from_label(self, label):
+        """Test that the definition of a label prepares that product state."""
+        definition = StatePreparation(label).definition
+        self.assertEqual(Statevector(definition), Statevector.from_label(label))
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -11,10 +11,15 @@
 # that they have been altered from the originals.
 
 """Test operations on circuit.data."""
+import copy
+import math
+import pickle
+
 import ddt
 
-from qiskit._accelerate.circuit import CircuitData
+from qiskit._accelerate.circuit import CircuitData, get_import, register_import
 from qiskit.circuit import (
+    Delay,
     ClassicalRegister,
     QuantumCircuit,
     QuantumRegister,
@@ -387,6 +392,31 @@ class TestQuantumCircuitData(QiskitTestCase):
             qr_foreign = QuantumRegister(1)
             data[0] = CircuitInstruction(XGate(), [qr_foreign[0]], [])
 
+    def test_global_phase_is_normalized(self):
+        """Test that a numeric global phase is reduced modulo 2pi."""
+        data = CircuitData(global_phase=3 * math.pi)
+        self.assertAlmostEqual(data.global_phase, math.pi)
+        data.global_phase = -math.pi / 2
+        self.assertAlmostEqual(data.global_phase, 3 * math.pi / 2)
+
+    def test_global_phase_parameter_expression(self):
+        """Test that an unbound expression is stored as is, and a bound one as a float."""
+        theta = Parameter("θ")
+        data = CircuitData(global_phase=2 * theta)
+        self.assertEqual(data.global_phase, 2 * theta)
+        data.global_phase = (2 * theta).assign(theta, math.pi / 4)
+        self.assertIsInstance(data.global_phase, float)
+        self.assertAlmostEqual(data.global_phase, math.pi / 2)
+
+    @ddt.data("pi", [0.5], None, 1j)
+    def test_global_phase_rejects_invalid_types(self, angle):
+        """Test that a global phase that is neither real nor an expression is rejected."""
+        data = CircuitData()
+        with self.assertRaisesRegex(TypeError, "expected a float or a ParameterExpression"):
+            data.global_phase = angle
+        with self.assertRaisesRegex(TypeError, "expected a float or a ParameterExpression"):
+            CircuitData(global_phase=angle)
+
 
 class TestQuantumCircuitInstructionData(QiskitTestCase):
     """QuantumCircuit.data operation tests."""
@@ -819,6 +849,55 @@ class TestQuantumCircuitInstructionData(QiskitTestCase):
         expected.append(MyOp(), [0, 1], [])
         self.assertEqual(qc, expected)
 
+    def test_global_phase_stored_in_data(self):
+        """Test that the circuit's global phase is the one stored in its ``CircuitData``."""
+        qc = QuantumCircuit(1, global_phase=0.5)
+        self.assertEqual(qc._data.global_phase, 0.5)
+        qc.global_phase = 1.5
+        self.assertEqual(qc._data.global_phase, 1.5)
+        qc._data.global_phase = 2.5
+        self.assertEqual(qc.global_phase, 2.5)
+
+    def test_global_phase_survives_copies(self):
+        """Test that copying a circuit, or replacing its data, keeps the global phase."""
+        qc = QuantumCircuit(1, 1, global_phase=0.5)
+        qc.h(0)
+        qc.measure(0, 0)
+        self.assertEqual(qc.copy().global_phase, 0.5)
+        self.assertEqual(copy.deepcopy(qc).global_phase, 0.5)
+        self.assertEqual(qc.copy_empty_like().global_phase, 0.5)
+        self.assertEqual(qc.remove_final_measurements(inplace=False).global_phase, 0.5)
+        self.assertEqual(pickle.loads(pickle.dumps(qc)).global_phase, 0.5)
+        self.assertAlmostEqual(qc.inverse().global_phase, 2 * math.pi - 0.5)
+
+        other = QuantumCircuit(1, global_phase=1.0)
+        self.assertAlmostEqual(qc.compose(other).global_phase, 1.5)
+
+    def test_parametric_global_phase(self):
+        """Test that a parametric global phase is tracked and bound through the data."""
+        theta = Parameter("θ")
+        qc = QuantumCircuit(1, global_phase=theta)
+        self.assertEqual(qc._data.global_phase, theta)
+        self.assertEqual(set(qc.parameters), {theta})
+
+        bound = qc.assign_parameters({theta: 0.25})
+        self.assertEqual(bound.global_phase, 0.25)
+        self.assertEqual(bound._data.global_phase, 0.25)
+        self.assertEqual(qc.global_phase, theta)
+
+        qc.global_phase = 0.0
+        self.assertEqual(set(qc.parameters), set())
+
+    def test_global_phase_from_circuit_data(self):
+        """Test that a circuit built from ``CircuitData`` takes its phase and parameters."""
+        theta = Parameter("θ")
+        qc = QuantumCircuit._from_circuit_data(CircuitData(global_phase=theta))
+        self.assertEqual(qc.global_phase, theta)
+        self.assertEqual(set(qc.parameters), {theta})
+
+        qc = QuantumCircuit._from_circuit_data(CircuitData(global_phase=0.5), global_phase=1.0)
+        self.assertEqual(qc.global_phase, 1.0)
+
     def test_param_gate_instance(self):
         """Verify that the same Parameter gate instance is not being used in
         multiple circuits."""
@@ -831,3 +910,97 @@ class TestQuantumCircuitInstructionData(QiskitTestCase):
         qc0_instance = next(iter(qc0._parameter_table[b]))[0]
         qc1_instance = next(iter(qc1._parameter_table[a]))[0]
         self.assertNotEqual(qc0_instance, qc1_instance)
+
+
+class TestCircuitDataTimings(QiskitTestCase):
+    """Test the durations and start times of the instructions of a scheduled circuit."""
+
+    def _data(self):
+        """The data of a small circuit without timings."""
+        qc = QuantumCircuit(2, 1)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.measure(1, 0)
+        return qc._data
+
+    def test_set_and_get_timing(self):
+        """Test the timing of a single instruction, set by index, and removed by setting neither a
+        duration nor a start time."""
+        data = self._data()
+        self.assertIsNone(data.get_timing(0))
+        data.set_timing(0, 160.0, start_time=0)
+        data.set_timing(-1, 1.5e-6, "s")
+        self.assertEqual(data.get_timing(0), (160.0, "dt", 0))
+        self.assertIsNone(data.get_timing(1))
+        self.assertEqual(data.get_timing(2), (1.5e-6, "s", None))
+        self.assertEqual(data.durations, [(160.0, "dt"), None, (1.5e-6, "s")])
+        self.assertEqual(data.start_times, [0, None, None])
+        data.set_timing(0)
+        self.assertIsNone(data.get_timing(0))
+        with self.assertRaises(IndexError):
+            data.set_timing(3, 160.0)
+
+    def test_start_times(self):
+        """Test setting the start times keeps the durations, and clearing them keeps the timings of
+        the instructions that have a duration."""
+        data = self._data()
+        data.set_timing(1, 800.0)
+        data.start_times = [0, 160, 960]
+        self.assertEqual(data.start_times, [0, 160, 960])
+        self.assertEqual(data.durations, [None, (800.0, "dt"), None])
+        data.start_times = None
+        self.assertEqual(data.get_timings(), [None, (800.0, "dt", None), None])
+        with self.assertRaises(ValueError):
+            data.start_times = [0, 160]
+        data.clear_timings()
+        self.assertEqual(data.get_timings(), [None, None, None])
+
+    def test_set_timings(self):
+        """Test the timings of all the instructions round-trip, and must match the instructions in
+        number."""
+        data = self._data()
+        timings = [(160.0, "dt", 0), None, (None, "dt", 960)]
+        data.set_timings(timings)
+        self.assertEqual(data.get_timings(), timings)
+        data.set_timings([(None, "dt", None)] * 3)
+        self.assertEqual(data.get_timings(), [None, None, None])
+        with self.assertRaises(ValueError):
+            data.set_timings([None])
+
+    def test_timings_survive_copies(self):
+        """Test copying and pickling the data keeps the timings, and that new instructions have
+        none."""
+        data = self._data()
+        data.set_timings([(160.0, "dt", 0), None, (1.5e-6, "s", 960)])
+        expected = data.get_timings()
+        for copied in [data.copy(), copy.copy(data), copy.deepcopy(data)]:
+            self.assertEqual(copied.get_timings(), expected)
+        loaded = pickle.loads(pickle.dumps(data))
+        self.assertEqual(loaded.get_timings(), expected)
+        self.assertEqual(list(loaded), list(data))
+
+        other = CircuitData(qubits=data.qubits, clbits=data.clbits)
+        other.extend(data)
+        self.assertEqual(other.get_timings(), expected)
+        other.append(data[0])
+        self.assertIsNone(other.get_timing(-1))
+
+
+class TestImportRegistry(QiskitTestCase):
+    """Test the registry of the Python objects that Rust looks up by their path."""
+
+    def test_import_by_path(self):
+        """Test an unregistered path imports its object, and a module path imports the module."""
+        self.assertIs(get_import("qiskit.circuit.Delay"), Delay)
+        self.assertIs(get_import("math"), math)
+        with self.assertRaises(ImportError):
+            get_import("qiskit.not_a_module.Thing")
+
+    def test_register(self):
+        """Test a registered object replaces the object of its path, and can be replaced."""
+        path = "qiskit.test.registered_object"
+        first, second = object(), object()
+        register_import(path, first)
+        self.assertIs(get_import(path), first)
+        register_import(path, second)
+        self.assertIs(get_import(path), second)
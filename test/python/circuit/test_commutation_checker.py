@@ -12,6 +12,7 @@
 
 """Test commutation checker class ."""
 
+import itertools
 import unittest
 
 import numpy as np
@@ -41,6 +42,7 @@ from qiskit.circuit.library import (
     SGate,
     RXXGate,
 )
+from qiskit._accelerate.commutation_checker import commute
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -448,5 +450,47 @@ class TestCommutationChecker(QiskitTestCase):
         self.assertTrue(res)
 
 
+class TestRustCommute(QiskitTestCase):
+    """Test the Rust check of whether two standard gates commute."""
+
+    def test_agrees_with_commutation_checker(self):
+        """Test the Rust check agrees with the commutation checker on overlapping gates."""
+        gates = [
+            (ZGate(), [0]),
+            (XGate(), [1]),
+            (RZGate(0.3), [1]),
+            (SGate(), [0]),
+            (CXGate(), [0, 1]),
+            (CXGate(), [1, 2]),
+            (CXGate(), [2, 0]),
+            (RXXGate(0.5), [0, 2]),
+            (CCXGate(), [0, 1, 2]),
+        ]
+        for (first, first_qubits), (second, second_qubits) in itertools.product(gates, gates):
+            with self.subTest(first=first.name, second=second.name):
+                expected = scc.commute(first, first_qubits, [], second, second_qubits, [])
+                self.assertEqual(
+                    commute(
+                        (first.name, [float(p) for p in first.params], first_qubits),
+                        (second.name, [float(p) for p in second.params], second_qubits),
+                    ),
+                    expected,
+                )
+
+    def test_disjoint(self):
+        """Test gates on disjoint qubits commute."""
+        self.assertTrue(commute(("rx", [0.2], [0]), ("cx", [], [1, 2])))
+
+    def test_bad_gates(self):
+        """Test names that aren't of standard gates, or the wrong numbers of parameters or qubits,
+        raise."""
+        with self.assertRaises(ValueError):
+            commute(("not_a_gate", [], [0]), ("x", [], [0]))
+        with self.assertRaises(ValueError):
+            commute(("rx", [], [0]), ("x", [], [0]))
+        with self.assertRaises(ValueError):
+            commute(("x", [], [0]), ("cx", [], [0]))
+
+
 if __name__ == "__main__":
     unittest.main()
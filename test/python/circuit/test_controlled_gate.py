@@ -13,6 +13,7 @@
 
 """Test Qiskit's controlled gate operation."""
 
+import math
 import unittest
 
 import numpy as np
@@ -38,6 +39,7 @@ from qiskit.circuit.library import (
     YGate,
     ZGate,
     U1Gate,
+    U2Gate,
     CYGate,
     CZGate,
     CU1Gate,
@@ -798,6 +800,67 @@ class TestControlledGate(QiskitTestCase):
                     statevector = corrected
                 np.testing.assert_array_almost_equal(statevector.real, reference)
 
+    @data(5, 6, 8)
+    def test_mcx_noancilla_definition(self, num_ctrl_qubits):
+        """Test the definition of the MCX gate without ancillas is the controlled X gate."""
+        gate = MCXGate(num_ctrl_qubits)
+        self.assertEqual(gate.definition.name, gate.name)
+        expected = _compute_control_matrix(XGate().to_matrix(), num_ctrl_qubits)
+        self.assertTrue(matrix_equal(Operator(gate.definition).data, expected, atol=1e-8))
+
+    @data(1, 2, 3, 5)
+    def test_mcxgraycode_definition(self, num_ctrl_qubits):
+        """Test the definition of MCXGrayCode is the controlled X gate."""
+        gate = MCXGrayCode(num_ctrl_qubits)
+        self.assertEqual(gate.definition.name, gate.name)
+        expected = _compute_control_matrix(XGate().to_matrix(), num_ctrl_qubits)
+        self.assertTrue(matrix_equal(Operator(gate.definition).data, expected, atol=1e-8))
+
+    @data(5, 6, 7)
+    def test_mcxrecursive_definition(self, num_ctrl_qubits):
+        """Test the definition of MCXRecursive matches its reference construction, including its
+        action on the dirty ancilla."""
+        gate = MCXRecursive(num_ctrl_qubits)
+        self.assertEqual(gate.definition.name, gate.name)
+        expected = _reference_mcx_recursive(num_ctrl_qubits)
+        self.assertEqual(Operator(gate.definition), Operator(expected))
+
+    @combine(num_ctrl_qubits=[3, 4, 5], dirty_ancillas=[False, True])
+    def test_mcxvchain_definition(self, num_ctrl_qubits, dirty_ancillas):
+        """Test the definition of MCXVChain matches its reference construction, including its
+        action on the ancillas."""
+        gate = MCXVChain(num_ctrl_qubits, dirty_ancillas=dirty_ancillas)
+        self.assertEqual(gate.definition.name, gate.name)
+        expected = _reference_mcx_vchain(num_ctrl_qubits, dirty_ancillas)
+        self.assertEqual(Operator(gate.definition), Operator(expected))
+
+    @data(1, 2, 3, 5)
+    def test_mcphase_definition(self, num_ctrl_qubits):
+        """Test the definition of the multi-controlled phase gate is the controlled phase."""
+        lam = 0.4321
+        gate = MCPhaseGate(lam, num_ctrl_qubits)
+        expected = _compute_control_matrix(PhaseGate(lam).to_matrix(), num_ctrl_qubits)
+        self.assertTrue(matrix_equal(Operator(gate.definition).data, expected, atol=1e-8))
+
+    def test_mcrz_single_control_basis_gates(self):
+        """Test mcrz with one control and basis gates is exactly the CRZ gate, with two CX."""
+        lam = 0.871236 * pi
+        qc = QuantumCircuit(2)
+        qc.mcrz(lam, [0], 1, use_basis_gates=True)
+        self.assertEqual(qc.count_ops().get("cx"), 2)
+        self.assertEqual(Operator(qc), Operator(CRZGate(lam)))
+
+    @data(2, 3, 5)
+    def test_mcrz_basis_gates_matches_mcsu2(self, num_ctrl_qubits):
+        """Test mcrz with basis gates matches the real-diagonal SU(2) construction it replaces."""
+        lam = 0.871236 * pi
+        qc = QuantumCircuit(num_ctrl_qubits + 1)
+        qc.mcrz(lam, list(range(num_ctrl_qubits)), num_ctrl_qubits, use_basis_gates=True)
+        expected = _mcsu2_real_diagonal(
+            RZGate(lam).to_matrix(), num_controls=num_ctrl_qubits, use_basis_gates=True
+        )
+        self.assertEqual(Operator(qc), Operator(expected))
+
     @data(1, 2, 3, 4)
     def test_inverse_x(self, num_ctrl_qubits):
         """Test inverting the controlled X gate."""
@@ -1783,5 +1846,87 @@ class TestControlledAnnotatedGate(QiskitTestCase):
         self.assertEqual(Operator(controlled), Operator(annotated))
 
 
+def _reference_mcx_recursive(num_ctrl_qubits):
+    """The recursive decomposition of the multi-controlled X gate with one dirty ancilla, built
+    from the C3X and C4X gates."""
+
+    def recurse(q, q_ancilla):
+        if len(q) == 4:
+            return [(C3XGate(), q[:])]
+        if len(q) == 5:
+            return [(C4XGate(), q[:])]
+        num_ctrl = len(q) - 1
+        middle = math.ceil(num_ctrl / 2)
+        first_half = [*q[:middle], q_ancilla]
+        second_half = [*q[middle:num_ctrl], q_ancilla, q[num_ctrl]]
+        rule = []
+        rule += recurse(first_half, q[middle])
+        rule += recurse(second_half, q[middle - 1])
+        rule += recurse(first_half, q[middle])
+        rule += recurse(second_half, q[middle - 1])
+        return rule
+
+    qc = QuantumCircuit(num_ctrl_qubits + 2)
+    for gate, qargs in recurse(list(range(num_ctrl_qubits + 1)), num_ctrl_qubits + 1):
+        qc.append(gate, qargs)
+    return qc
+
+
+def _reference_mcx_vchain(num_ctrl_qubits, dirty_ancillas):
+    """The V-chain decomposition of the multi-controlled X gate, built from relative-phase Toffoli
+    gates."""
+    qc = QuantumCircuit(2 * num_ctrl_qubits - 1)
+    q_controls = list(range(num_ctrl_qubits))
+    q_target = num_ctrl_qubits
+    q_ancillas = list(range(num_ctrl_qubits + 1, 2 * num_ctrl_qubits - 1))
+
+    rule = []
+    if dirty_ancillas:
+        i = num_ctrl_qubits - 3
+        rule += [
+            (U2Gate(0, pi), [q_target]),
+            (CXGate(), [q_target, q_ancillas[i]]),
+            (U1Gate(-pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_controls[-1], q_ancillas[i]]),
+            (U1Gate(pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_target, q_ancillas[i]]),
+            (U1Gate(-pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_controls[-1], q_ancillas[i]]),
+            (U1Gate(pi / 4), [q_ancillas[i]]),
+        ]
+        for j in reversed(range(2, num_ctrl_qubits - 1)):
+            rule.append((RCCXGate(), [q_controls[j], q_ancillas[i - 1], q_ancillas[i]]))
+            i -= 1
+    rule.append((RCCXGate(), [q_controls[0], q_controls[1], q_ancillas[0]]))
+    i = 0
+    for j in range(2, num_ctrl_qubits - 1):
+        rule.append((RCCXGate(), [q_controls[j], q_ancillas[i], q_ancillas[i + 1]]))
+        i += 1
+    if dirty_ancillas:
+        rule += [
+            (U1Gate(-pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_controls[-1], q_ancillas[i]]),
+            (U1Gate(pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_target, q_ancillas[i]]),
+            (U1Gate(-pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_controls[-1], q_ancillas[i]]),
+            (U1Gate(pi / 4), [q_ancillas[i]]),
+            (CXGate(), [q_target, q_ancillas[i]]),
+            (U2Gate(0, pi), [q_target]),
+        ]
+    else:
+        rule.append((CCXGate(), [q_controls[-1], q_ancillas[i], q_target]))
+    for j in reversed(range(2, num_ctrl_qubits - 1)):
+        rule.append((RCCXGate(), [q_controls[j], q_ancillas[i - 1], q_ancillas[i]]))
+        i -= 1
+    rule.append((RCCXGate(), [q_controls[0], q_controls[1], q_ancillas[i]]))
+    if dirty_ancillas:
+        for i, j in enumerate(range(2, num_ctrl_qubits - 1)):
+            rule.append((RCCXGate(), [q_controls[j], q_ancillas[i], q_ancillas[i + 1]]))
+    for gate, qargs in rule:
+        qc.append(gate, qargs)
+    return qc
+
+
 if __name__ == "__main__":
     unittest.main()
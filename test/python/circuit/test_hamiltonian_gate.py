@@ -15,12 +15,13 @@
 
 import numpy as np
 from numpy.testing import assert_allclose
+import scipy.linalg
 
 import qiskit
 from qiskit.circuit.library import HamiltonianGate
 from qiskit import QuantumRegister, ClassicalRegister, QuantumCircuit
 from qiskit.circuit import Parameter
-from qiskit.quantum_info import Operator
+from qiskit.quantum_info import Operator, random_hermitian
 from qiskit.converters import circuit_to_dag, dag_to_circuit
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -62,6 +63,20 @@ class TestHamiltonianGate(QiskitTestCase):
             ham.adjoint().to_matrix(), np.transpose(np.conj(ham.to_matrix()))
         )
 
+    def test_matrix(self):
+        """Test the matrix is the exponential of the Hamiltonian, both for Hamiltonians small
+        enough to be diagonalized and for larger ones."""
+        for num_qubits in [2, 7]:
+            hamiltonian = random_hermitian(2**num_qubits, seed=num_qubits).data
+            gate = HamiltonianGate(hamiltonian, 0.4)
+            assert_allclose(gate.to_matrix(), scipy.linalg.expm(-0.4j * hamiltonian), atol=1e-10)
+
+    def test_unbound_time_raises(self):
+        """Test the matrix of a gate with an unbound time can't be taken."""
+        gate = HamiltonianGate([[0, 1], [1, 0]], Parameter("t"))
+        with self.assertRaises(TypeError):
+            gate.to_matrix()
+
 
 class TestHamiltonianCircuit(QiskitTestCase):
     """Hamiltonian gate circuit tests."""
@@ -22,6 +22,8 @@ from qiskit import QuantumRegister
 from qiskit.compiler import transpile
 from qiskit.quantum_info import Operator
 from qiskit.circuit.library.generalized_gates import Isometry
+from qiskit.exceptions import QiskitError
+from qiskit._accelerate.synthesis import synth_isometry
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -134,6 +136,34 @@ class TestIsometry(QiskitTestCase):
         result = Operator(qc)
         np.testing.assert_array_almost_equal(result.data, np.identity(result.dim[0]))
 
+    @data(
+        random_unitary(2, seed=1).data,
+        random_unitary(8, seed=2).data[:, 0:2],
+        random_unitary(16, seed=3).data[:, 0:4],
+    )
+    def test_definition(self, iso):
+        """Test the definition is built from u, rz and cx gates, and keeps the global phase"""
+        num_q_output = int(np.log2(iso.shape[0]))
+        definition = Isometry(iso, 0, 0).definition
+        self.assertEqual(definition.num_qubits, num_q_output)
+        self.assertLessEqual(set(definition.count_ops()), {"u", "rz", "cx"})
+        np.testing.assert_allclose(Operator(definition).data[:, : iso.shape[1]], iso, atol=1e-8)
+
+    def test_definition_with_ancillas(self):
+        """Test the ancillas of the definition are left alone"""
+        iso = random_unitary(4, seed=4).data[:, 0:2]
+        definition = Isometry(iso, num_ancillas_zero=1, num_ancillas_dirty=1).definition
+        self.assertEqual(definition.num_qubits, 4)
+        used = {definition.find_bit(q).index for inst in definition.data for q in inst.qubits}
+        self.assertLessEqual(used, {0, 1})
+
+    def test_synth_isometry_bad_shape(self):
+        """Test the Rust synthesis rejects matrices that aren't isometries of qubits"""
+        with self.assertRaises(QiskitError):
+            synth_isometry(np.ones((3, 1), dtype=complex))
+        with self.assertRaises(QiskitError):
+            synth_isometry(np.ones((2, 4), dtype=complex))
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,99 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the Rust layers and depths of circuits."""
+
+import unittest
+from collections import Counter
+
+from qiskit import QuantumCircuit
+from qiskit.circuit.random import random_circuit
+from qiskit.converters import circuit_to_dag
+from qiskit._accelerate.layers import as_layers, depth, layer_counts
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _circuit():
+    qc = QuantumCircuit(3, 1)
+    qc.h(0)
+    qc.cx(0, 1)
+    qc.x(2)
+    qc.measure(1, 0)
+    qc.x(2).c_if(0, 1)
+    qc.barrier()
+    qc.cz(1, 2)
+    return qc
+
+
+class TestLayers(QiskitTestCase):
+    """Test the Rust partition of circuits into layers."""
+
+    def test_as_layers(self):
+        """Test the layers of a circuit with a measurement and a classical condition."""
+        self.assertEqual(as_layers(_circuit()._data), [[0, 2], [1], [3], [4], [5], [6]])
+        self.assertEqual(as_layers(QuantumCircuit(2)._data), [])
+
+    def test_layer_counts(self):
+        """Test the operations of each layer are counted by name."""
+        counts = layer_counts(_circuit()._data)
+        self.assertEqual(counts[0], {"h": 1, "x": 1})
+        self.assertEqual(counts[3:], [{"x": 1}, {"barrier": 1}, {"cz": 1}])
+
+    def test_same_as_dag_layers(self):
+        """Test the layers are those of ``DAGCircuit.layers``."""
+        for seed in range(5):
+            qc = random_circuit(5, 6, max_operands=3, measure=True, conditional=True, seed=seed)
+            expected = [
+                Counter(node.op.name for node in layer["graph"].op_nodes())
+                for layer in circuit_to_dag(qc).layers()
+            ]
+            self.assertEqual([Counter(counts) for counts in layer_counts(qc._data)], expected)
+            names = [
+                Counter(qc.data[index].operation.name for index in layer)
+                for layer in as_layers(qc._data)
+            ]
+            self.assertEqual(names, expected)
+
+
+class TestDepth(QiskitTestCase):
+    """Test the Rust depths of circuits."""
+
+    def test_depth(self):
+        """Test the depth doesn't count barriers, which still order the operations."""
+        qc = _circuit()
+        self.assertEqual(depth(qc._data), 5)
+        self.assertEqual(depth(qc._data, two_qubit=True), 2)
+        qc = QuantumCircuit(2)
+        qc.h(0)
+        qc.barrier()
+        qc.h(1)
+        self.assertEqual(depth(qc._data), 2)
+        self.assertEqual(depth(QuantumCircuit(2)._data), 0)
+
+    def test_same_as_circuit_depth(self):
+        """Test the depths are those of ``QuantumCircuit.depth``."""
+        for seed in range(5):
+            qc = random_circuit(5, 6, max_operands=3, measure=True, conditional=True, seed=seed)
+            qc.barrier()
+            qc.cx(0, 1)
+            self.assertEqual(depth(qc._data), qc.depth())
+            self.assertEqual(
+                depth(qc._data, two_qubit=True),
+                qc.depth(
+                    lambda instruction: len(instruction.qubits) == 2
+                    and not getattr(instruction.operation, "_directive", False)
+                ),
+            )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -12,10 +12,15 @@
 
 
 """Test random circuit generation utility."""
-from qiskit.circuit import QuantumCircuit, ClassicalRegister, Clbit
+import numpy as np
+
+from qiskit.circuit import QuantumCircuit, ClassicalRegister, Clbit, Parameter
 from qiskit.circuit import Measure
-from qiskit.circuit.random import random_circuit
+from qiskit.circuit.exceptions import CircuitError
+from qiskit.circuit.random import mirror_circuits, random_circuit, random_clifford_circuit
 from qiskit.converters import circuit_to_dag
+from qiskit.quantum_info import Operator, Statevector
+from qiskit._accelerate.random_circuit import random_circuit as random_circuit_rs
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -81,3 +86,103 @@ class TestCircuitRandom(QiskitTestCase):
             bool(getattr(instruction.operation, "condition", None)) for instruction in circ
         ]
         self.assertEqual([False, False, False, True], conditions)
+
+
+class TestRustRandomCircuit(QiskitTestCase):
+    """Test the Rust generation of random circuits of standard gates."""
+
+    def test_layers(self):
+        """Test every qubit is acted on by one gate in each layer."""
+        circuit = QuantumCircuit._from_circuit_data(random_circuit_rs(5, 4, seed=1))
+        self.assertEqual(circuit.num_qubits, 5)
+        self.assertEqual(circuit.num_clbits, 0)
+        self.assertEqual(circuit.depth(), 4)
+        self.assertEqual(sum(len(instruction.qubits) for instruction in circuit.data), 20)
+        self.assertTrue(all(len(instruction.qubits) <= 2 for instruction in circuit.data))
+
+    def test_gates(self):
+        """Test only the given gates, on at most ``max_operands`` qubits, are drawn."""
+        data = random_circuit_rs(4, 3, gates=["h", "rz", "cx", "ccx"], max_operands=3, seed=2)
+        circuit = QuantumCircuit._from_circuit_data(data)
+        self.assertLessEqual(set(circuit.count_ops()), {"h", "rz", "cx", "ccx"})
+        data = random_circuit_rs(4, 3, gates=["h", "cx"], two_qubit_density=0.0, seed=2)
+        circuit = QuantumCircuit._from_circuit_data(data)
+        self.assertEqual(circuit.count_ops(), {"h": 12})
+
+    def test_measure(self):
+        """Test every qubit is measured into its own clbit at the end."""
+        circuit = QuantumCircuit._from_circuit_data(random_circuit_rs(3, 2, measure=True, seed=3))
+        self.assertEqual(circuit.num_clbits, 3)
+        self.assertEqual(circuit.depth(), 3)
+        for index, instruction in enumerate(circuit.data[-3:]):
+            self.assertEqual(instruction.operation.name, "measure")
+            self.assertEqual(circuit.find_bit(instruction.qubits[0]).index, index)
+            self.assertEqual(circuit.find_bit(instruction.clbits[0]).index, index)
+
+    def test_seed(self):
+        """Test the same seed gives the same circuit."""
+        first = QuantumCircuit._from_circuit_data(random_circuit_rs(4, 5, seed=4))
+        second = QuantumCircuit._from_circuit_data(random_circuit_rs(4, 5, seed=4))
+        self.assertEqual(first, second)
+
+    def test_errors(self):
+        """Test unknown gates, no single-qubit gates and densities that aren't probabilities
+        raise."""
+        with self.assertRaises(ValueError):
+            random_circuit_rs(2, 2, gates=["not_a_gate"])
+        with self.assertRaises(ValueError):
+            random_circuit_rs(2, 2, gates=["cx"])
+        with self.assertRaises(ValueError):
+            random_circuit_rs(2, 2, gates=["h", "ccx"], max_operands=2, two_qubit_density=1.5)
+
+
+class TestMirrorCircuits(QiskitTestCase):
+    """Testing qiskit.circuit.random.mirror_circuits"""
+
+    def test_without_pauli_layer(self):
+        """Test a circuit with its inverse appended implements the identity."""
+        circ = random_circuit(num_qubits=4, depth=6, seed=12)
+        circ.global_phase = 0.4
+        [(mirror, expected)] = mirror_circuits(circ, pauli_layer=False)
+        self.assertEqual(expected, "0000")
+        self.assertEqual(mirror.name, f"{circ.name}_mirror")
+        self.assertEqual(mirror.global_phase, 0)
+        self.assertEqual(Operator(mirror), Operator(np.eye(16)))
+        self.assertEqual(mirror.count_ops().get("barrier"), 1)
+
+    def test_clifford_expected_bitstrings(self):
+        """Test each mirror circuit of a Clifford circuit measures its bitstring."""
+        circ = random_clifford_circuit(num_qubits=4, num_gates=30, seed=5)
+        mirrors = mirror_circuits(circ, num_circuits=10, seed=7)
+        self.assertEqual(len(mirrors), 10)
+        for mirror, expected in mirrors:
+            self.assertEqual(len(expected), 4)
+            self.assertEqual(Statevector(mirror).probabilities_dict(decimals=8), {expected: 1.0})
+            self.assertEqual(mirror.count_ops().get("barrier"), 2)
+
+    def test_non_clifford_no_expected_bitstring(self):
+        """Test the mirror circuits of a non-Clifford circuit with Paulis have no bitstring."""
+        circ = QuantumCircuit(2)
+        circ.h(0)
+        circ.t(0)
+        circ.cx(0, 1)
+        for mirror, expected in mirror_circuits(circ, num_circuits=3, barriers=False, seed=1):
+            self.assertIsNone(expected)
+            self.assertNotIn("barrier", mirror.count_ops())
+
+    def test_seed(self):
+        """Test the mirror circuits are reproducible from the seed."""
+        circ = random_clifford_circuit(num_qubits=3, num_gates=10, seed=2)
+        first = mirror_circuits(circ, num_circuits=5, seed=42)
+        self.assertEqual(first, mirror_circuits(circ, num_circuits=5, seed=42))
+        generated = mirror_circuits(circ, num_circuits=5, seed=np.random.default_rng(42))
+        self.assertEqual(
+            generated, mirror_circuits(circ, num_circuits=5, seed=np.random.default_rng(42))
+        )
+
+    def test_unbound_parameters(self):
+        """Test a circuit with unbound parameters raises."""
+        circ = QuantumCircuit(1)
+        circ.rx(Parameter("a"), 0)
+        with self.assertRaises(CircuitError):
+            mirror_circuits(circ)
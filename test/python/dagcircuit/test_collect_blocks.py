@@ -13,6 +13,7 @@
 """Test functionality to collect, split and consolidate blocks from DAGCircuits."""
 
 
+import itertools
 import unittest
 
 from qiskit import QuantumRegister, ClassicalRegister
@@ -24,7 +25,11 @@ from qiskit.converters import (
     dagdependency_to_circuit,
 )
 from qiskit.circuit import QuantumCircuit, Measure, Clbit
+from qiskit.circuit.random import random_circuit
 from qiskit.dagcircuit.collect_blocks import BlockCollector, BlockSplitter, BlockCollapser
+from qiskit.transpiler.passes.optimization.collect_and_collapse import (
+    collect_using_filter_function,
+)
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -931,6 +936,63 @@ class TestCollectBlocks(QiskitTestCase):
         self.assertEqual(collapsed_qc.data[0].operation.definition.num_qubits, 1)
         self.assertEqual(collapsed_qc.data[0].operation.definition.num_clbits, 2)
 
+    def test_rust_collection_matches_block_collector(self):
+        """Test the blocks collected in Rust from the gate names are the ones BlockCollector
+        collects with the equivalent filter function, for every combination of options."""
+        gate_names = {"cx", "swap", "h"}
+
+        def filter_function(node):
+            return node.op.name in gate_names and getattr(node.op, "condition", None) is None
+
+        qc = random_circuit(5, 12, max_operands=2, measure=True, conditional=True, seed=2024)
+        dag = circuit_to_dag(qc)
+        for split_blocks, split_layers, collect_from_back, min_block_size in itertools.product(
+            [False, True], [False, True], [False, True], [1, 2, 3]
+        ):
+            with self.subTest(
+                split_blocks=split_blocks,
+                split_layers=split_layers,
+                collect_from_back=collect_from_back,
+                min_block_size=min_block_size,
+            ):
+                kwargs = {
+                    "split_blocks": split_blocks,
+                    "min_block_size": min_block_size,
+                    "split_layers": split_layers,
+                    "collect_from_back": collect_from_back,
+                }
+                expected = collect_using_filter_function(dag, filter_function, **kwargs)
+                actual = collect_using_filter_function(
+                    dag, filter_function, gate_names=gate_names, **kwargs
+                )
+                self.assertEqual(
+                    [sorted(node._node_id for node in block) for block in actual],
+                    [sorted(node._node_id for node in block) for block in expected],
+                )
+
+    def test_rust_collection_of_linear_gates(self):
+        """Test the Rust collection of the linear gates of a small circuit."""
+        qc = QuantumCircuit(4)
+        qc.cx(0, 1)
+        qc.swap(2, 3)
+        qc.h(1)
+        qc.cx(1, 2)
+        qc.cx(2, 3)
+        blocks = collect_using_filter_function(
+            circuit_to_dag(qc),
+            None,
+            split_blocks=False,
+            min_block_size=2,
+            gate_names=("cx", "swap"),
+        )
+        self.assertEqual(
+            [[(node.op.name, node.qargs) for node in block] for block in blocks],
+            [
+                [("cx", (qc.qubits[0], qc.qubits[1])), ("swap", (qc.qubits[2], qc.qubits[3]))],
+                [("cx", (qc.qubits[1], qc.qubits[2])), ("cx", (qc.qubits[2], qc.qubits[3]))],
+            ],
+        )
+
 
 if __name__ == "__main__":
     unittest.main()
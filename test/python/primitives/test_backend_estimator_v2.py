@@ -473,6 +473,24 @@ class TestBackendEstimatorV2(QiskitTestCase):
         np.testing.assert_allclose(result[0].data.evs, [-1.284366511861733], rtol=self._rtol)
         np.testing.assert_allclose(result[1].data.evs, [-1.284366511861733], rtol=self._rtol)
 
+    @combine(abelian_grouping=[True, False])
+    def test_broadcasting(self, abelian_grouping):
+        """Test the parameter values and the observables broadcast together, with each parameter
+        set bound once for all of its observables."""
+        backend = BasicSimulator()
+        qc = RealAmplitudes(num_qubits=2, reps=1)
+        params = self._rng.random((3, 1, qc.num_parameters))
+        observables = [SparsePauliOp("ZZ"), SparsePauliOp("XI"), SparsePauliOp("IY")]
+        target = StatevectorEstimator().run([(qc, observables, params)]).result()
+        estimator = BackendEstimatorV2(backend=backend, options=self._options)
+        estimator.options.abelian_grouping = abelian_grouping
+        result = estimator.run([(qc, observables, params)]).result()
+        self.assertEqual(result[0].data.evs.shape, (3, 3))
+        self.assertEqual(result[0].data.stds.shape, (3, 3))
+        np.testing.assert_allclose(
+            result[0].data.evs, target[0].data.evs, rtol=self._rtol, atol=1e-1
+        )
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,71 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the grouping and aggregation of the observables of the backend estimator."""
+
+import unittest
+
+from qiskit._accelerate.estimator_grouping import ObservableGrouping
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestObservableGrouping(QiskitTestCase):
+    """Test the measurement groups of Pauli terms and the aggregation of their counts."""
+
+    def test_abelian_grouping(self):
+        """Test qubit-wise commuting terms of a parameter set are measured together."""
+        grouping = ObservableGrouping(2, [["ZZ", "XX", "ZI", "IX", "XX"], ["YI"]])
+        self.assertEqual(len(grouping), 3)
+        self.assertEqual(grouping.bases(), [(0, "XX", [0, 1]), (0, "ZZ", [0, 1]), (1, "YI", [1])])
+
+    def test_no_grouping(self):
+        """Test each term is measured on its own without grouping, and the identity measures
+        qubit 0."""
+        grouping = ObservableGrouping(2, [["ZI", "IZ", "II"]], abelian_grouping=False)
+        self.assertEqual(grouping.bases(), [(0, "II", [0]), (0, "IZ", [0]), (0, "ZI", [1])])
+
+    def test_aggregate(self):
+        """Test the expectation values and variances of observables are the sums of those of
+        their terms."""
+        grouping = ObservableGrouping(2, [["ZZ", "IZ"], ["XI"]])
+        counts = [{"00": 3, "01": 1}, {"1": 2, "0": 2}]
+        evs, variances = grouping.aggregate(
+            counts, [(0, {"ZZ": 2.0, "IZ": -1.0}), (1, {"XI": 1.0}), (0, {"IZ": 1.0})]
+        )
+        self.assertEqual(evs, [0.5, 0.0, 0.5])
+        self.assertEqual(variances, [3.75, 1.0, 0.75])
+
+    def test_multiple_registers(self):
+        """Test only the first register of the outcomes is read."""
+        grouping = ObservableGrouping(1, [["Z"]])
+        evs, _ = grouping.aggregate([{"1 00": 1, "1 01": 1}], [(0, {"Z": 1.0})])
+        self.assertEqual(evs, [-1.0])
+
+    def test_errors(self):
+        """Test bad labels, counts, parameter sets and terms raise."""
+        with self.assertRaises(ValueError):
+            ObservableGrouping(2, [["Z"]])
+        with self.assertRaises(ValueError):
+            ObservableGrouping(1, [["A"]])
+        grouping = ObservableGrouping(1, [["Z"]])
+        with self.assertRaises(ValueError):
+            grouping.aggregate([], [(0, {"Z": 1.0})])
+        with self.assertRaises(ValueError):
+            grouping.aggregate([{"2": 1}], [(0, {"Z": 1.0})])
+        with self.assertRaises(IndexError):
+            grouping.aggregate([{"0": 1}], [(1, {"Z": 1.0})])
+        with self.assertRaises(KeyError):
+            grouping.aggregate([{"0": 1}], [(0, {"X": 1.0})])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,90 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the assembly of parameter-shift Jacobians from expectation values."""
+
+import unittest
+
+import numpy as np
+
+from qiskit import QuantumCircuit
+from qiskit.circuit import ParameterVector
+from qiskit.primitives import StatevectorEstimator
+from qiskit.quantum_info import SparsePauliOp
+from qiskit._accelerate.parameter_shift import ShiftRule
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestShiftRule(QiskitTestCase):
+    """Test parameter-shift rules."""
+
+    def test_two_term(self):
+        """Test the points of the two-term rule."""
+        rule = ShiftRule.two_term(2)
+        self.assertEqual(len(rule), 4)
+        self.assertEqual(rule.num_parameters, 2)
+        self.assertEqual(
+            rule.points,
+            [
+                (0, np.pi / 2, 0.5),
+                (0, -np.pi / 2, -0.5),
+                (1, np.pi / 2, 0.5),
+                (1, -np.pi / 2, -0.5),
+            ],
+        )
+        np.testing.assert_allclose(
+            rule.parameter_values(np.array([0.1, 0.2])),
+            [
+                [0.1 + np.pi / 2, 0.2],
+                [0.1 - np.pi / 2, 0.2],
+                [0.1, 0.2 + np.pi / 2],
+                [0.1, 0.2 - np.pi / 2],
+            ],
+        )
+
+    def test_circuit_jacobian(self):
+        """Test the Jacobian of the expectation values of a circuit from its shifted values."""
+        theta = ParameterVector("t", 2)
+        circuit = QuantumCircuit(2)
+        circuit.ry(theta[0], 0)
+        circuit.rx(theta[1], 1)
+        circuit.cx(0, 1)
+        observables = [SparsePauliOp("IZ"), SparsePauliOp("ZI")]
+        base = np.array([0.4, -1.3])
+        for shift in [np.pi / 2, 0.3]:
+            with self.subTest(shift=shift):
+                rule = ShiftRule.two_term(2, shift)
+                points = rule.parameter_values(base)
+                pub = (circuit, [[obs] for obs in observables], points)
+                values = StatevectorEstimator().run([pub]).result()[0].data.evs
+                jacobian = rule.jacobian(np.ascontiguousarray(values.T))
+                # <Z_0> = cos(t0) and <Z_1> = cos(t0) cos(t1).
+                x, y = base
+                expected = [[-np.sin(x), 0], [-np.sin(x) * np.cos(y), -np.cos(x) * np.sin(y)]]
+                np.testing.assert_allclose(jacobian, expected, atol=1e-12)
+
+    def test_errors(self):
+        """Test points out of range, shifts that are multiples of pi, and values of the wrong
+        shape raise."""
+        with self.assertRaises(IndexError):
+            ShiftRule(1, [(1, 0.5, 1.0)])
+        with self.assertRaises(ValueError):
+            ShiftRule.two_term(1, np.pi)
+        rule = ShiftRule.two_term(2)
+        with self.assertRaises(ValueError):
+            rule.parameter_values(np.zeros(3))
+        with self.assertRaises(ValueError):
+            rule.jacobian(np.zeros((3, 1)))
+
+
+if __name__ == "__main__":
+    unittest.main()
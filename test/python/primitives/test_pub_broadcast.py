@@ -0,0 +1,69 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the broadcasting of the parameter values and observables of estimator pubs."""
+
+import unittest
+
+import numpy as np
+from ddt import ddt, data
+
+from qiskit._accelerate.pub_broadcast import PubBroadcast, broadcast_shapes
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+SHAPES = [((), ()), ((3,), ()), ((), (2, 2)), ((2, 1), (3,)), ((4, 1, 3), (2, 1)), ((0,), (1,))]
+
+
+@ddt
+class TestPubBroadcast(QiskitTestCase):
+    """Test the pairing of the parameter sets and the observables of each element of a pub."""
+
+    @data(*SHAPES)
+    def test_broadcast_shapes(self, shapes):
+        """Test the broadcast shape is NumPy's."""
+        self.assertEqual(broadcast_shapes(list(shapes)), np.broadcast_shapes(*shapes))
+
+    @data(*SHAPES)
+    def test_indices(self, shapes):
+        """Test the flat indices of each element are those of NumPy's broadcast arrays."""
+        parameter_shape, observable_shape = shapes
+        broadcast = PubBroadcast(parameter_shape, observable_shape)
+        parameters = np.arange(np.prod(parameter_shape, dtype=int)).reshape(parameter_shape)
+        observables = np.arange(np.prod(observable_shape, dtype=int)).reshape(observable_shape)
+        bc_parameters, bc_observables = np.broadcast_arrays(parameters, observables)
+        self.assertEqual(broadcast.shape, bc_parameters.shape)
+        self.assertEqual(broadcast.size, bc_parameters.size)
+        np.testing.assert_array_equal(broadcast.parameter_indices, bc_parameters.ravel())
+        np.testing.assert_array_equal(broadcast.observable_indices, bc_observables.ravel())
+
+    def test_binding_groups(self):
+        """Test each parameter set is listed once, by its index in the bindings array, with its
+        distinct observables in the order that they first appear."""
+        broadcast = PubBroadcast((2, 1), (3,))
+        groups, positions = broadcast.binding_groups()
+        self.assertEqual(groups, [((0, 0), [0, 1, 2]), ((1, 0), [0, 1, 2])])
+        self.assertEqual(positions, [0, 0, 0, 1, 1, 1])
+
+        groups, positions = PubBroadcast((3,), (2, 1)).binding_groups()
+        self.assertEqual(groups, [((0,), [0, 1]), ((1,), [0, 1]), ((2,), [0, 1])])
+        self.assertEqual(positions, [0, 1, 2, 0, 1, 2])
+
+    def test_not_broadcastable(self):
+        """Test shapes that don't broadcast together raise."""
+        with self.assertRaises(ValueError):
+            broadcast_shapes([(2, 3), (3, 3)])
+        with self.assertRaisesRegex(ValueError, "not broadcastable"):
+            PubBroadcast((2,), (3,))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,78 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the packing and counting of the shots of the samplers."""
+
+import unittest
+
+import numpy as np
+
+from qiskit.primitives.containers import BitArray
+from qiskit._accelerate.sampler_results import bitstring_counts, pack_memory
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestPackMemory(QiskitTestCase):
+    """Test the packing of the memory of circuits into their registers."""
+
+    def test_registers(self):
+        """Test each register is sliced out of the shots of each circuit."""
+        first, second, wide = pack_memory(
+            [["0x5", "0xe"], ["0x0", "0x3ff"]], [(0, 2), (2, 2), (0, 10)]
+        )
+        np.testing.assert_array_equal(first, [[[1], [2]], [[0], [3]]])
+        np.testing.assert_array_equal(second, [[[1], [3]], [[0], [3]]])
+        np.testing.assert_array_equal(wide, [[[0, 5], [0, 14]], [[0, 0], [3, 255]]])
+        self.assertEqual(wide.dtype, np.uint8)
+
+    def test_bad_memory(self):
+        """Test circuits with different numbers of shots, and outcomes that aren't hexadecimal or
+        are too wide for the registers, raise."""
+        with self.assertRaises(ValueError):
+            pack_memory([["0x0"], ["0x0", "0x1"]], [(0, 1)])
+        with self.assertRaises(ValueError):
+            pack_memory([["0xz"]], [(0, 1)])
+        with self.assertRaises(ValueError):
+            pack_memory([["0x100"]], [(0, 1)])
+
+
+class TestBitstringCounts(QiskitTestCase):
+    """Test the counts of packed bit strings."""
+
+    def test_counts(self):
+        """Test the bits above the bit strings are ignored, and the counts are in the order of
+        first occurrence."""
+        array = np.array([[0, 5], [0, 3], [255, 5], [0, 255]], dtype=np.uint8)
+        counts = bitstring_counts(array, 3)
+        self.assertEqual(list(counts.items()), [("101", 2), ("011", 1), ("111", 1)])
+
+    def test_get_counts(self):
+        """Test the counts of a bit array are those of its bit strings, at each location and
+        over all of them."""
+        rng = np.random.default_rng(12)
+        bit_array = BitArray(rng.integers(0, 256, (2, 3, 20, 2), dtype=np.uint8), 11)
+        strings = [
+            [format(int.from_bytes(shot.tobytes(), "big") & 0x7FF, "011b") for shot in shots]
+            for shots in bit_array.array.reshape(6, 20, 2)
+        ]
+        expected = {}
+        for string in strings[4]:
+            expected[string] = expected.get(string, 0) + 1
+        self.assertEqual(bit_array.get_counts((1, 1)), expected)
+        for string in sum(strings[:4] + strings[5:], []):
+            expected[string] = expected.get(string, 0) + 1
+        self.assertEqual(bit_array.get_counts(), expected)
+        self.assertEqual(sum(bit_array.get_counts(1).values()), 60)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -290,6 +290,20 @@ class TestStatevectorEstimator(QiskitTestCase):
         np.testing.assert_allclose(result[0].data.evs, [-1.284366511861733])
         np.testing.assert_allclose(result[1].data.evs, [-1.284366511861733])
 
+    def test_broadcasting(self):
+        """Test the parameter values and the observables broadcast together, and each element of
+        the result is the expectation value of its own parameter set and observable."""
+        qc = RealAmplitudes(num_qubits=2, reps=1)
+        rng = np.random.default_rng(13)
+        params = rng.random((3, 1, qc.num_parameters))
+        observables = [SparsePauliOp("ZZ"), SparsePauliOp("XI"), SparsePauliOp("IY")]
+        result = StatevectorEstimator().run([(qc, observables, params)]).result()
+        self.assertEqual(result[0].data.evs.shape, (3, 3))
+        for i in range(3):
+            for j, observable in enumerate(observables):
+                target = StatevectorEstimator().run([(qc, observable, params[i, 0])]).result()
+                self.assertAlmostEqual(result[0].data.evs[i, j], target[0].data.evs)
+
 
 if __name__ == "__main__":
     unittest.main()
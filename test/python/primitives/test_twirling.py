@@ -0,0 +1,126 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Tests for the Rust Pauli twirling of circuits and of their measurements."""
+
+import unittest
+
+from ddt import ddt, data
+import numpy as np
+
+from qiskit import QuantumCircuit
+from qiskit.circuit.library import EfficientSU2
+from qiskit.quantum_info import Operator
+from qiskit._accelerate import twirling
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _twirled_circuits(circuit, num_twirls, **kwargs):
+    return [
+        QuantumCircuit._from_circuit_data(data)
+        for data in twirling.twirl_circuit(circuit._data, num_twirls, **kwargs)
+    ]
+
+
+@ddt
+class TestTwirlCircuit(QiskitTestCase):
+    """Tests of the gate twirling of circuits."""
+
+    @data("cx", "cz", "ecr", "iswap")
+    def test_twirls_implement_circuit(self, gate):
+        """Test every twirled copy implements the circuit, including its global phase."""
+        circuit = QuantumCircuit(3, global_phase=0.3)
+        circuit.h(0)
+        getattr(circuit, gate)(0, 1)
+        circuit.s(1)
+        getattr(circuit, gate)(2, 1)
+        getattr(circuit, gate)(1, 0)
+        circuit.rz(0.2, 2)
+        expected = Operator(circuit)
+        twirled = _twirled_circuits(circuit, 20, seed=5)
+        self.assertEqual(len(twirled), 20)
+        for copy in twirled:
+            self.assertEqual(Operator(copy), expected)
+        # With twenty copies of three gates, some Paulis must have been added.
+        self.assertGreater(max(len(copy.data) for copy in twirled), len(circuit.data))
+
+    def test_only_named_gates_twirled(self):
+        """Test the gates that aren't named are left as they are."""
+        circuit = QuantumCircuit(2)
+        circuit.cx(0, 1)
+        circuit.cz(0, 1)
+        for copy in _twirled_circuits(circuit, 10, twirled_gates=["cz"], seed=2):
+            names = [instruction.operation.name for instruction in copy.data]
+            self.assertEqual(names[0], "cx")
+            self.assertEqual(names.count("cz"), 1)
+            self.assertEqual(Operator(copy), Operator(circuit))
+
+    def test_seeded(self):
+        """Test the copies are reproducible from the seed."""
+        circuit = EfficientSU2(3, entanglement="circular", reps=2).decompose()
+        circuit = circuit.assign_parameters(np.linspace(0, 1, circuit.num_parameters))
+        first = _twirled_circuits(circuit, 4, seed=17)
+        self.assertEqual(first, _twirled_circuits(circuit, 4, seed=17))
+
+    def test_measurements_kept(self):
+        """Test instructions that aren't gates are kept in place."""
+        circuit = QuantumCircuit(2, 2)
+        circuit.cx(0, 1)
+        circuit.barrier()
+        circuit.measure([0, 1], [0, 1])
+        for copy in _twirled_circuits(circuit, 5, seed=1):
+            self.assertEqual(copy.num_clbits, 2)
+            names = [instruction.operation.name for instruction in copy.data[-3:]]
+            self.assertEqual(names, ["barrier", "measure", "measure"])
+
+    def test_bad_gate(self):
+        """Test naming a gate that can't be twirled raises."""
+        with self.assertRaises(ValueError):
+            twirling.twirl_circuit(QuantumCircuit(2)._data, 1, twirled_gates=["ch"])
+
+
+class TestMeasurementTwirls(QiskitTestCase):
+    """Tests of the combination of expectation values measured under measurement twirls."""
+
+    def test_twirl_signs(self):
+        """Test a term is negated once for each flipped qubit in its support."""
+        flips = np.array([[False, False], [True, False], [True, True]])
+        supports = np.array([[True, False], [True, True], [False, False]])
+        signs = twirling.twirl_signs(flips, supports)
+        np.testing.assert_array_equal(signs, [[1, 1, 1], [-1, -1, 1], [-1, 1, 1]])
+
+    def test_combine_twirls(self):
+        """Test the values of the twirls are signed back and weighted by their shots."""
+        expvals = np.array([[0.5, 0.2], [-0.3, 0.2]])
+        variances = np.array([[0.75, 0.96], [0.91, 0.96]])
+        shots = np.array([100, 300], dtype=np.uint64)
+        flips = np.array([[False], [True]])
+        supports = np.array([[True], [False]])
+        values, variance, stds = twirling.combine_twirls(expvals, variances, shots, flips, supports)
+        np.testing.assert_allclose(values, [0.35, 0.2])
+        np.testing.assert_allclose(variance, [1 - 0.35**2, 0.96])
+        np.testing.assert_allclose(stds, np.sqrt(variance / 400))
+
+    def test_combine_twirls_no_shots(self):
+        """Test twirls without shots raise."""
+        with self.assertRaises(ValueError):
+            twirling.combine_twirls(
+                np.zeros((1, 1)),
+                np.zeros((1, 1)),
+                np.zeros(1, dtype=np.uint64),
+                np.zeros((1, 1), dtype=bool),
+                np.zeros((1, 1), dtype=bool),
+            )
+
+
+if __name__ == "__main__":
+    unittest.main()
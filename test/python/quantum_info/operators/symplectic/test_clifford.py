@@ -15,7 +15,7 @@
 
 import unittest
 import numpy as np
-from ddt import ddt
+from ddt import ddt, data, unpack
 
 from qiskit.circuit import Gate, QuantumCircuit
 from qiskit.circuit.random import random_clifford_circuit
@@ -28,6 +28,7 @@ from qiskit.circuit.library import (
     CXGate,
     CYGate,
     CZGate,
+    DCXGate,
     ECRGate,
     HGate,
     IGate,
@@ -38,8 +39,11 @@ from qiskit.circuit.library import (
     RYYGate,
     RZZGate,
     RZXGate,
+    SdgGate,
     SGate,
     SwapGate,
+    SXdgGate,
+    SXGate,
     XGate,
     XXMinusYYGate,
     XXPlusYYGate,
@@ -51,6 +55,7 @@ from qiskit.circuit.library import (
     PauliGate,
 )
 from qiskit.exceptions import QiskitError
+from qiskit._accelerate.clifford import CliffordTableau
 from qiskit.quantum_info import random_clifford
 from qiskit.quantum_info.operators import Clifford, Operator
 from qiskit.quantum_info.operators.predicates import matrix_equal
@@ -920,5 +925,95 @@ class TestCliffordOperators(QiskitTestCase):
         )
 
 
+@ddt
+class TestCliffordTableau(QiskitTestCase):
+    """Tests of the Rust Clifford tableau."""
+
+    @data(
+        (IGate(), [0]),
+        (XGate(), [1]),
+        (YGate(), [0]),
+        (ZGate(), [1]),
+        (HGate(), [0]),
+        (SGate(), [1]),
+        (SdgGate(), [0]),
+        (SXGate(), [1]),
+        (SXdgGate(), [0]),
+        (CXGate(), [1, 0]),
+        (CYGate(), [0, 1]),
+        (CZGate(), [1, 0]),
+        (SwapGate(), [0, 1]),
+        (iSwapGate(), [1, 0]),
+        (DCXGate(), [0, 1]),
+        (ECRGate(), [1, 0]),
+    )
+    @unpack
+    def test_append_gate(self, gate, qubits):
+        """Test appending each gate gives the tableau of its matrix"""
+        circuit = QuantumCircuit(2)
+        circuit.h(0)
+        circuit.s(1)
+        circuit.cx(0, 1)
+        tableau = CliffordTableau(Clifford(circuit).tableau)
+        tableau.append_gate(gate.name, qubits)
+        circuit.append(gate, qubits)
+        self.assertTrue(
+            matrix_equal(
+                Clifford(tableau.tableau).to_matrix(), Operator(circuit).data, ignore_phase=True
+            )
+        )
+
+    @data(1, 2, 4)
+    def test_compose(self, num_qubits):
+        """Test composing tableaus matches composing the operators"""
+        first = random_clifford(num_qubits, seed=num_qubits)
+        second = random_clifford(num_qubits, seed=10 + num_qubits)
+        first_tableau = CliffordTableau(first.tableau)
+        second_tableau = CliffordTableau(second.tableau)
+        composed = Clifford(first_tableau.compose(second_tableau).tableau)
+        self.assertTrue(composed.to_operator().equiv(first.to_operator() & second.to_operator()))
+        composed = Clifford(first_tableau.compose(second_tableau, front=True).tableau)
+        self.assertTrue(composed.to_operator().equiv(first.to_operator() @ second.to_operator()))
+
+    @data(1, 2, 4)
+    def test_adjoint(self, num_qubits):
+        """Test the adjoint tableau is the inverse"""
+        clifford = random_clifford(num_qubits, seed=20 + num_qubits)
+        tableau = CliffordTableau(clifford.tableau)
+        adjoint = tableau.adjoint()
+        self.assertTrue(
+            Clifford(adjoint.tableau).to_operator().equiv(clifford.to_operator().adjoint())
+        )
+        self.assertEqual(tableau.compose(adjoint), CliffordTableau.identity(num_qubits))
+
+    def test_circuit_mixing_basis_gates(self):
+        """Test a circuit that mixes basis gates with other operations is appended in order"""
+        circuit = QuantumCircuit(3)
+        circuit.h(0)
+        circuit.cx(0, 2)
+        circuit.append(LinearFunction([[1, 1, 0], [0, 1, 0], [0, 0, 1]]), [0, 1, 2])
+        circuit.s(1)
+        circuit.barrier()
+        circuit.append(PermutationGate([2, 0, 1]), [0, 1, 2])
+        circuit.sdg(2)
+        circuit.cz(1, 0)
+        self.assertTrue(Clifford(circuit).to_operator().equiv(Operator(circuit)))
+
+    def test_errors(self):
+        """Test invalid gates, qubits and shapes raise"""
+        tableau = CliffordTableau.identity(2)
+        with self.assertRaises(QiskitError):
+            tableau.append_gate("t", [0])
+        with self.assertRaises(QiskitError):
+            tableau.append_gate("cx", [1, 1])
+        with self.assertRaises(IndexError):
+            tableau.append_gate("h", [2])
+        with self.assertRaises(QiskitError):
+            tableau.compose(CliffordTableau.identity(3))
+        with self.assertRaises(QiskitError):
+            CliffordTableau(np.zeros((3, 4), dtype=bool))
+        self.assertEqual(tableau, CliffordTableau.identity(2))
+
+
 if __name__ == "__main__":
     unittest.main()
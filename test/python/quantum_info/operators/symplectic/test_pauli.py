@@ -48,6 +48,7 @@ from qiskit.providers.fake_provider import GenericBackendV2
 from qiskit.quantum_info.operators import Operator, Pauli, SparsePauliOp
 from qiskit.quantum_info.random import random_clifford, random_pauli
 from qiskit.utils import optionals
+from qiskit._accelerate.pauli import Pauli as Pauli_rs
 
 LABEL_REGEX = re.compile(r"(?P<coeff>[+-]?1?[ij]?)(?P<pauli>[IXYZ]*)")
 PHASE_MAP = {"": 0, "-i": 1, "-": 2, "i": 3}
@@ -89,12 +90,28 @@ class TestPauliConversions(QiskitTestCase):
         pauli = Pauli(label)
         self.assertEqual(Pauli(str(pauli)), pauli)
 
-    @data("S", "XX-")
+    @data("S", "XX-", "x", "iiX", "+-X", "i1X", "1+X", "X Y")
     def test_invalid_labels(self, label):
         """Test raise if invalid labels are supplied"""
         with self.assertRaises(QiskitError):
             Pauli(label)
 
+    @data(
+        ("+1iXY", "iXY"),
+        ("1jXY", "iXY"),
+        ("+jXY", "iXY"),
+        ("-1jZ", "-iZ"),
+        ("-1Y", "-Y"),
+        ("1I", "I"),
+        ("-1i", "-i"),
+    )
+    @unpack
+    def test_label_coefficients(self, label, canonical):
+        """Test every spelling of the coefficient of a label is accepted"""
+        pauli = Pauli(label)
+        self.assertEqual(pauli, Pauli(canonical))
+        self.assertEqual(pauli.phase, _phase_from_label(canonical))
+
     @data(*pauli_group_labels(1), *pauli_group_labels(2))
     def test_to_operator(self, label):
         """Test Pauli operator conversion"""
@@ -624,6 +641,88 @@ class TestPauli(QiskitTestCase):
         res = op.apply_layout(layout=layout, num_qubits=5)
         self.assertEqual(Pauli(phase + "IIIII"), res)
 
+    @data("", "-i", "-", "i")
+    def test_apply_layout_keeps_phase(self, phase):
+        """Test apply_layout moves the Y terms without changing the phase"""
+        op = Pauli(phase + "YXZ")
+        res = op.apply_layout([3, 0, 1], 5)
+        self.assertEqual(res, Pauli(phase + "IZIYX"))
+        self.assertEqual(res, Pauli("IIIII").compose(op, qargs=[3, 0, 1]))
+
+
+@ddt
+class TestPauliRust(QiskitTestCase):
+    """Tests of the Rust Pauli type"""
+
+    @data(*pauli_group_labels(2))
+    def test_label_round_trip(self, label):
+        """Test a label is parsed into the phase and the bits of Pauli"""
+        pauli = Pauli_rs.from_label(label)
+        self.assertEqual(pauli.to_label(True), label)
+        self.assertEqual(pauli.phase, Pauli(label).phase)
+        np.testing.assert_array_equal(pauli.z, Pauli(label).z)
+        np.testing.assert_array_equal(pauli.x, Pauli(label).x)
+        self.assertEqual(pauli.zx_phase, Pauli(label)._phase[0])
+
+    @data(*it.product(pauli_group_labels(2, False), ["", "-i", "-", "i"]))
+    @unpack
+    def test_dot(self, label, phase):
+        """Test the products of all pairs of two-qubit Paulis match their matrices"""
+        first = Pauli_rs.from_label(phase + label)
+        for other in pauli_group_labels(2, False):
+            second = Pauli_rs.from_label(other)
+            expected = operator_from_label(phase + label).dot(operator_from_label(other))
+            self.assertEqual(operator_from_label(first.dot(second).to_label(True)), expected)
+            self.assertEqual(first.compose(second, front=True), first.dot(second))
+            self.assertEqual(first.compose(second, front=False), second.dot(first))
+            first_op, second_op = operator_from_label(label), operator_from_label(other)
+            self.assertEqual(
+                first.commutes(second), first_op.dot(second_op) == second_op.dot(first_op)
+            )
+
+    @data(
+        (IGate(), [0]),
+        (XGate(), [1]),
+        (YGate(), [0]),
+        (ZGate(), [1]),
+        (HGate(), [0]),
+        (SGate(), [1]),
+        (SdgGate(), [0]),
+        (CXGate(), [1, 0]),
+        (CYGate(), [0, 1]),
+        (CZGate(), [1, 0]),
+        (SwapGate(), [0, 1]),
+        (ECRGate(), [1, 0]),
+    )
+    @unpack
+    def test_conjugate_by_gate(self, gate, qubits):
+        """Test conjugating by a gate matches conjugating the matrix"""
+        circuit = QuantumCircuit(2)
+        circuit.append(gate, qubits)
+        unitary = Operator(circuit)
+        for label in pauli_group_labels(2):
+            pauli = Pauli_rs.from_label(label)
+            pauli.conjugate_by_gate(gate.name, qubits)
+            expected = unitary.dot(operator_from_label(label)).dot(unitary.adjoint())
+            self.assertEqual(operator_from_label(pauli.to_label(True)), expected)
+
+    def test_errors(self):
+        """Test the Rust Pauli rejects invalid inputs"""
+        with self.assertRaises(QiskitError):
+            Pauli_rs([True], [True, False], 0)
+        with self.assertRaises(QiskitError):
+            Pauli_rs.from_label("XA")
+        with self.assertRaises(QiskitError):
+            Pauli_rs.from_label("XY").dot(Pauli_rs.from_label("X"))
+        pauli = Pauli_rs.from_label("XY")
+        with self.assertRaises(IndexError):
+            pauli.conjugate_by_gate("h", [2])
+        with self.assertRaises(QiskitError):
+            pauli.conjugate_by_gate("cx", [1, 1])
+        with self.assertRaises(QiskitError):
+            pauli.conjugate_by_gate("t", [0])
+        self.assertEqual(pauli, Pauli_rs.from_label("XY"))
+
 
 if __name__ == "__main__":
     unittest.main()
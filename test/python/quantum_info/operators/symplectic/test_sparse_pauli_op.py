@@ -30,6 +30,8 @@ from qiskit.primitives import BackendEstimator
 from qiskit.providers.fake_provider import GenericBackendV2
 from qiskit.quantum_info.operators import Operator, Pauli, PauliList, SparsePauliOp
 from qiskit.utils import optionals
+from qiskit._accelerate.nlayout import NLayout
+from qiskit._accelerate.sparse_pauli_op import apply_layout_to_paulis
 
 
 def pauli_mat(label):
@@ -300,6 +302,73 @@ class TestSparsePauliOpConversions(QiskitTestCase):
             target += coeff * pauli_mat(label)
         np.testing.assert_array_equal(spp_op.to_matrix(), target)
 
+    def test_to_matrix_out_dense(self):
+        """Test to_matrix writes a dense matrix into a given array, which can be reused."""
+        ops = [
+            SparsePauliOp(["XIZY", "YYII", "IZXZ"], [0.25, 0.125j, 0.5 - 0.25j]),
+            SparsePauliOp(["IIII", "ZXYI"], [-0.5, 2.0]),
+        ]
+        out = np.full((16, 16), 1 + 1j)
+        for op in ops:
+            for force_serial in (True, False):
+                with self.subTest(op=op, force_serial=force_serial):
+                    matrix = op.to_matrix(out=out, force_serial=force_serial)
+                    self.assertIs(matrix, out)
+                    np.testing.assert_array_equal(out, op.to_matrix())
+
+    @combine(index_dtype=[np.int32, np.int64])
+    def test_to_matrix_out_sparse(self, index_dtype):
+        """Test to_matrix writes a sparse matrix into given buffers, which can be reused."""
+        ops = [
+            SparsePauliOp(["XIZY", "YYII", "IZXZ"], [0.25, 0.125j, 0.5 - 0.25j]),
+            SparsePauliOp(["IIII", "ZXYI"], [-0.5, 2.0]),
+            SparsePauliOp.from_list([], num_qubits=4),
+        ]
+        data = np.zeros(64, dtype=complex)
+        indices = np.zeros(64, dtype=index_dtype)
+        indptr = np.zeros(17, dtype=index_dtype)
+        for op in ops:
+            with self.subTest(op=op):
+                matrix = op.to_matrix(sparse=True, out=(data, indices, indptr))
+                self.assertIsInstance(matrix, scipy.sparse.csr_matrix)
+                if matrix.nnz:
+                    self.assertTrue(np.shares_memory(matrix.data, data))
+                np.testing.assert_array_equal(matrix.toarray(), op.to_matrix())
+
+    def test_to_matrix_out_parameters(self):
+        """Test to_matrix writes a dense matrix with object coefficients into a given array, and
+        refuses sparse buffers."""
+        op = SparsePauliOp(["XI", "YZ"], np.array(ParameterVector("a", 2)))
+        out = np.zeros((4, 4), dtype=object)
+        self.assertIs(op.to_matrix(out=out), out)
+        np.testing.assert_array_equal(out, op.to_matrix())
+        buffers = (np.zeros(8, dtype=complex), np.zeros(8, dtype=np.int64), np.zeros(5, np.int64))
+        with self.assertRaises(QiskitError):
+            op.to_matrix(sparse=True, out=buffers)
+
+    def test_to_matrix_out_invalid(self):
+        """Test to_matrix raises for output buffers of the wrong shapes or types."""
+        op = SparsePauliOp(["XIZ", "YYI", "IZX"], [0.25, 0.125j, 0.5 - 0.25j])
+        with self.assertRaisesRegex(ValueError, "shape"):
+            op.to_matrix(out=np.zeros((8, 4), dtype=complex))
+        with self.assertRaisesRegex(ValueError, "C-contiguous"):
+            op.to_matrix(out=np.zeros((8, 16), dtype=complex)[:, ::2])
+        with self.assertRaisesRegex(ValueError, "only have room for 10"):
+            op.to_matrix(
+                sparse=True,
+                out=(np.zeros(10, dtype=complex), np.zeros(12, np.int64), np.zeros(9, np.int64)),
+            )
+        with self.assertRaisesRegex(ValueError, "indptr has 8 entries"):
+            op.to_matrix(
+                sparse=True,
+                out=(np.zeros(24, dtype=complex), np.zeros(24, np.int64), np.zeros(8, np.int64)),
+            )
+        with self.assertRaisesRegex(ValueError, "both of int32 or both of int64"):
+            op.to_matrix(
+                sparse=True,
+                out=(np.zeros(24, dtype=complex), np.zeros(24, np.int32), np.zeros(9, np.int64)),
+            )
+
     def test_to_operator(self):
         """Test to_operator method."""
         labels = ["XI", "YZ", "YY", "ZZ"]
@@ -1206,6 +1275,38 @@ class TestSparsePauliOpMethods(QiskitTestCase):
             res = op.apply_layout(layout=layout, num_qubits=5)
             self.assertEqual(SparsePauliOp.from_list([("IIIII", 1), ("IIIII", 2)]), res)
 
+    def test_apply_layout_matches_compose(self):
+        """Test apply_layout matches composing the operator onto an identity"""
+        rng = np.random.default_rng(2024)
+        paulis = PauliList.from_symplectic(
+            rng.integers(2, size=(50, 6), dtype=bool),
+            rng.integers(2, size=(50, 6), dtype=bool),
+            rng.integers(4, size=50),
+        )
+        op = SparsePauliOp(paulis, rng.normal(size=50) + 1j * rng.normal(size=50))
+        layout = [7, 2, 0, 5, 3, 8]
+        res = op.apply_layout(layout, 9)
+        target = SparsePauliOp("I" * 9).compose(op, qargs=layout)
+        self.assertEqual(res, target)
+
+    def test_apply_layout_to_paulis_nlayout(self):
+        """Test the Rust kernel of apply_layout accepts an NLayout"""
+        paulis = PauliList(["XYZ", "-iZZI", "IXY"])
+        layout = [2, 0, 1]
+        z_list, x_list = apply_layout_to_paulis(paulis.z, paulis.x, layout)
+        z_nlayout, x_nlayout = apply_layout_to_paulis(
+            paulis.z, paulis.x, NLayout.from_virtual_to_physical(layout)
+        )
+        np.testing.assert_array_equal(z_list, z_nlayout)
+        np.testing.assert_array_equal(x_list, x_nlayout)
+        np.testing.assert_array_equal(z_list[:, layout], paulis.z)
+        np.testing.assert_array_equal(x_list[:, layout], paulis.x)
+
+    def test_apply_layout_to_paulis_shape_mismatch(self):
+        """Test the Rust kernel of apply_layout rejects z and x of different shapes"""
+        with self.assertRaises(QiskitError):
+            apply_layout_to_paulis(np.zeros((2, 3), dtype=bool), np.zeros((2, 2), dtype=bool), None)
+
 
 if __name__ == "__main__":
     unittest.main()
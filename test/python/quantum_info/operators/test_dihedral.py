@@ -19,6 +19,7 @@ from test import combine
 from ddt import ddt
 
 import numpy as np
+from qiskit._accelerate.cnotdihedral import CNOTDihedralElement
 from qiskit.circuit import QuantumCircuit, Gate
 from qiskit.circuit.library import (
     IGate,
@@ -36,6 +37,7 @@ from qiskit.circuit.library import (
     SwapGate,
     CCZGate,
 )
+from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators import Operator
 from qiskit.quantum_info.operators import random
 from qiskit.quantum_info.operators.dihedral import CNOTDihedral
@@ -923,6 +925,58 @@ class TestCNOTDihedral(unittest.TestCase):
         value = CNOTDihedral(circ)
         self.assertEqual(value, target)
 
+    @combine(num_qubits=[1, 2, 3, 4])
+    def test_rust_element_append_gate(self, num_qubits):
+        """Test the Rust element built gate by gate matches CNOTDihedral on {num_qubits} qubits"""
+        rng = np.random.default_rng(4321)
+        for _ in range(10):
+            circ = random_cnotdihedral_circuit(num_qubits, 5 * num_qubits, seed=rng)
+            elem = CNOTDihedralElement.identity(num_qubits)
+            for instruction in circ.data:
+                elem.append_gate(
+                    instruction.operation.name,
+                    [circ.find_bit(qubit).index for qubit in instruction.qubits],
+                )
+            target = CNOTDihedral(circ)
+            self.assertEqual(CNOTDihedral._from_rust(elem), target)
+            self.assertEqual(target._to_rust(), elem)
+
+    def test_rust_element_compose(self):
+        """Test composing Rust elements matches composing the circuits"""
+        rng = np.random.default_rng(99)
+        first = random_cnotdihedral_circuit(3, 15, seed=rng)
+        second = random_cnotdihedral_circuit(3, 15, seed=rng)
+        target = CNOTDihedral(first.compose(second))
+        target.poly.weight_0 = 0
+        elem1, elem2 = CNOTDihedral(first)._to_rust(), CNOTDihedral(second)._to_rust()
+        self.assertEqual(CNOTDihedral._from_rust(elem1.compose(elem2)), target)
+        self.assertEqual(CNOTDihedral._from_rust(elem2.compose(elem1, front=True)), target)
+
+    def test_rust_element_errors(self):
+        """Test the Rust element raises on invalid gates and qubits"""
+        elem = CNOTDihedralElement.identity(2)
+        with self.assertRaises(QiskitError):
+            elem.append_gate("h", [0])
+        with self.assertRaises(QiskitError):
+            elem.append_gate("cx", [1, 1])
+        with self.assertRaises(QiskitError):
+            elem.append_gate("t", [0, 1])
+        with self.assertRaises(IndexError):
+            elem.append_gate("cx", [0, 2])
+        with self.assertRaises(QiskitError):
+            elem.compose(CNOTDihedralElement.identity(3))
+        with self.assertRaises(QiskitError):
+            CNOTDihedralElement(
+                np.eye(2, dtype=bool), np.zeros(2, dtype=bool), np.zeros(3, dtype=np.uint8)
+            )
+
+    def test_synth_general_not_invertible(self):
+        """Test synthesis of an element with a singular linear part raises"""
+        elem = CNOTDihedral(num_qubits=2)
+        elem.linear = np.zeros((2, 2), dtype=int)
+        with self.assertRaises(QiskitError):
+            synth_cnotdihedral_general(elem)
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -16,11 +16,14 @@ from ddt import ddt
 
 import numpy as np
 
-from qiskit.quantum_info import Operator, Choi
+from qiskit.circuit.library import CXGate
+from qiskit.exceptions import QiskitError
+from qiskit.quantum_info import Operator, Choi, Kraus, SuperOp, random_unitary
 from qiskit.quantum_info import process_fidelity
 from qiskit.quantum_info import average_gate_fidelity
 from qiskit.quantum_info import gate_error
 from qiskit.quantum_info import diamond_norm
+from qiskit._accelerate import fidelity
 from test import combine  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -185,5 +188,68 @@ class TestOperatorMeasures(QiskitTestCase):
             self.skipTest("CVXPY solver failed.")
 
 
+class TestGateFidelityRust(QiskitTestCase):
+    """Tests for the Rust errors of noisy channels with respect to an ideal gate"""
+
+    def _depolarized(self, unitary, prob):
+        """The superoperator of ``unitary`` followed by a depolarizing channel."""
+        dim = unitary.dim[0]
+        vec_identity = np.eye(dim).reshape(-1)
+        depolarizing = np.outer(vec_identity, vec_identity) / dim
+        return (1 - prob) * SuperOp(unitary).data + prob * depolarizing
+
+    def test_gate_process_fidelity(self):
+        """Test the process fidelity matches process_fidelity"""
+        ideal = random_unitary(4, seed=1)
+        noisy = SuperOp(self._depolarized(ideal, 0.1)).compose(random_unitary(4, seed=2))
+        target = process_fidelity(noisy, ideal)
+        value = fidelity.gate_process_fidelity(ideal.data, noisy.data)
+        self.assertAlmostEqual(value, target, places=10)
+        value = fidelity.gate_process_fidelity(ideal.data, SuperOp(ideal).data)
+        self.assertAlmostEqual(value, 1.0, places=10)
+
+    def test_gate_process_fidelity_gate(self):
+        """Test the ideal gate may be given as a gate object"""
+        noisy = self._depolarized(Operator(CXGate()), 0.2)
+        self.assertAlmostEqual(
+            fidelity.gate_process_fidelity(CXGate(), noisy),
+            fidelity.gate_process_fidelity(Operator(CXGate()).data, noisy),
+            places=12,
+        )
+
+    def test_gate_process_fidelity_kraus(self):
+        """Test the Kraus fidelity matches the superoperator fidelity"""
+        ideal = random_unitary(2, seed=3)
+        kraus = Kraus([np.sqrt(0.8) * ideal.data, np.sqrt(0.2) * random_unitary(2, seed=4).data])
+        value = fidelity.gate_process_fidelity_kraus(ideal.data, kraus.data)
+        target = fidelity.gate_process_fidelity(ideal.data, SuperOp(kraus).data)
+        self.assertAlmostEqual(value, target, places=10)
+        self.assertAlmostEqual(value, process_fidelity(kraus, ideal), places=10)
+
+    def test_gate_error(self):
+        """Test the gate error matches gate_error"""
+        ideal = random_unitary(4, seed=5)
+        noisy = SuperOp(self._depolarized(ideal, 0.05))
+        value = fidelity.gate_error(ideal.data, noisy.data)
+        self.assertAlmostEqual(value, gate_error(noisy, ideal), places=10)
+
+    def test_unitarity(self):
+        """Test the unitarity of unitary and depolarizing channels"""
+        ideal = random_unitary(4, seed=6)
+        self.assertAlmostEqual(fidelity.unitarity(SuperOp(ideal).data), 1.0, places=10)
+        for prob in [0.1, 0.5, 1.0]:
+            value = fidelity.unitarity(self._depolarized(ideal, prob))
+            self.assertAlmostEqual(value, (1 - prob) ** 2, places=10)
+
+    def test_errors(self):
+        """Test mismatched shapes raise"""
+        with self.assertRaises(QiskitError):
+            fidelity.gate_process_fidelity(np.eye(2, dtype=complex), np.eye(3, dtype=complex))
+        with self.assertRaises(QiskitError):
+            fidelity.gate_process_fidelity(np.eye(2, dtype=complex), np.eye(16, dtype=complex))
+        with self.assertRaises(ValueError):
+            fidelity.unitarity(np.eye(1, dtype=complex))
+
+
 if __name__ == "__main__":
     unittest.main()
@@ -25,14 +25,17 @@ from numpy.testing import assert_allclose
 import scipy.linalg as la
 
 from qiskit import QiskitError
+from qiskit._accelerate import operator as operator_rs
 from qiskit import QuantumRegister, ClassicalRegister, QuantumCircuit
 from qiskit.circuit.library import HGate, CHGate, CXGate, QFT
 from qiskit.transpiler import CouplingMap
 from qiskit.transpiler.layout import Layout, TranspileLayout
+from qiskit.quantum_info import Pauli, SparsePauliOp, random_unitary
 from qiskit.quantum_info.operators import Operator, ScalarOp
 from qiskit.quantum_info.operators.predicates import matrix_equal
 from qiskit.compiler.transpiler import transpile
-from qiskit.circuit import Qubit
+from qiskit.circuit import Gate, Qubit
+from qiskit.circuit.library.standard_gates import get_standard_gate_name_mapping
 from qiskit.circuit.library import Permutation, PermutationGate
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -179,6 +182,37 @@ class TestOperator(OperatorTestCase):
         global_phase_equivalent = matrix_equal(op.data, target, ignore_phase=True)
         self.assertTrue(global_phase_equivalent)
 
+    def test_circuit_init_standard_gates(self):
+        """Test initialization from circuits of each standard gate."""
+        for name, gate in get_standard_gate_name_mapping().items():
+            if not isinstance(gate, Gate):
+                continue
+            with self.subTest(gate=name):
+                gate = gate.copy()
+                gate.params = [0.3 * (i + 1) for i in range(len(gate.params))]
+                qargs = [2, 0, 3, 1][: gate.num_qubits]
+                circuit = QuantumCircuit(4, global_phase=0.25)
+                circuit.h(0)
+                circuit.cx(0, 3)
+                circuit.append(gate, qargs)
+                target = (
+                    Operator(np.eye(16))
+                    .compose(HGate().to_matrix(), [0])
+                    .compose(CXGate().to_matrix(), [0, 3])
+                    .compose(gate.to_matrix(), qargs)
+                )
+                self.assertEqual(Operator(circuit), np.exp(0.25j) * target)
+
+    def test_circuit_init_barrier_delay(self):
+        """Test barriers and delays in a circuit act as the identity."""
+        circuit = QuantumCircuit(2)
+        circuit.h(1)
+        circuit.barrier()
+        circuit.delay(10, 0)
+        circuit.cz(0, 1)
+        target = np.diag([1, 1, 1, -1]) @ np.kron(self.UH, self.UI)
+        self.assertEqual(Operator(circuit), Operator(target))
+
     def test_instruction_init(self):
         """Test initialization from a circuit."""
         gate = CXGate()
@@ -542,6 +576,33 @@ class TestOperator(OperatorTestCase):
         self.assertEqual(op12.dim, (6, 6))
         assert_allclose(op12.data, Operator(mat12).data)
 
+    @combine(dtype_a=[np.float64, np.complex64, np.complex128], dtype_b=[np.float64, np.complex128])
+    def test_compose_tensor_mixed_dtype(self, dtype_a, dtype_b):
+        """Test compose and tensor of operators of other types than complex128 match Numpy, and
+        tensor keeps the type Numpy gives."""
+        mat_a = self.rand_matrix(4, 4, real=np.dtype(dtype_a).kind == "f").astype(dtype_a)
+        mat_b = self.rand_matrix(4, 4, real=np.dtype(dtype_b).kind == "f").astype(dtype_b)
+        op_a = _operator_with_data(mat_a)
+        op_b = _operator_with_data(mat_b)
+        dtype = np.result_type(mat_a, mat_b)
+        atol = 1e-5 if dtype == np.complex64 else 1e-10
+
+        assert_allclose(op_a.compose(op_b).data, np.dot(mat_b, mat_a), atol=atol)
+        assert_allclose(op_a.compose(op_b, front=True).data, np.dot(mat_a, mat_b), atol=atol)
+
+        tensored = op_a.tensor(op_b)
+        self.assertEqual(tensored.data.dtype, dtype)
+        assert_allclose(tensored.data, np.kron(mat_a, mat_b), atol=atol)
+
+    @combine(dtype=[np.float64, np.complex64, np.complex128], n=[0, 1, 3])
+    def test_power_keeps_dtype(self, dtype, n):
+        """Test integer powers give the type and the value Numpy does for the operator."""
+        mat = self.rand_matrix(4, 4, real=np.dtype(dtype).kind == "f").astype(dtype)
+        powered = _operator_with_data(mat).power(n)
+        self.assertEqual(powered.data.dtype, np.dtype(dtype))
+        atol = 1e-5 if dtype == np.complex64 else 1e-10
+        assert_allclose(powered.data, np.linalg.matrix_power(mat, n), atol=atol)
+
     def test_power_except(self):
         """Test power method raises exceptions if not square."""
         op = Operator(self.rand_matrix(2, 3))
@@ -1256,5 +1317,134 @@ class TestOperator(OperatorTestCase):
         self.assertEqual(op2.input_dims(), (4, 2, 3))
 
 
+class TestEqualUpToPhase(QiskitTestCase):
+    """Tests of the Rust equality up to global phase of matrices and circuits."""
+
+    def test_matrices(self):
+        """Test the phase between equal matrices is found."""
+        mat = Operator(QFT(3)).data
+        for phase in [0.0, 0.5, -1.25, 3.0]:
+            found = operator_rs.equal_up_to_phase(np.exp(1j * phase) * mat, mat)
+            self.assertAlmostEqual(np.exp(1j * found), np.exp(1j * phase))
+
+    def test_matrices_not_equal(self):
+        """Test matrices that aren't equal up to phase, or have different shapes."""
+        identity = np.eye(2, dtype=complex)
+        self.assertIsNone(operator_rs.equal_up_to_phase(np.diag([1, -1j]), identity))
+        self.assertIsNone(operator_rs.equal_up_to_phase(identity, np.eye(4, dtype=complex)))
+
+    def test_matrices_tolerance(self):
+        """Test the tolerances are applied after the phase is removed."""
+        mat = np.eye(2, dtype=complex)
+        perturbed = 1j * mat + np.diag([1e-3, 0])
+        self.assertIsNone(operator_rs.equal_up_to_phase(perturbed, mat))
+        found = operator_rs.equal_up_to_phase(perturbed, mat, atol=1e-3)
+        self.assertAlmostEqual(found, np.pi / 2, places=3)
+
+    def test_circuits(self):
+        """Test the phase between the unitaries of two circuits is found."""
+        left = QuantumCircuit(2)
+        left.h(1)
+        left.cx(0, 1)
+        left.h(1)
+        left.rz(0.4, 0)
+        right = QuantumCircuit(2)
+        right.cz(1, 0)
+        right.p(0.4, 0)
+        found = operator_rs.circuits_equal_up_to_phase(left._data, right._data)
+        self.assertAlmostEqual(found, -0.2)
+        self.assertTrue(Operator(left).equiv(Operator(right)))
+
+    def test_circuits_not_equal(self):
+        """Test circuits with different unitaries, or different widths."""
+        left = QuantumCircuit(2)
+        left.cx(0, 1)
+        right = QuantumCircuit(2)
+        right.cx(1, 0)
+        self.assertIsNone(operator_rs.circuits_equal_up_to_phase(left._data, right._data))
+        wider = QuantumCircuit(3)
+        self.assertIsNone(operator_rs.circuits_equal_up_to_phase(left._data, wider._data))
+
+    def test_circuits_without_matrix(self):
+        """Test a circuit with an instruction without a matrix raises."""
+        left = QuantumCircuit(1, 1)
+        left.measure(0, 0)
+        right = QuantumCircuit(1, 1)
+        with self.assertRaises(QiskitError):
+            operator_rs.circuits_equal_up_to_phase(left._data, right._data)
+
+
+class TestUnitaryLogarithm(QiskitTestCase):
+    """Tests of the Rust logarithms and Pauli rotations of unitaries."""
+
+    def test_logm_unitary(self):
+        """Test the principal logarithm of a unitary is found."""
+        for seed in range(3):
+            mat = random_unitary(4, seed=seed).data
+            log = operator_rs.logm_unitary(mat)
+            assert_allclose(log, la.logm(mat), atol=1e-8)
+            assert_allclose(log, -log.conj().T, atol=1e-10)
+        with self.assertRaises(QiskitError):
+            operator_rs.logm_unitary(np.ones((2, 3), dtype=complex))
+
+    def test_pauli_rotation(self):
+        """Test a two-qubit unitary is decomposed into a phase and a rotation about Paulis."""
+        generator = 0.3 * SparsePauliOp(["XZ", "IZ", "YY"], [1.0, -0.5, 0.8])
+        mat = np.exp(0.4j) * la.expm(-1j * generator.to_matrix())
+        phase, coeffs = operator_rs.pauli_rotation(mat)
+        self.assertAlmostEqual(phase, 0.4)
+        self.assertEqual(len(coeffs), 15)
+        self.assertNotIn("II", coeffs)
+        expected = dict(zip(generator.paulis.to_labels(), generator.coeffs.real))
+        for label, coeff in coeffs.items():
+            self.assertAlmostEqual(coeff, expected.get(label, 0.0), msg=label)
+        with self.assertRaises(QiskitError):
+            operator_rs.pauli_rotation(np.eye(8, dtype=complex))
+
+    def test_axis_angle(self):
+        """Test a single-qubit unitary is decomposed into a phase and a rotation about an axis."""
+        axis = np.array([2.0, -1.0, 2.0]) / 3
+        rotation = np.cos(0.35) * np.eye(2) - 1j * np.sin(0.35) * (
+            axis[0] * Pauli("X").to_matrix()
+            + axis[1] * Pauli("Y").to_matrix()
+            + axis[2] * Pauli("Z").to_matrix()
+        )
+        phase, angle, found = operator_rs.axis_angle(np.exp(-0.1j) * rotation)
+        self.assertAlmostEqual(phase, -0.1)
+        self.assertAlmostEqual(angle, 0.7)
+        assert_allclose(found, axis, atol=1e-10)
+        with self.assertRaises(QiskitError):
+            operator_rs.axis_angle(np.eye(4, dtype=complex))
+
+
+class TestEmbed(QiskitTestCase):
+    """Tests of the Rust embedding of matrices into operators on more qubits."""
+
+    def test_embed(self):
+        """Test the embedding is the identity composed with the matrix on the given qubits."""
+        mat = random_unitary(4, seed=3).data
+        for qubits in [[0, 1], [2, 0], [1, 3]]:
+            expected = Operator(np.eye(16)).compose(Operator(mat), qargs=qubits)
+            assert_allclose(operator_rs.embed(mat, qubits, 4), expected.data, atol=1e-12)
+
+    def test_embed_errors(self):
+        """Test out-of-range or repeated qubits, and matrices of the wrong size, raise."""
+        mat = np.eye(4, dtype=complex)
+        with self.assertRaises(IndexError):
+            operator_rs.embed(mat, [0, 3], 3)
+        with self.assertRaises(ValueError):
+            operator_rs.embed(mat, [1, 1], 3)
+        with self.assertRaises(QiskitError):
+            operator_rs.embed(mat, [1], 3)
+
+
+def _operator_with_data(mat):
+    """An operator whose data is ``mat`` itself, of whatever type, as an operator built by other
+    means than the constructor may have."""
+    op = Operator(np.eye(mat.shape[0]))
+    op._data = mat
+    return op
+
+
 if __name__ == "__main__":
     unittest.main()
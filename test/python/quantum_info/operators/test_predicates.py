@@ -0,0 +1,93 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the predicates on matrices."""
+
+import unittest
+
+import numpy as np
+
+from qiskit.quantum_info import random_hermitian, random_unitary
+from qiskit.quantum_info.operators.predicates import (
+    is_hermitian_matrix,
+    is_identity_matrix,
+    is_unitary_matrix,
+)
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestPredicates(QiskitTestCase):
+    """Test the Rust checks of matrices agree with the Numpy definitions."""
+
+    def test_is_unitary_matrix(self):
+        """Test unitaries, and matrices close to unitary, are recognized."""
+        for num_qubits in [1, 3, 6]:
+            mat = random_unitary(2**num_qubits, seed=num_qubits).data
+            self.assertTrue(is_unitary_matrix(mat))
+            self.assertTrue(is_unitary_matrix(np.exp(0.3j) * mat))
+            self.assertFalse(is_unitary_matrix(1.001 * mat))
+            self.assertTrue(is_unitary_matrix(1.001 * mat, rtol=1e-2))
+        self.assertTrue(is_unitary_matrix([[0, 1], [1, 0]]))
+        self.assertFalse(is_unitary_matrix(np.ones((2, 2))))
+
+    def test_is_unitary_matrix_isometry(self):
+        """Test a matrix that isn't square is unitary if its columns are orthonormal."""
+        mat = random_unitary(4, seed=1).data
+        self.assertTrue(is_unitary_matrix(mat[:, :2]))
+        self.assertFalse(is_unitary_matrix(mat[:2, :]))
+
+    def test_is_hermitian_matrix(self):
+        """Test Hermitian matrices are recognized, and other matrices aren't."""
+        mat = random_hermitian(8, seed=2).data
+        self.assertTrue(is_hermitian_matrix(mat))
+        self.assertTrue(is_hermitian_matrix(np.real(mat + mat.T)))
+        self.assertFalse(is_hermitian_matrix(1j * mat))
+        self.assertFalse(is_hermitian_matrix(mat[:4, :]))
+        self.assertFalse(is_hermitian_matrix(np.ones(4)))
+        perturbed = mat.copy()
+        perturbed[0, 1] += 1e-3
+        self.assertFalse(is_hermitian_matrix(perturbed))
+        self.assertTrue(is_hermitian_matrix(perturbed, atol=1e-2))
+
+    def test_is_identity_matrix(self):
+        """Test the identity is recognized, with or without a phase."""
+        identity = np.eye(4)
+        self.assertTrue(is_identity_matrix(identity))
+        self.assertTrue(is_identity_matrix(identity.astype(int)))
+        self.assertFalse(is_identity_matrix(-1j * identity))
+        self.assertTrue(is_identity_matrix(-1j * identity, ignore_phase=True))
+        self.assertFalse(is_identity_matrix(np.diag([1, 1j]), ignore_phase=True))
+        self.assertFalse(is_identity_matrix(identity[:2, :]))
+        self.assertFalse(is_identity_matrix(np.ones(4)))
+
+    def test_tolerances(self):
+        """Test the tolerances are applied as they are by ``numpy.allclose``, with ``None``
+        meaning the default."""
+        rng = np.random.default_rng(5)
+        for _ in range(20):
+            mat = np.eye(3) + rng.normal(scale=1e-5, size=(3, 3))
+            atol = 10 ** rng.uniform(-7, -4)
+            rtol = 10 ** rng.uniform(-7, -4)
+            self.assertEqual(
+                is_identity_matrix(mat, rtol=rtol, atol=atol),
+                np.allclose(mat, np.eye(3), rtol=rtol, atol=atol),
+            )
+            self.assertEqual(
+                is_hermitian_matrix(mat, rtol=rtol, atol=atol),
+                np.allclose(mat, mat.conj().T, rtol=rtol, atol=atol),
+            )
+        mat = np.eye(2) + 1e-6
+        self.assertEqual(is_identity_matrix(mat, rtol=None, atol=None), is_identity_matrix(mat))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -20,7 +20,10 @@ from ddt import data, ddt
 from numpy.testing import assert_allclose
 
 from qiskit import QiskitError, QuantumCircuit, QuantumRegister
+from qiskit.circuit import Gate
 from qiskit.circuit.library import QFT, HGate
+from qiskit.circuit.library.standard_gates import get_standard_gate_name_mapping
+from qiskit.quantum_info.operators.channel import Kraus, SuperOp
 from qiskit.quantum_info.operators.operator import Operator
 from qiskit.quantum_info.operators.symplectic import Pauli, SparsePauliOp
 from qiskit.quantum_info.random import random_density_matrix, random_pauli, random_unitary
@@ -169,6 +172,36 @@ class TestDensityMatrix(QiskitTestCase):
         rho = DensityMatrix.from_instruction(circuit)
         self.assertEqual(rho, target)
 
+    def test_from_circuit_standard_gates(self):
+        """Test the circuits of each standard gate are simulated by their matrices."""
+        for name, gate in get_standard_gate_name_mapping().items():
+            if not isinstance(gate, Gate) or gate.num_qubits == 0:
+                continue
+            with self.subTest(gate=name):
+                gate = gate.copy()
+                gate.params = [0.3 * (i + 1) for i in range(len(gate.params))]
+                qargs = [2, 0, 3, 1][: gate.num_qubits]
+                circuit = QuantumCircuit(4, global_phase=0.25)
+                circuit.h(range(4))
+                circuit.s(2)
+                circuit.z(1)
+                circuit.sdg(0)
+                circuit.append(gate, qargs)
+                target = DensityMatrix(
+                    Statevector.from_label("+r-l").evolve(Operator(gate.to_matrix()), qargs)
+                )
+                self.assertEqual(DensityMatrix.from_instruction(circuit), target)
+
+    def test_from_circuit_skips_directives(self):
+        """Test barriers and delays leave the state unchanged."""
+        circuit = QuantumCircuit(2)
+        circuit.h(0)
+        circuit.barrier()
+        circuit.delay(100, 1)
+        circuit.cx(0, 1)
+        target = DensityMatrix(Statevector([1, 0, 0, 1]) / np.sqrt(2))
+        self.assertEqual(DensityMatrix.from_instruction(circuit), target)
+
     def test_from_instruction(self):
         """Test initialization from an instruction."""
         target_vec = Statevector(np.dot(HGate().to_matrix(), [1, 0]))
@@ -285,6 +318,40 @@ class TestDensityMatrix(QiskitTestCase):
             target = DensityMatrix(np.dot(op_full.data, rho).dot(op_full.adjoint().data))
             self.assertEqual(state.evolve(op, qargs=[2, 1, 0]), target)
 
+    def test_evolve_non_unitary_subsystem(self):
+        """Test evolving by a non-unitary operator on non-contiguous qubits."""
+        rng = np.random.default_rng(11)
+        rho = random_density_matrix(8, seed=rng)
+        mat = rng.normal(size=(4, 4)) + 1j * rng.normal(size=(4, 4))
+        full = Operator(np.eye(8)).compose(mat, qargs=[2, 0]).data
+        target = full @ rho.data @ full.conj().T
+        assert_allclose(rho.evolve(Operator(mat), qargs=[2, 0]).data, target, atol=1e-12)
+
+    def test_evolve_kraus_subsystem(self):
+        """Test evolving by Kraus channels on some of the qubits."""
+        rho = random_density_matrix(8, seed=12)
+        rng = np.random.default_rng(12)
+        left = [rng.normal(size=(4, 4)) + 1j * rng.normal(size=(4, 4)) for _ in range(3)]
+        right = [rng.normal(size=(4, 4)) + 1j * rng.normal(size=(4, 4)) for _ in range(3)]
+        qargs = [0, 2]
+
+        def embed(mat):
+            return Operator(np.eye(8)).compose(mat, qargs=qargs).data
+
+        target = sum(embed(k) @ rho.data @ embed(k).conj().T for k in left)
+        assert_allclose(rho.evolve(Kraus(left), qargs=qargs).data, target, atol=1e-12)
+        assert_allclose(rho.evolve(SuperOp(Kraus(left)), qargs=qargs).data, target, atol=1e-12)
+        target = sum(embed(k) @ rho.data @ embed(l).conj().T for k, l in zip(left, right))
+        assert_allclose(rho.evolve(Kraus((left, right)), qargs=qargs).data, target, atol=1e-12)
+
+    def test_evolve_channel_full(self):
+        """Test evolving by a channel on all the qubits."""
+        rho = random_density_matrix(4, seed=13)
+        kraus = Kraus([np.sqrt(0.7) * np.eye(4), np.sqrt(0.3) * random_unitary(4, seed=13).data])
+        target = 0.7 * rho.data + 0.3 * rho.evolve(random_unitary(4, seed=13)).data
+        assert_allclose(rho.evolve(kraus).data, target, atol=1e-12)
+        assert_allclose(rho.evolve(SuperOp(kraus)).data, target, atol=1e-12)
+
     def test_evolve_qudit_subsystems(self):
         """Test nested evolve calls on qudit subsystems."""
         dims = (3, 4, 5)
@@ -25,6 +25,9 @@ from qiskit.quantum_info import entanglement_of_formation
 from qiskit.quantum_info import mutual_information
 from qiskit.quantum_info.states import shannon_entropy
 from qiskit.quantum_info import negativity
+from qiskit.quantum_info.random import random_density_matrix, random_statevector
+from qiskit.quantum_info.states import partial_trace
+from qiskit.quantum_info.states.utils import _funm_svd
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -81,6 +84,33 @@ class TestStateMeasures(QiskitTestCase):
         self.assertAlmostEqual(state_fidelity(psi1, mix), 0.25, places=7, msg="vector-matrix input")
         self.assertAlmostEqual(state_fidelity(rho1, psi1), 1.0, places=7, msg="matrix-vector input")
 
+    def test_state_fidelity_matches_svd(self):
+        """Test the fidelity of random density matrices against the SVD-based formula."""
+        for seed in range(5):
+            rho1 = random_density_matrix(8, seed=seed)
+            rho2 = random_density_matrix(8, rank=2, seed=seed + 100)
+            expected = _svd_fidelity(rho1.data, rho2.data)
+            self.assertAlmostEqual(state_fidelity(rho1, rho2), expected, places=10)
+            self.assertAlmostEqual(state_fidelity(rho2, rho1), expected, places=10)
+
+    def test_state_fidelity_low_rank(self):
+        """Test the fidelity of rank-deficient density matrices against the SVD-based formula."""
+        # These have numerically negative eigenvalues, which must not be clipped.
+        rho1 = DensityMatrix.from_label("+0-")
+        rho2 = DensityMatrix(0.5 * (rho1.data + DensityMatrix.from_label("000").data))
+        expected = _svd_fidelity(rho1.data, rho2.data)
+        self.assertAlmostEqual(state_fidelity(rho1, rho2), expected, places=10)
+        self.assertAlmostEqual(state_fidelity(rho1, rho1), 1.0, places=10)
+
+    def test_state_fidelity_non_hermitian(self):
+        """Test that non-Hermitian matrices, with validation off, use the SVD-based formula."""
+        rng = np.random.default_rng(1234)
+        rho1 = random_density_matrix(4, seed=1).data + 1e-3 * rng.normal(size=(4, 4))
+        rho2 = random_density_matrix(4, seed=2).data
+        expected = _svd_fidelity(rho1, rho2)
+        fid = state_fidelity(DensityMatrix(rho1), DensityMatrix(rho2), validate=False)
+        self.assertAlmostEqual(fid, expected, places=10)
+
     def test_purity_statevector(self):
         """Test purity function on statevector inputs"""
         psi = Statevector([1, 0, 0, 0])
@@ -140,6 +170,12 @@ class TestStateMeasures(QiskitTestCase):
             rho = DensityMatrix(psi)
             self.assertAlmostEqual(purity(psi), purity(rho))
 
+    def test_purity_random_density_matrix(self):
+        """Test purity matches Tr[rho^2] for random density matrices"""
+        for rank in [1, 2, 4]:
+            rho = random_density_matrix(4, rank=rank, seed=rank)
+            self.assertAlmostEqual(purity(rho), np.trace(rho.data @ rho.data).real)
+
     def test_entropy_statevector(self):
         """Test entropy function on statevector inputs"""
         test_psis = [
@@ -189,6 +225,14 @@ class TestStateMeasures(QiskitTestCase):
             rho = DensityMatrix(psi)
             self.assertAlmostEqual(entropy(psi), entropy(rho))
 
+    def test_entropy_random_density_matrix(self):
+        """Test entropy matches the Shannon entropy of the spectrum of random density matrices"""
+        for rank in [1, 3, 6]:
+            rho = random_density_matrix(6, rank=rank, seed=10 + rank)
+            evals = np.linalg.eigvalsh(rho.data)
+            self.assertAlmostEqual(entropy(rho), shannon_entropy(evals))
+            self.assertAlmostEqual(entropy(rho, base=np.e), shannon_entropy(evals, base=np.e))
+
     def test_concurrence_statevector(self):
         """Test concurrence function on statevector inputs"""
         # Statevector input
@@ -225,6 +269,13 @@ class TestStateMeasures(QiskitTestCase):
         rho = np.diag([0.25, 0.25, 0.25, 0.25])
         self.assertEqual(concurrence(rho), 0)
 
+    def test_concurrence_werner(self):
+        """Test concurrence of Werner states"""
+        bell = DensityMatrix(Statevector(np.array([1, 0, 0, 1]) / np.sqrt(2)))
+        for prob in [0, 0.2, 1 / 3, 0.5, 0.9, 1]:
+            werner = prob * bell + (1 - prob) * DensityMatrix(np.eye(4) / 4)
+            self.assertAlmostEqual(concurrence(werner), max(0, (3 * prob - 1) / 2), places=6)
+
     def test_concurrence_equivalence(self):
         """Test concurrence is same for equivalent inputs"""
         for alpha, beta in [
@@ -260,6 +311,14 @@ class TestStateMeasures(QiskitTestCase):
         psi = Statevector(psi_ls, dims=(8, 2))
         self.assertAlmostEqual(entanglement_of_formation(psi), 1)
 
+    def test_entanglement_of_formation_qudits(self):
+        """Test entanglement of formation of entangled statevectors of unequal dimensions"""
+        for dims in [(2, 3), (3, 2), (4, 2), (3, 5)]:
+            psi = random_statevector(dims, seed=sum(dims))
+            expected = entropy(partial_trace(psi, [0]))
+            self.assertAlmostEqual(entanglement_of_formation(psi), expected)
+            self.assertAlmostEqual(entanglement_of_formation(psi), entropy(partial_trace(psi, [1])))
+
     def test_entanglement_of_formation_density_matrix(self):
         """Test entanglement of formation function on density matrix inputs"""
         # Density matrix input
@@ -369,5 +428,12 @@ class TestStateMeasures(QiskitTestCase):
         self.assertAlmostEqual(negv, 0.5, places=7)
 
 
+def _svd_fidelity(rho1, rho2):
+    """The fidelity of two density matrices as computed before the Rust kernel."""
+    s1sq = _funm_svd(rho1, np.sqrt)
+    s2sq = _funm_svd(rho2, np.sqrt)
+    return np.linalg.norm(s1sq.dot(s2sq), ord="nuc") ** 2
+
+
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,59 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the Rust sampling of the shots of a distribution."""
+
+import unittest
+
+import numpy as np
+
+from qiskit._accelerate.sampling import sample_counts, sample_memory
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestSampling(QiskitTestCase):
+    """Test sampling shots from probabilities and amplitudes."""
+
+    def test_probabilities(self):
+        """Test unnormalized probabilities are sampled in proportion, and outcomes with no weight
+        never occur."""
+        weights = np.array([1.0, 0.0, 2.0, 3.0, 0.0, 4.0])
+        outcomes, counts = sample_counts(weights, 100_000, seed=2024)
+        np.testing.assert_array_equal(outcomes, [0, 2, 3, 5])
+        self.assertEqual(counts.sum(), 100_000)
+        np.testing.assert_allclose(counts / 100_000, weights[outcomes] / 10, atol=0.01)
+
+    def test_amplitudes(self):
+        """Test the amplitudes of a statevector are sampled by their squared magnitudes."""
+        amplitudes = np.array([0.0, 1j, 0.0, 0.0], dtype=complex)
+        np.testing.assert_array_equal(sample_memory(amplitudes, 10, seed=1), [1] * 10)
+
+    def test_seeded(self):
+        """Test the samples of a seed are reproducible, and the counts are those of the memory."""
+        weights = np.array([0.1, 0.2, 0.3, 0.4])
+        memory = sample_memory(weights, 200, seed=7)
+        np.testing.assert_array_equal(sample_memory(weights, 200, seed=7), memory)
+        outcomes, counts = sample_counts(weights, 200, seed=7)
+        expected_outcomes, expected_counts = np.unique(memory, return_counts=True)
+        np.testing.assert_array_equal(outcomes, expected_outcomes)
+        np.testing.assert_array_equal(counts, expected_counts)
+
+    def test_bad_weights(self):
+        """Test negative, non-finite or all-zero weights raise."""
+        for weights in ([0.0, 0.0], [1.0, -0.5], [np.nan, 1.0], []):
+            with self.subTest(weights=weights):
+                with self.assertRaises(ValueError):
+                    sample_memory(np.array(weights, dtype=float), 1, seed=0)
+
+
+if __name__ == "__main__":
+    unittest.main()
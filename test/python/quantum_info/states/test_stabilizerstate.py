@@ -21,6 +21,7 @@ from ddt import ddt, data, unpack
 import numpy as np
 
 from qiskit import QuantumCircuit
+from qiskit.exceptions import QiskitError
 
 from qiskit.quantum_info.random import random_clifford, random_pauli
 from qiskit.quantum_info.states import StabilizerState, Statevector
@@ -837,6 +838,59 @@ class TestStabilizerState(QiskitTestCase):
                 self.assertEqual(len(memory), self.shots)
                 self.assertEqual(set(memory), set(target))
 
+    def test_sample_memory_seed(self):
+        """Test sample_memory is reproducible from the seed, and leaves the state unchanged"""
+        qc = QuantumCircuit(3)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.h(2)
+        stab = StabilizerState(qc)
+        stab.seed(2024)
+        memory = stab.sample_memory(self.shots, qargs=[2, 0])
+        stab.seed(2024)
+        self.assertEqual(stab.sample_memory(self.shots, qargs=[2, 0]), memory)
+        self.assertEqual(stab, StabilizerState(qc))
+        self.assertEqual(stab.sample_memory(0), [])
+
+    def test_sample_circuit_bell(self):
+        """Test sample_circuit on a Bell circuit with measurements"""
+        qc = QuantumCircuit(2)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.measure_all()
+        memory, state = StabilizerState.sample_circuit(qc, self.shots, seed=11)
+        self.assertEqual(len(memory), self.shots)
+        self.assertEqual(set(memory), {"00", "11"})
+        counts = {label: memory.count(label) for label in ["00", "11"]}
+        target = {"00": self.shots / 2, "11": self.shots / 2}
+        self.assertDictAlmostEqual(counts, target, self.threshold)
+        # The final state is the one the last shot collapsed to.
+        self.assertEqual(state.probabilities_dict(), {memory[-1]: 1.0})
+        again, _ = StabilizerState.sample_circuit(qc, self.shots, seed=11)
+        self.assertEqual(again, memory)
+
+    def test_sample_circuit_reset(self):
+        """Test sample_circuit with resets and measurements into reordered clbits"""
+        qc = QuantumCircuit(2, 3)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.reset(0)
+        qc.x(0)
+        qc.measure(0, 2)
+        qc.measure(1, 0)
+        qc.cx(1, 0)
+        qc.measure(0, 1)
+        memory, _ = StabilizerState.sample_circuit(qc, 100, seed=3)
+        self.assertEqual(set(memory), {"101", "110"})
+
+    def test_sample_circuit_not_clifford(self):
+        """Test sample_circuit raises on a circuit that isn't Clifford"""
+        qc = QuantumCircuit(1, 1)
+        qc.t(0)
+        qc.measure(0, 0)
+        with self.assertRaises(QiskitError):
+            StabilizerState.sample_circuit(qc, 10)
+
 
 @ddt
 class TestStabilizerStateExpectationValue(QiskitTestCase):
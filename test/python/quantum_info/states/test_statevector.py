@@ -15,6 +15,7 @@
 
 import unittest
 import logging
+from concurrent.futures import ThreadPoolExecutor
 from itertools import permutations
 from ddt import ddt, data
 import numpy as np
@@ -23,7 +24,9 @@ from numpy.testing import assert_allclose
 from qiskit import QiskitError
 from qiskit import QuantumRegister, QuantumCircuit
 from qiskit import transpile
+from qiskit.circuit import Gate
 from qiskit.circuit.library import HGate, QFT, GlobalPhaseGate
+from qiskit.circuit.library.standard_gates import get_standard_gate_name_mapping
 from qiskit.providers.basic_provider import BasicSimulator
 from qiskit.utils import optionals
 from qiskit.quantum_info.random import random_unitary, random_statevector, random_pauli
@@ -32,6 +35,8 @@ from qiskit.quantum_info.operators.operator import Operator
 from qiskit.quantum_info.operators.symplectic import Pauli, SparsePauliOp
 from qiskit.quantum_info.operators.predicates import matrix_equal
 from qiskit.visualization.state_visualization import state_to_latex
+from qiskit._accelerate.pauli_expval import expval_paulis
+from qiskit._accelerate.statevector import StatevectorSession
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 logger = logging.getLogger(__name__)
@@ -200,6 +205,36 @@ class TestStatevector(QiskitTestCase):
         global_phase_equivalent = matrix_equal(vec, target, ignore_phase=True)
         self.assertTrue(global_phase_equivalent)
 
+    def test_from_circuit_standard_gates(self):
+        """Test the circuits of each standard gate are simulated by their matrices."""
+        for name, gate in get_standard_gate_name_mapping().items():
+            if not isinstance(gate, Gate) or gate.num_qubits == 0:
+                continue
+            with self.subTest(gate=name):
+                gate = gate.copy()
+                gate.params = [0.3 * (i + 1) for i in range(len(gate.params))]
+                qargs = [2, 0, 3, 1][: gate.num_qubits]
+                circuit = QuantumCircuit(4, global_phase=0.25)
+                circuit.h(range(4))
+                circuit.s(2)
+                circuit.z(1)
+                circuit.sdg(0)
+                circuit.append(gate, qargs)
+                target = np.exp(0.25j) * Statevector.from_label("+r-l").evolve(
+                    Operator(gate.to_matrix()), qargs
+                )
+                self.assertEqual(Statevector.from_instruction(circuit), target)
+
+    def test_from_circuit_skips_directives(self):
+        """Test barriers and delays leave the state unchanged."""
+        circuit = QuantumCircuit(2)
+        circuit.h(0)
+        circuit.barrier()
+        circuit.delay(100, 1)
+        circuit.cx(0, 1)
+        target = Statevector([1, 0, 0, 1]) / np.sqrt(2)
+        self.assertEqual(Statevector.from_instruction(circuit), target)
+
     def test_from_label(self):
         """Test initialization from a label"""
         x_p = Statevector(np.array([1, 1]) / np.sqrt(2))
@@ -729,6 +764,47 @@ class TestStatevector(QiskitTestCase):
                 counts = state.sample_counts(shots, qargs=qargs)
                 self.assertDictAlmostEqual(counts, target, threshold)
 
+    def test_probabilities_marginal_reference(self):
+        """Test the marginal probabilities of subsystems of unequal dimensions, in any order, are
+        those of the squared amplitudes summed over the other subsystems."""
+        dims = (3, 2, 4)
+        state = random_statevector(dims, seed=11)
+        # The axes of the tensor are the subsystems with the last first.
+        tensor = np.abs(state.data.reshape(dims[::-1])) ** 2
+        for qargs in [[0], [2], [1, 0], [0, 2], [2, 0, 1]]:
+            with self.subTest(qargs=qargs):
+                others = tuple(2 - i for i in range(3) if i not in qargs)
+                # Sum out the other subsystems, and order the kept ones with qargs[0] last.
+                kept = sorted(qargs, reverse=True)
+                expected = np.transpose(
+                    tensor.sum(axis=others), [kept.index(q) for q in qargs[::-1]]
+                ).ravel()
+                np.testing.assert_allclose(state.probabilities(qargs), expected, atol=1e-12)
+
+    def test_probabilities_dict_large_dims(self):
+        """Test the labels of subsystems of more than 10 levels are separated by commas, and
+        rounded-away probabilities are dropped."""
+        vec = np.zeros(24)
+        vec[11] = np.sqrt(0.6)
+        vec[13] = np.sqrt(0.399999)
+        vec[0] = np.sqrt(1e-6)
+        state = Statevector(vec, dims=(12, 2))
+        self.assertDictAlmostEqual(
+            state.probabilities_dict(), {"0,11": 0.6, "1,1": 0.399999, "0,0": 1e-6}, delta=1e-12
+        )
+        self.assertEqual(state.probabilities_dict(decimals=3), {"0,11": 0.6, "1,1": 0.4})
+        self.assertEqual(list(state.probabilities_dict()), ["0,0", "0,11", "1,1"])
+
+    def test_probabilities_decimals_ties(self):
+        """Test probabilities are rounded with ties to even, as NumPy rounds them."""
+        state = Statevector(np.full(4, 0.5))
+        np.testing.assert_array_equal(state.probabilities(decimals=1), [0.2] * 4)
+
+    def test_probabilities_duplicate_qargs(self):
+        """Test repeated subsystems raise."""
+        with self.assertRaises(QiskitError):
+            Statevector.from_label("01").probabilities([0, 0])
+
     def test_probabilities_dict_unequal_dims(self):
         """Test probabilities_dict for a state with unequal subsystem dimensions."""
 
@@ -885,6 +961,33 @@ class TestStatevector(QiskitTestCase):
             self.assertEqual(len(memory), shots)
             self.assertEqual(set(memory), {"0", "2"})
 
+    def test_sample_seeded(self):
+        """Test seeded samples are reproducible, and the counts are those of the memory drawn from
+        the same seed."""
+        state = Statevector(random_statevector(8, seed=1))
+        state.seed(42)
+        memory = state.sample_memory(500)
+        state.seed(42)
+        np.testing.assert_array_equal(state.sample_memory(500), memory)
+        state.seed(42)
+        counts = state.sample_counts(500)
+        expected = {}
+        for outcome in memory:
+            expected[outcome] = expected.get(outcome, 0) + 1
+        self.assertEqual(dict(counts), expected)
+        self.assertEqual(list(counts), sorted(counts))
+
+    def test_sample_qargs_seeded(self):
+        """Test seeded samples of some of the qubits are drawn from their marginal
+        distribution."""
+        state = Statevector.from_label("+1")
+        state.seed(3)
+        counts = state.sample_counts(1000, qargs=[0])
+        self.assertEqual(dict(counts), {"1": 1000})
+        memory = state.sample_memory(1000, qargs=[1])
+        self.assertEqual(set(memory), {"0", "1"})
+        self.assertAlmostEqual(np.mean(memory == "1"), 0.5, delta=0.1)
+
     def test_reset_2qubit(self):
         """Test reset method for 2-qubit state"""
 
@@ -1152,6 +1255,45 @@ class TestStatevector(QiskitTestCase):
         expval = state.expectation_value(op, qubits)
         self.assertAlmostEqual(expval, target)
 
+    def test_expval_sparse_pauli_op(self):
+        """Test the expectation value of a SparsePauliOp is that of its matrix, with identity
+        terms and complex coefficients."""
+        op = SparsePauliOp(
+            ["III", "XYZ", "ZIX", "YYI", "IIZ"], coeffs=[0.5, 1 - 2j, -0.3, 2.0, 1j]
+        )
+        state = random_statevector(2**3, seed=1020)
+        target = state.expectation_value(op.to_matrix())
+        self.assertAlmostEqual(state.expectation_value(op), target)
+
+    @data([0, 1], [0, 2], [1, 0], [2, 1])
+    def test_expval_sparse_pauli_op_qargs(self, qubits):
+        """Test the expectation value of a SparsePauliOp on some of the qubits of the state."""
+        op = SparsePauliOp(["XY", "ZZ", "II", "YI"], coeffs=[0.7, -1.1, 0.2, 0.4j])
+        state = random_statevector(2**3, seed=1021)
+        target = state.expectation_value(op.to_matrix(), qubits)
+        self.assertAlmostEqual(state.expectation_value(op, qubits), target)
+
+    def test_expval_sparse_pauli_op_unnormalized(self):
+        """Test each term of a SparsePauliOp is evaluated as the Pauli alone, for a state that
+        isn't normalized."""
+        op = SparsePauliOp(["II", "XZ", "ZY"], coeffs=[1.0, 0.5, -2.0])
+        state = Statevector(2 * random_statevector(4, seed=7).data)
+        target = sum(
+            coeff * state.expectation_value(pauli) for pauli, coeff in zip(op.paulis, op.coeffs)
+        )
+        self.assertAlmostEqual(state.expectation_value(op), target)
+
+    def test_expval_paulis_errors(self):
+        """Test the Rust evaluation of many Paulis checks the lengths of its lists and the
+        masks."""
+        data = np.array([1, 0], dtype=complex)
+        with self.assertRaises(ValueError):
+            expval_paulis(data, 1, [1], [0], [1], [])
+        with self.assertRaises(ValueError):
+            expval_paulis(data, 1, [1], [0, 1], [1], [1])
+        with self.assertRaises(ValueError):
+            expval_paulis(data, 1, [2], [0], [1], [1])
+
     @data(*(qargs for i in range(4) for qargs in permutations(range(4), r=i + 1)))
     def test_probabilities_qargs(self, qargs):
         """Test probabilities method with qargs"""
@@ -1368,5 +1510,84 @@ class TestStatevector(QiskitTestCase):
         self.assertEqual(list(sv.probabilities(decimals=2)), expected)
 
 
+class TestStatevectorSession(QiskitTestCase):
+    """Test the statevector session pinned for many queries."""
+
+    @staticmethod
+    def _masks(op):
+        """The arguments of the Pauli terms of ``op`` for a session."""
+        weights = 1 << np.arange(op.num_qubits)
+        return (
+            np.dot(op.paulis.z, weights).tolist(),
+            np.dot(op.paulis.x, weights).tolist(),
+            ((-1j) ** op.paulis._count_y()).tolist(),
+            op.coeffs.tolist(),
+        )
+
+    def test_expval_paulis(self):
+        """Test the weighted expectation values of the session are those of the statevector."""
+        op = SparsePauliOp(["XYZ", "ZIX", "YYI", "IIZ"], coeffs=[1 - 2j, -0.3, 2.0, 1j])
+        state = random_statevector(2**3, seed=1030)
+        session = StatevectorSession(state.data)
+        self.assertEqual(len(session), 8)
+        self.assertEqual(session.dims, [2, 2, 2])
+        values = session.expval_paulis(*self._masks(op))
+        for value, pauli, coeff in zip(values, op.paulis, op.coeffs):
+            self.assertAlmostEqual(value, coeff * state.expectation_value(pauli))
+
+    def test_probabilities(self):
+        """Test the probabilities of the session are those of the statevector, for qubits and
+        for subsystems of unequal dimensions."""
+        for dims in [(2, 2, 2), (3, 2, 4)]:
+            state = random_statevector(dims, seed=1031)
+            session = StatevectorSession(state.data, list(dims))
+            self.assertEqual(session.dims, list(dims))
+            for qargs in [None, [0], [2, 0]]:
+                with self.subTest(dims=dims, qargs=qargs):
+                    np.testing.assert_allclose(
+                        session.probabilities(qargs), state.probabilities(qargs), atol=1e-12
+                    )
+            np.testing.assert_array_equal(
+                session.probabilities(decimals=2), state.probabilities(decimals=2)
+            )
+
+    def test_pinned_copy(self):
+        """Test the session keeps its own copy of the amplitudes, and locks the array only when
+        asked to."""
+        data = np.array([1, 0, 0, 0], dtype=complex)
+        session = StatevectorSession(data)
+        data[:] = [0, 0, 0, 1]
+        np.testing.assert_allclose(session.probabilities(), [1, 0, 0, 0])
+        self.assertTrue(data.flags.writeable)
+        StatevectorSession(data, lock=True)
+        self.assertFalse(data.flags.writeable)
+
+    def test_concurrent_queries(self):
+        """Test queries from several threads give the results of serial queries."""
+        op = SparsePauliOp(["XXZZ", "YIYI", "ZZZZ"], coeffs=[0.5, -1.0, 2j])
+        session = StatevectorSession(random_statevector(2**4, seed=1032).data)
+        masks = self._masks(op)
+        expected = session.expval_paulis(*masks)
+        with ThreadPoolExecutor(max_workers=4) as executor:
+            results = list(executor.map(lambda _: session.expval_paulis(*masks), range(16)))
+        for result in results:
+            np.testing.assert_allclose(result, expected)
+
+    def test_errors(self):
+        """Test bad amplitudes, dimensions and Pauli lists raise."""
+        with self.assertRaises(ValueError):
+            StatevectorSession(np.ones(3, dtype=complex))
+        with self.assertRaises(ValueError):
+            StatevectorSession(np.ones(6, dtype=complex), [2, 2])
+        qutrits = StatevectorSession(np.ones(9, dtype=complex) / 3, [3, 3])
+        with self.assertRaises(ValueError):
+            qutrits.expval_paulis([1], [0], [1], [1])
+        session = StatevectorSession(np.array([1, 0], dtype=complex))
+        with self.assertRaises(ValueError):
+            session.expval_paulis([1], [0], [1], [])
+        with self.assertRaises(ValueError):
+            session.expval_paulis([2], [0], [1], [1])
+
+
 if __name__ == "__main__":
     unittest.main()
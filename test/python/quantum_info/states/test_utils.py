@@ -15,11 +15,29 @@
 import unittest
 import numpy as np
 
+from qiskit.exceptions import QiskitError
+from qiskit.quantum_info import random_density_matrix, random_statevector
 from qiskit.quantum_info.states import Statevector, DensityMatrix
 from qiskit.quantum_info.states import partial_trace, shannon_entropy, schmidt_decomposition
+from qiskit._accelerate import partial_trace as partial_trace_rs
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
+def _reference_partial_trace(rho, dims, qargs):
+    """The partial trace of the density matrix ``rho`` over ``qargs``, computed with einsum."""
+    num = len(dims)
+    rows = [chr(ord("a") + i) for i in range(num)]
+    cols = [chr(ord("A") + i) for i in range(num)]
+    for qarg in qargs:
+        cols[num - 1 - qarg] = rows[num - 1 - qarg]
+    kept = [axis for axis in range(num) if num - 1 - axis not in qargs]
+    subscripts = "".join(rows + cols) + "->"
+    subscripts += "".join(rows[axis] for axis in kept) + "".join(cols[axis] for axis in kept)
+    out = np.einsum(subscripts, rho.reshape(tuple(reversed(dims)) * 2))
+    kept_dim = int(np.prod([dims[num - 1 - axis] for axis in kept]))
+    return out.reshape(kept_dim, kept_dim)
+
+
 class TestStateUtils(QiskitTestCase):
     """Test utility functions for QuantumState classes."""
 
@@ -45,6 +63,48 @@ class TestStateUtils(QiskitTestCase):
         self.assertEqual(partial_trace(rho, [2]), DensityMatrix.from_label("0+"))
         self.assertEqual(partial_trace(rho, []), rho)
 
+    def test_statevector_partial_trace_qudits(self):
+        """Test partial_trace on an entangled statevector of mixed dimensions"""
+        psi = random_statevector((2, 3, 2), seed=42)
+        rho = np.outer(psi.data, psi.data.conj())
+        for qargs in [[0], [1], [2], [2, 0], [1, 2]]:
+            with self.subTest(qargs=qargs):
+                reduced = partial_trace(psi, qargs)
+                kept_dims = tuple(dim for i, dim in enumerate((2, 3, 2)) if i not in qargs)
+                self.assertEqual(reduced.dims(), kept_dims)
+                np.testing.assert_allclose(
+                    reduced.data, _reference_partial_trace(rho, (2, 3, 2), qargs), atol=1e-12
+                )
+
+    def test_density_matrix_partial_trace_qudits(self):
+        """Test partial_trace on a mixed density matrix of mixed dimensions"""
+        rho = random_density_matrix((3, 2, 2), rank=3, seed=7)
+        for qargs in [[0], [1], [2], [0, 2], [2, 1]]:
+            with self.subTest(qargs=qargs):
+                reduced = partial_trace(rho, qargs)
+                np.testing.assert_allclose(
+                    reduced.data, _reference_partial_trace(rho.data, (3, 2, 2), qargs), atol=1e-12
+                )
+                self.assertAlmostEqual(reduced.trace(), 1)
+
+    def test_partial_trace_full(self):
+        """Test tracing out every subsystem gives the trace"""
+        psi = random_statevector((2, 3), seed=3)
+        self.assertAlmostEqual(partial_trace(psi, [0, 1]), 1)
+        self.assertAlmostEqual(partial_trace(DensityMatrix(psi), [1, 0]), 1)
+
+    def test_rust_partial_trace_errors(self):
+        """Test the Rust partial traces reject invalid subsystems"""
+        psi = np.full(8, 1 / np.sqrt(8), dtype=complex)
+        with self.assertRaises(IndexError):
+            partial_trace_rs.partial_trace_statevector(psi, [2, 2, 2], [3])
+        with self.assertRaises(QiskitError):
+            partial_trace_rs.partial_trace_statevector(psi, [2, 2, 2], [1, 1])
+        with self.assertRaises(QiskitError):
+            partial_trace_rs.partial_trace_statevector(psi, [2, 3], [0])
+        with self.assertRaises(QiskitError):
+            partial_trace_rs.partial_trace_density_matrix(np.zeros((2, 4), dtype=complex), [2], [0])
+
     def test_shannon_entropy(self):
         """Test shannon_entropy function"""
         input_pvec = np.array([0.5, 0.3, 0.07, 0.1, 0.03])
@@ -0,0 +1,155 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the seeded Rust generators of random quantum-info objects."""
+
+import pickle
+import unittest
+
+from ddt import ddt, data
+import numpy as np
+
+from qiskit.exceptions import QiskitError
+from qiskit._accelerate import random_quantum_info
+from qiskit._accelerate.rng import SeedSequence
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+@ddt
+class TestRandomQuantumInfo(QiskitTestCase):
+    """Test the Rust generators of random quantum-info objects."""
+
+    @data(1, 2, 3, 8)
+    def test_random_unitary(self, dim):
+        """Test random_unitary returns a unitary."""
+        unitary = random_quantum_info.random_unitary(dim, seed=42)
+        self.assertEqual(unitary.shape, (dim, dim))
+        np.testing.assert_allclose(unitary.conj().T @ unitary, np.eye(dim), atol=1e-10)
+
+    def test_random_unitary_seed(self):
+        """Test the same seed gives the same unitary, and different seeds different ones."""
+        first = random_quantum_info.random_unitary(4, seed=2024)
+        np.testing.assert_array_equal(first, random_quantum_info.random_unitary(4, seed=2024))
+        self.assertFalse(np.allclose(first, random_quantum_info.random_unitary(4, seed=2025)))
+
+    def test_random_unitary_large_seed(self):
+        """Test seeds beyond 64 bits are accepted and reproducible."""
+        seed = 2**100 + 17
+        np.testing.assert_array_equal(
+            random_quantum_info.random_unitary(2, seed=seed),
+            random_quantum_info.random_unitary(2, seed=seed),
+        )
+
+    def test_random_unitaries(self):
+        """Test a batch of unitaries is reproducible and matches the seeds of its sequence."""
+        batch = random_quantum_info.random_unitaries(4, 5, seed=7)
+        self.assertEqual(batch.shape, (5, 4, 4))
+        for unitary in batch:
+            np.testing.assert_allclose(unitary.conj().T @ unitary, np.eye(4), atol=1e-10)
+        np.testing.assert_array_equal(batch, random_quantum_info.random_unitaries(4, 5, seed=7))
+        # Passing a sequence draws one seed from it per item.
+        sequence = SeedSequence(7)
+        np.testing.assert_array_equal(
+            batch, random_quantum_info.random_unitaries(4, 5, seed=sequence)
+        )
+        self.assertEqual(sequence.draws, 5)
+
+    def test_random_unitaries_empty(self):
+        """Test an empty batch has the right shape."""
+        self.assertEqual(random_quantum_info.random_unitaries(3, 0, seed=1).shape, (0, 3, 3))
+
+    def test_random_two_qubit_unitaries(self):
+        """Test a batch of two-qubit unitaries is reproducible and has unit determinants."""
+        batch = random_quantum_info.random_two_qubit_unitaries(6, seed=9)
+        self.assertEqual(batch.shape, (6, 4, 4))
+        for unitary in batch:
+            np.testing.assert_allclose(unitary.conj().T @ unitary, np.eye(4), atol=1e-10)
+            self.assertAlmostEqual(np.linalg.det(unitary), 1.0)
+        np.testing.assert_array_equal(
+            batch, random_quantum_info.random_two_qubit_unitaries(6, seed=9)
+        )
+        self.assertEqual(random_quantum_info.random_two_qubit_unitaries(0, seed=9).shape, (0, 4, 4))
+
+    @data(1, 2, 5, 16)
+    def test_random_statevector(self, dim):
+        """Test random_statevector returns a normalized state."""
+        state = random_quantum_info.random_statevector(dim, seed=3)
+        self.assertEqual(state.shape, (dim,))
+        self.assertAlmostEqual(np.vdot(state, state).real, 1.0)
+
+    def test_random_statevectors(self):
+        """Test a batch of statevectors is normalized and reproducible."""
+        batch = random_quantum_info.random_statevectors(8, 4, seed=11)
+        self.assertEqual(batch.shape, (4, 8))
+        np.testing.assert_allclose(np.linalg.norm(batch, axis=1), np.ones(4))
+        np.testing.assert_array_equal(batch, random_quantum_info.random_statevectors(8, 4, seed=11))
+
+    @data("Hilbert-Schmidt", "Bures")
+    def test_random_density_matrix(self, method):
+        """Test random_density_matrix returns a valid state of the given rank."""
+        rho = random_quantum_info.random_density_matrix(6, rank=2, method=method, seed=5)
+        self.assertAlmostEqual(np.trace(rho).real, 1.0)
+        np.testing.assert_allclose(rho, rho.conj().T, atol=1e-12)
+        eigenvalues = np.linalg.eigvalsh(rho)
+        self.assertTrue(np.all(eigenvalues > -1e-12))
+        self.assertEqual(np.sum(eigenvalues > 1e-10), 2)
+
+    def test_random_density_matrix_full_rank(self):
+        """Test the default rank is full."""
+        rho = random_quantum_info.random_density_matrix(4, seed=5)
+        self.assertEqual(np.linalg.matrix_rank(rho), 4)
+
+    def test_random_density_matrix_bad_method(self):
+        """Test an unknown method raises."""
+        with self.assertRaises(QiskitError):
+            random_quantum_info.random_density_matrix(2, method="Frobenius", seed=5)
+
+    @data(False, True)
+    def test_random_hermitian(self, traceless):
+        """Test random_hermitian returns a Hermitian matrix."""
+        mat = random_quantum_info.random_hermitian(5, traceless=traceless, seed=13)
+        np.testing.assert_allclose(mat, mat.conj().T)
+        if traceless:
+            np.testing.assert_array_equal(np.diag(mat), np.zeros(5))
+
+    def test_seed_sequence_state(self):
+        """Test a sequence restored from its state carries on with the same seeds."""
+        sequence = SeedSequence(2**100 + 3)
+        random_quantum_info.random_unitaries(2, 3, seed=sequence)
+        self.assertEqual(sequence.state(), (2**100 + 3, 3))
+        restored = SeedSequence(*sequence.state())
+        np.testing.assert_array_equal(
+            random_quantum_info.random_unitaries(2, 2, seed=sequence),
+            random_quantum_info.random_unitaries(2, 2, seed=restored),
+        )
+        self.assertEqual(restored.draws, 5)
+
+    def test_seed_sequence_pickle(self):
+        """Test a pickled sequence carries on with the same seeds."""
+        sequence = SeedSequence(11)
+        sequence.next_seed()
+        copied = pickle.loads(pickle.dumps(sequence))
+        self.assertEqual(copied.state(), sequence.state())
+        self.assertEqual(copied.next_seed(), sequence.next_seed())
+
+    def test_seed_sequence_from_entropy(self):
+        """Test a sequence without a seed records the seed it drew, so it can be reproduced."""
+        sequence = SeedSequence()
+        seeds = [sequence.next_seed() for _ in range(3)]
+        restored = SeedSequence(sequence.seed)
+        self.assertEqual([restored.next_seed() for _ in range(3)], seeds)
+        with self.assertRaises(ValueError):
+            SeedSequence(None, 2)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -473,5 +473,61 @@ class TestLocalReadoutMitigation(QiskitTestCase):
         self.assertTrue(matrix_equal(expected_assignment_matrix, LRM.assignment_matrix()))
 
 
+    def test_from_calibration_counts(self):
+        """Test the assignment matrices are built from the counts of calibration circuits."""
+        calibrations = [
+            ("00", Counts({"00": 90, "01": 10})),
+            ("11", Counts({"11": 80, "10": 20})),
+        ]
+        LRM = LocalReadoutMitigator.from_calibration_counts(calibrations, qubits=[3, 5])
+        np.testing.assert_allclose(LRM.assignment_matrix([3]), [[0.9, 0.2], [0.1, 0.8]])
+        np.testing.assert_allclose(LRM.assignment_matrix([5]), np.eye(2))
+        with self.assertRaises(QiskitError):
+            LocalReadoutMitigator.from_calibration_counts([])
+
+    def test_sparse_quasi_probabilities(self):
+        """Test the quasi-probabilities of the observed outcomes alone are those of the dense
+        mitigation."""
+        assignment_matrices = LocalReadoutMitigator(backend=Fake5QV1())._assignment_mats[0:3]
+        counts = Counts({"000": 400, "101": 300, "011": 200, "110": 124})
+        dense = LocalReadoutMitigator(assignment_matrices)
+        sparse = LocalReadoutMitigator(assignment_matrices)
+        sparse._DENSE_QUBITS = 0
+        expected = dense.quasi_probabilities(counts)
+        quasi = sparse.quasi_probabilities(counts)
+        self.assertEqual(set(quasi), {0b000, 0b101, 0b011, 0b110})
+        for outcome, value in quasi.items():
+            self.assertAlmostEqual(value, expected[outcome])
+        self.assertEqual(quasi.shots, 1024)
+
+    def test_product_expectation_value(self):
+        """Test the expectation values of product diagonals are those of their full diagonals."""
+        assignment_matrices = LocalReadoutMitigator(backend=Fake5QV1())._assignment_mats[0:3]
+        LRM = LocalReadoutMitigator(assignment_matrices)
+        counts = Counts({"000": 400, "101": 300, "011": 200, "110": 124})
+        for diagonal in ["ZIZ", "Z0Z", "1ZI"]:
+            with self.subTest(diagonal=diagonal):
+                expval, stddev_bound = LRM.expectation_value(counts, diagonal, qubits=[1, 2, 0])
+                expected = LRM.expectation_value(counts, str2diag(diagonal), qubits=[1, 2, 0])
+                self.assertAlmostEqual(expval, expected[0])
+                self.assertAlmostEqual(stddev_bound, expected[1])
+
+    def test_unfolded_probabilities(self):
+        """Test the unfolded probabilities are a probability distribution over the observed
+        outcomes that undoes the readout errors."""
+        amat = np.array([[0.9, 0.2], [0.1, 0.8]])
+        LRM = LocalReadoutMitigator([amat, amat])
+        probs = np.array([0.1, 0.2, 0.3, 0.4])
+        noisy = np.kron(amat, amat) @ probs
+        counts = Counts({format(i, "02b"): int(round(10000 * p)) for i, p in enumerate(noisy)})
+        unfolded = LRM.unfolded_probabilities(counts, max_iterations=10000, tolerance=1e-14)
+        self.assertEqual(unfolded.shots, 10000)
+        self.assertEqual(set(unfolded), {0, 1, 2, 3})
+        self.assertTrue(all(value >= 0 for value in unfolded.values()))
+        self.assertAlmostEqual(sum(unfolded.values()), 1.0)
+        for outcome, value in unfolded.items():
+            self.assertAlmostEqual(value, probs[outcome], delta=1e-3)
+
+
 if __name__ == "__main__":
     unittest.main()
@@ -16,6 +16,7 @@ from math import sqrt
 import numpy as np
 
 from qiskit.result import QuasiDistribution, marginal_distribution
+from qiskit._accelerate.results import nearest_probability_distribution
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -196,6 +197,31 @@ class TestQuasi(QiskitTestCase):
         # Check if distance calculation is correct
         self.assertAlmostEqual(dist, sqrt(0.38), places=14)
 
+    def test_nearest_probability_dropped_in_turn(self):
+        """Test outcomes are dropped in turn, as the share of the dropped ones makes the next one
+        negative, and the shots are kept."""
+        quasi = QuasiDistribution({0: 1.0, 1: -0.1, 2: 0.3, 3: -0.2}, shots=100)
+        closest, dist = quasi.nearest_probability_distribution(return_distance=True)
+        self.assertEqual(set(closest), {0, 2})
+        self.assertAlmostEqual(closest[0], 0.85, places=14)
+        self.assertAlmostEqual(closest[2], 0.15, places=14)
+        self.assertAlmostEqual(dist, sqrt(0.095), places=14)
+        self.assertEqual(closest.shots, 100)
+
+    def test_nearest_probability_unchanged(self):
+        """Test a distribution that is already a probability distribution is unchanged."""
+        quasi = QuasiDistribution({"00": 0.25, "11": 0.75})
+        closest, dist = quasi.nearest_probability_distribution(return_distance=True)
+        self.assertEqual(closest, {0: 0.25, 3: 0.75})
+        self.assertEqual(dist, 0.0)
+
+    def test_nearest_probability_array(self):
+        """Test the nearest probabilities of an array of quasi-probabilities, with the dropped ones
+        zero."""
+        probs, dist = nearest_probability_distribution(np.array([0.5, 0.6, -0.1]))
+        np.testing.assert_allclose(probs, [0.45, 0.55, 0.0], atol=1e-14)
+        self.assertAlmostEqual(dist, sqrt(0.015), places=14)
+
     def test_marginal_distribution(self):
         """Test marginal_distribution with float value."""
         qprobs = {0: 3 / 5, 1: 1 / 2, 2: 7 / 20, 3: 1 / 10, 4: -11 / 20}
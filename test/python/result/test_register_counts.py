@@ -0,0 +1,61 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the splitting of counts by classical register."""
+
+import unittest
+
+from qiskit._accelerate.results import register_slice_counts, split_register_counts
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+COUNTS = {"01 110": 3, "11 110": 2, "01 001": 1}
+
+
+class TestSplitRegisterCounts(QiskitTestCase):
+    """Test the counts of each register of outcomes grouped by register."""
+
+    def test_split(self):
+        """Test the counts of each register, with the first register rightmost in the outcomes."""
+        self.assertEqual(split_register_counts(COUNTS), [{"110": 5, "001": 1}, {"01": 4, "11": 2}])
+
+    def test_single_register_and_empty(self):
+        """Test counts of a single register are unchanged, and empty counts have no registers."""
+        self.assertEqual(split_register_counts({"10": 2, "01": 1}), [{"10": 2, "01": 1}])
+        self.assertEqual(split_register_counts({}), [])
+
+    def test_mismatched_registers(self):
+        """Test outcomes with different numbers of registers raise."""
+        with self.assertRaises(ValueError):
+            split_register_counts({"01 110": 3, "110": 2})
+
+
+class TestRegisterSliceCounts(QiskitTestCase):
+    """Test the counts of slices of the bits of a register."""
+
+    def test_slice(self):
+        """Test slices of a register, with bit 0 the rightmost of the register."""
+        self.assertEqual(register_slice_counts(COUNTS, 0, 1, 3), {"11": 5, "00": 1})
+        self.assertEqual(register_slice_counts(COUNTS, 0, stop=1), {"0": 5, "1": 1})
+        self.assertEqual(register_slice_counts(COUNTS, 1), {"01": 4, "11": 2})
+
+    def test_out_of_range(self):
+        """Test registers and bits out of range raise."""
+        with self.assertRaises(IndexError):
+            register_slice_counts(COUNTS, 2)
+        with self.assertRaises(IndexError):
+            register_slice_counts(COUNTS, 0, 0, 4)
+        with self.assertRaises(IndexError):
+            register_slice_counts(COUNTS, 0, 2, 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
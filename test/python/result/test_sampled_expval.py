@@ -14,7 +14,14 @@
 
 import unittest
 
-from qiskit.result import Counts, QuasiDistribution, ProbDistribution, sampled_expectation_value
+from qiskit.exceptions import QiskitError
+from qiskit.result import (
+    Counts,
+    QuasiDistribution,
+    ProbDistribution,
+    sampled_expectation_value,
+    sampled_expectation_values,
+)
 from qiskit.quantum_info import Pauli, SparsePauliOp
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -101,6 +108,32 @@ class TestSampledExpval(QiskitTestCase):
         result2 = sampled_expectation_value(dist, "00ZI")
         self.assertAlmostEqual(result2, 0.4376)
 
+    def test_many_operators(self):
+        """Test the batched expectation values agree with those of each operator alone, with
+        terms shared between operators."""
+        dist = QuasiDistribution(PROBS)
+        opers = [
+            "ZZZZ",
+            Pauli("IZIZ"),
+            SparsePauliOp(["IZIZ", "ZZII", "IIII"], coeffs=[0.5, -2.0, 1.5]),
+            SparsePauliOp(["ZZII", "IIZZ"], coeffs=[1 + 2j, 3.0]),
+            "00ZI",
+        ]
+        values = sampled_expectation_values(dist, opers)
+        self.assertEqual(len(values), len(opers))
+        for value, oper in zip(values, opers):
+            self.assertAlmostEqual(value, sampled_expectation_value(dist, oper))
+        self.assertEqual(sampled_expectation_values(dist, []), [])
+
+    def test_many_operators_invalid(self):
+        """Test the batched expectation values validate each operator and the distribution."""
+        with self.assertRaises(QiskitError):
+            sampled_expectation_values(PROBS, ["ZZZZ", "XZZZ"])
+        with self.assertRaises(QiskitError):
+            sampled_expectation_values(PROBS, ["ZZZZ", "ZZ"])
+        with self.assertRaises(QiskitError):
+            sampled_expectation_values([0.5, 0.5], ["Z"])
+
 
 if __name__ == "__main__":
     unittest.main(verbosity=2)
@@ -16,7 +16,9 @@
 import numpy as np
 from ddt import ddt
 from qiskit.circuit.random import random_clifford_circuit
-from qiskit.quantum_info.operators import Clifford
+from qiskit.exceptions import SynthesisError
+from qiskit.quantum_info import random_clifford
+from qiskit.quantum_info.operators import Clifford, Operator
 from qiskit.synthesis.clifford import (
     synth_clifford_full,
     synth_clifford_ag,
@@ -25,6 +27,7 @@ from qiskit.synthesis.clifford import (
 )
 
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
+from qiskit._accelerate.synthesis import synth_clifford_greedy as synth_clifford_greedy_rs
 from test import combine  # pylint: disable=wrong-import-order
 
 
@@ -116,3 +119,39 @@ class TestCliffordSynthesis(QiskitTestCase):
             synth_circ = synth_clifford_full(target)
             value = Clifford(synth_circ)
             self.assertEqual(value, target)
+
+    @combine(num_qubits=[2, 3, 4, 5, 6])
+    def test_synth_ag_random(self, num_qubits):
+        """Test A&G synthesis of uniformly random {num_qubits}-qubit Cliffords"""
+        for seed in range(10):
+            target = random_clifford(num_qubits, seed=seed)
+            synth_circ = synth_clifford_ag(target)
+            self.assertEqual(Clifford(synth_circ), target)
+            self.assertEqual(synth_circ.name, f"{target}_dg")
+            self.assertLessEqual(
+                set(synth_circ.count_ops()), {"h", "s", "sdg", "cx", "swap", "x", "z"}
+            )
+
+    @combine(num_qubits=[2, 3, 4, 5, 6])
+    def test_synth_greedy_random(self, num_qubits):
+        """Test greedy synthesis of uniformly random {num_qubits}-qubit Cliffords"""
+        for seed in range(10):
+            target = random_clifford(num_qubits, seed=seed)
+            synth_circ = synth_clifford_greedy(target)
+            self.assertEqual(Clifford(synth_circ), target)
+            self.assertTrue(Operator(synth_circ).equiv(target.to_operator()))
+            self.assertLessEqual(
+                set(synth_circ.count_ops()), {"h", "s", "cx", "swap", "x", "y", "z"}
+            )
+
+    def test_synth_identity(self):
+        """Test the identity is synthesized without gates"""
+        identity = Clifford.from_label("IIII")
+        self.assertEqual(len(synth_clifford_ag(identity)), 0)
+        self.assertEqual(len(synth_clifford_greedy(identity)), 0)
+
+    def test_synth_greedy_not_symplectic(self):
+        """Test greedy synthesis of a tableau that isn't symplectic raises"""
+        with self.assertRaisesRegex(SynthesisError, "Symplectic Gaussian elimination fails") as cm:
+            synth_clifford_greedy_rs(np.zeros((4, 5), dtype=bool))
+        self.assertEqual(cm.exception.pass_name, "synth_clifford_greedy")
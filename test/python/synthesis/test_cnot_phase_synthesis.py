@@ -14,9 +14,11 @@
 
 import unittest
 import ddt
+import numpy as np
 
 from qiskit.circuit import QuantumCircuit, QuantumRegister
 from qiskit.circuit.library import UnitaryGate
+from qiskit.exceptions import QiskitError
 from qiskit.quantum_info.operators import Operator
 from qiskit.synthesis.linear import synth_cnot_count_full_pmh
 from qiskit.synthesis.linear_phase import synth_cnot_phase_aam
@@ -202,6 +204,31 @@ class TestGraySynth(QiskitTestCase):
         # Check if the two circuits are equivalent
         self.assertEqual(unitary_gray, unitary_compare)
 
+    @ddt.data(1, 2, 3, 4)
+    def test_phase_angles(self, num_qubits):
+        """Test synthesis of a random phase polynomial with numeric angles."""
+        rng = np.random.default_rng(num_qubits)
+        parities = rng.integers(1, 2**num_qubits, size=2 * num_qubits)
+        cnots = [[(int(parity) >> i) & 1 for parity in parities] for i in range(num_qubits)]
+        # The angles are below pi, so they aren't changed by the reduction modulo pi.
+        angles = list(rng.uniform(0, np.pi, size=len(parities)))
+        circuit = synth_cnot_phase_aam(cnots, angles)
+        phases = [
+            sum(angle for parity, angle in zip(parities, angles) if bin(parity & x).count("1") % 2)
+            for x in range(2**num_qubits)
+        ]
+        self.assertEqual(Operator(circuit), Operator(np.diag(np.exp(1j * np.array(phases)))))
+
+    def test_size_mismatch(self):
+        """Test a different number of parities and angles raises."""
+        with self.assertRaises(QiskitError):
+            synth_cnot_phase_aam([[1, 0], [0, 1]], ["t"])
+
+    def test_unknown_gate(self):
+        """Test an unknown phase gate name raises."""
+        with self.assertRaises(ValueError):
+            synth_cnot_phase_aam([[1, 0], [0, 1]], ["t", "u"])
+
 
 @ddt.ddt
 class TestPatelMarkovHayes(QiskitTestCase):
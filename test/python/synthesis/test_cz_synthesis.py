@@ -62,6 +62,15 @@ class TestCZSynth(QiskitTestCase):
             qctest = qctest.compose(perm)
             self.assertEqual(Clifford(qc), Clifford(qctest))
 
+    def test_cz_synth_lnn_empty(self):
+        """Test a matrix without CZ gates gives a circuit that only reverses the qubits."""
+        num_qubits = 4
+        qc = synth_cz_depth_line_mr(np.zeros((num_qubits, num_qubits), dtype=bool))
+        self.assertTrue(check_lnn_connectivity(qc))
+        self.assertEqual(set(qc.count_ops()), {"cx"})
+        perm = Permutation(num_qubits=num_qubits, pattern=range(num_qubits)[::-1])
+        self.assertEqual(Clifford(qc), Clifford(perm))
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,60 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the Clifford+T approximation of RZ rotations."""
+
+import unittest
+
+import numpy as np
+from ddt import ddt
+
+from qiskit.circuit.library import RZGate
+from qiskit.quantum_info import Operator
+from qiskit.synthesis import gridsynth_rz
+from test import combine  # pylint: disable=wrong-import-order
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+CLIFFORD_T = {"h", "t", "tdg", "s", "sdg", "z", "x"}
+
+
+@ddt
+class TestGridsynth(QiskitTestCase):
+    """Test gridsynth_rz."""
+
+    @combine(theta=[0.1, -0.7, 2.9, np.pi / 8], epsilon=[1e-2, 1e-5])
+    def test_approximation(self, theta, epsilon):
+        """Test the circuit is within epsilon of the rotation, with its global phase."""
+        circuit = gridsynth_rz(theta, epsilon)
+        self.assertEqual(circuit.num_qubits, 1)
+        self.assertLessEqual(set(circuit.count_ops()), CLIFFORD_T)
+        distance = np.linalg.norm(Operator(circuit).data - RZGate(theta).to_matrix(), 2)
+        self.assertLessEqual(distance, epsilon)
+
+    def test_clifford_angles(self):
+        """Test multiples of pi/2 are synthesized exactly without T gates."""
+        for k in range(-3, 4):
+            theta = k * np.pi / 2
+            with self.subTest(theta=theta):
+                circuit = gridsynth_rz(theta, 1e-8)
+                self.assertNotIn("t", circuit.count_ops())
+                self.assertNotIn("tdg", circuit.count_ops())
+                self.assertEqual(Operator(circuit), Operator(RZGate(theta)))
+
+    def test_bad_inputs(self):
+        """Test a non-finite angle or a precision out of range raises."""
+        for theta, epsilon in [(np.nan, 1e-3), (np.inf, 1e-3), (0.5, 1e-12), (0.5, 1.0)]:
+            with self.subTest(theta=theta, epsilon=epsilon), self.assertRaises(ValueError):
+                gridsynth_rz(theta, epsilon)
+
+
+if __name__ == "__main__":
+    unittest.main()
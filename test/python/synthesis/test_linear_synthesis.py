@@ -17,6 +17,7 @@ import unittest
 import numpy as np
 from ddt import ddt, data
 from qiskit import QuantumCircuit
+from qiskit.exceptions import QiskitError
 from qiskit.circuit.library import LinearFunction
 from qiskit.synthesis.linear import (
     synth_cnot_count_full_pmh,
@@ -26,6 +27,7 @@ from qiskit.synthesis.linear import (
     calc_inverse_matrix,
 )
 from qiskit.synthesis.linear.linear_circuits_utils import transpose_cx_circ, optimize_cx_4_options
+from qiskit.synthesis.linear.linear_matrix_utils import _compute_rank, _gauss_elimination_with_perm
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -136,6 +138,42 @@ class TestLinearSynth(QiskitTestCase):
                 dist = abs(q0 - q1)
                 self.assertEqual(dist, 1)
 
+    @data(1, 2, 3, 4, 7)
+    def test_synth_pmh_section_sizes(self, section_size):
+        """Test synth_cnot_count_full_pmh for section sizes that don't divide the qubits."""
+        mat = random_invertible_binary_matrix(7, seed=5678)
+        qc = synth_cnot_count_full_pmh(mat, section_size=section_size)
+        self.assertTrue(np.array_equal(LinearFunction(qc).linear, mat))
+        self.assertLessEqual(set(qc.count_ops()), {"cx"})
+
+    def test_synth_pmh_bad_section_size(self):
+        """Test synth_cnot_count_full_pmh rejects an empty section."""
+        with self.assertRaises(ValueError):
+            synth_cnot_count_full_pmh(np.eye(3, dtype=bool), section_size=0)
+
+    def test_not_invertible(self):
+        """Test the functions on a singular and a non-square matrix."""
+        mat = np.array([[1, 1, 0], [0, 1, 1], [1, 0, 1]], dtype=bool)
+        self.assertFalse(check_invertible_binary_matrix(mat))
+        self.assertFalse(check_invertible_binary_matrix(np.ones((2, 3), dtype=bool)))
+        with self.assertRaises(QiskitError):
+            calc_inverse_matrix(mat)
+        with self.assertRaises(QiskitError):
+            calc_inverse_matrix(np.ones((2, 3), dtype=bool))
+        with self.assertRaises(QiskitError):
+            synth_cnot_depth_line_kms(mat)
+
+    def test_rank_and_elimination(self):
+        """Test the rank and the Gaussian elimination of a binary matrix."""
+        mat = np.array([[0, 1, 1], [0, 0, 0], [1, 0, 1], [1, 1, 0]], dtype=int)
+        self.assertEqual(_compute_rank(mat), 2)
+        reduced, perm = _gauss_elimination_with_perm(mat, full_elim=True)
+        self.assertEqual(reduced.dtype, int)
+        np.testing.assert_array_equal(reduced, [[1, 0, 1], [0, 1, 1], [0, 0, 0], [0, 0, 0]])
+        self.assertEqual(_compute_rank(mat[perm[:2]]), 2)
+        # The input is left alone.
+        np.testing.assert_array_equal(mat[1], [0, 0, 0])
+
 
 if __name__ == "__main__":
     unittest.main()
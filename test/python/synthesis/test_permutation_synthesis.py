@@ -25,7 +25,7 @@ from qiskit.synthesis.permutation import (
     synth_permutation_basic,
     synth_permutation_reverse_lnn_kms,
 )
-from qiskit.synthesis.permutation.permutation_utils import _get_ordered_swap
+from qiskit.synthesis.permutation.permutation_utils import _get_ordered_swap, _inverse_pattern
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -144,6 +144,26 @@ class TestPermutationSynthesis(QiskitTestCase):
             constructed = Operator(PermutationGate(pattern))
             self.assertEqual(expected, constructed)
 
+    def test_inverse_pattern(self):
+        """Test _inverse_pattern inverts the permutation."""
+        np.random.seed(1)
+        pattern = np.random.permutation(10)
+        inverse = _inverse_pattern(pattern)
+        self.assertEqual([inverse[x] for x in pattern], list(range(10)))
+
+    @data([0, 3, 1], [0, -1, 1], [0, 1, 1])
+    def test_invalid_pattern(self, pattern):
+        """Test the synthesis functions raise on a pattern that isn't a permutation."""
+        for synth in [
+            synth_permutation_basic,
+            synth_permutation_acg,
+            synth_permutation_depth_lnn_kms,
+        ]:
+            with self.subTest(synth=synth.__name__), self.assertRaises(ValueError):
+                synth(pattern)
+        with self.assertRaises(ValueError):
+            _get_ordered_swap(pattern)
+
 
 if __name__ == "__main__":
     unittest.main()
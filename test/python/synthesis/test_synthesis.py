@@ -12,6 +12,7 @@
 
 """Tests for quantum synthesis methods."""
 
+import copy
 import pickle
 import unittest
 import contextlib
@@ -55,6 +56,7 @@ from qiskit.circuit.library import (
 from qiskit.quantum_info.operators import Operator
 from qiskit.quantum_info.random import random_unitary
 from qiskit.synthesis.one_qubit.one_qubit_decompose import OneQubitEulerDecomposer
+from qiskit.synthesis.two_qubit import two_qubit_decompose
 from qiskit.synthesis.two_qubit.two_qubit_decompose import (
     TwoQubitWeylDecomposition,
     two_qubit_cnot_decompose,
@@ -64,7 +66,8 @@ from qiskit.synthesis.two_qubit.two_qubit_decompose import (
     decompose_two_qubit_product_gate,
     TwoQubitDecomposeUpToDiagonal,
 )
-from qiskit._accelerate.two_qubit_decompose import Specialization
+from qiskit._accelerate import euler_one_qubit_decomposer
+from qiskit._accelerate.two_qubit_decompose import Specialization, num_basis_gates_weyl
 from qiskit.synthesis.unitary import qsd
 from test import combine  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
@@ -150,7 +153,9 @@ class CheckDecompositions(QiskitTestCase):
         """Fail if eval(repr(weyl1)) not equal to weyl1"""
         repr1 = repr(weyl1)
         with self.assertDebugOnly():
-            weyl2: TwoQubitWeylDecomposition = eval(repr1)  # pylint: disable=eval-used
+            # The representation names the subclass of the specialization.
+            namespace = {**globals(), **vars(two_qubit_decompose)}
+            weyl2: TwoQubitWeylDecomposition = eval(repr1, namespace)  # pylint: disable=eval-used
         msg_base = f"weyl1:\n{repr1}\nweyl2:\n{repr(weyl2)}"
         self.assertEqual(type(weyl1), type(weyl2), msg_base)
         maxdiff = np.max(abs(weyl1.unitary_matrix - weyl2.unitary_matrix))
@@ -635,6 +640,29 @@ class TestOneQubitEulerDecomposer(CheckDecompositions):
         self.assertAlmostEqual(phi, expected_phi)
         self.assertAlmostEqual(lam, expected_lam)
 
+    @combine(
+        basis=ONEQ_BASES,
+        seed=range(5),
+        name="test_circuit_data_matches_dag_{basis}_{seed}",
+    )
+    def test_circuit_data_matches_dag(self, basis, seed):
+        """Test the circuit built from Rust circuit data in {basis} basis matches the one built
+        from the DAG (seed={seed})."""
+        unitary = random_unitary(2, seed=seed)
+        circuit = OneQubitEulerDecomposer(basis)(unitary)
+        dag = OneQubitEulerDecomposer(basis, use_dag=True)(unitary)
+        self.assertEqual(circuit, dag_to_circuit(dag))
+
+    def test_unitary_to_circuit(self):
+        """Test the Rust decomposition to circuit data carries the global phase, and gives nothing
+        without a basis."""
+        unitary = np.exp(0.25j) * RXGate(0.5).to_matrix()
+        data = euler_one_qubit_decomposer.unitary_to_circuit(unitary, ["ZSX"], 0)
+        circuit = QuantumCircuit._from_circuit_data(data)
+        self.assertLessEqual(set(circuit.count_ops()), {"rz", "sx"})
+        self.assertEqual(Operator(circuit), Operator(unitary))
+        self.assertIsNone(euler_one_qubit_decomposer.unitary_to_circuit(unitary, [], 0))
+
 
 # FIXME: streamline the set of test cases
 class TestTwoQubitWeylDecomposition(CheckDecompositions):
@@ -652,6 +680,48 @@ class TestTwoQubitWeylDecomposition(CheckDecompositions):
         weyl1 = TwoQubitWeylDecomposition(target, fidelity=0.99)
         self.assertRoundTripPickle(weyl1)
 
+    def test_specialized_subclasses(self):
+        """Check decompositions are instances of the subclass of their specialization, and that
+        constructing a subclass forces its specialization"""
+        cx = Operator(CXGate()).data
+        decomp = TwoQubitWeylDecomposition(cx)
+        self.assertIs(type(decomp), two_qubit_decompose.TwoQubitWeylControlledEquiv)
+        self.assertIsInstance(decomp, TwoQubitWeylDecomposition)
+        general = two_qubit_decompose.TwoQubitWeylGeneral(cx)
+        self.assertIs(type(general), two_qubit_decompose.TwoQubitWeylGeneral)
+        self.assertEqual(general._inner_decomposition.specialization, Specialization.General)
+        self.assertEqual(Operator(general.circuit()), Operator(cx))
+        self.assertRoundTrip(general)
+        self.assertRoundTripPickle(general)
+        self.assertIs(type(copy.deepcopy(general)), two_qubit_decompose.TwoQubitWeylGeneral)
+
+    def test_specialization_names(self):
+        """Check every specialization has a subclass named after it"""
+        for specialization in Specialization._variants():
+            name = str(specialization).split(".")[1]
+            self.assertEqual(specialization.name, name)
+            subclass = getattr(two_qubit_decompose, f"TwoQubitWeyl{name}")
+            self.assertTrue(issubclass(subclass, TwoQubitWeylDecomposition))
+            self.assertEqual(subclass._class_specialization, specialization)
+
+    def test_k_matrices_and_closeness(self):
+        """Check the stacked local matrices and the flags of special Weyl coordinates"""
+        decomp = TwoQubitWeylDecomposition(random_unitary(4, seed=7))
+        k_matrices, flags = decomp.k_matrices_and_closeness()
+        self.assertEqual(k_matrices.shape, (4, 2, 2))
+        for stacked, k in zip(k_matrices, [decomp.K1l, decomp.K1r, decomp.K2l, decomp.K2r]):
+            np.testing.assert_array_equal(stacked, k)
+        names = {"a_is_zero", "a_is_pi4", "b_is_zero", "b_is_pi4", "c_is_zero", "c_is_pi4"}
+        self.assertEqual(set(flags), names | {"a_equals_b", "b_equals_c"})
+        self.assertFalse(any(flags.values()))
+
+        _, flags = TwoQubitWeylDecomposition(Operator(SwapGate()).data).k_matrices_and_closeness()
+        true = {"a_is_pi4", "b_is_pi4", "c_is_pi4", "a_equals_b", "b_equals_c"}
+        self.assertEqual({name for name, value in flags.items() if value}, true)
+        decomp = TwoQubitWeylDecomposition(Ud(0.3, 0.2 + 1e-6, 0.2), fidelity=None)
+        self.assertFalse(decomp.k_matrices_and_closeness()[1]["b_equals_c"])
+        self.assertTrue(decomp.k_matrices_and_closeness(atol=1e-5)[1]["b_equals_c"])
+
     def test_two_qubit_weyl_decomposition_cnot(self):
         """Verify Weyl KAK decomposition for U~CNOT"""
         for k1l, k1r, k2l, k2r in K1K2S:
@@ -1404,6 +1474,59 @@ class TestTwoQubitDecomposeApprox(CheckDecompositions):
         self.check_approx_decomposition(tgt_unitary, decomposer, num_basis_uses=3)
 
 
+@ddt
+class TestNumBasisGatesWeyl(CheckDecompositions):
+    """Test the estimated number of applications of basis gates given by their Weyl coordinates"""
+
+    def dressed(self, a, b, c, seed):
+        """The canonical gate of coordinates (a, b, c) between random one-qubit gates."""
+        k1 = np.kron(random_unitary(2, seed=seed).data, random_unitary(2, seed=seed + 1).data)
+        k2 = np.kron(random_unitary(2, seed=seed + 2).data, random_unitary(2, seed=seed + 3).data)
+        return k1 @ Ud(a, b, c) @ k2
+
+    @combine(seed=range(10), basis_fidelity=[1.0, 0.99, 0.9], name="seed_{seed}_{basis_fidelity}")
+    def test_supercontrolled_matches_decomposer(self, seed, basis_fidelity):
+        """Verify supercontrolled bases are counted as by TwoQubitBasisDecomposer (seed={seed})."""
+        unitary = random_unitary(4, seed=seed).data
+        decomposer = TwoQubitBasisDecomposer(CXGate(), basis_fidelity=basis_fidelity)
+        expected = decomposer.num_basis_gates(unitary)
+        # The coordinates of CX, outside the Weyl chamber.
+        basis = [-np.pi / 4, 0, np.pi / 2]
+        self.assertEqual(num_basis_gates_weyl(basis, basis_fidelity, unitary), expected)
+
+    def test_sqrt_iswap(self):
+        """Verify the counts of exact decompositions with the square root of iSWAP."""
+        basis = [np.pi / 8, np.pi / 8, 0]
+        cases = [
+            ((0, 0, 0), 0),
+            ((np.pi / 8, np.pi / 8, 0), 1),
+            ((np.pi / 4, 0, 0), 2),
+            ((np.pi / 4, np.pi / 4, np.pi / 4), 3),
+        ]
+        for seed, (coordinates, expected) in enumerate(cases):
+            with self.subTest(coordinates=coordinates):
+                unitary = self.dressed(*coordinates, seed=4 * seed)
+                self.assertEqual(num_basis_gates_weyl(basis, 0.99, unitary), expected)
+
+    def test_xx_family(self):
+        """Verify two applications of a basis of the XX family don't leave the c = 0 plane."""
+        basis = [np.pi / 8, 0, 0]
+        self.assertEqual(num_basis_gates_weyl(basis, 1.0, self.dressed(np.pi / 4, 0, 0, 1)), 2)
+        unitary = self.dressed(np.pi / 4, np.pi / 8, 0, 2)
+        self.assertEqual(num_basis_gates_weyl(basis, 1.0, unitary), 3)
+        unitary = self.dressed(np.pi / 8, np.pi / 16, np.pi / 32, 3)
+        self.assertEqual(num_basis_gates_weyl(basis, 1.0, unitary), 3)
+
+    def test_basis_fidelity(self):
+        """Verify noisy basis gates are skipped for targets close to the identity, and the identity
+        basis is never applied."""
+        basis = [np.pi / 8, np.pi / 8, 0]
+        unitary = self.dressed(0.01, 0, 0, 5)
+        self.assertEqual(num_basis_gates_weyl(basis, 1.0, unitary), 2)
+        self.assertEqual(num_basis_gates_weyl(basis, 0.9, unitary), 0)
+        self.assertEqual(num_basis_gates_weyl([0, 0, 0], 1.0, self.dressed(0.3, 0.2, 0.1, 6)), 0)
+
+
 @ddt
 class TestTwoQubitControlledUDecompose(CheckDecompositions):
     """Test TwoQubitControlledUDecomposer() for exact decompositions and raised exceptions"""
@@ -1669,6 +1792,56 @@ class TestQuantumShannonDecomposer(QiskitTestCase):
         except UnboundLocalError as uerr:
             self.fail(str(uerr))
 
+    @combine(nqubits=[2, 3, 4], opt_a1=[False, True], opt_a2=[False, True])
+    def test_rust_matches_python(self, nqubits, opt_a1, opt_a2):
+        """Test the Rust decomposition matches the Python one, including the global phase"""
+        mat = random_unitary(2**nqubits, seed=41 + nqubits).data
+        circ = self.qsd(mat, opt_a1=opt_a1, opt_a2=opt_a2)
+        self.assertEqual(Operator(circ), Operator(mat))
+        self.assertLessEqual(set(circ.count_ops()), {"u", "ry", "rz", "cx", "cz"})
+        # Passing a decomposer goes through the Python implementation.
+        reference = self.qsd(
+            mat, opt_a1=opt_a1, opt_a2=opt_a2, decomposer_1q=OneQubitEulerDecomposer("U")
+        )
+        self.assertEqual(
+            circ.count_ops().get("cx", 0) + circ.count_ops().get("cz", 0),
+            reference.count_ops().get("cx", 0) + reference.count_ops().get("cz", 0),
+        )
+
+    def test_rust_global_phase(self):
+        """Test a unitary that differs from the identity by a phase keeps its phase"""
+        mat = np.exp(0.7j) * np.eye(8)
+        circ = self.qsd(mat)
+        self.assertEqual(Operator(circ), Operator(mat))
+
+    def test_rust_bad_shape(self):
+        """Test the Rust decomposition rejects a matrix that isn't on qubits"""
+        with self.assertRaises(QiskitError):
+            qsd.qs_decomposition_rs(np.eye(3, dtype=complex))
+
+    @data(1, 2, 3, 4)
+    def test_cossin_matches_scipy(self, nqubits):
+        """Test the Rust cosine-sine decomposition rebuilds the unitary with SciPy's angles"""
+        mat = random_unitary(2**nqubits, seed=7 + nqubits).data
+        (u1, u2), theta, (v1h, v2h) = qsd.cossin(mat)
+        middle = np.block(
+            [
+                [np.diag(np.cos(theta)), -np.diag(np.sin(theta))],
+                [np.diag(np.sin(theta)), np.diag(np.cos(theta))],
+            ]
+        )
+        left = scipy.linalg.block_diag(u1, u2)
+        right = scipy.linalg.block_diag(v1h, v2h)
+        np.testing.assert_allclose(left @ middle @ right, mat, atol=1e-10)
+        dim = 2 ** (nqubits - 1)
+        _, reference, _ = scipy.linalg.cossin(mat, p=dim, q=dim, separate=True)
+        np.testing.assert_allclose(np.sort(theta), np.sort(reference), atol=1e-10)
+
+    def test_cossin_bad_shape(self):
+        """Test the cosine-sine decomposition rejects a matrix of odd dimension"""
+        with self.assertRaises(QiskitError):
+            qsd.cossin(np.eye(3, dtype=complex))
+
 
 class TestTwoQubitDecomposeUpToDiagonal(QiskitTestCase):
     """test TwoQubitDecomposeUpToDiagonal class"""
@@ -0,0 +1,64 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the Rust entry point that synthesizes a unitary into circuit data."""
+
+import unittest
+
+import numpy as np
+from ddt import ddt
+
+from qiskit import QuantumCircuit
+from qiskit._accelerate.unitary_synthesis import synthesize_unitary
+from qiskit.quantum_info import Operator, random_unitary
+from test import combine  # pylint: disable=wrong-import-order
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+@ddt
+class TestSynthesizeUnitary(QiskitTestCase):
+    """Test the ``synthesize_unitary`` Rust function."""
+
+    @combine(
+        num_qubits=[1, 2, 3, 4],
+        basis_gates=[["u", "cx"], ["rz", "sx", "x", "cz"], ["rz", "ry", "ecr"]],
+        seed=[0, 1],
+    )
+    def test_synthesis_is_exact(self, num_qubits, basis_gates, seed):
+        """Test that the synthesized circuit implements the unitary, including its phase."""
+        unitary = random_unitary(2**num_qubits, seed=seed)
+        data = synthesize_unitary(unitary.data, basis_gates)
+        circuit = QuantumCircuit._from_circuit_data(data)
+        self.assertEqual(circuit.num_qubits, num_qubits)
+        self.assertEqual(Operator(circuit), unitary)
+
+    @combine(num_qubits=[1, 2], seed=[0, 1])
+    def test_synthesis_in_basis(self, num_qubits, seed):
+        """Test that one- and two-qubit unitaries are synthesized into the basis gates."""
+        basis_gates = ["rz", "sx", "x", "cz"]
+        unitary = random_unitary(2**num_qubits, seed=seed)
+        circuit = QuantumCircuit._from_circuit_data(synthesize_unitary(unitary.data, basis_gates))
+        self.assertLessEqual(set(circuit.count_ops()), set(basis_gates))
+
+    def test_no_decomposer(self):
+        """Test that ``None`` is returned when the basis has no two-qubit decomposer."""
+        unitary = random_unitary(4, seed=42)
+        self.assertIsNone(synthesize_unitary(unitary.data, ["u", "rxx"]))
+
+    def test_invalid_shape(self):
+        """Test that a matrix whose size isn't a power of two is rejected."""
+        with self.assertRaisesRegex(ValueError, "power-of-two size"):
+            synthesize_unitary(np.eye(3, dtype=complex), ["u", "cx"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -16,7 +16,10 @@
 import unittest
 import numpy as np
 from numpy.testing import assert_allclose
+import scipy.linalg
 
+from qiskit._accelerate import weyl as weyl_rs
+from qiskit.quantum_info import Pauli
 from qiskit.quantum_info.random import random_unitary
 from qiskit.synthesis.two_qubit.weyl import weyl_coordinates
 from qiskit.synthesis.two_qubit.local_invariance import (
@@ -70,6 +73,27 @@ class TestWeyl(QiskitTestCase):
             local = two_qubit_local_invariants(U)
             assert_allclose(local, local_equiv)
 
+    def test_weyl_coordinates_canonical(self):
+        """Check the coordinates of canonical gates, dressed in single-qubit gates, are the
+        canonical coordinates brought into the Weyl chamber."""
+        xx, yy, zz = (Pauli(label).to_matrix() for label in ["XX", "YY", "ZZ"])
+        before = np.kron(random_unitary(2, seed=1).data, random_unitary(2, seed=2).data)
+        after = np.kron(random_unitary(2, seed=3).data, random_unitary(2, seed=4).data)
+        for point in [[0.3, 0.2, 0.1], [0.6, 0.3, -0.2], [0.1, -0.6, 0.3 + np.pi / 2]]:
+            canonical = scipy.linalg.expm(1j * (point[0] * xx + point[1] * yy + point[2] * zz))
+            weyl = weyl_coordinates(after @ canonical @ before)
+            assert_allclose(weyl, weyl_rs.canonicalize(point), atol=1e-7)
+        assert_allclose(weyl_rs.canonicalize([0.1, -0.6, 0.3 + np.pi / 2]), [0.6, 0.3, -0.1])
+
+    def test_chamber_distance(self):
+        """Check the infidelity between points of the Weyl chamber."""
+        self.assertAlmostEqual(weyl_rs.chamber_distance([0.3, 0.2, 0.1], [0.3, 0.2, 0.1]), 0)
+        # The mirror image across the a = pi/4 face is the same class.
+        mirror = [np.pi / 2 - 0.6, 0.2, -0.1]
+        self.assertAlmostEqual(weyl_rs.chamber_distance([0.6, 0.2, 0.1], mirror), 0)
+        self.assertAlmostEqual(weyl_rs.chamber_distance([0, 0, 0], [np.pi / 4, 0, 0]), 0.4)
+        self.assertAlmostEqual(weyl_rs.canonical_trace([0, 0, 0]), 4)
+
 
 if __name__ == "__main__":
     unittest.main()
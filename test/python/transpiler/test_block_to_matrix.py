@@ -0,0 +1,239 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the conversion of blocks of operations to matrices."""
+
+import unittest
+
+import numpy as np
+
+from qiskit import QuantumCircuit
+from qiskit.circuit import Parameter
+from qiskit.circuit.library import CXGate, UnitaryGate
+from qiskit.converters import circuit_to_dag
+from qiskit.quantum_info import Operator, random_unitary
+from qiskit.transpiler.passes.utils.block_to_matrix import _block_to_matrix, _rust_gate_params
+from qiskit._accelerate.convert_2q_block_matrix import (
+    blocks_to_matrix,
+    blocks_to_matrix_3q,
+    circuit_block_to_matrix,
+    permute_qubits,
+)
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _two_qubit_circuit():
+    circuit = QuantumCircuit(2)
+    circuit.h(0)
+    circuit.rx(0.3, 1)
+    circuit.cx(1, 0)
+    circuit.cp(0.7, 0, 1)
+    circuit.u(0.1, 0.2, 0.3, 1)
+    circuit.rzz(0.4, 0, 1)
+    circuit.swap(0, 1)
+    circuit.ecr(0, 1)
+    circuit.sxdg(0)
+    return circuit
+
+
+def _three_qubit_circuit():
+    circuit = QuantumCircuit(3)
+    circuit.h(2)
+    circuit.ccx(2, 0, 1)
+    circuit.ry(0.8, 1)
+    circuit.cswap(1, 2, 0)
+    circuit.crz(-0.6, 2, 0)
+    circuit.rxx(1.1, 1, 2)
+    circuit.t(0)
+    circuit.ccx(0, 1, 2)
+    return circuit
+
+
+def _op_list(circuit, by_name):
+    """The operations of ``circuit`` as given to ``blocks_to_matrix``, each by its name and
+    parameters if ``by_name`` says so, and by its matrix otherwise."""
+    out = []
+    for index, instruction in enumerate(circuit.data):
+        operation = instruction.operation
+        q_list = [circuit.find_bit(qubit).index for qubit in instruction.qubits]
+        if by_name(index):
+            out.append((operation.name, [float(param) for param in operation.params], q_list))
+        else:
+            out.append((operation.to_matrix(), q_list))
+    return out
+
+
+class TestBlocksToMatrix(QiskitTestCase):
+    """Test the matrices of blocks of operations given by name or by matrix."""
+
+    def test_standard_gates_by_name(self):
+        """Test the matrix of a block of standard gates given by name is the block's operator."""
+        circuit = _two_qubit_circuit()
+        matrix = blocks_to_matrix(_op_list(circuit, lambda _: True))
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_names_and_matrices(self):
+        """Test gates given by name and by matrix can be mixed in a block."""
+        circuit = _two_qubit_circuit()
+        matrix = blocks_to_matrix(_op_list(circuit, lambda index: index % 2 == 0))
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_unknown_gate(self):
+        """Test a name that isn't a standard gate raises."""
+        with self.assertRaises(ValueError):
+            blocks_to_matrix([("not_a_gate", [], [0])])
+
+    def test_wrong_number_of_params(self):
+        """Test a standard gate given the wrong number of parameters raises."""
+        with self.assertRaises(ValueError):
+            blocks_to_matrix([("rx", [], [0])])
+
+    def test_wrong_number_of_qubits(self):
+        """Test a standard gate on the wrong number of qubits raises."""
+        with self.assertRaises(ValueError):
+            blocks_to_matrix([("cx", [], [0])])
+
+
+class TestBlocksToMatrix3q(QiskitTestCase):
+    """Test the matrices of three-qubit blocks of operations."""
+
+    def test_standard_gates_by_name(self):
+        """Test the matrix of a block of standard gates on the block qubits in every order is the
+        block's operator."""
+        circuit = _three_qubit_circuit()
+        matrix = blocks_to_matrix_3q(_op_list(circuit, lambda _: True))
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_names_and_matrices(self):
+        """Test gates given by name and by matrix can be mixed in a block."""
+        circuit = _three_qubit_circuit()
+        matrix = blocks_to_matrix_3q(_op_list(circuit, lambda index: index % 2 == 1))
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_empty(self):
+        """Test an empty block is the identity."""
+        np.testing.assert_array_equal(blocks_to_matrix_3q([]), np.eye(8))
+
+    def test_bad_qubits(self):
+        """Test operations on repeated or out-of-range block qubits raise."""
+        with self.assertRaises(ValueError):
+            blocks_to_matrix_3q([("cx", [], [1, 1])])
+        with self.assertRaises(ValueError):
+            blocks_to_matrix_3q([("x", [], [3])])
+        with self.assertRaises(ValueError):
+            blocks_to_matrix_3q([(np.eye(4), [2])])
+
+
+class TestBlockToMatrix(QiskitTestCase):
+    """Test the matrices of blocks of DAG nodes."""
+
+    def test_rust_gate_params(self):
+        """Test only the bound standard gates with their default control states go by name."""
+        self.assertEqual(_rust_gate_params(CXGate()), [])
+        qc = QuantumCircuit(1)
+        qc.rz(0.5, 0)
+        self.assertEqual(_rust_gate_params(qc.data[0].operation), [0.5])
+        qc.rz(Parameter("a"), 0)
+        self.assertIsNone(_rust_gate_params(qc.data[1].operation))
+        self.assertIsNone(_rust_gate_params(CXGate(ctrl_state=0)))
+        self.assertIsNone(_rust_gate_params(UnitaryGate(np.eye(2))))
+
+    def test_block(self):
+        """Test the matrix of a block of standard and other gates is the block's operator."""
+        circuit = _two_qubit_circuit()
+        circuit.append(CXGate(ctrl_state=0), [1, 0])
+        circuit.append(UnitaryGate(random_unitary(4, seed=7)), [0, 1])
+        dag = circuit_to_dag(circuit)
+        block_index_map = {qubit: index for index, qubit in enumerate(dag.qubits)}
+        matrix = _block_to_matrix(list(dag.topological_op_nodes()), block_index_map)
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_3q_block(self):
+        """Test the matrix of a three-qubit block is the block's operator."""
+        circuit = _three_qubit_circuit()
+        circuit.append(UnitaryGate(random_unitary(4, seed=3)), [2, 0])
+        dag = circuit_to_dag(circuit)
+        block_index_map = {qubit: index for index, qubit in enumerate(dag.qubits)}
+        matrix = _block_to_matrix(list(dag.topological_op_nodes()), block_index_map)
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+
+class TestCircuitBlockToMatrix(QiskitTestCase):
+    """Test the matrices of blocks of the instructions of a circuit."""
+
+    def test_block(self):
+        """Test the matrix of some instructions of a circuit is the operator of those
+        instructions, with the block qubits in the given order."""
+        circuit = QuantumCircuit(4)
+        circuit.h(3)
+        circuit.cx(3, 1)
+        circuit.x(0)
+        circuit.barrier(1, 3)
+        circuit.append(UnitaryGate(random_unitary(4, seed=5)), [1, 3])
+        circuit.rz(0.3, 1)
+        block = QuantumCircuit(2)
+        block.h(0)
+        block.cx(0, 1)
+        block.append(UnitaryGate(random_unitary(4, seed=5)), [1, 0])
+        block.rz(0.3, 1)
+        matrix = circuit_block_to_matrix(circuit._data, [0, 1, 3, 4, 5], [3, 1])
+        np.testing.assert_allclose(matrix, Operator(block).data, atol=1e-12)
+
+    def test_3q_block(self):
+        """Test the matrix of a three-qubit block of a circuit."""
+        circuit = _three_qubit_circuit()
+        matrix = circuit_block_to_matrix(circuit._data, list(range(len(circuit.data))), [0, 1, 2])
+        np.testing.assert_allclose(matrix, Operator(circuit).data, atol=1e-12)
+
+    def test_no_matrix(self):
+        """Test a block with an instruction that has no matrix, or acts on clbits, has none."""
+        circuit = QuantumCircuit(2, 1)
+        circuit.cx(0, 1)
+        circuit.reset(0)
+        circuit.measure(1, 0)
+        self.assertIsNone(circuit_block_to_matrix(circuit._data, [0, 1], [0, 1]))
+        self.assertIsNone(circuit_block_to_matrix(circuit._data, [0, 2], [0, 1]))
+
+    def test_bad_block(self):
+        """Test out-of-range instructions, qubits outside the block, and blocks of the wrong size
+        raise."""
+        circuit = QuantumCircuit(3)
+        circuit.cx(0, 2)
+        with self.assertRaises(IndexError):
+            circuit_block_to_matrix(circuit._data, [1], [0, 2])
+        with self.assertRaises(ValueError):
+            circuit_block_to_matrix(circuit._data, [0], [0, 1])
+        with self.assertRaises(ValueError):
+            circuit_block_to_matrix(circuit._data, [0], [0])
+
+
+class TestPermuteQubits(QiskitTestCase):
+    """Test the reordering of the qubits of operator matrices."""
+
+    def test_permute_qubits(self):
+        """Test qubit ``i`` of the output is qubit ``perm[i]`` of the input."""
+        a, b, c = (random_unitary(2, seed=seed).data for seed in range(3))
+        # Qubit 0 is the rightmost factor.
+        matrix = np.kron(a, np.kron(b, c))
+        np.testing.assert_allclose(permute_qubits(matrix, [1, 2, 0]), np.kron(c, np.kron(a, b)))
+        np.testing.assert_allclose(permute_qubits(matrix, [0, 1, 2]), matrix)
+
+    def test_bad_permutation(self):
+        """Test a permutation that doesn't match the qubits of the matrix raises."""
+        matrix = np.eye(4, dtype=complex)
+        for perm in [[0], [1, 1], [0, 2], [0, 1, 2]]:
+            with self.assertRaises(ValueError):
+                permute_qubits(matrix, perm)
+
+
+if __name__ == "__main__":
+    unittest.main()
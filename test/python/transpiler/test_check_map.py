@@ -19,6 +19,8 @@ from qiskit.circuit.library import CXGate
 from qiskit.transpiler.passes import CheckMap
 from qiskit.transpiler import CouplingMap, Target
 from qiskit.converters import circuit_to_dag
+from qiskit._accelerate.check_map import check_map
+from qiskit._accelerate.stochastic_swap import EdgeCollection
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -277,6 +279,57 @@ class TestCheckMapCX(QiskitTestCase):
         pass_(outer)
         self.assertTrue(pass_.property_set["is_swap_mapped"])
 
+    def test_swap_mapped_false_message(self):
+        """Test the message names the first instruction that isn't on a coupled pair."""
+        circuit = QuantumCircuit(4)
+        circuit.cx(0, 1)
+        circuit.barrier(0, 3)
+        circuit.cz(3, 1)
+        circuit.cx(0, 2)
+        pass_ = CheckMap(CouplingMap.from_line(4))
+        pass_(circuit)
+        self.assertFalse(pass_.property_set["is_swap_mapped"])
+        self.assertEqual(pass_.property_set["check_map_msg"], "cz(3, 1) failed")
+
+    def test_swap_mapped_ignores_directives(self):
+        """Test directives on uncoupled qubits don't make the circuit unmapped."""
+        circuit = QuantumCircuit(3, 2)
+        circuit.cx(1, 0)
+        circuit.barrier(0, 2)
+        circuit.measure([0, 2], [0, 1])
+        pass_ = CheckMap(CouplingMap([[0, 1], [1, 2]]))
+        pass_(circuit)
+        self.assertTrue(pass_.property_set["is_swap_mapped"])
+
+
+class TestCheckMapRust(QiskitTestCase):
+    """Tests the Rust function the CheckMap pass uses for circuits without control flow."""
+
+    def setUp(self):
+        super().setUp()
+        self.edges = EdgeCollection()
+        for edge in CouplingMap.from_line(4).get_edges():
+            self.edges.add(*edge)
+
+    def test_mapped(self):
+        """Test a mapped circuit has no violation, whatever the direction of its gates."""
+        circuit = QuantumCircuit(4)
+        circuit.cx(0, 1)
+        circuit.cx(2, 1)
+        circuit.ccx(0, 1, 2)
+        circuit.barrier()
+        circuit.swap(3, 2)
+        self.assertIsNone(check_map(circuit._data, self.edges))
+
+    def test_first_violation(self):
+        """Test the index of the first violating instruction is returned."""
+        circuit = QuantumCircuit(4)
+        circuit.h(0)
+        circuit.cx(0, 1)
+        circuit.cx(0, 2)
+        circuit.cx(3, 0)
+        self.assertEqual(check_map(circuit._data, self.edges), 2)
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -21,6 +21,7 @@ from qiskit.circuit.library import RZGate, UnitaryGate
 from qiskit.transpiler import PassManager
 from qiskit.transpiler.passes import CommutativeInverseCancellation
 from qiskit.quantum_info import Operator
+from qiskit._accelerate.commutative_inverse_cancellation import commutative_inverse_cancellation
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -887,6 +888,56 @@ class TestCommutativeInverseCancellation(QiskitTestCase):
         new_circuit = passmanager.run(circuit)
         self.assertEqual(circuit, new_circuit)
 
+    @data(False, True)
+    def test_random_circuits(self, matrix_based):
+        """Test the pass keeps the operators of random circuits of gates that often cancel."""
+        rng = np.random.default_rng(2024)
+        names = ["x", "y", "z", "h", "s", "sdg", "t", "tdg", "cx", "cz", "swap"]
+        passmanager = PassManager(CommutativeInverseCancellation(matrix_based=matrix_based))
+        for _ in range(20):
+            circuit = QuantumCircuit(3)
+            for name in rng.choice(names, size=30):
+                num_qubits = 2 if name in ("cx", "cz", "swap") else 1
+                getattr(circuit, name)(*rng.choice(3, size=num_qubits, replace=False).tolist())
+            new_circuit = passmanager.run(circuit)
+            self.assertLessEqual(len(new_circuit), len(circuit))
+            self.assertEqual(Operator(new_circuit), Operator(circuit))
+
+    @data(False, True)
+    def test_control_flow(self, matrix_based):
+        """Test circuits with control flow still cancel the gates outside of it."""
+        circuit = QuantumCircuit(2, 1)
+        circuit.x(0)
+        circuit.x(0)
+        with circuit.if_test((circuit.clbits[0], 1)):
+            circuit.z(1)
+        circuit.h(1)
+        circuit.h(1)
+
+        passmanager = PassManager(CommutativeInverseCancellation(matrix_based=matrix_based))
+        new_circuit = passmanager.run(circuit)
+
+        expected = QuantumCircuit(2, 1)
+        with expected.if_test((expected.clbits[0], 1)):
+            expected.z(1)
+        self.assertEqual(expected, new_circuit)
+
+    def test_rust_circuit_data(self):
+        """Test the Rust pass returns the circuit data without the cancelled gates, and the phase
+        to add to it."""
+        circuit = QuantumCircuit(2)
+        circuit.rz(0.5, 0)
+        circuit.cx(0, 1)
+        circuit.p(-0.5, 0)
+        data, phase = commutative_inverse_cancellation(circuit._data, matrix_based=True)
+        expected = QuantumCircuit(2)
+        expected.cx(0, 1)
+        self.assertEqual(QuantumCircuit._from_circuit_data(data), expected)
+        self.assertAlmostEqual(phase, -0.25)
+        data, phase = commutative_inverse_cancellation(circuit._data)
+        self.assertEqual(QuantumCircuit._from_circuit_data(data), circuit)
+        self.assertEqual(phase, 0)
+
 
 if __name__ == "__main__":
     unittest.main()
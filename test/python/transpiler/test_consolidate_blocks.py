@@ -553,6 +553,75 @@ class TestConsolidateBlocks(QiskitTestCase):
             )
         self.assertEqual(expected, actual)
 
+    def test_collect_and_consolidate_in_one_pass(self):
+        """Test the blocks collected by the pass itself give an equivalent circuit."""
+        qc = QuantumCircuit(3)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.rz(0.3, 1)
+        qc.cx(1, 0)
+        qc.cx(1, 2)
+        qc.s(2)
+        qc.cx(2, 1)
+        qc.cx(1, 2)
+        qc.t(0)
+        pass_manager = PassManager(
+            [Collect2qBlocks(), ConsolidateBlocks(basis_gates=["cx", "u"], force_consolidate=True)]
+        )
+        result = pass_manager.run(qc)
+        self.assertEqual(Operator(result), Operator(qc))
+        # The trailing `t` is a one-qubit run, which is only consolidated after Collect1qRuns.
+        self.assertEqual(result.count_ops(), {"unitary": 2, "t": 1})
+        self.assertNotIn("block_list", pass_manager.property_set)
+
+    def test_consolidate_1q_runs(self):
+        """Test the one-qubit runs outside the two-qubit blocks are consolidated too."""
+        qc = QuantumCircuit(3)
+        qc.h(2)
+        qc.t(2)
+        qc.cx(0, 1)
+        qc.x(2)
+        pass_manager = PassManager(
+            [
+                Collect2qBlocks(),
+                Collect1qRuns(),
+                ConsolidateBlocks(basis_gates=["cx", "u"], force_consolidate=True),
+            ]
+        )
+        result = pass_manager.run(qc)
+        self.assertEqual(Operator(result), Operator(qc))
+        self.assertEqual(result.count_ops(), {"unitary": 2})
+        self.assertNotIn("run_list", pass_manager.property_set)
+
+    def test_measure_splits_blocks(self):
+        """Test a block isn't consolidated across a measurement on one of its qubits."""
+        qc = QuantumCircuit(2, 1)
+        qc.cx(0, 1)
+        qc.h(0)
+        qc.measure(0, 0)
+        qc.h(0)
+        qc.cx(0, 1)
+        pass_manager = PassManager(
+            [Collect2qBlocks(), ConsolidateBlocks(basis_gates=["cx", "u"], force_consolidate=True)]
+        )
+        result = pass_manager.run(qc)
+        names = [instruction.operation.name for instruction in result.data]
+        self.assertEqual(names, ["unitary", "measure", "unitary"])
+        first = QuantumCircuit(2)
+        first.cx(0, 1)
+        first.h(0)
+        self.assertEqual(Operator(result.data[0].operation), Operator(first))
+
+    def test_identity_block_removed(self):
+        """Test a block that multiplies out to the identity is removed."""
+        qc = QuantumCircuit(2)
+        qc.cx(0, 1)
+        qc.h(1)
+        qc.h(1)
+        qc.cx(0, 1)
+        pass_manager = PassManager([Collect2qBlocks(), ConsolidateBlocks(basis_gates=["cx", "u"])])
+        self.assertEqual(len(pass_manager.run(qc).data), 0)
+
 
 if __name__ == "__main__":
     unittest.main()
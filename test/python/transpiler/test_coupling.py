@@ -12,13 +12,15 @@
 
 # pylint: disable=missing-docstring
 
+import pickle
 import unittest
 
 import numpy as np
 import rustworkx as rx
 
 from qiskit.transpiler import CouplingMap
-from qiskit.transpiler.exceptions import CouplingError
+from qiskit.exceptions import QiskitError, QiskitRustError
+from qiskit.transpiler.exceptions import CouplingError, RoutingError, TranspilerError
 from qiskit.utils import optionals
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
@@ -477,6 +479,72 @@ class CouplingTest(QiskitTestCase):
         with self.assertRaises(CouplingError):
             cmap.distance(0, 3)
 
+    def test_rust_distance_no_path_routing_error(self):
+        """Test that the Rust coupling map raises a RoutingError with the disconnected qubits."""
+        cmap = CouplingMap([[0, 1], [1, 0], [2, 3], [3, 2]])._get_rust_coupling_map()
+        with self.assertRaises(RoutingError) as cm:
+            cmap.distance(0, 3)
+        self.assertIsInstance(cm.exception, TranspilerError)
+        self.assertIsInstance(cm.exception, QiskitRustError)
+        self.assertEqual(cm.exception.qubits, (0, 3))
+        with self.assertRaises(RoutingError) as cm:
+            cmap.shortest_undirected_path(1, 2)
+        self.assertEqual(cm.exception.qubits, (1, 2))
+
+    def test_rust_distance_matrix(self):
+        """Test the Rust distance matrix matches the undirected one of rustworkx."""
+        for cmap in [
+            CouplingMap.from_heavy_hex(3),
+            CouplingMap([[0, 1], [2, 1], [3, 4]]),
+            CouplingMap(RUESCHLIKON_CMAP),
+        ]:
+            expected = rx.digraph_distance_matrix(cmap.graph, as_undirected=True, null_value=np.inf)
+            np.testing.assert_array_equal(cmap._get_rust_coupling_map().distance_matrix(), expected)
+            np.testing.assert_array_equal(cmap.distance_matrix, expected)
+
+    def test_rust_coupling_map_invalidated(self):
+        """Test the Rust coupling map is cached, and rebuilt after the graph is modified."""
+        cmap = CouplingMap.from_line(3)
+        rust_cmap = cmap._get_rust_coupling_map()
+        self.assertIs(cmap._get_rust_coupling_map(), rust_cmap)
+        self.assertEqual(cmap.distance(0, 2), 2)
+        cmap.add_edge(2, 3)
+        self.assertIsNot(cmap._get_rust_coupling_map(), rust_cmap)
+        self.assertEqual(cmap._get_rust_coupling_map().size(), 4)
+        self.assertEqual(cmap.distance(0, 3), 3)
+        cmap.make_symmetric()
+        self.assertEqual(len(cmap._get_rust_coupling_map().get_edges()), 6)
+
+    def test_rust_coupling_map_paths_and_components(self):
+        """Test the shortest paths and the components of the Rust coupling map."""
+        rust_cmap = CouplingMap([[0, 1], [2, 1], [2, 3], [4, 5]])._get_rust_coupling_map()
+        self.assertEqual(rust_cmap.neighbors(2), [1, 3])
+        self.assertEqual(rust_cmap.shortest_undirected_path(0, 3), [0, 1, 2, 3])
+        self.assertEqual(rust_cmap.shortest_undirected_path(3, 3), [3])
+        self.assertFalse(rust_cmap.is_connected())
+        self.assertEqual(rust_cmap.connected_components(), [[0, 1, 2, 3], [4, 5]])
+        with self.assertRaises(QiskitError):
+            rust_cmap.distance(0, 6)
+
+    def test_rust_coupling_map_subgraph(self):
+        """Test the subgraph of the Rust coupling map relabels the qubits in the given order."""
+        rust_cmap = CouplingMap.from_line(5, bidirectional=False)._get_rust_coupling_map()
+        subgraph = rust_cmap.subgraph([3, 1, 2])
+        self.assertEqual(subgraph.size(), 3)
+        self.assertEqual(sorted(subgraph.get_edges()), [(1, 2), (2, 0)])
+        with self.assertRaises(ValueError):
+            rust_cmap.subgraph([1, 1])
+        with self.assertRaises(IndexError):
+            rust_cmap.subgraph([5])
+
+    def test_rust_coupling_map_pickle(self):
+        """Test the Rust coupling map round-trips through pickle."""
+        rust_cmap = CouplingMap([[0, 1], [1, 2], [3, 2]])._get_rust_coupling_map()
+        copied = pickle.loads(pickle.dumps(rust_cmap))
+        self.assertEqual(copied.size(), rust_cmap.size())
+        self.assertEqual(copied.get_edges(), rust_cmap.get_edges())
+        np.testing.assert_array_equal(copied.distance_matrix(), rust_cmap.distance_matrix())
+
     def test_component_mapping(self):
         cmap = CouplingMap([[0, 1], [1, 0], [2, 3], [3, 2]])
         components = cmap.connected_components()
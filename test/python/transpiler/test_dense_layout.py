@@ -24,6 +24,7 @@ from qiskit.transpiler import CouplingMap, Target, InstructionProperties, Transp
 from qiskit.transpiler.passes import DenseLayout
 from qiskit.converters import circuit_to_dag
 from qiskit.transpiler.passes.layout.dense_layout import _build_error_matrix
+from qiskit._accelerate.error_map import ErrorMap
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 from ..legacy_cmaps import TOKYO_CMAP
@@ -263,5 +264,32 @@ class TestDenseLayout(QiskitTestCase):
         self.assertTrue(sub_map.is_connected(), msg=f"chosen layout is not dense: {actual}")
 
 
+    def test_uncertainty_weight(self):
+        """Test the variances of the error rates, weighted, steer the layout away from an edge whose
+        error rate is low but uncertain."""
+        target = Target(num_qubits=3)
+        target.add_instruction(
+            CXGate(),
+            {
+                (0, 1): InstructionProperties(error=0.01),
+                (1, 0): InstructionProperties(error=0.01),
+                (1, 2): InstructionProperties(error=0.02),
+                (2, 1): InstructionProperties(error=0.02),
+            },
+        )
+        variances = ErrorMap(2)
+        variances.add_error((0, 1), 0.01, 0.0004)
+        variances.add_error((1, 0), 0.01, 0.0004)
+        circuit = QuantumCircuit(2)
+        circuit.cx(0, 1)
+        dag = circuit_to_dag(circuit)
+        for weight, expected in [(0.0, {0, 1}), (2.0, {1, 2})]:
+            with self.subTest(uncertainty_weight=weight):
+                pass_ = DenseLayout(target=target, variances=variances, uncertainty_weight=weight)
+                pass_.run(dag)
+                layout = pass_.property_set["layout"]
+                self.assertEqual({layout[q] for q in circuit.qubits}, expected)
+
+
 if __name__ == "__main__":
     unittest.main()
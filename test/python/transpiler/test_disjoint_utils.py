@@ -0,0 +1,112 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the splitting of layout problems over disjoint coupling maps."""
+
+import unittest
+
+from qiskit import QuantumCircuit
+from qiskit.converters import circuit_to_dag
+from qiskit.transpiler import CouplingMap
+from qiskit.transpiler.exceptions import TranspilerError
+from qiskit.transpiler.passes.layout.disjoint_utils import split_dag_over_components
+from qiskit._accelerate.disjoint_components import split_disjoint_components
+from qiskit._accelerate.error_map import ErrorMap
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _two_lines(first, second):
+    """A coupling map of two lines of ``first`` and ``second`` qubits."""
+    edges = [(qubit, qubit + 1) for qubit in range(first - 1)]
+    edges += [(qubit, qubit + 1) for qubit in range(first, first + second - 1)]
+    return CouplingMap(edges)
+
+
+class TestSplitDagOverComponents(QiskitTestCase):
+    """Test the splitting of a circuit over the components of a coupling map."""
+
+    def test_largest_groups_first(self):
+        """Test the largest group of interacting qubits is placed first, where it fits."""
+        qc = QuantumCircuit(7)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        qc.cx(2, 3)
+        qc.cx(4, 5)
+        coupling_map = _two_lines(3, 5)
+        out = split_dag_over_components(circuit_to_dag(qc), coupling_map)
+        self.assertEqual(len(out), 2)
+        (small_dag, small_cmap), (large_dag, large_cmap) = out
+        self.assertEqual(small_dag.qubits, qc.qubits[4:6])
+        self.assertEqual(list(small_cmap.graph.nodes()), [0, 1, 2])
+        # The idle qubit isn't laid out.
+        self.assertEqual(large_dag.qubits, qc.qubits[:4])
+        self.assertEqual(list(large_cmap.graph.nodes()), [3, 4, 5, 6, 7])
+        self.assertEqual(large_dag.count_ops(), {"cx": 3})
+
+    def test_lowest_error_component(self):
+        """Test a group goes on the component with the lowest average error."""
+        qc = QuantumCircuit(2)
+        qc.cx(0, 1)
+        coupling_map = _two_lines(3, 3)
+        error_map = ErrorMap(4)
+        error_map.add_error((0, 1), 0.1)
+        error_map.add_error((1, 2), 0.1)
+        error_map.add_error((3, 4), 0.01)
+        error_map.add_error((4, 5), 0.02)
+        out = split_dag_over_components(circuit_to_dag(qc), coupling_map, error_map)
+        self.assertEqual(len(out), 1)
+        self.assertEqual(list(out[0][1].graph.nodes()), [3, 4, 5])
+
+    def test_barriers_kept(self):
+        """Test the barriers of the input circuit are left as they were."""
+        qc = QuantumCircuit(4)
+        qc.cx(0, 1)
+        qc.barrier()
+        qc.cx(2, 3)
+        dag = circuit_to_dag(qc)
+        out = split_dag_over_components(dag, _two_lines(2, 2))
+        self.assertEqual(len(out), 2)
+        for component_dag, _ in out:
+            self.assertEqual(component_dag.count_ops(), {"cx": 1, "barrier": 1})
+        self.assertEqual(dag.count_ops(), {"cx": 2, "barrier": 1})
+
+    def test_group_too_large(self):
+        """Test a group of interacting qubits that fits on no component raises."""
+        qc = QuantumCircuit(4)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        qc.cx(2, 3)
+        with self.assertRaises(TranspilerError):
+            split_dag_over_components(circuit_to_dag(qc), _two_lines(3, 3))
+
+
+class TestSplitDisjointComponents(QiskitTestCase):
+    """Test the Rust splitting of virtual qubits over the components of a coupling map."""
+
+    def test_physical_qubits(self):
+        """Test only the given physical qubits are used."""
+        coupling_map = CouplingMap.from_line(6)._get_rust_coupling_map()
+        # Without qubit 2, the line splits in two.
+        problems = split_disjoint_components(
+            coupling_map, 3, [(0, 1)], physical_qubits=[0, 1, 3, 4, 5]
+        )
+        self.assertEqual(problems, [([2], [0, 1]), ([0, 1], [3, 4, 5])])
+
+    def test_bad_edges(self):
+        """Test interactions of qubits that aren't in the circuit raise."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        with self.assertRaises(IndexError):
+            split_disjoint_components(coupling_map, 2, [(0, 2)])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -15,7 +15,7 @@
 import unittest
 import numpy as np
 from numpy import pi
-from ddt import ddt, data
+from ddt import ddt, data, unpack
 
 from qiskit import pulse
 from qiskit.circuit import Gate, QuantumCircuit, Delay, Measure, Reset, Parameter
@@ -1051,6 +1051,52 @@ class TestPadDynamicalDecoupling(QiskitTestCase):
 
         self.assertEqual(qc.global_phase + np.pi, pm.run(qc).global_phase)
 
+    @data(("middle", [320, 656, 320]), ("edges", [320, 640, 336]))
+    @unpack
+    def test_extra_slack_distribution(self, distribution, delays):
+        """Test the slack lost to the pulse alignment is put back where the distribution says."""
+        durations = InstructionDurations([("h", None, 160), ("x", None, 160)])
+        pm = PassManager(
+            [
+                ALAPScheduleAnalysis(durations),
+                PadDynamicalDecoupling(
+                    durations,
+                    [XGate(), XGate()],
+                    pulse_alignment=16,
+                    extra_slack_distribution=distribution,
+                ),
+            ]
+        )
+
+        t2 = QuantumCircuit(1)
+        t2.h(0)
+        t2.delay(1616, 0)
+        t2.h(0)
+
+        expected = QuantumCircuit(1)
+        expected.h(0)
+        expected.delay(delays[0], 0)
+        expected.x(0)
+        expected.delay(delays[1], 0)
+        expected.x(0)
+        expected.delay(delays[2], 0)
+        expected.h(0)
+
+        self.assertEqual(pm.run(t2), expected)
+
+    def test_invalid_extra_slack_distribution(self):
+        """Test an unknown slack distribution raises."""
+        pm = PassManager(
+            [
+                ALAPScheduleAnalysis(self.durations),
+                PadDynamicalDecoupling(
+                    self.durations, [XGate(), XGate()], extra_slack_distribution="random"
+                ),
+            ]
+        )
+        with self.assertRaises(TranspilerError):
+            pm.run(self.ghz4)
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,154 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the building of average error maps from backend properties."""
+
+import io
+import json
+import pickle
+import statistics
+import unittest
+from collections import defaultdict
+
+import numpy as np
+
+from qiskit.providers.fake_provider import Fake5QV1
+from qiskit.transpiler import CouplingMap
+from qiskit.transpiler.passes.layout import vf2_utils
+from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.nlayout import NLayout
+from qiskit._accelerate.vf2_layout import EdgeList, score_layout
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _mean_errors(properties, num_qubits):
+    """The average error of each qubit and pair of qubits, computed directly in Python."""
+    errors = defaultdict(list)
+    for qubit in range(len(properties.qubits)):
+        errors[(qubit, qubit)].append(properties.readout_error(qubit))
+    for gate in properties.gates:
+        qargs = tuple(gate.qubits) * 2 if len(gate.qubits) == 1 else tuple(gate.qubits)
+        for param in gate.parameters:
+            if param.name == "gate_error":
+                errors[qargs].append(param.value)
+    return {
+        qargs: statistics.mean(values)
+        for qargs, values in errors.items()
+        if max(qargs) < num_qubits
+    }
+
+
+class TestErrorMapFromProperties(QiskitTestCase):
+    """Test the average error maps built from properties payloads and arrays."""
+
+    def assertErrorMap(self, error_map, expected):
+        """Assert an error map has exactly the expected entries."""
+        self.assertEqual(len(error_map), len(expected))
+        for qargs, error in expected.items():
+            self.assertAlmostEqual(error_map[qargs], error)
+
+    def test_from_properties(self):
+        """Test the error map of a properties payload, as a dictionary and as JSON, is the mean
+        of the readout and gate errors."""
+        properties = Fake5QV1().properties()
+        expected = _mean_errors(properties, 5)
+        payload = properties.to_dict()
+        self.assertErrorMap(ErrorMap.from_properties(5, payload), expected)
+        self.assertErrorMap(ErrorMap.from_properties(5, json.dumps(payload, default=str)), expected)
+
+    def test_from_arrays(self):
+        """Test the error map of arrays of errors, with qubits out of range and gates on more than
+        two qubits skipped."""
+        error_map = ErrorMap.from_arrays(
+            2,
+            [0.1, None, 0.3],
+            [[0], [0, 1], [0, 1], [1, 0], [0, 1, 2], [1, 2]],
+            [0.3, 0.01, 0.03, 0.05, 0.5, 0.5],
+        )
+        self.assertErrorMap(error_map, {(0, 0): 0.2, (0, 1): 0.02, (1, 0): 0.05})
+
+    def test_faulty_qubits_skipped(self):
+        """Test the entries of qubits beyond the given number are skipped."""
+        properties = Fake5QV1().properties()
+        self.assertErrorMap(
+            ErrorMap.from_properties(3, properties.to_dict()), _mean_errors(properties, 3)
+        )
+
+    def test_build_average_error_map(self):
+        """Test the VF2 utilities build the error map of properties in Rust."""
+        backend = Fake5QV1()
+        properties = backend.properties()
+        coupling_map = CouplingMap(backend.configuration().coupling_map)
+        error_map = vf2_utils.build_average_error_map(None, properties, coupling_map)
+        self.assertErrorMap(error_map, _mean_errors(properties, 5))
+
+    def test_bad_input(self):
+        """Test mismatched arrays and malformed payloads raise."""
+        with self.assertRaises(ValueError):
+            ErrorMap.from_arrays(2, [], [[0]], [])
+        with self.assertRaises(KeyError):
+            ErrorMap.from_properties(2, {"qubits": []})
+        with self.assertRaises(KeyError):
+            ErrorMap.from_properties(2, {"qubits": [[{"value": 0.1}]], "gates": []})
+
+
+class TestErrorMapVariances(QiskitTestCase):
+    """Test the variances of the error rates of an error map, and their penalty in scoring."""
+
+    def test_add_and_set_variance(self):
+        """Test variances are stored with their entries, and replacing an entry without a variance
+        drops its old one."""
+        error_map = ErrorMap(2)
+        error_map.add_error((0, 0), 0.1, 0.01)
+        error_map.add_error((0, 1), 0.05)
+        self.assertEqual(error_map.variance((0, 0)), 0.01)
+        self.assertIsNone(error_map.variance((0, 1)))
+        error_map.set_variance((0, 1), 0.0025)
+        self.assertEqual(error_map.variances(), {(0, 0): 0.01, (0, 1): 0.0025})
+        error_map.add_error((0, 0), 0.2)
+        self.assertEqual(error_map[(0, 0)], 0.2)
+        self.assertEqual(error_map.variances(), {(0, 1): 0.0025})
+        with self.assertRaises(IndexError):
+            error_map.set_variance((1, 1), 0.1)
+
+    def test_pickle(self):
+        """Test the variances survive pickling."""
+        error_map = ErrorMap.from_dict({(0, 0): 0.1, (0, 1): 0.05})
+        error_map.set_variance((0, 1), 0.0025)
+        with io.BytesIO() as fptr:
+            pickle.dump(error_map, fptr)
+            fptr.seek(0)
+            loaded = pickle.load(fptr)
+        self.assertEqual(loaded[(0, 0)], 0.1)
+        self.assertEqual(loaded.variances(), {(0, 1): 0.0025})
+
+    def test_score_layout(self):
+        """Test each error rate with a variance is raised by the weighted standard deviation when
+        scoring, and capped at one."""
+        error_map = ErrorMap(3)
+        error_map.add_error((0, 0), 0.1, 0.01)
+        error_map.add_error((1, 1), 0.2)
+        error_map.add_error((0, 1), 0.05, 0.0025)
+        edge_list = EdgeList([((0, 1), 2)])
+        bit_list = np.array([1, 1], dtype=np.int32)
+        layout = NLayout.generate_trivial_layout(2)
+
+        def score(weight):
+            return score_layout(bit_list, edge_list, error_map, layout, True, False, weight)
+
+        self.assertAlmostEqual(score(0.0), 1 - 0.95**2 * 0.9 * 0.8)
+        self.assertAlmostEqual(score(2.0), 1 - 0.85**2 * 0.7 * 0.8)
+        self.assertAlmostEqual(score(100.0), 1.0)
+
+
+if __name__ == "__main__":
+    unittest.main()
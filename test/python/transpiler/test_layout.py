@@ -13,11 +13,13 @@
 """Tests the layout object"""
 
 import copy
+import math
 import pickle
 import unittest
 import numpy
 
 from qiskit.circuit import QuantumRegister, Qubit
+from qiskit.transpiler import CouplingMap
 from qiskit.transpiler.layout import Layout
 from qiskit.transpiler.exceptions import LayoutError
 from qiskit._accelerate.nlayout import NLayout
@@ -561,6 +563,37 @@ class TestNLayout(QiskitTestCase):
         self.assertEqual([layout.virtual_to_physical(x) for x in range(size)], v2p)
         self.assertEqual([roundtripped.virtual_to_physical(x) for x in range(size)], expected)
 
+    def test_stats(self):
+        """Test the statistics of how a layout places the interactions of a circuit."""
+        coupling_map = CouplingMap.from_line(4)._get_rust_coupling_map()
+        layout = NLayout.from_virtual_to_physical([3, 0, 1, 2])
+        stats = layout.stats(coupling_map, [(0, 1), (1, 2), (1, 0)])
+        self.assertEqual(stats.num_interactions, 3)
+        self.assertAlmostEqual(stats.mean_distance, 7 / 3)
+        self.assertEqual(stats.max_distance, 3.0)
+        self.assertEqual(stats.non_adjacent, 2)
+        self.assertEqual(stats.num_displaced, 4)
+        self.assertEqual(stats.mean_displacement, 1.5)
+        self.assertIn("non_adjacent=2", repr(stats))
+        stats = NLayout.generate_trivial_layout(4).stats(coupling_map, [])
+        self.assertEqual((stats.num_interactions, stats.mean_distance), (0, 0.0))
+        self.assertEqual((stats.num_displaced, stats.mean_displacement), (0, 0.0))
+
+    def test_stats_disconnected(self):
+        """Test interactions across components of the coupling map are infinitely far apart."""
+        coupling_map = CouplingMap([(0, 1), (2, 3)])._get_rust_coupling_map()
+        stats = NLayout.generate_trivial_layout(4).stats(coupling_map, [(0, 1), (1, 2)])
+        self.assertEqual(stats.max_distance, math.inf)
+        self.assertEqual(stats.non_adjacent, 1)
+
+    def test_stats_out_of_range(self):
+        """Test qubits out of range of the layout or of the coupling map raise."""
+        coupling_map = CouplingMap.from_line(2)._get_rust_coupling_map()
+        with self.assertRaises(IndexError):
+            NLayout.generate_trivial_layout(2).stats(coupling_map, [(0, 2)])
+        with self.assertRaises(IndexError):
+            NLayout.from_virtual_to_physical([2, 0, 1]).stats(coupling_map, [(0, 1)])
+
 
 if __name__ == "__main__":
     unittest.main()
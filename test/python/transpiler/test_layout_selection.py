@@ -0,0 +1,97 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the selection of the best of several candidate layouts."""
+
+import unittest
+
+from qiskit.transpiler import CouplingMap
+from qiskit.transpiler.exceptions import LayoutError
+from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.layout_selection import select_layout
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _error_map(edge_errors):
+    error_map = ErrorMap(2 * len(edge_errors))
+    for (a, b), error in edge_errors.items():
+        error_map.add_error((a, b), error)
+        error_map.add_error((b, a), error)
+    return error_map
+
+
+class TestSelectLayout(QiskitTestCase):
+    """Test the scoring and selection of candidate layouts."""
+
+    def test_avoids_noisy_edge(self):
+        """Test the trivial layout loses to a layout off the noisiest edge."""
+        coupling_map = CouplingMap.from_line(4)._get_rust_coupling_map()
+        error_map = _error_map({(0, 1): 0.5, (1, 2): 0.01, (2, 3): 0.01})
+        name, layout, scores = select_layout(coupling_map, 2, [((0, 1), 10)], error_map)
+        self.assertEqual(list(scores), ["trivial", "dense", "noise_adaptive", "line"])
+        self.assertNotEqual(name, "trivial")
+        self.assertNotIn(0, layout)
+        self.assertAlmostEqual(scores[name][0], 1 - 0.99**10)
+        self.assertAlmostEqual(scores["trivial"][0], 1 - 0.5**10)
+
+    def test_scores(self):
+        """Test the error and duration of a given candidate, with interactions between qubits
+        that aren't neighbors costed as a chain of swaps."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        error_map = _error_map({(0, 1): 0.1, (1, 2): 0.2})
+        durations = {(0, 1): 100.0, (1, 2): 300.0}
+        _, _, scores = select_layout(
+            coupling_map,
+            2,
+            [((0, 1), 2)],
+            error_map,
+            durations=durations,
+            qubit_counts=[0, 1],
+            candidates=[("far", [2, 0])],
+        )
+        error, duration = scores["far"]
+        self.assertAlmostEqual(error, 1 - (0.9 * 0.8) ** 6)
+        self.assertAlmostEqual(duration, 2 * (3 * 100.0 + 3 * 300.0))
+        error, duration = scores["trivial"]
+        self.assertAlmostEqual(error, 1 - 0.9**2)
+        self.assertAlmostEqual(duration, 200.0)
+
+    def test_ties(self):
+        """Test ties in the error are broken by the duration, and then by the order of the
+        candidates."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        durations = {(0, 1): 500.0, (1, 2): 100.0}
+        name, layout, scores = select_layout(
+            coupling_map, 2, [((0, 1), 1)], durations=durations, candidates=[("fast", [2, 1])]
+        )
+        self.assertEqual(scores[name], (0.0, 100.0))
+        self.assertEqual(sorted(layout), [1, 2])
+        name, layout, _ = select_layout(
+            coupling_map, 2, [((0, 1), 1)], candidates=[("same", [0, 1])]
+        )
+        self.assertEqual((name, layout), ("trivial", [0, 1]))
+
+    def test_errors(self):
+        """Test circuits larger than the device and bad candidates raise."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        with self.assertRaises(LayoutError):
+            select_layout(coupling_map, 4, [])
+        with self.assertRaises(ValueError):
+            select_layout(coupling_map, 2, [], candidates=[("bad", [1, 1])])
+        with self.assertRaises(ValueError):
+            select_layout(coupling_map, 2, [], qubit_counts=[1])
+        with self.assertRaises(IndexError):
+            select_layout(coupling_map, 2, [((0, 2), 1)])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,87 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the layout of chain circuits along the most reliable line of a coupling map."""
+
+import unittest
+
+from qiskit.transpiler import CouplingMap
+from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.line_layout import best_line, line_layout
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _error_map(edge_errors, qubit_errors=()):
+    error_map = ErrorMap(2 * len(edge_errors) + len(qubit_errors))
+    for (a, b), error in edge_errors.items():
+        error_map.add_error((a, b), error)
+        error_map.add_error((b, a), error)
+    for qubit, error in enumerate(qubit_errors):
+        error_map.add_error((qubit, qubit), error)
+    return error_map
+
+
+class TestBestLine(QiskitTestCase):
+    """Test the search for the most reliable line of physical qubits."""
+
+    def test_avoids_bad_edges(self):
+        """Test the line goes over the most reliable edges."""
+        coupling_map = CouplingMap.from_line(5)._get_rust_coupling_map()
+        error_map = _error_map({(0, 1): 0.1, (1, 2): 0.1, (2, 3): 0.01, (3, 4): 0.01})
+        self.assertEqual(best_line(coupling_map, 3, error_map), [2, 3, 4])
+
+    def test_avoids_bad_qubits(self):
+        """Test the line goes around unreliable qubits."""
+        coupling_map = CouplingMap.from_ring(4)._get_rust_coupling_map()
+        error_map = _error_map({}, [0.5, 0.01, 0.0, 0.0])
+        self.assertEqual(sorted(best_line(coupling_map, 3, error_map)), [1, 2, 3])
+
+    def test_no_line(self):
+        """Test lines longer than the coupling map, or than any of its paths, aren't found."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        self.assertIsNone(best_line(coupling_map, 4))
+        self.assertEqual(best_line(coupling_map, 0), [])
+        # The search stops before it reaches a path of two qubits.
+        self.assertIsNone(best_line(coupling_map, 2, max_steps=1))
+        pairs = CouplingMap([(0, 1), (2, 3)])._get_rust_coupling_map()
+        self.assertIsNone(best_line(pairs, 3))
+
+
+class TestLineLayout(QiskitTestCase):
+    """Test the layout of circuits whose interactions form chains."""
+
+    def test_chains(self):
+        """Test every interaction is on an edge, and the layout is on the most reliable line."""
+        coupling = CouplingMap.from_line(6)
+        error_map = _error_map({(0, 1): 0.2})
+        edges = [(0, 2), (2, 1)]
+        layout = line_layout(coupling._get_rust_coupling_map(), 4, edges, error_map)
+        self.assertEqual(len(set(layout)), 4)
+        self.assertNotIn(0, layout)
+        for a, b in edges:
+            self.assertEqual(coupling.distance(layout[a], layout[b]), 1)
+
+    def test_not_chains(self):
+        """Test interactions that form a ring or a star have no line layout."""
+        coupling_map = CouplingMap.from_line(5)._get_rust_coupling_map()
+        self.assertIsNone(line_layout(coupling_map, 3, [(0, 1), (1, 2), (2, 0)]))
+        self.assertIsNone(line_layout(coupling_map, 4, [(0, 1), (0, 2), (0, 3)]))
+
+    def test_bad_edges(self):
+        """Test interactions of qubits that aren't in the circuit raise."""
+        coupling_map = CouplingMap.from_line(3)._get_rust_coupling_map()
+        with self.assertRaises(IndexError):
+            line_layout(coupling_map, 2, [(0, 2)])
+
+
+if __name__ == "__main__":
+    unittest.main()
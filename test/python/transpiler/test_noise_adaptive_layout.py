@@ -0,0 +1,116 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the NoiseAdaptiveLayout pass"""
+
+import unittest
+
+from qiskit import QuantumCircuit
+from qiskit.circuit import Measure
+from qiskit.circuit.library import CXGate
+from qiskit.converters import circuit_to_dag
+from qiskit.exceptions import QiskitRustError
+from qiskit.transpiler import CouplingMap, InstructionProperties, Target
+from qiskit.transpiler.exceptions import LayoutError, TranspilerError
+from qiskit.transpiler.passes import NoiseAdaptiveLayout
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+class TestNoiseAdaptiveLayout(QiskitTestCase):
+    """Tests the NoiseAdaptiveLayout pass"""
+
+    def test_too_wide_circuit(self):
+        """Test that a circuit wider than the device raises a LayoutError."""
+        qc = QuantumCircuit(3)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        pass_ = NoiseAdaptiveLayout(CouplingMap.from_line(2))
+        with self.assertRaises(LayoutError) as cm:
+            pass_.run(circuit_to_dag(qc))
+        self.assertIsInstance(cm.exception, TranspilerError)
+        self.assertIsInstance(cm.exception, QiskitRustError)
+        self.assertEqual(cm.exception.pass_name, "NoiseAdaptiveLayout")
+
+    def _line_target(self, edge_errors, measure_errors):
+        target = Target(num_qubits=len(measure_errors))
+        cx_props = {}
+        for (a, b), error in edge_errors.items():
+            cx_props[(a, b)] = InstructionProperties(error=error)
+            cx_props[(b, a)] = InstructionProperties(error=error)
+        target.add_instruction(CXGate(), cx_props)
+        target.add_instruction(
+            Measure(),
+            {
+                (qubit,): InstructionProperties(error=error)
+                for qubit, error in enumerate(measure_errors)
+            },
+        )
+        return target
+
+    def test_avoids_noisy_edges(self):
+        """Test the interactions are placed on the most reliable edges."""
+        target = self._line_target(
+            {(0, 1): 0.2, (1, 2): 0.01, (2, 3): 0.02, (3, 4): 0.3}, [0.01] * 5
+        )
+        qc = QuantumCircuit(3)
+        qc.cx(0, 1)
+        qc.cx(1, 0)
+        qc.cx(2, 1)
+        pass_ = NoiseAdaptiveLayout(target=target)
+        pass_.run(circuit_to_dag(qc))
+        layout = pass_.property_set["layout"]
+        self.assertEqual([layout[qubit] for qubit in qc.qubits], [1, 2, 3])
+        self.assertEqual(layout.get_registers(), set(qc.qregs))
+
+    def test_idle_qubits_on_most_reliable(self):
+        """Test the qubits without interactions are placed on the most reliable qubits."""
+        target = self._line_target(
+            {(0, 1): 0.01, (1, 2): 0.01, (2, 3): 0.01}, [0.3, 0.05, 0.2, 0.1]
+        )
+        qc = QuantumCircuit(2)
+        qc.h(0)
+        qc.x(1)
+        pass_ = NoiseAdaptiveLayout(target=target)
+        pass_.run(circuit_to_dag(qc))
+        layout = pass_.property_set["layout"]
+        self.assertEqual([layout[qubit] for qubit in qc.qubits], [1, 3])
+
+    def test_coupling_map_only(self):
+        """Test a chain is laid out along a line with the errors estimated from the degrees."""
+        qc = QuantumCircuit(3)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        pass_ = NoiseAdaptiveLayout(CouplingMap.from_line(5))
+        pass_.run(circuit_to_dag(qc))
+        layout = pass_.property_set["layout"]
+        self.assertEqual([layout[qubit] for qubit in qc.qubits], [0, 1, 2])
+
+    def test_multi_qubit_gates(self):
+        """Test the interactions of a gate on three qubits are counted between each pair."""
+        target = self._line_target({(0, 1): 0.01, (1, 2): 0.01, (2, 3): 0.01}, [0.01] * 4)
+        qc = QuantumCircuit(4)
+        qc.ccx(0, 1, 2)
+        qc.cx(2, 3)
+        pass_ = NoiseAdaptiveLayout(target=target)
+        pass_.run(circuit_to_dag(qc))
+        layout = pass_.property_set["layout"]
+        physical = [layout[qubit] for qubit in qc.qubits]
+        self.assertEqual(sorted(physical), [0, 1, 2, 3])
+
+    def test_no_coupling_map(self):
+        """Test the pass raises without a coupling map or a target."""
+        with self.assertRaises(TranspilerError):
+            NoiseAdaptiveLayout().run(circuit_to_dag(QuantumCircuit(1)))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -23,6 +23,7 @@ from qiskit.circuit import Parameter
 from qiskit.circuit.library import U1Gate, U2Gate, U3Gate, UGate, PhaseGate
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.target import Target
+from qiskit._accelerate.optimize_1q_gates import compose_u3_rust
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 from qiskit.circuit.library.standard_gates.equivalence_library import (
@@ -349,6 +350,13 @@ class TestOptimize1qGates(QiskitTestCase):
         after = Optimize1qGates(["u1", "u2", "u", "cx"]).run(dag)
         self.assertAlmostEqual(after.global_phase, 5 * np.pi / 6, 8)
 
+    def test_compose_u3_clifford_angles(self):
+        """Check composed angles within rounding of a multiple of pi/2 are exactly that multiple."""
+        for theta in [np.pi / 3, np.pi / 5, 0.1]:
+            with self.subTest(theta=theta):
+                composed = compose_u3_rust(theta, 0, 0, np.pi / 2 - theta, 0, 0)
+                self.assertEqual(tuple(composed), (np.pi / 2, 0, 0))
+
 
 class TestOptimize1qGatesParamReduction(QiskitTestCase):
     """Test for 1q gate optimizations parameter reduction, reduce n in Un"""
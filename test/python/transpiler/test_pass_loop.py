@@ -0,0 +1,206 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Test the Rust controller that runs passes until the circuit stops changing."""
+
+import unittest
+
+from qiskit import QuantumCircuit, QuantumRegister
+from qiskit.circuit.library import CXGate
+from qiskit.converters import circuit_to_dag
+from qiskit.dagcircuit import DAGCircuit
+from qiskit.quantum_info import Operator
+from qiskit.synthesis import TwoQubitBasisDecomposer
+from qiskit.transpiler import PassManager, TransformationPass
+from qiskit.transpiler.passes import Collect2qBlocks, ConsolidateBlocks, UnitarySynthesis
+from qiskit._accelerate.pass_loop import CircuitHandle, RustPass, run_passes, run_to_fixed_point
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _passes():
+    decomposer = TwoQubitBasisDecomposer(CXGate(), euler_basis="ZSX")._inner_decomposer
+    basis_gates = {"cx", "rz", "sx", "x"}
+    return [
+        RustPass.consolidate_blocks(decomposer, "cx", basis_gates=basis_gates),
+        RustPass.unitary_synthesis(["ZSXX"], decomposer, CXGate(), False, True),
+    ]
+
+
+def _circuit():
+    # Every one-qubit gate is part of a two-qubit block, so consolidation takes all of them.
+    circuit = QuantumCircuit(3, global_phase=0.2)
+    circuit.h(0)
+    circuit.cx(0, 1)
+    circuit.s(1)
+    circuit.cx(0, 1)
+    circuit.cx(1, 2)
+    circuit.t(2)
+    circuit.cx(1, 2)
+    circuit.cx(2, 1)
+    return circuit
+
+
+def _run(circuit, passes, **kwargs):
+    data, phase, iterations = run_to_fixed_point(circuit._data, passes, **kwargs)
+    out = QuantumCircuit._from_circuit_data(data)
+    out.global_phase += phase
+    return out, iterations
+
+
+class TestRunToFixedPoint(QiskitTestCase):
+    """Test running Rust passes to a fixed point."""
+
+    def test_consolidate_and_synthesize(self):
+        """Test consolidation and synthesis run until the circuit is in the basis and unchanged."""
+        circuit = _circuit()
+        out, iterations = _run(circuit, _passes())
+        # The first iteration changes the circuit, so at least one more is needed to see that
+        # it has stopped changing.
+        self.assertGreaterEqual(iterations, 2)
+        self.assertLess(iterations, 1000)
+        self.assertLessEqual(set(out.count_ops()), {"cx", "rz", "sx", "x"})
+        self.assertEqual(Operator(out), Operator(circuit))
+
+    def test_unchanged_circuit(self):
+        """Test a circuit the passes don't change stops after the first iteration."""
+        circuit = QuantumCircuit(2)
+        circuit.cx(0, 1)
+        out, iterations = _run(circuit, _passes())
+        self.assertEqual(iterations, 1)
+        self.assertEqual(out, circuit)
+
+    def test_no_passes(self):
+        """Test an empty sequence of passes stops after the first iteration."""
+        circuit = _circuit()
+        out, iterations = _run(circuit, [])
+        self.assertEqual(iterations, 1)
+        self.assertEqual(out, circuit)
+
+    def test_max_iterations(self):
+        """Test the passes run at most the given number of times."""
+        circuit = _circuit()
+        out, iterations = _run(circuit, _passes(), max_iterations=0)
+        self.assertEqual(iterations, 0)
+        self.assertEqual(out, circuit)
+        out, iterations = _run(circuit, _passes(), max_iterations=1)
+        self.assertEqual(iterations, 1)
+        self.assertEqual(Operator(out), Operator(circuit))
+
+    def test_input_not_modified(self):
+        """Test the circuit that's passed in is left as it is."""
+        circuit = _circuit()
+        expected = circuit.copy()
+        _run(circuit, _passes())
+        self.assertEqual(circuit, expected)
+
+
+class _RecordIR(TransformationPass):
+    """Record the type of the circuit each run is given, running on a handle if ``on_handle``."""
+
+    def __init__(self, on_handle):
+        super().__init__()
+        self.on_handle = on_handle
+        self.seen = []
+
+    def run(self, dag):
+        self.seen.append(type(dag))
+        return dag
+
+    def _can_run_on_circuit_handle(self, dag):
+        return self.on_handle
+
+    def _run_on_circuit_handle(self, handle):
+        self.seen.append(type(handle))
+        return handle
+
+
+class TestCircuitHandle(QiskitTestCase):
+    """Test the Rust-space handles of circuits that are passed between Rust passes."""
+
+    def test_round_trip(self):
+        """Test a circuit converted to a handle and back is unchanged."""
+        circuit = _circuit()
+        circuit.name = "handled"
+        circuit.metadata = {"key": "value"}
+        handle = CircuitHandle.from_dag(circuit_to_dag(circuit))
+        self.assertEqual(len(handle), len(circuit.data))
+        self.assertEqual(handle.global_phase, 0.0)
+        out = handle.to_circuit()
+        self.assertEqual(out, circuit)
+        self.assertEqual(out.name, "handled")
+        self.assertEqual(out.metadata, {"key": "value"})
+        self.assertEqual(handle.to_dag(), circuit_to_dag(circuit))
+
+    def test_registers_kept(self):
+        """Test the registers of the circuit are restored from a handle."""
+        circuit = QuantumCircuit(QuantumRegister(2, "a"), QuantumRegister(1, "b"))
+        circuit.cx(0, 2)
+        out = CircuitHandle.from_dag(circuit_to_dag(circuit)).to_circuit()
+        self.assertEqual(out.qregs, circuit.qregs)
+        self.assertEqual(out, circuit)
+
+    def test_run_passes(self):
+        """Test running passes on a handle gives a new handle with the phase they added."""
+        circuit = _circuit()
+        handle = CircuitHandle.from_dag(circuit_to_dag(circuit))
+        self.assertEqual(run_passes(handle, []).to_circuit(), circuit)
+        out = run_passes(handle, _passes())
+        self.assertEqual(Operator(out.to_circuit()), Operator(circuit))
+        self.assertLessEqual(set(out.to_circuit().count_ops()), {"cx", "rz", "sx", "x"})
+        # The handle that was passed in is unchanged.
+        self.assertEqual(handle.to_circuit(), circuit)
+
+    def test_pass_manager_hands_on_handles(self):
+        """Test a pass manager hands a handle from a Rust pass to the next pass that can take one,
+        and a DAG to the next pass that can't."""
+        basis_gates = ["cx", "rz", "sx", "x"]
+        on_handle = _RecordIR(on_handle=True)
+        on_dag = _RecordIR(on_handle=False)
+        dags = []
+        pm = PassManager(
+            [
+                Collect2qBlocks(),
+                ConsolidateBlocks(basis_gates=basis_gates),
+                on_handle,
+                UnitarySynthesis(basis_gates=basis_gates),
+                on_dag,
+            ]
+        )
+        circuit = _circuit()
+        out = pm.run(circuit, callback=lambda **kwargs: dags.append(kwargs["dag"]))
+        self.assertEqual(on_handle.seen, [CircuitHandle])
+        self.assertEqual(on_dag.seen, [DAGCircuit])
+        # Callbacks still see DAGs.
+        self.assertTrue(all(isinstance(dag, DAGCircuit) for dag in dags))
+        self.assertEqual(Operator(out), Operator(circuit))
+        self.assertLessEqual(set(out.count_ops()), set(basis_gates))
+
+    def test_same_as_separate_passes(self):
+        """Test handing handles between the passes gives what running them one by one does."""
+        basis_gates = ["cx", "rz", "sx", "x"]
+        circuit = _circuit()
+        together = PassManager(
+            [
+                Collect2qBlocks(),
+                ConsolidateBlocks(basis_gates=basis_gates),
+                UnitarySynthesis(basis_gates=basis_gates),
+            ]
+        ).run(circuit)
+        consolidated = PassManager(
+            [Collect2qBlocks(), ConsolidateBlocks(basis_gates=basis_gates)]
+        ).run(circuit)
+        separate = UnitarySynthesis(basis_gates=basis_gates)(consolidated)
+        self.assertEqual(together, separate)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -12,6 +12,7 @@
 
 """Test the SabreLayout pass"""
 
+import itertools
 import unittest
 
 import math
@@ -20,8 +21,9 @@ from qiskit import QuantumRegister, QuantumCircuit
 from qiskit.circuit.classical import expr, types
 from qiskit.circuit.library import EfficientSU2
 from qiskit.transpiler import CouplingMap, AnalysisPass, PassManager
-from qiskit.transpiler.passes import SabreLayout, DenseLayout, StochasticSwap
-from qiskit.transpiler.exceptions import TranspilerError
+from qiskit.transpiler.passes import SabreLayout, DenseLayout, StochasticSwap, CheckMap
+from qiskit.exceptions import QiskitRustError
+from qiskit.transpiler.exceptions import LayoutError, TranspilerError
 from qiskit.converters import circuit_to_dag
 from qiskit.compiler.transpiler import transpile
 from qiskit.providers.fake_provider import Fake27QPulseV1, GenericBackendV2
@@ -298,6 +300,71 @@ barrier q18585[5],q18585[2],q18585[8],q18585[3],q18585[6];
         layout = pass_.property_set["layout"]
         self.assertEqual([layout[q] for q in qc.qubits], [2, 3, 4, 1, 5])
 
+    def test_objective_weights(self):
+        """Test that the layout trials scored on the depth give a routed circuit."""
+        circuit = EfficientSU2(6, entanglement="full", reps=2).decompose()
+        coupling = CouplingMap(self.cmap20)
+        pass_ = SabreLayout(
+            coupling, seed=0, swap_trials=4, layout_trials=4, objective_weights={"depth": 1.0}
+        )
+        routed = pass_(circuit)
+        check_map = CheckMap(coupling)
+        check_map.run(circuit_to_dag(routed))
+        self.assertTrue(check_map.property_set["is_swap_mapped"])
+
+    def test_rejects_unknown_objective(self):
+        """Test that an unknown routing objective is rejected."""
+        with self.assertRaisesRegex(TranspilerError, "Unknown routing objectives"):
+            SabreLayout(CouplingMap(self.cmap20), objective_weights={"gates": 1.0})
+
+    def test_pareto_front(self):
+        """Test that the Pareto set of the layout trials is stored, and that with positive weights
+        the layout chosen from it is the one chosen from all the trials."""
+        circuit = EfficientSU2(6, entanglement="full", reps=2).decompose()
+        coupling = CouplingMap(self.cmap20)
+        weights = {"swaps": 1.0, "depth": 0.5, "error": 0.25}
+        weighted_pass = SabreLayout(
+            coupling, seed=5, swap_trials=4, layout_trials=8, objective_weights=weights
+        )
+        expected = weighted_pass(circuit)
+        self.assertNotIn("sabre_layout_pareto_front", weighted_pass.property_set)
+        pareto_pass = SabreLayout(
+            coupling,
+            seed=5,
+            swap_trials=4,
+            layout_trials=8,
+            objective_weights=weights,
+            pareto_front=True,
+        )
+        self.assertEqual(pareto_pass(circuit), expected)
+        self.assertEqual(pareto_pass.property_set["layout"], weighted_pass.property_set["layout"])
+        (front,) = pareto_pass.property_set["sabre_layout_pareto_front"]
+        self.assertGreater(len(front), 0)
+        for objectives in front:
+            self.assertEqual(set(objectives), {"swaps", "depth", "error"})
+        for left, right in itertools.permutations(front, 2):
+            self.assertFalse(
+                all(left[key] <= right[key] for key in left)
+                and any(left[key] < right[key] for key in left),
+                f"{left} dominates {right}",
+            )
+
+
+    def test_seed_recorded(self):
+        """Test the seed that was used is stored, and reproduces the layout, including a seed of
+        more than 64 bits and one drawn because none was given."""
+        circuit = EfficientSU2(6, entanglement="full", reps=2).decompose()
+        coupling = CouplingMap(self.cmap20)
+        for seed in [2**100 + 1, None]:
+            first = SabreLayout(coupling, seed=seed, swap_trials=4, layout_trials=4)
+            first(circuit)
+            used = first.property_set["sabre_layout_seed"]
+            if seed is not None:
+                self.assertEqual(used, seed)
+            second = SabreLayout(coupling, seed=used, swap_trials=4, layout_trials=4)
+            second(circuit)
+            self.assertEqual(second.property_set["layout"], first.property_set["layout"])
+
 
 class DensePartialSabreTrial(AnalysisPass):
     """Pass to run dense layout as a sabre trial."""
@@ -412,6 +479,23 @@ class TestDisjointDeviceSabreLayout(QiskitTestCase):
         with self.assertRaises(TranspilerError):
             layout_routing_pass(qc)
 
+    def test_too_large_components_layout_error(self):
+        """Test that the error for a too large component is a LayoutError with its qubits."""
+        qc = QuantumCircuit(8)
+        qc.h(0)
+        for i in range(1, 6):
+            qc.cx(0, i)
+        qc.h(7)
+        qc.cx(7, 6)
+        layout_routing_pass = SabreLayout(
+            self.dual_grid_cmap, seed=123456, swap_trials=1, layout_trials=1
+        )
+        with self.assertRaises(LayoutError) as cm:
+            layout_routing_pass(qc)
+        self.assertIsInstance(cm.exception, TranspilerError)
+        self.assertIsInstance(cm.exception, QiskitRustError)
+        self.assertEqual(sorted(cm.exception.qubits), [0, 1, 2, 3, 4, 5])
+
     def test_with_partial_layout(self):
         """Test a partial layout with a disjoint connectivity graph."""
         qc = QuantumCircuit(8, name="double dhz")
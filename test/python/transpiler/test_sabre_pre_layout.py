@@ -16,9 +16,31 @@ from qiskit.circuit import QuantumCircuit
 from qiskit.transpiler import TranspilerError, CouplingMap, PassManager
 from qiskit.transpiler.passes.layout.sabre_pre_layout import SabrePreLayout
 from qiskit.converters import circuit_to_dag
+from qiskit._accelerate.heavy_hex_layout import heavy_hex_layout
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
+def _two_heavy_hex_cells():
+    """Two hexagons sharing an edge, with an extra qubit on each of their eleven edges."""
+    hexagons = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 0),
+        (1, 6),
+        (6, 7),
+        (7, 8),
+        (8, 9),
+        (9, 0),
+    ]
+    edges = []
+    for index, (a, b) in enumerate(hexagons):
+        edges.extend([(a, 10 + index), (10 + index, b)])
+    return CouplingMap(edges)
+
+
 class TestSabrePreLayout(QiskitTestCase):
     """Tests the SabrePreLayout pass."""
 
@@ -96,3 +118,73 @@ class TestSabrePreLayout(QiskitTestCase):
         )
         pm.run(qc)
         self.assertNotIn("sabre_starting_layouts", pm.property_set)
+
+    def _assert_perfect_layout(self, circuit, coupling_map, layout):
+        edges = set(coupling_map.get_edges())
+        physical = [layout[qubit] for qubit in circuit.qubits]
+        self.assertEqual(len(set(physical)), len(physical))
+        for instruction in circuit.data:
+            a, b = (layout[qubit] for qubit in instruction.qubits)
+            self.assertTrue((a, b) in edges or (b, a) in edges)
+
+    def test_heavy_hex_chain(self):
+        """Test a perfect layout is found for a chain on a heavy-hex coupling map."""
+        qc = QuantumCircuit(15)
+        for qubit in range(14):
+            qc.cx(qubit, qubit + 1)
+        coupling_map = _two_heavy_hex_cells()
+        pm = PassManager([SabrePreLayout(coupling_map=coupling_map)])
+        pm.run(qc)
+        layouts = pm.property_set["sabre_starting_layouts"]
+        self.assertEqual(len(layouts), 1)
+        self._assert_perfect_layout(qc, coupling_map, layouts[0])
+
+    def test_heavy_hex_ring_and_chain(self):
+        """Test a perfect layout is found for a ring and a chain on a heavy-hex coupling map."""
+        qc = QuantumCircuit(16)
+        for qubit in range(12):
+            qc.cx(qubit, (qubit + 1) % 12)
+        qc.cx(12, 13)
+        qc.cx(14, 13)
+        coupling_map = _two_heavy_hex_cells()
+        pm = PassManager([SabrePreLayout(coupling_map=coupling_map)])
+        pm.run(qc)
+        layouts = pm.property_set["sabre_starting_layouts"]
+        self.assertEqual(len(layouts), 1)
+        self._assert_perfect_layout(qc, coupling_map, layouts[0])
+
+    def test_heavy_hex_search_skipped(self):
+        """Test the heavy-hex search can be turned off, and doesn't apply to other circuits."""
+        qc = QuantumCircuit(4)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        qc.cx(2, 3)
+        coupling_map = _two_heavy_hex_cells()
+        pm = PassManager([SabrePreLayout(coupling_map=coupling_map, max_steps_heavy_hex=0)])
+        pm.run(qc)
+        # VF2 finds a perfect layout, so there's no starting layout.
+        self.assertNotIn("sabre_starting_layouts", pm.property_set)
+
+        qc.ccx(0, 1, 2)
+        pm = PassManager([SabrePreLayout(coupling_map=coupling_map)])
+        pm.run(qc)
+        self.assertNotIn("sabre_starting_layouts", pm.property_set)
+
+    def test_heavy_hex_search_needs_heavy_hex(self):
+        """Test the heavy-hex search doesn't apply to other coupling maps."""
+        qc = QuantumCircuit(4)
+        qc.cx(0, 1)
+        qc.cx(1, 2)
+        qc.cx(2, 3)
+        self.assertIsNone(
+            heavy_hex_layout(CouplingMap.from_line(5)._get_rust_coupling_map(), 4, [(0, 1)])
+        )
+        pm = PassManager([SabrePreLayout(coupling_map=CouplingMap.from_grid(3, 3))])
+        pm.run(qc)
+        self.assertNotIn("sabre_starting_layouts", pm.property_set)
+
+    def test_heavy_hex_layout_bad_edges(self):
+        """Test the heavy-hex search rejects interactions of qubits that aren't in the circuit."""
+        coupling_map = _two_heavy_hex_cells()._get_rust_coupling_map()
+        with self.assertRaises(IndexError):
+            heavy_hex_layout(coupling_map, 2, [(0, 2)])
@@ -28,6 +28,7 @@ from qiskit.providers.fake_provider import Fake27QPulseV1, GenericBackendV2
 from qiskit.transpiler.passes import SabreSwap, TrivialLayout, CheckMap
 from qiskit.transpiler import CouplingMap, Layout, PassManager, Target, TranspilerError
 from qiskit import ClassicalRegister, QuantumRegister, QuantumCircuit
+from qiskit.quantum_info import Operator
 from qiskit.utils import optionals
 from test.utils._canonical import canonicalize_control_flow  # pylint: disable=wrong-import-order
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
@@ -401,6 +402,85 @@ class TestSabreSwap(QiskitTestCase):
         with self.assertRaisesRegex(TranspilerError, "Fewer qubits in the circuit"):
             pass_(qc)
 
+    def test_rejects_unknown_objective(self):
+        """Test that an unknown routing objective is rejected."""
+        with self.assertRaisesRegex(TranspilerError, "Unknown routing objectives"):
+            SabreSwap(CouplingMap.from_line(4), objective_weights={"swaps": 1.0, "fidelity": 1.0})
+
+    @ddt.data({"swaps": 1.0}, {"depth": 1.0}, {"error": 1.0}, {"swaps": 1.0, "depth": 0.5})
+    def test_objective_weights_route_circuit(self, weights):
+        """Test that the trials scored on any of the objectives give a routed circuit."""
+        coupling = CouplingMap.from_grid(3, 3)
+        qc = _random_cx_circuit(9, 30, seed=2024)
+        pass_ = SabreSwap(coupling, "decay", seed=42, trials=8, objective_weights=weights)
+        routed = pass_(qc)
+        check_map = CheckMap(coupling)
+        check_map.run(circuit_to_dag(routed))
+        self.assertTrue(check_map.property_set["is_swap_mapped"])
+        self.assertEqual(routed.count_ops()["cx"], 30)
+
+    def test_pareto_front(self):
+        """Test that the Pareto set of the trials is stored, and that the routing applied is the
+        one among them with the fewest swaps."""
+        coupling = CouplingMap.from_grid(3, 3)
+        qc = _random_cx_circuit(9, 30, seed=2024)
+        pass_ = SabreSwap(coupling, "decay", seed=42, trials=16, pareto_front=True)
+        routed = pass_(qc)
+        front = pass_.property_set["sabre_swap_pareto_front"]
+        self.assertGreater(len(front), 0)
+        for objectives in front:
+            self.assertEqual(set(objectives), {"swaps", "depth", "error"})
+        for left, right in itertools.permutations(front, 2):
+            self.assertFalse(_dominates(left, right), f"{left} dominates {right}")
+        self.assertEqual(
+            routed.count_ops().get("swap", 0), min(objectives["swaps"] for objectives in front)
+        )
+
+    def test_pareto_front_matches_weighted_choice(self):
+        """Test that, with positive weights, the trial chosen from the Pareto set is the trial
+        chosen from all of them."""
+        coupling = CouplingMap.from_grid(3, 3)
+        qc = _random_cx_circuit(9, 30, seed=7)
+        weights = {"swaps": 1.0, "depth": 0.5, "error": 0.25}
+        weighted_pass = SabreSwap(coupling, "decay", seed=3, trials=16, objective_weights=weights)
+        expected = weighted_pass(qc)
+        self.assertNotIn("sabre_swap_pareto_front", weighted_pass.property_set)
+        pareto_pass = SabreSwap(
+            coupling, "decay", seed=3, trials=16, objective_weights=weights, pareto_front=True
+        )
+        self.assertEqual(pareto_pass(qc), expected)
+
+
+    def test_seed_recorded(self):
+        """Test the seed that was used is stored, and reproduces the routing, including a seed of
+        more than 64 bits and one drawn because none was given."""
+        coupling = CouplingMap.from_grid(3, 3)
+        qc = _random_cx_circuit(9, 30, seed=11)
+        for seed in [2**127 + 5, None]:
+            first = SabreSwap(coupling, "decay", seed=seed, trials=4)
+            expected = first(qc)
+            used = first.property_set["sabre_swap_seed"]
+            if seed is not None:
+                self.assertEqual(used, seed)
+            self.assertEqual(SabreSwap(coupling, "decay", seed=used, trials=4)(qc), expected)
+
+
+def _random_cx_circuit(num_qubits, num_gates, seed):
+    """A circuit of CX gates between random pairs of qubits."""
+    rng = numpy.random.default_rng(seed)
+    qc = QuantumCircuit(num_qubits)
+    for _ in range(num_gates):
+        qc.cx(*rng.choice(num_qubits, 2, replace=False).tolist())
+    return qc
+
+
+def _dominates(left, right):
+    """Whether the routing objectives ``left`` are at least as good as ``right`` on each objective,
+    and better on one."""
+    return all(left[key] <= right[key] for key in left) and any(
+        left[key] < right[key] for key in left
+    )
+
 
 @ddt.ddt
 class TestSabreSwapControlFlow(QiskitTestCase):
@@ -1320,6 +1400,113 @@ class TestSabreSwapControlFlow(QiskitTestCase):
         self.assertEqual(initial_layout, running_layout)
 
 
+class TestSabreSwapBridges(QiskitTestCase):
+    """Test the routing of CX gates with bridges through a shared neighbor."""
+
+    def _circuit(self):
+        # Bridging the first gate leaves qubits 0 and 1 where the later gates want them, where any
+        # swap that brings qubits 0 and 2 together moves one of them away.
+        qc = QuantumCircuit(4)
+        qc.cx(0, 2)
+        qc.cx(1, 2)
+        qc.cx(0, 1)
+        qc.cx(2, 3)
+        return qc
+
+    def test_bridge(self):
+        """Test a CX gate is bridged when that's cheaper than a swap, leaving the layout as is."""
+        qc = self._circuit()
+        coupling = CouplingMap.from_line(4)
+        routing = SabreSwap(coupling, heuristic="basic", seed=0, trials=2, bridges=True)
+        out = routing(qc)
+        self.assertEqual(out.count_ops(), {"cx": 7})
+        self.assertEqual(Operator(out), Operator(qc))
+        check_map = CheckMap(coupling)
+        check_map.run(circuit_to_dag(out))
+        self.assertTrue(check_map.property_set["is_swap_mapped"])
+
+    def test_no_bridges_by_default(self):
+        """Test gates are only brought together with swaps without ``bridges``."""
+        qc = self._circuit()
+        out = SabreSwap(CouplingMap.from_line(4), heuristic="basic", seed=0, trials=2)(qc)
+        self.assertIn("swap", out.count_ops())
+        self.assertEqual(out.count_ops()["cx"], 4)
+
+    def test_conditioned_gate_not_bridged(self):
+        """Test a conditioned CX gate isn't replaced by a bridge."""
+        qc = QuantumCircuit(3, 1)
+        qc.cx(0, 2).c_if(0, 1)
+        routing = SabreSwap(
+            CouplingMap.from_line(3), heuristic="basic", seed=0, trials=2, bridges=True
+        )
+        out = routing(qc)
+        self.assertEqual(out.count_ops(), {"swap": 1, "cx": 1})
+
+
+class TestSabreSwapCommutingSets(QiskitTestCase):
+    """Test the routing of runs of commuting gates in any order."""
+
+    def _virtual_gates(self, circuit):
+        """The gates of a routed circuit, with the swaps undone, each by its name, its parameters
+        and its sorted virtual qubits."""
+        layout = list(range(circuit.num_qubits))
+        gates = []
+        for instruction in circuit.data:
+            physical = [circuit.find_bit(qubit).index for qubit in instruction.qubits]
+            if instruction.operation.name == "swap":
+                a, b = physical
+                layout[a], layout[b] = layout[b], layout[a]
+                continue
+            virtual = sorted(layout[qubit] for qubit in physical)
+            gates.append((instruction.operation.name, instruction.operation.params, virtual))
+        return sorted(gates)
+
+    def test_commuting_gates_reordered(self):
+        """Test a later gate that commutes with an earlier one is routed first when it's already
+        executable, so one swap is enough whatever the seed."""
+        qc = QuantumCircuit(3)
+        qc.rzz(0.1, 0, 2)
+        qc.rzz(0.2, 0, 1)
+        coupling = CouplingMap.from_line(3)
+        for seed in range(5):
+            with self.subTest(seed=seed):
+                routing = SabreSwap(
+                    coupling, heuristic="basic", seed=seed, trials=1, commuting_sets=True
+                )
+                out = routing(qc)
+                self.assertEqual(out.count_ops()["swap"], 1)
+                first = out.data[0]
+                self.assertEqual(first.operation.name, "rzz")
+                self.assertEqual(first.operation.params, [0.2])
+                self.assertEqual(self._virtual_gates(out), self._virtual_gates(qc))
+
+    def test_qaoa_layer(self):
+        """Test a layer of commuting two-qubit gates is routed to a valid circuit with the same
+        gates."""
+        qc = QuantumCircuit(5)
+        for a, b in itertools.combinations(range(5), 2):
+            qc.rzz(0.1 * (a + b), a, b)
+        coupling = CouplingMap.from_line(5)
+        out = SabreSwap(coupling, seed=7, trials=2, commuting_sets=True)(qc)
+        check_map = CheckMap(coupling)
+        check_map.run(circuit_to_dag(out))
+        self.assertTrue(check_map.property_set["is_swap_mapped"])
+        self.assertEqual(self._virtual_gates(out), self._virtual_gates(qc))
+
+    def test_non_commuting_order_kept(self):
+        """Test gates that don't commute keep their order."""
+        qc = QuantumCircuit(2)
+        qc.h(0)
+        qc.cx(0, 1)
+        qc.rx(0.3, 1)
+        qc.cx(1, 0)
+        qc.rz(0.4, 0)
+        qc.cx(0, 1)
+        out = SabreSwap(CouplingMap.from_line(2), seed=0, trials=1, commuting_sets=True)(qc)
+        self.assertNotIn("swap", out.count_ops())
+        self.assertEqual(Operator(out), Operator(qc))
+
+
 @ddt.ddt
 class TestSabreSwapRandomCircuitValidOutput(QiskitTestCase):
     """Assert the output of a transpilation with stochastic swap is a physical circuit."""
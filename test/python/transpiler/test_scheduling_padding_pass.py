@@ -17,7 +17,7 @@ import unittest
 from ddt import ddt, data, unpack
 from qiskit import QuantumCircuit
 from qiskit.circuit import Measure
-from qiskit.circuit.library import CXGate, HGate
+from qiskit.circuit.library import CXGate, HGate, SXGate, XGate, YGate
 from qiskit.pulse import Schedule, Play, Constant, DriveChannel
 from qiskit.transpiler.instruction_durations import InstructionDurations
 from qiskit.transpiler.passes import (
@@ -29,6 +29,7 @@ from qiskit.transpiler.passes import (
 from qiskit.transpiler.passmanager import PassManager
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler.target import Target, InstructionProperties
+from qiskit._accelerate.scheduling import asap_schedule, critical_path, pad_delay
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -868,6 +869,150 @@ class TestSchedulingAndPaddingPass(QiskitTestCase):
 
         self.assertEqual(qc, scheduled)
 
+    @data(
+        (ASAPScheduleAnalysis, {"sx": 0, "cx": 160, "x": 960, "y": 0}),
+        (ALAPScheduleAnalysis, {"sx": 0, "cx": 160, "x": 960, "y": 960}),
+    )
+    @unpack
+    def test_start_times(self, schedule_pass, expected):
+        """Test the start times of the instructions of a small circuit."""
+        qc = QuantumCircuit(3)
+        qc.sx(0)
+        qc.cx(0, 1)
+        qc.x(1)
+        qc.y(2)
+        durations = InstructionDurations(
+            [("sx", None, 160), ("cx", [0, 1], 800), ("x", None, 160), ("y", None, 160)]
+        )
+        pm = PassManager([schedule_pass(durations)])
+        pm.run(qc)
+        start_times = {
+            node.op.name: time for node, time in pm.property_set["node_start_time"].items()
+        }
+        self.assertEqual(start_times, expected)
+
+    def test_measurements_on_one_clbit_with_write_latency(self):
+        """Test a measurement can start before the clbit it writes is free, by the write latency."""
+        qc = QuantumCircuit(2, 1)
+        qc.measure(0, 0)
+        qc.measure(1, 0)
+        durations = InstructionDurations([("measure", None, 1000)])
+        pm = PassManager([SetIOLatency(clbit_write_latency=300), ASAPScheduleAnalysis(durations)])
+        pm.run(qc)
+        start_times = sorted(pm.property_set["node_start_time"].values())
+        self.assertEqual(start_times, [0, 700])
+
+    @data(
+        (ASAPScheduleAnalysis, {"sx": 0, "cx": 160, "x": 960, "y": 0}),
+        (ALAPScheduleAnalysis, {"sx": 0, "cx": 160, "x": 960, "y": 960}),
+    )
+    @unpack
+    def test_start_times_from_target(self, schedule_pass, expected):
+        """Test the durations in seconds of a target are converted to dt by the scheduler."""
+        qc = QuantumCircuit(3)
+        qc.sx(0)
+        qc.cx(0, 1)
+        qc.x(1)
+        qc.y(2)
+        dt = 1e-9
+        target = Target(num_qubits=3, dt=dt)
+        for gate in (SXGate(), XGate(), YGate()):
+            target.add_instruction(
+                gate, {(qubit,): InstructionProperties(duration=160 * dt) for qubit in range(3)}
+            )
+        target.add_instruction(CXGate(), {(0, 1): InstructionProperties(duration=800 * dt)})
+        pm = PassManager([schedule_pass(target=target)])
+        pm.run(qc)
+        start_times = {
+            node.op.name: time for node, time in pm.property_set["node_start_time"].items()
+        }
+        self.assertEqual(start_times, expected)
+
+
+class TestCriticalPath(QiskitTestCase):
+    """Test the Rust analysis of the critical path of a scheduled circuit."""
+
+    def test_critical_path(self):
+        """Test the path, slack and idle time of a small circuit."""
+        qc = QuantumCircuit(3)
+        qc.append(SXGate(duration=160), [0])
+        qc.append(CXGate(duration=800), [0, 1])
+        qc.append(XGate(duration=160), [1])
+        qc.append(YGate(duration=160), [2])
+        duration, path, slack, idle_time = critical_path(qc._data)
+        self.assertEqual(duration, 1120)
+        self.assertEqual(path, [0, 1, 2])
+        self.assertEqual(slack, [0, 0, 0, 960])
+        self.assertEqual(idle_time, [160, 160, 960])
+
+    def test_delays_are_idle(self):
+        """Test a delay is on the path but counts as idle time."""
+        qc = QuantumCircuit(2)
+        qc.delay(500, 0)
+        qc.append(XGate(duration=160), [0])
+        qc.append(XGate(duration=100), [1])
+        duration, path, slack, idle_time = critical_path(qc._data)
+        self.assertEqual(duration, 660)
+        self.assertEqual(path, [0, 1])
+        self.assertEqual(slack, [0, 0, 560])
+        self.assertEqual(idle_time, [500, 560])
+
+    def test_target_durations(self):
+        """Test the durations are taken from the target where it has them."""
+        target = Target(num_qubits=1, dt=1e-9)
+        target.add_instruction(XGate(), {(0,): InstructionProperties(duration=2e-7)})
+        qc = QuantumCircuit(1)
+        qc.append(XGate(duration=160), [0])
+        self.assertEqual(critical_path(qc._data)[0], 160)
+        self.assertEqual(critical_path(qc._data, target._build_rust_target())[0], 200)
+
+    def test_missing_duration(self):
+        """Test an instruction without a duration raises."""
+        qc = QuantumCircuit(1)
+        qc.x(0)
+        with self.assertRaises(TranspilerError):
+            critical_path(qc._data)
+
+
+class TestRecordedSchedule(QiskitTestCase):
+    """Test the schedules recorded on the circuit data by the Rust scheduling routines."""
+
+    def _circuit(self):
+        """A small circuit whose instructions all have durations in ``dt``."""
+        qc = QuantumCircuit(2)
+        qc.append(SXGate(duration=160), [0])
+        qc.append(CXGate(duration=800), [0, 1])
+        qc.append(XGate(duration=160), [1])
+        return qc
+
+    def test_schedule_is_recorded(self):
+        """Test the start times and the durations of a schedule are recorded on the circuit."""
+        data = self._circuit()._data
+        start_times = asap_schedule(data)
+        self.assertEqual(start_times, [0, 160, 960])
+        self.assertEqual(data.start_times, start_times)
+        self.assertEqual(data.durations, [(160.0, "dt"), (800.0, "dt"), (160.0, "dt")])
+
+    def test_pad_delay_from_recorded_schedule(self):
+        """Test padding uses the recorded start times, and records those of the padded circuit."""
+        data = self._circuit()._data
+        start_times = asap_schedule(data)
+        expected = pad_delay(data, start_times, "dt")
+        padded, padded_start_times, duration = pad_delay(data, None, "dt")
+        self.assertEqual(duration, 1120)
+        self.assertEqual(list(padded), list(expected[0]))
+        self.assertEqual(padded_start_times, expected[1])
+        self.assertEqual(padded.start_times, padded_start_times)
+        self.assertEqual(
+            [duration for duration, _ in padded.durations],
+            [instruction.operation.duration for instruction in padded],
+        )
+
+    def test_pad_delay_unscheduled(self):
+        """Test padding without start times raises if the circuit isn't scheduled."""
+        with self.assertRaises(TranspilerError):
+            pad_delay(self._circuit()._data, None, "dt")
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -555,6 +555,20 @@ class TestStochasticSwap(QiskitTestCase):
         self.assertEqual(expected_dag, after)
 
 
+    def test_seed_recorded(self):
+        """Test the seed that was used is stored, and reproduces the routing, including a seed of
+        more than 64 bits and one drawn because none was given."""
+        coupling = CouplingMap.from_line(5)
+        circuit = random_circuit(5, 6, max_operands=2, seed=4)
+        for seed in [2**90 + 9, None]:
+            first = StochasticSwap(coupling, trials=4, seed=seed)
+            expected = first(circuit)
+            used = first.property_set["stochastic_swap_seed"]
+            if seed is not None:
+                self.assertEqual(used, seed)
+            self.assertEqual(StochasticSwap(coupling, trials=4, seed=used)(circuit), expected)
+
+
 @ddt
 class TestStochasticSwapControlFlow(QiskitTestCase):
     """Tests for control flow in stochastic swap."""
@@ -1,6 +1,6 @@
 # This code is part of Qiskit.
 #
-# (C) Copyright IBM 2022.
+# (C) Copyright IBM 2022, 2024.
 #
 # This code is licensed under the Apache License, Version 2.0. You may
 # obtain a copy of this license in the LICENSE.txt file in the root directory
@@ -16,9 +16,11 @@ from typing import List
 from ddt import data, ddt, unpack
 import numpy as np
 
-from qiskit import QiskitError
-from qiskit.transpiler import CouplingMap
+from qiskit import QiskitError, QuantumCircuit
+from qiskit.circuit.library import RZZGate
+from qiskit.transpiler import CouplingMap, TranspilerError
 from qiskit.transpiler.passes.routing.commuting_2q_gate_routing import SwapStrategy
+from qiskit._accelerate import swap_strategy as swap_strategy_rs
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -259,3 +261,77 @@ class TestLineSwapStrategy(QiskitTestCase):
             "[[0, 1], [1, 0], [1, 2], [2, 1]] coupling map."
         )
         self.assertEqual(repr(SwapStrategy.from_line([0, 1, 2])), expected)
+
+
+class TestRustSwapStrategy(QiskitTestCase):
+    """Test the Rust swap strategies that ``Commuting2qGateRouter`` routes with."""
+
+    @staticmethod
+    def _to_rust(strategy):
+        return swap_strategy_rs.SwapStrategy(
+            strategy._num_vertices,
+            list(strategy._coupling_map.get_edges()),
+            [list(layer) for layer in strategy._swap_layers],
+        )
+
+    def test_distance_matrix(self):
+        """Test the distances are those of the Python strategy."""
+        ring = SwapStrategy(
+            CouplingMap.from_ring(6), [[(0, 1), (2, 3), (4, 5)], [(1, 2), (3, 4), (5, 0)]]
+        )
+        for strategy in [SwapStrategy.from_line(list(range(5))), ring]:
+            rust = self._to_rust(strategy)
+            self.assertEqual(len(rust), len(strategy._swap_layers))
+            self.assertEqual(rust.num_vertices, strategy._num_vertices)
+            np.testing.assert_array_equal(rust.distance_matrix, strategy.distance_matrix)
+
+    def test_from_line(self):
+        """Test a line strategy is the same as the Python one."""
+        rust = swap_strategy_rs.SwapStrategy.from_line([0, 2, 1, 3])
+        strategy = SwapStrategy.from_line([0, 2, 1, 3])
+        np.testing.assert_array_equal(rust.distance_matrix, strategy.distance_matrix)
+        self.assertEqual(len(swap_strategy_rs.SwapStrategy.from_line([0, 1, 2], 5)), 5)
+        with self.assertRaises(ValueError):
+            swap_strategy_rs.SwapStrategy.from_line([1])
+
+    def test_invalid_strategy(self):
+        """Test swap layers off the coupling map, or that swap a qubit twice, raise."""
+        edges = [(0, 1), (1, 0), (1, 2), (2, 1)]
+        with self.assertRaises(QiskitError):
+            swap_strategy_rs.SwapStrategy(3, edges, [[(0, 2)]])
+        with self.assertRaises(QiskitError):
+            swap_strategy_rs.SwapStrategy(3, edges, [[(0, 1), (1, 2)]])
+        with self.assertRaises(IndexError):
+            swap_strategy_rs.SwapStrategy(2, edges, [])
+
+    def test_swap_decompose(self):
+        """Test the gates of a block are applied as soon as their qubits are adjacent."""
+        strategy = swap_strategy_rs.SwapStrategy.from_line([0, 1, 2])
+        gates = [(RZZGate(0.1), (0, 2)), (RZZGate(0.2), (0, 1))]
+        data, layout = swap_strategy_rs.swap_decompose(strategy, 3, gates, [0, 1, 2])
+        expected = QuantumCircuit(3)
+        expected.rzz(0.2, 0, 1)
+        expected.swap(0, 1)
+        expected.rzz(0.1, 1, 2)
+        self.assertEqual(QuantumCircuit._from_circuit_data(data), expected)
+        self.assertEqual(layout, [1, 0, 2])
+
+    def test_swap_decompose_edge_coloring(self):
+        """Test the gates at each distance are applied in the order of the colors of their
+        edges."""
+        strategy = swap_strategy_rs.SwapStrategy.from_line([0, 1, 2])
+        gates = [(RZZGate(0.1), (0, 1)), (RZZGate(0.2), (1, 2))]
+        coloring = {(0, 1): 1, (1, 2): 0}
+        data, _ = swap_strategy_rs.swap_decompose(strategy, 3, gates, [0, 1, 2], coloring)
+        expected = QuantumCircuit(3)
+        expected.rzz(0.2, 1, 2)
+        expected.rzz(0.1, 0, 1)
+        self.assertEqual(QuantumCircuit._from_circuit_data(data), expected)
+
+    def test_swap_decompose_errors(self):
+        """Test gates the strategy can't make adjacent, and layouts of the wrong size, raise."""
+        strategy = swap_strategy_rs.SwapStrategy.from_line([0, 1, 2], 0)
+        with self.assertRaises(TranspilerError):
+            swap_strategy_rs.swap_decompose(strategy, 3, [(RZZGate(0.1), (0, 2))], [0, 1, 2])
+        with self.assertRaises(ValueError):
+            swap_strategy_rs.swap_decompose(strategy, 3, [(RZZGate(0.1), (0, 1))], [0, 1])
@@ -43,6 +43,7 @@ from qiskit.transpiler.timing_constraints import TimingConstraints
 from qiskit.transpiler.exceptions import TranspilerError
 from qiskit.transpiler import Target
 from qiskit.transpiler import InstructionProperties
+from qiskit.providers.backend import QubitProperties
 from qiskit.providers.fake_provider import (
     GenericBackendV2,
     Fake5QV1,
@@ -1880,6 +1881,130 @@ class TestGlobalVariableWidthOperations(QiskitTestCase):
         self.assertEqual(max(coupling_map.physical_qubits), 4)
         self.assertEqual(coupling_map.get_edges(), [(0, 1), (1, 2), (2, 3), (3, 4)])
 
+    def _assert_rust_target_matches(self, target):
+        rust_target = target._build_rust_target()
+        self.assertEqual(rust_target.operation_names(), list(target.operation_names))
+        self.assertEqual(len(rust_target), len(target))
+        self.assertEqual(rust_target.num_qubits, target.num_qubits)
+        for qargs in [None, (0,), (4,), (5,), (0, 1), (1, 0), (0, 4), (0, 1, 2)]:
+            try:
+                expected = target.operation_names_for_qargs(qargs)
+            except KeyError:
+                with self.assertRaises(KeyError):
+                    rust_target.operation_names_for_qargs(qargs)
+            else:
+                self.assertEqual(set(rust_target.operation_names_for_qargs(qargs)), expected)
+            for name in target.operation_names:
+                self.assertEqual(
+                    rust_target.instruction_supported(name, qargs),
+                    target.instruction_supported(name, qargs),
+                    msg=f"{name} on {qargs}",
+                )
+        for name in target.operation_names:
+            expected = target.qargs_for_operation_name(name)
+            qargs = rust_target.qargs_for_operation_name(name)
+            self.assertEqual(None if qargs is None else set(qargs), expected)
+            for qargs, props in target[name].items():
+                expected = None if props is None else (props.duration, props.error)
+                self.assertEqual(rust_target.instruction_properties(name, qargs), expected)
+        self.assertFalse(rust_target.instruction_supported("not_an_op", (0,)))
+        with self.assertRaises(KeyError):
+            rust_target.qargs_for_operation_name("not_an_op")
+
+    def test_rust_target_global_gates_only(self):
+        """Test the Rust copy of a target of global operations answers as the target does."""
+        self._assert_rust_target_matches(self.target_global_gates_only)
+
+    def test_rust_target_ibm(self):
+        """Test the Rust copy of a target with variadic operations answers as the target does."""
+        self._assert_rust_target_matches(self.ibm_target)
+
+    def test_rust_target_aqt(self):
+        """Test the Rust copy of a target with no properties answers as the target does."""
+        self._assert_rust_target_matches(self.aqt_target)
+
+    def test_rust_target_global_properties(self):
+        """Test the Rust copy of a target has its global properties."""
+        target = Target(
+            description="Rust target",
+            dt=2.2e-10,
+            granularity=16,
+            min_length=64,
+            pulse_alignment=8,
+            acquire_alignment=4,
+            qubit_properties=[QubitProperties(t1=1e-4, t2=5e-5), None],
+        )
+        target.add_instruction(CXGate(), {(0, 1): InstructionProperties(duration=3e-7)})
+        rust_target = target._build_rust_target()
+        self.assertEqual(rust_target.num_qubits, 2)
+        self.assertEqual(rust_target.dt, 2.2e-10)
+        self.assertEqual(rust_target.instruction_properties("cx", (0, 1)), (3e-7, None))
+        self.assertIsNone(rust_target.instruction_properties("cx", (1, 0)))
+
+    def test_rust_target_timing_constraints(self):
+        """Test the Rust copy of a target has its timing constraints."""
+        target = Target(granularity=16, min_length=64, pulse_alignment=8, acquire_alignment=4)
+        rust_target = target._build_rust_target()
+        self.assertEqual(rust_target.granularity, 16)
+        self.assertEqual(rust_target.min_length, 64)
+        self.assertEqual(rust_target.pulse_alignment, 8)
+        self.assertEqual(rust_target.acquire_alignment, 4)
+
+    def test_rust_target_durations(self):
+        """Test the Rust copy of a target converts durations to dt as InstructionDurations does."""
+        dt = 2**-30
+        target = Target(num_qubits=2, dt=dt)
+        target.add_instruction(
+            XGate(),
+            {
+                (0,): InstructionProperties(duration=2.5 * dt),
+                (1,): InstructionProperties(duration=3.5 * dt),
+            },
+        )
+        target.add_instruction(CXGate(), {(0, 1): InstructionProperties(duration=1e-7)})
+        target.add_instruction(Measure(), {(0,): None})
+        rust_target = target._build_rust_target()
+        self.assertEqual(rust_target.duration("cx", (0, 1)), 1e-7)
+        self.assertEqual(rust_target.duration_dt("cx", (0, 1)), 107)
+        # Halves are rounded to even, as Python's ``round`` does.
+        self.assertEqual(rust_target.duration_dt("x", (0,)), 2)
+        self.assertEqual(rust_target.duration_dt("x", (1,)), 4)
+        self.assertIsNone(rust_target.duration("measure", (0,)))
+        self.assertIsNone(rust_target.duration_dt("measure", (0,)))
+        self.assertIsNone(rust_target.duration_dt("cx", (1, 0)))
+
+    def test_rust_target_durations_without_dt(self):
+        """Test a duration can't be converted to dt by the Rust copy of a target without a dt."""
+        target = Target(num_qubits=2)
+        target.add_instruction(CXGate(), {(0, 1): InstructionProperties(duration=1e-7)})
+        rust_target = target._build_rust_target()
+        self.assertEqual(rust_target.duration("cx", (0, 1)), 1e-7)
+        with self.assertRaises(TranspilerError):
+            rust_target.duration_dt("cx", (0, 1))
+
+    def test_rust_target_update_instruction_properties(self):
+        """Test the properties of an instruction can be updated in the Rust copy of a target."""
+        rust_target = self.ibm_target._build_rust_target()
+        rust_target.update_instruction_properties("cx", (0, 1), (1e-7, 0.5))
+        self.assertEqual(rust_target.instruction_properties("cx", (0, 1)), (1e-7, 0.5))
+        rust_target.update_instruction_properties("cx", (0, 1), None)
+        self.assertIsNone(rust_target.instruction_properties("cx", (0, 1)))
+        self.assertTrue(rust_target.instruction_supported("cx", (0, 1)))
+        with self.assertRaises(KeyError):
+            rust_target.update_instruction_properties("cx", (0, 4), None)
+        with self.assertRaises(KeyError):
+            rust_target.update_instruction_properties("ecr", (0, 1), None)
+
+    def test_rust_target_add_instruction_errors(self):
+        """Test the Rust target rejects instructions the target would."""
+        rust_target = self.ibm_target._build_rust_target()
+        with self.assertRaises(ValueError):
+            rust_target.add_instruction("cx", 2)
+        with self.assertRaises(TranspilerError):
+            rust_target.add_instruction("ccx", 3, {(0, 1): None})
+        with self.assertRaises(TranspilerError):
+            rust_target.add_instruction("box", None, {(0, 1): None})
+
 
 class TestInstructionProperties(QiskitTestCase):
     def test_empty_repr(self):
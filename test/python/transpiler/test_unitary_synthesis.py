@@ -1013,6 +1013,84 @@ class TestUnitarySynthesis(QiskitTestCase):
         self.assertTrue(set(opcount).issubset({"rz", "rx", "rxx"}))
         self.assertTrue(np.allclose(Operator(qc_transpiled), Operator(qc)))
 
+    def test_three_qubit_unitary(self):
+        """Test a three-qubit unitary is synthesized with at most 20 two-qubit gates."""
+        unitary = random_unitary(8, seed=1234)
+        qc = QuantumCircuit(3)
+        qc.unitary(unitary, [0, 1, 2])
+        result_qc = dag_to_circuit(
+            UnitarySynthesis(basis_gates=["u", "cx"]).run(circuit_to_dag(qc))
+        )
+        self.assertEqual(Operator(result_qc), Operator(unitary))
+        self.assertLessEqual(
+            sum(1 for instruction in result_qc.data if instruction.operation.num_qubits == 2), 20
+        )
+
+    @data([0, 1, 2], [0, 2, 1], [2, 1, 0])
+    def test_three_qubit_tensor_product(self, qubits):
+        """Test a three-qubit unitary that is a tensor product needs at most three two-qubit
+        gates, whichever qubit is the single-qubit factor."""
+        block = QuantumCircuit(3)
+        block.unitary(random_unitary(4, seed=7), qubits[:2])
+        block.unitary(random_unitary(2, seed=8), qubits[2:])
+        qc = QuantumCircuit(3)
+        qc.unitary(Operator(block), [0, 1, 2])
+        result_qc = dag_to_circuit(
+            UnitarySynthesis(basis_gates=["u", "cx"]).run(circuit_to_dag(qc))
+        )
+        self.assertEqual(Operator(result_qc), Operator(block))
+        self.assertLessEqual(
+            sum(1 for instruction in result_qc.data if instruction.operation.num_qubits == 2), 3
+        )
+
+    def test_unitaries_of_every_size(self):
+        """Test one-, two- and three-qubit unitaries in one circuit are all synthesized exactly,
+        including the global phase."""
+        qc = QuantumCircuit(3, global_phase=0.4)
+        qc.unitary(random_unitary(2, seed=1), [2])
+        qc.unitary(random_unitary(4, seed=2), [2, 0])
+        qc.unitary(random_unitary(8, seed=3), [1, 2, 0])
+        qc.unitary(random_unitary(2, seed=4), [1])
+        result_qc = dag_to_circuit(
+            UnitarySynthesis(basis_gates=["u", "cx"]).run(circuit_to_dag(qc))
+        )
+        self.assertLessEqual(set(result_qc.count_ops()), {"u", "cx"})
+        self.assertEqual(Operator(result_qc), Operator(qc))
+
+    def test_min_qubits_leaves_small_unitaries(self):
+        """Test unitaries on fewer than ``min_qubits`` qubits are left as they are."""
+        qc = QuantumCircuit(3)
+        qc.unitary(random_unitary(4, seed=5), [0, 1])
+        qc.unitary(random_unitary(8, seed=6), [0, 1, 2])
+        result_qc = dag_to_circuit(
+            UnitarySynthesis(basis_gates=["u", "cx"], min_qubits=3).run(circuit_to_dag(qc))
+        )
+        self.assertEqual(result_qc.count_ops()["unitary"], 1)
+        self.assertEqual(result_qc.data[0].operation.name, "unitary")
+        self.assertEqual(Operator(result_qc), Operator(qc))
+
+    def test_non_unitary_instructions_kept(self):
+        """Test the instructions around the unitaries are kept in place."""
+        qc = QuantumCircuit(2, 2)
+        qc.h(0)
+        qc.unitary(random_unitary(4, seed=9), [0, 1])
+        qc.barrier()
+        qc.measure([0, 1], [0, 1])
+        result_qc = dag_to_circuit(
+            UnitarySynthesis(basis_gates=["u", "cx"]).run(circuit_to_dag(qc))
+        )
+        names = [instruction.operation.name for instruction in result_qc.data]
+        self.assertEqual(names[0], "h")
+        self.assertEqual(names[-3:], ["barrier", "measure", "measure"])
+        self.assertNotIn("unitary", names)
+
+    def test_one_qubit_unitary_without_euler_basis(self):
+        """Test a one-qubit unitary is left as it is when no Euler basis is in the basis gates."""
+        qc = QuantumCircuit(1)
+        qc.unitary(random_unitary(2, seed=10), [0])
+        result_qc = dag_to_circuit(UnitarySynthesis(basis_gates=["cx"]).run(circuit_to_dag(qc)))
+        self.assertEqual(result_qc.count_ops(), {"unitary": 1})
+
 
 if __name__ == "__main__":
     unittest.main()
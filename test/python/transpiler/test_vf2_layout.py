@@ -26,6 +26,7 @@ from qiskit.circuit import ControlFlowOp
 from qiskit.transpiler import CouplingMap, Target, TranspilerError
 from qiskit.transpiler.passes.layout.vf2_layout import VF2Layout, VF2LayoutStopReason
 from qiskit._accelerate.error_map import ErrorMap
+from qiskit._accelerate.vf2_layout import LayoutSymmetry
 from qiskit.converters import circuit_to_dag
 from qiskit.providers.fake_provider import Fake5QV1, Fake127QPulseV1, GenericBackendV2
 from qiskit.circuit import Measure
@@ -749,5 +750,78 @@ class TestMultipleTrials(QiskitTestCase):
         self.assertEqual(res.num_qubits, 16)
 
 
+class TestVF2LayoutSymmetry(QiskitTestCase):
+    """Test the skipping of layouts equivalent under symmetries of the device."""
+
+    def _trials(self, automorphisms):
+        """The number of trials VF2Layout runs, and the log lines of skipped layouts, for a single
+        CX gate on a line."""
+        qc = QuantumCircuit(2)
+        qc.cx(0, 1)
+        vf2_pass = VF2Layout(
+            CouplingMap.from_line(5), seed=-1, max_trials=100, automorphisms=automorphisms
+        )
+        property_set = {}
+        with self.assertLogs("qiskit.transpiler.passes.layout.vf2_layout", level="DEBUG") as cm:
+            vf2_pass(qc, property_set)
+        self.assertEqual(property_set["VF2Layout_stop_reason"], VF2LayoutStopReason.SOLUTION_FOUND)
+        trials = [line for line in cm.output if "Running trial" in line]
+        skipped = [line for line in cm.output if "Skipped scoring" in line]
+        return len(trials), skipped
+
+    def test_reflection_skipped(self):
+        """Test only one layout of each pair of reflected layouts on a line is scored."""
+        all_trials, skipped = self._trials(False)
+        self.assertEqual(skipped, [])
+        trials, skipped = self._trials(True)
+        self.assertEqual(2 * trials, all_trials)
+        self.assertEqual(
+            skipped,
+            [
+                "DEBUG:qiskit.transpiler.passes.layout.vf2_layout:"
+                f"Skipped scoring {trials} layouts equivalent to scored ones"
+            ],
+        )
+
+    def test_generators(self):
+        """Test symmetries given as generators are used instead of those of the error map."""
+        all_trials, _ = self._trials(False)
+        trials, _ = self._trials([[4, 3, 2, 1, 0]])
+        self.assertEqual(2 * trials, all_trials)
+        trials, skipped = self._trials([[0, 1, 2, 3, 4]])
+        self.assertEqual(trials, all_trials)
+        self.assertEqual(skipped, [])
+
+    def test_layout_symmetry(self):
+        """Test the canonical layouts of the symmetries of an error map."""
+        error_map = ErrorMap(4)
+        for edge in [(0, 1), (1, 0), (1, 2), (2, 1)]:
+            error_map.add_error(edge, 0.01)
+        symmetry = LayoutSymmetry.from_error_map(3, error_map)
+        self.assertEqual(len(symmetry), 2)
+        self.assertEqual(symmetry.canonical([2, 1]), [0, 1])
+        self.assertEqual(symmetry.canonical([0, 1]), [0, 1])
+        self.assertEqual(symmetry.canonical([1, 0]), [1, 0])
+        error_map.add_error((0, 0), 0.1)
+        self.assertEqual(len(LayoutSymmetry.from_error_map(3, error_map)), 1)
+        self.assertEqual(len(LayoutSymmetry.from_generators(4, [[1, 2, 3, 0]])), 4)
+        self.assertEqual(len(LayoutSymmetry.from_generators(4, [[1, 2, 3, 0]], max_size=3)), 3)
+
+    def test_bad_symmetries(self):
+        """Test generators that aren't permutations, and qubits out of range, raise."""
+        with self.assertRaises(ValueError):
+            LayoutSymmetry.from_generators(3, [[0, 0, 1]])
+        with self.assertRaises(ValueError):
+            LayoutSymmetry.from_generators(3, [[0, 1]])
+        with self.assertRaises(ValueError):
+            LayoutSymmetry.from_generators(3, [[0, 1, 3]])
+        error_map = ErrorMap(1)
+        error_map.add_error((0, 3), 0.01)
+        with self.assertRaises(IndexError):
+            LayoutSymmetry.from_error_map(3, error_map)
+        with self.assertRaises(IndexError):
+            LayoutSymmetry.from_generators(3, []).canonical([0, 3])
+
+
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,91 @@
+# This code is part of Qiskit.
+#
+# (C) Copyright IBM 2024.
+#
+# This code is licensed under the Apache License, Version 2.0. You may
+# obtain a copy of this license in the LICENSE.txt file in the root directory
+# of this source tree or at http://www.apache.org/licenses/LICENSE-2.0.
+#
+# Any modifications or derivative works of this code must retain this
+# copyright notice, and modified files need to carry a notice indicating
+# that they have been altered from the originals.
+
+"""Tests for the instrumentation of the Rust routines."""
+
+from qiskit.circuit import QuantumCircuit
+from qiskit.transpiler import CouplingMap, PassManager
+from qiskit.transpiler.passes import DenseLayout, SabreLayout
+from qiskit.utils import (
+    enable_rust_instrumentation,
+    rust_instrumentation,
+    rust_instrumentation_stats,
+)
+from test import QiskitTestCase  # pylint: disable=wrong-import-order
+
+
+def _run_dense_layout():
+    circuit = QuantumCircuit(3)
+    circuit.h(0)
+    circuit.cx(0, 1)
+    circuit.cx(1, 2)
+    PassManager(DenseLayout(CouplingMap.from_line(5))).run(circuit)
+
+
+class TestRustInstrumentation(QiskitTestCase):
+    """Tests for the instrumentation of the Rust routines."""
+
+    def tearDown(self):
+        enable_rust_instrumentation(False)
+        rust_instrumentation_stats(reset=True)
+        super().tearDown()
+
+    def test_enable_and_read_stats(self):
+        """Test that an enabled kernel records its calls."""
+        rust_instrumentation_stats(reset=True)
+        enable_rust_instrumentation()
+        _run_dense_layout()
+        stats = rust_instrumentation_stats()
+        self.assertIn("dense_layout", stats)
+        self.assertEqual(stats["dense_layout"]["calls"], 1)
+        self.assertGreaterEqual(stats["dense_layout"]["wall_time"], 0.0)
+        self.assertEqual(
+            set(stats["dense_layout"]),
+            {"calls", "wall_time", "trials", "allocations", "allocated_bytes"},
+        )
+
+    def test_disabled_records_nothing(self):
+        """Test that nothing is recorded while the instrumentation is off."""
+        enable_rust_instrumentation(False)
+        rust_instrumentation_stats(reset=True)
+        _run_dense_layout()
+        self.assertEqual(rust_instrumentation_stats(), {})
+
+    def test_reset(self):
+        """Test that reading the stats with a reset clears them."""
+        enable_rust_instrumentation()
+        _run_dense_layout()
+        self.assertIn("dense_layout", rust_instrumentation_stats(reset=True))
+        self.assertEqual(rust_instrumentation_stats(), {})
+
+    def test_context_manager(self):
+        """Test that the context manager fills in the stats of the calls within it."""
+        enable_rust_instrumentation(False)
+        with rust_instrumentation() as stats:
+            _run_dense_layout()
+            _run_dense_layout()
+        self.assertEqual(stats["dense_layout"]["calls"], 2)
+        # The instrumentation is restored to off afterwards.
+        _run_dense_layout()
+        self.assertEqual(rust_instrumentation_stats(), {})
+
+    def test_sabre_layout_trials(self):
+        """Test that the Sabre layout counts its dense-layout trial with the random ones."""
+        circuit = QuantumCircuit(3)
+        circuit.h(0)
+        circuit.cx(0, 1)
+        circuit.cx(1, 2)
+        circuit.cx(0, 2)
+        layout_pass = SabreLayout(CouplingMap.from_line(5), seed=42, swap_trials=2, layout_trials=3)
+        with rust_instrumentation() as stats:
+            PassManager(layout_pass).run(circuit)
+        self.assertEqual(stats["sabre_layout"]["trials"], 4)
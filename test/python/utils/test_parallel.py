@@ -16,9 +16,16 @@ import time
 
 from unittest.mock import patch
 
-from qiskit.utils.parallel import get_platform_parallel_default, parallel_map
+from qiskit.utils.parallel import (
+    configure_rust_threads,
+    get_platform_parallel_default,
+    parallel_map,
+    rust_thread_config,
+)
 from qiskit import QuantumRegister, ClassicalRegister, QuantumCircuit
+from qiskit.circuit.random import random_circuit
 from qiskit.pulse import Schedule
+from qiskit.quantum_info import Statevector
 from test import QiskitTestCase  # pylint: disable=wrong-import-order
 
 
@@ -85,3 +92,59 @@ class TestParallel(QiskitTestCase):
         out_schedules = parallel_map(_build_simple_schedule, list(range(10)))
         names = [schedule.name for schedule in out_schedules]
         self.assertEqual(len(names), len(set(names)))
+
+
+class TestRustThreads(QiskitTestCase):
+    """Test the configuration of the threads of the Rust routines."""
+
+    def setUp(self):
+        super().setUp()
+        config = rust_thread_config()
+        self.addCleanup(
+            configure_rust_threads,
+            max_threads=config["max_threads"],
+            serial_in_parallel=config["serial_in_parallel"],
+            parallel_thresholds={name: None for name in config["parallel_thresholds"]},
+        )
+
+    def test_max_threads(self):
+        """Test the number of threads of the shared pool can be set."""
+        configure_rust_threads(max_threads=2)
+        config = rust_thread_config()
+        self.assertEqual(config["max_threads"], 2)
+        self.assertEqual(config["num_threads"], 2)
+        configure_rust_threads(max_threads=1)
+        self.assertEqual(rust_thread_config()["num_threads"], 1)
+
+    def test_serial_in_parallel(self):
+        """Test the routines can be allowed to go parallel within a process pool."""
+        configure_rust_threads(serial_in_parallel=False)
+        self.assertFalse(rust_thread_config()["serial_in_parallel"])
+        configure_rust_threads(serial_in_parallel=True)
+        self.assertTrue(rust_thread_config()["serial_in_parallel"])
+
+    def test_parallel_thresholds(self):
+        """Test a parallel threshold can be set and restored to its default."""
+        default = rust_thread_config()["parallel_thresholds"]["statevector"]
+        configure_rust_threads(parallel_thresholds={"statevector": 0})
+        self.assertEqual(rust_thread_config()["parallel_thresholds"]["statevector"], 0)
+        configure_rust_threads(parallel_thresholds={"statevector": None})
+        self.assertEqual(rust_thread_config()["parallel_thresholds"]["statevector"], default)
+
+    def test_unknown_threshold(self):
+        """Test an unknown routine raises and leaves every threshold as it was."""
+        before = rust_thread_config()["parallel_thresholds"]
+        with self.assertRaises(KeyError):
+            configure_rust_threads(parallel_thresholds={"statevector": 0, "not_a_kernel": 1})
+        self.assertEqual(rust_thread_config()["parallel_thresholds"], before)
+
+    def test_same_result_serial_and_parallel(self):
+        """Test a routine gives the same result whether it runs serially or on many threads."""
+        circuit = random_circuit(4, 10, seed=2024)
+        configure_rust_threads(max_threads=1)
+        serial = Statevector(circuit)
+        configure_rust_threads(
+            max_threads=4, serial_in_parallel=False, parallel_thresholds={"statevector": 0}
+        )
+        parallel = Statevector(circuit)
+        self.assertEqual(serial, parallel)